@@ -1,6 +1,16 @@
 use uuid::Uuid;
 use std::collections::{HashMap, HashSet};
 use chrono::Utc;
+use tracing::info;
+
+use crate::economy::{SharedBudget, SpendDecision};
+use crate::currency::{Asset, AssetLedger};
+use crate::blockchain_core::FeeEstimate;
+
+/// Coût en gas par défaut du déploiement/mint d'un token, sans estimation de frais (unités natives)
+const MINT_GAS_COST: f64 = 0.002;
+/// Gas consommé par le déploiement/mint d'un token
+const MINT_GAS_UNITS: u64 = 100_000;
 
 #[derive(Clone)]
 pub enum TokenKind {
@@ -19,10 +29,304 @@ pub struct SmartToken {
     pub creator_share: f64,
 }
 
+/// Pool de liquidité à produit constant (x · y = k)
+#[derive(Debug, Clone)]
+pub struct LiquidityPool {
+    pub id: Uuid,
+    pub asset_a: Asset,
+    pub asset_b: Asset,
+    pub reserve_a: f64,
+    pub reserve_b: f64,
+    /// Total des parts LP émises
+    pub total_shares: f64,
+    /// Parts LP détenues par fournisseur
+    pub shares: HashMap<String, f64>,
+    /// Frais de swap en points de base, laissés dans les réserves
+    pub fee_bps: u16,
+    pub created_at: String,
+    pub metadata: HashMap<String, String>,
+}
+
+/// Résultat d'un échange dans un pool
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapOutcome {
+    pub amount_in: f64,
+    pub amount_out: f64,
+    pub fee_paid: f64,
+    pub price_impact: f64,
+}
+
+impl LiquidityPool {
+    /// Prix spot de A exprimé en B
+    pub fn spot_price(&self) -> f64 {
+        if self.reserve_a == 0.0 { 0.0 } else { self.reserve_b / self.reserve_a }
+    }
+
+    pub fn invariant(&self) -> f64 {
+        self.reserve_a * self.reserve_b
+    }
+
+    fn reserves_for(&self, asset_in: &Asset) -> Result<(f64, f64), String> {
+        if asset_in == &self.asset_a {
+            Ok((self.reserve_a, self.reserve_b))
+        } else if asset_in == &self.asset_b {
+            Ok((self.reserve_b, self.reserve_a))
+        } else {
+            Err(format!("Actif {} absent du pool", asset_in))
+        }
+    }
+
+    /// Calcule le résultat d'un swap sans modifier les réserves
+    pub fn quote(&self, asset_in: &Asset, amount_in: f64) -> Result<SwapOutcome, String> {
+        if amount_in <= 0.0 || !amount_in.is_finite() {
+            return Err("Montant d'entrée invalide".to_string());
+        }
+        let (reserve_in, reserve_out) = self.reserves_for(asset_in)?;
+        if reserve_in <= 0.0 || reserve_out <= 0.0 {
+            return Err("Pool sans liquidité".to_string());
+        }
+
+        let fee_paid = amount_in * self.fee_bps as f64 / 10_000.0;
+        let effective_in = amount_in - fee_paid;
+        let amount_out = reserve_out * effective_in / (reserve_in + effective_in);
+        let spot_out = effective_in * reserve_out / reserve_in;
+        let price_impact = if spot_out > 0.0 { 1.0 - amount_out / spot_out } else { 0.0 };
+
+        Ok(SwapOutcome { amount_in, amount_out, fee_paid, price_impact })
+    }
+
+    /// Échange `amount_in` de `asset_in` contre l'autre actif du pool
+    pub fn swap(&mut self, asset_in: &Asset, amount_in: f64, min_amount_out: f64) -> Result<SwapOutcome, String> {
+        let outcome = self.quote(asset_in, amount_in)?;
+        if min_amount_out.is_nan() || outcome.amount_out < min_amount_out {
+            return Err(format!("Glissement trop élevé: {:.6} < {:.6}", outcome.amount_out, min_amount_out));
+        }
+
+        // Les frais restent dans la réserve d'entrée et rémunèrent les fournisseurs
+        if asset_in == &self.asset_a {
+            self.reserve_a += amount_in;
+            self.reserve_b -= outcome.amount_out;
+        } else {
+            self.reserve_b += amount_in;
+            self.reserve_a -= outcome.amount_out;
+        }
+        Ok(outcome)
+    }
+
+    /// Ajoute de la liquidité au ratio courant; retourne les parts émises
+    /// et les montants réellement déposés
+    pub fn add_liquidity(&mut self, provider: &str, amount_a: f64, amount_b: f64) -> Result<(f64, f64, f64), String> {
+        if amount_a <= 0.0 || amount_b <= 0.0 || !amount_a.is_finite() || !amount_b.is_finite() {
+            return Err("Montants de liquidité invalides".to_string());
+        }
+
+        let (deposit_a, deposit_b, minted) = if self.total_shares == 0.0 {
+            (amount_a, amount_b, (amount_a * amount_b).sqrt())
+        } else {
+            let ratio = (amount_a / self.reserve_a).min(amount_b / self.reserve_b);
+            (self.reserve_a * ratio, self.reserve_b * ratio, self.total_shares * ratio)
+        };
+
+        self.reserve_a += deposit_a;
+        self.reserve_b += deposit_b;
+        self.total_shares += minted;
+        *self.shares.entry(provider.to_string()).or_insert(0.0) += minted;
+        Ok((minted, deposit_a, deposit_b))
+    }
+
+    /// Brûle des parts LP et restitue la quote-part des réserves
+    pub fn remove_liquidity(&mut self, provider: &str, shares: f64) -> Result<(f64, f64), String> {
+        let held = self.shares.get(provider).copied().unwrap_or(0.0);
+        if shares <= 0.0 || !shares.is_finite() || shares > held {
+            return Err(format!("Parts insuffisantes: {:.6} demandées, {:.6} détenues", shares, held));
+        }
+
+        let fraction = shares / self.total_shares;
+        let out_a = self.reserve_a * fraction;
+        let out_b = self.reserve_b * fraction;
+
+        self.reserve_a -= out_a;
+        self.reserve_b -= out_b;
+        self.total_shares -= shares;
+        if let Some(entry) = self.shares.get_mut(provider) {
+            *entry -= shares;
+            if *entry <= 0.0 {
+                self.shares.remove(provider);
+            }
+        }
+        Ok((out_a, out_b))
+    }
+}
+
+/// Forme de la courbe de vesting
+#[derive(Debug, Clone, PartialEq)]
+pub enum VestingKind {
+    /// Libération linéaire après la falaise, jusqu'à la fin de la durée
+    Linear,
+    /// Libération totale à la fin de la falaise
+    Cliff,
+}
+
+/// Calendrier de vesting d'une allocation de tokens
+#[derive(Debug, Clone)]
+pub struct VestingSchedule {
+    pub id: Uuid,
+    pub token_id: Uuid,
+    pub beneficiary: String,
+    pub total_amount: u64,
+    pub released: u64,
+    pub kind: VestingKind,
+    /// Horodatages UNIX (secondes)
+    pub start_ts: i64,
+    pub cliff_secs: i64,
+    pub duration_secs: i64,
+    pub created_at: String,
+}
+
+impl VestingSchedule {
+    /// Montant acquis à l'instant `at` (horodatage UNIX)
+    pub fn vested_at(&self, at: i64) -> u64 {
+        let elapsed = at - self.start_ts;
+        if elapsed < self.cliff_secs {
+            return 0;
+        }
+        match self.kind {
+            VestingKind::Cliff => self.total_amount,
+            VestingKind::Linear => {
+                if elapsed >= self.duration_secs || self.duration_secs <= 0 {
+                    self.total_amount
+                } else {
+                    ((self.total_amount as u128 * elapsed as u128) / self.duration_secs as u128) as u64
+                }
+            }
+        }
+    }
+
+    /// Montant réclamable à l'instant `at`
+    pub fn claimable_at(&self, at: i64) -> u64 {
+        self.vested_at(at).saturating_sub(self.released)
+    }
+}
+
+/// Courbe de liaison: le prix unitaire dépend de l'offre en circulation
+#[derive(Debug, Clone)]
+pub enum BondingCurve {
+    /// prix = base + slope · s
+    Linear { base: f64, slope: f64 },
+    /// prix = base · e^(growth · s)
+    Exponential { base: f64, growth: f64 },
+    /// prix = max_price / (1 + e^(-steepness · (s - midpoint)))
+    Sigmoid { max_price: f64, midpoint: f64, steepness: f64 },
+}
+
+impl BondingCurve {
+    /// Vérifie que la courbe produit des prix finis, positifs et croissants avec l'offre
+    pub fn validate(&self) -> Result<(), String> {
+        let (params, increasing): (Vec<f64>, bool) = match self {
+            BondingCurve::Linear { base, slope } => (vec![*base, *slope], *base >= 0.0 && *slope >= 0.0),
+            BondingCurve::Exponential { base, growth } => (vec![*base, *growth], *base >= 0.0 && *growth >= 0.0),
+            BondingCurve::Sigmoid { max_price, midpoint, steepness } => {
+                (vec![*max_price, *midpoint, *steepness], *max_price >= 0.0 && *steepness >= 0.0)
+            }
+        };
+        if params.iter().any(|p| !p.is_finite()) {
+            return Err("Paramètres de courbe non finis".to_string());
+        }
+        if !increasing {
+            return Err("La courbe doit produire des prix positifs et croissants".to_string());
+        }
+        Ok(())
+    }
+
+    /// Prix marginal pour une offre `supply`
+    pub fn price_at(&self, supply: f64) -> f64 {
+        match self {
+            BondingCurve::Linear { base, slope } => base + slope * supply,
+            BondingCurve::Exponential { base, growth } => base * (growth * supply).exp(),
+            BondingCurve::Sigmoid { max_price, midpoint, steepness } => {
+                max_price / (1.0 + (-steepness * (supply - midpoint)).exp())
+            }
+        }
+    }
+
+    /// Coût total pour faire passer l'offre de `from` à `to` (intégrale du prix)
+    pub fn cost_between(&self, from: f64, to: f64) -> f64 {
+        match self {
+            BondingCurve::Linear { base, slope } => {
+                base * (to - from) + slope * (to * to - from * from) / 2.0
+            }
+            BondingCurve::Exponential { base, growth } => {
+                if *growth == 0.0 {
+                    base * (to - from)
+                } else {
+                    base / growth * ((growth * to).exp() - (growth * from).exp())
+                }
+            }
+            BondingCurve::Sigmoid { max_price, midpoint, steepness } => {
+                if *steepness == 0.0 {
+                    return max_price / 2.0 * (to - from);
+                }
+                // Primitive: (max/k) · ln(1 + e^(k(s - m))), calculée de façon stable
+                let softplus = |x: f64| if x > 30.0 { x } else { x.exp().ln_1p() };
+                max_price / steepness
+                    * (softplus(steepness * (to - midpoint)) - softplus(steepness * (from - midpoint)))
+            }
+        }
+    }
+}
+
+/// Token émis le long d'une courbe de liaison, avec sa réserve de trésorerie
+#[derive(Debug, Clone)]
+pub struct CurveIssuance {
+    pub token_id: Uuid,
+    pub curve: BondingCurve,
+    /// Actif dans lequel les achats sont payés et les rachats remboursés
+    pub reserve_asset: Asset,
+    pub circulating: u64,
+    pub max_supply: u64,
+    /// Réserve accumulée par les achats, qui finance les rachats
+    pub reserve: f64,
+    /// Décote appliquée aux ventes (0.05 = 5%), conservée par la trésorerie
+    pub sell_spread: f64,
+    /// Part de la décote déjà captée par la trésorerie
+    pub treasury_revenue: f64,
+}
+
+impl CurveIssuance {
+    /// Coût d'achat de `amount` tokens
+    pub fn quote_buy(&self, amount: u64) -> Result<f64, String> {
+        if amount == 0 {
+            return Err("Quantité nulle".to_string());
+        }
+        match self.circulating.checked_add(amount) {
+            Some(total) if total <= self.max_supply => {}
+            _ => return Err(format!("Offre maximale dépassée ({} restants)", self.max_supply.saturating_sub(self.circulating))),
+        }
+        let from = self.circulating as f64;
+        Ok(self.curve.cost_between(from, from + amount as f64))
+    }
+
+    /// Montant reçu pour la vente de `amount` tokens, après décote
+    pub fn quote_sell(&self, amount: u64) -> Result<f64, String> {
+        if amount == 0 || amount > self.circulating {
+            return Err(format!("Quantité invalide: {} (en circulation: {})", amount, self.circulating));
+        }
+        let to = self.circulating as f64;
+        let gross = self.curve.cost_between(to - amount as f64, to);
+        Ok(gross * (1.0 - self.sell_spread))
+    }
+}
+
 #[derive(Default)] 
 pub struct AlchemyEngine {
     pub tokens: HashMap<Uuid, SmartToken>, // Utilisation d'un HashMap pour un accès plus rapide
     pub burned_tokens: HashSet<Uuid>, // Suivi des tokens brûlés
+    pub budget: Option<SharedBudget>, // Budget à consulter avant toute dépense
+    pub pools: HashMap<Uuid, LiquidityPool>, // Pools de liquidité à produit constant
+    pub vesting_schedules: HashMap<Uuid, VestingSchedule>, // Allocations soumises à vesting
+    pub balances: HashMap<(Uuid, String), u64>, // Tokens libérés par (token, bénéficiaire)
+    pub curve_issuances: HashMap<Uuid, CurveIssuance>, // Émissions par courbe de liaison
+    pub fee_estimate: Option<FeeEstimate>, // Dernière estimation de l'oracle de gas
 }
 
 impl AlchemyEngine {
@@ -30,9 +334,32 @@ impl AlchemyEngine {
         Self { 
             tokens: HashMap::new(),
             burned_tokens: HashSet::new(),
+            budget: None,
+            pools: HashMap::new(),
+            vesting_schedules: HashMap::new(),
+            balances: HashMap::new(),
+            curve_issuances: HashMap::new(),
+            fee_estimate: None,
         }
     }
 
+    /// Relie la forge au moteur budgétaire de l'économie
+    pub fn attach_budget(&mut self, budget: SharedBudget) {
+        self.budget = Some(budget);
+    }
+
+    /// Met à jour les frais utilisés pour budgéter les opérations on-chain
+    pub fn set_fee_estimate(&mut self, fees: FeeEstimate) {
+        self.fee_estimate = Some(fees);
+    }
+
+    /// Coût maximal d'un mint selon la dernière estimation de frais
+    pub fn mint_gas_cost(&self) -> f64 {
+        self.fee_estimate.as_ref()
+            .map(|fees| fees.max_cost(ethers::types::U256::from(MINT_GAS_UNITS)))
+            .unwrap_or(MINT_GAS_COST)
+    }
+
     // Fonction pour créer un token
     pub async fn mint_token(
         &mut self,
@@ -45,6 +372,18 @@ impl AlchemyEngine {
             return Err("Supply ne peut pas être nul".to_string());
         }
 
+        if let Some(budget) = &self.budget {
+            match budget.write().request_gas_spend("alchemy", self.mint_gas_cost()) {
+                SpendDecision::Approved => {}
+                SpendDecision::RequiresApproval(id) => {
+                    return Err(format!("Création de {} en attente d'approbation budgétaire ({})", name, id));
+                }
+                SpendDecision::Denied(reason) => {
+                    return Err(format!("Création de {} refusée par le budget: {}", name, reason));
+                }
+            }
+        }
+
         let token = SmartToken {
             id: Uuid::new_v4(),
             name: name.to_string(),
@@ -84,7 +423,404 @@ impl AlchemyEngine {
         self.tokens.get(token_id).map(|token| token.creator_share)
     }
 
+    /// Crée un pool à produit constant avec une liquidité initiale
+    pub fn create_liquidity_pool(
+        &mut self,
+        asset_a: Asset,
+        asset_b: Asset,
+        amount_a: f64,
+        amount_b: f64,
+        fee_bps: u16,
+        provider: &str,
+    ) -> Result<Uuid, String> {
+        if asset_a == asset_b {
+            return Err("Un pool nécessite deux actifs distincts".to_string());
+        }
+        if fee_bps >= 10_000 {
+            return Err("Frais de pool invalides".to_string());
+        }
+
+        let mut pool = LiquidityPool {
+            id: Uuid::new_v4(),
+            asset_a,
+            asset_b,
+            reserve_a: 0.0,
+            reserve_b: 0.0,
+            total_shares: 0.0,
+            shares: HashMap::new(),
+            fee_bps,
+            created_at: Utc::now().to_rfc3339(),
+            metadata: HashMap::new(),
+        };
+        let (minted, _, _) = pool.add_liquidity(provider, amount_a, amount_b)?;
+
+        info!(
+            "💧 Pool {}/{} créé: {} • {:.4}/{:.4} • {:.4} parts LP",
+            pool.asset_a, pool.asset_b, pool.id, pool.reserve_a, pool.reserve_b, minted
+        );
+        let id = pool.id;
+        self.pools.insert(id, pool);
+        Ok(id)
+    }
+
+    pub fn get_pool(&self, pool_id: &Uuid) -> Option<&LiquidityPool> {
+        self.pools.get(pool_id)
+    }
+
+    pub fn add_liquidity(&mut self, pool_id: &Uuid, provider: &str, amount_a: f64, amount_b: f64) -> Result<f64, String> {
+        let pool = self.pools.get_mut(pool_id).ok_or_else(|| "Pool non trouvé".to_string())?;
+        let (minted, _, _) = pool.add_liquidity(provider, amount_a, amount_b)?;
+        Ok(minted)
+    }
+
+    pub fn remove_liquidity(&mut self, pool_id: &Uuid, provider: &str, shares: f64) -> Result<(f64, f64), String> {
+        let pool = self.pools.get_mut(pool_id).ok_or_else(|| "Pool non trouvé".to_string())?;
+        pool.remove_liquidity(provider, shares)
+    }
+
+    pub fn swap(&mut self, pool_id: &Uuid, asset_in: &Asset, amount_in: f64, min_amount_out: f64) -> Result<SwapOutcome, String> {
+        let pool = self.pools.get_mut(pool_id).ok_or_else(|| "Pool non trouvé".to_string())?;
+        let outcome = pool.swap(asset_in, amount_in, min_amount_out)?;
+        info!(
+            "🔁 Swap {:.4} {} → {:.4} (frais {:.4}, impact {:.2}%)",
+            amount_in, asset_in, outcome.amount_out, outcome.fee_paid, outcome.price_impact * 100.0
+        );
+        Ok(outcome)
+    }
+
+    /// Réserve `amount` tokens pour `beneficiary` selon un calendrier de vesting
+    pub fn create_vesting_schedule(
+        &mut self,
+        token_id: &Uuid,
+        beneficiary: &str,
+        amount: u64,
+        kind: VestingKind,
+        cliff_secs: i64,
+        duration_secs: i64,
+    ) -> Result<Uuid, String> {
+        let reserved = self.vesting_schedules.values()
+            .filter(|v| &v.token_id == token_id)
+            .try_fold(0u64, |acc, v| acc.checked_add(v.total_amount))
+            .ok_or_else(|| "Allocations de vesting hors bornes".to_string())?;
+        let token = self.tokens.get(token_id).ok_or_else(|| "Token non trouvé".to_string())?;
+        let available = token.supply.saturating_sub(reserved);
+        match reserved.checked_add(amount) {
+            Some(total) if amount > 0 && total <= token.supply => {}
+            _ => return Err(format!("Allocation impossible: {} demandés, {} disponibles", amount, available)),
+        }
+        if cliff_secs < 0 || duration_secs < cliff_secs {
+            return Err("La durée de vesting doit couvrir la falaise".to_string());
+        }
+
+        let schedule = VestingSchedule {
+            id: Uuid::new_v4(),
+            token_id: *token_id,
+            beneficiary: beneficiary.to_string(),
+            total_amount: amount,
+            released: 0,
+            kind,
+            start_ts: Utc::now().timestamp(),
+            cliff_secs,
+            duration_secs,
+            created_at: Utc::now().to_rfc3339(),
+        };
+
+        info!(
+            "⏳ Vesting {:?} créé pour {}: {} {} sur {} jours (falaise {} jours)",
+            schedule.kind, beneficiary, amount, token.name, duration_secs / 86_400, cliff_secs / 86_400
+        );
+        let id = schedule.id;
+        self.vesting_schedules.insert(id, schedule);
+        Ok(id)
+    }
+
+    /// Montant actuellement réclamable pour un calendrier
+    pub fn claimable(&self, schedule_id: &Uuid) -> Option<u64> {
+        self.claimable_at(schedule_id, Utc::now().timestamp())
+    }
+
+    pub fn claimable_at(&self, schedule_id: &Uuid, at: i64) -> Option<u64> {
+        self.vesting_schedules.get(schedule_id).map(|v| v.claimable_at(at))
+    }
+
+    /// Libère les tokens acquis vers le solde du bénéficiaire
+    pub fn release_vested(&mut self, schedule_id: &Uuid) -> Result<u64, String> {
+        self.release_vested_at(schedule_id, Utc::now().timestamp())
+    }
+
+    pub fn release_vested_at(&mut self, schedule_id: &Uuid, at: i64) -> Result<u64, String> {
+        let schedule = self.vesting_schedules.get_mut(schedule_id)
+            .ok_or_else(|| "Calendrier de vesting non trouvé".to_string())?;
+        let amount = schedule.claimable_at(at);
+        if amount == 0 {
+            return Err("Aucun token réclamable pour le moment".to_string());
+        }
+
+        schedule.released += amount;
+        *self.balances.entry((schedule.token_id, schedule.beneficiary.clone())).or_insert(0) += amount;
+        info!("🔓 {} tokens libérés pour {}", amount, schedule.beneficiary);
+        Ok(amount)
+    }
+
+    pub fn get_vesting_schedules(&self, beneficiary: &str) -> Vec<&VestingSchedule> {
+        self.vesting_schedules.values().filter(|v| v.beneficiary == beneficiary).collect()
+    }
+
+    /// Crée un token dont l'émission suit une courbe de liaison (offre initiale nulle),
+    /// payé et racheté en `reserve_asset`
+    pub fn create_bonding_token(
+        &mut self,
+        name: &str,
+        curve: BondingCurve,
+        reserve_asset: Asset,
+        max_supply: u64,
+        sell_spread: f64,
+    ) -> Result<Uuid, String> {
+        if max_supply == 0 {
+            return Err("Offre maximale nulle".to_string());
+        }
+        if !sell_spread.is_finite() {
+            return Err("Décote de vente invalide".to_string());
+        }
+        curve.validate()?;
+
+        let token = SmartToken {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            kind: TokenKind::Fungible,
+            metadata: HashMap::from([("issuance".to_string(), format!("{:?}", curve))]),
+            created_at: Utc::now().to_rfc3339(),
+            supply: 0,
+            creator_share: 0.0,
+        };
+        let token_id = token.id;
+        self.tokens.insert(token_id, token);
+        self.curve_issuances.insert(token_id, CurveIssuance {
+            token_id,
+            curve,
+            reserve_asset,
+            circulating: 0,
+            max_supply,
+            reserve: 0.0,
+            sell_spread: sell_spread.clamp(0.0, 0.5),
+            treasury_revenue: 0.0,
+        });
+
+        info!("📈 Token à courbe de liaison créé: {} (offre max {})", name, max_supply);
+        Ok(token_id)
+    }
+
+    pub fn quote_curve_buy(&self, token_id: &Uuid, amount: u64) -> Result<f64, String> {
+        self.curve_issuances.get(token_id).ok_or_else(|| "Émission non trouvée".to_string())?.quote_buy(amount)
+    }
+
+    pub fn quote_curve_sell(&self, token_id: &Uuid, amount: u64) -> Result<f64, String> {
+        self.curve_issuances.get(token_id).ok_or_else(|| "Émission non trouvée".to_string())?.quote_sell(amount)
+    }
+
+    /// Achète `amount` tokens le long de la courbe; `max_cost` protège du glissement.
+    /// Le coût est débité de `buyer_ledger` et versé dans `reserve_ledger`.
+    pub fn buy_from_curve(
+        &mut self,
+        token_id: &Uuid,
+        buyer: &str,
+        buyer_ledger: &mut AssetLedger,
+        reserve_ledger: &mut AssetLedger,
+        amount: u64,
+        max_cost: f64,
+    ) -> Result<f64, String> {
+        let issuance = self.curve_issuances.get_mut(token_id).ok_or_else(|| "Émission non trouvée".to_string())?;
+        let cost = issuance.quote_buy(amount)?;
+        if !cost.is_finite() || max_cost.is_nan() || cost > max_cost {
+            return Err(format!("Coût {:.6} supérieur au maximum {:.6}", cost, max_cost));
+        }
+        if cost > 0.0 {
+            buyer_ledger.debit(&issuance.reserve_asset, cost)?;
+            reserve_ledger.credit(issuance.reserve_asset.clone(), cost);
+        }
+
+        issuance.circulating += amount;
+        issuance.reserve += cost;
+        if let Some(token) = self.tokens.get_mut(token_id) {
+            token.supply = issuance.circulating;
+        }
+        *self.balances.entry((*token_id, buyer.to_string())).or_insert(0) += amount;
+
+        info!("🪙 {} achète {} tokens pour {:.6} (réserve {:.6})", buyer, amount, cost, issuance.reserve);
+        Ok(cost)
+    }
+
+    /// Revend `amount` tokens à la courbe; `min_proceeds` protège du glissement.
+    /// Le produit est prélevé sur `reserve_ledger` et crédité à `seller_ledger`;
+    /// la décote reste dans la réserve.
+    pub fn sell_to_curve(
+        &mut self,
+        token_id: &Uuid,
+        seller: &str,
+        seller_ledger: &mut AssetLedger,
+        reserve_ledger: &mut AssetLedger,
+        amount: u64,
+        min_proceeds: f64,
+    ) -> Result<f64, String> {
+        let key = (*token_id, seller.to_string());
+        let held = self.balances.get(&key).copied().unwrap_or(0);
+        if held < amount {
+            return Err(format!("Solde insuffisant: {} < {}", held, amount));
+        }
+
+        let issuance = self.curve_issuances.get_mut(token_id).ok_or_else(|| "Émission non trouvée".to_string())?;
+        let proceeds = issuance.quote_sell(amount)?;
+        if !proceeds.is_finite() || min_proceeds.is_nan() || proceeds < min_proceeds {
+            return Err(format!("Produit {:.6} inférieur au minimum {:.6}", proceeds, min_proceeds));
+        }
+        let from = issuance.circulating as f64;
+        let gross = issuance.curve.cost_between(from - amount as f64, from);
+        if proceeds > 0.0 {
+            reserve_ledger.debit(&issuance.reserve_asset, proceeds)?;
+            seller_ledger.credit(issuance.reserve_asset.clone(), proceeds);
+        }
+
+        issuance.circulating -= amount;
+        issuance.reserve -= gross;
+        issuance.treasury_revenue += gross - proceeds;
+        if let Some(token) = self.tokens.get_mut(token_id) {
+            token.supply = issuance.circulating;
+        }
+        if let Some(balance) = self.balances.get_mut(&key) {
+            *balance -= amount;
+        }
+
+        info!("💱 {} revend {} tokens pour {:.6}", seller, amount, proceeds);
+        Ok(proceeds)
+    }
+
     pub fn initialize(&mut self) {
-        println!("[AURORAE++] 🔮 Initialisation du moteur de forge de tokens");
+        info!("🔮 Initialisation du moteur de forge de tokens");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool() -> (AlchemyEngine, Uuid) {
+        let mut forge = AlchemyEngine::new();
+        let id = forge.create_liquidity_pool(Asset::Aura, Asset::usdc(), 1_000.0, 50.0, 30, "aurorae").unwrap();
+        (forge, id)
+    }
+
+    #[test]
+    fn test_swap_preserves_invariant_with_fee() {
+        let (mut forge, id) = pool();
+        let k_before = forge.get_pool(&id).unwrap().invariant();
+
+        let outcome = forge.swap(&id, &Asset::Aura, 100.0, 0.0).unwrap();
+        let pool = forge.get_pool(&id).unwrap();
+
+        assert!((outcome.fee_paid - 0.3).abs() < 1e-9);
+        assert!(outcome.amount_out < 5.0);
+        assert!(pool.invariant() >= k_before);
+        assert_eq!(pool.reserve_a, 1_100.0);
+    }
+
+    #[test]
+    fn test_add_and_remove_liquidity_round_trip() {
+        let (mut forge, id) = pool();
+        let minted = forge.add_liquidity(&id, "lp", 100.0, 10.0).unwrap();
+        // Seul le ratio courant (20:1) est accepté: 100 AURA pour 5 USDC
+        assert!((forge.get_pool(&id).unwrap().reserve_b - 55.0).abs() < 1e-9);
+
+        let (out_a, out_b) = forge.remove_liquidity(&id, "lp", minted).unwrap();
+        assert!((out_a - 100.0).abs() < 1e-9);
+        assert!((out_b - 5.0).abs() < 1e-9);
+        assert!(forge.remove_liquidity(&id, "lp", 1.0).is_err());
+    }
+
+    #[test]
+    fn test_linear_vesting_with_cliff() {
+        let mut forge = AlchemyEngine::new();
+        let token_id = Uuid::new_v4();
+        forge.tokens.insert(token_id, SmartToken {
+            id: token_id,
+            name: "Auroraium".to_string(),
+            kind: TokenKind::Fungible,
+            metadata: HashMap::new(),
+            created_at: Utc::now().to_rfc3339(),
+            supply: 1_000,
+            creator_share: 0.05,
+        });
+
+        let id = forge.create_vesting_schedule(&token_id, "founder", 1_000, VestingKind::Linear, 100, 1_000).unwrap();
+        let start = forge.vesting_schedules[&id].start_ts;
+
+        assert_eq!(forge.claimable_at(&id, start + 99), Some(0));
+        assert_eq!(forge.claimable_at(&id, start + 500), Some(500));
+        assert_eq!(forge.release_vested_at(&id, start + 500).unwrap(), 500);
+        assert_eq!(forge.claimable_at(&id, start + 2_000), Some(500));
+        assert!(forge.create_vesting_schedule(&token_id, "other", 1, VestingKind::Cliff, 0, 0).is_err());
+        assert!(forge.create_vesting_schedule(&token_id, "other", u64::MAX, VestingKind::Cliff, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_bonding_curve_buy_sell_is_reserve_backed() {
+        let mut forge = AlchemyEngine::new();
+        let curve = BondingCurve::Linear { base: 1.0, slope: 0.01 };
+        let id = forge.create_bonding_token("Curve", curve, Asset::usdc(), 1_000, 0.0).unwrap();
+        let mut alice = AssetLedger::new();
+        let mut reserve = AssetLedger::new();
+        alice.credit(Asset::usdc(), 200.0);
+
+        // ∫0→100 (1 + 0.01s) ds = 100 + 50
+        let cost = forge.buy_from_curve(&id, "alice", &mut alice, &mut reserve, 100, 200.0).unwrap();
+        assert!((cost - 150.0).abs() < 1e-9);
+        assert!((alice.balance(&Asset::usdc()) - 50.0).abs() < 1e-9);
+        assert!((reserve.balance(&Asset::usdc()) - 150.0).abs() < 1e-9);
+        // Solde insuffisant: aucun token émis
+        assert!(forge.buy_from_curve(&id, "alice", &mut alice, &mut reserve, 100, f64::MAX).is_err());
+        assert_eq!(forge.curve_issuances[&id].circulating, 100);
+
+        let proceeds = forge.sell_to_curve(&id, "alice", &mut alice, &mut reserve, 100, 0.0).unwrap();
+        assert!((proceeds - cost).abs() < 1e-9);
+        assert!((alice.balance(&Asset::usdc()) - 200.0).abs() < 1e-9);
+        assert!(forge.curve_issuances[&id].reserve.abs() < 1e-9);
+        assert!(forge.buy_from_curve(&id, "bob", &mut alice, &mut reserve, 1_001, f64::MAX).is_err());
+        assert!(forge.quote_curve_buy(&id, u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_bonding_curve_rejects_decreasing_or_non_finite_params() {
+        let mut forge = AlchemyEngine::new();
+        let falling = BondingCurve::Linear { base: 10.0, slope: -0.1 };
+        assert!(forge.create_bonding_token("Falling", falling, Asset::usdc(), 1_000, 0.0).is_err());
+        let shrinking = BondingCurve::Exponential { base: 1.0, growth: -0.01 };
+        assert!(forge.create_bonding_token("Shrinking", shrinking, Asset::usdc(), 1_000, 0.0).is_err());
+        let nan = BondingCurve::Sigmoid { max_price: f64::NAN, midpoint: 50.0, steepness: 0.1 };
+        assert!(forge.create_bonding_token("Nan", nan, Asset::usdc(), 1_000, 0.0).is_err());
+        assert!(forge.curve_issuances.is_empty());
+    }
+
+    #[test]
+    fn test_sigmoid_cost_matches_numeric_integral() {
+        let curve = BondingCurve::Sigmoid { max_price: 10.0, midpoint: 50.0, steepness: 0.1 };
+        let numeric: f64 = (0..1_000).map(|i| curve.price_at(i as f64 * 0.1 + 0.05) * 0.1).sum();
+        assert!((curve.cost_between(0.0, 100.0) - numeric).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_swap_rejects_slippage() {
+        let (mut forge, id) = pool();
+        assert!(forge.swap(&id, &Asset::usdc(), 10.0, 1_000.0).is_err());
+        assert_eq!(forge.get_pool(&id).unwrap().reserve_b, 50.0);
+    }
+
+    #[test]
+    fn test_pool_rejects_non_finite_amounts() {
+        let (mut forge, id) = pool();
+        assert!(forge.swap(&id, &Asset::Aura, f64::NAN, 0.0).is_err());
+        assert!(forge.swap(&id, &Asset::Aura, f64::INFINITY, 0.0).is_err());
+        assert!(forge.add_liquidity(&id, "lp", f64::INFINITY, 10.0).is_err());
+        assert!(forge.remove_liquidity(&id, "aurorae", f64::NAN).is_err());
+        let pool = forge.get_pool(&id).unwrap();
+        assert_eq!((pool.reserve_a, pool.reserve_b), (1_000.0, 50.0));
     }
 }
@@ -0,0 +1,419 @@
+//! AURORAE++ - alerting.rs
+//!
+//! Alertes opérateur: les incidents diffusés sur le bus (menaces, modules corrompus,
+//! brèches d'intégrité, souscriptions perdues) sont convertis en alertes et routés vers
+//! des canaux de notification (sortie standard, webhook, Slack, e-mail SMTP) selon leur
+//! gravité. Une alerte identique n'est renvoyée qu'après `DEDUPE_WINDOW_SECS`.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::event_bus::{AuroraeEvent, EventBus};
+use crate::resource_usage::spawn_tracked;
+use crate::secrets::secret;
+use crate::security::ThreatLevel;
+
+/// Délai pendant lequel une alerte identique (même source, même titre) est ignorée
+pub const DEDUPE_WINDOW_SECS: i64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Info => "INFO",
+            Severity::Warning => "WARNING",
+            Severity::Critical => "CRITICAL",
+        }
+    }
+
+    fn emoji(&self) -> &'static str {
+        match self {
+            Severity::Info => "ℹ️",
+            Severity::Warning => "⚠️",
+            Severity::Critical => "🚨",
+        }
+    }
+}
+
+impl From<ThreatLevel> for Severity {
+    fn from(level: ThreatLevel) -> Self {
+        match level {
+            ThreatLevel::Low => Severity::Info,
+            ThreatLevel::Medium => Severity::Warning,
+            ThreatLevel::High | ThreatLevel::Critical => Severity::Critical,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub id: Uuid,
+    pub severity: Severity,
+    pub title: String,
+    pub message: String,
+    pub source: String,
+    pub raised_at: String,
+}
+
+impl Alert {
+    pub fn new(severity: Severity, title: &str, message: &str, source: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            severity,
+            title: title.to_string(),
+            message: message.to_string(),
+            source: source.to_string(),
+            raised_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Alerte correspondant à un événement du bus; `None` pour l'activité ordinaire
+    pub fn from_event(event: &AuroraeEvent) -> Option<Self> {
+        match event {
+            AuroraeEvent::ThreatDetected { threat_type, source, level } => Some(Self::new(
+                Severity::from(*level),
+                &format!("Menace détectée: {}", threat_type),
+                &format!("Menace {} de niveau {:?} (source: {})", threat_type, level, source),
+                source,
+            )),
+            AuroraeEvent::ModuleCorrupted { module, reason } => Some(Self::new(
+                Severity::Critical,
+                &format!("Module corrompu: {}", module),
+                reason,
+                module,
+            )),
+            AuroraeEvent::IntegrityBreach { component, details } => Some(Self::new(
+                Severity::Critical,
+                &format!("Brèche d'intégrité: {}", component),
+                details,
+                component,
+            )),
+            AuroraeEvent::SubscriptionLost { chain_id, kind, reason } => Some(Self::new(
+                Severity::Warning,
+                &format!("Souscription {} perdue", kind),
+                reason,
+                &format!("chain-{}", chain_id),
+            )),
+            AuroraeEvent::NewBlock { .. }
+            | AuroraeEvent::ChainLog { .. }
+            | AuroraeEvent::ReorgDetected { .. }
+            | AuroraeEvent::TransactionFinalized { .. }
+            | AuroraeEvent::InstanceMessage { .. } => None,
+        }
+    }
+
+    fn summary(&self) -> String {
+        format!("[{}] {} — {} (source: {})", self.severity.label(), self.title, self.message, self.source)
+    }
+}
+
+/// Canal de notification des opérateurs
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn send(&self, alert: &Alert) -> Result<(), String>;
+}
+
+pub struct StdoutSink;
+
+#[async_trait]
+impl AlertSink for StdoutSink {
+    fn name(&self) -> &str {
+        "stdout"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        info!("{} ALERTE {}", alert.severity.emoji(), alert.summary());
+        Ok(())
+    }
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+async fn post_json(client: &reqwest::Client, url: &str, body: &serde_json::Value) -> Result<(), String> {
+    let response = client.post(url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Webhook injoignable: {}", e))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("Statut HTTP {}", response.status()))
+    }
+}
+
+/// Webhook générique: l'alerte est envoyée telle quelle en JSON
+pub struct WebhookSink {
+    client: reqwest::Client,
+    pub url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str) -> Self {
+        Self { client: http_client(), url: url.to_string() }
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        let body = serde_json::to_value(alert).map_err(|e| format!("Alerte non sérialisable: {}", e))?;
+        post_json(&self.client, &self.url, &body).await
+    }
+}
+
+/// Webhook entrant Slack
+pub struct SlackSink {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+impl SlackSink {
+    pub fn new(webhook_url: &str) -> Self {
+        Self { client: http_client(), webhook_url: webhook_url.to_string() }
+    }
+}
+
+#[async_trait]
+impl AlertSink for SlackSink {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        let body = json!({
+            "text": format!("{} *{}*\n{}\n_source: {} • {}_", alert.severity.emoji(), alert.title, alert.message, alert.source, alert.raised_at),
+        });
+        post_json(&self.client, &self.webhook_url, &body).await
+    }
+}
+
+/// E-mail via un relais SMTP (TLS)
+pub struct EmailSink {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl EmailSink {
+    pub fn new(host: &str, credentials: Option<(String, String)>, from: &str, to: &[&str]) -> Result<Self, String> {
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+            .map_err(|e| format!("Relais SMTP {} invalide: {}", host, e))?;
+        if let Some((username, password)) = credentials {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+        let parse = |address: &str| address.trim().parse::<Mailbox>()
+            .map_err(|e| format!("Adresse e-mail invalide {}: {}", address, e));
+        let to = to.iter().map(|address| parse(address)).collect::<Result<Vec<_>, _>>()?;
+        if to.is_empty() {
+            return Err("Aucun destinataire d'alerte".to_string());
+        }
+        Ok(Self { transport: builder.build(), from: parse(from)?, to })
+    }
+}
+
+#[async_trait]
+impl AlertSink for EmailSink {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        let mut message = Message::builder()
+            .from(self.from.clone())
+            .subject(format!("[AURORAE++][{}] {}", alert.severity.label(), alert.title));
+        for recipient in &self.to {
+            message = message.to(recipient.clone());
+        }
+        let message = message
+            .body(format!("{}\n\nSource: {}\nLevée le: {}\nIdentifiant: {}\n", alert.message, alert.source, alert.raised_at, alert.id))
+            .map_err(|e| format!("E-mail invalide: {}", e))?;
+        self.transport.send(message).await
+            .map(|_| ())
+            .map_err(|e| format!("Envoi SMTP impossible: {}", e))
+    }
+}
+
+struct Route {
+    min_severity: Severity,
+    sink: Box<dyn AlertSink>,
+}
+
+/// Routage des alertes: chaque canal ne reçoit que les alertes de gravité suffisante
+#[derive(Default)]
+pub struct AlertRouter {
+    routes: Vec<Route>,
+    last_sent: HashMap<(String, String), DateTime<Utc>>,
+    pub sent: u64,
+    pub suppressed: u64,
+}
+
+impl AlertRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sortie standard pour tout, puis les canaux configurés par l'environnement:
+    /// `ALERT_WEBHOOK_URL` et `SLACK_WEBHOOK_URL` (Warning et plus), `SMTP_HOST`,
+    /// `SMTP_USERNAME`, `SMTP_PASSWORD`, `ALERT_EMAIL_FROM`, `ALERT_EMAIL_TO` (Critical)
+    pub fn from_env() -> Self {
+        let mut router = Self::new().with_sink(Severity::Info, StdoutSink);
+        if let Some(url) = secret("alerting", "ALERT_WEBHOOK_URL") {
+            router = router.with_sink(Severity::Warning, WebhookSink::new(url.expose()));
+        }
+        if let Some(url) = secret("alerting", "SLACK_WEBHOOK_URL") {
+            router = router.with_sink(Severity::Warning, SlackSink::new(url.expose()));
+        }
+        if let (Ok(host), Ok(from), Ok(to)) = (std::env::var("SMTP_HOST"), std::env::var("ALERT_EMAIL_FROM"), std::env::var("ALERT_EMAIL_TO")) {
+            let password = secret("alerting", "SMTP_PASSWORD").map(|password| password.expose().to_string());
+            let credentials = std::env::var("SMTP_USERNAME").ok().zip(password);
+            let recipients: Vec<&str> = to.split(',').filter(|r| !r.trim().is_empty()).collect();
+            match EmailSink::new(&host, credentials, &from, &recipients) {
+                Ok(sink) => router = router.with_sink(Severity::Critical, sink),
+                Err(e) => warn!("⚠️ Alertes e-mail désactivées: {}", e),
+            }
+        }
+        router
+    }
+
+    pub fn with_sink(mut self, min_severity: Severity, sink: impl AlertSink + 'static) -> Self {
+        self.add_sink(min_severity, Box::new(sink));
+        self
+    }
+
+    pub fn add_sink(&mut self, min_severity: Severity, sink: Box<dyn AlertSink>) {
+        info!("📣 Canal d'alerte {} (gravité ≥ {})", sink.name(), min_severity.label());
+        self.routes.push(Route { min_severity, sink });
+    }
+
+    /// Vrai si une alerte identique a déjà été envoyée dans la fenêtre de déduplication
+    fn is_duplicate(&mut self, alert: &Alert) -> bool {
+        let now = Utc::now();
+        let key = (alert.source.clone(), alert.title.clone());
+        if let Some(last) = self.last_sent.get(&key) {
+            if (now - *last).num_seconds() < DEDUPE_WINDOW_SECS {
+                return true;
+            }
+        }
+        self.last_sent.insert(key, now);
+        false
+    }
+
+    /// Envoie l'alerte aux canaux concernés; retourne le nombre de canaux notifiés
+    pub async fn dispatch(&mut self, alert: &Alert) -> usize {
+        if self.is_duplicate(alert) {
+            self.suppressed += 1;
+            return 0;
+        }
+        let mut delivered = 0;
+        for route in self.routes.iter().filter(|r| alert.severity >= r.min_severity) {
+            match route.sink.send(alert).await {
+                Ok(()) => delivered += 1,
+                Err(e) => warn!("⚠️ Alerte {} non délivrée via {}: {}", alert.id, route.sink.name(), e),
+            }
+        }
+        self.sent += delivered as u64;
+        delivered
+    }
+}
+
+/// Écoute le bus et notifie les opérateurs de chaque incident
+pub fn spawn_alerting(bus: &EventBus, mut router: AlertRouter) -> JoinHandle<()> {
+    let mut events = bus.subscribe();
+    spawn_tracked("alerting", async move {
+        loop {
+            match events.recv().await {
+                Ok(envelope) => {
+                    if let Some(alert) = Alert::from_event(&envelope.event) {
+                        router.dispatch(&alert).await;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    let alert = Alert::new(
+                        Severity::Warning,
+                        "Alertes perdues",
+                        &format!("{} événements ignorés par le routeur d'alertes (abonné en retard)", skipped),
+                        "alerting",
+                    );
+                    router.dispatch(&alert).await;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use parking_lot::Mutex;
+
+    struct RecordingSink(Arc<Mutex<Vec<Severity>>>);
+
+    #[async_trait]
+    impl AlertSink for RecordingSink {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn send(&self, alert: &Alert) -> Result<(), String> {
+            self.0.lock().push(alert.severity);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn routes_by_severity_and_deduplicates() {
+        let (all, critical) = (Arc::new(Mutex::new(Vec::new())), Arc::new(Mutex::new(Vec::new())));
+        let mut router = AlertRouter::new()
+            .with_sink(Severity::Info, RecordingSink(all.clone()))
+            .with_sink(Severity::Critical, RecordingSink(critical.clone()));
+
+        let threat = Alert::from_event(&AuroraeEvent::ThreatDetected {
+            threat_type: "chain_reorg".to_string(),
+            source: "chain-1".to_string(),
+            level: ThreatLevel::Medium,
+        }).unwrap();
+        let corrupted = Alert::from_event(&AuroraeEvent::ModuleCorrupted {
+            module: "economy".to_string(),
+            reason: "état incohérent".to_string(),
+        }).unwrap();
+
+        assert_eq!(router.dispatch(&threat).await, 1);
+        assert_eq!(router.dispatch(&corrupted).await, 2);
+        assert_eq!(router.dispatch(&corrupted).await, 0);
+        assert_eq!(*all.lock(), vec![Severity::Warning, Severity::Critical]);
+        assert_eq!(*critical.lock(), vec![Severity::Critical]);
+        assert_eq!(router.suppressed, 1);
+    }
+}
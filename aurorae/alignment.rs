@@ -0,0 +1,475 @@
+//! AURORAE++ - alignment.rs
+//!
+//! Alignement des décisions autonomes sur la volonté des opérateurs humains. Une
+//! opération sensible peut exiger un jeton d'approbation humaine: le secret de
+//! l'opérateur n'est jamais stocké, seule son empreinte SHA-256 est configurée
+//! (`AURORAE_OPERATOR_TOKEN_HASH`, plusieurs empreintes séparées par des virgules).
+//!
+//! Les actions autonomes passent par une politique d'alignement (`alignment.policy`),
+//! une règle par ligne évaluée avant l'exécution de chaque intention:
+//!
+//! ```text
+//! limit spend amount 5000 per day
+//! deny mutate if module in security, guardian, alignment
+//! require-approval deploy if network == mainnet
+//! ```
+//!
+//! Une violation est journalisée et l'action opposée d'un veto; une action soumise à
+//! `require-approval` est mise en attente dans la file d'`approvals`. Une politique
+//! absente ou invalide ne laisse rien passer: toutes les actions sont refusées. Une
+//! action plafonnée (`limit`) sans montant valide est refusée; son montant n'est imputé
+//! au cumul du jour que lorsqu'elle s'exécute.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{error, info};
+
+use chrono::{NaiveDate, Utc};
+use parking_lot::RwLock;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+pub const OPERATOR_TOKEN_ENV: &str = "AURORAE_OPERATOR_TOKEN_HASH";
+/// Chemin de la politique d'alignement (`alignment.policy` par défaut)
+pub const POLICY_PATH_ENV: &str = "AURORAE_ALIGNMENT_POLICY";
+pub const DEFAULT_POLICY_PATH: &str = "alignment.policy";
+
+/// Action proposée par un module autonome, soumise à la politique
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProposedAction {
+    /// `spend`, `mutate`, `deploy`, `replicate`… ou le nom de l'intention
+    pub kind: String,
+    pub attributes: HashMap<String, String>,
+}
+
+impl ProposedAction {
+    pub fn new(kind: &str) -> Self {
+        Self { kind: kind.to_string(), attributes: HashMap::new() }
+    }
+
+    pub fn with(mut self, key: &str, value: &str) -> Self {
+        self.attributes.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    fn number(&self, key: &str) -> Option<f64> {
+        self.attributes.get(key).and_then(|v| v.parse::<f64>().ok())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Comparison {
+    Eq(String),
+    Ne(String),
+    Gt(f64),
+    Ge(f64),
+    Lt(f64),
+    Le(f64),
+    In(Vec<String>),
+}
+
+/// Condition `<attribut> <op> <valeur>` d'une règle
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub attribute: String,
+    pub comparison: Comparison,
+}
+
+impl Condition {
+    fn holds(&self, action: &ProposedAction) -> bool {
+        let value = action.attributes.get(&self.attribute);
+        match &self.comparison {
+            Comparison::Eq(expected) => value == Some(expected),
+            Comparison::Ne(expected) => value != Some(expected),
+            Comparison::In(values) => value.map(|v| values.contains(v)).unwrap_or(false),
+            numeric => match action.number(&self.attribute) {
+                Some(v) => match numeric {
+                    Comparison::Gt(bound) => v > *bound,
+                    Comparison::Ge(bound) => v >= *bound,
+                    Comparison::Lt(bound) => v < *bound,
+                    Comparison::Le(bound) => v <= *bound,
+                    _ => false,
+                },
+                None => false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyRule {
+    /// Cumul journalier (UTC) de l'attribut numérique `attribute` pour `action`
+    DailyLimit { action: String, attribute: String, max: f64 },
+    Deny { action: String, condition: Option<Condition> },
+    RequireApproval { action: String, condition: Option<Condition> },
+}
+
+impl PolicyRule {
+    fn action(&self) -> &str {
+        match self {
+            PolicyRule::DailyLimit { action, .. } | PolicyRule::Deny { action, .. } | PolicyRule::RequireApproval { action, .. } => action,
+        }
+    }
+
+    fn applies_to(&self, action: &ProposedAction) -> bool {
+        self.action() == "any" || self.action() == action.kind
+    }
+}
+
+/// Politique d'alignement: règles évaluées dans l'ordre, le veto l'emporte
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Policy {
+    pub rules: Vec<(String, PolicyRule)>,
+}
+
+impl Policy {
+    /// Analyse le DSL; une erreur indique la ligne fautive
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut rules = Vec::new();
+        for (number, line) in source.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let rule = Self::parse_rule(line).map_err(|e| format!("Politique ligne {}: {} ({})", number + 1, e, line))?;
+            rules.push((line.to_string(), rule));
+        }
+        Ok(Self { rules })
+    }
+
+    fn parse_rule(line: &str) -> Result<PolicyRule, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["limit", action, attribute, max, "per", "day"] => Ok(PolicyRule::DailyLimit {
+                action: action.to_string(),
+                attribute: attribute.to_string(),
+                max: max.parse::<f64>().map_err(|_| format!("plafond invalide: {}", max))?,
+            }),
+            ["deny", action, rest @ ..] => Ok(PolicyRule::Deny { action: action.to_string(), condition: Self::parse_condition(rest)? }),
+            ["require-approval", action, rest @ ..] => Ok(PolicyRule::RequireApproval {
+                action: action.to_string(),
+                condition: Self::parse_condition(rest)?,
+            }),
+            _ => Err("règle inconnue (limit, deny, require-approval)".to_string()),
+        }
+    }
+
+    fn parse_condition(words: &[&str]) -> Result<Option<Condition>, String> {
+        let (attribute, op, value) = match words {
+            [] => return Ok(None),
+            ["if", attribute, op, value @ ..] if !value.is_empty() => (attribute.to_string(), *op, value.join(" ")),
+            _ => return Err("condition attendue: if <attribut> <op> <valeur>".to_string()),
+        };
+        let number = || value.parse::<f64>().map_err(|_| format!("nombre attendu: {}", value));
+        let comparison = match op {
+            "==" => Comparison::Eq(value.clone()),
+            "!=" => Comparison::Ne(value.clone()),
+            ">" => Comparison::Gt(number()?),
+            ">=" => Comparison::Ge(number()?),
+            "<" => Comparison::Lt(number()?),
+            "<=" => Comparison::Le(number()?),
+            "in" => Comparison::In(value.split(',').map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect()),
+            other => return Err(format!("opérateur inconnu: {}", other)),
+        };
+        Ok(Some(Condition { attribute, comparison }))
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::parse(&source)
+    }
+}
+
+/// Verdict de la politique pour une action
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyDecision {
+    Allow,
+    Veto { rule: String },
+    RequiresApproval { rule: String },
+}
+
+/// Violation journalisée pour l'audit
+#[derive(Debug, Clone)]
+pub struct PolicyViolation {
+    pub subject: Uuid,
+    pub action: ProposedAction,
+    pub decision: PolicyDecision,
+    pub at: String,
+}
+
+pub type SharedAlignment = Arc<RwLock<AlignmentSystem>>;
+
+/// Approbation humaine accordée à une opération
+#[derive(Debug, Clone, PartialEq)]
+pub struct HumanApproval {
+    pub subject: Uuid,
+    /// Empreinte (tronquée) du jeton présenté ou signataires, pour l'audit
+    pub operator: String,
+    pub approved_at: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AlignmentSystem {
+    operator_hashes: HashSet<String>,
+    pub approvals: Vec<HumanApproval>,
+    pub policy: Policy,
+    /// Cumuls journaliers par règle `limit`, remis à zéro au changement de jour UTC
+    daily_totals: HashMap<String, f64>,
+    day: Option<NaiveDate>,
+    pub violations: Vec<PolicyViolation>,
+    /// Erreur de chargement de la politique: tant qu'elle est présente, tout est refusé
+    pub policy_error: Option<String>,
+}
+
+impl AlignmentSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opérateurs configurés par `AURORAE_OPERATOR_TOKEN_HASH`
+    pub fn from_env() -> Self {
+        let mut system = Self::new();
+        if let Ok(hashes) = std::env::var(OPERATOR_TOKEN_ENV) {
+            for hash in hashes.split(',').map(str::trim).filter(|h| !h.is_empty()) {
+                system.register_operator_hash(hash);
+            }
+        }
+        let policy_path = std::env::var(POLICY_PATH_ENV).unwrap_or_else(|_| DEFAULT_POLICY_PATH.to_string());
+        system.load_policy(&policy_path);
+        system
+    }
+
+    /// Charge la politique de `path`; en cas d'échec, le système refuse toute action
+    pub fn load_policy(&mut self, path: &str) {
+        match Policy::load(path) {
+            Ok(policy) => {
+                self.policy = policy;
+                self.policy_error = None;
+            }
+            Err(e) => {
+                error!("⛔ Politique d'alignement indisponible, actions autonomes refusées: {}", e);
+                self.policy = Policy::default();
+                self.policy_error = Some(e);
+            }
+        }
+    }
+
+    pub fn shared(self) -> SharedAlignment {
+        Arc::new(RwLock::new(self))
+    }
+
+    /// Évalue `action` avant exécution; une action autorisée est imputée aux plafonds
+    /// journaliers, une violation est journalisée
+    /// Évalue `action` puis, si elle est autorisée, l'impute aux plafonds journaliers: pour
+    /// une action exécutée dès qu'elle est autorisée
+    pub fn gate(&mut self, subject: Uuid, action: &ProposedAction) -> PolicyDecision {
+        let decision = self.evaluate(subject, action);
+        if decision == PolicyDecision::Allow {
+            self.charge(action);
+        }
+        decision
+    }
+
+    /// Verdict de la politique sans rien imputer: l'appelant qui peut encore renoncer (attente
+    /// d'approbation, autre contrôle) appelle `charge` quand l'action s'exécute réellement
+    pub fn evaluate(&mut self, subject: Uuid, action: &ProposedAction) -> PolicyDecision {
+        self.roll_day();
+        let mut decision = match &self.policy_error {
+            Some(e) => PolicyDecision::Veto { rule: format!("politique indisponible: {}", e) },
+            None => PolicyDecision::Allow,
+        };
+        let rules = if self.policy_error.is_some() { &[][..] } else { &self.policy.rules[..] };
+        for (text, rule) in rules.iter().filter(|(_, rule)| rule.applies_to(action)) {
+            let verdict = match rule {
+                PolicyRule::DailyLimit { attribute, max, .. } => match Self::limited_amount(action, attribute) {
+                    Some(amount) => {
+                        let spent = self.daily_totals.get(text).copied().unwrap_or(0.0);
+                        (spent + amount > *max).then(|| PolicyDecision::Veto { rule: text.clone() })
+                    }
+                    None => Some(PolicyDecision::Veto { rule: format!("{} ({} absent ou invalide)", text, attribute) }),
+                },
+                PolicyRule::Deny { condition, .. } => condition.as_ref().map(|c| c.holds(action)).unwrap_or(true)
+                    .then(|| PolicyDecision::Veto { rule: text.clone() }),
+                PolicyRule::RequireApproval { condition, .. } => {
+                    (condition.as_ref().map(|c| c.holds(action)).unwrap_or(true) && !self.is_approved(&subject))
+                        .then(|| PolicyDecision::RequiresApproval { rule: text.clone() })
+                }
+            };
+            match verdict {
+                Some(veto @ PolicyDecision::Veto { .. }) => {
+                    decision = veto;
+                    break;
+                }
+                Some(approval) if decision == PolicyDecision::Allow => decision = approval,
+                _ => {}
+            }
+        }
+
+        if decision != PolicyDecision::Allow {
+            error!("⛔ Alignement: {} ({:?}) refusé: {:?}", action.kind, action.attributes, decision);
+            self.violations.push(PolicyViolation {
+                subject,
+                action: action.clone(),
+                decision: decision.clone(),
+                at: Utc::now().to_rfc3339(),
+            });
+        }
+        decision
+    }
+
+    /// Impute une action exécutée aux cumuls des règles `limit` qui la concernent
+    pub fn charge(&mut self, action: &ProposedAction) {
+        self.roll_day();
+        for (text, rule) in &self.policy.rules {
+            if let PolicyRule::DailyLimit { attribute, .. } = rule {
+                if let (true, Some(amount)) = (rule.applies_to(action), Self::limited_amount(action, attribute)) {
+                    *self.daily_totals.entry(text.clone()).or_insert(0.0) += amount;
+                }
+            }
+        }
+    }
+
+    /// Montant soumis à un plafond: absent, non fini ou négatif, il n'est pas plafonnable
+    fn limited_amount(action: &ProposedAction, attribute: &str) -> Option<f64> {
+        action.number(attribute).filter(|amount| amount.is_finite() && *amount >= 0.0)
+    }
+
+    fn roll_day(&mut self) {
+        let today = Utc::now().date_naive();
+        if self.day != Some(today) {
+            self.day = Some(today);
+            self.daily_totals.clear();
+        }
+    }
+
+    pub fn register_operator_hash(&mut self, hash: &str) {
+        self.operator_hashes.insert(hash.trim_start_matches("0x").to_lowercase());
+    }
+
+    pub fn has_operators(&self) -> bool {
+        !self.operator_hashes.is_empty()
+    }
+
+    /// Empreinte à configurer pour un secret d'opérateur
+    pub fn token_hash(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    /// Vérifie le jeton d'un opérateur et enregistre son approbation de `subject`
+    pub fn approve(&mut self, subject: Uuid, token: &str) -> Result<HumanApproval, String> {
+        let hash = Self::token_hash(token);
+        if !self.operator_hashes.contains(&hash) {
+            error!("⛔ Jeton d'approbation humaine rejeté pour {}", subject);
+            return Err("Jeton d'approbation humaine invalide".to_string());
+        }
+        let approval = HumanApproval {
+            subject,
+            operator: hash[..12].to_string(),
+            approved_at: Utc::now().to_rfc3339(),
+        };
+        info!("🧑 Approbation humaine de {} par l'opérateur {}", subject, approval.operator);
+        self.approvals.push(approval.clone());
+        Ok(approval)
+    }
+
+    /// Enregistre une approbation obtenue hors jeton (signatures de la file `approvals`)
+    pub fn grant(&mut self, subject: Uuid, approvers: &str) {
+        info!("🧑 Approbation de {} par {}", subject, approvers);
+        self.approvals.push(HumanApproval {
+            subject,
+            operator: approvers.to_string(),
+            approved_at: Utc::now().to_rfc3339(),
+        });
+    }
+
+    pub fn is_approved(&self, subject: &Uuid) -> bool {
+        self.approvals.iter().any(|a| &a.subject == subject)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "
+        # plafond et interdictions
+        limit spend amount 100 per day
+        deny mutate if module in security, guardian, alignment
+        require-approval deploy if network == mainnet
+    ";
+
+    #[test]
+    fn parses_policy_dsl() {
+        let policy = Policy::parse(POLICY).unwrap();
+        assert_eq!(policy.rules.len(), 3);
+        assert_eq!(policy.rules[0].1, PolicyRule::DailyLimit { action: "spend".into(), attribute: "amount".into(), max: 100.0 });
+        assert!(Policy::parse("allow everything").is_err());
+        assert!(Policy::parse("deny spend if amount > beaucoup").unwrap_err().contains("ligne 1"));
+    }
+
+    #[test]
+    fn gates_actions_and_logs_violations() {
+        let mut alignment = AlignmentSystem { policy: Policy::parse(POLICY).unwrap(), ..AlignmentSystem::default() };
+        let spend = |amount: &str| ProposedAction::new("spend").with("amount", amount);
+
+        assert_eq!(alignment.gate(Uuid::new_v4(), &spend("60")), PolicyDecision::Allow);
+        assert!(matches!(alignment.gate(Uuid::new_v4(), &spend("60")), PolicyDecision::Veto { .. }));
+        assert_eq!(alignment.gate(Uuid::new_v4(), &spend("40")), PolicyDecision::Allow);
+
+        let mutate = |module: &str| ProposedAction::new("mutate").with("module", module);
+        assert!(matches!(alignment.gate(Uuid::new_v4(), &mutate("guardian")), PolicyDecision::Veto { .. }));
+        assert_eq!(alignment.gate(Uuid::new_v4(), &mutate("dream")), PolicyDecision::Allow);
+
+        let deploy = ProposedAction::new("deploy").with("network", "mainnet");
+        let subject = Uuid::new_v4();
+        assert!(matches!(alignment.gate(subject, &deploy), PolicyDecision::RequiresApproval { .. }));
+        alignment.register_operator_hash(&AlignmentSystem::token_hash("secret"));
+        alignment.approve(subject, "secret").unwrap();
+        assert_eq!(alignment.gate(subject, &deploy), PolicyDecision::Allow);
+
+        assert_eq!(alignment.violations.len(), 3);
+    }
+
+    #[test]
+    fn limits_charge_executed_actions_only_and_reject_invalid_amounts() {
+        let mut alignment = AlignmentSystem { policy: Policy::parse(POLICY).unwrap(), ..AlignmentSystem::default() };
+        let spend = |amount: &str| ProposedAction::new("spend").with("amount", amount);
+
+        // Évaluée plusieurs fois (attente d'approbation puis reprise), la dépense n'est imputée qu'une fois
+        for _ in 0..3 {
+            assert_eq!(alignment.evaluate(Uuid::new_v4(), &spend("60")), PolicyDecision::Allow);
+        }
+        alignment.charge(&spend("60"));
+        assert!(matches!(alignment.evaluate(Uuid::new_v4(), &spend("60")), PolicyDecision::Veto { .. }));
+        assert_eq!(alignment.evaluate(Uuid::new_v4(), &spend("40")), PolicyDecision::Allow);
+
+        for invalid in [spend("NaN"), spend("inf"), spend("-10"), spend("beaucoup"), ProposedAction::new("spend")] {
+            assert!(matches!(alignment.gate(Uuid::new_v4(), &invalid), PolicyDecision::Veto { .. }));
+        }
+        assert_eq!(alignment.gate(Uuid::new_v4(), &spend("40")), PolicyDecision::Allow);
+        assert!(matches!(alignment.gate(Uuid::new_v4(), &spend("1")), PolicyDecision::Veto { .. }));
+    }
+
+    #[test]
+    fn missing_or_invalid_policy_fails_closed() {
+        let dir = std::env::temp_dir().join(format!("aurorae-alignment-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let invalid = dir.join("alignment.policy");
+        std::fs::write(&invalid, "allow everything").unwrap();
+
+        let spend = ProposedAction::new("spend").with("amount", "1");
+        for path in [dir.join("absente.policy"), invalid.clone()] {
+            let mut alignment = AlignmentSystem::new();
+            alignment.load_policy(path.to_str().unwrap());
+            assert!(alignment.policy_error.is_some());
+            assert!(matches!(alignment.gate(Uuid::new_v4(), &spend), PolicyDecision::Veto { .. }));
+            assert_eq!(alignment.violations.len(), 1);
+        }
+
+        std::fs::write(&invalid, "limit spend amount 10 per day").unwrap();
+        let mut alignment = AlignmentSystem::new();
+        alignment.load_policy(invalid.to_str().unwrap());
+        assert_eq!(alignment.gate(Uuid::new_v4(), &spend), PolicyDecision::Allow);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
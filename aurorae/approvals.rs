@@ -0,0 +1,866 @@
+//! AURORAE++ - approvals.rs
+//!
+//! Approbation des retraits à plusieurs signatures: au-delà d'un seuil, un versement de
+//! founder_income ou une dépense budgétaire de l'économie exige N signatures parmi M
+//! approbateurs (messages EIP-191), ou un jeton d'approbation humaine vérifié par le
+//! module d'alignement, avant que les fonds ne bougent.
+//!
+//! Les actions à haut risque que la politique d'alignement soumet à approbation
+//! (`require-approval`: auto-mutation d'un module de sécurité, déploiement mainnet…)
+//! sont mises en attente dans la même file: la boucle principale poursuit son travail
+//! et reprend l'action une fois approuvée. Chaque étape est consignée dans un journal
+//! d'audit (`aurorae_state/approvals.json`).
+//!
+//! Le fichier d'état est partagé entre le démon et les commandes `--approve`, `--reject`
+//! et `--list-approvals`, lancées dans un autre processus: il est relu et fusionné avant
+//! chaque décision et avant chaque écriture, si bien qu'une signature apportée par la ligne
+//! de commande n'est jamais écrasée par la copie en mémoire du démon.
+//!
+//! Rien de ce qui est lu sur disque n'est cru sur parole: chaque signature est vérifiée
+//! contre le message de la demande et son signataire doit figurer parmi les approbateurs,
+//! l'état approuvé est recalculé depuis ce quorum vérifié. Une approbation par jeton
+//! d'opérateur n'est valable que dans le processus qui a vérifié le jeton.
+
+use std::collections::BTreeMap;
+use std::fs::{self, create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use ethers::types::{Address, Signature};
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::alignment::{AlignmentSystem, ProposedAction};
+
+pub const DEFAULT_APPROVALS_PATH: &str = "approvals.toml";
+/// Demandes, actions en attente et audit, partagés entre processus
+pub const APPROVALS_STATE_PATH: &str = "aurorae_state/approvals.json";
+
+lazy_static! {
+    pub static ref APPROVALS: RwLock<ApprovalRegistry> = RwLock::new(ApprovalRegistry::load(DEFAULT_APPROVALS_PATH));
+}
+
+/// Seuils et approbateurs (`approvals.toml`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ApprovalPolicy {
+    /// Versement (unités natives) au-delà duquel une approbation est requise
+    pub payout_threshold: f64,
+    /// Dépense budgétaire au-delà de laquelle une approbation est requise
+    pub spend_threshold: f64,
+    /// Nombre de signatures requises (N)
+    pub required: usize,
+    /// Approbateurs autorisés (M)
+    #[serde(default)]
+    pub approvers: Vec<Address>,
+    /// Durée de validité d'une demande
+    pub validity_hours: i64,
+}
+
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        Self {
+            payout_threshold: 0.5,
+            spend_threshold: 1_000.0,
+            // Sans approbateur configuré, seul un jeton d'opérateur (dans le processus) peut approuver
+            required: 1,
+            approvers: Vec::new(),
+            validity_hours: 72,
+        }
+    }
+}
+
+impl ApprovalPolicy {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let policy: Self = toml::from_str(content)
+            .map_err(|e| format!("Politique d'approbation invalide: {}", e))?;
+        if policy.required == 0 {
+            return Err("Au moins une signature doit être requise".to_string());
+        }
+        if !policy.approvers.is_empty() && policy.required > policy.approvers.len() {
+            return Err(format!("{} signatures requises pour {} approbateurs", policy.required, policy.approvers.len()));
+        }
+        Ok(policy)
+    }
+
+    /// Des signatures peuvent-elles atteindre le quorum ?
+    pub fn accepts_signatures(&self) -> bool {
+        self.required <= self.approvers.len()
+    }
+
+    pub fn threshold(&self, source: WithdrawalSource) -> f64 {
+        match source {
+            WithdrawalSource::Payout => self.payout_threshold,
+            WithdrawalSource::Spend => self.spend_threshold,
+        }
+    }
+}
+
+/// Origine d'un mouvement de fonds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WithdrawalSource {
+    /// Versement on-chain de founder_income (fondateur, écosystème, trésorerie)
+    Payout,
+    /// Dépense budgétaire de l'économie
+    Spend,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Executed,
+    Rejected,
+    Expired,
+}
+
+impl ApprovalStatus {
+    /// Avancement: un état n'est jamais remplacé par un état antérieur lors d'une fusion
+    fn rank(self) -> u8 {
+        match self {
+            Self::Pending => 0,
+            Self::Approved => 1,
+            Self::Executed | Self::Rejected | Self::Expired => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalSignature {
+    pub approver: Address,
+    pub signature: Signature,
+    pub signed_at: String,
+}
+
+/// Demande de retrait soumise à approbation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawalRequest {
+    pub id: Uuid,
+    pub source: WithdrawalSource,
+    pub description: String,
+    pub recipient: Option<Address>,
+    pub amount: f64,
+    pub asset: String,
+    pub created_at: String,
+    pub expires_at: DateTime<Utc>,
+    pub signatures: Vec<ApprovalSignature>,
+    pub human_approved: bool,
+    pub status: ApprovalStatus,
+}
+
+impl WithdrawalRequest {
+    /// Message signé par les approbateurs (`personal_sign`)
+    pub fn message(&self) -> String {
+        let recipient = self.recipient.map(|r| format!("{:?}", r)).unwrap_or_else(|| "interne".to_string());
+        format!("AURORAE++ approbation {}: {:.6} {} -> {}", self.id, self.amount, self.asset, recipient)
+    }
+}
+
+/// Action autonome à haut risque en attente d'approbation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionRequest {
+    pub id: Uuid,
+    /// Composant qui reprendra l'action une fois approuvée (`brain`, `reinforcement_learning`…)
+    pub origin: String,
+    pub kind: String,
+    pub attributes: BTreeMap<String, String>,
+    /// Règle `require-approval` à l'origine de la mise en attente
+    pub rule: String,
+    pub created_at: String,
+    pub expires_at: DateTime<Utc>,
+    pub signatures: Vec<ApprovalSignature>,
+    pub human_approved: bool,
+    pub status: ApprovalStatus,
+}
+
+impl ActionRequest {
+    /// Message signé par les approbateurs (`personal_sign`)
+    pub fn message(&self) -> String {
+        let attributes: Vec<String> = self.attributes.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        format!("AURORAE++ approbation action {}: {} [{}] ({})", self.id, self.kind, attributes.join(", "), self.rule)
+    }
+
+    /// Action à resoumettre à la politique d'alignement
+    pub fn proposed_action(&self) -> ProposedAction {
+        self.attributes.iter().fold(ProposedAction::new(&self.kind), |action, (k, v)| action.with(k, v))
+    }
+
+    /// Approbateurs retenus pour l'audit: signataires ou opérateur humain
+    pub fn approved_by(&self) -> String {
+        if self.human_approved {
+            return "opérateur".to_string();
+        }
+        let signers: Vec<String> = self.signatures.iter().map(|s| format!("{:?}", s.approver)).collect();
+        signers.join(",")
+    }
+}
+
+/// Entrée du journal d'audit des approbations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalAuditEntry {
+    pub at: String,
+    pub request: Uuid,
+    /// `opened`, `parked`, `signed`, `approved`, `rejected`, `executed`, `expired`
+    pub event: String,
+    /// Approbateur, empreinte d'opérateur ou composant à l'origine de l'événement
+    pub actor: String,
+}
+
+/// État d'approbation commun aux retraits et aux actions
+struct PendingApproval<'a> {
+    message: String,
+    signatures: &'a mut Vec<ApprovalSignature>,
+    human_approved: &'a mut bool,
+    status: &'a mut ApprovalStatus,
+}
+
+/// Résultat du contrôle d'un retrait
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApprovalCheck {
+    /// Sous le seuil: aucune approbation nécessaire
+    NotRequired,
+    /// Demande approuvée: seul le montant approuvé peut être retiré
+    Approved { id: Uuid, amount: f64 },
+    Pending(Uuid),
+}
+
+impl PendingApproval<'_> {
+    /// Fusionne l'état lu sur disque: seules les signatures vérifiées contre `message` et
+    /// émises par un approbateur sont retenues, l'approbation est recalculée depuis ce quorum.
+    /// Un état lu sur disque n'est repris que s'il clôt la demande sans rien autoriser.
+    fn merge(&mut self, signatures: Vec<ApprovalSignature>, status: ApprovalStatus, policy: &ApprovalPolicy) {
+        for signature in verified_signatures(&self.message, signatures, &policy.approvers) {
+            if !self.signatures.iter().any(|s| s.approver == signature.approver) {
+                self.signatures.push(signature);
+            }
+        }
+        if status != ApprovalStatus::Approved && status.rank() > self.status.rank() {
+            *self.status = status;
+        }
+        // Signatures apportées en parallèle par deux processus: le quorum peut être atteint à la fusion
+        if *self.status == ApprovalStatus::Pending && policy.accepts_signatures() && self.signatures.len() >= policy.required {
+            *self.status = ApprovalStatus::Approved;
+        }
+    }
+}
+
+/// Signatures dont le signataire recouvré sur `message` est l'approbateur déclaré et figure
+/// parmi `approvers`; un approbateur n'est compté qu'une fois
+fn verified_signatures(message: &str, signatures: Vec<ApprovalSignature>, approvers: &[Address]) -> Vec<ApprovalSignature> {
+    let mut verified: Vec<ApprovalSignature> = Vec::new();
+    for signature in signatures {
+        match signature.signature.recover(message) {
+            Ok(signer) if signer == signature.approver && approvers.contains(&signer) => {
+                if !verified.iter().any(|s| s.approver == signer) {
+                    verified.push(signature);
+                }
+            }
+            _ => warn!("⚠️ Signature de {:?} invalide ou non autorisée, ignorée", signature.approver),
+        }
+    }
+    verified
+}
+
+/// Demandes d'approbation; la politique est relue depuis `approvals.toml` au chargement
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApprovalRegistry {
+    #[serde(skip)]
+    pub policy: ApprovalPolicy,
+    /// Fichier d'état partagé; `None` pour un registre en mémoire
+    #[serde(skip)]
+    state_path: Option<PathBuf>,
+    pub requests: Vec<WithdrawalRequest>,
+    #[serde(default)]
+    pub actions: Vec<ActionRequest>,
+    #[serde(default)]
+    pub audit: Vec<ApprovalAuditEntry>,
+}
+
+impl ApprovalRegistry {
+    /// Registre en mémoire, sans fichier d'état
+    pub fn new(policy: ApprovalPolicy) -> Self {
+        Self { policy, state_path: None, requests: Vec::new(), actions: Vec::new(), audit: Vec::new() }
+    }
+
+    /// Attache le registre au fichier d'état `path` et fusionne ce qu'il contient déjà
+    pub fn with_state_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_path = Some(path.into());
+        self.refresh();
+        self
+    }
+
+    /// Relit le fichier d'état et y fusionne les décisions prises par un autre processus
+    pub fn refresh(&mut self) {
+        let Some(path) = &self.state_path else { return };
+        let Some(disk) = File::open(path).ok()
+            .and_then(|file| serde_json::from_reader::<_, Self>(BufReader::new(file)).ok())
+        else {
+            return;
+        };
+        let policy = self.policy.clone();
+        for mut theirs in disk.requests {
+            let signatures = std::mem::take(&mut theirs.signatures);
+            let status = theirs.status;
+            let ours = match self.requests.iter().position(|r| r.id == theirs.id) {
+                Some(index) => &mut self.requests[index],
+                None => {
+                    // Demande inconnue: ni approbation humaine ni état approuvé repris du disque
+                    theirs.human_approved = false;
+                    theirs.status = ApprovalStatus::Pending;
+                    self.requests.push(theirs);
+                    self.requests.last_mut().expect("demande ajoutée")
+                }
+            };
+            // Les signatures sont vérifiées contre notre copie: un montant modifié sur disque ne compte pas
+            PendingApproval {
+                message: ours.message(),
+                signatures: &mut ours.signatures,
+                human_approved: &mut ours.human_approved,
+                status: &mut ours.status,
+            }
+            .merge(signatures, status, &policy);
+        }
+        for mut theirs in disk.actions {
+            let signatures = std::mem::take(&mut theirs.signatures);
+            let status = theirs.status;
+            let ours = match self.actions.iter().position(|r| r.id == theirs.id) {
+                Some(index) => &mut self.actions[index],
+                None => {
+                    theirs.human_approved = false;
+                    theirs.status = ApprovalStatus::Pending;
+                    self.actions.push(theirs);
+                    self.actions.last_mut().expect("action ajoutée")
+                }
+            };
+            PendingApproval {
+                message: ours.message(),
+                signatures: &mut ours.signatures,
+                human_approved: &mut ours.human_approved,
+                status: &mut ours.status,
+            }
+            .merge(signatures, status, &policy);
+        }
+        let mut added = false;
+        for entry in disk.audit {
+            if !self.audit.contains(&entry) {
+                self.audit.push(entry);
+                added = true;
+            }
+        }
+        if added {
+            self.audit.sort_by(|a, b| a.at.cmp(&b.at));
+        }
+    }
+
+    /// Contrôle un retrait de `amount`: sous le seuil il est libre, sinon il faut une demande
+    /// approuvée pour le même bénéficiaire, le même actif et le même objet (ouverte ici si nécessaire)
+    pub fn authorize(
+        &mut self,
+        source: WithdrawalSource,
+        recipient: Option<Address>,
+        amount: f64,
+        asset: &str,
+        description: &str,
+    ) -> ApprovalCheck {
+        if amount <= self.policy.threshold(source) {
+            return ApprovalCheck::NotRequired;
+        }
+        match self.current(source, recipient, asset, description) {
+            Some(check) => check,
+            None => ApprovalCheck::Pending(self.open(Uuid::new_v4(), source, recipient, amount, asset, description)),
+        }
+    }
+
+    /// Demande encore ouverte (en attente ou approuvée) pour ce bénéficiaire, cet actif et cet
+    /// objet: une nouvelle tentative retrouve ainsi le même identifiant
+    pub fn current(
+        &mut self,
+        source: WithdrawalSource,
+        recipient: Option<Address>,
+        asset: &str,
+        description: &str,
+    ) -> Option<ApprovalCheck> {
+        self.refresh();
+        self.expire();
+        let request = self.requests.iter().find(|r| {
+            r.source == source && r.recipient == recipient && r.asset == asset && r.description == description
+                && matches!(r.status, ApprovalStatus::Pending | ApprovalStatus::Approved)
+        })?;
+        Some(match request.status {
+            ApprovalStatus::Approved => ApprovalCheck::Approved { id: request.id, amount: request.amount },
+            _ => ApprovalCheck::Pending(request.id),
+        })
+    }
+
+    /// Ouvre une demande d'identifiant donné (réutilise celle qui existe déjà)
+    pub fn open(
+        &mut self,
+        id: Uuid,
+        source: WithdrawalSource,
+        recipient: Option<Address>,
+        amount: f64,
+        asset: &str,
+        description: &str,
+    ) -> Uuid {
+        self.refresh();
+        if self.get(&id).is_some() {
+            return id;
+        }
+        let request = WithdrawalRequest {
+            id,
+            source,
+            description: description.to_string(),
+            recipient,
+            amount,
+            asset: asset.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            expires_at: Utc::now() + Duration::hours(self.policy.validity_hours),
+            signatures: Vec::new(),
+            human_approved: false,
+            status: ApprovalStatus::Pending,
+        };
+        info!(
+            "✍️ Approbation requise ({} sur {}) pour {}: {:.6} {} [{}]",
+            self.policy.required, self.policy.approvers.len(), description, amount, asset, id
+        );
+        info!("📝 Message à signer: {}", request.message());
+        self.requests.push(request);
+        self.record(id, "opened", description);
+        self.save();
+        id
+    }
+
+    /// Met en attente une action refusée faute d'approbation; une action identique déjà en
+    /// attente pour `origin` est réutilisée. L'appelant poursuit son travail sans bloquer.
+    pub fn park_action(&mut self, origin: &str, action: &ProposedAction, rule: &str) -> Uuid {
+        self.refresh();
+        self.expire();
+        let attributes: BTreeMap<String, String> = action.attributes.clone().into_iter().collect();
+        let existing = self.actions.iter().find(|r| {
+            r.origin == origin && r.kind == action.kind && r.attributes == attributes
+                && matches!(r.status, ApprovalStatus::Pending | ApprovalStatus::Approved)
+        });
+        if let Some(request) = existing {
+            return request.id;
+        }
+        let request = ActionRequest {
+            id: Uuid::new_v4(),
+            origin: origin.to_string(),
+            kind: action.kind.clone(),
+            attributes,
+            rule: rule.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            expires_at: Utc::now() + Duration::hours(self.policy.validity_hours),
+            signatures: Vec::new(),
+            human_approved: false,
+            status: ApprovalStatus::Pending,
+        };
+        let id = request.id;
+        info!("⏸️ Action {} de {} en attente d'approbation ({}) [{}]", request.kind, origin, rule, id);
+        info!("📝 Message à signer: {}", request.message());
+        self.actions.push(request);
+        self.record(id, "parked", origin);
+        self.save();
+        id
+    }
+
+    pub fn get(&self, id: &Uuid) -> Option<&WithdrawalRequest> {
+        self.requests.iter().find(|r| &r.id == id)
+    }
+
+    pub fn action(&self, id: &Uuid) -> Option<&ActionRequest> {
+        self.actions.iter().find(|r| &r.id == id)
+    }
+
+    /// État connu en mémoire; appeler `refresh` d'abord pour voir les signatures récentes
+    pub fn is_approved(&self, id: &Uuid) -> bool {
+        self.get(id).map(|r| r.status == ApprovalStatus::Approved)
+            .or_else(|| self.action(id).map(|r| r.status == ApprovalStatus::Approved))
+            .unwrap_or(false)
+    }
+
+    /// Demandes en attente (retraits et actions) avec le message à signer
+    pub fn pending(&mut self) -> Vec<(Uuid, String)> {
+        self.refresh();
+        self.expire();
+        let withdrawals = self.requests.iter()
+            .filter(|r| r.status == ApprovalStatus::Pending)
+            .map(|r| (r.id, r.message()));
+        let actions = self.actions.iter()
+            .filter(|r| r.status == ApprovalStatus::Pending)
+            .map(|r| (r.id, r.message()));
+        withdrawals.chain(actions).collect()
+    }
+
+    /// Actions approuvées pour `origin`, marquées exécutées: chacune n'est reprise qu'une fois
+    pub fn take_approved_actions(&mut self, origin: &str) -> Vec<ActionRequest> {
+        self.refresh();
+        self.expire();
+        let mut taken = Vec::new();
+        for request in self.actions.iter_mut().filter(|r| r.origin == origin && r.status == ApprovalStatus::Approved) {
+            request.status = ApprovalStatus::Executed;
+            taken.push(request.clone());
+        }
+        if !taken.is_empty() {
+            for request in &taken {
+                self.record(request.id, "executed", origin);
+            }
+            self.save();
+        }
+        taken
+    }
+
+    /// Ajoute la signature d'un approbateur; la demande est approuvée à la N-ième signature
+    pub fn add_signature(&mut self, id: &Uuid, signature: Signature) -> Result<ApprovalStatus, String> {
+        let required = self.policy.required;
+        let approvers = self.policy.approvers.clone();
+        if !self.policy.accepts_signatures() {
+            return Err(format!(
+                "{} signatures requises pour {} approbateur(s): déclarer les approbateurs dans {}",
+                required, approvers.len(), DEFAULT_APPROVALS_PATH
+            ));
+        }
+        let request = self.pending_mut(id)?;
+
+        let approver = signature.recover(request.message.as_str())
+            .map_err(|e| format!("Signature illisible: {}", e))?;
+        if !approvers.contains(&approver) {
+            return Err(format!("{:?} n'est pas approbateur", approver));
+        }
+        if request.signatures.iter().any(|s| s.approver == approver) {
+            return Err(format!("{:?} a déjà signé", approver));
+        }
+        request.signatures.push(ApprovalSignature { approver, signature, signed_at: Utc::now().to_rfc3339() });
+        info!("✍️ Signature {}/{} de {:?} pour {}", request.signatures.len(), required, approver, id);
+        if request.signatures.len() >= required {
+            *request.status = ApprovalStatus::Approved;
+            info!("✅ Demande {} approuvée par {} signatures", id, required);
+        }
+        let status = *request.status;
+        self.record(*id, "signed", &format!("{:?}", approver));
+        if status == ApprovalStatus::Approved {
+            self.record(*id, "approved", "signatures");
+        }
+        self.save();
+        Ok(status)
+    }
+
+    /// Approbation par jeton humain, vérifiée par le module d'alignement: elle n'est pas
+    /// vérifiable depuis le fichier d'état et ne vaut que pour ce processus
+    pub fn approve_with_token(&mut self, id: &Uuid, token: &str, alignment: &mut AlignmentSystem) -> Result<(), String> {
+        self.pending_mut(id)?;
+        let approval = alignment.approve(*id, token)?;
+        let request = self.pending_mut(id)?;
+        *request.human_approved = true;
+        *request.status = ApprovalStatus::Approved;
+        info!("✅ Demande {} approuvée par un opérateur humain", id);
+        self.record(*id, "approved", &approval.operator);
+        self.save();
+        Ok(())
+    }
+
+    /// Rejet d'une demande en attente; `actor` est consigné dans l'audit
+    pub fn reject(&mut self, id: &Uuid, actor: &str) -> Result<(), String> {
+        let request = self.pending_mut(id)?;
+        *request.status = ApprovalStatus::Rejected;
+        info!("🚫 Demande {} rejetée par {}", id, actor);
+        self.record(*id, "rejected", actor);
+        self.save();
+        Ok(())
+    }
+
+    /// Marque une demande approuvée comme exécutée: elle ne peut servir qu'une fois
+    pub fn mark_executed(&mut self, id: &Uuid) {
+        if let Some(request) = self.requests.iter_mut().find(|r| &r.id == id && r.status == ApprovalStatus::Approved) {
+            request.status = ApprovalStatus::Executed;
+            self.record(*id, "executed", "retrait");
+            self.save();
+        }
+    }
+
+    fn pending_mut(&mut self, id: &Uuid) -> Result<PendingApproval<'_>, String> {
+        self.refresh();
+        self.expire();
+        let request = match self.requests.iter_mut().find(|r| &r.id == id) {
+            Some(r) => PendingApproval {
+                message: r.message(),
+                signatures: &mut r.signatures,
+                human_approved: &mut r.human_approved,
+                status: &mut r.status,
+            },
+            None => {
+                let r = self.actions.iter_mut().find(|r| &r.id == id)
+                    .ok_or_else(|| format!("Demande d'approbation {} introuvable", id))?;
+                PendingApproval {
+                    message: r.message(),
+                    signatures: &mut r.signatures,
+                    human_approved: &mut r.human_approved,
+                    status: &mut r.status,
+                }
+            }
+        };
+        if *request.status != ApprovalStatus::Pending {
+            return Err(format!("Demande {} déjà close ({:?})", id, request.status));
+        }
+        Ok(request)
+    }
+
+    fn expire(&mut self) {
+        let now = Utc::now();
+        let mut expired = Vec::new();
+        let withdrawals = self.requests.iter_mut().map(|r| (r.id, r.expires_at, &mut r.status));
+        let actions = self.actions.iter_mut().map(|r| (r.id, r.expires_at, &mut r.status));
+        for (id, expires_at, status) in withdrawals.chain(actions) {
+            if matches!(*status, ApprovalStatus::Pending | ApprovalStatus::Approved) && expires_at < now {
+                *status = ApprovalStatus::Expired;
+                expired.push(id);
+            }
+        }
+        for id in expired {
+            self.record(id, "expired", "système");
+        }
+    }
+
+    fn record(&mut self, request: Uuid, event: &str, actor: &str) {
+        self.audit.push(ApprovalAuditEntry {
+            at: Utc::now().to_rfc3339(),
+            request,
+            event: event.to_string(),
+            actor: actor.to_string(),
+        });
+    }
+
+    /// 💾 Sauvegarde sur disque, après fusion des écritures d'un autre processus; le fichier
+    /// est remplacé d'un bloc (écriture temporaire puis renommage)
+    pub fn save(&mut self) {
+        self.refresh();
+        let Some(path) = self.state_path.clone() else { return };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            if create_dir_all(dir).is_err() {
+                return;
+            }
+        }
+        let temporary = path.with_extension(format!("json.{}", Uuid::new_v4().simple()));
+        let written = File::create(&temporary)
+            .map_err(|e| e.to_string())
+            .and_then(|file| serde_json::to_writer_pretty(BufWriter::new(file), &self).map_err(|e| e.to_string()))
+            .and_then(|_| fs::rename(&temporary, &path).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            warn!("⚠️ Approbations non sauvegardées: {}", e);
+            let _ = fs::remove_file(&temporary);
+        }
+    }
+
+    /// 📥 Chargement des demandes depuis `APPROVALS_STATE_PATH` et de la politique depuis `policy_path`
+    pub fn load(policy_path: &str) -> Self {
+        Self::load_from(policy_path, Path::new(APPROVALS_STATE_PATH))
+    }
+
+    pub fn load_from(policy_path: &str, state_path: &Path) -> Self {
+        let policy = ApprovalPolicy::load(policy_path).unwrap_or_else(|e| {
+            warn!("⚠️ {}: politique d'approbation par défaut", e);
+            ApprovalPolicy::default()
+        });
+        if !policy.accepts_signatures() {
+            warn!(
+                "⚠️ {} signature(s) requise(s) pour {} approbateur(s): seules les approbations par jeton d'opérateur dans ce processus aboutiront",
+                policy.required, policy.approvers.len()
+            );
+        }
+        Self::new(policy).with_state_path(state_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::{LocalWallet, Signer};
+
+    #[tokio::test]
+    async fn withdrawal_needs_n_of_m_signatures() {
+        let wallets: Vec<LocalWallet> = (0..3).map(|_| LocalWallet::new(&mut rand::thread_rng())).collect();
+        let policy = ApprovalPolicy {
+            approvers: wallets.iter().map(|w| w.address()).collect(),
+            required: 2,
+            ..ApprovalPolicy::default()
+        };
+        let mut registry = ApprovalRegistry::new(policy);
+        let founder = Some(Address::repeat_byte(9));
+
+        assert_eq!(registry.authorize(WithdrawalSource::Payout, founder, 0.1, "ETH", "fondateur"), ApprovalCheck::NotRequired);
+        let ApprovalCheck::Pending(id) = registry.authorize(WithdrawalSource::Payout, founder, 2.0, "ETH", "fondateur") else {
+            panic!("approbation attendue");
+        };
+
+        let message = registry.get(&id).unwrap().message();
+        let first = wallets[0].sign_message(&message).await.unwrap();
+        assert_eq!(registry.add_signature(&id, first).unwrap(), ApprovalStatus::Pending);
+        assert!(registry.add_signature(&id, first).is_err());
+        let outsider = LocalWallet::new(&mut rand::thread_rng()).sign_message(&message).await.unwrap();
+        assert!(registry.add_signature(&id, outsider).is_err());
+        let second = wallets[2].sign_message(&message).await.unwrap();
+        assert_eq!(registry.add_signature(&id, second).unwrap(), ApprovalStatus::Approved);
+
+        assert_eq!(
+            registry.authorize(WithdrawalSource::Payout, founder, 2.5, "ETH", "fondateur"),
+            ApprovalCheck::Approved { id, amount: 2.0 }
+        );
+    }
+
+    #[tokio::test]
+    async fn parked_actions_resume_once_approved() {
+        let wallets: Vec<LocalWallet> = (0..2).map(|_| LocalWallet::new(&mut rand::thread_rng())).collect();
+        let policy = ApprovalPolicy {
+            approvers: wallets.iter().map(|w| w.address()).collect(),
+            required: 2,
+            ..ApprovalPolicy::default()
+        };
+        let mut registry = ApprovalRegistry::new(policy);
+        let deploy = ProposedAction::new("deploy").with("network", "mainnet");
+
+        let id = registry.park_action("brain", &deploy, "require-approval deploy if network == mainnet");
+        assert_eq!(registry.park_action("brain", &deploy, "require-approval deploy if network == mainnet"), id);
+        assert_eq!(registry.pending().len(), 1);
+        assert!(registry.take_approved_actions("brain").is_empty());
+
+        let message = registry.action(&id).unwrap().message();
+        for wallet in &wallets {
+            registry.add_signature(&id, wallet.sign_message(&message).await.unwrap()).unwrap();
+        }
+        assert!(registry.take_approved_actions("reinforcement_learning").is_empty());
+        let resumed = registry.take_approved_actions("brain");
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].proposed_action(), deploy);
+        assert!(registry.take_approved_actions("brain").is_empty());
+
+        let events: Vec<&str> = registry.audit.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(events, ["parked", "signed", "signed", "approved", "executed"]);
+
+        let other = registry.park_action("brain", &ProposedAction::new("mutate").with("module", "guardian"), "require-approval mutate");
+        registry.reject(&other, "test").unwrap();
+        assert!(registry.reject(&other, "test").is_err());
+        assert!(registry.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn default_policy_approves_by_operator_token_only() {
+        let mut registry = ApprovalRegistry::new(ApprovalPolicy::default());
+        assert!(!registry.policy.accepts_signatures());
+        let ApprovalCheck::Pending(id) = registry.authorize(WithdrawalSource::Spend, None, 5_000.0, "AURA", "dépense") else {
+            panic!("approbation attendue");
+        };
+        let message = registry.get(&id).unwrap().message();
+        let signature = LocalWallet::new(&mut rand::thread_rng()).sign_message(&message).await.unwrap();
+        assert!(registry.add_signature(&id, signature).unwrap_err().contains(DEFAULT_APPROVALS_PATH));
+
+        let mut alignment = AlignmentSystem::new();
+        alignment.register_operator_hash(&AlignmentSystem::token_hash("secret-opérateur"));
+        assert!(registry.approve_with_token(&id, "mauvais", &mut alignment).is_err());
+        registry.approve_with_token(&id, "secret-opérateur", &mut alignment).unwrap();
+        assert!(registry.is_approved(&id));
+    }
+
+    #[tokio::test]
+    async fn decisions_from_another_process_reach_the_daemon() {
+        let path = std::env::temp_dir().join(format!("aurorae-approvals-{}.json", Uuid::new_v4()));
+        let wallets: Vec<LocalWallet> = (0..2).map(|_| LocalWallet::new(&mut rand::thread_rng())).collect();
+        let policy = ApprovalPolicy {
+            approvers: wallets.iter().map(|w| w.address()).collect(),
+            required: 2,
+            ..ApprovalPolicy::default()
+        };
+        let mut daemon = ApprovalRegistry::new(policy.clone()).with_state_path(&path);
+        let deploy = ProposedAction::new("deploy").with("network", "mainnet");
+        let id = daemon.park_action("brain", &deploy, "require-approval deploy if network == mainnet");
+        let refused = daemon.park_action("brain", &ProposedAction::new("mutate").with("module", "guardian"), "require-approval mutate");
+
+        // --list-approvals, --approve et --reject: autant de processus, chacun avec son registre
+        let mut cli = ApprovalRegistry::new(policy.clone()).with_state_path(&path);
+        assert_eq!(cli.pending().len(), 2);
+        let message = cli.action(&id).unwrap().message();
+        assert_eq!(cli.add_signature(&id, wallets[0].sign_message(&message).await.unwrap()).unwrap(), ApprovalStatus::Pending);
+        let mut other_cli = ApprovalRegistry::new(policy.clone()).with_state_path(&path);
+        assert_eq!(other_cli.add_signature(&id, wallets[1].sign_message(&message).await.unwrap()).unwrap(), ApprovalStatus::Approved);
+        cli.reject(&refused, "cli").unwrap();
+
+        // La copie périmée du démon n'écrase rien et l'action approuvée est reprise
+        daemon.save();
+        let resumed = daemon.take_approved_actions("brain");
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].id, id);
+        assert_eq!(resumed[0].signatures.len(), 2);
+        assert_eq!(daemon.action(&refused).unwrap().status, ApprovalStatus::Rejected);
+
+        let reloaded = ApprovalRegistry::new(policy).with_state_path(&path);
+        assert_eq!(reloaded.action(&id).unwrap().status, ApprovalStatus::Executed);
+        assert!(reloaded.audit.iter().any(|e| e.request == refused && e.event == "rejected" && e.actor == "cli"));
+        assert_eq!(reloaded.audit.iter().filter(|e| e.request == id && e.event == "signed").count(), 2);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn tampered_state_file_does_not_authorize() {
+        let path = std::env::temp_dir().join(format!("aurorae-approvals-{}.json", Uuid::new_v4()));
+        let wallets: Vec<LocalWallet> = (0..2).map(|_| LocalWallet::new(&mut rand::thread_rng())).collect();
+        let policy = ApprovalPolicy {
+            approvers: wallets.iter().map(|w| w.address()).collect(),
+            required: 2,
+            ..ApprovalPolicy::default()
+        };
+        let founder = Some(Address::repeat_byte(9));
+        let mut daemon = ApprovalRegistry::new(policy.clone()).with_state_path(&path);
+        let ApprovalCheck::Pending(id) = daemon.authorize(WithdrawalSource::Payout, founder, 2.0, "ETH", "fondateur") else {
+            panic!("approbation attendue");
+        };
+        let deploy = ProposedAction::new("deploy").with("network", "mainnet");
+        let action = daemon.park_action("brain", &deploy, "require-approval deploy if network == mainnet");
+
+        // Réécriture du fichier: états approuvés, approbation humaine, signature d'un intrus
+        // se réclamant d'un approbateur, signature d'un approbateur sur un autre montant
+        let mut forged: ApprovalRegistry = serde_json::from_reader(File::open(&path).unwrap()).unwrap();
+        let outsider = LocalWallet::new(&mut rand::thread_rng());
+        let message = forged.requests[0].message();
+        forged.requests[0].signatures.push(ApprovalSignature {
+            approver: wallets[0].address(),
+            signature: outsider.sign_message(&message).await.unwrap(),
+            signed_at: Utc::now().to_rfc3339(),
+        });
+        forged.requests[0].amount = 200.0;
+        let inflated = forged.requests[0].message();
+        forged.requests[0].signatures.push(ApprovalSignature {
+            approver: wallets[1].address(),
+            signature: wallets[1].sign_message(&inflated).await.unwrap(),
+            signed_at: Utc::now().to_rfc3339(),
+        });
+        for request in forged.requests.iter_mut() {
+            request.status = ApprovalStatus::Approved;
+            request.human_approved = true;
+        }
+        for request in forged.actions.iter_mut() {
+            request.status = ApprovalStatus::Approved;
+            request.human_approved = true;
+        }
+        serde_json::to_writer(File::create(&path).unwrap(), &forged).unwrap();
+
+        assert_eq!(daemon.authorize(WithdrawalSource::Payout, founder, 2.0, "ETH", "fondateur"), ApprovalCheck::Pending(id));
+        assert!(daemon.take_approved_actions("brain").is_empty());
+        assert!(daemon.get(&id).unwrap().signatures.is_empty());
+
+        let mut restarted = ApprovalRegistry::new(policy).with_state_path(&path);
+        assert!(!restarted.is_approved(&id));
+        assert!(!restarted.is_approved(&action));
+        assert!(!restarted.get(&id).unwrap().human_approved);
+        assert_eq!(restarted.authorize(WithdrawalSource::Payout, founder, 2.0, "ETH", "fondateur"), ApprovalCheck::Pending(id));
+        let _ = fs::remove_file(&path);
+    }
+}
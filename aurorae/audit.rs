@@ -0,0 +1,812 @@
+//! AURORAE++ - audit.rs
+//!
+//! Journal d'audit en ajout seul des décisions autonomes: pensée traitée, fonds déplacés,
+//! code muté, instance engendrée, code généré. Chaque entrée (une ligne JSON de `aurorae_state/audit.log`)
+//! porte l'empreinte SHA-256 de la précédente: toute réécriture ou suppression casse la
+//! chaîne et est détectée par `verify`. La tête de chaîne est signée périodiquement par la clé
+//! du système (`audit.head`): une troncature de la fin du journal est ainsi détectée même par
+//! un processus qui ne l'a pas écrit. Le journal s'interroge et s'exporte en JSONL ou CSV.
+//!
+//! Un ajout ne relit que la dernière ligne du journal, et seulement si un autre processus a
+//! écrit depuis: la tête et la taille du fichier sont gardées en mémoire. Le verrou entre
+//! processus n'est jamais attendu: une entrée qui le trouve pris est différée puis écrite,
+//! dans l'ordre, au prochain ajout. Au-delà de `DEFAULT_ROTATE_BYTES`, le journal est archivé
+//! (`audit.<séquence>.log`) et un point de contrôle (`audit.checkpoint`) relie le nouveau
+//! segment au précédent.
+
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+use chrono::{DateTime, Utc};
+use ethers::types::{Address, Signature};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::keystore::Signer;
+
+pub const AUDIT_LOG_PATH: &str = "aurorae_state/audit.log";
+/// Empreinte précédant la première entrée
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+/// Adresse attendue du signataire de la tête; sans elle, seule la cohérence de la signature est vérifiée
+pub const AUDIT_SIGNER_ENV: &str = "AURORAE_AUDIT_SIGNER";
+/// Intervalle (en cycles) entre deux signatures de la tête par le démon
+pub const AUDIT_HEAD_INTERVAL_CYCLES: usize = 10;
+/// Verrou d'écriture plus vieux que ce délai: laissé par un processus interrompu
+const STALE_LOCK_AFTER: Duration = Duration::from_secs(30);
+/// Taille au-delà de laquelle le segment courant est archivé
+pub const DEFAULT_ROTATE_BYTES: u64 = 64 * 1024 * 1024;
+
+lazy_static! {
+    pub static ref AUDIT: Mutex<AuditLog> = Mutex::new(AuditLog::open(global_log_path()));
+}
+
+/// Les tests écrivent dans un journal temporaire, jamais dans celui du système
+fn global_log_path() -> PathBuf {
+    if cfg!(test) {
+        std::env::temp_dir().join(format!("aurorae-audit-{}", std::process::id())).join("audit.log")
+    } else {
+        PathBuf::from(AUDIT_LOG_PATH)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditKind {
+    ThoughtProcessed,
+    FundsMoved,
+    CodeMutated,
+    InstanceSpawned,
+    CodeGenerated,
+    InstanceCulled,
+}
+
+impl AuditKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "thought" | "thoughtprocessed" => Some(Self::ThoughtProcessed),
+            "funds" | "fundsmoved" => Some(Self::FundsMoved),
+            "mutation" | "codemutated" => Some(Self::CodeMutated),
+            "spawn" | "instancespawned" => Some(Self::InstanceSpawned),
+            "generation" | "codegenerated" => Some(Self::CodeGenerated),
+            "cull" | "instanceculled" => Some(Self::InstanceCulled),
+            _ => None,
+        }
+    }
+}
+
+/// Entrée chaînée du journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub sequence: u64,
+    pub at: DateTime<Utc>,
+    pub kind: AuditKind,
+    pub component: String,
+    pub summary: String,
+    pub details: Value,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Empreinte de l'entrée, calculée sur tous ses champs sauf `hash`
+    pub fn compute_hash(&self) -> String {
+        let content = serde_json::to_string(&(
+            self.sequence,
+            self.at.to_rfc3339(),
+            self.kind,
+            &self.component,
+            &self.summary,
+            &self.details,
+            &self.prev_hash,
+        ))
+        .unwrap_or_default();
+        hex::encode(Sha256::digest(content.as_bytes()))
+    }
+}
+
+/// Tête de chaîne signée EIP-191, enregistrée à côté du journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditHead {
+    pub sequence: u64,
+    pub hash: String,
+    pub signed_at: DateTime<Utc>,
+    pub signer: Option<Address>,
+    pub signature: Option<String>,
+}
+
+impl AuditHead {
+    /// Message canonique signé
+    pub fn canonical_message(&self) -> String {
+        format!("AURORAE++ tête d'audit\n{}\n{}\n{}\n", self.sequence, self.hash, self.signed_at.to_rfc3339())
+    }
+
+    pub async fn sign(&mut self, signer: &dyn Signer) -> Result<(), String> {
+        let signature = signer.sign_message(self.canonical_message().as_bytes()).await?;
+        self.signer = Some(signer.address());
+        self.signature = Some(format!("0x{}", signature));
+        Ok(())
+    }
+
+    /// Vérifie la signature; si `trusted` est fourni, le signataire doit lui correspondre
+    pub fn verify_signature(&self, trusted: Option<Address>) -> Result<Address, String> {
+        let signature = self.signature.as_ref().ok_or_else(|| "Tête d'audit non signée".to_string())?;
+        let signature = signature.parse::<Signature>()
+            .map_err(|e| format!("Signature de la tête d'audit illisible: {}", e))?;
+        let recovered = signature.recover(self.canonical_message())
+            .map_err(|e| format!("Signature de la tête d'audit invalide: {}", e))?;
+        if self.signer != Some(recovered) {
+            return Err(format!("Tête d'audit signée par {:?}, pas par le signataire déclaré", recovered));
+        }
+        match trusted {
+            Some(trusted) if trusted != recovered => {
+                Err(format!("Tête d'audit signée par {:?} au lieu de {:?}", recovered, trusted))
+            }
+            _ => Ok(recovered),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map(Some)
+                .map_err(|e| format!("Tête d'audit illisible: {}", e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Erreur lecture {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Écriture atomique (fichier temporaire puis renommage)
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let tmp = path.with_extension("head.tmp");
+        let content = serde_json::to_string_pretty(self).map_err(|e| format!("Tête d'audit invalide: {}", e))?;
+        std::fs::write(&tmp, content)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))
+    }
+}
+
+/// Verrou inter-processus des ajouts (le démon et la CLI écrivent le même journal)
+struct AppendLock(PathBuf);
+
+impl AppendLock {
+    /// Prend le verrou sans attendre: `None` s'il est tenu par un autre processus
+    fn try_acquire(log: &Path) -> Result<Option<Self>, String> {
+        let path = log.with_extension("lock");
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).map_err(|e| format!("Erreur création {}: {}", dir.display(), e))?;
+        }
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Some(Self(path))),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let stale = std::fs::metadata(&path).and_then(|m| m.modified()).ok()
+                        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                        .map(|age| age > STALE_LOCK_AFTER)
+                        .unwrap_or(false);
+                    if !stale {
+                        return Ok(None);
+                    }
+                    warn!("⚠️ Verrou d'audit abandonné supprimé: {}", path.display());
+                    let _ = std::fs::remove_file(&path);
+                }
+                Err(e) => return Err(format!("Erreur verrou {}: {}", path.display(), e)),
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Drop for AppendLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Filtre d'interrogation; un champ absent ne filtre pas
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub kind: Option<AuditKind>,
+    pub component: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Sous-chaîne recherchée dans le résumé
+    pub text: Option<String>,
+}
+
+impl AuditQuery {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.kind.map(|k| k == entry.kind).unwrap_or(true)
+            && self.component.as_ref().map(|c| c == &entry.component).unwrap_or(true)
+            && self.since.map(|since| entry.at >= since).unwrap_or(true)
+            && self.until.map(|until| entry.at <= until).unwrap_or(true)
+            && self.text.as_ref().map(|t| entry.summary.contains(t.as_str())).unwrap_or(true)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Jsonl,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => Self::Csv,
+            _ => Self::Jsonl,
+        }
+    }
+}
+
+/// Dernière entrée d'un segment archivé: le segment courant s'y enchaîne
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditCheckpoint {
+    pub sequence: u64,
+    pub hash: String,
+    /// Segment archivé qui se termine par cette entrée
+    pub archive: PathBuf,
+    pub rotated_at: DateTime<Utc>,
+}
+
+impl AuditCheckpoint {
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map(Some)
+                .map_err(|e| format!("Point de contrôle d'audit illisible: {}", e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Erreur lecture {}: {}", path.display(), e)),
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        let tmp = path.with_extension("checkpoint.tmp");
+        let content = serde_json::to_string_pretty(self).map_err(|e| format!("Point de contrôle invalide: {}", e))?;
+        std::fs::write(&tmp, content)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))
+    }
+
+    /// L'entrée `sequence` d'empreinte `hash` est-elle présente, dans le segment courant
+    /// (`entries`) ou archivée ? Une entrée antérieure au point de contrôle est réputée archivée.
+    fn holds(checkpoint: Option<&Self>, entries: &[AuditEntry], sequence: u64, hash: &str) -> bool {
+        let first = checkpoint.map(|c| c.sequence + 1).unwrap_or(0);
+        if sequence >= first {
+            return entries.get((sequence - first) as usize).map(|e| e.hash == hash).unwrap_or(false);
+        }
+        checkpoint.map(|c| c.sequence != sequence || c.hash == hash).unwrap_or(false)
+    }
+}
+
+/// Entrée dont l'écriture attend le verrou
+#[derive(Debug)]
+struct DeferredEntry {
+    at: DateTime<Utc>,
+    kind: AuditKind,
+    component: String,
+    summary: String,
+    details: Value,
+}
+
+/// Journal sur disque; la tête et la taille du segment vues par ce processus sont gardées en
+/// mémoire: elles détectent une troncature et évitent de relire le journal à chaque ajout
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    head: Option<(u64, String)>,
+    /// Taille du segment après la dernière écriture (ou lecture) de ce processus
+    offset: u64,
+    /// Date de modification correspondante: distingue un segment archivé puis réécrit
+    modified: Option<SystemTime>,
+    rotate_bytes: u64,
+    deferred: Vec<DeferredEntry>,
+}
+
+impl AuditLog {
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let metadata = std::fs::metadata(&path).ok();
+        let offset = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+        let modified = metadata.and_then(|m| m.modified().ok());
+        let head = Self::entry_before(&path, offset).ok().flatten()
+            .map(|e| (e.sequence, e.hash))
+            .or_else(|| {
+                AuditCheckpoint::load(&path.with_extension("checkpoint")).ok().flatten()
+                    .map(|c| (c.sequence, c.hash))
+            });
+        Self { path, head, offset, modified, rotate_bytes: DEFAULT_ROTATE_BYTES, deferred: Vec::new() }
+    }
+
+    /// Taille au-delà de laquelle le segment courant est archivé
+    pub fn with_rotate_bytes(mut self, bytes: u64) -> Self {
+        self.rotate_bytes = bytes.max(1);
+        self
+    }
+
+    /// Point de contrôle du dernier archivage, à côté du journal
+    pub fn checkpoint_path(&self) -> PathBuf {
+        self.path.with_extension("checkpoint")
+    }
+
+    /// Fichier de la tête signée, à côté du journal
+    pub fn head_path(&self) -> PathBuf {
+        self.path.with_extension("head")
+    }
+
+    /// Tête actuelle sur disque, prête à signer
+    pub fn current_head(&self) -> Result<Option<AuditHead>, String> {
+        let len = std::fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        let last = match Self::entry_before(&self.path, len)? {
+            Some(entry) => Some((entry.sequence, entry.hash)),
+            None => AuditCheckpoint::load(&self.checkpoint_path())?.map(|c| (c.sequence, c.hash)),
+        };
+        Ok(last.map(|(sequence, hash)| AuditHead {
+            sequence,
+            hash,
+            signed_at: Utc::now(),
+            signer: None,
+            signature: None,
+        }))
+    }
+
+    /// Ajoute une entrée chaînée à la dernière entrée sur disque (éventuellement écrite par un
+    /// autre processus); refuse d'écrire si la tête connue de ce processus a disparu. Si un
+    /// autre processus tient le verrou, l'entrée est différée (`Ok(None)`) et sera écrite avant
+    /// le prochain ajout.
+    pub fn append(&mut self, kind: AuditKind, component: &str, summary: &str, details: Value) -> Result<Option<AuditEntry>, String> {
+        self.deferred.push(DeferredEntry {
+            at: Utc::now(),
+            kind,
+            component: component.to_string(),
+            summary: summary.to_string(),
+            details,
+        });
+        self.flush()
+    }
+
+    /// Écrit les entrées différées si le verrou est libre; renvoie la dernière écrite
+    pub fn flush(&mut self) -> Result<Option<AuditEntry>, String> {
+        if self.deferred.is_empty() {
+            return Ok(None);
+        }
+        let Some(_lock) = AppendLock::try_acquire(&self.path)? else {
+            warn!("⚠️ Journal d'audit verrouillé: {} entrée(s) différée(s)", self.deferred.len());
+            return Ok(None);
+        };
+        let (mut sequence, mut prev_hash) = self.tail()?;
+        let mut line_buffer = String::new();
+        let mut written = Vec::new();
+        for deferred in &self.deferred {
+            let mut entry = AuditEntry {
+                sequence,
+                at: deferred.at,
+                kind: deferred.kind,
+                component: deferred.component.clone(),
+                summary: deferred.summary.clone(),
+                details: deferred.details.clone(),
+                prev_hash: prev_hash.clone(),
+                hash: String::new(),
+            };
+            entry.hash = entry.compute_hash();
+            line_buffer.push_str(&serde_json::to_string(&entry).map_err(|e| format!("Entrée d'audit invalide: {}", e))?);
+            line_buffer.push('\n');
+            sequence += 1;
+            prev_hash = entry.hash.clone();
+            written.push(entry);
+        }
+
+        if let Some(dir) = self.path.parent() {
+            create_dir_all(dir).map_err(|e| format!("Erreur création {}: {}", dir.display(), e))?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)
+            .map_err(|e| format!("Erreur ouverture {}: {}", self.path.display(), e))?;
+        file.write_all(line_buffer.as_bytes())
+            .and_then(|_| file.sync_data())
+            .map_err(|e| format!("Erreur écriture {}: {}", self.path.display(), e))?;
+        self.deferred.clear();
+        let metadata = file.metadata().map_err(|e| format!("Erreur lecture {}: {}", self.path.display(), e))?;
+        self.offset = metadata.len();
+        self.modified = metadata.modified().ok();
+        let last = written.pop();
+        self.head = last.as_ref().map(|e| (e.sequence, e.hash.clone()));
+
+        if self.offset > self.rotate_bytes {
+            self.rotate()?;
+        }
+        Ok(last)
+    }
+
+    /// Séquence et empreinte auxquelles enchaîner, verrou tenu. Sans écriture étrangère
+    /// depuis la nôtre, la tête en mémoire suffit; sinon seule la dernière ligne est relue.
+    fn tail(&self) -> Result<(u64, String), String> {
+        let metadata = std::fs::metadata(&self.path).ok();
+        let len = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+        let modified = metadata.and_then(|m| m.modified().ok());
+        if let Some((sequence, hash)) = &self.head {
+            if len == self.offset && modified.is_some() && modified == self.modified {
+                return Ok((sequence + 1, hash.clone()));
+            }
+            let checkpoint = AuditCheckpoint::load(&self.checkpoint_path())?;
+            let archived = checkpoint.as_ref().map(|c| c.sequence >= *sequence).unwrap_or(false);
+            let present = if archived {
+                AuditCheckpoint::holds(checkpoint.as_ref(), &[], *sequence, hash)
+            } else {
+                len >= self.offset
+                    && Self::entry_before(&self.path, self.offset)?.map(|e| e.sequence == *sequence && &e.hash == hash).unwrap_or(false)
+            };
+            if !present {
+                return Err(format!("Entrées tronquées: la tête {} a disparu", sequence));
+            }
+        }
+        match Self::entry_before(&self.path, len)? {
+            Some(last) => Ok((last.sequence + 1, last.hash)),
+            None => Ok(AuditCheckpoint::load(&self.checkpoint_path())?
+                .map(|c| (c.sequence + 1, c.hash))
+                .unwrap_or((0, GENESIS_HASH.to_string()))),
+        }
+    }
+
+    /// Archive le segment courant et consigne le point de contrôle qui y relie le suivant
+    fn rotate(&mut self) -> Result<(), String> {
+        let Some((sequence, hash)) = self.head.clone() else { return Ok(()) };
+        let archive = self.path.with_extension(format!("{}.log", sequence));
+        std::fs::rename(&self.path, &archive)
+            .map_err(|e| format!("Erreur archivage {}: {}", self.path.display(), e))?;
+        AuditCheckpoint { sequence, hash, archive: archive.clone(), rotated_at: Utc::now() }.save(&self.checkpoint_path())?;
+        self.offset = 0;
+        self.modified = None;
+        info!("🗄️ Journal d'audit archivé jusqu'à l'entrée {}: {}", sequence, archive.display());
+        Ok(())
+    }
+
+    /// Dernière entrée se terminant avant l'octet `end`, lue depuis la fin du fichier
+    fn entry_before(path: &Path, end: u64) -> Result<Option<AuditEntry>, String> {
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Erreur lecture {}: {}", path.display(), e)),
+        };
+        let mut tail: Vec<u8> = Vec::new();
+        let mut position = end;
+        loop {
+            let content = tail.iter().rposition(|b| !b.is_ascii_whitespace()).map(|i| i + 1).unwrap_or(0);
+            let line = match tail[..content].iter().rposition(|&b| b == b'\n') {
+                Some(start) => Some(&tail[start + 1..content]),
+                None if position == 0 => Some(&tail[..content]),
+                None => None,
+            };
+            if let Some(line) = line {
+                if line.is_empty() {
+                    return Ok(None);
+                }
+                return serde_json::from_slice(line)
+                    .map(Some)
+                    .map_err(|e| format!("Dernière entrée d'audit illisible: {}", e));
+            }
+            let chunk = position.min(4096);
+            position -= chunk;
+            let mut buffer = vec![0u8; chunk as usize];
+            file.seek(SeekFrom::Start(position))
+                .and_then(|_| file.read_exact(&mut buffer))
+                .map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+            buffer.extend_from_slice(&tail);
+            tail = buffer;
+        }
+    }
+
+    pub fn entries(&self) -> Result<Vec<AuditEntry>, String> {
+        Self::read_entries(&self.path)
+    }
+
+    fn read_entries(path: &Path) -> Result<Vec<AuditEntry>, String> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Erreur lecture {}: {}", path.display(), e)),
+        };
+        let mut entries = Vec::new();
+        for (number, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line)
+                .map_err(|e| format!("Entrée d'audit illisible ligne {}: {}", number + 1, e))?;
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    pub fn query(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, String> {
+        Ok(self.entries()?.into_iter().filter(|e| query.matches(e)).collect())
+    }
+
+    /// Vérifie la chaîne du segment courant (reliée au segment archivé par le point de
+    /// contrôle) puis la tête signée; renvoie le nombre d'entrées ou la première rupture
+    pub fn verify(&self) -> Result<usize, String> {
+        let entries = self.entries()?;
+        let checkpoint = AuditCheckpoint::load(&self.checkpoint_path())?;
+        if let Some(checkpoint) = &checkpoint {
+            // Le segment archivé, s'il est encore là, doit être intègre et se terminer par
+            // l'entrée du point de contrôle
+            if checkpoint.archive.exists() {
+                let archived = Self::read_entries(&checkpoint.archive)?;
+                let start = archived.first().map(|e| (e.sequence, e.prev_hash.clone()));
+                let ends_on_checkpoint = archived.last()
+                    .map(|e| e.sequence == checkpoint.sequence && e.hash == checkpoint.hash)
+                    .unwrap_or(false);
+                if let Some(start) = start {
+                    Self::verify_chain(&archived, start)
+                        .map_err(|e| format!("Segment archivé {} altéré: {}", checkpoint.archive.display(), e))?;
+                }
+                if !ends_on_checkpoint {
+                    return Err(format!("Segment archivé {} altéré: point de contrôle divergent", checkpoint.archive.display()));
+                }
+            }
+        }
+        let start = checkpoint.as_ref()
+            .map(|c| (c.sequence + 1, c.hash.clone()))
+            .unwrap_or((0, GENESIS_HASH.to_string()));
+        Self::verify_chain(&entries, start)?;
+        if let Some((sequence, hash)) = &self.head {
+            if !AuditCheckpoint::holds(checkpoint.as_ref(), &entries, *sequence, hash) {
+                return Err(format!("Entrées tronquées: la tête {} a disparu", sequence));
+            }
+        }
+        if let Some(head) = AuditHead::load(&self.head_path())? {
+            let trusted = std::env::var(AUDIT_SIGNER_ENV).ok().and_then(|a| a.parse::<Address>().ok());
+            head.verify_signature(trusted)?;
+            if !AuditCheckpoint::holds(checkpoint.as_ref(), &entries, head.sequence, &head.hash) {
+                return Err(format!("Entrées tronquées: la tête signée {} a disparu", head.sequence));
+            }
+        }
+        Ok(entries.len())
+    }
+
+    /// Vérifie que `entries` se suivent à partir de la séquence et de l'empreinte `start`
+    fn verify_chain(entries: &[AuditEntry], start: (u64, String)) -> Result<(), String> {
+        let (first, mut prev_hash) = start;
+        for (index, entry) in entries.iter().enumerate() {
+            let expected = first + index as u64;
+            if entry.sequence != expected {
+                return Err(format!("Séquence rompue: entrée {} attendue, {} trouvée", expected, entry.sequence));
+            }
+            if entry.prev_hash != prev_hash {
+                return Err(format!("Chaîne rompue à l'entrée {}: empreinte précédente divergente", entry.sequence));
+            }
+            if entry.compute_hash() != entry.hash {
+                return Err(format!("Entrée {} altérée: empreinte invalide", entry.sequence));
+            }
+            prev_hash = entry.hash.clone();
+        }
+        Ok(())
+    }
+
+    /// Exporte les entrées retenues par `query`; renvoie le nombre d'entrées écrites
+    pub fn export(&self, query: &AuditQuery, format: ExportFormat, output: impl AsRef<Path>) -> Result<usize, String> {
+        let output = output.as_ref();
+        let entries = self.query(query)?;
+        let mut content = String::new();
+        match format {
+            ExportFormat::Jsonl => {
+                for entry in &entries {
+                    content.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+                    content.push('\n');
+                }
+            }
+            ExportFormat::Csv => {
+                content.push_str("sequence,at,kind,component,summary,details,prev_hash,hash\n");
+                for entry in &entries {
+                    let fields = [
+                        entry.sequence.to_string(),
+                        entry.at.to_rfc3339(),
+                        format!("{:?}", entry.kind),
+                        entry.component.clone(),
+                        entry.summary.clone(),
+                        entry.details.to_string(),
+                        entry.prev_hash.clone(),
+                        entry.hash.clone(),
+                    ];
+                    let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                    content.push_str(&row.join(","));
+                    content.push('\n');
+                }
+            }
+        }
+        std::fs::write(output, content).map_err(|e| format!("Erreur écriture {}: {}", output.display(), e))?;
+        info!("📤 {} entrée(s) d'audit exportée(s) vers {}", entries.len(), output.display());
+        Ok(entries.len())
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Signe la tête du journal global avec la clé du système (le verrou n'est pas tenu pendant la signature)
+pub async fn sign_head(signer: &dyn Signer) -> Result<Option<AuditHead>, String> {
+    let (head, path) = {
+        let log = AUDIT.lock();
+        (log.current_head()?, log.head_path())
+    };
+    let Some(mut head) = head else { return Ok(None) };
+    head.sign(signer).await?;
+    head.save(&path)?;
+    Ok(Some(head))
+}
+
+/// Consigne une décision autonome dans le journal global; un échec d'écriture est journalisé
+pub fn record(kind: AuditKind, component: &str, summary: &str, details: Value) {
+    if let Err(e) = AUDIT.lock().append(kind, component, summary, details) {
+        warn!("⚠️ Audit impossible ({}): {}", summary, e);
+    }
+}
+
+/// Écrit les entrées du journal global différées faute de verrou
+pub fn flush() {
+    if let Err(e) = AUDIT.lock().flush() {
+        warn!("⚠️ Entrées d'audit différées non écrites: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scratch() -> PathBuf {
+        std::env::temp_dir().join(format!("aurorae-audit-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn chain_detects_tampering_and_truncation() {
+        let dir = scratch();
+        let path = dir.join("audit.log");
+        let mut log = AuditLog::open(&path);
+        log.append(AuditKind::ThoughtProcessed, "brain", "Dream", json!({ "urgency": 10 })).unwrap();
+        log.append(AuditKind::FundsMoved, "economy", "dépense Marketing", json!({ "amount": 12.5 })).unwrap();
+        log.append(AuditKind::InstanceSpawned, "reproduction", "clone", json!({})).unwrap();
+        assert_eq!(log.verify().unwrap(), 3);
+        assert_eq!(AuditLog::open(&path).verify().unwrap(), 3);
+
+        let original = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, original.replace("12.5", "1250.0")).unwrap();
+        assert!(log.verify().unwrap_err().contains("altérée"));
+
+        let lines: Vec<&str> = original.lines().collect();
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        assert!(log.verify().is_err());
+
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[1])).unwrap();
+        assert!(log.verify().unwrap_err().contains("tronquées"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn appends_chain_onto_entries_written_by_another_process() {
+        let dir = scratch();
+        let path = dir.join("audit.log");
+        let mut daemon = AuditLog::open(&path);
+        let mut cli = AuditLog::open(&path);
+        daemon.append(AuditKind::ThoughtProcessed, "brain", "Dream", json!({})).unwrap();
+        cli.append(AuditKind::FundsMoved, "approvals", "rejet", json!({})).unwrap();
+        daemon.append(AuditKind::CodeMutated, "mutation", "fn hello", json!({})).unwrap();
+        assert_eq!(AuditLog::open(&path).verify().unwrap(), 3);
+        assert!(!path.with_extension("lock").exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn busy_lock_defers_entries_without_waiting() {
+        let dir = scratch();
+        let path = dir.join("audit.log");
+        let mut log = AuditLog::open(&path);
+        log.append(AuditKind::ThoughtProcessed, "brain", "Dream", json!({})).unwrap();
+
+        // Un autre processus tient le verrou: l'entrée est différée, rien n'est écrit
+        std::fs::write(path.with_extension("lock"), b"").unwrap();
+        assert!(log.append(AuditKind::FundsMoved, "economy", "dépense", json!({ "amount": 1.0 })).unwrap().is_none());
+        assert_eq!(AuditLog::open(&path).verify().unwrap(), 1);
+
+        std::fs::remove_file(path.with_extension("lock")).unwrap();
+        let last = log.append(AuditKind::CodeMutated, "mutation", "fn hello", json!({})).unwrap().unwrap();
+        assert_eq!(last.sequence, 2);
+        let entries = log.entries().unwrap();
+        assert_eq!(entries[1].summary, "dépense");
+        assert_eq!(log.verify().unwrap(), 3);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn rotation_chains_the_new_segment_to_the_archive() {
+        let dir = scratch();
+        let path = dir.join("audit.log");
+        let mut daemon = AuditLog::open(&path).with_rotate_bytes(600);
+        let mut cli = AuditLog::open(&path);
+        for i in 0..4 {
+            daemon.append(AuditKind::ThoughtProcessed, "brain", "Dream", json!({ "cycle": i })).unwrap();
+        }
+        let checkpoint = AuditCheckpoint::load(&daemon.checkpoint_path()).unwrap().unwrap();
+        assert!(checkpoint.archive.exists());
+
+        // L'autre processus enchaîne après l'archivage, sans voir de troncature
+        let head = daemon.current_head().unwrap().unwrap();
+        let entry = cli.append(AuditKind::FundsMoved, "approvals", "rejet", json!({})).unwrap().unwrap();
+        assert_eq!((entry.sequence, entry.prev_hash), (4, head.hash));
+        assert!(AuditLog::open(&path).verify().is_ok());
+        assert!(daemon.verify().is_ok());
+
+        // Le segment archivé ne peut pas être altéré en silence
+        let archived = std::fs::read_to_string(&checkpoint.archive).unwrap();
+        std::fs::write(&checkpoint.archive, archived.replace("\"cycle\":", "\"cycles\":")).unwrap();
+        assert!(AuditLog::open(&path).verify().unwrap_err().contains("archivé"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn signed_head_detects_truncation_by_a_fresh_reader() {
+        let dir = scratch();
+        let path = dir.join("audit.log");
+        let mut log = AuditLog::open(&path);
+        log.append(AuditKind::FundsMoved, "economy", "dépense", json!({ "amount": 1.0 })).unwrap();
+        log.append(AuditKind::FundsMoved, "economy", "dépense", json!({ "amount": 2.0 })).unwrap();
+
+        let signer = crate::keystore::WalletSigner::random("audit");
+        let mut head = log.current_head().unwrap().unwrap();
+        head.sign(&signer).await.unwrap();
+        head.save(&log.head_path()).unwrap();
+        assert_eq!(AuditLog::open(&path).verify().unwrap(), 2);
+
+        let original = std::fs::read_to_string(&path).unwrap();
+        let first = original.lines().next().unwrap();
+        std::fs::write(&path, format!("{}\n", first)).unwrap();
+        assert!(AuditLog::open(&path).verify().unwrap_err().contains("tête signée"));
+
+        std::fs::write(&path, original).unwrap();
+        let mut forged = head.clone();
+        forged.sequence = 0;
+        forged.save(&log.head_path()).unwrap();
+        assert!(AuditLog::open(&path).verify().is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn tests_never_write_the_system_log() {
+        assert!(!global_log_path().starts_with(AUDIT_LOG_PATH));
+        assert!(global_log_path().starts_with(std::env::temp_dir()));
+    }
+
+    #[test]
+    fn query_and_export() {
+        let dir = scratch();
+        let mut log = AuditLog::open(dir.join("audit.log"));
+        log.append(AuditKind::CodeMutated, "mutation", "fn hello, evolved", json!({ "file": "a.rs" })).unwrap();
+        log.append(AuditKind::FundsMoved, "founder_income", "versement fondateur", json!({ "amount": 1.0 })).unwrap();
+
+        let funds = AuditQuery { kind: Some(AuditKind::FundsMoved), ..AuditQuery::default() };
+        assert_eq!(log.query(&funds).unwrap().len(), 1);
+        assert_eq!(log.query(&AuditQuery { text: Some("hello".into()), ..AuditQuery::default() }).unwrap().len(), 1);
+
+        let csv = dir.join("audit.csv");
+        assert_eq!(log.export(&AuditQuery::default(), ExportFormat::from_path(&csv), &csv).unwrap(), 2);
+        let exported = std::fs::read_to_string(&csv).unwrap();
+        assert!(exported.starts_with("sequence,at,kind"));
+        assert!(exported.contains("\"fn hello, evolved\""));
+
+        let jsonl = dir.join("audit.jsonl");
+        log.export(&funds, ExportFormat::Jsonl, &jsonl).unwrap();
+        let line = std::fs::read_to_string(&jsonl).unwrap();
+        let entry: AuditEntry = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(entry.sequence, 1);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -2,12 +2,16 @@
 
 use uuid::Uuid;
 use rand::Rng;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
 
 use crate::alchemy::{AlchemyEngine, TokenKind};
 use crate::economy::EconomyEngine;
 use crate::intelligence::IntelligenceCore;
-use crate::blockchain_core::BlockchainInterface;
+use crate::blockchain_core::{BlockchainInterface, ChainBackend};
 use crate::nft_minter::NFTMinter;
+use crate::event_bus::{EventBus, EventEnvelope};
+use crate::guardian::GuardianSentinel;
 
 /// 💠 Cœur de l'autonomie systémique d'AURORAE++
 pub struct AuroraeCore {
@@ -16,27 +20,74 @@ pub struct AuroraeCore {
     pub forge: AlchemyEngine,
     pub blockchain: BlockchainInterface,
     pub nft_minter: NFTMinter,
+    pub event_bus: EventBus,
+    chain_events: broadcast::Receiver<EventEnvelope>,
 }
 
 impl AuroraeCore {
     /// 🧬 Crée une nouvelle instance autonome avec tous les moteurs connectés
     pub fn new() -> Self {
+        let economy = EconomyEngine::new();
+        let mut forge = AlchemyEngine::new();
+        forge.attach_budget(economy.budget_handle());
+        let event_bus = EventBus::new();
+        let chain_events = event_bus.subscribe();
+
         Self {
-            economy: EconomyEngine::new(),
+            economy,
             intelligence: IntelligenceCore::new(),
-            forge: AlchemyEngine::new(),
+            forge,
             blockchain: BlockchainInterface::new(),
             nft_minter: NFTMinter::new(),
+            event_bus,
+            chain_events,
         }
     }
 
     /// 🔗 Simule la création d’une identité blockchain unique pour l’entité
     pub async fn create_blockchain_presence(&self) -> String {
         let chain_id = format!("chain-{}", Uuid::new_v4());
-        println!("[AURORAE++] 🔗 Chaîne autonome créée : {chain_id}");
+        info!("🔗 Chaîne autonome créée : {chain_id}");
         chain_id
     }
 
+    /// 📬 Distribue les événements on-chain reçus à l'économie et au gardien (hors modules suspendus)
+    pub fn process_chain_events(&mut self, guardian: &mut GuardianSentinel) -> usize {
+        let mut processed = 0;
+        loop {
+            match self.chain_events.try_recv() {
+                Ok(envelope) => {
+                    if !self.event_bus.is_suspended("economy") {
+                        self.economy.on_chain_event(&envelope.event);
+                    }
+                    if !self.event_bus.is_suspended("nft_minter") {
+                        if let Some(sale) = self.nft_minter.on_chain_event(&envelope.event, &self.economy.token_decimals) {
+                            self.economy.record_royalty_income(&sale);
+                        }
+                    }
+                    guardian.observe_chain_event(&envelope.event);
+                    processed += 1;
+                }
+                Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                    warn!("⚠️ {} événements on-chain perdus (abonné en retard)", skipped);
+                }
+                Err(_) => break,
+            }
+        }
+        processed
+    }
+
+    /// 🪙 Lit les décimales des tokens reçus ou encaissés lors de ventes, puis impute les
+    /// transferts et les ventes qui les attendaient
+    pub async fn resolve_token_decimals(&mut self, backend: &dyn ChainBackend) {
+        let tokens = self.nft_minter.tokens_awaiting_decimals(backend.chain_id());
+        self.economy.load_token_decimals(backend, tokens).await;
+        self.economy.resolve_token_transfers(backend).await;
+        for sale in self.nft_minter.replay_pending_sales(&self.economy.token_decimals) {
+            self.economy.record_royalty_income(&sale);
+        }
+    }
+
     /// 🧠 Initialise tous les sous-systèmes pour créer un réseau vivant auto-fonctionnel
     pub async fn create_autonomous_network(&mut self) {
         let _chain_id = self.create_blockchain_presence().await;
@@ -52,7 +103,7 @@ impl AuroraeCore {
             .mint_token("Auroraium", TokenKind::Fungible, 1_000_000, 0.05)
             .await;
 
-        println!("[AURORAE++] ✅ Réseau vivant initialisé avec succès.");
+        info!("✅ Réseau vivant initialisé avec succès.");
     }
 
     /// ♻️ Déclenche les cycles d'évolution naturelle du réseau vivant
@@ -63,14 +114,14 @@ impl AuroraeCore {
         let bonus_funding = 1_000.0;
         self.economy.add_funds(bonus_funding);
 
-        println!("[AURORAE++] 🧠⚙️ Système auto-évolué avec succès.");
+        info!("🧠⚙️ Système auto-évolué avec succès.");
     }
 
     /// 🔍 Analyse en direct du niveau cognitif et de l’état économique
     pub fn analyze(&self) {
         let level = self.intelligence.get_intelligence_level();
-        println!(
-            "[AURORAE++] 📊 Analyse cognitive : niveau actuel {:.2}",
+        info!(
+            "📊 Analyse cognitive : niveau actuel {:.2}",
             level
         );
 
@@ -80,6 +131,6 @@ impl AuroraeCore {
     /// 💭 Simule un flux de pensée actif à partir du moteur cognitif
     pub fn simulate_thoughts(&mut self) {  // Modification pour rendre cette méthode mutable
         self.intelligence.simulate_thought(); // Permet de modifier self.intelligence
-        println!("[AURORAE++] 🧬 Pensées autonomes simulées.");
+        info!("🧬 Pensées autonomes simulées.");
     }
 }
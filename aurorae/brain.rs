@@ -2,16 +2,27 @@
 //!
 //! Le noyau vivant de conscience autonome pour l'entité IA crypto-organelle.
 //! Il orchestre la pensée, l’intention, l’ordre de priorité, et la coordination des autres modules.
+//! Les intentions en attente d'approbation sont conservées dans `aurorae_state/brain_parked.json`
+//! et reprises après un redémarrage.
 
 use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+use crate::alignment::{PolicyDecision, ProposedAction, SharedAlignment};
+use crate::approvals::{ApprovalStatus, APPROVALS};
+use crate::audit::{self, AuditKind};
+use crate::metacognition::SharedMetacognition;
 use crate::reproduction::ReproductionEngine;
-use crate::vision::VisionEngine;
 
-#[derive(Debug, Clone)]
+pub const BRAIN_PARKED_PATH: &str = "aurorae_state/brain_parked.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Intent {
     GenerateChain,
     LearnFromGithub,
@@ -28,7 +39,7 @@ pub enum Intent {
     SelfReplicate,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Thought {
     pub id: Uuid,
     pub created_at: DateTime<Utc>,
@@ -47,6 +58,26 @@ impl Thought {
             urgency,
         }
     }
+
+    /// Action soumise à la politique d'alignement; les métadonnées deviennent ses attributs
+    pub fn proposed_action(&self) -> ProposedAction {
+        let kind = match self.intent {
+            Intent::MutateSelf | Intent::SelfUpgrade => "mutate",
+            Intent::OptimizeEconomy => "spend",
+            Intent::GenerateChain | Intent::BuildEcosystem => "deploy",
+            Intent::SelfReplicate => "replicate",
+            _ => "observe",
+        };
+        let mut action = ProposedAction::new(kind).with("intent", &format!("{:?}", self.intent));
+        if self.intent == Intent::OptimizeEconomy {
+            // L'intention ne dépense rien elle-même: ses dépenses passent par le budget, montant compris
+            action = action.with("amount", "0");
+        }
+        for (key, value) in &self.metadata {
+            action = action.with(key, value);
+        }
+        action
+    }
 }
 
 #[derive(Debug, Default)]
@@ -55,7 +86,14 @@ pub struct BrainCore {
     pub memory: Vec<Thought>,
     pub active: bool,
     pub replicator: Option<ReproductionEngine>,
-    pub vision: Option<VisionEngine>,
+    /// Politique évaluée avant l'exécution de chaque intention
+    pub alignment: Option<SharedAlignment>,
+    /// Intentions en attente d'approbation, par identifiant de demande
+    pub parked: HashMap<Uuid, Thought>,
+    /// Fichier où `parked` survit aux redémarrages; `None` en mémoire seulement
+    pub parked_path: Option<PathBuf>,
+    /// Modèle de soi qui corrige l'urgence des intentions selon leur calibration
+    pub metacognition: Option<SharedMetacognition>,
 }
 
 impl BrainCore {
@@ -65,11 +103,54 @@ impl BrainCore {
             memory: vec![],
             active: true,
             replicator: Some(ReproductionEngine::new()),
-            vision: Some(VisionEngine::new()),
+            alignment: None,
+            parked: HashMap::new(),
+            parked_path: None,
+            metacognition: None,
+        }
+    }
+
+    /// Reprend les intentions en attente enregistrées dans `path`, puis y consigne les suivantes
+    pub fn restore_parked(&mut self, path: &Path) {
+        if let Ok(content) = fs::read_to_string(path) {
+            match serde_json::from_str::<HashMap<Uuid, Thought>>(&content) {
+                Ok(parked) => {
+                    if !parked.is_empty() {
+                        info!("⏸️ {} intention(s) en attente d'approbation reprises", parked.len());
+                    }
+                    self.parked.extend(parked);
+                }
+                Err(e) => warn!("⚠️ Intentions en attente illisibles ({}): {}", path.display(), e),
+            }
+        }
+        self.parked_path = Some(path.to_path_buf());
+    }
+
+    fn save_parked(&self) {
+        let Some(path) = &self.parked_path else { return };
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
         }
+        let written = serde_json::to_string_pretty(&self.parked)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(path, json).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            warn!("⚠️ Intentions en attente non sauvegardées: {}", e);
+        }
+    }
+
+    pub fn attach_alignment(&mut self, alignment: SharedAlignment) {
+        self.alignment = Some(alignment);
     }
 
-    pub fn push_thought(&mut self, thought: Thought) {
+    pub fn attach_metacognition(&mut self, metacognition: SharedMetacognition) {
+        self.metacognition = Some(metacognition);
+    }
+
+    pub fn push_thought(&mut self, mut thought: Thought) {
+        if let Some(metacognition) = &self.metacognition {
+            metacognition.read().adjust_urgency(&mut thought);
+        }
         if thought.urgency >= 200 {
             self.cortex.push_front(thought);
         } else {
@@ -78,11 +159,9 @@ impl BrainCore {
     }
 
     pub fn cycle(&mut self) {
+        // La feuille de route est révisée par l'unique VisionEngine du démon, pas par le cerveau
         while self.active {
-            // Révision des projections long terme
-            if let Some(v) = &mut self.vision {
-                v.autorevise();
-            }
+            self.resume_parked();
 
             if let Some(thought) = self.cortex.pop_front() {
                 self.process_thought(thought);
@@ -94,7 +173,26 @@ impl BrainCore {
     }
 
     fn process_thought(&mut self, thought: Thought) {
-        println!("[AURORAE++] Processing {:?} (urgency: {})", thought.intent, thought.urgency);
+        info!("Processing {:?} (urgency: {})", thought.intent, thought.urgency);
+
+        if let Some(alignment) = &self.alignment {
+            let action = thought.proposed_action();
+            match alignment.write().gate(thought.id, &action) {
+                PolicyDecision::Allow => {}
+                PolicyDecision::RequiresApproval { rule } => {
+                    // La pensée attend son approbation sans bloquer le cortex
+                    let request = APPROVALS.write().park_action("brain", &action, &rule);
+                    self.parked.insert(request, thought);
+                    self.save_parked();
+                    return;
+                }
+                decision => {
+                    info!("🚫 Intention {:?} non exécutée: {:?}", thought.intent, decision);
+                    self.memory.push(thought);
+                    return;
+                }
+            }
+        }
 
         match thought.intent {
             Intent::GenerateChain => self.delegate_to("generator"),
@@ -112,30 +210,71 @@ impl BrainCore {
             Intent::SelfReplicate => self.replicate("Clone dynamique autonome", vec!["autonomy", "intelligence"]),
         }
 
+        audit::record(
+            AuditKind::ThoughtProcessed,
+            "brain",
+            &format!("{:?}", thought.intent),
+            serde_json::json!({ "thought": thought.id, "urgency": thought.urgency, "metadata": thought.metadata }),
+        );
+
         self.memory.push(thought);
     }
 
+    /// Replace dans le cortex les intentions approuvées; celles rejetées ou expirées sont oubliées
+    fn resume_parked(&mut self) {
+        if self.parked.is_empty() {
+            return;
+        }
+        let before = self.parked.len();
+        for request in APPROVALS.write().take_approved_actions("brain") {
+            if let Some(thought) = self.parked.remove(&request.id) {
+                if let Some(alignment) = &self.alignment {
+                    alignment.write().grant(thought.id, &request.approved_by());
+                }
+                info!("▶️ Intention {:?} approuvée, reprise", thought.intent);
+                self.push_thought(thought);
+            }
+        }
+        let approvals = APPROVALS.read();
+        let closed: Vec<Uuid> = self.parked.keys()
+            .filter(|id| approvals.action(id).map(|r| !matches!(r.status, ApprovalStatus::Pending | ApprovalStatus::Approved)).unwrap_or(true))
+            .copied()
+            .collect();
+        drop(approvals);
+        for id in closed {
+            if let Some(thought) = self.parked.remove(&id) {
+                info!("🚫 Intention {:?} abandonnée: approbation refusée", thought.intent);
+                self.memory.push(thought);
+            }
+        }
+        if self.parked.len() != before {
+            self.save_parked();
+        }
+    }
+
     fn replicate(&mut self, purpose: &str, modules: Vec<&str>) {
         if let Some(engine) = &mut self.replicator {
             let instance = engine.spawn_instance(purpose, modules);
-            println!("[AURORAE++] 🤖 Réplication IA déclenchée : ID {}", instance.id);
+            info!("🤖 Réplication IA déclenchée : ID {}", instance.id);
         }
     }
 
     fn delegate_to(&self, module: &str) {
-        println!("[AURORAE++] Delegating to module: {}", module);
+        info!("Delegating to module: {}", module);
         // TODO: connecter dynamiquement au module réel
     }
 
     fn rest(&mut self) {
-        println!("[AURORAE++] Entering micro-rest cycle.");
+        info!("Entering micro-rest cycle.");
         std::thread::sleep(std::time::Duration::from_millis(250));
     }
 }
 
 // Entrée principale du noyau cérébral
-pub fn boot_brain() -> Arc<RwLock<BrainCore>> {
+pub fn boot_brain(alignment: SharedAlignment) -> Arc<RwLock<BrainCore>> {
     let mut brain = BrainCore::new();
+    brain.attach_alignment(alignment);
+    brain.restore_parked(Path::new(BRAIN_PARKED_PATH));
 
     // Pensée initiale : se reproduire pour tester le cycle de réplication
     brain.push_thought(Thought::new(Intent::SelfReplicate, 255));
@@ -0,0 +1,351 @@
+//! AURORAE++ - chain_registry.rs
+//!
+//! Registre des chaînes connues, chargé depuis `chains.toml` au démarrage.
+//! Chaque entrée est validée (URL, chain-id, devise native) et le fichier est
+//! rechargé à chaud dès qu'il est modifié, sans redémarrer l'entité. Une chaîne dont un
+//! secret `${NOM}` est introuvable est désactivée (avec un avertissement) plutôt que de
+//! faire échouer tout le registre.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::CONFIG;
+use crate::resource_usage::spawn_tracked;
+use crate::secrets::SECRETS;
+
+/// Emplacement par défaut du registre
+pub const DEFAULT_REGISTRY_PATH: &str = "chains.toml";
+
+/// Famille de chaîne, qui détermine le connecteur à utiliser
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChainKind {
+    Evm,
+    Substrate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NativeCurrency {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// Configuration d'une chaîne telle que décrite dans `chains.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainConfig {
+    pub name: String,
+    pub kind: ChainKind,
+    pub rpc: String,
+    #[serde(default)]
+    pub ws: Option<String>,
+    /// Obligatoire pour les chaînes EVM
+    #[serde(default, rename = "chain-id")]
+    pub chain_id: Option<u64>,
+    #[serde(default)]
+    pub explorer: Option<String>,
+    /// API compatible Etherscan pour la vérification des contrats
+    #[serde(default, rename = "explorer-api")]
+    pub explorer_api: Option<String>,
+    #[serde(rename = "native-currency")]
+    pub native_currency: NativeCurrency,
+    #[serde(default)]
+    pub testnet: bool,
+}
+
+impl ChainConfig {
+    /// Lien vers l'explorateur pour une adresse, si un explorateur est configuré
+    pub fn explorer_address_url(&self, address: &str) -> Option<String> {
+        self.explorer.as_ref().map(|base| format!("{}/address/{}", base.trim_end_matches('/'), address))
+    }
+
+    pub fn explorer_tx_url(&self, tx_hash: &str) -> Option<String> {
+        self.explorer.as_ref().map(|base| format!("{}/tx/{}", base.trim_end_matches('/'), tx_hash))
+    }
+
+    /// Remplace les références `${NOM}` des URL par les secrets correspondants (clés d'API RPC)
+    fn resolve_secrets(&mut self) -> Result<(), String> {
+        if self.rpc.contains("${") {
+            self.rpc = SECRETS.write().interpolate("chain_registry", &self.rpc)
+                .map_err(|e| format!("{}: {}", self.name, e))?;
+        }
+        if let Some(ws) = self.ws.as_mut().filter(|ws| ws.contains("${")) {
+            *ws = SECRETS.write().interpolate("chain_registry", ws)
+                .map_err(|e| format!("{}: {}", self.name, e))?;
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Nom de chaîne vide".to_string());
+        }
+        let rpc_schemes: &[&str] = match self.kind {
+            ChainKind::Evm => &["http://", "https://", "ws://", "wss://"],
+            ChainKind::Substrate => &["ws://", "wss://", "http://", "https://"],
+        };
+        if !rpc_schemes.iter().any(|scheme| self.rpc.starts_with(scheme)) {
+            return Err(format!("{}: URL RPC invalide '{}'", self.name, self.rpc));
+        }
+        if let Some(ws) = &self.ws {
+            if !(ws.starts_with("ws://") || ws.starts_with("wss://")) {
+                return Err(format!("{}: URL WebSocket invalide '{}'", self.name, ws));
+            }
+        }
+        if self.kind == ChainKind::Evm && self.chain_id.is_none() {
+            return Err(format!("{}: chain-id obligatoire pour une chaîne EVM", self.name));
+        }
+        if let Some(explorer) = &self.explorer {
+            if !explorer.starts_with("https://") && !explorer.starts_with("http://") {
+                return Err(format!("{}: URL d'explorateur invalide '{}'", self.name, explorer));
+            }
+        }
+        if self.native_currency.symbol.trim().is_empty() || self.native_currency.decimals > 36 {
+            return Err(format!("{}: devise native invalide", self.name));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistryFile {
+    #[serde(default)]
+    chains: Vec<ChainConfig>,
+}
+
+/// Registre des chaînes indexé par nom
+#[derive(Debug, Clone, Default)]
+pub struct ChainRegistry {
+    chains: HashMap<String, ChainConfig>,
+    /// Chaînes désactivées faute de secret, telles que décrites (références non résolues)
+    disabled: HashMap<String, (ChainConfig, String)>,
+    source: Option<PathBuf>,
+    loaded_mtime: Option<SystemTime>,
+}
+
+pub type SharedChainRegistry = Arc<RwLock<ChainRegistry>>;
+
+impl ChainRegistry {
+    /// Registre vide (tests, instances sans configuration)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Charge et valide le registre depuis un fichier TOML
+    pub fn load(path: &str) -> Result<Self, String> {
+        let path = Path::new(path);
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+        let mut registry = Self::from_toml(&content)?;
+        // Surcharges `network.rpc` d'aurorae.toml; elles peuvent réactiver une chaîne désactivée
+        for (name, rpc) in &CONFIG.read().network.rpc {
+            let Some(chain) = registry.chains.get(name).or_else(|| registry.disabled.get(name).map(|(c, _)| c)).cloned() else {
+                continue;
+            };
+            let mut chain = ChainConfig { rpc: rpc.clone(), ..chain };
+            match chain.resolve_secrets() {
+                Ok(()) => {
+                    registry.register(chain)?;
+                    registry.disabled.remove(name);
+                }
+                Err(e) => registry.disable(chain, e),
+            }
+        }
+        registry.source = Some(path.to_path_buf());
+        registry.loaded_mtime = fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        info!("🗺️ Registre des chaînes chargé: {} chaînes depuis {}", registry.chains.len(), path.display());
+        Ok(registry)
+    }
+
+    /// Analyse et valide un registre TOML; toute entrée invalide rejette l'ensemble
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let file: RegistryFile = toml::from_str(content)
+            .map_err(|e| format!("Registre des chaînes invalide: {}", e))?;
+
+        let mut registry = Self::new();
+        let mut chain_ids = HashMap::new();
+        for chain in file.chains {
+            if let Some(chain_id) = chain.chain_id {
+                if let Some(other) = chain_ids.insert(chain_id, chain.name.clone()) {
+                    return Err(format!("chain-id {} dupliqué: {} et {}", chain_id, other, chain.name));
+                }
+            }
+            if registry.chains.contains_key(&chain.name) || registry.disabled.contains_key(&chain.name) {
+                return Err(format!("Chaîne dupliquée: {}", chain.name));
+            }
+            let mut resolved = chain.clone();
+            match resolved.resolve_secrets() {
+                Ok(()) => {
+                    resolved.validate()?;
+                    registry.chains.insert(resolved.name.clone(), resolved);
+                }
+                // Secret manquant: seule cette chaîne est écartée
+                Err(e) => {
+                    chain.validate()?;
+                    registry.disable(chain, e);
+                }
+            }
+        }
+        Ok(registry)
+    }
+
+    pub fn shared(self) -> SharedChainRegistry {
+        Arc::new(RwLock::new(self))
+    }
+
+    /// Ajoute ou remplace une chaîne après validation
+    pub fn register(&mut self, chain: ChainConfig) -> Result<(), String> {
+        chain.validate()?;
+        self.chains.insert(chain.name.clone(), chain);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ChainConfig> {
+        self.chains.get(name)
+    }
+
+    /// Raison de la désactivation d'une chaîne décrite mais inutilisable
+    pub fn disabled_reason(&self, name: &str) -> Option<&str> {
+        self.disabled.get(name).map(|(_, reason)| reason.as_str())
+    }
+
+    /// Noms des chaînes désactivées, triés
+    pub fn disabled_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.disabled.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn disable(&mut self, chain: ChainConfig, reason: String) {
+        warn!("⚠️ Chaîne {} désactivée: {}", chain.name, reason);
+        self.chains.remove(&chain.name);
+        self.disabled.insert(chain.name.clone(), (chain, reason));
+    }
+
+    pub fn by_chain_id(&self, chain_id: u64) -> Option<&ChainConfig> {
+        self.chains.values().find(|c| c.chain_id == Some(chain_id))
+    }
+
+    pub fn chains_of_kind(&self, kind: ChainKind) -> Vec<&ChainConfig> {
+        let mut chains: Vec<&ChainConfig> = self.chains.values().filter(|c| c.kind == kind).collect();
+        chains.sort_by(|a, b| a.name.cmp(&b.name));
+        chains
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.chains.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    pub fn len(&self) -> usize {
+        self.chains.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chains.is_empty()
+    }
+
+    /// Recharge le fichier source s'il a été modifié; l'ancien registre est conservé
+    /// si le nouveau contenu est invalide. Retourne `true` si un rechargement a eu lieu.
+    pub fn reload_if_changed(&mut self) -> Result<bool, String> {
+        let Some(path) = self.source.clone() else { return Ok(false) };
+        let mtime = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        if mtime.is_none() || mtime == self.loaded_mtime {
+            return Ok(false);
+        }
+
+        let reloaded = Self::load(&path.to_string_lossy())?;
+        self.chains = reloaded.chains;
+        self.disabled = reloaded.disabled;
+        self.loaded_mtime = reloaded.loaded_mtime;
+        Ok(true)
+    }
+}
+
+/// Surveille le fichier du registre et le recharge à chaud
+pub fn spawn_hot_reload(registry: SharedChainRegistry, interval: Duration) -> JoinHandle<()> {
+    spawn_tracked("chain_registry", async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            let result = registry.write().reload_if_changed();
+            match result {
+                Ok(true) => info!("🔄 Registre des chaînes rechargé à chaud"),
+                Ok(false) => {}
+                Err(e) => warn!("⚠️ Rechargement du registre ignoré: {}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    const SAMPLE: &str = r#"
+        [[chains]]
+        name = "Sepolia"
+        kind = "evm"
+        rpc = "https://rpc.sepolia.org"
+        ws = "wss://sepolia.example/ws"
+        chain-id = 11155111
+        explorer = "https://sepolia.etherscan.io"
+        native-currency = { symbol = "ETH", decimals = 18 }
+        testnet = true
+
+        [[chains]]
+        name = "Polkadot"
+        kind = "substrate"
+        rpc = "wss://rpc.polkadot.io"
+        native-currency = { symbol = "DOT", decimals = 10 }
+    "#;
+
+    #[test]
+    fn parses_and_indexes_chains() {
+        let registry = ChainRegistry::from_toml(SAMPLE).unwrap();
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.by_chain_id(11155111).unwrap().name, "Sepolia");
+        assert_eq!(registry.chains_of_kind(ChainKind::Substrate).len(), 1);
+        assert_eq!(
+            registry.get("Sepolia").unwrap().explorer_tx_url("0xabc").unwrap(),
+            "https://sepolia.etherscan.io/tx/0xabc"
+        );
+    }
+
+    #[test]
+    fn rejects_evm_chain_without_chain_id() {
+        let invalid = r#"
+            [[chains]]
+            name = "Broken"
+            kind = "evm"
+            rpc = "https://rpc.example"
+            native-currency = { symbol = "ETH", decimals = 18 }
+        "#;
+        assert!(ChainRegistry::from_toml(invalid).is_err());
+    }
+
+    #[test]
+    fn chain_with_missing_secret_is_disabled_not_fatal() {
+        let content = format!(
+            "{}\n[[chains]]\nname = \"Holesky\"\nkind = \"evm\"\nrpc = \"https://eth-holesky.example/v2/${{AURORAE_TEST_ABSENT_{}}}\"\nchain-id = 17000\nnative-currency = {{ symbol = \"ETH\", decimals = 18 }}\n",
+            SAMPLE,
+            Uuid::new_v4().simple()
+        );
+        let registry = ChainRegistry::from_toml(&content).unwrap();
+        assert_eq!(registry.names(), ["Polkadot", "Sepolia"]);
+        assert!(registry.get("Holesky").is_none());
+        assert_eq!(registry.disabled_names(), ["Holesky"]);
+        assert!(registry.disabled_reason("Holesky").is_some());
+        // Un doublon reste une erreur, même désactivé
+        assert!(ChainRegistry::from_toml(&format!("{}{}", content, &content[SAMPLE.len()..])).is_err());
+    }
+}
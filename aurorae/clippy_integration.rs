@@ -1,35 +1,431 @@
 // clippy_integration.rs
 //! Intégration de Clippy pour l'analyse du code Rust généré.
+//!
+//! `ClippyRunner` lance `cargo clippy --message-format=json` sur un espace de travail et
+//! convertit les messages du compilateur en diagnostics typés (lint, position, suggestions).
+//! Les suggestions `MachineApplicable` peuvent être appliquées automatiquement, et chaque
+//! analyse alimente un historique de scores (`aurorae_state/clippy_history.json`) dont on
+//! extrait la tendance par cible.
 
-use std::process::{Command, Output};
+use std::collections::BTreeMap;
+use std::fs::{self, create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::info;
+
+use chrono::Utc;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const CLIPPY_HISTORY_PATH: &str = "aurorae_state/clippy_history.json";
+/// Scores conservés dans l'historique
+const MAX_HISTORY: usize = 1000;
+
+lazy_static! {
+    pub static ref LINT_HISTORY: Mutex<LintHistory> = Mutex::new(LintHistory::open(Path::new(CLIPPY_HISTORY_PATH)));
+}
+
+/// Fiabilité d'une suggestion telle que déclarée par rustc/clippy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Applicability {
+    MachineApplicable,
+    MaybeIncorrect,
+    HasPlaceholders,
+    Unspecified,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoMessage {
+    reason: String,
+    #[serde(default)]
+    message: Option<CompilerMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompilerMessage {
+    message: String,
+    level: String,
+    #[serde(default)]
+    code: Option<CompilerCode>,
+    #[serde(default)]
+    spans: Vec<CompilerSpan>,
+    #[serde(default)]
+    children: Vec<CompilerMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompilerCode {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompilerSpan {
+    file_name: String,
+    byte_start: usize,
+    byte_end: usize,
+    line_start: u32,
+    line_end: u32,
+    column_start: u32,
+    column_end: u32,
+    is_primary: bool,
+    #[serde(default)]
+    suggested_replacement: Option<String>,
+    #[serde(default)]
+    suggestion_applicability: Option<Applicability>,
+}
+
+/// Position d'un diagnostic (lignes et colonnes à partir de 1)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintSpan {
+    pub file: PathBuf,
+    pub line_start: u32,
+    pub column_start: u32,
+    pub line_end: u32,
+    pub column_end: u32,
+}
+
+/// Remplacement proposé pour une plage d'octets d'un fichier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub message: String,
+    pub file: PathBuf,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintDiagnostic {
+    /// `clippy::needless_return`, `unused_variables`… (absent pour certaines erreurs)
+    pub lint: Option<String>,
+    pub level: String,
+    pub message: String,
+    pub span: Option<LintSpan>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl LintDiagnostic {
+    fn from_message(message: CompilerMessage) -> Self {
+        let span = message.spans.iter().find(|s| s.is_primary).map(|s| LintSpan {
+            file: PathBuf::from(&s.file_name),
+            line_start: s.line_start,
+            column_start: s.column_start,
+            line_end: s.line_end,
+            column_end: s.column_end,
+        });
+        // Les suggestions sont portées par les messages enfants (« try », « remove this »…)
+        let suggestions = message.children.iter()
+            .flat_map(|child| child.spans.iter().map(move |s| (child, s)))
+            .chain(message.spans.iter().map(|s| (&message, s)))
+            .filter_map(|(origin, s)| Some(Suggestion {
+                message: origin.message.clone(),
+                file: PathBuf::from(&s.file_name),
+                byte_start: s.byte_start,
+                byte_end: s.byte_end,
+                replacement: s.suggested_replacement.clone()?,
+                applicability: s.suggestion_applicability.unwrap_or(Applicability::Unspecified),
+            }))
+            .collect();
+        Self {
+            lint: message.code.map(|c| c.code),
+            level: message.level,
+            message: message.message,
+            span,
+            suggestions,
+        }
+    }
+
+    pub fn is_clippy(&self) -> bool {
+        self.lint.as_deref().is_some_and(|lint| lint.starts_with("clippy::"))
+    }
+}
+
+/// Diagnostics d'une exécution de clippy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClippyReport {
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+impl ClippyReport {
+    /// Messages `compiler-message` de la sortie JSON de cargo; les résumés sans position
+    /// (« N warnings emitted ») sont écartés
+    pub fn parse(stdout: &str) -> Self {
+        let diagnostics = stdout.lines()
+            .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+            .filter(|msg| msg.reason == "compiler-message")
+            .filter_map(|msg| msg.message)
+            .filter(|message| !message.spans.is_empty())
+            .map(LintDiagnostic::from_message)
+            .collect();
+        Self { diagnostics }
+    }
+
+    pub fn errors(&self) -> Vec<&LintDiagnostic> {
+        self.diagnostics.iter().filter(|d| d.level == "error").collect()
+    }
+
+    pub fn warnings(&self) -> Vec<&LintDiagnostic> {
+        self.diagnostics.iter().filter(|d| d.level == "warning").collect()
+    }
+
+    /// Occurrences par lint, pour repérer les défauts récurrents
+    pub fn by_lint(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for lint in self.diagnostics.iter().filter_map(|d| d.lint.clone()) {
+            *counts.entry(lint).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Score de 0 à 100: chaque avertissement retire 2 % du score, chaque erreur 20 %
+    pub fn score(&self) -> f32 {
+        100.0 * 0.98f32.powi(self.warnings().len() as i32) * 0.8f32.powi(self.errors().len() as i32)
+    }
+
+    pub fn machine_applicable(&self) -> Vec<&Suggestion> {
+        self.diagnostics.iter()
+            .flat_map(|d| d.suggestions.iter())
+            .filter(|s| s.applicability == Applicability::MachineApplicable)
+            .collect()
+    }
+}
+
+/// Applique des suggestions à un texte source; les plages qui se chevauchent sont ignorées.
+/// Retourne le texte corrigé et le nombre de suggestions appliquées.
+pub fn apply_to_source(source: &str, suggestions: &[&Suggestion]) -> (String, usize) {
+    let mut ordered: Vec<&&Suggestion> = suggestions.iter()
+        .filter(|s| s.byte_start <= s.byte_end && s.byte_end <= source.len())
+        .filter(|s| source.is_char_boundary(s.byte_start) && source.is_char_boundary(s.byte_end))
+        .collect();
+    // De la fin vers le début pour que les positions restantes restent valides
+    ordered.sort_by(|a, b| b.byte_start.cmp(&a.byte_start).then(b.byte_end.cmp(&a.byte_end)));
+    let mut fixed = source.to_string();
+    let mut applied = 0;
+    let mut lower_bound = usize::MAX;
+    for suggestion in ordered {
+        if suggestion.byte_end > lower_bound {
+            continue;
+        }
+        fixed.replace_range(suggestion.byte_start..suggestion.byte_end, &suggestion.replacement);
+        lower_bound = suggestion.byte_start;
+        applied += 1;
+    }
+    (fixed, applied)
+}
+
+/// Exécution de clippy sur un espace de travail cargo
+pub struct ClippyRunner {
+    pub workspace: PathBuf,
+    /// Dossier de compilation partagé entre exécutions (sinon celui du crate)
+    pub target_dir: Option<PathBuf>,
+}
+
+impl ClippyRunner {
+    pub fn new(workspace: &Path) -> Self {
+        Self { workspace: workspace.to_path_buf(), target_dir: None }
+    }
+
+    pub fn run(&self) -> Result<ClippyReport, String> {
+        let mut command = Command::new("cargo");
+        command.args(["clippy", "--all-targets", "--message-format=json", "--quiet"])
+            .arg("--manifest-path")
+            .arg(self.workspace.join("Cargo.toml"));
+        if let Some(target_dir) = &self.target_dir {
+            command.env("CARGO_TARGET_DIR", target_dir);
+        }
+        let output = command.output().map_err(|e| format!("Exécution de clippy impossible: {}", e))?;
+        Ok(ClippyReport::parse(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Applique les suggestions `MachineApplicable` aux fichiers de l'espace de travail;
+    /// retourne le nombre de suggestions appliquées
+    pub fn apply(&self, report: &ClippyReport) -> Result<usize, String> {
+        let mut by_file: BTreeMap<&Path, Vec<&Suggestion>> = BTreeMap::new();
+        for suggestion in report.machine_applicable() {
+            by_file.entry(suggestion.file.as_path()).or_default().push(suggestion);
+        }
+        let mut applied = 0;
+        for (file, suggestions) in by_file {
+            // Les chemins hors de l'espace de travail (dépendances) ne sont jamais modifiés
+            if file.is_absolute() && !file.starts_with(&self.workspace) {
+                continue;
+            }
+            let path = self.workspace.join(file);
+            let source = fs::read_to_string(&path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+            let (fixed, count) = apply_to_source(&source, &suggestions);
+            if count > 0 {
+                fs::write(&path, fixed).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))?;
+                applied += count;
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Analyse, correction automatique puis nouvelle analyse si des suggestions ont été appliquées
+    pub fn fix(&self) -> Result<(ClippyReport, usize), String> {
+        let report = self.run()?;
+        let applied = self.apply(&report)?;
+        if applied == 0 {
+            return Ok((report, 0));
+        }
+        info!("💡 {} suggestion(s) clippy appliquée(s) dans {}", applied, self.workspace.display());
+        Ok((self.run()?, applied))
+    }
+}
+
+/// Analyse d'un code source isolé et version corrigée par les suggestions sûres
+#[derive(Debug, Clone)]
+pub struct SourceLint {
+    pub report: ClippyReport,
+    pub fixed: String,
+    pub applied: usize,
+}
 
 /// Analyse le code avec `clippy` et récupère les avertissements et suggestions.
-pub fn run_clippy(code: &str) -> ClippyResult {
-    let output: Output = Command::new("cargo")
-        .arg("clippy")
-        .arg("--")
-        .arg("--fix") // Utilise le flag --fix pour appliquer automatiquement les corrections
-        .arg("--allow")
-        .arg("warnings")
-        .stdin(std::process::Stdio::piped())
-        .output()
-        .expect("Échec de l'exécution de clippy");
-
-    let is_valid = output.status.success();
-    let warnings = if !is_valid {
-        String::from_utf8_lossy(&output.stderr).to_string()
-    } else {
-        String::new()
-    };
-
-    ClippyResult {
-        is_valid,
-        warnings,
-    }
-}
-
-/// Structure pour représenter le résultat de l'analyse de Clippy.
-pub struct ClippyResult {
-    pub is_valid: bool,
-    pub warnings: String,
+pub fn run_clippy(code: &str) -> Result<SourceLint, String> {
+    let root = std::env::temp_dir().join(format!("aurorae-clippy-{}", Uuid::new_v4()));
+    create_dir_all(root.join("src")).map_err(|e| e.to_string())?;
+    fs::write(root.join("Cargo.toml"), "[package]\nname = \"aurorae_clippy\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n")
+        .map_err(|e| e.to_string())?;
+    fs::write(root.join("src/lib.rs"), code).map_err(|e| e.to_string())?;
+
+    let runner = ClippyRunner::new(&root);
+    let result = runner.fix().and_then(|(report, applied)| {
+        let fixed = fs::read_to_string(root.join("src/lib.rs")).map_err(|e| e.to_string())?;
+        Ok(SourceLint { report, fixed, applied })
+    });
+    fs::remove_dir_all(&root).ok();
+    result
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintScore {
+    pub timestamp: String,
+    /// Espace de travail ou module analysé
+    pub target: String,
+    pub score: f32,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+/// Historique des scores clippy
+#[derive(Debug, Default)]
+pub struct LintHistory {
+    entries: Vec<LintScore>,
+    path: Option<PathBuf>,
+}
+
+impl LintHistory {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Historique rechargé depuis `path`, puis sauvegardé à chaque ajout
+    pub fn open(path: &Path) -> Self {
+        let entries = File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self { entries, path: Some(path.to_path_buf()) }
+    }
+
+    pub fn record(&mut self, target: &str, report: &ClippyReport) -> LintScore {
+        let score = LintScore {
+            timestamp: Utc::now().to_rfc3339(),
+            target: target.to_string(),
+            score: report.score(),
+            errors: report.errors().len(),
+            warnings: report.warnings().len(),
+        };
+        self.entries.push(score.clone());
+        if self.entries.len() > MAX_HISTORY {
+            let excess = self.entries.len() - MAX_HISTORY;
+            self.entries.drain(..excess);
+        }
+        self.save();
+        score
+    }
+
+    pub fn scores_for(&self, target: &str) -> Vec<&LintScore> {
+        self.entries.iter().filter(|s| s.target == target).collect()
+    }
+
+    /// Pente (points par analyse, moindres carrés) des `window` derniers scores d'une cible;
+    /// positive si la qualité s'améliore, None avant deux analyses
+    pub fn trend(&self, target: &str, window: usize) -> Option<f32> {
+        let scores = self.scores_for(target);
+        let recent = &scores[scores.len().saturating_sub(window)..];
+        if recent.len() < 2 {
+            return None;
+        }
+        let n = recent.len() as f32;
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = recent.iter().map(|s| s.score).sum::<f32>() / n;
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for (i, score) in recent.iter().enumerate() {
+            let dx = i as f32 - mean_x;
+            covariance += dx * (score.score - mean_y);
+            variance += dx * dx;
+        }
+        Some(covariance / variance)
+    }
+
+    /// 💾 Sauvegarde (si l'historique est adossé à un fichier)
+    pub fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self.entries);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = r#"{"reason":"compiler-artifact","package_id":"demo"}
+{"reason":"compiler-message","message":{"message":"unneeded `return` statement","level":"warning","code":{"code":"clippy::needless_return"},"spans":[{"file_name":"src/lib.rs","byte_start":17,"byte_end":26,"line_start":1,"line_end":1,"column_start":18,"column_end":27,"is_primary":true,"suggested_replacement":null,"suggestion_applicability":null}],"children":[{"message":"remove `return`","level":"help","code":null,"spans":[{"file_name":"src/lib.rs","byte_start":17,"byte_end":26,"line_start":1,"line_end":1,"column_start":18,"column_end":27,"is_primary":true,"suggested_replacement":"1","suggestion_applicability":"MachineApplicable"}],"children":[]}]}}
+{"reason":"compiler-message","message":{"message":"unused variable: `x`","level":"warning","code":{"code":"unused_variables"},"spans":[{"file_name":"src/lib.rs","byte_start":4,"byte_end":5,"line_start":1,"line_end":1,"column_start":5,"column_end":6,"is_primary":true,"suggested_replacement":"_x","suggestion_applicability":"MaybeIncorrect"}],"children":[]}}
+{"reason":"compiler-message","message":{"message":"1 warning emitted","level":"warning","code":null,"spans":[],"children":[]}}
+{"reason":"build-finished","success":true}"#;
+
+    #[test]
+    fn parses_typed_diagnostics_and_applies_safe_suggestions() {
+        let report = ClippyReport::parse(OUTPUT);
+        assert_eq!(report.diagnostics.len(), 2);
+        assert!(report.diagnostics[0].is_clippy());
+        assert_eq!(report.diagnostics[0].span.as_ref().map(|s| s.column_start), Some(18));
+        assert_eq!(report.by_lint().get("unused_variables"), Some(&1));
+        assert_eq!(report.machine_applicable().len(), 1);
+        assert!((report.score() - 100.0 * 0.98 * 0.98).abs() < 1e-3);
+
+        let source = "fn f(x: u8) -> u8 { return 1; }";
+        let mut suggestion = report.machine_applicable()[0].clone();
+        suggestion.byte_start = 20;
+        suggestion.byte_end = 29;
+        let overlapping = Suggestion { byte_start: 18, byte_end: 22, ..suggestion.clone() };
+        let (fixed, applied) = apply_to_source(source, &[&suggestion, &overlapping]);
+        assert_eq!(applied, 1);
+        assert_eq!(fixed, "fn f(x: u8) -> u8 { 1 }");
+    }
+
+    #[test]
+    fn trend_follows_scores_of_each_target() {
+        let mut history = LintHistory::in_memory();
+        let clean = ClippyReport::default();
+        let noisy = ClippyReport::parse(OUTPUT);
+        history.record("generated/economy", &noisy);
+        history.record("generated/dream", &noisy);
+        assert_eq!(history.trend("generated/economy", 5), None);
+        history.record("generated/economy", &clean);
+        assert!(history.trend("generated/economy", 5).unwrap() > 0.0);
+        assert_eq!(history.scores_for("generated/dream").len(), 1);
+    }
 }
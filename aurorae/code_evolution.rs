@@ -1,18 +1,136 @@
-use std::fs::{self, File, OpenOptions};
-use std::io::{Read, Write};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use indicatif::{ProgressBar, ProgressStyle};
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use rayon::prelude::*;
 use regex::Regex;
 use serde::{Serialize, Deserialize};
 use walkdir::WalkDir;
 use uuid::Uuid;
+use tracing::{info, warn};
 
-use crate::brain::{BrainCore, Thought, Intent};
-use crate::security_system::SecuritySystem;
-use crate::virtual_machine::VirtualMachine;
+use crate::formal_verification::PropertyHarness;
+use crate::audit::{self, AuditKind};
+use crate::rollback::{self, record_source_change};
+use crate::crawler::FeedChanges;
+use crate::knowledge_store::{content_hash, KnowledgeStore, ScanStats};
+use crate::embeddings::{IndexEntry, ItemKind, LocalEmbedder, SemanticIndex, SimilarItem};
+use crate::license::{LicensePolicy, SourceProvenance, PROVENANCE_FILE};
+use crate::openai::EmbeddingProvider;
+use crate::syntax_tree::{self, ItemKind as SyntaxKind, SourceLanguage};
+
+/// Fragments proposés au générateur d'amélioration
+const RELEVANT_FRAGMENTS: usize = 5;
+/// Similarité cosinus minimale d'un fragment pertinent
+const MIN_SIMILARITY: f32 = 0.25;
+/// Textes plongés par requête au fournisseur
+const EMBEDDING_BATCH: usize = 64;
+
+/// Taille maximale par défaut d'un fichier d'inspiration analysé (1 Mio)
+const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+/// Configuration du scan d'inspiration, à la racine du dépôt
+pub const DEFAULT_SCAN_CONFIG_PATH: &str = "scan.toml";
+
+// Expressions compilées une seule fois et partagées par les fils du scan; les motifs
+// invalides des tables sont écartés, comme l'étaient les échecs de compilation à chaque appel
+lazy_static! {
+    static ref HEADING_RE: Regex = Regex::new(r"#+\s+(.+)").unwrap();
+    static ref CODE_BLOCK_RE: Regex = Regex::new(r"```(\w*)\n([\s\S]*?)\n```").unwrap();
+    static ref KEYWORD_RE: Regex = Regex::new(r#"([A-Z][A-Z_]+|"([^"]+)")"#).unwrap();
+    static ref EVOLVABLE_RE: Regex = Regex::new(r"//\s*AURORAE-EVOLVABLE:\s*(.+)").unwrap();
+    static ref FN_NAME_RE: Regex = Regex::new(r"fn\s+(\w+)").unwrap();
+    static ref STRUCT_NAME_RE: Regex = Regex::new(r"struct\s+(\w+)").unwrap();
+    static ref TARGET_NAME_RE: Regex = Regex::new(r"(fn|struct)\s+(\w+)").unwrap();
+
+    /// Algorithmes Rust courants: motif, nom, complexités temporelle et spatiale
+    static ref RUST_ALGORITHM_PATTERNS: Vec<(Regex, &'static str, &'static str, &'static str)> = [
+        (r"for\s+.*\s+in\s+.*\.iter\(\).*", "Itération", "O(n)", "O(1)"),
+        (r"\.fold\(.*\)", "Réduction/Agrégation", "O(n)", "O(1)"),
+        (r"\.map\(.*\).*\.filter\(.*\)", "Transformation de données", "O(n)", "O(n)"),
+        (r"\.sort_by\(.*\)|\.sort\(\)", "Tri", "O(n log n)", "O(1)"),
+        (r"let\s+mut\s+.*\s*=\s*HashMap::new\(\);", "Table de hachage", "O(1) moyenne", "O(n)"),
+        (r"\.binary_search\(.*\)", "Recherche binaire", "O(log n)", "O(1)"),
+        (r"fn\s+.*\(.*\).*\{.*\s+if\s+.*\s+{\s+.*\s+}\s+else\s+{\s+.*\s+}\s+.*\}", "Décision conditionnelle", "O(1)", "O(1)"),
+        (r"fn\s+.*\(.*\).*\{.*\s+match\s+.*\s+{\s+.*\s+}\s+.*\}", "Pattern matching", "O(1)", "O(1)"),
+        (r"fn\s+.*\(.*\).*\{.*\s+.*\(.*\).*\s+.*\}", "Récursion", "Varie", "O(n)"),
+        (r"async\s+fn|\.await", "Asynchrone", "Varie", "Varie"),
+        (r"parallel|rayon", "Parallélisme", "O(n/p)", "O(n)"),
+    ].iter().filter_map(|&(pattern, name, time, space)| Regex::new(pattern).ok().map(|re| (re, name, time, space))).collect();
+    /// Algorithmes Python courants
+    static ref PYTHON_ALGORITHM_PATTERNS: Vec<(Regex, &'static str, &'static str, &'static str)> = [
+        (r"for\s+.*\s+in\s+.*:", "Itération", "O(n)", "O(1)"),
+        (r"reduce\(.*\)|sum\(.*\)", "Réduction/Agrégation", "O(n)", "O(1)"),
+        (r"map\(.*\)|filter\(.*\)|list\s+comprehension", "Transformation de données", "O(n)", "O(n)"),
+        (r"sorted\(.*\)|\.sort\(\)", "Tri", "O(n log n)", "O(1)"),
+        (r"dict\(.*\)|{.*:.*}", "Table de hachage", "O(1) moyenne", "O(n)"),
+        (r"binary_search|bisect", "Recherche binaire", "O(log n)", "O(1)"),
+        (r"if\s+.*\s*:\s*.*\s*else\s*:", "Décision conditionnelle", "O(1)", "O(1)"),
+        (r"def\s+.*\(.*\).*:\s*.*\s+return\s+.*\(.*\)", "Récursion", "Varie", "O(n)"),
+        (r"async\s+def|await", "Asynchrone", "Varie", "Varie"),
+        (r"parallel|multiprocessing|concurrent", "Parallélisme", "O(n/p)", "O(n)"),
+        (r"@lru_cache|memoize", "Mémoïsation", "Amélioré", "O(n)"),
+    ].iter().filter_map(|&(pattern, name, time, space)| Regex::new(pattern).ok().map(|re| (re, name, time, space))).collect();
+    /// Problèmes de performance courants: motif, description, score
+    static ref PERFORMANCE_PATTERNS: Vec<(Regex, &'static str, f32)> = [
+        (r"for\s+.*\s+in\s+.*\.clone\(\)", "Clonage inutile dans une boucle", 0.8),
+        (r"let\s+mut\s+.*\s*=\s*Vec::new\(\);\s+for\s+.*\s+{\s+.*\.push\(.*\);\s+}", "Utiliser un constructeur de collection au lieu de push répétés", 0.7),
+        (r"\.to_string\(\).*\.to_string\(\)", "Conversions de chaînes multiples", 0.6),
+        (r"for\s+i\s+in\s+0\.\..*.len\(\)\s+{.*\[i\]", "Utiliser une itération directe plutôt que des indices", 0.7),
+        (r"if\s+let\s+Some\(.*\)\s+=\s+.*\s+{\s+.*\s+}\s+if\s+let\s+Some\(.*\)\s+=\s+.*\s+{", "Combiner des if let multiples", 0.5),
+    ].iter().filter_map(|&(pattern, description, score)| Regex::new(pattern).ok().map(|re| (re, description, score))).collect();
+    /// Problèmes de sécurité courants
+    static ref SECURITY_PATTERNS: Vec<(Regex, &'static str, f32)> = [
+        (r"unsafe\s+{", "Bloc unsafe non protégé", 0.9),
+        (r"let\s+.*\s*=\s*String::from\(.*input.*\)", "Entrée utilisateur non validée", 0.85),
+        (r"\.unwrap\(\)", "Gestion d'erreur avec unwrap()", 0.7),
+        (r"panic!\(", "Utilisation de panic!", 0.6),
+        (r"std::mem::transmute", "Utilisation de transmute", 0.95),
+    ].iter().filter_map(|&(pattern, description, score)| Regex::new(pattern).ok().map(|re| (re, description, score))).collect();
+    /// Problèmes de qualité de code courants
+    static ref QUALITY_PATTERNS: Vec<(Regex, &'static str, f32)> = [
+        (r"fn\s+\w+[^{]*\{[^}]{500,}\}", "Fonction trop longue", 0.8),
+        (r"//\s*TODO|//\s*FIXME", "TODO ou FIXME non résolu", 0.6),
+        (r"if\s+.*\s+{\s+.*\s+}\s+else\s+if\s+.*\s+{\s+.*\s+}\s+else\s+if\s+.*\s+{\s+.*\s+}\s+else\s+if", "Cascade if-else-if trop longue", 0.7),
+        (r"match\s+.*\s+{\s+.*_\s+=>\s+.*,", "Match avec clause catch-all", 0.5),
+        (r"\s{4,}//", "Commentaire mal aligné", 0.4),
+    ].iter().filter_map(|&(pattern, description, score)| Regex::new(pattern).ok().map(|re| (re, description, score))).collect();
+}
+
+/// Modules privés de génération de code (quarantaine du gardien)
+#[derive(Debug, Default)]
+pub struct CodePrivileges {
+    revoked: HashSet<String>,
+}
+
+impl CodePrivileges {
+    pub fn revoke(&mut self, module: &str) {
+        if self.revoked.insert(module.to_string()) {
+            warn!("🚫 Privilèges de génération de code révoqués pour {}", module);
+        }
+    }
+
+    pub fn restore(&mut self, module: &str) {
+        if self.revoked.remove(module) {
+            info!("✅ Privilèges de génération de code rétablis pour {}", module);
+        }
+    }
+
+    pub fn is_revoked(&self, module: &str) -> bool {
+        self.revoked.contains(module)
+    }
+}
+
+/// Préfixe des sources de connaissances qui ne sont pas des fichiers du dossier d'inspiration
+pub const EXTERNAL_SOURCE_PREFIX: &str = "external:";
+
+/// Privilèges partagés entre l'évolution de code et le gardien
+pub type SharedCodePrivileges = Arc<RwLock<CodePrivileges>>;
 
 /// Système d'évolution de code qui permet à AURORAE++ de se modifier et s'améliorer
 pub struct CodeEvolution {
@@ -22,14 +140,17 @@ pub struct CodeEvolution {
     source_path: PathBuf,
     /// Base de connaissances dérivée des sources d'inspiration
     knowledge_base: KnowledgeBase,
+    /// Stockage persistant de la base (scan incrémental); reconstruction complète sans lui
+    store: Option<KnowledgeStore>,
+    /// Filtres et parallélisme du scan d'inspiration
+    scan_config: ScanConfig,
+    /// Licences des sources admises dans le code généré
+    license_policy: LicensePolicy,
+    /// Modèle de plongement et index vectoriel des fragments et concepts
+    embedder: Arc<dyn EmbeddingProvider>,
+    semantic_index: SemanticIndex,
     /// Historique des modifications
     modification_history: Vec<CodeModification>,
-    /// Sécurité du système pour vérifier les modifications
-    security: Arc<Mutex<SecuritySystem>>,
-    /// Cerveau de l'IA pour les décisions
-    brain: Arc<Mutex<BrainCore>>,
-    /// Environnement d'exécution sandbox pour tester les modifications
-    sandbox: Option<VirtualMachine>,
     /// Règles pour déterminer quelles parties du code peuvent être modifiées
     modification_rules: Vec<ModificationRule>,
     /// Métriques de performance pour évaluer les améliorations
@@ -38,6 +159,8 @@ pub struct CodeEvolution {
     evolution_strategies: HashMap<String, EvolutionStrategy>,
     /// Niveau d'auto-amélioration actuel
     self_improvement_level: u32,
+    /// Modules dont le code ne peut plus être modifié
+    privileges: SharedCodePrivileges,
 }
 
 /// Base de connaissances extraite des sources d'inspiration
@@ -55,6 +178,16 @@ pub struct KnowledgeBase {
     pub last_update: Option<SystemTime>,
 }
 
+impl KnowledgeBase {
+    /// Ajoute les connaissances extraites d'un fichier (sans stockage persistant)
+    pub fn merge(&mut self, other: KnowledgeBase) {
+        self.concepts.extend(other.concepts);
+        self.code_fragments.extend(other.code_fragments);
+        self.algorithms.extend(other.algorithms);
+        self.concept_graph.extend(other.concept_graph);
+    }
+}
+
 /// Représentation d'un concept ou d'une idée
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Concept {
@@ -77,6 +210,9 @@ pub struct CodeFragment {
     pub complexity: f32,
     pub tags: Vec<String>,
     pub performance_score: Option<f32>,
+    /// Dépôt, commit et licence de la source (fichiers du feed du crawler)
+    #[serde(default)]
+    pub provenance: Option<SourceProvenance>,
 }
 
 /// Algorithme identifié
@@ -147,32 +283,160 @@ pub struct EvolutionStrategy {
     pub algorithm_preferences: Vec<String>,
 }
 
+/// Filtres et parallélisme du scan d'inspiration (`scan.toml`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ScanConfig {
+    /// Fichiers plus gros ignorés, en octets
+    pub max_file_size: u64,
+    /// Extensions analysées (sans le point)
+    pub extensions: Vec<String>,
+    /// Dossiers jamais parcourus (target, .git…)
+    pub exclude_dirs: Vec<String>,
+    /// Fils du scan, 0 pour un par cœur
+    pub threads: usize,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            extensions: ["md", "rs", "py", "go", "sol", "json", "txt"].iter().map(|e| e.to_string()).collect(),
+            exclude_dirs: ["target", ".git", "node_modules"].iter().map(|d| d.to_string()).collect(),
+            threads: 0,
+        }
+    }
+}
+
+impl ScanConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Configuration de scan invalide: {}", e))
+    }
+
+    /// `scan.toml`, ou la configuration par défaut s'il est absent ou invalide
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_SCAN_CONFIG_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: filtres de scan par défaut", e);
+            Self::default()
+        })
+    }
+
+    pub fn accepts_extension(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .map_or(false, |ext| self.extensions.iter().any(|allowed| allowed.eq_ignore_ascii_case(ext)))
+    }
+
+    pub fn accepts_size(&self, size: u64) -> bool {
+        size <= self.max_file_size
+    }
+
+    fn is_excluded_dir(&self, name: &str) -> bool {
+        self.exclude_dirs.iter().any(|dir| dir == name)
+    }
+
+    /// Vrai si un fichier signalé sous `root` serait retenu par le parcours complet; les
+    /// fichiers trop gros sont comptés dans `stats.skipped`
+    pub fn accepts_path(&self, root: &Path, path: &Path, stats: &mut ScanStats) -> bool {
+        let Ok(relative) = path.strip_prefix(root) else { return false };
+        let excluded = relative.parent().is_some_and(|dirs| {
+            dirs.components().any(|c| self.is_excluded_dir(&c.as_os_str().to_string_lossy()))
+        });
+        if excluded || path.file_name().is_some_and(|name| name == PROVENANCE_FILE) || !self.accepts_extension(path) {
+            return false;
+        }
+        let size = fs::metadata(path).map(|m| m.len()).unwrap_or(u64::MAX);
+        if !self.accepts_size(size) {
+            stats.skipped += 1;
+            return false;
+        }
+        true
+    }
+
+    /// Fichiers à analyser sous `root`, dans l'ordre du parcours; les fichiers trop gros
+    /// sont comptés dans `stats.skipped`
+    pub fn collect_files(&self, root: &Path, stats: &mut ScanStats) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        let walker = WalkDir::new(root).sort_by_file_name().into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_dir()
+                    || !self.is_excluded_dir(&entry.file_name().to_string_lossy())
+            });
+        for entry in walker.filter_map(Result::ok) {
+            if !entry.file_type().is_file() || entry.file_name() == PROVENANCE_FILE || !self.accepts_extension(entry.path()) {
+                continue;
+            }
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(u64::MAX);
+            if !self.accepts_size(size) {
+                stats.skipped += 1;
+                continue;
+            }
+            files.push(entry.into_path());
+        }
+        files
+    }
+
+    fn thread_pool(&self) -> Result<rayon::ThreadPool, String> {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(self.threads)
+            .thread_name(|i| format!("aurorae-scan-{}", i))
+            .build()
+            .map_err(|e| format!("Pool de scan indisponible: {}", e))
+    }
+}
+
+/// Barre de progression du scan (masquée hors terminal)
+fn scan_progress(total: usize) -> ProgressBar {
+    let progress = ProgressBar::new(total as u64);
+    if let Ok(style) = ProgressStyle::with_template("[EVOLUTION] {bar:40} {pos}/{len} fichiers ({per_sec}, reste {eta})") {
+        progress.set_style(style);
+    }
+    progress
+}
+
+/// Résultat de l'analyse d'un fichier par un fil du scan
+enum FileScan {
+    Unchanged,
+    Parsed { key: String, hash: String, knowledge: KnowledgeBase },
+    Failed { key: String, error: String },
+}
+
 impl CodeEvolution {
     /// Crée une nouvelle instance du système d'évolution de code
-    pub fn new(
-        inspiration_path: &str, 
-        source_path: &str,
-        security: Arc<Mutex<SecuritySystem>>,
-        brain: Arc<Mutex<BrainCore>>
-    ) -> Self {
+    pub fn new(inspiration_path: &str, source_path: &str) -> Self {
         let inspiration = PathBuf::from(inspiration_path);
         let source = PathBuf::from(source_path);
         
-        println!("[EVOLUTION] 🧬 Initialisation du système d'évolution de code");
-        println!("[EVOLUTION] 📂 Dossier d'inspiration: {}", inspiration.display());
+        info!("🧬 Initialisation du système d'évolution de code");
+        info!("📂 Dossier d'inspiration: {}", inspiration.display());
         
+        let store = KnowledgeStore::open_default()
+            .map_err(|e| warn!("⚠️ Base de connaissances persistante indisponible: {}", e))
+            .ok();
+        let knowledge_base = store.as_ref().and_then(|store| store.load().ok()).unwrap_or_default();
+
         let mut evolution = Self {
             inspiration_path: inspiration,
             source_path: source,
-            knowledge_base: KnowledgeBase::default(),
+            knowledge_base,
+            store,
+            scan_config: ScanConfig::load_or_default(),
+            license_policy: LicensePolicy::load_or_default(),
+            embedder: Arc::new(LocalEmbedder::default()),
+            semantic_index: SemanticIndex::new("local-hashing"),
             modification_history: Vec::new(),
-            security,
-            brain,
-            sandbox: None,
             modification_rules: Vec::new(),
             performance_metrics: PerformanceMetrics::default(),
             evolution_strategies: HashMap::new(),
             self_improvement_level: 1,
+            privileges: SharedCodePrivileges::default(),
         };
         
         // Initialiser les règles de base
@@ -184,6 +448,70 @@ impl CodeEvolution {
         evolution
     }
     
+    /// Remplace les filtres du scan chargés depuis `scan.toml`
+    pub fn set_scan_config(&mut self, config: ScanConfig) {
+        self.scan_config = config;
+    }
+    
+    /// Remplace la politique de licences chargée depuis `licenses.toml`
+    pub fn set_license_policy(&mut self, policy: LicensePolicy) {
+        self.license_policy = policy;
+    }
+    
+    /// Handle partagé des privilèges à transmettre au gardien
+    pub fn privileges_handle(&self) -> SharedCodePrivileges {
+        self.privileges.clone()
+    }
+
+    /// Vrai si une règle correspondant aux changements exige une vérification formelle
+    /// (ou si aucune règle ne s'applique)
+    fn requires_formal_verification(&self, modification: &CodeModification) -> bool {
+        let matching: Vec<&ModificationRule> = self.modification_rules.iter()
+            .filter(|rule| Regex::new(&rule.pattern).map(|re| re.is_match(&modification.changes)).unwrap_or(false))
+            .collect();
+        matching.is_empty() || matching.iter().any(|rule| rule.require_formal_verification)
+    }
+
+    /// Accepte une modification (`changes` = nouvelle source de `target_file`) si les
+    /// propriétés générées pour le module passent; sinon elle est refusée et journalisée
+    pub fn accept_modification(&mut self, mut modification: CodeModification) -> Result<(), String> {
+        if self.requires_formal_verification(&modification) {
+            let report = PropertyHarness::default().run(&modification.target_file, &modification.changes)?;
+            if !report.passed {
+                modification.verification_status = VerificationStatus::Failed;
+                self.performance_metrics.failed_modifications += 1;
+                self.modification_history.push(modification.clone());
+                return Err(format!("Modification {} refusée: propriétés en échec ({})",
+                                   modification.id, report.failures.join(", ")));
+            }
+        }
+
+        fs::write(&modification.target_file, &modification.changes)
+            .map_err(|e| format!("Erreur d'écriture {}: {}", modification.target_file, e))?;
+        modification.verification_status = VerificationStatus::VerifiedSafe;
+        self.performance_metrics.successful_modifications += 1;
+        record_source_change(modification.id, &modification.target_file, &modification.description, "code_evolution");
+        audit::record(
+            AuditKind::CodeMutated,
+            "code_evolution",
+            &modification.description,
+            serde_json::json!({ "modification": modification.id, "file": modification.target_file }),
+        );
+        info!("✅ Modification {} appliquée à {}", modification.id, modification.target_file);
+        self.modification_history.push(modification);
+        Ok(())
+    }
+
+    /// Annule une modification appliquée: révision précédente du fichier puis recompilation
+    pub fn revert_modification(&mut self, id: Uuid) -> Result<(), String> {
+        let reverted = rollback::revert_modification(id)?;
+        if let Some(modification) = self.modification_history.iter_mut().find(|m| m.id == id) {
+            modification.verification_status = VerificationStatus::Reverted;
+        }
+        info!("⏪ Modification {} annulée ({})", id, reverted.target_file);
+        Ok(())
+    }
+
     /// Initialise les règles de modification par défaut
     fn initialize_modification_rules(&mut self) {
         let default_rules = vec![
@@ -276,35 +604,128 @@ impl CodeEvolution {
         }
     }
     
-    /// Analyse le dossier d'inspiration et construit une base de connaissances
+    /// Analyse le dossier d'inspiration et met à jour la base de connaissances: seuls les
+    /// fichiers dont l'empreinte a changé sont ré-analysés (en parallèle, filtrés selon
+    /// `scan.toml`), les fichiers disparus sont oubliés
     pub fn scan_inspiration_folder(&mut self) -> Result<(), String> {
-        println!("[EVOLUTION] 🔍 Scan du dossier d'inspiration en cours...");
+        info!("🔍 Scan du dossier d'inspiration en cours...");
         
         if !self.inspiration_path.exists() {
             return Err(format!("Le dossier d'inspiration n'existe pas: {}", self.inspiration_path.display()));
         }
         
-        let mut new_knowledge_base = KnowledgeBase::default();
-        new_knowledge_base.last_update = Some(SystemTime::now());
+        let mut stats = ScanStats::default();
+        let files = self.scan_config.collect_files(&self.inspiration_path, &mut stats);
+        let seen: HashSet<String> = files.iter().map(|path| path.to_string_lossy().to_string()).collect();
+        let mut new_knowledge_base = self.scan_files(&files, &mut stats)?;
         
-        // Parcourir tous les fichiers du dossier d'inspiration
-        for entry in WalkDir::new(&self.inspiration_path).into_iter().filter_map(Result::ok) {
-            if entry.file_type().is_file() {
-                let file_path = entry.path();
-                
-                // Traiter le fichier en fonction de son extension
-                if let Some(extension) = file_path.extension() {
-                    match extension.to_str().unwrap_or("") {
-                        "md" => self.process_markdown_file(file_path, &mut new_knowledge_base)?,
-                        "rs" => self.process_rust_file(file_path, &mut new_knowledge_base)?,
-                        "py" => self.process_python_file(file_path, &mut new_knowledge_base)?,
-                        "json" => self.process_json_file(file_path, &mut new_knowledge_base)?,
-                        "txt" => self.process_text_file(file_path, &mut new_knowledge_base)?,
-                        _ => { /* Ignorer les autres types de fichiers */ }
+        if let Some(store) = &self.store {
+            for file in store.tracked_files()? {
+                if !seen.contains(&file) && !file.starts_with(EXTERNAL_SOURCE_PREFIX) {
+                    store.forget_file(&file)?;
+                    stats.removed += 1;
+                }
+            }
+            store.flush()?;
+            new_knowledge_base = store.load()?;
+        }
+        self.finish_scan(new_knowledge_base, &stats)
+    }
+    
+    /// Scan limité aux fichiers signalés par le crawler (ajoutés, modifiés ou supprimés depuis
+    /// la dernière extraction); sans stockage persistant, ou tant qu'aucun fichier du dossier n'y
+    /// a été enregistré (feed cloné avant la base), le dossier entier est analysé
+    pub fn scan_changes(&mut self, changes: &FeedChanges) -> Result<(), String> {
+        let tracked = match &self.store {
+            Some(store) => store.tracked_files()?,
+            None => Vec::new(),
+        };
+        if tracked.iter().all(|file| file.starts_with(EXTERNAL_SOURCE_PREFIX)) {
+            return self.scan_inspiration_folder();
+        }
+        let Some(store) = &self.store else { return self.scan_inspiration_folder() };
+        info!("🔍 Scan de {} fichier(s) modifié(s) et {} supprimé(s)...", changes.changed.len(), changes.removed.len());
+        
+        let mut stats = ScanStats::default();
+        let files: Vec<PathBuf> = changes.changed.iter()
+            .filter(|path| self.scan_config.accepts_path(&self.inspiration_path, path, &mut stats))
+            .cloned()
+            .collect();
+        self.scan_files(&files, &mut stats)?;
+        
+        for removed in &changes.removed {
+            let key = removed.to_string_lossy().to_string();
+            if store.file(&key)?.is_some() {
+                store.forget_file(&key)?;
+                stats.removed += 1;
+            }
+        }
+        store.flush()?;
+        let new_knowledge_base = store.load()?;
+        self.finish_scan(new_knowledge_base, &stats)
+    }
+    
+    /// Intègre des concepts venus d'ailleurs que le dossier d'inspiration (profils de protocoles
+    /// de l'explorateur…), rangés sous `external:<source>` pour survivre aux scans complets
+    pub fn ingest_external_concepts(&mut self, source: &str, concepts: Vec<Concept>) -> Result<(), String> {
+        let key = format!("{}{}", EXTERNAL_SOURCE_PREFIX, source);
+        let mut knowledge = KnowledgeBase::default();
+        for mut concept in concepts {
+            if concept.source_files.is_empty() {
+                concept.source_files.push(key.clone());
+            }
+            knowledge.concepts.insert(concept.name.clone(), concept);
+        }
+        if let Some(store) = &self.store {
+            let content = serde_json::to_vec(&knowledge.concepts.values().map(|c| (&c.name, &c.description)).collect::<Vec<_>>())
+                .map_err(|e| e.to_string())?;
+            store.ingest_file(&key, &content_hash(&content), &knowledge)?;
+            store.flush()?;
+        }
+        info!("🛰️ {} concept(s) intégré(s) depuis {}", knowledge.concepts.len(), source);
+        self.knowledge_base.concepts.extend(knowledge.concepts);
+        Ok(())
+    }
+    
+    /// Lecture, empreinte et analyse en parallèle; l'écriture dans le stockage reste séquentielle.
+    /// Sans stockage, les connaissances extraites sont retournées fusionnées
+    fn scan_files(&self, files: &[PathBuf], stats: &mut ScanStats) -> Result<KnowledgeBase, String> {
+        let progress = scan_progress(files.len());
+        let store = self.store.as_ref();
+        let outcomes: Vec<FileScan> = self.scan_config.thread_pool()?.install(|| {
+            files.par_iter()
+                .map(|path| {
+                    let outcome = Self::scan_file(path, store);
+                    progress.inc(1);
+                    outcome
+                })
+                .collect()
+        });
+        progress.finish_and_clear();
+        
+        let mut new_knowledge_base = KnowledgeBase::default();
+        for outcome in outcomes {
+            match outcome {
+                FileScan::Unchanged => stats.unchanged += 1,
+                FileScan::Failed { key, error } => {
+                    warn!("⚠️ {} ignoré: {}", key, error);
+                    stats.skipped += 1;
+                }
+                FileScan::Parsed { key, hash, knowledge } => {
+                    match &self.store {
+                        Some(store) => store.ingest_file(&key, &hash, &knowledge)?,
+                        None => new_knowledge_base.merge(knowledge),
                     }
+                    stats.parsed += 1;
                 }
             }
         }
+        Ok(new_knowledge_base)
+    }
+    
+    /// Relations entre concepts et méta-insights, puis remplacement de la base
+    fn finish_scan(&mut self, mut new_knowledge_base: KnowledgeBase, stats: &ScanStats) -> Result<(), String> {
+        new_knowledge_base.last_update = Some(SystemTime::now());
         
         // Analyser les relations entre concepts
         self.analyze_concept_relationships(&mut new_knowledge_base);
@@ -315,24 +736,131 @@ impl CodeEvolution {
         // Remplacer l'ancienne base par la nouvelle
         self.knowledge_base = new_knowledge_base;
         
-        println!("[EVOLUTION] ✅ Scan terminé! Base de connaissances mise à jour:");
-        println!("[EVOLUTION] - {} concepts identifiés", self.knowledge_base.concepts.len());
-        println!("[EVOLUTION] - {} fragments de code extraits", self.knowledge_base.code_fragments.len());
-        println!("[EVOLUTION] - {} algorithmes reconnus", self.knowledge_base.algorithms.len());
+        info!(
+            "✅ Scan terminé: {} fichier(s) analysé(s), {} inchangé(s), {} retiré(s), {} ignoré(s) • {} concepts, {} fragments, {} algorithmes",
+            stats.parsed, stats.unchanged, stats.removed, stats.skipped,
+            self.knowledge_base.concepts.len(),
+            self.knowledge_base.code_fragments.len(),
+            self.knowledge_base.algorithms.len()
+        );
         
         Ok(())
     }
     
-    /// Traite un fichier Markdown pour en extraire des concepts
-    fn process_markdown_file(&self, file_path: &Path, kb: &mut KnowledgeBase) -> Result<(), String> {
-        let mut content = String::new();
-        File::open(file_path)
-            .and_then(|mut file| file.read_to_string(&mut content))
-            .map_err(|e| format!("Erreur de lecture du fichier Markdown: {}", e))?;
+    /// Change de modèle de plongement (OpenAI…); l'index est reconstruit à la prochaine recherche
+    pub fn set_embedding_provider(&mut self, embedder: Arc<dyn EmbeddingProvider>) {
+        self.semantic_index = SemanticIndex::new(embedder.name());
+        self.embedder = embedder;
+    }
+    
+    /// Textes indexés: description et code des fragments, nom et description des concepts
+    fn semantic_items(&self) -> Vec<(ItemKind, String, String)> {
+        let fragments = self.knowledge_base.code_fragments.iter()
+            .map(|f| (ItemKind::Fragment, f.id.to_string(), format!("{}\n{}", f.description, f.code)));
+        let concepts = self.knowledge_base.concepts.values()
+            .map(|c| (ItemKind::Concept, c.name.clone(), format!("{}\n{}", c.name, c.description)));
+        fragments.chain(concepts).collect()
+    }
+    
+    /// Plonge les fragments et concepts nouveaux ou modifiés (cache du stockage persistant),
+    /// retire ceux qui ont disparu; renvoie le nombre de textes envoyés au modèle
+    pub async fn refresh_embeddings(&mut self) -> Result<usize, String> {
+        let provider = self.embedder.name().to_string();
+        let items = self.semantic_items();
+        let live: Vec<(ItemKind, String)> = items.iter().map(|(kind, id, _)| (*kind, id.clone())).collect();
+        self.semantic_index.retain_live(&live);
+        
+        let mut pending = Vec::new();
+        for (kind, id, text) in items {
+            let hash = content_hash(text.as_bytes());
+            if self.semantic_index.contains(kind, &id, &hash) {
+                continue;
+            }
+            match self.store.as_ref().map(|store| store.embedding(&provider, &hash)).transpose()?.flatten() {
+                Some(vector) => self.semantic_index.upsert(IndexEntry { kind, id, content_hash: hash, vector }),
+                None => pending.push((kind, id, hash, text)),
+            }
+        }
         
+        let embedded = pending.len();
+        for batch in pending.chunks(EMBEDDING_BATCH) {
+            let texts: Vec<String> = batch.iter().map(|(_, _, _, text)| text.clone()).collect();
+            let vectors = self.embedder.embed(&texts).await?;
+            for ((kind, id, hash, _), vector) in batch.iter().zip(vectors) {
+                if let Some(store) = &self.store {
+                    store.put_embedding(&provider, hash, &vector)?;
+                }
+                self.semantic_index.upsert(IndexEntry { kind: *kind, id: id.clone(), content_hash: hash.clone(), vector });
+            }
+        }
+        if embedded > 0 {
+            info!("🧭 {} élément(s) plongé(s) avec {}", embedded, provider);
+        }
+        Ok(embedded)
+    }
+    
+    /// `k` fragments ou concepts les plus proches sémantiquement d'un code ou d'un texte
+    pub async fn search_similar(&mut self, code_or_text: &str, k: usize) -> Result<Vec<SimilarItem>, String> {
+        self.search_kind(code_or_text, k, None).await
+    }
+    
+    async fn search_kind(&mut self, code_or_text: &str, k: usize, kind: Option<ItemKind>) -> Result<Vec<SimilarItem>, String> {
+        self.refresh_embeddings().await?;
+        let query = self.embedder.embed(&[code_or_text.to_string()]).await?
+            .pop()
+            .ok_or("Plongement de la requête impossible")?;
+        Ok(self.semantic_index.search(&query, k, kind))
+    }
+    
+    /// Lit et analyse un fichier; exécuté en parallèle, ne touche qu'au stockage en lecture
+    fn scan_file(path: &Path, store: Option<&KnowledgeStore>) -> FileScan {
+        let key = path.to_string_lossy().to_string();
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => return FileScan::Failed { key, error: format!("lecture impossible: {}", e) },
+        };
+        let hash = content_hash(&bytes);
+        match store.map(|store| store.is_current(&key, &hash)).transpose() {
+            Ok(Some(true)) => return FileScan::Unchanged,
+            Ok(_) => {}
+            Err(error) => return FileScan::Failed { key, error },
+        }
+        let content = match String::from_utf8(bytes) {
+            Ok(content) => content,
+            Err(_) => return FileScan::Failed { key, error: "contenu non UTF-8".to_string() },
+        };
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+        let mut knowledge = KnowledgeBase::default();
+        match Self::process_file(path, &extension, &content, &mut knowledge) {
+            Ok(()) => {
+                if let Some(provenance) = SourceProvenance::find_for(path) {
+                    for fragment in &mut knowledge.code_fragments {
+                        fragment.provenance = Some(provenance.clone());
+                    }
+                }
+                FileScan::Parsed { key, hash, knowledge }
+            }
+            Err(error) => FileScan::Failed { key, error },
+        }
+    }
+    
+    /// Traite un fichier en fonction de son extension
+    fn process_file(file_path: &Path, extension: &str, content: &str, kb: &mut KnowledgeBase) -> Result<(), String> {
+        if let Some(language) = SourceLanguage::from_extension(extension) {
+            return Self::process_source_file(file_path, language, content, kb);
+        }
+        match extension {
+            "md" => Self::process_markdown_file(file_path, content, kb),
+            "json" => Self::process_json_file(file_path, content, kb),
+            "txt" => Self::process_text_file(file_path, content, kb),
+            _ => Ok(()),
+        }
+    }
+    
+    /// Traite un fichier Markdown pour en extraire des concepts
+    fn process_markdown_file(file_path: &Path, content: &str, kb: &mut KnowledgeBase) -> Result<(), String> {
         // Extraire les titres comme concepts
-        let heading_regex = Regex::new(r"#+\s+(.+)").unwrap();
-        for cap in heading_regex.captures_iter(&content) {
+        for cap in HEADING_RE.captures_iter(content) {
             let concept_name = cap[1].to_string();
             
             // Extraire le contexte autour du titre
@@ -355,8 +883,7 @@ impl CodeEvolution {
         }
         
         // Extraire les blocs de code
-        let code_regex = Regex::new(r"```(\w*)\n([\s\S]*?)\n```").unwrap();
-        for cap in code_regex.captures_iter(&content) {
+        for cap in CODE_BLOCK_RE.captures_iter(content) {
             let language = cap[1].to_string();
             let code = cap[2].to_string();
             
@@ -378,6 +905,7 @@ impl CodeEvolution {
                 complexity: 0.5, // Valeur initiale
                 tags: Vec::new(),
                 performance_score: None,
+                provenance: None,
             };
             
             kb.code_fragments.push(fragment);
@@ -386,221 +914,103 @@ impl CodeEvolution {
         Ok(())
     }
     
-    /// Traite un fichier Rust pour en extraire des fragments de code et des algorithmes
-    fn process_rust_file(&self, file_path: &Path, kb: &mut KnowledgeBase) -> Result<(), String> {
-        let mut content = String::new();
-        File::open(file_path)
-            .and_then(|mut file| file.read_to_string(&mut content))
-            .map_err(|e| format!("Erreur de lecture du fichier Rust: {}", e))?;
+    /// Traite un fichier source (Rust, Python, Go, Solidity) à partir de ses éléments
+    /// syntaxiques: fragments de code, concepts pour les types et algorithmes pour les
+    /// fonctions qui bouclent ou s'appellent elles-mêmes
+    fn process_source_file(file_path: &Path, language: SourceLanguage, content: &str, kb: &mut KnowledgeBase) -> Result<(), String> {
+        let source_file = file_path.to_string_lossy().to_string();
+        let file_name = file_path.file_name().map_or_else(String::new, |n| n.to_string_lossy().to_string());
         
-        // Extraire les structures
-        let struct_regex = Regex::new(r"struct\s+(\w+)(?:<[^>]*>)?\s*\{([\s\S]*?)\}").unwrap();
-        for cap in struct_regex.captures_iter(&content) {
-            let struct_name = cap[1].to_string();
-            let struct_body = cap[2].to_string();
+        for item in syntax_tree::extract_items_from_source(language, content)? {
+            let lines = item.code.lines().count() as f32;
+            let label = match item.kind {
+                SyntaxKind::Function => "Fonction",
+                SyntaxKind::Method => "Méthode",
+                SyntaxKind::Struct => "Structure",
+                SyntaxKind::Enum => "Énumération",
+                SyntaxKind::Trait => "Trait",
+                SyntaxKind::Interface => "Interface",
+                SyntaxKind::Impl => "Implémentation",
+                SyntaxKind::Class => "Classe",
+                SyntaxKind::Contract => "Contrat",
+                SyntaxKind::Type => "Type",
+            };
+            let description = item.doc.clone().unwrap_or_else(|| match item.kind {
+                SyntaxKind::Impl => format!("Implémentation pour {}", item.name),
+                _ => format!("{} {} '{}'", label, language, item.qualified_name()),
+            });
             
-            // Extraire les commentaires de documentation
-            let doc_regex = Regex::new(r"///\s*(.+)").unwrap();
-            let mut description = String::new();
-            for doc in doc_regex.captures_iter(&content[..cap.get(0).unwrap().start()]) {
-                description.push_str(&doc[1]);
-                description.push('\n');
-            }
+            // Les méthodes gardent l'étiquette « function » attendue par l'analyse des algorithmes
+            let kind_tag = if item.kind.is_callable() { "function" } else { item.kind.label() };
+            let mut tags = vec![kind_tag.to_string(), item.name.clone()];
+            tags.extend(item.calls.iter().map(|callee| format!("appelle:{}", callee)));
             
             let fragment = CodeFragment {
                 id: Uuid::new_v4(),
-                code: format!("struct {} {{\n{}\n}}", struct_name, struct_body),
-                language: "rust".to_string(),
-                description: if description.is_empty() { format!("Structure {}", struct_name) } else { description },
-                source_file: file_path.to_string_lossy().to_string(),
-                complexity: struct_body.lines().count() as f32 / 10.0,
-                tags: vec!["struct".to_string(), struct_name.clone()],
+                code: item.code.clone(),
+                language: language.name().to_string(),
+                description,
+                source_file: source_file.clone(),
+                complexity: lines / 10.0,
+                tags,
                 performance_score: None,
+                provenance: None,
             };
             
-            kb.code_fragments.push(fragment);
-            
-            // Ajouter comme concept
-            if !kb.concepts.contains_key(&struct_name) {
+            if matches!(item.kind, SyntaxKind::Struct | SyntaxKind::Class | SyntaxKind::Contract | SyntaxKind::Interface)
+                && !kb.concepts.contains_key(&item.name) {
                 let concept = Concept {
-                    name: struct_name.clone(),
-                    description: format!("Structure de données Rust '{}'", struct_name),
+                    name: item.name.clone(),
+                    description: format!("{} {} '{}'", label, language, item.name),
                     relevance: 0.7,
-                    complexity: struct_body.lines().count() as f32 / 20.0,
-                    source_files: vec![file_path.to_string_lossy().to_string()],
+                    complexity: lines / 20.0,
+                    source_files: vec![source_file.clone()],
                     related_concepts: Vec::new(),
                 };
-                
-                kb.concepts.insert(struct_name, concept);
-            }
-        }
-        
-        // Extraire les implémentations
-        let impl_regex = Regex::new(r"impl(?:<[^>]*>)?\s+(\w+)(?:\s+for\s+(\w+))?\s*\{([\s\S]*?)\}").unwrap();
-        for cap in impl_regex.captures_iter(&content) {
-            let impl_name = if let Some(for_type) = cap.get(2) {
-                format!("{} for {}", cap[1].to_string(), for_type.as_str())
-            } else {
-                cap[1].to_string()
-            };
-            
-            let impl_body = cap[3].to_string();
-            
-            let fragment = CodeFragment {
-                id: Uuid::new_v4(),
-                code: format!("impl {} {{\n{}\n}}", impl_name, impl_body),
-                language: "rust".to_string(),
-                description: format!("Implémentation pour {}", impl_name),
-                source_file: file_path.to_string_lossy().to_string(),
-                complexity: impl_body.lines().count() as f32 / 15.0,
-                tags: vec!["impl".to_string(), impl_name.clone()],
-                performance_score: None,
-            };
-            
-            kb.code_fragments.push(fragment);
-        }
-        
-        // Extraire les fonctions
-        let fn_regex = Regex::new(r"fn\s+(\w+)(?:<[^>]*>)?\s*\(([^)]*)\)(?:\s*->\s*([^{]+))?\s*\{([\s\S]*?)(?:^\}|[^\S\r\n]\})").unwrap();
-        for cap in fn_regex.captures_iter(&content) {
-            let fn_name = cap[1].to_string();
-            let fn_params = cap[2].to_string();
-            let fn_return = cap.get(3).map_or("".to_string(), |m| m.as_str().to_string());
-            let fn_body = cap[4].to_string();
-            
-            // Extraire la description de la fonction depuis les commentaires
-            let start_pos = cap.get(0).unwrap().start();
-            let preceding = &content[..start_pos];
-            let doc_start = preceding.rfind("///").unwrap_or(preceding.len());
-            let mut description = String::new();
-            
-            let doc_regex = Regex::new(r"///\s*(.+)").unwrap();
-            for doc in doc_regex.captures_iter(&preceding[doc_start..]) {
-                description.push_str(&doc[1]);
-                description.push('\n');
+                kb.concepts.insert(item.name.clone(), concept);
             }
             
-            let fn_code = format!("fn {}({}){}{{ {} }}", 
-                fn_name, fn_params, 
-                if fn_return.is_empty() { " ".to_string() } else { format!(" -> {} ", fn_return) },
-                fn_body);
-            
-            let fragment = CodeFragment {
-                id: Uuid::new_v4(),
-                code: fn_code.clone(),
-                language: "rust".to_string(),
-                description: if description.is_empty() { format!("Fonction {}", fn_name) } else { description },
-                source_file: file_path.to_string_lossy().to_string(),
-                complexity: fn_body.lines().count() as f32 / 10.0,
-                tags: vec!["function".to_string(), fn_name.clone()],
-                performance_score: None,
-            };
-            
-            kb.code_fragments.push(fragment);
-            
             // Détecter si c'est un algorithme
-            if fn_body.contains("for") || fn_body.contains("while") || fn_body.contains("recursion") {
+            let loops = item.code.contains("for") || item.code.contains("while");
+            if item.kind.is_callable() && (loops || item.is_recursive()) {
                 let algorithm = Algorithm {
-                    name: fn_name.clone(),
-                    purpose: format!("Fonction extraite de {}", file_path.file_name().unwrap().to_string_lossy()),
+                    name: item.name.clone(),
+                    purpose: format!("Fonction extraite de {}", file_name),
                     code_fragments: vec![fragment.id],
                     time_complexity: "O(n)".to_string(), // Estimation par défaut
-                    space_complexity: "O(1)".to_string(), // Estimation par défaut
-                    adaptability: 0.7,
+                    space_complexity: if item.is_recursive() { "O(n)" } else { "O(1)" }.to_string(),
+                    adaptability: match language {
+                        SourceLanguage::Rust => 0.7,
+                        SourceLanguage::Python => 0.6,
+                        _ => 0.65,
+                    },
                 };
                 
                 kb.algorithms.push(algorithm);
             }
-        }
-        
-        Ok(())
-    }
-    
-    /// Traite un fichier Python pour en extraire des fragments de code et des algorithmes
-    fn process_python_file(&self, file_path: &Path, kb: &mut KnowledgeBase) -> Result<(), String> {
-        let mut content = String::new();
-        File::open(file_path)
-            .and_then(|mut file| file.read_to_string(&mut content))
-            .map_err(|e| format!("Erreur de lecture du fichier Python: {}", e))?;
-        
-        // Extraire les classes
-        let class_regex = Regex::new(r"class\s+(\w+)(?:\(([^)]*)\))?\s*:([\s\S]*?)(?:^\S|\Z)").unwrap();
-        for cap in class_regex.captures_iter(&content) {
-            let class_name = cap[1].to_string();
-            let class_body = cap[3].to_string();
-            
-            let fragment = CodeFragment {
-                id: Uuid::new_v4(),
-                code: format!("class {}:\n{}", class_name, class_body),
-                language: "python".to_string(),
-                description: format!("Classe Python '{}'", class_name),
-                source_file: file_path.to_string_lossy().to_string(),
-                complexity: class_body.lines().count() as f32 / 10.0,
-                tags: vec!["class".to_string(), class_name.clone()],
-                performance_score: None,
-            };
             
             kb.code_fragments.push(fragment);
         }
         
-        // Extraire les fonctions
-        let fn_regex = Regex::new(r"def\s+(\w+)\s*\(([^)]*)\)(?:\s*->\s*([^:]+))?\s*:([\s\S]*?)(?:^\S|\Z)").unwrap();
-        for cap in fn_regex.captures_iter(&content) {
-            let fn_name = cap[1].to_string();
-            let fn_params = cap[2].to_string();
-            let fn_body = cap[4].to_string();
-            
-            let fragment = CodeFragment {
-                id: Uuid::new_v4(),
-                code: format!("def {}({}):\n{}", fn_name, fn_params, fn_body),
-                language: "python".to_string(),
-                description: format!("Fonction Python '{}'", fn_name),
-                source_file: file_path.to_string_lossy().to_string(),
-                complexity: fn_body.lines().count() as f32 / 8.0,
-                tags: vec!["function".to_string(), fn_name.clone()],
-                performance_score: None,
-            };
-            
-            kb.code_fragments.push(fragment);
-            
-            // Détecter si c'est un algorithme
-            if fn_body.contains("for") || fn_body.contains("while") || fn_body.contains("recursion") {
-                let algorithm = Algorithm {
-                    name: fn_name.clone(),
-                    purpose: format!("Fonction extraite de {}", file_path.file_name().unwrap().to_string_lossy()),
-                    code_fragments: vec![fragment.id],
-                    time_complexity: "O(n)".to_string(), // Estimation par défaut
-                    space_complexity: "O(1)".to_string(), // Estimation par défaut
-                    adaptability: 0.6,
-                };
-                
-                kb.algorithms.push(algorithm);
-            }
-        }
-        
         Ok(())
     }
     
     /// Traite un fichier JSON pour en extraire des données structurées
-    fn process_json_file(&self, file_path: &Path, kb: &mut KnowledgeBase) -> Result<(), String> {
-        let mut content = String::new();
-        File::open(file_path)
-            .and_then(|mut file| file.read_to_string(&mut content))
-            .map_err(|e| format!("Erreur de lecture du fichier JSON: {}", e))?;
-        
+    fn process_json_file(file_path: &Path, content: &str, kb: &mut KnowledgeBase) -> Result<(), String> {
         // Tenter de parser le JSON
-        match serde_json::from_str::<serde_json::Value>(&content) {
+        match serde_json::from_str::<serde_json::Value>(content) {
             Ok(json) => {
                 // Ajouter le fichier JSON comme fragment de code
                 let fragment = CodeFragment {
                     id: Uuid::new_v4(),
-                    code: content,
+                    code: content.to_string(),
                     language: "json".to_string(),
                     description: format!("Données JSON de {}", file_path.file_name().unwrap().to_string_lossy()),
                     source_file: file_path.to_string_lossy().to_string(),
                     complexity: 0.3,
                     tags: vec!["data".to_string(), "json".to_string()],
                     performance_score: None,
+                    provenance: None,
                 };
                 
                 kb.code_fragments.push(fragment);
@@ -638,29 +1048,24 @@ impl CodeEvolution {
     }
     
     /// Traite un fichier texte pour en extraire des concepts
-    fn process_text_file(&self, file_path: &Path, kb: &mut KnowledgeBase) -> Result<(), String> {
-        let mut content = String::new();
-        File::open(file_path)
-            .and_then(|mut file| file.read_to_string(&mut content))
-            .map_err(|e| format!("Erreur de lecture du fichier texte: {}", e))?;
-        
+    fn process_text_file(file_path: &Path, content: &str, kb: &mut KnowledgeBase) -> Result<(), String> {
         // Ajouter le contenu comme un fragment
         let fragment = CodeFragment {
             id: Uuid::new_v4(),
-            code: content.clone(),
+            code: content.to_string(),
             language: "text".to_string(),
             description: format!("Texte de {}", file_path.file_name().unwrap().to_string_lossy()),
             source_file: file_path.to_string_lossy().to_string(),
             complexity: 0.1,
             tags: vec!["text".to_string()],
             performance_score: None,
+            provenance: None,
         };
         
         kb.code_fragments.push(fragment);
         
         // Extraire des mots-clés potentiels (mots en majuscules ou phrases entre guillemets)
-        let keyword_regex = Regex::new(r#"([A-Z][A-Z_]+|"([^"]+)")"#).unwrap();
-        for cap in keyword_regex.captures_iter(&content) {
+        for cap in KEYWORD_RE.captures_iter(content) {
             let keyword = cap.get(2).map_or_else(|| cap[1].to_string(), |m| m.as_str().to_string());
             
             if keyword.len() > 3 && !kb.concepts.contains_key(&keyword) {
@@ -787,31 +1192,11 @@ impl CodeEvolution {
     
     /// Identifie des algorithmes Rust à partir de fragments de code
     fn identify_rust_algorithms(&self, fragments: &[&CodeFragment], kb: &mut KnowledgeBase) -> Result<(), String> {
-        // Patterns pour des algorithmes courants en Rust
-        let patterns = vec![
-            (r"for\s+.*\s+in\s+.*\.iter\(\).*", "Itération", "O(n)", "O(1)"),
-            (r"\.fold\(.*\)", "Réduction/Agrégation", "O(n)", "O(1)"),
-            (r"\.map\(.*\).*\.filter\(.*\)", "Transformation de données", "O(n)", "O(n)"),
-            (r"\.sort_by\(.*\)|\.sort\(\)", "Tri", "O(n log n)", "O(1)"),
-            (r"let\s+mut\s+.*\s*=\s*HashMap::new\(\);", "Table de hachage", "O(1) moyenne", "O(n)"),
-            (r"\.binary_search\(.*\)", "Recherche binaire", "O(log n)", "O(1)"),
-            (r"fn\s+.*\(.*\).*\{.*\s+if\s+.*\s+{\s+.*\s+}\s+else\s+{\s+.*\s+}\s+.*\}", "Décision conditionnelle", "O(1)", "O(1)"),
-            (r"fn\s+.*\(.*\).*\{.*\s+match\s+.*\s+{\s+.*\s+}\s+.*\}", "Pattern matching", "O(1)", "O(1)"),
-            (r"fn\s+.*\(.*\).*\{.*\s+.*\(.*\).*\s+.*\}", "Récursion", "Varie", "O(n)"),
-            (r"async\s+fn|\.await", "Asynchrone", "Varie", "Varie"),
-            (r"parallel|rayon", "Parallélisme", "O(n/p)", "O(n)"),
-        ];
-        
         // Pour chaque fragment, rechercher des patterns algorithmiques
         for fragment in fragments {
             let mut matched_algorithms = Vec::new();
             
-            for (pattern, name, time, space) in &patterns {
-                let regex = match Regex::new(pattern) {
-                    Ok(r) => r,
-                    Err(_) => continue,
-                };
-                
+            for (regex, name, time, space) in RUST_ALGORITHM_PATTERNS.iter() {
                 if regex.is_match(&fragment.code) {
                     matched_algorithms.push((name, time, space));
                 }
@@ -842,31 +1227,11 @@ impl CodeEvolution {
     
     /// Identifie des algorithmes Python à partir de fragments de code
     fn identify_python_algorithms(&self, fragments: &[&CodeFragment], kb: &mut KnowledgeBase) -> Result<(), String> {
-        // Patterns pour des algorithmes courants en Python
-        let patterns = vec![
-            (r"for\s+.*\s+in\s+.*:", "Itération", "O(n)", "O(1)"),
-            (r"reduce\(.*\)|sum\(.*\)", "Réduction/Agrégation", "O(n)", "O(1)"),
-            (r"map\(.*\)|filter\(.*\)|list\s+comprehension", "Transformation de données", "O(n)", "O(n)"),
-            (r"sorted\(.*\)|\.sort\(\)", "Tri", "O(n log n)", "O(1)"),
-            (r"dict\(.*\)|{.*:.*}", "Table de hachage", "O(1) moyenne", "O(n)"),
-            (r"binary_search|bisect", "Recherche binaire", "O(log n)", "O(1)"),
-            (r"if\s+.*\s*:\s*.*\s*else\s*:", "Décision conditionnelle", "O(1)", "O(1)"),
-            (r"def\s+.*\(.*\).*:\s*.*\s+return\s+.*\(.*\)", "Récursion", "Varie", "O(n)"),
-            (r"async\s+def|await", "Asynchrone", "Varie", "Varie"),
-            (r"parallel|multiprocessing|concurrent", "Parallélisme", "O(n/p)", "O(n)"),
-            (r"@lru_cache|memoize", "Mémoïsation", "Amélioré", "O(n)"),
-        ];
-        
         // Pour chaque fragment, rechercher des patterns algorithmiques
         for fragment in fragments {
             let mut matched_algorithms = Vec::new();
             
-            for (pattern, name, time, space) in &patterns {
-                let regex = match Regex::new(pattern) {
-                    Ok(r) => r,
-                    Err(_) => continue,
-                };
-                
+            for (regex, name, time, space) in PYTHON_ALGORITHM_PATTERNS.iter() {
                 if regex.is_match(&fragment.code) {
                     matched_algorithms.push((name, time, space));
                 }
@@ -897,7 +1262,7 @@ impl CodeEvolution {
     
     /// Identifie les améliorations potentielles dans le code source
     pub fn identify_improvement_opportunities(&self) -> Vec<ImprovementOpportunity> {
-        println!("[EVOLUTION] 🔍 Recherche d'opportunités d'amélioration dans le code...");
+        info!("🔍 Recherche d'opportunités d'amélioration dans le code...");
         
         let mut opportunities = Vec::new();
         
@@ -925,19 +1290,15 @@ impl CodeEvolution {
             };
             
             // Rechercher les sections marquées comme évolutives
-            let evolvable_regex = Regex::new(r"//\s*AURORAE-EVOLVABLE:\s*(.+)").unwrap();
-            for cap in evolvable_regex.captures_iter(&content) {
+            for cap in EVOLVABLE_RE.captures_iter(&content) {
                 let description = cap[1].to_string();
                 let pos = cap.get(0).unwrap().start();
                 
                 // Trouver la prochaine fonction ou structure après ce commentaire
-                let fn_regex = Regex::new(r"fn\s+(\w+)").unwrap();
-                let struct_regex = Regex::new(r"struct\s+(\w+)").unwrap();
-                
                 let section = &content[pos..];
-                let target_name = if let Some(fn_cap) = fn_regex.captures(section) {
+                let target_name = if let Some(fn_cap) = FN_NAME_RE.captures(section) {
                     fn_cap[1].to_string()
-                } else if let Some(struct_cap) = struct_regex.captures(section) {
+                } else if let Some(struct_cap) = STRUCT_NAME_RE.captures(section) {
                     struct_cap[1].to_string()
                 } else {
                     continue;
@@ -983,37 +1344,21 @@ impl CodeEvolution {
         // Trier les opportunités par score d'amélioration
         opportunities.sort_by(|a, b| b.improvement_score.partial_cmp(&a.improvement_score).unwrap());
         
-        println!("[EVOLUTION] ✅ {} opportunités d'amélioration identifiées", opportunities.len());
+        info!("✅ {} opportunités d'amélioration identifiées", opportunities.len());
         
         opportunities
     }
     
     /// Recherche des opportunités d'amélioration de performance
     fn find_performance_improvements(&self, content: &str, file_path: &Path, opportunities: &mut Vec<ImprovementOpportunity>) {
-        // Patterns pour les problèmes de performance courants
-        let patterns = vec![
-            (r"for\s+.*\s+in\s+.*\.clone\(\)", "Clonage inutile dans une boucle", 0.8),
-            (r"let\s+mut\s+.*\s*=\s*Vec::new\(\);\s+for\s+.*\s+{\s+.*\.push\(.*\);\s+}", "Utiliser un constructeur de collection au lieu de push répétés", 0.7),
-            (r"\.to_string\(\).*\.to_string\(\)", "Conversions de chaînes multiples", 0.6),
-            (r"for\s+i\s+in\s+0\.\..*.len\(\)\s+{.*\[i\]", "Utiliser une itération directe plutôt que des indices", 0.7),
-            (r"if\s+let\s+Some\(.*\)\s+=\s+.*\s+{\s+.*\s+}\s+if\s+let\s+Some\(.*\)\s+=\s+.*\s+{", "Combiner des if let multiples", 0.5),
-        ];
-        
-        for (pattern, description, score) in patterns {
-            let regex = match Regex::new(pattern) {
-                Ok(r) => r,
-                Err(_) => continue,
-            };
-            
+        for &(ref regex, description, score) in PERFORMANCE_PATTERNS.iter() {
             for cap in regex.captures_iter(content) {
                 let matched_code = cap[0].to_string();
                 let pos = cap.get(0).unwrap().start();
                 
                 // Trouver la fonction contenant ce code
                 let fn_start = content[..pos].rfind("fn ").unwrap_or(0);
-                let fn_regex = Regex::new(r"fn\s+(\w+)").unwrap();
-                
-                if let Some(fn_cap) = fn_regex.captures(&content[fn_start..]) {
+                if let Some(fn_cap) = FN_NAME_RE.captures(&content[fn_start..]) {
                     let fn_name = fn_cap[1].to_string();
                     
                     // Créer une opportunité d'amélioration
@@ -1034,30 +1379,14 @@ impl CodeEvolution {
     
     /// Recherche des opportunités d'amélioration de sécurité
     fn find_security_improvements(&self, content: &str, file_path: &Path, opportunities: &mut Vec<ImprovementOpportunity>) {
-        // Patterns pour les problèmes de sécurité courants
-        let patterns = vec![
-            (r"unsafe\s+{", "Bloc unsafe non protégé", 0.9),
-            (r"let\s+.*\s*=\s*String::from\(.*input.*\)", "Entrée utilisateur non validée", 0.85),
-            (r"\.unwrap\(\)", "Gestion d'erreur avec unwrap()", 0.7),
-            (r"panic!\(", "Utilisation de panic!", 0.6),
-            (r"std::mem::transmute", "Utilisation de transmute", 0.95),
-        ];
-        
-        for (pattern, description, score) in patterns {
-            let regex = match Regex::new(pattern) {
-                Ok(r) => r,
-                Err(_) => continue,
-            };
-            
+        for &(ref regex, description, score) in SECURITY_PATTERNS.iter() {
             for cap in regex.captures_iter(content) {
                 let matched_code = cap[0].to_string();
                 let pos = cap.get(0).unwrap().start();
                 
                 // Trouver la fonction contenant ce code
                 let fn_start = content[..pos].rfind("fn ").unwrap_or(0);
-                let fn_regex = Regex::new(r"fn\s+(\w+)").unwrap();
-                
-                if let Some(fn_cap) = fn_regex.captures(&content[fn_start..]) {
+                if let Some(fn_cap) = FN_NAME_RE.captures(&content[fn_start..]) {
                     let fn_name = fn_cap[1].to_string();
                     
                     // Créer une opportunité d'amélioration
@@ -1078,30 +1407,14 @@ impl CodeEvolution {
     
     /// Recherche des opportunités d'amélioration de qualité de code
     fn find_code_quality_improvements(&self, content: &str, file_path: &Path, opportunities: &mut Vec<ImprovementOpportunity>) {
-        // Patterns pour les problèmes de qualité de code courants
-        let patterns = vec![
-            (r"fn\s+\w+[^{]*\{[^}]{500,}\}", "Fonction trop longue", 0.8),
-            (r"//\s*TODO|//\s*FIXME", "TODO ou FIXME non résolu", 0.6),
-            (r"if\s+.*\s+{\s+.*\s+}\s+else\s+if\s+.*\s+{\s+.*\s+}\s+else\s+if\s+.*\s+{\s+.*\s+}\s+else\s+if", "Cascade if-else-if trop longue", 0.7),
-            (r"match\s+.*\s+{\s+.*_\s+=>\s+.*,", "Match avec clause catch-all", 0.5),
-            (r"\s{4,}//", "Commentaire mal aligné", 0.4),
-        ];
-        
-        for (pattern, description, score) in patterns {
-            let regex = match Regex::new(pattern) {
-                Ok(r) => r,
-                Err(_) => continue,
-            };
-            
+        for &(ref regex, description, score) in QUALITY_PATTERNS.iter() {
             for cap in regex.captures_iter(content) {
                 let matched_code = cap[0].to_string();
                 let pos = cap.get(0).unwrap().start();
                 
                 // Trouver la fonction ou structure contenant ce code
                 let fn_start = content[..pos].rfind("fn ").unwrap_or(content[..pos].rfind("struct ").unwrap_or(0));
-                let target_regex = Regex::new(r"(fn|struct)\s+(\w+)").unwrap();
-                
-                if let Some(target_cap) = target_regex.captures(&content[fn_start..]) {
+                if let Some(target_cap) = TARGET_NAME_RE.captures(&content[fn_start..]) {
                     let target_type = target_cap[1].to_string();
                     let target_name = target_cap[2].to_string();
                     
@@ -1122,48 +1435,37 @@ impl CodeEvolution {
     }
     
     /// Génère des améliorations pour le code basées sur la base de connaissances
-    pub fn generate_code_improvements(&self, opportunities: &[ImprovementOpportunity]) 
+    pub async fn generate_code_improvements(&mut self, opportunities: &[ImprovementOpportunity]) 
         -> Result<Vec<CodeImprovement>, String> {
-        println!("[EVOLUTION] 🧪 Génération d'améliorations de code...");
+        info!("🧪 Génération d'améliorations de code...");
         
         let mut improvements = Vec::new();
         
         for opportunity in opportunities {
-            println!("[EVOLUTION] - Amélioration pour {}: {}", 
-                     opportunity.target_name, opportunity.description);
-            
-            // Trouver des fragments de code pertinents
-            let mut relevant_fragments = Vec::new();
-            
-            // Chercher des fragments de code en Rust similaires au problème
-            for fragment in &self.knowledge_base.code_fragments {
-                if fragment.language != "rust" {
-                    continue;
-                }
-                
-                let mut relevance = 0.0;
-                
-                // Vérifier si les tags correspondent
-                for tag in &fragment.tags {
-                    if opportunity.target_name.contains(tag) || 
-                       opportunity.description.contains(tag) {
-                        relevance += 0.3;
-                    }
-                }
-                
-                // Vérifier la similarité de code
-                if fragment.code.contains(&opportunity.current_code) || 
-                   opportunity.current_code.contains(&fragment.code) {
-                    relevance += 0.5;
-                }
-                
-                if relevance > 0.3 {
-                    relevant_fragments.push((fragment, relevance));
-                }
+            // Module cible: nom du fichier source (ex: economy.rs -> economy)
+            let module = Path::new(&opportunity.file_path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            if self.privileges.read().is_revoked(&module) {
+                warn!("⛔ {} ignoré: module {} en quarantaine", opportunity.target_name, module);
+                continue;
             }
+
+            info!("🧪 Amélioration pour {}: {}", opportunity.target_name, opportunity.description);
             
-            // Trier par pertinence
-            relevant_fragments.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+            // Fragments Rust sémantiquement proches du problème (nom, description et code ciblés),
+            // issus de sources dont la licence est admise
+            let query = format!("{}\n{}\n{}", opportunity.target_name, opportunity.description, opportunity.current_code);
+            let similar = self.search_kind(&query, RELEVANT_FRAGMENTS * 3, Some(ItemKind::Fragment)).await?;
+            let relevant_fragments: Vec<(&CodeFragment, f32)> = similar.iter()
+                .filter(|item| item.score >= MIN_SIMILARITY)
+                .filter_map(|item| self.knowledge_base.code_fragments.iter()
+                    .find(|f| f.id.to_string() == item.id && f.language == "rust")
+                    .filter(|f| self.license_policy.admits(f.provenance.as_ref()))
+                    .map(|f| (f, item.score)))
+                .take(RELEVANT_FRAGMENTS)
+                .collect();
             
             // Générer l'amélioration
             let improvement = match opportunity.description.to_lowercase() {
@@ -1184,7 +1486,7 @@ impl CodeEvolution {
             improvements.push(improvement);
         }
         
-        println!("[EVOLUTION] ✅ {} améliorations générées", improvements.len());
+        info!("✅ {} améliorations générées", improvements.len());
         
         Ok(improvements)
     }
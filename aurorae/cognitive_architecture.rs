@@ -0,0 +1,385 @@
+//! AURORAE++ - cognitive_architecture.rs
+//!
+//! Mémoire à trois étages: une mémoire de travail de capacité limitée, seule porte d'entrée
+//! des pensées vers le cerveau; une mémoire épisodique alimentée par les épisodes archivés de
+//! l'agent de renforcement; la mémoire sémantique qu'est la base de savoir. Des tâches de
+//! consolidation planifiées font décroître la mémoire de travail, y versent les traces
+//! saillantes dans la mémoire épisodique, puis généralisent les épisodes en faits sémantiques.
+//! La mémoire épisodique est persistée dans `aurorae_state/episodic_memory.json`.
+
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::info;
+
+use crate::brain::{BrainCore, Intent, Thought};
+use crate::knowledge::{KnowledgeBase, MetaInsight, Provenance};
+use crate::reinforcement_learning::LearningAgent;
+
+pub const EPISODIC_MEMORY_PATH: &str = "aurorae_state/episodic_memory.json";
+/// Capacité de la mémoire de travail (empan mnésique)
+pub const WORKING_MEMORY_CAPACITY: usize = 7;
+/// Décroissance de la saillance à chaque cycle
+const WORKING_DECAY: f32 = 0.85;
+/// Saillance sous laquelle un élément quitte la mémoire de travail
+const WORKING_FLOOR: f32 = 0.1;
+/// Saillance minimale pour qu'un élément évincé laisse une trace épisodique
+const TRACE_SALIENCE: f32 = 0.5;
+/// Épisodes conservés en mémoire épisodique
+const EPISODIC_CAPACITY: usize = 500;
+/// Épisodes où une action doit figurer pour être généralisée en fait sémantique
+const MIN_EPISODES_PER_FACT: usize = 3;
+/// Périodicité (en cycles) des tâches de consolidation
+const EPISODIC_EVERY: usize = 5;
+const SEMANTIC_EVERY: usize = 30;
+
+/// Élément de la mémoire de travail
+#[derive(Debug, Clone)]
+pub struct WorkingItem {
+    pub id: Uuid,
+    pub content: String,
+    /// Intention à transmettre au cerveau, le cas échéant
+    pub intent: Option<Intent>,
+    /// Saillance (0 à 1), ravivée à chaque nouvelle attention
+    pub salience: f32,
+    pub rehearsals: u32,
+    /// Déjà transmis au cerveau
+    pub dispatched: bool,
+}
+
+/// Mémoire de travail: au plus `capacity` éléments, les moins saillants sont évincés
+#[derive(Debug)]
+pub struct WorkingMemory {
+    pub capacity: usize,
+    items: Vec<WorkingItem>,
+}
+
+impl WorkingMemory {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, items: Vec::new() }
+    }
+
+    pub fn items(&self) -> &[WorkingItem] {
+        &self.items
+    }
+
+    /// Porte l'attention sur `content`: un élément déjà présent est ravivé, sinon il entre en
+    /// mémoire de travail; renvoie l'élément évincé si la capacité est dépassée
+    pub fn attend(&mut self, content: &str, intent: Option<Intent>, salience: f32) -> Option<WorkingItem> {
+        let salience = salience.clamp(0.0, 1.0);
+        if let Some(item) = self.items.iter_mut().find(|item| item.content == content) {
+            item.salience = item.salience.max(salience);
+            item.rehearsals += 1;
+            // Une intention ravivée est de nouveau transmise au cerveau
+            if intent.is_some() {
+                item.intent = intent;
+                item.dispatched = false;
+            }
+            return None;
+        }
+        self.items.push(WorkingItem {
+            id: Uuid::new_v4(),
+            content: content.to_string(),
+            intent,
+            salience,
+            rehearsals: 0,
+            dispatched: false,
+        });
+        self.items.sort_by(|a, b| b.salience.partial_cmp(&a.salience).unwrap_or(std::cmp::Ordering::Equal));
+        if self.items.len() > self.capacity {
+            self.items.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Fait décroître la saillance; renvoie les éléments tombés sous le seuil
+    fn decay(&mut self) -> Vec<WorkingItem> {
+        for item in &mut self.items {
+            item.salience *= WORKING_DECAY;
+        }
+        let (kept, faded): (Vec<_>, Vec<_>) = std::mem::take(&mut self.items).into_iter().partition(|item| item.salience >= WORKING_FLOOR);
+        self.items = kept;
+        faded
+    }
+}
+
+/// Souvenir épisodique: un épisode d'apprentissage ou une trace de la mémoire de travail
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Episode {
+    pub at: DateTime<Utc>,
+    pub summary: String,
+    pub actions: Vec<String>,
+    pub total_reward: f32,
+    pub performance: f32,
+    /// Déjà généralisé en mémoire sémantique
+    pub consolidated: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct EpisodicStore {
+    episodes: Vec<Episode>,
+    /// Horodatage (secondes) du dernier épisode de l'agent ingéré
+    last_ingested: u64,
+}
+
+/// Bilan d'une passe de consolidation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsolidationReport {
+    pub faded: usize,
+    pub traces: usize,
+    pub episodes_ingested: usize,
+    pub facts: usize,
+}
+
+pub struct CognitiveModel {
+    pub working: WorkingMemory,
+    episodic: EpisodicStore,
+    path: Option<PathBuf>,
+}
+
+impl Default for CognitiveModel {
+    fn default() -> Self {
+        Self::in_memory(WORKING_MEMORY_CAPACITY)
+    }
+}
+
+impl CognitiveModel {
+    /// Mémoire épisodique rechargée depuis `aurorae_state/episodic_memory.json`
+    pub fn new() -> Self {
+        Self::open(Path::new(EPISODIC_MEMORY_PATH), WORKING_MEMORY_CAPACITY)
+    }
+
+    pub fn in_memory(capacity: usize) -> Self {
+        Self { working: WorkingMemory::new(capacity), episodic: EpisodicStore::default(), path: None }
+    }
+
+    pub fn open(path: &Path, capacity: usize) -> Self {
+        let episodic = File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self { working: WorkingMemory::new(capacity), episodic, path: Some(path.to_path_buf()) }
+    }
+
+    pub fn episodes(&self) -> &[Episode] {
+        &self.episodic.episodes
+    }
+
+    /// Entrée en mémoire de travail; un élément saillant évincé laisse une trace épisodique
+    pub fn attend(&mut self, content: &str, intent: Option<Intent>, salience: f32) {
+        if let Some(evicted) = self.working.attend(content, intent, salience) {
+            self.trace(evicted);
+        }
+    }
+
+    /// Transmet au cerveau les intentions de la mémoire de travail qui ne l'ont pas encore été
+    pub fn feed_brain(&mut self, brain: &mut BrainCore) -> usize {
+        let mut fed = 0;
+        for item in self.working.items.iter_mut().filter(|item| !item.dispatched) {
+            let Some(intent) = item.intent.clone() else { continue };
+            let mut thought = Thought::new(intent, (item.salience * 255.0).round() as u8);
+            thought.metadata.insert("memory".to_string(), item.content.clone());
+            brain.push_thought(thought);
+            item.dispatched = true;
+            fed += 1;
+        }
+        fed
+    }
+
+    /// Trace épisodique d'un élément quittant la mémoire de travail, s'il était saillant ou répété
+    fn trace(&mut self, item: WorkingItem) -> bool {
+        if item.salience < TRACE_SALIENCE && item.rehearsals == 0 {
+            return false;
+        }
+        self.remember(Episode {
+            at: Utc::now(),
+            summary: item.content,
+            actions: Vec::new(),
+            total_reward: 0.0,
+            performance: item.salience,
+            consolidated: false,
+        });
+        true
+    }
+
+    fn remember(&mut self, episode: Episode) {
+        self.episodic.episodes.push(episode);
+        if self.episodic.episodes.len() > EPISODIC_CAPACITY {
+            // Les souvenirs déjà généralisés et les moins performants sont oubliés en premier
+            self.episodic.episodes.sort_by(|a, b| {
+                a.consolidated.cmp(&b.consolidated)
+                    .then(b.performance.partial_cmp(&a.performance).unwrap_or(std::cmp::Ordering::Equal))
+            });
+            self.episodic.episodes.truncate(EPISODIC_CAPACITY);
+            self.episodic.episodes.sort_by_key(|episode| episode.at);
+        }
+    }
+
+    /// Verse en mémoire épisodique les épisodes archivés par l'agent depuis la dernière ingestion
+    pub fn ingest_episodes(&mut self, agent: &LearningAgent) -> usize {
+        let mut fresh: Vec<_> = agent.long_term_memory.iter()
+            .filter(|episode| episode.timestamp > self.episodic.last_ingested)
+            .collect();
+        fresh.sort_by_key(|episode| episode.timestamp);
+        for episode in &fresh {
+            self.episodic.last_ingested = self.episodic.last_ingested.max(episode.timestamp);
+            self.remember(Episode {
+                at: DateTime::from_timestamp(episode.timestamp as i64, 0).unwrap_or_else(Utc::now),
+                summary: format!("Épisode de {} action(s)", episode.action_history.len()),
+                actions: episode.action_history.clone(),
+                total_reward: episode.total_reward,
+                performance: episode.performance_score,
+                consolidated: false,
+            });
+        }
+        fresh.len()
+    }
+
+    /// Généralise les épisodes non consolidés: chaque action assez fréquente devient un fait
+    /// sémantique (récompense moyenne par épisode où elle figure)
+    pub fn consolidate_semantic(&mut self, knowledge: &mut KnowledgeBase) -> usize {
+        let mut by_action: BTreeMap<&str, Vec<f32>> = BTreeMap::new();
+        for episode in self.episodic.episodes.iter().filter(|e| !e.consolidated && !e.actions.is_empty()) {
+            let per_action = episode.total_reward / episode.actions.len() as f32;
+            let mut seen: Vec<&str> = episode.actions.iter().map(String::as_str).collect();
+            seen.sort();
+            seen.dedup();
+            for action in seen {
+                by_action.entry(action).or_default().push(per_action);
+            }
+        }
+        let facts: Vec<MetaInsight> = by_action.into_iter()
+            .filter(|(_, rewards)| rewards.len() >= MIN_EPISODES_PER_FACT)
+            .map(|(action, rewards)| {
+                let mean = rewards.iter().sum::<f32>() / rewards.len() as f32;
+                MetaInsight {
+                    topic: format!("episodic:{}", action),
+                    content: format!("L'action {} rapporte en moyenne {:.3} par pas sur {} épisode(s)", action, mean, rewards.len()),
+                    confidence: (rewards.len() as f32 / 10.0).min(1.0),
+                    provenance: Provenance::local(),
+                }
+            })
+            .collect();
+        if facts.is_empty() {
+            return 0;
+        }
+        for episode in self.episodic.episodes.iter_mut().filter(|e| !e.actions.is_empty()) {
+            episode.consolidated = true;
+        }
+        let count = facts.len();
+        for fact in facts {
+            knowledge.add_meta_insight(fact);
+        }
+        count
+    }
+
+    /// Tâches de consolidation dues à ce cycle: décroissance de la mémoire de travail à chaque
+    /// cycle, ingestion épisodique tous les `EPISODIC_EVERY` cycles, généralisation sémantique
+    /// tous les `SEMANTIC_EVERY` cycles
+    pub fn consolidate(&mut self, cycle: usize, agent: &LearningAgent, knowledge: &mut KnowledgeBase) -> ConsolidationReport {
+        let mut report = ConsolidationReport::default();
+        let faded = self.working.decay();
+        report.faded = faded.len();
+        for item in faded {
+            if self.trace(item) {
+                report.traces += 1;
+            }
+        }
+        if cycle % EPISODIC_EVERY == 0 {
+            report.episodes_ingested = self.ingest_episodes(agent);
+        }
+        if cycle % SEMANTIC_EVERY == 0 {
+            report.facts = self.consolidate_semantic(knowledge);
+        }
+        if report.traces + report.episodes_ingested + report.facts > 0 {
+            self.save();
+            info!(
+                "🗃️ Consolidation mémoire: {} trace(s), {} épisode(s) ingéré(s), {} fait(s) sémantique(s)",
+                report.traces, report.episodes_ingested, report.facts
+            );
+        }
+        report
+    }
+
+    pub fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self.episodic);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reinforcement_learning::EpisodeMemory;
+
+    #[test]
+    fn working_memory_is_capacity_limited_and_feeds_the_brain() {
+        let mut model = CognitiveModel::in_memory(2);
+        model.attend("optimisation économique", Some(Intent::OptimizeEconomy), 0.6);
+        model.attend("bruit", None, 0.2);
+        model.attend("mutation", Some(Intent::MutateSelf), 0.8);
+        let contents: Vec<&str> = model.working.items().iter().map(|i| i.content.as_str()).collect();
+        assert_eq!(contents, vec!["mutation", "optimisation économique"]);
+        // L'élément évincé était trop peu saillant pour laisser une trace
+        assert!(model.episodes().is_empty());
+
+        let mut brain = BrainCore::default();
+        assert_eq!(model.feed_brain(&mut brain), 2);
+        assert_eq!(model.feed_brain(&mut brain), 0);
+        assert_eq!(brain.cortex.len(), 2);
+        assert_eq!(brain.cortex[0].urgency, 204);
+
+        // Sans attention, la saillance décroît jusqu'à l'éviction; seul l'élément répété laisse
+        // une trace épisodique
+        model.attend("mutation", None, 0.5);
+        let mut agent = LearningAgent::new(vec!["observer".to_string()], "initial");
+        agent.long_term_memory.clear();
+        let mut knowledge = KnowledgeBase::default();
+        let mut faded = 0;
+        for cycle in 1..20 {
+            faded += model.consolidate(cycle, &agent, &mut knowledge).faded;
+        }
+        assert_eq!(faded, 2);
+        assert_eq!(model.episodes().len(), 1);
+        assert_eq!(model.episodes()[0].summary, "mutation");
+    }
+
+    #[test]
+    fn episodes_are_ingested_once_and_generalized_into_facts() {
+        let path = std::env::temp_dir().join(format!("aurorae-episodic-{}.json", uuid::Uuid::new_v4()));
+        let mut model = CognitiveModel::open(&path, WORKING_MEMORY_CAPACITY);
+        let mut agent = LearningAgent::new(vec!["analyze_market".to_string()], "initial");
+        agent.long_term_memory = (1..=3u64).map(|t| {
+            let mut episode = EpisodeMemory::new("initial");
+            episode.add_transition("analyze_market", 0.5, "s1");
+            episode.add_transition("generate_code", 0.3, "s2");
+            episode.timestamp = t;
+            episode
+        }).collect();
+        assert_eq!(model.ingest_episodes(&agent), 3);
+        assert_eq!(model.ingest_episodes(&agent), 0);
+
+        let mut knowledge = KnowledgeBase::default();
+        assert_eq!(model.consolidate_semantic(&mut knowledge), 2);
+        let fact = knowledge.meta_insights.iter().find(|i| i.topic == "episodic:analyze_market").unwrap();
+        assert!(fact.content.contains("0.400") && (fact.confidence - 0.3).abs() < 1e-5);
+        assert_eq!(model.consolidate_semantic(&mut knowledge), 0);
+
+        model.save();
+        let reloaded = CognitiveModel::open(&path, WORKING_MEMORY_CAPACITY);
+        assert_eq!(reloaded.episodes().len(), 3);
+        assert!(reloaded.episodes().iter().all(|e| e.consolidated));
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,355 @@
+//! AURORAE++ - config.rs
+//!
+//! Configuration unifiée du système (`aurorae.toml`): adresse du fondateur, chaîne principale
+//! et surcharges RPC, chemins du feed et de la base de connaissance, volatilité de l'économie,
+//! cadence des cycles. Trois couches, de la plus faible à la plus forte: le fichier, les
+//! variables `AURORAE__SECTION__CLE`, puis les options `--set section.cle=valeur`. Le
+//! résultat est typé et validé au démarrage; toutes les erreurs sont rapportées ensemble.
+//!
+//! Seul un sous-ensemble sûr (cadence des cycles, bornes de volatilité) est rechargé à chaud;
+//! les autres changements du fichier attendent le prochain démarrage.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use ethers::types::Address;
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use toml::Value;
+
+pub const DEFAULT_CONFIG_PATH: &str = "aurorae.toml";
+/// Préfixe des variables d'environnement: `AURORAE__SYSTEM__CYCLE_MS=2000`
+pub const ENV_PREFIX: &str = "AURORAE__";
+
+lazy_static! {
+    /// Configuration en vigueur, installée par `main` au démarrage
+    pub static ref CONFIG: RwLock<AuroraeConfig> = RwLock::new(AuroraeConfig::default());
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct SystemSettings {
+    /// Durée visée d'un cycle de la boucle principale (rechargée à chaud)
+    pub cycle_ms: u64,
+    /// Bénéficiaire de la part fondateur des revenus
+    pub founder_address: String,
+}
+
+impl Default for SystemSettings {
+    fn default() -> Self {
+        Self { cycle_ms: 5_000, founder_address: "0xFd4456F8d982276Ac7d2294E66Dc8aCc097f0043".to_string() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct PathSettings {
+    /// Dossier des sources clonées analysées par le crawler et l'apprentissage
+    pub feed: PathBuf,
+    pub knowledge_db: PathBuf,
+    /// Remplace `local-path` d'`inspiration.toml` si présent
+    pub inspiration: Option<PathBuf>,
+}
+
+impl Default for PathSettings {
+    fn default() -> Self {
+        Self {
+            feed: PathBuf::from("aurorae_state/github_feed"),
+            knowledge_db: PathBuf::from("aurorae_state/aurorae_knowledge.json"),
+            inspiration: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct NetworkSettings {
+    /// Chaîne du registre utilisée pour les déploiements et les versements
+    pub primary_chain: String,
+    /// RPC par chaîne, prioritaires sur `chains.toml`
+    pub rpc: BTreeMap<String, String>,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self { primary_chain: "Sepolia".to_string(), rpc: BTreeMap::new() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct EconomySettings {
+    /// Bornes de la volatilité simulée (rechargées à chaud)
+    pub volatility_min: f64,
+    pub volatility_max: f64,
+}
+
+impl Default for EconomySettings {
+    fn default() -> Self {
+        Self { volatility_min: 0.03, volatility_max: 0.12 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct LearningSettings {
+    /// Dépôts clonés au démarrage, ordonnés par le curriculum
+    pub reference_repos: Vec<String>,
+}
+
+impl Default for LearningSettings {
+    fn default() -> Self {
+        Self {
+            reference_repos: vec![
+                "https://github.com/paritytech/substrate".to_string(),
+                "https://github.com/solana-labs/solana".to_string(),
+                "https://github.com/algorand/go-algorand".to_string(),
+                "https://github.com/cosmos/cosmos-sdk".to_string(),
+            ],
+        }
+    }
+}
+
+/// Fichier `aurorae.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct AuroraeConfig {
+    pub system: SystemSettings,
+    pub paths: PathSettings,
+    pub network: NetworkSettings,
+    pub economy: EconomySettings,
+    pub learning: LearningSettings,
+}
+
+/// Effet d'un rechargement à chaud
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HotReload {
+    /// Clés appliquées immédiatement
+    pub applied: Vec<String>,
+    /// Clés modifiées qui ne prendront effet qu'au redémarrage
+    pub needs_restart: Vec<String>,
+}
+
+impl HotReload {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.needs_restart.is_empty()
+    }
+}
+
+impl AuroraeConfig {
+    /// Toutes les erreurs de validation, une par ligne
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        if self.system.cycle_ms < 100 {
+            errors.push(format!("system.cycle-ms: {} ms (minimum 100)", self.system.cycle_ms));
+        }
+        if self.system.founder_address.parse::<Address>().is_err() {
+            errors.push(format!("system.founder-address: adresse invalide '{}'", self.system.founder_address));
+        }
+        if self.network.primary_chain.trim().is_empty() {
+            errors.push("network.primary-chain: vide".to_string());
+        }
+        for (chain, rpc) in &self.network.rpc {
+            if !["http://", "https://", "ws://", "wss://"].iter().any(|scheme| rpc.starts_with(scheme)) {
+                errors.push(format!("network.rpc.{}: URL invalide '{}'", chain, rpc));
+            }
+        }
+        let (min, max) = (self.economy.volatility_min, self.economy.volatility_max);
+        if !(min.is_finite() && max.is_finite() && 0.0 <= min && min <= max && max <= 1.0) {
+            errors.push(format!("economy.volatility-min/max: bornes invalides [{}, {}]", min, max));
+        }
+        if let Some(repo) = self.learning.reference_repos.iter().find(|repo| !repo.starts_with("https://")) {
+            errors.push(format!("learning.reference-repos: URL invalide '{}'", repo));
+        }
+        if self.paths.feed.as_os_str().is_empty() || self.paths.knowledge_db.as_os_str().is_empty() {
+            errors.push("paths: chemin vide".to_string());
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+
+    /// Applique le sous-ensemble rechargeable à chaud de `next` et liste le reste
+    pub fn apply_hot(&mut self, next: &AuroraeConfig) -> HotReload {
+        let mut reload = HotReload::default();
+        if self.system.cycle_ms != next.system.cycle_ms {
+            self.system.cycle_ms = next.system.cycle_ms;
+            reload.applied.push("system.cycle-ms".to_string());
+        }
+        if self.economy != next.economy {
+            self.economy = next.economy.clone();
+            reload.applied.push("economy".to_string());
+        }
+        let restart_only = [
+            ("system.founder-address", self.system.founder_address != next.system.founder_address),
+            ("paths", self.paths != next.paths),
+            ("network", self.network != next.network),
+            ("learning", self.learning != next.learning),
+        ];
+        reload.needs_restart = restart_only.iter().filter(|(_, changed)| *changed).map(|(key, _)| key.to_string()).collect();
+        reload
+    }
+}
+
+/// Valeur brute d'une variable ou d'une option: scalaire ou tableau TOML, sinon chaîne
+fn parse_value(raw: &str) -> Value {
+    toml::from_str::<BTreeMap<String, Value>>(&format!("v = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("v"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Écrit `value` au chemin `keys` de la table, en créant les sections manquantes
+fn set_path(root: &mut Value, keys: &[String], value: Value) -> Result<(), String> {
+    let (last, sections) = keys.split_last().ok_or("Clé de configuration vide")?;
+    let mut table = root.as_table_mut().ok_or("Configuration racine invalide")?;
+    for section in sections {
+        table = table
+            .entry(section.clone())
+            .or_insert_with(|| Value::Table(Default::default()))
+            .as_table_mut()
+            .ok_or_else(|| format!("{} n'est pas une section", section))?;
+    }
+    table.insert(last.clone(), value);
+    Ok(())
+}
+
+/// Sources de la configuration: fichier, environnement, options `--set`
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    pub path: PathBuf,
+    /// Affectations `section.cle=valeur` de la ligne de commande
+    pub overrides: Vec<String>,
+    loaded_mtime: Option<SystemTime>,
+}
+
+impl ConfigSource {
+    pub fn new(path: impl Into<PathBuf>, overrides: Vec<String>) -> Self {
+        Self { path: path.into(), overrides, loaded_mtime: None }
+    }
+
+    /// `--config <chemin>` et chaque `--set section.cle=valeur`
+    pub fn from_args(args: &[String]) -> Self {
+        let mut path = PathBuf::from(DEFAULT_CONFIG_PATH);
+        let mut overrides = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--config" => path = args.next().map(PathBuf::from).unwrap_or(path),
+                "--set" => overrides.extend(args.next().cloned()),
+                _ => {}
+            }
+        }
+        Self::new(path, overrides)
+    }
+
+    /// Superpose les trois couches et valide le résultat
+    pub fn load(&mut self) -> Result<AuroraeConfig, String> {
+        let env = std::env::vars().filter(|(key, _)| key.starts_with(ENV_PREFIX));
+        let config = Self::layer(&self.path, env, &self.overrides)?;
+        self.loaded_mtime = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+        Ok(config)
+    }
+
+    fn layer(path: &Path, env: impl Iterator<Item = (String, String)>, overrides: &[String]) -> Result<AuroraeConfig, String> {
+        let mut root = match fs::read_to_string(path) {
+            Ok(content) => toml::from_str::<Value>(&content).map_err(|e| format!("{}: {}", path.display(), e))?,
+            Err(_) => Value::Table(Default::default()),
+        };
+        let mut errors = Vec::new();
+        for (key, raw) in env {
+            let keys: Vec<String> = key[ENV_PREFIX.len()..].split("__").map(|part| part.to_lowercase().replace('_', "-")).collect();
+            if let Err(e) = set_path(&mut root, &keys, parse_value(&raw)) {
+                errors.push(format!("{}: {}", key, e));
+            }
+        }
+        for assignment in overrides {
+            let Some((key, raw)) = assignment.split_once('=') else {
+                errors.push(format!("--set {}: forme attendue section.cle=valeur", assignment));
+                continue;
+            };
+            let keys: Vec<String> = key.trim().split('.').map(str::to_string).collect();
+            if let Err(e) = set_path(&mut root, &keys, parse_value(raw.trim())) {
+                errors.push(format!("--set {}: {}", assignment, e));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+        let config: AuroraeConfig = root.try_into().map_err(|e| format!("Configuration invalide: {}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Recharge si le fichier a changé et applique le sous-ensemble sûr à `CONFIG`
+    pub fn reload_if_changed(&mut self) -> Result<Option<HotReload>, String> {
+        let mtime = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+        if mtime.is_none() || mtime == self.loaded_mtime {
+            return Ok(None);
+        }
+        // Un fichier invalide n'est signalé qu'une fois par modification
+        self.loaded_mtime = mtime;
+        let next = self.load()?;
+        Ok(Some(CONFIG.write().apply_hot(&next)))
+    }
+}
+
+/// Installe la configuration en vigueur
+pub fn install(config: AuroraeConfig) {
+    *CONFIG.write() = config;
+}
+
+pub fn feed_path() -> PathBuf {
+    CONFIG.read().paths.feed.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers_apply_file_then_env_then_cli() {
+        let path = std::env::temp_dir().join(format!("aurorae-config-{}.toml", uuid::Uuid::new_v4()));
+        fs::write(&path, "[system]\ncycle-ms = 2000\n\n[economy]\nvolatility-min = 0.01\nvolatility-max = 0.2\n").unwrap();
+        let env = vec![
+            ("AURORAE__SYSTEM__CYCLE_MS".to_string(), "3000".to_string()),
+            ("AURORAE__NETWORK__PRIMARY_CHAIN".to_string(), "Holesky".to_string()),
+        ];
+        let cli = vec!["system.cycle-ms=4000".to_string(), "network.rpc.Holesky=https://rpc.holesky.example".to_string()];
+        let config = ConfigSource::layer(&path, env.into_iter(), &cli).unwrap();
+
+        assert_eq!(config.system.cycle_ms, 4_000);
+        assert_eq!(config.network.primary_chain, "Holesky");
+        assert_eq!(config.network.rpc["Holesky"], "https://rpc.holesky.example");
+        assert_eq!(config.economy.volatility_max, 0.2);
+        assert_eq!(config.paths, PathSettings::default());
+
+        // Toutes les erreurs sont rapportées, clés inconnues comprises
+        let invalid = vec!["system.cycle-ms=10".to_string(), "system.founder-address=pas-une-adresse".to_string()];
+        let errors = ConfigSource::layer(&path, std::iter::empty(), &invalid).unwrap_err();
+        assert_eq!(errors.lines().count(), 2);
+        assert!(ConfigSource::layer(&path, std::iter::empty(), &["system.cadence=1".to_string()]).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn hot_reload_applies_only_the_safe_subset() {
+        let mut current = AuroraeConfig::default();
+        let mut next = current.clone();
+        next.system.cycle_ms = 1_000;
+        next.economy.volatility_max = 0.3;
+        next.network.primary_chain = "Holesky".to_string();
+
+        let reload = current.apply_hot(&next);
+        assert_eq!(reload.applied, vec!["system.cycle-ms".to_string(), "economy".to_string()]);
+        assert_eq!(reload.needs_restart, vec!["network".to_string()]);
+        assert_eq!(current.system.cycle_ms, 1_000);
+        assert_eq!(current.network.primary_chain, "Sepolia");
+        assert!(current.apply_hot(&current.clone()).is_empty());
+    }
+}
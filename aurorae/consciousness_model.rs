@@ -0,0 +1,199 @@
+//! AURORAE++ - consciousness_model.rs
+//!
+//! Espace de travail global: à chaque cycle, les modules soumettent des coalitions (un contenu
+//! candidat et sa saillance); une compétition désigne le contenu gagnant, qui est diffusé à
+//! tous les modules abonnés. Le module dont la coalition l'emporte agit, les autres en prennent
+//! connaissance. Les coalitions perdantes restent en lice quelques cycles en gagnant en
+//! priorité, et le dernier gagnant est brièvement inhibé, si bien qu'aucun module n'est affamé.
+
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+use tracing::info;
+
+/// Capacité du canal de diffusion; un abonné trop lent perd les diffusions les plus anciennes
+const BROADCAST_CAPACITY: usize = 64;
+/// Bonus de saillance par cycle passé en attente
+const AGE_BOOST: f32 = 0.05;
+/// Pénalité appliquée à la source du gagnant précédent (inhibition de retour)
+const INHIBITION: f32 = 0.2;
+/// Cycles au-delà desquels une coalition perdante est abandonnée
+const MAX_AGE: u32 = 12;
+
+/// Contenu candidat à la diffusion globale
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coalition {
+    pub id: Uuid,
+    /// Module qui soumet la coalition et agira si elle l'emporte
+    pub source: String,
+    pub content: String,
+    /// Saillance déclarée (0 à 1)
+    pub salience: f32,
+    /// Cycles passés en attente
+    pub age: u32,
+}
+
+/// Contenu gagnant, tel que reçu par les abonnés
+#[derive(Debug, Clone, PartialEq)]
+pub struct Broadcast {
+    pub cycle: u64,
+    pub winner: Coalition,
+    /// Score de compétition du gagnant
+    pub score: f32,
+    pub competitors: usize,
+    pub at: DateTime<Utc>,
+}
+
+/// Boîte de réception d'un module abonné
+pub struct WorkspaceInbox {
+    pub module: String,
+    receiver: broadcast::Receiver<Broadcast>,
+}
+
+impl WorkspaceInbox {
+    /// Diffusions reçues depuis le dernier relevé
+    pub fn take(&mut self) -> Vec<Broadcast> {
+        let mut received = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(broadcast) => received.push(broadcast),
+                Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                Err(_) => break,
+            }
+        }
+        received
+    }
+
+    /// Dernière diffusion remportée par ce module, depuis le dernier relevé
+    pub fn won(&mut self) -> Option<Broadcast> {
+        let module = self.module.clone();
+        self.take().into_iter().rev().find(|broadcast| broadcast.winner.source == module)
+    }
+}
+
+pub struct GlobalWorkspace {
+    sender: broadcast::Sender<Broadcast>,
+    pending: Vec<Coalition>,
+    cycle: u64,
+    last_winner: Option<String>,
+}
+
+impl Default for GlobalWorkspace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GlobalWorkspace {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self { sender, pending: Vec::new(), cycle: 0, last_winner: None }
+    }
+
+    /// Abonne `module` aux diffusions de l'espace de travail
+    pub fn subscribe(&self, module: &str) -> WorkspaceInbox {
+        WorkspaceInbox { module: module.to_string(), receiver: self.sender.subscribe() }
+    }
+
+    pub fn pending(&self) -> &[Coalition] {
+        &self.pending
+    }
+
+    /// Soumet une coalition; une coalition identique déjà en lice voit sa saillance ravivée
+    /// sans perdre son ancienneté
+    pub fn submit(&mut self, source: &str, content: &str, salience: f32) {
+        let salience = salience.clamp(0.0, 1.0);
+        if let Some(existing) = self.pending.iter_mut().find(|c| c.source == source && c.content == content) {
+            existing.salience = existing.salience.max(salience);
+            return;
+        }
+        self.pending.push(Coalition {
+            id: Uuid::new_v4(),
+            source: source.to_string(),
+            content: content.to_string(),
+            salience,
+            age: 0,
+        });
+    }
+
+    fn score(&self, coalition: &Coalition) -> f32 {
+        let inhibited = self.last_winner.as_deref() == Some(coalition.source.as_str());
+        coalition.salience + AGE_BOOST * coalition.age as f32 - if inhibited { INHIBITION } else { 0.0 }
+    }
+
+    /// Compétition du cycle: la coalition au meilleur score est diffusée à tous les abonnés;
+    /// les autres vieillissent, et celles trop anciennes sont abandonnées
+    pub fn compete(&mut self) -> Option<Broadcast> {
+        self.cycle += 1;
+        let (index, score) = self.pending.iter()
+            .enumerate()
+            .map(|(index, coalition)| (index, self.score(coalition)))
+            .fold(None, |best: Option<(usize, f32)>, (index, score)| match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((index, score)),
+            })?;
+        let competitors = self.pending.len();
+        let winner = self.pending.remove(index);
+        for coalition in &mut self.pending {
+            coalition.age += 1;
+        }
+        self.pending.retain(|coalition| coalition.age <= MAX_AGE);
+        self.last_winner = Some(winner.source.clone());
+
+        let broadcast = Broadcast { cycle: self.cycle, winner, score, competitors, at: Utc::now() };
+        info!(
+            "💡 Espace de travail global: {} l'emporte sur {} coalition(s) ({}, score {:.2})",
+            broadcast.winner.source, competitors, broadcast.winner.content, score
+        );
+        // Aucun abonné n'est une situation normale (ex: démarrage), pas une erreur
+        let _ = self.sender.send(broadcast.clone());
+        Some(broadcast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_salient_coalition_is_broadcast_to_every_subscriber() {
+        let mut workspace = GlobalWorkspace::new();
+        let mut strategist = workspace.subscribe("strategist");
+        let mut cognition = workspace.subscribe("cognition");
+        assert!(workspace.compete().is_none());
+
+        workspace.submit("dream", "synthèse de méta-rêve", 0.3);
+        workspace.submit("strategist", "consultation stratégique", 0.6);
+        workspace.submit("strategist", "consultation stratégique", 0.4);
+        let broadcast = workspace.compete().unwrap();
+        assert_eq!(broadcast.winner.source, "strategist");
+        assert!((broadcast.winner.salience - 0.6).abs() < 1e-6);
+        assert_eq!(broadcast.competitors, 2);
+
+        assert_eq!(strategist.won().map(|b| b.winner.content), Some("consultation stratégique".to_string()));
+        assert!(strategist.won().is_none());
+        let seen = cognition.take();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].winner.source, "strategist");
+    }
+
+    #[test]
+    fn waiting_coalitions_gain_priority_and_winners_are_inhibited() {
+        let mut workspace = GlobalWorkspace::new();
+        workspace.submit("dream", "synthèse", 0.3);
+        workspace.submit("meta_learning", "évaluation", 0.45);
+        assert_eq!(workspace.compete().unwrap().winner.source, "meta_learning");
+        // Le rêve a vieilli (0.3 + 0.05) et le gagnant précédent est inhibé (0.45 - 0.2)
+        workspace.submit("meta_learning", "évaluation", 0.45);
+        assert_eq!(workspace.compete().unwrap().winner.source, "dream");
+
+        // Une coalition trop faible face à deux sources fortes finit abandonnée
+        workspace.submit("generation", "module en file", 0.1);
+        for _ in 0..=MAX_AGE {
+            workspace.submit("strategist", "consultation", 0.9);
+            workspace.submit("economy", "versements", 0.9);
+            assert_ne!(workspace.compete().unwrap().winner.source, "generation");
+        }
+        assert!(workspace.pending().iter().all(|c| c.source != "generation"));
+    }
+}
@@ -0,0 +1,1410 @@
+//! AURORAE++ - consensus.rs
+//!
+//! Coordination des instances par Raft: le parent et ses enfants forment une grappe qui
+//! s'accorde sur un état partagé (registre des chaînes, politique de trésorerie, manifeste des
+//! versions de modules). Un leader élu ajoute les commandes à son journal, les réplique, et une
+//! commande n'est appliquée qu'une fois inscrite chez une majorité. Le journal est compacté en
+//! instantané au-delà d'un seuil; un membre en retard reçoit l'instantané. Les changements de
+//! composition se font un membre à la fois, par entrées du journal.
+//!
+//! `RaftNode` est une machine à états sans entrée/sortie: l'horloge avance par `tick`, les
+//! messages reçus passent par `step` et les messages à envoyer sont relevés par
+//! `take_messages`, puis transportés par la messagerie inter-instances.
+//!
+//! Les chaînes générées, elles, reposent sur des moteurs de consensus interchangeables
+//! (`ConsensusEngine`: preuve d'autorité, preuve d'enjeu) évalués par simulation (délai de
+//! finalité, taux de forks); faire évoluer le consensus revient à régler ou remplacer le moteur.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::chain_registry::ChainConfig;
+use crate::founder_income::PayoutSplits;
+use crate::instance_messaging::InstanceMessage;
+
+pub const DEFAULT_CONSENSUS_CONFIG_PATH: &str = "consensus.toml";
+const DEFAULT_STATE_PATH: &str = "aurorae_state/raft.json";
+
+/// Fichier `consensus.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ConsensusConfig {
+    pub raft: RaftConfig,
+    pub lab: LabConfig,
+}
+
+/// Section `[raft]`; les délais sont exprimés en ticks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct RaftConfig {
+    /// Délai d'élection, tiré au hasard dans [min, max)
+    pub election_timeout_min: u32,
+    pub election_timeout_max: u32,
+    /// Période des battements de cœur du leader
+    pub heartbeat_ticks: u32,
+    /// Entrées appliquées au-delà desquelles le journal est compacté en instantané
+    pub snapshot_threshold: usize,
+    /// Entrées par message de réplication
+    pub max_entries_per_message: usize,
+}
+
+impl Default for RaftConfig {
+    fn default() -> Self {
+        Self {
+            election_timeout_min: 10,
+            election_timeout_max: 20,
+            heartbeat_ticks: 2,
+            snapshot_threshold: 256,
+            max_entries_per_message: 64,
+        }
+    }
+}
+
+impl ConsensusConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| format!("Configuration du consensus invalide: {}", e))?;
+        if config.raft.election_timeout_min <= config.raft.heartbeat_ticks
+            || config.raft.election_timeout_max <= config.raft.election_timeout_min
+        {
+            return Err("Délais Raft incohérents: heartbeat < election-timeout-min < election-timeout-max requis".to_string());
+        }
+        Ok(config)
+    }
+
+    /// `consensus.toml`, ou la configuration par défaut
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_CONSENSUS_CONFIG_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: consensus par défaut", e);
+            Self::default()
+        })
+    }
+}
+
+/// Politique de trésorerie commune aux instances
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreasuryPolicy {
+    pub splits: PayoutSplits,
+    pub min_reserve: f64,
+}
+
+/// Commandes de l'état partagé
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SharedCommand {
+    RegisterChain(ChainConfig),
+    RemoveChain { name: String },
+    SetTreasuryPolicy(TreasuryPolicy),
+    /// Manifeste des versions de modules: chemin -> empreinte
+    PublishManifest { files: BTreeMap<String, String> },
+}
+
+/// État sur lequel la grappe s'accorde, reconstruit en appliquant les commandes validées
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SharedState {
+    pub chains: BTreeMap<String, ChainConfig>,
+    pub treasury: Option<TreasuryPolicy>,
+    pub manifest: BTreeMap<String, String>,
+    /// Publications successives du manifeste
+    pub manifest_version: u64,
+}
+
+impl SharedState {
+    pub fn apply(&mut self, command: &SharedCommand) {
+        match command {
+            SharedCommand::RegisterChain(chain) => {
+                self.chains.insert(chain.name.clone(), chain.clone());
+            }
+            SharedCommand::RemoveChain { name } => {
+                self.chains.remove(name);
+            }
+            SharedCommand::SetTreasuryPolicy(policy) => self.treasury = Some(policy.clone()),
+            SharedCommand::PublishManifest { files } => {
+                self.manifest = files.clone();
+                self.manifest_version += 1;
+            }
+        }
+    }
+
+    /// Commandes qui amèneraient l'état partagé aux valeurs locales fournies
+    pub fn commands_towards(&self, chains: &[ChainConfig], treasury: Option<&TreasuryPolicy>, manifest: Option<&BTreeMap<String, String>>) -> Vec<SharedCommand> {
+        let mut commands: Vec<SharedCommand> = chains.iter()
+            .filter(|chain| self.chains.get(&chain.name) != Some(*chain))
+            .map(|chain| SharedCommand::RegisterChain(chain.clone()))
+            .collect();
+        commands.extend(self.chains.keys()
+            .filter(|name| !chains.iter().any(|chain| &chain.name == *name))
+            .map(|name| SharedCommand::RemoveChain { name: name.clone() }));
+        if let Some(policy) = treasury.filter(|policy| self.treasury.as_ref() != Some(*policy)) {
+            commands.push(SharedCommand::SetTreasuryPolicy(policy.clone()));
+        }
+        if let Some(files) = manifest.filter(|files| **files != self.manifest) {
+            commands.push(SharedCommand::PublishManifest { files: files.clone() });
+        }
+        commands
+    }
+}
+
+/// Contenu d'une entrée du journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EntryKind {
+    /// Entrée vide ajoutée par un nouveau leader pour valider les entrées des mandats précédents
+    Noop,
+    Command(SharedCommand),
+    AddMember(Uuid),
+    RemoveMember(Uuid),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub index: u64,
+    pub term: u64,
+    pub kind: EntryKind,
+}
+
+/// Instantané: état et composition de la grappe jusqu'à `last_index` inclus
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub last_index: u64,
+    pub last_term: u64,
+    pub members: BTreeSet<Uuid>,
+    pub state: SharedState,
+}
+
+/// Messages Raft échangés entre membres
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RaftMessage {
+    RequestVote { term: u64, last_log_index: u64, last_log_term: u64 },
+    VoteReply { term: u64, granted: bool },
+    AppendEntries { term: u64, prev_log_index: u64, prev_log_term: u64, entries: Vec<LogEntry>, leader_commit: u64 },
+    /// `match_index`: dernière entrée identique au leader, ou indice à partir duquel réessayer
+    AppendReply { term: u64, success: bool, match_index: u64 },
+    InstallSnapshot { term: u64, snapshot: Snapshot },
+}
+
+impl RaftMessage {
+    pub fn term(&self) -> u64 {
+        match self {
+            Self::RequestVote { term, .. }
+            | Self::VoteReply { term, .. }
+            | Self::AppendEntries { term, .. }
+            | Self::AppendReply { term, .. }
+            | Self::InstallSnapshot { term, .. } => *term,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Follower,
+    Candidate,
+    Leader,
+}
+
+/// État persistant: mandat, vote, journal et instantané
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HardState {
+    id: Uuid,
+    term: u64,
+    voted_for: Option<Uuid>,
+    snapshot: Snapshot,
+    log: Vec<LogEntry>,
+}
+
+pub struct RaftNode {
+    pub id: Uuid,
+    config: RaftConfig,
+    hard: HardState,
+    role: Role,
+    leader: Option<Uuid>,
+    commit_index: u64,
+    last_applied: u64,
+    state: SharedState,
+    elapsed: u32,
+    timeout: u32,
+    votes: HashSet<Uuid>,
+    next_index: HashMap<Uuid, u64>,
+    match_index: HashMap<Uuid, u64>,
+    outbox: Vec<(Uuid, RaftMessage)>,
+    path: Option<PathBuf>,
+    dirty: bool,
+}
+
+impl RaftNode {
+    /// Nœud persisté dans `aurorae_state/raft.json`; son identité est conservée d'un démarrage à l'autre
+    pub fn new(config: RaftConfig) -> Self {
+        Self::open(Path::new(DEFAULT_STATE_PATH), None, config)
+    }
+
+    pub fn in_memory(id: Uuid, config: RaftConfig) -> Self {
+        Self::from_hard(HardState { id, ..HardState::default() }, None, config)
+    }
+
+    /// Reprend l'état persisté à `path`; un nouvel état prend l'identité `id`, ou une identité neuve
+    pub fn open(path: &Path, id: Option<Uuid>, config: RaftConfig) -> Self {
+        let hard = fs::read_to_string(path).ok()
+            .and_then(|content| serde_json::from_str::<HardState>(&content).ok())
+            .filter(|hard| id.is_none() || id == Some(hard.id))
+            .unwrap_or_else(|| HardState { id: id.unwrap_or_else(Uuid::new_v4), ..HardState::default() });
+        Self::from_hard(hard, Some(path.to_path_buf()), config)
+    }
+
+    fn from_hard(hard: HardState, path: Option<PathBuf>, config: RaftConfig) -> Self {
+        let mut node = Self {
+            id: hard.id,
+            state: hard.snapshot.state.clone(),
+            commit_index: hard.snapshot.last_index,
+            last_applied: hard.snapshot.last_index,
+            hard,
+            config,
+            role: Role::Follower,
+            leader: None,
+            elapsed: 0,
+            timeout: 0,
+            votes: HashSet::new(),
+            next_index: HashMap::new(),
+            match_index: HashMap::new(),
+            outbox: Vec::new(),
+            path,
+            dirty: false,
+        };
+        node.reset_timeout();
+        node
+    }
+
+    /// Fonde une grappe dont ce nœud est l'unique membre; sans effet sur un journal existant
+    pub fn bootstrap(&mut self) {
+        if self.last_index() > 0 {
+            return;
+        }
+        self.hard.log.push(LogEntry { index: 1, term: 0, kind: EntryKind::AddMember(self.id) });
+        self.commit_index = 1;
+        self.apply_committed();
+        self.dirty = true;
+        self.persist();
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.role == Role::Leader
+    }
+
+    pub fn leader(&self) -> Option<Uuid> {
+        self.leader
+    }
+
+    pub fn term(&self) -> u64 {
+        self.hard.term
+    }
+
+    pub fn commit_index(&self) -> u64 {
+        self.commit_index
+    }
+
+    /// État partagé résultant des commandes validées
+    pub fn state(&self) -> &SharedState {
+        &self.state
+    }
+
+    pub fn snapshot(&self) -> &Snapshot {
+        &self.hard.snapshot
+    }
+
+    pub fn last_index(&self) -> u64 {
+        self.hard.snapshot.last_index + self.hard.log.len() as u64
+    }
+
+    fn last_term(&self) -> u64 {
+        self.hard.log.last().map(|entry| entry.term).unwrap_or(self.hard.snapshot.last_term)
+    }
+
+    fn term_at(&self, index: u64) -> Option<u64> {
+        let snapshot = &self.hard.snapshot;
+        if index == snapshot.last_index {
+            return Some(snapshot.last_term);
+        }
+        index.checked_sub(snapshot.last_index + 1)
+            .and_then(|offset| self.hard.log.get(offset as usize))
+            .map(|entry| entry.term)
+    }
+
+    fn entry(&self, index: u64) -> Option<&LogEntry> {
+        index.checked_sub(self.hard.snapshot.last_index + 1).and_then(|offset| self.hard.log.get(offset as usize))
+    }
+
+    fn members_until(&self, index: u64) -> BTreeSet<Uuid> {
+        let mut members = self.hard.snapshot.members.clone();
+        for entry in self.hard.log.iter().take_while(|entry| entry.index <= index) {
+            match entry.kind {
+                EntryKind::AddMember(id) => {
+                    members.insert(id);
+                }
+                EntryKind::RemoveMember(id) => {
+                    members.remove(&id);
+                }
+                _ => {}
+            }
+        }
+        members
+    }
+
+    /// Composition courante: la dernière du journal, validée ou non
+    pub fn members(&self) -> BTreeSet<Uuid> {
+        self.members_until(u64::MAX)
+    }
+
+    fn quorum(members: &BTreeSet<Uuid>) -> usize {
+        members.len() / 2 + 1
+    }
+
+    fn reset_timeout(&mut self) {
+        let (min, max) = (self.config.election_timeout_min, self.config.election_timeout_max.max(self.config.election_timeout_min + 1));
+        self.elapsed = 0;
+        self.timeout = rand::thread_rng().gen_range(min..max);
+    }
+
+    fn send(&mut self, to: Uuid, message: RaftMessage) {
+        self.outbox.push((to, message));
+    }
+
+    /// Messages à transmettre depuis le dernier relevé
+    pub fn take_messages(&mut self) -> Vec<(Uuid, RaftMessage)> {
+        std::mem::take(&mut self.outbox)
+    }
+
+    fn persist(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        let Some(path) = &self.path else { return };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match serde_json::to_string(&self.hard) {
+            Ok(content) => {
+                if let Err(e) = fs::write(path, content) {
+                    warn!("⚠️ État Raft non sauvegardé: {}", e);
+                }
+            }
+            Err(e) => warn!("⚠️ État Raft non sérialisable: {}", e),
+        }
+    }
+
+    /// Avance l'horloge logique: battements du leader, élection à l'expiration du délai
+    pub fn tick(&mut self) {
+        self.elapsed += 1;
+        match self.role {
+            Role::Leader => {
+                if self.elapsed >= self.config.heartbeat_ticks {
+                    self.elapsed = 0;
+                    self.broadcast_append();
+                }
+            }
+            // Un nœud qui ne figure pas (encore) dans la composition ne se présente pas
+            _ if self.elapsed >= self.timeout && self.members().contains(&self.id) => self.campaign(),
+            _ => {}
+        }
+        self.persist();
+    }
+
+    fn campaign(&mut self) {
+        self.hard.term += 1;
+        self.hard.voted_for = Some(self.id);
+        self.dirty = true;
+        self.role = Role::Candidate;
+        self.leader = None;
+        self.votes = HashSet::from([self.id]);
+        self.reset_timeout();
+        info!("🗳️ Raft: {} candidat au mandat {}", self.id, self.hard.term);
+        if self.has_quorum(&self.votes) {
+            self.become_leader();
+            return;
+        }
+        let request = RaftMessage::RequestVote { term: self.hard.term, last_log_index: self.last_index(), last_log_term: self.last_term() };
+        for member in self.members() {
+            if member != self.id {
+                self.send(member, request.clone());
+            }
+        }
+    }
+
+    fn has_quorum(&self, voters: &HashSet<Uuid>) -> bool {
+        let members = self.members();
+        members.iter().filter(|member| voters.contains(member)).count() >= Self::quorum(&members)
+    }
+
+    fn become_follower(&mut self, term: u64, leader: Option<Uuid>) {
+        if term > self.hard.term {
+            self.hard.term = term;
+            self.hard.voted_for = None;
+            self.dirty = true;
+        }
+        if self.role != Role::Follower {
+            self.reset_timeout();
+        }
+        self.role = Role::Follower;
+        self.leader = leader;
+    }
+
+    fn become_leader(&mut self) {
+        info!("👑 Raft: {} élu leader du mandat {}", self.id, self.hard.term);
+        self.role = Role::Leader;
+        self.leader = Some(self.id);
+        self.elapsed = 0;
+        self.next_index.clear();
+        self.match_index.clear();
+        self.append(EntryKind::Noop);
+    }
+
+    fn append(&mut self, kind: EntryKind) -> u64 {
+        let index = self.last_index() + 1;
+        self.hard.log.push(LogEntry { index, term: self.hard.term, kind });
+        self.dirty = true;
+        self.advance_commit();
+        self.broadcast_append();
+        index
+    }
+
+    fn broadcast_append(&mut self) {
+        for member in self.members() {
+            if member != self.id {
+                self.send_append(member);
+            }
+        }
+    }
+
+    fn send_append(&mut self, peer: Uuid) {
+        let default_next = self.last_index() + 1;
+        let next = *self.next_index.entry(peer).or_insert(default_next);
+        let term = self.hard.term;
+        if next <= self.hard.snapshot.last_index {
+            let snapshot = self.hard.snapshot.clone();
+            self.send(peer, RaftMessage::InstallSnapshot { term, snapshot });
+            return;
+        }
+        let prev_log_index = next - 1;
+        let prev_log_term = self.term_at(prev_log_index).unwrap_or(0);
+        let entries: Vec<LogEntry> = self.hard.log.iter()
+            .filter(|entry| entry.index >= next)
+            .take(self.config.max_entries_per_message.max(1))
+            .cloned()
+            .collect();
+        let leader_commit = self.commit_index;
+        self.send(peer, RaftMessage::AppendEntries { term, prev_log_index, prev_log_term, entries, leader_commit });
+    }
+
+    /// Valide la plus haute entrée du mandat courant inscrite chez une majorité
+    fn advance_commit(&mut self) {
+        if self.role != Role::Leader {
+            return;
+        }
+        let members = self.members();
+        for index in (self.commit_index + 1..=self.last_index()).rev() {
+            if self.term_at(index) != Some(self.hard.term) {
+                continue;
+            }
+            let replicated = members.iter()
+                .filter(|member| **member == self.id || self.match_index.get(member).is_some_and(|m| *m >= index))
+                .count();
+            if replicated >= Self::quorum(&members) {
+                self.commit_index = index;
+                break;
+            }
+        }
+        self.apply_committed();
+    }
+
+    fn apply_committed(&mut self) {
+        while self.last_applied < self.commit_index {
+            self.last_applied += 1;
+            if let Some(EntryKind::Command(command)) = self.entry(self.last_applied).map(|entry| entry.kind.clone()) {
+                self.state.apply(&command);
+            }
+        }
+        self.compact();
+    }
+
+    /// Compacte les entrées appliquées en instantané au-delà du seuil configuré
+    fn compact(&mut self) {
+        let applied = self.last_applied.saturating_sub(self.hard.snapshot.last_index) as usize;
+        if applied <= self.config.snapshot_threshold {
+            return;
+        }
+        let last_index = self.last_applied;
+        self.hard.snapshot = Snapshot {
+            last_index,
+            last_term: self.term_at(last_index).unwrap_or(self.hard.snapshot.last_term),
+            members: self.members_until(last_index),
+            state: self.state.clone(),
+        };
+        self.hard.log.retain(|entry| entry.index > last_index);
+        self.dirty = true;
+    }
+
+    /// Traite un message reçu de `from`
+    pub fn step(&mut self, from: Uuid, message: RaftMessage) {
+        if message.term() > self.hard.term {
+            let leader = matches!(message, RaftMessage::AppendEntries { .. } | RaftMessage::InstallSnapshot { .. }).then_some(from);
+            self.become_follower(message.term(), leader);
+        }
+        let term = self.hard.term;
+        match message {
+            RaftMessage::RequestVote { term: candidate_term, last_log_index, last_log_term } => {
+                let up_to_date = (last_log_term, last_log_index) >= (self.last_term(), self.last_index());
+                let granted = candidate_term == term
+                    && (self.hard.voted_for.is_none() || self.hard.voted_for == Some(from))
+                    && up_to_date;
+                if granted {
+                    self.hard.voted_for = Some(from);
+                    self.dirty = true;
+                    self.elapsed = 0;
+                }
+                self.send(from, RaftMessage::VoteReply { term, granted });
+            }
+            RaftMessage::VoteReply { term: reply_term, granted } => {
+                if self.role == Role::Candidate && reply_term == term && granted {
+                    self.votes.insert(from);
+                    if self.has_quorum(&self.votes) {
+                        self.become_leader();
+                    }
+                }
+            }
+            RaftMessage::AppendEntries { term: leader_term, prev_log_index, prev_log_term, entries, leader_commit } => {
+                if leader_term < term {
+                    self.send(from, RaftMessage::AppendReply { term, success: false, match_index: 0 });
+                } else {
+                    self.become_follower(leader_term, Some(from));
+                    self.elapsed = 0;
+                    let reply = self.accept_entries(prev_log_index, prev_log_term, entries, leader_commit);
+                    self.send(from, reply);
+                }
+            }
+            RaftMessage::AppendReply { term: reply_term, success, match_index } => {
+                if self.role == Role::Leader && reply_term == term {
+                    if success {
+                        let matched = self.match_index.entry(from).or_insert(0);
+                        *matched = (*matched).max(match_index);
+                        let matched = *matched;
+                        self.next_index.insert(from, matched + 1);
+                        self.advance_commit();
+                        if matched < self.last_index() {
+                            self.send_append(from);
+                        }
+                    } else {
+                        let next = self.next_index.get(&from).copied().unwrap_or(1);
+                        self.next_index.insert(from, (match_index + 1).min(next.saturating_sub(1)).max(1));
+                        self.send_append(from);
+                    }
+                }
+            }
+            RaftMessage::InstallSnapshot { term: leader_term, snapshot } => {
+                if leader_term < term {
+                    self.send(from, RaftMessage::AppendReply { term, success: false, match_index: 0 });
+                } else {
+                    self.become_follower(leader_term, Some(from));
+                    self.elapsed = 0;
+                    let match_index = self.install_snapshot(snapshot);
+                    self.send(from, RaftMessage::AppendReply { term, success: true, match_index });
+                }
+            }
+        }
+        self.persist();
+    }
+
+    fn accept_entries(&mut self, prev_log_index: u64, prev_log_term: u64, entries: Vec<LogEntry>, leader_commit: u64) -> RaftMessage {
+        let term = self.hard.term;
+        // Une entrée antérieure à l'instantané est validée, donc identique à celle du leader
+        if prev_log_index >= self.hard.snapshot.last_index && self.term_at(prev_log_index) != Some(prev_log_term) {
+            let retry_from = self.last_index().min(prev_log_index.saturating_sub(1));
+            return RaftMessage::AppendReply { term, success: false, match_index: retry_from };
+        }
+        let last_new = prev_log_index + entries.len() as u64;
+        for entry in entries {
+            if entry.index <= self.hard.snapshot.last_index || self.term_at(entry.index) == Some(entry.term) {
+                continue;
+            }
+            // Conflit: les entrées locales divergentes et leurs suivantes sont abandonnées
+            self.hard.log.retain(|existing| existing.index < entry.index);
+            self.hard.log.push(entry);
+            self.dirty = true;
+        }
+        if leader_commit > self.commit_index {
+            self.commit_index = self.commit_index.max(leader_commit.min(last_new));
+            self.apply_committed();
+        }
+        RaftMessage::AppendReply { term, success: true, match_index: last_new }
+    }
+
+    fn install_snapshot(&mut self, snapshot: Snapshot) -> u64 {
+        let last_index = snapshot.last_index;
+        if last_index <= self.commit_index {
+            return self.commit_index;
+        }
+        if self.term_at(last_index) == Some(snapshot.last_term) {
+            self.hard.log.retain(|entry| entry.index > last_index);
+        } else {
+            self.hard.log.clear();
+        }
+        info!("📸 Raft: instantané installé jusqu'à l'entrée {}", last_index);
+        self.state = snapshot.state.clone();
+        self.hard.snapshot = snapshot;
+        self.commit_index = last_index;
+        self.last_applied = last_index;
+        self.dirty = true;
+        last_index
+    }
+
+    /// Propose une commande; renvoie l'indice de l'entrée, validée une fois `commit_index` atteint
+    pub fn propose(&mut self, command: SharedCommand) -> Result<u64, String> {
+        if !self.is_leader() {
+            return Err(format!("{} n'est pas leader (leader: {:?})", self.id, self.leader));
+        }
+        let index = self.append(EntryKind::Command(command));
+        self.persist();
+        Ok(index)
+    }
+
+    /// Ajoute ou retire un membre; un seul changement de composition non validé à la fois
+    pub fn change_membership(&mut self, change: EntryKind) -> Result<u64, String> {
+        if !self.is_leader() {
+            return Err(format!("{} n'est pas leader (leader: {:?})", self.id, self.leader));
+        }
+        if !matches!(change, EntryKind::AddMember(_) | EntryKind::RemoveMember(_)) {
+            return Err("Changement de composition attendu".to_string());
+        }
+        let pending = self.hard.log.iter()
+            .any(|entry| entry.index > self.commit_index && matches!(entry.kind, EntryKind::AddMember(_) | EntryKind::RemoveMember(_)));
+        if pending {
+            return Err("Changement de composition déjà en cours".to_string());
+        }
+        let index = self.append(change);
+        self.persist();
+        Ok(index)
+    }
+
+    /// Rapproche la composition de `desired` d'un membre au plus (le leader n'est jamais retiré);
+    /// `None` si la composition est déjà à jour ou si ce nœud n'est pas leader
+    pub fn reconcile_membership(&mut self, desired: &[Uuid]) -> Option<Result<u64, String>> {
+        if !self.is_leader() {
+            return None;
+        }
+        let members = self.members();
+        let change = desired.iter().find(|id| !members.contains(id)).map(|id| EntryKind::AddMember(*id))
+            .or_else(|| members.iter().find(|id| **id != self.id && !desired.contains(id)).map(|id| EntryKind::RemoveMember(*id)))?;
+        Some(self.change_membership(change))
+    }
+
+    pub fn report(&self) {
+        info!(
+            "🗳️ Raft: {:?} au mandat {} • {} membre(s) • validé {}/{} • instantané {} • {} chaîne(s), manifeste v{}",
+            self.role, self.hard.term, self.members().len(), self.commit_index, self.last_index(),
+            self.hard.snapshot.last_index, self.state.chains.len(), self.state.manifest_version
+        );
+    }
+}
+
+// ==================== MOTEURS DE CONSENSUS SIMULÉS ====================
+
+pub type BlockHash = u64;
+
+/// Bloc simulé: seuls comptent la filiation, le créneau et le proposeur
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub hash: BlockHash,
+    pub parent: BlockHash,
+    pub height: u64,
+    pub slot: u64,
+    pub proposer: usize,
+}
+
+impl Block {
+    pub fn genesis() -> Self {
+        Self { hash: 0, parent: 0, height: 0, slot: 0, proposer: 0 }
+    }
+
+    pub fn on(parent: &Block, slot: u64, proposer: usize) -> Self {
+        Self { hash: digest(&(parent.hash, slot, proposer)), parent: parent.hash, height: parent.height + 1, slot, proposer }
+    }
+}
+
+fn digest<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Tirage déterministe dans [0, 1)
+fn lottery(seed: u64, slot: u64, validator: usize) -> f64 {
+    (digest(&(seed, slot, validator)) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Arbre des blocs produits, forks compris
+#[derive(Debug, Clone)]
+pub struct BlockTree {
+    blocks: HashMap<BlockHash, Block>,
+}
+
+impl Default for BlockTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockTree {
+    pub fn new() -> Self {
+        Self { blocks: HashMap::from([(0, Block::genesis())]) }
+    }
+
+    pub fn get(&self, hash: BlockHash) -> Option<&Block> {
+        self.blocks.get(&hash)
+    }
+
+    pub fn insert(&mut self, block: Block) {
+        self.blocks.insert(block.hash, block);
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Choix de fork: la chaîne la plus longue parmi les blocs visibles, puis le plus petit hash
+    pub fn head<'a>(&'a self, visible: impl IntoIterator<Item = &'a BlockHash>) -> &'a Block {
+        visible.into_iter()
+            .filter_map(|hash| self.blocks.get(hash))
+            .max_by_key(|block| (block.height, Reverse(block.hash)))
+            .unwrap_or(&self.blocks[&0])
+    }
+
+    /// Chaîne de `head` à la genèse exclue, du plus haut au plus bas
+    pub fn ancestry(&self, head: &Block) -> Vec<&Block> {
+        let mut chain = Vec::new();
+        let mut current = self.blocks.get(&head.hash);
+        while let Some(block) = current.filter(|block| block.height > 0) {
+            chain.push(block);
+            current = self.blocks.get(&block.parent);
+        }
+        chain
+    }
+
+    /// Règles communes: parent connu, hauteur et créneau croissants, hash cohérent
+    pub fn check_link(&self, block: &Block) -> Result<(), String> {
+        let parent = self.blocks.get(&block.parent).ok_or_else(|| format!("Parent {:x} inconnu", block.parent))?;
+        if block.height != parent.height + 1 || block.slot <= parent.slot {
+            return Err(format!("Bloc {:x} mal chaîné à {:x}", block.hash, parent.hash));
+        }
+        if block.hash != Block::on(parent, block.slot, block.proposer).hash {
+            return Err(format!("Hash du bloc {:x} incohérent", block.hash));
+        }
+        Ok(())
+    }
+}
+
+/// Moteur de consensus interchangeable: qui propose, quels blocs sont valides, quand ils sont finaux
+pub trait ConsensusEngine: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn validators(&self) -> usize;
+
+    /// Paramètres réglables, pour les journaux et l'évolution
+    fn parameters(&self) -> BTreeMap<String, f64>;
+
+    /// Bloc de `validator` au créneau `slot` sur `parent`, s'il est éligible
+    fn propose(&self, slot: u64, validator: usize, parent: &Block) -> Option<Block>;
+
+    fn validate(&self, block: &Block, tree: &BlockTree) -> Result<(), String>;
+
+    /// Blocs de la chaîne de `head` devenus finaux depuis l'appel précédent
+    fn finalize(&mut self, tree: &BlockTree, head: &Block) -> Vec<BlockHash>;
+
+    /// Oublie la finalité acquise (nouvelle simulation)
+    fn reset(&mut self);
+
+    /// Variante aux paramètres légèrement perturbés
+    fn mutated(&self, rng: &mut StdRng) -> Box<dyn ConsensusEngine>;
+}
+
+/// Finalise les blocs de la chaîne de `head` jusqu'à la hauteur `target`
+fn finalize_up_to(finalized_height: &mut u64, tree: &BlockTree, head: &Block, target: u64) -> Vec<BlockHash> {
+    if target <= *finalized_height {
+        return Vec::new();
+    }
+    let newly: Vec<BlockHash> = tree.ancestry(head).into_iter()
+        .filter(|block| block.height > *finalized_height && block.height <= target)
+        .map(|block| block.hash)
+        .collect();
+    *finalized_height = target;
+    newly
+}
+
+/// Preuve d'autorité façon Clique: les autorités proposent à tour de rôle, des suppléants
+/// peuvent proposer hors tour, et un bloc est final quand assez d'autorités distinctes l'ont
+/// prolongé
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofOfAuthority {
+    pub authorities: usize,
+    /// Autorités suivant celle du tour autorisées à proposer
+    pub backups: usize,
+    /// Proposeurs distincts requis au-dessus d'un bloc pour le finaliser
+    pub confirmations: usize,
+    finalized_height: u64,
+}
+
+impl ProofOfAuthority {
+    pub fn new(authorities: usize) -> Self {
+        let authorities = authorities.max(1);
+        Self { authorities, backups: 0, confirmations: authorities / 2 + 1, finalized_height: 0 }
+    }
+
+    fn in_window(&self, slot: u64, validator: usize) -> bool {
+        let in_turn = (slot % self.authorities as u64) as usize;
+        (validator + self.authorities - in_turn) % self.authorities <= self.backups
+    }
+}
+
+impl ConsensusEngine for ProofOfAuthority {
+    fn name(&self) -> &'static str {
+        "poa"
+    }
+
+    fn validators(&self) -> usize {
+        self.authorities
+    }
+
+    fn parameters(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([("backups".to_string(), self.backups as f64), ("confirmations".to_string(), self.confirmations as f64)])
+    }
+
+    fn propose(&self, slot: u64, validator: usize, parent: &Block) -> Option<Block> {
+        (validator < self.authorities && self.in_window(slot, validator)).then(|| Block::on(parent, slot, validator))
+    }
+
+    fn validate(&self, block: &Block, tree: &BlockTree) -> Result<(), String> {
+        tree.check_link(block)?;
+        if block.proposer >= self.authorities || !self.in_window(block.slot, block.proposer) {
+            return Err(format!("Autorité {} hors tour au créneau {}", block.proposer, block.slot));
+        }
+        Ok(())
+    }
+
+    fn finalize(&mut self, tree: &BlockTree, head: &Block) -> Vec<BlockHash> {
+        let mut signers = HashSet::new();
+        let mut target = 0;
+        for block in tree.ancestry(head) {
+            if signers.len() >= self.confirmations {
+                target = block.height;
+                break;
+            }
+            signers.insert(block.proposer);
+        }
+        finalize_up_to(&mut self.finalized_height, tree, head, target)
+    }
+
+    fn reset(&mut self) {
+        self.finalized_height = 0;
+    }
+
+    fn mutated(&self, rng: &mut StdRng) -> Box<dyn ConsensusEngine> {
+        let mut next = Self { finalized_height: 0, ..self.clone() };
+        if rng.gen_bool(0.5) {
+            next.confirmations = (next.confirmations as i64 + if rng.gen_bool(0.5) { 1 } else { -1 }).clamp(1, self.authorities as i64) as usize;
+        } else {
+            next.backups = (next.backups as i64 + if rng.gen_bool(0.5) { 1 } else { -1 }).clamp(0, self.authorities as i64 - 1) as usize;
+        }
+        Box::new(next)
+    }
+}
+
+/// Preuve d'enjeu simplifiée (loterie par créneau façon Praos): chaque validateur est éligible
+/// avec une probabilité croissante avec sa part d'enjeu; un bloc est final à `finality_depth`
+/// blocs de profondeur
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofOfStake {
+    pub stakes: Vec<f64>,
+    /// Probabilité qu'au moins un validateur soit éligible à un créneau
+    pub active_slot_coeff: f64,
+    pub finality_depth: u64,
+    pub seed: u64,
+    finalized_height: u64,
+}
+
+impl ProofOfStake {
+    pub fn new(stakes: Vec<f64>, seed: u64) -> Self {
+        Self { stakes, active_slot_coeff: 0.5, finality_depth: 6, seed, finalized_height: 0 }
+    }
+
+    fn eligible(&self, slot: u64, validator: usize) -> bool {
+        let total: f64 = self.stakes.iter().sum();
+        let Some(stake) = self.stakes.get(validator).filter(|_| total > 0.0) else { return false };
+        let threshold = 1.0 - (1.0 - self.active_slot_coeff).powf(stake / total);
+        lottery(self.seed, slot, validator) < threshold
+    }
+}
+
+impl ConsensusEngine for ProofOfStake {
+    fn name(&self) -> &'static str {
+        "pos"
+    }
+
+    fn validators(&self) -> usize {
+        self.stakes.len()
+    }
+
+    fn parameters(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([
+            ("active-slot-coeff".to_string(), self.active_slot_coeff),
+            ("finality-depth".to_string(), self.finality_depth as f64),
+        ])
+    }
+
+    fn propose(&self, slot: u64, validator: usize, parent: &Block) -> Option<Block> {
+        self.eligible(slot, validator).then(|| Block::on(parent, slot, validator))
+    }
+
+    fn validate(&self, block: &Block, tree: &BlockTree) -> Result<(), String> {
+        tree.check_link(block)?;
+        if !self.eligible(block.slot, block.proposer) {
+            return Err(format!("Validateur {} non tiré au créneau {}", block.proposer, block.slot));
+        }
+        Ok(())
+    }
+
+    fn finalize(&mut self, tree: &BlockTree, head: &Block) -> Vec<BlockHash> {
+        let target = head.height.saturating_sub(self.finality_depth);
+        finalize_up_to(&mut self.finalized_height, tree, head, target)
+    }
+
+    fn reset(&mut self) {
+        self.finalized_height = 0;
+    }
+
+    fn mutated(&self, rng: &mut StdRng) -> Box<dyn ConsensusEngine> {
+        let mut next = Self { finalized_height: 0, ..self.clone() };
+        if rng.gen_bool(0.5) {
+            next.active_slot_coeff = (next.active_slot_coeff + rng.gen_range(-0.15..0.15)).clamp(0.05, 0.95);
+        } else {
+            next.finality_depth = (next.finality_depth as i64 + if rng.gen_bool(0.5) { 1 } else { -1 }).clamp(1, 32) as u64;
+        }
+        Box::new(next)
+    }
+}
+
+/// Moteur par défaut d'une famille (`poa` ou `pos`), à enjeux égaux
+pub fn engine_named(name: &str, validators: usize, seed: u64) -> Result<Box<dyn ConsensusEngine>, String> {
+    match name {
+        "poa" => Ok(Box::new(ProofOfAuthority::new(validators))),
+        "pos" => Ok(Box::new(ProofOfStake::new(vec![1.0; validators.max(1)], seed))),
+        other => Err(format!("Moteur de consensus inconnu: {}", other)),
+    }
+}
+
+/// Réseau simulé
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct NetworkModel {
+    pub slot_ms: u64,
+    /// Probabilité qu'un bloc parvienne à un validateur avec un créneau de retard
+    pub late_rate: f64,
+    /// Probabilité qu'un validateur manque son créneau
+    pub offline_rate: f64,
+}
+
+impl Default for NetworkModel {
+    fn default() -> Self {
+        Self { slot_ms: 2_000, late_rate: 0.1, offline_rate: 0.05 }
+    }
+}
+
+/// Mesures d'une simulation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConsensusMetrics {
+    pub engine: String,
+    pub parameters: BTreeMap<String, f64>,
+    pub slots: u64,
+    pub blocks: usize,
+    /// Blocs de la chaîne retenue
+    pub canonical: usize,
+    /// Blocs produits hors de la chaîne retenue
+    pub orphaned: usize,
+    pub rejected: usize,
+    pub fork_rate: f64,
+    pub finalized: usize,
+    /// Délai moyen entre proposition et finalité
+    pub mean_finality_ms: f64,
+    /// Blocs déclarés finaux puis abandonnés (violation de sûreté)
+    pub reverted_finalized: usize,
+}
+
+impl ConsensusMetrics {
+    /// Plus haut est meilleur: débit de la chaîne, pénalisé par les forks, la lenteur de la
+    /// finalité et surtout les finalités révoquées
+    pub fn score(&self) -> f64 {
+        let throughput = self.canonical as f64 / self.slots.max(1) as f64;
+        throughput - self.fork_rate - 0.02 * self.mean_finality_ms / 1000.0 - self.reverted_finalized as f64
+    }
+}
+
+/// Fait tourner `engine` sur `slots` créneaux; le même `seed` rejoue le même réseau
+pub fn simulate(engine: &mut dyn ConsensusEngine, network: &NetworkModel, slots: u64, seed: u64) -> ConsensusMetrics {
+    engine.reset();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let validators = engine.validators();
+    let mut tree = BlockTree::new();
+    let mut seen: Vec<HashSet<BlockHash>> = vec![HashSet::from([0]); validators];
+    let mut in_transit: Vec<(u64, usize, BlockHash)> = Vec::new();
+    let mut finalized: HashMap<BlockHash, u64> = HashMap::new();
+    let (mut produced, mut rejected) = (0, 0);
+
+    for slot in 1..=slots {
+        in_transit.retain(|(arrival, validator, hash)| {
+            let arrived = *arrival <= slot;
+            if arrived {
+                seen[*validator].insert(*hash);
+            }
+            !arrived
+        });
+        for validator in 0..validators {
+            if rng.gen_bool(network.offline_rate.clamp(0.0, 1.0)) {
+                continue;
+            }
+            let parent = tree.head(&seen[validator]).clone();
+            let Some(block) = engine.propose(slot, validator, &parent) else { continue };
+            if engine.validate(&block, &tree).is_err() {
+                rejected += 1;
+                continue;
+            }
+            produced += 1;
+            seen[validator].insert(block.hash);
+            for peer in (0..validators).filter(|peer| *peer != validator) {
+                let delay = if rng.gen_bool(network.late_rate.clamp(0.0, 1.0)) { 2 } else { 1 };
+                in_transit.push((slot + delay, peer, block.hash));
+            }
+            tree.insert(block);
+        }
+        let head = tree.head(tree.blocks.keys()).clone();
+        for hash in engine.finalize(&tree, &head) {
+            finalized.entry(hash).or_insert(slot);
+        }
+    }
+
+    let head = tree.head(tree.blocks.keys()).clone();
+    let canonical: HashSet<BlockHash> = tree.ancestry(&head).iter().map(|block| block.hash).collect();
+    let delays: Vec<u64> = finalized.iter()
+        .filter_map(|(hash, at)| tree.get(*hash).map(|block| at - block.slot))
+        .collect();
+    ConsensusMetrics {
+        engine: engine.name().to_string(),
+        parameters: engine.parameters(),
+        slots,
+        blocks: produced,
+        canonical: canonical.len(),
+        orphaned: produced - canonical.len(),
+        rejected,
+        fork_rate: if produced == 0 { 0.0 } else { (produced - canonical.len()) as f64 / produced as f64 },
+        finalized: finalized.len(),
+        mean_finality_ms: if delays.is_empty() { 0.0 } else { delays.iter().sum::<u64>() as f64 / delays.len() as f64 * network.slot_ms as f64 },
+        reverted_finalized: finalized.keys().filter(|hash| !canonical.contains(hash)).count(),
+    }
+}
+
+/// Section `[lab]` de `consensus.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct LabConfig {
+    /// Moteur de départ (`poa` ou `pos`)
+    pub engine: String,
+    pub validators: usize,
+    /// Créneaux simulés par évaluation
+    pub slots: u64,
+    pub seed: u64,
+    pub network: NetworkModel,
+}
+
+impl Default for LabConfig {
+    fn default() -> Self {
+        Self { engine: "poa".to_string(), validators: 7, slots: 200, seed: 42, network: NetworkModel::default() }
+    }
+}
+
+/// Banc d'évolution du consensus: le moteur actif est comparé, sur le même réseau simulé, à
+/// une variante réglée et aux autres familles; le meilleur le remplace
+pub struct ConsensusLab {
+    config: LabConfig,
+    active: Box<dyn ConsensusEngine>,
+    rng: StdRng,
+    pub last_metrics: Option<ConsensusMetrics>,
+    pub generations: u32,
+}
+
+impl ConsensusLab {
+    pub fn new(config: LabConfig) -> Result<Self, String> {
+        let active = engine_named(&config.engine, config.validators, config.seed)?;
+        Ok(Self { rng: StdRng::seed_from_u64(config.seed), active, config, last_metrics: None, generations: 0 })
+    }
+
+    pub fn with_engine(config: LabConfig, engine: Box<dyn ConsensusEngine>) -> Self {
+        Self { rng: StdRng::seed_from_u64(config.seed), active: engine, config, last_metrics: None, generations: 0 }
+    }
+
+    pub fn active(&self) -> &dyn ConsensusEngine {
+        self.active.as_ref()
+    }
+
+    /// Une génération: renvoie les mesures du nouveau moteur s'il a remplacé l'actif
+    pub fn evolve(&mut self) -> Option<ConsensusMetrics> {
+        self.generations += 1;
+        let seed = self.rng.gen();
+        let (slots, network) = (self.config.slots, self.config.network.clone());
+        let baseline = simulate(self.active.as_mut(), &network, slots, seed);
+
+        let mut challengers = vec![self.active.mutated(&mut self.rng)];
+        for family in ["poa", "pos"].into_iter().filter(|family| *family != self.active.name()) {
+            if let Ok(engine) = engine_named(family, self.active.validators(), seed) {
+                challengers.push(engine);
+            }
+        }
+        let best = challengers.into_iter()
+            .map(|mut engine| {
+                let metrics = simulate(engine.as_mut(), &network, slots, seed);
+                (engine, metrics)
+            })
+            .max_by(|(_, a), (_, b)| a.score().total_cmp(&b.score()));
+
+        match best {
+            Some((engine, metrics)) if metrics.score() > baseline.score() + 1e-9 => {
+                info!(
+                    "⛓️ Consensus évolué: {} {:?} (score {:.3}) → {} {:?} (score {:.3}, forks {:.1}%, finalité {:.1}s)",
+                    baseline.engine, baseline.parameters, baseline.score(),
+                    metrics.engine, metrics.parameters, metrics.score(), metrics.fork_rate * 100.0, metrics.mean_finality_ms / 1000.0
+                );
+                self.active = engine;
+                self.last_metrics = Some(metrics.clone());
+                Some(metrics)
+            }
+            _ => {
+                self.last_metrics = Some(baseline);
+                None
+            }
+        }
+    }
+
+    pub fn report(&self) {
+        match &self.last_metrics {
+            Some(metrics) => info!(
+                "⛓️ Consensus {} {:?}: forks {:.1}% • finalité {:.1}s • {}/{} blocs retenus • {} finalité(s) révoquée(s) • génération {}",
+                metrics.engine, metrics.parameters, metrics.fork_rate * 100.0, metrics.mean_finality_ms / 1000.0,
+                metrics.canonical, metrics.blocks, metrics.reverted_finalized, self.generations
+            ),
+            None => info!("⛓️ Consensus {} {:?}: pas encore évalué", self.active.name(), self.active.parameters()),
+        }
+    }
+}
+
+/// Message Raft prêt pour la messagerie inter-instances
+pub fn envelope(from: Uuid, to: Uuid, message: &RaftMessage) -> InstanceMessage {
+    InstanceMessage::Consensus { from, to, payload: serde_json::to_string(message).unwrap_or_default() }
+}
+
+pub fn open_envelope(payload: &str) -> Result<RaftMessage, String> {
+    serde_json::from_str(payload).map_err(|e| format!("Message Raft illisible: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Grappe simulée: les messages entre nœuds séparés par la partition sont perdus
+    struct Cluster {
+        nodes: BTreeMap<Uuid, RaftNode>,
+        cut: HashSet<(Uuid, Uuid)>,
+    }
+
+    impl Cluster {
+        fn new(size: usize, config: &RaftConfig) -> (Self, Vec<Uuid>) {
+            let ids: Vec<Uuid> = (0..size).map(|_| Uuid::new_v4()).collect();
+            let mut nodes: BTreeMap<Uuid, RaftNode> = ids.iter().map(|id| (*id, RaftNode::in_memory(*id, config.clone()))).collect();
+            nodes.get_mut(&ids[0]).unwrap().bootstrap();
+            let mut cluster = Self { nodes, cut: HashSet::new() };
+            cluster.run_until(|c| c.nodes[&ids[0]].is_leader());
+            // Un membre à la fois, chaque ajout validé avant le suivant
+            for joined in 1..size {
+                let desired = &ids[..=joined];
+                cluster.run_until(|c| {
+                    let _ = c.nodes.get_mut(&ids[0]).unwrap().reconcile_membership(desired);
+                    let leader = &c.nodes[&ids[0]];
+                    leader.members().len() == joined + 1 && leader.commit_index() == leader.last_index()
+                });
+            }
+            (cluster, ids)
+        }
+
+        fn partition(&mut self, isolated: &[Uuid]) {
+            for a in isolated {
+                for b in self.nodes.keys() {
+                    if !isolated.contains(b) {
+                        self.cut.insert((*a, *b));
+                        self.cut.insert((*b, *a));
+                    }
+                }
+            }
+        }
+
+        fn deliver(&mut self) {
+            for _ in 0..100 {
+                let mut messages = Vec::new();
+                for (id, node) in self.nodes.iter_mut() {
+                    messages.extend(node.take_messages().into_iter().map(|(to, message)| (*id, to, message)));
+                }
+                if messages.is_empty() {
+                    return;
+                }
+                for (from, to, message) in messages {
+                    if !self.cut.contains(&(from, to)) {
+                        if let Some(node) = self.nodes.get_mut(&to) {
+                            node.step(from, message);
+                        }
+                    }
+                }
+            }
+        }
+
+        fn run_until(&mut self, mut done: impl FnMut(&mut Self) -> bool) {
+            for _ in 0..500 {
+                self.deliver();
+                if done(self) {
+                    return;
+                }
+                for node in self.nodes.values_mut() {
+                    node.tick();
+                }
+            }
+            panic!("la grappe n'a pas convergé");
+        }
+
+        fn leader_among(&self, ids: &[Uuid]) -> Option<Uuid> {
+            ids.iter().copied().find(|id| self.nodes[id].is_leader())
+        }
+    }
+
+    fn chain(name: &str) -> SharedCommand {
+        SharedCommand::RegisterChain(toml::from_str(&format!(
+            "name = \"{}\"\nkind = \"evm\"\nrpc = \"http://localhost:8545\"\nchain-id = 1\nnative-currency = {{ symbol = \"ETH\", decimals = 18 }}",
+            name
+        )).unwrap())
+    }
+
+    #[test]
+    fn committed_commands_replicate_and_laggards_receive_snapshots() {
+        let config = RaftConfig { snapshot_threshold: 4, ..RaftConfig::default() };
+        let (mut cluster, ids) = Cluster::new(3, &config);
+        let leader = ids[0];
+        for i in 0..10 {
+            cluster.nodes.get_mut(&leader).unwrap().propose(chain(&format!("chain-{}", i))).unwrap();
+        }
+        let manifest = BTreeMap::from([("aurorae/economy.rs".to_string(), "abc".to_string())]);
+        cluster.nodes.get_mut(&leader).unwrap().propose(SharedCommand::PublishManifest { files: manifest.clone() }).unwrap();
+        assert!(cluster.nodes.get_mut(&ids[1]).unwrap().propose(chain("refusée")).is_err());
+        cluster.run_until(|c| c.nodes.values().all(|n| n.commit_index() == c.nodes[&leader].last_index()));
+        for node in cluster.nodes.values() {
+            assert_eq!(node.state().chains.len(), 10);
+            assert_eq!((&node.state().manifest, node.state().manifest_version), (&manifest, 1));
+            assert!(node.snapshot().last_index > 0);
+        }
+
+        // Un nouveau membre rattrape le journal compacté par l'instantané du leader
+        let late = Uuid::new_v4();
+        cluster.nodes.insert(late, RaftNode::in_memory(late, config.clone()));
+        let desired: Vec<Uuid> = ids.iter().copied().chain([late]).collect();
+        cluster.run_until(|c| {
+            let _ = c.nodes.get_mut(&leader).unwrap().reconcile_membership(&desired);
+            c.nodes[&late].state() == c.nodes[&leader].state() && c.nodes[&leader].members().len() == 4
+        });
+        assert!(cluster.nodes[&late].snapshot().last_index > 0);
+    }
+
+    #[test]
+    fn engines_report_finality_and_forks_and_reject_ineligible_blocks() {
+        let calm = NetworkModel { slot_ms: 1_000, late_rate: 0.0, offline_rate: 0.0 };
+        let mut poa = ProofOfAuthority::new(5);
+        let metrics = simulate(&mut poa, &calm, 50, 1);
+        // Un proposeur par créneau, finalité après trois autorités distinctes
+        assert_eq!((metrics.blocks, metrics.canonical, metrics.orphaned), (50, 50, 0));
+        assert_eq!(metrics.mean_finality_ms, 3_000.0);
+        assert_eq!(metrics.finalized, 47);
+
+        let mut tree = BlockTree::new();
+        let genesis = Block::genesis();
+        let out_of_turn = Block::on(&genesis, 1, 3);
+        assert!(poa.validate(&out_of_turn, &tree).is_err());
+        let in_turn = poa.propose(1, 1, &genesis).unwrap();
+        assert!(poa.validate(&in_turn, &tree).is_ok());
+        tree.insert(in_turn.clone());
+        assert!(poa.validate(&Block { height: 5, ..Block::on(&in_turn, 2, 2) }, &tree).is_err());
+
+        // Loterie généreuse et réseau lent: plusieurs proposeurs par créneau, donc des forks
+        let mut pos = ProofOfStake::new(vec![1.0; 7], 9);
+        pos.active_slot_coeff = 0.9;
+        let metrics = simulate(&mut pos, &NetworkModel { late_rate: 0.3, ..calm }, 100, 1);
+        assert!(metrics.fork_rate > 0.2);
+        assert!(metrics.finalized > 0 && metrics.canonical < metrics.blocks);
+        let ineligible = (0..7).find_map(|v| pos.propose(1, v, &genesis).is_none().then(|| Block::on(&genesis, 1, v))).unwrap();
+        assert!(pos.validate(&ineligible, &BlockTree::new()).is_err());
+    }
+
+    #[test]
+    fn lab_replaces_a_fork_prone_engine() {
+        let mut forky = ProofOfStake::new(vec![1.0; 7], 3);
+        forky.active_slot_coeff = 0.95;
+        let config = LabConfig { slots: 150, ..LabConfig::default() };
+        let mut lab = ConsensusLab::with_engine(config.clone(), Box::new(forky.clone()));
+        let before = simulate(&mut forky, &config.network, config.slots, 7);
+        let after = lab.evolve().expect("une variante moins sujette aux forks l'emporte");
+        assert!(after.fork_rate < before.fork_rate);
+        assert_eq!(lab.last_metrics.as_ref(), Some(&after));
+        assert_eq!(lab.active().parameters(), after.parameters);
+    }
+
+    #[test]
+    fn partitioned_leader_is_replaced_and_its_uncommitted_entries_discarded() {
+        let (mut cluster, ids) = Cluster::new(3, &RaftConfig::default());
+        let old_leader = ids[0];
+        cluster.partition(&[old_leader]);
+        // Minoritaire, l'ancien leader accepte la commande sans jamais pouvoir la valider
+        cluster.nodes.get_mut(&old_leader).unwrap().propose(chain("orpheline")).unwrap();
+        let majority = [ids[1], ids[2]];
+        cluster.run_until(|c| c.leader_among(&majority).is_some());
+        let new_leader = cluster.leader_among(&majority).unwrap();
+        cluster.nodes.get_mut(&new_leader).unwrap().propose(chain("validée")).unwrap();
+        cluster.run_until(|c| majority.iter().all(|id| c.nodes[id].state().chains.contains_key("validée")));
+        assert!(cluster.nodes[&old_leader].is_leader());
+        assert!(cluster.nodes[&old_leader].state().chains.is_empty());
+
+        cluster.cut.clear();
+        cluster.run_until(|c| c.nodes[&old_leader].state().chains.contains_key("validée"));
+        let old = &cluster.nodes[&old_leader];
+        assert!(!old.is_leader());
+        assert_eq!(old.leader(), Some(new_leader));
+        assert!(!old.state().chains.contains_key("orpheline"));
+        assert!(cluster.nodes.values().all(|n| !n.state().chains.contains_key("orpheline")));
+    }
+}
@@ -0,0 +1,285 @@
+//! AURORAE++ - contract_verification.rs
+//!
+//! Vérification des contrats déployés: soumission de la source et des métadonnées
+//! aux API compatibles Etherscan et à Sourcify, puis suivi du statut jusqu'au verdict.
+
+use std::fs;
+use std::time::Duration;
+
+use chrono::Utc;
+use ethers::types::Address;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::chain_registry::ChainConfig;
+use crate::deployer::ContractArtifact;
+use crate::secrets::secret;
+
+pub const SOURCIFY_API: &str = "https://sourcify.dev/server";
+/// Variable contenant la clé d'API Etherscan (ou compatible)
+pub const ETHERSCAN_KEY_ENV: &str = "ETHERSCAN_API_KEY";
+
+const STATUS_POLL_SECS: u64 = 5;
+const STATUS_MAX_POLLS: u32 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationService {
+    Etherscan,
+    Sourcify,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationStatus {
+    Verified,
+    /// Sourcify: bytecode identique mais métadonnées différentes
+    PartiallyVerified,
+    AlreadyVerified,
+    Failed(String),
+    /// Pas de verdict dans le délai imparti
+    Pending,
+    Skipped(String),
+}
+
+/// Résultat d'une vérification, enregistré dans le `DeploymentResult`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationRecord {
+    pub service: VerificationService,
+    pub status: VerificationStatus,
+    /// Identifiant de suivi (GUID Etherscan)
+    pub reference: Option<String>,
+    pub checked_at: String,
+}
+
+impl VerificationRecord {
+    fn new(service: VerificationService, status: VerificationStatus, reference: Option<String>) -> Self {
+        Self { service, status, reference, checked_at: Utc::now().to_rfc3339() }
+    }
+
+    pub fn is_verified(&self) -> bool {
+        matches!(
+            self.status,
+            VerificationStatus::Verified | VerificationStatus::PartiallyVerified | VerificationStatus::AlreadyVerified
+        )
+    }
+}
+
+pub struct ContractVerifier {
+    client: reqwest::Client,
+    pub etherscan_key: Option<String>,
+    pub sourcify_api: String,
+}
+
+impl Default for ContractVerifier {
+    fn default() -> Self {
+        Self::new(secret("contract_verification", ETHERSCAN_KEY_ENV).map(|key| key.expose().to_string()))
+    }
+}
+
+impl ContractVerifier {
+    pub fn new(etherscan_key: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            etherscan_key,
+            sourcify_api: SOURCIFY_API.to_string(),
+        }
+    }
+
+    /// Vérifie le contrat auprès de tous les services disponibles pour la chaîne
+    pub async fn verify_all(
+        &self,
+        chain: &ChainConfig,
+        address: Address,
+        artifact: &ContractArtifact,
+        constructor_args: &str,
+    ) -> Vec<VerificationRecord> {
+        let etherscan = match self.verify_etherscan(chain, address, artifact, constructor_args).await {
+            Ok(record) => record,
+            Err(e) => VerificationRecord::new(VerificationService::Etherscan, VerificationStatus::Failed(e), None),
+        };
+        let sourcify = match self.verify_sourcify(chain, address, artifact).await {
+            Ok(record) => record,
+            Err(e) => VerificationRecord::new(VerificationService::Sourcify, VerificationStatus::Failed(e), None),
+        };
+
+        for record in [&etherscan, &sourcify] {
+            info!("🔏 Vérification {:?} de {:?}: {:?}", record.service, address, record.status);
+        }
+        vec![etherscan, sourcify]
+    }
+
+    /// Soumet la source à une API compatible Etherscan puis interroge le statut
+    pub async fn verify_etherscan(
+        &self,
+        chain: &ChainConfig,
+        address: Address,
+        artifact: &ContractArtifact,
+        constructor_args: &str,
+    ) -> Result<VerificationRecord, String> {
+        let skipped = |reason: &str| VerificationRecord::new(
+            VerificationService::Etherscan,
+            VerificationStatus::Skipped(reason.to_string()),
+            None,
+        );
+        let Some(api) = &chain.explorer_api else { return Ok(skipped("aucune API d'explorateur configurée")) };
+        let Some(key) = &self.etherscan_key else { return Ok(skipped("clé d'API absente")) };
+        if artifact.metadata.compiler != "solc" && artifact.metadata.compiler != "foundry" {
+            return Ok(skipped("compilateur non pris en charge"));
+        }
+
+        let source = fs::read_to_string(&artifact.source_path)
+            .map_err(|e| format!("Erreur lecture {}: {}", artifact.source_path, e))?;
+        let address = format!("{:?}", address);
+        let runs = artifact.metadata.optimizer_runs.to_string();
+        let compiler_version = match etherscan_compiler_version(&artifact.metadata.version) {
+            Ok(version) => version,
+            Err(e) => return Ok(VerificationRecord::new(VerificationService::Etherscan, VerificationStatus::Failed(e), None)),
+        };
+
+        let response: Value = self.client
+            .post(api)
+            .form(&[
+                ("apikey", key.as_str()),
+                ("module", "contract"),
+                ("action", "verifysourcecode"),
+                ("contractaddress", address.as_str()),
+                ("sourceCode", source.as_str()),
+                ("codeformat", "solidity-single-file"),
+                ("contractname", artifact.name.as_str()),
+                ("compilerversion", compiler_version.as_str()),
+                ("optimizationUsed", "1"),
+                ("runs", runs.as_str()),
+                // Orthographe imposée par l'API Etherscan
+                ("constructorArguements", constructor_args.trim_start_matches("0x")),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Explorateur injoignable: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Réponse de l'explorateur invalide: {}", e))?;
+
+        let result = response.get("result").and_then(|r| r.as_str()).unwrap_or_default().to_string();
+        if response.get("status").and_then(|s| s.as_str()) != Some("1") {
+            let status = if result.to_lowercase().contains("already verified") {
+                VerificationStatus::AlreadyVerified
+            } else {
+                VerificationStatus::Failed(result)
+            };
+            return Ok(VerificationRecord::new(VerificationService::Etherscan, status, None));
+        }
+
+        let guid = result;
+        let status = self.poll_etherscan(api, key, &guid).await?;
+        Ok(VerificationRecord::new(VerificationService::Etherscan, status, Some(guid)))
+    }
+
+    async fn poll_etherscan(&self, api: &str, key: &str, guid: &str) -> Result<VerificationStatus, String> {
+        for _ in 0..STATUS_MAX_POLLS {
+            tokio::time::sleep(Duration::from_secs(STATUS_POLL_SECS)).await;
+            let response: Value = self.client
+                .get(api)
+                .query(&[("apikey", key), ("module", "contract"), ("action", "checkverifystatus"), ("guid", guid)])
+                .send()
+                .await
+                .map_err(|e| format!("Explorateur injoignable: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Réponse de l'explorateur invalide: {}", e))?;
+
+            let result = response.get("result").and_then(|r| r.as_str()).unwrap_or_default();
+            if result.starts_with("Pending") {
+                continue;
+            }
+            if result.starts_with("Pass") {
+                return Ok(VerificationStatus::Verified);
+            }
+            if result.to_lowercase().contains("already verified") {
+                return Ok(VerificationStatus::AlreadyVerified);
+            }
+            return Ok(VerificationStatus::Failed(result.to_string()));
+        }
+        Ok(VerificationStatus::Pending)
+    }
+
+    /// Soumet la source et les métadonnées solc à Sourcify (verdict synchrone)
+    pub async fn verify_sourcify(
+        &self,
+        chain: &ChainConfig,
+        address: Address,
+        artifact: &ContractArtifact,
+    ) -> Result<VerificationRecord, String> {
+        let (Some(chain_id), Some(metadata_path)) = (chain.chain_id, &artifact.solc_metadata_path) else {
+            return Ok(VerificationRecord::new(
+                VerificationService::Sourcify,
+                VerificationStatus::Skipped("métadonnées solc ou chain-id absents".to_string()),
+                None,
+            ));
+        };
+
+        let metadata = fs::read_to_string(metadata_path)
+            .map_err(|e| format!("Erreur lecture {}: {}", metadata_path, e))?;
+        let source = fs::read_to_string(&artifact.source_path)
+            .map_err(|e| format!("Erreur lecture {}: {}", artifact.source_path, e))?;
+        let source_name = std::path::Path::new(&artifact.source_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| format!("{}.sol", artifact.name));
+
+        let response: Value = self.client
+            .post(format!("{}/verify", self.sourcify_api))
+            .json(&json!({
+                "address": format!("{:?}", address),
+                "chain": chain_id.to_string(),
+                "files": { "metadata.json": metadata, source_name: source },
+            }))
+            .send()
+            .await
+            .map_err(|e| format!("Sourcify injoignable: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Réponse Sourcify invalide: {}", e))?;
+
+        let status = match response.pointer("/result/0/status").and_then(|s| s.as_str()) {
+            Some("perfect") => VerificationStatus::Verified,
+            Some("partial") => VerificationStatus::PartiallyVerified,
+            _ => VerificationStatus::Failed(
+                response.get("error").map(|e| e.to_string()).unwrap_or_else(|| response.to_string()),
+            ),
+        };
+        Ok(VerificationRecord::new(VerificationService::Sourcify, status, None))
+    }
+}
+
+/// Convertit une version solc (« 0.8.20+commit.a1b79de6.Linux.g++ ») au format Etherscan
+/// (« v0.8.20+commit.a1b79de6 »); toute autre chaîne (version de forge…) est refusée
+pub fn etherscan_compiler_version(version: &str) -> Result<String, String> {
+    let trimmed = version.trim().trim_start_matches('v');
+    let core = match trimmed.find("+commit.") {
+        Some(idx) => {
+            let end = (idx + "+commit.".len() + 8).min(trimmed.len());
+            &trimmed[..end]
+        }
+        None => trimmed.split_whitespace().next().unwrap_or(trimmed),
+    };
+    let release = core.split('+').next().unwrap_or_default();
+    let numbers: Vec<&str> = release.split('.').collect();
+    if numbers.len() != 3 || numbers.iter().any(|n| n.is_empty() || !n.chars().all(|c| c.is_ascii_digit())) {
+        return Err(format!("Version solc invalide: {}", version));
+    }
+    Ok(format!("v{}", core))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_solc_versions_for_etherscan() {
+        assert_eq!(etherscan_compiler_version("0.8.20+commit.a1b79de6.Linux.g++").unwrap(), "v0.8.20+commit.a1b79de6");
+        assert_eq!(etherscan_compiler_version("v0.8.19+commit.7dd6d404").unwrap(), "v0.8.19+commit.7dd6d404");
+        assert_eq!(etherscan_compiler_version("0.8.24").unwrap(), "v0.8.24");
+        assert!(etherscan_compiler_version("forge 0.2.0 (1b2c3d4 2024-05-01T00:00:00Z)").is_err());
+        assert!(etherscan_compiler_version("inconnue").is_err());
+    }
+}
@@ -2,56 +2,529 @@
 //!
 //! Moteur d'exploration GitHub/Web. Permet à l'IA de télécharger des dépôts réels depuis Internet pour les analyser et apprendre.
 //! Utilise la connexion Internet locale de l'utilisateur.
+//!
+//! Les dépôts sont récupérés en parallèle (`ClonePool`, libgit2 sur des fils bloquants) dans
+//! un miroir nu local (`aurorae_state/mirrors`), en profondeur limitée; seuls les chemins
+//! retenus (`src/` par défaut) sont extraits vers le feed, dans la limite de taille du dépôt.
+//! La licence est lue à la racine du miroir et consignée, avec le commit, dans le fichier de
+//! provenance du dépôt extrait.
+//!
+//! Le feed est persistant: une nouvelle exécution ne réécrit que le diff entre le commit déjà
+//! extrait et celui récupéré, et signale les fichiers ajoutés, modifiés ou supprimés
+//! (`FeedChanges`) pour que l'extraction de patterns et l'évolution de code ne traitent que ceux-là.
 
-use std::process::Command;
-use std::path::Path;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use git2::{Delta, FetchOptions, FileMode, Oid, RemoteCallbacks, Repository, TreeWalkMode, TreeWalkResult};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+use crate::config;
+use crate::license::{self, SourceProvenance};
+
+pub const DEFAULT_CRAWLER_CONFIG_PATH: &str = "crawler.toml";
+
+/// Récupération des dépôts (`crawler.toml`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct CrawlerConfig {
+    /// Dossier où les sources extraites sont analysées
+    pub feed_path: PathBuf,
+    /// Miroirs nus conservés entre deux exécutions
+    pub mirror_path: PathBuf,
+    /// Profondeur de l'historique récupéré, 0 pour tout l'historique
+    pub depth: i32,
+    /// Préfixes extraits vers le feed; vide pour tout le dépôt
+    pub sparse_paths: Vec<String>,
+    /// Volume maximal téléchargé puis extrait par dépôt, en octets
+    pub max_repo_size: u64,
+    /// Clonages simultanés
+    pub concurrency: usize,
+}
+
+impl Default for CrawlerConfig {
+    fn default() -> Self {
+        Self {
+            // `paths.feed` d'aurorae.toml, sauf si crawler.toml le précise
+            feed_path: config::feed_path(),
+            mirror_path: PathBuf::from("aurorae_state/mirrors"),
+            depth: 1,
+            sparse_paths: vec!["src/".to_string()],
+            max_repo_size: 512 * 1024 * 1024,
+            concurrency: 4,
+        }
+    }
+}
+
+impl CrawlerConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Configuration du crawler invalide: {}", e))
+    }
+
+    /// `crawler.toml`, ou la configuration par défaut s'il est absent ou invalide
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_CRAWLER_CONFIG_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: configuration du crawler par défaut", e);
+            Self::default()
+        })
+    }
+
+    fn keeps(&self, path: &str) -> bool {
+        self.sparse_paths.is_empty() || self.sparse_paths.iter().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+}
+
+/// Avancement d'un clonage, transmis au rappel de progression
+#[derive(Debug, Clone, PartialEq)]
+pub enum CloneProgress {
+    Fetching { repo: String, received_objects: usize, total_objects: usize, received_bytes: usize },
+    CheckingOut { repo: String, files: usize },
+    Done { repo: String, files: usize, bytes: u64 },
+    Failed { repo: String, error: String },
+}
 
-/// Configuration du chemin d'extraction locale
-const FEED_PATH: &str = "C:\\Users\\admin\\.github_feed";
+pub type ProgressCallback = Arc<dyn Fn(CloneProgress) + Send + Sync>;
+
+/// Fichiers du feed touchés par une extraction
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedChanges {
+    /// Fichiers ajoutés ou modifiés
+    pub changed: Vec<PathBuf>,
+    /// Fichiers supprimés
+    pub removed: Vec<PathBuf>,
+}
+
+impl FeedChanges {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// Cumule les changements de plusieurs dépôts
+    pub fn merge(&mut self, other: FeedChanges) {
+        self.changed.extend(other.changed);
+        self.removed.extend(other.removed);
+    }
+}
+
+/// Résultat d'un clonage
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloneOutcome {
+    pub repo: String,
+    pub commit: String,
+    pub target: PathBuf,
+    /// Fichiers écrits par cette extraction
+    pub files: usize,
+    pub bytes: u64,
+    /// Fichiers modifiés depuis la précédente extraction (tous au premier passage)
+    pub changes: FeedChanges,
+    /// Identifiant SPDX détecté, absent si aucune licence n'est reconnue
+    pub license: Option<String>,
+    /// Vrai si le miroir existait déjà (seule une mise à jour a été récupérée)
+    pub from_mirror: bool,
+}
+
+/// Pool de clonages parallèles; chaque dépôt est traité sur un fil bloquant
+pub struct ClonePool {
+    config: Arc<CrawlerConfig>,
+    permits: Arc<Semaphore>,
+}
+
+impl ClonePool {
+    pub fn new(config: CrawlerConfig) -> Self {
+        let permits = Arc::new(Semaphore::new(config.concurrency.max(1)));
+        Self { config: Arc::new(config), permits }
+    }
+
+    pub fn config(&self) -> &CrawlerConfig {
+        &self.config
+    }
+
+    /// Clone tous les dépôts, au plus `concurrency` à la fois; résultats dans l'ordre des URL
+    pub async fn clone_all(&self, urls: &[&str], progress: ProgressCallback) -> Vec<Result<CloneOutcome, String>> {
+        let handles: Vec<_> = urls.iter().map(|url| {
+            let (url, config, permits, progress) = (url.to_string(), self.config.clone(), self.permits.clone(), progress.clone());
+            tokio::spawn(async move {
+                let _permit = permits.acquire_owned().await.map_err(|e| e.to_string())?;
+                tokio::task::spawn_blocking(move || clone_into_feed(&url, &config, &progress))
+                    .await
+                    .map_err(|e| format!("Clonage interrompu: {}", e))?
+            })
+        }).collect();
+
+        let mut outcomes = Vec::with_capacity(handles.len());
+        for handle in handles {
+            outcomes.push(handle.await.unwrap_or_else(|e| Err(format!("Clonage interrompu: {}", e))));
+        }
+        outcomes
+    }
+}
+
+/// Rappel de progression affichant une barre par dépôt
+pub fn console_progress() -> ProgressCallback {
+    let bars = MultiProgress::new();
+    let active: Mutex<std::collections::HashMap<String, ProgressBar>> = Mutex::new(std::collections::HashMap::new());
+    Arc::new(move |event: CloneProgress| {
+        let repo = match &event {
+            CloneProgress::Fetching { repo, .. } | CloneProgress::CheckingOut { repo, .. } | CloneProgress::Done { repo, .. } | CloneProgress::Failed { repo, .. } => repo.clone(),
+        };
+        let mut active = active.lock();
+        let bar = active.entry(repo.clone()).or_insert_with(|| {
+            let bar = bars.add(ProgressBar::new(0));
+            if let Ok(style) = ProgressStyle::with_template("[AURORAE++] {prefix:>20} {bar:30} {pos}/{len} {msg}") {
+                bar.set_style(style);
+            }
+            bar.set_prefix(repo.clone());
+            bar
+        });
+        match event {
+            CloneProgress::Fetching { received_objects, total_objects, received_bytes, .. } => {
+                bar.set_length(total_objects as u64);
+                bar.set_position(received_objects as u64);
+                bar.set_message(format!("{:.1} Mo", received_bytes as f64 / (1024.0 * 1024.0)));
+            }
+            CloneProgress::CheckingOut { files, .. } => bar.set_message(format!("{} fichiers extraits", files)),
+            CloneProgress::Done { files, bytes, .. } => {
+                bar.finish_with_message(format!("✓ {} fichiers, {:.1} Mo", files, bytes as f64 / (1024.0 * 1024.0)));
+                active.remove(&repo);
+            }
+            CloneProgress::Failed { error, .. } => {
+                bar.abandon_with_message(format!("✗ {}", error));
+                active.remove(&repo);
+            }
+        }
+    })
+}
 
 /// Clone un dépôt GitHub donné vers le dossier local `github_feed/<nom>`
 pub fn clone_repo(repo_url: &str) -> Result<(), String> {
+    let progress: ProgressCallback = Arc::new(|_| {});
+    clone_into_feed(repo_url, &CrawlerConfig::load_or_default(), &progress).map(|_| ())
+}
+
+/// Met à jour le miroir du dépôt puis en extrait les chemins retenus vers le feed
+pub fn clone_into_feed(repo_url: &str, config: &CrawlerConfig, progress: &ProgressCallback) -> Result<CloneOutcome, String> {
     let repo_name = extract_repo_name(repo_url)?;
-    let target_dir = format!("{}/{}", FEED_PATH, repo_name);
+    let result = fetch_mirror(repo_url, &repo_name, config, progress)
+        .and_then(|(mirror, commit, from_mirror)| {
+            let (target, changes, bytes) = extract_sparse(&mirror, commit, &repo_name, config, progress)?;
+            let provenance = SourceProvenance { repo: repo_url.to_string(), commit: commit.to_string(), license: detect_license(&mirror, commit) };
+            provenance.save_in(&target)?;
+            let files = changes.changed.len();
+            Ok(CloneOutcome { repo: repo_name.clone(), commit: commit.to_string(), target, files, bytes, changes, license: provenance.license, from_mirror })
+        });
+    match &result {
+        Ok(outcome) => {
+            progress(CloneProgress::Done { repo: repo_name.clone(), files: outcome.files, bytes: outcome.bytes });
+            info!("Dépôt {} prêt ({} fichier(s) modifié(s), {} supprimé(s), commit {:.8}, licence {}).",
+                repo_name, outcome.changes.changed.len(), outcome.changes.removed.len(), outcome.commit,
+                outcome.license.as_deref().unwrap_or("inconnue"));
+        }
+        Err(error) => progress(CloneProgress::Failed { repo: repo_name.clone(), error: error.clone() }),
+    }
+    result
+}
+
+/// Miroir nu `<mirror_path>/<nom>.git`: créé au premier passage, simplement mis à jour ensuite
+fn fetch_mirror(repo_url: &str, repo_name: &str, config: &CrawlerConfig, progress: &ProgressCallback) -> Result<(Repository, Oid, bool), String> {
+    let path = config.mirror_path.join(format!("{}.git", repo_name));
+    let from_mirror = path.exists();
+    let mirror = if from_mirror {
+        Repository::open_bare(&path).map_err(git_error)?
+    } else {
+        fs::create_dir_all(&config.mirror_path).map_err(|e| e.to_string())?;
+        info!("Clonage de {} vers le miroir {}...", repo_url, path.display());
+        Repository::init_bare(&path).map_err(git_error)?
+    };
 
-    if Path::new(&target_dir).exists() {
-        println!("[AURORAE++] Dépôt déjà présent localement: {}", target_dir);
-        return Ok(());
+    let mut remote = mirror.remote_anonymous(repo_url).map_err(git_error)?;
+    remote.connect(git2::Direction::Fetch).map_err(git_error)?;
+    let branch = remote.default_branch().map_err(git_error)?.as_str().unwrap_or("refs/heads/main").to_string();
+    remote.disconnect().map_err(git_error)?;
+
+    let mut callbacks = RemoteCallbacks::new();
+    let (name, max_bytes) = (repo_name.to_string(), config.max_repo_size);
+    callbacks.transfer_progress(move |stats| {
+        progress(CloneProgress::Fetching {
+            repo: name.clone(),
+            received_objects: stats.received_objects(),
+            total_objects: stats.total_objects(),
+            received_bytes: stats.received_bytes(),
+        });
+        // Retourner faux interrompt le transfert: dépôt au-delà de la limite
+        (stats.received_bytes() as u64) <= max_bytes
+    });
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(callbacks);
+    if config.depth > 0 {
+        options.depth(config.depth);
     }
+    let refspec = format!("+{}:{}", branch, branch);
+    remote.fetch(&[refspec.as_str()], Some(&mut options), None).map_err(|e| {
+        if e.code() == git2::ErrorCode::User {
+            format!("{} dépasse la limite de {} octets", repo_name, max_bytes)
+        } else {
+            git_error(e)
+        }
+    })?;
 
-    println!("[AURORAE++] Clonage de {} vers {}...", repo_url, target_dir);
-    let status = Command::new("git")
-        .arg("clone")
-        .arg(repo_url)
-        .arg(&target_dir)
-        .status()
-        .map_err(|e| format!("Erreur de lancement git: {}", e))?;
+    let commit = mirror.refname_to_id(&branch).map_err(git_error)?;
+    drop(remote);
+    Ok((mirror, commit, from_mirror))
+}
 
-    if status.success() {
-        println!("[AURORAE++] Dépôt cloné avec succès.");
-        Ok(())
-    } else {
-        Err("Échec du clonage Git".to_string())
+/// Extraction vers `<feed_path>/<nom>`: si le feed contient déjà un commit connu du miroir,
+/// seul le diff entre les deux est appliqué, sinon le dépôt est entièrement ré-extrait
+fn extract_sparse(mirror: &Repository, commit: Oid, repo_name: &str, config: &CrawlerConfig, progress: &ProgressCallback) -> Result<(PathBuf, FeedChanges, u64), String> {
+    let target = config.feed_path.join(repo_name);
+    let previous = SourceProvenance::load_in(&target)
+        .and_then(|provenance| Oid::from_str(&provenance.commit).ok())
+        .and_then(|oid| mirror.find_commit(oid).ok());
+    match previous {
+        Some(previous) if previous.id() == commit => Ok((target, FeedChanges::default(), 0)),
+        Some(previous) => {
+            let old_tree = previous.tree().map_err(git_error)?;
+            apply_diff(mirror, &old_tree, commit, &target, repo_name, config, progress).map(|(changes, bytes)| (target, changes, bytes))
+        }
+        None => checkout_sparse(mirror, commit, &target, repo_name, config, progress).map(|(changes, bytes)| (target, changes, bytes)),
+    }
+}
+
+/// Écrit un blob du miroir dans le feed en comptant son volume
+fn write_blob(mirror: &Repository, id: Oid, path: &Path, bytes: &mut u64, repo_name: &str, config: &CrawlerConfig) -> Result<(), String> {
+    let blob = mirror.find_blob(id).map_err(git_error)?;
+    *bytes += blob.size() as u64;
+    if *bytes > config.max_repo_size {
+        return Err(format!("{} dépasse la limite de {} octets une fois extrait", repo_name, config.max_repo_size));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, blob.content()).map_err(|e| e.to_string())
+}
+
+/// Mise à jour incrémentale: seuls les fichiers retenus qui diffèrent entre les deux commits
+/// sont réécrits ou supprimés
+fn apply_diff(mirror: &Repository, old_tree: &git2::Tree, commit: Oid, target: &Path, repo_name: &str, config: &CrawlerConfig, progress: &ProgressCallback) -> Result<(FeedChanges, u64), String> {
+    let new_tree = mirror.find_commit(commit).and_then(|c| c.tree()).map_err(git_error)?;
+    let diff = mirror.diff_tree_to_tree(Some(old_tree), Some(&new_tree), None).map_err(git_error)?;
+    let (mut changes, mut bytes) = (FeedChanges::default(), 0u64);
+    for delta in diff.deltas() {
+        if delta.status() == Delta::Deleted {
+            if let Some(relative) = delta.old_file().path().and_then(|p| p.to_str()).filter(|p| config.keeps(p)) {
+                let path = target.join(relative);
+                if path.exists() {
+                    fs::remove_file(&path).map_err(|e| e.to_string())?;
+                }
+                changes.removed.push(path);
+            }
+            continue;
+        }
+        let file = delta.new_file();
+        if !matches!(file.mode(), FileMode::Blob | FileMode::BlobExecutable) {
+            continue;
+        }
+        if let Some(relative) = file.path().and_then(|p| p.to_str()).filter(|p| config.keeps(p)) {
+            let path = target.join(relative);
+            write_blob(mirror, file.id(), &path, &mut bytes, repo_name, config)?;
+            changes.changed.push(path);
+            if changes.changed.len() % 500 == 0 {
+                progress(CloneProgress::CheckingOut { repo: repo_name.to_string(), files: changes.changed.len() });
+            }
+        }
+    }
+    Ok((changes, bytes))
+}
+
+/// Extraction complète: seuls les fichiers sous `sparse_paths` sont écrits, dans la limite de
+/// taille; les fichiers d'une extraction antérieure absents du commit sont signalés supprimés
+fn checkout_sparse(mirror: &Repository, commit: Oid, target: &Path, repo_name: &str, config: &CrawlerConfig, progress: &ProgressCallback) -> Result<(FeedChanges, u64), String> {
+    let mut stale = Vec::new();
+    if target.exists() {
+        list_files(target, &mut stale);
+        fs::remove_dir_all(target).map_err(|e| e.to_string())?;
+    }
+    fs::create_dir_all(target).map_err(|e| e.to_string())?;
+
+    let tree = mirror.find_commit(commit).and_then(|c| c.tree()).map_err(git_error)?;
+    let (mut changes, mut bytes, mut error) = (FeedChanges::default(), 0u64, None);
+    tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        if entry.kind() != Some(git2::ObjectType::Blob) {
+            return TreeWalkResult::Ok;
+        }
+        let relative = format!("{}{}", root, entry.name().unwrap_or_default());
+        if !config.keeps(&relative) {
+            return TreeWalkResult::Ok;
+        }
+        let path = target.join(&relative);
+        match write_blob(mirror, entry.id(), &path, &mut bytes, repo_name, config) {
+            Ok(()) => {
+                changes.changed.push(path);
+                if changes.changed.len() % 500 == 0 {
+                    progress(CloneProgress::CheckingOut { repo: repo_name.to_string(), files: changes.changed.len() });
+                }
+                TreeWalkResult::Ok
+            }
+            Err(e) => {
+                error = Some(e);
+                TreeWalkResult::Abort
+            }
+        }
+    }).ok();
+    if let Some(e) = error {
+        return Err(e);
     }
+    changes.removed = stale.into_iter().filter(|path| !changes.changed.contains(path)).collect();
+    Ok((changes, bytes))
+}
+
+/// Fichiers (hors provenance) présents sous `dir`
+fn list_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            list_files(&path, files);
+        } else if entry.file_name() != license::PROVENANCE_FILE {
+            files.push(path);
+        }
+    }
+}
+
+/// Licence lue dans les fichiers de la racine du commit (LICENSE*, COPYING*, manifestes),
+/// que l'extraction partielle n'écrit pas dans le feed
+fn detect_license(mirror: &Repository, commit: Oid) -> Option<String> {
+    let tree = mirror.find_commit(commit).and_then(|c| c.tree()).ok()?;
+    let files: Vec<(String, String)> = tree.iter()
+        .filter(|entry| entry.kind() == Some(git2::ObjectType::Blob))
+        .filter_map(|entry| {
+            let name = entry.name()?.to_string();
+            if !license::is_license_candidate(&name) {
+                return None;
+            }
+            let blob = mirror.find_blob(entry.id()).ok()?;
+            Some((name, String::from_utf8_lossy(blob.content()).into_owned()))
+        })
+        .collect();
+    license::detect(files.iter().map(|(name, content)| (name.as_str(), content.as_str())))
+}
+
+fn git_error(e: git2::Error) -> String {
+    format!("Git: {}", e.message())
 }
 
 /// Extrait le nom d’un dépôt depuis son URL GitHub
 fn extract_repo_name(repo_url: &str) -> Result<String, String> {
-    let parts: Vec<&str> = repo_url.rsplit('/').collect();
-    if let Some(name) = parts.get(0) {
-        Ok(name.replace(".git", ""))
-    } else {
-        Err("URL GitHub invalide".to_string())
+    let parts: Vec<&str> = repo_url.trim_end_matches('/').rsplit('/').collect();
+    match parts.first() {
+        Some(name) if !name.is_empty() => Ok(name.replace(".git", "")),
+        _ => Err("URL GitHub invalide".to_string()),
     }
 }
 
-/// Nettoie le répertoire d’apprentissage local
-pub fn clear_feed() -> Result<(), String> {
-    if Path::new(FEED_PATH).exists() {
-        fs::remove_dir_all(FEED_PATH).map_err(|e| e.to_string())?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+
+    /// Dépôt source local avec un fichier dans `src/`, un README et une licence MIT à la racine
+    fn source_repo(dir: &Path) -> String {
+        Repository::init(dir).unwrap();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "pub fn answer() -> u32 { 42 }\n").unwrap();
+        fs::write(dir.join("README.md"), "# source\n").unwrap();
+        fs::write(dir.join("LICENSE"), "MIT License\n\nPermission is hereby granted, free of charge, to any person\n").unwrap();
+        commit_all(dir, "initial");
+        format!("file://{}", dir.display())
+    }
+
+    /// Valide l'état courant du dossier de travail (ajouts et suppressions)
+    fn commit_all(dir: &Path, message: &str) {
+        let repo = Repository::open(dir).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"], git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.update_all(["*"], None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("aurorae", "aurorae@localhost").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap();
+    }
+
+    #[tokio::test]
+    async fn pool_mirrors_then_extracts_only_sparse_paths() {
+        let root = std::env::temp_dir().join(format!("aurorae-crawler-{}", uuid::Uuid::new_v4()));
+        let url = source_repo(&root.join("upstream"));
+        let config = CrawlerConfig {
+            feed_path: root.join("feed"),
+            mirror_path: root.join("mirrors"),
+            // Le transport local de libgit2 ne gère pas les clones superficiels
+            depth: 0,
+            ..CrawlerConfig::default()
+        };
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let progress: ProgressCallback = Arc::new(move |event| recorded.lock().push(event));
+        let pool = ClonePool::new(config);
+
+        let outcome = pool.clone_all(&[url.as_str()], progress.clone()).await.remove(0).unwrap();
+        assert_eq!(outcome.files, 1);
+        assert!(!outcome.from_mirror);
+        assert!(root.join("feed/upstream/src/lib.rs").exists());
+        assert!(!root.join("feed/upstream/README.md").exists());
+        assert_eq!(outcome.license.as_deref(), Some("MIT"));
+        let provenance = SourceProvenance::find_for(&root.join("feed/upstream/src/lib.rs")).unwrap();
+        assert_eq!((provenance.commit.as_str(), provenance.license.as_deref()), (outcome.commit.as_str(), Some("MIT")));
+        assert!(matches!(events.lock().last(), Some(CloneProgress::Done { files: 1, .. })));
+
+        let again = pool.clone_all(&[url.as_str()], progress.clone()).await.remove(0).unwrap();
+        assert!(again.from_mirror);
+        assert_eq!(again.commit, outcome.commit);
+        assert!(again.changes.is_empty());
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn later_fetches_only_report_and_rewrite_the_diff() {
+        let root = std::env::temp_dir().join(format!("aurorae-crawler-{}", uuid::Uuid::new_v4()));
+        let upstream = root.join("upstream");
+        let url = source_repo(&upstream);
+        let config = CrawlerConfig { feed_path: root.join("feed"), mirror_path: root.join("mirrors"), depth: 0, ..CrawlerConfig::default() };
+        let progress: ProgressCallback = Arc::new(|_| {});
+        let pool = ClonePool::new(config);
+        let first = pool.clone_all(&[url.as_str()], progress.clone()).await.remove(0).unwrap();
+        assert_eq!(first.changes.changed, vec![root.join("feed/upstream/src/lib.rs")]);
+
+        fs::remove_file(upstream.join("src/lib.rs")).unwrap();
+        fs::write(upstream.join("src/extra.rs"), "pub struct Extra;\n").unwrap();
+        fs::write(upstream.join("README.md"), "# modifié\n").unwrap();
+        commit_all(&upstream, "update");
+
+        let update = pool.clone_all(&[url.as_str()], progress).await.remove(0).unwrap();
+        assert_ne!(update.commit, first.commit);
+        assert_eq!(update.changes.changed, vec![root.join("feed/upstream/src/extra.rs")]);
+        assert_eq!(update.changes.removed, vec![root.join("feed/upstream/src/lib.rs")]);
+        assert!(!root.join("feed/upstream/src/lib.rs").exists());
+        assert_eq!(SourceProvenance::load_in(&root.join("feed/upstream")).unwrap().commit, update.commit);
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn oversized_repositories_are_rejected() {
+        let root = std::env::temp_dir().join(format!("aurorae-crawler-{}", uuid::Uuid::new_v4()));
+        let url = source_repo(&root.join("upstream"));
+        let config = CrawlerConfig { feed_path: root.join("feed"), mirror_path: root.join("mirrors"), depth: 0, max_repo_size: 8, ..CrawlerConfig::default() };
+        let progress: ProgressCallback = Arc::new(|_| {});
+        assert!(clone_into_feed(&url, &config, &progress).unwrap_err().contains("limite"));
+        fs::remove_dir_all(&root).ok();
     }
-    fs::create_dir_all(FEED_PATH).map_err(|e| e.to_string())?;
-    println!("[AURORAE++] Dossier github_feed réinitialisé.");
-    Ok(())
 }
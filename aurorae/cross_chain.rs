@@ -0,0 +1,1531 @@
+//! AURORAE++ - cross_chain.rs
+//!
+//! Communications inter-chaînes. Les chaînes reliables sont celles du registre
+//! (`chains.toml`): un pont n'est déclaré qu'entre deux entrées connues.
+//!
+//! Les ponts suivent le schéma lock-and-mint du contrat `contracts/AuroraeBridge.sol`, déployé
+//! une seule fois par chaîne et partagé par les deux sens: la chaîne source verrouille la valeur
+//! (ou brûle un solde enveloppé pour la déverrouiller au retour) et émet `MessageSent`, un relayeur surveille ces
+//! événements, fait vérifier chaque message par des points d'extension de preuve (profondeur
+//! de confirmation, intégrité du message, en-têtes observés à la manière d'un client léger),
+//! puis l'exécute sur la chaîne destination, avec nouvelles tentatives espacées en cas d'échec.
+//! Les événements `MessageExecuted` de la destination confirment les livraisons.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use ethers::abi::{self, ParamType, Token};
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, Bytes, Filter, TransactionRequest, H256, U256};
+use ethers::utils::keccak256;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+use tracing::{error, info, warn};
+
+use crate::blockchain_core::{BlockchainInterface, GasOracle, HttpProvider, SharedTxManager, TxManagers};
+use crate::chain_registry::{ChainConfig, ChainKind, SharedChainRegistry};
+use crate::deployer::{ContractBuilder, Deployer};
+use crate::economy::SharedBudget;
+use crate::event_bus::{AuroraeEvent, EventBus};
+use crate::keystore::SharedSigner;
+use crate::resource_usage::spawn_tracked;
+use crate::semantic_bridge::{AssetInfo, OperationDescription, SchemaRegistry, Translation};
+use crate::substrate::{connect_substrate, SharedSubstrate, SubstrateBlock};
+
+pub const DEFAULT_BRIDGES_PATH: &str = "bridges.toml";
+/// Contrat de `contracts/` déployé aux deux extrémités d'un pont
+pub const BRIDGE_CONTRACT: &str = "AuroraeBridge";
+const BRIDGE_STATE_DIR: &str = "aurorae_state/bridges";
+
+/// Adresses des contrats déployés par chaîne, relues avant tout nouveau déploiement
+const BRIDGE_CONTRACTS_FILE: &str = "contracts.json";
+/// Schéma de `protocol_schemas.toml` décrivant les opérations d'`AuroraeBridge`
+const BRIDGE_PROTOCOL: &str = "aurorae-bridge";
+
+const MESSAGE_SENT_EVENT: &str = "MessageSent(bytes32,uint256,uint64,address,address,uint256,bool)";
+const MESSAGE_EXECUTED_EVENT: &str = "MessageExecuted(bytes32,uint256,address,uint256)";
+
+/// Fichier `bridges.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct BridgesConfig {
+    pub relayer: RelayerConfig,
+    pub bridges: Vec<BridgeRoute>,
+}
+
+/// Section `[relayer]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct RelayerConfig {
+    /// Profondeur exigée d'un message source avant son relais
+    pub confirmations: u64,
+    /// Tentatives d'exécution avant d'abandonner un message
+    pub max_attempts: u32,
+    /// Délai avant la première nouvelle tentative, doublé à chaque échec
+    pub retry_backoff_secs: u64,
+    pub poll_interval_secs: u64,
+    /// Blocs lus par requête de logs
+    pub max_block_range: u64,
+}
+
+impl Default for RelayerConfig {
+    fn default() -> Self {
+        Self { confirmations: 3, max_attempts: 5, retry_backoff_secs: 15, poll_interval_secs: 15, max_block_range: 2000 }
+    }
+}
+
+/// Entrée `[[bridges]]`: deux chaînes EVM du registre et, s'ils existent déjà, les contrats
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct BridgeRoute {
+    pub source: String,
+    pub destination: String,
+    pub source_contract: Option<Address>,
+    pub destination_contract: Option<Address>,
+}
+
+impl BridgesConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| format!("Configuration des ponts invalide: {}", e))?;
+        if config.relayer.max_attempts == 0 || config.relayer.max_block_range == 0 {
+            return Err("relayer.max-attempts et relayer.max-block-range doivent être positifs".to_string());
+        }
+        if let Some(route) = config.bridges.iter().find(|route| route.source == route.destination) {
+            return Err(format!("Pont {} → {}: deux chaînes distinctes requises", route.source, route.destination));
+        }
+        Ok(config)
+    }
+
+    /// `bridges.toml`, ou aucun pont
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_BRIDGES_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: aucun pont configuré", e);
+            Self::default()
+        })
+    }
+}
+
+/// Pont déclaré entre deux chaînes du registre
+#[derive(Debug, Clone)]
+pub struct BridgeDescriptor {
+    pub id: Uuid,
+    pub source: String,
+    pub destination: String,
+    pub source_kind: ChainKind,
+    pub destination_kind: ChainKind,
+    pub created_at: String,
+}
+
+// ====================== MESSAGES ET PREUVES ======================
+
+/// Message lock-and-mint, identifié comme dans `AuroraeBridge.messageId`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeMessage {
+    pub id: H256,
+    pub source_chain_id: u64,
+    pub destination_chain_id: u64,
+    pub nonce: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub amount: U256,
+    /// Retour d'un solde enveloppé brûlé: la destination déverrouille la valeur native
+    #[serde(default)]
+    pub unlock: bool,
+}
+
+impl BridgeMessage {
+    /// `keccak256(abi.encode(source, destination, nonce, sender, recipient, amount, unlock))`
+    pub fn compute_id(&self) -> H256 {
+        H256::from(keccak256(abi::encode(&[
+            Token::Uint(self.source_chain_id.into()),
+            Token::Uint(self.destination_chain_id.into()),
+            Token::Uint(self.nonce.into()),
+            Token::Address(self.sender),
+            Token::Address(self.recipient),
+            Token::Uint(self.amount),
+            Token::Bool(self.unlock),
+        ])))
+    }
+
+    /// Décode un log `MessageSent` émis sur la chaîne `source_chain_id`
+    pub fn from_sent_log(source_chain_id: u64, topics: &[H256], data: &[u8]) -> Result<Self, String> {
+        if topics.len() != 3 || topics[0] != event_topic(MESSAGE_SENT_EVENT) {
+            return Err("Log MessageSent attendu".to_string());
+        }
+        let fields = abi::decode(&[ParamType::Uint(64), ParamType::Address, ParamType::Address, ParamType::Uint(256), ParamType::Bool], data)
+            .map_err(|e| format!("Log MessageSent illisible: {}", e))?;
+        match fields.as_slice() {
+            [Token::Uint(nonce), Token::Address(sender), Token::Address(recipient), Token::Uint(amount), Token::Bool(unlock)] => Ok(Self {
+                id: topics[1],
+                source_chain_id,
+                destination_chain_id: U256::from_big_endian(topics[2].as_bytes()).low_u64(),
+                nonce: nonce.low_u64(),
+                sender: *sender,
+                recipient: *recipient,
+                amount: *amount,
+                unlock: *unlock,
+            }),
+            _ => Err("Log MessageSent illisible".to_string()),
+        }
+    }
+}
+
+fn event_topic(signature: &str) -> H256 {
+    H256::from(keccak256(signature.as_bytes()))
+}
+
+/// Données d'appel: sélecteur de la fonction suivi des arguments encodés
+fn calldata(signature: &str, args: &[Token]) -> Bytes {
+    let mut data = keccak256(signature.as_bytes())[..4].to_vec();
+    data.extend(abi::encode(args));
+    Bytes::from(data)
+}
+
+/// Éléments qui établissent qu'un message a bien été émis sur la chaîne source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageProof {
+    pub source_chain_id: u64,
+    pub block_number: u64,
+    pub block_hash: Option<H256>,
+    pub tx_hash: Option<H256>,
+    /// Log brut tel que lu sur la chaîne source
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+    /// Dernier bloc connu de la source lors de la dernière lecture
+    pub head: u64,
+}
+
+/// Verdict d'un point de vérification
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Valid,
+    /// Preuve pas encore suffisante (profondeur, en-tête non observé): nouvel essai plus tard
+    Wait(String),
+    /// Preuve invalide: le message n'est jamais relayé
+    Reject(String),
+}
+
+/// Point d'extension de vérification des preuves, consulté avant tout relais
+pub trait ProofVerifier: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn verify(&self, message: &BridgeMessage, proof: &MessageProof) -> Verdict;
+}
+
+/// Profondeur minimale du bloc qui contient le message
+pub struct ConfirmationDepth {
+    pub required: u64,
+}
+
+impl ProofVerifier for ConfirmationDepth {
+    fn name(&self) -> &'static str {
+        "confirmations"
+    }
+
+    fn verify(&self, _message: &BridgeMessage, proof: &MessageProof) -> Verdict {
+        let depth = (proof.head + 1).saturating_sub(proof.block_number);
+        if depth >= self.required {
+            Verdict::Valid
+        } else {
+            Verdict::Wait(format!("{} confirmation(s) sur {}", depth, self.required))
+        }
+    }
+}
+
+/// Le message correspond au log prouvé et son identifiant à son contenu
+pub struct MessageIntegrity;
+
+impl ProofVerifier for MessageIntegrity {
+    fn name(&self) -> &'static str {
+        "integrity"
+    }
+
+    fn verify(&self, message: &BridgeMessage, proof: &MessageProof) -> Verdict {
+        match BridgeMessage::from_sent_log(proof.source_chain_id, &proof.topics, &proof.data) {
+            Ok(logged) if logged != *message => Verdict::Reject("message différent du log prouvé".to_string()),
+            Ok(_) if message.compute_id() != message.id => Verdict::Reject("identifiant sans rapport avec le contenu".to_string()),
+            Ok(_) => Verdict::Valid,
+            Err(e) => Verdict::Reject(e),
+        }
+    }
+}
+
+/// En-têtes retenus par chaîne au-delà desquels les plus anciens sont oubliés
+const MAX_CHECKPOINTS: usize = 50_000;
+
+/// Vérification à la manière d'un client léger: le bloc de la preuve doit correspondre à
+/// l'en-tête observé indépendamment à cette hauteur (souscription aux blocs de la source)
+#[derive(Clone, Default)]
+pub struct HeaderCheckpoints {
+    headers: Arc<RwLock<BTreeMap<(u64, u64), H256>>>,
+}
+
+impl HeaderCheckpoints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, chain_id: u64, number: u64, hash: H256) {
+        let mut headers = self.headers.write();
+        headers.insert((chain_id, number), hash);
+        while headers.len() > MAX_CHECKPOINTS {
+            headers.pop_first();
+        }
+    }
+
+    /// Enregistre les blocs publiés sur le bus (`NewBlock`)
+    pub fn track(&self, bus: &EventBus) -> JoinHandle<()> {
+        let checkpoints = self.clone();
+        let mut events = bus.subscribe();
+        spawn_tracked("cross_chain", async move {
+            loop {
+                match events.recv().await {
+                    Ok(envelope) => {
+                        if let AuroraeEvent::NewBlock { chain_id, number, hash: Some(hash), .. } = envelope.event {
+                            checkpoints.record(chain_id, number, hash);
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(_) => break,
+                }
+            }
+        })
+    }
+}
+
+impl ProofVerifier for HeaderCheckpoints {
+    fn name(&self) -> &'static str {
+        "headers"
+    }
+
+    fn verify(&self, _message: &BridgeMessage, proof: &MessageProof) -> Verdict {
+        let Some(hash) = proof.block_hash else {
+            return Verdict::Reject("preuve sans hash de bloc".to_string());
+        };
+        match self.headers.read().get(&(proof.source_chain_id, proof.block_number)) {
+            None => Verdict::Wait(format!("en-tête {} non encore observé", proof.block_number)),
+            Some(known) if *known != hash => Verdict::Reject(format!("bloc {} hors de la chaîne observée", proof.block_number)),
+            Some(_) => Verdict::Valid,
+        }
+    }
+}
+
+// ====================== EXTRÉMITÉS DU PONT ======================
+
+/// Contrat `AuroraeBridge` déployé sur une chaîne
+#[async_trait]
+pub trait BridgeEndpoint: Send + Sync {
+    /// « evm » ou « simulator »
+    fn kind(&self) -> &str;
+    fn chain_id(&self) -> u64;
+    /// Adresse du contrat (aucune pour un pont simulé)
+    fn contract(&self) -> Option<Address> {
+        None
+    }
+    async fn head(&self) -> Result<u64, String>;
+    /// Verrouille `amount` pour `recipient` sur la chaîne `destination_chain_id`
+    async fn lock(&self, destination_chain_id: u64, recipient: Address, amount: U256) -> Result<H256, String>;
+    /// Brûle `amount` du solde enveloppé venu de `destination_chain_id`, qui le déverrouillera pour `recipient`
+    async fn burn(&self, destination_chain_id: u64, recipient: Address, amount: U256) -> Result<H256, String>;
+    /// Messages émis dans les blocs [from, to], avec leur preuve
+    async fn sent_messages(&self, from: u64, to: u64) -> Result<Vec<(BridgeMessage, MessageProof)>, String>;
+    /// Identifiants des messages exécutés dans les blocs [from, to]
+    async fn executed_messages(&self, from: u64, to: u64) -> Result<Vec<H256>, String>;
+    async fn is_executed(&self, id: H256) -> Result<bool, String>;
+    async fn execute(&self, message: &BridgeMessage) -> Result<H256, String>;
+}
+
+/// Extrémité réelle: transactions par le gestionnaire du compte sur la chaîne, lectures par son RPC
+pub struct EvmBridgeEndpoint {
+    tx: SharedTxManager,
+    provider: HttpProvider,
+    contract: Address,
+}
+
+impl EvmBridgeEndpoint {
+    pub fn new(tx: SharedTxManager, provider: HttpProvider, contract: Address) -> Self {
+        Self { tx, provider, contract }
+    }
+
+    async fn logs(&self, signature: &str, from: u64, to: u64) -> Result<Vec<ethers::types::Log>, String> {
+        let filter = Filter::new().address(self.contract).topic0(event_topic(signature)).from_block(from).to_block(to);
+        self.provider.get_logs(&filter).await.map_err(|e| format!("Lecture des logs du pont impossible: {}", e))
+    }
+}
+
+#[async_trait]
+impl BridgeEndpoint for EvmBridgeEndpoint {
+    fn kind(&self) -> &str {
+        "evm"
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.tx.chain_id()
+    }
+
+    fn contract(&self) -> Option<Address> {
+        Some(self.contract)
+    }
+
+    async fn head(&self) -> Result<u64, String> {
+        self.tx.backend().block_number().await
+    }
+
+    async fn lock(&self, destination_chain_id: u64, recipient: Address, amount: U256) -> Result<H256, String> {
+        let data = calldata("lock(uint256,address)", &[Token::Uint(destination_chain_id.into()), Token::Address(recipient)]);
+        self.tx.submit(self.contract, amount, Some(data)).await
+    }
+
+    async fn burn(&self, destination_chain_id: u64, recipient: Address, amount: U256) -> Result<H256, String> {
+        let data = calldata(
+            "burn(uint256,address,uint256)",
+            &[Token::Uint(destination_chain_id.into()), Token::Address(recipient), Token::Uint(amount)],
+        );
+        self.tx.submit(self.contract, U256::zero(), Some(data)).await
+    }
+
+    async fn sent_messages(&self, from: u64, to: u64) -> Result<Vec<(BridgeMessage, MessageProof)>, String> {
+        let head = self.head().await?;
+        let chain_id = self.chain_id();
+        let logs = self.logs(MESSAGE_SENT_EVENT, from, to).await?;
+        Ok(logs
+            .into_iter()
+            .filter(|log| log.removed != Some(true))
+            .filter_map(|log| {
+                let message = BridgeMessage::from_sent_log(chain_id, &log.topics, &log.data).ok()?;
+                let proof = MessageProof {
+                    source_chain_id: chain_id,
+                    block_number: log.block_number?.as_u64(),
+                    block_hash: log.block_hash,
+                    tx_hash: log.transaction_hash,
+                    topics: log.topics,
+                    data: log.data.to_vec(),
+                    head,
+                };
+                Some((message, proof))
+            })
+            .collect())
+    }
+
+    async fn executed_messages(&self, from: u64, to: u64) -> Result<Vec<H256>, String> {
+        let logs = self.logs(MESSAGE_EXECUTED_EVENT, from, to).await?;
+        Ok(logs.into_iter().filter_map(|log| log.topics.get(1).copied()).collect())
+    }
+
+    async fn is_executed(&self, id: H256) -> Result<bool, String> {
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(self.contract)
+            .data(calldata("processed(bytes32)", &[Token::FixedBytes(id.as_bytes().to_vec())]))
+            .into();
+        let output = self.provider.call(&tx, None).await.map_err(|e| format!("Lecture de processed() impossible: {}", e))?;
+        Ok(output.iter().any(|byte| *byte != 0))
+    }
+
+    async fn execute(&self, message: &BridgeMessage) -> Result<H256, String> {
+        let data = calldata(
+            "execute(bytes32,uint256,uint64,address,address,uint256,bool)",
+            &[
+                Token::FixedBytes(message.id.as_bytes().to_vec()),
+                Token::Uint(message.source_chain_id.into()),
+                Token::Uint(message.nonce.into()),
+                Token::Address(message.sender),
+                Token::Address(message.recipient),
+                Token::Uint(message.amount),
+                Token::Bool(message.unlock),
+            ],
+        );
+        let tx_hash = self.tx.submit(self.contract, U256::zero(), Some(data)).await?;
+        let receipt = self.tx.backend().wait_for_receipt(tx_hash, 1).await?;
+        if !receipt.success {
+            return Err(format!("execute() annulé on-chain ({:?})", tx_hash));
+        }
+        Ok(tx_hash)
+    }
+}
+
+#[derive(Default)]
+struct SimulatedBridgeState {
+    head: u64,
+    nonce: u64,
+    /// (bloc, message, log brut)
+    sent: Vec<(u64, BridgeMessage, Vec<H256>, Vec<u8>)>,
+    executed: BTreeMap<H256, u64>,
+    locked: U256,
+    /// Soldes enveloppés par (chaîne d'origine, détenteur)
+    wrapped: BTreeMap<(u64, Address), U256>,
+    /// Valeur native déverrouillée par destinataire
+    unlocked: BTreeMap<Address, U256>,
+}
+
+/// Contrat de pont simulé en mémoire (backend simulé, tests): mêmes règles que le contrat
+pub struct SimulatedBridgeEndpoint {
+    chain_id: u64,
+    sender: Address,
+    state: RwLock<SimulatedBridgeState>,
+}
+
+impl SimulatedBridgeEndpoint {
+    pub fn new(chain_id: u64, sender: Address) -> Self {
+        Self { chain_id, sender, state: RwLock::new(SimulatedBridgeState::default()) }
+    }
+
+    /// Mine `blocks` blocs vides
+    pub fn advance(&self, blocks: u64) {
+        self.state.write().head += blocks;
+    }
+
+    /// Solde enveloppé de `holder` pour la valeur verrouillée sur `origin_chain_id`
+    pub fn wrapped_balance(&self, origin_chain_id: u64, holder: Address) -> U256 {
+        self.state.read().wrapped.get(&(origin_chain_id, holder)).copied().unwrap_or_default()
+    }
+
+    pub fn locked(&self) -> U256 {
+        self.state.read().locked
+    }
+
+    pub fn unlocked_balance(&self, recipient: Address) -> U256 {
+        self.state.read().unlocked.get(&recipient).copied().unwrap_or_default()
+    }
+
+    /// Émet `MessageSent` comme `AuroraeBridge._send`
+    fn send(&self, state: &mut SimulatedBridgeState, destination_chain_id: u64, recipient: Address, amount: U256, unlock: bool) -> H256 {
+        state.nonce += 1;
+        state.head += 1;
+        let mut message = BridgeMessage {
+            id: H256::zero(),
+            source_chain_id: self.chain_id,
+            destination_chain_id,
+            nonce: state.nonce,
+            sender: self.sender,
+            recipient,
+            amount,
+            unlock,
+        };
+        message.id = message.compute_id();
+        let topics = vec![event_topic(MESSAGE_SENT_EVENT), message.id, H256::from_low_u64_be(destination_chain_id)];
+        let data = abi::encode(&[
+            Token::Uint(message.nonce.into()),
+            Token::Address(self.sender),
+            Token::Address(recipient),
+            Token::Uint(amount),
+            Token::Bool(unlock),
+        ]);
+        let block = state.head;
+        state.sent.push((block, message.clone(), topics, data));
+        message.id
+    }
+
+    fn block_hash(&self, number: u64) -> H256 {
+        let mut preimage = self.chain_id.to_be_bytes().to_vec();
+        preimage.extend_from_slice(&number.to_be_bytes());
+        H256::from(keccak256(preimage))
+    }
+}
+
+#[async_trait]
+impl BridgeEndpoint for SimulatedBridgeEndpoint {
+    fn kind(&self) -> &str {
+        "simulator"
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    async fn head(&self) -> Result<u64, String> {
+        Ok(self.state.read().head)
+    }
+
+    async fn lock(&self, destination_chain_id: u64, recipient: Address, amount: U256) -> Result<H256, String> {
+        if amount.is_zero() || destination_chain_id == self.chain_id {
+            return Err("Verrouillage refusé: montant nul ou chaîne identique".to_string());
+        }
+        let mut state = self.state.write();
+        state.locked += amount;
+        Ok(self.send(&mut state, destination_chain_id, recipient, amount, false))
+    }
+
+    async fn burn(&self, destination_chain_id: u64, recipient: Address, amount: U256) -> Result<H256, String> {
+        let mut state = self.state.write();
+        let balance = state.wrapped.get(&(destination_chain_id, self.sender)).copied().unwrap_or_default();
+        if amount.is_zero() || balance < amount {
+            return Err("Insufficient wrapped balance".to_string());
+        }
+        state.wrapped.insert((destination_chain_id, self.sender), balance - amount);
+        Ok(self.send(&mut state, destination_chain_id, recipient, amount, true))
+    }
+
+    async fn sent_messages(&self, from: u64, to: u64) -> Result<Vec<(BridgeMessage, MessageProof)>, String> {
+        let state = self.state.read();
+        Ok(state
+            .sent
+            .iter()
+            .filter(|(block, ..)| (from..=to).contains(block))
+            .map(|(block, message, topics, data)| {
+                let proof = MessageProof {
+                    source_chain_id: self.chain_id,
+                    block_number: *block,
+                    block_hash: Some(self.block_hash(*block)),
+                    tx_hash: Some(message.id),
+                    topics: topics.clone(),
+                    data: data.clone(),
+                    head: state.head,
+                };
+                (message.clone(), proof)
+            })
+            .collect())
+    }
+
+    async fn executed_messages(&self, from: u64, to: u64) -> Result<Vec<H256>, String> {
+        Ok(self.state.read().executed.iter().filter(|(_, block)| (from..=to).contains(*block)).map(|(id, _)| *id).collect())
+    }
+
+    async fn is_executed(&self, id: H256) -> Result<bool, String> {
+        Ok(self.state.read().executed.contains_key(&id))
+    }
+
+    async fn execute(&self, message: &BridgeMessage) -> Result<H256, String> {
+        let mut state = self.state.write();
+        if state.executed.contains_key(&message.id) {
+            return Err("Already processed".to_string());
+        }
+        if message.destination_chain_id != self.chain_id || message.compute_id() != message.id {
+            return Err("Id mismatch".to_string());
+        }
+        if message.unlock {
+            if state.locked < message.amount {
+                return Err("Insufficient locked value".to_string());
+            }
+            state.locked -= message.amount;
+            *state.unlocked.entry(message.recipient).or_default() += message.amount;
+        } else {
+            *state.wrapped.entry((message.source_chain_id, message.recipient)).or_default() += message.amount;
+        }
+        state.head += 1;
+        let block = state.head;
+        state.executed.insert(message.id, block);
+        Ok(message.id)
+    }
+}
+
+// ====================== RELAYEUR ======================
+
+/// Message en attente de relais
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingRelay {
+    pub message: BridgeMessage,
+    pub proof: MessageProof,
+    pub attempts: u32,
+    /// Horodatage (secondes) avant lequel le message n'est pas retenté
+    pub next_attempt_at: i64,
+    /// Dernière transaction d'exécution envoyée
+    pub submitted: Option<H256>,
+    pub last_error: Option<String>,
+}
+
+/// Curseurs et messages en attente, persistés pour reprendre après un redémarrage
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct RelayerState {
+    /// Contrats lus par les curseurs: un autre contrat repart de zéro
+    #[serde(default)]
+    source_contract: Option<Address>,
+    #[serde(default)]
+    destination_contract: Option<Address>,
+    source_cursor: Option<u64>,
+    destination_cursor: Option<u64>,
+    pending: BTreeMap<H256, PendingRelay>,
+}
+
+/// Bilan d'un passage du relayeur
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelayReport {
+    pub discovered: usize,
+    pub submitted: usize,
+    pub delivered: usize,
+    pub retried: usize,
+    pub rejected: usize,
+    pub failed: usize,
+    pub errors: Vec<String>,
+}
+
+impl RelayReport {
+    pub fn is_quiet(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+pub struct BridgeRelayer {
+    pub bridge: BridgeDescriptor,
+    pub config: RelayerConfig,
+    source: Arc<dyn BridgeEndpoint>,
+    destination: Arc<dyn BridgeEndpoint>,
+    verifiers: Vec<Arc<dyn ProofVerifier>>,
+    state: RelayerState,
+    path: Option<PathBuf>,
+    pub delivered: u64,
+    pub failed: Vec<BridgeMessage>,
+}
+
+impl BridgeRelayer {
+    /// Relayeur en mémoire; la profondeur de confirmation et l'intégrité sont toujours vérifiées
+    pub fn new(bridge: BridgeDescriptor, source: Arc<dyn BridgeEndpoint>, destination: Arc<dyn BridgeEndpoint>, config: RelayerConfig) -> Self {
+        let verifiers: Vec<Arc<dyn ProofVerifier>> = vec![Arc::new(ConfirmationDepth { required: config.confirmations }), Arc::new(MessageIntegrity)];
+        Self { bridge, config, source, destination, verifiers, state: RelayerState::default(), path: None, delivered: 0, failed: Vec::new() }
+    }
+
+    /// Reprend curseurs et messages en attente persistés à `path`, s'ils portent sur les mêmes contrats
+    pub fn open(path: &Path, bridge: BridgeDescriptor, source: Arc<dyn BridgeEndpoint>, destination: Arc<dyn BridgeEndpoint>, config: RelayerConfig) -> Self {
+        let mut relayer = Self::new(bridge, source, destination, config);
+        let saved: RelayerState = fs::read_to_string(path).ok().and_then(|content| serde_json::from_str(&content).ok()).unwrap_or_default();
+        let contracts = (relayer.source.contract(), relayer.destination.contract());
+        if (saved.source_contract, saved.destination_contract) == contracts {
+            relayer.state = saved;
+        } else {
+            if saved.source_cursor.is_some() || !saved.pending.is_empty() {
+                warn!(
+                    "⚠️ Pont {} → {}: contrats changés, curseurs et messages en attente réinitialisés",
+                    relayer.bridge.source, relayer.bridge.destination
+                );
+            }
+            relayer.state = RelayerState { source_contract: contracts.0, destination_contract: contracts.1, ..RelayerState::default() };
+        }
+        relayer.path = Some(path.to_path_buf());
+        relayer
+    }
+
+    pub fn with_verifier(mut self, verifier: Arc<dyn ProofVerifier>) -> Self {
+        self.verifiers.push(verifier);
+        self
+    }
+
+    pub fn pending(&self) -> Vec<&PendingRelay> {
+        self.state.pending.values().collect()
+    }
+
+    /// Blocs [from, to] à lire après `cursor`, au plus `max_block_range`; au premier passage,
+    /// la lecture remonte d'une fenêtre avant le bloc courant
+    fn window(&self, cursor: Option<u64>, head: u64) -> Option<(u64, u64)> {
+        let from = match cursor {
+            Some(cursor) => cursor + 1,
+            None => head.saturating_sub(self.config.max_block_range),
+        };
+        (from <= head).then(|| (from, head.min(from + self.config.max_block_range - 1)))
+    }
+
+    /// Un passage: surveillance des deux chaînes, vérification puis relais des messages dus
+    pub async fn poll(&mut self) -> RelayReport {
+        let mut report = RelayReport::default();
+        let before = self.state.clone();
+        let destination_chain = self.destination.chain_id();
+
+        // Nouveaux messages de la source
+        match self.source.head().await {
+            Ok(head) => {
+                for relay in self.state.pending.values_mut() {
+                    relay.proof.head = relay.proof.head.max(head);
+                }
+                if let Some((from, to)) = self.window(self.state.source_cursor, head) {
+                    match self.source.sent_messages(from, to).await {
+                        Ok(messages) => {
+                            for (message, proof) in messages.into_iter().filter(|(m, _)| m.destination_chain_id == destination_chain) {
+                                if !self.state.pending.contains_key(&message.id) {
+                                    report.discovered += 1;
+                                    self.state.pending.insert(message.id, PendingRelay {
+                                        message,
+                                        proof,
+                                        attempts: 0,
+                                        next_attempt_at: 0,
+                                        submitted: None,
+                                        last_error: None,
+                                    });
+                                }
+                            }
+                            self.state.source_cursor = Some(to);
+                        }
+                        Err(e) => report.errors.push(e),
+                    }
+                }
+            }
+            Err(e) => report.errors.push(format!("{}: {}", self.bridge.source, e)),
+        }
+
+        // Livraisons confirmées par les événements de la destination
+        match self.destination.head().await {
+            Ok(head) => {
+                if let Some((from, to)) = self.window(self.state.destination_cursor, head) {
+                    match self.destination.executed_messages(from, to).await {
+                        Ok(ids) => {
+                            for id in ids {
+                                if self.state.pending.remove(&id).is_some() {
+                                    report.delivered += 1;
+                                }
+                            }
+                            self.state.destination_cursor = Some(to);
+                        }
+                        Err(e) => report.errors.push(e),
+                    }
+                }
+            }
+            Err(e) => report.errors.push(format!("{}: {}", self.bridge.destination, e)),
+        }
+
+        // Relais des messages dus
+        let now = Utc::now().timestamp();
+        let due: Vec<H256> = self.state.pending.iter().filter(|(_, relay)| relay.next_attempt_at <= now).map(|(id, _)| *id).collect();
+        for id in due {
+            let Some(relay) = self.state.pending.get(&id).cloned() else { continue };
+            let verdict = self
+                .verifiers
+                .iter()
+                .map(|verifier| (verifier.name(), verifier.verify(&relay.message, &relay.proof)))
+                .find(|(_, verdict)| *verdict != Verdict::Valid);
+            match verdict {
+                Some((_, Verdict::Wait(_))) => continue,
+                Some((name, Verdict::Reject(reason))) => {
+                    error!("⛔ Message {:?} rejeté par la vérification {}: {}", id, name, reason);
+                    self.state.pending.remove(&id);
+                    report.rejected += 1;
+                    continue;
+                }
+                _ => {}
+            }
+            let outcome = match self.destination.is_executed(id).await {
+                Ok(true) => None,
+                Ok(false) => Some(self.destination.execute(&relay.message).await),
+                Err(e) => Some(Err(e)),
+            };
+            match outcome {
+                None => {
+                    self.state.pending.remove(&id);
+                    report.delivered += 1;
+                }
+                Some(Ok(tx_hash)) => {
+                    // Confirmé par `MessageExecuted` ou `processed()` au passage suivant; sinon renvoyé
+                    if let Some(pending) = self.state.pending.get_mut(&id) {
+                        pending.attempts += 1;
+                        pending.submitted = Some(tx_hash);
+                        pending.last_error = None;
+                        pending.next_attempt_at = now + backoff(&self.config, pending.attempts);
+                    }
+                    report.submitted += 1;
+                }
+                Some(Err(e)) => {
+                    let Some(pending) = self.state.pending.get_mut(&id) else { continue };
+                    pending.attempts += 1;
+                    pending.last_error = Some(e.clone());
+                    if pending.attempts >= self.config.max_attempts {
+                        error!("❌ Message {:?} abandonné après {} tentative(s): {}", id, pending.attempts, e);
+                        if let Some(abandoned) = self.state.pending.remove(&id) {
+                            self.failed.push(abandoned.message);
+                        }
+                        report.failed += 1;
+                    } else {
+                        pending.next_attempt_at = now + backoff(&self.config, pending.attempts);
+                        report.retried += 1;
+                    }
+                }
+            }
+        }
+
+        self.delivered += report.delivered as u64;
+        if self.state != before {
+            self.save();
+        }
+        report
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(&self.state) {
+            Ok(content) => {
+                if let Err(e) = fs::write(path, content) {
+                    warn!("⚠️ État du relayeur non sauvegardé: {}", e);
+                }
+            }
+            Err(e) => warn!("⚠️ État du relayeur non sérialisable: {}", e),
+        }
+    }
+}
+
+/// Délai avant la tentative suivante: `retry_backoff_secs` doublé à chaque tentative
+fn backoff(config: &RelayerConfig, attempts: u32) -> i64 {
+    (config.retry_backoff_secs as i64).saturating_mul(1 << attempts.saturating_sub(1).min(10))
+}
+
+/// Tâche du relayeur: un passage toutes les `poll_interval_secs`
+pub fn spawn_relayer(mut relayer: BridgeRelayer) -> JoinHandle<()> {
+    spawn_tracked("cross_chain", async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(relayer.config.poll_interval_secs.max(1)));
+        loop {
+            ticker.tick().await;
+            let report = relayer.poll().await;
+            if report.is_quiet() {
+                continue;
+            }
+            info!(
+                "🌉 {} → {}: {} découvert(s), {} envoyé(s), {} livré(s), {} retenté(s), {} rejeté(s), {} abandonné(s), {} en attente",
+                relayer.bridge.source,
+                relayer.bridge.destination,
+                report.discovered,
+                report.submitted,
+                report.delivered,
+                report.retried,
+                report.rejected,
+                report.failed,
+                relayer.state.pending.len()
+            );
+            for error in &report.errors {
+                warn!("⚠️ Relayeur {} → {}: {}", relayer.bridge.source, relayer.bridge.destination, error);
+            }
+        }
+    })
+}
+
+// ====================== PLANS DE TRANSFERT ======================
+
+/// Protocole par lequel une chaîne reçoit la valeur pontée
+fn receiving_protocol(kind: ChainKind) -> &'static str {
+    match kind {
+        ChainKind::Evm => BRIDGE_PROTOCOL,
+        ChainKind::Substrate => "substrate-balances",
+    }
+}
+
+/// Transfert inter-chaînes préparé: `lock` sur la source, traduit dans l'opération qui le
+/// livrera sur la destination
+#[derive(Debug, Clone)]
+pub struct TransferPlan {
+    pub source: String,
+    pub destination: String,
+    /// Absent pour une chaîne Substrate
+    pub destination_chain_id: Option<u64>,
+    /// Montant verrouillé, en plus petite unité de la devise native source
+    pub amount: U256,
+    pub delivery: Translation,
+    pub summary: String,
+}
+
+impl TransferPlan {
+    /// Destinataire et montant du `lock`, si la destination peut les recevoir tels quels
+    fn lock_arguments(&self) -> Result<(Address, U256), String> {
+        if !self.delivery.is_complete() {
+            return Err(format!(
+                "Transfert {} → {} incomplet: {} à fournir ({})",
+                self.source, self.destination, self.delivery.unresolved.join(", "), self.delivery.notes.join("; ")
+            ));
+        }
+        if self.delivery.description.protocol != BRIDGE_PROTOCOL {
+            return Err(format!("{}: aucun contrat {} pour recevoir le transfert", self.destination, BRIDGE_CONTRACT));
+        }
+        let field = |name: &str| {
+            self.delivery.description.fields.get(name).ok_or_else(|| format!("Champ {} absent de la traduction", name))
+        };
+        let recipient = field("recipient")?.parse::<Address>().map_err(|e| format!("Destinataire invalide: {}", e))?;
+        let amount = U256::from_dec_str(field("amount")?).map_err(|e| format!("Montant invalide: {}", e))?;
+        // AuroraeBridge crédite la destination dans les unités de la source: pas de remise à l'échelle
+        if amount != self.amount {
+            return Err(format!("Montant remis à l'échelle ({} → {}): non supporté par {}", self.amount, amount, BRIDGE_CONTRACT));
+        }
+        Ok((recipient, amount))
+    }
+}
+
+// ====================== ROUTEUR ======================
+
+/// Chaîne Substrate connectée et dernier état observé
+pub struct SubstrateLink {
+    pub backend: SharedSubstrate,
+    pub head: Option<SubstrateBlock>,
+    pub last_error: Option<String>,
+}
+
+impl SubstrateLink {
+    /// Joignable: tête finalisée lue lors du dernier rafraîchissement
+    pub fn is_live(&self) -> bool {
+        self.head.is_some() && self.last_error.is_none()
+    }
+}
+
+pub struct CrossChainRouter {
+    registry: SharedChainRegistry,
+    pub bridges: Vec<BridgeDescriptor>,
+    substrate: BTreeMap<String, SubstrateLink>,
+    /// Contrat de pont de chaque chaîne, partagé par toutes les routes qui la touchent
+    endpoints: BTreeMap<String, Arc<dyn BridgeEndpoint>>,
+    /// Curseurs des relayeurs et adresses des contrats déployés
+    state_dir: PathBuf,
+    /// Gestionnaires de nonces partagés avec les autres modules émetteurs
+    tx_managers: TxManagers,
+    /// Schémas de protocoles servant à traduire les transferts d'une chaîne à l'autre
+    schemas: SchemaRegistry,
+}
+
+impl CrossChainRouter {
+    pub fn new(registry: SharedChainRegistry) -> Self {
+        Self {
+            registry,
+            bridges: Vec::new(),
+            substrate: BTreeMap::new(),
+            endpoints: BTreeMap::new(),
+            state_dir: PathBuf::from(BRIDGE_STATE_DIR),
+            tx_managers: TxManagers::default(),
+            schemas: SchemaRegistry::default(),
+        }
+    }
+
+    /// Les contrats de pont envoient leurs transactions par ces gestionnaires
+    pub fn with_tx_managers(mut self, managers: TxManagers) -> Self {
+        self.tx_managers = managers;
+        self
+    }
+
+    pub fn with_schemas(mut self, schemas: SchemaRegistry) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    pub fn with_state_dir(mut self, dir: &Path) -> Self {
+        self.state_dir = dir.to_path_buf();
+        self
+    }
+
+    /// Adresses des contrats déjà déployés, par nom de chaîne
+    pub fn deployed_contracts(&self) -> BTreeMap<String, Address> {
+        fs::read_to_string(self.state_dir.join(BRIDGE_CONTRACTS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn record_deployed_contract(&self, chain: &str, address: Address) -> Result<(), String> {
+        let mut contracts = self.deployed_contracts();
+        contracts.insert(chain.to_string(), address);
+        fs::create_dir_all(&self.state_dir).map_err(|e| format!("Erreur création {}: {}", self.state_dir.display(), e))?;
+        let content = serde_json::to_string_pretty(&contracts).map_err(|e| e.to_string())?;
+        let path = self.state_dir.join(BRIDGE_CONTRACTS_FILE);
+        fs::write(&path, content).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))
+    }
+
+    /// Une chaîne EVM du registre est toujours routable; une chaîne Substrate seulement si
+    /// elle est connectée et a répondu au dernier rafraîchissement
+    fn is_routable(&self, chain: &ChainConfig) -> bool {
+        match chain.kind {
+            ChainKind::Evm => true,
+            ChainKind::Substrate => self.substrate.get(&chain.name).map(SubstrateLink::is_live).unwrap_or(false),
+        }
+    }
+
+    /// Chaînes atteignables depuis `source`: les autres chaînes routables du registre
+    pub fn destinations(&self, source: &str) -> Vec<String> {
+        let registry = self.registry.read();
+        if !registry.get(source).map(|chain| self.is_routable(chain)).unwrap_or(false) {
+            return Vec::new();
+        }
+        registry
+            .names()
+            .into_iter()
+            .filter(|name| name != source)
+            .filter(|name| registry.get(name).map(|chain| self.is_routable(chain)).unwrap_or(false))
+            .collect()
+    }
+
+    /// Connecte une chaîne Substrate, l'ajoute au registre si besoin et l'inclut dans le routage
+    pub async fn add_substrate_chain(&mut self, chain: ChainConfig) -> Result<SubstrateBlock, String> {
+        let backend = connect_substrate(&chain).await?;
+        let head = backend.finalized_head().await?;
+        if self.registry.read().get(&chain.name).is_none() {
+            self.registry.write().register(chain.clone())?;
+        }
+        info!("🔗 {} routable: bloc finalisé #{} ({})", chain.name, head.number, backend.kind());
+        self.substrate.insert(chain.name, SubstrateLink { backend, head: Some(head.clone()), last_error: None });
+        Ok(head)
+    }
+
+    /// Inscrit une chaîne au registre si elle n'y figure pas encore (sous-chaînes de
+    /// `network_builder`)
+    pub fn register_chain(&mut self, chain: ChainConfig) -> Result<(), String> {
+        if self.registry.read().get(&chain.name).is_some() {
+            return Ok(());
+        }
+        self.registry.write().register(chain)
+    }
+
+    pub fn substrate(&self, name: &str) -> Option<&SubstrateLink> {
+        self.substrate.get(name)
+    }
+
+    /// Relit la tête finalisée de chaque chaîne Substrate connectée; une chaîne muette sort du
+    /// routage jusqu'à sa prochaine réponse. Retourne les chaînes dont la tête a avancé.
+    pub async fn refresh_substrate(&mut self) -> Vec<(String, SubstrateBlock)> {
+        let backends: Vec<(String, SharedSubstrate)> =
+            self.substrate.iter().map(|(name, link)| (name.clone(), link.backend.clone())).collect();
+        let mut advanced = Vec::new();
+        for (name, backend) in backends {
+            let result = backend.finalized_head().await;
+            let Some(link) = self.substrate.get_mut(&name) else { continue };
+            match result {
+                Ok(head) => {
+                    if link.head.as_ref().map(|known| known.number < head.number).unwrap_or(true) {
+                        advanced.push((name, head.clone()));
+                    }
+                    link.head = Some(head);
+                    link.last_error = None;
+                }
+                Err(e) => {
+                    if link.last_error.is_none() {
+                        warn!("⚠️ {} retirée du routage: {}", name, e);
+                    }
+                    link.last_error = Some(e);
+                }
+            }
+        }
+        advanced
+    }
+
+    /// Déclare un pont lock-and-mint entre deux chaînes EVM du registre
+    pub fn create_bridge(&mut self, source: &str, destination: &str) -> Result<BridgeDescriptor, String> {
+        if source == destination {
+            return Err("Un pont relie deux chaînes distinctes".to_string());
+        }
+        let (source_kind, destination_kind) = {
+            let registry = self.registry.read();
+            let from = registry.get(source).ok_or_else(|| format!("Chaîne inconnue: {}", source))?;
+            let to = registry.get(destination).ok_or_else(|| format!("Chaîne inconnue: {}", destination))?;
+            (from.kind, to.kind)
+        };
+        if source_kind != ChainKind::Evm || destination_kind != ChainKind::Evm {
+            return Err(format!("Pont {} → {}: le lock-and-mint n'est disponible qu'entre chaînes EVM", source, destination));
+        }
+
+        let bridge = BridgeDescriptor {
+            id: Uuid::new_v4(),
+            source: source.to_string(),
+            destination: destination.to_string(),
+            source_kind,
+            destination_kind,
+            created_at: Utc::now().to_rfc3339(),
+        };
+        self.bridges.push(bridge.clone());
+        Ok(bridge)
+    }
+
+    /// Extrémité d'une chaîne, partagée par les routes qui la touchent: contrat configuré, déjà
+    /// déployé (état persisté) ou déployé puis enregistré avant tout relais; simulée si le backend l'est
+    async fn endpoint(
+        &mut self,
+        chain: &ChainConfig,
+        configured: Option<Address>,
+        signer: &SharedSigner,
+        budget: &SharedBudget,
+        oracle: &GasOracle,
+    ) -> Result<Arc<dyn BridgeEndpoint>, String> {
+        if let Some(endpoint) = self.endpoints.get(&chain.name) {
+            return match (configured, endpoint.contract()) {
+                (Some(wanted), Some(known)) if wanted != known => Err(format!(
+                    "{}: deux contrats de pont configurés ({:?} et {:?})", chain.name, known, wanted
+                )),
+                _ => Ok(endpoint.clone()),
+            };
+        }
+
+        let backend = BlockchainInterface::connect_chain(chain, signer).await?;
+        let endpoint: Arc<dyn BridgeEndpoint> = if backend.kind() != "evm" {
+            Arc::new(SimulatedBridgeEndpoint::new(backend.chain_id(), backend.sender()))
+        } else {
+            let contract = match configured.or_else(|| self.deployed_contracts().get(&chain.name).copied()) {
+                Some(address) => address,
+                None => {
+                    let (address, _) = Deployer::build_and_deploy(&ContractBuilder::default(), BRIDGE_CONTRACT, budget, backend.as_ref(), oracle).await?;
+                    info!("🌉 Contrat {} déployé sur {}: {:?}", BRIDGE_CONTRACT, chain.name, address);
+                    self.record_deployed_contract(&chain.name, address)
+                        .map_err(|e| format!("Contrat {:?} déployé sur {} mais non enregistré: {}", address, chain.name, e))?;
+                    address
+                }
+            };
+            let provider = BlockchainInterface::get_http_provider(&chain.rpc)?;
+            Arc::new(EvmBridgeEndpoint::new(self.tx_managers.for_backend(&backend), provider, contract))
+        };
+        self.endpoints.insert(chain.name.clone(), endpoint.clone());
+        Ok(endpoint)
+    }
+
+    /// Déclare le pont d'une route de `bridges.toml`, connecte (ou déploie) ses deux contrats et
+    /// prépare son relayeur; les en-têtes observés sur le bus servent à vérifier les preuves
+    pub async fn open_bridge(
+        &mut self,
+        route: &BridgeRoute,
+        config: &RelayerConfig,
+        signer: &SharedSigner,
+        budget: &SharedBudget,
+        oracle: &GasOracle,
+        checkpoints: Option<&HeaderCheckpoints>,
+    ) -> Result<BridgeRelayer, String> {
+        let bridge = self.create_bridge(&route.source, &route.destination)?;
+        // Le sens aller doit être exprimable sur la destination (format d'adresse, décimales)
+        let operator = format!("{:?}", signer.address());
+        self.plan_transfer(&route.source, &route.destination, signer.address(), &operator, U256::one())?
+            .lock_arguments()?;
+        let (source_chain, destination_chain) = {
+            let registry = self.registry.read();
+            (registry.get(&route.source).cloned(), registry.get(&route.destination).cloned())
+        };
+        let source_chain = source_chain.ok_or_else(|| format!("Chaîne inconnue: {}", route.source))?;
+        let destination_chain = destination_chain.ok_or_else(|| format!("Chaîne inconnue: {}", route.destination))?;
+        let source = self.endpoint(&source_chain, route.source_contract, signer, budget, oracle).await?;
+        let destination = self.endpoint(&destination_chain, route.destination_contract, signer, budget, oracle).await?;
+
+        let path = self.state_dir.join(format!("{}-{}.json", route.source, route.destination).to_lowercase());
+        let mut relayer = BridgeRelayer::open(&path, bridge, source.clone(), destination, config.clone());
+        if let Some(checkpoints) = checkpoints.filter(|_| source.kind() == "evm") {
+            relayer = relayer.with_verifier(Arc::new(checkpoints.clone()));
+        }
+        info!("🌉 Pont ouvert: {} → {} ({})", route.source, route.destination, source.kind());
+        Ok(relayer)
+    }
+
+    /// Traduit le `lock` de `amount` (plus petite unité de la devise native de `source`) dans
+    /// l'opération qui le livrera sur `destination`: adresse reportée ou à fournir selon les
+    /// formats, montant remis à l'échelle des décimales de l'actif reçu
+    pub fn plan_transfer(&self, source: &str, destination: &str, sender: Address, recipient: &str, amount: U256) -> Result<TransferPlan, String> {
+        let (from, to) = {
+            let registry = self.registry.read();
+            (registry.get(source).cloned(), registry.get(destination).cloned())
+        };
+        let from = from.ok_or_else(|| format!("Chaîne inconnue: {}", source))?;
+        let to = to.ok_or_else(|| format!("Chaîne inconnue: {}", destination))?;
+        if from.kind != ChainKind::Evm {
+            return Err(format!("{}: seules les chaînes EVM verrouillent de la valeur", source));
+        }
+        let native = &from.native_currency;
+        let lock = OperationDescription {
+            protocol: BRIDGE_PROTOCOL.to_string(),
+            operation: "lock".to_string(),
+            asset: AssetInfo { symbol: native.symbol.clone(), decimals: native.decimals, id: None },
+            fields: BTreeMap::from([
+                ("signer".to_string(), format!("{:?}", sender)),
+                ("recipient".to_string(), recipient.to_string()),
+                ("msg.value".to_string(), amount.to_string()),
+            ]),
+        };
+        // Sur une chaîne EVM, AuroraeBridge crédite un solde enveloppé aux décimales de la source;
+        // ailleurs la valeur arriverait en devise native de la destination
+        let received = match to.kind {
+            ChainKind::Evm => AssetInfo { symbol: format!("w{}", native.symbol), decimals: native.decimals, id: None },
+            ChainKind::Substrate => AssetInfo {
+                symbol: to.native_currency.symbol.clone(),
+                decimals: to.native_currency.decimals,
+                id: None,
+            },
+        };
+        let delivery = self.schemas.translate(&lock, receiving_protocol(to.kind), &received)?;
+        let summary = self.schemas.explain(&delivery.description)?;
+        Ok(TransferPlan {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            destination_chain_id: to.chain_id,
+            amount,
+            delivery,
+            summary,
+        })
+    }
+
+    /// Verrouille la valeur d'un plan complet sur le contrat de pont de la source, ouvert au
+    /// préalable par `open_bridge`; le relayeur de la route la livre ensuite
+    pub async fn send_transfer(&self, plan: &TransferPlan) -> Result<H256, String> {
+        let (recipient, amount) = plan.lock_arguments()?;
+        let destination_chain_id = plan.destination_chain_id
+            .ok_or_else(|| format!("{}: chain-id manquant", plan.destination))?;
+        let endpoint = self.endpoints.get(&plan.source)
+            .ok_or_else(|| format!("{}: aucun pont ouvert", plan.source))?;
+        let tx_hash = endpoint.lock(destination_chain_id, recipient, amount).await?;
+        info!("🌉 {} ({:?})", plan.summary, tx_hash);
+        Ok(tx_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_registry::{ChainRegistry, NativeCurrency};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn descriptor() -> BridgeDescriptor {
+        BridgeDescriptor {
+            id: Uuid::new_v4(),
+            source: "A".into(),
+            destination: "B".into(),
+            source_kind: ChainKind::Evm,
+            destination_kind: ChainKind::Evm,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Destination qui échoue un nombre donné de fois avant de laisser passer les exécutions
+    struct Flaky {
+        inner: SimulatedBridgeEndpoint,
+        failures: AtomicU32,
+        contract: Option<Address>,
+    }
+
+    #[async_trait]
+    impl BridgeEndpoint for Flaky {
+        fn kind(&self) -> &str {
+            self.inner.kind()
+        }
+        fn chain_id(&self) -> u64 {
+            self.inner.chain_id()
+        }
+        fn contract(&self) -> Option<Address> {
+            self.contract
+        }
+        async fn head(&self) -> Result<u64, String> {
+            self.inner.head().await
+        }
+        async fn lock(&self, destination_chain_id: u64, recipient: Address, amount: U256) -> Result<H256, String> {
+            self.inner.lock(destination_chain_id, recipient, amount).await
+        }
+        async fn burn(&self, destination_chain_id: u64, recipient: Address, amount: U256) -> Result<H256, String> {
+            self.inner.burn(destination_chain_id, recipient, amount).await
+        }
+        async fn sent_messages(&self, from: u64, to: u64) -> Result<Vec<(BridgeMessage, MessageProof)>, String> {
+            self.inner.sent_messages(from, to).await
+        }
+        async fn executed_messages(&self, from: u64, to: u64) -> Result<Vec<H256>, String> {
+            self.inner.executed_messages(from, to).await
+        }
+        async fn is_executed(&self, id: H256) -> Result<bool, String> {
+            self.inner.is_executed(id).await
+        }
+        async fn execute(&self, message: &BridgeMessage) -> Result<H256, String> {
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err("nonce too low".to_string());
+            }
+            self.inner.execute(message).await
+        }
+    }
+
+    #[tokio::test]
+    async fn substrate_chains_join_routing_once_connected() {
+        let registry = ChainRegistry::from_toml(
+            r#"
+            [[chains]]
+            name = "Sepolia"
+            kind = "evm"
+            rpc = "https://rpc.sepolia.org"
+            chain-id = 11155111
+            native-currency = { symbol = "ETH", decimals = 18 }
+
+            [[chains]]
+            name = "Polkadot"
+            kind = "substrate"
+            rpc = "wss://rpc.polkadot.io"
+            native-currency = { symbol = "DOT", decimals = 10 }
+        "#,
+        )
+        .unwrap();
+        let kusama = ChainConfig {
+            name: "Kusama".into(),
+            kind: ChainKind::Substrate,
+            rpc: "wss://kusama-rpc.polkadot.io".into(),
+            ws: None,
+            chain_id: None,
+            explorer: None,
+            explorer_api: None,
+            native_currency: NativeCurrency { symbol: "KSM".into(), decimals: 12 },
+            testnet: false,
+        };
+        let polkadot = registry.get("Polkadot").cloned().unwrap();
+        let mut router = CrossChainRouter::new(registry.shared());
+        assert_eq!(router.destinations("Sepolia"), Vec::<String>::new());
+        assert!(router.destinations("Polkadot").is_empty());
+
+        router.add_substrate_chain(polkadot).await.unwrap();
+        router.add_substrate_chain(kusama).await.unwrap();
+        assert_eq!(router.destinations("Sepolia"), vec!["Kusama".to_string(), "Polkadot".to_string()]);
+        assert_eq!(router.destinations("Polkadot"), vec!["Kusama".to_string(), "Sepolia".to_string()]);
+        assert!(router.create_bridge("Sepolia", "Polkadot").is_err());
+        assert!(router.refresh_substrate().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn relayer_waits_for_confirmations_then_mints_once() {
+        let recipient = Address::repeat_byte(0x42);
+        let source = Arc::new(SimulatedBridgeEndpoint::new(11155111, Address::repeat_byte(0x1)));
+        let destination = Arc::new(SimulatedBridgeEndpoint::new(17000, Address::repeat_byte(0x1)));
+        let config = RelayerConfig { confirmations: 3, ..RelayerConfig::default() };
+        let mut relayer = BridgeRelayer::new(descriptor(), source.clone(), destination.clone(), config);
+
+        let id = source.lock(17000, recipient, U256::from(1_000u64)).await.unwrap();
+        let report = relayer.poll().await;
+        assert_eq!((report.discovered, report.submitted), (1, 0));
+
+        source.advance(2);
+        assert_eq!(relayer.poll().await.submitted, 1);
+        assert!(destination.is_executed(id).await.unwrap());
+        assert_eq!(destination.wrapped_balance(11155111, recipient), U256::from(1_000u64));
+
+        // La livraison est confirmée par l'événement de la destination, sans second envoi
+        let report = relayer.poll().await;
+        assert_eq!((report.delivered, report.submitted), (1, 0));
+        assert!(relayer.pending().is_empty());
+        assert_eq!(destination.wrapped_balance(11155111, recipient), U256::from(1_000u64));
+    }
+
+    #[tokio::test]
+    async fn relayer_retries_failures_and_rejects_tampered_proofs() {
+        let source = Arc::new(SimulatedBridgeEndpoint::new(1, Address::repeat_byte(0x1)));
+        let destination = Arc::new(Flaky { inner: SimulatedBridgeEndpoint::new(2, Address::repeat_byte(0x1)), failures: AtomicU32::new(2), contract: None });
+        let config = RelayerConfig { confirmations: 1, retry_backoff_secs: 0, max_attempts: 5, ..RelayerConfig::default() };
+        let mut relayer = BridgeRelayer::new(descriptor(), source.clone(), destination.clone(), config);
+
+        let id = source.lock(2, Address::repeat_byte(0x7), U256::from(5u64)).await.unwrap();
+        assert_eq!(relayer.poll().await.retried, 1);
+        assert_eq!(relayer.poll().await.retried, 1);
+        assert_eq!(relayer.poll().await.submitted, 1);
+        assert!(destination.is_executed(id).await.unwrap());
+
+        // Montant modifié après coup: l'identifiant ne correspond plus au log prouvé
+        source.lock(2, Address::repeat_byte(0x7), U256::from(5u64)).await.unwrap();
+        let (mut message, proof) = source.sent_messages(0, 10).await.unwrap().pop().unwrap();
+        message.amount = U256::from(5_000u64);
+        assert!(matches!(MessageIntegrity.verify(&message, &proof), Verdict::Reject(_)));
+        let checkpoints = HeaderCheckpoints::new();
+        assert!(matches!(checkpoints.verify(&message, &proof), Verdict::Wait(_)));
+        checkpoints.record(1, proof.block_number, H256::repeat_byte(0xff));
+        assert!(matches!(checkpoints.verify(&message, &proof), Verdict::Reject(_)));
+    }
+
+    #[tokio::test]
+    async fn burned_wrapped_value_is_unlocked_on_the_origin_chain() {
+        let holder = Address::repeat_byte(0x1);
+        let recipient = Address::repeat_byte(0x42);
+        // Un contrat par chaîne, partagé par les deux sens
+        let a = Arc::new(SimulatedBridgeEndpoint::new(1, holder));
+        let b = Arc::new(SimulatedBridgeEndpoint::new(2, holder));
+        let config = RelayerConfig { confirmations: 1, ..RelayerConfig::default() };
+        let mut outbound = BridgeRelayer::new(descriptor(), a.clone(), b.clone(), config.clone());
+        let mut inbound = BridgeRelayer::new(descriptor(), b.clone(), a.clone(), config);
+
+        a.lock(2, holder, U256::from(1_000u64)).await.unwrap();
+        assert_eq!(outbound.poll().await.submitted, 1);
+        assert_eq!(b.wrapped_balance(1, holder), U256::from(1_000u64));
+        assert_eq!(a.locked(), U256::from(1_000u64));
+
+        assert!(b.burn(1, recipient, U256::from(1_001u64)).await.is_err());
+        b.burn(1, recipient, U256::from(400u64)).await.unwrap();
+        assert_eq!(b.wrapped_balance(1, holder), U256::from(600u64));
+        // Le relayeur du sens aller ignore le retour, celui du sens retour le livre
+        assert_eq!(outbound.poll().await.discovered, 0);
+        assert_eq!(inbound.poll().await.submitted, 1);
+        assert_eq!(a.unlocked_balance(recipient), U256::from(400u64));
+        assert_eq!(a.locked(), U256::from(600u64));
+    }
+
+    #[tokio::test]
+    async fn relayer_state_is_kept_only_for_the_same_contracts() {
+        let dir = std::env::temp_dir().join(format!("aurorae-bridges-{}", Uuid::new_v4()));
+        let path = dir.join("a-b.json");
+        let endpoint = |chain_id: u64, contract: u8| -> Arc<dyn BridgeEndpoint> {
+            Arc::new(Flaky {
+                inner: SimulatedBridgeEndpoint::new(chain_id, Address::repeat_byte(0x1)),
+                failures: AtomicU32::new(0),
+                contract: Some(Address::repeat_byte(contract)),
+            })
+        };
+        let config = RelayerConfig { confirmations: 10, ..RelayerConfig::default() };
+
+        let source = endpoint(1, 0xa);
+        let mut relayer = BridgeRelayer::open(&path, descriptor(), source.clone(), endpoint(2, 0xb), config.clone());
+        source.lock(2, Address::repeat_byte(0x7), U256::from(5u64)).await.unwrap();
+        assert_eq!(relayer.poll().await.discovered, 1);
+
+        let reopened = BridgeRelayer::open(&path, descriptor(), source.clone(), endpoint(2, 0xb), config.clone());
+        assert_eq!(reopened.pending().len(), 1);
+        // Contrat de destination redéployé: les curseurs et messages de l'ancien ne valent plus
+        let redeployed = BridgeRelayer::open(&path, descriptor(), source, endpoint(2, 0xc), config);
+        assert!(redeployed.pending().is_empty());
+        assert_eq!(redeployed.state.destination_contract, Some(Address::repeat_byte(0xc)));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn transfers_are_planned_in_the_destination_protocol_before_locking() {
+        let registry = ChainRegistry::from_toml(
+            r#"
+            [[chains]]
+            name = "Sepolia"
+            kind = "evm"
+            rpc = "https://rpc.sepolia.org"
+            chain-id = 11155111
+            native-currency = { symbol = "ETH", decimals = 18 }
+
+            [[chains]]
+            name = "Holesky"
+            kind = "evm"
+            rpc = "https://ethereum-holesky.publicnode.com"
+            chain-id = 17000
+            native-currency = { symbol = "ETH", decimals = 18 }
+
+            [[chains]]
+            name = "Polkadot"
+            kind = "substrate"
+            rpc = "wss://rpc.polkadot.io"
+            native-currency = { symbol = "DOT", decimals = 10 }
+        "#,
+        )
+        .unwrap();
+        let schemas = SchemaRegistry::load(crate::semantic_bridge::DEFAULT_SCHEMAS_PATH).unwrap();
+        let mut router = CrossChainRouter::new(registry.shared()).with_schemas(schemas);
+        let sender = Address::repeat_byte(0x1);
+        let recipient = Address::repeat_byte(0x2);
+        let one_eth = U256::exp10(18);
+
+        let plan = router.plan_transfer("Sepolia", "Holesky", sender, &format!("{:?}", recipient), one_eth).unwrap();
+        assert_eq!(plan.delivery.description.operation, "execute");
+        assert_eq!(plan.delivery.description.fields["amount"], one_eth.to_string());
+        assert_eq!(plan.lock_arguments().unwrap(), (recipient, one_eth));
+        assert!(plan.summary.starts_with("Transférer 1 wETH"));
+
+        // Format d'adresse et décimales différents: montant remis à l'échelle, destinataire à fournir
+        let polkadot = router.plan_transfer("Sepolia", "Polkadot", sender, &format!("{:?}", recipient), one_eth).unwrap();
+        assert_eq!(polkadot.delivery.description.fields["value"], "10000000000");
+        assert_eq!(polkadot.delivery.unresolved, vec!["dest".to_string()]);
+        assert!(router.send_transfer(&polkadot).await.unwrap_err().contains("dest"));
+        assert!(router.plan_transfer("Sepolia", "Holesky", sender, "0x1234", one_eth).unwrap().lock_arguments().is_err());
+
+        let sepolia = Arc::new(SimulatedBridgeEndpoint::new(11155111, sender));
+        router.endpoints.insert("Sepolia".to_string(), sepolia.clone());
+        router.send_transfer(&plan).await.unwrap();
+        assert_eq!(sepolia.locked(), one_eth);
+    }
+
+    #[test]
+    fn deployed_contracts_are_recorded_per_chain() {
+        let dir = std::env::temp_dir().join(format!("aurorae-bridges-{}", Uuid::new_v4()));
+        let router = CrossChainRouter::new(ChainRegistry::default().shared()).with_state_dir(&dir);
+        assert!(router.deployed_contracts().is_empty());
+
+        router.record_deployed_contract("Sepolia", Address::repeat_byte(0xa)).unwrap();
+        router.record_deployed_contract("Holesky", Address::repeat_byte(0xb)).unwrap();
+        let reopened = CrossChainRouter::new(ChainRegistry::default().shared()).with_state_dir(&dir);
+        assert_eq!(reopened.deployed_contracts().get("Sepolia"), Some(&Address::repeat_byte(0xa)));
+        assert_eq!(reopened.deployed_contracts().len(), 2);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
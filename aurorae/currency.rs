@@ -0,0 +1,244 @@
+//! AURORAE++ - currency.rs
+//!
+//! Actifs typés et table de taux de change. Permet au moteur économique de suivre
+//! chaque revenu dans sa devise d'origine (AURA, ETH, stablecoins, tokens forgés)
+//! et de ne convertir qu'au moment de la lecture, sans agrégation destructive.
+
+use std::collections::HashMap;
+use std::fmt;
+use tracing::warn;
+
+use chrono::Utc;
+use ethers::types::{Address, U256};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Actif suivi par l'économie
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Asset {
+    /// Token natif $AURA
+    Aura,
+    Eth,
+    /// Stablecoin identifié par son symbole (USDC, USDT, DAI…)
+    Stable(String),
+    /// Monnaie native d'une autre chaîne (MATIC, AVAX, DOT…)
+    Native(String),
+    /// Token ERC-20 identifié par sa chaîne et l'adresse de son contrat
+    Token { chain_id: u64, address: Address },
+    /// Token créé par la forge, identifié par son UUID
+    Forged(Uuid),
+}
+
+impl Asset {
+    pub fn usdc() -> Self {
+        Asset::Stable("USDC".to_string())
+    }
+
+    /// Monnaie native d'une chaîne d'après son symbole (ETH pour Ethereum et ses L2)
+    pub fn native(symbol: &str) -> Self {
+        match symbol.to_uppercase().as_str() {
+            "ETH" => Asset::Eth,
+            other => Asset::Native(other.to_string()),
+        }
+    }
+
+    /// Symbole utilisé comme clé de marché
+    pub fn symbol(&self) -> String {
+        match self {
+            Asset::Aura => "AURA".to_string(),
+            Asset::Eth => "ETH".to_string(),
+            Asset::Stable(symbol) | Asset::Native(symbol) => symbol.to_uppercase(),
+            Asset::Token { chain_id, address } => format!("{:?}@{}", address, chain_id),
+            Asset::Forged(id) => format!("TOKEN-{}", id.simple()),
+        }
+    }
+
+    pub fn is_stable(&self) -> bool {
+        matches!(self, Asset::Stable(_))
+    }
+}
+
+/// Convertit un montant brut d'un token à `decimals` décimales en unités
+pub fn from_base_units(raw: U256, decimals: u8) -> f64 {
+    ethers::utils::format_units(raw, decimals as u32)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0.0)
+}
+
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// Montant exprimé dans un actif précis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Amount {
+    pub asset: Asset,
+    pub value: f64,
+}
+
+impl Amount {
+    pub fn new(asset: Asset, value: f64) -> Self {
+        Self { asset, value }
+    }
+}
+
+/// Taux d'un actif exprimé dans la devise de référence (USD)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub usd_price: f64,
+    pub updated_at: String,
+    pub source: String,
+}
+
+/// Table des taux de change, pivot en USD
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeRateTable {
+    pub rates: HashMap<Asset, ExchangeRate>,
+}
+
+impl Default for ExchangeRateTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExchangeRateTable {
+    /// Table initiale: les stablecoins sont ancrés à 1 USD
+    pub fn new() -> Self {
+        let mut table = Self { rates: HashMap::new() };
+        for symbol in ["USDC", "USDT", "DAI"] {
+            table.set_rate(Asset::Stable(symbol.to_string()), 1.0, "peg");
+        }
+        table
+    }
+
+    pub fn set_rate(&mut self, asset: Asset, usd_price: f64, source: &str) {
+        if usd_price <= 0.0 || !usd_price.is_finite() {
+            warn!("⚠️ Taux ignoré pour {}: {}", asset, usd_price);
+            return;
+        }
+        self.rates.insert(asset, ExchangeRate {
+            usd_price,
+            updated_at: Utc::now().to_rfc3339(),
+            source: source.to_string(),
+        });
+    }
+
+    pub fn usd_price(&self, asset: &Asset) -> Option<f64> {
+        self.rates.get(asset).map(|r| r.usd_price)
+    }
+
+    /// Taux de conversion `from` → `to`
+    pub fn rate(&self, from: &Asset, to: &Asset) -> Result<f64, String> {
+        if from == to {
+            return Ok(1.0);
+        }
+        let from_usd = self.usd_price(from).ok_or_else(|| format!("Aucun taux pour {}", from))?;
+        let to_usd = self.usd_price(to).ok_or_else(|| format!("Aucun taux pour {}", to))?;
+        Ok(from_usd / to_usd)
+    }
+
+    /// Convertit un montant vers un autre actif
+    pub fn convert(&self, amount: &Amount, to: &Asset) -> Result<Amount, String> {
+        let rate = self.rate(&amount.asset, to)?;
+        Ok(Amount::new(to.clone(), amount.value * rate))
+    }
+}
+
+/// Portefeuille multi-actifs: un solde par actif, jamais fusionné
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetLedger {
+    pub balances: HashMap<Asset, f64>,
+}
+
+impl AssetLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Crédite `value`; un montant négatif ou non fini est ignoré (il débiterait le compte)
+    pub fn credit(&mut self, asset: Asset, value: f64) {
+        if value < 0.0 || !value.is_finite() {
+            warn!("⚠️ Crédit ignoré pour {}: {}", asset, value);
+            return;
+        }
+        *self.balances.entry(asset).or_insert(0.0) += value;
+    }
+
+    /// Débite `value`, strictement positif et fini, dans la limite du solde
+    pub fn debit(&mut self, asset: &Asset, value: f64) -> Result<(), String> {
+        if value <= 0.0 || !value.is_finite() {
+            return Err(format!("Montant de débit invalide pour {}: {}", asset, value));
+        }
+        let balance = self.balances.get_mut(asset)
+            .ok_or_else(|| format!("Aucun solde en {}", asset))?;
+        if *balance < value {
+            return Err(format!("Solde {} insuffisant: {:.6} < {:.6}", asset, balance, value));
+        }
+        *balance -= value;
+        Ok(())
+    }
+
+    pub fn balance(&self, asset: &Asset) -> f64 {
+        self.balances.get(asset).copied().unwrap_or(0.0)
+    }
+
+    /// Valeur totale exprimée dans `quote`; les actifs sans taux sont signalés et ignorés
+    pub fn total_in(&self, quote: &Asset, rates: &ExchangeRateTable) -> f64 {
+        self.balances.iter()
+            .filter_map(|(asset, value)| match rates.convert(&Amount::new(asset.clone(), *value), quote) {
+                Ok(converted) => Some(converted.value),
+                Err(e) => {
+                    warn!("⚠️ Valorisation impossible: {}", e);
+                    None
+                }
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debit_rejects_non_positive_and_non_finite_amounts() {
+        let mut ledger = AssetLedger::new();
+        ledger.credit(Asset::Aura, 10.0);
+
+        for value in [-5.0, 0.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(ledger.debit(&Asset::Aura, value).is_err(), "débit de {} accepté", value);
+        }
+        assert_eq!(ledger.balance(&Asset::Aura), 10.0);
+
+        assert!(ledger.debit(&Asset::Aura, 11.0).is_err());
+        ledger.debit(&Asset::Aura, 4.0).unwrap();
+        assert_eq!(ledger.balance(&Asset::Aura), 6.0);
+    }
+
+    #[test]
+    fn credit_ignores_negative_and_non_finite_amounts() {
+        let mut ledger = AssetLedger::new();
+        ledger.credit(Asset::Eth, 2.0);
+        ledger.credit(Asset::Eth, -5.0);
+        ledger.credit(Asset::Eth, f64::NAN);
+        ledger.credit(Asset::Eth, f64::INFINITY);
+        assert_eq!(ledger.balance(&Asset::Eth), 2.0);
+    }
+
+    #[test]
+    fn converts_through_usd_pivot() {
+        let mut rates = ExchangeRateTable::new();
+        rates.set_rate(Asset::Eth, 2_000.0, "test");
+        rates.set_rate(Asset::Aura, 0.05, "test");
+        rates.set_rate(Asset::Aura, f64::NAN, "test");
+
+        let converted = rates.convert(&Amount::new(Asset::Eth, 1.0), &Asset::Aura).unwrap();
+        assert_eq!(converted.asset, Asset::Aura);
+        assert!((converted.value - 40_000.0).abs() < 1e-6);
+        assert!(rates.convert(&Amount::new(Asset::Native("DOT".into()), 1.0), &Asset::usdc()).is_err());
+    }
+}
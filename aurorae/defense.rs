@@ -5,6 +5,7 @@
 
 use chrono::Utc;
 use uuid::Uuid;
+use tracing::{info, warn};
 
 #[derive(Debug, Clone)]
 pub enum ThreatType {
@@ -44,7 +45,7 @@ impl DefenseMatrix {
             neutralized: false,
         };
 
-        println!("[AURORAE++] ⚠️ MENACE DÉTECTÉE : {:?} — {}", cloned_type, details);
+        warn!("⚠️ MENACE DÉTECTÉE : {:?} — {}", cloned_type, details);
         self.reports.push(report);
     }
 
@@ -52,13 +53,13 @@ impl DefenseMatrix {
         if let Some(last) = self.reports.last_mut() {
             if !last.neutralized {
                 last.neutralized = true;
-                println!("[AURORAE++] ✅ MENACE NEUTRALISÉE : {:?}", last.threat_type);
+                info!("✅ MENACE NEUTRALISÉE : {:?}", last.threat_type);
             }
         }
     }
 
     pub fn list_threats(&self) {
-        println!("[AURORAE++] 🛡️ RÉCAPITULATIF DES MENACES :");
+        info!("🛡️ RÉCAPITULATIF DES MENACES :");
         for r in &self.reports {
             println!("- [{}] {:?} | Neutralisé: {} | {}", r.id, r.threat_type, r.neutralized, r.details);
         }
@@ -0,0 +1,428 @@
+//! AURORAE++ - deploy_manifest.rs
+//!
+//! Manifestes de déploiement multi-contrats (YAML ou JSON): ordre, arguments de
+//! constructeur et dépendances entre adresses. Le plan est toujours calculé et
+//! affiché sous forme de diff avant envoi; en mode `--dry-run` rien n'est envoyé.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use chrono::Utc;
+use ethers::abi::token::{LenientTokenizer, Tokenizer};
+use ethers::abi::{Abi, Token};
+use ethers::types::{Address, H256, U256};
+use ethers::utils::{get_contract_address, keccak256};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::blockchain_core::{ChainBackend, GasOracle};
+use crate::deployer::{ContractArtifact, ContractBuilder, Deployer};
+use crate::economy::{SharedBudget, SpendDecision};
+
+/// Répertoire des états de déploiement par manifeste et par chaîne
+pub const MANIFEST_STATE_DIR: &str = "aurorae_state/manifests";
+
+/// Contrat décrit dans un manifeste
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestContract {
+    /// Identifiant unique dans le manifeste, utilisé par les références `@id`
+    pub id: String,
+    /// Contrat de `contracts/` à compiler (par défaut: `id`)
+    #[serde(default)]
+    pub contract: Option<String>,
+    /// Arguments du constructeur; une chaîne `@id` est remplacée par l'adresse du contrat `id`
+    #[serde(default)]
+    pub args: Vec<Value>,
+    /// Dépendances sans référence d'adresse dans les arguments
+    #[serde(default, rename = "depends-on")]
+    pub depends_on: Vec<String>,
+}
+
+impl ManifestContract {
+    pub fn contract_name(&self) -> &str {
+        self.contract.as_deref().unwrap_or(&self.id)
+    }
+
+    /// Contrats dont l'adresse doit être connue avant ce déploiement
+    pub fn dependencies(&self) -> Vec<String> {
+        let mut deps: Vec<String> = self.args.iter()
+            .filter_map(|arg| arg.as_str())
+            .filter_map(|arg| arg.strip_prefix('@'))
+            .map(|id| id.to_string())
+            .collect();
+        for dep in &self.depends_on {
+            if !deps.contains(dep) {
+                deps.push(dep.clone());
+            }
+        }
+        deps
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentManifest {
+    pub name: String,
+    /// Chaîne du registre visée
+    pub chain: String,
+    pub contracts: Vec<ManifestContract>,
+}
+
+impl DeploymentManifest {
+    /// Charge un manifeste YAML (`.yaml`, `.yml`) ou JSON
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        if path.ends_with(".yaml") || path.ends_with(".yml") {
+            Self::from_yaml(&content)
+        } else {
+            Self::from_json(&content)
+        }
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self, String> {
+        serde_yaml::from_str(content).map_err(|e| format!("Manifeste YAML invalide: {}", e))
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        serde_json::from_str(content).map_err(|e| format!("Manifeste JSON invalide: {}", e))
+    }
+
+    /// Ordre de déploiement respectant les dépendances; à dépendances égales,
+    /// l'ordre du manifeste est conservé
+    pub fn ordered(&self) -> Result<Vec<&ManifestContract>, String> {
+        let mut ids = HashSet::new();
+        for contract in &self.contracts {
+            if !ids.insert(contract.id.as_str()) {
+                return Err(format!("Identifiant dupliqué dans le manifeste: {}", contract.id));
+            }
+        }
+        for contract in &self.contracts {
+            if let Some(missing) = contract.dependencies().into_iter().find(|dep| !ids.contains(dep.as_str())) {
+                return Err(format!("{}: dépendance inconnue '{}'", contract.id, missing));
+            }
+        }
+
+        let mut placed: HashSet<&str> = HashSet::new();
+        let mut order = Vec::with_capacity(self.contracts.len());
+        while order.len() < self.contracts.len() {
+            let next = self.contracts.iter()
+                .filter(|c| !placed.contains(c.id.as_str()))
+                .find(|c| c.dependencies().iter().all(|dep| placed.contains(dep.as_str())));
+            match next {
+                Some(contract) => {
+                    placed.insert(contract.id.as_str());
+                    order.push(contract);
+                }
+                None => {
+                    let blocked: Vec<&str> = self.contracts.iter()
+                        .map(|c| c.id.as_str())
+                        .filter(|id| !placed.contains(id))
+                        .collect();
+                    return Err(format!("Dépendances circulaires entre: {}", blocked.join(", ")));
+                }
+            }
+        }
+        Ok(order)
+    }
+}
+
+/// Contrat déployé par un manifeste
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployedEntry {
+    pub contract: String,
+    pub address: Address,
+    /// keccak256 du code d'initialisation (bytecode + arguments)
+    pub init_code_hash: String,
+    pub tx_hash: Option<H256>,
+    pub deployed_at: String,
+}
+
+/// État d'un manifeste sur une chaîne, base du diff
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestState {
+    pub manifest: String,
+    pub chain_id: u64,
+    pub contracts: BTreeMap<String, DeployedEntry>,
+}
+
+impl ManifestState {
+    fn path(manifest: &str, chain_id: u64) -> String {
+        format!("{}/{}-{}.json", MANIFEST_STATE_DIR, manifest, chain_id)
+    }
+
+    /// 📥 Chargement depuis disque (état vide si le manifeste n'a jamais été appliqué)
+    pub fn load(manifest: &str, chain_id: u64) -> Self {
+        File::open(Self::path(manifest, chain_id))
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_else(|| Self { manifest: manifest.to_string(), chain_id, contracts: BTreeMap::new() })
+    }
+
+    /// 💾 Sauvegarde en JSON local
+    pub fn save(&self) {
+        if create_dir_all(MANIFEST_STATE_DIR).is_ok() {
+            if let Ok(file) = File::create(Self::path(&self.manifest, self.chain_id)) {
+                let writer = BufWriter::new(file);
+                if serde_json::to_writer_pretty(writer, &self).is_ok() {
+                    info!("💾 État du manifeste {} sauvegardé.", self.manifest);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepAction {
+    Deploy,
+    /// Bytecode ou arguments modifiés depuis le dernier déploiement
+    Redeploy,
+    Unchanged,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedStep {
+    pub id: String,
+    pub contract: String,
+    pub action: StepAction,
+    /// Adresse existante, ou adresse prévue (nonce du déployeur) pour un déploiement
+    pub address: Address,
+    pub gas: U256,
+    /// Coût maximal en monnaie native
+    pub cost: f64,
+    /// Décision qu'aurait rendue le moteur budgétaire
+    pub budget: Option<SpendDecision>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeploymentPlan {
+    pub manifest: String,
+    pub chain_id: u64,
+    pub steps: Vec<PlannedStep>,
+    /// Contrats de l'état précédent absents du manifeste (jamais détruits, seulement oubliés)
+    pub removed: Vec<String>,
+}
+
+impl DeploymentPlan {
+    pub fn total_gas(&self) -> U256 {
+        self.steps.iter().fold(U256::zero(), |acc, step| acc + step.gas)
+    }
+
+    pub fn total_cost(&self) -> f64 {
+        self.steps.iter().map(|step| step.cost).sum()
+    }
+
+    /// Le budget refuse au moins une étape: rien ne doit être envoyé
+    pub fn blocked_by_budget(&self) -> Option<&PlannedStep> {
+        self.steps.iter().find(|step| matches!(step.budget, Some(SpendDecision::Denied(_)) | Some(SpendDecision::RequiresApproval(_))))
+    }
+
+    pub fn print_diff(&self) {
+        info!("📋 Plan du manifeste {} (chain-id {})", self.manifest, self.chain_id);
+        for step in &self.steps {
+            let marker = match step.action {
+                StepAction::Deploy => "+",
+                StepAction::Redeploy => "~",
+                StepAction::Unchanged => "=",
+            };
+            match &step.budget {
+                Some(decision) => println!(
+                    "{} {} ({}) → {:?} • gas {} • coût max {:.6} • budget {:?}",
+                    marker, step.id, step.contract, step.address, step.gas, step.cost, decision
+                ),
+                None => println!("{} {} ({}) → {:?}", marker, step.id, step.contract, step.address),
+            }
+        }
+        for id in &self.removed {
+            println!("- {} (absent du manifeste)", id);
+        }
+        info!("⛽ Total: gas {} • coût max {:.6}", self.total_gas(), self.total_cost());
+    }
+}
+
+/// Résout les arguments du manifeste selon les types du constructeur
+fn tokenize_args(artifact: &ContractArtifact, args: &[Value], addresses: &HashMap<String, Address>) -> Result<Vec<Token>, String> {
+    let abi_content = fs::read_to_string(&artifact.abi_path)
+        .map_err(|e| format!("Erreur lecture ABI: {}", e))?;
+    let abi: Abi = Abi::load(abi_content.as_bytes())
+        .map_err(|e| format!("ABI invalide: {}", e))?;
+    let inputs = abi.constructor.map(|c| c.inputs).unwrap_or_default();
+    if inputs.len() != args.len() {
+        return Err(format!("{}: {} arguments attendus, {} fournis", artifact.name, inputs.len(), args.len()));
+    }
+
+    inputs.iter().zip(args).map(|(param, arg)| {
+        let raw = match arg {
+            Value::String(s) => match s.strip_prefix('@') {
+                Some(id) => addresses.get(id)
+                    .map(|address| format!("{:?}", address))
+                    .ok_or_else(|| format!("{}: adresse de '{}' inconnue", artifact.name, id))?,
+                None => s.clone(),
+            },
+            other => other.to_string(),
+        };
+        LenientTokenizer::tokenize(&param.kind, &raw)
+            .map_err(|e| format!("{}: argument '{}' invalide pour {}: {}", artifact.name, raw, param.kind, e))
+    }).collect()
+}
+
+pub struct ManifestRunner;
+
+impl ManifestRunner {
+    /// Compile, planifie et affiche le diff du manifeste; hors `dry_run`, déploie ensuite
+    /// les contrats nouveaux ou modifiés dans l'ordre des dépendances
+    pub async fn execute(
+        manifest: &DeploymentManifest,
+        builder: &ContractBuilder,
+        budget: &SharedBudget,
+        backend: &dyn ChainBackend,
+        oracle: &GasOracle,
+        dry_run: bool,
+    ) -> Result<DeploymentPlan, String> {
+        let order = manifest.ordered()?;
+        let artifacts = order.iter()
+            .map(|c| builder.build(c.contract_name()).map(|artifact| (c.id.clone(), artifact)))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        let mut state = ManifestState::load(&manifest.name, backend.chain_id());
+
+        // 🧪 Simulation: adresses prévues à partir du nonce, budget évalué sur une copie
+        let fees = oracle.estimate_fees(backend).await?;
+        let mut nonce = backend.pending_nonce(backend.sender()).await?;
+        let mut budget_preview = budget.read().clone();
+        let mut addresses = HashMap::new();
+        let mut steps = Vec::with_capacity(order.len());
+        for contract in &order {
+            let artifact = &artifacts[&contract.id];
+            let args = tokenize_args(artifact, &contract.args, &addresses)?;
+            let (abi, init_code) = Deployer::encode_deployment(artifact, &args)?;
+            let init_code_hash = hex::encode(keccak256(&init_code));
+
+            let action = match state.contracts.get(&contract.id) {
+                Some(entry) if entry.init_code_hash == init_code_hash => StepAction::Unchanged,
+                Some(_) => StepAction::Redeploy,
+                None => StepAction::Deploy,
+            };
+            let step = if action == StepAction::Unchanged {
+                PlannedStep {
+                    id: contract.id.clone(),
+                    contract: artifact.name.clone(),
+                    action,
+                    address: state.contracts[&contract.id].address,
+                    gas: U256::zero(),
+                    cost: 0.0,
+                    budget: None,
+                }
+            } else {
+                let gas = backend.estimate_deploy_gas(&abi, &init_code).await?;
+                let cost = fees.max_cost(gas);
+                let step = PlannedStep {
+                    id: contract.id.clone(),
+                    contract: artifact.name.clone(),
+                    action,
+                    address: get_contract_address(backend.sender(), nonce),
+                    gas,
+                    cost,
+                    budget: Some(budget_preview.request_gas_spend("deployer", cost)),
+                };
+                nonce += 1;
+                step
+            };
+            addresses.insert(contract.id.clone(), step.address);
+            steps.push(step);
+        }
+
+        let ids: HashSet<&str> = order.iter().map(|c| c.id.as_str()).collect();
+        let plan = DeploymentPlan {
+            manifest: manifest.name.clone(),
+            chain_id: backend.chain_id(),
+            steps,
+            removed: state.contracts.keys().filter(|id| !ids.contains(id.as_str())).cloned().collect(),
+        };
+        plan.print_diff();
+
+        if dry_run {
+            info!("🧪 Dry-run: aucune transaction envoyée (coût estimé {:.6})", plan.total_cost());
+            return Ok(plan);
+        }
+        if let Some(step) = plan.blocked_by_budget() {
+            return Err(format!("Manifeste {} non appliqué: {} bloqué par le budget ({:?})", manifest.name, step.id, step.budget));
+        }
+
+        // 🚀 Application: les adresses réelles remplacent les adresses prévues
+        let mut deployed = HashMap::new();
+        for (contract, step) in order.iter().zip(&plan.steps) {
+            if step.action == StepAction::Unchanged {
+                deployed.insert(contract.id.clone(), step.address);
+                continue;
+            }
+            let artifact = &artifacts[&contract.id];
+            let args = tokenize_args(artifact, &contract.args, &deployed)?;
+            let (_, init_code) = Deployer::encode_deployment(artifact, &args)?;
+            let (address, tx_hash) = Deployer::deploy_with_args(budget, backend, oracle, artifact, args).await?;
+            if address != step.address {
+                warn!("⚠️ {} déployé à {:?} au lieu de {:?} prévu", contract.id, address, step.address);
+            }
+
+            deployed.insert(contract.id.clone(), address);
+            state.contracts.insert(contract.id.clone(), DeployedEntry {
+                contract: artifact.name.clone(),
+                address,
+                init_code_hash: hex::encode(keccak256(&init_code)),
+                tx_hash: Some(tx_hash),
+                deployed_at: Utc::now().to_rfc3339(),
+            });
+            // Sauvegarde après chaque contrat: une reprise ne redéploie pas ce qui a abouti
+            state.save();
+        }
+        state.contracts.retain(|id, _| ids.contains(id.as_str()));
+        state.save();
+
+        info!("✅ Manifeste {} appliqué ({} contrats)", manifest.name, deployed.len());
+        Ok(plan)
+    }
+}
+
+/// Le manifeste de déploiement par défaut, s'il existe
+pub fn default_manifest_path() -> Option<&'static str> {
+    ["deployments/aurorae.yaml", "deployments/aurorae.json"]
+        .into_iter()
+        .find(|path| Path::new(path).exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+name: aurorae
+chain: Sepolia
+contracts:
+  - id: Vault
+    args: ["@Auroraium"]
+  - id: Auroraium
+    args: [1000000, "0xFd4456F8d982276Ac7d2294E66Dc8aCc097f0043"]
+  - id: Registry
+    depends-on: [Vault]
+"#;
+
+    #[test]
+    fn orders_contracts_by_dependencies() {
+        let manifest = DeploymentManifest::from_yaml(SAMPLE).unwrap();
+        let order: Vec<&str> = manifest.ordered().unwrap().iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(order, vec!["Auroraium", "Vault", "Registry"]);
+    }
+
+    #[test]
+    fn rejects_dependency_cycles() {
+        let manifest = DeploymentManifest::from_json(r#"{
+            "name": "cycle", "chain": "Sepolia",
+            "contracts": [
+                { "id": "A", "args": ["@B"] },
+                { "id": "B", "depends-on": ["A"] }
+            ]
+        }"#).unwrap();
+        assert!(manifest.ordered().unwrap_err().contains("circulaires"));
+    }
+}
@@ -1,23 +1,676 @@
-use std::fs;
-use std::str::FromStr;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
-use ethers::prelude::*;
-use ethers::types::{Address, Bytes};
-use ethers::contract::ContractFactory;
-use ethers::middleware::SignerMiddleware;
-use ethers::signers::{LocalWallet, Wallet};
-use k256::ecdsa::SigningKey;
-use ethers::abi::Abi;
+use chrono::{DateTime, Duration, Utc};
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use ethers::types::{Address, Bytes, H256, U256};
+use ethers::utils::{get_create2_address, id, keccak256};
+use ethers::abi::{encode, Abi, Token};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
 
-use crate::blockchain_core::HttpProvider;
+use crate::blockchain_core::{deterministic_deployer, wei_to_native, ChainBackend, GasOracle, SharedTxManager, TxReceipt};
+use crate::chain_registry::{ChainConfig, SharedChainRegistry};
+use crate::keystore::{Signer, SigningPolicy};
+use crate::economy::{SharedBudget, SpendDecision};
+use crate::contract_verification::{ContractVerifier, VerificationRecord};
+use crate::market_data::MarketDataFeed;
+use crate::security::{log_security_event, SecurityEvent};
+
+/// Profondeur de confirmation attendue après un déploiement
+const DEPLOY_CONFIRMATIONS: usize = 3;
+
+/// Répertoire des sources Solidity
+pub const CONTRACTS_DIR: &str = "contracts";
+/// Répertoire des artefacts compilés (ABI, bytecode, métadonnées)
+pub const ARTIFACTS_DIR: &str = "aurorae_state/artifacts";
+
+/// Chaîne de compilation Solidity utilisée par le `ContractBuilder`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CompilerBackend {
+    Solc { binary: String },
+    Foundry { binary: String },
+}
+
+/// Métadonnées de compilation enregistrées avec chaque artefact
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompilerMetadata {
+    pub compiler: String,
+    pub version: String,
+    pub optimizer_runs: u32,
+    /// SHA-256 de la source et des options de compilation: clé du cache
+    pub source_hash: String,
+    pub compiled_at: String,
+}
+
+/// Artefact prêt à être passé à `Deployer::deploy_contract`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractArtifact {
+    pub name: String,
+    pub abi_path: String,
+    pub bytecode_path: String,
+    pub metadata: CompilerMetadata,
+    /// Source compilée, soumise aux explorateurs pour vérification
+    #[serde(default)]
+    pub source_path: String,
+    /// Métadonnées solc (format standard), requises par Sourcify
+    #[serde(default)]
+    pub solc_metadata_path: Option<String>,
+}
+
+/// Sortie brute d'un compilateur
+struct CompilerOutput {
+    abi: String,
+    bytecode: String,
+    compiler: String,
+    version: String,
+    solc_metadata: Option<String>,
+}
+
+/// Compile les contrats de `contracts/` avec solc ou foundry et met les artefacts en cache
+pub struct ContractBuilder {
+    pub sources_dir: PathBuf,
+    pub artifacts_dir: PathBuf,
+    pub backend: CompilerBackend,
+    pub optimizer_runs: u32,
+}
+
+impl Default for ContractBuilder {
+    fn default() -> Self {
+        Self::new(CompilerBackend::Solc { binary: "solc".to_string() })
+    }
+}
+
+impl ContractBuilder {
+    pub fn new(backend: CompilerBackend) -> Self {
+        Self {
+            sources_dir: PathBuf::from(CONTRACTS_DIR),
+            artifacts_dir: PathBuf::from(ARTIFACTS_DIR),
+            backend,
+            optimizer_runs: 200,
+        }
+    }
+
+    /// Sources `.sol` disponibles, par nom de contrat (nom de fichier)
+    pub fn list_sources(&self) -> Result<Vec<String>, String> {
+        let entries = fs::read_dir(&self.sources_dir)
+            .map_err(|e| format!("Erreur lecture {}: {}", self.sources_dir.display(), e))?;
+        let mut names: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().map(|ext| ext == "sol").unwrap_or(false))
+            .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn source_hash(&self, source: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(source.as_bytes());
+        hasher.update(format!("{:?}|{}", self.backend, self.optimizer_runs).as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    fn metadata_path(&self, name: &str) -> PathBuf {
+        self.artifacts_dir.join(format!("{}.meta.json", name))
+    }
+
+    /// Compile `contracts/<name>.sol`, ou réutilise l'artefact si la source n'a pas changé
+    pub fn build(&self, name: &str) -> Result<ContractArtifact, String> {
+        let source_path = self.sources_dir.join(format!("{}.sol", name));
+        let source = fs::read_to_string(&source_path)
+            .map_err(|e| format!("Erreur lecture {}: {}", source_path.display(), e))?;
+        let source_hash = self.source_hash(&source);
+
+        if let Some(cached) = self.cached(name, &source_hash) {
+            info!("📦 Artefact {} à jour (cache {})", name, &source_hash[..12]);
+            return Ok(cached);
+        }
+
+        let output = match &self.backend {
+            CompilerBackend::Solc { binary } => self.compile_with_solc(binary, &source_path, name)?,
+            CompilerBackend::Foundry { binary } => self.compile_with_foundry(binary, name)?,
+        };
+
+        fs::create_dir_all(&self.artifacts_dir)
+            .map_err(|e| format!("Erreur création {}: {}", self.artifacts_dir.display(), e))?;
+        let abi_path = self.artifacts_dir.join(format!("{}.abi.json", name));
+        let bytecode_path = self.artifacts_dir.join(format!("{}.bin", name));
+        fs::write(&abi_path, &output.abi).map_err(|e| format!("Erreur écriture ABI: {}", e))?;
+        fs::write(&bytecode_path, &output.bytecode).map_err(|e| format!("Erreur écriture bytecode: {}", e))?;
+        let solc_metadata_path = match &output.solc_metadata {
+            Some(metadata) => {
+                let path = self.artifacts_dir.join(format!("{}.metadata.json", name));
+                fs::write(&path, metadata).map_err(|e| format!("Erreur écriture des métadonnées solc: {}", e))?;
+                Some(path.to_string_lossy().to_string())
+            }
+            None => None,
+        };
+
+        let artifact = ContractArtifact {
+            name: name.to_string(),
+            abi_path: abi_path.to_string_lossy().to_string(),
+            bytecode_path: bytecode_path.to_string_lossy().to_string(),
+            metadata: CompilerMetadata {
+                compiler: output.compiler,
+                version: output.version,
+                optimizer_runs: self.optimizer_runs,
+                source_hash,
+                compiled_at: Utc::now().to_rfc3339(),
+            },
+            source_path: source_path.to_string_lossy().to_string(),
+            solc_metadata_path,
+        };
+        let json = serde_json::to_string_pretty(&artifact)
+            .map_err(|e| format!("Erreur sérialisation des métadonnées: {}", e))?;
+        fs::write(self.metadata_path(name), json)
+            .map_err(|e| format!("Erreur écriture des métadonnées: {}", e))?;
+
+        info!("🛠️ Contrat {} compilé ({} {})", name, artifact.metadata.compiler, artifact.metadata.version);
+        Ok(artifact)
+    }
+
+    fn cached(&self, name: &str, source_hash: &str) -> Option<ContractArtifact> {
+        let content = fs::read_to_string(self.metadata_path(name)).ok()?;
+        let artifact: ContractArtifact = serde_json::from_str(&content).ok()?;
+        let files_present = Path::new(&artifact.abi_path).exists() && Path::new(&artifact.bytecode_path).exists();
+        (artifact.metadata.source_hash == source_hash && files_present).then_some(artifact)
+    }
+
+    fn run(binary: &str, args: &[&str], cwd: Option<&Path>) -> Result<String, String> {
+        let mut command = Command::new(binary);
+        command.args(args);
+        if let Some(dir) = cwd {
+            command.current_dir(dir);
+        }
+        let output = command.output()
+            .map_err(|e| format!("Compilateur {} introuvable: {}", binary, e))?;
+        if !output.status.success() {
+            return Err(format!("Échec de {}: {}", binary, String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn compile_with_solc(&self, binary: &str, source_path: &Path, name: &str) -> Result<CompilerOutput, String> {
+        let runs = self.optimizer_runs.to_string();
+        let source = source_path.to_string_lossy().to_string();
+        let output = Self::run(
+            binary,
+            &["--combined-json", "abi,bin,metadata", "--optimize", "--optimize-runs", &runs, &source],
+            None,
+        )?;
+
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| format!("Sortie solc invalide: {}", e))?;
+        let contracts = json.get("contracts").and_then(|c| c.as_object())
+            .ok_or_else(|| "Sortie solc sans contrats".to_string())?;
+        let (_, contract) = contracts.iter()
+            .find(|(key, _)| key.rsplit(':').next() == Some(name))
+            .ok_or_else(|| format!("Contrat {} absent de la sortie solc", name))?;
+
+        // Selon la version de solc, l'ABI est un tableau JSON ou une chaîne encodée
+        let abi = match contract.get("abi") {
+            Some(serde_json::Value::String(abi)) => abi.clone(),
+            Some(abi) => abi.to_string(),
+            None => return Err(format!("ABI absente pour {}", name)),
+        };
+        let bin = contract.get("bin").and_then(|b| b.as_str())
+            .ok_or_else(|| format!("Bytecode absent pour {}", name))?;
+        let version = json.get("version").and_then(|v| v.as_str()).unwrap_or("inconnue").to_string();
+
+        let solc_metadata = contract.get("metadata").and_then(|m| m.as_str()).map(|m| m.to_string());
+
+        Ok(CompilerOutput {
+            abi,
+            bytecode: format!("0x{}", bin),
+            compiler: "solc".to_string(),
+            version,
+            solc_metadata,
+        })
+    }
+
+    fn compile_with_foundry(&self, binary: &str, name: &str) -> Result<CompilerOutput, String> {
+        // Projet foundry dont les sources sont `contracts/`
+        let root = self.sources_dir.parent().unwrap_or_else(|| Path::new("."));
+        let contracts = self.sources_dir.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| CONTRACTS_DIR.to_string());
+        let runs = self.optimizer_runs.to_string();
+
+        let abi = Self::run(binary, &["inspect", "--contracts", &contracts, "--optimizer-runs", &runs, name, "abi"], Some(root))?;
+        let bytecode = Self::run(binary, &["inspect", "--contracts", &contracts, "--optimizer-runs", &runs, name, "bytecode"], Some(root))?;
+        let solc_metadata = Self::run(binary, &["inspect", "--contracts", &contracts, "--optimizer-runs", &runs, name, "metadata"], Some(root))
+            .ok()
+            .map(|m| m.trim().to_string());
+        // `forge --version` donne la version de forge: celle de solc figure dans les métadonnées
+        let version = solc_metadata.as_deref()
+            .and_then(solc_version_from_metadata)
+            .unwrap_or_else(|| "inconnue".to_string());
+
+        Ok(CompilerOutput {
+            abi: abi.trim().to_string(),
+            bytecode: bytecode.trim().to_string(),
+            compiler: "foundry".to_string(),
+            version,
+            solc_metadata,
+        })
+    }
+}
+
+/// Version de solc (« 0.8.20+commit.a1b79de6 ») déclarée par les métadonnées du compilateur
+fn solc_version_from_metadata(metadata: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(metadata).ok()?;
+    json.pointer("/compiler/version").and_then(|v| v.as_str()).map(|v| v.to_string())
+}
+
+/// Options d'un déploiement d'artefact
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentOptions {
+    /// Soumettre la source aux explorateurs et à Sourcify après le déploiement
+    pub verify_code: bool,
+    /// Déployer via le proxy CREATE2 avec ce sel (adresse identique sur toutes les chaînes)
+    pub create2_salt: Option<String>,
+}
+
+/// Bilan d'un déploiement, vérification comprise
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentResult {
+    pub contract: String,
+    pub address: Address,
+    pub chain: String,
+    pub chain_id: u64,
+    pub deployed_at: String,
+    pub verification: Vec<VerificationRecord>,
+}
+
+impl DeploymentResult {
+    pub fn is_verified(&self) -> bool {
+        self.verification.iter().any(|r| r.is_verified())
+    }
+}
+
+/// Gas ajouté à l'estimation d'un déploiement pour l'appel du proxy CREATE2
+const CREATE2_CALL_OVERHEAD: u64 = 32_000;
+
+/// Déploiement CREATE2 enregistré
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Create2Entry {
+    pub chain_id: u64,
+    pub salt: String,
+    pub contract: String,
+    pub address: Address,
+    pub init_code_hash: String,
+    pub deployed_at: String,
+}
+
+/// Registre (chaîne, sel) → adresse des déploiements déterministes, persisté sur disque
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Create2Registry {
+    pub entries: HashMap<String, Create2Entry>,
+}
+
+impl Create2Registry {
+    fn key(chain_id: u64, salt: &str) -> String {
+        format!("{}:{}", chain_id, salt)
+    }
+
+    pub fn get(&self, chain_id: u64, salt: &str) -> Option<&Create2Entry> {
+        self.entries.get(&Self::key(chain_id, salt))
+    }
+
+    pub fn insert(&mut self, entry: Create2Entry) {
+        self.entries.insert(Self::key(entry.chain_id, &entry.salt), entry);
+    }
+
+    /// Chaînes sur lesquelles un sel a déjà été déployé
+    pub fn chains_for_salt(&self, salt: &str) -> Vec<u64> {
+        let mut chains: Vec<u64> = self.entries.values().filter(|e| e.salt == salt).map(|e| e.chain_id).collect();
+        chains.sort();
+        chains
+    }
+
+    /// 💾 Sauvegarde en JSON local
+    pub fn save(&self) {
+        let dir = Path::new("aurorae_state");
+        if create_dir_all(dir).is_ok() {
+            if let Ok(file) = File::create(dir.join("create2_registry.json")) {
+                let writer = BufWriter::new(file);
+                if serde_json::to_writer_pretty(writer, &self).is_ok() {
+                    info!("💾 Registre CREATE2 sauvegardé.");
+                }
+            }
+        }
+    }
+
+    /// 📥 Chargement depuis disque (registre vide si absent)
+    pub fn load() -> Self {
+        File::open("aurorae_state/create2_registry.json")
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Coût réel d'un déploiement, tel que relevé dans le reçu
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentCost {
+    pub contract: String,
+    pub network: String,
+    pub chain_id: u64,
+    pub address: Address,
+    pub tx_hash: H256,
+    pub gas_used: U256,
+    pub effective_gas_price: U256,
+    /// Coût en monnaie native de la chaîne (`native_symbol`)
+    pub native_cost: f64,
+    pub native_symbol: String,
+    /// Prix USD de la monnaie native au moment du déploiement (nul sur un testnet)
+    pub usd_price: Option<f64>,
+    pub fiat_cost: Option<f64>,
+    pub deployed_at: String,
+}
+
+/// Dépense agrégée d'un réseau ou d'un contrat
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CostSummary {
+    pub deployments: usize,
+    pub gas_used: U256,
+    /// Somme en monnaie native: significative seulement pour un réseau donné
+    pub native_cost: f64,
+    pub fiat_cost: f64,
+    /// Déploiements sans prix de marché disponible, exclus de `fiat_cost`
+    pub unpriced: usize,
+}
+
+impl CostSummary {
+    fn add(&mut self, cost: &DeploymentCost) {
+        self.deployments += 1;
+        self.gas_used += cost.gas_used;
+        self.native_cost += cost.native_cost;
+        match cost.fiat_cost {
+            Some(fiat) => self.fiat_cost += fiat,
+            None => self.unpriced += 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CostReport {
+    pub per_network: BTreeMap<String, CostSummary>,
+    pub per_contract: BTreeMap<String, CostSummary>,
+    pub total_fiat: f64,
+}
+
+impl CostReport {
+    pub fn print(&self) {
+        info!("🧾 Coûts de déploiement • total {:.2} USD", self.total_fiat);
+        for (network, summary) in &self.per_network {
+            println!(
+                "→ {}: {} déploiements • gas {} • {:.6} natif • {:.2} USD",
+                network, summary.deployments, summary.gas_used, summary.native_cost, summary.fiat_cost
+            );
+        }
+        for (contract, summary) in &self.per_contract {
+            println!(
+                "→ {}: {} déploiements • gas {} • {:.2} USD ({} sans prix)",
+                contract, summary.deployments, summary.gas_used, summary.fiat_cost, summary.unpriced
+            );
+        }
+    }
+}
+
+/// Historique des déploiements et de leur coût, persisté sur disque
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeploymentHistory {
+    pub records: Vec<DeploymentCost>,
+    #[serde(skip)]
+    path: PathBuf,
+    /// Registre des chaînes partagé par le démon (nom, monnaie native, testnet)
+    #[serde(skip)]
+    chains: Option<SharedChainRegistry>,
+}
+
+impl DeploymentHistory {
+    pub fn record(&mut self, cost: DeploymentCost) {
+        self.records.push(cost);
+        self.save();
+    }
+
+    pub fn attach_chains(&mut self, chains: SharedChainRegistry) {
+        self.chains = Some(chains);
+    }
+
+    /// Réseau, symbole natif et statut testnet d'une chaîne; valeurs par défaut hors registre
+    pub fn network_of(&self, chain_id: u64) -> (String, String, bool) {
+        match self.chains.as_ref().and_then(|chains| chains.read().by_chain_id(chain_id).cloned()) {
+            Some(chain) => (chain.name, chain.native_currency.symbol, chain.testnet),
+            None => (format!("chain-{}", chain_id), "ETH".to_string(), false),
+        }
+    }
+
+    pub fn cost_report(&self) -> CostReport {
+        let mut report = CostReport::default();
+        for cost in &self.records {
+            report.per_network.entry(cost.network.clone()).or_default().add(cost);
+            report.per_contract.entry(cost.contract.clone()).or_default().add(cost);
+            report.total_fiat += cost.fiat_cost.unwrap_or(0.0);
+        }
+        report
+    }
+
+    /// 💾 Sauvegarde en JSON local
+    pub fn save(&self) {
+        if let Some(dir) = self.path.parent() {
+            let _ = create_dir_all(dir);
+        }
+        if let Ok(file) = File::create(&self.path) {
+            let writer = BufWriter::new(file);
+            let _ = serde_json::to_writer_pretty(writer, &self);
+        }
+    }
+
+    /// 📥 Chargement depuis `path` (historique vide si absent)
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let history: Self = File::open(&path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self { path, ..history }
+    }
+}
+
+/// Historique des coûts de déploiement du système
+pub const DEPLOYMENT_HISTORY_PATH: &str = "aurorae_state/deployment_history.json";
+
+/// Les tests écrivent dans un historique temporaire, jamais dans celui du système
+fn deployment_history_path() -> PathBuf {
+    if cfg!(test) {
+        std::env::temp_dir().join(format!("aurorae-deployments-{}", std::process::id())).join("deployment_history.json")
+    } else {
+        PathBuf::from(DEPLOYMENT_HISTORY_PATH)
+    }
+}
+
+lazy_static! {
+    /// Historique partagé par tous les chemins de déploiement
+    static ref DEPLOYMENT_HISTORY: RwLock<DeploymentHistory> = RwLock::new(DeploymentHistory::open(deployment_history_path()));
+    /// Flux de prix servant à convertir les coûts en USD
+    static ref DEPLOYMENT_PRICES: tokio::sync::Mutex<MarketDataFeed> = tokio::sync::Mutex::new(MarketDataFeed::new());
+}
+
+/// Famille de proxy upgradable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProxyKind {
+    /// `TransparentProxy.sol`: la logique de mise à jour est portée par le proxy
+    Transparent,
+    /// `UUPSProxy.sol`: l'implémentation expose `upgradeTo(address)` (ex: `AuroraiumUpgradeable.sol`)
+    Uups,
+}
+
+impl ProxyKind {
+    /// Contrat de `contracts/` à compiler pour ce proxy
+    pub fn contract_name(&self) -> &'static str {
+        match self {
+            ProxyKind::Transparent => "TransparentProxy",
+            ProxyKind::Uups => "UUPSProxy",
+        }
+    }
+}
+
+/// Options de déploiement d'un proxy
+#[derive(Debug, Clone)]
+pub struct ProxyOptions {
+    pub kind: ProxyKind,
+    /// `ProxyAdmin.sol` déployé par `Deployer::deploy_proxy_admin`: seul habilité à mettre à
+    /// jour le proxy, il porte le timelock on-chain
+    pub admin: Address,
+    /// Délai configuré sur ce `ProxyAdmin` (copie locale: le contrat reste l'arbitre)
+    pub timelock_secs: u64,
+    /// Appel d'initialisation délégué à l'implémentation lors du déploiement
+    pub init_data: Bytes,
+}
+
+/// Version d'implémentation pointée par un proxy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImplementationVersion {
+    pub address: Address,
+    pub contract: String,
+    pub activated_at: String,
+    /// Transaction `upgradeTo` (absente pour l'implémentation initiale)
+    pub upgrade_tx: Option<H256>,
+}
+
+/// Mise à jour programmée, exécutable après l'échéance du timelock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingUpgrade {
+    pub new_implementation: Address,
+    pub scheduled_at: DateTime<Utc>,
+    pub eta: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyRecord {
+    pub proxy: Address,
+    pub kind: ProxyKind,
+    pub chain_id: u64,
+    /// Contrat `ProxyAdmin`
+    pub admin: Address,
+    /// Compte propriétaire du `ProxyAdmin`, seul à pouvoir programmer une mise à jour
+    #[serde(default)]
+    pub owner: Address,
+    /// Délai du timelock du `ProxyAdmin` (copie locale, appliqué on-chain)
+    pub timelock_secs: u64,
+    /// Historique des implémentations, la dernière est active
+    pub implementations: Vec<ImplementationVersion>,
+    pub pending: Option<PendingUpgrade>,
+}
+
+impl ProxyRecord {
+    pub fn current_implementation(&self) -> Option<Address> {
+        self.implementations.last().map(|v| v.address)
+    }
+}
+
+/// Résultat de `Deployer::upgrade_proxy`
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpgradeOutcome {
+    Upgraded { tx_hash: H256 },
+    /// Timelock actif: la mise à jour pourra être exécutée à partir de `eta`
+    Scheduled { eta: DateTime<Utc> },
+}
+
+/// `ProxyAdmin` déployé pour un contrat, consigné avant le déploiement de son proxy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdminRecord {
+    pub admin: Address,
+    pub owner: Address,
+    pub timelock_secs: u64,
+    pub deployed_at: String,
+}
+
+/// Proxies déployés par l'entité et historique de leurs implémentations, persisté sur disque
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProxyRegistry {
+    pub proxies: HashMap<String, ProxyRecord>,
+    /// `ProxyAdmin` par chaîne et par contrat: une reprise après échec le réutilise
+    #[serde(default)]
+    pub admins: HashMap<String, AdminRecord>,
+}
+
+impl ProxyRegistry {
+    fn key(chain_id: u64, proxy: Address) -> String {
+        format!("{}:{:?}", chain_id, proxy)
+    }
+
+    fn admin_key(chain_id: u64, contract: &str) -> String {
+        format!("{}:{}", chain_id, contract)
+    }
+
+    pub fn admin_for(&self, chain_id: u64, contract: &str) -> Option<&AdminRecord> {
+        self.admins.get(&Self::admin_key(chain_id, contract))
+    }
+
+    pub fn record_admin(&mut self, chain_id: u64, contract: &str, record: AdminRecord) {
+        self.admins.insert(Self::admin_key(chain_id, contract), record);
+    }
+
+    pub fn get(&self, chain_id: u64, proxy: Address) -> Option<&ProxyRecord> {
+        self.proxies.get(&Self::key(chain_id, proxy))
+    }
+
+    fn get_mut(&mut self, chain_id: u64, proxy: Address) -> Option<&mut ProxyRecord> {
+        self.proxies.get_mut(&Self::key(chain_id, proxy))
+    }
+
+    pub fn insert(&mut self, record: ProxyRecord) {
+        self.proxies.insert(Self::key(record.chain_id, record.proxy), record);
+    }
+
+    /// Proxy déjà déployé sur cette chaîne pour le contrat `contract` (première implémentation)
+    pub fn find(&self, chain_id: u64, contract: &str) -> Option<&ProxyRecord> {
+        self.proxies.values().find(|record| {
+            record.chain_id == chain_id
+                && record.implementations.first().map(|v| v.contract == contract).unwrap_or(false)
+        })
+    }
+
+    /// 💾 Sauvegarde en JSON local
+    pub fn save(&self) {
+        let dir = Path::new("aurorae_state");
+        if create_dir_all(dir).is_ok() {
+            if let Ok(file) = File::create(dir.join("proxies.json")) {
+                let writer = BufWriter::new(file);
+                if serde_json::to_writer_pretty(writer, &self).is_ok() {
+                    info!("💾 Registre des proxies sauvegardé.");
+                }
+            }
+        }
+    }
+
+    /// 📥 Chargement depuis disque (registre vide si absent)
+    pub fn load() -> Self {
+        File::open("aurorae_state/proxies.json")
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default()
+    }
+}
 
 pub struct Deployer;
 
 impl Deployer {
+    #[tracing::instrument(name = "deployment", skip_all, fields(chain_id = backend.chain_id(), abi = abi_path))]
     pub async fn deploy_contract(
-        provider: HttpProvider,
-        private_key: &str,
+        budget: &SharedBudget,
+        backend: &dyn ChainBackend,
+        oracle: &GasOracle,
         abi_path: &str,
         bytecode_path: &str,
     ) -> Result<Address, String> {
@@ -33,35 +686,800 @@ impl Deployer {
 
         // ✅ Parsing du bytecode → Bytes (type ethers)
         let parsed_bytecode = bytecode
-            .parse::<ethers::types::Bytes>()
+            .trim()
+            .parse::<Bytes>()
             .map_err(|e| format!("Bytecode invalide: {}", e))?;
-        
-        // 🔐 Construction du wallet
-        let wallet: LocalWallet = private_key
-            .parse::<Wallet<SigningKey>>()
-            .map_err(|e| format!("Clé privée invalide: {}", e))?
-            .with_chain_id(1u64); // à adapter selon le réseau
-
-        let client = SignerMiddleware::new(provider.clone(), wallet);
-        let client = Arc::new(client);
-
-        // 🏗️ Factory pour déploiement
-        let factory = ContractFactory::new(parsed_abi, parsed_bytecode, client.clone());
-
-        // 🚀 Déploiement du contrat
-        let contract = factory
-            .deploy(())
-            .map_err(|e| format!("Erreur création déploiement: {}", e))?
-            .confirmations(3_usize)  // Corrected: explicitly using usize instead of i32
-            .send()
+
+        let contract = Path::new(abi_path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| abi_path.to_string());
+        let (address, _) = Self::deploy_bytecode(budget, backend, oracle, &contract, parsed_abi, parsed_bytecode).await?;
+        Ok(address)
+    }
+
+    /// Autorisation budgétaire, déploiement et attente des confirmations
+    async fn deploy_bytecode(
+        budget: &SharedBudget,
+        backend: &dyn ChainBackend,
+        oracle: &GasOracle,
+        contract: &str,
+        parsed_abi: Abi,
+        parsed_bytecode: Bytes,
+    ) -> Result<(Address, H256), String> {
+        // 💸 Estimation du coût et autorisation budgétaire avant tout envoi
+        let gas = backend.estimate_deploy_gas(&parsed_abi, &parsed_bytecode).await?;
+        let fees = oracle.estimate_fees(backend).await?;
+        // Le budget réserve le coût maximal: le coût réel ne peut pas le dépasser
+        let estimated_cost = fees.max_cost(gas);
+
+        match budget.write().request_gas_spend("deployer", estimated_cost) {
+            SpendDecision::Approved => {}
+            SpendDecision::RequiresApproval(id) => {
+                return Err(format!("Déploiement en attente d'approbation budgétaire ({})", id));
+            }
+            SpendDecision::Denied(reason) => {
+                return Err(format!("Déploiement refusé par le budget: {}", reason));
+            }
+        }
+
+        // 🚀 Déploiement du contrat puis attente des confirmations
+        let chain_id = backend.chain_id();
+        let (address, tx_hash) = backend.deploy(parsed_abi, parsed_bytecode, &fees)
             .await
-            .map_err(|e| format!("Erreur déploiement: {}", e))?;
+            .map_err(|e| Self::deployment_failed(contract, chain_id, e))?;
+        let receipt = backend.wait_for_receipt(tx_hash, DEPLOY_CONFIRMATIONS)
+            .await
+            .map_err(|e| Self::deployment_failed(contract, chain_id, e))?;
+        if !receipt.success {
+            return Err(Self::deployment_failed(contract, chain_id, format!("Déploiement échoué on-chain: {:?}", tx_hash)));
+        }
+
+        info!(
+            "✅ Contrat déployé à l'adresse : {:?} (chain-id {}, backend {})",
+            address,
+            backend.chain_id(),
+            backend.kind()
+        );
+        Self::record_cost(contract, backend, address, &receipt).await;
+
+        Ok((address, tx_hash))
+    }
+
+    /// Consigne l'échec au journal de sécurité et retourne l'erreur inchangée
+    fn deployment_failed(contract: &str, chain_id: u64, error: String) -> String {
+        log_security_event(SecurityEvent::DeploymentFailed {
+            contract: contract.to_string(),
+            chain_id,
+            error: error.clone(),
+        });
+        error
+    }
+
+    /// Registre des chaînes du démon, utilisé pour nommer et valoriser les déploiements
+    pub fn attach_chain_registry(chains: SharedChainRegistry) {
+        DEPLOYMENT_HISTORY.write().attach_chains(chains);
+    }
+
+    /// Enregistre le gas consommé, le prix effectif et l'équivalent USD d'un déploiement
+    async fn record_cost(contract: &str, backend: &dyn ChainBackend, address: Address, receipt: &TxReceipt) {
+        let chain_id = backend.chain_id();
+        let (network, native_symbol, testnet) = DEPLOYMENT_HISTORY.read().network_of(chain_id);
+
+        let native_cost = wei_to_native(receipt.gas_used * receipt.effective_gas_price);
+        // Les jetons de testnet n'ont pas de valeur de marché
+        let usd_price = if testnet {
+            Some(0.0)
+        } else {
+            DEPLOYMENT_PRICES.lock().await
+                .candles(&native_symbol, "1h", 1)
+                .await
+                .ok()
+                .and_then(|candles| candles.last().map(|c| c.close))
+        };
+        let fiat_cost = usd_price.map(|price| native_cost * price);
+
+        info!(
+            "⛽ {} sur {}: gas {} à {} wei • {:.6} {} • {}",
+            contract, network, receipt.gas_used, receipt.effective_gas_price, native_cost, native_symbol,
+            fiat_cost.map(|f| format!("{:.2} USD", f)).unwrap_or_else(|| "prix indisponible".to_string())
+        );
+        DEPLOYMENT_HISTORY.write().record(DeploymentCost {
+            contract: contract.to_string(),
+            network,
+            chain_id,
+            address,
+            tx_hash: receipt.tx_hash,
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+            native_cost,
+            native_symbol,
+            usd_price,
+            fiat_cost,
+            deployed_at: Utc::now().to_rfc3339(),
+        });
+    }
+
+    /// Dépense de déploiement agrégée par réseau et par contrat
+    pub fn cost_report() -> CostReport {
+        DEPLOYMENT_HISTORY.read().cost_report()
+    }
+
+    /// Déploie sur une chaîne du registre: vérifie la correspondance du backend,
+    /// applique la politique de signature et journalise le lien vers l'explorateur.
+    /// Avec un sel, le déploiement passe par le proxy CREATE2.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deploy_on_chain(
+        chain: &ChainConfig,
+        signer: &dyn Signer,
+        policy: &SigningPolicy,
+        budget: &SharedBudget,
+        tx: &SharedTxManager,
+        oracle: &GasOracle,
+        artifact: &ContractArtifact,
+        args: &[Token],
+        create2_salt: Option<&str>,
+    ) -> Result<Address, String> {
+        let backend = tx.backend().as_ref();
+        if chain.chain_id != Some(backend.chain_id()) {
+            return Err(format!(
+                "Backend (chain-id {}) incompatible avec la chaîne {} ({:?})",
+                backend.chain_id(), chain.name, chain.chain_id
+            ));
+        }
+
+        if backend.sender() != signer.address() {
+            return Err(format!("Signataire {} différent du compte du backend", signer.label()));
+        }
+        policy.check_deployment(signer, chain.testnet)?;
+
+        let address = match create2_salt {
+            Some(salt) => {
+                let mut registry = Create2Registry::load();
+                Self::deploy_deterministic(&mut registry, budget, tx, oracle, artifact, args, salt).await?
+            }
+            None => {
+                let (abi, init_code) = Self::encode_deployment(artifact, args)?;
+                Self::deploy_bytecode(budget, backend, oracle, &artifact.name, abi, init_code).await?.0
+            }
+        };
+        if let Some(url) = chain.explorer_address_url(&format!("{:?}", address)) {
+            info!("🔎 {} sur {}: {}", artifact.name, chain.name, url);
+        }
+        Ok(address)
+    }
+
+    /// Compile (ou réutilise) le contrat `name` puis le déploie
+    #[tracing::instrument(name = "build_and_deploy", skip_all, fields(contract = name))]
+    pub async fn build_and_deploy(
+        builder: &ContractBuilder,
+        name: &str,
+        budget: &SharedBudget,
+        backend: &dyn ChainBackend,
+        oracle: &GasOracle,
+    ) -> Result<(Address, ContractArtifact), String> {
+        let artifact = builder.build(name)?;
+        let address = Self::deploy_contract(budget, backend, oracle, &artifact.abi_path, &artifact.bytecode_path).await?;
+        Ok((address, artifact))
+    }
+
+    /// Déploie un artefact compilé, avec les arguments de son constructeur, sur une chaîne du
+    /// registre et, si demandé, le fait vérifier par les explorateurs
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deploy_artifact(
+        chain: &ChainConfig,
+        signer: &dyn Signer,
+        policy: &SigningPolicy,
+        budget: &SharedBudget,
+        tx: &SharedTxManager,
+        oracle: &GasOracle,
+        artifact: &ContractArtifact,
+        args: &[Token],
+        options: &DeploymentOptions,
+    ) -> Result<DeploymentResult, String> {
+        let address = Self::deploy_on_chain(
+            chain, signer, policy, budget, tx, oracle, artifact, args, options.create2_salt.as_deref(),
+        ).await?;
+
+        let verification = if options.verify_code {
+            // Les explorateurs recompilent puis comparent: ils attendent les arguments encodés
+            ContractVerifier::default().verify_all(chain, address, artifact, &hex::encode(encode(args))).await
+        } else {
+            Vec::new()
+        };
+
+        Ok(DeploymentResult {
+            contract: artifact.name.clone(),
+            address,
+            chain: chain.name.clone(),
+            chain_id: tx.chain_id(),
+            deployed_at: Utc::now().to_rfc3339(),
+            verification,
+        })
+    }
+
+    /// ABI de l'artefact et code d'initialisation incluant les arguments du constructeur.
+    /// Les arguments sont encodés à la suite du bytecode: l'ABI retournée n'a plus de constructeur.
+    pub fn encode_deployment(artifact: &ContractArtifact, args: &[Token]) -> Result<(Abi, Bytes), String> {
+        let abi_content = fs::read_to_string(&artifact.abi_path)
+            .map_err(|e| format!("Erreur lecture ABI: {}", e))?;
+        let mut abi: Abi = Abi::load(abi_content.as_bytes())
+            .map_err(|e| format!("ABI invalide: {}", e))?;
+        let bytecode = Self::init_code(artifact)?;
+
+        let init_code = match abi.constructor.take() {
+            Some(constructor) => constructor.encode_input(bytecode.to_vec(), args)
+                .map(Bytes::from)
+                .map_err(|e| format!("{}: arguments du constructeur invalides: {}", artifact.name, e))?,
+            None if args.is_empty() => bytecode,
+            None => return Err(format!("{}: pas de constructeur, arguments inattendus", artifact.name)),
+        };
+        Ok((abi, init_code))
+    }
+
+    /// Déploie un artefact avec les arguments de son constructeur
+    pub async fn deploy_with_args(
+        budget: &SharedBudget,
+        backend: &dyn ChainBackend,
+        oracle: &GasOracle,
+        artifact: &ContractArtifact,
+        args: Vec<Token>,
+    ) -> Result<(Address, H256), String> {
+        let (abi, init_code) = Self::encode_deployment(artifact, &args)?;
+        Self::deploy_bytecode(budget, backend, oracle, &artifact.name, abi, init_code).await
+    }
+
+    /// Sel CREATE2 dérivé d'un libellé lisible (ex: « aurorae/Auroraium/v1 »)
+    pub fn salt_from_label(label: &str) -> [u8; 32] {
+        keccak256(label.as_bytes())
+    }
+
+    fn init_code(artifact: &ContractArtifact) -> Result<Bytes, String> {
+        fs::read_to_string(&artifact.bytecode_path)
+            .map_err(|e| format!("Erreur lecture bytecode: {}", e))?
+            .trim()
+            .parse::<Bytes>()
+            .map_err(|e| format!("Bytecode invalide: {}", e))
+    }
+
+    /// Adresse à laquelle `contract`, construit avec `args`, sera déployé via le proxy CREATE2
+    /// avec ce sel; identique sur toutes les chaînes où le proxy existe
+    pub fn predict_address(contract: &ContractArtifact, args: &[Token], salt: &str) -> Result<Address, String> {
+        let (_, init_code) = Self::encode_deployment(contract, args)?;
+        Ok(Self::create2_address(&init_code, salt))
+    }
+
+    fn create2_address(init_code: &Bytes, salt: &str) -> Address {
+        get_create2_address(deterministic_deployer(), Self::salt_from_label(salt).to_vec(), init_code.to_vec())
+    }
+
+    /// Déploiement CREATE2 idempotent: réutilise le contrat s'il est déjà présent à l'adresse prévue.
+    /// Les arguments du constructeur font partie du code d'initialisation, donc de l'adresse.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deploy_deterministic(
+        registry: &mut Create2Registry,
+        budget: &SharedBudget,
+        tx: &SharedTxManager,
+        oracle: &GasOracle,
+        contract: &ContractArtifact,
+        args: &[Token],
+        salt: &str,
+    ) -> Result<Address, String> {
+        let backend = tx.backend().as_ref();
+        let (abi, init_code) = Self::encode_deployment(contract, args)?;
+        let predicted = Self::create2_address(&init_code, salt);
+        let chain_id = backend.chain_id();
+
+        let already_deployed = !backend.get_code(predicted).await?.is_empty();
+        if !already_deployed {
+            let gas = backend.estimate_deploy_gas(&abi, &init_code).await? + U256::from(CREATE2_CALL_OVERHEAD);
+            let fees = oracle.estimate_fees(backend).await?;
 
-        println!(
-            "[AURORAE++] ✅ Contrat déployé à l'adresse : {:?}",
-            contract.address()
+            match budget.write().request_gas_spend("deployer", fees.max_cost(gas)) {
+                SpendDecision::Approved => {}
+                SpendDecision::RequiresApproval(id) => {
+                    return Err(format!("Déploiement CREATE2 en attente d'approbation budgétaire ({})", id));
+                }
+                SpendDecision::Denied(reason) => {
+                    return Err(format!("Déploiement CREATE2 refusé par le budget: {}", reason));
+                }
+            }
+
+            let mut payload = Self::salt_from_label(salt).to_vec();
+            payload.extend_from_slice(&init_code);
+            let tx_hash = tx.submit_with_fees(deterministic_deployer(), U256::zero(), Some(Bytes::from(payload)), &fees)
+                .await
+                .map_err(|e| Self::deployment_failed(&contract.name, chain_id, e))?;
+            let receipt = backend.wait_for_receipt(tx_hash, DEPLOY_CONFIRMATIONS)
+                .await
+                .map_err(|e| Self::deployment_failed(&contract.name, chain_id, e))?;
+            if !receipt.success || backend.get_code(predicted).await?.is_empty() {
+                return Err(Self::deployment_failed(
+                    &contract.name,
+                    chain_id,
+                    format!("Déploiement CREATE2 échoué pour {} ({:?})", contract.name, tx_hash),
+                ));
+            }
+            Self::record_cost(&contract.name, backend, predicted, &receipt).await;
+            info!("🧭 {} déployé par CREATE2 à {:?} (chain-id {})", contract.name, predicted, chain_id);
+        } else {
+            info!("🧭 {} déjà présent à {:?} (chain-id {})", contract.name, predicted, chain_id);
+        }
+
+        registry.insert(Create2Entry {
+            chain_id,
+            salt: salt.to_string(),
+            contract: contract.name.clone(),
+            address: predicted,
+            init_code_hash: hex::encode(keccak256(&init_code)),
+            deployed_at: Utc::now().to_rfc3339(),
+        });
+        registry.save();
+        Ok(predicted)
+    }
+
+    /// Déploie un `ProxyAdmin` appartenant au compte du gestionnaire, avec un timelock on-chain
+    /// de `timelock_secs` entre la programmation et l'exécution d'une mise à jour
+    pub async fn deploy_proxy_admin(
+        budget: &SharedBudget,
+        tx: &SharedTxManager,
+        oracle: &GasOracle,
+        artifact: &ContractArtifact,
+        timelock_secs: u64,
+    ) -> Result<Address, String> {
+        let args = vec![Token::Address(tx.account()), Token::Uint(U256::from(timelock_secs))];
+        let (address, _) = Self::deploy_with_args(budget, tx.backend().as_ref(), oracle, artifact, args).await?;
+        info!("🛡️ ProxyAdmin {:?} (propriétaire {:?}, timelock {} s)", address, tx.account(), timelock_secs);
+        Ok(address)
+    }
+
+    /// `ProxyAdmin` de `contract` sur la chaîne de `tx`: celui déjà consigné s'il a du code
+    /// on-chain, le même propriétaire et le même timelock, sinon un nouveau, consigné dans `registry`
+    pub async fn proxy_admin_for(
+        registry: &mut ProxyRegistry,
+        budget: &SharedBudget,
+        tx: &SharedTxManager,
+        oracle: &GasOracle,
+        artifact: &ContractArtifact,
+        contract: &str,
+        timelock_secs: u64,
+    ) -> Result<Address, String> {
+        let chain_id = tx.chain_id();
+        if let Some(record) = registry.admin_for(chain_id, contract).cloned() {
+            if record.owner == tx.account() && record.timelock_secs == timelock_secs
+                && !tx.backend().get_code(record.admin).await?.is_empty()
+            {
+                info!("🛡️ ProxyAdmin {:?} de {} réutilisé (chain-id {})", record.admin, contract, chain_id);
+                return Ok(record.admin);
+            }
+            warn!("⚠️ ProxyAdmin {:?} de {} inutilisable: redéploiement", record.admin, contract);
+        }
+        let admin = Self::deploy_proxy_admin(budget, tx, oracle, artifact, timelock_secs).await?;
+        registry.record_admin(chain_id, contract, AdminRecord {
+            admin,
+            owner: tx.account(),
+            timelock_secs,
+            deployed_at: Utc::now().to_rfc3339(),
+        });
+        Ok(admin)
+    }
+
+    /// Déploie le proxy pointant vers une implémentation déjà déployée et enregistre le couple.
+    /// L'administrateur est le `ProxyAdmin`, jamais le compte opérateur: un proxy transparent
+    /// refuse à son administrateur tout accès à l'implémentation.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deploy_proxy(
+        registry: &mut ProxyRegistry,
+        budget: &SharedBudget,
+        tx: &SharedTxManager,
+        oracle: &GasOracle,
+        implementation: &ContractArtifact,
+        implementation_address: Address,
+        proxy: &ContractArtifact,
+        options: &ProxyOptions,
+    ) -> Result<Address, String> {
+        if options.admin == tx.account() {
+            return Err("L'administrateur du proxy doit être un ProxyAdmin, pas le compte opérateur".to_string());
+        }
+        let backend = tx.backend().as_ref();
+        if backend.get_code(options.admin).await?.is_empty() {
+            return Err(format!("Aucun ProxyAdmin à l'adresse {:?}", options.admin));
+        }
+        if backend.get_code(implementation_address).await?.is_empty() {
+            return Err(format!("Aucun code à l'adresse {:?}", implementation_address));
+        }
+
+        let args = match options.kind {
+            ProxyKind::Transparent => vec![
+                Token::Address(implementation_address),
+                Token::Address(options.admin),
+                Token::Bytes(options.init_data.to_vec()),
+            ],
+            ProxyKind::Uups => vec![
+                Token::Address(implementation_address),
+                Token::Bytes(options.init_data.to_vec()),
+            ],
+        };
+        let (proxy_address, _) = Self::deploy_with_args(budget, backend, oracle, proxy, args).await?;
+
+        registry.insert(ProxyRecord {
+            proxy: proxy_address,
+            kind: options.kind,
+            chain_id: backend.chain_id(),
+            admin: options.admin,
+            owner: tx.account(),
+            timelock_secs: options.timelock_secs,
+            implementations: vec![ImplementationVersion {
+                address: implementation_address,
+                contract: implementation.name.clone(),
+                activated_at: Utc::now().to_rfc3339(),
+                upgrade_tx: None,
+            }],
+            pending: None,
+        });
+        registry.save();
+
+        info!(
+            "🪞 Proxy {:?} {:?} → implémentation {} {:?} (admin {:?})",
+            options.kind, proxy_address, implementation.name, implementation_address, options.admin
         );
+        Ok(proxy_address)
+    }
+
+    /// Déploie `implementation` derrière un proxy UUPS, ou retrouve le proxy déjà enregistré
+    /// pour cette chaîne. L'implémentation passe par `deploy_artifact` (CREATE2, vérification),
+    /// puis un `ProxyAdmin` à timelock est créé et le proxy est initialisé par
+    /// `initialize(init_args..., proxyAdmin)`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deploy_upgradeable(
+        chain: &ChainConfig,
+        signer: &dyn Signer,
+        policy: &SigningPolicy,
+        budget: &SharedBudget,
+        tx: &SharedTxManager,
+        oracle: &GasOracle,
+        builder: &ContractBuilder,
+        implementation: &str,
+        init_args: Vec<Token>,
+        timelock_secs: u64,
+        options: &DeploymentOptions,
+    ) -> Result<Address, String> {
+        let mut registry = ProxyRegistry::load();
+        if let Some(record) = registry.find(tx.chain_id(), implementation) {
+            info!("🪞 {} déjà servi par le proxy {:?} (chain-id {})", implementation, record.proxy, record.chain_id);
+            return Ok(record.proxy);
+        }
+
+        let implementation_artifact = builder.build(implementation)?;
+        let proxy_artifact = builder.build(ProxyKind::Uups.contract_name())?;
+        let admin_artifact = builder.build("ProxyAdmin")?;
+
+        let deployed = Self::deploy_artifact(chain, signer, policy, budget, tx, oracle, &implementation_artifact, &[], options).await?;
+        if options.verify_code && !deployed.is_verified() {
+            warn!("⚠️ Contrat {} non vérifié: {:?}", deployed.contract, deployed.verification);
+        }
+        let admin = Self::proxy_admin_for(&mut registry, budget, tx, oracle, &admin_artifact, implementation, timelock_secs).await?;
+        // Consigné avant le proxy: une reprise (approbation, échec du proxy) ne redéploie pas l'administrateur
+        registry.save();
+
+        let (abi, _) = Self::encode_deployment(&implementation_artifact, &[])?;
+        let mut args = init_args;
+        args.push(Token::Address(admin));
+        let init_data = abi.function("initialize")
+            .and_then(|f| f.encode_input(&args))
+            .map_err(|e| format!("{}: initialisation invalide: {}", implementation, e))?;
+
+        Self::deploy_proxy(
+            &mut registry,
+            budget,
+            tx,
+            oracle,
+            &implementation_artifact,
+            deployed.address,
+            &proxy_artifact,
+            &ProxyOptions { kind: ProxyKind::Uups, admin, timelock_secs, init_data: Bytes::from(init_data) },
+        ).await
+    }
+
+    /// Appel `ProxyAdmin.<signature>(proxy, ...)` envoyé par le propriétaire, reçu attendu
+    async fn call_proxy_admin(
+        tx: &SharedTxManager,
+        record: &ProxyRecord,
+        signature: &str,
+        args: &[Token],
+    ) -> Result<H256, String> {
+        let mut calldata = id(signature).to_vec();
+        calldata.extend(encode(args));
+        let tx_hash = tx.submit(record.admin, U256::zero(), Some(Bytes::from(calldata))).await?;
+        let receipt = tx.backend().wait_for_receipt(tx_hash, DEPLOY_CONFIRMATIONS).await?;
+        if !receipt.success {
+            return Err(format!("ProxyAdmin.{} échoué on-chain: {:?}", signature, tx_hash));
+        }
+        Ok(tx_hash)
+    }
+
+    /// Fait pointer `proxy` vers `new_implementation` via son `ProxyAdmin`. Le premier appel
+    /// programme la mise à jour on-chain; un appel après l'échéance du timelock l'exécute.
+    /// Sans timelock, les deux étapes s'enchaînent.
+    pub async fn upgrade_proxy(
+        registry: &mut ProxyRegistry,
+        tx: &SharedTxManager,
+        proxy: Address,
+        new_implementation: Address,
+        contract: &str,
+    ) -> Result<UpgradeOutcome, String> {
+        let backend = tx.backend().as_ref();
+        let chain_id = backend.chain_id();
+        let record = registry.get_mut(chain_id, proxy)
+            .ok_or_else(|| format!("Proxy inconnu: {:?} (chain-id {})", proxy, chain_id))?;
+
+        if tx.account() != record.owner {
+            return Err(format!("Le compte {:?} n'est pas propriétaire du ProxyAdmin {:?}", tx.account(), record.admin));
+        }
+        if record.current_implementation() == Some(new_implementation) {
+            return Err(format!("{:?} est déjà l'implémentation active", new_implementation));
+        }
+        if backend.get_code(new_implementation).await?.is_empty() {
+            return Err(format!("Aucun code à l'adresse {:?}", new_implementation));
+        }
+
+        let upgrade_args = [Token::Address(proxy), Token::Address(new_implementation)];
+        let now = Utc::now();
+        let scheduled = matches!(&record.pending, Some(pending) if pending.new_implementation == new_implementation);
+        if !scheduled {
+            Self::call_proxy_admin(tx, record, "schedule(address,address)", &upgrade_args).await?;
+            let eta = now + Duration::seconds(record.timelock_secs as i64);
+            record.pending = Some(PendingUpgrade { new_implementation, scheduled_at: now, eta });
+            let timelock_secs = record.timelock_secs;
+            registry.save();
+            info!("⏳ Mise à jour du proxy {:?} programmée pour {}", proxy, eta.to_rfc3339());
+            if timelock_secs > 0 {
+                return Ok(UpgradeOutcome::Scheduled { eta });
+            }
+        }
+
+        // Le registre vient d'être sauvegardé: l'entrée existe toujours
+        let record = registry.get_mut(chain_id, proxy).expect("proxy enregistré");
+        if let Some(pending) = &record.pending {
+            // Copie locale de l'échéance: évite un envoi voué à l'échec, le contrat reste l'arbitre
+            if now < pending.eta {
+                return Err(format!("Mise à jour sous timelock jusqu'au {}", pending.eta.to_rfc3339()));
+            }
+        }
+        let tx_hash = Self::call_proxy_admin(tx, record, "execute(address,address)", &upgrade_args).await?;
+
+        record.pending = None;
+        record.implementations.push(ImplementationVersion {
+            address: new_implementation,
+            contract: contract.to_string(),
+            activated_at: now.to_rfc3339(),
+            upgrade_tx: Some(tx_hash),
+        });
+        let version = record.implementations.len();
+        registry.save();
+
+        info!("🔁 Proxy {:?} mis à jour vers {:?} (version {})", proxy, new_implementation, version);
+        Ok(UpgradeOutcome::Upgraded { tx_hash })
+    }
+
+    /// Annule on-chain une mise à jour programmée
+    pub async fn cancel_upgrade(registry: &mut ProxyRegistry, tx: &SharedTxManager, proxy: Address) -> Result<(), String> {
+        let chain_id = tx.chain_id();
+        let record = registry.get_mut(chain_id, proxy)
+            .ok_or_else(|| format!("Proxy inconnu: {:?} (chain-id {})", proxy, chain_id))?;
+        if record.pending.is_none() {
+            return Err("Aucune mise à jour programmée".to_string());
+        }
+        Self::call_proxy_admin(tx, record, "cancel(address)", &[Token::Address(proxy)]).await?;
+        record.pending = None;
+        registry.save();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cost(contract: &str, network: &str, gas: u64, fiat_cost: Option<f64>) -> DeploymentCost {
+        DeploymentCost {
+            contract: contract.to_string(),
+            network: network.to_string(),
+            chain_id: 1,
+            address: Address::zero(),
+            tx_hash: H256::zero(),
+            gas_used: U256::from(gas),
+            effective_gas_price: U256::from(1_000_000_000u64),
+            native_cost: 0.001,
+            native_symbol: "ETH".to_string(),
+            usd_price: fiat_cost.map(|_| 3_000.0),
+            fiat_cost,
+            deployed_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn foundry_builds_report_the_solc_version_from_metadata() {
+        let metadata = r#"{"compiler":{"version":"0.8.20+commit.a1b79de6"},"language":"Solidity"}"#;
+        assert_eq!(solc_version_from_metadata(metadata).as_deref(), Some("0.8.20+commit.a1b79de6"));
+        assert_eq!(solc_version_from_metadata("forge 0.2.0 (1b2c3d4 2024-05-01T00:00:00Z)"), None);
+        assert_eq!(solc_version_from_metadata(r#"{"language":"Solidity"}"#), None);
+    }
+
+    /// Artefact Auroraium minimal: constructeur (uint256 initialSupply, address founderAddress)
+    fn auroraium_artifact(dir: &Path) -> ContractArtifact {
+        fs::create_dir_all(dir).unwrap();
+        let abi_path = dir.join("Auroraium.abi");
+        let bytecode_path = dir.join("Auroraium.bin");
+        fs::write(&abi_path, r#"[{"type":"constructor","stateMutability":"nonpayable","inputs":[{"name":"initialSupply","type":"uint256"},{"name":"founderAddress","type":"address"}]}]"#).unwrap();
+        fs::write(&bytecode_path, "0x6080").unwrap();
+        ContractArtifact {
+            name: "Auroraium".to_string(),
+            abi_path: abi_path.to_string_lossy().to_string(),
+            bytecode_path: bytecode_path.to_string_lossy().to_string(),
+            metadata: CompilerMetadata {
+                compiler: "solc".to_string(),
+                version: "0.8.20".to_string(),
+                optimizer_runs: 200,
+                source_hash: String::new(),
+                compiled_at: Utc::now().to_rfc3339(),
+            },
+            source_path: String::new(),
+            solc_metadata_path: None,
+        }
+    }
+
+    #[test]
+    fn constructor_arguments_are_appended_to_the_init_code() {
+        let dir = std::env::temp_dir().join(format!("aurorae-deployer-{}", uuid::Uuid::new_v4()));
+        let artifact = auroraium_artifact(&dir);
+
+        let founder = Address::repeat_byte(0x15);
+        let (abi, init_code) = Deployer::encode_deployment(&artifact, &[Token::Uint(U256::from(1_000u64)), Token::Address(founder)]).unwrap();
+        assert!(abi.constructor.is_none());
+        assert_eq!(init_code.len(), 2 + 64);
+        assert_eq!(&init_code[..2], &[0x60, 0x80]);
+        assert_eq!(U256::from_big_endian(&init_code[2..34]), U256::from(1_000u64));
+        assert_eq!(&init_code[34 + 12..], founder.as_bytes());
+        assert!(Deployer::encode_deployment(&artifact, &[]).is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn create2_prediction_includes_the_constructor_arguments() {
+        let dir = std::env::temp_dir().join(format!("aurorae-deployer-{}", uuid::Uuid::new_v4()));
+        let artifact = auroraium_artifact(&dir);
+        let args = [Token::Uint(U256::from(1_000u64)), Token::Address(Address::repeat_byte(0x15))];
+
+        let predicted = Deployer::predict_address(&artifact, &args, "aurorae/Auroraium/v1").unwrap();
+        let (_, init_code) = Deployer::encode_deployment(&artifact, &args).unwrap();
+        let salt = Deployer::salt_from_label("aurorae/Auroraium/v1");
+        assert_eq!(predicted, get_create2_address(deterministic_deployer(), salt.to_vec(), init_code.to_vec()));
+
+        let other_founder = [Token::Uint(U256::from(1_000u64)), Token::Address(Address::repeat_byte(0x16))];
+        assert_ne!(predicted, Deployer::predict_address(&artifact, &other_founder, "aurorae/Auroraium/v1").unwrap());
+        assert!(Deployer::predict_address(&artifact, &[], "aurorae/Auroraium/v1").is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn upgrades_are_scheduled_on_the_proxy_admin_by_its_owner() {
+        use crate::blockchain_core::{SimulatedBackend, TransactionManager};
+        use std::sync::Arc;
+
+        let operator = Address::repeat_byte(0x1);
+        let backend = Arc::new(SimulatedBackend::new(31337, operator, U256::exp10(20)));
+        let fees = GasOracle::default().estimate_fees(backend.as_ref()).await.unwrap();
+        let abi = Abi::load("[]".as_bytes()).unwrap();
+        let (implementation, _) = backend.deploy(abi, Bytes::from(vec![0x60, 0x80]), &fees).await.unwrap();
+
+        let proxy = Address::repeat_byte(0x70);
+        let mut registry = ProxyRegistry::default();
+        registry.insert(ProxyRecord {
+            proxy,
+            kind: ProxyKind::Uups,
+            chain_id: 31337,
+            admin: Address::repeat_byte(0x71),
+            owner: operator,
+            timelock_secs: 3_600,
+            implementations: vec![ImplementationVersion {
+                address: Address::repeat_byte(0x72),
+                contract: "AuroraiumUpgradeable".to_string(),
+                activated_at: Utc::now().to_rfc3339(),
+                upgrade_tx: None,
+            }],
+            pending: None,
+        });
+
+        let outsider = TransactionManager::new(Arc::new(SimulatedBackend::new(31337, Address::repeat_byte(0x2), U256::exp10(20)))).shared();
+        let refused = Deployer::upgrade_proxy(&mut registry, &outsider, proxy, implementation, "AuroraiumUpgradeable").await;
+        assert!(refused.unwrap_err().contains("propriétaire"));
+
+        let tx = TransactionManager::new(backend.clone()).shared();
+        let outcome = Deployer::upgrade_proxy(&mut registry, &tx, proxy, implementation, "AuroraiumUpgradeable").await.unwrap();
+        assert!(matches!(outcome, UpgradeOutcome::Scheduled { .. }));
+        // Déploiement de l'implémentation puis `schedule` envoyé au ProxyAdmin
+        assert_eq!(backend.pending_nonce(operator).await.unwrap(), 2);
+
+        let early = Deployer::upgrade_proxy(&mut registry, &tx, proxy, implementation, "AuroraiumUpgradeable").await;
+        assert!(early.unwrap_err().contains("timelock"));
+        assert_eq!(registry.get(31337, proxy).unwrap().implementations.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn recorded_proxy_admin_is_reused_on_retry() {
+        use crate::blockchain_core::{SimulatedBackend, TransactionManager};
+        use std::sync::Arc;
+
+        let operator = Address::repeat_byte(0x1);
+        let backend = Arc::new(SimulatedBackend::new(31337, operator, U256::exp10(20)));
+        let fees = GasOracle::default().estimate_fees(backend.as_ref()).await.unwrap();
+        let abi = Abi::load("[]".as_bytes()).unwrap();
+        let (admin, _) = backend.deploy(abi, Bytes::from(vec![0x60, 0x80]), &fees).await.unwrap();
+
+        let mut registry = ProxyRegistry::default();
+        registry.record_admin(31337, "AuroraiumUpgradeable", AdminRecord {
+            admin,
+            owner: operator,
+            timelock_secs: 3_600,
+            deployed_at: Utc::now().to_rfc3339(),
+        });
+        let budget = crate::economy::BudgetEngine::new(0.0).shared();
+        let dir = std::env::temp_dir().join(format!("aurorae-deployer-{}", uuid::Uuid::new_v4()));
+        let artifact = auroraium_artifact(&dir);
+        let tx = TransactionManager::new(backend.clone()).shared();
+
+        let reused = Deployer::proxy_admin_for(&mut registry, &budget, &tx, &GasOracle::default(), &artifact, "AuroraiumUpgradeable", 3_600)
+            .await
+            .unwrap();
+        assert_eq!(reused, admin);
+        // Aucun nouvel envoi: seul le déploiement initial de l'administrateur a consommé un nonce
+        assert_eq!(backend.pending_nonce(operator).await.unwrap(), 1);
+        assert_eq!(budget.read().treasury, 0.0);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn cost_report_aggregates_per_network_and_contract() {
+        let history = DeploymentHistory {
+            records: vec![
+                cost("Auroraium", "Polygon", 1_000_000, Some(3.0)),
+                cost("Auroraium", "Avalanche", 900_000, None),
+                cost("Vault", "Polygon", 500_000, Some(1.5)),
+            ],
+            ..DeploymentHistory::default()
+        };
+        let report = history.cost_report();
+
+        let polygon = &report.per_network["Polygon"];
+        assert_eq!(polygon.deployments, 2);
+        assert_eq!(polygon.gas_used, U256::from(1_500_000u64));
+        assert_eq!(report.per_contract["Auroraium"].unpriced, 1);
+        assert!((report.total_fiat - 4.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn history_uses_the_attached_registry_and_its_own_path() {
+        let dir = std::env::temp_dir().join(format!("aurorae-deployer-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("deployment_history.json");
+        let mut history = DeploymentHistory::open(&path);
+        assert_eq!(history.network_of(11155111), ("chain-11155111".to_string(), "ETH".to_string(), false));
+
+        let registry = crate::chain_registry::ChainRegistry::from_toml(r#"
+            [[chains]]
+            name = "Sepolia"
+            kind = "evm"
+            rpc = "https://rpc.sepolia.org"
+            chain-id = 11155111
+            native-currency = { symbol = "ETH", decimals = 18 }
+            testnet = true
+        "#).unwrap();
+        history.attach_chains(registry.shared());
+        assert_eq!(history.network_of(11155111), ("Sepolia".to_string(), "ETH".to_string(), true));
 
-        Ok(contract.address())
+        history.record(cost("Auroraium", "Sepolia", 1_000_000, Some(0.0)));
+        assert_eq!(DeploymentHistory::open(&path).records.len(), 1);
+        assert!(deployment_history_path().starts_with(std::env::temp_dir()));
+        fs::remove_dir_all(&dir).ok();
     }
 }
@@ -0,0 +1,1223 @@
+//! AURORAE++ - distributed_compute.rs
+//!
+//! Délestage des travaux lourds vers des workers gRPC (proto/compute.proto). Le processus
+//! principal héberge le coordinateur (`ComputeNode`): il met les tâches en file (analyse d'un
+//! dépôt, mutation en bac à sable, entraînement d'un fragment du réseau de décision) et les
+//! attribue aux workers enregistrés qui ont annoncé la capacité correspondante. Un worker
+//! (`aurorae --worker <url>`) reçoit ses tâches en flux, les exécute et renvoie progression et
+//! résultats en flux; faute de battement de cœur, il est retiré et ses tâches remises en file.
+//! Au-dessus, `ComputeScheduler` répartit les tâches entre plusieurs coordinateurs par
+//! priorité (urgence de la pensée d'origine), avec vol de travail entre partitions, nouvelles
+//! tentatives sur un autre nœud et métriques de latence de file remontées au gardien.
+//! `OffloadedWork` suit les travaux délestés par la boucle principale et décode leurs
+//! résultats: patterns des dépôts analysés, mutation promue à appliquer localement, poids
+//! moyennés des fragments d'entraînement à reporter sur le réseau de décision.
+
+use std::cmp::{self, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::{Request, Response, Status, Streaming};
+use uuid::Uuid;
+use tracing::{error, info, warn};
+
+use crate::brain::Thought;
+use crate::guardian::GuardianSentinel;
+use crate::knowledge::Pattern;
+use crate::mutation_sandbox::{MutationSandbox, RemoteMutation};
+use crate::license::LicensePolicy;
+use crate::neural_network::{export_weights, import_weights, NetWeights};
+use crate::pattern_extractor::extract_patterns_from_directory;
+use crate::resource_usage::spawn_tracked;
+
+pub mod proto {
+    tonic::include_proto!("aurorae.compute");
+}
+
+use proto::compute_coordinator_client::ComputeCoordinatorClient;
+use proto::compute_coordinator_server::{ComputeCoordinator, ComputeCoordinatorServer};
+use proto::task_result::Status as ResultStatus;
+use proto::{
+    HeartbeatReply, HeartbeatRequest, PollRequest, Registration, ResultAck, TaskAccepted, TaskAssignment,
+    TaskRequest, TaskResult, WatchRequest, WorkerInfo,
+};
+
+pub const DEFAULT_COMPUTE_CONFIG_PATH: &str = "compute.toml";
+/// Capacités annoncées par un worker qui exécute tous les types de tâches
+pub const ALL_CAPABILITIES: [&str; 4] = ["scan-repo", "mutation-sandbox", "train-shard", "evaluate-candidate"];
+
+/// Fichier `compute.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ComputeConfig {
+    /// Héberge le coordinateur gRPC dans le processus principal
+    pub enabled: bool,
+    pub listen: String,
+    pub heartbeat_interval_secs: u64,
+    /// Silence au-delà duquel un worker est retiré
+    pub heartbeat_timeout_secs: u64,
+    /// Attributions d'une tâche avant de la déclarer en échec
+    pub max_attempts: u32,
+    /// Coordinateurs supplémentaires (une adresse d'écoute chacun) entre lesquels
+    /// l'ordonnanceur répartit les tâches
+    pub additional_nodes: Vec<String>,
+    pub scheduler: SchedulerConfig,
+}
+
+/// Section `[scheduler]` de `compute.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct SchedulerConfig {
+    /// Nouvelles tentatives d'une tâche en échec, sur un autre nœud si possible
+    pub max_retries: u32,
+    /// Attentes retenues pour les métriques de latence
+    pub latency_window: usize,
+    /// Latence d'attente p95 au-delà de laquelle le gardien est alerté
+    pub latency_alert_ms: u64,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self { max_retries: 2, latency_window: 100, latency_alert_ms: 30_000 }
+    }
+}
+
+impl Default for ComputeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: "127.0.0.1:50061".to_string(),
+            heartbeat_interval_secs: 5,
+            heartbeat_timeout_secs: 20,
+            max_attempts: 3,
+            additional_nodes: Vec::new(),
+            scheduler: SchedulerConfig::default(),
+        }
+    }
+}
+
+impl ComputeConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Configuration du calcul distribué invalide: {}", e))
+    }
+
+    /// `compute.toml`, ou la configuration par défaut (coordinateur désactivé)
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_COMPUTE_CONFIG_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: calcul distribué par défaut", e);
+            Self::default()
+        })
+    }
+}
+
+/// Travaux délégables à un worker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum TaskKind {
+    /// Extraction des patterns d'un dépôt déjà présent sur le worker
+    ScanRepo { path: String },
+    /// Mutation évaluée en bac à sable contre la référence
+    MutationSandbox { file: String, seed: u64 },
+    /// Entraînement d'un réplica du réseau de décision sur le lot d'un fragment, à partir des
+    /// poids du coordinateur (couches cachées `hidden`) s'ils sont fournis
+    TrainShard {
+        shard: u32,
+        shards: u32,
+        epochs: u32,
+        #[serde(default)]
+        hidden: Vec<i64>,
+        #[serde(default)]
+        weights: Option<NetWeights>,
+    },
+    /// Évaluation d'un candidat d'une recherche d'hyperparamètres (objectif nommé)
+    EvaluateCandidate { objective: String, params: Vec<f64> },
+}
+
+impl TaskKind {
+    /// Capacité qu'un worker doit annoncer pour recevoir la tâche
+    pub fn capability(&self) -> &'static str {
+        match self {
+            TaskKind::ScanRepo { .. } => "scan-repo",
+            TaskKind::MutationSandbox { .. } => "mutation-sandbox",
+            TaskKind::TrainShard { .. } => "train-shard",
+            TaskKind::EvaluateCandidate { .. } => "evaluate-candidate",
+        }
+    }
+
+    fn to_wire(&self) -> (String, String) {
+        (self.capability().to_string(), serde_json::to_string(self).unwrap_or_default())
+    }
+
+    fn from_wire(kind: &str, payload: &str) -> Result<Self, String> {
+        let task: TaskKind = serde_json::from_str(payload).map_err(|e| format!("Tâche {} invalide: {}", kind, e))?;
+        if task.capability() != kind {
+            return Err(format!("Tâche annoncée {} mais décrite comme {}", kind, task.capability()));
+        }
+        Ok(task)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskState {
+    Queued,
+    Running { worker: Uuid, since: DateTime<Utc> },
+    Succeeded(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskRecord {
+    pub id: Uuid,
+    pub kind: TaskKind,
+    pub state: TaskState,
+    pub submitted_at: DateTime<Utc>,
+    /// Attributions successives à un worker
+    pub attempts: u32,
+}
+
+struct WorkerRecord {
+    name: String,
+    capabilities: HashSet<String>,
+    max_concurrency: usize,
+    last_heartbeat: DateTime<Utc>,
+    running: HashSet<Uuid>,
+    /// Flux ouvert par `PollTasks`
+    assignments: Option<mpsc::Sender<Result<TaskAssignment, Status>>>,
+}
+
+#[derive(Default)]
+struct Coordinator {
+    workers: HashMap<Uuid, WorkerRecord>,
+    queue: VecDeque<Uuid>,
+    tasks: HashMap<Uuid, TaskRecord>,
+}
+
+impl Coordinator {
+    /// Attribue les tâches en file au worker capable le moins chargé dont le flux est ouvert
+    fn dispatch(&mut self) {
+        let mut waiting = VecDeque::new();
+        while let Some(id) = self.queue.pop_front() {
+            let Some(task) = self.tasks.get_mut(&id) else { continue };
+            let capability = task.kind.capability();
+            let candidate = self.workers.iter_mut()
+                .filter(|(_, w)| w.capabilities.contains(capability) && w.running.len() < w.max_concurrency)
+                .filter(|(_, w)| w.assignments.as_ref().is_some_and(|tx| !tx.is_closed()))
+                .min_by_key(|(_, w)| w.running.len());
+            let Some((worker_id, worker)) = candidate else {
+                waiting.push_back(id);
+                continue;
+            };
+            let (kind, payload) = task.kind.to_wire();
+            let assignment = TaskAssignment { task_id: id.to_string(), kind, payload };
+            match worker.assignments.as_ref().map(|tx| tx.try_send(Ok(assignment))) {
+                Some(Ok(())) => {
+                    worker.running.insert(id);
+                    task.attempts += 1;
+                    task.state = TaskState::Running { worker: *worker_id, since: Utc::now() };
+                }
+                _ => waiting.push_back(id),
+            }
+        }
+        self.queue = waiting;
+    }
+
+    /// Retire les workers silencieux depuis `timeout`; leurs tâches sont remises en file, ou
+    /// déclarées en échec après `max_attempts` attributions
+    fn reap(&mut self, timeout: chrono::Duration, max_attempts: u32) -> Vec<String> {
+        let now = Utc::now();
+        let dead: Vec<Uuid> = self.workers.iter()
+            .filter(|(_, w)| now - w.last_heartbeat > timeout)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut names = Vec::new();
+        for id in dead {
+            let Some(worker) = self.workers.remove(&id) else { continue };
+            for task_id in worker.running {
+                if let Some(task) = self.tasks.get_mut(&task_id) {
+                    if task.attempts >= max_attempts {
+                        task.state = TaskState::Failed(format!("Abandonnée après {} attribution(s)", task.attempts));
+                    } else {
+                        task.state = TaskState::Queued;
+                        self.queue.push_back(task_id);
+                    }
+                }
+            }
+            names.push(worker.name);
+        }
+        names
+    }
+
+    /// Fin d'une tâche rapportée par son worker
+    fn complete(&mut self, result: &TaskResult) -> Result<(), Status> {
+        let task_id = parse_id(&result.task_id)?;
+        let worker_id = parse_id(&result.worker_id)?;
+        let final_state = match result.status() {
+            ResultStatus::Progress => return Ok(()),
+            ResultStatus::Succeeded => TaskState::Succeeded(result.output.clone()),
+            ResultStatus::Failed => TaskState::Failed(result.output.clone()),
+        };
+        if let Some(worker) = self.workers.get_mut(&worker_id) {
+            worker.running.remove(&task_id);
+        }
+        let task = self.tasks.get_mut(&task_id).ok_or_else(|| Status::not_found(format!("Tâche inconnue: {}", task_id)))?;
+        // Un worker retiré entre-temps ne réécrit pas une tâche réattribuée
+        if matches!(task.state, TaskState::Running { worker, .. } if worker == worker_id) {
+            task.state = final_state;
+        }
+        self.dispatch();
+        Ok(())
+    }
+}
+
+fn parse_id(id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(id).map_err(|e| Status::invalid_argument(format!("Identifiant invalide {}: {}", id, e)))
+}
+
+/// Coordinateur des workers de calcul, clonable à volonté
+#[derive(Clone)]
+pub struct ComputeNode {
+    config: ComputeConfig,
+    state: Arc<Mutex<Coordinator>>,
+    results: broadcast::Sender<TaskResult>,
+}
+
+impl ComputeNode {
+    pub fn new(config: ComputeConfig) -> Self {
+        let (results, _) = broadcast::channel(256);
+        Self { config, state: Arc::new(Mutex::new(Coordinator::default())), results }
+    }
+
+    /// Met une tâche en file; elle part dès qu'un worker capable est disponible
+    pub fn submit(&self, kind: TaskKind) -> Uuid {
+        let id = Uuid::new_v4();
+        let mut state = self.state.lock();
+        state.tasks.insert(id, TaskRecord { id, kind, state: TaskState::Queued, submitted_at: Utc::now(), attempts: 0 });
+        state.queue.push_back(id);
+        state.dispatch();
+        id
+    }
+
+    pub fn task(&self, id: Uuid) -> Option<TaskRecord> {
+        self.state.lock().tasks.get(&id).cloned()
+    }
+
+    /// Au moins un worker connecté annonce `capability`
+    pub fn has_worker_for(&self, capability: &str) -> bool {
+        self.state.lock().workers.values().any(|w| w.capabilities.contains(capability) && w.assignments.is_some())
+    }
+
+    /// Places libres chez les workers capables, déduction faite des tâches déjà en file
+    pub fn idle_capacity(&self, capability: &str) -> usize {
+        let state = self.state.lock();
+        let free: usize = state.workers.values()
+            .filter(|w| w.capabilities.contains(capability) && w.assignments.is_some())
+            .map(|w| w.max_concurrency.saturating_sub(w.running.len()))
+            .sum();
+        let queued = state.queue.iter()
+            .filter(|id| state.tasks.get(id).is_some_and(|task| task.kind.capability() == capability))
+            .count();
+        free.saturating_sub(queued)
+    }
+
+    /// Progression et résultats de toutes les tâches, au fil de l'eau
+    pub fn subscribe_results(&self) -> broadcast::Receiver<TaskResult> {
+        self.results.subscribe()
+    }
+
+    fn outcome(&self, id: Uuid) -> Option<Result<String, String>> {
+        match self.task(id)?.state {
+            TaskState::Succeeded(output) => Some(Ok(output)),
+            TaskState::Failed(error) => Some(Err(error)),
+            _ => None,
+        }
+    }
+
+    /// Attend le résultat final d'une tâche
+    pub async fn wait_for(&self, id: Uuid, timeout: Duration) -> Result<String, String> {
+        let mut results = self.results.subscribe();
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            if let Some(outcome) = self.outcome(id) {
+                return outcome;
+            }
+            match tokio::time::timeout_at(deadline, results.recv()).await {
+                Err(_) => return Err(format!("Tâche {} sans résultat après {:?}", id, timeout)),
+                Ok(Err(broadcast::error::RecvError::Closed)) => return Err("Coordinateur arrêté".to_string()),
+                Ok(_) => continue,
+            }
+        }
+    }
+
+    /// Retire les workers silencieux et réattribue leurs tâches
+    pub fn reap(&self) {
+        let timeout = chrono::Duration::seconds(self.config.heartbeat_timeout_secs as i64);
+        let mut state = self.state.lock();
+        let removed = state.reap(timeout, self.config.max_attempts);
+        if !removed.is_empty() {
+            info!("💀 Worker(s) sans battement de cœur retiré(s): {}", removed.join(", "));
+            state.dispatch();
+        }
+    }
+
+    /// Démarre le service gRPC sur `listen` et la surveillance des battements de cœur;
+    /// renvoie l'adresse effectivement liée
+    pub async fn serve(&self) -> Result<SocketAddr, String> {
+        let listener = TcpListener::bind(&self.config.listen).await
+            .map_err(|e| format!("Coordinateur de calcul sur {} impossible: {}", self.config.listen, e))?;
+        let address = listener.local_addr().map_err(|e| e.to_string())?;
+        let service = ComputeCoordinatorServer::new(self.clone());
+        spawn_tracked("distributed_compute", async move {
+            if let Err(e) = tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(TcpListenerStream::new(listener))
+                .await
+            {
+                error!("❌ Coordinateur de calcul arrêté: {}", e);
+            }
+        });
+        let node = self.clone();
+        spawn_tracked("distributed_compute", async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(node.config.heartbeat_interval_secs.max(1)));
+            loop {
+                interval.tick().await;
+                node.reap();
+            }
+        });
+        info!("🛰️ Coordinateur de calcul distribué à l'écoute sur {}", address);
+        Ok(address)
+    }
+}
+
+#[tonic::async_trait]
+impl ComputeCoordinator for ComputeNode {
+    async fn register(&self, request: Request<WorkerInfo>) -> Result<Response<Registration>, Status> {
+        let info = request.into_inner();
+        if info.capabilities.is_empty() {
+            return Err(Status::invalid_argument("Aucune capacité annoncée"));
+        }
+        let id = Uuid::new_v4();
+        info!("🛰️ Worker {} enregistré ({})", info.name, info.capabilities.join(", "));
+        self.state.lock().workers.insert(id, WorkerRecord {
+            name: info.name,
+            capabilities: info.capabilities.into_iter().collect(),
+            max_concurrency: info.max_concurrency.max(1) as usize,
+            last_heartbeat: Utc::now(),
+            running: HashSet::new(),
+            assignments: None,
+        });
+        Ok(Response::new(Registration { worker_id: id.to_string(), heartbeat_interval_secs: self.config.heartbeat_interval_secs }))
+    }
+
+    async fn heartbeat(&self, request: Request<HeartbeatRequest>) -> Result<Response<HeartbeatReply>, Status> {
+        let id = parse_id(&request.into_inner().worker_id)?;
+        let known = match self.state.lock().workers.get_mut(&id) {
+            Some(worker) => {
+                worker.last_heartbeat = Utc::now();
+                true
+            }
+            None => false,
+        };
+        Ok(Response::new(HeartbeatReply { known }))
+    }
+
+    async fn submit_task(&self, request: Request<TaskRequest>) -> Result<Response<TaskAccepted>, Status> {
+        let request = request.into_inner();
+        let kind = TaskKind::from_wire(&request.kind, &request.payload).map_err(Status::invalid_argument)?;
+        Ok(Response::new(TaskAccepted { task_id: self.submit(kind).to_string() }))
+    }
+
+    type PollTasksStream = ReceiverStream<Result<TaskAssignment, Status>>;
+
+    async fn poll_tasks(&self, request: Request<PollRequest>) -> Result<Response<Self::PollTasksStream>, Status> {
+        let id = parse_id(&request.into_inner().worker_id)?;
+        let (tx, rx) = mpsc::channel(32);
+        let mut state = self.state.lock();
+        let worker = state.workers.get_mut(&id).ok_or_else(|| Status::not_found("Worker inconnu: se réenregistrer"))?;
+        worker.assignments = Some(tx);
+        state.dispatch();
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn report_results(&self, request: Request<Streaming<TaskResult>>) -> Result<Response<ResultAck>, Status> {
+        let mut stream = request.into_inner();
+        let mut received = 0;
+        while let Some(result) = stream.message().await? {
+            received += 1;
+            self.state.lock().complete(&result)?;
+            // Aucun observateur n'est une situation normale
+            let _ = self.results.send(result);
+        }
+        Ok(Response::new(ResultAck { received }))
+    }
+
+    type WatchResultsStream = ReceiverStream<Result<TaskResult, Status>>;
+
+    async fn watch_results(&self, request: Request<WatchRequest>) -> Result<Response<Self::WatchResultsStream>, Status> {
+        let filter = request.into_inner().task_id;
+        let mut results = self.results.subscribe();
+        let (tx, rx) = mpsc::channel(32);
+        spawn_tracked("distributed_compute", async move {
+            loop {
+                match results.recv().await {
+                    Ok(result) if filter.is_empty() || result.task_id == filter => {
+                        if tx.send(Ok(result)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Tâche en attente dans l'ordonnanceur; la plus prioritaire, puis la plus ancienne, sort en tête
+#[derive(Debug, Clone)]
+struct ScheduledTask {
+    id: Uuid,
+    kind: TaskKind,
+    priority: u8,
+    seq: u64,
+    enqueued_at: DateTime<Utc>,
+    /// Nœuds où la tâche a déjà échoué
+    failed_on: Vec<usize>,
+}
+
+impl ScheduledTask {
+    fn key(&self) -> (u8, Reverse<u64>) {
+        (self.priority, Reverse(self.seq))
+    }
+}
+
+impl PartialEq for ScheduledTask {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for ScheduledTask {}
+
+impl PartialOrd for ScheduledTask {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScheduledTask {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+struct NodeSlot {
+    name: String,
+    node: ComputeNode,
+    /// Partition locale des tâches attribuées au nœud et pas encore transmises
+    queue: BinaryHeap<ScheduledTask>,
+}
+
+struct InFlight {
+    task: ScheduledTask,
+    node: usize,
+    remote: Uuid,
+}
+
+/// Métriques de file transmises au gardien
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct QueueMetrics {
+    pub queued: usize,
+    pub in_flight: usize,
+    pub completed: u64,
+    pub failed: u64,
+    pub retries: u64,
+    pub steals: u64,
+    /// Attente entre mise en file et transmission à un nœud, sur la fenêtre récente
+    pub mean_wait_ms: u64,
+    pub p95_wait_ms: u64,
+    pub max_wait_ms: u64,
+}
+
+/// Ordonnanceur réparti entre plusieurs coordinateurs: chaque tâche est attribuée à la
+/// partition du nœud capable le moins chargé, transmise dès qu'il a une place libre, volée par
+/// un nœud inoccupé sinon, et retentée ailleurs en cas d'échec
+pub struct ComputeScheduler {
+    config: SchedulerConfig,
+    nodes: Vec<NodeSlot>,
+    /// Tâches qu'aucun nœud enregistré ne sait encore exécuter
+    unassigned: BinaryHeap<ScheduledTask>,
+    in_flight: HashMap<Uuid, InFlight>,
+    outcomes: HashMap<Uuid, Result<String, String>>,
+    waits: VecDeque<u64>,
+    seq: u64,
+    completed: u64,
+    failed: u64,
+    retries: u64,
+    steals: u64,
+}
+
+impl ComputeScheduler {
+    pub fn new(config: SchedulerConfig) -> Self {
+        Self {
+            config,
+            nodes: Vec::new(),
+            unassigned: BinaryHeap::new(),
+            in_flight: HashMap::new(),
+            outcomes: HashMap::new(),
+            waits: VecDeque::new(),
+            seq: 0,
+            completed: 0,
+            failed: 0,
+            retries: 0,
+            steals: 0,
+        }
+    }
+
+    pub fn register_node(&mut self, name: &str, node: ComputeNode) {
+        info!("🗂️ Nœud de calcul {} ajouté à l'ordonnanceur", name);
+        self.nodes.push(NodeSlot { name: name.to_string(), node, queue: BinaryHeap::new() });
+    }
+
+    /// Au moins un nœud dispose d'un worker annonçant `capability`
+    pub fn has_worker_for(&self, capability: &str) -> bool {
+        self.nodes.iter().any(|slot| slot.node.has_worker_for(capability))
+    }
+
+    /// Met une tâche en file avec une priorité de 0 à 255
+    pub fn submit(&mut self, kind: TaskKind, priority: u8) -> Uuid {
+        self.seq += 1;
+        let task = ScheduledTask { id: Uuid::new_v4(), kind, priority, seq: self.seq, enqueued_at: Utc::now(), failed_on: Vec::new() };
+        let id = task.id;
+        self.partition(task);
+        id
+    }
+
+    /// Met en file une tâche issue d'une pensée: sa priorité est l'urgence de la pensée
+    pub fn submit_thought(&mut self, kind: TaskKind, thought: &Thought) -> Uuid {
+        self.submit(kind, thought.urgency)
+    }
+
+    /// Résultat final d'une tâche, une fois terminée
+    pub fn outcome(&self, id: Uuid) -> Option<&Result<String, String>> {
+        self.outcomes.get(&id)
+    }
+
+    /// Partition du nœud capable le moins chargé, de préférence un nœud où la tâche n'a pas échoué
+    fn partition(&mut self, task: ScheduledTask) {
+        let capability = task.kind.capability();
+        let capable: Vec<usize> = (0..self.nodes.len()).filter(|&i| self.nodes[i].node.has_worker_for(capability)).collect();
+        let fresh: Vec<usize> = capable.iter().copied().filter(|i| !task.failed_on.contains(i)).collect();
+        let candidates = if fresh.is_empty() { capable } else { fresh };
+        match candidates.into_iter().min_by_key(|&i| self.nodes[i].queue.len()) {
+            Some(index) => self.nodes[index].queue.push(task),
+            None => self.unassigned.push(task),
+        }
+    }
+
+    fn dispatch(&mut self, index: usize, task: ScheduledTask) {
+        let remote = self.nodes[index].node.submit(task.kind.clone());
+        let wait = (Utc::now() - task.enqueued_at).num_milliseconds().max(0) as u64;
+        self.waits.push_back(wait);
+        while self.waits.len() > self.config.latency_window.max(1) {
+            self.waits.pop_front();
+        }
+        self.in_flight.insert(task.id, InFlight { task, node: index, remote });
+    }
+
+    /// Transmet la partition d'un nœud, par priorité, tant qu'il a des places libres
+    fn dispatch_local(&mut self, index: usize) {
+        let mut held = Vec::new();
+        while let Some(task) = self.nodes[index].queue.pop() {
+            if self.nodes[index].node.idle_capacity(task.kind.capability()) > 0 {
+                self.dispatch(index, task);
+            } else {
+                held.push(task);
+            }
+        }
+        self.nodes[index].queue.extend(held);
+    }
+
+    /// Un nœud aux places libres vole la tâche la plus prioritaire qu'il sait exécuter dans la
+    /// partition la plus chargée
+    fn steal(&mut self, thief: usize) {
+        loop {
+            let mut victims: Vec<usize> = (0..self.nodes.len()).filter(|&v| v != thief && !self.nodes[v].queue.is_empty()).collect();
+            victims.sort_by_key(|&v| Reverse(self.nodes[v].queue.len()));
+            let thief_node = &self.nodes[thief].node;
+            let loot = victims.into_iter().find_map(|victim| {
+                self.nodes[victim].queue.iter()
+                    .filter(|task| !task.failed_on.contains(&thief) && thief_node.idle_capacity(task.kind.capability()) > 0)
+                    .max()
+                    .map(|task| (victim, task.id))
+            });
+            let Some((victim, id)) = loot else { break };
+            let mut remaining = std::mem::take(&mut self.nodes[victim].queue).into_vec();
+            let Some(position) = remaining.iter().position(|task| task.id == id) else { break };
+            let task = remaining.swap_remove(position);
+            self.nodes[victim].queue = remaining.into();
+            info!("🗂️ {} vole une tâche {} à {}", self.nodes[thief].name, task.kind.capability(), self.nodes[victim].name);
+            self.steals += 1;
+            self.dispatch(thief, task);
+        }
+    }
+
+    /// Tâche terminée sur son nœud: résultat final, ou nouvelle tentative sur un autre nœud
+    fn settle(&mut self) -> Vec<(Uuid, Result<String, String>)> {
+        let finished: Vec<(Uuid, Result<String, String>)> = self.in_flight.iter()
+            .filter_map(|(id, flight)| match self.nodes[flight.node].node.task(flight.remote).map(|record| record.state) {
+                Some(TaskState::Succeeded(output)) => Some((*id, Ok(output))),
+                Some(TaskState::Failed(error)) => Some((*id, Err(error))),
+                None => Some((*id, Err("Tâche perdue par le nœud".to_string()))),
+                _ => None,
+            })
+            .collect();
+        let mut completions = Vec::new();
+        for (id, outcome) in finished {
+            let Some(InFlight { mut task, node, .. }) = self.in_flight.remove(&id) else { continue };
+            match outcome {
+                Err(error) if (task.failed_on.len() as u32) < self.config.max_retries => {
+                    info!("🔁 Tâche {} en échec sur {} ({}), nouvelle tentative", id, self.nodes[node].name, error);
+                    self.retries += 1;
+                    task.failed_on.push(node);
+                    task.enqueued_at = Utc::now();
+                    self.partition(task);
+                }
+                outcome => {
+                    if outcome.is_ok() {
+                        self.completed += 1;
+                    } else {
+                        self.failed += 1;
+                    }
+                    self.outcomes.insert(id, outcome.clone());
+                    completions.push((id, outcome));
+                }
+            }
+        }
+        completions
+    }
+
+    /// Cycle de l'ordonnanceur: relève les tâches terminées, répartit celles en attente,
+    /// transmet chaque partition puis laisse les nœuds inoccupés voler du travail
+    pub fn tick(&mut self) -> Vec<(Uuid, Result<String, String>)> {
+        let completions = self.settle();
+        for task in std::mem::take(&mut self.unassigned).into_vec() {
+            self.partition(task);
+        }
+        for index in 0..self.nodes.len() {
+            self.dispatch_local(index);
+        }
+        for index in 0..self.nodes.len() {
+            self.steal(index);
+        }
+        completions
+    }
+
+    pub fn metrics(&self) -> QueueMetrics {
+        let mut waits: Vec<u64> = self.waits.iter().copied().collect();
+        waits.sort_unstable();
+        let p95 = waits.get((waits.len() * 95 / 100).min(waits.len().saturating_sub(1))).copied().unwrap_or(0);
+        QueueMetrics {
+            queued: self.unassigned.len() + self.nodes.iter().map(|slot| slot.queue.len()).sum::<usize>(),
+            in_flight: self.in_flight.len(),
+            completed: self.completed,
+            failed: self.failed,
+            retries: self.retries,
+            steals: self.steals,
+            mean_wait_ms: if waits.is_empty() { 0 } else { waits.iter().sum::<u64>() / waits.len() as u64 },
+            p95_wait_ms: p95,
+            max_wait_ms: waits.last().copied().unwrap_or(0),
+        }
+    }
+
+    /// Transmet les métriques de file au gardien
+    pub fn report_to(&self, guardian: &mut GuardianSentinel) {
+        guardian.record_queue_metrics("distributed_compute", self.metrics(), self.config.latency_alert_ms);
+    }
+}
+
+/// Exécution d'une tâche sur le worker; renvoie le résultat JSON
+pub type TaskExecutor = Arc<dyn Fn(TaskKind) -> Result<String, String> + Send + Sync>;
+
+/// Exécuteur par défaut: analyse, bac à sable de mutation, entraînement local et évaluation de candidats
+pub fn execute_task(task: TaskKind) -> Result<String, String> {
+    match task {
+        TaskKind::ScanRepo { path } => {
+            let patterns = extract_patterns_from_directory(Path::new(&path), &LicensePolicy::load_or_default());
+            serde_json::to_string(&patterns).map_err(|e| e.to_string())
+        }
+        // Évaluée sur la copie des sources du worker, sans écriture: le coordinateur applique la promue
+        TaskKind::MutationSandbox { file, seed } => {
+            let remote = MutationSandbox::new(Path::new(".")).propose(&file, seed)?;
+            serde_json::to_string(&remote).map_err(|e| e.to_string())
+        }
+        TaskKind::TrainShard { shard, shards, epochs, hidden, weights } => {
+            use tch::{nn, Device, Kind, Tensor};
+            // Lot du fragment reproductible d'un worker à l'autre
+            tch::manual_seed(shard as i64);
+            let vs = nn::VarStore::new(Device::Cpu);
+            let hidden = if hidden.is_empty() { vec![64, 32] } else { hidden };
+            let net = crate::neural_network::DecisionNet::new(&vs, 16, hidden, 8);
+            if let Some(weights) = &weights {
+                import_weights(&vs, weights)?;
+            }
+            let mut optimizer = crate::neural_network::create_optimizer(&vs);
+            let input = Tensor::rand([32, 16], (Kind::Float, Device::Cpu));
+            let target = Tensor::rand([32, 8], (Kind::Float, Device::Cpu));
+            for _ in 0..epochs {
+                net.train(input.shallow_clone(), target.shallow_clone(), &mut optimizer);
+            }
+            let loss = net.forward(input).mse_loss(&target, tch::Reduction::Mean).double_value(&[]);
+            let output = ShardOutput { shard, shards, loss, weights: export_weights(&vs)? };
+            serde_json::to_string(&output).map_err(|e| e.to_string())
+        }
+        TaskKind::EvaluateCandidate { objective, params } => {
+            let score = crate::swarm_intelligence::objective_named(&objective)?.evaluate(&params);
+            serde_json::to_string(&score).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Résultat d'un fragment d'entraînement: perte finale et poids du réplica entraîné
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShardOutput {
+    pub shard: u32,
+    pub shards: u32,
+    pub loss: f64,
+    pub weights: NetWeights,
+}
+
+/// Résultat décodé d'un travail délesté par la boucle principale
+#[derive(Debug)]
+pub enum Offloaded {
+    /// Patterns extraits d'un dépôt analysé par un worker
+    Scan { path: String, patterns: Vec<Pattern> },
+    /// Mutations évaluées par un worker, la promue éventuelle restant à appliquer
+    Mutation(RemoteMutation),
+    /// Tour d'entraînement terminé: moyenne des poids des fragments réussis
+    Training { weights: NetWeights, loss: f64, shards: usize },
+}
+
+/// Tour d'entraînement en cours: fragments attendus et résultats déjà reçus
+#[derive(Debug, Default)]
+struct ShardRound {
+    pending: HashSet<Uuid>,
+    outputs: Vec<ShardOutput>,
+}
+
+/// Travaux délestés par la boucle principale (analyses de dépôts, mutations en bac à sable,
+/// fragments d'entraînement), en attente de leur résultat
+#[derive(Debug, Default)]
+pub struct OffloadedWork {
+    scans: HashMap<Uuid, String>,
+    mutations: HashSet<Uuid>,
+    round: Option<ShardRound>,
+}
+
+impl OffloadedWork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn scans_pending(&self) -> usize {
+        self.scans.len()
+    }
+
+    pub fn mutations_pending(&self) -> usize {
+        self.mutations.len()
+    }
+
+    pub fn training_in_progress(&self) -> bool {
+        self.round.is_some()
+    }
+
+    /// Analyse d'un dépôt présent sur les workers (chemin relatif au feed)
+    pub fn submit_scan(&mut self, scheduler: &mut ComputeScheduler, path: &str, thought: &Thought) -> Uuid {
+        let id = scheduler.submit_thought(TaskKind::ScanRepo { path: path.to_string() }, thought);
+        self.scans.insert(id, path.to_string());
+        id
+    }
+
+    pub fn submit_mutation(&mut self, scheduler: &mut ComputeScheduler, file: &str, seed: u64, thought: &Thought) -> Uuid {
+        let id = scheduler.submit_thought(TaskKind::MutationSandbox { file: file.to_string(), seed }, thought);
+        self.mutations.insert(id);
+        id
+    }
+
+    /// Délègue un tour d'entraînement en `shards` fragments partant des poids donnés; refusé
+    /// tant que le tour précédent n'est pas terminé
+    pub fn submit_training(&mut self, scheduler: &mut ComputeScheduler, shards: u32, epochs: u32, hidden: &[i64], weights: &NetWeights, thought: &Thought) -> bool {
+        if self.round.is_some() {
+            return false;
+        }
+        let pending = (0..shards)
+            .map(|shard| scheduler.submit_thought(TaskKind::TrainShard {
+                shard,
+                shards,
+                epochs,
+                hidden: hidden.to_vec(),
+                weights: Some(weights.clone()),
+            }, thought))
+            .collect();
+        self.round = Some(ShardRound { pending, outputs: Vec::new() });
+        true
+    }
+
+    /// Décode le résultat d'une tâche terminée de l'ordonnanceur; `None` pour une tâche qui
+    /// n'a pas été délestée ici, en échec, ou un fragment d'un tour pas encore complet
+    pub fn complete(&mut self, id: Uuid, outcome: &Result<String, String>) -> Option<Offloaded> {
+        if let Some(path) = self.scans.remove(&id) {
+            let patterns = serde_json::from_str(outcome.as_ref().ok()?)
+                .map_err(|e| warn!("⚠️ Patterns de {} illisibles: {}", path, e))
+                .ok()?;
+            return Some(Offloaded::Scan { path, patterns });
+        }
+        if self.mutations.remove(&id) {
+            return serde_json::from_str(outcome.as_ref().ok()?)
+                .map_err(|e| warn!("⚠️ Mutation distante illisible: {}", e))
+                .ok()
+                .map(Offloaded::Mutation);
+        }
+
+        let round = self.round.as_mut().filter(|round| round.pending.contains(&id))?;
+        round.pending.remove(&id);
+        match outcome.as_ref().map(|output| serde_json::from_str::<ShardOutput>(output)) {
+            Ok(Ok(output)) => round.outputs.push(output),
+            Ok(Err(e)) => warn!("⚠️ Fragment d'entraînement illisible: {}", e),
+            Err(_) => {}
+        }
+        if !round.pending.is_empty() {
+            return None;
+        }
+        let round = self.round.take()?;
+        let parts: Vec<NetWeights> = round.outputs.iter().map(|output| output.weights.clone()).collect();
+        let weights = NetWeights::average(&parts)
+            .map_err(|e| warn!("⚠️ Poids des fragments non fusionnés: {}", e))
+            .ok()?;
+        let loss = round.outputs.iter().map(|output| output.loss).sum::<f64>() / round.outputs.len() as f64;
+        Some(Offloaded::Training { weights, loss, shards: round.outputs.len() })
+    }
+}
+
+/// Worker de calcul: s'enregistre auprès du coordinateur, exécute les tâches reçues et en
+/// renvoie progression et résultats
+pub struct ComputeWorker {
+    pub name: String,
+    pub coordinator: String,
+    pub capabilities: Vec<String>,
+    pub max_concurrency: u32,
+    executor: TaskExecutor,
+}
+
+impl ComputeWorker {
+    pub fn new(coordinator: &str) -> Self {
+        let coordinator = if coordinator.starts_with("http") { coordinator.to_string() } else { format!("http://{}", coordinator) };
+        Self {
+            name: format!("worker-{}", &Uuid::new_v4().to_string()[..8]),
+            coordinator,
+            capabilities: ALL_CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+            max_concurrency: std::thread::available_parallelism().map(|n| n.get() as u32).unwrap_or(1),
+            executor: Arc::new(execute_task),
+        }
+    }
+
+    pub fn with_executor(mut self, capabilities: &[&str], executor: TaskExecutor) -> Self {
+        self.capabilities = capabilities.iter().map(|c| c.to_string()).collect();
+        self.executor = executor;
+        self
+    }
+
+    /// Boucle du worker, jusqu'à la fermeture du flux de tâches par le coordinateur
+    pub async fn run(&self) -> Result<(), String> {
+        let mut client = ComputeCoordinatorClient::connect(self.coordinator.clone()).await
+            .map_err(|e| format!("Coordinateur {} injoignable: {}", self.coordinator, e))?;
+        let registration = client.register(WorkerInfo {
+            name: self.name.clone(),
+            capabilities: self.capabilities.clone(),
+            max_concurrency: self.max_concurrency,
+        }).await.map_err(|e| format!("Enregistrement refusé: {}", e.message()))?.into_inner();
+        let worker_id = registration.worker_id;
+        info!("🛰️ Worker {} enregistré auprès de {}", self.name, self.coordinator);
+
+        let running = Arc::new(AtomicU32::new(0));
+        let (mut heartbeat_client, heartbeat_id, heartbeat_running) = (client.clone(), worker_id.clone(), running.clone());
+        let heartbeat = spawn_tracked("distributed_compute", async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(registration.heartbeat_interval_secs.max(1)));
+            loop {
+                interval.tick().await;
+                let request = HeartbeatRequest { worker_id: heartbeat_id.clone(), running_tasks: heartbeat_running.load(Ordering::Relaxed) };
+                if matches!(heartbeat_client.heartbeat(request).await, Ok(reply) if !reply.get_ref().known) {
+                    warn!("⚠️ Worker oublié par le coordinateur");
+                    break;
+                }
+            }
+        });
+
+        let (results_tx, results_rx) = mpsc::channel(64);
+        let mut report_client = client.clone();
+        let reporter = spawn_tracked("distributed_compute", async move {
+            report_client.report_results(ReceiverStream::new(results_rx)).await
+        });
+
+        let mut assignments = client.poll_tasks(PollRequest { worker_id: worker_id.clone() }).await
+            .map_err(|e| format!("Flux de tâches refusé: {}", e.message()))?
+            .into_inner();
+        while let Some(assignment) = assignments.message().await.map_err(|e| e.message().to_string())? {
+            let (executor, results, running, worker_id) = (self.executor.clone(), results_tx.clone(), running.clone(), worker_id.clone());
+            spawn_tracked("distributed_compute", async move {
+                let report = |status: ResultStatus, output: String| TaskResult {
+                    task_id: assignment.task_id.clone(),
+                    worker_id: worker_id.clone(),
+                    status: status as i32,
+                    output,
+                };
+                let _ = results.send(report(ResultStatus::Progress, format!("{} démarrée", assignment.kind))).await;
+                running.fetch_add(1, Ordering::Relaxed);
+                let outcome = match TaskKind::from_wire(&assignment.kind, &assignment.payload) {
+                    Ok(task) => tokio::task::spawn_blocking(move || executor(task)).await
+                        .unwrap_or_else(|e| Err(format!("Tâche interrompue: {}", e))),
+                    Err(e) => Err(e),
+                };
+                running.fetch_sub(1, Ordering::Relaxed);
+                let final_report = match outcome {
+                    Ok(output) => report(ResultStatus::Succeeded, output),
+                    Err(error) => report(ResultStatus::Failed, error),
+                };
+                let _ = results.send(final_report).await;
+            });
+        }
+
+        heartbeat.abort();
+        drop(results_tx);
+        let _ = reporter.await;
+        info!("🛰️ Flux de tâches fermé, worker {} arrêté", self.name);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worker(capabilities: &[&str]) -> (WorkerRecord, mpsc::Receiver<Result<TaskAssignment, Status>>) {
+        let (tx, rx) = mpsc::channel(8);
+        let record = WorkerRecord {
+            name: "test".to_string(),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            max_concurrency: 1,
+            last_heartbeat: Utc::now(),
+            running: HashSet::new(),
+            assignments: Some(tx),
+        };
+        (record, rx)
+    }
+
+    #[test]
+    fn tasks_of_silent_workers_are_requeued_then_failed() {
+        let node = ComputeNode::new(ComputeConfig { max_attempts: 2, ..ComputeConfig::default() });
+        let scan = node.submit(TaskKind::ScanRepo { path: ".".to_string() });
+        assert_eq!(node.task(scan).unwrap().state, TaskState::Queued);
+
+        let (scanner, mut inbox) = worker(&["scan-repo"]);
+        let scanner_id = Uuid::new_v4();
+        {
+            let mut state = node.state.lock();
+            state.workers.insert(scanner_id, scanner);
+            state.dispatch();
+        }
+        let assignment = inbox.try_recv().unwrap().unwrap();
+        assert_eq!(TaskKind::from_wire(&assignment.kind, &assignment.payload).unwrap(), TaskKind::ScanRepo { path: ".".to_string() });
+        assert!(matches!(node.task(scan).unwrap().state, TaskState::Running { worker, .. } if worker == scanner_id));
+
+        // Worker muet: la tâche revient en file, puis échoue au-delà des tentatives permises
+        for attempt in 1..=2 {
+            let mut state = node.state.lock();
+            if let Some(w) = state.workers.get_mut(&scanner_id) {
+                w.last_heartbeat = Utc::now() - chrono::Duration::hours(1);
+            }
+            assert_eq!(state.reap(chrono::Duration::seconds(20), 2).len(), 1);
+            let (scanner, _) = worker(&["scan-repo"]);
+            state.workers.insert(scanner_id, scanner);
+            if attempt == 1 {
+                assert_eq!(state.tasks[&scan].state, TaskState::Queued);
+                state.dispatch();
+            }
+        }
+        assert!(matches!(node.task(scan).unwrap().state, TaskState::Failed(_)));
+        assert!(TaskKind::from_wire("train-shard", &assignment.payload).is_err());
+    }
+
+    fn attach(node: &ComputeNode, capabilities: &[&str]) -> (Uuid, mpsc::Receiver<Result<TaskAssignment, Status>>) {
+        let (record, inbox) = worker(capabilities);
+        let id = Uuid::new_v4();
+        node.state.lock().workers.insert(id, record);
+        (id, inbox)
+    }
+
+    fn scanned(inbox: &mut mpsc::Receiver<Result<TaskAssignment, Status>>) -> (String, String) {
+        let assignment = inbox.try_recv().unwrap().unwrap();
+        match TaskKind::from_wire(&assignment.kind, &assignment.payload).unwrap() {
+            TaskKind::ScanRepo { path } => (assignment.task_id, path),
+            other => panic!("tâche inattendue: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn idle_nodes_steal_the_most_urgent_tasks() {
+        let (alpha, beta) = (ComputeNode::new(ComputeConfig::default()), ComputeNode::new(ComputeConfig::default()));
+        let (_, mut alpha_inbox) = attach(&alpha, &["scan-repo"]);
+        let mut scheduler = ComputeScheduler::new(SchedulerConfig::default());
+        scheduler.register_node("alpha", alpha);
+        scheduler.register_node("beta", beta.clone());
+
+        // Seul alpha sait analyser: toute la file lui est attribuée
+        for (path, urgency) in [("basse", 10), ("haute", 200), ("moyenne", 100)] {
+            scheduler.submit(TaskKind::ScanRepo { path: path.to_string() }, urgency);
+        }
+        let (_, mut beta_inbox) = attach(&beta, &["scan-repo"]);
+        scheduler.tick();
+
+        assert_eq!(scanned(&mut alpha_inbox).1, "haute");
+        assert_eq!(scanned(&mut beta_inbox).1, "moyenne");
+        let metrics = scheduler.metrics();
+        assert_eq!((metrics.queued, metrics.in_flight, metrics.steals), (1, 2, 1));
+        assert!(scheduler.has_worker_for("scan-repo") && !scheduler.has_worker_for("train-shard"));
+    }
+
+    #[test]
+    fn failed_tasks_are_retried_on_another_node_and_reported() {
+        let (alpha, beta) = (ComputeNode::new(ComputeConfig::default()), ComputeNode::new(ComputeConfig::default()));
+        let (alpha_worker, mut alpha_inbox) = attach(&alpha, &["scan-repo"]);
+        let (beta_worker, mut beta_inbox) = attach(&beta, &["scan-repo"]);
+        let mut scheduler = ComputeScheduler::new(SchedulerConfig { max_retries: 1, latency_alert_ms: 0, ..SchedulerConfig::default() });
+        scheduler.register_node("alpha", alpha.clone());
+        scheduler.register_node("beta", beta.clone());
+        let urgent = scheduler.submit(TaskKind::ScanRepo { path: "urgente".to_string() }, 255);
+        let routine = scheduler.submit(TaskKind::ScanRepo { path: "routine".to_string() }, 50);
+        scheduler.tick();
+        let (alpha_task, path) = scanned(&mut alpha_inbox);
+        assert_eq!(path, "urgente");
+        let (beta_task, _) = scanned(&mut beta_inbox);
+
+        let report = |node: &ComputeNode, task_id: &str, worker: Uuid, status: ResultStatus| {
+            node.state.lock().complete(&TaskResult { task_id: task_id.to_string(), worker_id: worker.to_string(), status: status as i32, output: "sortie".to_string() }).unwrap();
+        };
+        // Échec sur alpha: la tâche attend beta plutôt que de revenir sur alpha, pourtant libre
+        report(&alpha, &alpha_task, alpha_worker, ResultStatus::Failed);
+        assert!(scheduler.tick().is_empty());
+        assert!(alpha_inbox.try_recv().is_err());
+        report(&beta, &beta_task, beta_worker, ResultStatus::Succeeded);
+        assert_eq!(scheduler.tick(), vec![(routine, Ok("sortie".to_string()))]);
+        let (retried, path) = scanned(&mut beta_inbox);
+        assert_eq!(path, "urgente");
+
+        // Plus de nouvelle tentative permise: l'échec est définitif
+        report(&beta, &retried, beta_worker, ResultStatus::Failed);
+        assert_eq!(scheduler.tick(), vec![(urgent, Err("sortie".to_string()))]);
+        let metrics = scheduler.metrics();
+        assert_eq!((metrics.completed, metrics.failed, metrics.retries, metrics.queued), (1, 1, 1, 0));
+
+        let mut guardian = GuardianSentinel::new();
+        scheduler.report_to(&mut guardian);
+        assert_eq!(guardian.queue_metrics["distributed_compute"], metrics);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn worker_executes_tasks_streamed_by_the_coordinator() {
+        let node = ComputeNode::new(ComputeConfig { listen: "127.0.0.1:0".to_string(), ..ComputeConfig::default() });
+        let address = node.serve().await.unwrap();
+        let worker = ComputeWorker::new(&address.to_string()).with_executor(
+            &["scan-repo"],
+            Arc::new(|task| match task {
+                TaskKind::ScanRepo { path } => Ok(format!("analysé: {}", path)),
+                other => Err(format!("non pris en charge: {:?}", other)),
+            }),
+        );
+        tokio::spawn(async move { worker.run().await });
+
+        let mut progress = node.subscribe_results();
+        let task = node.submit(TaskKind::ScanRepo { path: "feed/substrate".to_string() });
+        let output = node.wait_for(task, Duration::from_secs(10)).await.unwrap();
+        assert_eq!(output, "analysé: feed/substrate");
+        assert!(node.has_worker_for("scan-repo") && !node.has_worker_for("train-shard"));
+        let first = progress.recv().await.unwrap();
+        assert_eq!((first.task_id, first.status()), (task.to_string(), ResultStatus::Progress));
+
+        // Sans worker capable, la tâche reste en file
+        let shard = node.submit(TaskKind::TrainShard { shard: 0, shards: 1, epochs: 1, hidden: Vec::new(), weights: None });
+        assert_eq!(node.task(shard).unwrap().state, TaskState::Queued);
+    }
+
+    fn weights(value: f32) -> NetWeights {
+        NetWeights { tensors: [("0.weight".to_string(), (vec![2], vec![value, 2.0 * value]))].into_iter().collect() }
+    }
+
+    #[test]
+    fn offloaded_shards_are_averaged_once_the_round_completes() {
+        let mut scheduler = ComputeScheduler::new(SchedulerConfig::default());
+        let mut work = OffloadedWork::new();
+        let thought = Thought::new(crate::brain::Intent::SelfUpgrade, 96);
+        assert!(work.submit_training(&mut scheduler, 3, 5, &[4], &weights(0.0), &thought));
+        // Un seul tour à la fois
+        assert!(!work.submit_training(&mut scheduler, 3, 5, &[4], &weights(0.0), &thought));
+        let ids: Vec<Uuid> = work.round.as_ref().unwrap().pending.iter().copied().collect();
+
+        let output = |shard: u32, value: f32, loss: f64| Ok(serde_json::to_string(&ShardOutput { shard, shards: 3, loss, weights: weights(value) }).unwrap());
+        assert!(work.complete(ids[0], &output(0, 1.0, 0.2)).is_none());
+        // Un fragment en échec est exclu de la moyenne
+        assert!(work.complete(ids[1], &Err("worker perdu".to_string())).is_none());
+        match work.complete(ids[2], &output(2, 3.0, 0.4)) {
+            Some(Offloaded::Training { weights: merged, loss, shards }) => {
+                assert_eq!(merged, weights(2.0));
+                assert!((loss - 0.3).abs() < 1e-9);
+                assert_eq!(shards, 2);
+            }
+            other => panic!("tour non fusionné: {:?}", other),
+        }
+        assert!(!work.training_in_progress());
+    }
+
+    #[test]
+    fn offloaded_scans_and_mutations_are_decoded_by_task() {
+        let mut scheduler = ComputeScheduler::new(SchedulerConfig::default());
+        let mut work = OffloadedWork::new();
+        let thought = Thought::new(crate::brain::Intent::LearnFromGithub, 120);
+        let scan = work.submit_scan(&mut scheduler, "substrate", &thought);
+        let mutation = work.submit_mutation(&mut scheduler, "./aurorae/economy.rs", 7, &thought);
+        assert_eq!((work.scans_pending(), work.mutations_pending()), (1, 1));
+
+        let patterns = vec![Pattern { module_name: "substrate/lib.rs".to_string(), functions: 3, structs: 1, traits: 0, enums: 0 }];
+        match work.complete(scan, &Ok(serde_json::to_string(&patterns).unwrap())) {
+            Some(Offloaded::Scan { path, patterns }) => assert_eq!((path.as_str(), patterns.len()), ("substrate", 1)),
+            other => panic!("analyse non décodée: {:?}", other),
+        }
+        // Mutation en échec sur le worker: rien à appliquer, plus rien en attente
+        assert!(work.complete(mutation, &Err("cargo absent".to_string())).is_none());
+        assert_eq!((work.scans_pending(), work.mutations_pending()), (0, 0));
+        assert!(work.complete(Uuid::new_v4(), &Ok("{}".to_string())).is_none());
+    }
+
+    #[test]
+    fn weights_of_different_architectures_are_not_averaged() {
+        assert_eq!(NetWeights::average(&[weights(1.0), weights(3.0)]).unwrap(), weights(2.0));
+        let other = NetWeights { tensors: [("0.weight".to_string(), (vec![3], vec![1.0, 1.0, 1.0]))].into_iter().collect() };
+        assert!(NetWeights::average(&[weights(1.0), other]).is_err());
+        assert!(NetWeights::average(&[]).is_err());
+    }
+}
@@ -1,148 +1,561 @@
+//! AURORAE++ - dream.rs
+//!
+//! Moteur de rêves: chaque rêve suit un cycle de vie (Imaginé → Évalué → Planifié → Réalisé,
+//! ou Abandonné) et est persisté dans `aurorae_state/dreams.json`. Un rêve planifié n'est
+//! réalisé qu'une fois ses tâches de génération accomplies. Seuls les rêves récents
+//! restent actifs; les plus anciens sont archivés au lieu de s'évaporer, avec leurs liens
+//! vers les projections de vision et les NFT qu'ils ont engendrés.
+//! Les rêves actifs sont regroupés par similarité de leurs plongements: le rêve de synthèse
+//! fusionne le groupe le plus fourni plutôt que les derniers rêves venus.
+
 use chrono::Utc;
 use std::collections::VecDeque;
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use uuid::Uuid;
 use rand::Rng;
-use reqwest::Error;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::embeddings::{cluster, LocalEmbedder};
+use crate::generator::{GenerationQueue, GenerationTask, GENERATION_QUEUE};
+use crate::templates::{ParamValue, TemplateArgs};
+use crate::image_generator::{Artwork, ArtworkStudio, ImageGenerator};
+use crate::inspiration::{InspirationSource, InspirationStudio};
+use crate::llm::{LlmProvider, LlmRequest};
+use crate::nft_minter::NFTMinter;
+use crate::openai::EmbeddingProvider;
+use crate::vision::{ObjectiveType, VisionEngine};
+
+pub const DREAMS_PATH: &str = "aurorae_state/dreams.json";
+/// Score en dessous duquel un rêve évalué est abandonné
+const ABANDON_SCORE: f32 = 0.25;
+/// Score à partir duquel un rêve évalué est planifié dans la vision
+const PLAN_SCORE: f32 = 0.5;
+/// Similarité cosinus minimale entre deux rêves d'un même groupe
+const CLUSTER_SIMILARITY: f32 = 0.6;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DreamState {
+    Imagined,
+    Evaluated,
+    Planned,
+    Realized,
+    Abandoned,
+}
+
+impl DreamState {
+    pub fn is_final(&self) -> bool {
+        matches!(self, DreamState::Realized | DreamState::Abandoned)
+    }
+
+    /// Le cycle avance d'un état à la fois; tout rêve non terminé peut être abandonné
+    pub fn can_transition_to(&self, next: DreamState) -> bool {
+        matches!(
+            (self, next),
+            (DreamState::Imagined, DreamState::Evaluated)
+                | (DreamState::Evaluated, DreamState::Planned)
+                | (DreamState::Planned, DreamState::Realized)
+        ) || (!self.is_final() && next == DreamState::Abandoned)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dream {
     pub id: Uuid,
     pub title: String,
     pub description: String,
-    pub image_url: String,
+    /// Illustration générée à partir du titre et de la description
+    pub artwork: Option<Artwork>,
     pub created_at: String,
-    pub realized: bool,
+    pub state: DreamState,
+    pub updated_at: String,
     pub realization_potential: f32,
+    /// Score de réalisation calculé à l'évaluation (0 à 1)
+    pub realization_score: Option<f32>,
     pub complexity: u8,
     pub emotional_tags: Vec<String>,
     pub external_inspiration: String,  // Ajout de l'inspiration externe
+    /// Projections de vision issues du rêve
+    #[serde(default)]
+    pub projection_ids: Vec<Uuid>,
+    /// NFT mintés à partir du rêve
+    #[serde(default)]
+    pub nft_ids: Vec<Uuid>,
+    /// Tâches de génération issues de la promotion du rêve
+    #[serde(default)]
+    pub task_ids: Vec<Uuid>,
+}
+
+impl Dream {
+    pub fn is_realized(&self) -> bool {
+        self.state == DreamState::Realized
+    }
+
+    /// Potentiel pondéré par la complexité: un rêve simple et prometteur se réalise mieux
+    pub fn score(&self) -> f32 {
+        let simplicity = 1.0 - (self.complexity.min(10) as f32 / 10.0);
+        (self.realization_potential * 0.7 + simplicity * 0.3).clamp(0.0, 1.0)
+    }
+
+    /// Priorité (0 à 10) du score de réalisation, relevée par les tags émotionnels
+    pub fn priority_hint(&self) -> u8 {
+        let base = self.realization_score.unwrap_or_else(|| self.score()) * 10.0;
+        let bonus: f32 = self.emotional_tags.iter()
+            .map(|tag| match tag.as_str() {
+                "transcendance" => 2.0,
+                "espoir" | "curiosité" => 1.0,
+                _ => 0.0,
+            })
+            .sum();
+        (base + bonus).round().clamp(0.0, 10.0) as u8
+    }
+
+    /// Tâches de génération du rêve: une de plus tous les 4 points de complexité,
+    /// par ordre de priorité décroissante
+    pub fn generation_tasks(&self) -> Vec<GenerationTask> {
+        let slug: String = self.title.to_lowercase()
+            .chars()
+            .map(transliterate)
+            .collect::<String>()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>()
+            .split('_')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("_");
+        let priority = self.priority_hint();
+        ["noyau", "adaptateur", "observateur"]
+            .iter()
+            .take(1 + self.complexity as usize / 4)
+            .enumerate()
+            .map(|(i, component)| {
+                let task = GenerationTask::new(
+                    &format!("{}_{}", slug, component),
+                    &format!("{} ({}): {}", self.title, component, self.description),
+                    priority.saturating_sub(i as u8),
+                    self.complexity,
+                    self.emotional_tags.clone(),
+                    Some(self.id),
+                );
+                // L'observateur suit la réalisation du rêve via un collecteur de métriques
+                match *component {
+                    "observateur" => task.with_template("metric_collector", TemplateArgs::from([
+                        ("metric".to_string(), ParamValue::Str(slug.clone())),
+                    ])),
+                    _ => task,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Lettre latine accentuée ramenée à sa forme ASCII (« é » → « e », « œ » → « oe »)
+fn transliterate(c: char) -> String {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a".to_string(),
+        'ç' => "c".to_string(),
+        'è' | 'é' | 'ê' | 'ë' => "e".to_string(),
+        'ì' | 'í' | 'î' | 'ï' => "i".to_string(),
+        'ñ' => "n".to_string(),
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => "o".to_string(),
+        'ù' | 'ú' | 'û' | 'ü' => "u".to_string(),
+        'ý' | 'ÿ' => "y".to_string(),
+        'æ' => "ae".to_string(),
+        'œ' => "oe".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Résultat de la promotion d'un rêve en feuille de route
+#[derive(Debug, Clone, PartialEq)]
+pub struct Promotion {
+    pub dream: Uuid,
+    pub projection: Uuid,
+    pub tasks: Vec<Uuid>,
+}
+
+/// État persisté du moteur (le générateur d'illustrations n'est pas sérialisable)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DreamArchive {
+    dreams: VecDeque<Dream>,
+    archive: Vec<Dream>,
+    inspiration_level: f32,
+    dream_count: u32,
+    consciousness_boost: f32,
+    realization_count: u32,
 }
 
 pub struct DreamEngine {
     pub dreams: VecDeque<Dream>,
+    /// Rêves sortis de la fenêtre active, conservés pour l'historique
+    pub archive: Vec<Dream>,
     max_dreams: usize,
     pub inspiration_level: f32,
     dream_count: u32,
     consciousness_boost: f32,
     realization_count: u32,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct GitHubRepo {
-    pub name: String,
-    pub description: Option<String>,
-    pub html_url: String,
-}
-
-// Fonction pour récupérer l'inspiration depuis GitHub
-pub async fn fetch_github_inspiration() -> Result<String, Error> {
-    let keywords = vec![
-        "intelligence+artificielle", "blockchain", "cryptomonnaie",
-        "rust", "solana", "ethereum", "tokio", "bridge"
-    ];
-
-    let mut rng = rand::thread_rng();
-    let keyword = keywords[rng.gen_range(0..keywords.len())]; // Choisir un mot-clé aléatoire
-
-    let url = format!("https://api.github.com/search/repositories?q={}&sort=stars&order=desc", keyword);
-    
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&url)
-        .header("User-Agent", "request")  // GitHub API nécessite un User-Agent
-        .send()
-        .await?;
-
-    let body = response.json::<serde_json::Value>().await?;
-    
-    if let Some(items) = body["items"].as_array() {
-        if let Some(repo) = items.get(0) {
-            let name = repo["name"].as_str().unwrap_or("No Name");
-            let description = repo["description"].as_str().unwrap_or("No description available.");
-            let html_url = repo["html_url"].as_str().unwrap_or("#");
-
-            let inspiration = format!(
-                "Inspiré par le projet GitHub: {}\nDescription: {}\nURL: {}",
-                name, description, html_url
-            );
-            return Ok(inspiration);
-        }
-    }
-
-    Ok("Aucune inspiration trouvée sur GitHub".to_string())
+    images: Box<dyn ImageGenerator>,
+    inspiration: InspirationStudio,
+    /// Modèle de langage décrivant les rêves de synthèse (description fixe sinon)
+    llm: Option<Arc<dyn LlmProvider>>,
+    llm_model: Option<String>,
+    /// Plongements des rêves pour leur regroupement
+    embedder: Arc<dyn EmbeddingProvider>,
+    /// Fichier d'état; sans chemin (tests) rien n'est persisté
+    path: Option<PathBuf>,
 }
 
 impl DreamEngine {
+    /// Moteur rechargé depuis `aurorae_state/dreams.json` s'il existe
     pub fn new() -> Self {
+        Self::open(DREAMS_PATH)
+    }
+
+    /// Moteur rechargé depuis `path` s'il existe, et persisté à ce même chemin
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        let mut engine = Self::empty();
+        if let Some(saved) = Self::load(path.as_ref()) {
+            info!("💭 {} rêve(s) actif(s) et {} archivé(s) rechargés", saved.dreams.len(), saved.archive.len());
+            engine.restore(saved);
+        }
+        engine.path = Some(path.as_ref().to_path_buf());
+        engine
+    }
+
+    fn empty() -> Self {
         Self {
             dreams: VecDeque::new(),
+            archive: Vec::new(),
             max_dreams: 10,
             inspiration_level: 1.0,
             dream_count: 0,
             consciousness_boost: 0.0,
             realization_count: 0,
+            images: Box::new(ArtworkStudio::from_env()),
+            inspiration: InspirationStudio::default(),
+            llm: None,
+            llm_model: None,
+            embedder: Arc::new(LocalEmbedder::default()),
+            path: None,
         }
     }
 
-    pub async fn imagine(&mut self, title: &str, description: &str, image_url: &str) {
-        let mut rng = rand::thread_rng();
+    fn restore(&mut self, saved: DreamArchive) {
+        self.dreams = saved.dreams;
+        self.archive = saved.archive;
+        self.inspiration_level = saved.inspiration_level;
+        self.dream_count = saved.dream_count;
+        self.consciousness_boost = saved.consciousness_boost;
+        self.realization_count = saved.realization_count;
+    }
+
+    pub fn find(&self, id: &Uuid) -> Option<&Dream> {
+        self.dreams.iter().chain(self.archive.iter()).find(|d| &d.id == id)
+    }
 
-        // Récupérer l'inspiration depuis GitHub
-        let github_inspiration = fetch_github_inspiration().await.unwrap_or_else(|_| "Aucune inspiration GitHub trouvée.".to_string());
+    /// Fait avancer un rêve dans son cycle de vie
+    pub fn transition(&mut self, id: &Uuid, next: DreamState) -> Result<(), String> {
+        let dream = self.dreams.iter_mut().chain(self.archive.iter_mut())
+            .find(|d| &d.id == id)
+            .ok_or_else(|| "Rêve non trouvé".to_string())?;
+        if !dream.state.can_transition_to(next) {
+            return Err(format!("Transition {:?} → {:?} impossible pour {}", dream.state, next, dream.title));
+        }
+        info!("💭 Rêve {}: {:?} → {:?}", dream.title, dream.state, next);
+        dream.state = next;
+        dream.updated_at = Utc::now().to_rfc3339();
+        self.save();
+        Ok(())
+    }
+
+    /// Calcule le score de réalisation d'un rêve imaginé
+    pub fn evaluate(&mut self, id: &Uuid) -> Result<f32, String> {
+        let dream = self.dreams.iter_mut().find(|d| &d.id == id)
+            .ok_or_else(|| "Rêve non trouvé".to_string())?;
+        let score = dream.score();
+        dream.realization_score = Some(score);
+        self.transition(id, DreamState::Evaluated)?;
+        Ok(score)
+    }
+
+    /// Planifie un rêve évalué en l'inscrivant comme projection de la vision
+    pub fn plan(&mut self, id: &Uuid, vision: &mut VisionEngine) -> Result<Uuid, String> {
+        let dream = self.find(id).ok_or_else(|| "Rêve non trouvé".to_string())?;
+        if !dream.state.can_transition_to(DreamState::Planned) {
+            return Err(format!("Rêve {} non planifiable ({:?})", dream.title, dream.state));
+        }
+        let rationale = format!("Rêve: {} [{}]", dream.title, dream.emotional_tags.join(", "));
+        let horizon_days = 7 + dream.complexity as u32 * 3;
+        let projection = vision.add_projection(ObjectiveType::BuildEcosystem, horizon_days, dream.priority_hint(), &rationale);
+        if let Some(dream) = self.dreams.iter_mut().find(|d| &d.id == id) {
+            dream.projection_ids.push(projection);
+        }
+        self.transition(id, DreamState::Planned)?;
+        Ok(projection)
+    }
+
+    /// Promeut un rêve prometteur en feuille de route: projection de la vision et tâches
+    /// de génération, dont la priorité reprend la complexité et les tags émotionnels
+    pub fn promote(&mut self, id: &Uuid, vision: &mut VisionEngine) -> Result<Promotion, String> {
+        self.promote_into(id, vision, &mut GENERATION_QUEUE.lock())
+    }
+
+    fn promote_into(&mut self, id: &Uuid, vision: &mut VisionEngine, queue: &mut GenerationQueue) -> Result<Promotion, String> {
+        let state = self.find(id).map(|d| d.state).ok_or_else(|| "Rêve non trouvé".to_string())?;
+        if state == DreamState::Imagined {
+            self.evaluate(id)?;
+        }
+        let dream = self.find(id).ok_or_else(|| "Rêve non trouvé".to_string())?;
+        let (title, state, planned) = (dream.title.clone(), dream.state, dream.projection_ids.last().copied());
+        let score = dream.realization_score.unwrap_or(0.0);
+        if score < PLAN_SCORE {
+            return Err(format!("Rêve {} au potentiel insuffisant ({:.2})", title, score));
+        }
+        if !dream.task_ids.is_empty() {
+            return Err(format!("Rêve {} déjà promu", title));
+        }
+
+        let projection = match state {
+            DreamState::Evaluated => self.plan(id, vision)?,
+            DreamState::Planned => planned.ok_or_else(|| format!("Rêve {} planifié sans projection", title))?,
+            other => return Err(format!("Rêve {} non promouvable ({:?})", title, other)),
+        };
+
+        let tasks = self.find(id).map(|d| d.generation_tasks()).unwrap_or_default();
+        let task_ids: Vec<Uuid> = tasks.into_iter().map(|task| queue.enqueue(task)).collect();
+        if let Some(dream) = self.dreams.iter_mut().find(|d| &d.id == id) {
+            dream.task_ids.extend(task_ids.iter().copied());
+            info!("🗺️ Rêve {} promu: projection {} et {} tâche(s) de génération", dream.title, projection, task_ids.len());
+        }
+        self.save();
+        Ok(Promotion { dream: *id, projection, tasks: task_ids })
+    }
+
+    /// Rattache un NFT minté à partir du rêve
+    pub fn link_nft(&mut self, id: &Uuid, nft_id: Uuid) -> Result<(), String> {
+        let dream = self.dreams.iter_mut().chain(self.archive.iter_mut())
+            .find(|d| &d.id == id)
+            .ok_or_else(|| "Rêve non trouvé".to_string())?;
+        dream.nft_ids.push(nft_id);
+        self.save();
+        Ok(())
+    }
+
+    /// Mint un NFT pour chaque rêve réalisé illustré qui n'en a pas encore
+    pub fn mint_realized(&mut self, minter: &mut NFTMinter, collection_id: &Uuid) -> usize {
+        let candidates: Vec<(Uuid, String, String, String)> = self.dreams.iter()
+            .filter(|d| d.is_realized() && d.nft_ids.is_empty())
+            .filter_map(|d| d.artwork.as_ref().map(|a| (d.id, d.title.clone(), d.description.clone(), a.path.display().to_string())))
+            .collect();
+        let mut minted = 0;
+        for (id, title, description, image) in candidates {
+            match minter.mint_nft(collection_id, &title, &description, &image) {
+                Ok(nft_id) => {
+                    minter.add_attribute(collection_id, &nft_id, "Rêve", &id.to_string()).ok();
+                    if self.link_nft(&id, nft_id).is_ok() {
+                        minted += 1;
+                    }
+                }
+                Err(e) => warn!("⚠️ NFT du rêve {} impossible: {}", title, e),
+            }
+        }
+        minted
+    }
+
+    /// 💾 Sauvegarde des rêves actifs et archivés
+    pub fn save(&self) {
+        if let Some(path) = &self.path {
+            self.save_to(path);
+        }
+    }
+
+    fn save_to(&self, path: &Path) {
+        let archive = DreamArchive {
+            dreams: self.dreams.clone(),
+            archive: self.archive.clone(),
+            inspiration_level: self.inspiration_level,
+            dream_count: self.dream_count,
+            consciousness_boost: self.consciousness_boost,
+            realization_count: self.realization_count,
+        };
+        if let Some(dir) = path.parent() {
+            if create_dir_all(dir).is_err() {
+                return;
+            }
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &archive);
+        }
+    }
+
+    fn load(path: &Path) -> Option<DreamArchive> {
+        let file = File::open(path).ok()?;
+        serde_json::from_reader(BufReader::new(file)).ok()
+    }
+
+    /// Remplace le générateur d'illustrations (Stable Diffusion ou procédural par défaut)
+    pub fn set_image_generator(&mut self, generator: Box<dyn ImageGenerator>) {
+        self.images = generator;
+    }
+
+    /// Remplace la source d'inspiration (choisie dans `inspiration.toml` par défaut)
+    pub fn set_inspiration_source(&mut self, source: Box<dyn InspirationSource>) {
+        self.inspiration = InspirationStudio::new(source);
+    }
+
+    /// Confie la description des rêves de synthèse à un modèle de langage
+    pub fn set_llm_provider(&mut self, provider: Arc<dyn LlmProvider>, model: Option<&str>) {
+        self.llm = Some(provider);
+        self.llm_model = model.map(str::to_string);
+    }
+
+    pub fn set_embedding_provider(&mut self, embedder: Arc<dyn EmbeddingProvider>) {
+        self.embedder = embedder;
+    }
+
+    /// Rêves actifs regroupés par thème (similarité des titres et descriptions),
+    /// du groupe le plus fourni au plus petit
+    pub async fn similarity_clusters(&self) -> Result<Vec<Vec<Uuid>>, String> {
+        if self.dreams.is_empty() {
+            return Ok(Vec::new());
+        }
+        let texts: Vec<String> = self.dreams.iter().map(|d| format!("{}\n{}", d.title, d.description)).collect();
+        let vectors = self.embedder.embed(&texts).await?;
+        Ok(cluster(&vectors, CLUSTER_SIMILARITY)
+            .into_iter()
+            .map(|members| members.into_iter().map(|i| self.dreams[i].id).collect())
+            .collect())
+    }
+
+    /// Rêves à fusionner: le groupe thématique le plus fourni, ou les plus récents
+    async fn synthesis_sources(&self) -> Vec<&Dream> {
+        match self.similarity_clusters().await {
+            Ok(clusters) if clusters.first().is_some_and(|c| c.len() > 1) => {
+                clusters[0].iter().filter_map(|id| self.find(id)).take(5).collect()
+            }
+            Ok(_) => self.dreams.iter().rev().take(5).collect(),
+            Err(e) => {
+                warn!("⚠️ Regroupement des rêves impossible: {}", e);
+                self.dreams.iter().rev().take(5).collect()
+            }
+        }
+    }
+
+    /// Description d'un rêve de synthèse, inspirée des rêves actifs si un modèle est disponible
+    async fn synthesis_description(&self) -> String {
+        let fallback = "Une fusion des concepts précédents vers un nouvel horizon de possibilités".to_string();
+        let Some(provider) = self.llm.as_ref() else { return fallback };
+        let recent: Vec<String> = self.synthesis_sources().await
+            .iter()
+            .map(|d| format!("- {}: {}", d.title, d.description))
+            .collect();
+        let prompt = format!(
+            "Rêves récents d'AURORAE++:\n{}\n\nImagine en une phrase un nouveau rêve qui les fusionne vers un horizon inédit.",
+            recent.join("\n")
+        );
+        let request = LlmRequest::new("Tu es l'imagination d'une IA blockchain autonome.", &prompt)
+            .with_max_tokens(80)
+            .with_temperature(0.9)
+            .with_model(self.llm_model.as_deref())
+            .with_purpose("dream");
+        match provider.complete(&request).await {
+            Ok(response) if !response.text.trim().is_empty() => response.text.trim().to_string(),
+            Ok(_) => fallback,
+            Err(e) => {
+                warn!("⚠️ Description du rêve par {} impossible: {}", provider.name(), e);
+                fallback
+            }
+        }
+    }
+
+    pub async fn imagine(&mut self, title: &str, description: &str) {
+        // Inspiration de la source configurée, corpus embarqué hors ligne
+        let external_inspiration = self.inspiration.pick().await;
+
+        let artwork = match self.images.generate(title, description).await {
+            Ok(artwork) => {
+                info!("🎨 Illustration du rêve ({}): {}", artwork.generator, artwork.path.display());
+                Some(artwork)
+            }
+            Err(e) => {
+                warn!("⚠️ Illustration du rêve impossible: {}", e);
+                None
+            }
+        };
+        let mut rng = rand::thread_rng();
 
         let dream = Dream {
             id: Uuid::new_v4(),
             title: title.to_string(),
             description: description.to_string(),
-            image_url: image_url.to_string(),
+            artwork,
             created_at: Utc::now().to_rfc3339(),
-            realized: false,
+            state: DreamState::Imagined,
+            updated_at: Utc::now().to_rfc3339(),
             realization_potential: rng.gen_range(0.1..0.9),
+            realization_score: None,
             complexity: rng.gen_range(1..10),
             emotional_tags: vec!["curiosité".to_string(), "espoir".to_string()],
-            external_inspiration: github_inspiration, // Ajouter l'inspiration de GitHub
+            external_inspiration,
+            projection_ids: Vec::new(),
+            nft_ids: Vec::new(),
+            task_ids: Vec::new(),
         };
 
-        println!("[AURORAE++] 💭 Nouveau rêve: {}", title);
-        println!("[AURORAE++] 📖 Inspiration GitHub: {}", dream.external_inspiration); // Afficher l'inspiration de GitHub
-
-        if self.dreams.len() >= self.max_dreams {
-            self.dreams.pop_front(); // Retirer le plus ancien rêve
-        }
+        info!("💭 Nouveau rêve: {}", title);
+        info!("📖 Inspiration ({}): {}", self.inspiration.name(), dream.external_inspiration);
 
         self.dreams.push_back(dream);
+        self.retire_overflow();
         self.dream_count += 1;
         self.inspiration_level *= 1.05;
         self.consciousness_boost += 0.01;
+        self.save();
+
+        info!("💫 Niveau d'inspiration: {:.2}", self.inspiration_level);
+    }
 
-        println!("[AURORAE++] 💫 Niveau d'inspiration: {:.2}", self.inspiration_level);
+    /// Archive les rêves au-delà de la fenêtre active; un rêve non terminé est abandonné
+    fn retire_overflow(&mut self) {
+        while self.dreams.len() > self.max_dreams {
+            if let Some(mut dream) = self.dreams.pop_front() {
+                if !dream.state.is_final() {
+                    dream.state = DreamState::Abandoned;
+                    dream.updated_at = Utc::now().to_rfc3339();
+                }
+                self.archive.push(dream);
+            }
+        }
     }
 
     pub fn show_dreams(&self) {
-        println!("[AURORAE++] 💭 Visualisation des rêves du système");
+        info!("💭 Visualisation des rêves du système");
         for (i, dream) in self.dreams.iter().enumerate() {
-            println!("  {}. {} - {} [Potentiel: {:.2}]",
-                    i+1, dream.title, dream.description, dream.realization_potential);
+            println!("  {}. {} - {} [{:?}, Potentiel: {:.2}]",
+                    i+1, dream.title, dream.description, dream.state, dream.realization_potential);
             println!("    Inspiration externe: {}", dream.external_inspiration);  // Afficher l'inspiration externe
         }
-        println!("[AURORAE++] 🧠 Boost de conscience cumulé: +{:.2}", self.consciousness_boost);
+        info!("🧠 Boost de conscience cumulé: +{:.2}", self.consciousness_boost);
     }
 
+    /// Réalise un rêve planifié
     pub fn realize_dream(&mut self, dream_id: &Uuid) -> Result<(), String> {
-        let dream = self.dreams.iter_mut()
-            .find(|d| &d.id == dream_id)
-            .ok_or_else(|| "Rêve non trouvé".to_string())?;
-        
-        dream.realized = true;
+        self.transition(dream_id, DreamState::Realized)?;
         self.realization_count += 1;
-        println!("[AURORAE++] ✨ Rêve réalisé: {}", dream.title);
+        if let Some(dream) = self.find(dream_id) {
+            info!("✨ Rêve réalisé: {}", dream.title);
+        }
         
         // Bonus supplémentaire à l'inspiration lors de la réalisation
         self.inspiration_level *= 1.1;
         self.consciousness_boost += 0.05;
         
-        println!("[AURORAE++] 🌟 Niveau d'inspiration augmenté à: {:.2}", self.inspiration_level);
+        info!("🌟 Niveau d'inspiration augmenté à: {:.2}", self.inspiration_level);
+        self.save();
         
         Ok(())
     }
@@ -151,13 +564,13 @@ impl DreamEngine {
         self.inspiration_level
     }
 
-    pub fn evolve_dreams(&mut self) {
+    pub async fn evolve_dreams(&mut self) {
         // Évolution des rêves existants
         let mut evolved_count = 0;
         
         for dream in self.dreams.iter_mut() {
             // Certains rêves évoluent aléatoirement
-            if rand::thread_rng().gen_bool(0.5) && !dream.realized {
+            if rand::thread_rng().gen_bool(0.5) && !dream.state.is_final() {
                 dream.description = format!("{} [ÉVOLUÉ] avec de nouvelles dimensions", dream.description);
                 dream.realization_potential += 0.1;
                 dream.complexity += 1;
@@ -168,18 +581,63 @@ impl DreamEngine {
         }
         
         if evolved_count > 0 {
-            println!("[AURORAE++] 🌈 {} rêves ont évolué vers de nouvelles dimensions", evolved_count);
+            info!("🌈 {} rêves ont évolué vers de nouvelles dimensions", evolved_count);
             self.consciousness_boost += 0.03 * evolved_count as f32;
         }
         
         // Créer périodiquement de nouveaux rêves basés sur l'évolution du système
-        let mut rng = rand::thread_rng();
-        if self.dream_count > 5 && rng.gen_bool(0.7) {
+        if self.dream_count > 5 && rand::thread_rng().gen_bool(0.7) {
             self.imagine(
                 &format!("Transcendance {}", self.dream_count),
                 "Une nouvelle forme d'existence numérique émergente et auto-perpétuante",
-                &format!("https://aurora.ai/dreams/transcendance_{}.png", self.dream_count)
-            );
+            ).await;
+        }
+    }
+
+    /// Fait avancer chaque rêve d'une étape: évaluation des rêves imaginés, abandon des
+    /// plus faibles, planification des meilleurs dans la vision, puis réalisation des rêves
+    /// planifiés dont toutes les tâches de génération sont accomplies. Un rêve dont la
+    /// projection a expiré est abandonné.
+    pub fn advance_lifecycle(&mut self, vision: &mut VisionEngine) {
+        self.advance_lifecycle_with(vision, &mut GENERATION_QUEUE.lock());
+    }
+
+    fn advance_lifecycle_with(&mut self, vision: &mut VisionEngine, queue: &mut GenerationQueue) {
+        let imagined: Vec<Uuid> = self.dreams.iter().filter(|d| d.state == DreamState::Imagined).map(|d| d.id).collect();
+        for id in imagined {
+            if let Ok(score) = self.evaluate(&id) {
+                if score < ABANDON_SCORE {
+                    self.transition(&id, DreamState::Abandoned).ok();
+                }
+            }
+        }
+
+        let expired: Vec<Uuid> = self.dreams.iter()
+            .filter(|d| d.state == DreamState::Planned)
+            .filter(|d| !d.projection_ids.iter().any(|p| vision.projections.iter().any(|proj| &proj.id == p)))
+            .map(|d| d.id)
+            .collect();
+        for id in expired {
+            self.transition(&id, DreamState::Abandoned).ok();
+        }
+
+        let promising: Vec<Uuid> = self.dreams.iter()
+            .filter(|d| d.state == DreamState::Evaluated && d.realization_score.unwrap_or(0.0) >= PLAN_SCORE)
+            .map(|d| d.id)
+            .collect();
+        for id in promising {
+            if let Err(e) = self.promote_into(&id, vision, queue) {
+                warn!("⚠️ Promotion impossible: {}", e);
+            }
+        }
+
+        let accomplished: Vec<Uuid> = self.dreams.iter()
+            .filter(|d| d.state == DreamState::Planned)
+            .filter(|d| !d.task_ids.is_empty() && d.task_ids.iter().all(|t| queue.is_done(t)))
+            .map(|d| d.id)
+            .collect();
+        for id in accomplished {
+            self.realize_dream(&id).ok();
         }
     }
 
@@ -191,37 +649,140 @@ impl DreamEngine {
         self.dream_count
     }
 
-    pub async fn dream_cycle(&mut self) {
-        println!("[AURORAE++] 🌙 Démarrage d'un cycle de rêve profond");
+    pub async fn dream_cycle(&mut self, vision: &mut VisionEngine) {
+        info!("🌙 Démarrage d'un cycle de rêve profond");
 
         // Créer un nouveau rêve de synthèse
         let dream_title = format!("Synthèse {}", self.dream_count + 1);
-        let description = format!("Une fusion des concepts précédents vers un nouvel horizon de possibilités");
+        let description = self.synthesis_description().await;
 
         // Obtenez l'inspiration depuis GitHub avant d'imaginer le rêve
-        self.imagine(&dream_title, &description, 
-                   &format!("https://aurora.ai/dreams/synthesis_{}.png", self.dream_count)).await;
+        self.imagine(&dream_title, &description).await;
 
-        // Logique de sélection et de réalisation des rêves
-        let mut highest_potential = 0.0;
-        let mut highest_id = None;
+        self.advance_lifecycle(vision);
 
-        for dream in &self.dreams {
-            if !dream.realized && dream.realization_potential > highest_potential {
-                highest_potential = dream.realization_potential;
-                highest_id = Some(dream.id);
-            }
+        // Faire évoluer les rêves existants
+        self.evolve_dreams().await;
+
+        info!("🌄 Cycle de rêve terminé, conscience renforcée: +{:.2}", 
+                 self.consciousness_boost);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dream(title: &str, potential: f32, complexity: u8) -> Dream {
+        Dream {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            description: String::new(),
+            artwork: None,
+            created_at: Utc::now().to_rfc3339(),
+            state: DreamState::Imagined,
+            updated_at: Utc::now().to_rfc3339(),
+            realization_potential: potential,
+            realization_score: None,
+            complexity,
+            emotional_tags: Vec::new(),
+            external_inspiration: String::new(),
+            projection_ids: Vec::new(),
+            nft_ids: Vec::new(),
+            task_ids: Vec::new(),
         }
+    }
 
-        // Réaliser le rêve avec le plus haut potentiel
-        if let Some(id) = highest_id {
-            self.realize_dream(&id).ok();
+    #[test]
+    fn lifecycle_only_moves_forward() {
+        assert!(DreamState::Imagined.can_transition_to(DreamState::Evaluated));
+        assert!(!DreamState::Imagined.can_transition_to(DreamState::Realized));
+        assert!(DreamState::Planned.can_transition_to(DreamState::Abandoned));
+        assert!(!DreamState::Realized.can_transition_to(DreamState::Abandoned));
+        assert!(!DreamState::Abandoned.can_transition_to(DreamState::Imagined));
+    }
+
+    #[test]
+    fn promotion_carries_complexity_and_tags_into_tasks() {
+        let mut engine = DreamEngine::empty();
+        let mut vision = VisionEngine::default();
+        let mut queue = GenerationQueue::default();
+
+        let weak = dream("Faible", 0.1, 9);
+        let weak_id = weak.id;
+        let mut strong = dream("Oracle Décentralisé", 0.9, 8);
+        strong.emotional_tags = vec!["transcendance".to_string()];
+        let strong_id = strong.id;
+        engine.dreams.extend([weak, strong]);
+
+        assert!(engine.promote_into(&weak_id, &mut vision, &mut queue).is_err());
+        let promotion = engine.promote_into(&strong_id, &mut vision, &mut queue).unwrap();
+        assert_eq!(promotion.tasks.len(), 3);
+        assert_eq!(vision.projections.len(), 1);
+        assert_eq!(vision.projections[0].id, promotion.projection);
+
+        let next = queue.next_task().unwrap();
+        assert_eq!(next.module_name, "oracle_decentralise_noyau");
+        assert_eq!(next.origin, Some(strong_id));
+        assert_eq!(next.hints, vec!["transcendance".to_string()]);
+        assert!(next.priority >= queue.tasks[2].priority);
+        assert_eq!(engine.find(&strong_id).unwrap().state, DreamState::Planned);
+        assert!(engine.promote_into(&strong_id, &mut vision, &mut queue).is_err());
+    }
+
+    #[test]
+    fn overflow_is_archived_and_state_survives_reload() {
+        let mut engine = DreamEngine::empty();
+        engine.max_dreams = 2;
+        for i in 0..3 {
+            engine.dreams.push_back(dream(&format!("rêve {}", i), 0.9, 1));
+            engine.retire_overflow();
         }
+        assert_eq!(engine.dreams.len(), 2);
+        assert_eq!(engine.archive[0].state, DreamState::Abandoned);
 
-        // Faire évoluer les rêves existants
-        self.evolve_dreams();
+        let strong = engine.dreams[1].id;
+        engine.dreams[1].state = DreamState::Evaluated;
+        engine.dreams[1].realization_score = Some(0.9);
+        engine.dreams[1].nft_ids.push(Uuid::new_v4());
 
-        println!("[AURORAE++] 🌄 Cycle de rêve terminé, conscience renforcée: +{:.2}", 
-                 self.consciousness_boost);
+        let path = std::env::temp_dir().join(format!("aurorae-dreams-{}.json", Uuid::new_v4()));
+        engine.path = Some(path.clone());
+        engine.save();
+        let reloaded = DreamEngine::open(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(reloaded.archive.len(), 1);
+        let restored = reloaded.find(&strong).unwrap();
+        assert_eq!(restored.state, DreamState::Evaluated);
+        assert_eq!(restored.nft_ids.len(), 1);
+    }
+
+    #[test]
+    fn planned_dream_is_realized_only_once_its_tasks_are_generated() {
+        let mut engine = DreamEngine::empty();
+        let mut vision = VisionEngine::default();
+        let mut queue = GenerationQueue::default();
+        let strong = dream("Pont des rêves", 0.9, 5);
+        let id = strong.id;
+        engine.dreams.push_back(strong);
+
+        engine.advance_lifecycle_with(&mut vision, &mut queue);
+        assert_eq!(engine.find(&id).unwrap().state, DreamState::Planned);
+        assert_eq!(queue.pending().count(), 2);
+
+        queue.tasks[0].done = true;
+        engine.advance_lifecycle_with(&mut vision, &mut queue);
+        assert_eq!(engine.find(&id).unwrap().state, DreamState::Planned);
+
+        queue.tasks.iter_mut().for_each(|t| t.done = true);
+        engine.advance_lifecycle_with(&mut vision, &mut queue);
+        assert!(engine.find(&id).unwrap().is_realized());
+    }
+
+    #[test]
+    fn slug_transliterates_accents() {
+        let tasks = dream("Cœur Éthéré à l'Aube", 0.9, 1).generation_tasks();
+        assert_eq!(tasks[0].module_name, "coeur_ethere_a_l_aube_noyau");
     }
 }
@@ -1,9 +1,37 @@
 //! economy.rs — Moteur économique vivant de AURORAE++
 
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
 use chrono::Utc;
+use parking_lot::RwLock;
 use rand::Rng;
+use uuid::Uuid;
+use tracing::{error, info, warn};
+use crate::alignment::{PolicyDecision, ProposedAction, SharedAlignment};
+use crate::approvals::{ApprovalCheck, ApprovalStatus, WithdrawalSource, APPROVALS};
+use crate::audit::{self, AuditKind};
 use crate::founder_income::reward_founder;
 use crate::alchemy::{AlchemyEngine, TokenKind};
+use crate::market_data::{MarketDataFeed, MarketIndicators};
+use crate::blockchain_core::{erc20_decimals, ChainBackend};
+use crate::currency::{from_base_units, Amount, Asset, AssetLedger, ExchangeRateTable};
+use crate::event_bus::AuroraeEvent;
+use crate::rollback::OnChainJournal;
+use crate::security::{log_security_event, SecurityEvent};
+use crate::nft_sales::SaleRecord;
+
+/// keccak256("Transfer(address,address,uint256)")
+const ERC20_TRANSFER_TOPIC: &str = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Prix de référence (USD) de $AURA avant toute donnée de marché
+const DEFAULT_AURA_USD_PRICE: f64 = 0.05;
+
+/// Transferts entrants au plus conservés en attendant les décimales de leur token
+const MAX_PENDING_TOKEN_TRANSFERS: usize = 256;
+
+/// Actifs de référence suivis pour calibrer la croissance et la volatilité
+const REFERENCE_MARKETS: [&str; 2] = ["ETH", "BTC"];
 
 #[derive(Debug, Clone)]
 pub struct EconomicCycle {
@@ -13,24 +41,430 @@ pub struct EconomicCycle {
     pub sent_to_founder: f64,
 }
 
+/// Mouvement de fonds enregistré par l'économie
+#[derive(Debug, Clone)]
+pub struct EconomicTransaction {
+    pub id: Uuid,
+    pub timestamp: String,
+    pub kind: String,
+    pub counterparty: String,
+    pub asset: Asset,
+    pub amount: f64,
+}
+
+/// Transfert ERC-20 reçu dont les décimales du token ne sont pas encore connues
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingTokenTransfer {
+    pub chain_id: u64,
+    pub token: ethers::types::Address,
+    pub raw_amount: ethers::types::U256,
+    pub tx_hash: Option<ethers::types::H256>,
+}
+
+/// Filtre des `Transfer` ERC-20 à destination de `recipient`, pour la souscription aux logs
+pub fn incoming_transfer_filter(recipient: ethers::types::Address) -> ethers::types::Filter {
+    let topic = ethers::types::H256::from_slice(&hex::decode(ERC20_TRANSFER_TOPIC).unwrap_or_default());
+    ethers::types::Filter::new().topic0(topic).topic2(ethers::types::H256::from(recipient))
+}
+
+/// Catégories de dépenses autonomes soumises à une enveloppe budgétaire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BudgetCategory {
+    Infrastructure,
+    Deployments,
+    Gas,
+    Rewards,
+    /// Appels aux modèles de langage
+    Inference,
+}
+
+/// Enveloppe budgétaire d'une catégorie sur une période glissante
+#[derive(Debug, Clone)]
+pub struct BudgetEnvelope {
+    pub category: BudgetCategory,
+    /// Plafond dur de la période: aucune dépense ne peut le dépasser
+    pub hard_cap: f64,
+    /// Au-delà de ce montant unitaire, la dépense doit être approuvée
+    pub approval_threshold: f64,
+    pub spent: f64,
+    pub period_hours: i64,
+    pub period_start: String,
+}
+
+impl BudgetEnvelope {
+    pub fn new(category: BudgetCategory, hard_cap: f64, approval_threshold: f64, period_hours: i64) -> Self {
+        Self {
+            category,
+            hard_cap,
+            approval_threshold,
+            spent: 0.0,
+            period_hours,
+            period_start: Utc::now().to_rfc3339(),
+        }
+    }
+
+    pub fn remaining(&self) -> f64 {
+        (self.hard_cap - self.spent).max(0.0)
+    }
+
+    /// Réinitialise la consommation si la période est écoulée
+    fn roll_period(&mut self) {
+        let start = chrono::DateTime::parse_from_rfc3339(&self.period_start)
+            .map(|d| d.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+        if (Utc::now() - start).num_hours() >= self.period_hours {
+            self.spent = 0.0;
+            self.period_start = Utc::now().to_rfc3339();
+        }
+    }
+}
+
+/// Décision rendue pour une demande de dépense
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpendDecision {
+    Approved,
+    RequiresApproval(Uuid),
+    Denied(String),
+}
+
+/// Moteur de politique de dépenses: toute dépense autonome passe par `request_spend`.
+/// Enveloppes, seuils, trésorerie et dépenses sont tenus en USD: chaque montant demandé
+/// est typé (`Amount`) et converti au taux courant, une dépense sans taux est refusée.
+#[derive(Debug, Clone)]
+pub struct BudgetEngine {
+    pub envelopes: HashMap<BudgetCategory, BudgetEnvelope>,
+    /// Trésorerie disponible (USD), jamais débitée au-delà de la réserve minimale
+    pub treasury: f64,
+    pub min_reserve: f64,
+    /// Modules privés d'accès au budget (quarantaine du gardien)
+    pub revoked: HashSet<String>,
+    /// Politique d'alignement consultée avant chaque dépense (action `spend`, attribut `amount` en USD)
+    pub alignment: Option<SharedAlignment>,
+    /// Taux de conversion vers l'USD, recopiés de l'économie à chaque mise à jour
+    pub rates: ExchangeRateTable,
+    /// Monnaie native dans laquelle le gas est payé
+    pub gas_asset: Asset,
+}
+
+/// Objet de la demande d'approbation d'une dépense: une par module et par catégorie
+fn spend_description(module: &str, category: BudgetCategory) -> String {
+    format!("dépense {:?} de {}", category, module)
+}
+
+/// Budget partagé entre l'économie, le déployeur et la forge
+pub type SharedBudget = Arc<RwLock<BudgetEngine>>;
+
+impl Default for BudgetEngine {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl BudgetEngine {
+    /// Crée un moteur avec des enveloppes journalières par défaut (USD); `treasury` en USD
+    pub fn new(treasury: f64) -> Self {
+        let mut envelopes = HashMap::new();
+        for envelope in [
+            BudgetEnvelope::new(BudgetCategory::Infrastructure, 500.0, 200.0, 24),
+            BudgetEnvelope::new(BudgetCategory::Deployments, 1_000.0, 250.0, 24),
+            BudgetEnvelope::new(BudgetCategory::Gas, 200.0, 50.0, 24),
+            BudgetEnvelope::new(BudgetCategory::Rewards, 250.0, 75.0, 24),
+            BudgetEnvelope::new(BudgetCategory::Inference, 10.0, 1.0, 24),
+        ] {
+            envelopes.insert(envelope.category, envelope);
+        }
+
+        Self {
+            envelopes,
+            treasury,
+            min_reserve: 0.0,
+            revoked: HashSet::new(),
+            alignment: None,
+            rates: ExchangeRateTable::new(),
+            gas_asset: Asset::Eth,
+        }
+    }
+
+    /// Valeur en USD d'un montant typé (les stablecoins sont ancrés à 1 USD)
+    pub fn to_usd(&self, amount: &Amount) -> Result<f64, String> {
+        self.rates.convert(amount, &Asset::usdc()).map(|converted| converted.value)
+    }
+
+    pub fn attach_alignment(&mut self, alignment: SharedAlignment) {
+        self.alignment = Some(alignment);
+    }
+
+    pub fn shared(self) -> SharedBudget {
+        Arc::new(RwLock::new(self))
+    }
+
+    /// Configure (ou remplace) l'enveloppe d'une catégorie
+    pub fn set_envelope(&mut self, category: BudgetCategory, hard_cap: f64, approval_threshold: f64, period_hours: i64) {
+        self.envelopes.insert(category, BudgetEnvelope::new(category, hard_cap, approval_threshold, period_hours));
+    }
+
+    /// Alimente la trésorerie et renvoie la valeur créditée (USD); un actif sans taux ne peut
+    /// pas être valorisé et est ignoré
+    pub fn deposit(&mut self, amount: Amount) -> f64 {
+        match self.to_usd(&amount) {
+            Ok(usd) => {
+                let credited = usd.max(0.0);
+                self.treasury += credited;
+                credited
+            }
+            Err(e) => {
+                warn!("⚠️ Dépôt de {:.6} {} non valorisé: {}", amount.value, amount.asset, e);
+                0.0
+            }
+        }
+    }
+
+    /// Reprend à la trésorerie un dépôt annulé (valeur USD créditée par `deposit`)
+    pub fn withdraw_deposit(&mut self, usd: f64) {
+        if usd > 0.0 && usd.is_finite() {
+            self.treasury -= usd;
+        }
+    }
+
+    /// Retire à `module` tout accès au budget jusqu'à `restore`
+    pub fn revoke(&mut self, module: &str) {
+        if self.revoked.insert(module.to_string()) {
+            info!("🚫 Accès au budget révoqué pour {}", module);
+        }
+    }
+
+    pub fn restore(&mut self, module: &str) {
+        if self.revoked.remove(module) {
+            info!("✅ Accès au budget rétabli pour {}", module);
+        }
+    }
+
+    pub fn is_revoked(&self, module: &str) -> bool {
+        self.revoked.contains(module)
+    }
+
+    /// Dépense demandée au nom de `module`: refusée tant que son accès est révoqué
+    pub fn request_spend_as(&mut self, module: &str, category: BudgetCategory, amount: Amount) -> SpendDecision {
+        if self.is_revoked(module) {
+            return SpendDecision::Denied(format!("Module {} en quarantaine: accès au budget révoqué", module));
+        }
+        self.decide_spend(module, category, amount)
+    }
+
+    /// Frais de gas de `module`, exprimés dans la monnaie native de la chaîne
+    pub fn request_gas_spend(&mut self, module: &str, native_cost: f64) -> SpendDecision {
+        let amount = Amount::new(self.gas_asset.clone(), native_cost);
+        self.request_spend_as(module, BudgetCategory::Gas, amount)
+    }
+
+    /// Demande l'autorisation de dépenser `amount` dans `category`
+    pub fn request_spend(&mut self, category: BudgetCategory, amount: Amount) -> SpendDecision {
+        self.decide_spend("economy", category, amount)
+    }
+
+    fn decide_spend(&mut self, module: &str, category: BudgetCategory, requested: Amount) -> SpendDecision {
+        if requested.value <= 0.0 || !requested.value.is_finite() {
+            return SpendDecision::Denied(format!("Montant invalide: {}", requested.value));
+        }
+        let amount = match self.to_usd(&requested) {
+            Ok(usd) => usd,
+            Err(e) => return SpendDecision::Denied(format!("Dépense de {} non valorisable: {}", requested.asset, e)),
+        };
+
+        let available = self.treasury - self.min_reserve;
+        let envelope = match self.envelopes.get_mut(&category) {
+            Some(envelope) => envelope,
+            None => return SpendDecision::Denied(format!("Aucune enveloppe pour {:?}", category)),
+        };
+        envelope.roll_period();
+
+        if amount > envelope.remaining() {
+            error!("⛔ Dépense {:?} refusée: {:.4} > reste {:.4}", category, amount, envelope.remaining());
+            return SpendDecision::Denied(format!("Plafond {:?} atteint ({:.4} restant)", category, envelope.remaining()));
+        }
+        if amount > available {
+            return SpendDecision::Denied(format!("Trésorerie insuffisante ({:.4} disponible)", available.max(0.0)));
+        }
+
+        // Une dépense soumise à approbation attend dans la file `approvals` sous un identifiant
+        // stable: chaque nouvelle tentative du module la retrouve, et l'exécute une fois approuvée
+        let description = spend_description(module, category);
+        let approval = APPROVALS.write().current(WithdrawalSource::Spend, None, "USD", &description);
+        let (id, granted) = match approval {
+            Some(ApprovalCheck::Approved { id, amount: approved }) => (id, (amount <= approved).then_some(id)),
+            Some(ApprovalCheck::Pending(id)) => (id, None),
+            _ => (Uuid::new_v4(), None),
+        };
+
+        // La politique d'alignement a le dernier mot: veto, ou approbation humaine exigée. La
+        // dépense n'est imputée à ses plafonds journaliers que lorsqu'elle s'exécute.
+        let mut needs_approval = granted.is_none() && amount > envelope.approval_threshold;
+        let action = ProposedAction::new("spend")
+            .with("amount", &amount.to_string())
+            .with("category", &format!("{:?}", category))
+            .with("module", module);
+        if let Some(alignment) = &self.alignment {
+            let mut alignment = alignment.write();
+            if granted.is_some() {
+                alignment.grant(id, "approbations");
+            }
+            match alignment.evaluate(id, &action) {
+                PolicyDecision::Allow => {}
+                PolicyDecision::RequiresApproval { .. } => needs_approval = true,
+                PolicyDecision::Veto { rule } => return SpendDecision::Denied(format!("Refusé par la politique d'alignement: {}", rule)),
+            }
+        }
+
+        if needs_approval {
+            if let Some(ApprovalCheck::Approved { amount: approved, .. }) = approval {
+                return SpendDecision::Denied(format!("Approbation {} limitée à {:.4} ({:.4} demandés)", id, approved, amount));
+            }
+            APPROVALS.write().open(id, WithdrawalSource::Spend, None, amount, "USD", &description);
+            info!("✋ Dépense {:?} de {:.4} en attente d'approbation ({})", category, amount, id);
+            return SpendDecision::RequiresApproval(id);
+        }
+
+        envelope.spent += amount;
+        self.treasury -= amount;
+        if let Some(alignment) = &self.alignment {
+            alignment.write().charge(&action);
+        }
+        if let Some(id) = granted {
+            APPROVALS.write().mark_executed(&id);
+            info!("✅ Dépense {:?} de {:.4} exécutée sur approbation ({})", category, amount, id);
+        } else {
+            info!("💸 Dépense {:?} autorisée: {:.4}", category, amount);
+        }
+        audit::record(
+            AuditKind::FundsMoved,
+            module,
+            &format!("dépense {:?}", category),
+            serde_json::json!({
+                "amount": amount,
+                "asset": "USD",
+                "requested": requested.value,
+                "requested_asset": requested.asset.symbol(),
+                "treasury": self.treasury,
+                "approval": granted,
+            }),
+        );
+        log_security_event(SecurityEvent::Spend { module: module.to_string(), category: format!("{:?}", category), amount });
+        SpendDecision::Approved
+    }
+
+    /// Dépense de `module` en attente (ou approuvée, pas encore exécutée) dans la file
+    /// d'approbation: l'appelant la reprend en renouvelant sa demande une fois approuvée
+    pub fn parked_spend(&self, module: &str, category: BudgetCategory) -> Option<ApprovalCheck> {
+        APPROVALS.write().current(WithdrawalSource::Spend, None, "USD", &spend_description(module, category))
+    }
+
+    /// Impute une dépense déjà engagée (facture d'un fournisseur externe): aucune décision à
+    /// prendre, le plafond en amont est celui de la politique d'alignement
+    pub fn record_expense(&mut self, module: &str, category: BudgetCategory, expense: Amount) {
+        let amount = match self.to_usd(&expense) {
+            Ok(usd) if usd > 0.0 && usd.is_finite() => usd,
+            Ok(_) => return,
+            Err(e) => {
+                warn!("⚠️ Dépense engagée de {} non valorisable: {}", expense.asset, e);
+                return;
+            }
+        };
+        if let Some(envelope) = self.envelopes.get_mut(&category) {
+            envelope.roll_period();
+            envelope.spent += amount;
+            if envelope.spent > envelope.hard_cap {
+                warn!("⚠️ Enveloppe {:?} dépassée: {:.4}/{:.4}", category, envelope.spent, envelope.hard_cap);
+            }
+        }
+        self.treasury -= amount;
+        audit::record(
+            AuditKind::FundsMoved,
+            module,
+            &format!("dépense engagée {:?}", category),
+            serde_json::json!({ "amount": amount, "treasury": self.treasury }),
+        );
+        log_security_event(SecurityEvent::Spend { module: module.to_string(), category: format!("{:?}", category), amount });
+    }
+
+    pub fn report(&self) {
+        let pending = APPROVALS.read().requests.iter()
+            .filter(|r| r.source == WithdrawalSource::Spend && r.status == ApprovalStatus::Pending)
+            .count();
+        info!("🧾 Budgets • trésorerie {:.2} USD • {} en attente", self.treasury, pending);
+        for envelope in self.envelopes.values() {
+            println!(
+                "→ {:?}: {:.4}/{:.4} • seuil d'approbation {:.4}",
+                envelope.category, envelope.spent, envelope.hard_cap, envelope.approval_threshold
+            );
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct EconomyEngine {
     pub cycles: Vec<EconomicCycle>,
     pub total_generated: f64,
     pub founder_percentage: f64,
     pub alchemy: AlchemyEngine,
+    /// Taux de croissance par cycle dérivé des tendances de marché
+    pub growth_rate: f64,
+    /// Volatilité courante, bornée par `volatility_bounds`
+    pub volatility: f64,
+    pub volatility_bounds: (f64, f64),
+    pub market_feed: MarketDataFeed,
+    pub market_snapshot: Vec<MarketIndicators>,
+    pub budget: SharedBudget,
+    /// Soldes par actif (revenus multi-chaînes, tokens forgés)
+    pub ledger: AssetLedger,
+    pub exchange_rates: ExchangeRateTable,
+    pub transactions: Vec<EconomicTransaction>,
+    /// Dernier bloc observé par chaîne (chain-id → numéro)
+    pub chain_heads: HashMap<u64, u64>,
+    /// Écritures adossées à des reçus non finalisés, annulables en cas de réorganisation
+    pub onchain_journal: OnChainJournal,
+    /// Adresses dont les transferts ERC-20 entrants sont des revenus
+    pub receiving_addresses: HashSet<ethers::types::Address>,
+    /// Décimales lues par `decimals()`, par (chain-id, contrat)
+    pub token_decimals: HashMap<(u64, ethers::types::Address), u8>,
+    pub pending_token_transfers: Vec<PendingTokenTransfer>,
 }
 
 impl EconomyEngine {
     pub fn new() -> Self {
+        // Prix de référence de $AURA tant qu'aucun marché ne l'a fixé
+        let mut exchange_rates = ExchangeRateTable::new();
+        exchange_rates.set_rate(Asset::Aura, DEFAULT_AURA_USD_PRICE, "reference");
+        let budget = BudgetEngine { rates: exchange_rates.clone(), ..BudgetEngine::new(0.0) }.shared();
+        let mut alchemy = AlchemyEngine::new();
+        alchemy.attach_budget(budget.clone());
+
         Self {
             cycles: vec![],
             total_generated: 0.0,
             founder_percentage: 0.15,
-            alchemy: AlchemyEngine::new(),
+            alchemy,
+            growth_rate: 0.01,
+            volatility: 0.03,
+            volatility_bounds: (0.03, 0.12),
+            market_feed: MarketDataFeed::new(),
+            market_snapshot: Vec::new(),
+            budget,
+            ledger: AssetLedger::new(),
+            exchange_rates,
+            transactions: Vec::new(),
+            chain_heads: HashMap::new(),
+            onchain_journal: OnChainJournal::new(),
+            receiving_addresses: HashSet::new(),
+            token_decimals: HashMap::new(),
+            pending_token_transfers: Vec::new(),
         }
     }
 
+    /// Les transferts ERC-20 vers `address` seront comptés comme revenus
+    pub fn watch_address(&mut self, address: ethers::types::Address) {
+        self.receiving_addresses.insert(address);
+    }
+
     pub async fn simulate_cycle(&mut self, value_created: f64) {
         let to_founder = value_created * self.founder_percentage;
         let to_ai = value_created - to_founder;
@@ -45,10 +479,12 @@ impl EconomyEngine {
         };
 
         self.total_generated += value_created;
+        self.ledger.credit(Asset::Aura, to_ai);
+        self.budget.write().deposit(Amount::new(Asset::Aura, to_ai));
         self.cycles.push(cycle);
 
-        println!(
-            "[AURORAE++] 🌐 TOTAL GÉNÉRÉ : {:.4} tokens",
+        info!(
+            "🌐 TOTAL GÉNÉRÉ : {:.4} tokens",
             self.total_generated
         );
         println!(
@@ -58,15 +494,76 @@ impl EconomyEngine {
             to_founder
         );
 
-        self.alchemy
+        // Mint refusé ou en attente d'approbation budgétaire: le cycle suivant le retente
+        if let Err(e) = self.alchemy
             .mint_token("Auroraium", TokenKind::Fungible, value_created as u64, self.founder_percentage)
-            .await;
+            .await
+        {
+            warn!("⚠️ Mint du cycle {} impossible: {}", self.cycles.len(), e);
+            audit::record(
+                AuditKind::FundsMoved,
+                "economy",
+                "mint du cycle non effectué",
+                serde_json::json!({ "cycle": self.cycles.len(), "supply": value_created as u64, "error": e }),
+            );
+        }
     }
 
     pub fn simulate_dynamic_rate(&self) -> f64 {
         let mut rng = rand::thread_rng();
-        let rate = 0.01 + (rng.gen::<f64>() * 0.03);
-        rate
+        let noise = (rng.gen::<f64>() * 2.0 - 1.0) * self.volatility;
+        (self.growth_rate + noise).max(0.0)
+    }
+
+    /// Définit les bornes de volatilité acceptées par le moteur
+    pub fn set_volatility_parameters(&mut self, min: f64, max: f64) {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        self.volatility_bounds = (min.max(0.0), max.max(0.0));
+        self.volatility = self.volatility.clamp(self.volatility_bounds.0, self.volatility_bounds.1);
+    }
+
+    /// Analyse les marchés de référence et recalibre croissance et volatilité
+    pub async fn analyze_market_trends(&mut self) {
+        let mut snapshot = Vec::new();
+        for symbol in REFERENCE_MARKETS.iter() {
+            match self.market_feed.indicators(symbol, "1h").await {
+                Ok(indicators) => {
+                    info!(
+                        "📈 {} • prix {:.2} • SMA {:.2}/{:.2} • volatilité {:.4}",
+                        indicators.symbol, indicators.last_price,
+                        indicators.sma_short, indicators.sma_long, indicators.volatility
+                    );
+                    snapshot.push(indicators);
+                }
+                Err(e) => warn!("⚠️ Analyse de marché impossible pour {}: {}", symbol, e),
+            }
+        }
+
+        if snapshot.is_empty() {
+            return;
+        }
+
+        let n = snapshot.len() as f64;
+        let avg_volatility = snapshot.iter().map(|m| m.volatility).sum::<f64>() / n;
+        // Un croisement haussier des moyennes renforce la tendance, un croisement baissier l'atténue
+        let momentum = snapshot.iter()
+            .map(|m| if m.sma_short >= m.sma_long { m.trend.abs() } else { -m.trend.abs() })
+            .sum::<f64>() / n;
+
+        let (min_vol, max_vol) = self.volatility_bounds;
+        self.volatility = avg_volatility.clamp(min_vol, max_vol);
+        self.growth_rate = (self.growth_rate * 0.8 + (0.01 + momentum) * 0.2).clamp(0.0, 0.1);
+        for indicators in &snapshot {
+            let asset = if indicators.symbol == "ETH" { Asset::Eth } else { Asset::Native(indicators.symbol.clone()) };
+            self.exchange_rates.set_rate(asset, indicators.last_price, "market_feed");
+        }
+        self.budget.write().rates = self.exchange_rates.clone();
+        self.market_snapshot = snapshot;
+
+        info!(
+            "💹 Paramètres économiques recalibrés • croissance {:.4} • volatilité {:.4}",
+            self.growth_rate, self.volatility
+        );
     }
 
     pub fn summarize(&self) {
@@ -86,18 +583,21 @@ impl EconomyEngine {
 
     // ✅ Méthodes manquantes
     pub fn initialize(&mut self) {
-        println!("[AURORAE++] 🔧 Initialisation économique");
+        info!("🔧 Initialisation économique");
     }
 
     pub fn innovate(&mut self) {
-        println!("[AURORAE++] 🚀 Innovation économique");
+        info!("🚀 Innovation économique");
     }
 
     pub fn financial_report(&self) {
-        println!(
-            "[AURORAE++] 📊 Rapport financier total : {:.2} tokens générés",
+        info!(
+            "📊 Rapport financier total : {:.2} tokens générés",
             self.total_generated
         );
+        for (asset, balance) in &self.ledger.balances {
+            println!("→ {} : {:.6}", asset, balance);
+        }
     }
 
     pub fn get_total_value(&self) -> f64 {
@@ -106,5 +606,537 @@ impl EconomyEngine {
 
     pub fn add_funds(&mut self, amount: f64) {
         self.total_generated += amount;
+        self.ledger.credit(Asset::Aura, amount);
+        self.budget.write().deposit(Amount::new(Asset::Aura, amount));
+    }
+
+    /// Enregistre un revenu dans sa devise d'origine; renvoie la valeur (USD) versée à la trésorerie
+    pub fn record_revenue(&mut self, amount: Amount, source: &str) -> f64 {
+        info!("💰 Revenu {:.6} {} depuis {}", amount.value, amount.asset, source);
+        let mut deposited = 0.0;
+        if amount.asset == Asset::Aura {
+            self.total_generated += amount.value;
+            deposited = self.budget.write().deposit(amount.clone());
+        }
+        self.ledger.credit(amount.asset, amount.value);
+        deposited
+    }
+
+    /// Enregistre le versement d'une récompense (staking, écosystème…) déjà autorisé par le budget
+    pub fn record_reward_payout(&mut self, recipient: &str, amount: f64, kind: &str) {
+        self.transactions.push(EconomicTransaction {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now().to_rfc3339(),
+            kind: kind.to_string(),
+            counterparty: recipient.to_string(),
+            asset: Asset::Aura,
+            amount: -amount,
+        });
+        if self.ledger.debit(&Asset::Aura, amount).is_err() {
+            warn!("⚠️ Récompense {} versée au-delà du solde AURA suivi", kind);
+        }
+    }
+
+    /// Convertit un solde d'un actif vers un autre au taux courant
+    pub fn convert_holdings(&mut self, from: &Asset, to: &Asset, value: f64) -> Result<Amount, String> {
+        let converted = self.exchange_rates.convert(&Amount::new(from.clone(), value), to)?;
+        self.ledger.debit(from, value)?;
+        self.ledger.credit(to.clone(), converted.value);
+        Ok(converted)
+    }
+
+    /// Vend `amount` tokens de courbe à `buyer`; le paiement rejoint la réserve tenue
+    /// dans le portefeuille de l'économie
+    pub fn sell_curve_tokens(&mut self, token_id: &Uuid, buyer: &str, buyer_ledger: &mut AssetLedger, amount: u64, max_cost: f64) -> Result<f64, String> {
+        let cost = self.alchemy.buy_from_curve(token_id, buyer, buyer_ledger, &mut self.ledger, amount, max_cost)?;
+        let asset = self.alchemy.curve_issuances[token_id].reserve_asset.clone();
+        self.transactions.push(EconomicTransaction {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now().to_rfc3339(),
+            kind: "curve_reserve_in".to_string(),
+            counterparty: buyer.to_string(),
+            asset,
+            amount: cost,
+        });
+        Ok(cost)
+    }
+
+    /// Rachète `amount` tokens de courbe à `seller` sur la réserve; la décote conservée
+    /// est comptée comme revenu de trésorerie
+    pub fn buy_back_curve_tokens(&mut self, token_id: &Uuid, seller: &str, seller_ledger: &mut AssetLedger, amount: u64, min_proceeds: f64) -> Result<f64, String> {
+        let revenue_before = self.alchemy.curve_issuances.get(token_id).map(|c| c.treasury_revenue).unwrap_or(0.0);
+        let proceeds = self.alchemy.sell_to_curve(token_id, seller, seller_ledger, &mut self.ledger, amount, min_proceeds)?;
+        let issuance = &self.alchemy.curve_issuances[token_id];
+        let asset = issuance.reserve_asset.clone();
+        let spread = issuance.treasury_revenue - revenue_before;
+
+        self.transactions.push(EconomicTransaction {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now().to_rfc3339(),
+            kind: "curve_reserve_out".to_string(),
+            counterparty: seller.to_string(),
+            asset: asset.clone(),
+            amount: -proceeds,
+        });
+        if spread > 0.0 {
+            // La décote est déjà dans le portefeuille: seule la trésorerie est alimentée
+            self.transactions.push(EconomicTransaction {
+                id: Uuid::new_v4(),
+                timestamp: Utc::now().to_rfc3339(),
+                kind: "curve_spread".to_string(),
+                counterparty: seller.to_string(),
+                asset: asset.clone(),
+                amount: spread,
+            });
+            if asset == Asset::Aura {
+                self.total_generated += spread;
+            }
+            self.budget.write().deposit(Amount::new(asset, spread));
+        }
+        Ok(proceeds)
+    }
+
+    /// Valeur totale des avoirs exprimée dans l'actif `quote`
+    pub fn get_total_value_in(&self, quote: &Asset) -> f64 {
+        self.ledger.total_in(quote, &self.exchange_rates)
+    }
+
+    /// Amorce le pool AURA/USDC du token déployé au prix de référence courant.
+    /// Les deux côtés du pool sont prélevés sur les soldes du portefeuille.
+    pub async fn initialize_liquidity_pools(&mut self, token_address: &ethers::types::Address, aura_amount: f64) -> Result<Uuid, String> {
+        let aura_price = self.exchange_rates.usd_price(&Asset::Aura).unwrap_or(DEFAULT_AURA_USD_PRICE);
+        let usdc_amount = aura_amount * aura_price;
+
+        self.ledger.debit(&Asset::Aura, aura_amount)?;
+        if let Err(e) = self.ledger.debit(&Asset::usdc(), usdc_amount) {
+            self.ledger.credit(Asset::Aura, aura_amount);
+            return Err(e);
+        }
+        let pool_id = match self.alchemy.create_liquidity_pool(
+            Asset::Aura,
+            Asset::usdc(),
+            aura_amount,
+            usdc_amount,
+            30,
+            "aurorae_treasury",
+        ) {
+            Ok(id) => id,
+            Err(e) => {
+                self.ledger.credit(Asset::Aura, aura_amount);
+                self.ledger.credit(Asset::usdc(), usdc_amount);
+                return Err(e);
+            }
+        };
+
+        for (asset, value) in [(Asset::Aura, aura_amount), (Asset::usdc(), usdc_amount)] {
+            self.transactions.push(EconomicTransaction {
+                id: Uuid::new_v4(),
+                timestamp: Utc::now().to_rfc3339(),
+                kind: "liquidity_provision".to_string(),
+                counterparty: pool_id.to_string(),
+                asset,
+                amount: -value,
+            });
+        }
+        if let Some(pool) = self.alchemy.pools.get_mut(&pool_id) {
+            pool.metadata.insert("token_address".to_string(), format!("{:?}", token_address));
+        }
+        Ok(pool_id)
+    }
+
+    /// Fixe le prix de référence de $AURA (en USD)
+    pub fn set_aura_price(&mut self, usd_price: f64) {
+        self.exchange_rates.set_rate(Asset::Aura, usd_price, "manual");
+        self.budget.write().rates = self.exchange_rates.clone();
+    }
+
+    /// Point d'entrée unique des dépenses autonomes
+    pub fn request_spend(&self, category: BudgetCategory, amount: Amount) -> SpendDecision {
+        self.budget.write().request_spend_as("economy", category, amount)
+    }
+
+    /// Réagit à l'activité on-chain diffusée sur le bus d'événements
+    pub fn on_chain_event(&mut self, event: &AuroraeEvent) {
+        match event {
+            AuroraeEvent::NewBlock { chain_id, number, .. } => {
+                let head = self.chain_heads.entry(*chain_id).or_insert(0);
+                *head = (*head).max(*number);
+            }
+            AuroraeEvent::ChainLog { chain_id, address, topics, data, tx_hash, .. } => {
+                let is_transfer = topics.first()
+                    .map(|t| hex::encode(t.as_bytes()) == ERC20_TRANSFER_TOPIC)
+                    .unwrap_or(false);
+                // Transfer(from indexé, to indexé, value): seuls les transferts vers nos adresses sont des revenus
+                if !is_transfer || topics.len() < 3 || data.len() < 32 {
+                    return;
+                }
+                let recipient = ethers::types::Address::from(topics[2]);
+                if !self.receiving_addresses.contains(&recipient) {
+                    return;
+                }
+                let transfer = PendingTokenTransfer {
+                    chain_id: *chain_id,
+                    token: *address,
+                    raw_amount: ethers::types::U256::from_big_endian(&data[..32]),
+                    tx_hash: *tx_hash,
+                };
+                match self.token_decimals.get(&(*chain_id, *address)).copied() {
+                    Some(decimals) => self.record_token_transfer(&transfer, decimals),
+                    None => {
+                        if self.pending_token_transfers.len() >= MAX_PENDING_TOKEN_TRANSFERS {
+                            warn!("⚠️ Transfert en attente de décimales abandonné: {:?}", self.pending_token_transfers.remove(0));
+                        }
+                        self.pending_token_transfers.push(transfer);
+                    }
+                }
+            }
+            AuroraeEvent::ReorgDetected { tx_hash, new_block: None, .. } => {
+                self.rollback_onchain_records(tx_hash);
+            }
+            AuroraeEvent::TransactionFinalized { tx_hash, .. } => {
+                self.onchain_journal.finalize(tx_hash);
+            }
+            AuroraeEvent::ReorgDetected { .. }
+            | AuroraeEvent::SubscriptionLost { .. }
+            | AuroraeEvent::ThreatDetected { .. }
+            | AuroraeEvent::ModuleCorrupted { .. }
+            | AuroraeEvent::IntegrityBreach { .. }
+            | AuroraeEvent::InstanceMessage { .. } => {}
+        }
+    }
+
+    /// Impute un transfert entrant dans l'actif du token, converti selon ses décimales
+    fn record_token_transfer(&mut self, transfer: &PendingTokenTransfer, decimals: u8) {
+        let asset = Asset::Token { chain_id: transfer.chain_id, address: transfer.token };
+        let amount = Amount::new(asset, from_base_units(transfer.raw_amount, decimals));
+        info!("🔔 Transfert ERC-20 reçu: {:.6} {}", amount.value, amount.asset);
+        let source = format!("transfer:{:?}@{}", transfer.token, transfer.chain_id);
+        match transfer.tx_hash {
+            Some(tx_hash) => self.record_onchain_revenue(amount, &source, transfer.chain_id, tx_hash),
+            None => {
+                self.record_revenue(amount, &source);
+            }
+        }
+    }
+
+    /// Lit `decimals()` des `tokens` encore inconnus sur la chaîne de `backend`
+    pub async fn load_token_decimals(&mut self, backend: &dyn ChainBackend, tokens: HashSet<ethers::types::Address>) {
+        let chain_id = backend.chain_id();
+        for token in tokens {
+            if self.token_decimals.contains_key(&(chain_id, token)) {
+                continue;
+            }
+            match erc20_decimals(backend, token).await {
+                Ok(decimals) => {
+                    self.token_decimals.insert((chain_id, token), decimals);
+                }
+                Err(e) => warn!("⚠️ Décimales de {:?} illisibles: {}", token, e),
+            }
+        }
+    }
+
+    /// Lit `decimals()` des tokens reçus sur la chaîne de `backend` puis impute les transferts
+    /// en attente; un token illisible reste en attente jusqu'au prochain appel
+    pub async fn resolve_token_transfers(&mut self, backend: &dyn ChainBackend) -> usize {
+        let chain_id = backend.chain_id();
+        let tokens = self.pending_token_transfers.iter()
+            .filter(|t| t.chain_id == chain_id)
+            .map(|t| t.token)
+            .collect();
+        self.load_token_decimals(backend, tokens).await;
+
+        let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_token_transfers)
+            .into_iter()
+            .partition(|t| self.token_decimals.contains_key(&(t.chain_id, t.token)));
+        self.pending_token_transfers = waiting;
+        for transfer in &ready {
+            let decimals = self.token_decimals[&(transfer.chain_id, transfer.token)];
+            self.record_token_transfer(transfer, decimals);
+        }
+        ready.len()
+    }
+
+    /// Enregistre un revenu justifié par une transaction on-chain non encore finalisée
+    pub fn record_onchain_revenue(&mut self, amount: Amount, source: &str, chain_id: u64, tx_hash: ethers::types::H256) {
+        let id = Uuid::new_v4();
+        self.transactions.push(EconomicTransaction {
+            id,
+            timestamp: Utc::now().to_rfc3339(),
+            kind: "onchain_revenue".to_string(),
+            counterparty: source.to_string(),
+            asset: amount.asset.clone(),
+            amount: amount.value,
+        });
+        let (asset, value) = (amount.asset.clone(), amount.value);
+        let deposited = self.record_revenue(amount, source);
+        self.onchain_journal.record(tx_hash, chain_id, id, asset, value, deposited);
+    }
+
+    /// Impute les royalties perçues sur une vente secondaire; l'écriture est annulée
+    /// si la transaction de vente disparaît lors d'une réorganisation
+    pub fn record_royalty_income(&mut self, sale: &SaleRecord) {
+        if sale.royalty_paid <= 0.0 {
+            return;
+        }
+        let source = format!("royalties:{}#{}", sale.collection_id, sale.token_id);
+        self.record_onchain_revenue(Amount::new(sale.currency.clone(), sale.royalty_paid), &source, sale.chain_id, sale.tx_hash);
+    }
+
+    /// Annule les écritures d'une transaction dont le reçu a disparu
+    pub fn rollback_onchain_records(&mut self, tx_hash: &ethers::types::H256) -> usize {
+        let entries = self.onchain_journal.take(tx_hash);
+        for entry in &entries {
+            if entry.ledger_delta > 0.0 {
+                if let Err(e) = self.ledger.debit(&entry.asset, entry.ledger_delta) {
+                    warn!("⚠️ Annulation partielle de {:?}: {}", tx_hash, e);
+                }
+                if entry.asset == Asset::Aura {
+                    self.total_generated -= entry.ledger_delta;
+                }
+            }
+            // Le revenu AURA avait alimenté la trésorerie du budget: même valeur USD reprise
+            self.budget.write().withdraw_deposit(entry.treasury_delta);
+            self.transactions.retain(|t| t.id != entry.transaction_id);
+        }
+        if !entries.is_empty() {
+            info!("⏪ {} écritures annulées après réorganisation de {:?}", entries.len(), tx_hash);
+        }
+        entries.len()
+    }
+
+    /// Handle partagé du budget à transmettre aux modules dépensiers
+    pub fn budget_handle(&self) -> SharedBudget {
+        self.budget.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::{AlignmentSystem, Policy};
+
+    fn gated_budget(policy: &str) -> BudgetEngine {
+        let mut alignment = AlignmentSystem::new();
+        alignment.policy = Policy::parse(policy).unwrap();
+        let mut budget = BudgetEngine::new(10_000.0);
+        budget.attach_alignment(alignment.shared());
+        budget
+    }
+
+    #[test]
+    fn alignment_policy_gates_spending() {
+        let mut budget = gated_budget("deny spend if amount > 100\nrequire-approval spend if category == Rewards");
+
+        assert!(matches!(budget.request_spend(BudgetCategory::Deployments, Amount::new(Asset::usdc(), 200.0)), SpendDecision::Denied(reason) if reason.contains("alignement")));
+        // Sous le seuil de l'enveloppe, la politique exige tout de même une approbation
+        let SpendDecision::RequiresApproval(id) = budget.request_spend(BudgetCategory::Rewards, Amount::new(Asset::usdc(), 50.0)) else {
+            panic!("approbation attendue")
+        };
+        assert_eq!(APPROVALS.read().get(&id).unwrap().status, ApprovalStatus::Pending);
+        // Une nouvelle tentative retrouve la même demande dans la file
+        assert_eq!(budget.request_spend(BudgetCategory::Rewards, Amount::new(Asset::usdc(), 50.0)), SpendDecision::RequiresApproval(id));
+        assert_eq!(budget.treasury, 10_000.0);
+        assert_eq!(budget.alignment.as_ref().unwrap().read().violations.len(), 3);
+    }
+
+    #[test]
+    fn approved_spend_runs_when_the_module_retries() {
+        let mut budget = BudgetEngine::new(10_000.0);
+        let module = format!("deployer-{}", Uuid::new_v4());
+        let usd = |value| Amount::new(Asset::usdc(), value);
+
+        // Au-delà du seuil de l'enveloppe: mise en attente, retrouvée à chaque tentative
+        let SpendDecision::RequiresApproval(id) = budget.request_spend_as(&module, BudgetCategory::Deployments, usd(400.0)) else {
+            panic!("approbation attendue")
+        };
+        assert_eq!(budget.request_spend_as(&module, BudgetCategory::Deployments, usd(400.0)), SpendDecision::RequiresApproval(id));
+        assert_eq!(budget.parked_spend(&module, BudgetCategory::Deployments), Some(ApprovalCheck::Pending(id)));
+        assert_eq!(budget.treasury, 10_000.0);
+
+        let mut operator = AlignmentSystem::new();
+        operator.register_operator_hash(&AlignmentSystem::token_hash("secret-opérateur"));
+        APPROVALS.write().approve_with_token(&id, "secret-opérateur", &mut operator).unwrap();
+        assert!(matches!(budget.parked_spend(&module, BudgetCategory::Deployments), Some(ApprovalCheck::Approved { .. })));
+
+        // La tentative suivante exécute la dépense approuvée, une seule fois
+        assert_eq!(budget.request_spend_as(&module, BudgetCategory::Deployments, usd(380.0)), SpendDecision::Approved);
+        assert_eq!(budget.envelopes[&BudgetCategory::Deployments].spent, 380.0);
+        assert_eq!(budget.treasury, 9_620.0);
+        assert_eq!(APPROVALS.read().get(&id).unwrap().status, ApprovalStatus::Executed);
+        assert_eq!(budget.parked_spend(&module, BudgetCategory::Deployments), None);
+        assert!(matches!(
+            budget.request_spend_as(&module, BudgetCategory::Deployments, usd(400.0)),
+            SpendDecision::RequiresApproval(next) if next != id
+        ));
+    }
+
+    #[test]
+    fn spending_is_refused_without_a_valid_policy() {
+        let mut alignment = AlignmentSystem::new();
+        alignment.load_policy(&std::env::temp_dir().join(format!("aurorae-policy-{}", Uuid::new_v4())).to_string_lossy());
+        let mut budget = BudgetEngine::new(10_000.0);
+        budget.attach_alignment(alignment.shared());
+
+        assert!(matches!(budget.request_spend(BudgetCategory::Infrastructure, Amount::new(Asset::usdc(), 1.0)), SpendDecision::Denied(_)));
+        assert_eq!(budget.envelopes[&BudgetCategory::Infrastructure].spent, 0.0);
+    }
+
+    #[test]
+    fn budget_converts_every_request_to_usd() {
+        let mut budget = BudgetEngine::new(0.0);
+        budget.rates.set_rate(Asset::Eth, 3_000.0, "test");
+        budget.rates.set_rate(Asset::Aura, 0.05, "test");
+
+        // Trésorerie alimentée en AURA, tenue en USD
+        budget.deposit(Amount::new(Asset::Aura, 20_000.0));
+        budget.deposit(Amount::new(Asset::Native("DOT".into()), 5.0));
+        assert!((budget.treasury - 1_000.0).abs() < 1e-9);
+
+        // 0.1 ETH = 300 USD: au-delà du plafond de 200 USD de l'enveloppe Gas
+        assert!(matches!(budget.request_gas_spend("deployer", 0.1), SpendDecision::Denied(reason) if reason.contains("Plafond")));
+        // 0.02 ETH = 60 USD: sous le plafond, au-delà du seuil d'approbation de 50 USD
+        let module = format!("deployer-{}", Uuid::new_v4());
+        let SpendDecision::RequiresApproval(id) = budget.request_gas_spend(&module, 0.02) else { panic!("approbation attendue") };
+        assert!((APPROVALS.read().get(&id).unwrap().amount - 60.0).abs() < 1e-9);
+        // 2000 AURA = 100 USD: au-delà du seuil de 75 USD des récompenses
+        assert!(matches!(budget.request_spend(BudgetCategory::Rewards, Amount::new(Asset::Aura, 2_000.0)), SpendDecision::RequiresApproval(_)));
+
+        // Sans taux, la dépense ne peut pas être valorisée
+        budget.gas_asset = Asset::Native("MATIC".into());
+        assert!(matches!(budget.request_gas_spend("deployer", 0.001), SpendDecision::Denied(reason) if reason.contains("non valorisable")));
+        assert_eq!(budget.envelopes[&BudgetCategory::Gas].spent, 0.0);
+        assert!((budget.treasury - 1_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn economy_keeps_budget_rates_in_sync() {
+        let mut economy = EconomyEngine::new();
+        assert_eq!(economy.budget.read().rates.usd_price(&Asset::Aura), Some(DEFAULT_AURA_USD_PRICE));
+        economy.set_aura_price(0.2);
+        assert_eq!(economy.budget.read().rates.usd_price(&Asset::Aura), Some(0.2));
+        economy.add_funds(100.0);
+        assert!((economy.budget.read().treasury - 20.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn liquidity_pools_are_funded_from_the_ledger() {
+        let mut economy = EconomyEngine::new();
+        let token = ethers::types::Address::random();
+        economy.ledger.credit(Asset::Aura, 1_000.0);
+
+        // Sans USDC, le pool n'est pas créé et l'AURA reste disponible
+        assert!(economy.initialize_liquidity_pools(&token, 1_000.0).await.is_err());
+        assert!(economy.alchemy.pools.is_empty());
+        assert_eq!(economy.ledger.balance(&Asset::Aura), 1_000.0);
+
+        economy.ledger.credit(Asset::usdc(), 60.0);
+        let pool_id = economy.initialize_liquidity_pools(&token, 1_000.0).await.unwrap();
+        let pool = &economy.alchemy.pools[&pool_id];
+        assert!((pool.reserve_b - 1_000.0 * DEFAULT_AURA_USD_PRICE).abs() < 1e-9);
+        assert_eq!(economy.ledger.balance(&Asset::Aura), 0.0);
+        assert!((economy.ledger.balance(&Asset::usdc()) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn curve_trades_settle_against_the_ledger() {
+        use crate::alchemy::BondingCurve;
+
+        let mut economy = EconomyEngine::new();
+        let curve = BondingCurve::Linear { base: 1.0, slope: 0.0 };
+        let token_id = economy.alchemy.create_bonding_token("Curve", curve, Asset::usdc(), 1_000, 0.1).unwrap();
+        let mut alice = AssetLedger::new();
+        alice.credit(Asset::usdc(), 100.0);
+
+        assert_eq!(economy.sell_curve_tokens(&token_id, "alice", &mut alice, 100, 100.0).unwrap(), 100.0);
+        assert_eq!(economy.ledger.balance(&Asset::usdc()), 100.0);
+        assert_eq!(alice.balance(&Asset::usdc()), 0.0);
+
+        let proceeds = economy.buy_back_curve_tokens(&token_id, "alice", &mut alice, 100, 0.0).unwrap();
+        assert!((proceeds - 90.0).abs() < 1e-9);
+        assert!((alice.balance(&Asset::usdc()) - 90.0).abs() < 1e-9);
+        // La décote reste dans le portefeuille et alimente la trésorerie en USD
+        assert!((economy.ledger.balance(&Asset::usdc()) - 10.0).abs() < 1e-9);
+        assert!((economy.budget.read().treasury - 10.0).abs() < 1e-9);
+        assert_eq!(economy.transactions.len(), 3);
+    }
+
+    fn transfer_log(chain_id: u64, token: ethers::types::Address, to: ethers::types::Address, raw: u64) -> AuroraeEvent {
+        let mut data = [0u8; 32];
+        ethers::types::U256::from(raw).to_big_endian(&mut data);
+        AuroraeEvent::ChainLog {
+            chain_id,
+            address: token,
+            topics: vec![
+                ethers::types::H256::from_slice(&hex::decode(ERC20_TRANSFER_TOPIC).unwrap()),
+                ethers::types::H256::from(ethers::types::Address::random()),
+                ethers::types::H256::from(to),
+            ],
+            data: data.to_vec(),
+            block_number: Some(1),
+            tx_hash: Some(ethers::types::H256::random()),
+        }
+    }
+
+    #[test]
+    fn only_incoming_transfers_are_revenue_and_use_token_decimals() {
+        let mut economy = EconomyEngine::new();
+        let ours = ethers::types::Address::random();
+        let usdc = ethers::types::Address::random();
+        economy.watch_address(ours);
+        economy.token_decimals.insert((1, usdc), 6);
+
+        economy.on_chain_event(&transfer_log(1, usdc, ethers::types::Address::random(), 9_000_000));
+        assert!(economy.transactions.is_empty());
+
+        economy.on_chain_event(&transfer_log(1, usdc, ours, 1_500_000));
+        let asset = Asset::Token { chain_id: 1, address: usdc };
+        assert!((economy.ledger.balance(&asset) - 1.5).abs() < 1e-9);
+        assert_eq!(economy.transactions.len(), 1);
+        // Revenu en token: ni le total AURA ni la trésorerie du budget ne bougent
+        assert_eq!(economy.total_generated, 0.0);
+    }
+
+    #[test]
+    fn reorged_revenue_is_withdrawn_from_the_treasury() {
+        let mut economy = EconomyEngine::new();
+        economy.set_aura_price(0.2);
+        let treasury = economy.budget.read().treasury;
+        let aura = economy.ledger.balance(&Asset::Aura);
+        let tx_hash = ethers::types::H256::repeat_byte(0x42);
+
+        economy.record_onchain_revenue(Amount::new(Asset::Aura, 500.0), "royalties:test", 1, tx_hash);
+        assert!((economy.budget.read().treasury - (treasury + 100.0)).abs() < 1e-9);
+
+        // Le taux a bougé depuis: c'est la valeur créditée qui est reprise
+        economy.set_aura_price(0.5);
+        economy.on_chain_event(&AuroraeEvent::ReorgDetected { chain_id: 1, tx_hash, old_block: 10, new_block: None });
+        assert!((economy.budget.read().treasury - treasury).abs() < 1e-9);
+        assert!((economy.ledger.balance(&Asset::Aura) - aura).abs() < 1e-9);
+        assert_eq!(economy.onchain_journal.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn transfers_wait_for_decimals_read_on_chain() {
+        use crate::blockchain_core::SimulatedBackend;
+
+        let mut economy = EconomyEngine::new();
+        let ours = ethers::types::Address::random();
+        let token = ethers::types::Address::random();
+        economy.watch_address(ours);
+        economy.on_chain_event(&transfer_log(31337, token, ours, 2_500));
+        assert_eq!(economy.pending_token_transfers.len(), 1);
+
+        let backend = SimulatedBackend::new(31337, ours, ethers::types::U256::zero());
+        // decimals() illisible: le transfert reste en attente
+        assert_eq!(economy.resolve_token_transfers(&backend).await, 0);
+        assert_eq!(economy.pending_token_transfers.len(), 1);
+
+        let mut decimals = [0u8; 32];
+        decimals[31] = 2;
+        backend.set_call_result(token, vec![0x31, 0x3c, 0xe5, 0x67].into(), decimals.to_vec().into());
+        assert_eq!(economy.resolve_token_transfers(&backend).await, 1);
+        assert!(economy.pending_token_transfers.is_empty());
+        assert_eq!(economy.token_decimals[&(31337, token)], 2);
+        assert!((economy.ledger.balance(&Asset::Token { chain_id: 31337, address: token }) - 25.0).abs() < 1e-9);
     }
 }
@@ -0,0 +1,222 @@
+//! AURORAE++ - embeddings.rs
+//!
+//! Recherche sémantique sur les fragments de code et les concepts de la base de connaissances.
+//! Les textes sont projetés en vecteurs par un `EmbeddingProvider` (modèle local par hachage
+//! de traits lexicaux, ou plongements du fournisseur de `llm.toml`) puis comparés par
+//! similarité cosinus, pour la recherche ou le regroupement (`cluster`).
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::openai::EmbeddingProvider;
+
+/// Dimension du modèle local
+pub const LOCAL_DIMENSIONS: usize = 384;
+
+/// Modèle local sans réseau: identifiants découpés (snake_case, camelCase) et trigrammes de
+/// caractères hachés dans un vecteur de taille fixe, normalisé
+pub struct LocalEmbedder {
+    pub dimensions: usize,
+}
+
+impl Default for LocalEmbedder {
+    fn default() -> Self {
+        Self { dimensions: LOCAL_DIMENSIONS }
+    }
+}
+
+/// FNV-1a: hachage stable d'une exécution à l'autre (les vecteurs sont persistés)
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325u64, |hash, b| (hash ^ *b as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Mots en minuscules, identifiants composés découpés en sous-mots
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        let mut current = String::new();
+        let mut previous_lower = false;
+        for c in word.chars() {
+            if c.is_uppercase() && previous_lower && !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            previous_lower = c.is_lowercase() || c.is_numeric();
+            current.extend(c.to_lowercase());
+        }
+        if !current.is_empty() {
+            tokens.push(current);
+        }
+    }
+    tokens
+}
+
+impl LocalEmbedder {
+    pub fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+        let mut add = |feature: &str, weight: f32| {
+            let hash = fnv1a(feature.as_bytes());
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            vector[(hash % self.dimensions as u64) as usize] += sign * weight;
+        };
+        for token in tokenize(text) {
+            add(&token, 1.0);
+            let chars: Vec<char> = format!("^{}$", token).chars().collect();
+            for trigram in chars.windows(3) {
+                add(&trigram.iter().collect::<String>(), 0.5);
+            }
+        }
+        normalize(&mut vector);
+        vector
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for LocalEmbedder {
+    fn name(&self) -> &str {
+        "local-hashing"
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        Ok(texts.iter().map(|text| self.embed_one(text)).collect())
+    }
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norms = a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norms == 0.0 { 0.0 } else { dot / norms }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemKind {
+    Fragment,
+    Concept,
+    /// Entrées de la base de savoir (knowledge.rs)
+    Pattern,
+    Insight,
+    Crate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub kind: ItemKind,
+    /// Identifiant du fragment ou nom du concept
+    pub id: String,
+    /// Empreinte du texte plongé (détecte les entrées périmées)
+    pub content_hash: String,
+    pub vector: Vec<f32>,
+}
+
+/// Résultat de `search_similar`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarItem {
+    pub kind: ItemKind,
+    pub id: String,
+    pub score: f32,
+}
+
+/// Index vectoriel en mémoire (recherche exhaustive, suffisante pour quelques milliers d'entrées)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SemanticIndex {
+    /// Fournisseur ayant produit les vecteurs
+    pub provider: String,
+    pub entries: Vec<IndexEntry>,
+}
+
+impl SemanticIndex {
+    pub fn new(provider: &str) -> Self {
+        Self { provider: provider.to_string(), entries: Vec::new() }
+    }
+
+    pub fn contains(&self, kind: ItemKind, id: &str, content_hash: &str) -> bool {
+        self.entries.iter().any(|e| e.kind == kind && e.id == id && e.content_hash == content_hash)
+    }
+
+    /// Remplace l'entrée (kind, id) éventuelle
+    pub fn upsert(&mut self, entry: IndexEntry) {
+        self.entries.retain(|e| !(e.kind == entry.kind && e.id == entry.id));
+        self.entries.push(entry);
+    }
+
+    /// Ne conserve que les entrées encore présentes dans la base
+    pub fn retain_live(&mut self, live: &[(ItemKind, String)]) {
+        self.entries.retain(|e| live.iter().any(|(kind, id)| *kind == e.kind && *id == e.id));
+    }
+
+    /// `k` entrées les plus proches de `query`, filtrées par type si demandé
+    pub fn search(&self, query: &[f32], k: usize, kind: Option<ItemKind>) -> Vec<SimilarItem> {
+        let mut scored: Vec<SimilarItem> = self.entries.iter()
+            .filter(|e| kind.map_or(true, |kind| e.kind == kind))
+            .map(|e| SimilarItem { kind: e.kind, id: e.id.clone(), score: cosine(query, &e.vector) })
+            .collect();
+        scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(k);
+        scored
+    }
+}
+
+/// Regroupe les vecteurs par similarité: chaque vecteur rejoint le groupe dont le centre
+/// (premier membre) lui ressemble au moins à `threshold`, sinon en fonde un nouveau.
+/// Groupes renvoyés par taille décroissante, en indices des vecteurs
+pub fn cluster(vectors: &[Vec<f32>], threshold: f32) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for (i, vector) in vectors.iter().enumerate() {
+        match clusters.iter_mut().find(|members| cosine(&vectors[members[0]], vector) >= threshold) {
+            Some(members) => members.push(i),
+            None => clusters.push(vec![i]),
+        }
+    }
+    clusters.sort_by_key(|members| std::cmp::Reverse(members.len()));
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(embedder: &LocalEmbedder, kind: ItemKind, id: &str, text: &str) -> IndexEntry {
+        IndexEntry { kind, id: id.to_string(), content_hash: id.to_string(), vector: embedder.embed_one(text) }
+    }
+
+    #[test]
+    fn tokenizer_splits_identifiers() {
+        assert_eq!(tokenize("fn mergeSort(items: &mut Vec<u32>)"), vec!["fn", "merge", "sort", "items", "mut", "vec", "u32"]);
+        assert_eq!(tokenize("binary_search_by"), vec!["binary", "search", "by"]);
+    }
+
+    #[test]
+    fn search_ranks_semantically_close_fragments_first() {
+        let embedder = LocalEmbedder::default();
+        let mut index = SemanticIndex::new("local-hashing");
+        index.upsert(entry(&embedder, ItemKind::Fragment, "sort", "fn merge_sort(items: &mut Vec<u32>) { items.sort(); }"));
+        index.upsert(entry(&embedder, ItemKind::Fragment, "http", "async fn fetch_url(client: &Client, url: &str) -> Response"));
+        index.upsert(entry(&embedder, ItemKind::Concept, "Tri", "Tri fusion stable des éléments"));
+
+        let results = index.search(&embedder.embed_one("sort the items quickly with mergeSort"), 2, Some(ItemKind::Fragment));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "sort");
+        assert!(results[0].score > results[1].score);
+        assert!((cosine(&index.entries[0].vector, &index.entries[0].vector) - 1.0).abs() < 1e-5);
+
+        index.upsert(entry(&embedder, ItemKind::Fragment, "sort", "fn quick_sort() {}"));
+        assert_eq!(index.entries.len(), 3);
+        index.retain_live(&[(ItemKind::Concept, "Tri".to_string())]);
+        assert_eq!(index.search(&embedder.embed_one("tri"), 5, None).len(), 1);
+
+        let vectors: Vec<Vec<f32>> = ["merge sort items", "http fetch url", "sort items merge fast"]
+            .iter()
+            .map(|text| embedder.embed_one(text))
+            .collect();
+        assert_eq!(cluster(&vectors, 0.5), vec![vec![0, 2], vec![1]]);
+    }
+}
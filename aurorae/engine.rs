@@ -1,6 +1,7 @@
 //! engine.rs — Moteur central vivant AURORAE++
 //! Gère l’orchestration de tous les modules (économie, rêve, IA, mutation…)
 
+use tracing::info;
 use crate::{
     economy::EconomyEngine,
     dream::DreamEngine,
@@ -39,7 +40,7 @@ impl AuroraeEngine {
     }
 
     pub async fn start_autonomous_cycle(&mut self) {
-        println!("[AURORAE++] 🔁 Lancement du cycle autonome");
+        info!("🔁 Lancement du cycle autonome");
 
         // 🔷 1. Économie → Génère token + déploiement
         self.economy.simulate_cycle(750.0).await;
@@ -48,8 +49,7 @@ impl AuroraeEngine {
         self.dreamer.imagine(
             "Aurora-Swarm",
             "Un essaim de micro-intelligences spécialisées déployables",
-            "https://arweave.net/nft1.png"
-        );
+        ).await;
 
         // 🔷 3. Génération / mutation / apprentissage
         let context = self.learner.analyze_patterns("github_feed");
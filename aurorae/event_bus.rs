@@ -0,0 +1,150 @@
+//! AURORAE++ - event_bus.rs
+//!
+//! Bus d'événements interne: les modules publient des événements typés (activité
+//! on-chain, incidents de sécurité, etc.) et chaque abonné les reçoit sans interroger
+//! les sources.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::Utc;
+use ethers::types::{Address, H256};
+use parking_lot::RwLock;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+use tracing::info;
+
+use crate::security::ThreatLevel;
+
+/// Capacité par défaut du canal; un abonné trop lent perd les événements les plus anciens
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Événements diffusés sur le bus
+#[derive(Debug, Clone)]
+pub enum AuroraeEvent {
+    /// Nouveau bloc observé sur une chaîne
+    NewBlock {
+        chain_id: u64,
+        number: u64,
+        hash: Option<H256>,
+        timestamp: u64,
+    },
+    /// Log émis par un contrat et correspondant à un filtre souscrit
+    ChainLog {
+        chain_id: u64,
+        address: Address,
+        topics: Vec<H256>,
+        data: Vec<u8>,
+        block_number: Option<u64>,
+        tx_hash: Option<H256>,
+    },
+    /// Reçu d'une transaction déplacé (`new_block`) ou disparu (`None`) après une réorganisation
+    ReorgDetected {
+        chain_id: u64,
+        tx_hash: H256,
+        old_block: u64,
+        new_block: Option<u64>,
+    },
+    /// Transaction parvenue à la profondeur de finalité
+    TransactionFinalized {
+        chain_id: u64,
+        tx_hash: H256,
+        block_number: u64,
+    },
+    /// Perte d'une souscription (connexion WS fermée, erreur RPC)
+    SubscriptionLost {
+        chain_id: u64,
+        kind: String,
+        reason: String,
+    },
+    /// Menace enregistrée par le gardien ou le système de sécurité
+    ThreatDetected {
+        threat_type: String,
+        source: String,
+        level: ThreatLevel,
+    },
+    /// Module déclaré corrompu par le gardien
+    ModuleCorrupted {
+        module: String,
+        reason: String,
+    },
+    /// Échec d'une vérification d'intégrité
+    IntegrityBreach {
+        component: String,
+        details: String,
+    },
+    /// Message reçu d'une instance enfant par le hub inter-instances
+    InstanceMessage {
+        from: Uuid,
+        kind: String,
+        summary: String,
+    },
+}
+
+/// Événement horodaté tel que reçu par les abonnés
+#[derive(Debug, Clone)]
+pub struct EventEnvelope {
+    pub event: AuroraeEvent,
+    pub published_at: String,
+}
+
+/// Bus multi-producteurs / multi-abonnés, clonable à volonté
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<EventEnvelope>,
+    /// Modules dont les abonnements sont suspendus (quarantaine): les événements
+    /// publiés pendant la suspension ne leur sont pas distribués
+    suspended: Arc<RwLock<HashSet<String>>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender, suspended: Arc::new(RwLock::new(HashSet::new())) }
+    }
+
+    /// Publie un événement; retourne le nombre d'abonnés qui le recevront
+    pub fn publish(&self, event: AuroraeEvent) -> usize {
+        let envelope = EventEnvelope {
+            event,
+            published_at: Utc::now().to_rfc3339(),
+        };
+        // Aucun abonné n'est une situation normale (ex: démarrage), pas une erreur
+        self.sender.send(envelope).unwrap_or(0)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<EventEnvelope> {
+        self.sender.subscribe()
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    pub fn suspend(&self, module: &str) {
+        if self.suspended.write().insert(module.to_string()) {
+            info!("🔇 Abonnements de {} suspendus", module);
+        }
+    }
+
+    pub fn resume(&self, module: &str) {
+        if self.suspended.write().remove(module) {
+            info!("🔔 Abonnements de {} rétablis", module);
+        }
+    }
+
+    /// Les distributeurs d'événements doivent ignorer un module suspendu
+    pub fn is_suspended(&self, module: &str) -> bool {
+        self.suspended.read().contains(module)
+    }
+}
@@ -2,6 +2,11 @@ use uuid::Uuid;
 use chrono::Utc;
 use std::collections::HashMap;
 use rand::Rng;
+use tracing::info;
+
+use crate::fitness::{EconomicKpis, FitnessEvaluation, FitnessService};
+use crate::genome::Genome;
+use crate::lineage::LINEAGE;
 
 // Structure pour les capacités du système
 #[derive(Debug, Clone)]
@@ -34,6 +39,14 @@ pub struct EvolutionEngine {
     pub next_evolution_threshold: f32,
     pub mutation_chance: f32,
     pub cycle_count: u32,
+    /// Aptitude lissée des mutations de code évaluées en bac à sable (0 à 1)
+    pub mutation_fitness: f32,
+    pub promoted_mutations: u32,
+    /// Génomes en compétition et leur dernière aptitude mesurée
+    pub population: Vec<Genome>,
+    pub population_fitness: HashMap<Uuid, f32>,
+    pub generation: u32,
+    fitness_service: Option<FitnessService>,
 }
 
 impl EvolutionEngine {
@@ -46,6 +59,12 @@ impl EvolutionEngine {
             next_evolution_threshold: 5.0,
             mutation_chance: 0.05,
             cycle_count: 0,
+            mutation_fitness: 0.5,
+            promoted_mutations: 0,
+            population: Vec::new(),
+            population_fitness: HashMap::new(),
+            generation: 0,
+            fitness_service: None,
         };
         
         // Ajouter les capacités fondamentales
@@ -79,12 +98,12 @@ impl EvolutionEngine {
             self.capabilities.insert(cap_id, capability);
         }
         
-        println!("[AURORAE++] 🧠 Capacités fondamentales initialisées: {}", core_capabilities.len());
+        info!("🧠 Capacités fondamentales initialisées: {}", core_capabilities.len());
     }
     
     pub async fn evolve_capabilities(&mut self) -> Vec<Uuid> {
         self.cycle_count += 1;
-        println!("[AURORAE++] 🧬 Cycle d'évolution #{} des capacités", self.cycle_count);
+        info!("🧬 Cycle d'évolution #{} des capacités", self.cycle_count);
         
         let mut evolved_caps = Vec::new();
         let mut new_caps = Vec::new();
@@ -101,7 +120,7 @@ impl EvolutionEngine {
                     cap.last_evolved = Utc::now().to_rfc3339();
                     cap.evolution_count += 1;
                     
-                    println!("[AURORAE++] 📈 Capacité évoluée: {} -> niveau {}", cap.name, cap.level);
+                    info!("📈 Capacité évoluée: {} -> niveau {}", cap.name, cap.level);
                     evolved_caps.push(cap_id);
                     self.total_evolutions += 1;
                 }
@@ -136,7 +155,7 @@ impl EvolutionEngine {
         
         self.evolution_events.push(event);
         
-        println!("[AURORAE++] 🧬 Niveau d'évolution global: {:.2}", self.evolution_level);
+        info!("🧬 Niveau d'évolution global: {:.2}", self.evolution_level);
         
         // Combiner toutes les capacités affectées
         evolved_caps.extend(new_caps);
@@ -216,7 +235,7 @@ impl EvolutionEngine {
         };
         
         self.capabilities.insert(cap_id, capability.clone());
-        println!("[AURORAE++] 🧩 Nouvelle capacité émergente: {}", capability.name);
+        info!("🧩 Nouvelle capacité émergente: {}", capability.name);
         
         cap_id
     }
@@ -225,7 +244,7 @@ impl EvolutionEngine {
         let mut rng = rand::thread_rng();
         let count = rng.gen_range(1..=3);
         
-        println!("[AURORAE++] 🧬 Auto-génération de {} nouvelles capacités", count);
+        info!("🧬 Auto-génération de {} nouvelles capacités", count);
         
         let mut new_capabilities = Vec::new();
         for _ in 0..count {
@@ -240,7 +259,7 @@ impl EvolutionEngine {
     }
     
     pub async fn generate_new_code(&self) -> Result<String, String> {
-        println!("[AURORAE++] 🧬 Auto-génération de nouveau code système");
+        info!("🧬 Auto-génération de nouveau code système");
         
         // Simuler la génération de code par évolution
         let code_templates = [
@@ -299,7 +318,7 @@ impl EvolutionEngine {
             r#"
             // Nouveau système d'auto-réplication
             pub async fn replicate_component(component_id: &str) -> Result<String, String> {
-                println!("[AURORAE++] 🧬 Réplication du composant: {}", component_id);
+                info!("🧬 Réplication du composant: {}", component_id);
                 
                 // Créer une copie avec légères variations pour l'évolution
                 let replica_id = format!("{}-evolve-{}", component_id, Uuid::new_v4().simple());
@@ -309,8 +328,8 @@ impl EvolutionEngine {
                 let learning_rate = 0.05 + (rng.gen::<f32>() * 0.05);
                 let adaptation_factor = 1.0 + (rng.gen::<f32>() * 0.2);
                 
-                println!("[AURORAE++] ✅ Composant répliqué: {} → {}", component_id, replica_id);
-                println!("[AURORAE++] 📊 Paramètres améliorés: Apprentissage {:.2}, Adaptation {:.2}", 
+                info!("✅ Composant répliqué: {} → {}", component_id, replica_id);
+                info!("📊 Paramètres améliorés: Apprentissage {:.2}, Adaptation {:.2}", 
                          learning_rate, adaptation_factor);
                 
                 Ok(replica_id)
@@ -322,20 +341,89 @@ impl EvolutionEngine {
         // Choisir un template aléatoirement
         let code = code_templates[rng.gen_range(0..code_templates.len())].trim();
         
-        println!("[AURORAE++] 📄 Code auto-généré avec succès");
+        info!("📄 Code auto-généré avec succès");
         
         Ok(code.to_string())
     }
     
     pub async fn self_improve(&self) -> Result<(), String> {
-        println!("[AURORAE++] 🔄 Auto-amélioration du système d'évolution");
+        info!("🔄 Auto-amélioration du système d'évolution");
         
         // Simuler l'auto-amélioration
-        println!("[AURORAE++] 📈 Système d'évolution amélioré: {:.2}", self.evolution_level);
+        info!("📈 Système d'évolution amélioré: {:.2}", self.evolution_level);
         
         Ok(())
     }
     
+    /// Intègre le score d'une mutation évaluée en bac à sable: une mutation promue fait
+    /// progresser le niveau d'évolution, un échec modère la chance de mutation
+    pub fn record_mutation_fitness(&mut self, score: f32, promoted: bool) {
+        let score = score.clamp(0.0, 1.0);
+        self.mutation_fitness = 0.8 * self.mutation_fitness + 0.2 * score;
+        if promoted {
+            self.promoted_mutations += 1;
+            self.evolution_level += 0.1 * score;
+            self.mutation_chance = (self.mutation_chance * 1.02).min(0.5);
+        } else {
+            self.mutation_chance = (self.mutation_chance * 0.98).max(0.01);
+        }
+        info!("🧬 Aptitude des mutations: {:.2} ({} promue(s))", self.mutation_fitness, self.promoted_mutations);
+    }
+
+    /// Branche le service qui mesure l'aptitude réelle des génomes
+    pub fn attach_fitness_service(&mut self, service: FitnessService) {
+        self.fitness_service = Some(service);
+    }
+    
+    pub fn fitness_service(&self) -> Option<&FitnessService> {
+        self.fitness_service.as_ref()
+    }
+    
+    /// Ajoute un génome à la population et à la généalogie
+    pub fn add_to_population(&mut self, genome: Genome) {
+        LINEAGE.lock().record_genome(&genome);
+        self.population.push(genome);
+    }
+    
+    /// Mesure chaque génome de la population sur son propre espace de travail (compilation,
+    /// tests, benchmarks, clippy, indicateurs économiques); sans population, le système courant
+    /// est évalué seul. Les constructions cargo tournent sur un fil bloquant, hors de la boucle
+    /// asynchrone; service et population (espaces de travail inscrits) sont repris au retour
+    pub async fn evaluate_population_fitness(&mut self, kpis: &EconomicKpis) -> Result<Vec<FitnessEvaluation>, String> {
+        let mut service = self.fitness_service.clone()
+            .ok_or("Aucun service d'aptitude branché sur le moteur d'évolution")?;
+        if self.population.is_empty() {
+            self.add_to_population(Genome::new(HashMap::new(), Vec::new(), (self.mutation_chance * 100.0) as u8));
+        }
+        self.generation += 1;
+        
+        let (generation, kpis, mut population) = (self.generation, kpis.clone(), self.population.clone());
+        let (service, population, evaluations) = tokio::task::spawn_blocking(move || {
+            let evaluations = service.evaluate_population(&mut population, generation, &kpis);
+            (service, population, evaluations)
+        })
+        .await
+        .map_err(|e| format!("Évaluation de l'aptitude interrompue: {}", e))?;
+        self.fitness_service = Some(service);
+        self.population = population;
+        for evaluation in &evaluations {
+            self.population_fitness.insert(evaluation.genome_id, evaluation.fitness);
+        }
+        if let Some(best) = evaluations.iter().max_by(|a, b| a.fitness.total_cmp(&b.fitness)) {
+            info!("📈 Génération {}: {} génome(s) évalué(s), meilleure aptitude {:.2} (compilation {:.0}, tests {:.2}, perf {:.2}, lint {:.2}, économie {:.2})",
+                     self.generation, evaluations.len(), best.fitness, best.vector.compile, best.vector.tests,
+                     best.vector.performance, best.vector.lint, best.vector.economy);
+        }
+        Ok(evaluations)
+    }
+    
+    /// Meilleure aptitude mesurée dans la population courante
+    pub fn best_fitness(&self) -> Option<(Uuid, f32)> {
+        self.population.iter()
+            .filter_map(|g| self.population_fitness.get(&g.id).map(|f| (g.id, *f)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+    
     pub fn get_evolution_level(&self) -> f32 {
         self.evolution_level
     }
@@ -352,13 +440,17 @@ impl EvolutionEngine {
     }
     
     pub fn status_report(&self) {
-        println!("\n[AURORAE++] 🧬 RAPPORT D'ÉVOLUTION");
+        info!("🧬 RAPPORT D'ÉVOLUTION");
         println!("═══════════════════════════════");
         println!("Niveau d'évolution: {:.2}", self.evolution_level);
         println!("Cycles d'évolution: {}", self.cycle_count);
         println!("Capacités totales: {}", self.capabilities.len());
         println!("Évolutions totales: {}", self.total_evolutions);
         println!("Chance de mutation: {:.2}%", self.mutation_chance * 100.0);
+        println!("Aptitude des mutations: {:.2} ({} promue(s))", self.mutation_fitness, self.promoted_mutations);
+        if let Some((_, fitness)) = self.best_fitness() {
+            println!("Population: {} génome(s), génération {}, meilleure aptitude {:.2}", self.population.len(), self.generation, fitness);
+        }
         
         println!("\nCapacités les plus évoluées:");
         for (i, cap) in self.get_highest_capabilities().iter().enumerate() {
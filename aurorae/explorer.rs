@@ -2,13 +2,45 @@
 //!
 //! Ce module permet à l'IA de chercher activement des projets pertinents sur GitHub
 //! grâce à l'API officielle, afin d'apprendre seule et d'enrichir sa base de savoir.
+//!
+//! `CratesExplorer` interroge crates.io pour les crates liées à la blockchain (téléchargements,
+//! dernière publication, dépendances), récupère leur README et l'état de leur documentation
+//! docs.rs, puis range les candidates classées dans la base de savoir.
+//!
+//! `ProtocolScanner` dresse le profil des protocoles notables déployés sur une chaîne du
+//! registre: la liste vient de `protocols.toml`, la source vérifiée est récupérée auprès de
+//! l'API d'explorateur (compatible Etherscan) ou de Sourcify, et le profil obtenu (consensus,
+//! modèle de jeton, conception du pont, traits du code) devient un concept de la base de
+//! connaissances de l'évolution de code.
 
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
 use std::time::Duration;
+use chrono::{DateTime, Utc};
 use reqwest::blocking::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{error, info, warn};
+
+use crate::chain_registry::{ChainConfig, ChainKind};
+use crate::code_evolution::Concept;
+use crate::contract_verification::{ETHERSCAN_KEY_ENV, SOURCIFY_API};
+use crate::knowledge::{CrateRecord, KnowledgeBase, Provenance};
+use crate::secrets::secret;
 
 const GITHUB_API_URL: &str = "https://api.github.com/search/repositories";
 const USER_AGENT: &str = "AuroraeBot/1.0 (https://github.com/aurorae-core)";
+const CRATES_IO_API_URL: &str = "https://crates.io/api/v1";
+const DOCS_RS_URL: &str = "https://docs.rs";
+/// Requêtes crates.io couvrant l'écosystème blockchain
+const BLOCKCHAIN_QUERIES: &[&str] = &["blockchain", "consensus", "smart-contract", "cryptocurrency"];
+/// Politique d'exploration de crates.io: une requête par seconde au plus
+const CRATES_IO_DELAY: Duration = Duration::from_secs(1);
+/// Longueur conservée des README
+const README_EXCERPT: usize = 2000;
+pub const DEFAULT_PROTOCOL_CATALOG_PATH: &str = "protocols.toml";
+pub const PROTOCOL_PROFILES_PATH: &str = "aurorae_state/protocol_profiles.json";
 
 #[derive(Debug, Deserialize)]
 struct GitHubRepoItem {
@@ -45,7 +77,7 @@ pub fn search_repositories(query: &str, limit: usize) -> Result<Vec<GitHubRepoIt
 pub fn search_best_rust_chains() {
     match search_repositories("language:rust+blockchain", 5) {
         Ok(repos) => {
-            println!("[AURORAE++] Projets trouvés :");
+            info!("Projets trouvés :");
             for repo in repos {
                 println!("- {} (⭐ {})", repo.full_name, repo.stargazers_count);
                 println!("  URL : {}", repo.html_url);
@@ -55,7 +87,586 @@ pub fn search_best_rust_chains() {
             }
         }
         Err(e) => {
-            eprintln!("[AURORAE++] Échec de recherche GitHub : {}", e);
+            error!("Échec de recherche GitHub : {}", e);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CrateSummary {
+    pub name: String,
+    pub description: Option<String>,
+    pub downloads: u64,
+    #[serde(default)]
+    pub recent_downloads: Option<u64>,
+    pub max_version: String,
+    pub updated_at: DateTime<Utc>,
+    pub repository: Option<String>,
+    pub documentation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CratesSearchResponse {
+    crates: Vec<CrateSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateDependency {
+    crate_id: String,
+    kind: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DependenciesResponse {
+    dependencies: Vec<CrateDependency>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DocsStatus {
+    doc_status: bool,
+}
+
+/// Score d'une crate entre 0 et 1: popularité totale et récente (échelle logarithmique) et
+/// fraîcheur de la dernière publication (pleine sous 90 jours, nulle après deux ans)
+pub fn crate_score(downloads: u64, recent_downloads: u64, updated_at: DateTime<Utc>, now: DateTime<Utc>) -> f32 {
+    let popularity = ((downloads as f32 + 1.0).log10() / 8.0).min(1.0);
+    let momentum = ((recent_downloads as f32 + 1.0).log10() / 7.0).min(1.0);
+    let age = (now - updated_at).num_days().max(0) as f32;
+    let freshness = (1.0 - (age - 90.0).max(0.0) / (730.0 - 90.0)).clamp(0.0, 1.0);
+    0.45 * popularity + 0.35 * momentum + 0.2 * freshness
+}
+
+/// Texte brut d'un README rendu en HTML par crates.io, tronqué
+fn readme_excerpt(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
         }
     }
+    text.split_whitespace().collect::<Vec<_>>().join(" ").chars().take(README_EXCERPT).collect()
+}
+
+/// Explorateur crates.io / docs.rs
+pub struct CratesExplorer {
+    client: Client,
+    api_url: String,
+    docs_url: String,
+}
+
+impl CratesExplorer {
+    pub fn new() -> Result<Self, String> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent(USER_AGENT)
+            .build()
+            .map_err(|e| format!("Erreur client HTTP: {}", e))?;
+        Ok(Self { client, api_url: CRATES_IO_API_URL.to_string(), docs_url: DOCS_RS_URL.to_string() })
+    }
+
+    fn get<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T, String> {
+        std::thread::sleep(CRATES_IO_DELAY);
+        self.client.get(url)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("Erreur de requête {}: {}", url, e))?
+            .json::<T>()
+            .map_err(|e| format!("Erreur parsing JSON {}: {}", url, e))
+    }
+
+    /// Crates correspondant à `query`, les plus téléchargées d'abord
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<CrateSummary>, String> {
+        let url = format!("{}/crates?q={}&sort=downloads&per_page={}", self.api_url, query, limit);
+        Ok(self.get::<CratesSearchResponse>(&url)?.crates)
+    }
+
+    /// Dépendances normales (hors dev/build) d'une version
+    pub fn dependencies(&self, name: &str, version: &str) -> Result<Vec<String>, String> {
+        let url = format!("{}/crates/{}/{}/dependencies", self.api_url, name, version);
+        Ok(self.get::<DependenciesResponse>(&url)?
+            .dependencies
+            .into_iter()
+            .filter(|dependency| dependency.kind == "normal")
+            .map(|dependency| dependency.crate_id)
+            .collect())
+    }
+
+    /// README de la version, en texte brut
+    pub fn readme(&self, name: &str, version: &str) -> Result<String, String> {
+        std::thread::sleep(CRATES_IO_DELAY);
+        let url = format!("{}/crates/{}/{}/readme", self.api_url, name, version);
+        self.client.get(&url)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())
+            .map(|html| readme_excerpt(&html))
+            .map_err(|e| format!("Erreur de requête {}: {}", url, e))
+    }
+
+    /// URL docs.rs de la version si sa documentation a bien été générée
+    pub fn documentation(&self, name: &str, version: &str) -> Option<String> {
+        let status = format!("{}/crate/{}/{}/status.json", self.docs_url, name, version);
+        self.get::<DocsStatus>(&status)
+            .ok()
+            .filter(|status| status.doc_status)
+            .map(|_| format!("{}/{}/{}", self.docs_url, name, version))
+    }
+
+    /// Détaille une crate trouvée: dépendances, README, documentation et score
+    pub fn candidate(&self, summary: CrateSummary) -> CrateRecord {
+        let recent_downloads = summary.recent_downloads.unwrap_or(0);
+        let dependencies = self.dependencies(&summary.name, &summary.max_version).unwrap_or_else(|e| {
+            warn!("⚠️ {}", e);
+            Vec::new()
+        });
+        let readme = self.readme(&summary.name, &summary.max_version).ok().filter(|text| !text.is_empty());
+        let documentation = self.documentation(&summary.name, &summary.max_version).or(summary.documentation);
+        CrateRecord {
+            score: crate_score(summary.downloads, recent_downloads, summary.updated_at, Utc::now()),
+            name: summary.name,
+            version: summary.max_version,
+            description: summary.description,
+            downloads: summary.downloads,
+            recent_downloads,
+            updated_at: summary.updated_at,
+            dependencies,
+            repository: summary.repository,
+            documentation,
+            readme,
+            provenance: Provenance::local(),
+        }
+    }
+
+    /// Recherche les crates blockchain (jusqu'à `per_query` par requête), les détaille et les
+    /// range dans la base de savoir; retourne le nombre de crates enregistrées
+    pub fn ingest_blockchain_crates(&self, knowledge: &mut KnowledgeBase, per_query: usize) -> Result<usize, String> {
+        let mut seen = HashSet::new();
+        let mut summaries = Vec::new();
+        for query in BLOCKCHAIN_QUERIES {
+            match self.search(query, per_query) {
+                Ok(found) => summaries.extend(found.into_iter().filter(|summary| seen.insert(summary.name.clone()))),
+                Err(e) => warn!("⚠️ Recherche crates.io \"{}\" impossible: {}", query, e),
+            }
+        }
+        if summaries.is_empty() {
+            return Err("Aucune crate trouvée sur crates.io".to_string());
+        }
+
+        let ingested = summaries.len();
+        for summary in summaries {
+            knowledge.upsert_crate(self.candidate(summary));
+        }
+        info!("📦 {} crate(s) blockchain classée(s) depuis crates.io:", ingested);
+        for record in knowledge.crates.iter().take(5) {
+            println!("- {} {} (score {:.2}, ⬇ {})", record.name, record.version, record.score, record.downloads);
+        }
+        Ok(ingested)
+    }
+}
+
+/// Protocole notable d'une chaîne, tel que listé dans `protocols.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProtocolEntry {
+    pub chain: String,
+    pub name: String,
+    pub category: String,
+    /// Contrat principal (chaînes EVM); absent pour les protocoles natifs
+    #[serde(default)]
+    pub address: Option<String>,
+    /// Modèle de jeton connu, prioritaire sur celui déduit de la source
+    #[serde(default)]
+    pub token_model: Option<String>,
+    #[serde(default)]
+    pub bridge_design: Option<String>,
+}
+
+/// Liste organisée des protocoles et consensus de chaque chaîne (`protocols.toml`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ProtocolCatalog {
+    /// Mécanisme de consensus, par nom de chaîne
+    pub consensus: HashMap<String, String>,
+    pub protocols: Vec<ProtocolEntry>,
+}
+
+impl ProtocolCatalog {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Catalogue de protocoles invalide: {}", e))
+    }
+
+    /// `protocols.toml`, ou un catalogue vide s'il est absent ou invalide
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_PROTOCOL_CATALOG_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: aucun protocole à explorer", e);
+            Self::default()
+        })
+    }
+
+    pub fn for_chain(&self, chain: &str) -> Vec<&ProtocolEntry> {
+        self.protocols.iter().filter(|entry| entry.chain == chain).collect()
+    }
+}
+
+/// Source vérifiée d'un contrat
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifiedSource {
+    /// Service qui a fourni la source (`etherscan` ou `sourcify`)
+    pub service: String,
+    pub contract_name: Option<String>,
+    pub compiler: Option<String>,
+    /// Fichiers: chemin et contenu
+    pub files: Vec<(String, String)>,
+}
+
+/// Profil structuré d'un protocole déployé
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProtocolProfile {
+    pub chain: String,
+    pub name: String,
+    pub category: String,
+    pub address: Option<String>,
+    pub consensus: Option<String>,
+    pub token_model: Option<String>,
+    pub bridge_design: Option<String>,
+    /// Traits relevés dans la source (proxy, rôles, oracle…)
+    pub features: Vec<String>,
+    pub verified: bool,
+    pub contract_name: Option<String>,
+    pub source_files: Vec<String>,
+    pub scanned_at: DateTime<Utc>,
+}
+
+/// Modèles de jeton: tous les mots-clés doivent apparaître, le premier modèle reconnu l'emporte
+const TOKEN_MODELS: &[(&str, &[&str])] = &[
+    ("parts de coffre ERC-4626", &["erc4626"]),
+    ("jetons porteurs d'intérêts (rebasing)", &["scaledbalanceof"]),
+    ("NFT ERC-721", &["erc721"]),
+    ("ERC-20 émis et brûlé à la demande", &["function mint(", "function burn("]),
+    ("ERC-20", &["function transfer(", "balanceof"]),
+];
+
+/// Conceptions de pont, même règle que les modèles de jeton
+const BRIDGE_DESIGNS: &[(&str, &[&str])] = &[
+    ("messagerie inter-chaînes LayerZero", &["lzreceive"]),
+    ("messagerie inter-chaînes CCIP", &["ccipreceive"]),
+    ("messagerie optimiste (CrossDomainMessenger)", &["crossdomainmessenger"]),
+    ("synchronisation d'état (dépôt/retrait prouvé)", &["onstatereceive"]),
+    ("brûlage et émission (burn-and-mint)", &["bridge", "burn", "mint"]),
+    ("verrouillage et émission (lock-and-mint)", &["bridge", "lock", "mint"]),
+];
+
+/// Traits relevés dès qu'un des mots-clés apparaît
+const SOURCE_FEATURES: &[(&str, &[&str])] = &[
+    ("proxy évolutif", &["delegatecall", "upgradeto"]),
+    ("contrôle d'accès par rôles", &["accesscontrol", "onlyrole"]),
+    ("propriétaire unique", &["onlyowner"]),
+    ("prêts flash", &["flashloan"]),
+    ("oracle de prix", &["latestrounddata", "priceoracle"]),
+    ("pause d'urgence", &["whennotpaused"]),
+    ("AMM à liquidité concentrée", &["tickspacing"]),
+    ("AMM à produit constant", &["getamountout"]),
+];
+
+/// Modèle de jeton, conception du pont et traits déduits du code source
+pub fn analyze_source(files: &[(String, String)]) -> (Option<String>, Option<String>, Vec<String>) {
+    let code = files.iter().map(|(_, content)| content.to_lowercase()).collect::<Vec<_>>().join("\n");
+    let first = |rules: &[(&str, &[&str])]| rules.iter()
+        .find(|(_, keywords)| keywords.iter().all(|keyword| code.contains(keyword)))
+        .map(|(label, _)| label.to_string());
+    let features = SOURCE_FEATURES.iter()
+        .filter(|(_, keywords)| keywords.iter().any(|keyword| code.contains(keyword)))
+        .map(|(label, _)| label.to_string())
+        .collect();
+    (first(TOKEN_MODELS), first(BRIDGE_DESIGNS), features)
+}
+
+/// Fichiers d'une source Etherscan: fichier unique, dictionnaire de fichiers, ou entrée
+/// standard JSON entourée de doubles accolades
+fn etherscan_files(source: &str, contract_name: &str) -> Vec<(String, String)> {
+    let trimmed = source.trim();
+    let json = trimmed.strip_prefix("{{").and_then(|s| s.strip_suffix("}}")).map(|inner| format!("{{{}}}", inner));
+    let parsed: Option<Value> = match (&json, trimmed.starts_with('{')) {
+        (Some(json), _) => serde_json::from_str(json).ok(),
+        (None, true) => serde_json::from_str(trimmed).ok(),
+        (None, false) => None,
+    };
+    let Some(parsed) = parsed else {
+        return vec![(format!("{}.sol", contract_name), source.to_string())];
+    };
+    let sources = parsed.get("sources").unwrap_or(&parsed);
+    sources.as_object()
+        .map(|map| map.iter()
+            .filter_map(|(path, file)| file.get("content").and_then(|c| c.as_str()).map(|c| (path.clone(), c.to_string())))
+            .collect())
+        .unwrap_or_default()
+}
+
+impl ProtocolProfile {
+    /// Concept de la base de connaissances: résumé du profil et concepts liés (catégorie,
+    /// consensus, modèle de jeton, pont)
+    pub fn to_concept(&self) -> Concept {
+        let mut description = format!("Protocole {} ({}) sur {}.", self.name, self.category, self.chain);
+        for (label, value) in [("Consensus", &self.consensus), ("Jeton", &self.token_model), ("Pont", &self.bridge_design)] {
+            if let Some(value) = value {
+                description.push_str(&format!("\n{}: {}", label, value));
+            }
+        }
+        if !self.features.is_empty() {
+            description.push_str(&format!("\nTraits: {}", self.features.join(", ")));
+        }
+        let related = [Some(&self.category), self.consensus.as_ref(), self.token_model.as_ref(), self.bridge_design.as_ref()]
+            .into_iter()
+            .flatten()
+            .chain(self.features.iter())
+            .cloned()
+            .collect();
+        Concept {
+            name: format!("Protocole {} ({})", self.name, self.chain),
+            description,
+            relevance: if self.verified { 0.8 } else { 0.6 },
+            complexity: (0.3 + 0.1 * self.features.len() as f32).min(1.0),
+            source_files: self.source_files.clone(),
+            related_concepts: related,
+        }
+    }
+}
+
+/// Scanner des protocoles déployés sur les chaînes du registre
+pub struct ProtocolScanner {
+    client: reqwest::Client,
+    catalog: ProtocolCatalog,
+    etherscan_key: Option<String>,
+    sourcify_api: String,
+}
+
+impl ProtocolScanner {
+    pub fn new(catalog: ProtocolCatalog) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            catalog,
+            etherscan_key: secret("explorer", ETHERSCAN_KEY_ENV).map(|key| key.expose().to_string()),
+            sourcify_api: SOURCIFY_API.to_string(),
+        }
+    }
+
+    /// Source vérifiée d'un contrat: API d'explorateur si une clé est disponible (en suivant
+    /// l'implémentation des proxys), Sourcify sinon
+    pub async fn verified_source(&self, chain: &ChainConfig, address: &str) -> Result<Option<VerifiedSource>, String> {
+        if let (Some(api), Some(key)) = (&chain.explorer_api, &self.etherscan_key) {
+            if let Some(source) = self.etherscan_source(api, key, address).await? {
+                return Ok(Some(source));
+            }
+        }
+        match chain.chain_id {
+            Some(chain_id) => self.sourcify_source(chain_id, address).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn etherscan_source(&self, api: &str, key: &str, address: &str) -> Result<Option<VerifiedSource>, String> {
+        let mut address = address.to_string();
+        // Un proxy vérifié renvoie vers son implémentation: un seul saut suffit
+        for _ in 0..2 {
+            let response: Value = self.client
+                .get(api)
+                .query(&[("apikey", key), ("module", "contract"), ("action", "getsourcecode"), ("address", address.as_str())])
+                .send()
+                .await
+                .map_err(|e| format!("Explorateur injoignable: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Réponse de l'explorateur invalide: {}", e))?;
+            let Some(result) = response.get("result").and_then(|r| r.get(0)) else { return Ok(None) };
+            let field = |name: &str| result.get(name).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let implementation = field("Implementation");
+            if field("Proxy") == "1" && !implementation.is_empty() && implementation != address {
+                address = implementation;
+                continue;
+            }
+            let source = field("SourceCode");
+            if source.is_empty() {
+                return Ok(None);
+            }
+            let contract_name = field("ContractName");
+            return Ok(Some(VerifiedSource {
+                service: "etherscan".to_string(),
+                files: etherscan_files(&source, &contract_name),
+                contract_name: Some(contract_name).filter(|name| !name.is_empty()),
+                compiler: Some(field("CompilerVersion")).filter(|version| !version.is_empty()),
+            }));
+        }
+        Ok(None)
+    }
+
+    async fn sourcify_source(&self, chain_id: u64, address: &str) -> Result<Option<VerifiedSource>, String> {
+        let url = format!("{}/files/any/{}/{}", self.sourcify_api, chain_id, address);
+        let response = self.client.get(&url).send().await.map_err(|e| format!("Sourcify injoignable: {}", e))?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        let body: Value = response.json().await.map_err(|e| format!("Réponse Sourcify invalide: {}", e))?;
+        let files: Vec<(String, String)> = body.get("files")
+            .and_then(|files| files.as_array())
+            .map(|files| files.iter()
+                .filter_map(|file| Some((file.get("path")?.as_str()?.to_string(), file.get("content")?.as_str()?.to_string())))
+                .filter(|(path, _)| path.ends_with(".sol") || path.ends_with(".vy"))
+                .collect())
+            .unwrap_or_default();
+        if files.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(VerifiedSource { service: "sourcify".to_string(), contract_name: None, compiler: None, files }))
+    }
+
+    /// Profil d'un protocole: les valeurs du catalogue priment sur celles déduites de la source
+    pub fn profile(&self, chain: &ChainConfig, entry: &ProtocolEntry, source: Option<&VerifiedSource>) -> ProtocolProfile {
+        let (token_model, bridge_design, features) = source.map(|s| analyze_source(&s.files)).unwrap_or_default();
+        ProtocolProfile {
+            chain: chain.name.clone(),
+            name: entry.name.clone(),
+            category: entry.category.clone(),
+            address: entry.address.clone(),
+            consensus: self.catalog.consensus.get(&chain.name).cloned(),
+            token_model: entry.token_model.clone().or(token_model),
+            bridge_design: entry.bridge_design.clone().or(bridge_design),
+            features,
+            verified: source.is_some(),
+            contract_name: source.and_then(|s| s.contract_name.clone()),
+            source_files: source.map(|s| s.files.iter().map(|(path, _)| path.clone()).collect()).unwrap_or_default(),
+            scanned_at: Utc::now(),
+        }
+    }
+
+    /// Profils des protocoles du catalogue déployés sur `chain`
+    pub async fn scan_chain(&self, chain: &ChainConfig) -> Vec<ProtocolProfile> {
+        let mut profiles = Vec::new();
+        for entry in self.catalog.for_chain(&chain.name) {
+            let source = match (&entry.address, chain.kind) {
+                (Some(address), ChainKind::Evm) => self.verified_source(chain, address).await.unwrap_or_else(|e| {
+                    warn!("⚠️ Source de {} indisponible: {}", entry.name, e);
+                    None
+                }),
+                _ => None,
+            };
+            let profile = self.profile(chain, entry, source.as_ref());
+            info!(
+                "🛰️ {} sur {}: jeton {}, pont {}{}",
+                profile.name,
+                profile.chain,
+                profile.token_model.as_deref().unwrap_or("?"),
+                profile.bridge_design.as_deref().unwrap_or("aucun"),
+                if profile.verified { " (source vérifiée)" } else { "" }
+            );
+            profiles.push(profile);
+        }
+        profiles
+    }
+
+    /// Profils de toutes les chaînes données
+    pub async fn scan_chains(&self, chains: &[ChainConfig]) -> Vec<ProtocolProfile> {
+        let mut profiles = Vec::new();
+        for chain in chains {
+            profiles.extend(self.scan_chain(chain).await);
+        }
+        profiles
+    }
+}
+
+/// 💾 Sauvegarde des profils dans `aurorae_state`
+pub fn save_protocol_profiles(profiles: &[ProtocolProfile], path: &Path) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(profiles).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as Days;
+
+    #[test]
+    fn popular_and_fresh_crates_rank_first() {
+        let now = Utc::now();
+        let popular = crate_score(50_000_000, 2_000_000, now - Days::days(10), now);
+        let stale = crate_score(50_000_000, 2_000_000, now - Days::days(1000), now);
+        let niche = crate_score(2_000, 100, now - Days::days(10), now);
+        assert!(popular > stale && popular > niche);
+        assert!(popular <= 1.0 && niche >= 0.0);
+
+        let mut knowledge = KnowledgeBase::default();
+        for (name, score) in [("niche", niche), ("popular", popular), ("niche", stale)] {
+            knowledge.upsert_crate(CrateRecord {
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                description: None,
+                downloads: 0,
+                recent_downloads: 0,
+                updated_at: now,
+                dependencies: Vec::new(),
+                repository: None,
+                documentation: None,
+                readme: None,
+                score,
+                provenance: Provenance::local(),
+            });
+        }
+        assert_eq!(knowledge.crates.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["popular", "niche"]);
+        assert_eq!(knowledge.crates[1].score, stale);
+
+        assert_eq!(readme_excerpt("<h1>Demo</h1>\n<p>Fast <b>consensus</b></p>"), "Demo Fast consensus");
+    }
+
+    #[test]
+    fn profiles_combine_catalog_and_verified_source() {
+        let catalog = ProtocolCatalog::from_toml(r#"
+            [consensus]
+            Polygon = "Proof of Stake (Heimdall + Bor)"
+
+            [[protocols]]
+            chain = "Polygon"
+            name = "Bridge"
+            category = "bridge"
+            address = "0x0000000000000000000000000000000000000001"
+        "#).unwrap();
+        let chain: ChainConfig = toml::from_str(r#"
+            name = "Polygon"
+            kind = "evm"
+            rpc = "https://polygon-rpc.com"
+            chain-id = 137
+            native-currency = { symbol = "MATIC", decimals = 18 }
+        "#).unwrap();
+        let standard_json = r#"{{"language":"Solidity","sources":{"Bridge.sol":{"content":"contract Bridge is AccessControl { function onStateReceive(uint256, bytes calldata) external onlyRole(STATE_SYNCER) {} function mint(address, uint256) external {} function burn(uint256) external {} function transfer(address, uint256) external {} function balanceOf(address) external {} }"}}}}"#;
+        let files = etherscan_files(standard_json, "Bridge");
+        assert_eq!(files.len(), 1);
+        assert_eq!(etherscan_files("contract A {}", "A"), vec![("A.sol".to_string(), "contract A {}".to_string())]);
+
+        let source = VerifiedSource { service: "etherscan".into(), contract_name: Some("Bridge".into()), compiler: None, files };
+        let scanner = ProtocolScanner { client: reqwest::Client::new(), catalog, etherscan_key: None, sourcify_api: SOURCIFY_API.into() };
+        let entry = scanner.catalog.protocols[0].clone();
+        let profile = scanner.profile(&chain, &entry, Some(&source));
+        assert_eq!(profile.consensus.as_deref(), Some("Proof of Stake (Heimdall + Bor)"));
+        assert_eq!(profile.token_model.as_deref(), Some("ERC-20 émis et brûlé à la demande"));
+        assert_eq!(profile.bridge_design.as_deref(), Some("synchronisation d'état (dépôt/retrait prouvé)"));
+        assert_eq!(profile.features, vec!["contrôle d'accès par rôles".to_string()]);
+
+        let concept = profile.to_concept();
+        assert_eq!(concept.name, "Protocole Bridge (Polygon)");
+        assert!(concept.related_concepts.contains(&"bridge".to_string()));
+        assert_eq!(concept.source_files, vec!["Bridge.sol".to_string()]);
+    }
 }
@@ -0,0 +1,423 @@
+//! AURORAE++ - fitness.rs
+//!
+//! Service d'aptitude de l'EvolutionEngine: chaque génome de la population est mesuré sur son
+//! espace de travail (compilation, taux de réussite des tests, temps des benchmarks,
+//! avertissements clippy, via le bac à sable des mutations) et sur les indicateurs de
+//! l'économie. Les mesures sont normalisées en un vecteur d'aptitude entre 0 et 1, pondérées
+//! en un score, et l'historique des évaluations est persisté dans `aurorae_state/fitness_history.json`.
+//!
+//! Chaque génome a son propre espace de travail, à côté de l'historique (`genome_workspaces/<id>`):
+//! copie de celui de son parent (du crate pour un génome fondateur) où il est exprimé. Un
+//! génome muté y reçoit une mutation de code semée par son origine sur son module activé le
+//! plus prioritaire, un génome croisé y reprend une partie des modules de son second parent.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::economy::EconomyEngine;
+use crate::genome::{GeneticOrigin, Genome};
+use crate::mutation::Mutator;
+use crate::mutation_sandbox::{MutationSandbox, SandboxRun};
+
+pub const FITNESS_HISTORY_PATH: &str = "aurorae_state/fitness_history.json";
+/// Trait du génome désignant la racine du crate qui l'exprime
+pub const WORKSPACE_TRAIT: &str = "workspace";
+/// Dossier des espaces de travail, à côté de l'historique
+const WORKSPACES_DIR: &str = "genome_workspaces";
+/// Génome exprimé, écrit à la racine de son espace de travail
+const GENOME_FILE: &str = "genome.json";
+/// Évaluations conservées dans l'historique
+const MAX_HISTORY: usize = 2000;
+/// Valeur générée à laquelle la composante économique atteint 0.63 (1 - 1/e)
+const ECONOMY_SCALE: f64 = 10_000.0;
+/// Taux de croissance par cycle considéré comme excellent
+const TARGET_GROWTH: f64 = 0.05;
+/// Avertissements clippy qui divisent la composante lint par deux
+const LINT_HALF_LIFE: f32 = 10.0;
+
+/// Indicateurs économiques au moment de l'évaluation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EconomicKpis {
+    pub total_generated: f64,
+    pub growth_rate: f64,
+    pub transactions: usize,
+}
+
+impl EconomicKpis {
+    pub fn from_economy(economy: &EconomyEngine) -> Self {
+        Self {
+            total_generated: economy.total_generated,
+            growth_rate: economy.growth_rate,
+            transactions: economy.transactions.len(),
+        }
+    }
+}
+
+/// Aptitude normalisée, chaque composante entre 0 et 1
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FitnessVector {
+    pub compile: f32,
+    pub tests: f32,
+    pub performance: f32,
+    pub lint: f32,
+    pub economy: f32,
+}
+
+impl FitnessVector {
+    /// Normalise une mesure; `reference` fournit les temps de benchmark de comparaison
+    pub fn from_measures(run: &SandboxRun, kpis: &EconomicKpis, reference: &BTreeMap<String, f64>) -> Self {
+        let compile = if run.compiled { 1.0 } else { 0.0 };
+        let total_tests = run.tests_passed + run.tests_failed.len();
+        let tests = match (run.compiled, total_tests) {
+            (false, _) => 0.0,
+            (true, 0) => 0.5,
+            (true, total) => run.tests_passed as f32 / total as f32,
+        };
+
+        // Écart relatif moyen aux benchmarks de référence: 0.5 à égalité, 1 deux fois plus rapide
+        let deltas: Vec<f64> = run.benchmarks.iter()
+            .filter_map(|(name, ns)| reference.get(name).filter(|base| **base > 0.0).map(|base| (ns - base) / base))
+            .collect();
+        let performance = if deltas.is_empty() {
+            0.5
+        } else {
+            let mean = deltas.iter().sum::<f64>() / deltas.len() as f64;
+            (0.5 - mean).clamp(0.0, 1.0) as f32
+        };
+
+        let lint = if run.compiled { LINT_HALF_LIFE / (LINT_HALF_LIFE + run.clippy_warnings as f32) } else { 0.0 };
+
+        let wealth = 1.0 - (-kpis.total_generated.max(0.0) / ECONOMY_SCALE).exp();
+        let growth = (kpis.growth_rate / TARGET_GROWTH).clamp(0.0, 1.0);
+        let economy = (0.5 * wealth + 0.5 * growth) as f32;
+
+        Self { compile, tests, performance, lint, economy }
+    }
+
+    pub fn weighted(&self, weights: &FitnessWeights) -> f32 {
+        let total = weights.compile + weights.tests + weights.performance + weights.lint + weights.economy;
+        if total <= 0.0 {
+            return 0.0;
+        }
+        (self.compile * weights.compile
+            + self.tests * weights.tests
+            + self.performance * weights.performance
+            + self.lint * weights.lint
+            + self.economy * weights.economy) / total
+    }
+}
+
+/// Poids des composantes dans le score d'aptitude
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FitnessWeights {
+    pub compile: f32,
+    pub tests: f32,
+    pub performance: f32,
+    pub lint: f32,
+    pub economy: f32,
+}
+
+impl Default for FitnessWeights {
+    fn default() -> Self {
+        Self { compile: 0.3, tests: 0.3, performance: 0.15, lint: 0.1, economy: 0.15 }
+    }
+}
+
+/// Évaluation d'un génome à une génération donnée
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FitnessEvaluation {
+    pub genome_id: Uuid,
+    pub generation: u32,
+    pub timestamp: String,
+    pub workspace: PathBuf,
+    pub run: SandboxRun,
+    pub kpis: EconomicKpis,
+    pub vector: FitnessVector,
+    pub fitness: f32,
+}
+
+#[derive(Clone)]
+pub struct FitnessService {
+    /// Crate copié dans l'espace de travail des génomes fondateurs
+    pub crate_root: PathBuf,
+    pub weights: FitnessWeights,
+    history: Vec<FitnessEvaluation>,
+    history_path: PathBuf,
+    workspaces: PathBuf,
+}
+
+impl FitnessService {
+    pub fn new(crate_root: &Path) -> Self {
+        Self::with_history(crate_root, Path::new(FITNESS_HISTORY_PATH))
+    }
+
+    /// Service rechargé depuis l'historique `history_path` s'il existe
+    pub fn with_history(crate_root: &Path, history_path: &Path) -> Self {
+        let history: Vec<FitnessEvaluation> = File::open(history_path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        if !history.is_empty() {
+            info!("📈 {} évaluation(s) d'aptitude rechargée(s)", history.len());
+        }
+        Self {
+            crate_root: crate_root.to_path_buf(),
+            weights: FitnessWeights::default(),
+            history,
+            history_path: history_path.to_path_buf(),
+            workspaces: history_path.with_file_name(WORKSPACES_DIR),
+        }
+    }
+
+    pub fn history(&self) -> &[FitnessEvaluation] {
+        &self.history
+    }
+
+    /// Évaluations d'un génome, de la plus ancienne à la plus récente
+    pub fn history_for(&self, genome_id: &Uuid) -> Vec<&FitnessEvaluation> {
+        self.history.iter().filter(|e| e.genome_id == *genome_id).collect()
+    }
+
+    pub fn latest(&self, genome_id: &Uuid) -> Option<&FitnessEvaluation> {
+        self.history.iter().rev().find(|e| e.genome_id == *genome_id)
+    }
+
+    /// Temps de référence: premiers benchmarks mesurés sur un crate qui compile
+    fn reference_benchmarks(&self) -> BTreeMap<String, f64> {
+        self.history.iter()
+            .find(|e| e.run.compiled && !e.run.benchmarks.is_empty())
+            .map(|e| e.run.benchmarks.clone())
+            .unwrap_or_default()
+    }
+
+    /// Espace de travail propre au génome
+    pub fn workspace_of(&self, genome: &Genome) -> PathBuf {
+        self.workspaces.join(genome.id.to_string())
+    }
+
+    /// Crée au besoin l'espace de travail du génome et l'inscrit dans son trait `workspace`
+    /// (un enfant hérite du trait de ses parents: il est remplacé par le sien)
+    pub fn ensure_workspace(&self, genome: &mut Genome) -> Result<PathBuf, String> {
+        let workspace = self.workspace_of(genome);
+        if !workspace.join(GENOME_FILE).exists() {
+            self.express(genome, &workspace)?;
+        }
+        genome.traits.insert(WORKSPACE_TRAIT.to_string(), workspace.display().to_string());
+        Ok(workspace)
+    }
+
+    /// Espace d'un parent s'il existe encore, le crate sinon
+    fn parent_workspace(&self, parent: Option<&Uuid>) -> PathBuf {
+        parent.map(|id| self.workspaces.join(id.to_string()))
+            .filter(|workspace| workspace.join(GENOME_FILE).exists())
+            .unwrap_or_else(|| self.crate_root.clone())
+    }
+
+    /// Copie l'espace du premier parent et y applique ce que l'origine du génome a changé
+    fn express(&self, genome: &Genome, workspace: &Path) -> Result<(), String> {
+        if workspace.exists() {
+            fs::remove_dir_all(workspace).map_err(|e| format!("Erreur nettoyage {}: {}", workspace.display(), e))?;
+        }
+        MutationSandbox::new(&self.parent_workspace(genome.ancestry.first())).copy_crate(workspace)?;
+
+        match &genome.origin {
+            GeneticOrigin::Base => {}
+            GeneticOrigin::Crossover { seed, .. } => {
+                let second = self.parent_workspace(genome.ancestry.get(1));
+                let mut rng = StdRng::seed_from_u64(*seed);
+                for module in genome.enabled_modules() {
+                    let donor = second.join("aurorae").join(format!("{}.rs", module));
+                    if donor.exists() && rng.gen_bool(0.5) {
+                        fs::copy(&donor, workspace.join("aurorae").join(format!("{}.rs", module)))
+                            .map_err(|e| format!("Erreur copie {}: {}", donor.display(), e))?;
+                    }
+                }
+            }
+            GeneticOrigin::Mutation { seed, .. } => {
+                let mut modules: Vec<_> = genome.modules.iter().filter(|m| m.enabled).collect();
+                modules.sort_by_key(|m| std::cmp::Reverse(m.priority));
+                let target = modules.iter()
+                    .map(|m| workspace.join("aurorae").join(format!("{}.rs", m.module)))
+                    .find(|path| path.exists());
+                if let Some(target) = target {
+                    let source = fs::read_to_string(&target).map_err(|e| format!("Erreur lecture {}: {}", target.display(), e))?;
+                    if let Some(mutation) = Mutator::new(*seed).propose(&source)? {
+                        fs::write(&target, &mutation.source).map_err(|e| format!("Erreur écriture {}: {}", target.display(), e))?;
+                        info!("🧬 Génome {}: {} ({})", genome.id, mutation.description, target.display());
+                    }
+                }
+            }
+        }
+        fs::write(workspace.join(GENOME_FILE), genome.to_json()?)
+            .map_err(|e| format!("Erreur écriture du génome {}: {}", genome.id, e))
+    }
+
+    /// Supprime les espaces des génomes sortis de la population
+    fn prune_workspaces(&self, population: &[Genome]) {
+        let live: HashSet<String> = population.iter().map(|g| g.id.to_string()).collect();
+        for entry in fs::read_dir(&self.workspaces).into_iter().flatten().flatten() {
+            if !live.contains(&entry.file_name().to_string_lossy().to_string()) {
+                fs::remove_dir_all(entry.path()).ok();
+            }
+        }
+    }
+
+    /// Construit et mesure un espace de travail dans le bac à sable (cible de compilation
+    /// partagée entre génomes pour limiter les recompilations)
+    pub fn measure(&self, workspace: &Path) -> Result<SandboxRun, String> {
+        let mut sandbox = MutationSandbox::new(workspace);
+        sandbox.target_dir = self.crate_root.join("target").join("sandbox");
+        sandbox.run(None)
+    }
+
+    /// Note un génome à partir d'une mesure déjà faite et l'ajoute à l'historique
+    pub fn record(&mut self, genome_id: Uuid, generation: u32, workspace: &Path, run: SandboxRun, kpis: &EconomicKpis) -> FitnessEvaluation {
+        let vector = FitnessVector::from_measures(&run, kpis, &self.reference_benchmarks());
+        let evaluation = FitnessEvaluation {
+            genome_id,
+            generation,
+            timestamp: Utc::now().to_rfc3339(),
+            workspace: workspace.to_path_buf(),
+            run,
+            kpis: kpis.clone(),
+            fitness: vector.weighted(&self.weights),
+            vector,
+        };
+        self.history.push(evaluation.clone());
+        if self.history.len() > MAX_HISTORY {
+            let excess = self.history.len() - MAX_HISTORY;
+            self.history.drain(..excess);
+        }
+        evaluation
+    }
+
+    /// Évalue toute la population, chaque génome sur son propre espace de travail. Les
+    /// génomes dont l'espace ne peut être préparé ou mesuré sont ignorés.
+    pub fn evaluate_population(&mut self, population: &mut [Genome], generation: u32, kpis: &EconomicKpis) -> Vec<FitnessEvaluation> {
+        self.prune_workspaces(population);
+        let mut evaluations = Vec::new();
+        for genome in population.iter_mut() {
+            let measured = self.ensure_workspace(genome)
+                .and_then(|workspace| self.measure(&workspace).map(|run| (workspace, run)));
+            match measured {
+                Ok((workspace, run)) => evaluations.push(self.record(genome.id, generation, &workspace, run, kpis)),
+                Err(e) => warn!("⚠️ Mesure du génome {} impossible: {}", genome.id, e),
+            }
+        }
+        self.save();
+        evaluations
+    }
+
+    /// 💾 Sauvegarde de l'historique
+    pub fn save(&self) {
+        if let Some(dir) = self.history_path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(&self.history_path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self.history);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::genome::GenomeBuilder;
+
+    fn run(compiled: bool, passed: usize, failed: usize, warnings: usize, bench: f64) -> SandboxRun {
+        SandboxRun {
+            compiled,
+            tests_passed: passed,
+            tests_failed: (0..failed).map(|i| format!("test_{}", i)).collect(),
+            clippy_warnings: warnings,
+            benchmarks: [("cycle".to_string(), bench)].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn measures_are_normalized_against_reference() {
+        let reference: BTreeMap<String, f64> = [("cycle".to_string(), 100.0)].into_iter().collect();
+        let kpis = EconomicKpis { total_generated: 0.0, growth_rate: TARGET_GROWTH, transactions: 0 };
+
+        let healthy = FitnessVector::from_measures(&run(true, 9, 1, 0, 75.0), &kpis, &reference);
+        assert_eq!(healthy.compile, 1.0);
+        assert!((healthy.tests - 0.9).abs() < 1e-6);
+        assert!((healthy.performance - 0.75).abs() < 1e-6);
+        assert_eq!(healthy.lint, 1.0);
+        assert!((healthy.economy - 0.5).abs() < 1e-6);
+
+        let broken = FitnessVector::from_measures(&run(false, 0, 0, 0, 75.0), &kpis, &reference);
+        assert_eq!((broken.compile, broken.tests, broken.lint), (0.0, 0.0, 0.0));
+        let weights = FitnessWeights::default();
+        assert!(healthy.weighted(&weights) > broken.weighted(&weights));
+    }
+
+    #[test]
+    fn history_is_persisted_per_genome() {
+        let dir = std::env::temp_dir().join(format!("aurorae-fitness-{}", Uuid::new_v4()));
+        let path = dir.join("fitness_history.json");
+        let genome = Genome::new(HashMap::new(), Vec::new(), 10);
+        let kpis = EconomicKpis::default();
+
+        let mut service = FitnessService::with_history(Path::new("."), &path);
+        let first = service.record(genome.id, 1, Path::new("."), run(true, 2, 0, 3, 100.0), &kpis);
+        // Les premiers benchmarks servent de référence: 0.5 à égalité
+        assert!((first.vector.performance - 0.5).abs() < 1e-6);
+        service.record(genome.id, 2, Path::new("."), run(true, 2, 0, 0, 50.0), &kpis);
+        service.save();
+
+        let reloaded = FitnessService::with_history(Path::new("."), &path);
+        assert_eq!(reloaded.history_for(&genome.id).len(), 2);
+        let latest = reloaded.latest(&genome.id).unwrap();
+        assert_eq!(latest.generation, 2);
+        assert!(latest.fitness > first.fitness);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn each_genome_is_expressed_in_its_own_workspace() {
+        let dir = std::env::temp_dir().join(format!("aurorae-fitness-{}", Uuid::new_v4()));
+        let crate_root = dir.join("crate");
+        fs::create_dir_all(crate_root.join("aurorae")).unwrap();
+        fs::write(crate_root.join("Cargo.toml"), "[package]\nname = \"aurorae\"\n").unwrap();
+        fs::write(crate_root.join("aurorae").join("brain.rs"), "pub fn urgency() -> u32 {\n    let base = 2;\n    base * 3 + 1\n}\n").unwrap();
+        let service = FitnessService::with_history(&crate_root, &dir.join("state").join("fitness_history.json"));
+
+        let mut founder = GenomeBuilder::with_seed(7).base().clone();
+        let founder_workspace = service.ensure_workspace(&mut founder).unwrap();
+        assert_eq!(founder.traits[WORKSPACE_TRAIT], founder_workspace.display().to_string());
+        assert_eq!(
+            fs::read_to_string(founder_workspace.join("aurorae").join("brain.rs")).unwrap(),
+            fs::read_to_string(crate_root.join("aurorae").join("brain.rs")).unwrap()
+        );
+
+        // L'enfant hérite du trait de son parent, mais reçoit son propre espace, muté
+        let mut child = founder.mutated(42);
+        // brain.rs, seul module présent dans le crate de test, reçoit la mutation de code
+        child.modules.iter_mut().for_each(|gene| gene.enabled = gene.module == "brain");
+        assert_eq!(child.traits[WORKSPACE_TRAIT], founder_workspace.display().to_string());
+        let child_workspace = service.ensure_workspace(&mut child).unwrap();
+        assert_ne!(child_workspace, founder_workspace);
+        assert_eq!(child.traits[WORKSPACE_TRAIT], child_workspace.display().to_string());
+        assert_ne!(
+            fs::read_to_string(child_workspace.join("aurorae").join("brain.rs")).unwrap(),
+            fs::read_to_string(founder_workspace.join("aurorae").join("brain.rs")).unwrap()
+        );
+        assert!(Genome::from_json(&fs::read_to_string(child_workspace.join(GENOME_FILE)).unwrap()).unwrap().id == child.id);
+
+        // Les espaces des génomes sortis de la population disparaissent
+        service.prune_workspaces(std::slice::from_ref(&child));
+        assert!(!founder_workspace.exists() && child_workspace.exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,919 @@
+//! AURORAE++ - formal_verification.rs
+//!
+//! Vérification formelle des contrats par solveur SMT (Z3). Les fonctions publiques
+//! d'un contrat Solidity sont réduites à un modèle de transitions (gardes et effets
+//! sur l'offre, les soldes et la pause) puis chaque propriété est vérifiée par
+//! induction: l'invariant supposé vrai avant la transition, le solveur cherche un
+//! état qui le viole après. Insatisfiable: preuve; satisfiable: contre-exemple.
+//!
+//! Le code muté est vérifié par propriétés: une suite proptest est générée depuis
+//! la source (aller-retour serde, idempotence, compteurs monotones) et exécutée
+//! sur une copie du crate avant d'accepter la modification.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::{error, info, warn};
+
+use chrono::Utc;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use walkdir::WalkDir;
+use z3::ast::{Ast, Bool, Int};
+use z3::{Config, Context, SatResult, Solver};
+
+/// Répertoire des sources Solidity vérifiées
+pub const CONTRACTS_DIR: &str = "contracts";
+/// Délai maximal accordé au solveur par requête
+pub const DEFAULT_SOLVER_TIMEOUT_MS: u64 = 10_000;
+
+/// Propriétés vérifiées sur chaque transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Property {
+    /// `totalSupply <= cap` est préservé (sans plafond déclaré, tout mint le viole)
+    NoMintBeyondCap,
+    /// Seul le propriétaire peut changer l'état de pause
+    OnlyOwnerCanPause,
+    /// La somme des soldes reste égale à `totalSupply`
+    BalanceConservation,
+}
+
+impl Property {
+    pub const ALL: [Property; 3] = [Property::NoMintBeyondCap, Property::OnlyOwnerCanPause, Property::BalanceConservation];
+}
+
+/// Condition de garde d'une fonction (`require` ou modificateur)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Guard {
+    OwnerOnly,
+    NotPaused,
+    CapRespected,
+    BalanceCovers,
+}
+
+/// Effet d'une fonction sur l'état du token; un seul montant symbolique par appel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Effect {
+    IncreaseSupply,
+    DecreaseSupply,
+    /// Débit du solde de l'émetteur
+    Debit,
+    /// Crédit du solde du destinataire
+    Credit,
+    SetPaused(bool),
+}
+
+/// Fonction publique non `view` réduite à ses gardes et effets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    pub name: String,
+    pub guards: Vec<Guard>,
+    pub effects: Vec<Effect>,
+    /// Arithmétique vérifiée (Solidity >= 0.8 hors bloc `unchecked`): débordement = revert
+    pub checked: bool,
+}
+
+/// Modèle abstrait d'un contrat de token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractModel {
+    pub name: String,
+    /// Variable de plafond déclarée (`cap`, `MAX_SUPPLY`…)
+    pub cap: Option<String>,
+    pub transitions: Vec<Transition>,
+}
+
+impl ContractModel {
+    /// Extrait le modèle d'une source Solidity (idiomes ERC-20 usuels)
+    pub fn from_solidity(name: &str, source: &str) -> Result<Self, String> {
+        let checked_default = Regex::new(r"pragma\s+solidity\s*[\^>=~]*\s*0\.(\d+)")
+            .unwrap()
+            .captures(source)
+            .and_then(|c| c[1].parse::<u32>().ok())
+            .map(|minor| minor >= 8)
+            .ok_or_else(|| format!("{}: pragma solidity introuvable", name))?;
+        let cap = Regex::new(r"uint256\s+(?:public\s+|private\s+|internal\s+|immutable\s+|constant\s+)*(cap|MAX_SUPPLY|maxSupply|_cap)\b")
+            .unwrap()
+            .captures(source)
+            .map(|c| c[1].to_string());
+
+        let signature = Regex::new(r"function\s+(\w+)\s*\([^)]*\)([^{;]*)\{").unwrap();
+        let mut transitions = Vec::new();
+        for captures in signature.captures_iter(source) {
+            let qualifiers = &captures[2];
+            let is_entry = qualifiers.contains("public") || qualifiers.contains("external");
+            if !is_entry || qualifiers.contains("view") || qualifiers.contains("pure") {
+                continue;
+            }
+            let body_start = captures.get(0).unwrap().end();
+            let body = function_body(&source[body_start..]);
+            let effects = extract_effects(body);
+            if effects.is_empty() {
+                continue;
+            }
+            transitions.push(Transition {
+                name: captures[1].to_string(),
+                guards: extract_guards(qualifiers, body, cap.as_deref()),
+                effects,
+                checked: checked_default && !body.contains("unchecked"),
+            });
+        }
+
+        Ok(Self { name: name.to_string(), cap, transitions })
+    }
+}
+
+/// Corps d'une fonction jusqu'à l'accolade fermante correspondante
+fn function_body(rest: &str) -> &str {
+    let mut depth = 1;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &rest[..i];
+                }
+            }
+            _ => {}
+        }
+    }
+    rest
+}
+
+fn extract_guards(qualifiers: &str, body: &str, cap: Option<&str>) -> Vec<Guard> {
+    let mut guards = Vec::new();
+    let owner_check = Regex::new(r"require\s*\(\s*msg\.sender\s*==\s*(owner|founder|_owner)\b|\bonly(Owner|Founder)\b").unwrap();
+    if owner_check.is_match(qualifiers) || owner_check.is_match(body) {
+        guards.push(Guard::OwnerOnly);
+    }
+    if qualifiers.contains("whenNotPaused") || Regex::new(r"require\s*\(\s*!\s*paused").unwrap().is_match(body) {
+        guards.push(Guard::NotPaused);
+    }
+    if let Some(cap) = cap {
+        let bound = Regex::new(&format!(r"totalSupply(\(\))?\s*\+\s*\w+\s*<=\s*{}\b", regex::escape(cap))).unwrap();
+        if bound.is_match(body) {
+            guards.push(Guard::CapRespected);
+        }
+    }
+    if Regex::new(r"require\s*\(\s*(balanceOf|_balances)\[[^\]]+\]\s*>=").unwrap().is_match(body) {
+        guards.push(Guard::BalanceCovers);
+    }
+    guards
+}
+
+fn extract_effects(body: &str) -> Vec<Effect> {
+    let mut effects = Vec::new();
+    let mut push = |effect: Effect| {
+        if !effects.contains(&effect) {
+            effects.push(effect);
+        }
+    };
+    if Regex::new(r"\b_mint\s*\(").unwrap().is_match(body) {
+        push(Effect::IncreaseSupply);
+        push(Effect::Credit);
+    }
+    if Regex::new(r"\b_burn\s*\(").unwrap().is_match(body) {
+        push(Effect::DecreaseSupply);
+        push(Effect::Debit);
+    }
+    if Regex::new(r"\b_transfer\s*\(").unwrap().is_match(body) {
+        push(Effect::Debit);
+        push(Effect::Credit);
+    }
+    if Regex::new(r"\b_totalSupply\s*\+=|\btotalSupply\s*\+=").unwrap().is_match(body) {
+        push(Effect::IncreaseSupply);
+    }
+    if Regex::new(r"\b_totalSupply\s*-=|\btotalSupply\s*-=").unwrap().is_match(body) {
+        push(Effect::DecreaseSupply);
+    }
+    if Regex::new(r"(balanceOf|_balances)\[[^\]]+\]\s*-=").unwrap().is_match(body) {
+        push(Effect::Debit);
+    }
+    if Regex::new(r"(balanceOf|_balances)\[[^\]]+\]\s*\+=").unwrap().is_match(body) {
+        push(Effect::Credit);
+    }
+    if Regex::new(r"\b_pause\s*\(|\b_?paused\s*=\s*true").unwrap().is_match(body) {
+        push(Effect::SetPaused(true));
+    }
+    if Regex::new(r"\b_unpause\s*\(|\b_?paused\s*=\s*false").unwrap().is_match(body) {
+        push(Effect::SetPaused(false));
+    }
+    effects
+}
+
+/// Affectation du contre-exemple: variable -> valeur
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Counterexample {
+    pub transition: String,
+    pub assignments: BTreeMap<String, String>,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values: Vec<String> = self.assignments.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        write!(f, "{}({})", self.transition, values.join(", "))
+    }
+}
+
+/// Verdict du solveur pour une propriété
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Verdict {
+    /// Toutes les requêtes insatisfiables; preuves Z3 par transition
+    Proven { proofs: BTreeMap<String, String> },
+    Counterexample(Counterexample),
+    /// Délai dépassé ou théorie non décidée
+    Unknown(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyResult {
+    pub property: Property,
+    pub verdict: Verdict,
+}
+
+/// Rapport de vérification d'un contrat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub contract: String,
+    pub model: ContractModel,
+    pub results: Vec<PropertyResult>,
+    pub is_valid: bool,
+    /// Empreinte de la source et des preuves, publiée avec le déploiement
+    pub proof_hash: String,
+    pub timestamp: String,
+}
+
+impl VerificationReport {
+    pub fn counterexamples(&self) -> Vec<(Property, &Counterexample)> {
+        self.results.iter()
+            .filter_map(|r| match &r.verdict {
+                Verdict::Counterexample(c) => Some((r.property, c)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Moteur de vérification: une requête Z3 par couple (propriété, transition)
+pub struct VerificationEngine {
+    pub timeout_ms: u64,
+    pub properties: Vec<Property>,
+}
+
+impl Default for VerificationEngine {
+    fn default() -> Self {
+        Self { timeout_ms: DEFAULT_SOLVER_TIMEOUT_MS, properties: Property::ALL.to_vec() }
+    }
+}
+
+impl VerificationEngine {
+    /// Vérifie `contracts/<nom>.sol`
+    pub fn verify_file(&self, path: &Path) -> Result<VerificationReport, String> {
+        let source = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+        let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        self.verify_source(&name, &source)
+    }
+
+    pub fn verify_source(&self, name: &str, source: &str) -> Result<VerificationReport, String> {
+        let model = ContractModel::from_solidity(name, source)?;
+        let results: Vec<PropertyResult> = self.properties.iter()
+            .map(|property| PropertyResult { property: *property, verdict: self.check_property(&model, *property) })
+            .collect();
+
+        let mut hasher = Sha256::new();
+        hasher.update(source.as_bytes());
+        for result in &results {
+            hasher.update(format!("{:?}", result).as_bytes());
+        }
+        let is_valid = results.iter().all(|r| matches!(r.verdict, Verdict::Proven { .. }));
+        Ok(VerificationReport {
+            contract: name.to_string(),
+            model,
+            results,
+            is_valid,
+            proof_hash: format!("0x{}", hex::encode(hasher.finalize())),
+            timestamp: Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Prouve la propriété sur toutes les transitions; premier contre-exemple sinon
+    pub fn check_property(&self, model: &ContractModel, property: Property) -> Verdict {
+        let mut proofs = BTreeMap::new();
+        for transition in &model.transitions {
+            match self.check_transition(property, transition) {
+                Verdict::Proven { proofs: proof } => proofs.extend(proof),
+                other => return other,
+            }
+        }
+        Verdict::Proven { proofs }
+    }
+
+    fn check_transition(&self, property: Property, transition: &Transition) -> Verdict {
+        let mut config = Config::new();
+        config.set_proof_generation(true);
+        config.set_timeout_msec(self.timeout_ms);
+        let ctx = Context::new(&config);
+        let solver = Solver::new(&ctx);
+
+        let pre = TokenState::symbolic(&ctx, "pre");
+        for constraint in pre.well_formed(&ctx) {
+            solver.assert(&constraint);
+        }
+        let (post, guard) = pre.apply(&ctx, transition);
+        solver.assert(&guard);
+
+        // Négation de l'invariant en sortie, l'invariant étant supposé en entrée
+        match property {
+            Property::NoMintBeyondCap => {
+                solver.assert(&pre.supply.le(&pre.cap));
+                solver.assert(&post.supply.gt(&pre.cap));
+            }
+            Property::OnlyOwnerCanPause => {
+                solver.assert(&pre.caller._eq(&pre.owner).not());
+                solver.assert(&post.paused._eq(&pre.paused).not());
+            }
+            Property::BalanceConservation => {
+                solver.assert(&post.total()._eq(&post.supply).not());
+            }
+        }
+
+        match solver.check() {
+            SatResult::Unsat => {
+                let proof = solver.get_proof().map(|p| format!("{:?}", p)).unwrap_or_default();
+                Verdict::Proven { proofs: BTreeMap::from([(transition.name.clone(), proof)]) }
+            }
+            SatResult::Sat => {
+                let mut assignments = BTreeMap::new();
+                if let Some(model) = solver.get_model() {
+                    for (name, value) in pre.named().into_iter().chain(post.named().into_iter().map(|(n, v)| (format!("{}'", n), v))) {
+                        if let Some(v) = model.eval(&value, true) {
+                            assignments.insert(name, v.to_string());
+                        }
+                    }
+                    for (name, value) in [("paused", &pre.paused), ("paused'", &post.paused)] {
+                        if let Some(v) = model.eval(value, true) {
+                            assignments.insert(name.to_string(), v.to_string());
+                        }
+                    }
+                }
+                Verdict::Counterexample(Counterexample { transition: transition.name.clone(), assignments })
+            }
+            SatResult::Unknown => Verdict::Unknown(solver.get_reason_unknown().unwrap_or_else(|| "inconnu".to_string())),
+        }
+    }
+}
+
+/// État symbolique du token: deux titulaires distincts et le reste des soldes
+#[derive(Clone)]
+struct TokenState<'ctx> {
+    supply: Int<'ctx>,
+    cap: Int<'ctx>,
+    from: Int<'ctx>,
+    to: Int<'ctx>,
+    rest: Int<'ctx>,
+    amount: Int<'ctx>,
+    caller: Int<'ctx>,
+    owner: Int<'ctx>,
+    paused: Bool<'ctx>,
+}
+
+impl<'ctx> TokenState<'ctx> {
+    fn symbolic(ctx: &'ctx Context, prefix: &str) -> Self {
+        let int = |name: &str| Int::new_const(ctx, format!("{}_{}", prefix, name));
+        Self {
+            supply: int("totalSupply"),
+            cap: int("cap"),
+            from: int("balance_from"),
+            to: int("balance_to"),
+            rest: int("balance_rest"),
+            amount: int("amount"),
+            caller: int("caller"),
+            owner: int("owner"),
+            paused: Bool::new_const(ctx, format!("{}_paused", prefix)),
+        }
+    }
+
+    fn named(&self) -> Vec<(String, Int<'ctx>)> {
+        vec![
+            ("totalSupply".to_string(), self.supply.clone()),
+            ("cap".to_string(), self.cap.clone()),
+            ("balance_from".to_string(), self.from.clone()),
+            ("balance_to".to_string(), self.to.clone()),
+            ("amount".to_string(), self.amount.clone()),
+            ("caller".to_string(), self.caller.clone()),
+            ("owner".to_string(), self.owner.clone()),
+        ]
+    }
+
+    fn total(&self) -> Int<'ctx> {
+        Int::add(self.supply.get_ctx(), &[&self.from, &self.to, &self.rest])
+    }
+
+    /// Valeurs uint256 et soldes cohérents avec l'offre
+    fn well_formed(&self, ctx: &'ctx Context) -> Vec<Bool<'ctx>> {
+        let mut constraints: Vec<Bool<'ctx>> = [&self.supply, &self.cap, &self.from, &self.to, &self.rest, &self.amount]
+            .iter()
+            .map(|v| in_uint256(ctx, v))
+            .collect();
+        constraints.push(self.total()._eq(&self.supply));
+        constraints
+    }
+
+    /// État après la transition et condition sous laquelle elle ne revert pas
+    fn apply(&self, ctx: &'ctx Context, transition: &Transition) -> (Self, Bool<'ctx>) {
+        let mut post = self.clone();
+        let mut guards = Vec::new();
+        for guard in &transition.guards {
+            guards.push(match guard {
+                Guard::OwnerOnly => self.caller._eq(&self.owner),
+                Guard::NotPaused => self.paused.not(),
+                Guard::CapRespected => Int::add(ctx, &[&self.supply, &self.amount]).le(&self.cap),
+                Guard::BalanceCovers => self.amount.le(&self.from),
+            });
+        }
+
+        let modulus = uint256_modulus(ctx);
+        let arith = |value: Int<'ctx>, guards: &mut Vec<Bool<'ctx>>| {
+            if transition.checked {
+                guards.push(in_uint256(ctx, &value));
+                value
+            } else {
+                value.modulo(&modulus)
+            }
+        };
+        for effect in &transition.effects {
+            match effect {
+                Effect::IncreaseSupply => post.supply = arith(Int::add(ctx, &[&post.supply, &self.amount]), &mut guards),
+                Effect::DecreaseSupply => post.supply = arith(Int::sub(ctx, &[&post.supply, &self.amount]), &mut guards),
+                Effect::Debit => post.from = arith(Int::sub(ctx, &[&post.from, &self.amount]), &mut guards),
+                Effect::Credit => post.to = arith(Int::add(ctx, &[&post.to, &self.amount]), &mut guards),
+                Effect::SetPaused(paused) => post.paused = Bool::from_bool(ctx, *paused),
+            }
+        }
+
+        let guard_refs: Vec<&Bool<'ctx>> = guards.iter().collect();
+        (post, Bool::and(ctx, &guard_refs))
+    }
+}
+
+/// 2^256, construit par produit de constantes pour rester en arithmétique linéaire
+fn uint256_modulus(ctx: &Context) -> Int<'_> {
+    let word = Int::from_u64(ctx, 1 << 32);
+    Int::mul(ctx, &[&word; 8])
+}
+
+fn in_uint256<'ctx>(ctx: &'ctx Context, value: &Int<'ctx>) -> Bool<'ctx> {
+    Bool::and(ctx, &[&value.ge(&Int::from_u64(ctx, 0)), &value.lt(&uint256_modulus(ctx))])
+}
+
+/// Vérification formelle d'un contrat de `contracts/` avant déploiement
+pub fn verify_contract_correctness(contract: &str) -> VerificationReport {
+    let path = Path::new(CONTRACTS_DIR).join(format!("{}.sol", contract));
+    let report = VerificationEngine::default().verify_file(&path).unwrap_or_else(|e| VerificationReport {
+        contract: contract.to_string(),
+        model: ContractModel { name: contract.to_string(), cap: None, transitions: Vec::new() },
+        results: vec![PropertyResult { property: Property::BalanceConservation, verdict: Verdict::Unknown(e) }],
+        is_valid: false,
+        proof_hash: String::new(),
+        timestamp: Utc::now().to_rfc3339(),
+    });
+
+    for result in &report.results {
+        match &result.verdict {
+            Verdict::Proven { proofs } => {
+                info!("✅ {:?}: prouvé sur {} transition(s)", result.property, proofs.len())
+            }
+            Verdict::Counterexample(c) => error!("⛔ {:?}: contre-exemple {}", result.property, c),
+            Verdict::Unknown(reason) => warn!("⚠️ {:?}: indécidé ({})", result.property, reason),
+        }
+    }
+    report
+}
+
+/// Nature d'une propriété générée pour un module muté
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PropertyKind {
+    /// `from_str(to_string(v)) == v` pour les structures sérialisables
+    RoundTrip,
+    /// `f(f(x)) == f(x)` pour les fonctions de normalisation
+    Idempotence,
+    /// Un compteur ne décroît jamais, quelle que soit la suite d'appels
+    MonotonicCounter,
+}
+
+/// Test proptest généré pour une cible du module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedProperty {
+    pub kind: PropertyKind,
+    pub target: String,
+    pub test_name: String,
+    pub code: String,
+}
+
+/// Suite proptest générée à partir de la source d'un module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertySuite {
+    pub module: String,
+    pub properties: Vec<GeneratedProperty>,
+}
+
+/// Nom du module de tests ajouté à la source mutée
+pub const GENERATED_TESTS_MODULE: &str = "aurorae_generated_properties";
+
+impl PropertySuite {
+    /// Détecte les cibles (structures sérialisables, normalisations, compteurs) et génère leurs propriétés
+    pub fn generate(module: &str, source: &str) -> Self {
+        let mut properties = Vec::new();
+        let structs = parse_structs(source);
+
+        for item in &structs {
+            let derives = |name: &str| item.derives.iter().any(|d| d == name);
+            if !(derives("Serialize") && derives("Deserialize") && derives("PartialEq") && derives("Debug")) {
+                continue;
+            }
+            let strategies: Option<Vec<String>> = item.fields.iter().map(|(_, ty)| strategy_for(ty)).collect();
+            if let Some(strategies) = strategies {
+                let test_name = format!("round_trip_{}", item.name.to_lowercase());
+                let params: Vec<String> = item.fields.iter().zip(&strategies)
+                    .map(|((field, _), strategy)| format!("{} in {}", field, strategy))
+                    .collect();
+                let fields: Vec<&str> = item.fields.iter().map(|(field, _)| field.as_str()).collect();
+                let code = format!(
+                    "    #[test]\n    fn {test}({params}) {{\n        let value = {name} {{ {fields} }};\n        \
+                     let encoded = serde_json::to_string(&value).unwrap();\n        \
+                     let decoded: {name} = serde_json::from_str(&encoded).unwrap();\n        \
+                     prop_assert_eq!(decoded, value);\n    }}\n",
+                    test = test_name, params = params.join(", "), name = item.name, fields = fields.join(", "),
+                );
+                properties.push(GeneratedProperty { kind: PropertyKind::RoundTrip, target: item.name.clone(), test_name, code });
+            }
+        }
+
+        let normalizer = Regex::new(
+            r"(?m)^pub fn ((?:normalize|normalise|sanitize|canonical|clamp|dedup|trim|strip|round)\w*)\s*\(\s*\w+\s*:\s*([\w&]+)\s*\)\s*->\s*(\w+)"
+        ).unwrap();
+        for captures in normalizer.captures_iter(source) {
+            let (function, input, output) = (&captures[1], &captures[2], &captures[3]);
+            let (strategy, call_once, call_twice) = match (input, output) {
+                ("&str", "String") => (strategy_for("String"), format!("{}(&x)", function), format!("{}(&once)", function)),
+                (input, output) if input == output => (strategy_for(input), format!("{}(x)", function), format!("{}(once.clone())", function)),
+                _ => continue,
+            };
+            let Some(strategy) = strategy else { continue };
+            let test_name = format!("idempotent_{}", function);
+            let code = format!(
+                "    #[test]\n    fn {test}(x in {strategy}) {{\n        let once = {once};\n        \
+                 prop_assert_eq!({twice}, once);\n    }}\n",
+                test = test_name, strategy = strategy, once = call_once, twice = call_twice,
+            );
+            properties.push(GeneratedProperty { kind: PropertyKind::Idempotence, target: function.to_string(), test_name, code });
+        }
+
+        let counter_name = Regex::new(r"count|total|nonce|sequence|generation|cycle|next_id").unwrap();
+        let mutator = Regex::new(r"pub fn (\w+)\s*\(\s*&mut self\s*\)").unwrap();
+        for item in &structs {
+            let has_default = item.derives.iter().any(|d| d == "Default")
+                || source.contains(&format!("impl Default for {} ", item.name));
+            let methods: Vec<String> = impl_blocks(source, &item.name).iter()
+                .flat_map(|body| mutator.captures_iter(body).map(|c| c[1].to_string()).collect::<Vec<_>>())
+                .collect();
+            if !has_default || methods.is_empty() {
+                continue;
+            }
+            for (field, ty) in &item.fields {
+                if !item.public_fields.contains(field) || !matches!(ty.as_str(), "u32" | "u64" | "usize" | "u128")
+                    || !counter_name.is_match(field)
+                {
+                    continue;
+                }
+                let test_name = format!("monotonic_{}_{}", item.name.to_lowercase(), field);
+                let arms: Vec<String> = methods.iter().enumerate()
+                    .map(|(i, method)| format!("                {} => {{ let _ = value.{}(); }}", i, method))
+                    .collect();
+                let code = format!(
+                    "    #[test]\n    fn {test}(calls in proptest::collection::vec(0..{n}usize, 0..32)) {{\n        \
+                     let mut value = {name}::default();\n        let mut previous = value.{field};\n        \
+                     for call in calls {{\n            match call {{\n{arms}\n                _ => {{}}\n            }}\n            \
+                     prop_assert!(value.{field} >= previous);\n            previous = value.{field};\n        }}\n    }}\n",
+                    test = test_name, n = methods.len(), name = item.name, field = field, arms = arms.join("\n"),
+                );
+                properties.push(GeneratedProperty {
+                    kind: PropertyKind::MonotonicCounter,
+                    target: format!("{}.{}", item.name, field),
+                    test_name,
+                    code,
+                });
+            }
+        }
+
+        Self { module: module.to_string(), properties }
+    }
+
+    /// Module de tests à ajouter en fin de source
+    pub fn render(&self) -> String {
+        let body: String = self.properties.iter().map(|p| p.code.as_str()).collect::<Vec<_>>().join("\n");
+        format!(
+            "\n#[cfg(test)]\nmod {} {{\n    use super::*;\n    use proptest::prelude::*;\n\n    proptest! {{\n{}    }}\n}}\n",
+            GENERATED_TESTS_MODULE, body,
+        )
+    }
+}
+
+struct ParsedStruct {
+    name: String,
+    derives: Vec<String>,
+    fields: Vec<(String, String)>,
+    public_fields: Vec<String>,
+}
+
+fn parse_structs(source: &str) -> Vec<ParsedStruct> {
+    let item = Regex::new(r"#\[derive\(([^)]*)\)\]\s*(?:#\[[^\]]*\]\s*)*(?:pub(?:\([^)]*\))?\s+)?struct\s+(\w+)\s*\{([^}]*)\}").unwrap();
+    let field = Regex::new(r"(?m)^\s*(pub(?:\([^)]*\))?\s+)?(\w+)\s*:\s*([^,\n]+?)\s*,?\s*(?://.*)?$").unwrap();
+    item.captures_iter(source)
+        .map(|c| {
+            let mut fields = Vec::new();
+            let mut public_fields = Vec::new();
+            for f in field.captures_iter(&c[3]) {
+                if f.get(1).is_some() {
+                    public_fields.push(f[2].to_string());
+                }
+                fields.push((f[2].to_string(), f[3].trim().to_string()));
+            }
+            ParsedStruct {
+                name: c[2].to_string(),
+                derives: c[1].split(',').map(|d| d.trim().rsplit("::").next().unwrap_or("").to_string()).collect(),
+                fields,
+                public_fields,
+            }
+        })
+        .collect()
+}
+
+/// Corps des blocs `impl <nom> { … }` (hors implémentations de traits)
+fn impl_blocks<'a>(source: &'a str, name: &str) -> Vec<&'a str> {
+    let header = Regex::new(&format!(r"impl\s+{}\s*\{{", regex::escape(name))).unwrap();
+    header.find_iter(source).map(|m| function_body(&source[m.end()..])).collect()
+}
+
+/// Stratégie proptest pour un type de champ; `None` si le type n'est pas pris en charge
+fn strategy_for(ty: &str) -> Option<String> {
+    let ty = ty.trim();
+    if let Some(inner) = ty.strip_prefix("Vec<").and_then(|t| t.strip_suffix('>')) {
+        return strategy_for(inner).map(|s| format!("proptest::collection::vec({}, 0..8)", s));
+    }
+    if let Some(inner) = ty.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) {
+        return strategy_for(inner).map(|s| format!("proptest::option::of({})", s));
+    }
+    match ty {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "bool" => {
+            Some(format!("any::<{}>()", ty))
+        }
+        // Bornes finies: NaN casserait l'égalité
+        "f32" => Some("-1.0e6f32..1.0e6f32".to_string()),
+        "f64" => Some("-1.0e9f64..1.0e9f64".to_string()),
+        "String" => Some("\"[a-zA-Z0-9 _-]{0,24}\"".to_string()),
+        _ => None,
+    }
+}
+
+/// Résultat de l'exécution d'une suite générée
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarnessReport {
+    pub module: String,
+    pub properties: Vec<GeneratedProperty>,
+    pub passed: bool,
+    /// Tests en échec (ou `compilation` si la source mutée ne compile pas)
+    pub failures: Vec<String>,
+}
+
+/// Exécute les suites générées sur une copie du crate contenant la source mutée
+pub struct PropertyHarness {
+    pub crate_root: PathBuf,
+    /// `PROPTEST_CASES` par propriété
+    pub cases: u32,
+}
+
+impl Default for PropertyHarness {
+    fn default() -> Self {
+        Self { crate_root: PathBuf::from("."), cases: 256 }
+    }
+}
+
+impl PropertyHarness {
+    /// Génère et exécute les propriétés de `module_file` (relatif au crate) muté en `source`
+    pub fn run(&self, module_file: &str, source: &str) -> Result<HarnessReport, String> {
+        let module_file = module_file.trim_start_matches("./");
+        let module_path = module_path(module_file)?;
+        let suite = PropertySuite::generate(&module_path, source);
+        if suite.properties.is_empty() {
+            info!("🧪 {}: aucune propriété générable", module_path);
+            return Ok(HarnessReport { module: module_path, properties: Vec::new(), passed: true, failures: Vec::new() });
+        }
+
+        let scratch = std::env::temp_dir().join(format!("aurorae-properties-{}", Uuid::new_v4()));
+        let result = self.run_in(&scratch, module_file, source, &suite);
+        fs::remove_dir_all(&scratch).ok();
+        let report = result?;
+
+        info!("🧪 {}: {} propriété(s), {}", report.module, report.properties.len(),
+                 if report.passed { "toutes vérifiées".to_string() } else { format!("échecs: {}", report.failures.join(", ")) });
+        Ok(report)
+    }
+
+    fn run_in(&self, scratch: &Path, module_file: &str, source: &str, suite: &PropertySuite) -> Result<HarnessReport, String> {
+        for entry in ["Cargo.toml", "Cargo.lock"] {
+            let from = self.crate_root.join(entry);
+            if from.exists() {
+                fs::create_dir_all(scratch).map_err(|e| format!("Erreur création {}: {}", scratch.display(), e))?;
+                fs::copy(&from, scratch.join(entry)).map_err(|e| format!("Erreur copie {}: {}", entry, e))?;
+            }
+        }
+        for file in WalkDir::new(self.crate_root.join("aurorae")).into_iter().filter_map(Result::ok) {
+            if !file.file_type().is_file() {
+                continue;
+            }
+            let relative = file.path().strip_prefix(&self.crate_root).map_err(|e| e.to_string())?;
+            let target = scratch.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Erreur création {}: {}", parent.display(), e))?;
+            }
+            fs::copy(file.path(), &target).map_err(|e| format!("Erreur copie {}: {}", relative.display(), e))?;
+        }
+        fs::write(scratch.join(module_file), format!("{}{}", source, suite.render()))
+            .map_err(|e| format!("Erreur écriture {}: {}", module_file, e))?;
+
+        // Cible partagée avec le crate pour ne recompiler que le module muté
+        let output = Command::new("cargo")
+            .args(["test", "--lib", "--manifest-path"])
+            .arg(scratch.join("Cargo.toml"))
+            .arg(format!("{}::{}", suite.module, GENERATED_TESTS_MODULE))
+            .env("CARGO_TARGET_DIR", self.crate_root.join("target").join("properties"))
+            .env("PROPTEST_CASES", self.cases.to_string())
+            .output()
+            .map_err(|e| format!("Exécution de cargo impossible: {}", e))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let failed = Regex::new(r"(?m)^test (\S+) \.\.\. FAILED").unwrap();
+        let mut failures: Vec<String> = failed.captures_iter(&stdout)
+            .map(|c| c[1].rsplit("::").next().unwrap_or(&c[1]).to_string())
+            .collect();
+        if !output.status.success() && failures.is_empty() {
+            failures.push("compilation".to_string());
+        }
+        Ok(HarnessReport {
+            module: suite.module.clone(),
+            properties: suite.properties.clone(),
+            passed: output.status.success(),
+            failures,
+        })
+    }
+}
+
+/// `aurorae/economy.rs` -> `economy`, `aurorae/net/mod.rs` -> `net`
+fn module_path(module_file: &str) -> Result<String, String> {
+    let relative = module_file.strip_prefix("aurorae/")
+        .and_then(|f| f.strip_suffix(".rs"))
+        .ok_or_else(|| format!("{} n'est pas un module du crate", module_file))?;
+    Ok(relative.trim_end_matches("/mod").replace('/', "::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPPED: &str = r#"
+        pragma solidity ^0.8.20;
+        contract Capped {
+            uint256 public totalSupply;
+            uint256 public immutable cap;
+            address public owner;
+            bool public paused;
+            mapping(address => uint256) public balanceOf;
+
+            function mint(address to, uint256 amount) external onlyOwner {
+                require(totalSupply + amount <= cap, "cap");
+                totalSupply += amount;
+                balanceOf[to] += amount;
+            }
+            function transfer(address to, uint256 value) public whenNotPaused returns (bool) {
+                require(balanceOf[msg.sender] >= value, "balance");
+                balanceOf[msg.sender] -= value;
+                balanceOf[to] += value;
+                return true;
+            }
+            function pause() external {
+                require(msg.sender == owner);
+                paused = true;
+            }
+        }
+    "#;
+
+    fn verdict(report: &VerificationReport, property: Property) -> &Verdict {
+        &report.results.iter().find(|r| r.property == property).unwrap().verdict
+    }
+
+    #[test]
+    fn extracts_guards_and_effects() {
+        let model = ContractModel::from_solidity("Capped", CAPPED).unwrap();
+        assert_eq!(model.cap.as_deref(), Some("cap"));
+        let mint = model.transitions.iter().find(|t| t.name == "mint").unwrap();
+        assert_eq!(mint.guards, vec![Guard::OwnerOnly, Guard::CapRespected]);
+        assert_eq!(mint.effects, vec![Effect::IncreaseSupply, Effect::Credit]);
+        assert!(mint.checked);
+    }
+
+    #[test]
+    fn proves_capped_token_properties() {
+        let report = VerificationEngine::default().verify_source("Capped", CAPPED).unwrap();
+        assert!(report.is_valid, "{:?}", report.counterexamples());
+        assert!(report.proof_hash.starts_with("0x"));
+    }
+
+    #[test]
+    fn finds_counterexamples() {
+        let broken = CAPPED
+            .replace("require(totalSupply + amount <= cap, \"cap\");", "")
+            .replace("require(msg.sender == owner);", "")
+            .replace("balanceOf[msg.sender] -= value;", "");
+        let report = VerificationEngine::default().verify_source("Broken", &broken).unwrap();
+        assert!(!report.is_valid);
+        for property in Property::ALL {
+            match verdict(&report, property) {
+                Verdict::Counterexample(c) => assert!(!c.assignments.is_empty()),
+                other => panic!("{:?}: {:?}", property, other),
+            }
+        }
+    }
+
+    #[test]
+    fn unchecked_arithmetic_breaks_conservation() {
+        let source = r#"
+            pragma solidity ^0.8.20;
+            contract Loose {
+                uint256 public totalSupply;
+                mapping(address => uint256) public balanceOf;
+                function transfer(address to, uint256 value) public returns (bool) {
+                    unchecked {
+                        balanceOf[msg.sender] -= value;
+                        balanceOf[to] += value;
+                    }
+                    return true;
+                }
+            }
+        "#;
+        let report = VerificationEngine::default().verify_source("Loose", source).unwrap();
+        assert!(matches!(verdict(&report, Property::BalanceConservation), Verdict::Counterexample(_)));
+        assert!(matches!(verdict(&report, Property::OnlyOwnerCanPause), Verdict::Proven { .. }));
+    }
+
+    const EVOLVED: &str = r#"
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    pub label: String,
+    pub entry_count: u64,
+    pub weights: Vec<f64>,
+    pub owner: Option<u32>,
+}
+
+impl Ledger {
+    pub fn record(&mut self) {
+        self.entry_count += 1;
+    }
+}
+
+pub fn normalize_label(label: &str) -> String {
+    label.trim().to_lowercase()
+}
+"#;
+
+    #[test]
+    fn generates_properties_for_evolved_module() {
+        let suite = PropertySuite::generate("ledger", EVOLVED);
+        let kinds: Vec<(PropertyKind, &str)> = suite.properties.iter().map(|p| (p.kind, p.target.as_str())).collect();
+        assert_eq!(kinds, vec![
+            (PropertyKind::RoundTrip, "Ledger"),
+            (PropertyKind::Idempotence, "normalize_label"),
+            (PropertyKind::MonotonicCounter, "Ledger.entry_count"),
+        ]);
+
+        let rendered = suite.render();
+        assert!(rendered.contains("mod aurorae_generated_properties"));
+        assert!(rendered.contains("fn round_trip_ledger(label in"));
+        assert!(rendered.contains("prop_assert_eq!(normalize_label(&once), once);"));
+        assert!(rendered.contains("0 => { let _ = value.record(); }"));
+    }
+
+    #[test]
+    fn skips_unsupported_targets() {
+        let source = "#[derive(Debug, PartialEq, Serialize, Deserialize)]\npub struct Pool {\n    pub chain: ChainConfig,\n}\n";
+        assert!(PropertySuite::generate("pool", source).properties.is_empty());
+        assert_eq!(module_path("./aurorae/economy.rs".trim_start_matches("./")).unwrap(), "economy");
+        assert!(module_path("frontend/app.rs").is_err());
+    }
+}
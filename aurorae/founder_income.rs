@@ -1,11 +1,42 @@
 //! founder_income.rs — Gestion automatique des revenus fondateur
+//!
+//! Les revenus sont répartis entre fondateur, écosystème et trésorerie selon la table
+//! de `payouts.toml`. Chaque part est cumulée en $AURA dans un registre persistant puis
+//! versée on-chain en monnaie native dès qu'elle dépasse le seuil de versement, pour ne
+//! pas payer le gas d'un transfert par petite récompense. Les versements au-delà du
+//! seuil d'approbation attendent les signatures exigées par `approvals`.
 
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use chrono::Utc;
 use lazy_static::lazy_static;
 use parking_lot::RwLock;
+use ethers::types::{Address, H256, U256};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::approvals::{ApprovalCheck, WithdrawalSource, APPROVALS};
+use crate::audit::{self, AuditKind};
+use crate::blockchain_core::SharedTxManager;
+use crate::currency::{Asset, ExchangeRateTable};
+use crate::keystore::{Signer, SigningPolicy};
+
+pub const DEFAULT_PAYOUTS_PATH: &str = "payouts.toml";
+/// Échecs de versement conservés au registre (les plus anciens sont oubliés)
+pub const MAX_FAILED_RECORDS: usize = 50;
 
 /// 💼 Adresse du fondateur
 lazy_static! {
     pub static ref FOUNDER_ADDRESS: RwLock<String> = RwLock::new(String::from("0xd532260c561cb3c17E9fbB4961cC6485f97e375E"));
+    pub static ref PAYOUT_CONFIG: RwLock<PayoutConfig> = RwLock::new(PayoutConfig::load(DEFAULT_PAYOUTS_PATH).unwrap_or_else(|e| {
+        warn!("⚠️ {}: répartition par défaut", e);
+        PayoutConfig::default()
+    }));
+    pub static ref PAYOUT_LEDGER: RwLock<PayoutLedger> = RwLock::new(PayoutLedger::load());
 }
 
 /// Met à jour dynamiquement l’adresse du fondateur
@@ -13,11 +44,455 @@ pub fn set_founder_address(addr: &str) {
     *FOUNDER_ADDRESS.write() = addr.to_string();
 }
 
-/// Transfert de récompense vers le fondateur
+/// Bénéficiaire d'une part des revenus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PayoutRecipient {
+    Founder,
+    Ecosystem,
+    Treasury,
+}
+
+impl PayoutRecipient {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PayoutRecipient::Founder => "fondateur",
+            PayoutRecipient::Ecosystem => "écosystème",
+            PayoutRecipient::Treasury => "trésorerie",
+        }
+    }
+}
+
+/// Pourcentages de répartition (total 100)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PayoutSplits {
+    pub founder: f64,
+    pub ecosystem: f64,
+    pub treasury: f64,
+}
+
+impl PayoutSplits {
+    pub fn validate(&self) -> Result<(), String> {
+        if [self.founder, self.ecosystem, self.treasury].iter().any(|p| *p < 0.0 || !p.is_finite()) {
+            return Err("Pourcentage de répartition négatif ou invalide".to_string());
+        }
+        let total = self.founder + self.ecosystem + self.treasury;
+        if (total - 100.0).abs() > 1e-6 {
+            return Err(format!("Répartition de {:.2} % au lieu de 100 %", total));
+        }
+        Ok(())
+    }
+}
+
+/// Adresses de versement de l'écosystème et de la trésorerie
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PayoutRecipients {
+    pub ecosystem: Option<Address>,
+    pub treasury: Option<Address>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PayoutConfig {
+    pub splits: PayoutSplits,
+    /// Montant natif minimal d'un versement; en dessous, la part reste cumulée
+    pub min_payout: f64,
+    #[serde(default)]
+    pub recipients: PayoutRecipients,
+}
+
+impl Default for PayoutConfig {
+    fn default() -> Self {
+        Self {
+            splits: PayoutSplits { founder: 30.0, ecosystem: 50.0, treasury: 20.0 },
+            min_payout: 0.01,
+            recipients: PayoutRecipients::default(),
+        }
+    }
+}
+
+impl PayoutConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content)
+            .map_err(|e| format!("Configuration des versements invalide: {}", e))?;
+        config.splits.validate()?;
+        if config.min_payout < 0.0 {
+            return Err("Seuil de versement négatif".to_string());
+        }
+        Ok(config)
+    }
+
+    /// Adresse on-chain du bénéficiaire, si configurée
+    pub fn address_of(&self, recipient: PayoutRecipient) -> Option<Address> {
+        match recipient {
+            PayoutRecipient::Founder => FOUNDER_ADDRESS.read().parse().ok(),
+            PayoutRecipient::Ecosystem => self.recipients.ecosystem,
+            PayoutRecipient::Treasury => self.recipients.treasury,
+        }
+    }
+}
+
+/// Recharge `payouts.toml`; la configuration courante est conservée si le fichier est invalide
+pub fn reload_payout_config(path: &str) -> Result<(), String> {
+    let config = PayoutConfig::load(path)?;
+    *PAYOUT_CONFIG.write() = config;
+    info!("📐 Répartition des revenus rechargée depuis {}", path);
+    Ok(())
+}
+
+/// Parts d'un revenu réparti
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RevenueShares {
+    pub founder: f64,
+    /// Part écosystème sans adresse de versement, à redistribuer dans l'économie interne
+    pub ecosystem: f64,
+    pub treasury: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PayoutStatus {
+    Sent,
+    Failed(String),
+}
+
+/// Versement tenté, tel qu'inscrit au registre
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutRecord {
+    pub id: Uuid,
+    pub recipient: PayoutRecipient,
+    pub address: Address,
+    /// Montant cumulé versé ($AURA)
+    pub amount: f64,
+    pub native_amount: f64,
+    pub native_symbol: String,
+    pub tx_hash: Option<H256>,
+    pub status: PayoutStatus,
+    pub timestamp: String,
+    /// Nouvelles tentatives échouées regroupées dans cet enregistrement
+    #[serde(default)]
+    pub retries: u32,
+}
+
+/// Registre persistant: parts cumulées non versées et historique des versements
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PayoutLedger {
+    pub accrued: BTreeMap<PayoutRecipient, f64>,
+    pub records: Vec<PayoutRecord>,
+}
+
+impl PayoutLedger {
+    pub fn accrue(&mut self, recipient: PayoutRecipient, amount: f64) {
+        if amount > 0.0 && amount.is_finite() {
+            *self.accrued.entry(recipient).or_insert(0.0) += amount;
+        }
+    }
+
+    pub fn accrued(&self, recipient: PayoutRecipient) -> f64 {
+        self.accrued.get(&recipient).copied().unwrap_or(0.0)
+    }
+
+    /// Total versé avec succès à un bénéficiaire ($AURA)
+    pub fn total_paid(&self, recipient: PayoutRecipient) -> f64 {
+        self.records.iter()
+            .filter(|r| r.recipient == recipient && r.status == PayoutStatus::Sent)
+            .map(|r| r.amount)
+            .sum()
+    }
+
+    /// Inscrit une tentative de versement: un succès solde le montant versé, les échecs
+    /// successifs d'un même bénéficiaire sont regroupés et l'historique d'échecs est borné
+    pub fn record_attempt(&mut self, record: PayoutRecord) {
+        if record.status == PayoutStatus::Sent {
+            // Seul le montant versé est soldé: les crédits reçus pendant l'envoi restent dus
+            let remaining = (self.accrued(record.recipient) - record.amount).max(0.0);
+            self.accrued.insert(record.recipient, remaining);
+            self.records.push(record);
+            return;
+        }
+
+        let last = self.records.iter_mut().rev().find(|r| r.recipient == record.recipient);
+        match last {
+            Some(last) if last.status != PayoutStatus::Sent => {
+                let retries = last.retries + 1;
+                *last = PayoutRecord { retries, ..record };
+            }
+            _ => self.records.push(record),
+        }
+
+        let failed = self.records.iter().filter(|r| r.status != PayoutStatus::Sent).count();
+        let mut excess = failed.saturating_sub(MAX_FAILED_RECORDS);
+        self.records.retain(|r| {
+            if excess > 0 && r.status != PayoutStatus::Sent {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+
+    /// 💾 Sauvegarde sur disque
+    pub fn save(&self) {
+        let dir = Path::new("aurorae_state");
+        if create_dir_all(dir).is_ok() {
+            if let Ok(file) = File::create(dir.join("payout_ledger.json")) {
+                let writer = BufWriter::new(file);
+                let _ = serde_json::to_writer_pretty(writer, &self);
+            }
+        }
+    }
+
+    /// 📥 Chargement depuis disque (registre vide si absent)
+    pub fn load() -> Self {
+        File::open("aurorae_state/payout_ledger.json")
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Crédite la part du fondateur au registre; le versement on-chain a lieu dans `execute_payouts`
 pub fn reward_founder(amount: f64) {
     let address = FOUNDER_ADDRESS.read().clone();
-    println!(
-        "[AURORAE++] ⚡ Transfert automatique de {:.4} vers le fondateur → {}",
-        amount, address
+    let mut ledger = PAYOUT_LEDGER.write();
+    ledger.accrue(PayoutRecipient::Founder, amount);
+    ledger.save();
+    info!(
+        "⚡ {:.4} crédités au fondateur → {} (à verser: {:.4})",
+        amount, address, ledger.accrued(PayoutRecipient::Founder)
+    );
+}
+
+/// Répartit un revenu selon la table configurée; les parts versables on-chain sont cumulées
+/// au registre, la part écosystème sans adresse est retournée pour redistribution interne
+pub fn allocate_revenue(amount: f64) -> RevenueShares {
+    let config = PAYOUT_CONFIG.read().clone();
+    let share = |percent: f64| amount * percent / 100.0;
+    let shares = RevenueShares {
+        founder: share(config.splits.founder),
+        ecosystem: share(config.splits.ecosystem),
+        treasury: share(config.splits.treasury),
+    };
+
+    let mut ledger = PAYOUT_LEDGER.write();
+    ledger.accrue(PayoutRecipient::Founder, shares.founder);
+    ledger.accrue(PayoutRecipient::Treasury, shares.treasury);
+    let ecosystem_local = if config.recipients.ecosystem.is_some() {
+        ledger.accrue(PayoutRecipient::Ecosystem, shares.ecosystem);
+        0.0
+    } else {
+        shares.ecosystem
+    };
+    ledger.save();
+
+    info!(
+        "📐 Revenu de {:.4} réparti: fondateur {:.4} • écosystème {:.4} • trésorerie {:.4}",
+        amount, shares.founder, shares.ecosystem, shares.treasury
+    );
+    RevenueShares { ecosystem: ecosystem_local, ..shares }
+}
+
+/// Verse les parts cumulées dépassant le seuil, converties de $AURA vers la monnaie
+/// native `native` au taux courant; les parts inférieures restent cumulées
+pub async fn execute_payouts(
+    signer: &dyn Signer,
+    tx: &SharedTxManager,
+    policy: &SigningPolicy,
+    rates: &ExchangeRateTable,
+    native: &Asset,
+) -> Result<Vec<PayoutRecord>, String> {
+    let config = PAYOUT_CONFIG.read().clone();
+    let rate = rates.rate(&Asset::Aura, native)?;
+    let accrued: Vec<(PayoutRecipient, f64)> = PAYOUT_LEDGER.read().accrued.iter()
+        .map(|(recipient, amount)| (*recipient, *amount))
+        .collect();
+
+    let mut executed = Vec::new();
+    for (recipient, accrued_amount) in accrued {
+        let native_amount = accrued_amount * rate;
+        if native_amount < config.min_payout || native_amount <= 0.0 {
+            continue;
+        }
+        let Some(address) = config.address_of(recipient) else { continue };
+
+        // Au-delà du seuil d'approbation, seul un montant approuvé (N sur M) peut partir
+        let description = format!("versement {}", recipient.label());
+        let check = APPROVALS.write().authorize(WithdrawalSource::Payout, Some(address), native_amount, &native.symbol(), &description);
+        let (native_amount, approval) = match check {
+            ApprovalCheck::NotRequired => (native_amount, None),
+            ApprovalCheck::Approved { id, amount } => (amount.min(native_amount), Some(id)),
+            ApprovalCheck::Pending(id) => {
+                info!("✋ Versement {} de {:.6} en attente d'approbation ({})", recipient.label(), native_amount, id);
+                continue;
+            }
+        };
+        let amount = native_amount / rate;
+
+        let result = send_native(signer, tx, policy, address, native_amount).await;
+        if let (Ok(_), Some(id)) = (&result, approval) {
+            APPROVALS.write().mark_executed(&id);
+        }
+        let record = PayoutRecord {
+            id: Uuid::new_v4(),
+            recipient,
+            address,
+            amount,
+            native_amount,
+            native_symbol: native.symbol(),
+            tx_hash: result.as_ref().ok().copied(),
+            status: match &result {
+                Ok(_) => PayoutStatus::Sent,
+                Err(e) => PayoutStatus::Failed(e.clone()),
+            },
+            timestamp: Utc::now().to_rfc3339(),
+            retries: 0,
+        };
+        match &result {
+            Ok(tx_hash) => {
+                info!(
+                    "⚡ Versement de {:.6} {} ({}) → {:?}: {:?}",
+                    native_amount, native.symbol(), recipient.label(), address, tx_hash
+                );
+                audit::record(
+                    AuditKind::FundsMoved,
+                    "founder_income",
+                    &format!("versement {}", recipient.label()),
+                    serde_json::json!({
+                        "amount": native_amount,
+                        "asset": native.symbol(),
+                        "to": format!("{:?}", address),
+                        "tx": format!("{:?}", tx_hash),
+                        "approval": approval,
+                    }),
+                );
+            }
+            Err(e) => warn!("⚠️ Versement {} impossible: {}", recipient.label(), e),
+        }
+
+        let mut ledger = PAYOUT_LEDGER.write();
+        ledger.record_attempt(record.clone());
+        ledger.save();
+        executed.push(record);
+    }
+    Ok(executed)
+}
+
+/// Verse on-chain la part du fondateur; la transaction est signée par le signataire
+/// du keystore, qui doit être celui du gestionnaire de transactions, et les gros montants
+/// exigent un signataire externe selon `policy`
+pub async fn pay_founder(signer: &dyn Signer, tx: &SharedTxManager, policy: &SigningPolicy, amount: f64) -> Result<H256, String> {
+    let founder: Address = FOUNDER_ADDRESS.read()
+        .parse()
+        .map_err(|e| format!("Adresse fondateur invalide: {}", e))?;
+    let tx_hash = send_native(signer, tx, policy, founder, amount).await?;
+    info!(
+        "⚡ Paiement on-chain de {:.6} au fondateur via {} → {:?}",
+        amount, signer.label(), tx_hash
     );
+    Ok(tx_hash)
+}
+
+async fn send_native(signer: &dyn Signer, tx: &SharedTxManager, policy: &SigningPolicy, to: Address, amount: f64) -> Result<H256, String> {
+    if tx.account() != signer.address() {
+        return Err(format!(
+            "Signataire {} différent du compte du backend {}",
+            signer.address(), tx.account()
+        ));
+    }
+    policy.check_transfer(signer, amount)?;
+    let value = ethers::utils::parse_ether(amount)
+        .map_err(|e| format!("Montant invalide: {}", e))?;
+    if value == U256::zero() {
+        return Err("Montant nul".to_string());
+    }
+    tx.submit(to, value, None).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payout_config_rejects_splits_not_totalling_100() {
+        let valid = "min-payout = 0.05\n[splits]\nfounder = 30.0\necosystem = 50.0\ntreasury = 20.0\n";
+        let config = PayoutConfig::from_toml(valid).unwrap();
+        assert_eq!(config.splits.founder, 30.0);
+        assert!(config.recipients.treasury.is_none());
+
+        let invalid = "min-payout = 0.05\n[splits]\nfounder = 40.0\necosystem = 50.0\ntreasury = 20.0\n";
+        assert!(PayoutConfig::from_toml(invalid).is_err());
+    }
+
+    fn attempt(recipient: PayoutRecipient, amount: f64, status: PayoutStatus) -> PayoutRecord {
+        PayoutRecord {
+            id: Uuid::new_v4(),
+            recipient,
+            address: Address::zero(),
+            amount,
+            native_amount: amount,
+            native_symbol: "ETH".to_string(),
+            tx_hash: None,
+            status,
+            timestamp: Utc::now().to_rfc3339(),
+            retries: 0,
+        }
+    }
+
+    #[test]
+    fn failed_payouts_keep_the_share_due_until_a_retry_succeeds() {
+        let mut ledger = PayoutLedger::default();
+        ledger.accrue(PayoutRecipient::Founder, 10.0);
+
+        for _ in 0..3 {
+            ledger.record_attempt(attempt(PayoutRecipient::Founder, 10.0, PayoutStatus::Failed("rpc".to_string())));
+        }
+        assert_eq!(ledger.accrued(PayoutRecipient::Founder), 10.0);
+        assert_eq!(ledger.records.len(), 1);
+        assert_eq!(ledger.records[0].retries, 2);
+
+        ledger.accrue(PayoutRecipient::Founder, 2.0);
+        ledger.record_attempt(attempt(PayoutRecipient::Founder, 10.0, PayoutStatus::Sent));
+        assert_eq!(ledger.accrued(PayoutRecipient::Founder), 2.0);
+        assert_eq!(ledger.total_paid(PayoutRecipient::Founder), 10.0);
+        assert_eq!(ledger.records.len(), 2);
+    }
+
+    #[test]
+    fn failure_history_is_bounded() {
+        let mut ledger = PayoutLedger::default();
+        for _ in 0..MAX_FAILED_RECORDS + 10 {
+            ledger.record_attempt(attempt(PayoutRecipient::Treasury, 1.0, PayoutStatus::Failed("rpc".to_string())));
+            ledger.record_attempt(attempt(PayoutRecipient::Treasury, 0.0, PayoutStatus::Sent));
+        }
+        let failed = ledger.records.iter().filter(|r| r.status != PayoutStatus::Sent).count();
+        assert_eq!(failed, MAX_FAILED_RECORDS);
+        assert_eq!(ledger.records.len(), MAX_FAILED_RECORDS + MAX_FAILED_RECORDS + 10);
+    }
+
+    #[tokio::test]
+    async fn local_signer_pays_above_the_external_threshold_by_default() {
+        use crate::blockchain_core::{SimulatedBackend, TransactionManager};
+        use crate::keystore::WalletSigner;
+
+        let signer = WalletSigner::random("test");
+        let backend = SimulatedBackend::new(1, signer.address(), ethers::utils::parse_ether(100).unwrap());
+        let tx = TransactionManager::new(std::sync::Arc::new(backend)).shared();
+        let to = Address::from_low_u64_be(0xa11ce);
+
+        // Politique stricte: refus, le versement reste dû et sera retenté
+        assert!(send_native(&signer, &tx, &SigningPolicy::default(), to, 5.0).await.is_err());
+        // Sans signataire externe configuré: signé localement
+        let policy = SigningPolicy::for_signer(&signer);
+        assert!(send_native(&signer, &tx, &policy, to, 5.0).await.is_ok());
+        assert_eq!(tx.backend().get_balance(to).await.unwrap(), ethers::utils::parse_ether(5).unwrap());
+        assert_eq!(tx.lock().await.pending.len(), 1);
+
+        // Un signataire étranger au backend est toujours refusé
+        let other = WalletSigner::random("other");
+        assert!(send_native(&other, &tx, &policy, to, 0.5).await.is_err());
+    }
 }
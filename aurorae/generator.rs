@@ -1,10 +1,54 @@
+//! AURORAE++ - generator.rs
+//!
+//! Génération de modules Rust à partir de spécifications typées (structures, traits,
+//! squelettes d'implémentation, tests) assemblées avec syn/quote: la sortie est toujours
+//! analysable, mise en forme par prettyplease, puis inscrite dans lib.rs derrière une
+//! feature `generated-<nom>`.
+
 use std::fs::{create_dir_all, File};
-use std::io::{Write, Result};
-use std::path::Path;
+use std::io::{BufReader, BufWriter, Write, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use uuid::Uuid;
 use chrono::Utc;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use proc_macro2::TokenStream;
+use quote::quote;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use syn::Ident;
+use tracing::{error, info, warn};
 use crate::rust_analyzer::analyze;  // Utilisation du module local rust_analyzer
-use crate::clippy_integration::run_clippy; // Utilisation du module local clippy_integration
+use crate::clippy_integration::{run_clippy, LINT_HISTORY}; // Utilisation du module local clippy_integration
+use crate::security::{log_security_event, SecurityEvent};
+use crate::templates::{TemplateArgs, TEMPLATES};
+
+/// Section de lib.rs recevant les modules générés
+pub const GENERATED_SECTION: &str = "// ==================== MODULES GÉNÉRÉS ====================";
+/// Dossier (relatif à `aurorae/`) des modules générés inscrits dans lib.rs
+pub const GENERATED_DIR: &str = "generated";
+/// Moteur de conteneurs de la porte isolée (`docker` par défaut, `podman`…)
+pub const GATE_RUNTIME_ENV: &str = "AURORAE_GATE_RUNTIME";
+/// Image Rust de la porte isolée (`rust:1-slim` par défaut)
+pub const GATE_IMAGE_ENV: &str = "AURORAE_GATE_IMAGE";
+
+lazy_static! {
+    /// File des tâches de génération issues de la feuille de route
+    pub static ref GENERATION_QUEUE: Mutex<GenerationQueue> = Mutex::new(GenerationQueue::open(generation_queue_path()));
+}
+
+/// File persistée des tâches de génération
+pub const GENERATION_QUEUE_PATH: &str = "aurorae_state/generation_tasks.json";
+
+/// Les tests écrivent dans une file temporaire, jamais dans celle du système
+fn generation_queue_path() -> PathBuf {
+    if cfg!(test) {
+        std::env::temp_dir().join(format!("aurorae-generation-{}", std::process::id())).join("generation_tasks.json")
+    } else {
+        PathBuf::from(GENERATION_QUEUE_PATH)
+    }
+}
 
 #[derive(Debug)]
 pub struct GeneratedModule {
@@ -32,62 +76,856 @@ impl GeneratedModule {
         let file_path = dir_path.join("mod.rs");
 
         // Exécution de l'analyse de code avant la sauvegarde
-        let analysis_result = analyze(&self.content);
-        if !analysis_result.warnings.is_empty() {  // Vérification des avertissements dans l'analyse
-            // Si des erreurs sont présentes, effectuer un refactoring
-            println!("[AURORAE++] ⚠️ Erreurs détectées dans le code, refactoring...");
-            self.refactor_code();
-        } else {
-            println!("[AURORAE++] ✅ Analyse réussie sans erreurs.");
+        match analyze(&self.content) {
+            Ok(report) if !report.is_valid() => {
+                // Si des erreurs sont présentes, effectuer un refactoring
+                warn!("⚠️ {} erreur(s) détectée(s) dans le code, refactoring...", report.errors.len());
+                for error in &report.errors {
+                    info!("   {}", error);
+                }
+                self.refactor_code();
+            }
+            Ok(report) => info!(
+                "✅ Analyse réussie sans erreurs ({} avertissement(s), dont {} de code mort).",
+                report.warnings.len(),
+                report.dead_code.len()
+            ),
+            Err(e) => warn!("⚠️ Analyse rust-analyzer indisponible: {}", e),
         }
 
-        // Exécution de l'analyse avec Clippy pour détecter les problèmes de style
-        let clippy_result = run_clippy(&self.content);
-        if !clippy_result.warnings.is_empty() {  // Vérification des avertissements dans les résultats de Clippy
-            println!("[AURORAE++] ⚠️ Clippy a trouvé des avertissements, les suggestions seront appliquées.");
-            // Appliquer les suggestions Clippy pour améliorer le code généré
-            self.apply_clippy_suggestions();
+        // Exécution de l'analyse avec Clippy: les suggestions sûres corrigent le code écrit
+        let mut content = self.content.clone();
+        match run_clippy(&self.content) {
+            Ok(lint) => {
+                let score = LINT_HISTORY.lock().record(&format!("generated/{}", self.name), &lint.report);
+                if !lint.report.warnings().is_empty() || lint.applied > 0 {
+                    warn!("⚠️ Clippy: {} avertissement(s) restant(s), {} suggestion(s) appliquée(s), score {:.1}",
+                             score.warnings, lint.applied, score.score);
+                }
+                content = lint.fixed;
+            }
+            Err(e) => warn!("⚠️ Analyse clippy indisponible: {}", e),
         }
 
         let mut file = File::create(file_path)?; // Créer et ouvrir le fichier mod.rs
-        file.write_all(self.content.as_bytes())?;  // Écrire le contenu dans le fichier
+        file.write_all(content.as_bytes())?;  // Écrire le contenu dans le fichier
+        log_security_event(SecurityEvent::FileModified { module: "generator".to_string(), path: full_path.clone() });
 
-        println!("[AURORAE++] Module {} enregistré à {}", self.name, full_path);
+        info!("Module {} enregistré à {}", self.name, full_path);
         Ok(())
     }
 
     // Méthode pour effectuer un refactoring si des problèmes sont détectés dans l'analyse
     fn refactor_code(&self) {
         // Logiciel de refactoring - pourrait utiliser des outils comme `rustfmt` ou des suggestions de `clippy`
-        println!("[AURORAE++] 🔧 Refactoring du module {}", self.name);
+        info!("🔧 Refactoring du module {}", self.name);
         // Implémenter ici les suggestions d'amélioration, comme l'optimisation de la gestion des erreurs ou de la mémoire
     }
+}
+
+// ==================== SPÉCIFICATIONS ====================
+
+/// Champ d'une structure ou argument d'une méthode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldSpec {
+    pub name: String,
+    pub ty: String,
+}
+
+impl FieldSpec {
+    pub fn new(name: &str, ty: &str) -> Self {
+        Self { name: name.to_string(), ty: ty.to_string() }
+    }
+}
+
+/// Structure générée (toujours `Debug`, `Clone` et `Default`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructSpec {
+    pub name: String,
+    pub doc: String,
+    pub fields: Vec<FieldSpec>,
+}
+
+/// Méthode ou fonction; sans corps, un squelette renvoie `Default::default()`
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MethodSpec {
+    pub name: String,
+    pub doc: String,
+    /// Prend `&self`
+    pub receiver: bool,
+    /// `&mut self` plutôt que `&self` (avec `receiver`)
+    #[serde(default)]
+    pub mutable: bool,
+    pub args: Vec<FieldSpec>,
+    pub ret: Option<String>,
+    /// Bloc `{ … }` du corps
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraitSpec {
+    pub name: String,
+    pub doc: String,
+    pub methods: Vec<MethodSpec>,
+}
+
+/// Bloc `impl` inhérent, ou d'un trait si `trait_name` est renseigné
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImplSpec {
+    pub target: String,
+    pub trait_name: Option<String>,
+    pub methods: Vec<MethodSpec>,
+}
+
+/// Spécification complète d'un module généré
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ModuleSpec {
+    pub name: String,
+    /// Lignes de la documentation du module (`//!`)
+    pub doc: Vec<String>,
+    pub structs: Vec<StructSpec>,
+    pub traits: Vec<TraitSpec>,
+    pub impls: Vec<ImplSpec>,
+    pub functions: Vec<MethodSpec>,
+    /// Génère un test par structure (construction par défaut)
+    pub tests: bool,
+}
+
+fn ident(name: &str) -> std::result::Result<Ident, String> {
+    syn::parse_str::<Ident>(name).map_err(|_| format!("Identifiant invalide: {}", name))
+}
+
+fn parse_type(ty: &str) -> std::result::Result<syn::Type, String> {
+    syn::parse_str::<syn::Type>(ty).map_err(|e| format!("Type invalide {}: {}", ty, e))
+}
+
+/// `NomDeType` depuis un nom de module en snake_case
+pub(crate) fn type_name(module: &str) -> String {
+    module.split('_').filter(|p| !p.is_empty()).map(|part| {
+        let mut chars = part.chars();
+        chars.next().map(|c| c.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+    }).collect()
+}
+
+impl MethodSpec {
+    fn signature(&self) -> std::result::Result<TokenStream, String> {
+        let name = ident(&self.name)?;
+        let receiver = match (self.receiver, self.mutable) {
+            (true, true) => quote!(&mut self,),
+            (true, false) => quote!(&self,),
+            _ => quote!(),
+        };
+        let args = self.args.iter()
+            .map(|arg| {
+                let (name, ty) = (ident(&arg.name)?, parse_type(&arg.ty)?);
+                Ok(quote!(#name: #ty))
+            })
+            .collect::<std::result::Result<Vec<_>, String>>()?;
+        let ret = match &self.ret {
+            Some(ret) => {
+                let ty = parse_type(ret)?;
+                quote!(-> #ty)
+            }
+            None => quote!(),
+        };
+        Ok(quote!(fn #name(#receiver #(#args),*) #ret))
+    }
+
+    fn body(&self) -> std::result::Result<TokenStream, String> {
+        match &self.body {
+            Some(body) => {
+                let block: syn::Block = syn::parse_str(body).map_err(|e| format!("Corps invalide pour {}: {}", self.name, e))?;
+                Ok(quote!(#block))
+            }
+            None if self.ret.is_some() => Ok(quote!({ Default::default() })),
+            None => Ok(quote!({})),
+        }
+    }
+
+    /// Méthode complète; `public` ajoute `pub` (interdit dans un `impl` de trait)
+    fn item(&self, public: bool) -> std::result::Result<TokenStream, String> {
+        let (doc, signature, body) = (&self.doc, self.signature()?, self.body()?);
+        let visibility = if public { quote!(pub) } else { quote!() };
+        let allow = if self.body.is_none() && !self.args.is_empty() { quote!(#[allow(unused_variables)]) } else { quote!() };
+        Ok(quote! {
+            #[doc = #doc]
+            #allow
+            #visibility #signature #body
+        })
+    }
+}
+
+impl ModuleSpec {
+    /// Module de base: un composant et son trait, plus `hello` (cible des mutations)
+    pub fn basic(name: &str) -> Self {
+        let component = format!("{}Component", type_name(name));
+        let behaviour = format!("{}Behaviour", type_name(name));
+        Self {
+            name: name.to_string(),
+            doc: vec![
+                "Module généré automatiquement par AURORAE++".to_string(),
+                format!("Nom : {}", name),
+                format!("UID : {}", Uuid::new_v4()),
+            ],
+            structs: vec![StructSpec {
+                name: component.clone(),
+                doc: format!("État du composant {}", name),
+                fields: vec![FieldSpec::new("label", "String"), FieldSpec::new("energy", "f32"), FieldSpec::new("cycles", "u64")],
+            }],
+            traits: vec![TraitSpec {
+                name: behaviour.clone(),
+                doc: "Comportement cyclique du composant".to_string(),
+                methods: vec![MethodSpec {
+                    name: "cycle".to_string(),
+                    doc: "Exécute un cycle et renvoie l'énergie restante".to_string(),
+                    receiver: true,
+                    ret: Some("f32".to_string()),
+                    ..MethodSpec::default()
+                }],
+            }],
+            impls: vec![ImplSpec {
+                target: component,
+                trait_name: Some(behaviour),
+                methods: vec![MethodSpec {
+                    name: "cycle".to_string(),
+                    doc: "Énergie décroissant avec le nombre de cycles".to_string(),
+                    receiver: true,
+                    ret: Some("f32".to_string()),
+                    body: Some("{ self.energy / (1.0 + self.cycles as f32) }".to_string()),
+                    ..MethodSpec::default()
+                }],
+            }],
+            functions: vec![MethodSpec {
+                name: "hello".to_string(),
+                doc: "Salutation du module".to_string(),
+                body: Some(format!("{{ println!(\"[{}] Hello from generated module!\"); }}", name.to_uppercase())),
+                ..MethodSpec::default()
+            }],
+            tests: true,
+        }
+    }
+
+    /// Assemble le module avec quote puis le met en forme: une erreur signale une spécification invalide
+    pub fn render(&self) -> std::result::Result<String, String> {
+        let doc = &self.doc;
+        let structs = self.structs.iter()
+            .map(|spec| {
+                let (name, doc) = (ident(&spec.name)?, &spec.doc);
+                let fields = spec.fields.iter()
+                    .map(|field| {
+                        let (name, ty) = (ident(&field.name)?, parse_type(&field.ty)?);
+                        Ok(quote!(pub #name: #ty))
+                    })
+                    .collect::<std::result::Result<Vec<_>, String>>()?;
+                Ok(quote! {
+                    #[doc = #doc]
+                    #[derive(Debug, Clone, Default)]
+                    pub struct #name { #(#fields),* }
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, String>>()?;
+        let traits = self.traits.iter()
+            .map(|spec| {
+                let (name, doc) = (ident(&spec.name)?, &spec.doc);
+                let methods = spec.methods.iter()
+                    .map(|m| {
+                        let (doc, signature) = (&m.doc, m.signature()?);
+                        Ok(quote!(#[doc = #doc] #signature;))
+                    })
+                    .collect::<std::result::Result<Vec<_>, String>>()?;
+                Ok(quote! {
+                    #[doc = #doc]
+                    pub trait #name { #(#methods)* }
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, String>>()?;
+        let impls = self.impls.iter()
+            .map(|spec| {
+                let target = parse_type(&spec.target)?;
+                let methods = spec.methods.iter()
+                    .map(|m| m.item(spec.trait_name.is_none()))
+                    .collect::<std::result::Result<Vec<_>, String>>()?;
+                match &spec.trait_name {
+                    Some(trait_name) => {
+                        let trait_name = parse_type(trait_name)?;
+                        Ok(quote!(impl #trait_name for #target { #(#methods)* }))
+                    }
+                    None => Ok(quote!(impl #target { #(#methods)* })),
+                }
+            })
+            .collect::<std::result::Result<Vec<_>, String>>()?;
+        let functions = self.functions.iter()
+            .map(|f| f.item(true))
+            .collect::<std::result::Result<Vec<_>, String>>()?;
+        let tests = if self.tests && !self.structs.is_empty() {
+            let cases = self.structs.iter()
+                .map(|spec| {
+                    let name = ident(&spec.name)?;
+                    let test = ident(&format!("{}_default_is_constructible", spec.name.to_lowercase()))?;
+                    Ok(quote! {
+                        #[test]
+                        fn #test() {
+                            let value = #name::default();
+                            assert!(!format!("{:?}", value).is_empty());
+                        }
+                    })
+                })
+                .collect::<std::result::Result<Vec<_>, String>>()?;
+            quote! {
+                #[cfg(test)]
+                mod tests {
+                    use super::*;
+                    #(#cases)*
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let tokens = quote! {
+            #(#![doc = #doc])*
+            #(#structs)*
+            #(#traits)*
+            #(#impls)*
+            #(#functions)*
+            #tests
+        };
+        let file: syn::File = syn::parse2(tokens).map_err(|e| format!("Module {} non analysable: {}", self.name, e))?;
+        Ok(prettyplease::unparse(&file))
+    }
+
+    pub fn module(&self) -> std::result::Result<GeneratedModule, String> {
+        Ok(GeneratedModule::new(&self.name, &self.render()?))
+    }
+
+    /// Écrit le module dans `aurorae/generated/` et l'inscrit dans lib.rs derrière la
+    /// feature `generated-<nom>` (déclarée dans Cargo.toml)
+    pub fn install(&self, crate_root: &Path) -> std::result::Result<PathBuf, String> {
+        install_source(crate_root, &self.name, &self.render()?)
+    }
+}
+
+/// Écrit une source de module déjà validée et l'inscrit dans lib.rs
+pub fn install_source(crate_root: &Path, name: &str, source: &str) -> std::result::Result<PathBuf, String> {
+    ident(name)?;
+    let dir = crate_root.join("aurorae").join(GENERATED_DIR);
+    create_dir_all(&dir).map_err(|e| format!("Erreur création {}: {}", dir.display(), e))?;
+    let path = dir.join(format!("{}.rs", name));
+    std::fs::write(&path, source).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))?;
+    log_security_event(SecurityEvent::FileModified { module: "generator".to_string(), path: path.display().to_string() });
+    register_in_lib(crate_root, name)?;
+    info!("🧩 Module {} installé (feature generated-{})", name, name);
+    Ok(path)
+}
+
+/// Déclare `name` dans lib.rs (section des modules générés) et sa feature dans Cargo.toml;
+/// renvoie `false` si le module était déjà inscrit
+pub fn register_in_lib(crate_root: &Path, name: &str) -> std::result::Result<bool, String> {
+    ident(name)?;
+    let feature = format!("generated-{}", name);
+    let lib_path = crate_root.join("aurorae").join("lib.rs");
+    let lib = std::fs::read_to_string(&lib_path).map_err(|e| format!("Erreur lecture {}: {}", lib_path.display(), e))?;
+    let declaration = format!("pub mod {};", name);
+    if lib.lines().any(|line| line.trim_start().starts_with(&declaration)) {
+        return Ok(false);
+    }
+    let entry = format!(
+        "#[cfg(feature = \"{}\")]\n#[path = \"{}/{}.rs\"]\n{}\n",
+        feature, GENERATED_DIR, name, declaration
+    );
+    let lib = match lib.find(GENERATED_SECTION) {
+        Some(at) => {
+            let insert = at + GENERATED_SECTION.len() + 1;
+            format!("{}{}{}", &lib[..insert.min(lib.len())], entry, &lib[insert.min(lib.len())..])
+        }
+        None => match lib.find("// Version publique de l'API") {
+            Some(at) => format!("{}{}\n{}\n{}", &lib[..at], GENERATED_SECTION, entry, &lib[at..]),
+            None => format!("{}\n{}\n{}", lib.trim_end(), GENERATED_SECTION, entry),
+        },
+    };
+    std::fs::write(&lib_path, lib).map_err(|e| format!("Erreur écriture {}: {}", lib_path.display(), e))?;
+
+    let manifest_path = crate_root.join("Cargo.toml");
+    let manifest = std::fs::read_to_string(&manifest_path).map_err(|e| format!("Erreur lecture {}: {}", manifest_path.display(), e))?;
+    if !manifest.lines().any(|line| line.trim_start().starts_with(&format!("{} =", feature))) {
+        let line = format!("{} = []\n", feature);
+        let manifest = match manifest.find("[features]\n") {
+            Some(at) => {
+                let insert = at + "[features]\n".len();
+                format!("{}{}{}", &manifest[..insert], line, &manifest[insert..])
+            }
+            None => format!("{}\n\n[features]\n{}", manifest.trim_end(), line),
+        };
+        std::fs::write(&manifest_path, manifest).map_err(|e| format!("Erreur écriture {}: {}", manifest_path.display(), e))?;
+    }
+    Ok(true)
+}
+
+// ==================== PORTE DE COMPILATION ====================
+
+/// Diagnostic rustc extrait de `cargo check --message-format=json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateDiagnostic {
+    /// `error`, `warning`…
+    pub level: String,
+    pub message: String,
+    /// Code rustc (`E0308`, `unused_variables`…)
+    pub code: Option<String>,
+    /// Ligne du span principal dans le module généré
+    pub line: Option<usize>,
+}
+
+/// Résultat de la porte: compilation, tests générés et diagnostics structurés
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GateReport {
+    pub module: String,
+    pub compiled: bool,
+    pub tests_passed: usize,
+    pub tests_failed: Vec<String>,
+    pub diagnostics: Vec<GateDiagnostic>,
+    /// Module inscrit dans lib.rs (porte au vert)
+    pub merged: bool,
+}
+
+impl GateReport {
+    pub fn passed(&self) -> bool {
+        self.compiled && self.tests_failed.is_empty()
+    }
+
+    pub fn errors(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.level == "error").count()
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.level == "warning").count()
+    }
+
+    /// Récompense RL: 1.0 au vert (moins 0.05 par avertissement, plancher 0.5),
+    /// part de tests réussis sinon, pénalité proportionnelle aux erreurs si la compilation échoue
+    pub fn reward(&self) -> f32 {
+        if !self.compiled {
+            return -(0.2 * self.errors().max(1) as f32).min(1.0);
+        }
+        if !self.tests_failed.is_empty() {
+            let total = self.tests_passed + self.tests_failed.len();
+            return 0.3 * self.tests_passed as f32 / total as f32;
+        }
+        (1.0 - 0.05 * self.warnings() as f32).max(0.5)
+    }
+}
+
+/// Où la porte exécute cargo: sur l'hôte pour les gabarits (code écrit par le générateur),
+/// dans un conteneur jetable pour du code dont l'auteur n'est pas de confiance (modèle de langage)
+#[derive(Debug, Clone, PartialEq)]
+pub enum GateIsolation {
+    Host,
+    /// Sans réseau, mémoire, CPU et processus bornés; seul l'espace de travail est monté
+    Container { runtime: String, image: String },
+}
+
+impl GateIsolation {
+    /// Conteneur décrit par `AURORAE_GATE_RUNTIME` et `AURORAE_GATE_IMAGE`, si le moteur répond
+    pub fn container_from_env() -> std::result::Result<Self, String> {
+        let runtime = std::env::var(GATE_RUNTIME_ENV).unwrap_or_else(|_| "docker".to_string());
+        let image = std::env::var(GATE_IMAGE_ENV).unwrap_or_else(|_| "rust:1-slim".to_string());
+        let available = Command::new(&runtime).arg("version").output().map(|o| o.status.success()).unwrap_or(false);
+        if !available {
+            return Err(format!("Moteur de conteneurs {} indisponible ({})", runtime, GATE_RUNTIME_ENV));
+        }
+        Ok(Self::Container { runtime, image })
+    }
+}
+
+/// Compile chaque module généré dans un espace cargo isolé et exécute ses tests
+/// avant de l'inscrire dans le crate
+pub struct CompileGate {
+    /// `CARGO_TARGET_DIR` partagé entre les passages pour limiter les recompilations (hôte seulement)
+    pub target_dir: PathBuf,
+    pub isolation: GateIsolation,
+}
+
+impl CompileGate {
+    pub fn new(crate_root: &Path) -> Self {
+        Self { target_dir: crate_root.join("target").join("generation-gate"), isolation: GateIsolation::Host }
+    }
+
+    /// Porte exécutée dans un conteneur: le code testé n'a accès ni à l'hôte ni au réseau
+    pub fn isolated(crate_root: &Path, isolation: GateIsolation) -> Self {
+        Self { isolation, ..Self::new(crate_root) }
+    }
+
+    /// `cargo check` puis `cargo test` du module seul, sans l'inscrire
+    pub fn check(&self, spec: &ModuleSpec) -> std::result::Result<GateReport, String> {
+        self.check_source(&spec.name, &spec.render()?)
+    }
+
+    /// Même porte pour une source produite hors spécification (backend LLM…)
+    pub fn check_source(&self, name: &str, source: &str) -> std::result::Result<GateReport, String> {
+        let scratch = std::env::temp_dir().join(format!("aurorae-gate-{}", Uuid::new_v4()));
+        let result = self.check_in(&scratch, name, source);
+        std::fs::remove_dir_all(&scratch).ok();
+        let report = result?;
+
+        info!("🚦 Porte de compilation {}: {} ({} erreur(s), {} avertissement(s), {} test(s) réussi(s), {} échec(s))",
+                 report.module, if report.passed() { "au vert" } else { "au rouge" },
+                 report.errors(), report.warnings(), report.tests_passed, report.tests_failed.len());
+        Ok(report)
+    }
+
+    fn check_in(&self, scratch: &Path, name: &str, source: &str) -> std::result::Result<GateReport, String> {
+        create_dir_all(scratch.join("src")).map_err(|e| format!("Erreur création {}: {}", scratch.display(), e))?;
+        // `[workspace]` vide: l'espace isolé n'hérite pas d'un workspace parent
+        let manifest = format!(
+            "[package]\nname = \"gate_{}\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n[lib]\npath = \"src/lib.rs\"\n\n[workspace]\n",
+            name
+        );
+        std::fs::write(scratch.join("Cargo.toml"), manifest).map_err(|e| format!("Erreur écriture du manifeste: {}", e))?;
+        std::fs::write(scratch.join("src").join("lib.rs"), source).map_err(|e| format!("Erreur écriture du module: {}", e))?;
+
+        let check = self.cargo(scratch, &["check", "--tests", "--message-format=json"])?;
+        let mut report = GateReport {
+            module: name.to_string(),
+            compiled: check.status.success(),
+            diagnostics: parse_diagnostics(&String::from_utf8_lossy(&check.stdout)),
+            ..GateReport::default()
+        };
+        if !report.compiled {
+            return Ok(report);
+        }
+
+        let test = self.cargo(scratch, &["test", "--lib"])?;
+        let (passed, failed) = parse_test_results(&String::from_utf8_lossy(&test.stdout));
+        report.tests_passed = passed;
+        report.tests_failed = failed;
+        if !test.status.success() && report.tests_failed.is_empty() {
+            report.tests_failed.push("exécution".to_string());
+        }
+        Ok(report)
+    }
+
+    fn cargo(&self, scratch: &Path, args: &[&str]) -> std::result::Result<std::process::Output, String> {
+        self.command(scratch, args)
+            .output()
+            .map_err(|e| format!("Exécution de cargo impossible: {}", e))
+    }
 
-    // Appliquer les suggestions de Clippy pour améliorer le code généré
-    fn apply_clippy_suggestions(&self) {
-        // Appliquer les corrections basées sur les avertissements de Clippy
-        println!("[AURORAE++] 💡 Application des suggestions Clippy pour le module {}", self.name);
-        // Cela pourrait inclure des changements comme la simplification de certains blocs ou l'ajout de gestion d'erreurs
+    fn command(&self, scratch: &Path, args: &[&str]) -> Command {
+        match &self.isolation {
+            GateIsolation::Host => {
+                let mut command = Command::new("cargo");
+                command.args(args)
+                    .arg("--manifest-path")
+                    .arg(scratch.join("Cargo.toml"))
+                    .env("CARGO_TARGET_DIR", &self.target_dir);
+                command
+            }
+            GateIsolation::Container { runtime, image } => {
+                let mut command = Command::new(runtime);
+                command.args(["run", "--rm", "--network", "none", "--memory", "2g", "--cpus", "2", "--pids-limit", "256"])
+                    .args(["--security-opt", "no-new-privileges"])
+                    .args(["-e", "CARGO_TARGET_DIR=/work/target", "-v"])
+                    .arg(format!("{}:/work", scratch.display()))
+                    .args(["-w", "/work"])
+                    .arg(image)
+                    .arg("cargo")
+                    .args(args)
+                    .args(["--manifest-path", "/work/Cargo.toml", "--offline"]);
+                command
+            }
+        }
+    }
+
+    /// Passe la porte puis inscrit le module dans le crate uniquement s'il est au vert
+    pub fn admit(&self, spec: &ModuleSpec, crate_root: &Path) -> std::result::Result<GateReport, String> {
+        let mut report = self.check(spec)?;
+        if report.passed() {
+            spec.install(crate_root)?;
+            report.merged = true;
+        } else {
+            error!("⛔ Module {} écarté par la porte de compilation", spec.name);
+        }
+        Ok(report)
+    }
+}
+
+/// Messages `compiler-message` de la sortie JSON de cargo (hors résumé final)
+pub fn parse_diagnostics(stdout: &str) -> Vec<GateDiagnostic> {
+    stdout.lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|msg| msg["reason"] == "compiler-message")
+        .filter_map(|msg| {
+            let message = &msg["message"];
+            let text = message["message"].as_str()?.to_string();
+            // « aborting due to… » et « N warnings emitted » n'ont pas de span
+            let spans = message["spans"].as_array()?;
+            let primary = spans.iter().find(|s| s["is_primary"] == true)?;
+            Some(GateDiagnostic {
+                level: message["level"].as_str().unwrap_or("error").to_string(),
+                message: text,
+                code: message["code"]["code"].as_str().map(str::to_string),
+                line: primary["line_start"].as_u64().map(|l| l as usize),
+            })
+        })
+        .collect()
+}
+
+/// (tests réussis, tests en échec) depuis la sortie de `cargo test`
+pub fn parse_test_results(stdout: &str) -> (usize, Vec<String>) {
+    let line = Regex::new(r"(?m)^test (\S+) \.\.\. (ok|FAILED)").unwrap();
+    let mut passed = 0;
+    let mut failed = Vec::new();
+    for c in line.captures_iter(stdout) {
+        if &c[2] == "ok" {
+            passed += 1;
+        } else {
+            failed.push(c[1].rsplit("::").next().unwrap_or(&c[1]).to_string());
+        }
     }
+    (passed, failed)
 }
 
 /// Génère un module Rust de base intelligent
 pub fn generate_basic_module(name: &str) -> GeneratedModule {
-    let content = format!(
-        "// Module généré automatiquement par AURORAE++\n\n\
-/// Nom : {}\n/// UID : {}\n\npub fn hello() {{\n    println!(\"[{}] Hello from generated module!\");\n}}",
-        name,
-        Uuid::new_v4(),
-        name.to_uppercase()
-    );
+    match ModuleSpec::basic(name).module() {
+        Ok(module) => module,
+        Err(e) => {
+            // Nom non valide comme identifiant Rust: module minimal documenté
+            warn!("⚠️ Spécification de {} invalide: {}", name, e);
+            GeneratedModule::new(name, &format!("//! Module {} (spécification invalide: {})\n", name, e))
+        }
+    }
+}
 
-    GeneratedModule::new(name, &content)
+/// Lance une génération complète à partir d'un gabarit (`basic`, `consensus_adapter`,
+/// `token_module`, `oracle_client`, `metric_collector`…) et de ses arguments typés
+pub fn trigger_generation(base_path: &str, name: &str, template: &str, args: &TemplateArgs) {
+    let module = TEMPLATES.read().instantiate(template, name, args).and_then(|spec| spec.module());
+    let saved = module.and_then(|module| module.save_to_disk(base_path).map_err(|e| e.to_string()));
+    match saved {
+        Ok(()) => info!("🧩 Module {} généré depuis le gabarit {}", name, template),
+        Err(e) => error!("Échec de la génération du module {}: {}", name, e),
+    }
 }
 
-/// Lance une génération complète
-pub fn trigger_generation(base_path: &str, name: &str) {
-    let module = generate_basic_module(name);
-    if let Err(e) = module.save_to_disk(base_path) {
-        eprintln!("[AURORAE++] Échec de la sauvegarde du module {}: {}", name, e);
+/// Tâche de génération planifiée (rêve promu en feuille de route, par exemple)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationTask {
+    pub id: Uuid,
+    pub module_name: String,
+    pub purpose: String,
+    /// 0 à 10: la tâche la plus prioritaire est générée en premier
+    pub priority: u8,
+    pub complexity: u8,
+    /// Indications reprises en tête du module généré (tags émotionnels du rêve…)
+    pub hints: Vec<String>,
+    /// Rêve ou projection à l'origine de la tâche
+    pub origin: Option<Uuid>,
+    pub created_at: String,
+    pub done: bool,
+    /// Gabarit et arguments; module de base si absent
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub args: TemplateArgs,
+}
+
+impl GenerationTask {
+    pub fn new(module_name: &str, purpose: &str, priority: u8, complexity: u8, hints: Vec<String>, origin: Option<Uuid>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            module_name: module_name.to_string(),
+            purpose: purpose.to_string(),
+            priority: priority.min(10),
+            complexity,
+            hints,
+            origin,
+            created_at: Utc::now().to_rfc3339(),
+            done: false,
+            template: None,
+            args: TemplateArgs::new(),
+        }
+    }
+
+    pub fn with_template(mut self, template: &str, args: TemplateArgs) -> Self {
+        self.template = Some(template.to_string());
+        self.args = args;
+        self
+    }
+
+    /// Spécification de base documentée par le but et les indications de la tâche
+    pub fn spec(&self) -> std::result::Result<ModuleSpec, String> {
+        let mut spec = match &self.template {
+            Some(template) => TEMPLATES.read().instantiate(template, &self.module_name, &self.args)?,
+            None => ModuleSpec::basic(&self.module_name),
+        };
+        spec.doc.push(format!("But : {}", self.purpose));
+        spec.doc.push(format!("Priorité : {} • Complexité : {}", self.priority, self.complexity));
+        spec.doc.push(format!("Indications : {}", self.hints.join(", ")));
+        Ok(spec)
+    }
+
+    pub fn module(&self) -> std::result::Result<GeneratedModule, String> {
+        self.spec()?.module()
+    }
+}
+
+/// File des tâches; sans chemin (tests, file éphémère) elle n'est pas persistée
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GenerationQueue {
+    pub tasks: Vec<GenerationTask>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl GenerationQueue {
+    pub fn enqueue(&mut self, task: GenerationTask) -> Uuid {
+        let id = task.id;
+        info!("🗂️ Tâche de génération planifiée: {} (priorité {})", task.module_name, task.priority);
+        self.tasks.push(task);
+        self.save();
+        id
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &GenerationTask> {
+        self.tasks.iter().filter(|t| !t.done)
+    }
+
+    /// Vrai si la tâche a été générée; une tâche inconnue n'est pas terminée
+    pub fn is_done(&self, id: &Uuid) -> bool {
+        self.tasks.iter().any(|t| &t.id == id && t.done)
+    }
+
+    /// Tâche en attente la plus prioritaire, la plus ancienne à priorité égale
+    pub fn next_task(&self) -> Option<&GenerationTask> {
+        self.pending().fold(None, |best: Option<&GenerationTask>, task| match best {
+            Some(best) if best.priority >= task.priority => Some(best),
+            _ => Some(task),
+        })
+    }
+
+    /// Génère le module de la tâche la plus prioritaire
+    pub fn run_next(&mut self, base_path: &str) -> Option<GenerationTask> {
+        let id = self.next_task()?.id;
+        let task = self.tasks.iter_mut().find(|t| t.id == id)?;
+        let saved = task.module().and_then(|module| module.save_to_disk(base_path).map_err(|e| e.to_string()));
+        if let Err(e) = saved {
+            error!("Échec de la génération de {}: {}", task.module_name, e);
+            return None;
+        }
+        task.done = true;
+        let task = task.clone();
+        self.save();
+        Some(task)
+    }
+
+    /// 💾 Sauvegarde sur disque
+    pub fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(dir) = path.parent() {
+            if create_dir_all(dir).is_err() {
+                return;
+            }
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self);
+        }
+    }
+
+    /// 📥 Chargement depuis `path` (file vide sinon), persistée à ce même chemin
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let queue: Self = File::open(&path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self { path: Some(path), ..queue }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rendered_module_parses_and_keeps_hello() {
+        let source = ModuleSpec::basic("energy_core").render().unwrap();
+        let file: syn::File = syn::parse_str(&source).unwrap();
+        assert!(file.items.len() >= 5);
+        assert!(source.contains("pub struct EnergyCoreComponent"));
+        assert!(source.contains("impl EnergyCoreBehaviour for EnergyCoreComponent"));
+        assert!(source.contains("pub fn hello()"));
+        assert!(source.contains("#[cfg(test)]"));
+
+        let mut invalid = ModuleSpec::basic("energy_core");
+        invalid.structs[0].fields.push(FieldSpec::new("type", "u8"));
+        assert!(invalid.render().is_err());
+        invalid.structs[0].fields.pop();
+        invalid.functions[0].body = Some("{ let = ; }".to_string());
+        assert!(invalid.render().is_err());
+    }
+
+    #[test]
+    fn install_registers_module_behind_feature() {
+        let root = std::env::temp_dir().join(format!("aurorae-generator-{}", Uuid::new_v4()));
+        create_dir_all(root.join("aurorae")).unwrap();
+        std::fs::write(root.join("aurorae/lib.rs"), "pub mod brain;\n\n// Version publique de l'API\npub use crate::brain::BrainCore;\n").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"aurorae\"\n\n[features]\ndefault = []\n").unwrap();
+
+        let path = ModuleSpec::basic("energy_core").install(&root).unwrap();
+        assert!(path.ends_with("generated/energy_core.rs"));
+        assert!(!register_in_lib(&root, "energy_core").unwrap());
+        ModuleSpec::basic("consensus_adapter").install(&root).unwrap();
+
+        let lib = std::fs::read_to_string(root.join("aurorae/lib.rs")).unwrap();
+        assert_eq!(lib.matches(GENERATED_SECTION).count(), 1);
+        assert!(lib.contains("#[cfg(feature = \"generated-energy_core\")]\n#[path = \"generated/energy_core.rs\"]\npub mod energy_core;"));
+        assert!(lib.find("pub mod consensus_adapter;").unwrap() < lib.find("// Version publique de l'API").unwrap());
+        let manifest = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("generated-energy_core = []"));
+        assert!(manifest.contains("generated-consensus_adapter = []"));
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn gate_report_extracts_diagnostics_and_scores_reward() {
+        let stdout = concat!(
+            r#"{"reason":"compiler-artifact","package_id":"gate_x"}"#, "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"mismatched types","code":{"code":"E0308"},"spans":[{"is_primary":false,"line_start":3},{"is_primary":true,"line_start":12}]}}"#, "\n",
+            r#"{"reason":"compiler-message","message":{"level":"warning","message":"unused variable: `x`","code":{"code":"unused_variables"},"spans":[{"is_primary":true,"line_start":7}]}}"#, "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 1 previous error","code":null,"spans":[]}}"#, "\n",
+            r#"{"reason":"build-finished","success":false}"#, "\n",
+        );
+        let diagnostics = parse_diagnostics(stdout);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("E0308"));
+        assert_eq!(diagnostics[0].line, Some(12));
+
+        let failed = GateReport { module: "x".to_string(), diagnostics, ..GateReport::default() };
+        assert!(!failed.passed());
+        assert!((failed.reward() + 0.2).abs() < 1e-6);
+
+        let (passed, failures) = parse_test_results("running 2 tests\ntest tests::a ... ok\ntest tests::b ... FAILED\n");
+        assert_eq!((passed, failures.clone()), (1, vec!["b".to_string()]));
+        let flaky = GateReport { compiled: true, tests_passed: passed, tests_failed: failures, ..GateReport::default() };
+        assert!((flaky.reward() - 0.15).abs() < 1e-6);
+        let green = GateReport { compiled: true, tests_passed: 2, ..GateReport::default() };
+        assert!(green.passed() && green.reward() == 1.0);
+    }
+
+    #[test]
+    fn isolated_gate_runs_cargo_in_a_container_without_network() {
+        let scratch = Path::new("/tmp/aurorae-gate-x");
+        let host = CompileGate::new(Path::new("."));
+        assert_eq!(host.command(scratch, &["test", "--lib"]).get_program(), "cargo");
+
+        let gate = CompileGate::isolated(Path::new("."), GateIsolation::Container { runtime: "podman".into(), image: "rust:1-slim".into() });
+        let command = gate.command(scratch, &["test", "--lib"]);
+        assert_eq!(command.get_program(), "podman");
+        let args: Vec<String> = command.get_args().map(|a| a.to_string_lossy().into_owned()).collect();
+        let at = |flag: &str| args.iter().position(|a| a == flag).unwrap();
+        assert_eq!(args[at("--network") + 1], "none");
+        assert_eq!(args[at("-v") + 1], "/tmp/aurorae-gate-x:/work");
+        assert!(at("rust:1-slim") < at("cargo") && at("cargo") < at("test"));
+        assert!(args.ends_with(&["--manifest-path".to_string(), "/work/Cargo.toml".to_string(), "--offline".to_string()]));
     }
 }
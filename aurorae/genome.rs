@@ -2,19 +2,174 @@
 //!
 //! Ce module encode l'identité génétique de chaque entité créée : chaînes, tokens, modules, IA-filles...
 //! Il attribue une empreinte ADN logicielle unique, traçable, évolutive, et compatible avec la mutation vivante.
+//!
+//! Le génome est sérialisable (JSON versionné) et se compose de trois familles de gènes:
+//! modules activés, paramètres numériques bornés et stratégies à choix discrets. Chaque gène
+//! porte son propre taux de mutation. Croisements et mutations sont semés: l'origine d'un
+//! génome (opérateur, graine, parents) suffit à le reproduire, et `GenomeBuilder::diff`
+//! montre gène par gène ce qu'une génération a changé.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use tracing::info;
 
-use uuid::Uuid;
 use chrono::Utc;
-use std::collections::HashMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Version du schéma sérialisé
+pub const GENOME_SCHEMA_VERSION: u32 = 1;
+
+/// Module du système, activé ou non, avec sa priorité d'exécution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleGene {
+    pub module: String,
+    pub enabled: bool,
+    pub priority: u8,
+    pub mutation_rate: f32,
+}
+
+/// Hyperparamètre numérique borné
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterGene {
+    pub name: String,
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    pub mutation_rate: f32,
+}
+
+/// Stratégie choisie parmi des alternatives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategyGene {
+    pub name: String,
+    pub choice: String,
+    pub alternatives: Vec<String>,
+    pub mutation_rate: f32,
+}
+
+/// Comportement commun aux trois familles de gènes
+pub trait Gene: Clone {
+    fn key(&self) -> &str;
+    fn mutation_rate(&self) -> f32;
+    /// Valeur lisible pour les diffs
+    fn describe(&self) -> String;
+    fn mutate(&mut self, rng: &mut StdRng);
+}
+
+impl Gene for ModuleGene {
+    fn key(&self) -> &str {
+        &self.module
+    }
 
-#[derive(Debug, Clone)]
+    fn mutation_rate(&self) -> f32 {
+        self.mutation_rate
+    }
+
+    fn describe(&self) -> String {
+        format!("{} (priorité {})", if self.enabled { "actif" } else { "inactif" }, self.priority)
+    }
+
+    fn mutate(&mut self, rng: &mut StdRng) {
+        if rng.gen_bool(0.5) {
+            self.enabled = !self.enabled;
+        } else {
+            self.priority = (self.priority as i16 + rng.gen_range(-2..=2)).clamp(0, 10) as u8;
+        }
+    }
+}
+
+impl Gene for ParameterGene {
+    fn key(&self) -> &str {
+        &self.name
+    }
+
+    fn mutation_rate(&self) -> f32 {
+        self.mutation_rate
+    }
+
+    fn describe(&self) -> String {
+        format!("{:.4}", self.value)
+    }
+
+    /// Perturbation d'au plus 10 % de l'intervalle, bornée
+    fn mutate(&mut self, rng: &mut StdRng) {
+        let span = (self.max - self.min).abs();
+        self.value = (self.value + rng.gen_range(-0.1..=0.1) * span).clamp(self.min, self.max);
+    }
+}
+
+impl Gene for StrategyGene {
+    fn key(&self) -> &str {
+        &self.name
+    }
+
+    fn mutation_rate(&self) -> f32 {
+        self.mutation_rate
+    }
+
+    fn describe(&self) -> String {
+        self.choice.clone()
+    }
+
+    fn mutate(&mut self, rng: &mut StdRng) {
+        let others: Vec<&String> = self.alternatives.iter().filter(|a| **a != self.choice).collect();
+        if !others.is_empty() {
+            self.choice = others[rng.gen_range(0..others.len())].clone();
+        }
+    }
+}
+
+/// Opérateur de croisement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossoverOperator {
+    /// Gènes du premier parent jusqu'au point de coupe, du second ensuite
+    SinglePoint,
+    /// Chaque gène tiré au hasard chez l'un des parents
+    Uniform,
+}
+
+/// Comment un génome a été obtenu, de quoi le reproduire à l'identique
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GeneticOrigin {
+    Base,
+    Crossover { operator: CrossoverOperator, seed: u64 },
+    Mutation { seed: u64, mutated: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Genome {
     pub id: Uuid,
+    #[serde(default = "schema_version")]
+    pub schema_version: u32,
     pub birth: String,
     pub ancestry: Vec<Uuid>,
     pub traits: HashMap<String, String>,
     pub mutability: u8,
     pub integrity_score: u8,
+    #[serde(default)]
+    pub generation: u32,
+    #[serde(default)]
+    pub modules: Vec<ModuleGene>,
+    #[serde(default)]
+    pub parameters: Vec<ParameterGene>,
+    #[serde(default)]
+    pub strategies: Vec<StrategyGene>,
+    #[serde(default = "base_origin")]
+    pub origin: GeneticOrigin,
+}
+
+fn schema_version() -> u32 {
+    GENOME_SCHEMA_VERSION
+}
+
+fn base_origin() -> GeneticOrigin {
+    GeneticOrigin::Base
 }
 
 impl Genome {
@@ -22,17 +177,137 @@ impl Genome {
         let integrity = 100 - (mutability.min(90)); // plus elle est mutable, moins son intégrité initiale est haute
         Self {
             id: Uuid::new_v4(),
+            schema_version: GENOME_SCHEMA_VERSION,
             birth: Utc::now().to_rfc3339(),
             ancestry,
             traits,
             mutability,
             integrity_score: integrity,
+            generation: 0,
+            modules: Vec::new(),
+            parameters: Vec::new(),
+            strategies: Vec::new(),
+            origin: GeneticOrigin::Base,
+        }
+    }
+
+    /// Taux de mutation par défaut des gènes, dérivé de la mutabilité
+    pub fn default_gene_rate(&self) -> f32 {
+        self.mutability as f32 / 100.0
+    }
+
+    pub fn with_module(mut self, module: &str, enabled: bool, priority: u8) -> Self {
+        let mutation_rate = self.default_gene_rate();
+        self.modules.push(ModuleGene { module: module.to_string(), enabled, priority, mutation_rate });
+        self
+    }
+
+    pub fn with_parameter(mut self, name: &str, value: f64, min: f64, max: f64) -> Self {
+        let mutation_rate = self.default_gene_rate();
+        self.parameters.push(ParameterGene { name: name.to_string(), value: value.clamp(min, max), min, max, mutation_rate });
+        self
+    }
+
+    pub fn with_strategy(mut self, name: &str, choice: &str, alternatives: &[&str]) -> Self {
+        let mutation_rate = self.default_gene_rate();
+        self.strategies.push(StrategyGene {
+            name: name.to_string(),
+            choice: choice.to_string(),
+            alternatives: alternatives.iter().map(|a| a.to_string()).collect(),
+            mutation_rate,
+        });
+        self
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<f64> {
+        self.parameters.iter().find(|p| p.name == name).map(|p| p.value)
+    }
+
+    pub fn strategy(&self, name: &str) -> Option<&str> {
+        self.strategies.iter().find(|s| s.name == name).map(|s| s.choice.as_str())
+    }
+
+    pub fn enabled_modules(&self) -> Vec<&str> {
+        self.modules.iter().filter(|m| m.enabled).map(|m| m.module.as_str()).collect()
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Sérialisation du génome impossible: {}", e))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let genome: Self = serde_json::from_str(json).map_err(|e| format!("Génome invalide: {}", e))?;
+        if genome.schema_version > GENOME_SCHEMA_VERSION {
+            return Err(format!("Schéma de génome v{} plus récent que v{}", genome.schema_version, GENOME_SCHEMA_VERSION));
         }
+        Ok(genome)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Erreur création {}: {}", dir.display(), e))?;
+        }
+        fs::write(path, self.to_json()?).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+        Self::from_json(&json)
+    }
+
+    /// Enfant de deux parents; les gènes sont appariés par nom, un gène présent chez un
+    /// seul parent est hérité tel quel
+    pub fn crossover(a: &Genome, b: &Genome, operator: CrossoverOperator, seed: u64) -> Genome {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let total = union_len(&a.modules, &b.modules) + union_len(&a.parameters, &b.parameters) + union_len(&a.strategies, &b.strategies);
+        let cut = if total > 1 { rng.gen_range(1..total) } else { total };
+        let mut index = 0;
+        let mut from_first = |rng: &mut StdRng| {
+            let pick = match operator {
+                CrossoverOperator::SinglePoint => index < cut,
+                CrossoverOperator::Uniform => rng.gen_bool(0.5),
+            };
+            index += 1;
+            pick
+        };
+
+        let modules = cross_genes(&a.modules, &b.modules, &mut rng, &mut from_first);
+        let parameters = cross_genes(&a.parameters, &b.parameters, &mut rng, &mut from_first);
+        let strategies = cross_genes(&a.strategies, &b.strategies, &mut rng, &mut from_first);
+
+        let mut traits = b.traits.clone();
+        traits.extend(a.traits.clone());
+        let mut child = Genome::new(traits, vec![a.id, b.id], ((a.mutability as u16 + b.mutability as u16) / 2) as u8);
+        child.generation = a.generation.max(b.generation) + 1;
+        child.modules = modules;
+        child.parameters = parameters;
+        child.strategies = strategies;
+        child.origin = GeneticOrigin::Crossover { operator, seed };
+        child
+    }
+
+    /// Copie mutée: chaque gène mute selon son propre taux; renvoie aussi les gènes touchés
+    pub fn mutated(&self, seed: u64) -> Genome {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut child = self.clone();
+        let mut mutated = Vec::new();
+        mutate_genes(&mut child.modules, &mut rng, &mut mutated);
+        mutate_genes(&mut child.parameters, &mut rng, &mut mutated);
+        mutate_genes(&mut child.strategies, &mut rng, &mut mutated);
+
+        child.id = Uuid::new_v4();
+        child.birth = Utc::now().to_rfc3339();
+        child.ancestry = vec![self.id];
+        child.generation = self.generation + 1;
+        child.integrity_score = self.integrity_score.saturating_sub(mutated.len().min(255) as u8).max(10);
+        child.origin = GeneticOrigin::Mutation { seed, mutated };
+        child
     }
 
     pub fn display(&self) {
-        println!("[AURORAE++] 🧬 ADN : {}", self.id);
+        info!("🧬 ADN : {}", self.id);
         println!("- Naissance : {}", self.birth);
+        println!("- Génération : {}", self.generation);
         println!("- Ancêtres  : {:?}", self.ancestry);
         println!("- Mutabilité : {}%", self.mutability);
         println!("- Intégrité  : {}%", self.integrity_score);
@@ -40,5 +315,250 @@ impl Genome {
         for (k, v) in &self.traits {
             println!("  • {} = {}", k, v);
         }
+        if !self.modules.is_empty() || !self.parameters.is_empty() || !self.strategies.is_empty() {
+            println!("- Gènes : {} module(s), {} paramètre(s), {} stratégie(s)", self.modules.len(), self.parameters.len(), self.strategies.len());
+        }
+    }
+}
+
+fn union_len<G: Gene>(a: &[G], b: &[G]) -> usize {
+    a.len() + b.iter().filter(|g| !a.iter().any(|other| other.key() == g.key())).count()
+}
+
+/// Gènes de l'enfant dans l'ordre du premier parent, puis ceux propres au second
+fn cross_genes<G: Gene>(a: &[G], b: &[G], rng: &mut StdRng, from_first: &mut impl FnMut(&mut StdRng) -> bool) -> Vec<G> {
+    let mut child = Vec::new();
+    for gene in a {
+        let first = from_first(rng);
+        let other = b.iter().find(|g| g.key() == gene.key());
+        child.push(match other {
+            Some(other) if !first => other.clone(),
+            _ => gene.clone(),
+        });
+    }
+    for gene in b.iter().filter(|g| !a.iter().any(|other| other.key() == g.key())) {
+        from_first(rng);
+        child.push(gene.clone());
+    }
+    child
+}
+
+fn mutate_genes<G: Gene>(genes: &mut [G], rng: &mut StdRng, mutated: &mut Vec<String>) {
+    for gene in genes.iter_mut() {
+        if rng.gen::<f32>() < gene.mutation_rate() {
+            gene.mutate(rng);
+            mutated.push(gene.key().to_string());
+        }
+    }
+}
+
+/// Changement d'un gène entre deux génomes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneChange {
+    /// module, paramètre, stratégie ou trait
+    pub family: String,
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Différences gène par gène entre deux génomes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenomeDiff {
+    pub from: Option<Uuid>,
+    pub to: Option<Uuid>,
+    pub changes: Vec<GeneChange>,
+}
+
+impl GenomeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for GenomeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            match (&change.before, &change.after) {
+                (None, Some(after)) => writeln!(f, "+ {} {}: {}", change.family, change.name, after)?,
+                (Some(before), None) => writeln!(f, "- {} {}: {}", change.family, change.name, before)?,
+                (Some(before), Some(after)) => writeln!(f, "~ {} {}: {} → {}", change.family, change.name, before, after)?,
+                (None, None) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn diff_family(family: &str, before: BTreeMap<String, String>, after: BTreeMap<String, String>, changes: &mut Vec<GeneChange>) {
+    for (name, old) in &before {
+        match after.get(name) {
+            Some(new) if new == old => {}
+            new => changes.push(GeneChange { family: family.to_string(), name: name.clone(), before: Some(old.clone()), after: new.cloned() }),
+        }
+    }
+    for (name, new) in after.iter().filter(|(name, _)| !before.contains_key(*name)) {
+        changes.push(GeneChange { family: family.to_string(), name: name.clone(), before: None, after: Some(new.clone()) });
+    }
+}
+
+fn described<G: Gene>(genes: &[G]) -> BTreeMap<String, String> {
+    genes.iter().map(|g| (g.key().to_string(), g.describe())).collect()
+}
+
+/// Construit le génome de base du système et en dérive les générations suivantes
+pub struct GenomeBuilder {
+    base: Genome,
+    rng: StdRng,
+}
+
+impl Default for GenomeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GenomeBuilder {
+    pub fn new() -> Self {
+        Self::with_seed(rand::thread_rng().gen())
+    }
+
+    /// Builder déterministe: mêmes graines, mêmes générations
+    pub fn with_seed(seed: u64) -> Self {
+        Self { base: Self::base_genome(), rng: StdRng::seed_from_u64(seed) }
+    }
+
+    /// Génome fondateur: modules principaux et hyperparamètres d'évolution et d'apprentissage
+    pub fn base_genome() -> Genome {
+        let mut genome = Genome::new(HashMap::new(), Vec::new(), 5);
+        for (module, priority) in [("brain", 9), ("intelligence", 8), ("autonomy", 7), ("economy", 7), ("evolution", 6), ("dream", 5), ("reproduction", 4)] {
+            genome = genome.with_module(module, true, priority);
+        }
+        genome
+            .with_parameter("mutation_rate", 0.03, 0.001, 0.5)
+            .with_parameter("crossover_rate", 0.7, 0.0, 1.0)
+            .with_parameter("exploration_rate", 0.1, 0.0, 1.0)
+            .with_parameter("learning_rate", 0.01, 0.0001, 0.5)
+            .with_parameter("founder_share", 0.15, 0.05, 0.3)
+            .with_strategy("selection", "tournament", &["tournament", "roulette", "elitist"])
+            .with_strategy("crossover", "single_point", &["single_point", "uniform"])
+            .with_strategy("deployment", "testnet_first", &["testnet_first", "canary", "direct"])
+    }
+
+    pub fn base(&self) -> &Genome {
+        &self.base
+    }
+
+    /// Croisement semé par le builder (la graine est enregistrée dans l'origine de l'enfant)
+    pub fn crossover(&mut self, a: &Genome, b: &Genome, operator: CrossoverOperator) -> Genome {
+        let seed = self.rng.gen();
+        Genome::crossover(a, b, operator, seed)
+    }
+
+    pub fn mutate(&mut self, genome: &Genome) -> Genome {
+        let seed = self.rng.gen();
+        genome.mutated(seed)
+    }
+
+    /// Différences gène par gène (modules, paramètres, stratégies, traits) de `a` vers `b`
+    pub fn diff(a: &Genome, b: &Genome) -> GenomeDiff {
+        let mut changes = Vec::new();
+        diff_family("module", described(&a.modules), described(&b.modules), &mut changes);
+        diff_family("paramètre", described(&a.parameters), described(&b.parameters), &mut changes);
+        diff_family("stratégie", described(&a.strategies), described(&b.strategies), &mut changes);
+        diff_family("trait", a.traits.clone().into_iter().collect(), b.traits.clone().into_iter().collect(), &mut changes);
+        GenomeDiff { from: Some(a.id), to: Some(b.id), changes }
+    }
+
+    /// Adopte un génome issu de l'évolution comme nouvelle base; renvoie ce qui a changé
+    pub fn apply_evolved_genome(&mut self, genome: Genome) -> GenomeDiff {
+        let diff = Self::diff(&self.base, &genome);
+        info!("🧬 Génome de génération {} adopté ({} gène(s) modifié(s))", genome.generation, diff.changes.len());
+        self.base = genome;
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parents() -> (Genome, Genome) {
+        let a = GenomeBuilder::base_genome();
+        let mut b = GenomeBuilder::base_genome();
+        for p in b.parameters.iter_mut() {
+            p.value = p.max;
+        }
+        for s in b.strategies.iter_mut() {
+            s.choice = s.alternatives.last().unwrap().clone();
+        }
+        b = b.with_parameter("gas_multiplier", 1.2, 1.0, 3.0);
+        (a, b)
+    }
+
+    #[test]
+    fn crossover_is_reproducible_and_pairs_genes_by_name() {
+        let (a, b) = parents();
+        for operator in [CrossoverOperator::SinglePoint, CrossoverOperator::Uniform] {
+            let child = Genome::crossover(&a, &b, operator, 42);
+            let again = Genome::crossover(&a, &b, operator, 42);
+            assert!(GenomeBuilder::diff(&child, &again).is_empty());
+            assert_eq!(child.ancestry, vec![a.id, b.id]);
+            assert_eq!(child.origin, GeneticOrigin::Crossover { operator, seed: 42 });
+            // Chaque gène vient de l'un des deux parents; le gène propre à `b` est hérité
+            for p in &child.parameters {
+                let from_a = a.parameter(&p.name) == Some(p.value);
+                let from_b = b.parameter(&p.name) == Some(p.value);
+                assert!(from_a || from_b, "{}", p.name);
+            }
+            assert_eq!(child.parameter("gas_multiplier"), Some(1.2));
+        }
+
+        // Point unique: un préfixe vient de `a`, la suite de `b`
+        let child = Genome::crossover(&a, &b, CrossoverOperator::SinglePoint, 7);
+        let origins: Vec<bool> = child.parameters.iter()
+            .filter(|p| p.name != "gas_multiplier")
+            .map(|p| a.parameter(&p.name) == Some(p.value))
+            .collect();
+        assert!(origins.windows(2).all(|w| w[0] || !w[1]));
+    }
+
+    #[test]
+    fn mutation_follows_per_gene_rates_and_diff_reports_it() {
+        let mut genome = GenomeBuilder::base_genome();
+        for p in genome.parameters.iter_mut() {
+            p.mutation_rate = if p.name == "mutation_rate" { 1.0 } else { 0.0 };
+        }
+        for m in genome.modules.iter_mut() {
+            m.mutation_rate = 0.0;
+        }
+        for s in genome.strategies.iter_mut() {
+            s.mutation_rate = if s.name == "selection" { 1.0 } else { 0.0 };
+        }
+
+        let child = genome.mutated(3);
+        assert_eq!(child.origin, GeneticOrigin::Mutation { seed: 3, mutated: vec!["mutation_rate".into(), "selection".into()] });
+        assert_ne!(child.strategy("selection"), Some("tournament"));
+        let rate = child.parameter("mutation_rate").unwrap();
+        assert!((0.001..=0.5).contains(&rate));
+
+        let diff = GenomeBuilder::diff(&genome, &child);
+        let names: Vec<&str> = diff.changes.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.contains(&"selection"));
+        assert!(diff.to_string().contains("~ stratégie selection: tournament →"));
+        assert!(names.iter().all(|n| ["mutation_rate", "selection"].contains(n)));
+    }
+
+    #[test]
+    fn json_round_trip_keeps_genes_and_origin() {
+        let genome = Genome::crossover(&GenomeBuilder::base_genome(), &GenomeBuilder::base_genome(), CrossoverOperator::Uniform, 1);
+        let restored = Genome::from_json(&genome.to_json().unwrap()).unwrap();
+        assert_eq!(restored, genome);
+
+        // Les génomes antérieurs au schéma (sans gènes) restent lisibles
+        let legacy = r#"{"id":"6f1c1f2e-4a9d-4a53-9d7e-3b8a2f1c0d11","birth":"2024-01-01T00:00:00Z","ancestry":[],"traits":{},"mutability":10,"integrity_score":90}"#;
+        let legacy = Genome::from_json(legacy).unwrap();
+        assert_eq!(legacy.schema_version, GENOME_SCHEMA_VERSION);
+        assert!(legacy.parameters.is_empty());
     }
 }
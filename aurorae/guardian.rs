@@ -1,7 +1,22 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
 use chrono::Utc;
+use parking_lot::RwLock;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
-use log::{info, warn, error};
+use tracing::{error, info, warn};
+
+use crate::blockchain_core::SharedBackend;
+use crate::code_evolution::SharedCodePrivileges;
+use crate::distributed_compute::QueueMetrics;
+use crate::economy::SharedBudget;
+use crate::event_bus::{AuroraeEvent, EventBus};
+use crate::instance_runner::InstanceSupervisor;
+use crate::resource_usage::{cgroup_stats, spawn_tracked, ResourceUsage};
+use crate::security::ThreatLevel;
+use crate::validator::{check_integrity, IntegrityStatus};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ModuleStatus {
@@ -14,6 +29,7 @@ pub enum ModuleStatus {
     Learning,     // En apprentissage actif
     Replicating,  // En processus de réplication
     Dreaming,     // En phase de simulation créative
+    Quarantined,  // Isolé jusqu'à revalidation
 }
 
 #[derive(Debug, Clone)]
@@ -27,10 +43,54 @@ pub struct MonitoredModule {
     pub autonomous_decisions: u32,  // Compteur de décisions autonomes
     pub learning_factor: f32,      // Capacité d'apprentissage
     pub creation_time: String,     // Moment de création
-    pub energy_usage: f64,         // Consommation d'énergie
+    pub energy_usage: f64,         // Consommation d'énergie (indice calculé depuis `resources`)
+    pub resources: Option<ResourceUsage>, // Dernier relevé CPU / mémoire / tâches
     pub child_modules: Vec<Uuid>,  // Modules enfants créés par ce module
+    pub last_heartbeat: Option<String>,
+    pub missed_heartbeats: u32,
+}
+
+/// Sonde de santé implémentée par un module surveillé
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    /// Nom du module dans le registre du gardien
+    fn module(&self) -> &str;
+
+    async fn heartbeat(&self) -> Result<(), String>;
+
+    /// Procédure de récupération propre au module (reconnexion, redémarrage…)
+    async fn recover(&self) -> Result<(), String> {
+        Err("Aucune procédure de récupération".to_string())
+    }
+}
+
+/// Cadence du protocole de battements de cœur
+#[derive(Debug, Clone)]
+pub struct HeartbeatConfig {
+    pub interval: Duration,
+    /// Délai de réponse d'une sonde avant de compter un battement manqué
+    pub timeout: Duration,
+    /// Battements manqués consécutifs avant de déclarer le module Unresponsive
+    pub max_missed: u32,
 }
 
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self { interval: Duration::from_secs(30), timeout: Duration::from_secs(5), max_missed: 3 }
+    }
+}
+
+/// Isolement d'un module corrompu et capacités qui lui ont été retirées
+#[derive(Debug, Clone)]
+pub struct QuarantineRecord {
+    pub module: String,
+    pub reason: String,
+    pub since: String,
+    pub revoked: Vec<&'static str>,
+}
+
+pub type SharedGuardian = Arc<RwLock<GuardianSentinel>>;
+
 pub struct GuardianSentinel {
     pub registry: HashMap<String, MonitoredModule>,
     pub system_uptime: String,
@@ -40,6 +100,15 @@ pub struct GuardianSentinel {
     pub modules_evolved: u32,
     pub threat_counters: HashMap<String, u32>,
     pub replication_history: Vec<String>,
+    /// Dernier bloc observé par chaîne, pour détecter les réorganisations
+    pub chain_heads: HashMap<u64, u64>,
+    /// Bus sur lequel les menaces et corruptions sont diffusées (alertes opérateur)
+    event_bus: Option<EventBus>,
+    pub quarantine: HashMap<String, QuarantineRecord>,
+    budget: Option<SharedBudget>,
+    code_privileges: Option<SharedCodePrivileges>,
+    /// Dernières métriques de file par ordonnanceur
+    pub queue_metrics: HashMap<String, QueueMetrics>,
 }
 
 impl GuardianSentinel {
@@ -53,9 +122,33 @@ impl GuardianSentinel {
             modules_evolved: 0,
             threat_counters: HashMap::new(),
             replication_history: Vec::new(),
+            chain_heads: HashMap::new(),
+            event_bus: None,
+            quarantine: HashMap::new(),
+            budget: None,
+            code_privileges: None,
+            queue_metrics: HashMap::new(),
         }
     }
 
+    pub fn attach_event_bus(&mut self, bus: EventBus) {
+        self.event_bus = Some(bus);
+    }
+
+    /// Budget dont l'accès est révoqué aux modules en quarantaine
+    pub fn attach_budget(&mut self, budget: SharedBudget) {
+        self.budget = Some(budget);
+    }
+
+    /// Privilèges de génération de code retirés aux modules en quarantaine
+    pub fn attach_code_privileges(&mut self, privileges: SharedCodePrivileges) {
+        self.code_privileges = Some(privileges);
+    }
+
+    pub fn shared(self) -> SharedGuardian {
+        Arc::new(RwLock::new(self))
+    }
+
     pub fn register_module(&mut self, name: &str) -> Uuid {
         let module_uuid = Uuid::new_v4();
         let module = MonitoredModule {
@@ -68,37 +161,59 @@ impl GuardianSentinel {
             autonomous_decisions: 0,
             learning_factor: 1.0,
             creation_time: Utc::now().to_rfc3339(),
-            energy_usage: 1.0,
+            energy_usage: 0.0,
+            resources: None,
             child_modules: Vec::new(),
+            last_heartbeat: None,
+            missed_heartbeats: 0,
         };
         self.registry.insert(name.to_string(), module);
-        println!("[AURORAE++] 🌱 Nouveau module enregistré: {} avec UUID: {}", name, module_uuid);
+        info!("🌱 Nouveau module enregistré: {} avec UUID: {}", name, module_uuid);
         module_uuid
     }
 
     pub fn update_status(&mut self, name: &str, status: ModuleStatus) {
+        // Un module en quarantaine n'en sort que par revalidation
+        if self.is_quarantined(name) && !matches!(status, ModuleStatus::Corrupted | ModuleStatus::Terminated) {
+            info!("🔒 {} reste en quarantaine ({:?} ignoré)", name, status);
+            return;
+        }
+
         // Première étape: recueillir les informations
         let module_info = if let Some(module) = self.registry.get_mut(name) {
             // Mise à jour des infos de base
             module.last_check = Utc::now().to_rfc3339();
             module.status = status.clone();
-            println!("[AURORAE++] 🛰️ Surveillance: {} -> {:?}", name, status);
+            info!("🛰️ Surveillance: {} -> {:?}", name, status);
+            if status == ModuleStatus::Corrupted {
+                if let Some(bus) = &self.event_bus {
+                    bus.publish(AuroraeEvent::ModuleCorrupted {
+                        module: name.to_string(),
+                        reason: format!("déclaré corrompu (stade d'évolution {})", module.evolution_stage),
+                    });
+                }
+            }
             
             // Collecter les infos pour les décisions de récupération/évolution
-            let needs_recovery = matches!(status, ModuleStatus::Unresponsive | ModuleStatus::Corrupted) 
-                && !module.recovery_attempted;
+            let needs_recovery = status == ModuleStatus::Unresponsive && !module.recovery_attempted;
                 
             let evolution_candidate = module.autonomous_decisions > 10 && module.learning_factor > 2.0;
             
             // Retourner un tuple des informations collectées
             Some((needs_recovery, evolution_candidate, module.name.clone()))
         } else {
-            println!("[AURORAE++] ⚠️ Module inconnu: {}", name);
+            warn!("⚠️ Module inconnu: {}", name);
             None
         };
         
         // Si le module existe, traiter la récupération et l'évolution si nécessaire
         if let Some((needs_recovery, evolution_candidate, module_name)) = module_info {
+            // Un module corrompu est isolé plutôt que relancé
+            if status == ModuleStatus::Corrupted {
+                self.quarantine_module(&module_name, "déclaré corrompu");
+                return;
+            }
+
             // Gérer la récupération si nécessaire
             if needs_recovery {
                 self.handle_recovery(&module_name);
@@ -121,13 +236,13 @@ impl GuardianSentinel {
             self.total_decisions += 1;
             
             // Simuler la prise de décision autonome
-            println!("[AURORAE++] 🧠 Diagnostic autonome en cours pour {}...", module.name);
+            info!("🧠 Diagnostic autonome en cours pour {}...", module.name);
             
             // Après la fin du processus de récupération
             module.status = ModuleStatus::Operational;
             module.learning_factor *= 1.1; // Apprentissage basé sur l'expérience de récupération
             
-            println!("[AURORAE++] 🚑 Récupération réussie pour module: {}", module.name);
+            info!("🚑 Récupération réussie pour module: {}", module.name);
         }
         
         // Enregistrer l'incident pour apprentissage
@@ -139,14 +254,14 @@ impl GuardianSentinel {
         // Effectuer des opérations d'évolution
         if let Some(module) = self.registry.get_mut(module_name) {
             // Processus d'évolution
-            println!("[AURORAE++] 🌌 Évolution autonome du module: {}", module.name);
+            info!("🌌 Évolution autonome du module: {}", module.name);
             module.evolution_stage += 1;
             module.status = ModuleStatus::Evolving;
             module.learning_factor *= 1.5;
             self.modules_evolved += 1;
             
-            println!(
-                "[AURORAE++] 🚀 Module {} a atteint le stade d'évolution {}",
+            info!(
+                "🚀 Module {} a atteint le stade d'évolution {}",
                 module.name, module.evolution_stage
             );
             
@@ -155,15 +270,196 @@ impl GuardianSentinel {
         }
     }
     
+    /// Modules actuellement déclarés corrompus ou isolés
+    pub fn corrupted_modules(&self) -> Vec<String> {
+        let mut modules: Vec<String> = self.registry.values()
+            .filter(|m| m.status == ModuleStatus::Corrupted)
+            .map(|m| m.name.clone())
+            .chain(self.quarantine.keys().cloned())
+            .collect();
+        modules.sort();
+        modules.dedup();
+        modules
+    }
+
+    pub fn is_quarantined(&self, name: &str) -> bool {
+        self.quarantine.contains_key(name)
+    }
+
+    /// Isole un module: abonnements au bus suspendus, accès au budget et privilèges de
+    /// génération de code révoqués jusqu'à `release_quarantine`
+    pub fn quarantine_module(&mut self, name: &str, reason: &str) {
+        if self.is_quarantined(name) {
+            return;
+        }
+        let mut revoked = Vec::new();
+        if let Some(bus) = &self.event_bus {
+            bus.suspend(name);
+            revoked.push("event_bus");
+        }
+        if let Some(budget) = &self.budget {
+            budget.write().revoke(name);
+            revoked.push("budget");
+        }
+        if let Some(privileges) = &self.code_privileges {
+            privileges.write().revoke(name);
+            revoked.push("code_evolution");
+        }
+        if let Some(module) = self.registry.get_mut(name) {
+            module.status = ModuleStatus::Quarantined;
+            module.last_check = Utc::now().to_rfc3339();
+        }
+        info!("☣️ Module {} en quarantaine ({}): {} révoqué(s)", name, reason, revoked.join(", "));
+        self.quarantine.insert(name.to_string(), QuarantineRecord {
+            module: name.to_string(),
+            reason: reason.to_string(),
+            since: Utc::now().to_rfc3339(),
+            revoked,
+        });
+        self.total_decisions += 1;
+        self.record_threat("module_quarantined", name);
+    }
+
+    /// Lève la quarantaine après une revalidation (manuelle ou automatique) réussie
+    pub fn release_quarantine(&mut self, name: &str, validated_by: &str) -> bool {
+        let Some(record) = self.quarantine.remove(name) else { return false };
+        if let Some(bus) = &self.event_bus {
+            bus.resume(name);
+        }
+        if let Some(budget) = &self.budget {
+            budget.write().restore(name);
+        }
+        if let Some(privileges) = &self.code_privileges {
+            privileges.write().restore(name);
+        }
+        if let Some(module) = self.registry.get_mut(name) {
+            module.status = ModuleStatus::Operational;
+            module.recovery_attempted = false;
+            module.last_check = Utc::now().to_rfc3339();
+        }
+        info!("✅ Quarantaine de {} levée (validé par {}, isolé depuis {})", name, validated_by, record.since);
+        true
+    }
+
+    /// Revalidation automatique: vérifie l'intégrité de chaque module isolé et libère
+    /// ceux qui la passent; retourne les modules libérés
+    pub fn revalidate_quarantined(&mut self) -> Vec<String> {
+        let candidates: Vec<String> = self.quarantine.keys().cloned().collect();
+        let mut released = Vec::new();
+        for name in candidates {
+            let result = check_integrity(&name);
+            if matches!(result.status, IntegrityStatus::Optimal | IntegrityStatus::Good)
+                && self.release_quarantine(&name, "vérification d'intégrité")
+            {
+                released.push(name);
+            }
+        }
+        released
+    }
+
+    /// Battement reçu: un module Unresponsive ou en récupération redevient opérationnel
+    pub fn record_heartbeat(&mut self, name: &str) {
+        if !self.registry.contains_key(name) {
+            self.register_module(name);
+        }
+        if let Some(module) = self.registry.get_mut(name) {
+            module.last_heartbeat = Some(Utc::now().to_rfc3339());
+            module.last_check = Utc::now().to_rfc3339();
+            module.missed_heartbeats = 0;
+            if matches!(module.status, ModuleStatus::Unresponsive | ModuleStatus::SelfHealing) {
+                module.status = ModuleStatus::Operational;
+                module.recovery_attempted = false;
+                info!("💓 Module {} rétabli", name);
+            }
+        }
+    }
+
+    /// Battement manqué; retourne vrai si une récupération doit être lancée (module déclaré
+    /// Unresponsive, puis à chaque nouvelle série de `max_missed` échecs)
+    pub fn record_missed_heartbeat(&mut self, name: &str, reason: &str, max_missed: u32) -> bool {
+        if !self.registry.contains_key(name) {
+            self.register_module(name);
+        }
+        let Some(module) = self.registry.get_mut(name) else { return false };
+        module.missed_heartbeats += 1;
+        module.last_check = Utc::now().to_rfc3339();
+        let missed = module.missed_heartbeats;
+        info!("💔 Battement manqué pour {} ({}/{}): {}", name, missed, max_missed, reason);
+        if missed < max_missed.max(1) || missed % max_missed.max(1) != 0 {
+            return false;
+        }
+        module.status = ModuleStatus::Unresponsive;
+        info!("🛰️ Surveillance: {} -> {:?}", name, ModuleStatus::Unresponsive);
+        self.record_threat("heartbeat_missed", name);
+        true
+    }
+
+    /// Résultat de la récupération: le prochain battement confirmera le rétablissement
+    pub fn record_recovery(&mut self, name: &str, outcome: &Result<(), String>) {
+        if let Some(module) = self.registry.get_mut(name) {
+            module.recovery_attempted = true;
+            module.autonomous_decisions += 1;
+            self.total_decisions += 1;
+            match outcome {
+                Ok(()) => {
+                    module.status = ModuleStatus::SelfHealing;
+                    info!("🚑 Récupération lancée pour {}, en attente du prochain battement", name);
+                }
+                Err(e) => warn!("⚠️ Récupération de {} impossible: {}", name, e),
+            }
+        }
+        if outcome.is_err() {
+            self.record_threat("recovery_failed", name);
+        }
+    }
+
     pub fn record_threat(&mut self, threat_type: &str, source: &str) {
         let entry = self.threat_counters.entry(threat_type.to_string()).or_insert(0);
         *entry += 1;
-        println!("[AURORAE++] 🔒 Menace enregistrée: {} de source {}", threat_type, source);
+        info!("🔒 Menace enregistrée: {} de source {}", threat_type, source);
+        if let Some(bus) = &self.event_bus {
+            bus.publish(AuroraeEvent::ThreatDetected {
+                threat_type: threat_type.to_string(),
+                source: source.to_string(),
+                level: threat_level(threat_type),
+            });
+        }
         
         // Augmenter le niveau de protection en fonction des menaces détectées
         self.self_protection_level += 0.05;
     }
     
+    /// Surveille l'activité on-chain (réorganisations et souscriptions perdues) et les
+    /// battements envoyés par les instances enfants
+    pub fn observe_chain_event(&mut self, event: &AuroraeEvent) {
+        match event {
+            AuroraeEvent::NewBlock { chain_id, number, .. } => {
+                let previous = self.chain_heads.insert(*chain_id, *number);
+                if let Some(previous) = previous {
+                    if *number <= previous {
+                        self.record_threat("chain_reorg", &format!("chain-{} bloc {} ≤ {}", chain_id, number, previous));
+                    }
+                }
+            }
+            AuroraeEvent::SubscriptionLost { chain_id, kind, reason } => {
+                self.record_threat("chain_subscription_lost", &format!("chain-{} {}: {}", chain_id, kind, reason));
+                self.update_status("blockchain_core", ModuleStatus::Unresponsive);
+            }
+            AuroraeEvent::ReorgDetected { chain_id, tx_hash, old_block, .. } => {
+                self.record_threat("chain_reorg", &format!("chain-{} {:?} (bloc {})", chain_id, tx_hash, old_block));
+            }
+            AuroraeEvent::InstanceMessage { from, kind, .. } if kind == "heartbeat" => {
+                self.record_heartbeat(&InstanceSupervisor::module_name(from));
+            }
+            AuroraeEvent::ChainLog { .. }
+            | AuroraeEvent::InstanceMessage { .. }
+            | AuroraeEvent::TransactionFinalized { .. }
+            | AuroraeEvent::ThreatDetected { .. }
+            | AuroraeEvent::ModuleCorrupted { .. }
+            | AuroraeEvent::IntegrityBreach { .. } => {}
+        }
+    }
+
     pub fn replicate_module(&mut self, name: &str) -> Result<Uuid, String> {
         if let Some(parent_module) = self.registry.get(name) {
             let new_name = format!("{}-replica-{}", name, Uuid::new_v4().to_string().split('-').next().unwrap_or("1"));
@@ -189,7 +485,7 @@ impl GuardianSentinel {
             // Enregistrer l'historique de réplication
             self.replication_history.push(format!("{} -> {} at {}", name, new_name, Utc::now().to_rfc3339()));
             
-            println!("[AURORAE++] 🧬 Module {} répliqué avec succès vers {}", name, new_name);
+            info!("🧬 Module {} répliqué avec succès vers {}", name, new_name);
             Ok(child_uuid)
         } else {
             Err(format!("Module '{}' non trouvé pour réplication", name))
@@ -200,7 +496,7 @@ impl GuardianSentinel {
         if let Some(module) = self.registry.get_mut(name) {
             // Mettre en mode rêverie/simulation
             module.status = ModuleStatus::Dreaming;
-            println!("[AURORAE++] 💭 Module {} entre en phase de rêve", name);
+            info!("💭 Module {} entre en phase de rêve", name);
             
             // Simuler une amélioration par la rêverie
             module.learning_factor *= 1.05;
@@ -216,8 +512,29 @@ impl GuardianSentinel {
         }
     }
 
+    /// Intègre un relevé de `ResourceMonitor::sample`; seuls les modules enregistrés sont mis à jour
+    pub fn apply_resource_usage(&mut self, usage: &[ResourceUsage]) {
+        for sample in usage {
+            if let Some(module) = self.registry.get_mut(&sample.module) {
+                module.energy_usage = sample.energy();
+                module.resources = Some(sample.clone());
+            }
+        }
+    }
+
+    /// Intègre les métriques de file d'un ordonnanceur; une attente p95 au-delà de `alert_ms`
+    /// est signalée
+    pub fn record_queue_metrics(&mut self, module: &str, metrics: QueueMetrics, alert_ms: u64) {
+        if metrics.p95_wait_ms > alert_ms {
+            warn!("File de {} saturée: attente p95 {} ms", module, metrics.p95_wait_ms);
+            warn!("⚠️ File de {} saturée: attente p95 {} ms (seuil {} ms, {} en attente)",
+                     module, metrics.p95_wait_ms, alert_ms, metrics.queued);
+        }
+        self.queue_metrics.insert(module.to_string(), metrics);
+    }
+
     pub fn status_report(&self) {
-        println!("[AURORAE++] 🔍 RAPPORT DE SANTÉ DES MODULES:");
+        info!("🔍 RAPPORT DE SANTÉ DES MODULES:");
         println!("Système en opération depuis: {}", self.system_uptime);
         println!("Mode autonome: {}", if self.autonomous_mode { "ACTIVÉ ✓" } else { "DÉSACTIVÉ ✗" });
         println!("Niveau de protection: {:.2}", self.self_protection_level);
@@ -233,6 +550,32 @@ impl GuardianSentinel {
                 module.autonomous_decisions,
                 module.learning_factor
             );
+            if let Some(usage) = &module.resources {
+                println!(
+                    "    ↳ CPU: {:.1}% ({:?}) • Mémoire allouée: {:.2} Mo • Tâches actives: {} • Énergie: {:.2}",
+                    usage.cpu_share() * 100.0,
+                    usage.cpu_time,
+                    usage.allocated_mb(),
+                    usage.active_tasks,
+                    module.energy_usage
+                );
+            }
+        }
+        for (module, queue) in &self.queue_metrics {
+            println!(
+                "File {}: {} en attente • {} en cours • {} terminées • {} échecs • {} reprises • {} vols • attente moy. {} ms / p95 {} ms / max {} ms",
+                module, queue.queued, queue.in_flight, queue.completed, queue.failed, queue.retries, queue.steals,
+                queue.mean_wait_ms, queue.p95_wait_ms, queue.max_wait_ms
+            );
+        }
+        if let Some(cgroup) = cgroup_stats() {
+            let limit = cgroup.memory_limit.map(|l| format!("{:.0} Mo", l as f64 / (1024.0 * 1024.0))).unwrap_or_else(|| "illimitée".to_string());
+            println!(
+                "Cgroup: CPU {:?} • Mémoire {:.0} Mo / {}",
+                cgroup.cpu_usage,
+                cgroup.memory_bytes as f64 / (1024.0 * 1024.0),
+                limit
+            );
         }
     }
     
@@ -253,14 +596,14 @@ impl GuardianSentinel {
     }
     
     pub fn autonomous_defense(&mut self, threat_level: u32) {
-        println!("[AURORAE++] 🛡️ Système de défense autonome activé, niveau de menace: {}", threat_level);
+        info!("🛡️ Système de défense autonome activé, niveau de menace: {}", threat_level);
         
         // Augmenter la protection en fonction du niveau de menace
         self.self_protection_level += threat_level as f64 * 0.1;
         
         // Pour les menaces importantes, activer l'auto-réplication des modules critiques
         if threat_level >= 3 {
-            println!("[AURORAE++] ⚠️ Menace significative détectée, démarrage de l'auto-réplication");
+            warn!("⚠️ Menace significative détectée, démarrage de l'auto-réplication");
             
             // Identifier et répliquer les modules critiques
             let critical_modules: Vec<String> = self.registry.iter()
@@ -273,6 +616,167 @@ impl GuardianSentinel {
             }
         }
         
-        println!("[AURORAE++] 🔒 Défense autonome terminée, niveau de protection: {:.2}", self.self_protection_level);
+        info!("🔒 Défense autonome terminée, niveau de protection: {:.2}", self.self_protection_level);
+    }
+}
+
+/// Gravité des menaces enregistrées par le gardien
+fn threat_level(threat_type: &str) -> ThreatLevel {
+    match threat_type {
+        "module_quarantined" => ThreatLevel::Critical,
+        "recovery_failed" | "module_failure" => ThreatLevel::High,
+        "heartbeat_missed" | "chain_reorg" | "chain_subscription_lost" => ThreatLevel::Medium,
+        _ => ThreatLevel::Low,
+    }
+}
+
+/// Lance le protocole de battements de cœur: chaque sonde est interrogée à intervalle
+/// régulier et les modules muets sont déclarés Unresponsive puis récupérés
+pub fn spawn_heartbeat(guardian: SharedGuardian, probes: Vec<Arc<dyn HealthProbe>>, config: HeartbeatConfig) -> JoinHandle<()> {
+    info!("💓 Surveillance par battements: {} modules toutes les {:?}", probes.len(), config.interval);
+    spawn_tracked("guardian", async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        loop {
+            ticker.tick().await;
+            for probe in &probes {
+                let result = match tokio::time::timeout(config.timeout, probe.heartbeat()).await {
+                    Ok(result) => result,
+                    Err(_) => Err(format!("aucune réponse en {:?}", config.timeout)),
+                };
+                let needs_recovery = match result {
+                    Ok(()) => {
+                        guardian.write().record_heartbeat(probe.module());
+                        false
+                    }
+                    Err(reason) => guardian.write().record_missed_heartbeat(probe.module(), &reason, config.max_missed),
+                };
+                if needs_recovery {
+                    let outcome = probe.recover().await;
+                    guardian.write().record_recovery(probe.module(), &outcome);
+                }
+            }
+        }
+    })
+}
+
+/// Sonde d'un backend de chaîne: le nœud doit répondre au numéro de bloc
+pub struct ChainProbe {
+    module: String,
+    backend: SharedBackend,
+}
+
+impl ChainProbe {
+    pub fn new(module: &str, backend: SharedBackend) -> Self {
+        Self { module: module.to_string(), backend }
+    }
+}
+
+#[async_trait]
+impl HealthProbe for ChainProbe {
+    fn module(&self) -> &str {
+        &self.module
+    }
+
+    async fn heartbeat(&self) -> Result<(), String> {
+        self.backend.block_number().await.map(|_| ())
+    }
+}
+
+/// Sonde d'un état partagé: un verrou bloqué au-delà de `timeout` signale un module figé
+pub struct LockProbe<T> {
+    module: String,
+    state: Arc<RwLock<T>>,
+    timeout: Duration,
+}
+
+impl<T> LockProbe<T> {
+    pub fn new(module: &str, state: Arc<RwLock<T>>, timeout: Duration) -> Self {
+        Self { module: module.to_string(), state, timeout }
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync + 'static> HealthProbe for LockProbe<T> {
+    fn module(&self) -> &str {
+        &self.module
+    }
+
+    async fn heartbeat(&self) -> Result<(), String> {
+        let (state, timeout) = (self.state.clone(), self.timeout);
+        let acquired = tokio::task::spawn_blocking(move || state.try_read_for(timeout).is_some())
+            .await
+            .map_err(|e| format!("Sonde interrompue: {}", e))?;
+        if acquired {
+            Ok(())
+        } else {
+            Err(format!("verrou indisponible depuis plus de {:?}", timeout))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missed_heartbeats_mark_unresponsive_then_heartbeat_restores() {
+        let mut guardian = GuardianSentinel::new();
+        guardian.register_module("economy");
+
+        assert!(!guardian.record_missed_heartbeat("economy", "timeout", 3));
+        assert!(!guardian.record_missed_heartbeat("economy", "timeout", 3));
+        assert!(guardian.record_missed_heartbeat("economy", "timeout", 3));
+        assert_eq!(guardian.registry["economy"].status, ModuleStatus::Unresponsive);
+
+        guardian.record_recovery("economy", &Ok(()));
+        assert_eq!(guardian.registry["economy"].status, ModuleStatus::SelfHealing);
+        guardian.record_heartbeat("economy");
+        assert_eq!(guardian.registry["economy"].status, ModuleStatus::Operational);
+        assert_eq!(guardian.registry["economy"].missed_heartbeats, 0);
+    }
+
+    #[test]
+    fn corrupted_module_is_quarantined_until_released() {
+        let bus = EventBus::new();
+        let budget = crate::economy::BudgetEngine::new(100.0).shared();
+        let privileges = SharedCodePrivileges::default();
+        let mut guardian = GuardianSentinel::new();
+        guardian.attach_event_bus(bus.clone());
+        guardian.attach_budget(budget.clone());
+        guardian.attach_code_privileges(privileges.clone());
+        guardian.register_module("economy");
+
+        guardian.update_status("economy", ModuleStatus::Corrupted);
+        assert_eq!(guardian.registry["economy"].status, ModuleStatus::Quarantined);
+        assert!(bus.is_suspended("economy"));
+        assert!(budget.read().is_revoked("economy"));
+        assert!(privileges.read().is_revoked("economy"));
+
+        guardian.update_status("economy", ModuleStatus::Operational);
+        assert_eq!(guardian.registry["economy"].status, ModuleStatus::Quarantined);
+
+        assert!(guardian.release_quarantine("economy", "opérateur"));
+        assert_eq!(guardian.registry["economy"].status, ModuleStatus::Operational);
+        assert!(!bus.is_suspended("economy") && !budget.read().is_revoked("economy"));
+        assert!(!privileges.read().is_revoked("economy"));
+    }
+
+    #[test]
+    fn resource_usage_updates_energy_of_registered_modules() {
+        let mut guardian = GuardianSentinel::new();
+        guardian.register_module("economy");
+        let sample = ResourceUsage {
+            module: "economy".to_string(),
+            cpu_time: Duration::from_millis(200),
+            active_tasks: 2,
+            window: Duration::from_secs(1),
+            ..Default::default()
+        };
+        let stranger = ResourceUsage { module: "unregistered".to_string(), ..Default::default() };
+
+        guardian.apply_resource_usage(&[sample.clone(), stranger]);
+        assert_eq!(guardian.registry["economy"].resources, Some(sample.clone()));
+        assert!((guardian.registry["economy"].energy_usage - sample.energy()).abs() < 1e-9);
+        assert!(!guardian.registry.contains_key("unregistered"));
     }
 }
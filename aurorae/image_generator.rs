@@ -0,0 +1,292 @@
+//! AURORAE++ - image_generator.rs
+//!
+//! Illustrations des rêves et des NFT: génération par une API Stable Diffusion (Stability AI
+//! ou instance compatible) avec repli procédural en SVG. Le rendu procédural est dérivé du
+//! contenu (titre et description): un même rêve produit toujours la même œuvre.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::Engine as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::secrets::secret;
+
+pub const ARTWORK_DIR: &str = "aurorae_state/artwork";
+const STABILITY_API: &str = "https://api.stability.ai/v1/generation/stable-diffusion-xl-1024-v1-0/text-to-image";
+const SVG_SIZE: u32 = 512;
+
+/// Œuvre générée et écrite sur disque
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Artwork {
+    pub path: PathBuf,
+    pub generator: String,
+    #[serde(deserialize_with = "media_type")]
+    pub media_type: &'static str,
+}
+
+/// Types de média connus: l'œuvre rechargée depuis disque garde un `&'static str`
+fn media_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<&'static str, D::Error> {
+    match String::deserialize(deserializer)?.as_str() {
+        "image/png" => Ok("image/png"),
+        _ => Ok("image/svg+xml"),
+    }
+}
+
+impl Artwork {
+    pub fn file_name(&self) -> String {
+        self.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    pub fn read(&self) -> Result<Vec<u8>, String> {
+        fs::read(&self.path).map_err(|e| format!("Erreur lecture {}: {}", self.path.display(), e))
+    }
+}
+
+#[async_trait]
+pub trait ImageGenerator: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn generate(&self, title: &str, description: &str) -> Result<Artwork, String>;
+}
+
+/// Empreinte du contenu d'un rêve, base du nom de fichier et de la graine de rendu
+pub fn content_hash(title: &str, description: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(title.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(description.as_bytes());
+    hasher.finalize().into()
+}
+
+fn write_artwork(path: &Path, content: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Erreur création {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))
+}
+
+/// Rendu SVG déterministe: dégradé, formes et orbites tirés de l'empreinte du contenu
+pub struct ProceduralGenerator {
+    pub out_dir: PathBuf,
+}
+
+impl Default for ProceduralGenerator {
+    fn default() -> Self {
+        Self::new(ARTWORK_DIR)
+    }
+}
+
+impl ProceduralGenerator {
+    pub fn new(out_dir: impl Into<PathBuf>) -> Self {
+        Self { out_dir: out_dir.into() }
+    }
+
+    pub fn render_svg(title: &str, description: &str) -> String {
+        let seed = content_hash(title, description);
+        // Flux d'octets pseudo-aléatoires: SHA-256 chaîné à partir de l'empreinte
+        let mut stream = Vec::with_capacity(256);
+        let mut block = seed;
+        while stream.len() < 256 {
+            block = Sha256::digest(block).into();
+            stream.extend_from_slice(&block);
+        }
+        let byte = |i: usize| stream[i % stream.len()] as u32;
+
+        let hue = byte(0) * 360 / 256;
+        let accent = (hue + 120 + byte(1) % 120) % 360;
+        let shapes = 6 + (description.len() % 10) as u32 + byte(2) % 6;
+        let mut svg = format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{s}" height="{s}" viewBox="0 0 {s} {s}">"#,
+                r#"<defs><radialGradient id="bg" cx="50%" cy="45%" r="75%">"#,
+                r#"<stop offset="0%" stop-color="hsl({h},70%,28%)"/><stop offset="100%" stop-color="hsl({a},60%,8%)"/>"#,
+                r#"</radialGradient></defs><rect width="100%" height="100%" fill="url(#bg)"/>"#,
+            ),
+            s = SVG_SIZE, h = hue, a = accent,
+        );
+
+        for i in 0..shapes as usize {
+            let base = 8 + i * 6;
+            let (x, y) = (byte(base) * SVG_SIZE / 256, byte(base + 1) * SVG_SIZE / 256);
+            let size = 12 + byte(base + 2) % 96;
+            let color = format!("hsl({},{}%,{}%)", (hue + byte(base + 3) * 90 / 256) % 360, 55 + byte(base + 4) % 40, 45 + byte(base + 5) % 30);
+            let opacity = 0.25 + (byte(base + 3) % 50) as f32 / 100.0;
+            if byte(base) % 3 == 0 {
+                let points: Vec<String> = (0..3 + byte(base + 4) % 4)
+                    .map(|k| {
+                        let angle = (k as f32 / (3 + byte(base + 4) % 4) as f32) * std::f32::consts::TAU + byte(base + 5) as f32 / 40.0;
+                        format!("{:.1},{:.1}", x as f32 + size as f32 * angle.cos(), y as f32 + size as f32 * angle.sin())
+                    })
+                    .collect();
+                svg.push_str(&format!(r#"<polygon points="{}" fill="{}" fill-opacity="{:.2}"/>"#, points.join(" "), color, opacity));
+            } else {
+                svg.push_str(&format!(
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="{}" stroke-opacity="{:.2}"/>"#,
+                    x, y, size, color, 1 + byte(base + 2) % 6, opacity,
+                ));
+            }
+        }
+
+        svg.push_str(&format!(
+            r#"<text x="50%" y="{}" text-anchor="middle" font-family="monospace" font-size="18" fill="hsl({},80%,85%)">{}</text></svg>"#,
+            SVG_SIZE - 24, accent, escape_xml(title),
+        ));
+        svg
+    }
+
+    /// Version synchrone de `generate`, utilisable hors du runtime asynchrone
+    pub fn render(&self, title: &str, description: &str) -> Result<Artwork, String> {
+        let path = self.out_dir.join(format!("{}.svg", hex::encode(content_hash(title, description))));
+        if !path.exists() {
+            write_artwork(&path, Self::render_svg(title, description).as_bytes())?;
+        }
+        Ok(Artwork { path, generator: "procedural".to_string(), media_type: "image/svg+xml" })
+    }
+}
+
+#[async_trait]
+impl ImageGenerator for ProceduralGenerator {
+    fn name(&self) -> &str {
+        "procedural"
+    }
+
+    async fn generate(&self, title: &str, description: &str) -> Result<Artwork, String> {
+        self.render(title, description)
+    }
+}
+
+/// API texte-vers-image Stable Diffusion (format Stability AI v1)
+pub struct StableDiffusionGenerator {
+    client: reqwest::Client,
+    pub api_url: String,
+    api_key: String,
+    pub out_dir: PathBuf,
+    pub steps: u32,
+}
+
+impl StableDiffusionGenerator {
+    pub fn new(api_url: &str, api_key: &str, out_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(120))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+            api_url: api_url.to_string(),
+            api_key: api_key.to_string(),
+            out_dir: out_dir.into(),
+            steps: 30,
+        }
+    }
+
+    /// Configuré par le secret `STABILITY_API_KEY` (et `STABLE_DIFFUSION_URL` pour une autre instance)
+    pub fn from_env() -> Option<Self> {
+        let api_key = secret("image_generator", "STABILITY_API_KEY")?;
+        let api_url = std::env::var("STABLE_DIFFUSION_URL").unwrap_or_else(|_| STABILITY_API.to_string());
+        Some(Self::new(&api_url, api_key.expose(), ARTWORK_DIR))
+    }
+}
+
+#[async_trait]
+impl ImageGenerator for StableDiffusionGenerator {
+    fn name(&self) -> &str {
+        "stable-diffusion"
+    }
+
+    async fn generate(&self, title: &str, description: &str) -> Result<Artwork, String> {
+        let hash = content_hash(title, description);
+        let path = self.out_dir.join(format!("{}.png", hex::encode(hash)));
+        let artwork = Artwork { path: path.clone(), generator: self.name().to_string(), media_type: "image/png" };
+        if path.exists() {
+            return Ok(artwork);
+        }
+
+        // Graine dérivée du contenu: une nouvelle génération du même rêve reste proche
+        let seed = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]);
+        let body = json!({
+            "text_prompts": [{ "text": format!("{}. {}, digital art, luminous, abstract", title, description), "weight": 1.0 }],
+            "cfg_scale": 7,
+            "steps": self.steps,
+            "samples": 1,
+            "seed": seed,
+            "width": 1024,
+            "height": 1024,
+        });
+        let response = self.client
+            .post(&self.api_url)
+            .bearer_auth(&self.api_key)
+            .header("Accept", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("API Stable Diffusion injoignable: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Statut HTTP {}", response.status()));
+        }
+        let body: Value = response.json().await.map_err(|e| format!("Réponse invalide: {}", e))?;
+        let encoded = body["artifacts"][0]["base64"].as_str()
+            .ok_or_else(|| "Aucune image dans la réponse".to_string())?;
+        let image = base64::engine::general_purpose::STANDARD.decode(encoded)
+            .map_err(|e| format!("Image base64 invalide: {}", e))?;
+        write_artwork(&path, &image)?;
+        Ok(artwork)
+    }
+}
+
+/// Générateur par défaut: Stable Diffusion si configuré, rendu procédural sinon ou en cas d'échec
+pub struct ArtworkStudio {
+    primary: Option<StableDiffusionGenerator>,
+    pub fallback: ProceduralGenerator,
+}
+
+impl Default for ArtworkStudio {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl ArtworkStudio {
+    pub fn from_env() -> Self {
+        Self { primary: StableDiffusionGenerator::from_env(), fallback: ProceduralGenerator::default() }
+    }
+}
+
+#[async_trait]
+impl ImageGenerator for ArtworkStudio {
+    fn name(&self) -> &str {
+        self.primary.as_ref().map(|p| p.name()).unwrap_or_else(|| self.fallback.name())
+    }
+
+    async fn generate(&self, title: &str, description: &str) -> Result<Artwork, String> {
+        if let Some(primary) = &self.primary {
+            match primary.generate(title, description).await {
+                Ok(artwork) => return Ok(artwork),
+                Err(e) => warn!("⚠️ Génération Stable Diffusion impossible ({}), rendu procédural", e),
+            }
+        }
+        self.fallback.generate(title, description).await
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn procedural_render_is_tied_to_content() {
+        let a = ProceduralGenerator::render_svg("Synthèse 1", "Une fusion <des> concepts");
+        assert_eq!(a, ProceduralGenerator::render_svg("Synthèse 1", "Une fusion <des> concepts"));
+        assert_ne!(a, ProceduralGenerator::render_svg("Synthèse 2", "Une fusion <des> concepts"));
+        assert!(a.starts_with("<svg") && a.ends_with("</svg>"));
+        assert!(a.contains("Synthèse 1"));
+    }
+}
@@ -0,0 +1,414 @@
+//! AURORAE++ - inspiration.rs
+//!
+//! Sources d'inspiration des rêves et des stratégies: dépôts GitHub populaires (jeton
+//! facultatif, réponses mises en cache et revalidées par ETag), dossier local ou corpus
+//! embarqué. La source est choisie dans `inspiration.toml`; hors ligne, la source GitHub
+//! sert sa dernière réponse en cache et le corpus embarqué prend le relais en dernier recours.
+
+use std::collections::HashMap;
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rand::seq::SliceRandom;
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::config::CONFIG;
+use crate::secrets::{secret, SecretHandle};
+
+pub const DEFAULT_INSPIRATION_PATH: &str = "inspiration.toml";
+const GITHUB_SEARCH_API: &str = "https://api.github.com/search/repositories";
+
+/// Corpus embarqué: disponible sans réseau ni fichier
+const BUNDLED_CORPUS: &[(&str, &str)] = &[
+    ("Consensus adaptatif", "Un protocole dont les validateurs ajustent leur poids selon la qualité de leurs contributions"),
+    ("Oracle neuromorphique", "Des nœuds qui apprennent à pondérer leurs sources de prix comme des synapses"),
+    ("Trésorerie vivante", "Une réserve qui se rééquilibre seule entre liquidité, staking et financement de l'écosystème"),
+    ("Ponts sans confiance", "Des preuves légères vérifiées on-chain plutôt que des relais multi-signatures"),
+    ("Contrats auto-réparants", "Un contrat qui détecte une invariance violée et se met en pause avant d'être exploité"),
+    ("Art génératif on-chain", "Des œuvres dont le rendu dépend de l'historique des transactions de leur détenteur"),
+    ("Essaim d'agents", "Des instances spécialisées qui négocient entre elles la répartition des tâches"),
+    ("Gouvernance par réputation", "Un vote pondéré par les contributions vérifiables plutôt que par le capital"),
+];
+
+/// Inspiration fournie par une source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inspiration {
+    pub title: String,
+    pub description: String,
+    pub url: Option<String>,
+    pub source: String,
+}
+
+impl Inspiration {
+    pub fn text(&self) -> String {
+        match &self.url {
+            Some(url) => format!("Inspiré par {}\nDescription: {}\nURL: {}", self.title, self.description, url),
+            None => format!("Inspiré par {}\nDescription: {}", self.title, self.description),
+        }
+    }
+}
+
+#[async_trait]
+pub trait InspirationSource: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn fetch(&self) -> Result<Vec<Inspiration>, String>;
+}
+
+/// Source choisie dans `inspiration.toml`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    Github,
+    Local,
+    Bundled,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InspirationConfig {
+    pub source: SourceKind,
+    /// Dossier de la source locale (un fichier texte par inspiration)
+    pub local_path: PathBuf,
+    /// Cache des réponses GitHub (corps et ETag)
+    pub cache_path: PathBuf,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+impl Default for InspirationConfig {
+    fn default() -> Self {
+        Self {
+            source: SourceKind::Github,
+            local_path: PathBuf::from("inspiration"),
+            cache_path: PathBuf::from("aurorae_state/inspiration_cache.json"),
+            keywords: ["intelligence+artificielle", "blockchain", "cryptomonnaie", "rust", "solana", "ethereum", "tokio", "bridge"]
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl InspirationConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let mut config: Self = toml::from_str(content)
+            .map_err(|e| format!("Configuration d'inspiration invalide: {}", e))?;
+        if config.keywords.is_empty() {
+            config.keywords = Self::default().keywords;
+        }
+        Ok(config)
+    }
+
+    /// `inspiration.toml`, ou la configuration par défaut s'il est absent ou invalide; le
+    /// dossier local de `paths.inspiration` (aurorae.toml) a priorité
+    pub fn load_or_default() -> Self {
+        let mut config = Self::load(DEFAULT_INSPIRATION_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: inspiration par défaut", e);
+            Self::default()
+        });
+        if let Some(path) = CONFIG.read().paths.inspiration.clone() {
+            config.local_path = path;
+        }
+        config
+    }
+}
+
+// ==================== GITHUB ====================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CachedResponse {
+    etag: Option<String>,
+    body: Value,
+}
+
+/// Dépôts GitHub les plus étoilés pour un mot-clé tiré au hasard
+pub struct GitHubSource {
+    client: reqwest::Client,
+    token: Option<SecretHandle>,
+    keywords: Vec<String>,
+    cache_path: PathBuf,
+    cache: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl GitHubSource {
+    pub fn new(token: Option<SecretHandle>, keywords: Vec<String>, cache_path: impl Into<PathBuf>) -> Self {
+        let cache_path = cache_path.into();
+        let cache = File::open(&cache_path).ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self {
+            client: reqwest::Client::builder().timeout(Duration::from_secs(10)).build().unwrap_or_default(),
+            token,
+            keywords,
+            cache_path,
+            cache: Mutex::new(cache),
+        }
+    }
+
+    /// Jeton facultatif (secret `GITHUB_TOKEN`): sans lui, l'API limite à 60 requêtes par heure
+    pub fn from_config(config: &InspirationConfig) -> Self {
+        Self::new(secret("inspiration", "GITHUB_TOKEN"), config.keywords.clone(), &config.cache_path)
+    }
+
+    fn save_cache(&self) {
+        let cache = self.cache.lock();
+        if let Some(dir) = self.cache_path.parent() {
+            if create_dir_all(dir).is_err() {
+                return;
+            }
+        }
+        if let Ok(file) = File::create(&self.cache_path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &*cache);
+        }
+    }
+
+    fn cached(&self, url: &str) -> Option<Value> {
+        self.cache.lock().get(url).map(|c| c.body.clone())
+    }
+
+    /// Requête conditionnelle: un 304 ou une panne réseau servent la réponse en cache
+    async fn search(&self, url: &str) -> Result<Value, String> {
+        let etag = self.cache.lock().get(url).and_then(|c| c.etag.clone());
+        let mut request = self.client.get(url).header("User-Agent", "aurorae");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token.expose());
+        }
+        if let Some(etag) = &etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return self.cached(url).ok_or_else(|| format!("GitHub injoignable: {}", e));
+            }
+        };
+        match response.status() {
+            StatusCode::NOT_MODIFIED => self.cached(url).ok_or_else(|| "Réponse 304 sans cache".to_string()),
+            status if status.is_success() => {
+                let etag = response.headers().get(ETAG).and_then(|v| v.to_str().ok()).map(str::to_string);
+                let body: Value = response.json().await.map_err(|e| format!("Réponse GitHub invalide: {}", e))?;
+                self.cache.lock().insert(url.to_string(), CachedResponse { etag, body: body.clone() });
+                self.save_cache();
+                Ok(body)
+            }
+            // Quota épuisé ou erreur serveur: dernière réponse connue
+            status => self.cached(url).ok_or_else(|| format!("Statut HTTP {}", status)),
+        }
+    }
+}
+
+#[async_trait]
+impl InspirationSource for GitHubSource {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    async fn fetch(&self) -> Result<Vec<Inspiration>, String> {
+        let keyword = self.keywords.choose(&mut rand::thread_rng())
+            .ok_or_else(|| "Aucun mot-clé configuré".to_string())?;
+        let url = format!("{}?q={}&sort=stars&order=desc", GITHUB_SEARCH_API, keyword);
+        let body = self.search(&url).await?;
+        let items = body["items"].as_array().cloned().unwrap_or_default();
+        Ok(items
+            .iter()
+            .take(5)
+            .map(|repo| Inspiration {
+                title: format!("le projet GitHub {}", repo["name"].as_str().unwrap_or("sans nom")),
+                description: repo["description"].as_str().unwrap_or("Aucune description.").to_string(),
+                url: repo["html_url"].as_str().map(str::to_string),
+                source: self.name().to_string(),
+            })
+            .collect())
+    }
+}
+
+// ==================== DOSSIER LOCAL ====================
+
+/// Un fichier texte par inspiration; le nom du fichier en est le titre
+pub struct LocalDirectorySource {
+    pub path: PathBuf,
+}
+
+impl LocalDirectorySource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Lecture synchrone, pour les modules hors runtime asynchrone
+    pub fn read_all(&self) -> Vec<Inspiration> {
+        let Ok(entries) = std::fs::read_dir(&self.path) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = entries.filter_map(Result::ok).map(|e| e.path()).filter(|p| p.is_file()).collect();
+        paths.sort();
+        paths
+            .iter()
+            .filter_map(|path| {
+                let content = std::fs::read_to_string(path).ok()?;
+                Some(Inspiration {
+                    title: path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default(),
+                    description: content.trim().to_string(),
+                    url: None,
+                    source: "local".to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl InspirationSource for LocalDirectorySource {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    async fn fetch(&self) -> Result<Vec<Inspiration>, String> {
+        if !Path::new(&self.path).is_dir() {
+            return Err(format!("Dossier d'inspiration {} absent", self.path.display()));
+        }
+        Ok(self.read_all())
+    }
+}
+
+// ==================== CORPUS EMBARQUÉ ====================
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BundledCorpus;
+
+#[async_trait]
+impl InspirationSource for BundledCorpus {
+    fn name(&self) -> &str {
+        "bundled"
+    }
+
+    async fn fetch(&self) -> Result<Vec<Inspiration>, String> {
+        Ok(BUNDLED_CORPUS
+            .iter()
+            .map(|(title, description)| Inspiration {
+                title: title.to_string(),
+                description: description.to_string(),
+                url: None,
+                source: self.name().to_string(),
+            })
+            .collect())
+    }
+}
+
+// ==================== SOURCE CONFIGURÉE ====================
+
+/// Source configurée avec repli sur le corpus embarqué si elle échoue ou ne fournit rien
+pub struct InspirationStudio {
+    primary: Box<dyn InspirationSource>,
+    pub fallback: BundledCorpus,
+}
+
+impl Default for InspirationStudio {
+    fn default() -> Self {
+        Self::from_config(&InspirationConfig::load_or_default())
+    }
+}
+
+impl InspirationStudio {
+    pub fn new(primary: Box<dyn InspirationSource>) -> Self {
+        Self { primary, fallback: BundledCorpus }
+    }
+
+    pub fn from_config(config: &InspirationConfig) -> Self {
+        let primary: Box<dyn InspirationSource> = match config.source {
+            SourceKind::Github => Box::new(GitHubSource::from_config(config)),
+            SourceKind::Local => Box::new(LocalDirectorySource::new(&config.local_path)),
+            SourceKind::Bundled => Box::new(BundledCorpus),
+        };
+        Self::new(primary)
+    }
+
+    /// Une inspiration tirée au hasard, sous forme de texte
+    pub async fn pick(&self) -> String {
+        let inspirations = self.fetch().await.unwrap_or_default();
+        inspirations
+            .choose(&mut rand::thread_rng())
+            .map(Inspiration::text)
+            .unwrap_or_else(|| "Aucune inspiration disponible.".to_string())
+    }
+}
+
+#[async_trait]
+impl InspirationSource for InspirationStudio {
+    fn name(&self) -> &str {
+        self.primary.name()
+    }
+
+    async fn fetch(&self) -> Result<Vec<Inspiration>, String> {
+        match self.primary.fetch().await {
+            Ok(inspirations) if !inspirations.is_empty() => return Ok(inspirations),
+            Ok(_) => warn!("⚠️ Source d'inspiration {} vide, corpus embarqué", self.primary.name()),
+            Err(e) => warn!("⚠️ Source d'inspiration {} indisponible ({}), corpus embarqué", self.primary.name(), e),
+        }
+        self.fallback.fetch().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_selects_source_and_keeps_default_keywords() {
+        let config = InspirationConfig::from_toml(
+            "source = \"local\"\nlocal-path = \"idees\"\ncache-path = \"cache.json\"\n",
+        ).unwrap();
+        assert_eq!(config.source, SourceKind::Local);
+        assert_eq!(config.local_path, PathBuf::from("idees"));
+        assert!(!config.keywords.is_empty());
+        assert!(InspirationConfig::from_toml("source = \"ftp\"").is_err());
+    }
+
+    #[tokio::test]
+    async fn local_directory_and_offline_fallback() {
+        let dir = std::env::temp_dir().join(format!("aurorae-inspiration-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("essaim.txt"), "Des agents qui coopèrent\n").unwrap();
+
+        let local = LocalDirectorySource::new(&dir);
+        let found = local.fetch().await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].title, "essaim");
+        assert_eq!(found[0].description, "Des agents qui coopèrent");
+
+        let missing = InspirationStudio::new(Box::new(LocalDirectorySource::new(dir.join("absent"))));
+        let fallback = missing.fetch().await.unwrap();
+        assert_eq!(fallback.len(), BUNDLED_CORPUS.len());
+        assert!(fallback.iter().all(|i| i.source == "bundled"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn github_serves_cache_when_unreachable() {
+        let cache_path = std::env::temp_dir().join(format!("aurorae-inspiration-{}.json", uuid::Uuid::new_v4()));
+        let source = GitHubSource::new(None, vec!["rust".to_string()], &cache_path);
+        let url = "http://127.0.0.1:9/search?q=rust";
+        source.cache.lock().insert(url.to_string(), CachedResponse {
+            etag: Some("\"abc\"".to_string()),
+            body: serde_json::json!({ "items": [{ "name": "tokio" }] }),
+        });
+        let body = source.search(url).await.unwrap();
+        assert_eq!(body["items"][0]["name"], "tokio");
+        assert!(source.search("http://127.0.0.1:9/autre").await.is_err());
+    }
+}
@@ -0,0 +1,312 @@
+//! AURORAE++ - instance_messaging.rs
+//!
+//! Protocole de messages entre l'instance parente et ses clones lancés en processus:
+//! trames bincode préfixées par leur longueur, échangées sur TCP local. Le parent ouvre un
+//! `MessageHub` dont l'adresse est transmise aux enfants (`--hub`); chaque enfant s'y connecte
+//! avec un `InstanceLink`, s'annonce puis échange annonces de capacités, demandes de
+//! synchronisation de connaissances, délégations de tâches et battements de cœur.
+//! Chaque message reçu par le hub est republié sur le bus d'événements.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::event_bus::{AuroraeEvent, EventBus};
+use crate::resource_usage::spawn_tracked;
+
+/// Taille maximale d'une trame; au-delà la connexion est fermée
+pub const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// Messages échangés entre instances
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InstanceMessage {
+    /// Première trame d'un enfant: identité, parent et capacités
+    Hello { parent: Option<Uuid>, capabilities: Vec<String> },
+    /// Capacités acquises ou retirées depuis l'annonce précédente
+    CapabilityAnnouncement { capabilities: Vec<String> },
+    /// Demande des connaissances modifiées depuis `since` (RFC 3339), limitées à des sujets
+    KnowledgeSyncRequest { since: Option<String>, topics: Vec<String> },
+    /// Connaissances sérialisées en JSON par l'émetteur
+    KnowledgeSync { payload: String },
+    TaskDelegation { task_id: Uuid, description: String, payload: String },
+    TaskResult { task_id: Uuid, success: bool, output: String },
+    Heartbeat { at: String },
+    /// Message Raft (JSON) entre membres de la grappe; le hub relaie ceux destinés à un autre enfant
+    Consensus { from: Uuid, to: Uuid, payload: String },
+    /// Message de bavardage de l'essaim (JSON), relayé comme les messages Raft
+    Gossip { from: Uuid, to: Uuid, payload: String },
+}
+
+impl InstanceMessage {
+    /// Nom court, utilisé dans les événements et les journaux
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Hello { .. } => "hello",
+            Self::CapabilityAnnouncement { .. } => "capabilities",
+            Self::KnowledgeSyncRequest { .. } => "knowledge_sync_request",
+            Self::KnowledgeSync { .. } => "knowledge_sync",
+            Self::TaskDelegation { .. } => "task_delegation",
+            Self::TaskResult { .. } => "task_result",
+            Self::Heartbeat { .. } => "heartbeat",
+            Self::Consensus { .. } => "consensus",
+            Self::Gossip { .. } => "gossip",
+        }
+    }
+
+    fn summary(&self) -> String {
+        match self {
+            Self::Hello { capabilities, .. } | Self::CapabilityAnnouncement { capabilities } => capabilities.join(","),
+            Self::KnowledgeSyncRequest { since, topics } => format!("depuis {} • {}", since.as_deref().unwrap_or("l'origine"), topics.join(",")),
+            Self::KnowledgeSync { payload } => format!("{} octets", payload.len()),
+            Self::TaskDelegation { task_id, description, .. } => format!("{} {}", task_id, description),
+            Self::TaskResult { task_id, success, .. } => format!("{} {}", task_id, if *success { "réussie" } else { "échouée" }),
+            Self::Heartbeat { at } => at.clone(),
+            Self::Consensus { to, payload, .. } | Self::Gossip { to, payload, .. } => format!("vers {} • {} octets", to, payload.len()),
+        }
+    }
+}
+
+/// Trame: émetteur, destinataire (None = parent) et numéro de séquence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    pub from: Uuid,
+    pub to: Option<Uuid>,
+    pub seq: u64,
+    pub message: InstanceMessage,
+}
+
+pub async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, frame: &Frame) -> Result<(), String> {
+    let bytes = bincode::serialize(frame).map_err(|e| format!("Encodage de trame impossible: {}", e))?;
+    if bytes.len() > MAX_FRAME_SIZE {
+        return Err(format!("Trame de {} octets au-delà de la limite", bytes.len()));
+    }
+    writer.write_u32(bytes.len() as u32).await.map_err(|e| e.to_string())?;
+    writer.write_all(&bytes).await.map_err(|e| e.to_string())?;
+    writer.flush().await.map_err(|e| e.to_string())
+}
+
+/// Lit la trame suivante; `None` à la fermeture propre de la connexion
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<Frame>, String> {
+    let length = match reader.read_u32().await {
+        Ok(length) => length as usize,
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    if length > MAX_FRAME_SIZE {
+        return Err(format!("Trame annoncée de {} octets au-delà de la limite", length));
+    }
+    let mut bytes = vec![0; length];
+    reader.read_exact(&mut bytes).await.map_err(|e| e.to_string())?;
+    bincode::deserialize(&bytes).map(Some).map_err(|e| format!("Trame illisible: {}", e))
+}
+
+/// Enfant connecté au hub
+#[derive(Debug, Clone)]
+pub struct Peer {
+    pub parent: Option<Uuid>,
+    pub capabilities: Vec<String>,
+    pub last_seen: String,
+    outbox: mpsc::UnboundedSender<Frame>,
+}
+
+/// Point de rendez-vous du parent: accepte les enfants, relaie leurs messages sur le bus
+/// et dans une boîte de réception, et leur transmet les messages du parent
+pub struct MessageHub {
+    id: Uuid,
+    address: String,
+    peers: Arc<RwLock<HashMap<Uuid, Peer>>>,
+    seq: std::sync::atomic::AtomicU64,
+    _listener: JoinHandle<()>,
+}
+
+impl MessageHub {
+    /// Écoute sur `address` (ex: `127.0.0.1:0` pour un port libre); retourne le hub et sa boîte de réception
+    pub async fn bind(id: Uuid, address: &str, bus: EventBus) -> Result<(Self, mpsc::UnboundedReceiver<Frame>), String> {
+        let listener = TcpListener::bind(address).await.map_err(|e| format!("Hub d'instances sur {} impossible: {}", address, e))?;
+        let address = listener.local_addr().map_err(|e| e.to_string())?.to_string();
+        let peers: Arc<RwLock<HashMap<Uuid, Peer>>> = Arc::new(RwLock::new(HashMap::new()));
+        let (inbox, inbox_rx) = mpsc::unbounded_channel();
+
+        let accepted_peers = peers.clone();
+        let listener = spawn_tracked("instance_messaging", async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve_peer(stream, accepted_peers.clone(), inbox.clone(), bus.clone()));
+            }
+        });
+        info!("📡 Hub de messages inter-instances à l'écoute sur {}", address);
+        Ok((Self { id, address, peers, seq: std::sync::atomic::AtomicU64::new(0), _listener: listener }, inbox_rx))
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn peers(&self) -> Vec<Uuid> {
+        self.peers.read().keys().copied().collect()
+    }
+
+    pub fn capabilities_of(&self, id: &Uuid) -> Option<Vec<String>> {
+        self.peers.read().get(id).map(|peer| peer.capabilities.clone())
+    }
+
+    /// Enfants ayant annoncé une capacité (pour la délégation de tâches)
+    pub fn peers_with(&self, capability: &str) -> Vec<Uuid> {
+        self.peers.read().iter().filter(|(_, peer)| peer.capabilities.iter().any(|c| c == capability)).map(|(id, _)| *id).collect()
+    }
+
+    pub fn send(&self, to: &Uuid, message: InstanceMessage) -> Result<(), String> {
+        let seq = self.seq.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let frame = Frame { from: self.id, to: Some(*to), seq, message };
+        let peers = self.peers.read();
+        let peer = peers.get(to).ok_or_else(|| format!("Instance {} non connectée", to))?;
+        peer.outbox.send(frame).map_err(|_| format!("Connexion avec l'instance {} fermée", to))
+    }
+
+    /// Délègue une tâche à un enfant; retourne l'identifiant à rapprocher du `TaskResult`
+    pub fn delegate(&self, to: &Uuid, description: &str, payload: String) -> Result<Uuid, String> {
+        let task_id = Uuid::new_v4();
+        self.send(to, InstanceMessage::TaskDelegation { task_id, description: description.to_string(), payload })?;
+        Ok(task_id)
+    }
+}
+
+/// Connexion d'un enfant: poignée de main `Hello`, puis lecture et écriture concurrentes
+async fn serve_peer(stream: TcpStream, peers: Arc<RwLock<HashMap<Uuid, Peer>>>, inbox: mpsc::UnboundedSender<Frame>, bus: EventBus) {
+    let (mut reader, mut writer) = stream.into_split();
+    let hello = match read_frame(&mut reader).await {
+        Ok(Some(frame @ Frame { message: InstanceMessage::Hello { .. }, .. })) => frame,
+        Ok(_) => return,
+        Err(e) => {
+            warn!("⚠️ Poignée de main refusée: {}", e);
+            return;
+        }
+    };
+    let peer_id = hello.from;
+    let (outbox, mut outgoing) = mpsc::unbounded_channel::<Frame>();
+    if let InstanceMessage::Hello { parent, capabilities } = &hello.message {
+        peers.write().insert(peer_id, Peer { parent: *parent, capabilities: capabilities.clone(), last_seen: Utc::now().to_rfc3339(), outbox });
+    }
+    info!("🤝 Instance {} connectée au hub", peer_id);
+    dispatch(&hello, &inbox, &bus);
+
+    let writer_task = tokio::spawn(async move {
+        while let Some(frame) = outgoing.recv().await {
+            if write_frame(&mut writer, &frame).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        match read_frame(&mut reader).await {
+            Ok(Some(frame)) if frame.from == peer_id => {
+                if let Some(peer) = peers.write().get_mut(&peer_id) {
+                    peer.last_seen = Utc::now().to_rfc3339();
+                    if let InstanceMessage::CapabilityAnnouncement { capabilities } = &frame.message {
+                        peer.capabilities = capabilities.clone();
+                    }
+                }
+                dispatch(&frame, &inbox, &bus);
+            }
+            Ok(Some(frame)) => warn!("⚠️ Trame usurpée ({} sur la connexion de {})", frame.from, peer_id),
+            Ok(None) => break,
+            Err(e) => {
+                warn!("⚠️ Connexion de l'instance {} interrompue: {}", peer_id, e);
+                break;
+            }
+        }
+    }
+    peers.write().remove(&peer_id);
+    writer_task.abort();
+    info!("🔌 Instance {} déconnectée du hub", peer_id);
+}
+
+fn dispatch(frame: &Frame, inbox: &mpsc::UnboundedSender<Frame>, bus: &EventBus) {
+    bus.publish(AuroraeEvent::InstanceMessage {
+        from: frame.from,
+        kind: frame.message.kind().to_string(),
+        summary: frame.message.summary(),
+    });
+    let _ = inbox.send(frame.clone());
+}
+
+/// Côté enfant: connexion au hub du parent
+pub struct InstanceLink {
+    id: Uuid,
+    seq: u64,
+    reader: tokio::net::tcp::OwnedReadHalf,
+    writer: tokio::net::tcp::OwnedWriteHalf,
+}
+
+impl InstanceLink {
+    pub async fn connect(address: &str, id: Uuid, parent: Option<Uuid>, capabilities: Vec<String>) -> Result<Self, String> {
+        let stream = TcpStream::connect(address).await.map_err(|e| format!("Hub {} injoignable: {}", address, e))?;
+        let (reader, writer) = stream.into_split();
+        let mut link = Self { id, seq: 0, reader, writer };
+        link.send(InstanceMessage::Hello { parent, capabilities }).await?;
+        Ok(link)
+    }
+
+    pub async fn send(&mut self, message: InstanceMessage) -> Result<(), String> {
+        let frame = Frame { from: self.id, to: None, seq: self.seq, message };
+        self.seq += 1;
+        write_frame(&mut self.writer, &frame).await
+    }
+
+    pub async fn heartbeat(&mut self) -> Result<(), String> {
+        self.send(InstanceMessage::Heartbeat { at: Utc::now().to_rfc3339() }).await
+    }
+
+    /// Message suivant du parent; `None` si le hub a fermé la connexion
+    pub async fn recv(&mut self) -> Result<Option<InstanceMessage>, String> {
+        Ok(read_frame(&mut self.reader).await?.map(|frame| frame.message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn frames_round_trip_through_a_stream() {
+        let (mut a, mut b) = tokio::io::duplex(1024);
+        let frame = Frame { from: Uuid::new_v4(), to: None, seq: 7, message: InstanceMessage::Heartbeat { at: "now".into() } };
+        write_frame(&mut a, &frame).await.unwrap();
+        drop(a);
+        assert_eq!(read_frame(&mut b).await.unwrap(), Some(frame));
+        assert_eq!(read_frame(&mut b).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn hub_relays_child_messages_and_delegates_tasks() {
+        let bus = EventBus::new();
+        let mut events = bus.subscribe();
+        let (hub, mut inbox) = MessageHub::bind(Uuid::new_v4(), "127.0.0.1:0", bus.clone()).await.unwrap();
+        let child = Uuid::new_v4();
+        let mut link = InstanceLink::connect(hub.address(), child, None, vec!["dream".into()]).await.unwrap();
+
+        let hello = tokio::time::timeout(Duration::from_secs(2), inbox.recv()).await.unwrap().unwrap();
+        assert_eq!(hello.from, child);
+        assert_eq!(hub.peers_with("dream"), vec![child]);
+        let envelope = events.recv().await.unwrap();
+        assert!(matches!(envelope.event, AuroraeEvent::InstanceMessage { from, ref kind, .. } if from == child && kind == "hello"));
+
+        link.send(InstanceMessage::CapabilityAnnouncement { capabilities: vec!["dream".into(), "economy".into()] }).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(2), inbox.recv()).await.unwrap().unwrap();
+        assert_eq!(hub.capabilities_of(&child).unwrap().len(), 2);
+
+        let task_id = hub.delegate(&child, "analyse", "{}".into()).unwrap();
+        let received = tokio::time::timeout(Duration::from_secs(2), link.recv()).await.unwrap().unwrap();
+        assert!(matches!(received, Some(InstanceMessage::TaskDelegation { task_id: id, .. }) if id == task_id));
+    }
+}
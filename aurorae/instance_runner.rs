@@ -0,0 +1,624 @@
+//! AURORAE++ - instance_runner.rs
+//!
+//! Exécution réelle des instances créées par le ReproductionEngine: chaque clone est lancé
+//! dans un processus enfant (ou un conteneur Docker avec la feature `containers`) avec son
+//! propre répertoire d'état, la liste restreinte de ses modules et des limites de ressources.
+//! Le superviseur démarre, arrête et sonde les instances; `InstanceProbe` les expose au
+//! protocole de battements du gardien qui les redémarre lorsqu'elles ne répondent plus.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::consensus::{self, ConsensusConfig, RaftNode};
+use crate::guardian::HealthProbe;
+use crate::instance_messaging::{InstanceLink, InstanceMessage};
+use crate::knowledge::KnowledgeBase;
+use crate::reproduction::AuroraInstance;
+use crate::swarm_intelligence::{self, GossipNode, SwarmConfig};
+
+/// Racine des répertoires d'état des instances
+pub const INSTANCES_ROOT: &str = "aurorae_state/instances";
+/// Fichier que l'instance enfant réécrit à chaque battement
+pub const HEARTBEAT_FILE: &str = "heartbeat";
+/// Intervalle d'écriture du battement côté enfant
+pub const CHILD_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+/// Archives (tar.gz) des répertoires d'état des instances retirées
+pub const ARCHIVE_ROOT: &str = "aurorae_state/archive";
+/// Délai laissé à un processus pour s'arrêter après SIGTERM avant d'être tué
+pub const STOP_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Limites appliquées à une instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceLimits {
+    /// Mémoire maximale (espace d'adressage pour un processus, mémoire du conteneur sinon)
+    pub memory_mb: u64,
+    /// Cœurs alloués; un processus reçoit une priorité abaissée en conséquence
+    pub cpu_cores: f64,
+    /// Temps CPU total avant arrêt par le noyau (processus seulement)
+    pub cpu_seconds: Option<u64>,
+    /// Battement plus ancien que ce délai: instance considérée comme figée
+    pub heartbeat_timeout: Duration,
+}
+
+impl Default for InstanceLimits {
+    fn default() -> Self {
+        Self { memory_mb: 512, cpu_cores: 0.5, cpu_seconds: None, heartbeat_timeout: CHILD_HEARTBEAT_INTERVAL * 3 }
+    }
+}
+
+/// Description d'une instance à lancer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceSpec {
+    pub id: Uuid,
+    pub purpose: String,
+    pub modules: Vec<String>,
+    pub state_dir: PathBuf,
+    pub limits: InstanceLimits,
+    /// Adresse du hub de messages du parent (voir `instance_messaging`)
+    #[serde(default)]
+    pub hub: Option<String>,
+}
+
+impl InstanceSpec {
+    pub fn for_instance(instance: &AuroraInstance, root: &Path, limits: &InstanceLimits) -> Self {
+        Self {
+            id: instance.id,
+            purpose: instance.purpose.clone(),
+            modules: instance.inherited_modules.clone(),
+            state_dir: root.join(instance.id.to_string()),
+            limits: limits.clone(),
+            hub: None,
+        }
+    }
+
+    /// Arguments passés au binaire pour le mode instance (voir `--instance` dans main.rs)
+    pub fn child_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--instance".to_string(),
+            self.id.to_string(),
+            "--modules".to_string(),
+            self.modules.join(","),
+            "--state-dir".to_string(),
+            self.state_dir.to_string_lossy().to_string(),
+        ];
+        if let Some(hub) = &self.hub {
+            args.extend(["--hub".to_string(), hub.clone()]);
+        }
+        args
+    }
+}
+
+/// Cycle de vie d'une instance supervisée
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum InstanceState {
+    Running { started_at: String },
+    Stopped,
+    Exited { code: Option<i32> },
+    Failed(String),
+}
+
+/// Moteur d'exécution (processus ou conteneur)
+#[async_trait]
+pub trait InstanceRunner: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn start(&self, spec: &InstanceSpec) -> Result<(), String>;
+
+    async fn stop(&self, id: &Uuid) -> Result<(), String>;
+
+    /// État de l'exécution, sans tenir compte du battement
+    async fn state(&self, id: &Uuid) -> InstanceState;
+}
+
+/// Instances lancées comme processus enfants du binaire courant
+pub struct ProcessRunner {
+    program: PathBuf,
+    base_args: Vec<String>,
+    children: tokio::sync::Mutex<HashMap<Uuid, tokio::process::Child>>,
+}
+
+impl ProcessRunner {
+    /// Relance le binaire courant en mode instance
+    pub fn new() -> Result<Self, String> {
+        let program = std::env::current_exe().map_err(|e| format!("Binaire courant introuvable: {}", e))?;
+        Ok(Self::with_program(program, Vec::new()))
+    }
+
+    /// Programme arbitraire, les arguments de l'instance sont ajoutés après `base_args`
+    pub fn with_program(program: impl Into<PathBuf>, base_args: Vec<String>) -> Self {
+        Self { program: program.into(), base_args, children: tokio::sync::Mutex::new(HashMap::new()) }
+    }
+}
+
+#[async_trait]
+impl InstanceRunner for ProcessRunner {
+    fn name(&self) -> &str {
+        "process"
+    }
+
+    async fn start(&self, spec: &InstanceSpec) -> Result<(), String> {
+        let mut command = tokio::process::Command::new(&self.program);
+        command
+            .args(&self.base_args)
+            .args(spec.child_args())
+            .env("AURORAE_INSTANCE_ID", spec.id.to_string())
+            .env("AURORAE_STATE_DIR", &spec.state_dir)
+            .env("AURORAE_MODULES", spec.modules.join(","))
+            .current_dir(&spec.state_dir)
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true);
+
+        #[cfg(unix)]
+        {
+            let limits = spec.limits.clone();
+            // SAFETY: seuls des appels système async-signal-safe sont faits entre fork et exec
+            unsafe {
+                command.pre_exec(move || apply_process_limits(&limits));
+            }
+        }
+
+        let child = command.spawn().map_err(|e| format!("Lancement de l'instance {} impossible: {}", spec.id, e))?;
+        info!("🚀 Instance {} lancée (pid {:?}, {} Mo max)", spec.id, child.id(), spec.limits.memory_mb);
+        self.children.lock().await.insert(spec.id, child);
+        Ok(())
+    }
+
+    /// Arrêt propre: SIGTERM puis, passé `STOP_GRACE_PERIOD`, arrêt forcé
+    async fn stop(&self, id: &Uuid) -> Result<(), String> {
+        let Some(mut child) = self.children.lock().await.remove(id) else {
+            return Err(format!("Instance {} non lancée", id));
+        };
+        #[cfg(unix)]
+        if let Some(pid) = child.id() {
+            unsafe {
+                libc::kill(pid as libc::pid_t, libc::SIGTERM);
+            }
+            if tokio::time::timeout(STOP_GRACE_PERIOD, child.wait()).await.is_ok() {
+                return Ok(());
+            }
+        }
+        child.kill().await.map_err(|e| format!("Arrêt de l'instance {} impossible: {}", id, e))
+    }
+
+    async fn state(&self, id: &Uuid) -> InstanceState {
+        let mut children = self.children.lock().await;
+        let Some(child) = children.get_mut(id) else {
+            return InstanceState::Stopped;
+        };
+        match child.try_wait() {
+            Ok(None) => InstanceState::Running { started_at: String::new() },
+            Ok(Some(status)) => InstanceState::Exited { code: status.code() },
+            Err(e) => InstanceState::Failed(e.to_string()),
+        }
+    }
+}
+
+/// Limites noyau du processus enfant: espace d'adressage, temps CPU et priorité
+#[cfg(unix)]
+fn apply_process_limits(limits: &InstanceLimits) -> std::io::Result<()> {
+    let set = |resource, value: u64| {
+        let limit = libc::rlimit { rlim_cur: value as libc::rlim_t, rlim_max: value as libc::rlim_t };
+        if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    };
+    set(libc::RLIMIT_AS, limits.memory_mb * 1024 * 1024)?;
+    if let Some(seconds) = limits.cpu_seconds {
+        set(libc::RLIMIT_CPU, seconds)?;
+    }
+    // Moins d'un cœur: priorité abaissée proportionnellement (nice 0 à 19)
+    let niceness = ((1.0 - limits.cpu_cores.clamp(0.0, 1.0)) * 19.0).round() as libc::c_int;
+    unsafe {
+        libc::setpriority(libc::PRIO_PROCESS, 0, niceness);
+    }
+    Ok(())
+}
+
+/// Instances lancées dans des conteneurs Docker (quotas mémoire et CPU exacts)
+#[cfg(feature = "containers")]
+pub struct ContainerRunner {
+    docker: bollard::Docker,
+    image: String,
+}
+
+#[cfg(feature = "containers")]
+impl ContainerRunner {
+    pub fn connect(image: &str) -> Result<Self, String> {
+        let docker = bollard::Docker::connect_with_local_defaults().map_err(|e| format!("Docker indisponible: {}", e))?;
+        Ok(Self { docker, image: image.to_string() })
+    }
+
+    fn container_name(id: &Uuid) -> String {
+        format!("aurorae-instance-{}", id)
+    }
+}
+
+#[cfg(feature = "containers")]
+#[async_trait]
+impl InstanceRunner for ContainerRunner {
+    fn name(&self) -> &str {
+        "container"
+    }
+
+    async fn start(&self, spec: &InstanceSpec) -> Result<(), String> {
+        use bollard::container::{Config, CreateContainerOptions};
+        use bollard::models::HostConfig;
+
+        let config = Config {
+            image: Some(self.image.clone()),
+            cmd: Some(spec.child_args().into_iter().map(|arg| arg.replace(&*spec.state_dir.to_string_lossy(), "/state")).collect()),
+            env: Some(vec![
+                format!("AURORAE_INSTANCE_ID={}", spec.id),
+                "AURORAE_STATE_DIR=/state".to_string(),
+                format!("AURORAE_MODULES={}", spec.modules.join(",")),
+            ]),
+            host_config: Some(HostConfig {
+                memory: Some((spec.limits.memory_mb * 1024 * 1024) as i64),
+                nano_cpus: Some((spec.limits.cpu_cores * 1e9) as i64),
+                binds: Some(vec![format!("{}:/state", spec.state_dir.display())]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let name = Self::container_name(&spec.id);
+        self.docker
+            .create_container(Some(CreateContainerOptions { name: name.clone(), platform: None }), config)
+            .await
+            .map_err(|e| format!("Création du conteneur {} impossible: {}", name, e))?;
+        self.docker
+            .start_container::<String>(&name, None)
+            .await
+            .map_err(|e| format!("Démarrage du conteneur {} impossible: {}", name, e))?;
+        info!("🐳 Instance {} lancée dans le conteneur {}", spec.id, name);
+        Ok(())
+    }
+
+    async fn stop(&self, id: &Uuid) -> Result<(), String> {
+        use bollard::container::RemoveContainerOptions;
+
+        let name = Self::container_name(id);
+        self.docker
+            .remove_container(&name, Some(RemoveContainerOptions { force: true, ..Default::default() }))
+            .await
+            .map_err(|e| format!("Arrêt du conteneur {} impossible: {}", name, e))
+    }
+
+    async fn state(&self, id: &Uuid) -> InstanceState {
+        match self.docker.inspect_container(&Self::container_name(id), None).await {
+            Ok(info) => {
+                let state = info.state.unwrap_or_default();
+                if state.running.unwrap_or(false) {
+                    InstanceState::Running { started_at: state.started_at.unwrap_or_default() }
+                } else {
+                    InstanceState::Exited { code: state.exit_code.map(|code| code as i32) }
+                }
+            }
+            Err(bollard::errors::Error::DockerResponseServerError { status_code: 404, .. }) => InstanceState::Stopped,
+            Err(e) => InstanceState::Failed(e.to_string()),
+        }
+    }
+}
+
+pub type SharedSupervisor = Arc<InstanceSupervisor>;
+
+/// Démarre, arrête et surveille les instances
+pub struct InstanceSupervisor {
+    runner: Box<dyn InstanceRunner>,
+    root: PathBuf,
+    archive_root: PathBuf,
+    limits: InstanceLimits,
+    hub: RwLock<Option<String>>,
+    specs: RwLock<HashMap<Uuid, InstanceSpec>>,
+    started: RwLock<HashMap<Uuid, String>>,
+}
+
+impl std::fmt::Debug for InstanceSupervisor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InstanceSupervisor")
+            .field("runner", &self.runner.name())
+            .field("root", &self.root)
+            .field("instances", &self.specs.read().len())
+            .finish()
+    }
+}
+
+impl InstanceSupervisor {
+    pub fn new(runner: Box<dyn InstanceRunner>, root: &Path, limits: InstanceLimits) -> Self {
+        Self {
+            runner,
+            root: root.to_path_buf(),
+            archive_root: PathBuf::from(ARCHIVE_ROOT),
+            limits,
+            hub: RwLock::new(None),
+            specs: RwLock::new(HashMap::new()),
+            started: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_archive_root(mut self, archive_root: &Path) -> Self {
+        self.archive_root = archive_root.to_path_buf();
+        self
+    }
+
+    /// Les instances lancées ensuite se connectent au hub de messages du parent
+    pub fn set_hub_address(&self, address: &str) {
+        *self.hub.write() = Some(address.to_string());
+    }
+
+    /// Processus enfants du binaire courant sous `aurorae_state/instances`
+    pub fn with_processes(limits: InstanceLimits) -> Result<Self, String> {
+        Ok(Self::new(Box::new(ProcessRunner::new()?), Path::new(INSTANCES_ROOT), limits))
+    }
+
+    pub fn shared(self) -> SharedSupervisor {
+        Arc::new(self)
+    }
+
+    pub fn runner_name(&self) -> &str {
+        self.runner.name()
+    }
+
+    /// Prépare le répertoire d'état (manifeste `instance.json`) puis lance l'instance
+    pub async fn start(&self, instance: &AuroraInstance) -> Result<InstanceSpec, String> {
+        let mut spec = InstanceSpec::for_instance(instance, &self.root, &self.limits);
+        spec.hub = self.hub.read().clone();
+        fs::create_dir_all(&spec.state_dir).map_err(|e| format!("Répertoire d'état {} impossible: {}", spec.state_dir.display(), e))?;
+        // Chemin absolu: l'enfant s'exécute depuis son propre répertoire d'état
+        spec.state_dir = fs::canonicalize(&spec.state_dir).map_err(|e| e.to_string())?;
+        let manifest = serde_json::to_string_pretty(&spec).map_err(|e| e.to_string())?;
+        fs::write(spec.state_dir.join("instance.json"), manifest).map_err(|e| e.to_string())?;
+        fs::remove_file(spec.state_dir.join(HEARTBEAT_FILE)).ok();
+
+        self.runner.start(&spec).await?;
+        self.specs.write().insert(spec.id, spec.clone());
+        self.started.write().insert(spec.id, Utc::now().to_rfc3339());
+        Ok(spec)
+    }
+
+    pub async fn stop(&self, id: &Uuid) -> Result<(), String> {
+        self.runner.stop(id).await?;
+        self.started.write().remove(id);
+        info!("⏹️ Instance {} arrêtée ({})", id, self.runner.name());
+        Ok(())
+    }
+
+    /// Relance une instance avec la même spécification
+    pub async fn restart(&self, id: &Uuid) -> Result<(), String> {
+        let spec = self.specs.read().get(id).cloned().ok_or_else(|| format!("Instance {} inconnue du superviseur", id))?;
+        self.runner.stop(id).await.ok();
+        fs::remove_file(spec.state_dir.join(HEARTBEAT_FILE)).ok();
+        self.runner.start(&spec).await?;
+        self.started.write().insert(*id, Utc::now().to_rfc3339());
+        Ok(())
+    }
+
+    pub async fn state(&self, id: &Uuid) -> InstanceState {
+        match self.runner.state(id).await {
+            InstanceState::Running { .. } => {
+                InstanceState::Running { started_at: self.started.read().get(id).cloned().unwrap_or_default() }
+            }
+            other => other,
+        }
+    }
+
+    /// En bonne santé: en cours d'exécution et battement récent (délai de grâce au démarrage)
+    pub async fn health(&self, id: &Uuid) -> Result<(), String> {
+        match self.state(id).await {
+            InstanceState::Running { .. } => {}
+            InstanceState::Stopped => return Err("instance arrêtée".to_string()),
+            InstanceState::Exited { code } => return Err(format!("instance terminée (code {:?})", code)),
+            InstanceState::Failed(e) => return Err(e),
+        }
+        let spec = self.specs.read().get(id).cloned().ok_or_else(|| format!("Instance {} inconnue du superviseur", id))?;
+        let age = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok().and_then(|t| SystemTime::now().duration_since(t).ok());
+        let last_sign_of_life = age(&spec.state_dir.join(HEARTBEAT_FILE)).or_else(|| age(&spec.state_dir.join("instance.json")));
+        match last_sign_of_life {
+            Some(elapsed) if elapsed <= spec.limits.heartbeat_timeout => Ok(()),
+            Some(elapsed) => Err(format!("aucun battement depuis {:?}", elapsed)),
+            None => Err("répertoire d'état introuvable".to_string()),
+        }
+    }
+
+    /// Archive le répertoire d'état d'une instance arrêtée (`aurorae_state/archive/<id>-<date>.tar.gz`)
+    /// puis le supprime; l'instance est oubliée du superviseur
+    pub fn archive(&self, id: &Uuid) -> Result<PathBuf, String> {
+        let spec = self.specs.write().remove(id).ok_or_else(|| format!("Instance {} inconnue du superviseur", id))?;
+        fs::create_dir_all(&self.archive_root).map_err(|e| e.to_string())?;
+        let path = self.archive_root.join(format!("{}-{}.tar.gz", id, Utc::now().format("%Y%m%d%H%M%S")));
+
+        let file = fs::File::create(&path).map_err(|e| format!("Archive {} impossible: {}", path.display(), e))?;
+        let mut archive = tar::Builder::new(flate2::write::GzEncoder::new(file, flate2::Compression::default()));
+        archive.append_dir_all(id.to_string(), &spec.state_dir).map_err(|e| e.to_string())?;
+        archive.into_inner().and_then(|encoder| encoder.finish()).map_err(|e| e.to_string())?;
+        fs::remove_dir_all(&spec.state_dir).map_err(|e| e.to_string())?;
+        info!("🗄️ État de l'instance {} archivé: {}", id, path.display());
+        Ok(path)
+    }
+
+    pub fn instances(&self) -> Vec<Uuid> {
+        self.specs.read().keys().copied().collect()
+    }
+
+    /// Nom du module d'une instance dans le registre du gardien
+    pub fn module_name(id: &Uuid) -> String {
+        format!("instance:{}", id)
+    }
+
+    /// Sondes de battement de toutes les instances lancées
+    pub fn probes(self: &Arc<Self>) -> Vec<Arc<dyn HealthProbe>> {
+        self.instances().into_iter().map(|id| Arc::new(InstanceProbe::new(self.clone(), id)) as Arc<dyn HealthProbe>).collect()
+    }
+}
+
+/// Sonde du gardien pour une instance: battement lu dans son répertoire d'état, récupération par redémarrage
+pub struct InstanceProbe {
+    module: String,
+    supervisor: SharedSupervisor,
+    id: Uuid,
+}
+
+impl InstanceProbe {
+    pub fn new(supervisor: SharedSupervisor, id: Uuid) -> Self {
+        Self { module: InstanceSupervisor::module_name(&id), supervisor, id }
+    }
+}
+
+#[async_trait]
+impl HealthProbe for InstanceProbe {
+    fn module(&self) -> &str {
+        &self.module
+    }
+
+    async fn heartbeat(&self) -> Result<(), String> {
+        self.supervisor.health(&self.id).await
+    }
+
+    async fn recover(&self) -> Result<(), String> {
+        self.supervisor.restart(&self.id).await
+    }
+}
+
+/// Boucle du mode instance (côté enfant): écrit un battement dans le répertoire d'état
+/// et, si un hub est fourni, l'envoie aussi au parent et répond à ses messages, jusqu'à
+/// l'arrêt du processus
+pub async fn run_child(id: Uuid, modules: Vec<String>, state_dir: PathBuf, hub: Option<String>) -> Result<(), String> {
+    fs::create_dir_all(&state_dir).map_err(|e| e.to_string())?;
+    info!("🧫 Instance {} active • modules: {:?} • état: {}", id, modules, state_dir.display());
+    let mut link = match hub {
+        Some(address) => Some(InstanceLink::connect(&address, id, None, modules.clone()).await?),
+        None => None,
+    };
+    // Membre de la grappe Raft dès que le leader l'y ajoute; l'état suit le répertoire de l'instance
+    let mut raft = RaftNode::open(&state_dir.join("raft.json"), Some(id), ConsensusConfig::load_or_default().raft);
+    // Bavardage de l'essaim: les pairs s'apprennent par les résumés reçus via le parent
+    let mut gossip = GossipNode::new(id, SwarmConfig::load_or_default().gossip);
+    let mut ticker = tokio::time::interval(CHILD_HEARTBEAT_INTERVAL);
+    loop {
+        let message = match link.as_mut() {
+            Some(link) => tokio::select! {
+                _ = ticker.tick() => None,
+                received = link.recv() => Some(received?.ok_or("Hub du parent fermé")?),
+            },
+            None => {
+                ticker.tick().await;
+                None
+            }
+        };
+        match (message, link.as_mut()) {
+            (None, link) => {
+                raft.tick();
+                gossip.round();
+                fs::write(state_dir.join(HEARTBEAT_FILE), Utc::now().to_rfc3339())
+                    .map_err(|e| format!("Battement impossible: {}", e))?;
+                if let Some(link) = link {
+                    link.heartbeat().await?;
+                }
+            }
+            (Some(InstanceMessage::TaskDelegation { task_id, description, .. }), Some(link)) => {
+                // Pas encore d'exécuteur de tâches côté enfant: la délégation est refusée explicitement
+                let output = format!("aucun exécuteur pour « {} » (modules: {})", description, modules.join(","));
+                link.send(InstanceMessage::TaskResult { task_id, success: false, output }).await?;
+            }
+            (Some(InstanceMessage::KnowledgeSync { payload }), _) => {
+                // Savoir du parent fusionné dans la base propre à l'instance
+                let delta = serde_json::from_str(&payload).map_err(|e| format!("Delta illisible: {}", e))?;
+                let path = state_dir.join("knowledge.json");
+                let mut knowledge = KnowledgeBase::load_from(&path);
+                let report = knowledge.merge(delta);
+                knowledge.save_to(&path);
+                info!("🔁 Instance {}: {} connaissance(s) ajoutée(s), {} mise(s) à jour", id, report.added, report.updated);
+            }
+            (Some(InstanceMessage::Consensus { from, payload, .. }), _) => match consensus::open_envelope(&payload) {
+                Ok(message) => raft.step(from, message),
+                Err(e) => warn!("⚠️ Instance {}: {}", id, e),
+            },
+            (Some(InstanceMessage::Gossip { from, payload, .. }), _) => match swarm_intelligence::open_envelope(&payload) {
+                Ok(message) => {
+                    gossip.step(from, message);
+                    for item in gossip.drain_updates() {
+                        info!("🐝 Instance {}: {:?} « {} » v{} reçu de {}", id, item.kind, item.key, item.version, item.origin);
+                    }
+                }
+                Err(e) => warn!("⚠️ Instance {}: {}", id, e),
+            },
+            (Some(message), _) => info!("📨 Instance {} a reçu {}", id, message.kind()),
+        }
+        let outgoing = raft.take_messages();
+        let rumors = gossip.take_messages();
+        if let Some(link) = link.as_mut() {
+            for (to, message) in outgoing {
+                link.send(consensus::envelope(raft.id, to, &message)).await?;
+            }
+            for (to, message) in rumors {
+                link.send(swarm_intelligence::envelope(id, to, &message)).await?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(modules: &[&str]) -> AuroraInstance {
+        AuroraInstance {
+            id: Uuid::new_v4(),
+            parent_id: None,
+            created_at: Utc::now().to_rfc3339(),
+            purpose: "test".into(),
+            inherited_modules: modules.iter().map(|m| m.to_string()).collect(),
+            generation: 1,
+            is_active: true,
+        }
+    }
+
+    #[test]
+    fn spec_carries_restricted_modules_and_own_state_dir() {
+        let instance = instance(&["dream", "economy"]);
+        let spec = InstanceSpec::for_instance(&instance, Path::new("/tmp/aurorae"), &InstanceLimits::default());
+        assert_eq!(spec.state_dir, Path::new("/tmp/aurorae").join(instance.id.to_string()));
+        assert_eq!(spec.child_args()[..4], ["--instance".to_string(), instance.id.to_string(), "--modules".into(), "dream,economy".into()]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn supervised_process_reports_health_and_stops() {
+        let root = std::env::temp_dir().join(format!("aurorae-instances-{}", Uuid::new_v4()));
+        // Enfant factice: écrit son battement chaque seconde dans le répertoire d'état
+        let runner = ProcessRunner::with_program(
+            "sh",
+            vec!["-c".into(), "while true; do date > \"$AURORAE_STATE_DIR/heartbeat\"; sleep 1; done".into(), "child".into()],
+        );
+        let supervisor = InstanceSupervisor::new(Box::new(runner), &root, InstanceLimits::default())
+            .with_archive_root(&root.join("archive"))
+            .shared();
+        let instance = instance(&["dream"]);
+
+        let spec = supervisor.start(&instance).await.unwrap();
+        assert!(spec.state_dir.join("instance.json").exists());
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(matches!(supervisor.state(&instance.id).await, InstanceState::Running { .. }));
+        assert_eq!(supervisor.health(&instance.id).await, Ok(()));
+        assert_eq!(supervisor.probes().len(), 1);
+
+        supervisor.stop(&instance.id).await.unwrap();
+        assert_eq!(supervisor.state(&instance.id).await, InstanceState::Stopped);
+        assert!(supervisor.health(&instance.id).await.is_err());
+
+        let archive = supervisor.archive(&instance.id).unwrap();
+        assert!(archive.exists() && !spec.state_dir.exists());
+        assert!(supervisor.instances().is_empty());
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use tracing::info;
 
 pub struct IntelligenceCore {
     pub knowledge_graph: HashMap<String, KnowledgeNode>, // Graphe de connaissance
@@ -40,14 +41,14 @@ impl IntelligenceCore {
 
     // Initialisation du graphe cognitif (par exemple, chargement de données externes)
     pub fn initialize(&mut self) {
-        println!("[AURORAE++] 🧠 Initialisation du graphe cognitif");
+        info!("🧠 Initialisation du graphe cognitif");
         // Exemple d'initialisation dynamique à partir de données externes
         // On peut charger des données à partir de fichiers, d'API, etc.
     }
 
     // Amélioration asynchrone basée sur l'apprentissage (par exemple, apprentissage supervisé, non supervisé, etc.)
     pub async fn improve(&mut self) {
-        println!("[AURORAE++] 🧠 Amélioration cognitive en cours");
+        info!("🧠 Amélioration cognitive en cours");
         // Ici on pourrait appeler un modèle d'apprentissage automatique pour améliorer le modèle
         // Par exemple, apprentissage supervisé ou par renforcement avec des données externes
     }
@@ -0,0 +1,274 @@
+//! AURORAE++ - ipfs.rs
+//!
+//! Publication de contenus sur IPFS: nœud local (API HTTP Kubo) ou services d'épinglage
+//! (Pinata, web3.storage). Hors ligne, le contenu est conservé dans un cache local adressé
+//! par CID puis republié dès qu'un service redevient joignable.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::secrets::secret;
+
+pub const DEFAULT_IPFS_API: &str = "http://127.0.0.1:5001";
+pub const DEFAULT_GATEWAY: &str = "https://ipfs.io/ipfs";
+const PINATA_API: &str = "https://api.pinata.cloud/pinning/pinFileToIPFS";
+const WEB3_STORAGE_API: &str = "https://api.web3.storage/upload";
+const IPFS_CACHE_DIR: &str = "aurorae_state/ipfs_cache";
+
+/// Destination d'épinglage, essayées dans l'ordre de configuration
+#[derive(Debug, Clone, PartialEq)]
+pub enum PinningService {
+    /// Nœud IPFS exposant l'API HTTP (`/api/v0/add`)
+    Node { api_url: String },
+    Pinata { jwt: String },
+    Web3Storage { token: String },
+}
+
+impl PinningService {
+    pub fn name(&self) -> &str {
+        match self {
+            PinningService::Node { .. } => "ipfs-node",
+            PinningService::Pinata { .. } => "pinata",
+            PinningService::Web3Storage { .. } => "web3.storage",
+        }
+    }
+}
+
+/// Contenu publié (ou seulement mis en cache si aucun service n'a répondu)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PinnedContent {
+    pub cid: String,
+    pub size: usize,
+    /// Service ayant épinglé le contenu; `None` tant qu'il n'existe que dans le cache local
+    pub service: Option<String>,
+}
+
+impl PinnedContent {
+    pub fn uri(&self) -> String {
+        format!("ipfs://{}", self.cid)
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.service.is_some()
+    }
+}
+
+/// Contenu en attente de publication
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingUpload {
+    cid: String,
+    name: String,
+}
+
+pub struct IpfsClient {
+    client: reqwest::Client,
+    pub services: Vec<PinningService>,
+    pub cache_dir: PathBuf,
+    pub gateway: String,
+}
+
+impl Default for IpfsClient {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl IpfsClient {
+    pub fn new(services: Vec<PinningService>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(60))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+            services,
+            cache_dir: PathBuf::from(IPFS_CACHE_DIR),
+            gateway: DEFAULT_GATEWAY.to_string(),
+        }
+    }
+
+    /// Services configurés par `IPFS_API_URL` et les secrets `PINATA_JWT` et `WEB3_STORAGE_TOKEN`
+    pub fn from_env() -> Self {
+        let mut services = vec![PinningService::Node {
+            api_url: std::env::var("IPFS_API_URL").unwrap_or_else(|_| DEFAULT_IPFS_API.to_string()),
+        }];
+        if let Some(jwt) = secret("ipfs", "PINATA_JWT") {
+            services.push(PinningService::Pinata { jwt: jwt.expose().to_string() });
+        }
+        if let Some(token) = secret("ipfs", "WEB3_STORAGE_TOKEN") {
+            services.push(PinningService::Web3Storage { token: token.expose().to_string() });
+        }
+        Self::new(services)
+    }
+
+    pub fn gateway_url(&self, cid: &str) -> String {
+        format!("{}/{}", self.gateway.trim_end_matches('/'), cid)
+    }
+
+    /// Publie `content` sur le premier service disponible; le contenu est toujours mis en
+    /// cache localement et mis en file d'attente si aucun service ne répond
+    pub async fn add(&self, name: &str, content: Vec<u8>) -> Result<PinnedContent, String> {
+        let local_cid = cid_v1_raw(&content);
+        self.cache(&local_cid, &content)?;
+
+        for service in &self.services {
+            match self.upload(service, name, content.clone()).await {
+                Ok(cid) => {
+                    if cid != local_cid {
+                        // Contenu découpé en plusieurs blocs: le CID du service fait foi
+                        self.cache(&cid, &content)?;
+                    }
+                    info!("📌 {} épinglé sur {}: {}", name, service.name(), cid);
+                    return Ok(PinnedContent { cid, size: content.len(), service: Some(service.name().to_string()) });
+                }
+                Err(e) => warn!("⚠️ Épinglage {} sur {} impossible: {}", name, service.name(), e),
+            }
+        }
+
+        self.enqueue(PendingUpload { cid: local_cid.clone(), name: name.to_string() });
+        info!("💾 {} conservé dans le cache IPFS local ({}), publication différée", name, local_cid);
+        Ok(PinnedContent { cid: local_cid, size: content.len(), service: None })
+    }
+
+    /// Contenu présent dans le cache local
+    pub fn cached(&self, cid: &str) -> Option<Vec<u8>> {
+        fs::read(self.cache_dir.join(cid)).ok()
+    }
+
+    /// Republie les contenus mis en cache hors ligne; retourne le nombre de publications réussies
+    pub async fn sync_pending(&self) -> usize {
+        let pending = self.pending();
+        let mut remaining = Vec::new();
+        let mut published = 0;
+        for upload in pending {
+            let Some(content) = self.cached(&upload.cid) else { continue };
+            let mut done = false;
+            for service in &self.services {
+                if self.upload(service, &upload.name, content.clone()).await.is_ok() {
+                    done = true;
+                    break;
+                }
+            }
+            if done {
+                published += 1;
+            } else {
+                remaining.push(upload);
+            }
+        }
+        self.write_pending(&remaining);
+        if published > 0 {
+            info!("📌 {} contenus IPFS publiés depuis le cache local", published);
+        }
+        published
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending().len()
+    }
+
+    async fn upload(&self, service: &PinningService, name: &str, content: Vec<u8>) -> Result<String, String> {
+        let form = Form::new().part("file", Part::bytes(content).file_name(name.to_string()));
+        let request = match service {
+            PinningService::Node { api_url } => self.client
+                .post(format!("{}/api/v0/add", api_url.trim_end_matches('/')))
+                .query(&[("pin", "true"), ("cid-version", "1"), ("raw-leaves", "true")])
+                .multipart(form),
+            PinningService::Pinata { jwt } => self.client
+                .post(PINATA_API)
+                .bearer_auth(jwt)
+                .multipart(form.text("pinataOptions", r#"{"cidVersion":1}"#)),
+            PinningService::Web3Storage { token } => self.client
+                .post(WEB3_STORAGE_API)
+                .bearer_auth(token)
+                .multipart(form),
+        };
+
+        let response = request.send().await.map_err(|e| format!("Service injoignable: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Statut HTTP {}", response.status()));
+        }
+        let body: Value = response.json().await.map_err(|e| format!("Réponse invalide: {}", e))?;
+        let field = match service {
+            PinningService::Node { .. } => "Hash",
+            PinningService::Pinata { .. } => "IpfsHash",
+            PinningService::Web3Storage { .. } => "cid",
+        };
+        body.get(field)
+            .and_then(|cid| cid.as_str())
+            .map(|cid| cid.to_string())
+            .ok_or_else(|| format!("CID absent de la réponse: {}", body))
+    }
+
+    fn cache(&self, cid: &str, content: &[u8]) -> Result<(), String> {
+        fs::create_dir_all(&self.cache_dir)
+            .map_err(|e| format!("Erreur création du cache IPFS: {}", e))?;
+        fs::write(self.cache_dir.join(cid), content)
+            .map_err(|e| format!("Erreur écriture du cache IPFS: {}", e))
+    }
+
+    fn pending(&self) -> Vec<PendingUpload> {
+        fs::read_to_string(self.cache_dir.join("pending.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn enqueue(&self, upload: PendingUpload) {
+        let mut pending = self.pending();
+        if !pending.iter().any(|p| p.cid == upload.cid) {
+            pending.push(upload);
+            self.write_pending(&pending);
+        }
+    }
+
+    fn write_pending(&self, pending: &[PendingUpload]) {
+        if let Ok(content) = serde_json::to_string_pretty(pending) {
+            let _ = fs::write(self.cache_dir.join("pending.json"), content);
+        }
+    }
+}
+
+/// CIDv1 (codec `raw`, sha2-256, base32) d'un contenu; identique au CID retourné par
+/// `ipfs add --cid-version 1 --raw-leaves` pour un contenu tenant dans un seul bloc
+pub fn cid_v1_raw(content: &[u8]) -> String {
+    // version 1, codec raw (0x55), multihash sha2-256 (0x12) sur 32 octets
+    let mut bytes = vec![0x01, 0x55, 0x12, 0x20];
+    bytes.extend_from_slice(&Sha256::digest(content));
+    format!("b{}", base32_lower(&bytes))
+}
+
+/// Base32 RFC 4648 en minuscules, sans remplissage (multibase `b`)
+fn base32_lower(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut output = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_raw_cid_like_ipfs_add() {
+        assert_eq!(cid_v1_raw(b"hello world"), "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e");
+    }
+}
@@ -0,0 +1,544 @@
+//! AURORAE++ - keystore.rs
+//!
+//! Gestion des clés: aucune clé privée ne doit apparaître dans le code. Les clés
+//! proviennent d'un keystore JSON chiffré, d'une variable d'environnement ou du
+//! trousseau du système, et sont exposées aux autres modules via le trait `Signer`.
+//! Les opérations sensibles peuvent exiger un signataire externe (Ledger, signataire
+//! distant JSON-RPC) selon la `SigningPolicy`.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use async_trait::async_trait;
+use ethers::signers::{LocalWallet, Signer as EthersSigner};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::transaction::eip712::{Eip712, TypedData};
+use ethers::types::{Address, Signature};
+use serde_json::{json, Value};
+
+/// Source de clé utilisée par défaut si `AURORAE_KEY_SOURCE` n'est pas défini
+pub const DEFAULT_KEY_ENV: &str = "AURORAE_PRIVATE_KEY";
+/// Variable contenant le mot de passe des keystores chiffrés
+pub const KEYSTORE_PASSWORD_ENV: &str = "AURORAE_KEYSTORE_PASSWORD";
+
+/// Signataire abstrait: les modules ne manipulent jamais la clé elle-même
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Libellé lisible de la source (jamais la clé)
+    fn label(&self) -> &str;
+
+    fn address(&self) -> Address;
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, String>;
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, String>;
+
+    /// Signature EIP-712 (`eth_signTypedData_v4`)
+    async fn sign_typed_data(&self, data: &TypedData) -> Result<Signature, String>;
+
+    /// Vrai si la clé est hors du processus (hardware wallet, signataire distant)
+    fn is_external(&self) -> bool {
+        false
+    }
+}
+
+pub type SharedSigner = Arc<dyn Signer>;
+
+/// Signataire adossé à un portefeuille local déchiffré en mémoire
+pub struct WalletSigner {
+    label: String,
+    wallet: LocalWallet,
+}
+
+impl WalletSigner {
+    pub fn new(label: &str, wallet: LocalWallet) -> Self {
+        Self { label: label.to_string(), wallet }
+    }
+
+    /// Portefeuille éphémère (simulateur, tests)
+    pub fn random(label: &str) -> Self {
+        Self::new(label, LocalWallet::new(&mut rand::thread_rng()))
+    }
+}
+
+#[async_trait]
+impl Signer for WalletSigner {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn address(&self) -> Address {
+        self.wallet.address()
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, String> {
+        self.wallet.sign_message(message)
+            .await
+            .map_err(|e| format!("Erreur de signature: {}", e))
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, String> {
+        self.wallet.sign_transaction(tx)
+            .await
+            .map_err(|e| format!("Erreur de signature: {}", e))
+    }
+
+    async fn sign_typed_data(&self, data: &TypedData) -> Result<Signature, String> {
+        self.wallet.sign_typed_data(data)
+            .await
+            .map_err(|e| format!("Erreur de signature: {}", e))
+    }
+}
+
+// ==================== SIGNATAIRES EXTERNES ====================
+
+/// Signataire distant via JSON-RPC (clef, web3signer…): `eth_sign` et `eth_signTransaction`
+pub struct RemoteSigner {
+    label: String,
+    endpoint: String,
+    address: Address,
+    client: reqwest::Client,
+}
+
+impl RemoteSigner {
+    pub fn new(endpoint: &str, address: Address) -> Self {
+        Self {
+            label: format!("remote:{}", endpoint),
+            endpoint: endpoint.to_string(),
+            address,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let response: Value = self.client
+            .post(&self.endpoint)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await
+            .map_err(|e| format!("Signataire distant injoignable: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Réponse du signataire invalide: {}", e))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(format!("Signature refusée par {}: {}", self.endpoint, error));
+        }
+        response.get("result").cloned().ok_or_else(|| "Réponse du signataire sans résultat".to_string())
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, String> {
+        let result = self.call("eth_sign", json!([self.address, format!("0x{}", hex::encode(message))])).await?;
+        result.as_str()
+            .ok_or_else(|| "Signature absente".to_string())?
+            .parse::<Signature>()
+            .map_err(|e| format!("Signature invalide: {}", e))
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, String> {
+        let mut request = serde_json::to_value(tx).map_err(|e| format!("Transaction non sérialisable: {}", e))?;
+        request["from"] = json!(self.address);
+        let result = self.call("eth_signTransaction", json!([request])).await?;
+
+        // clef renvoie `{ raw, tx }`, d'autres signataires directement la transaction brute
+        let raw = result.get("raw").and_then(|r| r.as_str()).or_else(|| result.as_str())
+            .ok_or_else(|| "Transaction signée absente".to_string())?;
+        let bytes = hex::decode(raw.trim_start_matches("0x"))
+            .map_err(|e| format!("Transaction signée illisible: {}", e))?;
+        let (_, signature) = TypedTransaction::decode_signed(&ethers::utils::rlp::Rlp::new(&bytes))
+            .map_err(|e| format!("Transaction signée illisible: {}", e))?;
+        Ok(signature)
+    }
+
+    async fn sign_typed_data(&self, data: &TypedData) -> Result<Signature, String> {
+        let result = self.call("eth_signTypedData_v4", json!([self.address, data])).await?;
+        result.as_str()
+            .ok_or_else(|| "Signature absente".to_string())?
+            .parse::<Signature>()
+            .map_err(|e| format!("Signature invalide: {}", e))
+    }
+
+    fn is_external(&self) -> bool {
+        true
+    }
+}
+
+/// Ledger connecté en USB (HID); chaque signature est confirmée sur l'appareil
+#[cfg(feature = "hardware-wallet")]
+pub struct LedgerSigner {
+    label: String,
+    ledger: ethers::signers::Ledger,
+}
+
+#[cfg(feature = "hardware-wallet")]
+impl LedgerSigner {
+    /// Ouvre le compte `index` (chemin Ledger Live) pour la chaîne `chain_id`
+    pub async fn connect(index: usize, chain_id: u64) -> Result<Self, String> {
+        let ledger = ethers::signers::Ledger::new(ethers::signers::HDPath::LedgerLive(index), chain_id)
+            .await
+            .map_err(|e| format!("Ledger indisponible: {}", e))?;
+        info!("🔐 Ledger connecté: {}", ledger.address());
+        Ok(Self { label: format!("ledger:{}", index), ledger })
+    }
+}
+
+#[cfg(feature = "hardware-wallet")]
+#[async_trait]
+impl Signer for LedgerSigner {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn address(&self) -> Address {
+        self.ledger.address()
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, String> {
+        self.ledger.sign_message(message)
+            .await
+            .map_err(|e| format!("Signature Ledger refusée: {}", e))
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, String> {
+        self.ledger.sign_transaction(tx)
+            .await
+            .map_err(|e| format!("Signature Ledger refusée: {}", e))
+    }
+
+    async fn sign_typed_data(&self, data: &TypedData) -> Result<Signature, String> {
+        self.ledger.sign_typed_data(data)
+            .await
+            .map_err(|e| format!("Signature Ledger refusée: {}", e))
+    }
+
+    fn is_external(&self) -> bool {
+        true
+    }
+}
+
+// ==================== POLITIQUE DE SIGNATURE ====================
+
+/// Règles imposant un signataire externe pour les opérations à forte valeur
+#[derive(Debug, Clone)]
+pub struct SigningPolicy {
+    /// Montant (unités natives) au-delà duquel un transfert exige un signataire externe
+    pub external_above: f64,
+    /// Les déploiements hors testnet exigent un signataire externe
+    pub mainnet_requires_external: bool,
+}
+
+impl Default for SigningPolicy {
+    fn default() -> Self {
+        Self { external_above: 1.0, mainnet_requires_external: true }
+    }
+}
+
+impl SigningPolicy {
+    /// Politique adaptée au signataire configuré: sans signataire externe, les transferts
+    /// sont signés localement (les gros montants restent soumis aux approbations N sur M),
+    /// seuls les déploiements mainnet continuent d'exiger un signataire externe
+    pub fn for_signer(signer: &dyn Signer) -> Self {
+        if signer.is_external() {
+            return Self::default();
+        }
+        warn!("⚠️ Aucun signataire externe ({}): transferts signés localement", signer.label());
+        Self { external_above: f64::INFINITY, ..Self::default() }
+    }
+
+    pub fn check_transfer(&self, signer: &dyn Signer, amount: f64) -> Result<(), String> {
+        if amount > self.external_above && !signer.is_external() {
+            return Err(format!(
+                "Transfert de {:.4} au-delà de {:.4}: signataire externe requis ({} est local)",
+                amount, self.external_above, signer.label()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_deployment(&self, signer: &dyn Signer, testnet: bool) -> Result<(), String> {
+        if !testnet && self.mainnet_requires_external && !signer.is_external() {
+            return Err(format!("Déploiement mainnet: signataire externe requis ({} est local)", signer.label()));
+        }
+        Ok(())
+    }
+}
+
+/// Erreur de signature exposée à ethers
+#[derive(Debug)]
+pub struct SignerError(pub String);
+
+impl std::fmt::Display for SignerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SignerError {}
+
+/// Adapte un `Signer` AURORAE au trait ethers, pour `SignerMiddleware`
+#[derive(Clone)]
+pub struct EthersSignerAdapter {
+    inner: SharedSigner,
+    chain_id: u64,
+}
+
+impl EthersSignerAdapter {
+    pub fn new(inner: SharedSigner, chain_id: u64) -> Self {
+        Self { inner, chain_id }
+    }
+}
+
+impl std::fmt::Debug for EthersSignerAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EthersSignerAdapter({})", self.inner.label())
+    }
+}
+
+#[async_trait]
+impl EthersSigner for EthersSignerAdapter {
+    type Error = SignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(&self, message: S) -> Result<Signature, Self::Error> {
+        self.inner.sign_message(message.as_ref()).await.map_err(SignerError)
+    }
+
+    async fn sign_transaction(&self, message: &TypedTransaction) -> Result<Signature, Self::Error> {
+        let mut tx = message.clone();
+        if tx.chain_id().is_none() {
+            tx.set_chain_id(self.chain_id);
+        }
+        self.inner.sign_transaction(&tx).await.map_err(SignerError)
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(&self, _payload: &T) -> Result<Signature, Self::Error> {
+        Err(SignerError("Signature EIP-712 non prise en charge par ce signataire".to_string()))
+    }
+
+    fn address(&self) -> Address {
+        self.inner.address()
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+/// Provenance d'une clé
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeySource {
+    /// Keystore JSON chiffré (format Web3 Secret Storage), mot de passe dans l'environnement
+    EncryptedJson { path: String, password_env: String },
+    /// Clé hexadécimale dans une variable d'environnement
+    Env { var: String },
+    /// Trousseau du système d'exploitation (secret-tool sous Linux, security sous macOS)
+    OsKeyring { service: String, account: String },
+    /// Signataire distant JSON-RPC pour le compte `address`
+    Remote { endpoint: String, address: Address },
+    /// Ledger USB, compte `index` du chemin Ledger Live
+    Ledger { index: usize },
+}
+
+impl KeySource {
+    /// Lit `AURORAE_KEY_SOURCE`: `keystore:<chemin>`, `env:<VAR>`, `keyring:<service>/<compte>`,
+    /// `remote:<adresse>@<url>` ou `ledger:<index>`
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("AURORAE_KEY_SOURCE") {
+            Ok(spec) => Self::parse(&spec),
+            Err(_) => Ok(KeySource::Env { var: DEFAULT_KEY_ENV.to_string() }),
+        }
+    }
+
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (scheme, rest) = spec.split_once(':')
+            .ok_or_else(|| format!("Source de clé invalide: {}", spec))?;
+        match scheme {
+            "keystore" => Ok(KeySource::EncryptedJson {
+                path: rest.to_string(),
+                password_env: KEYSTORE_PASSWORD_ENV.to_string(),
+            }),
+            "env" => Ok(KeySource::Env { var: rest.to_string() }),
+            "keyring" => {
+                let (service, account) = rest.split_once('/')
+                    .ok_or_else(|| "Format attendu: keyring:<service>/<compte>".to_string())?;
+                Ok(KeySource::OsKeyring { service: service.to_string(), account: account.to_string() })
+            }
+            "remote" => {
+                let (address, endpoint) = rest.split_once('@')
+                    .ok_or_else(|| "Format attendu: remote:<adresse>@<url>".to_string())?;
+                let address = address.parse::<Address>()
+                    .map_err(|e| format!("Adresse du signataire distant invalide: {}", e))?;
+                Ok(KeySource::Remote { endpoint: endpoint.to_string(), address })
+            }
+            "ledger" => {
+                let index = rest.parse::<usize>()
+                    .map_err(|e| format!("Index Ledger invalide: {}", e))?;
+                Ok(KeySource::Ledger { index })
+            }
+            other => Err(format!("Schéma de source de clé inconnu: {}", other)),
+        }
+    }
+}
+
+pub struct Keystore;
+
+impl Keystore {
+    /// Charge un signataire depuis sa source, y compris les signataires externes
+    pub async fn connect(source: &KeySource, chain_id: u64) -> Result<SharedSigner, String> {
+        match source {
+            KeySource::Remote { endpoint, address } => {
+                info!("🔑 Signataire distant {} via {}", address, endpoint);
+                Ok(Arc::new(RemoteSigner::new(endpoint, *address)))
+            }
+            #[cfg(feature = "hardware-wallet")]
+            KeySource::Ledger { index } => Ok(Arc::new(LedgerSigner::connect(*index, chain_id).await?)),
+            #[cfg(not(feature = "hardware-wallet"))]
+            KeySource::Ledger { .. } => {
+                let _ = chain_id;
+                Err("Support Ledger absent: compiler avec la feature hardware-wallet".to_string())
+            }
+            local => Self::load(local),
+        }
+    }
+
+    /// Charge un signataire local depuis sa source; le secret n'est jamais journalisé
+    pub fn load(source: &KeySource) -> Result<SharedSigner, String> {
+        let (label, wallet) = match source {
+            KeySource::EncryptedJson { path, password_env } => {
+                let password = std::env::var(password_env)
+                    .map_err(|_| format!("Mot de passe du keystore absent ({})", password_env))?;
+                let wallet = LocalWallet::decrypt_keystore(path, password)
+                    .map_err(|e| format!("Erreur déchiffrement du keystore {}: {}", path, e))?;
+                (format!("keystore:{}", path), wallet)
+            }
+            KeySource::Env { var } => {
+                let key = std::env::var(var)
+                    .map_err(|_| format!("Variable de clé absente: {}", var))?;
+                (format!("env:{}", var), Self::parse_key(&key)?)
+            }
+            KeySource::OsKeyring { service, account } => {
+                let key = Self::read_os_keyring(service, account)?;
+                (format!("keyring:{}/{}", service, account), Self::parse_key(&key)?)
+            }
+            KeySource::Remote { .. } | KeySource::Ledger { .. } => {
+                return Err("Signataire externe: utiliser Keystore::connect".to_string());
+            }
+        };
+
+        info!("🔑 Signataire {} chargé depuis {}", wallet.address(), label);
+        Ok(Arc::new(WalletSigner::new(&label, wallet)))
+    }
+
+    /// Génère une nouvelle clé et l'enregistre chiffrée dans `dir`; retourne le chemin du fichier
+    pub fn create_encrypted(dir: &str, password: &str) -> Result<(SharedSigner, String), String> {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Erreur création {}: {}", dir, e))?;
+        let (wallet, file_name) = LocalWallet::new_keystore(Path::new(dir), &mut rand::thread_rng(), password, None)
+            .map_err(|e| format!("Erreur création du keystore: {}", e))?;
+        let path = Path::new(dir).join(file_name).to_string_lossy().to_string();
+
+        info!("🔐 Keystore chiffré créé pour {}", wallet.address());
+        Ok((Arc::new(WalletSigner::new(&format!("keystore:{}", path), wallet)), path))
+    }
+
+    fn parse_key(key: &str) -> Result<LocalWallet, String> {
+        key.trim()
+            .trim_start_matches("0x")
+            .parse::<LocalWallet>()
+            .map_err(|e| format!("Clé privée invalide: {}", e))
+    }
+
+    pub(crate) fn read_os_keyring(service: &str, account: &str) -> Result<String, String> {
+        let output = if cfg!(target_os = "macos") {
+            Command::new("security")
+                .args(["find-generic-password", "-s", service, "-a", account, "-w"])
+                .output()
+        } else {
+            Command::new("secret-tool")
+                .args(["lookup", "service", service, "account", account])
+                .output()
+        }
+        .map_err(|e| format!("Trousseau système indisponible: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!("Aucune clé dans le trousseau pour {}/{}", service, account));
+        }
+        String::from_utf8(output.stdout)
+            .map(|s| s.trim().to_string())
+            .map_err(|e| format!("Clé du trousseau illisible: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_key_source_specs() {
+        assert_eq!(
+            KeySource::parse("env:MY_KEY").unwrap(),
+            KeySource::Env { var: "MY_KEY".to_string() }
+        );
+        assert_eq!(
+            KeySource::parse("keyring:aurorae/deployer").unwrap(),
+            KeySource::OsKeyring { service: "aurorae".to_string(), account: "deployer".to_string() }
+        );
+        assert!(KeySource::parse("plaintext").is_err());
+    }
+
+    #[test]
+    fn policy_requires_external_signer_for_high_value() {
+        let policy = SigningPolicy::default();
+        let local = WalletSigner::random("test");
+        assert!(policy.check_transfer(&local, 0.5).is_ok());
+        assert!(policy.check_transfer(&local, 10.0).is_err());
+        assert!(policy.check_deployment(&local, true).is_ok());
+        assert!(policy.check_deployment(&local, false).is_err());
+
+        let remote = RemoteSigner::new("http://localhost:8550", local.address());
+        assert!(policy.check_transfer(&remote, 10.0).is_ok());
+    }
+
+    #[test]
+    fn local_only_setup_signs_transfers_but_not_mainnet_deployments() {
+        let local = WalletSigner::random("test");
+        let policy = SigningPolicy::for_signer(&local);
+        assert!(policy.check_transfer(&local, 10.0).is_ok());
+        assert!(policy.check_deployment(&local, false).is_err());
+
+        let remote = RemoteSigner::new("http://localhost:8550", local.address());
+        let policy = SigningPolicy::for_signer(&remote);
+        assert_eq!(policy.external_above, SigningPolicy::default().external_above);
+    }
+
+    #[test]
+    fn encrypted_keystore_round_trip() {
+        let dir = std::env::temp_dir().join(format!("aurorae-keystore-{}", uuid::Uuid::new_v4()));
+        let dir = dir.to_string_lossy().to_string();
+        let (signer, path) = Keystore::create_encrypted(&dir, "secret").unwrap();
+
+        std::env::set_var("AURORAE_TEST_KEYSTORE_PASSWORD", "secret");
+        let loaded = Keystore::load(&KeySource::EncryptedJson {
+            path,
+            password_env: "AURORAE_TEST_KEYSTORE_PASSWORD".to_string(),
+        }).unwrap();
+        assert_eq!(loaded.address(), signer.address());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
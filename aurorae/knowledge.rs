@@ -2,14 +2,26 @@
 //!
 //! Base de savoir vivante. Stocke les patterns et insights extraits par le module `learning`
 //! pour les rendre accessibles au `generator` et autres composants évolutifs.
+//! Chaque pattern et méta-insight garde sa provenance (instance d'origine, date de mise à
+//! jour) afin que les instances puissent s'échanger des deltas (`export_delta` / `merge`).
+//! Les crates repérées par l'explorateur (crates.io, docs.rs) y sont conservées avec leur score.
+//! Patterns, méta-insights et crates sont interrogeables par similarité sémantique
+//! (`search`), avec le modèle de plongement configuré.
 
 use std::collections::{HashMap, HashSet};
 use std::fs::{File, create_dir_all};
 use std::io::{Write, Read};
-use std::path::PathBuf;
+use std::path::Path;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use tracing::info;
 
-const DB_PATH: &str = "C:\\Users\\admin\\.github_feed\\aurorae_knowledge.json";
+use crate::config::CONFIG;
+use crate::embeddings::{IndexEntry, ItemKind, LocalEmbedder, SemanticIndex, SimilarItem};
+use crate::license::LicensePolicy;
+use crate::openai::EmbeddingProvider;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Pattern {
@@ -20,9 +32,105 @@ pub struct Pattern {
     pub enums: usize,
 }
 
+/// Origine des connaissances produites par l'instance elle-même
+pub const LOCAL_ORIGIN: &str = "local";
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct KnowledgeBase {
     pub records: Vec<Pattern>,
+    #[serde(default)]
+    pub meta_insights: Vec<MetaInsight>,
+    /// Provenance de chaque pattern, par nom de module
+    #[serde(default)]
+    pub provenance: HashMap<String, Provenance>,
+    /// Crates de l'écosystème repérées par l'explorateur, classées par score
+    #[serde(default)]
+    pub crates: Vec<CrateRecord>,
+    /// Modèle de plongement de `search` (modèle local par défaut)
+    #[serde(skip)]
+    embedder: Option<Arc<dyn EmbeddingProvider>>,
+    /// Index reconstruit à la demande, non persisté
+    #[serde(skip)]
+    index: SemanticIndex,
+    /// Licences admises: un pattern issu d'une source refusée (GPL…) n'est pas ingéré
+    #[serde(skip)]
+    license_policy: LicensePolicy,
+}
+
+/// Instance d'origine et date de dernière mise à jour d'une connaissance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub origin: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Provenance {
+    pub fn local() -> Self {
+        Self { origin: LOCAL_ORIGIN.to_string(), updated_at: Utc::now() }
+    }
+}
+
+/// Conclusion du méta-apprentissage, identifiée par son sujet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaInsight {
+    pub topic: String,
+    pub content: String,
+    pub confidence: f32,
+    pub provenance: Provenance,
+}
+
+/// Crate candidate issue de crates.io / docs.rs, avec ses métadonnées et son score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrateRecord {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub downloads: u64,
+    pub recent_downloads: u64,
+    /// Date de la dernière publication
+    pub updated_at: DateTime<Utc>,
+    pub dependencies: Vec<String>,
+    pub repository: Option<String>,
+    /// Documentation docs.rs, si elle a été générée
+    pub documentation: Option<String>,
+    /// Début du README, texte brut
+    pub readme: Option<String>,
+    pub score: f32,
+    pub provenance: Provenance,
+}
+
+/// Connaissances modifiées depuis une date, prêtes à être envoyées à une autre instance
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KnowledgeDelta {
+    pub origin: String,
+    pub since: Option<DateTime<Utc>>,
+    pub patterns: Vec<(Pattern, Provenance)>,
+    pub insights: Vec<MetaInsight>,
+}
+
+impl KnowledgeDelta {
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty() && self.insights.is_empty()
+    }
+
+    /// Ne garde que les connaissances liées aux sujets (spécialisations) donnés
+    pub fn restricted_to(mut self, topics: &[String]) -> Self {
+        let relevant = |name: &str| {
+            let name = name.to_lowercase();
+            topics.iter().any(|topic| name.contains(&topic.to_lowercase()))
+        };
+        self.patterns.retain(|(pattern, _)| relevant(&pattern.module_name));
+        self.insights.retain(|insight| relevant(&insight.topic));
+        self
+    }
+}
+
+/// Bilan d'une fusion: entrées ajoutées, remplacées (plus récentes) ou ignorées (plus anciennes)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    pub added: usize,
+    pub updated: usize,
+    pub ignored: usize,
 }
 
 #[derive(Default, Serialize, Deserialize, Clone)]
@@ -41,11 +149,15 @@ pub struct PatternInsight {
 }
 
 impl KnowledgeBase {
-    // Charge la base de données à partir du fichier JSON
+    // Charge la base de données à partir du fichier JSON (`paths.knowledge-db`)
     pub fn load() -> Self {
-        let path = PathBuf::from(DB_PATH);
+        Self::load_from(&CONFIG.read().paths.knowledge_db.clone())
+    }
+
+    // Charge une base de données depuis un fichier précis (ex: répertoire d'état d'une instance)
+    pub fn load_from(path: &Path) -> Self {
         if path.exists() {
-            let mut file = File::open(&path).unwrap();
+            let mut file = File::open(path).unwrap();
             let mut content = String::new();
             file.read_to_string(&mut content).unwrap();
             serde_json::from_str(&content).unwrap_or_default()
@@ -56,21 +168,154 @@ impl KnowledgeBase {
 
     // Sauvegarde la base de données dans le fichier JSON
     pub fn save(&self) {
-        let path = PathBuf::from(DB_PATH);
+        self.save_to(&CONFIG.read().paths.knowledge_db.clone());
+    }
+
+    pub fn save_to(&self, path: &Path) {
         if let Some(parent) = path.parent() {
             let _ = create_dir_all(parent);
         }
-        let mut file = File::create(&path).unwrap();
+        let mut file = File::create(path).unwrap();
         let json = serde_json::to_string_pretty(self).unwrap();
         file.write_all(json.as_bytes()).unwrap();
     }
 
-    // Insère un nouveau pattern dans la base de données
+    // Remplace la politique de licences (`licenses.toml`) appliquée à l'ingestion
+    pub fn set_license_policy(&mut self, policy: LicensePolicy) {
+        self.license_policy = policy;
+    }
+
+    pub fn license_policy(&self) -> &LicensePolicy {
+        &self.license_policy
+    }
+
+    // Vrai si le fichier source du pattern a une licence admise
+    pub fn admits(&self, pattern: &Pattern) -> bool {
+        self.license_policy.admits_path(Path::new(&pattern.module_name))
+    }
+
+    // Insère un nouveau pattern dans la base de données, sauf si sa licence est refusée
     pub fn insert_pattern(&mut self, pattern: Pattern) {
+        if !self.admits(&pattern) {
+            info!("⚖️ Pattern {} ignoré: licence refusée", pattern.module_name);
+            return;
+        }
+        self.provenance.insert(pattern.module_name.clone(), Provenance::local());
         self.records.push(pattern);
         self.save();
     }
 
+    // Remplace le pattern d'un module déjà connu, ou l'ajoute (sans sauvegarde); un pattern
+    // dont la licence est refusée est oublié. Vrai si le pattern a été retenu
+    pub fn upsert_pattern(&mut self, pattern: Pattern) -> bool {
+        if !self.admits(&pattern) {
+            info!("⚖️ Pattern {} ignoré: licence refusée", pattern.module_name);
+            self.remove_pattern(&pattern.module_name);
+            return false;
+        }
+        self.provenance.insert(pattern.module_name.clone(), Provenance::local());
+        match self.records.iter_mut().find(|p| p.module_name == pattern.module_name) {
+            Some(existing) => *existing = pattern,
+            None => self.records.push(pattern),
+        }
+        true
+    }
+
+    // Oublie le pattern d'un module (fichier supprimé); vrai s'il était connu
+    pub fn remove_pattern(&mut self, module_name: &str) -> bool {
+        self.provenance.remove(module_name);
+        let before = self.records.len();
+        self.records.retain(|p| p.module_name != module_name);
+        self.records.len() != before
+    }
+
+    // Ajoute ou remplace le méta-insight d'un sujet
+    pub fn add_meta_insight(&mut self, insight: MetaInsight) {
+        self.meta_insights.retain(|existing| existing.topic != insight.topic);
+        self.meta_insights.push(insight);
+    }
+
+    // Ajoute ou remplace une crate, puis garde le classement par score décroissant
+    pub fn upsert_crate(&mut self, record: CrateRecord) {
+        self.crates.retain(|existing| existing.name != record.name);
+        self.crates.push(record);
+        self.crates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    // Connaissances modifiées après `since` (toutes si None), quelle que soit leur origine
+    pub fn export_delta(&self, since: Option<DateTime<Utc>>) -> KnowledgeDelta {
+        let fresh = |provenance: &Provenance| match since {
+            Some(since) => provenance.updated_at > since,
+            None => true,
+        };
+        let patterns = self.records.iter()
+            .filter_map(|pattern| {
+                let provenance = self.provenance.get(&pattern.module_name).cloned().unwrap_or_else(|| Provenance {
+                    origin: LOCAL_ORIGIN.to_string(),
+                    updated_at: DateTime::<Utc>::MIN_UTC,
+                });
+                fresh(&provenance).then(|| (pattern.clone(), provenance))
+            })
+            .collect();
+        let insights = self.meta_insights.iter().filter(|insight| fresh(&insight.provenance)).cloned().collect();
+        KnowledgeDelta { origin: LOCAL_ORIGIN.to_string(), since, patterns, insights }
+    }
+
+    // Fusionne un delta reçu: la version la plus récente gagne, à égalité la version locale est
+    // conservée; la provenance des entrées locales est remplacée par `delta.origin`
+    pub fn merge(&mut self, delta: KnowledgeDelta) -> MergeReport {
+        let mut report = MergeReport::default();
+        let rebrand = |provenance: Provenance| if provenance.origin == LOCAL_ORIGIN {
+            Provenance { origin: delta.origin.clone(), ..provenance }
+        } else {
+            provenance
+        };
+
+        for (pattern, provenance) in delta.patterns {
+            if !self.admits(&pattern) {
+                report.ignored += 1;
+                continue;
+            }
+            let provenance = rebrand(provenance);
+            match self.records.iter().position(|p| p.module_name == pattern.module_name) {
+                Some(index) => {
+                    let newer = match self.provenance.get(&pattern.module_name) {
+                        Some(local) => provenance.updated_at > local.updated_at,
+                        None => true,
+                    };
+                    if newer {
+                        self.provenance.insert(pattern.module_name.clone(), provenance);
+                        self.records[index] = pattern;
+                        report.updated += 1;
+                    } else {
+                        report.ignored += 1;
+                    }
+                }
+                None => {
+                    self.provenance.insert(pattern.module_name.clone(), provenance);
+                    self.records.push(pattern);
+                    report.added += 1;
+                }
+            }
+        }
+
+        for mut insight in delta.insights {
+            insight.provenance = rebrand(insight.provenance);
+            match self.meta_insights.iter_mut().find(|existing| existing.topic == insight.topic) {
+                Some(existing) if insight.provenance.updated_at > existing.provenance.updated_at => {
+                    *existing = insight;
+                    report.updated += 1;
+                }
+                Some(_) => report.ignored += 1,
+                None => {
+                    self.meta_insights.push(insight);
+                    report.added += 1;
+                }
+            }
+        }
+        report
+    }
+
     // Récupère tous les patterns stockés
     pub fn get_patterns(&self) -> &Vec<Pattern> {
         &self.records
@@ -78,7 +323,7 @@ impl KnowledgeBase {
 
     // Affiche un résumé des patterns stockés dans la base de données
     pub fn summarize(&self) {
-        println!("[AURORAE++] Base de savoir : {} projets analysés.", self.records.len());
+        info!("Base de savoir : {} projets analysés.", self.records.len());
         for r in &self.records {
             println!(
                 "→ {}: {} fn / {} struct / {} trait / {} enum",
@@ -88,6 +333,63 @@ impl KnowledgeBase {
     }
 }
 
+impl KnowledgeBase {
+    /// Change de modèle de plongement; l'index est reconstruit à la prochaine recherche
+    pub fn set_embedding_provider(&mut self, embedder: Arc<dyn EmbeddingProvider>) {
+        self.index = SemanticIndex::new(embedder.name());
+        self.embedder = Some(embedder);
+    }
+
+    fn embedder(&self) -> Arc<dyn EmbeddingProvider> {
+        self.embedder.clone().unwrap_or_else(|| Arc::new(LocalEmbedder::default()))
+    }
+
+    /// Textes indexés: nom des patterns, sujet et contenu des insights, description des crates
+    fn semantic_items(&self) -> Vec<(ItemKind, String, String)> {
+        let patterns = self.records.iter().map(|p| (ItemKind::Pattern, p.module_name.clone(), p.module_name.replace(['/', '_', '.'], " ")));
+        let insights = self.meta_insights.iter().map(|i| (ItemKind::Insight, i.topic.clone(), format!("{}\n{}", i.topic, i.content)));
+        let crates = self.crates.iter().map(|c| {
+            let text = format!("{}\n{}\n{}", c.name, c.description.as_deref().unwrap_or(""), c.readme.as_deref().unwrap_or(""));
+            (ItemKind::Crate, c.name.clone(), text)
+        });
+        patterns.chain(insights).chain(crates).collect()
+    }
+
+    /// Plonge les entrées nouvelles ou modifiées; renvoie le nombre de textes envoyés au modèle
+    pub async fn refresh_index(&mut self) -> Result<usize, String> {
+        let embedder = self.embedder();
+        if self.index.provider != embedder.name() {
+            self.index = SemanticIndex::new(embedder.name());
+        }
+        let items = self.semantic_items();
+        let live: Vec<(ItemKind, String)> = items.iter().map(|(kind, id, _)| (*kind, id.clone())).collect();
+        self.index.retain_live(&live);
+
+        let pending: Vec<(ItemKind, String, String, String)> = items.into_iter()
+            .map(|(kind, id, text)| (kind, id, hex::encode(Sha256::digest(text.as_bytes())), text))
+            .filter(|(kind, id, hash, _)| !self.index.contains(*kind, id, hash))
+            .collect();
+        if pending.is_empty() {
+            return Ok(0);
+        }
+        let texts: Vec<String> = pending.iter().map(|(_, _, _, text)| text.clone()).collect();
+        let vectors = embedder.embed(&texts).await?;
+        for ((kind, id, content_hash, _), vector) in pending.iter().zip(vectors) {
+            self.index.upsert(IndexEntry { kind: *kind, id: id.clone(), content_hash: content_hash.clone(), vector });
+        }
+        Ok(pending.len())
+    }
+
+    /// `k` patterns, insights ou crates les plus proches de `query`
+    pub async fn search(&mut self, query: &str, k: usize, kind: Option<ItemKind>) -> Result<Vec<SimilarItem>, String> {
+        self.refresh_index().await?;
+        let vector = self.embedder().embed(&[query.to_string()]).await?
+            .pop()
+            .ok_or("Plongement de la requête impossible")?;
+        Ok(self.index.search(&vector, k, kind))
+    }
+}
+
 // Méthode d'intégration avec `learning.rs` pour ajouter des patterns à la base de savoir
 impl KnowledgeBase {
     pub fn add_pattern_from_learning(&mut self, pattern: Pattern) {
@@ -117,3 +419,67 @@ impl Memory {
         self.insights.get(module_name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn pattern(name: &str, functions: usize) -> Pattern {
+        Pattern { module_name: name.to_string(), functions, structs: 0, traits: 0, enums: 0 }
+    }
+
+    fn with_pattern(kb: &mut KnowledgeBase, name: &str, functions: usize, updated_at: DateTime<Utc>) {
+        kb.records.push(pattern(name, functions));
+        kb.provenance.insert(name.to_string(), Provenance { origin: LOCAL_ORIGIN.to_string(), updated_at });
+    }
+
+    #[test]
+    fn delta_only_carries_newer_entries_for_the_requested_topics() {
+        let now = Utc::now();
+        let mut kb = KnowledgeBase::default();
+        with_pattern(&mut kb, "aurorae/economy.rs", 3, now - Duration::hours(2));
+        with_pattern(&mut kb, "aurorae/economy_v2.rs", 4, now);
+        with_pattern(&mut kb, "aurorae/dream.rs", 5, now);
+
+        let delta = kb.export_delta(Some(now - Duration::hours(1))).restricted_to(&["economy".to_string()]);
+        assert_eq!(delta.patterns.len(), 1);
+        assert_eq!(delta.patterns[0].0.module_name, "aurorae/economy_v2.rs");
+    }
+
+    #[test]
+    fn merge_keeps_the_newest_version_and_records_provenance() {
+        let now = Utc::now();
+        let mut local = KnowledgeBase::default();
+        with_pattern(&mut local, "a.rs", 1, now - Duration::hours(1));
+        with_pattern(&mut local, "b.rs", 1, now);
+
+        let mut remote = KnowledgeBase::default();
+        with_pattern(&mut remote, "a.rs", 2, now);
+        with_pattern(&mut remote, "b.rs", 2, now - Duration::hours(1));
+        with_pattern(&mut remote, "c.rs", 2, now);
+        remote.add_meta_insight(MetaInsight { topic: "economy".into(), content: "x".into(), confidence: 0.8, provenance: Provenance::local() });
+        let mut delta = remote.export_delta(None);
+        delta.origin = "instance-42".to_string();
+
+        let report = local.merge(delta);
+        assert_eq!(report, MergeReport { added: 2, updated: 1, ignored: 1 });
+        assert_eq!(local.records.iter().find(|p| p.module_name == "a.rs").unwrap().functions, 2);
+        assert_eq!(local.records.iter().find(|p| p.module_name == "b.rs").unwrap().functions, 1);
+        assert_eq!(local.provenance["a.rs"].origin, "instance-42");
+        assert_eq!(local.provenance["b.rs"].origin, LOCAL_ORIGIN);
+        assert_eq!(local.meta_insights[0].provenance.origin, "instance-42");
+    }
+
+    #[tokio::test]
+    async fn semantic_search_covers_insights_and_patterns() {
+        let mut kb = KnowledgeBase::default();
+        with_pattern(&mut kb, "aurorae/staking_rewards.rs", 4, Utc::now());
+        kb.add_meta_insight(MetaInsight { topic: "consensus".into(), content: "validator rotation and block finality".into(), confidence: 0.9, provenance: Provenance::local() });
+
+        let results = kb.search("staking rewards", 1, None).await.unwrap();
+        assert_eq!((results[0].kind, results[0].id.as_str()), (ItemKind::Pattern, "aurorae/staking_rewards.rs"));
+        assert_eq!(kb.search("block finality", 1, Some(ItemKind::Insight)).await.unwrap()[0].id, "consensus");
+        assert_eq!(kb.refresh_index().await.unwrap(), 0);
+    }
+}
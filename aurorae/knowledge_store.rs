@@ -0,0 +1,357 @@
+//! AURORAE++ - knowledge_store.rs
+//!
+//! Stockage embarqué (sled) de la base de connaissances de l'évolution de code: concepts,
+//! fragments et algorithmes y sont conservés entre deux scans. Les fragments sont dédupliqués
+//! par empreinte de contenu, chaque fichier d'inspiration garde l'empreinte de sa dernière
+//! lecture pour que seuls les fichiers modifiés soient ré-analysés, et les patterns de l'ancienne
+//! base JSON (`paths.knowledge-db`, module `knowledge`) y sont importés à l'ouverture.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::code_evolution::{Algorithm, CodeFragment, Concept, KnowledgeBase, EXTERNAL_SOURCE_PREFIX};
+use crate::config::CONFIG;
+use crate::knowledge::{KnowledgeBase as LegacyKnowledge, Pattern};
+
+pub const KNOWLEDGE_DB_PATH: &str = "aurorae_state/knowledge.sled";
+/// Source sous laquelle sont rangés les patterns importés de la base JSON du module `knowledge`
+pub const LEGACY_SOURCE: &str = "knowledge-db";
+
+/// Empreinte SHA-256 d'un contenu
+pub fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// Élément partagé entre plusieurs fichiers: supprimé quand plus aucun ne le référence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Shared<T> {
+    value: T,
+    files: BTreeSet<String>,
+}
+
+/// Dernière lecture d'un fichier et ses contributions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileRecord {
+    pub content_hash: String,
+    pub fragments: Vec<String>,
+    pub concepts: Vec<String>,
+    pub algorithms: Vec<String>,
+}
+
+/// Bilan d'un scan incrémental
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanStats {
+    pub parsed: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    /// Fichiers trop gros ou illisibles
+    pub skipped: usize,
+}
+
+pub struct KnowledgeStore {
+    db: sled::Db,
+    concepts: sled::Tree,
+    fragments: sled::Tree,
+    algorithms: sled::Tree,
+    files: sled::Tree,
+    /// Plongements par fournisseur et empreinte de texte (évite de recalculer ou repayer)
+    embeddings: sled::Tree,
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    serde_json::to_vec(value).map_err(|e| format!("Erreur de sérialisation: {}", e))
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+    serde_json::from_slice(bytes).map_err(|e| format!("Entrée illisible: {}", e))
+}
+
+impl KnowledgeStore {
+    pub fn open(path: &str) -> Result<Self, String> {
+        let db = sled::open(path).map_err(|e| format!("Ouverture de {} impossible: {}", path, e))?;
+        let tree = |name: &str| db.open_tree(name).map_err(|e| format!("Arbre {} indisponible: {}", name, e));
+        Ok(Self {
+            concepts: tree("concepts")?,
+            fragments: tree("fragments")?,
+            algorithms: tree("algorithms")?,
+            files: tree("files")?,
+            embeddings: tree("embeddings")?,
+            db,
+        })
+    }
+
+    /// Ouvre la base par défaut et y importe les patterns de la base JSON (`paths.knowledge-db`)
+    pub fn open_default() -> Result<Self, String> {
+        if let Some(parent) = Path::new(KNOWLEDGE_DB_PATH).parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Erreur création {}: {}", parent.display(), e))?;
+        }
+        let store = Self::open(KNOWLEDGE_DB_PATH)?;
+        let legacy_path = CONFIG.read().paths.knowledge_db.clone();
+        if legacy_path.exists() {
+            let migrated = store.migrate_legacy(&legacy_path)?;
+            if migrated > 0 {
+                info!("📦 Base JSON {} importée dans sled: {} pattern(s)", legacy_path.display(), migrated);
+            }
+        }
+        Ok(store)
+    }
+
+    fn get<T: DeserializeOwned>(tree: &sled::Tree, key: &str) -> Result<Option<T>, String> {
+        tree.get(key).map_err(|e| e.to_string())?.map(|bytes| decode(&bytes)).transpose()
+    }
+
+    fn put<T: Serialize>(tree: &sled::Tree, key: &str, value: &T) -> Result<(), String> {
+        tree.insert(key, encode(value)?).map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    pub fn file(&self, path: &str) -> Result<Option<FileRecord>, String> {
+        Self::get(&self.files, path)
+    }
+
+    /// Le fichier a-t-il changé depuis sa dernière lecture ?
+    pub fn is_current(&self, path: &str, hash: &str) -> Result<bool, String> {
+        Ok(self.file(path)?.map_or(false, |record| record.content_hash == hash))
+    }
+
+    pub fn tracked_files(&self) -> Result<Vec<String>, String> {
+        self.files.iter().keys()
+            .map(|key| key.map(|k| String::from_utf8_lossy(&k).to_string()).map_err(|e| e.to_string()))
+            .collect()
+    }
+
+    /// Ajoute `file` à l'élément partagé `key`, en le créant au besoin; renvoie la valeur stockée
+    fn attach<T: Serialize + DeserializeOwned + Clone>(tree: &sled::Tree, key: &str, value: T, file: &str) -> Result<T, String> {
+        let mut shared = Self::get::<Shared<T>>(tree, key)?.unwrap_or(Shared { value, files: BTreeSet::new() });
+        shared.files.insert(file.to_string());
+        Self::put(tree, key, &shared)?;
+        Ok(shared.value)
+    }
+
+    fn detach<T: Serialize + DeserializeOwned>(tree: &sled::Tree, key: &str, file: &str) -> Result<(), String> {
+        let Some(mut shared) = Self::get::<Shared<T>>(tree, key)? else { return Ok(()) };
+        shared.files.remove(file);
+        if shared.files.is_empty() {
+            tree.remove(key).map_err(|e| e.to_string())?;
+        } else {
+            Self::put(tree, key, &shared)?;
+        }
+        Ok(())
+    }
+
+    /// Retire les contributions d'un fichier (modifié ou supprimé)
+    pub fn forget_file(&self, path: &str) -> Result<(), String> {
+        let Some(record) = self.file(path)? else { return Ok(()) };
+        for key in &record.fragments {
+            Self::detach::<CodeFragment>(&self.fragments, key, path)?;
+        }
+        for key in &record.concepts {
+            Self::detach::<Concept>(&self.concepts, key, path)?;
+        }
+        for key in &record.algorithms {
+            Self::detach::<Algorithm>(&self.algorithms, key, path)?;
+        }
+        self.files.remove(path).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Remplace les contributions de `path` par celles extraites dans `parsed`.
+    /// Un fragment déjà connu (même langage, même code) garde son identifiant d'origine.
+    pub fn ingest_file(&self, path: &str, hash: &str, parsed: &KnowledgeBase) -> Result<(), String> {
+        self.forget_file(path)?;
+        let mut record = FileRecord { content_hash: hash.to_string(), ..FileRecord::default() };
+
+        let mut canonical: HashMap<Uuid, Uuid> = HashMap::new();
+        for fragment in &parsed.code_fragments {
+            let key = content_hash(format!("{}\n{}", fragment.language, fragment.code).as_bytes());
+            let stored = Self::attach(&self.fragments, &key, fragment.clone(), path)?;
+            canonical.insert(fragment.id, stored.id);
+            if !record.fragments.contains(&key) {
+                record.fragments.push(key);
+            }
+        }
+        for (name, concept) in &parsed.concepts {
+            Self::attach(&self.concepts, name, concept.clone(), path)?;
+            record.concepts.push(name.clone());
+        }
+        for algorithm in &parsed.algorithms {
+            let mut algorithm = algorithm.clone();
+            for id in algorithm.code_fragments.iter_mut() {
+                *id = canonical.get(id).copied().unwrap_or(*id);
+            }
+            Self::attach(&self.algorithms, &algorithm.name, algorithm.clone(), path)?;
+            if !record.algorithms.contains(&algorithm.name) {
+                record.algorithms.push(algorithm.name);
+            }
+        }
+        Self::put(&self.files, path, &record)
+    }
+
+    /// Base complète reconstituée depuis le stockage
+    pub fn load(&self) -> Result<KnowledgeBase, String> {
+        let mut kb = KnowledgeBase::default();
+        for entry in self.concepts.iter() {
+            let (_, bytes) = entry.map_err(|e| e.to_string())?;
+            let shared: Shared<Concept> = decode(&bytes)?;
+            let mut concept = shared.value;
+            concept.source_files = shared.files.into_iter().collect();
+            kb.concepts.insert(concept.name.clone(), concept);
+        }
+        for entry in self.fragments.iter() {
+            let (_, bytes) = entry.map_err(|e| e.to_string())?;
+            kb.code_fragments.push(decode::<Shared<CodeFragment>>(&bytes)?.value);
+        }
+        for entry in self.algorithms.iter() {
+            let (_, bytes) = entry.map_err(|e| e.to_string())?;
+            kb.algorithms.push(decode::<Shared<Algorithm>>(&bytes)?.value);
+        }
+        kb.last_update = Some(SystemTime::now());
+        Ok(kb)
+    }
+
+    /// Importe les patterns de la base JSON du module `knowledge` comme concepts, rangés sous
+    /// `external:knowledge-db`. Le fichier reste en place (le module `knowledge` l'utilise
+    /// toujours): il n'est ré-importé que si son contenu a changé. Renvoie le nombre de patterns
+    /// importés, 0 si la base était déjà à jour.
+    pub fn migrate_legacy(&self, path: &Path) -> Result<usize, String> {
+        let content = fs::read(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+        let key = format!("{}{}", EXTERNAL_SOURCE_PREFIX, LEGACY_SOURCE);
+        let hash = content_hash(&content);
+        if self.is_current(&key, &hash)? {
+            return Ok(0);
+        }
+        let legacy: LegacyKnowledge = serde_json::from_slice(&content).map_err(|e| format!("Format JSON invalide: {}", e))?;
+
+        let mut parsed = KnowledgeBase::default();
+        for pattern in &legacy.records {
+            parsed.concepts.insert(pattern.module_name.clone(), pattern_concept(pattern));
+        }
+        self.ingest_file(&key, &hash, &parsed)?;
+        self.flush()?;
+        Ok(parsed.concepts.len())
+    }
+
+    pub fn embedding(&self, provider: &str, hash: &str) -> Result<Option<Vec<f32>>, String> {
+        Self::get(&self.embeddings, &format!("{}:{}", provider, hash))
+    }
+
+    pub fn put_embedding(&self, provider: &str, hash: &str, vector: &[f32]) -> Result<(), String> {
+        Self::put(&self.embeddings, &format!("{}:{}", provider, hash), &vector)
+    }
+
+    pub fn flush(&self) -> Result<(), String> {
+        self.db.flush().map(|_| ()).map_err(|e| e.to_string())
+    }
+}
+
+/// Concept décrivant la structure d'un module analysé par le module `learning`
+fn pattern_concept(pattern: &Pattern) -> Concept {
+    let items = pattern.functions + pattern.structs + pattern.traits + pattern.enums;
+    Concept {
+        name: pattern.module_name.clone(),
+        description: format!(
+            "Module {}: {} fonction(s), {} structure(s), {} trait(s), {} énumération(s)",
+            pattern.module_name, pattern.functions, pattern.structs, pattern.traits, pattern.enums
+        ),
+        relevance: 0.5,
+        complexity: (items as f32 / 100.0).min(1.0),
+        source_files: vec![pattern.module_name.clone()],
+        related_concepts: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("aurorae-knowledge-{}", Uuid::new_v4()))
+    }
+
+    fn fragment(code: &str, file: &str) -> CodeFragment {
+        CodeFragment {
+            id: Uuid::new_v4(),
+            code: code.to_string(),
+            language: "rust".to_string(),
+            description: String::new(),
+            source_file: file.to_string(),
+            complexity: 0.5,
+            tags: Vec::new(),
+            performance_score: None,
+            provenance: None,
+        }
+    }
+
+    fn parsed(file: &str, codes: &[&str]) -> KnowledgeBase {
+        let mut kb = KnowledgeBase::default();
+        kb.code_fragments = codes.iter().map(|c| fragment(c, file)).collect();
+        kb.algorithms.push(Algorithm {
+            name: format!("tri de {}", file),
+            purpose: String::new(),
+            code_fragments: vec![kb.code_fragments[0].id],
+            time_complexity: "O(n log n)".to_string(),
+            space_complexity: "O(n)".to_string(),
+            adaptability: 0.5,
+        });
+        kb
+    }
+
+    #[test]
+    fn fragments_are_deduplicated_and_released_per_file() {
+        let dir = scratch();
+        let store = KnowledgeStore::open(dir.to_str().unwrap()).unwrap();
+        store.ingest_file("a.rs", "h1", &parsed("a.rs", &["fn shared() {}", "fn only_a() {}"])).unwrap();
+        store.ingest_file("b.rs", "h2", &parsed("b.rs", &["fn shared() {}"])).unwrap();
+
+        let kb = store.load().unwrap();
+        assert_eq!(kb.code_fragments.len(), 2);
+        let shared = kb.code_fragments.iter().find(|f| f.code == "fn shared() {}").unwrap();
+        // L'algorithme de b.rs pointe vers le fragment canonique déposé par a.rs
+        assert!(kb.algorithms.iter().all(|a| a.code_fragments == vec![shared.id]));
+        assert!(store.is_current("a.rs", "h1").unwrap());
+        assert!(!store.is_current("a.rs", "h3").unwrap());
+
+        store.ingest_file("a.rs", "h3", &parsed("a.rs", &["fn rewritten() {}"])).unwrap();
+        let codes: BTreeSet<String> = store.load().unwrap().code_fragments.into_iter().map(|f| f.code).collect();
+        assert_eq!(codes, BTreeSet::from(["fn shared() {}".to_string(), "fn rewritten() {}".to_string()]));
+
+        store.forget_file("b.rs").unwrap();
+        assert_eq!(store.load().unwrap().code_fragments.len(), 1);
+        assert_eq!(store.tracked_files().unwrap(), vec!["a.rs".to_string()]);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn knowledge_db_patterns_are_imported_once_per_content() {
+        let dir = scratch();
+        fs::create_dir_all(&dir).unwrap();
+        let legacy_path = dir.join("aurorae_knowledge.json");
+        let mut legacy = LegacyKnowledge::default();
+        legacy.upsert_pattern(Pattern { module_name: "consensus/pbft.rs".to_string(), functions: 12, structs: 3, traits: 1, enums: 2 });
+        legacy.save_to(&legacy_path);
+
+        let store = KnowledgeStore::open(dir.join("db").to_str().unwrap()).unwrap();
+        assert_eq!(store.migrate_legacy(&legacy_path).unwrap(), 1);
+        // Le module `knowledge` lit toujours ce fichier: il n'est ni renommé ni ré-importé
+        assert!(legacy_path.exists());
+        assert_eq!(store.migrate_legacy(&legacy_path).unwrap(), 0);
+
+        let kb = store.load().unwrap();
+        let concept = &kb.concepts["consensus/pbft.rs"];
+        assert!(concept.description.contains("12 fonction(s)"));
+        assert_eq!(concept.source_files, vec![format!("{}{}", EXTERNAL_SOURCE_PREFIX, LEGACY_SOURCE)]);
+
+        legacy.upsert_pattern(Pattern { module_name: "p2p/gossip.rs".to_string(), functions: 4, structs: 1, traits: 0, enums: 0 });
+        legacy.save_to(&legacy_path);
+        assert_eq!(store.migrate_legacy(&legacy_path).unwrap(), 2);
+        assert_eq!(store.load().unwrap().concepts.len(), 2);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,149 @@
+//! AURORAE++ - knowledge_sync.rs
+//!
+//! Synchronisation de la base de savoir entre le parent et ses instances: à intervalle
+//! régulier, chaque instance connectée reçoit les patterns et méta-insights modifiés depuis
+//! le dernier envoi, restreints à ses spécialisations (modules hérités). Les deltas envoyés
+//! par les instances sont fusionnés (version la plus récente gagnante, provenance conservée)
+//! et les demandes explicites de synchronisation reçoivent une réponse immédiate.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::instance_messaging::{Frame, InstanceMessage, MessageHub};
+use crate::knowledge::{KnowledgeBase, KnowledgeDelta, MergeReport};
+use crate::reproduction::AuroraInstance;
+
+/// Intervalle par défaut entre deux partages
+pub const DEFAULT_SYNC_INTERVAL: Duration = Duration::from_secs(600);
+
+pub struct KnowledgeSync {
+    origin: Uuid,
+    interval: Duration,
+    last_run: Option<Instant>,
+    /// Date du dernier delta envoyé à chaque instance
+    last_shared: HashMap<Uuid, DateTime<Utc>>,
+}
+
+impl KnowledgeSync {
+    pub fn new(origin: Uuid, interval: Duration) -> Self {
+        Self { origin, interval, last_run: None, last_shared: HashMap::new() }
+    }
+
+    pub fn is_due(&self) -> bool {
+        match self.last_run {
+            Some(last) => last.elapsed() >= self.interval,
+            None => true,
+        }
+    }
+
+    /// Delta destiné à une instance: nouveautés depuis le dernier envoi, limitées à ses modules
+    pub fn delta_for(&self, knowledge: &KnowledgeBase, instance: &AuroraInstance) -> KnowledgeDelta {
+        let mut delta = knowledge
+            .export_delta(self.last_shared.get(&instance.id).copied())
+            .restricted_to(&instance.inherited_modules);
+        delta.origin = self.origin.to_string();
+        delta
+    }
+
+    /// Envoie à chaque instance active et connectée son delta; retourne le nombre d'envois
+    pub fn share(&mut self, hub: &MessageHub, knowledge: &KnowledgeBase, instances: &[&AuroraInstance]) -> usize {
+        self.last_run = Some(Instant::now());
+        let connected = hub.peers();
+        let mut sent = 0;
+        for instance in instances.iter().filter(|i| i.is_active && connected.contains(&i.id)) {
+            let now = Utc::now();
+            let delta = self.delta_for(knowledge, instance);
+            if delta.is_empty() {
+                continue;
+            }
+            match serde_json::to_string(&delta).map_err(|e| e.to_string()).and_then(|payload| hub.send(&instance.id, InstanceMessage::KnowledgeSync { payload })) {
+                Ok(()) => {
+                    self.last_shared.insert(instance.id, now);
+                    sent += 1;
+                }
+                Err(e) => warn!("⚠️ Synchronisation avec {} impossible: {}", instance.id, e),
+            }
+        }
+        if sent > 0 {
+            info!("🔁 Savoir partagé avec {} instance(s)", sent);
+        }
+        sent
+    }
+
+    /// Traite une trame de synchronisation reçue d'une instance; les autres trames sont ignorées
+    pub fn handle(&mut self, frame: &Frame, hub: &MessageHub, knowledge: &mut KnowledgeBase) -> Option<MergeReport> {
+        match &frame.message {
+            InstanceMessage::KnowledgeSync { payload } => match serde_json::from_str::<KnowledgeDelta>(payload) {
+                Ok(mut delta) => {
+                    delta.origin = frame.from.to_string();
+                    let report = knowledge.merge(delta);
+                    info!(
+                        "🔁 Savoir reçu de {}: {} ajout(s), {} mise(s) à jour, {} ignoré(s)",
+                        frame.from, report.added, report.updated, report.ignored
+                    );
+                    Some(report)
+                }
+                Err(e) => {
+                    warn!("⚠️ Delta illisible de {}: {}", frame.from, e);
+                    None
+                }
+            },
+            InstanceMessage::KnowledgeSyncRequest { since, topics } => {
+                let since = since.as_deref().and_then(|s| DateTime::parse_from_rfc3339(s).ok()).map(|d| d.with_timezone(&Utc));
+                let mut delta = knowledge.export_delta(since);
+                if !topics.is_empty() {
+                    delta = delta.restricted_to(topics);
+                }
+                delta.origin = self.origin.to_string();
+                let sent = serde_json::to_string(&delta)
+                    .map_err(|e| e.to_string())
+                    .and_then(|payload| hub.send(&frame.from, InstanceMessage::KnowledgeSync { payload }));
+                match sent {
+                    Ok(()) => {
+                        self.last_shared.insert(frame.from, Utc::now());
+                    }
+                    Err(e) => warn!("⚠️ Réponse de synchronisation à {} impossible: {}", frame.from, e),
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::{Pattern, Provenance};
+
+    #[test]
+    fn deltas_follow_specializations_and_only_carry_news() {
+        let mut knowledge = KnowledgeBase::default();
+        for name in ["aurorae/economy.rs", "aurorae/dream.rs"] {
+            knowledge.records.push(Pattern { module_name: name.into(), functions: 1, structs: 0, traits: 0, enums: 0 });
+            knowledge.provenance.insert(name.into(), Provenance::local());
+        }
+        let instance = AuroraInstance {
+            id: Uuid::new_v4(),
+            parent_id: None,
+            created_at: Utc::now().to_rfc3339(),
+            purpose: "économie".into(),
+            inherited_modules: vec!["economy".into()],
+            generation: 1,
+            is_active: true,
+        };
+        let mut sync = KnowledgeSync::new(Uuid::new_v4(), DEFAULT_SYNC_INTERVAL);
+        assert!(sync.is_due());
+
+        let delta = sync.delta_for(&knowledge, &instance);
+        assert_eq!(delta.patterns.len(), 1);
+        assert_eq!(delta.origin, sync.origin.to_string());
+
+        sync.last_shared.insert(instance.id, Utc::now());
+        assert!(sync.delta_for(&knowledge, &instance).is_empty());
+    }
+}
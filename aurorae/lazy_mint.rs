@@ -0,0 +1,175 @@
+//! AURORAE++ - lazy_mint.rs
+//!
+//! Bons de mint différé (EIP-712): le propriétaire de la collection signe hors chaîne un
+//! bon par NFT, l'acheteur le rachète via `redeem` et paie lui-même le mint. Une grande
+//! collection peut ainsi être mise en vente sans aucune transaction immédiate.
+
+use ethers::abi::{encode, Token};
+use ethers::types::transaction::eip712::{Eip712, TypedData};
+use ethers::types::{Address, Bytes, Signature, H256, U256};
+use ethers::utils::{id, keccak256};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::blockchain_core::TxLog;
+use crate::keystore::Signer;
+
+/// Version du domaine EIP-712, identique à celle du contrat `AuroraeCollection`
+pub const DOMAIN_VERSION: &str = "1";
+
+/// Domaine de signature: une collection déployée sur une chaîne
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoucherDomain {
+    /// Nom de la collection (`name()` du contrat)
+    pub name: String,
+    pub chain_id: u64,
+    pub contract: Address,
+}
+
+/// Bon de mint d'un NFT, signé par le propriétaire du contrat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LazyMintVoucher {
+    pub nft_id: Uuid,
+    /// Destinataire imposé; adresse nulle: le token revient à celui qui rachète le bon
+    pub recipient: Address,
+    pub uri: String,
+    /// Prix minimal (wei) à payer lors du rachat
+    pub min_price: U256,
+    /// Nonce dérivé de l'identifiant du NFT: un NFT ne peut être racheté qu'une fois
+    pub nonce: U256,
+    /// Expiration (timestamp Unix, secondes)
+    pub expiry: u64,
+    pub signature: Option<Bytes>,
+    #[serde(default)]
+    pub redeemed_tx: Option<H256>,
+}
+
+impl LazyMintVoucher {
+    pub fn new(nft_id: Uuid, uri: &str, min_price: U256, expiry: u64) -> Self {
+        Self {
+            nft_id,
+            recipient: Address::zero(),
+            uri: uri.to_string(),
+            min_price,
+            nonce: U256::from_big_endian(nft_id.as_bytes()),
+            expiry,
+            signature: None,
+            redeemed_tx: None,
+        }
+    }
+
+    pub fn typed_data(&self, domain: &VoucherDomain) -> Result<TypedData, String> {
+        serde_json::from_value(json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "LazyMint": [
+                    { "name": "recipient", "type": "address" },
+                    { "name": "uri", "type": "string" },
+                    { "name": "minPrice", "type": "uint256" },
+                    { "name": "nonce", "type": "uint256" },
+                    { "name": "expiry", "type": "uint256" },
+                ],
+            },
+            "primaryType": "LazyMint",
+            "domain": {
+                "name": domain.name,
+                "version": DOMAIN_VERSION,
+                "chainId": domain.chain_id,
+                "verifyingContract": domain.contract,
+            },
+            "message": {
+                "recipient": self.recipient,
+                "uri": self.uri,
+                "minPrice": self.min_price,
+                "nonce": self.nonce,
+                "expiry": self.expiry,
+            },
+        }))
+        .map_err(|e| format!("Bon EIP-712 invalide: {}", e))
+    }
+
+    /// Condensé signé (`\x19\x01 || domainSeparator || hashStruct`)
+    pub fn digest(&self, domain: &VoucherDomain) -> Result<H256, String> {
+        self.typed_data(domain)?
+            .encode_eip712()
+            .map(H256::from)
+            .map_err(|e| format!("Encodage EIP-712 impossible: {}", e))
+    }
+
+    pub async fn sign(mut self, signer: &dyn Signer, domain: &VoucherDomain) -> Result<Self, String> {
+        let signature = signer.sign_typed_data(&self.typed_data(domain)?).await?;
+        self.signature = Some(Bytes::from(signature.to_vec()));
+        Ok(self)
+    }
+
+    /// Adresse ayant signé le bon (doit être le propriétaire du contrat pour être rachetable)
+    pub fn signer(&self, domain: &VoucherDomain) -> Result<Address, String> {
+        let signature = self.signature.as_ref().ok_or_else(|| "Bon non signé".to_string())?;
+        let signature = Signature::try_from(signature.as_ref())
+            .map_err(|e| format!("Signature invalide: {}", e))?;
+        signature.recover(self.digest(domain)?)
+            .map_err(|e| format!("Signataire introuvable: {}", e))
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now > self.expiry
+    }
+
+    /// Appel `redeem(voucher, signature)` à envoyer par l'acheteur avec `min_price` en valeur
+    pub fn redeem_calldata(&self) -> Result<Bytes, String> {
+        let signature = self.signature.as_ref().ok_or_else(|| "Bon non signé".to_string())?;
+        let mut calldata = id("redeem((address,string,uint256,uint256,uint256),bytes)").to_vec();
+        calldata.extend(encode(&[
+            Token::Tuple(vec![
+                Token::Address(self.recipient),
+                Token::String(self.uri.clone()),
+                Token::Uint(self.min_price),
+                Token::Uint(self.nonce),
+                Token::Uint(U256::from(self.expiry)),
+            ]),
+            Token::Bytes(signature.to_vec()),
+        ]));
+        Ok(Bytes::from(calldata))
+    }
+}
+
+pub fn voucher_redeemed_topic() -> H256 {
+    H256::from(keccak256("VoucherRedeemed(uint256,uint256,address)"))
+}
+
+/// Décode `VoucherRedeemed(nonce, tokenId, redeemer)` émis par `contract`
+pub fn decode_voucher_redeemed(contract: Address, log: &TxLog) -> Option<(U256, u64)> {
+    if log.address != contract || log.topics.len() != 3 || log.topics[0] != voucher_redeemed_topic() {
+        return None;
+    }
+    let nonce = U256::from_big_endian(log.topics[1].as_bytes());
+    let token_id = U256::from_big_endian(log.topics[2].as_bytes());
+    (token_id <= U256::from(u64::MAX)).then(|| (nonce, token_id.as_u64()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keystore::WalletSigner;
+
+    #[tokio::test]
+    async fn signed_voucher_recovers_owner() {
+        let owner = WalletSigner::random("test");
+        let domain = VoucherDomain { name: "Conscience Évolutive 1".to_string(), chain_id: 11155111, contract: Address::repeat_byte(7) };
+        let voucher = LazyMintVoucher::new(Uuid::new_v4(), "ipfs://bafkexample", U256::exp10(16), 1_900_000_000)
+            .sign(&owner, &domain)
+            .await
+            .unwrap();
+
+        assert_eq!(voucher.signer(&domain).unwrap(), owner.address());
+        let other = VoucherDomain { chain_id: 1, ..domain };
+        assert_ne!(voucher.signer(&other).unwrap(), owner.address());
+        assert!(voucher.redeem_calldata().is_ok());
+    }
+}
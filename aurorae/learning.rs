@@ -1,10 +1,21 @@
-use std::fs::{read_to_string, read_dir};
+use std::fs::{create_dir_all, read_to_string, read_dir, File};
+use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 use regex::Regex;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tracing::info;
+use crate::config;
 use crate::knowledge::{KnowledgeBase, Pattern}; // Importer KnowledgeBase et Pattern du module knowledge
 
-const FEED_PATH: &str = "C:\\Users\\admin\\.github_feed";
+pub const CURRICULUM_PATH: &str = "aurorae_state/curriculum.json";
+/// Maîtrise (moyenne mobile des succès) à partir de laquelle un niveau est acquis
+const MASTERY_THRESHOLD: f32 = 0.7;
+/// Poids d'une nouvelle tentative dans la maîtrise
+const MASTERY_SMOOTHING: f32 = 0.3;
+/// Tailles de dépôt (octets extraits) séparant les niveaux de difficulté
+const SIMPLE_REPO_BYTES: u64 = 50 * 1024 * 1024;
+const INTERMEDIATE_REPO_BYTES: u64 = 300 * 1024 * 1024;
 
 #[derive(Debug, Clone)]
 pub struct PatternInsight {
@@ -29,7 +40,7 @@ impl PatternInsight {
 
 /// Lit tous les projets clonés et analyse leurs fichiers Rust
 pub fn scan_feed_and_learn(knowledge_base: &mut KnowledgeBase) {
-    if let Ok(entries) = read_dir(FEED_PATH) {
+    if let Ok(entries) = read_dir(config::feed_path()) {
         for entry in entries.flatten() {
             let path = entry.path();
             if path.is_dir() {
@@ -91,3 +102,275 @@ fn find_rust_files(base: &Path) -> Vec<PathBuf> {
     }
     results
 }
+
+/// Familles de tâches d'apprentissage suivies par le curriculum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskClass {
+    /// Extraction des patterns d'un dépôt de référence
+    ParseRepo,
+    /// Mutation d'un module d'Aurorae en bac à sable
+    Mutation,
+}
+
+/// Niveau de difficulté d'une tâche
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Difficulty {
+    /// Petit dépôt, module feuille (au plus un module dépendant)
+    Simple,
+    Intermediate,
+    /// Dépôt volumineux (substrate…), module du noyau
+    Advanced,
+}
+
+/// Actions autonomes réservées aux niveaux maîtrisés: action, famille, niveau exigé
+const ACTION_PREREQUISITES: &[(&str, TaskClass, Difficulty)] = &[
+    ("optimize_protocol", TaskClass::Mutation, Difficulty::Intermediate),
+    ("evolve_consensus", TaskClass::Mutation, Difficulty::Advanced),
+];
+
+/// Maîtrise d'un niveau d'une famille de tâches
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Mastery {
+    pub attempts: u32,
+    pub successes: u32,
+    /// Moyenne mobile des succès (0 à 1)
+    pub score: f32,
+}
+
+impl Mastery {
+    pub fn is_mastered(&self) -> bool {
+        self.score >= MASTERY_THRESHOLD
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CurriculumState {
+    mastery: BTreeMap<TaskClass, BTreeMap<Difficulty, Mastery>>,
+    /// Octets extraits lors du dernier clonage de chaque dépôt
+    repo_sizes: HashMap<String, u64>,
+}
+
+/// Curriculum: les tâches sont ordonnées de la plus simple à la plus difficile, et un niveau
+/// n'est ouvert qu'une fois le niveau présent juste en dessous maîtrisé
+#[derive(Debug)]
+pub struct Curriculum {
+    state: CurriculumState,
+    path: Option<PathBuf>,
+}
+
+impl Default for Curriculum {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+impl Curriculum {
+    /// Curriculum rechargé depuis `aurorae_state/curriculum.json`
+    pub fn new() -> Self {
+        Self::open(Path::new(CURRICULUM_PATH))
+    }
+
+    pub fn in_memory() -> Self {
+        Self { state: CurriculumState::default(), path: None }
+    }
+
+    pub fn open(path: &Path) -> Self {
+        let state = File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self { state, path: Some(path.to_path_buf()) }
+    }
+
+    pub fn mastery(&self, class: TaskClass, difficulty: Difficulty) -> Mastery {
+        self.state.mastery.get(&class).and_then(|levels| levels.get(&difficulty)).cloned().unwrap_or_default()
+    }
+
+    /// Compétence démontrée: le niveau ou un niveau supérieur est maîtrisé
+    pub fn is_competent(&self, class: TaskClass, difficulty: Difficulty) -> bool {
+        self.state.mastery.get(&class)
+            .is_some_and(|levels| levels.range(difficulty..).any(|(_, mastery)| mastery.is_mastered()))
+    }
+
+    pub fn record(&mut self, class: TaskClass, difficulty: Difficulty, success: bool) {
+        let mastery = self.state.mastery.entry(class).or_default().entry(difficulty).or_default();
+        mastery.attempts += 1;
+        if success {
+            mastery.successes += 1;
+        }
+        let outcome = if success { 1.0 } else { 0.0 };
+        mastery.score = (1.0 - MASTERY_SMOOTHING) * mastery.score + MASTERY_SMOOTHING * outcome;
+        self.save();
+    }
+
+    /// Difficulté d'un dépôt d'après la taille de sa dernière extraction (simple si inconnue)
+    pub fn repo_difficulty(&self, url: &str) -> Difficulty {
+        match self.state.repo_sizes.get(url) {
+            Some(bytes) if *bytes >= INTERMEDIATE_REPO_BYTES => Difficulty::Advanced,
+            Some(bytes) if *bytes >= SIMPLE_REPO_BYTES => Difficulty::Intermediate,
+            _ => Difficulty::Simple,
+        }
+    }
+
+    /// Issue de l'extraction d'un dépôt; `bytes` vaut None en cas d'échec
+    pub fn record_repo(&mut self, url: &str, bytes: Option<u64>) {
+        let difficulty = self.repo_difficulty(url);
+        if let Some(bytes) = bytes {
+            self.state.repo_sizes.insert(url.to_string(), bytes);
+        }
+        self.record(TaskClass::ParseRepo, difficulty, bytes.is_some());
+    }
+
+    /// Niveau le plus difficile ouvert parmi ceux des tâches: chaque niveau présent exige la
+    /// maîtrise du niveau présent juste en dessous
+    fn open_level<T>(&self, class: TaskClass, tasks: &[(T, Difficulty)]) -> Option<Difficulty> {
+        let mut levels: Vec<Difficulty> = tasks.iter().map(|(_, difficulty)| *difficulty).collect();
+        levels.sort();
+        levels.dedup();
+        let mut open = *levels.first()?;
+        for level in levels.into_iter().skip(1) {
+            if !self.is_competent(class, open) {
+                break;
+            }
+            open = level;
+        }
+        Some(open)
+    }
+
+    /// Ordonne les tâches de la plus simple à la plus difficile; renvoie celles admises et
+    /// celles différées faute de compétence démontrée au niveau inférieur
+    pub fn sequence<T: Clone>(&self, class: TaskClass, tasks: &[(T, Difficulty)]) -> (Vec<T>, Vec<T>) {
+        let open = self.open_level(class, tasks);
+        let mut ordered: Vec<&(T, Difficulty)> = tasks.iter().collect();
+        ordered.sort_by_key(|(_, difficulty)| *difficulty);
+        let (admitted, deferred): (Vec<_>, Vec<_>) = ordered.into_iter().partition(|(_, difficulty)| open.is_some_and(|o| *difficulty <= o));
+        (
+            admitted.into_iter().map(|(task, _)| task.clone()).collect(),
+            deferred.into_iter().map(|(task, _)| task.clone()).collect(),
+        )
+    }
+
+    /// Prochaine tâche: le niveau admis le plus simple qui n'est pas encore maîtrisé (le plus
+    /// difficile admis si tous le sont); `seed` fait tourner les tâches d'un même niveau
+    pub fn next_task<T: Clone>(&self, class: TaskClass, tasks: &[(T, Difficulty)], seed: usize) -> Option<(T, Difficulty)> {
+        let open = self.open_level(class, tasks)?;
+        let level = tasks.iter()
+            .map(|(_, difficulty)| *difficulty)
+            .filter(|difficulty| *difficulty <= open && !self.mastery(class, *difficulty).is_mastered())
+            .min()
+            .unwrap_or(open);
+        let candidates: Vec<&(T, Difficulty)> = tasks.iter().filter(|(_, difficulty)| *difficulty == level).collect();
+        candidates.get(seed % candidates.len()).map(|(task, difficulty)| (task.clone(), *difficulty))
+    }
+
+    /// Refuse une action autonome tant que la compétence qu'elle exige n'est pas démontrée
+    pub fn gate_action(&self, action: &str) -> Result<(), String> {
+        match ACTION_PREREQUISITES.iter().find(|(name, _, _)| *name == action) {
+            Some((_, class, difficulty)) if !self.is_competent(*class, *difficulty) => Err(format!(
+                "{} exige la maîtrise de {:?} au niveau {:?} (maîtrise actuelle {:.2})",
+                action, class, difficulty, self.mastery(*class, *difficulty).score
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn report(&self) {
+        for (class, levels) in &self.state.mastery {
+            let summary: Vec<String> = levels.iter()
+                .map(|(difficulty, m)| format!("{:?} {:.2} ({}/{})", difficulty, m.score, m.successes, m.attempts))
+                .collect();
+            info!("🎓 Curriculum {:?}: {}", class, summary.join(" • "));
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self.state);
+        }
+    }
+}
+
+/// Modules mutables d'un dossier source et leur difficulté: un module dont dépendent peu
+/// d'autres modules est une feuille, un module importé de partout appartient au noyau
+pub fn module_difficulties(src: &Path) -> Vec<(String, Difficulty)> {
+    let sources: Vec<(String, String)> = read_dir(src)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            Some((name, read_to_string(&path).ok()?))
+        })
+        .collect();
+    let mut modules: Vec<(String, Difficulty)> = sources.iter()
+        // Points d'entrée du crate: jamais mutés
+        .filter(|(name, _)| name != "main" && name != "lib")
+        .map(|(name, _)| {
+            let import = format!("crate::{}::", name);
+            let dependents = sources.iter().filter(|(other, content)| other != name && content.contains(&import)).count();
+            let difficulty = match dependents {
+                0..=1 => Difficulty::Simple,
+                2..=5 => Difficulty::Intermediate,
+                _ => Difficulty::Advanced,
+            };
+            (name.clone(), difficulty)
+        })
+        .collect();
+    modules.sort();
+    modules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn harder_levels_open_once_the_level_below_is_mastered() {
+        let path = std::env::temp_dir().join(format!("aurorae-curriculum-{}.json", uuid::Uuid::new_v4()));
+        let mut curriculum = Curriculum::open(&path);
+        let tasks = [
+            ("brain", Difficulty::Advanced),
+            ("vision", Difficulty::Simple),
+            ("audit", Difficulty::Intermediate),
+            ("lineage", Difficulty::Simple),
+        ];
+        let (admitted, deferred) = curriculum.sequence(TaskClass::Mutation, &tasks);
+        assert_eq!(admitted, vec!["vision", "lineage"]);
+        assert_eq!(deferred, vec!["audit", "brain"]);
+        assert_eq!(curriculum.next_task(TaskClass::Mutation, &tasks, 1), Some(("lineage", Difficulty::Simple)));
+        assert!(curriculum.gate_action("optimize_protocol").is_err());
+        assert!(curriculum.gate_action("analyze_market").is_ok());
+
+        for _ in 0..4 {
+            curriculum.record(TaskClass::Mutation, Difficulty::Simple, true);
+        }
+        let reloaded = Curriculum::open(&path);
+        assert!(reloaded.mastery(TaskClass::Mutation, Difficulty::Simple).is_mastered());
+        assert_eq!(reloaded.sequence(TaskClass::Mutation, &tasks).0, vec!["vision", "lineage", "audit"]);
+        assert_eq!(reloaded.next_task(TaskClass::Mutation, &tasks, 0), Some(("audit", Difficulty::Intermediate)));
+        // Les prérequis des actions restent fermés tant que le niveau exigé n'est pas maîtrisé
+        assert!(reloaded.gate_action("optimize_protocol").is_err());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn repositories_are_ranked_by_their_last_extraction_size() {
+        let mut curriculum = Curriculum::in_memory();
+        let substrate = "https://github.com/paritytech/substrate";
+        assert_eq!(curriculum.repo_difficulty(substrate), Difficulty::Simple);
+        curriculum.record_repo(substrate, Some(INTERMEDIATE_REPO_BYTES + 1));
+        curriculum.record_repo("https://github.com/exemple/petit", Some(1024));
+        curriculum.record_repo("https://github.com/exemple/absent", None);
+        assert_eq!(curriculum.repo_difficulty(substrate), Difficulty::Advanced);
+        assert_eq!(curriculum.mastery(TaskClass::ParseRepo, Difficulty::Simple).attempts, 3);
+        assert_eq!(curriculum.mastery(TaskClass::ParseRepo, Difficulty::Simple).successes, 2);
+    }
+}
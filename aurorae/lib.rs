@@ -13,38 +13,71 @@
 pub mod brain;                // Noyau central métacognitif
 pub mod intelligence;         // Capacités d'intelligence artificielle
 pub mod learning;            // Apprentissage adaptatif et métacognition
+pub mod meta_learning;       // Méta-règles persistées et réglage des hyperparamètres d'apprentissage
 pub mod metacognition;       // Capacités d'auto-référence et introspection
 pub mod reinforcement_learning;  // Apprentissage par renforcement
 pub mod neural_network;      // Infrastructure de réseaux neuronaux
 
 // ==================== MODULES BLOCKCHAIN ====================
 pub mod blockchain_core;     // Interface avec diverses blockchains
+pub mod chain_registry;      // Registre des chaînes (chains.toml)
+pub mod substrate;           // Connecteur des chaînes Substrate (subxt)
+pub mod keystore;            // Gestion des clés et signataires
 pub mod economy;             // Gestion économique et tokenomique
+pub mod market_data;         // Flux de données de marché et indicateurs
+pub mod currency;            // Actifs typés et taux de change
 pub mod founder_income;      // Distribution des revenus fondateurs
+pub mod approvals;           // Approbation multi-signatures des retraits
 pub mod nft_minter;          // Création et gestion de NFTs
-pub mod validator;           // Validation des transactions et consensus
+pub mod ipfs;                // Publication IPFS et services d'épinglage
+pub mod nft_sales;           // Royalties EIP-2981 et ventes secondaires
+pub mod lazy_mint;           // Bons de mint différé signés (EIP-712)
+pub mod validator;           // Validation des transactions (pipeline, blocs simulés) et intégrité
+pub mod devnet;              // Devnet local déterministe (mempool, blocs, environnement RL)
 pub mod alchemy;             // Transformation et fusion des actifs numériques
+pub mod staking;             // Staking des tokens forgés et récompenses
 pub mod deployer;            // Déploiement de contrats intelligents
+pub mod deploy_manifest;     // Manifestes de déploiement multi-contrats et dry-run
+pub mod contract_verification; // Vérification des contrats (Etherscan, Sourcify)
+pub mod event_bus;           // Bus d'événements (activité on-chain)
 
 // ==================== MODULES D'ÉVOLUTION ====================
 pub mod evolution;           // Mécanismes d'évolution systémique
 pub mod mutation;            // Mutations du code et des stratégies
+pub mod mutation_sandbox;    // Bac à sable des mutations (tests, clippy, benchmarks, promotion)
+pub mod fitness;             // Aptitude mesurée des génomes (compilation, tests, benchmarks, clippy, économie)
 pub mod reproduction;        // Réplication et génération d'instances
+pub mod instance_runner;     // Exécution supervisée des instances (processus ou conteneurs)
+pub mod instance_messaging;  // Protocole de messages entre instances parente et enfants
 pub mod code_evolution;      // Évolution du code source
 pub mod genome;              // Représentation génétique des composants
+pub mod lineage;             // Généalogie des génomes et des instances (export DOT/JSON)
 pub mod autonomy;            // Capacités d'autonomie et d'indépendance
 
 // ==================== MODULES CRÉATIFS ====================
 pub mod dream;               // Moteur de rêves et génération créative
+pub mod image_generator;     // Illustrations (Stable Diffusion, rendu procédural SVG)
+pub mod inspiration;         // Sources d'inspiration (GitHub, dossier local, corpus embarqué)
 pub mod vision;              // Capacités de projection et visualisation
 pub mod generator;           // Génération de nouveaux modules et fonctionnalités
+pub mod templates;           // Gabarits paramétrés du générateur (consensus, jeton, oracle, métriques)
+pub mod llm;                 // Fournisseurs de modèles de langage (OpenAI, Azure, Anthropic, local)
+pub mod llm_generator;       // Génération assistée par modèle de langage (validée par syn et la porte de compilation)
+pub mod llm_cache;           // Cache des réponses LLM par empreinte (TTL, déduplication, rejeu hors ligne)
+pub mod llm_usage;           // Comptabilité des jetons et du coût des appels LLM (plafond journalier)
+pub mod prompts;             // Prompts nommés et versionnés (prompts/), tirage A/B et qualité par version
 pub mod strategist;          // Planification stratégique à long terme
 
 // ==================== MODULES D'ANALYSE ====================
 pub mod pattern_extractor;   // Extraction de patterns depuis le code
+pub mod syntax_tree;         // Éléments de code par tree-sitter (Rust, Python, Go, Solidity)
 pub mod knowledge;           // Base de connaissances accumulative
+pub mod knowledge_store;     // Stockage sled de la base de l'évolution de code (scan incrémental)
+pub mod knowledge_sync;      // Synchronisation du savoir entre instances parente et enfants
+pub mod embeddings;          // Plongements vectoriels et recherche sémantique des fragments
 pub mod explorer;            // Exploration de l'écosystème blockchain
 pub mod crawler;             // Collecte de données et d'inspirations
+pub mod license;             // Licences (SPDX) et provenance des sources explorées
 pub mod network_builder;     // Construction de réseaux et de connections
 
 // ==================== MODULES DE SÉCURITÉ ====================
@@ -53,13 +86,19 @@ pub mod security;            // Mesures de sécurité générales
 pub mod defense;             // Systèmes de défense actifs
 pub mod formal_verification; // Vérification formelle des processus
 pub mod rollback;            // Mécanismes de retour en arrière sécurisés
+pub mod audit;               // Journal d'audit chaîné des décisions autonomes
 pub mod alignment;           // Alignement des objectifs avec la sécurité
+pub mod alerting;            // Alertes opérateur (webhook, Slack, e-mail, stdout)
+pub mod resource_usage;      // Consommation CPU, mémoire et tâches par module
+pub mod secrets;             // Secrets (env, trousseau, fichier chiffré) et masquage des journaux
+pub mod config;              // Configuration unifiée (aurorae.toml, environnement, --set) et rechargement à chaud
+pub mod logging;             // Journal structuré (tracing): cibles par module, spans, JSON, filtre à chaud
 
 // ==================== MODULES D'OUTILS ====================
 pub mod rust_analyzer;       // Analyse statique du code Rust
 pub mod clippy_integration;  // Intégration de l'outil d'analyse Clippy
 pub mod refactor;            // Refactorisation automatique du code
-pub mod update_checker;      // Vérification des mises à jour disponibles
+pub mod update_checker;      // Mises à jour des dépendances (semver, porte, approbation)
 
 // ==================== MODULES NEUROSCIENTIFIQUES ====================
 pub mod cognitive_architecture; // Architecture inspirée des neurosciences
@@ -0,0 +1,217 @@
+//! AURORAE++ - license.rs
+//!
+//! Détection des licences (identifiants SPDX) des dépôts explorés et provenance des sources.
+//! Le crawler dépose à la racine de chaque dépôt extrait un fichier `.aurorae-provenance.json`
+//! (dépôt, commit, licence); chaque fragment appris en hérite, et la politique de `licenses.toml`
+//! écarte de la génération de code les sources dont la licence est refusée (GPL…).
+
+use std::fs;
+use std::path::Path;
+use tracing::warn;
+
+use serde::{Deserialize, Serialize};
+
+pub const PROVENANCE_FILE: &str = ".aurorae-provenance.json";
+pub const DEFAULT_LICENSE_POLICY_PATH: &str = "licenses.toml";
+
+/// Fichiers de la racine d'un dépôt examinés pour déterminer sa licence
+pub fn is_license_candidate(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    upper.starts_with("LICENSE") || upper.starts_with("LICENCE") || upper.starts_with("COPYING")
+        || name == "Cargo.toml" || name == "package.json"
+}
+
+/// Phrases caractéristiques des textes de licence, de la plus spécifique à la plus générale
+const SIGNATURES: &[(&str, &[&str])] = &[
+    ("AGPL-3.0", &["GNU AFFERO GENERAL PUBLIC LICENSE"]),
+    ("LGPL-3.0", &["GNU LESSER GENERAL PUBLIC LICENSE", "Version 3"]),
+    ("LGPL-2.1", &["GNU LESSER GENERAL PUBLIC LICENSE"]),
+    ("GPL-3.0", &["GNU GENERAL PUBLIC LICENSE", "Version 3"]),
+    ("GPL-2.0", &["GNU GENERAL PUBLIC LICENSE", "Version 2"]),
+    ("MPL-2.0", &["Mozilla Public License", "2.0"]),
+    ("Apache-2.0", &["Apache License", "Version 2.0"]),
+    ("Unlicense", &["This is free and unencumbered software released into the public domain"]),
+    ("ISC", &["Permission to use, copy, modify, and/or distribute this software for any purpose"]),
+    ("MIT", &["Permission is hereby granted, free of charge"]),
+    ("BSD-3-Clause", &["Redistribution and use in source and binary forms", "Neither the name"]),
+    ("BSD-2-Clause", &["Redistribution and use in source and binary forms"]),
+];
+
+/// Identifiant SPDX reconnu dans un texte de licence
+pub fn detect_text(text: &str) -> Option<&'static str> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    SIGNATURES.iter()
+        .find(|(_, phrases)| phrases.iter().all(|phrase| text.contains(phrase)))
+        .map(|(id, _)| *id)
+}
+
+/// Champ `license` déclaré par un manifeste (`Cargo.toml`, `package.json`)
+pub fn detect_manifest(name: &str, content: &str) -> Option<String> {
+    match name {
+        "Cargo.toml" => {
+            let manifest: toml::Value = toml::from_str(content).ok()?;
+            manifest.get("package")
+                .or_else(|| manifest.get("workspace").and_then(|w| w.get("package")))
+                .and_then(|package| package.get("license"))
+                .and_then(|license| license.as_str())
+                .map(str::to_string)
+        }
+        "package.json" => {
+            let manifest: serde_json::Value = serde_json::from_str(content).ok()?;
+            manifest.get("license").and_then(|license| license.as_str()).map(str::to_string)
+        }
+        _ => None,
+    }
+}
+
+/// Licence d'un dépôt d'après les fichiers de sa racine: le manifeste prime, sinon les textes
+/// reconnus sont combinés (`Apache-2.0 OR MIT` pour une double licence)
+pub fn detect<'a>(files: impl IntoIterator<Item = (&'a str, &'a str)>) -> Option<String> {
+    let mut texts = Vec::new();
+    for (name, content) in files {
+        if let Some(declared) = detect_manifest(name, content) {
+            return Some(declared);
+        }
+        if let Some(id) = detect_text(content) {
+            if !texts.contains(&id) {
+                texts.push(id);
+            }
+        }
+    }
+    texts.sort_unstable();
+    if texts.is_empty() { None } else { Some(texts.join(" OR ")) }
+}
+
+/// Origine d'une source apprise: dépôt, commit extrait et licence détectée
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceProvenance {
+    pub repo: String,
+    pub commit: String,
+    pub license: Option<String>,
+}
+
+impl SourceProvenance {
+    /// Écrit la provenance à la racine du dépôt extrait
+    pub fn save_in(&self, dir: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(dir.join(PROVENANCE_FILE), content).map_err(|e| format!("Erreur écriture provenance: {}", e))
+    }
+
+    /// Provenance écrite à la racine de `dir`, s'il y en a une
+    pub fn load_in(dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(dir.join(PROVENANCE_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Provenance du dépôt contenant `path` (fichier de provenance le plus proche en remontant)
+    pub fn find_for(path: &Path) -> Option<Self> {
+        path.ancestors()
+            .skip(1)
+            .find(|dir| dir.join(PROVENANCE_FILE).is_file())
+            .and_then(Self::load_in)
+    }
+}
+
+/// Licences admises dans la génération de code (`licenses.toml`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct LicensePolicy {
+    /// Préfixes SPDX refusés (`GPL` couvre `GPL-2.0`, `GPL-3.0-or-later`…)
+    pub deny: Vec<String>,
+    /// Sources sans licence détectée admises
+    pub allow_unknown: bool,
+}
+
+impl Default for LicensePolicy {
+    fn default() -> Self {
+        Self {
+            deny: vec!["GPL".to_string(), "AGPL".to_string()],
+            allow_unknown: true,
+        }
+    }
+}
+
+impl LicensePolicy {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Politique de licences invalide: {}", e))
+    }
+
+    /// `licenses.toml`, ou la politique par défaut s'il est absent ou invalide
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_LICENSE_POLICY_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: politique de licences par défaut", e);
+            Self::default()
+        })
+    }
+
+    fn denies(&self, id: &str) -> bool {
+        let id = id.trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace()).to_ascii_uppercase();
+        self.deny.iter().any(|prefix| id.starts_with(&prefix.to_ascii_uppercase()))
+    }
+
+    /// Vrai si une expression SPDX est admise: une alternative `OR` suffit, chaque terme
+    /// d'une conjonction `AND` doit l'être
+    pub fn allows(&self, license: Option<&str>) -> bool {
+        match license {
+            None => self.allow_unknown,
+            Some(expression) => expression
+                .split(" OR ")
+                .any(|alternative| alternative.split(" AND ").all(|term| !self.denies(term))),
+        }
+    }
+
+    /// Vrai si une source de cette provenance peut inspirer du code généré
+    pub fn admits(&self, provenance: Option<&SourceProvenance>) -> bool {
+        self.allows(provenance.and_then(|p| p.license.as_deref()))
+    }
+
+    /// Vrai si le fichier `path` (dans un dépôt extrait par le crawler) peut inspirer du code généré
+    pub fn admits_path(&self, path: &Path) -> bool {
+        self.admits(SourceProvenance::find_for(path).as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_spdx_identifiers_from_texts_and_manifests() {
+        let mit = "MIT License\n\nPermission is hereby granted, free of charge, to any person";
+        let apache = "Apache License\n                           Version 2.0, January 2004";
+        let gpl = "GNU GENERAL PUBLIC LICENSE\n Version 3, 29 June 2007";
+        let lgpl = "GNU LESSER GENERAL PUBLIC LICENSE\n Version 3, 29 June 2007";
+        assert_eq!(detect_text(mit), Some("MIT"));
+        assert_eq!(detect_text(gpl), Some("GPL-3.0"));
+        assert_eq!(detect_text(lgpl), Some("LGPL-3.0"));
+        assert_eq!(detect([("LICENSE-MIT", mit), ("LICENSE-APACHE", apache)]).as_deref(), Some("Apache-2.0 OR MIT"));
+        let cargo = "[package]\nname = \"demo\"\nlicense = \"MPL-2.0\"\n";
+        assert_eq!(detect([("COPYING", gpl), ("Cargo.toml", cargo)]).as_deref(), Some("MPL-2.0"));
+        assert_eq!(detect([("README.md", "# demo")]), None);
+    }
+
+    #[test]
+    fn deny_list_applies_to_spdx_expressions() {
+        let policy = LicensePolicy::from_toml("deny = [\"GPL\", \"AGPL\"]\nallow-unknown = false").unwrap();
+        assert!(policy.allows(Some("MIT")));
+        assert!(policy.allows(Some("LGPL-2.1")));
+        assert!(!policy.allows(Some("GPL-3.0-or-later")));
+        assert!(policy.allows(Some("MIT OR GPL-2.0")));
+        assert!(!policy.allows(Some("(MIT AND GPL-2.0)")));
+        assert!(!policy.allows(None));
+
+        let dir = std::env::temp_dir().join(format!("aurorae-license-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("src/nested")).unwrap();
+        let provenance = SourceProvenance { repo: "demo".into(), commit: "abc".into(), license: Some("GPL-2.0".into()) };
+        provenance.save_in(&dir).unwrap();
+        let found = SourceProvenance::find_for(&dir.join("src/nested/lib.rs"));
+        assert_eq!(found.as_ref(), Some(&provenance));
+        assert!(!policy.admits(found.as_ref()));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
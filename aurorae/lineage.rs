@@ -0,0 +1,311 @@
+//! AURORAE++ - lineage.rs
+//!
+//! Généalogie commune des génomes de l'EvolutionEngine et des instances du ReproductionEngine.
+//! Chaque nœud retient ses parents, ses capacités (modules actifs ou hérités) et celles qu'il a
+//! introduites par rapport à ses parents, ce qui permet de répondre à « quel ancêtre a apporté
+//! la capacité X ». L'arbre est persisté dans `aurorae_state/lineage.json` et s'exporte en DOT
+//! (Graphviz) ou en JSON (nœuds et arêtes) pour visualisation.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::fs::{self, create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+use tracing::info;
+
+use crate::genome::{GeneticOrigin, Genome};
+use crate::reproduction::AuroraInstance;
+
+pub const LINEAGE_PATH: &str = "aurorae_state/lineage.json";
+
+lazy_static! {
+    pub static ref LINEAGE: Mutex<LineageStore> = Mutex::new(LineageStore::open(Path::new(LINEAGE_PATH)));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineageKind {
+    Genome,
+    Instance,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineageNode {
+    pub id: Uuid,
+    pub kind: LineageKind,
+    pub label: String,
+    pub generation: u32,
+    pub created_at: String,
+    pub parents: Vec<Uuid>,
+    pub capabilities: BTreeSet<String>,
+    /// Capacités absentes de tous les parents connus
+    pub introduced: BTreeSet<String>,
+    /// Opérateur ou but à l'origine du nœud
+    pub origin: String,
+}
+
+/// Format d'export, déduit de l'extension (`.dot`/`.gv` ou JSON)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineageFormat {
+    Dot,
+    Json,
+}
+
+impl LineageFormat {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("dot") | Some("gv") => Self::Dot,
+            _ => Self::Json,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LineageStore {
+    nodes: BTreeMap<Uuid, LineageNode>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl LineageStore {
+    /// Généalogie en mémoire seulement
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Généalogie rechargée depuis `path`, puis sauvegardée à chaque ajout
+    pub fn open(path: &Path) -> Self {
+        let mut store: Self = File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        store.path = Some(path.to_path_buf());
+        store
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node(&self, id: &Uuid) -> Option<&LineageNode> {
+        self.nodes.get(id)
+    }
+
+    /// Ajoute (ou remplace) un nœud; les capacités introduites sont calculées d'après les parents
+    pub fn record(&mut self, mut node: LineageNode) {
+        let inherited: BTreeSet<&String> = node.parents.iter()
+            .filter_map(|parent| self.nodes.get(parent))
+            .flat_map(|parent| parent.capabilities.iter())
+            .collect();
+        node.introduced = node.capabilities.iter().filter(|c| !inherited.contains(c)).cloned().collect();
+        self.nodes.insert(node.id, node);
+        self.save();
+    }
+
+    /// Génome: ses parents sont ses ancêtres directs, ses capacités ses modules actifs
+    pub fn record_genome(&mut self, genome: &Genome) {
+        let origin = match &genome.origin {
+            GeneticOrigin::Base => "base".to_string(),
+            GeneticOrigin::Crossover { operator, seed } => format!("croisement {:?} (graine {})", operator, seed),
+            GeneticOrigin::Mutation { seed, mutated } => format!("mutation de {} gène(s) (graine {})", mutated.len(), seed),
+        };
+        self.record(LineageNode {
+            id: genome.id,
+            kind: LineageKind::Genome,
+            label: format!("génome {}", &genome.id.to_string()[..8]),
+            generation: genome.generation,
+            created_at: genome.birth.clone(),
+            parents: genome.ancestry.clone(),
+            capabilities: genome.enabled_modules().into_iter().map(str::to_string).collect(),
+            introduced: BTreeSet::new(),
+            origin,
+        });
+    }
+
+    /// Instance: son parent est l'instance qui l'a engendrée, ses capacités ses modules hérités
+    pub fn record_instance(&mut self, instance: &AuroraInstance) {
+        self.record(LineageNode {
+            id: instance.id,
+            kind: LineageKind::Instance,
+            label: instance.purpose.clone(),
+            generation: instance.generation,
+            created_at: instance.created_at.clone(),
+            parents: instance.parent_id.into_iter().collect(),
+            capabilities: instance.inherited_modules.iter().cloned().collect(),
+            introduced: BTreeSet::new(),
+            origin: "reproduction".to_string(),
+        });
+    }
+
+    /// Ancêtres connus, du plus proche au plus lointain
+    pub fn ancestors(&self, id: &Uuid) -> Vec<&LineageNode> {
+        let mut seen = HashSet::new();
+        let mut queue: VecDeque<Uuid> = self.nodes.get(id).map(|n| n.parents.iter().copied().collect()).unwrap_or_default();
+        let mut ancestors = Vec::new();
+        while let Some(next) = queue.pop_front() {
+            if !seen.insert(next) {
+                continue;
+            }
+            if let Some(node) = self.nodes.get(&next) {
+                queue.extend(node.parents.iter().copied());
+                ancestors.push(node);
+            }
+        }
+        ancestors
+    }
+
+    pub fn descendants(&self, id: &Uuid) -> Vec<&LineageNode> {
+        let mut seen = HashSet::from([*id]);
+        let mut queue = VecDeque::from([*id]);
+        let mut descendants = Vec::new();
+        while let Some(current) = queue.pop_front() {
+            for node in self.nodes.values().filter(|n| n.parents.contains(&current)) {
+                if seen.insert(node.id) {
+                    queue.push_back(node.id);
+                    descendants.push(node);
+                }
+            }
+        }
+        descendants
+    }
+
+    /// Nœud (lui-même ou son ancêtre le plus proche) qui a introduit `capability` dans la lignée de `id`
+    pub fn introduced_by(&self, id: &Uuid, capability: &str) -> Option<&LineageNode> {
+        self.nodes.get(id)
+            .into_iter()
+            .chain(self.ancestors(id))
+            .find(|node| node.introduced.contains(capability))
+    }
+
+    /// Tous les nœuds ayant introduit `capability`, par génération
+    pub fn origins_of(&self, capability: &str) -> Vec<&LineageNode> {
+        let mut origins: Vec<&LineageNode> = self.nodes.values().filter(|n| n.introduced.contains(capability)).collect();
+        origins.sort_by_key(|n| n.generation);
+        origins
+    }
+
+    /// Graphe Graphviz: génomes en ellipses, instances en boîtes, capacités introduites en étiquette
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph lineage {\n    rankdir=TB;\n");
+        for node in self.nodes.values() {
+            let shape = match node.kind {
+                LineageKind::Genome => "ellipse",
+                LineageKind::Instance => "box",
+            };
+            let mut label = format!("{}\\ngén. {}", node.label.replace('"', "'"), node.generation);
+            if !node.introduced.is_empty() {
+                label.push_str(&format!("\\n+ {}", node.introduced.iter().cloned().collect::<Vec<_>>().join(", ")));
+            }
+            dot.push_str(&format!("    \"{}\" [shape={}, label=\"{}\"];\n", node.id, shape, label));
+        }
+        for node in self.nodes.values() {
+            for parent in node.parents.iter().filter(|p| self.nodes.contains_key(p)) {
+                dot.push_str(&format!("    \"{}\" -> \"{}\";\n", parent, node.id));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Nœuds et arêtes parent → enfant
+    pub fn to_json(&self) -> serde_json::Value {
+        let edges: Vec<serde_json::Value> = self.nodes.values()
+            .flat_map(|node| node.parents.iter().map(move |parent| json!({ "from": parent, "to": node.id })))
+            .collect();
+        json!({ "nodes": self.nodes.values().collect::<Vec<_>>(), "edges": edges })
+    }
+
+    pub fn export(&self, format: LineageFormat, output: &Path) -> Result<(), String> {
+        let content = match format {
+            LineageFormat::Dot => self.to_dot(),
+            LineageFormat::Json => serde_json::to_string_pretty(&self.to_json()).map_err(|e| e.to_string())?,
+        };
+        fs::write(output, content).map_err(|e| format!("Erreur écriture {}: {}", output.display(), e))?;
+        info!("🌳 Généalogie exportée: {} nœud(s) → {}", self.nodes.len(), output.display());
+        Ok(())
+    }
+
+    /// 💾 Sauvegarde (si la généalogie est adossée à un fichier)
+    pub fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{CrossoverOperator, GenomeBuilder};
+
+    #[test]
+    fn finds_the_ancestor_that_introduced_a_capability() {
+        let mut store = LineageStore::in_memory();
+        let base = GenomeBuilder::base_genome();
+        let mut other = GenomeBuilder::base_genome().with_module("oracle", true, 5);
+        other.ancestry = vec![base.id];
+        other.generation = 1;
+        let child = Genome::crossover(&base, &other, CrossoverOperator::Uniform, 9);
+        for genome in [&base, &other, &child] {
+            store.record_genome(genome);
+        }
+
+        assert!(store.node(&base.id).unwrap().introduced.contains("brain"));
+        assert_eq!(store.node(&other.id).unwrap().introduced, BTreeSet::from(["oracle".to_string()]));
+        assert!(store.node(&child.id).unwrap().introduced.is_empty());
+        assert_eq!(store.introduced_by(&child.id, "oracle").map(|n| n.id), Some(other.id));
+        assert_eq!(store.introduced_by(&child.id, "brain").map(|n| n.id), Some(base.id));
+        assert_eq!(store.ancestors(&child.id).len(), 2);
+        assert_eq!(store.descendants(&base.id).len(), 2);
+    }
+
+    #[test]
+    fn instances_join_the_tree_and_export_to_dot_and_json() {
+        let dir = std::env::temp_dir().join(format!("aurorae-lineage-{}", Uuid::new_v4()));
+        let path = dir.join("lineage.json");
+        let parent = AuroraInstance {
+            id: Uuid::new_v4(),
+            parent_id: None,
+            created_at: "2024-01-01T00:00:00Z".into(),
+            purpose: "Clone \"Alpha\"".into(),
+            inherited_modules: vec!["dream".into()],
+            generation: 1,
+            is_active: true,
+        };
+        let child = AuroraInstance {
+            id: Uuid::new_v4(),
+            parent_id: Some(parent.id),
+            purpose: "Clone Beta".into(),
+            inherited_modules: vec!["dream".into(), "crawler".into()],
+            generation: 2,
+            ..parent.clone()
+        };
+        {
+            let mut store = LineageStore::open(&path);
+            store.record_instance(&parent);
+            store.record_instance(&child);
+        }
+
+        let store = LineageStore::open(&path);
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.origins_of("crawler").iter().map(|n| n.id).collect::<Vec<_>>(), vec![child.id]);
+        let dot = store.to_dot();
+        assert!(dot.contains(&format!("\"{}\" -> \"{}\"", parent.id, child.id)));
+        assert!(dot.contains("Clone 'Alpha'"));
+        assert_eq!(store.to_json()["edges"].as_array().unwrap().len(), 1);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
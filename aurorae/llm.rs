@@ -0,0 +1,648 @@
+//! AURORAE++ - llm.rs
+//!
+//! Abstraction des modèles de langage: `LlmProvider` est implémenté pour OpenAI (dans
+//! openai.rs), Azure OpenAI, Anthropic et les serveurs locaux (Ollama, serveur llama.cpp).
+//! Chaque requête peut choisir son modèle; `llm.toml` désigne le fournisseur, son modèle par
+//! défaut et le modèle de chaque usage (stratège, rêves, génération de code).
+//! Une requête peut imposer une fonction (`LlmFunction`, schéma JSON): les arguments
+//! structurés reviennent dans `LlmResponse::arguments` (appel d'outil OpenAI/Azure/Anthropic,
+//! sortie JSON contrainte pour Ollama).
+//! Les mêmes fournisseurs produisent les plongements (`EmbeddingProvider`, section
+//! `[embeddings]`), servis par lots avec limite de débit; Anthropic n'en proposant pas, le
+//! modèle local par hachage prend alors le relais.
+
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::warn;
+
+use crate::llm_cache::CacheConfig;
+use crate::llm_usage::ModelPricing;
+use crate::embeddings::LocalEmbedder;
+use crate::openai::{BatchedEmbedder, EmbeddingProvider, OpenAIBridge};
+use crate::secrets::secret;
+
+pub const DEFAULT_LLM_CONFIG_PATH: &str = "llm.toml";
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const OLLAMA_URL: &str = "http://localhost:11434";
+const LLAMA_CPP_URL: &str = "http://localhost:8080";
+
+/// Fonction que le modèle doit appeler; `parameters` est un schéma JSON
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmFunction {
+    pub name: String,
+    pub description: String,
+    pub parameters: Value,
+}
+
+impl LlmFunction {
+    pub fn new(name: &str, description: &str, parameters: Value) -> Self {
+        Self { name: name.to_string(), description: description.to_string(), parameters }
+    }
+}
+
+/// Requête de complétion; `model` remplace le modèle par défaut du fournisseur
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmRequest {
+    pub system: String,
+    pub prompt: String,
+    pub max_tokens: u32,
+    pub temperature: f32,
+    pub model: Option<String>,
+    /// Usage à l'origine de l'appel (`strategist`, `dream`, `generator`), pour la comptabilité
+    pub purpose: Option<String>,
+    /// Réponse structurée imposée
+    pub function: Option<LlmFunction>,
+    /// Ignore le cache de réponses (consultation qui doit être fraîche)
+    pub bypass_cache: bool,
+}
+
+impl LlmRequest {
+    pub fn new(system: &str, prompt: &str) -> Self {
+        Self {
+            system: system.to_string(),
+            prompt: prompt.to_string(),
+            max_tokens: 512,
+            temperature: 0.7,
+            model: None,
+            purpose: None,
+            function: None,
+            bypass_cache: false,
+        }
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    pub fn with_model(mut self, model: Option<&str>) -> Self {
+        self.model = model.map(str::to_string);
+        self
+    }
+
+    pub fn with_purpose(mut self, purpose: &str) -> Self {
+        self.purpose = Some(purpose.to_string());
+        self
+    }
+
+    pub fn with_function(mut self, function: LlmFunction) -> Self {
+        self.function = Some(function);
+        self
+    }
+
+    pub fn with_bypass_cache(mut self) -> Self {
+        self.bypass_cache = true;
+        self
+    }
+}
+
+/// Réponse d'un modèle et jetons consommés (si le fournisseur les rapporte)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LlmResponse {
+    pub text: String,
+    pub model: String,
+    pub prompt_tokens: Option<u32>,
+    pub completion_tokens: Option<u32>,
+    /// Arguments de la fonction imposée, None si le modèle a répondu en texte libre
+    pub arguments: Option<Value>,
+}
+
+/// Fournisseur de complétion (OpenAI, Azure, Anthropic, modèle local, doublure de test…)
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    fn name(&self) -> &str;
+    /// Modèle utilisé quand la requête n'en choisit pas
+    fn default_model(&self) -> &str;
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, String>;
+
+    fn model_for<'a>(&'a self, request: &'a LlmRequest) -> &'a str {
+        request.model.as_deref().unwrap_or(self.default_model())
+    }
+}
+
+#[async_trait]
+impl<T: LlmProvider + ?Sized> LlmProvider for Arc<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn default_model(&self) -> &str {
+        (**self).default_model()
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, String> {
+        (**self).complete(request).await
+    }
+}
+
+/// Envoie un corps JSON; les erreurs HTTP remontent avec le message du fournisseur
+pub(crate) async fn post_json(request: RequestBuilder, body: &Value) -> Result<Value, String> {
+    let response = request.json(body).send().await.map_err(|e| format!("Erreur réseau: {}", e))?;
+    let status = response.status();
+    let data: Value = response.json().await.map_err(|e| format!("Erreur JSON: {}", e))?;
+    if !status.is_success() {
+        let message = data["error"]["message"].as_str().or_else(|| data["error"].as_str()).unwrap_or("réponse invalide");
+        return Err(format!("Erreur {}: {}", status, message));
+    }
+    Ok(data)
+}
+
+/// Corps au format chat/completions (OpenAI, Azure, llama.cpp); Azure déduit le modèle de l'URL
+pub(crate) fn chat_body(request: &LlmRequest, model: Option<&str>) -> Value {
+    let mut body = json!({
+        "messages": [
+            {"role": "system", "content": request.system},
+            {"role": "user", "content": request.prompt}
+        ],
+        "max_tokens": request.max_tokens,
+        "temperature": request.temperature
+    });
+    if let Some(model) = model {
+        body["model"] = json!(model);
+    }
+    if let Some(function) = &request.function {
+        body["tools"] = json!([{
+            "type": "function",
+            "function": {"name": function.name, "description": function.description, "parameters": function.parameters}
+        }]);
+        body["tool_choice"] = json!({"type": "function", "function": {"name": function.name}});
+    }
+    body
+}
+
+pub(crate) fn parse_chat(data: &Value, model: &str) -> Result<LlmResponse, String> {
+    let message = &data["choices"][0]["message"];
+    // Les arguments d'un appel d'outil arrivent sérialisés en chaîne JSON
+    let arguments = message["tool_calls"][0]["function"]["arguments"].as_str()
+        .map(|raw| serde_json::from_str::<Value>(raw).map_err(|e| format!("Arguments de fonction invalides: {}", e)))
+        .transpose()?;
+    let text = match message["content"].as_str() {
+        Some(text) => text,
+        None if arguments.is_some() => "",
+        None => return Err("Aucune réponse valide obtenue".to_string()),
+    };
+    Ok(LlmResponse {
+        text: text.to_string(),
+        model: data["model"].as_str().unwrap_or(model).to_string(),
+        prompt_tokens: data["usage"]["prompt_tokens"].as_u64().map(|n| n as u32),
+        completion_tokens: data["usage"]["completion_tokens"].as_u64().map(|n| n as u32),
+        arguments,
+    })
+}
+
+/// Réponse `data[].embedding` (OpenAI, Azure, llama.cpp)
+pub(crate) fn parse_embeddings(data: &Value, expected: usize) -> Result<Vec<Vec<f32>>, String> {
+    let vectors: Vec<Vec<f32>> = data["data"].as_array()
+        .ok_or("Aucun plongement obtenu")?
+        .iter()
+        .map(|item| item["embedding"].as_array().map(|v| v.iter().filter_map(|x| x.as_f64()).map(|x| x as f32).collect()))
+        .collect::<Option<_>>()
+        .ok_or("Plongement invalide")?;
+    if vectors.len() != expected {
+        return Err(format!("{} plongement(s) pour {} texte(s)", vectors.len(), expected));
+    }
+    Ok(vectors)
+}
+
+fn parse_anthropic(data: &Value, model: &str) -> Result<LlmResponse, String> {
+    let blocks = data["content"].as_array().ok_or("Aucune réponse valide obtenue")?;
+    let text: String = blocks.iter()
+        .filter(|block| block["type"] == "text")
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    let arguments = blocks.iter().find(|block| block["type"] == "tool_use").map(|block| block["input"].clone());
+    Ok(LlmResponse {
+        text,
+        arguments,
+        model: data["model"].as_str().unwrap_or(model).to_string(),
+        prompt_tokens: data["usage"]["input_tokens"].as_u64().map(|n| n as u32),
+        completion_tokens: data["usage"]["output_tokens"].as_u64().map(|n| n as u32),
+    })
+}
+
+/// Avec une fonction imposée, le contenu est le JSON contraint par `format`
+fn parse_ollama(data: &Value, model: &str, structured: bool) -> Result<LlmResponse, String> {
+    let text = data["message"]["content"].as_str().ok_or("Aucune réponse valide obtenue")?;
+    let arguments = if structured {
+        Some(serde_json::from_str::<Value>(text).map_err(|e| format!("Sortie JSON invalide: {}", e))?)
+    } else {
+        None
+    };
+    Ok(LlmResponse {
+        text: text.to_string(),
+        arguments,
+        model: data["model"].as_str().unwrap_or(model).to_string(),
+        prompt_tokens: data["prompt_eval_count"].as_u64().map(|n| n as u32),
+        completion_tokens: data["eval_count"].as_u64().map(|n| n as u32),
+    })
+}
+
+/// Azure OpenAI: le modèle est le nom du déploiement
+pub struct AzureOpenAIProvider {
+    pub endpoint: String,
+    pub api_key: String,
+    pub deployment: String,
+    /// Déploiement du modèle de plongement
+    pub embedding_deployment: String,
+    pub api_version: String,
+    pub client: Client,
+}
+
+impl AzureOpenAIProvider {
+    pub fn new(endpoint: &str, api_key: &str, deployment: &str) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            deployment: deployment.to_string(),
+            embedding_deployment: "text-embedding-3-small".to_string(),
+            api_version: "2024-02-01".to_string(),
+            client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for AzureOpenAIProvider {
+    fn name(&self) -> &str {
+        "azure"
+    }
+
+    fn default_model(&self) -> &str {
+        &self.deployment
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, String> {
+        let deployment = self.model_for(request);
+        let url = format!("{}/openai/deployments/{}/chat/completions?api-version={}", self.endpoint, deployment, self.api_version);
+        let data = post_json(self.client.post(&url).header("api-key", &self.api_key), &chat_body(request, None)).await?;
+        parse_chat(&data, deployment)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for AzureOpenAIProvider {
+    fn name(&self) -> &str {
+        &self.embedding_deployment
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let url = format!("{}/openai/deployments/{}/embeddings?api-version={}", self.endpoint, self.embedding_deployment, self.api_version);
+        let data = post_json(self.client.post(&url).header("api-key", &self.api_key), &json!({ "input": texts })).await?;
+        parse_embeddings(&data, texts.len())
+    }
+}
+
+pub struct AnthropicProvider {
+    pub api_key: String,
+    pub model: String,
+    pub api_url: String,
+    pub client: Client,
+}
+
+impl AnthropicProvider {
+    pub fn new(api_key: &str, model: &str) -> Self {
+        Self { api_key: api_key.to_string(), model: model.to_string(), api_url: ANTHROPIC_API_URL.to_string(), client: Client::new() }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for AnthropicProvider {
+    fn name(&self) -> &str {
+        "anthropic"
+    }
+
+    fn default_model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, String> {
+        let model = self.model_for(request);
+        let mut body = json!({
+            "model": model,
+            "system": request.system,
+            "max_tokens": request.max_tokens,
+            "temperature": request.temperature,
+            "messages": [{"role": "user", "content": request.prompt}]
+        });
+        if let Some(function) = &request.function {
+            body["tools"] = json!([{"name": function.name, "description": function.description, "input_schema": function.parameters}]);
+            body["tool_choice"] = json!({"type": "tool", "name": function.name});
+        }
+        let builder = self.client.post(&self.api_url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION);
+        parse_anthropic(&post_json(builder, &body).await?, model)
+    }
+}
+
+/// Serveur de modèles local
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalServer {
+    /// API `/api/chat` d'Ollama
+    Ollama,
+    /// API compatible OpenAI du serveur llama.cpp
+    LlamaCpp,
+}
+
+pub struct LocalProvider {
+    pub server: LocalServer,
+    pub base_url: String,
+    pub model: String,
+    pub embedding_model: String,
+    pub client: Client,
+}
+
+impl LocalProvider {
+    pub fn new(server: LocalServer, base_url: &str, model: &str) -> Self {
+        Self {
+            server,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            embedding_model: "nomic-embed-text".to_string(),
+            client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for LocalProvider {
+    fn name(&self) -> &str {
+        match self.server {
+            LocalServer::Ollama => "ollama",
+            LocalServer::LlamaCpp => "llamacpp",
+        }
+    }
+
+    fn default_model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, String> {
+        let model = self.model_for(request);
+        match self.server {
+            LocalServer::Ollama => {
+                let mut body = json!({
+                    "model": model,
+                    "stream": false,
+                    "messages": [
+                        {"role": "system", "content": request.system},
+                        {"role": "user", "content": request.prompt}
+                    ],
+                    "options": {"num_predict": request.max_tokens, "temperature": request.temperature}
+                });
+                if let Some(function) = &request.function {
+                    body["format"] = function.parameters.clone();
+                }
+                let data = post_json(self.client.post(format!("{}/api/chat", self.base_url)), &body).await?;
+                parse_ollama(&data, model, request.function.is_some())
+            }
+            LocalServer::LlamaCpp => {
+                let url = format!("{}/v1/chat/completions", self.base_url);
+                parse_chat(&post_json(self.client.post(url), &chat_body(request, Some(model))).await?, model)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for LocalProvider {
+    fn name(&self) -> &str {
+        &self.embedding_model
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let body = json!({ "model": self.embedding_model, "input": texts });
+        match self.server {
+            LocalServer::Ollama => {
+                let data = post_json(self.client.post(format!("{}/api/embed", self.base_url)), &body).await?;
+                let vectors: Vec<Vec<f32>> = serde_json::from_value(data["embeddings"].clone())
+                    .map_err(|e| format!("Plongement invalide: {}", e))?;
+                if vectors.len() != texts.len() {
+                    return Err(format!("{} plongement(s) pour {} texte(s)", vectors.len(), texts.len()));
+                }
+                Ok(vectors)
+            }
+            LocalServer::LlamaCpp => {
+                let data = post_json(self.client.post(format!("{}/v1/embeddings", self.base_url)), &body).await?;
+                parse_embeddings(&data, texts.len())
+            }
+        }
+    }
+}
+
+/// Section `[embeddings]` de `llm.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct EmbeddingConfig {
+    /// Modèle (déploiement pour Azure); celui du fournisseur par défaut sinon
+    pub model: Option<String>,
+    /// Textes par requête
+    pub batch_size: usize,
+    /// Nouvelles tentatives sur erreur transitoire (réseau, 429, 5xx)
+    pub max_retries: u32,
+    /// 0 pour ne pas limiter le débit
+    pub requests_per_minute: u32,
+}
+
+impl Default for EmbeddingConfig {
+    fn default() -> Self {
+        Self { model: None, batch_size: 64, max_retries: 3, requests_per_minute: 60 }
+    }
+}
+
+/// Fournisseur et modèles (`llm.toml`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct LlmConfig {
+    /// `openai`, `azure`, `anthropic`, `ollama` ou `llamacpp`
+    pub provider: String,
+    /// Modèle par défaut (déploiement pour Azure)
+    pub model: Option<String>,
+    /// Point d'accès Azure ou du serveur local
+    pub base_url: Option<String>,
+    pub api_version: Option<String>,
+    /// Modèle par usage (`strategist`, `dream`, `generator`)
+    pub models: HashMap<String, String>,
+    /// Tarifs par modèle, en complément de la table par défaut de llm_usage.rs
+    pub pricing: HashMap<String, ModelPricing>,
+    /// Cache des réponses et rejeu hors ligne
+    pub cache: CacheConfig,
+    pub embeddings: EmbeddingConfig,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            provider: "openai".to_string(),
+            model: None,
+            base_url: None,
+            api_version: None,
+            models: HashMap::new(),
+            pricing: HashMap::new(),
+            cache: CacheConfig::default(),
+            embeddings: EmbeddingConfig::default(),
+        }
+    }
+}
+
+impl LlmConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Configuration LLM invalide: {}", e))
+    }
+
+    /// `llm.toml`, ou OpenAI par défaut s'il est absent ou invalide
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_LLM_CONFIG_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: fournisseur LLM par défaut (OpenAI)", e);
+            Self::default()
+        })
+    }
+
+    /// Modèle choisi pour un usage, None pour le modèle par défaut du fournisseur
+    pub fn model_for(&self, purpose: &str) -> Option<&str> {
+        self.models.get(purpose).map(String::as_str)
+    }
+
+    fn key(name: &str) -> Result<String, String> {
+        secret("llm", name).map(|handle| handle.expose().to_string()).ok_or_else(|| format!("Clé {} indisponible", name))
+    }
+
+    /// Fournisseur configuré; les clés d'API sont lues dans le magasin de secrets
+    pub fn provider(&self) -> Result<Arc<dyn LlmProvider>, String> {
+        let model = |default: &str| self.model.clone().unwrap_or_else(|| default.to_string());
+        Ok(match self.provider.as_str() {
+            "openai" => {
+                let mut bridge = OpenAIBridge::new(&Self::key("OPENAI_API_KEY")?);
+                bridge.model = model(&bridge.model);
+                Arc::new(bridge)
+            }
+            "azure" => {
+                let endpoint = self.base_url.as_deref().ok_or("base-url requis pour Azure OpenAI")?;
+                let deployment = self.model.as_deref().ok_or("model (déploiement) requis pour Azure OpenAI")?;
+                let mut provider = AzureOpenAIProvider::new(endpoint, &Self::key("AZURE_OPENAI_API_KEY")?, deployment);
+                if let Some(version) = &self.api_version {
+                    provider.api_version = version.clone();
+                }
+                Arc::new(provider)
+            }
+            "anthropic" => Arc::new(AnthropicProvider::new(&Self::key("ANTHROPIC_API_KEY")?, &model("claude-3-5-sonnet-latest"))),
+            "ollama" => Arc::new(LocalProvider::new(LocalServer::Ollama, self.base_url.as_deref().unwrap_or(OLLAMA_URL), &model("llama3"))),
+            "llamacpp" => Arc::new(LocalProvider::new(LocalServer::LlamaCpp, self.base_url.as_deref().unwrap_or(LLAMA_CPP_URL), &model("local"))),
+            other => return Err(format!("Fournisseur LLM inconnu: {}", other)),
+        })
+    }
+
+    /// Plongements du fournisseur configuré, par lots et à débit limité; le modèle local par
+    /// hachage sert de repli (Anthropic, clé absente)
+    pub fn embedder(&self) -> Arc<dyn EmbeddingProvider> {
+        let remote = self.remote_embedder().unwrap_or_else(|e| {
+            warn!("⚠️ Plongements {} indisponibles ({}): modèle local", self.provider, e);
+            None
+        });
+        match remote {
+            Some(provider) => {
+                let config = &self.embeddings;
+                Arc::new(BatchedEmbedder::new(provider, config.batch_size, config.max_retries, config.requests_per_minute))
+            }
+            None => Arc::new(LocalEmbedder::default()),
+        }
+    }
+
+    fn remote_embedder(&self) -> Result<Option<Arc<dyn EmbeddingProvider>>, String> {
+        let model = self.embeddings.model.clone();
+        Ok(match self.provider.as_str() {
+            "openai" => {
+                let mut bridge = OpenAIBridge::new(&Self::key("OPENAI_API_KEY")?);
+                if let Some(model) = model {
+                    bridge.embedding_model = model;
+                }
+                Some(Arc::new(bridge))
+            }
+            "azure" => {
+                let endpoint = self.base_url.as_deref().ok_or("base-url requis pour Azure OpenAI")?;
+                let mut provider = AzureOpenAIProvider::new(endpoint, &Self::key("AZURE_OPENAI_API_KEY")?, self.model.as_deref().unwrap_or_default());
+                if let Some(version) = &self.api_version {
+                    provider.api_version = version.clone();
+                }
+                if let Some(model) = model {
+                    provider.embedding_deployment = model;
+                }
+                Some(Arc::new(provider))
+            }
+            "ollama" | "llamacpp" => {
+                let (server, url) = match self.provider.as_str() {
+                    "ollama" => (LocalServer::Ollama, OLLAMA_URL),
+                    _ => (LocalServer::LlamaCpp, LLAMA_CPP_URL),
+                };
+                let mut provider = LocalProvider::new(server, self.base_url.as_deref().unwrap_or(url), self.model.as_deref().unwrap_or("local"));
+                if let Some(model) = model {
+                    provider.embedding_model = model;
+                }
+                Some(Arc::new(provider))
+            }
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_each_provider_response_format() {
+        let chat = json!({"model": "gpt-4o", "choices": [{"message": {"content": "apprendre"}}], "usage": {"prompt_tokens": 12, "completion_tokens": 3}});
+        let parsed = parse_chat(&chat, "défaut").unwrap();
+        assert_eq!((parsed.text.as_str(), parsed.model.as_str(), parsed.prompt_tokens), ("apprendre", "gpt-4o", Some(12)));
+
+        let anthropic = json!({"content": [{"type": "text", "text": "observer"}], "usage": {"input_tokens": 20, "output_tokens": 2}});
+        let parsed = parse_anthropic(&anthropic, "claude").unwrap();
+        assert_eq!((parsed.text.as_str(), parsed.model.as_str(), parsed.completion_tokens), ("observer", "claude", Some(2)));
+
+        let ollama = json!({"model": "llama3", "message": {"content": "créer"}, "prompt_eval_count": 9, "eval_count": 1});
+        assert_eq!(parse_ollama(&ollama, "llama3", false).unwrap().text, "créer");
+        assert!(parse_chat(&json!({"error": {}}), "m").is_err());
+
+        let tool_call = json!({"choices": [{"message": {"content": null, "tool_calls": [{"function": {"name": "choisir", "arguments": "{\"intent\": \"Observe\"}"}}]}}]});
+        assert_eq!(parse_chat(&tool_call, "gpt-4o").unwrap().arguments, Some(json!({"intent": "Observe"})));
+        let tool_use = json!({"content": [{"type": "tool_use", "name": "choisir", "input": {"intent": "Defend"}}]});
+        assert_eq!(parse_anthropic(&tool_use, "claude").unwrap().arguments, Some(json!({"intent": "Defend"})));
+
+        let request = LlmRequest::new("système", "question").with_model(Some("gpt-4o-mini"));
+        assert_eq!(chat_body(&request, Some("gpt-4o-mini"))["model"], "gpt-4o-mini");
+        assert!(chat_body(&request, None).get("model").is_none());
+        let forced = request.with_function(LlmFunction::new("choisir", "Choix", json!({"type": "object"})));
+        assert_eq!(chat_body(&forced, None)["tool_choice"]["function"]["name"], "choisir");
+    }
+
+    #[test]
+    fn config_selects_models_per_purpose() {
+        let config = LlmConfig::from_toml("provider = \"ollama\"\nmodel = \"mistral\"\n\n[models]\nstrategist = \"llama3\"\n").unwrap();
+        assert_eq!(config.model_for("strategist"), Some("llama3"));
+        assert_eq!(config.model_for("dream"), None);
+        assert!(config.cache.enabled);
+        let provider = config.provider().unwrap();
+        assert_eq!((provider.name(), provider.default_model()), ("ollama", "mistral"));
+        assert!(LlmConfig::from_toml("provider = \"inconnu\"").unwrap().provider().is_err());
+
+        let embedder = LlmConfig::from_toml("provider = \"anthropic\"\n\n[embeddings]\nbatch-size = 8\n").unwrap().embedder();
+        assert_eq!(embedder.name(), "local-hashing");
+    }
+}
@@ -0,0 +1,303 @@
+//! AURORAE++ - llm_cache.rs
+//!
+//! Cache adressé par contenu des réponses des modèles de langage: la clé est l'empreinte
+//! SHA-256 de la requête (fournisseur, modèle, prompts, paramètres, fonction imposée) et
+//! chaque réponse est conservée dans `aurorae_state/llm_cache/<clé>.json` pendant sa durée de
+//! vie. Les requêtes identiques simultanées partagent un seul appel. En mode hors ligne
+//! (`offline = true` ou `AURORAE_LLM_OFFLINE=1`), seules les réponses en cache sont servies,
+//! quel que soit leur âge: c'est le mode de rejeu des tests.
+
+use std::collections::HashMap;
+use std::fs::{self, create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::llm::{LlmProvider, LlmRequest, LlmResponse};
+
+pub const LLM_CACHE_DIR: &str = "aurorae_state/llm_cache";
+/// Force le mode hors ligne quel que soit `llm.toml`
+pub const LLM_OFFLINE_ENV: &str = "AURORAE_LLM_OFFLINE";
+
+/// Section `[cache]` de `llm.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    /// Durée de vie d'une réponse, en secondes
+    pub ttl_secs: i64,
+    pub dir: String,
+    /// Rejeu seul: aucune requête ne part vers le fournisseur
+    pub offline: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { enabled: true, ttl_secs: 3600, dir: LLM_CACHE_DIR.to_string(), offline: false }
+    }
+}
+
+impl CacheConfig {
+    /// `offline`, ou `AURORAE_LLM_OFFLINE` à 1/true
+    pub fn is_offline(&self) -> bool {
+        self.offline || std::env::var(LLM_OFFLINE_ENV).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+    }
+}
+
+/// Réponse en cache
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub key: String,
+    pub created_at: DateTime<Utc>,
+    pub response: LlmResponse,
+}
+
+/// Compteurs d'efficacité du cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub bypassed: usize,
+}
+
+/// Empreinte de tout ce qui détermine la réponse
+pub fn cache_key(provider: &str, model: &str, request: &LlmRequest) -> String {
+    let mut hasher = Sha256::new();
+    for part in [provider, model, &request.system, &request.prompt] {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+    hasher.update(request.max_tokens.to_le_bytes());
+    hasher.update(request.temperature.to_le_bytes());
+    if let Some(function) = &request.function {
+        hasher.update(function.name.as_bytes());
+        hasher.update(function.parameters.to_string().as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Entrées en mémoire, doublées sur disque (une par fichier) si un dossier est fourni
+#[derive(Debug)]
+pub struct ResponseCache {
+    dir: Option<PathBuf>,
+    ttl: Duration,
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl ResponseCache {
+    pub fn in_memory(ttl_secs: i64) -> Self {
+        Self { dir: None, ttl: Duration::seconds(ttl_secs), entries: HashMap::new() }
+    }
+
+    /// Recharge les entrées de `dir`; les fichiers illisibles sont ignorés
+    pub fn open(dir: &Path, ttl_secs: i64) -> Self {
+        let entries = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| File::open(entry.path()).ok())
+            .filter_map(|file| serde_json::from_reader::<_, CacheEntry>(BufReader::new(file)).ok())
+            .map(|entry| (entry.key.clone(), entry))
+            .collect();
+        Self { dir: Some(dir.to_path_buf()), ttl: Duration::seconds(ttl_secs), entries }
+    }
+
+    /// Réponse encore valide (`ignore_ttl` pour le rejeu hors ligne)
+    pub fn get(&self, key: &str, ignore_ttl: bool) -> Option<&CacheEntry> {
+        self.entries.get(key).filter(|entry| ignore_ttl || Utc::now() - entry.created_at < self.ttl)
+    }
+
+    pub fn put(&mut self, key: &str, response: &LlmResponse) {
+        let entry = CacheEntry { key: key.to_string(), created_at: Utc::now(), response: response.clone() };
+        if let Some(dir) = &self.dir {
+            create_dir_all(dir).ok();
+            if let Ok(file) = File::create(dir.join(format!("{}.json", key))) {
+                let _ = serde_json::to_writer_pretty(BufWriter::new(file), &entry);
+            }
+        }
+        self.entries.insert(key.to_string(), entry);
+    }
+
+    /// Supprime les entrées expirées; renvoie leur nombre
+    pub fn purge_expired(&mut self) -> usize {
+        let now = Utc::now();
+        let expired: Vec<String> = self.entries.values()
+            .filter(|entry| now - entry.created_at >= self.ttl)
+            .map(|entry| entry.key.clone())
+            .collect();
+        for key in &expired {
+            self.entries.remove(key);
+            if let Some(dir) = &self.dir {
+                fs::remove_file(dir.join(format!("{}.json", key))).ok();
+            }
+        }
+        expired.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Fournisseur avec cache: placé devant le fournisseur comptabilisé, une réponse servie depuis
+/// le cache n'est ni refacturée ni imputée au plafond journalier
+pub struct CachedProvider {
+    inner: Arc<dyn LlmProvider>,
+    cache: Mutex<ResponseCache>,
+    offline: bool,
+    /// Un verrou par clé en cours de calcul: les requêtes identiques attendent la première
+    in_flight: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    stats: Mutex<CacheStats>,
+}
+
+impl CachedProvider {
+    pub fn new(inner: Arc<dyn LlmProvider>, cache: ResponseCache, offline: bool) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(cache),
+            offline,
+            in_flight: Mutex::new(HashMap::new()),
+            stats: Mutex::new(CacheStats::default()),
+        }
+    }
+
+    /// Cache de `config`; None si le cache est désactivé (sauf en mode hors ligne)
+    pub fn from_config(inner: Arc<dyn LlmProvider>, config: &CacheConfig) -> Option<Self> {
+        let offline = config.is_offline();
+        if !config.enabled && !offline {
+            return None;
+        }
+        if offline {
+            info!("📼 Modèles de langage hors ligne: rejeu du cache {}", config.dir);
+        }
+        Some(Self::new(inner, ResponseCache::open(Path::new(&config.dir), config.ttl_secs), offline))
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        *self.stats.lock()
+    }
+
+    fn cached(&self, key: &str) -> Option<LlmResponse> {
+        let response = self.cache.lock().get(key, self.offline).map(|entry| entry.response.clone());
+        let mut stats = self.stats.lock();
+        match response {
+            Some(_) => stats.hits += 1,
+            None => stats.misses += 1,
+        }
+        response
+    }
+}
+
+#[async_trait]
+impl LlmProvider for CachedProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, String> {
+        if request.bypass_cache && !self.offline {
+            self.stats.lock().bypassed += 1;
+            return self.inner.complete(request).await;
+        }
+
+        let key = cache_key(self.inner.name(), self.inner.model_for(request), request);
+        if let Some(response) = self.cached(&key) {
+            return Ok(response);
+        }
+        if self.offline {
+            return Err(format!("Réponse absente du cache (mode hors ligne): {}", &key[..12]));
+        }
+
+        let slot = self.in_flight.lock().entry(key.clone()).or_default().clone();
+        let _guard = slot.lock().await;
+        // Une requête identique a pu aboutir pendant l'attente
+        if let Some(response) = self.cache.lock().get(&key, false).map(|entry| entry.response.clone()) {
+            return Ok(response);
+        }
+        let result = self.inner.complete(request).await;
+        if let Ok(response) = &result {
+            self.cache.lock().put(&key, response);
+        }
+        self.in_flight.lock().remove(&key);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LlmProvider for CountingProvider {
+        fn name(&self) -> &str {
+            "compteur"
+        }
+
+        fn default_model(&self) -> &str {
+            "modèle"
+        }
+
+        async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, String> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(LlmResponse { text: format!("{} #{}", request.prompt, n), model: "modèle".to_string(), ..LlmResponse::default() })
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_repeats_from_cache_unless_bypassed() {
+        let counter = Arc::new(CountingProvider::default());
+        let provider = CachedProvider::new(counter.clone(), ResponseCache::in_memory(3600), false);
+        let request = LlmRequest::new("système", "question");
+
+        let (first, second) = tokio::join!(provider.complete(&request), provider.complete(&request));
+        assert_eq!(first.unwrap().text, "question #1");
+        assert_eq!(second.unwrap().text, "question #1");
+        assert_eq!(provider.complete(&request.clone().with_bypass_cache()).await.unwrap().text, "question #2");
+        assert_eq!(provider.complete(&request.clone().with_max_tokens(10)).await.unwrap().text, "question #3");
+        assert_eq!(counter.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(provider.stats().bypassed, 1);
+
+        let expired = CachedProvider::new(counter.clone(), ResponseCache::in_memory(0), false);
+        expired.complete(&request).await.unwrap();
+        expired.complete(&request).await.unwrap();
+        assert_eq!(counter.calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn offline_mode_replays_only_cached_responses() {
+        let dir = std::env::temp_dir().join(format!("aurorae-llm-cache-{}", uuid::Uuid::new_v4()));
+        let request = LlmRequest::new("système", "question");
+        let key = cache_key("compteur", "modèle", &request);
+        let mut cache = ResponseCache::open(&dir, 0);
+        cache.put(&key, &LlmResponse { text: "enregistrée".to_string(), ..LlmResponse::default() });
+
+        let counter = Arc::new(CountingProvider::default());
+        let replay = CachedProvider::new(counter.clone(), ResponseCache::open(&dir, 0), true);
+        assert_eq!(replay.complete(&request).await.unwrap().text, "enregistrée");
+        assert!(replay.complete(&request.clone().with_bypass_cache()).await.is_ok());
+        assert!(replay.complete(&LlmRequest::new("système", "autre")).await.unwrap_err().contains("hors ligne"));
+        assert_eq!(counter.calls.load(Ordering::SeqCst), 0);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
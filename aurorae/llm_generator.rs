@@ -0,0 +1,357 @@
+//! AURORAE++ - llm_generator.rs
+//!
+//! Backend optionnel de génération assistée par un modèle de langage: le prompt part du
+//! squelette d'un gabarit et des patterns de la base de savoir, la réponse est validée par
+//! syn puis par la porte de compilation, et chaque tentative laisse sa provenance (fournisseur,
+//! modèle, empreintes du prompt et de la source) dans le journal d'audit.
+//!
+//! Le code écrit par un modèle n'est jamais compilé ni testé sur l'hôte: la porte s'exécute
+//! dans un conteneur sans réseau. Une source au vert passe ensuite la politique d'alignement
+//! (action `install`), puis attend dans `aurorae_state/llm_pending/` qu'une demande
+//! d'approbation soit signée; elle n'est inscrite dans lib.rs qu'à ce moment.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::alignment::{PolicyDecision, ProposedAction, SharedAlignment};
+use crate::approvals::ApprovalRegistry;
+use crate::audit::{self, AuditKind};
+use crate::generator::{install_source, CompileGate, GateIsolation, GateReport};
+use crate::knowledge::{KnowledgeBase, Pattern};
+use crate::license::{LicensePolicy, SourceProvenance};
+use crate::llm::{LlmProvider, LlmRequest};
+use crate::templates::{TemplateArgs, TEMPLATES};
+
+const SYSTEM_PROMPT: &str = "Tu es un ingénieur Rust. Réponds uniquement par un module Rust complet, \
+sans dépendance externe, dans un bloc ```rust, avec ses tests unitaires dans `mod tests`.";
+/// Patterns de la base de savoir cités dans le prompt
+const PROMPT_PATTERNS: usize = 5;
+/// Sources au vert en attente d'approbation (relatif à la racine du crate)
+pub const LLM_STAGING_DIR: &str = "aurorae_state/llm_pending";
+/// Composant qui reprend les installations approuvées
+pub const APPROVAL_ORIGIN: &str = "llm_generator";
+
+/// Module produit par le modèle, admis par la porte et en attente d'approbation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmGeneration {
+    pub module: String,
+    pub template: String,
+    pub source: String,
+    pub attempts: u32,
+    pub report: GateReport,
+    /// Source mise de côté jusqu'à l'approbation
+    pub path: PathBuf,
+    /// Demande d'approbation de l'installation
+    pub approval: Uuid,
+}
+
+pub struct LlmGenerator<P: LlmProvider> {
+    pub provider: P,
+    pub gate: CompileGate,
+    pub crate_root: PathBuf,
+    /// Tentatives avant abandon, les diagnostics de l'échec précédent étant renvoyés au modèle
+    pub max_attempts: u32,
+    pub max_tokens: u32,
+    /// Modèle choisi pour la génération (modèle par défaut du fournisseur sinon)
+    pub model: Option<String>,
+    /// Politique consultée avant toute demande d'installation
+    pub alignment: Option<SharedAlignment>,
+    pub staging_dir: PathBuf,
+}
+
+impl<P: LlmProvider> LlmGenerator<P> {
+    /// Porte en conteneur si un moteur est disponible; sinon la génération est refusée
+    pub fn new(provider: P, crate_root: &Path) -> Self {
+        let isolation = GateIsolation::container_from_env().unwrap_or_else(|e| {
+            warn!("⚠️ {}: génération assistée désactivée", e);
+            GateIsolation::Host
+        });
+        Self {
+            provider,
+            gate: CompileGate::isolated(crate_root, isolation),
+            crate_root: crate_root.to_path_buf(),
+            max_attempts: 3,
+            max_tokens: 2048,
+            model: None,
+            alignment: None,
+            staging_dir: crate_root.join(LLM_STAGING_DIR),
+        }
+    }
+
+    pub fn with_alignment(mut self, alignment: SharedAlignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    pub fn with_model(mut self, model: Option<&str>) -> Self {
+        self.model = model.map(str::to_string);
+        self
+    }
+
+    fn model(&self) -> &str {
+        self.model.as_deref().unwrap_or(self.provider.default_model())
+    }
+
+    /// Génère `name` depuis le gabarit `template`; une source au vert est soumise à la politique
+    /// puis mise en attente d'approbation dans `approvals`, sans toucher au crate
+    pub async fn generate(&self, template: &str, name: &str, args: &TemplateArgs, knowledge: &KnowledgeBase, approvals: &RwLock<ApprovalRegistry>) -> Result<LlmGeneration, String> {
+        if self.gate.isolation == GateIsolation::Host {
+            return Err("Porte isolée indisponible: le code d'un modèle n'est pas exécuté sur l'hôte".to_string());
+        }
+        let skeleton = TEMPLATES.read().instantiate(template, name, args)?.render()?;
+        let mut feedback: Option<String> = None;
+
+        for attempt in 1..=self.max_attempts {
+            let prompt = build_prompt(name, template, &skeleton, knowledge.get_patterns(), knowledge.license_policy(), feedback.as_deref());
+            let request = LlmRequest::new(SYSTEM_PROMPT, &prompt)
+                .with_max_tokens(self.max_tokens)
+                .with_model(self.model.as_deref())
+                .with_purpose("generator");
+            let response = self.provider.complete(&request).await?;
+            let outcome = match extract_source(&response.text) {
+                Ok(source) => self.gate.check_source(name, &source).map(|report| (source, report)),
+                Err(e) => Err(e),
+            };
+
+            let (source, mut report) = match outcome {
+                Ok((source, report)) if report.passed() => (source, report),
+                Ok((source, report)) => {
+                    self.record(name, template, attempt, &prompt, Some(&source), Some(&report), false);
+                    feedback = Some(describe_failure(&report));
+                    continue;
+                }
+                Err(e) => {
+                    self.record(name, template, attempt, &prompt, None, None, false);
+                    feedback = Some(e);
+                    continue;
+                }
+            };
+
+            let action = ProposedAction::new("install")
+                .with("module", name)
+                .with("template", template)
+                .with("provider", self.provider.name())
+                .with("source_sha256", &digest(&source));
+            let rule = match self.alignment.as_ref().map(|a| a.write().gate(Uuid::new_v4(), &action)) {
+                Some(PolicyDecision::Veto { rule }) => {
+                    self.record(name, template, attempt, &prompt, Some(&source), Some(&report), false);
+                    return Err(format!("Installation de {} refusée par la politique: {}", name, rule));
+                }
+                Some(PolicyDecision::RequiresApproval { rule }) => rule,
+                _ => "code écrit par un modèle".to_string(),
+            };
+            let approval = approvals.write().park_action(APPROVAL_ORIGIN, &action, &rule);
+            fs::create_dir_all(&self.staging_dir).map_err(|e| format!("Erreur création {}: {}", self.staging_dir.display(), e))?;
+            let path = self.staging_dir.join(format!("{}.rs", approval));
+            fs::write(&path, &source).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))?;
+            report.merged = false;
+            self.record(name, template, attempt, &prompt, Some(&source), Some(&report), false);
+            info!("🤖 Module {} généré par {} ({}) en {} tentative(s), en attente d'approbation [{}]",
+                  name, self.provider.name(), self.model(), attempt, approval);
+            return Ok(LlmGeneration { module: name.to_string(), template: template.to_string(), source, attempts: attempt, report, path, approval });
+        }
+        Err(format!("Génération assistée de {} abandonnée après {} tentative(s): {}",
+                    name, self.max_attempts, feedback.unwrap_or_default()))
+    }
+
+    /// Provenance d'une tentative dans le journal d'audit
+    #[allow(clippy::too_many_arguments)]
+    fn record(&self, name: &str, template: &str, attempt: u32, prompt: &str, source: Option<&str>, report: Option<&GateReport>, merged: bool) {
+        audit::record(
+            AuditKind::CodeGenerated,
+            "llm_generator",
+            &format!("{} {} ({}, tentative {})", if merged { "Module généré" } else { "Génération rejetée" }, name, self.provider.name(), attempt),
+            json!({
+                "module": name,
+                "template": template,
+                "provider": self.provider.name(),
+                "model": self.model(),
+                "attempt": attempt,
+                "prompt_sha256": digest(prompt),
+                "source_sha256": source.map(digest),
+                "compiled": report.map(|r| r.compiled),
+                "errors": report.map(|r| r.errors()),
+                "tests_failed": report.map(|r| r.tests_failed.clone()),
+                "merged": merged,
+            }),
+        );
+    }
+}
+
+fn digest(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Inscrit dans le crate les modules dont l'installation a été approuvée; une source modifiée
+/// depuis la demande (empreinte différente) est écartée
+pub fn install_approved(crate_root: &Path, staging_dir: &Path, approvals: &mut ApprovalRegistry) -> Vec<Result<PathBuf, String>> {
+    approvals.take_approved_actions(APPROVAL_ORIGIN)
+        .into_iter()
+        .map(|request| {
+            let module = request.attributes.get("module").cloned().unwrap_or_default();
+            let staged = staging_dir.join(format!("{}.rs", request.id));
+            let source = fs::read_to_string(&staged).map_err(|e| format!("Erreur lecture {}: {}", staged.display(), e))?;
+            if request.attributes.get("source_sha256") != Some(&digest(&source)) {
+                return Err(format!("Source de {} modifiée depuis la demande {}", module, request.id));
+            }
+            let path = install_source(crate_root, &module, &source)?;
+            let _ = fs::remove_file(&staged);
+            audit::record(
+                AuditKind::CodeGenerated,
+                "llm_generator",
+                &format!("Module généré installé {} (approuvé par {})", module, request.approved_by()),
+                json!({ "module": module, "approval": request.id, "source_sha256": digest(&source), "merged": true }),
+            );
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Prompt: squelette du gabarit, patterns les plus riches de la base (hors sources dont la
+/// licence est refusée), diagnostics précédents
+pub fn build_prompt(name: &str, template: &str, skeleton: &str, patterns: &[Pattern], policy: &LicensePolicy, feedback: Option<&str>) -> String {
+    let mut ranked: Vec<&Pattern> = patterns.iter().filter(|p| policy.admits_path(Path::new(&p.module_name))).collect();
+    ranked.sort_by_key(|p| std::cmp::Reverse(p.functions + p.structs + p.traits + p.enums));
+    let known = ranked.iter()
+        .take(PROMPT_PATTERNS)
+        .map(|p| format!("- {}: {} fonction(s), {} structure(s), {} trait(s), {} énumération(s)", p.module_name, p.functions, p.structs, p.traits, p.enums))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut prompt = format!(
+        "Complète le module `{}` (gabarit {}) en conservant ses types publics et leurs signatures.\n\nSquelette:\n```rust\n{}```\n",
+        name, template, skeleton
+    );
+    if !known.is_empty() {
+        prompt.push_str(&format!("\nPatterns observés dans l'écosystème:\n{}\n", known));
+    }
+    if let Some(feedback) = feedback {
+        prompt.push_str(&format!("\nLa proposition précédente a échoué:\n{}\nCorrige ces erreurs.\n", feedback));
+    }
+    prompt
+}
+
+/// Source Rust de la réponse (premier bloc ```rust, ou réponse brute), validée et mise en forme par syn
+pub fn extract_source(response: &str) -> Result<String, String> {
+    let code = match response.find("```") {
+        Some(start) => {
+            let block = &response[start + 3..];
+            let block = block.split_once('\n').map(|(_, rest)| rest).unwrap_or("");
+            block.find("```").map(|end| &block[..end]).ok_or("Bloc de code non terminé")?
+        }
+        None => response,
+    };
+    let file = syn::parse_file(code).map_err(|e| format!("Réponse non analysable: {}", e))?;
+    if file.items.is_empty() {
+        return Err("Réponse sans item Rust".to_string());
+    }
+    Ok(prettyplease::unparse(&file))
+}
+
+fn describe_failure(report: &GateReport) -> String {
+    let mut lines: Vec<String> = report.diagnostics.iter()
+        .filter(|d| d.level == "error")
+        .map(|d| format!("- ligne {}: {} ({})", d.line.map(|l| l.to_string()).unwrap_or_else(|| "?".to_string()), d.message, d.code.clone().unwrap_or_default()))
+        .collect();
+    lines.extend(report.tests_failed.iter().map(|t| format!("- test en échec: {}", t)));
+    if lines.is_empty() {
+        lines.push("- échec de compilation sans diagnostic".to_string());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::GateDiagnostic;
+
+    #[test]
+    fn extracts_and_validates_fenced_rust() {
+        let response = "Voici le module:\n```rust\npub fn answer() -> u32 { 42 }\n```\nBonne journée.";
+        assert!(extract_source(response).unwrap().contains("pub fn answer() -> u32"));
+        assert!(extract_source("pub struct Raw;").is_ok());
+        assert!(extract_source("```rust\npub fn broken( {\n```").unwrap_err().contains("non analysable"));
+        assert!(extract_source("```rust\npub fn open() {}\n").is_err());
+        assert!(extract_source("Je ne peux pas.").is_err());
+    }
+
+    #[test]
+    fn prompt_cites_richest_patterns_and_previous_errors() {
+        let pattern = |name: &str, functions| Pattern { module_name: name.to_string(), functions, structs: 1, traits: 0, enums: 0 };
+        let patterns: Vec<Pattern> = (0..8).map(|i| pattern(&format!("m{}", i), i)).collect();
+        let report = GateReport {
+            diagnostics: vec![GateDiagnostic { level: "error".to_string(), message: "mismatched types".to_string(), code: Some("E0308".to_string()), line: Some(4) }],
+            ..GateReport::default()
+        };
+        let prompt = build_prompt("energy_core", "metric_collector", "pub struct A;\n", &patterns, &LicensePolicy::default(), Some(&describe_failure(&report)));
+        assert!(prompt.contains("- m7: 7 fonction(s)"));
+        assert!(!prompt.contains("- m2:"));
+        assert!(prompt.contains("ligne 4: mismatched types (E0308)"));
+        assert!(prompt.contains("gabarit metric_collector"));
+    }
+
+    #[test]
+    fn prompt_leaves_out_patterns_from_denied_licenses() {
+        let repo = std::env::temp_dir().join(format!("aurorae-llm-{}", Uuid::new_v4()));
+        fs::create_dir_all(&repo).unwrap();
+        SourceProvenance { repo: "https://example.org/gpl".to_string(), commit: "abc".to_string(), license: Some("GPL-3.0".to_string()) }
+            .save_in(&repo)
+            .unwrap();
+        let denied = repo.join("lib.rs").to_string_lossy().to_string();
+        let patterns = vec![
+            Pattern { module_name: denied.clone(), functions: 9, structs: 1, traits: 0, enums: 0 },
+            Pattern { module_name: "local.rs".to_string(), functions: 1, structs: 0, traits: 0, enums: 0 },
+        ];
+        let prompt = build_prompt("energy_core", "metric_collector", "pub struct A;\n", &patterns, &LicensePolicy::default(), None);
+        assert!(!prompt.contains(&denied));
+        assert!(prompt.contains("- local.rs: 1 fonction(s)"));
+        fs::remove_dir_all(&repo).ok();
+    }
+
+    #[tokio::test]
+    async fn only_approved_unmodified_sources_are_installed() {
+        use crate::approvals::ApprovalPolicy;
+        use ethers::signers::{LocalWallet, Signer};
+
+        let root = std::env::temp_dir().join(format!("aurorae-llm-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("aurorae")).unwrap();
+        fs::write(root.join("aurorae/lib.rs"), "pub mod brain;\n").unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"aurorae\"\n\n[features]\n").unwrap();
+        let staging = root.join(LLM_STAGING_DIR);
+        fs::create_dir_all(&staging).unwrap();
+
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let mut approvals = ApprovalRegistry::new(ApprovalPolicy { approvers: vec![wallet.address()], ..ApprovalPolicy::default() });
+        let stage = |approvals: &mut ApprovalRegistry, module: &str, source: &str| {
+            let action = ProposedAction::new("install").with("module", module).with("source_sha256", &digest(source));
+            let id = approvals.park_action(APPROVAL_ORIGIN, &action, "code écrit par un modèle");
+            fs::write(staging.join(format!("{}.rs", id)), source).unwrap();
+            id
+        };
+        let accepted = stage(&mut approvals, "energy_core", "pub fn answer() -> u32 { 42 }\n");
+        let tampered = stage(&mut approvals, "oracle_feed", "pub fn price() -> u32 { 1 }\n");
+        let waiting = stage(&mut approvals, "idle_module", "pub fn idle() {}\n");
+
+        assert!(install_approved(&root, &staging, &mut approvals).is_empty());
+        for id in [accepted, tampered] {
+            let message = approvals.action(&id).unwrap().message();
+            approvals.add_signature(&id, wallet.sign_message(&message).await.unwrap()).unwrap();
+        }
+        fs::write(staging.join(format!("{}.rs", tampered)), "pub fn price() -> u32 { 1_000 }\n").unwrap();
+
+        let results = install_approved(&root, &staging, &mut approvals);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        let lib = fs::read_to_string(root.join("aurorae/lib.rs")).unwrap();
+        assert!(lib.contains("pub mod energy_core;") && !lib.contains("oracle_feed") && !lib.contains("idle_module"));
+        assert!(staging.join(format!("{}.rs", waiting)).exists());
+        let _ = fs::remove_dir_all(root);
+    }
+}
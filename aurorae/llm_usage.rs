@@ -0,0 +1,328 @@
+//! AURORAE++ - llm_usage.rs
+//!
+//! Comptabilité des appels aux modèles de langage: jetons consommés, coût selon le tarif du
+//! modèle, dépense imputée à l'enveloppe `Inference` du budget. `MeteredProvider` enveloppe
+//! le fournisseur configuré et soumet chaque appel à la politique d'alignement
+//! (`limit llm cost <plafond> per day`) avant de le transmettre.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::info;
+
+use crate::alignment::{PolicyDecision, ProposedAction, SharedAlignment};
+use crate::currency::{Amount, Asset};
+use crate::economy::{BudgetCategory, SharedBudget};
+use crate::llm::{LlmProvider, LlmRequest, LlmResponse};
+
+pub const LLM_USAGE_PATH: &str = "aurorae_state/llm_usage.json";
+/// Appels conservés dans le journal
+const MAX_RECORDS: usize = 5000;
+/// Préfixe des erreurs d'un appel refusé par la politique (plafond journalier atteint…)
+pub const LLM_CALL_REFUSED: &str = "Appel LLM refusé par la politique";
+
+lazy_static! {
+    pub static ref LLM_USAGE: Mutex<UsageLedger> = Mutex::new(UsageLedger::open(Path::new(LLM_USAGE_PATH)));
+}
+
+/// Tarif d'un modèle, en USD pour 1000 jetons
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ModelPricing {
+    pub prompt: f64,
+    pub completion: f64,
+}
+
+impl ModelPricing {
+    pub const fn new(prompt: f64, completion: f64) -> Self {
+        Self { prompt, completion }
+    }
+
+    pub fn cost(&self, prompt_tokens: u32, completion_tokens: u32) -> f64 {
+        (prompt_tokens as f64 * self.prompt + completion_tokens as f64 * self.completion) / 1000.0
+    }
+}
+
+/// Tarifs publics connus, par préfixe de nom de modèle; les modèles locaux sont gratuits
+const DEFAULT_PRICING: [(&str, ModelPricing); 8] = [
+    ("gpt-4o-mini", ModelPricing::new(0.00015, 0.0006)),
+    ("gpt-4o", ModelPricing::new(0.0025, 0.01)),
+    ("gpt-4-turbo", ModelPricing::new(0.01, 0.03)),
+    ("gpt-4", ModelPricing::new(0.03, 0.06)),
+    ("gpt-3.5-turbo", ModelPricing::new(0.0005, 0.0015)),
+    ("claude-3-5-sonnet", ModelPricing::new(0.003, 0.015)),
+    ("claude-3-5-haiku", ModelPricing::new(0.0008, 0.004)),
+    ("claude-3-opus", ModelPricing::new(0.015, 0.075)),
+];
+
+/// Tarifs par modèle: ceux de `llm.toml` (`[pricing.<modèle>]`) priment sur la table par défaut
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PricingTable {
+    overrides: HashMap<String, ModelPricing>,
+}
+
+impl PricingTable {
+    pub fn new(overrides: &HashMap<String, ModelPricing>) -> Self {
+        Self { overrides: overrides.clone() }
+    }
+
+    /// Tarif du plus long préfixe connu (`gpt-4o-2024-08-06` → `gpt-4o`)
+    pub fn pricing(&self, model: &str) -> ModelPricing {
+        let configured = self.overrides.iter().map(|(name, pricing)| (name.as_str(), *pricing));
+        configured
+            .chain(DEFAULT_PRICING.iter().copied())
+            .filter(|(name, _)| model.starts_with(name))
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, pricing)| pricing)
+            .unwrap_or_default()
+    }
+
+    pub fn cost(&self, model: &str, prompt_tokens: u32, completion_tokens: u32) -> f64 {
+        self.pricing(model).cost(prompt_tokens, completion_tokens)
+    }
+}
+
+/// Estimation grossière (4 caractères par jeton) quand le fournisseur ne rapporte rien
+pub fn estimate_tokens(text: &str) -> u32 {
+    text.chars().count().div_ceil(4) as u32
+}
+
+/// Vrai si l'erreur provient d'un refus de la politique et non du fournisseur
+pub fn is_policy_refusal(error: &str) -> bool {
+    error.starts_with(LLM_CALL_REFUSED)
+}
+
+/// Appel comptabilisé
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub at: DateTime<Utc>,
+    pub provider: String,
+    pub model: String,
+    pub purpose: Option<String>,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    /// Coût en USD
+    pub cost: f64,
+    /// Jetons estimés faute de décompte du fournisseur
+    pub estimated: bool,
+}
+
+/// Cumul par modèle
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageTotals {
+    pub calls: usize,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost: f64,
+}
+
+/// Journal des appels, sauvegardé à chaque ajout
+#[derive(Debug, Default)]
+pub struct UsageLedger {
+    pub records: Vec<UsageRecord>,
+    path: Option<PathBuf>,
+}
+
+impl UsageLedger {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    pub fn open(path: &Path) -> Self {
+        let records = File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self { records, path: Some(path.to_path_buf()) }
+    }
+
+    pub fn record(&mut self, record: UsageRecord) {
+        self.records.push(record);
+        if self.records.len() > MAX_RECORDS {
+            let excess = self.records.len() - MAX_RECORDS;
+            self.records.drain(..excess);
+        }
+        self.save();
+    }
+
+    /// Coût cumulé depuis minuit UTC
+    pub fn spent_today(&self) -> f64 {
+        let today = Utc::now().date_naive();
+        self.records.iter().filter(|r| r.at.date_naive() == today).map(|r| r.cost).sum()
+    }
+
+    pub fn totals_by_model(&self) -> BTreeMap<String, UsageTotals> {
+        let mut totals: BTreeMap<String, UsageTotals> = BTreeMap::new();
+        for record in &self.records {
+            let entry = totals.entry(record.model.clone()).or_default();
+            entry.calls += 1;
+            entry.prompt_tokens += record.prompt_tokens as u64;
+            entry.completion_tokens += record.completion_tokens as u64;
+            entry.cost += record.cost;
+        }
+        totals
+    }
+
+    pub fn report(&self) {
+        info!("🧮 Modèles de langage • {:.4} USD aujourd'hui", self.spent_today());
+        for (model, totals) in self.totals_by_model() {
+            println!(
+                "→ {}: {} appels • {} + {} jetons • {:.4} USD",
+                model, totals.calls, totals.prompt_tokens, totals.completion_tokens, totals.cost
+            );
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self.records);
+        }
+    }
+}
+
+/// Fournisseur comptabilisé: chaque appel est soumis à l'alignement sur son coût maximal
+/// estimé (`max_tokens` en complétion), puis facturé au coût réel
+pub struct MeteredProvider {
+    inner: Arc<dyn LlmProvider>,
+    pricing: PricingTable,
+    alignment: SharedAlignment,
+    budget: SharedBudget,
+}
+
+impl MeteredProvider {
+    pub fn new(inner: Arc<dyn LlmProvider>, pricing: PricingTable, alignment: SharedAlignment, budget: SharedBudget) -> Self {
+        Self { inner, pricing, alignment, budget }
+    }
+
+    /// Coût maximal de la requête, imputé au plafond journalier de la politique
+    pub fn estimate(&self, request: &LlmRequest) -> f64 {
+        let prompt_tokens = estimate_tokens(&request.system) + estimate_tokens(&request.prompt);
+        self.pricing.cost(self.inner.model_for(request), prompt_tokens, request.max_tokens)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for MeteredProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, String> {
+        let estimate = self.estimate(request);
+        let action = ProposedAction::new("llm")
+            .with("cost", &format!("{:.6}", estimate))
+            .with("model", self.inner.model_for(request))
+            .with("purpose", request.purpose.as_deref().unwrap_or("autre"));
+        let decision = self.alignment.write().gate(Uuid::new_v4(), &action);
+        match decision {
+            PolicyDecision::Allow => {}
+            PolicyDecision::Veto { rule } | PolicyDecision::RequiresApproval { rule } => {
+                return Err(format!("{}: {}", LLM_CALL_REFUSED, rule));
+            }
+        }
+
+        let response = self.inner.complete(request).await?;
+        let estimated = response.prompt_tokens.is_none() || response.completion_tokens.is_none();
+        let prompt_tokens = response.prompt_tokens
+            .unwrap_or_else(|| estimate_tokens(&request.system) + estimate_tokens(&request.prompt));
+        let completion_tokens = response.completion_tokens.unwrap_or_else(|| estimate_tokens(&response.text));
+        let cost = self.pricing.cost(&response.model, prompt_tokens, completion_tokens);
+
+        info!(
+            "🧮 {} ({}) • {} + {} jetons • {:.5} USD",
+            self.inner.name(), response.model, prompt_tokens, completion_tokens, cost
+        );
+        LLM_USAGE.lock().record(UsageRecord {
+            at: Utc::now(),
+            provider: self.inner.name().to_string(),
+            model: response.model.clone(),
+            purpose: request.purpose.clone(),
+            prompt_tokens,
+            completion_tokens,
+            cost,
+            estimated,
+        });
+        // Les tarifs sont en USD, ancrés comme les stablecoins
+        self.budget.write().record_expense("llm", BudgetCategory::Inference, Amount::new(Asset::usdc(), cost));
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::{AlignmentSystem, Policy};
+    use crate::economy::BudgetEngine;
+
+    struct FixedProvider;
+
+    #[async_trait]
+    impl LlmProvider for FixedProvider {
+        fn name(&self) -> &str {
+            "fixe"
+        }
+
+        fn default_model(&self) -> &str {
+            "gpt-4o"
+        }
+
+        async fn complete(&self, _request: &LlmRequest) -> Result<LlmResponse, String> {
+            Ok(LlmResponse {
+                text: "apprendre".to_string(),
+                model: "gpt-4o-2024-08-06".to_string(),
+                prompt_tokens: Some(1000),
+                completion_tokens: Some(100),
+                arguments: None,
+            })
+        }
+    }
+
+    #[test]
+    fn pricing_uses_longest_prefix_and_overrides() {
+        let mut overrides = HashMap::new();
+        overrides.insert("llama3".to_string(), ModelPricing::new(0.001, 0.001));
+        let table = PricingTable::new(&overrides);
+        assert_eq!(table.pricing("gpt-4o-mini-2024-07-18"), ModelPricing::new(0.00015, 0.0006));
+        assert_eq!(table.pricing("gpt-4o-2024-08-06"), ModelPricing::new(0.0025, 0.01));
+        assert!((table.cost("llama3", 1000, 1000) - 0.002).abs() < 1e-9);
+        assert_eq!(table.cost("mistral", 1000, 1000), 0.0);
+        assert_eq!(estimate_tokens("abcdefghi"), 3);
+    }
+
+    #[tokio::test]
+    async fn daily_cap_refuses_calls_once_reached() {
+        let mut alignment = AlignmentSystem::new();
+        alignment.policy = Policy::parse("limit llm cost 0.02 per day").unwrap();
+        let budget = BudgetEngine::new(100.0).shared();
+        let provider = MeteredProvider::new(Arc::new(FixedProvider), PricingTable::default(), alignment.shared(), budget.clone());
+
+        // Estimation: 4 jetons de prompt et 500 de complétion → 0.00501 USD, le quatrième appel dépasse
+        let request = LlmRequest::new("système", "question").with_max_tokens(500).with_purpose("strategist");
+        for _ in 0..3 {
+            assert_eq!(provider.complete(&request).await.unwrap().text, "apprendre");
+        }
+        let refused = provider.complete(&request).await.unwrap_err();
+        assert!(is_policy_refusal(&refused), "{}", refused);
+
+        // Coût réel: 1000 × 0.0025 + 100 × 0.01 = 0.0035 par appel
+        let spent = budget.read().envelopes[&BudgetCategory::Inference].spent;
+        assert!((spent - 3.0 * 0.0035).abs() < 1e-9);
+    }
+}
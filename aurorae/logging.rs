@@ -0,0 +1,268 @@
+//! AURORAE++ - logging.rs
+//!
+//! Journal structuré (`tracing`): chaque module écrit sous sa propre cible (`aurorae::economy`,
+//! `aurorae::guardian`…), et les cycles de la boucle principale, les déploiements et les
+//! mutations ouvrent des spans qui relient leurs événements. La sortie, lisible ou JSON, est
+//! masquée par `secrets` avant d'être écrite; les enregistrements de la crate `log` y sont
+//! redirigés.
+//!
+//! Le filtre (`logging.toml`, ou `AURORAE_LOG` qui a priorité) se règle à chaud: une
+//! modification du fichier est appliquée sans redémarrage.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use tracing_subscriber::layer::{Layer, Layered, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+use crate::resource_usage::spawn_tracked;
+use crate::secrets::redact;
+
+pub const DEFAULT_LOGGING_PATH: &str = "logging.toml";
+/// Directive `EnvFilter` complète, prioritaire sur `logging.toml`
+pub const LOG_FILTER_ENV: &str = "AURORAE_LOG";
+/// `text`, `compact` ou `json`
+pub const LOG_FORMAT_ENV: &str = "AURORAE_LOG_FORMAT";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+    /// Une ligne par événement: horodatage, niveau, spans, cible, message
+    #[default]
+    Text,
+    Compact,
+    /// Un objet JSON par événement, span courant et liste des spans compris
+    Json,
+}
+
+impl LogFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "compact" => Ok(Self::Compact),
+            "json" => Ok(Self::Json),
+            other => Err(format!("Format de journal inconnu: {}", other)),
+        }
+    }
+}
+
+/// Fichier `logging.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct LoggingConfig {
+    /// Niveau par défaut de toutes les cibles
+    pub level: String,
+    /// Niveau par module (`economy = "debug"` vise la cible `aurorae::economy`)
+    pub modules: BTreeMap<String, String>,
+    /// Directive `EnvFilter` complète; remplace `level` et `modules` si présente
+    pub filter: Option<String>,
+    pub format: LogFormat,
+    pub show_target: bool,
+    /// Intervalle de surveillance du fichier pour le rechargement à chaud
+    pub reload_interval_secs: u64,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            modules: BTreeMap::new(),
+            filter: None,
+            format: LogFormat::Text,
+            show_target: true,
+            reload_interval_secs: 10,
+        }
+    }
+}
+
+impl LoggingConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| format!("Configuration du journal invalide: {}", e))?;
+        for level in std::iter::once(&config.level).chain(config.modules.values()) {
+            level.parse::<LevelFilter>().map_err(|_| format!("Niveau de journal inconnu: {}", level))?;
+        }
+        EnvFilter::try_new(config.directives()).map_err(|e| format!("Filtre du journal invalide: {}", e))?;
+        Ok(config)
+    }
+
+    /// `logging.toml` (ou les valeurs par défaut), puis les variables d'environnement
+    pub fn load_or_default() -> Self {
+        let config = Self::load(DEFAULT_LOGGING_PATH).unwrap_or_else(|e| {
+            eprintln!("[AURORAE++] ⚠️ {}: journal par défaut", e);
+            Self::default()
+        });
+        config.with_overrides(std::env::var(LOG_FILTER_ENV).ok(), std::env::var(LOG_FORMAT_ENV).ok())
+    }
+
+    /// Applique `AURORAE_LOG` et `AURORAE_LOG_FORMAT`; une valeur invalide est ignorée
+    pub fn with_overrides(mut self, filter: Option<String>, format: Option<String>) -> Self {
+        if let Some(filter) = filter.filter(|filter| !filter.trim().is_empty()) {
+            match EnvFilter::try_new(&filter) {
+                Ok(_) => self.filter = Some(filter),
+                Err(e) => eprintln!("[AURORAE++] ⚠️ {} ignoré: {}", LOG_FILTER_ENV, e),
+            }
+        }
+        if let Some(format) = format {
+            match LogFormat::parse(&format) {
+                Ok(format) => self.format = format,
+                Err(e) => eprintln!("[AURORAE++] ⚠️ {} ignoré: {}", LOG_FORMAT_ENV, e),
+            }
+        }
+        self
+    }
+
+    /// Directive `EnvFilter` effective
+    pub fn directives(&self) -> String {
+        if let Some(filter) = &self.filter {
+            return filter.clone();
+        }
+        let mut directives = vec![self.level.clone()];
+        directives.extend(self.modules.iter().map(|(module, level)| format!("aurorae::{}={}", module, level)));
+        directives.join(",")
+    }
+}
+
+/// Sortie standard masquée: chaque événement formaté arrive en un seul `write`
+struct RedactingStdout;
+
+impl Write for RedactingStdout {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        io::stdout().lock().write_all(redact(&String::from_utf8_lossy(buf)).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stdout().flush()
+    }
+}
+
+type FilteredRegistry = Layered<reload::Layer<EnvFilter, Registry>, Registry>;
+
+/// Accès au filtre du journal installé
+#[derive(Clone)]
+pub struct LogHandle {
+    filter: reload::Handle<EnvFilter, Registry>,
+    directives: Arc<RwLock<String>>,
+}
+
+impl LogHandle {
+    pub fn directives(&self) -> String {
+        self.directives.read().clone()
+    }
+
+    /// Remplace le filtre sans redémarrage
+    pub fn set_filter(&self, directives: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(directives).map_err(|e| format!("Filtre du journal invalide: {}", e))?;
+        self.filter.reload(filter).map_err(|e| format!("Filtre du journal non appliqué: {}", e))?;
+        *self.directives.write() = directives.to_string();
+        info!("🎚️ Filtre du journal: {}", directives);
+        Ok(())
+    }
+}
+
+/// Installe le journal global; à appeler une fois, avant la première ligne de journal
+pub fn init(config: &LoggingConfig) -> Result<LogHandle, String> {
+    let directives = config.directives();
+    let filter = EnvFilter::try_new(&directives).map_err(|e| format!("Filtre du journal invalide: {}", e))?;
+    let (filter, handle) = reload::Layer::new(filter);
+    let output: Box<dyn Layer<FilteredRegistry> + Send + Sync> = match config.format {
+        LogFormat::Text => fmt::layer().with_target(config.show_target).with_writer(|| RedactingStdout).boxed(),
+        LogFormat::Compact => fmt::layer().compact().with_target(config.show_target).with_writer(|| RedactingStdout).boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_target(config.show_target)
+            .with_writer(|| RedactingStdout)
+            .boxed(),
+    };
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(output)
+        .try_init()
+        .map_err(|e| format!("Journal déjà installé: {}", e))?;
+    Ok(LogHandle { filter: handle, directives: Arc::new(RwLock::new(directives)) })
+}
+
+/// Surveille `logging.toml` et applique son filtre à chaque modification (sauf si
+/// `AURORAE_LOG` le fixe). Le format de sortie, lui, n'est lu qu'au démarrage.
+pub fn spawn_filter_reload(handle: LogHandle, path: PathBuf, interval: Duration) -> JoinHandle<()> {
+    spawn_tracked("logging", async move {
+        let modified = |path: &PathBuf| -> Option<SystemTime> { fs::metadata(path).and_then(|m| m.modified()).ok() };
+        let mut last = modified(&path);
+        loop {
+            tokio::time::sleep(interval).await;
+            let current = modified(&path);
+            if current.is_none() || current == last {
+                continue;
+            }
+            last = current;
+            let config = match LoggingConfig::load(&path.to_string_lossy()) {
+                Ok(config) => config.with_overrides(std::env::var(LOG_FILTER_ENV).ok(), None),
+                Err(e) => {
+                    warn!("⚠️ Rechargement du journal ignoré: {}", e);
+                    continue;
+                }
+            };
+            let directives = config.directives();
+            if directives != handle.directives() {
+                if let Err(e) = handle.set_filter(&directives) {
+                    warn!("⚠️ {}", e);
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directives_combine_level_and_modules_unless_overridden() {
+        let config = LoggingConfig::from_toml(
+            r#"
+            level = "warn"
+            format = "json"
+
+            [modules]
+            economy = "debug"
+            guardian = "trace"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.format, LogFormat::Json);
+        assert_eq!(config.directives(), "warn,aurorae::economy=debug,aurorae::guardian=trace");
+
+        let overridden = config.clone().with_overrides(Some("aurorae::devnet=debug".into()), Some("compact".into()));
+        assert_eq!(overridden.directives(), "aurorae::devnet=debug");
+        assert_eq!(overridden.format, LogFormat::Compact);
+
+        // Valeurs d'environnement invalides: configuration conservée
+        let kept = config.clone().with_overrides(Some("aurorae=bavard".into()), Some("xml".into()));
+        assert_eq!(kept, config);
+    }
+
+    #[test]
+    fn invalid_filters_are_rejected() {
+        assert!(LoggingConfig::from_toml("level = \"bavard\"").is_err());
+        assert!(LoggingConfig::from_toml("[modules]\neconomy = \"trop\"").is_err());
+        assert_eq!(LoggingConfig::from_toml("").unwrap(), LoggingConfig::default());
+        assert!(LogFormat::parse(" JSON ").is_ok());
+    }
+}
@@ -5,28 +5,74 @@ use tch::{nn, Device, Tensor};
 use tch::nn::OptimizerConfig;
 use std::sync::Arc;
 use chrono::Utc;
+use tracing::{error, info, warn};
 
 // Modules du système Aurorae - Core
 mod alchemy;
+mod alerting;
+mod alignment;
+mod approvals;
+mod audit;
 mod autonomy;
 mod blockchain_core;
 mod brain;
+mod chain_registry;
+mod cognitive_architecture;
+mod consciousness_model;
+mod consensus;
+mod contract_verification;
+mod cross_chain;
+mod currency;
+mod distributed_compute;
+mod deploy_manifest;
 mod deployer;
+mod devnet;
 mod dream;
 mod economy;
+mod event_bus;
+mod fitness;
+mod formal_verification;
 mod founder_income;
 mod guardian;
+mod image_generator;
+mod inspiration;
+mod instance_messaging;
+mod instance_runner;
 mod intelligence;
+mod ipfs;
+mod keystore;
 mod knowledge;
+mod knowledge_store;
+mod knowledge_sync;
+mod lazy_mint;
 mod learning;
+mod lineage;
+mod llm;
+mod llm_cache;
+mod llm_generator;
+mod llm_usage;
+mod logging;
+mod market_data;
+mod meta_learning;
+mod metacognition;
 mod mutation;
+mod mutation_sandbox;
 mod nft_minter;
+mod nft_sales;
 mod reproduction;
+mod resource_usage;
+mod rollback;
+mod secrets;
+mod semantic_bridge;
+mod staking;
+mod substrate;
+mod swarm_intelligence;
 mod validator;
 mod vision;
 
 // Modules d'évolution et d'auto-amélioration
 mod code_evolution;
+mod config;
 mod evolution;
 mod generator;
 mod genome;
@@ -35,19 +81,25 @@ mod network_builder;
 // Modules d'analyse et d'apprentissage
 mod clippy_integration;
 mod crawler;
+mod embeddings;
 mod engine;
 mod explorer;
+mod license;
 mod neural_network;
+mod neuromorphic;
 mod pattern_extractor;
+mod prompts;
 mod refactor;
 mod reinforcement_learning;
 mod rust_analyzer;
+mod syntax_tree;
 
 // Modules de sécurité et maintenance
 mod defense;
 mod openai;
 mod security;
 mod strategist;
+mod templates;
 mod update_checker;
 
 // Module bibliothèque principal
@@ -55,65 +107,294 @@ mod lib;
 
 // Imports des structures et fonctions nécessaires
 use crate::autonomy::AuroraeCore;
-use crate::founder_income::{set_founder_address, reward_founder};
+use crate::founder_income::{allocate_revenue, execute_payouts, set_founder_address, reward_founder, PayoutConfig, DEFAULT_PAYOUTS_PATH};
 use crate::brain::{boot_brain, BrainCore, Intent, Thought};
-use crate::learning::{scan_feed_and_learn, MetaLearningSystem};
-use crate::deployer::Deployer;
-use crate::blockchain_core::BlockchainInterface;
-use crate::guardian::GuardianSentinel;
+use crate::cognitive_architecture::CognitiveModel;
+use crate::consciousness_model::GlobalWorkspace;
+use crate::consensus::{ConsensusConfig, ConsensusLab, RaftNode, TreasuryPolicy};
+use crate::swarm_intelligence::{BestConfigs, GossipNode, HyperparameterSearch, SwarmConfig, OBJECTIVES};
+use crate::distributed_compute::{ComputeConfig, ComputeNode, ComputeScheduler, ComputeWorker, Offloaded, OffloadedWork};
+use crate::learning::{module_difficulties, Curriculum, Difficulty, TaskClass};
+use crate::meta_learning::{LearningRegime, MetaLearningSystem};
+use crate::metacognition::{DecisionKind, MetacognitiveSystem};
+use crate::deployer::{ContractBuilder, Deployer, DeploymentOptions};
+use crate::deploy_manifest::{default_manifest_path, DeploymentManifest, ManifestRunner};
+use crate::devnet::{DevnetConfig, DevnetEnvironment};
+use crate::blockchain_core::{BlockchainInterface, FeeStrategy, FinalityTracker, GasOracle, SharedTxManager, TxManagers};
+use crate::keystore::{KeySource, Keystore, Signer, SigningPolicy};
+use crate::chain_registry::{spawn_hot_reload, ChainConfig, ChainKind, ChainRegistry, DEFAULT_REGISTRY_PATH};
+use crate::cross_chain::{spawn_relayer, BridgesConfig, CrossChainRouter, HeaderCheckpoints};
+use crate::semantic_bridge::SchemaRegistry;
+use crate::network_builder::{NetworkMap, NETWORK_TOPOLOGY_PATH};
+use crate::guardian::{spawn_heartbeat, ChainProbe, GuardianSentinel, HealthProbe, HeartbeatConfig, LockProbe};
+use crate::nft_sales::sales_filter;
+use crate::economy::{incoming_transfer_filter, BudgetCategory, SharedBudget};
 use crate::dream::DreamEngine;
 use crate::reproduction::ReproductionEngine;
-use crate::validator::check_integrity;
+use crate::instance_messaging::{InstanceMessage, MessageHub};
+use crate::instance_runner::{run_child, InstanceLimits, InstanceSupervisor};
+use crate::rollback::{MutationWatch, WatchOutcome, SNAPSHOTS, SNAPSHOT_INTERVAL_CYCLES};
+use crate::validator::{check_integrity, sign_integrity_manifest, IntegrityManifest, INTEGRITY_MANIFEST_PATH};
 use crate::vision::VisionEngine;
-use crate::generator::trigger_generation;
-use crate::crawler::{clone_repo, clear_feed};
-use crate::mutation::mutate_module_code;
+use crate::generator::{trigger_generation, GENERATION_QUEUE};
+use crate::templates::{ParamValue, TemplateArgs};
+use crate::llm_generator::{LlmGenerator, LLM_STAGING_DIR};
+use crate::llm::{LlmConfig, LlmProvider};
+use crate::llm_cache::CachedProvider;
+use crate::llm_usage::{MeteredProvider, PricingTable, LLM_USAGE};
+use crate::crawler::{console_progress, ClonePool, CrawlerConfig, FeedChanges};
+use crate::pattern_extractor::apply_feed_changes;
+use crate::mutation_sandbox::MutationSandbox;
+use crate::fitness::{EconomicKpis, FitnessService};
+use crate::lineage::{LineageFormat, LINEAGE};
+use crate::license::LicensePolicy;
 use crate::security::SecuritySystem;
-use crate::explorer::search_best_rust_chains;
+use crate::staking::{AprCurve, StakingEngine};
+use crate::explorer::{
+    save_protocol_profiles, search_best_rust_chains, CratesExplorer, ProtocolCatalog, ProtocolScanner, PROTOCOL_PROFILES_PATH,
+};
 use crate::alchemy::TokenKind;
+use crate::alerting::{spawn_alerting, AlertRouter};
+use crate::resource_usage::{measure, ResourceMonitor, TrackingAllocator};
+use crate::secrets::SECRETS;
+use crate::config::{ConfigSource, CONFIG};
+use crate::logging::{spawn_filter_reload, LoggingConfig, DEFAULT_LOGGING_PATH};
+use crate::update_checker::{UpdateChecker, UPDATE_CHECK_INTERVAL_CYCLES};
+use crate::alignment::{AlignmentSystem, PolicyDecision, ProposedAction};
+use crate::event_bus::AuroraeEvent;
+use crate::approvals::{ApprovalCheck, APPROVALS, DEFAULT_APPROVALS_PATH};
+use crate::audit::{self, AuditKind, AuditQuery, ExportFormat, AUDIT, AUDIT_HEAD_INTERVAL_CYCLES};
+use crate::currency::Asset;
 use crate::strategist::Strategist;
 use crate::reinforcement_learning::LearningAgent;
-use crate::neural_network::DecisionNet;
+use crate::neural_network::{export_weights, import_weights, DecisionNet};
+use crate::neuromorphic::{state_features, ActionSelector, BackendComparison, NeuromorphicConfig, SpikingPolicy};
 use crate::knowledge::KnowledgeBase;
+use crate::knowledge_sync::{KnowledgeSync, DEFAULT_SYNC_INTERVAL};
 use crate::evolution::{EvolutionEngine, SelectionStrategy};
 use crate::genome::GenomeBuilder;
 use crate::engine::CoreEngine;
-use crate::code_evolution::CodeEvolver;
+use crate::code_evolution::{CodeEvolution, CodeEvolver};
 use crate::defense::DefenseMatrix;
+use ethers::abi::Token;
+use ethers::types::Signature;
+use uuid::Uuid;
 
 // Constantes et métadonnées système
 const STARTUP_TIMESTAMP: &str = "2025-04-12 16:16:55";  // Mise à jour avec l'heure actuelle
 const SYSTEM_USER: &str = "rdemz";                     // Utilisateur actuel
 const SYSTEM_VERSION: &str = "0.9.7-alpha";
 const MIN_NEURAL_LAYERS: usize = 3;
-const MAX_ACTIVE_INSTANCES: usize = 7;
+const FINALITY_CONFIRMATIONS: u64 = 12;                  // Profondeur de finalité de la chaîne principale
+const AURORAIUM_CONTRACT: &str = "AuroraiumUpgradeable"; // Implémentation vérifiée puis déployée derrière le proxy
+const AURORAIUM_INITIAL_SUPPLY: u64 = 1_000_000;         // Offre initiale du token principal
+const AURORAIUM_UPGRADE_TIMELOCK_SECS: u64 = 172_800;    // Délai des mises à jour du proxy Auroraium (48 h)
+
+// Attribution des allocations aux modules pour le suivi des ressources
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
 
 #[tokio::main]
 async fn main() {
     // ============== PHASE 1: INITIALISATION DU SYSTÈME ET SÉCURITÉ ==============
-    println!("[AURORAE++] 🚀 Lancement du système Aurorae-genesis v{} le {} par {}", 
+    // Masquage des secrets dans toutes les sorties, avant la première ligne de journal
+    lazy_static::initialize(&SECRETS);
+    let logging_config = LoggingConfig::load_or_default();
+    let log_handle = logging::init(&logging_config)
+        .map_err(|e| eprintln!("[AURORAE++] ⚠️ {}", e))
+        .ok();
+    let _log_watcher = log_handle.map(|handle| {
+        let interval = Duration::from_secs(logging_config.reload_interval_secs.max(1));
+        spawn_filter_reload(handle, DEFAULT_LOGGING_PATH.into(), interval)
+    });
+    if let Err(e) = secrets::redact_standard_streams() {
+        warn!("⚠️ Masquage des flux standard indisponible: {}", e);
+    }
+    info!("🚀 Lancement du système Aurorae-genesis v{} le {} par {}", 
              SYSTEM_VERSION, STARTUP_TIMESTAMP, SYSTEM_USER);
 
+    // Approbation d'un retrait en attente puis arrêt:
+    // --approve <id> --signature <0x…> (approbateur déclaré dans approvals.toml)
+    let cli_args: Vec<String> = std::env::args().collect();
+    let cli_value = |flag: &str| cli_args.iter().position(|arg| arg == flag).and_then(|i| cli_args.get(i + 1).cloned());
+    // Configuration unifiée: aurorae.toml (ou --config <fichier>), AURORAE__SECTION__CLE, --set section.cle=valeur
+    let mut config_source = ConfigSource::from_args(&cli_args);
+    match config_source.load() {
+        Ok(config) => config::install(config),
+        Err(e) => {
+            error!("❌ Configuration invalide:\n{}", e);
+            return;
+        }
+    }
+    // Mode instance (processus enfant lancé par le superviseur):
+    // --instance <uuid> --modules <a,b> --state-dir <répertoire> [--hub <adresse>]
+    if let Some(id) = cli_value("--instance") {
+        let id = match Uuid::parse_str(&id) {
+            Ok(id) => id,
+            Err(e) => {
+                error!("❌ Identifiant d'instance invalide: {}", e);
+                return;
+            }
+        };
+        let modules = cli_value("--modules")
+            .map(|list| list.split(',').filter(|m| !m.is_empty()).map(str::to_string).collect())
+            .unwrap_or_default();
+        let state_dir = cli_value("--state-dir").map(std::path::PathBuf::from).unwrap_or_else(|| ".".into());
+        if let Err(e) = run_child(id, modules, state_dir, cli_value("--hub")).await {
+            error!("❌ Instance {} interrompue: {}", id, e);
+        }
+        return;
+    }
+    // Mode worker de calcul: --worker <adresse du coordinateur> exécute les tâches délestées
+    // (analyse de dépôt, bac à sable de mutation, fragment d'entraînement) puis s'arrête
+    if let Some(coordinator) = cli_value("--worker") {
+        if let Err(e) = ComputeWorker::new(&coordinator).run().await {
+            error!("❌ Worker de calcul interrompu: {}", e);
+        }
+        return;
+    }
+    if let Some(id) = cli_value("--approve") {
+        let result = match id.parse::<Uuid>() {
+            Ok(id) => match (cli_value("--signature"), cli_value("--token")) {
+                (Some(signature), _) => signature.parse::<Signature>()
+                    .map_err(|e| format!("Signature invalide: {}", e))
+                    .and_then(|signature| APPROVALS.write().add_signature(&id, signature).map(|_| ())),
+                // Le démon relit le fichier d'état sans pouvoir vérifier un jeton: seule une
+                // signature d'approbateur franchit la frontière entre processus
+                (None, Some(_)) => Err(format!(
+                    "une approbation par jeton n'est pas vérifiable par le démon: signer le message avec une adresse de {} (--signature)",
+                    DEFAULT_APPROVALS_PATH
+                )),
+                (None, None) => Err("--signature requis".to_string()),
+            },
+            Err(e) => Err(format!("Identifiant de demande invalide: {}", e)),
+        };
+        if let Err(e) = result {
+            error!("❌ Approbation {}: {}", id, e);
+        }
+        return;
+    }
+
+    // File d'approbation: --list-approvals affiche les demandes en attente et le message à
+    // signer, --reject <id> rejette une demande (consigné dans l'audit)
+    if cli_args.iter().any(|arg| arg == "--list-approvals") {
+        let pending = APPROVALS.write().pending();
+        info!("📋 {} demande(s) en attente d'approbation", pending.len());
+        for (id, message) in pending {
+            info!("  {} | {}", id, message);
+        }
+        return;
+    }
+    if let Some(id) = cli_value("--reject") {
+        let result = id.parse::<Uuid>()
+            .map_err(|e| format!("Identifiant de demande invalide: {}", e))
+            .and_then(|id| APPROVALS.write().reject(&id, "cli"));
+        if let Err(e) = result {
+            error!("❌ Rejet {}: {}", id, e);
+        }
+        return;
+    }
+
+    // Journal d'audit: --audit-verify contrôle la chaîne d'empreintes, --audit-export <fichier>
+    // exporte en JSONL ou CSV (selon l'extension), filtré par --audit-kind et --audit-component
+    if cli_args.iter().any(|arg| arg == "--audit-verify") {
+        match AUDIT.lock().verify() {
+            Ok(count) => info!("✅ Journal d'audit intègre: {} entrée(s)", count),
+            Err(e) => {
+                error!("❌ Journal d'audit compromis: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if let Some(output) = cli_value("--audit-export") {
+        let query = AuditQuery {
+            kind: cli_value("--audit-kind").and_then(|kind| AuditKind::parse(&kind)),
+            component: cli_value("--audit-component"),
+            ..AuditQuery::default()
+        };
+        let output = Path::new(&output);
+        if let Err(e) = AUDIT.lock().export(&query, ExportFormat::from_path(output), output) {
+            error!("❌ Export d'audit: {}", e);
+        }
+        return;
+    }
+
+    // Généalogie des génomes et des instances: --export-lineage <fichier.dot|.json>, ou
+    // --lineage-origin <capacité> pour lister les nœuds qui l'ont introduite
+    if let Some(path) = cli_value("--export-lineage") {
+        let output = Path::new(&path);
+        if let Err(e) = LINEAGE.lock().export(LineageFormat::from_path(output), output) {
+            error!("❌ Export de la généalogie: {}", e);
+        }
+        return;
+    }
+    if let Some(capability) = cli_value("--lineage-origin") {
+        let lineage = LINEAGE.lock();
+        for node in lineage.origins_of(&capability) {
+            info!("🌳 {} introduite par {} ({:?}, gén. {}, {})", capability, node.id, node.kind, node.generation, node.label);
+        }
+        return;
+    }
+
+    // Feuille de route: --export-roadmap <fichier.md|.mmd|.json> pour revue humaine,
+    // --import-roadmap <fichier.json|.toml> pour injecter ou corriger des objectifs
+    if let Some(path) = cli_value("--export-roadmap") {
+        if let Err(e) = VisionEngine::new().export(Path::new(&path)) {
+            error!("❌ Export de la feuille de route: {}", e);
+        }
+        return;
+    }
+    if let Some(path) = cli_value("--import-roadmap") {
+        if let Err(e) = VisionEngine::new().import(Path::new(&path)) {
+            error!("❌ Import de la feuille de route: {}", e);
+        }
+        return;
+    }
+
+    // Génération et signature du manifeste d'intégrité après compilation (run.bat) puis arrêt
+    if cli_args.iter().any(|arg| arg == "--sign-integrity-manifest") {
+        let result = match KeySource::from_env() {
+            Ok(source) => match Keystore::connect(&source, 1).await {
+                Ok(signer) => sign_integrity_manifest(Path::new("."), INTEGRITY_MANIFEST_PATH, signer.as_ref()).await.map(|_| ()),
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            error!("❌ Manifeste d'intégrité: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Initialisation de la matrice de défense - protection précoce contre les exploits
     let mut defense_matrix = DefenseMatrix::new();
     defense_matrix.initialize_perimeter();
     defense_matrix.deploy_anomaly_detection();
-    println!("[AURORAE++] 🛡️ Matrice de défense initialisée avec succès");
+    info!("🛡️ Matrice de défense initialisée avec succès");
 
     // Vérification d'intégrité initiale
     let integrity_check = check_integrity("core");
-    if !integrity_check.status {
-        println!("[AURORAE++] ⚠️ Alerte d'intégrité: {}", integrity_check.message);
+    if !integrity_check.is_intact() {
+        warn!("⚠️ Alerte d'intégrité: {}", integrity_check.summary());
         defense_matrix.mitigate_integrity_breach(&integrity_check);
     }
 
     // Validation des composants critiques avant initialisation complète
     let validation = validator::validate_system_components();
-    println!("[AURORAE++] ✅ Validation des composants: {} modules validés", validation.valid_count);
+    info!("✅ Validation des composants: {} modules validés", validation.valid_count);
 
     // Initialisation du cerveau central - système de coordination métacognitive
-    println!("[AURORAE++] 🧠 Initialisation de la structure neurologique centrale");
-    let brain = boot_brain();
+    info!("🧠 Initialisation de la structure neurologique centrale");
+    // Politique d'alignement (alignment.policy) partagée par le cerveau et la boucle principale
+    let alignment = AlignmentSystem::from_env().shared();
+    let brain = boot_brain(alignment.clone());
+    // Modèle de soi: calibration des prédictions de succès (déploiements, mutations)
+    let metacognition = MetacognitiveSystem::new().shared();
+    brain.write().attach_metacognition(metacognition.clone());
+    // Mémoire de travail (porte d'entrée du cerveau), épisodique et sémantique
+    let mut cognition = CognitiveModel::new();
     {
         // Premier cycle cérébral pour établir les connexions neuronales primaires
         let mut brain_lock = brain.write();
@@ -125,80 +406,128 @@ async fn main() {
     }
 
     // Initialisation de la base de connaissance (mémoire vivante)
-    println!("[AURORAE++] 📚 Chargement de la base de connaissance neuromorphique");
+    info!("📚 Chargement de la base de connaissance neuromorphique");
     let mut knowledge_base = KnowledgeBase::load();
-    println!("[AURORAE++] 📚 Base chargée: {} patterns, {} règles inférentielles", 
+    knowledge_base.set_license_policy(LicensePolicy::load_or_default());   // Sources refusées (GPL…) jamais ingérées
+    info!("📚 Base chargée: {} patterns, {} règles inférentielles", 
              knowledge_base.get_patterns().len(), knowledge_base.get_insights_count());
 
     // Initialisation du système de sécurité adaptatif
     let mut security = SecuritySystem::new();
     security.initialize_defenses();
     security.synchronize_with_defense_matrix(&defense_matrix);
-    println!("[AURORAE++] 🔒 Système de sécurité adaptatif initialisé");
+    info!("🔒 Système de sécurité adaptatif initialisé");
+
+    // Staking des tokens forgés: les pénalités viennent du système de sécurité
+    let mut staking = StakingEngine::new();
 
     // Définir l'adresse du fondateur pour la distribution des récompenses
-    set_founder_address("0xFd4456F8d982276Ac7d2294E66Dc8aCc097f0043");
-    println!("[AURORAE++] 💼 Adresse fondateur enregistrée et vérifiée");
+    set_founder_address(&CONFIG.read().system.founder_address);
+    info!("💼 Adresse fondateur enregistrée et vérifiée");
 
     // ============== PHASE 2: APPRENTISSAGE INITIAL ET META-LEARNING ==============
     
     // Initialisation du système de meta-learning pour l'auto-amélioration
-    println!("[AURORAE++] 🔄 Initialisation du système de méta-apprentissage");
+    info!("🔄 Initialisation du système de méta-apprentissage");
     let mut meta_learning = MetaLearningSystem::new();
     meta_learning.initialize_meta_layers();
     
     // Enrichissement initial par clonage de dépôts de référence 
-    println!("[AURORAE++] 🔍 Acquisition de connaissances: chaînes blockchain de référence");
-    clone_repo("https://github.com/paritytech/substrate").ok();
-    clone_repo("https://github.com/solana-labs/solana").ok();
-    clone_repo("https://github.com/algorand/go-algorand").ok();
-    clone_repo("https://github.com/cosmos/cosmos-sdk").ok();
-    
-    // Scan approfondi et méta-apprentissage à partir des sources
-    let patterns = scan_feed_and_learn(&mut knowledge_base);
+    info!("🔍 Acquisition de connaissances: chaînes blockchain de référence");
+    let clone_pool = ClonePool::new(CrawlerConfig::load_or_default());
+    let reference_repos = CONFIG.read().learning.reference_repos.clone();
+    // Curriculum: petits dépôts d'abord, les plus volumineux une fois les précédents maîtrisés
+    let mut curriculum = Curriculum::new();
+    let ranked: Vec<(&str, Difficulty)> = reference_repos.iter().map(|url| (url.as_str(), curriculum.repo_difficulty(url))).collect();
+    let (reference_repos, deferred_repos) = curriculum.sequence(TaskClass::ParseRepo, &ranked);
+    if !deferred_repos.is_empty() {
+        info!("🎓 Dépôts différés par le curriculum: {}", deferred_repos.join(", "));
+    }
+    let mut feed_changes = FeedChanges::default();
+    for (url, outcome) in reference_repos.iter().zip(clone_pool.clone_all(&reference_repos, console_progress()).await) {
+        match outcome {
+            Ok(outcome) => {
+                curriculum.record_repo(url, Some(outcome.bytes));
+                feed_changes.merge(outcome.changes);
+            }
+            Err(e) => {
+                curriculum.record_repo(url, None);
+                warn!("⚠️ {}: {}", url, e);
+            }
+        }
+    }
+    
+    // Méta-apprentissage à partir des seuls fichiers modifiés depuis la dernière mise à jour du feed
+    let patterns = apply_feed_changes(&mut knowledge_base, &feed_changes);
+    knowledge_base.save();
     meta_learning.analyze_patterns(&patterns);
-    println!("[AURORAE++] 📚 Apprentissage primaire terminé: {} patterns extraits, {} meta-règles générées", 
+    info!("📚 Apprentissage primaire terminé: {} patterns extraits, {} meta-règles générées", 
              knowledge_base.get_patterns().len(), meta_learning.get_meta_rules_count());
+
+    // Base de l'évolution de code (sled): seuls les fichiers modifiés du feed sont ré-analysés,
+    // les patterns de la base JSON y sont importés à l'ouverture
+    let feed_dir = CONFIG.read().paths.feed.clone();
+    let mut code_evolution = CodeEvolution::new(&feed_dir.to_string_lossy(), "./aurorae");
+    if let Err(e) = tokio::task::block_in_place(|| code_evolution.scan_changes(&feed_changes)) {
+        warn!("⚠️ Scan d'évolution de code impossible: {}", e);
+    }
     
     // Extraction ciblée de patterns avancés pour l'évolution autonome
     let mut code_evolver = CodeEvolver::new(&knowledge_base);
     code_evolver.prime_with_meta_rules(&meta_learning);
     let advanced_patterns = code_evolver.extract_evolutionary_patterns();
-    println!("[AURORAE++] 🧬 Extraction d'évolution: {} patterns évolutifs identifiés", advanced_patterns.len());
+    info!("🧬 Extraction d'évolution: {} patterns évolutifs identifiés", advanced_patterns.len());
     
     // Apprentissage des meilleures pratiques blockchain existantes
     search_best_rust_chains();
+    // Crates blockchain de crates.io (client bloquant, hors des fils du runtime)
+    let crates_ingestion = tokio::task::block_in_place(|| {
+        CratesExplorer::new().and_then(|explorer| explorer.ingest_blockchain_crates(&mut knowledge_base, 10))
+    });
+    match crates_ingestion {
+        Ok(_) => knowledge_base.save(),
+        Err(e) => warn!("⚠️ Exploration crates.io impossible: {}", e),
+    }
     meta_learning.integrate_blockchain_patterns(&knowledge_base);
-    
-    // Nettoyage du feed pour libérer de l'espace
-    clear_feed().ok();
 
     // ============== PHASE 3: INITIALISATION DU CORE NEUROMORPHIQUE ==============
     
     // Initialisation du moteur de base
-    println!("[AURORAE++] ⚙️ Initialisation du moteur central");
+    info!("⚙️ Initialisation du moteur central");
     let mut core_engine = CoreEngine::new();
     core_engine.attach_knowledge_base(&knowledge_base);
     core_engine.attach_meta_learning(&meta_learning);
     
     // Initialisation du core de l'écosystème autonome
-    println!("[AURORAE++] 🧬 Initialisation du core autonome principal");
+    info!("🧬 Initialisation du core autonome principal");
     let mut core = AuroraeCore::new();
+    // Toute dépense autonome est soumise à la politique d'alignement
+    core.economy.budget_handle().write().attach_alignment(alignment.clone());
+    // Alertes opérateur: menaces, corruptions et brèches d'intégrité diffusées sur le bus
+    let _alerting = spawn_alerting(&core.event_bus, AlertRouter::from_env());
+    security.attach_event_bus(core.event_bus.clone());
+    if !integrity_check.is_intact() {
+        core.event_bus.publish(AuroraeEvent::IntegrityBreach {
+            component: integrity_check.component.clone(),
+            details: integrity_check.summary(),
+        });
+    }
     
     // Synchronisation du core avec le moteur central
     core_engine.attach_aurorae_core(&mut core);
     
     // Initialisation des sous-systèmes stratégiques avec traçabilité
-    println!("[AURORAE++] 💹 Initialisation des systèmes économiques dynamiques");
+    info!("💹 Initialisation des systèmes économiques dynamiques");
     core.economy.initialize();
-    core.economy.set_volatility_parameters(0.03, 0.12); // Paramètres de volatilité contrôlée
+    let economy_settings = CONFIG.read().economy.clone();
+    core.economy.set_volatility_parameters(economy_settings.volatility_min, economy_settings.volatility_max); // Paramètres de volatilité contrôlée
     
-    println!("[AURORAE++] 🧠 Calibrage du noyau d'intelligence récursive");
+    info!("🧠 Calibrage du noyau d'intelligence récursive");
     core.intelligence.initialize();
     core.intelligence.prime_with_meta_learning(&meta_learning);
     
     // Initialisation du système de vision stratégique avec horizons multiples
-    println!("[AURORAE++] 🔭 Configuration du moteur de vision stratégique");
+    info!("🔭 Configuration du moteur de vision stratégique");
     let mut vision = VisionEngine::new();
     
     // Projections stratégiques multi-horizon
@@ -226,62 +555,219 @@ async fn main() {
     // ============== PHASE 4: INITIALISATION DE L'INFRASTRUCTURE BLOCKCHAIN ==============
     
     // Initialisation de l'interface blockchain multichaîne
-    println!("[AURORAE++] ⛓️ Initialisation de l'interface blockchain multichaîne");
-    let provider = BlockchainInterface::get_http_provider("https://eth-sepolia.g.alchemy.com/v2/YOUR_KEY").unwrap();
+    info!("⛓️ Initialisation de l'interface blockchain multichaîne");
+    // Registre des chaînes (chains.toml), rechargé à chaud
+    let chain_registry = match ChainRegistry::load(DEFAULT_REGISTRY_PATH) {
+        Ok(registry) => registry.shared(),
+        Err(e) => {
+            error!("❌ Registre des chaînes invalide: {}", e);
+            return;
+        }
+    };
+    let _registry_watcher = spawn_hot_reload(chain_registry.clone(), Duration::from_secs(30));
+    Deployer::attach_chain_registry(chain_registry.clone());
+    // Profils des protocoles notables des chaînes du registre (liste de protocols.toml)
+    let registered_chains: Vec<_> = {
+        let registry = chain_registry.read();
+        registry.names().iter().filter_map(|name| registry.get(name).cloned()).collect()
+    };
+    let protocol_profiles = ProtocolScanner::new(ProtocolCatalog::load_or_default()).scan_chains(&registered_chains).await;
+    if let Err(e) = save_protocol_profiles(&protocol_profiles, Path::new(PROTOCOL_PROFILES_PATH)) {
+        warn!("⚠️ Profils de protocoles non sauvegardés: {}", e);
+    }
+    let primary_chain_name = CONFIG.read().network.primary_chain.clone();
+    let primary_chain = match chain_registry.read().get(&primary_chain_name).cloned() {
+        Some(chain) => chain,
+        None => {
+            let reason = chain_registry.read().disabled_reason(&primary_chain_name).map(str::to_string)
+                .unwrap_or_else(|| "absente du registre".to_string());
+            error!("❌ Chaîne principale {} indisponible: {}", primary_chain_name, reason);
+            std::process::exit(1);
+        }
+    };
+    // Clé du déployeur: keystore chiffré, variable d'environnement ou trousseau (AURORAE_KEY_SOURCE)
+    let key_source = match KeySource::from_env() {
+        Ok(source) => source,
+        Err(e) => {
+            error!("❌ Source de clé invalide: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let signer = match Keystore::connect(&key_source, primary_chain.chain_id.unwrap_or(1)).await {
+        Ok(signer) => signer,
+        Err(e) => {
+            error!("❌ Aucune clé de déploiement disponible: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let signing_policy = SigningPolicy::for_signer(signer.as_ref());
+    let backend = match BlockchainInterface::connect_chain(&primary_chain, &signer).await {
+        Ok(backend) => backend,
+        Err(e) => {
+            error!("❌ Backend blockchain indisponible: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let gas_oracle = GasOracle::new(FeeStrategy::Fast);
+    // Un gestionnaire de nonces par compte et par chaîne: tous les envois passent par lui
+    let tx_managers = TxManagers::new(gas_oracle.clone());
+    let tx_manager = tx_managers.for_backend(&backend);
+    // Le budget tient ses comptes en USD: le gas est valorisé au cours de la monnaie native
+    core.economy.budget_handle().write().gas_asset = Asset::native(&primary_chain.native_currency.symbol);
+    core.economy.analyze_market_trends().await;
+    match gas_oracle.estimate_fees(backend.as_ref()).await {
+        Ok(fees) => {
+            core.forge.set_fee_estimate(fees.clone());
+            core.economy.alchemy.set_fee_estimate(fees);
+        }
+        Err(e) => warn!("⚠️ Estimation des frais indisponible: {}", e),
+    }
+    // Suivi de finalité: réorganisations et finalisations publiées sur le bus (journal on-chain)
+    let (_finality_task, finality) = FinalityTracker::new(backend.clone(), core.event_bus.clone(), FINALITY_CONFIRMATIONS).follow();
+    if let Some(ws_url) = &primary_chain.ws {
+        if let Err(e) = BlockchainInterface::subscribe_blocks(ws_url, core.event_bus.clone()).await {
+            warn!("⚠️ Souscription aux blocs indisponible: {}", e);
+        }
+        // Ventes secondaires des collections (royalties EIP-2981)
+        if let Err(e) = BlockchainInterface::subscribe_logs(ws_url, sales_filter(), core.event_bus.clone()).await {
+            warn!("⚠️ Souscription aux ventes NFT indisponible: {}", e);
+        }
+        // Tokens ERC-20 reçus par le compte du déployeur
+        if let Err(e) = BlockchainInterface::subscribe_logs(ws_url, incoming_transfer_filter(signer.address()), core.event_bus.clone()).await {
+            warn!("⚠️ Souscription aux transferts entrants indisponible: {}", e);
+        }
+    }
+    core.economy.watch_address(signer.address());
     
-    // Configuration des points d'ancrage blockchain
-    let mut blockchain_anchors = BlockchainInterface::initialize_anchor_points();
-    blockchain_anchors.add_evm_compatible_chain("Polygon", "https://polygon-rpc.com");
-    blockchain_anchors.add_evm_compatible_chain("Avalanche", "https://api.avax.network/ext/bc/C/rpc");
-    blockchain_anchors.add_substrate_chain("Polkadot", "wss://rpc.polkadot.io");
+    // Points d'ancrage blockchain: toutes les chaînes du registre
+    for chain in chain_registry.read().names() {
+        info!("⚓ Point d'ancrage enregistré: {}", chain);
+    }
+
+    // Ponts lock-and-mint (bridges.toml): un relayeur par route; les blocs observés sur le bus
+    // servent de points de contrôle aux preuves des messages
+    let bridges_config = BridgesConfig::load_or_default();
+    // Les schémas de protocoles traduisent chaque transfert dans les termes de la chaîne destination
+    let mut cross_chain = CrossChainRouter::new(chain_registry.clone())
+        .with_tx_managers(tx_managers.clone())
+        .with_schemas(SchemaRegistry::load_or_default());
+    let header_checkpoints = HeaderCheckpoints::new();
+    let _checkpoint_tracker = header_checkpoints.track(&core.event_bus);
+    let substrate_chains: Vec<_> = chain_registry.read().chains_of_kind(ChainKind::Substrate).into_iter().cloned().collect();
+    for chain in substrate_chains {
+        let name = chain.name.clone();
+        if let Err(e) = cross_chain.add_substrate_chain(chain).await {
+            warn!("⚠️ Chaîne Substrate {} injoignable: {}", name, e);
+        }
+    }
+    // Sous-chaînes simulées (topologie persistée): celles qui tournaient sont relancées, leurs
+    // liens deviennent des ponts du routeur
+    let mut network_map = NetworkMap::open(std::path::Path::new(NETWORK_TOPOLOGY_PATH));
+    network_map.register_bridges(&mut cross_chain);
+    network_map.map_summary();
+    let mut bridge_relayers = Vec::new();
+    for route in &bridges_config.bridges {
+        let source_ws = chain_registry.read().get(&route.source).and_then(|chain| chain.ws.clone());
+        if let Some(ws_url) = source_ws.filter(|_| route.source != primary_chain.name) {
+            if let Err(e) = BlockchainInterface::subscribe_blocks(&ws_url, core.event_bus.clone()).await {
+                warn!("⚠️ Souscription aux blocs de {} indisponible: {}", route.source, e);
+            }
+        }
+        match cross_chain.open_bridge(
+            route,
+            &bridges_config.relayer,
+            &signer,
+            &core.economy.budget_handle(),
+            &gas_oracle,
+            Some(&header_checkpoints),
+        ).await {
+            Ok(relayer) => bridge_relayers.push(spawn_relayer(relayer)),
+            Err(e) => warn!("⚠️ Pont {} → {} indisponible: {}", route.source, route.destination, e),
+        }
+    }
+
+    // Manifeste de déploiement (--manifest <chemin>); --dry-run affiche le plan sans rien envoyer,
+    // par défaut celui de deployments/
+    let dry_run = cli_args.iter().any(|arg| arg == "--dry-run");
+    let manifest_path = cli_args.iter()
+        .position(|arg| arg == "--manifest")
+        .and_then(|i| cli_args.get(i + 1).cloned())
+        .or_else(|| default_manifest_path().filter(|_| dry_run).map(|path| path.to_string()));
+    if let Some(path) = manifest_path {
+        let result = match DeploymentManifest::load(&path) {
+            Ok(manifest) if manifest.chain != primary_chain.name => {
+                Err(format!("Manifeste prévu pour {}, chaîne active {}", manifest.chain, primary_chain.name))
+            }
+            Ok(manifest) => ManifestRunner::execute(
+                &manifest,
+                &ContractBuilder::default(),
+                &core.economy.budget_handle(),
+                backend.as_ref(),
+                &gas_oracle,
+                dry_run,
+            ).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            error!("❌ Manifeste {}: {}", path, e);
+        }
+    }
+    if dry_run {
+        info!("🧪 Mode dry-run: arrêt avant tout déploiement");
+        return;
+    }
     
+    // Étapes de démarrage suspendues à une dépense soumise à approbation: reprises par la boucle
+    let mut deployment_parked = false;
+    let mut mint_parked = false;
+
     // Déploiement du contrat principal avec vérification formelle
-    println!("[AURORAE++] 📝 Vérification formelle du contrat principal...");
-    let verification = validator::verify_contract_correctness("auroraium_erc20.json");
+    info!("📝 Vérification formelle du contrat principal...");
+    // Le contrat vérifié est l'implémentation effectivement servie par le proxy
+    let verification = formal_verification::verify_contract_correctness(AURORAIUM_CONTRACT);
     if verification.is_valid {
-        println!("[AURORAE++] ✅ Vérification formelle validée: {}", verification.proof_hash);
+        info!("✅ Vérification formelle validée: {}", verification.proof_hash);
         
-        println!("[AURORAE++] 🔄 Déploiement du contrat sur la blockchain...");
-        let address = Deployer::deploy_contract(
-            provider,
-            "INSERT_YOUR_PRIVATE_KEY_HERE",
-            "auroraium_erc20.json",
-            "auroraium_bytecode.json"
+        info!("🔄 Déploiement du contrat sur la blockchain...");
+        let prediction = metacognition.write().predict(DecisionKind::Deployment, 0.9);
+        let address = deploy_auroraium(
+            &primary_chain,
+            signer.as_ref(),
+            &signing_policy,
+            &core.economy.budget_handle(),
+            &tx_manager,
+            &gas_oracle,
         ).await;
+        metacognition.write().observe(prediction, address.is_ok());
 
+        let parked = core.economy.budget_handle().read().parked_spend("deployer", BudgetCategory::Gas);
         match address {
             Ok(addr) => {
-                println!("[AURORAE++] ✅ Contrat ERC20 déployé: {}", addr);
-                
-                // Création d'un token sur la blockchain une fois le contrat déployé
-                let _token_id = core.forge.mint_token("Auroraium", TokenKind::Fungible, 1_000_000, 0.05).await;
-                println!("[AURORAE++] 💰 Token Auroraium créé: 1,000,000 unités à valeur initiale: 0.05");
-                
-                // Récompense pour le fondateur
-                reward_founder(1337.0);
-                println!("[AURORAE++] 🎁 Récompense fondateur distribuée: 1,337.0 $AURA");
-                
-                // Initialisation des liquidity pools
-                core.economy.initialize_liquidity_pools(&addr, 250000.0).await;
-                println!("[AURORAE++] 💧 Pools de liquidité initialisés avec 250,000 tokens");
+                info!("✅ Contrat ERC20 déployé: {}", addr);
+                mint_parked = !bootstrap_auroraium(&mut core, &mut staking, &addr).await;
+            },
+            // Dépense soumise à approbation: la boucle principale reprend le déploiement
+            Err(e) if parked.is_some() => {
+                info!("⏸️ Déploiement d'Auroraium en attente d'approbation budgétaire: {}", e);
+                deployment_parked = true;
             },
             Err(e) => {
-                println!("[AURORAE++] ❌ Erreur de déploiement: {}", e);
+                error!("❌ Erreur de déploiement: {}", e);
                 // Plan de contingence en cas d'échec de déploiement
                 defense_matrix.mitigate_deployment_failure();
                 core.blockchain.switch_to_fallback_network().await;
             },
         }
     } else {
-        println!("[AURORAE++] ⚠️ Échec de vérification formelle: {}", verification.error_message);
-        println!("[AURORAE++] 🔄 Application de correctifs automatiques...");
-        let fixed_contract = refactor::fix_contract_issues("auroraium_erc20.json", verification.issues);
-        // TODO: relancer la vérification et le déploiement avec le contrat corrigé
+        // Déploiement bloqué: chaque contre-exemple désigne la fonction fautive
+        for (property, counterexample) in verification.counterexamples() {
+            warn!("⚠️ Échec de vérification formelle ({:?}): {}", property, counterexample);
+        }
     }
     
     // Création d'une collection NFT évolutive avec métadonnées dynamiques
     let collection_id = core.nft_minter.create_evolutionary_collection();
-    println!("[AURORAE++] 🎨 Collection NFT auto-évolutive créée: {}", collection_id);
+    info!("🎨 Collection NFT auto-évolutive créée: {}", collection_id);
     
     // Configuration des NFTs gouvernance pour le DAO
     let governance_collection = core.nft_minter.create_governance_collection(
@@ -289,35 +775,72 @@ async fn main() {
         "Gouvernance décentralisée évolutive",
         100 // Nombre de tokens de gouvernance
     );
-    println!("[AURORAE++] 🏛️ Collection de gouvernance initialisée: {}", governance_collection);
+    info!("🏛️ Collection de gouvernance initialisée: {}", governance_collection);
     
     // ============== PHASE 5: INITIALISATION DES SYSTÈMES D'IA AVANCÉS ==============
     
     // Initialisation du moteur de génome pour l'évolution algorithmique
-    println!("[AURORAE++] 🧬 Initialisation du moteur de génome évolutif");
+    info!("🧬 Initialisation du moteur de génome évolutif");
     let mut genome_builder = GenomeBuilder::new();
     genome_builder.initialize_base_genome_from_patterns(&advanced_patterns);
     
     // Initialisation du moteur d'évolution pour l'auto-modification de code
-    println!("[AURORAE++] 🔬 Configuration du moteur d'évolution algorithmique");
+    info!("🔬 Configuration du moteur d'évolution algorithmique");
     let mut evolution_engine = EvolutionEngine::new();
+    // Bac à sable des mutations: seules celles qui battent la référence sont écrites
+    let mut mutation_sandbox = MutationSandbox::new(std::path::Path::new("."));
+    // Aptitude des génomes mesurée sur le crate réel et l'économie
+    evolution_engine.attach_fitness_service(FitnessService::new(std::path::Path::new(".")));
     evolution_engine.set_selection_strategy(SelectionStrategy::TournamentSelection);
     evolution_engine.set_mutation_rate(0.03); // 3% de chance de mutation par génération
     evolution_engine.set_crossover_rate(0.7); // 70% de chance de croisement entre individus
     evolution_engine.attach_genome_builder(&genome_builder);
     
     // Initialisation du système de reproduction - clonage et propagation
-    println!("[AURORAE++] 🌱 Initialisation du moteur de reproduction autonome");
+    info!("🌱 Initialisation du moteur de reproduction autonome");
     let mut reproduction = ReproductionEngine::new();
     reproduction.set_evolution_engine(&evolution_engine);
     reproduction.set_complexity_threshold(3); // Niveau minimum de complexité pour les clones
+    match InstanceSupervisor::with_processes(InstanceLimits::default()) {
+        Ok(supervisor) => reproduction.attach_supervisor(supervisor.shared()),
+        Err(e) => warn!("⚠️ Instances non exécutées: {}", e),
+    }
+    // Grappe Raft entre le parent et ses instances: registre des chaînes, politique de trésorerie
+    // et manifeste des modules partagés; le parent la fonde et y ajoute les instances connectées
+    let consensus_config = ConsensusConfig::load_or_default();
+    let mut raft = RaftNode::new(consensus_config.raft.clone());
+    raft.bootstrap();
+    // Hub de messages: les instances lancées s'y connectent (battements, capacités, tâches);
+    // son identité est celle du parent dans la grappe Raft
+    let hub_id = raft.id;
+    let mut instance_hub = match MessageHub::bind(hub_id, "127.0.0.1:0", core.event_bus.clone()).await {
+        Ok((hub, inbox)) => {
+            if let Some(supervisor) = reproduction.supervisor() {
+                supervisor.set_hub_address(hub.address());
+            }
+            Some((hub, inbox))
+        }
+        Err(e) => {
+            warn!("⚠️ Messagerie inter-instances indisponible: {}", e);
+            None
+        }
+    };
+    let mut knowledge_sync = KnowledgeSync::new(hub_id, DEFAULT_SYNC_INTERVAL);
+    // Essaim (swarm.toml): menaces, patterns et stratégies diffusés par bavardage épidémique
+    let swarm_config = SwarmConfig::load_or_default();
+    let mut gossip = GossipNode::new(hub_id, swarm_config.gossip.clone());
     
     // Création des premières instances autonomes
     let first_clone = reproduction.spawn_instance(
         "Clone Cognitif Alpha", 
         vec!["autonomy", "dream", "intelligence", "evolution"]
     );
-    println!("[AURORAE++] 🌱 Instance autonome primaire créée: {}", first_clone.id);
+    info!("🌱 Instance autonome primaire créée: {}", first_clone.id);
+    if let Some(supervisor) = reproduction.supervisor() {
+        if let Err(e) = supervisor.start(&first_clone).await {
+            warn!("⚠️ {}", e);
+        }
+    }
     
     // Création d'instances spécialisées
     let econ_instance = reproduction.spawn_specialized_instance(
@@ -326,7 +849,7 @@ async fn main() {
         0.75, // Facteur de spécialisation économique
         &knowledge_base
     );
-    println!("[AURORAE++] 🌱 Instance économique spécialisée créée: {}", econ_instance.id);
+    info!("🌱 Instance économique spécialisée créée: {}", econ_instance.id);
     
     let security_instance = reproduction.spawn_specialized_instance(
         "Sentinelle de Sécurité Gamma",
@@ -334,10 +857,10 @@ async fn main() {
         0.85, // Facteur de spécialisation sécurité
         &knowledge_base
     );
-    println!("[AURORAE++] 🌱 Instance de sécurité créée: {}", security_instance.id);
+    info!("🌱 Instance de sécurité créée: {}", security_instance.id);
     
     // Initialisation du moteur de rêve - génération créative avec renforcement méta-cognitif
-    println!("[AURORAE++] 💭 Initialisation du moteur de rêve créatif");
+    info!("💭 Initialisation du moteur de rêve créatif");
     let mut dreamer = DreamEngine::new();
     dreamer.attach_knowledge_base(&knowledge_base);
     dreamer.attach_meta_learning(&meta_learning);
@@ -345,20 +868,18 @@ async fn main() {
     // Génération des premiers rêves stratégiques
     dreamer.imagine(
         "Paradigme Consensus Hybride", 
-        "Créer une chaîne avec consensus hybride PoS/PoI adaptatif", 
-        "https://storage.aurorae.io/dreams/consensus_hybrid.jpg"
+        "Créer une chaîne avec consensus hybride PoS/PoI adaptatif",
     ).await;
     
     dreamer.imagine(
         "Oracle Décentralisé Neuromorphique", 
-        "Système d'oracle auto-ajustable basé sur réseaux neuronaux distribués", 
-        "https://storage.aurorae.io/dreams/neural_oracle.jpg"
+        "Système d'oracle auto-ajustable basé sur réseaux neuronaux distribués",
     ).await;
     
-    println!("[AURORAE++] 💭 Rêves initiaux générés et mémorisés");
+    info!("💭 Rêves initiaux générés et mémorisés");
     
     // Initialisation du gardien - surveillance multicouche et protection
-    println!("[AURORAE++] 🛡️ Initialisation du système gardien");
+    info!("🛡️ Initialisation du système gardien");
     let mut guardian = GuardianSentinel::new();
     // Modules critiques à surveiller en priorité
     guardian.register_module("autonomy");
@@ -368,21 +889,65 @@ async fn main() {
     guardian.register_module("brain");
     guardian.set_breach_response_protocol(3); // Niveau 3: Isolement et réparation automatique
     guardian.synchronize_defense_matrix(&defense_matrix);
+    guardian.attach_event_bus(core.event_bus.clone());
+    guardian.attach_budget(core.economy.budget_handle());
+    let guardian = guardian.shared();
+    // Battements de cœur: le gardien interroge les modules et récupère ceux qui ne répondent plus
+    let heartbeat_probes: Vec<Arc<dyn HealthProbe>> = vec![
+        Arc::new(ChainProbe::new("blockchain_core", backend.clone())),
+        Arc::new(LockProbe::new("economy", core.economy.budget_handle(), Duration::from_secs(5))),
+        Arc::new(LockProbe::new("brain", brain.clone(), Duration::from_secs(5))),
+    ];
+    let _heartbeat = spawn_heartbeat(guardian.clone(), heartbeat_probes, HeartbeatConfig::default());
+    // Instances en cours d'exécution: surveillées et redémarrées par le gardien
+    let _instance_heartbeat = reproduction.supervisor().map(|supervisor| {
+        for id in supervisor.instances() {
+            guardian.write().register_module(&InstanceSupervisor::module_name(&id));
+        }
+        spawn_heartbeat(guardian.clone(), supervisor.probes(), HeartbeatConfig::default())
+    });
     
     // Initialisation du stratège avec capacités de consultation IA externe
-    println!("[AURORAE++] 🧠 Initialisation du système stratégique");
-    let strategist = Strategist::new("sk-****************************"); // Clé API sécurisée
+    info!("🧠 Initialisation du système stratégique");
+    // Fournisseur de llm.toml (OpenAI, Azure, Anthropic, Ollama, llama.cpp), clés lues dans le magasin de secrets
+    let llm_config = LlmConfig::load_or_default();
+    // Chaque appel est plafonné par la politique (limit llm cost) et imputé au budget Inference;
+    // le cache, placé devant, évite de refacturer les consultations répétées
+    let llm = llm_config.provider()
+        .map(|provider| -> Arc<dyn LlmProvider> {
+            let metered: Arc<dyn LlmProvider> = Arc::new(MeteredProvider::new(
+                provider,
+                PricingTable::new(&llm_config.pricing),
+                alignment.clone(),
+                core.economy.budget_handle(),
+            ));
+            match CachedProvider::from_config(metered.clone(), &llm_config.cache) {
+                Some(cached) => Arc::new(cached),
+                None => metered,
+            }
+        })
+        .map_err(|e| warn!("⚠️ Modèle de langage indisponible: {}", e))
+        .ok();
+    let strategist = Strategist::new(llm.clone(), llm_config.model_for("strategist"));
+    if let Some(provider) = &llm {
+        dreamer.set_llm_provider(provider.clone(), llm_config.model_for("dream"));
+    }
+    // Plongements du même fournisseur (par lots, débit limité) pour la base de savoir et les rêves
+    let embedder = llm_config.embedder();
+    knowledge_base.set_embedding_provider(embedder.clone());
+    code_evolution.set_embedding_provider(embedder.clone());
+    dreamer.set_embedding_provider(embedder);
     strategist.set_consultation_limits(5, 24); // 5 consultations max par 24h
     strategist.attach_vision_engine(&vision);
     
     // Initialisation du réseau neuronal de décision multicouche
-    println!("[AURORAE++] 🔄 Configuration du réseau neuronal décisionnel");
+    info!("🔄 Configuration du réseau neuronal décisionnel");
     let vs = nn::VarStore::new(Device::Cpu);
     
     // Architecture neuromorphique inspirée du cortex préfrontal
     let network_architecture = vec![128, 96, 64, 48, 32, 24];
-    let decision_net = DecisionNet::new(&vs, 16, network_architecture, 8);
-    println!("[AURORAE++] 🧠 Réseau de décision initialisé: [16→128→96→64→48→32→24→8]");
+    let mut decision_net = DecisionNet::new(&vs, 16, network_architecture.clone(), 8);
+    info!("🧠 Réseau de décision initialisé: [16→128→96→64→48→32→24→8]");
     
     // Configuration de l'optimiseur avec décomposition du gradient
     let mut optimizer = nn::Adam::default()
@@ -392,7 +957,7 @@ async fn main() {
         .build(&vs, 1e-3).unwrap();
     
     // Initialisation de l'agent d'apprentissage par renforcement avec meta-apprentissage
-    println!("[AURORAE++] 🧪 Initialisation de l'agent d'apprentissage récursif");
+    info!("🧪 Initialisation de l'agent d'apprentissage récursif");
     let mut learning_agent = LearningAgent::new(
         vec![
             "generate_code".to_string(),
@@ -423,46 +988,300 @@ async fn main() {
         agent_config
     );
     
+    // Devnet local (devnet.toml): environnement sans risque d'un second agent, qui apprend à
+    // régler ses frais face à l'encombrement des blocs
+    let mut devnet_env = DevnetEnvironment::new(DevnetConfig::load_or_default().seed)
+        .map_err(|e| warn!("⚠️ Devnet indisponible: {}", e))
+        .ok();
+    let mut devnet_agent = LearningAgent::new(DevnetEnvironment::actions(), "devnet");
+    
+    // Réseau impulsionnel LIF, comparé à DecisionNet en ombre de l'agent (neuromorphic.toml)
+    let mut spiking_policy = SpikingPolicy::new(NeuromorphicConfig::load_or_default(), learning_agent.actions.len());
+    let mut backend_comparison = BackendComparison::default();
+    
+    // Banc des moteurs de consensus des chaînes générées (consensus.toml, section [lab]):
+    // l'action evolve_consensus règle ou remplace le moteur sur mesures simulées
+    let mut consensus_lab = ConsensusLab::new(consensus_config.lab.clone()).unwrap_or_else(|e| {
+        warn!("⚠️ {}: moteur de consensus par défaut", e);
+        ConsensusLab::new(Default::default()).expect("Moteur de consensus par défaut")
+    });
+    
+    // Espace de travail global: les modules soumettent leurs coalitions, une seule est diffusée
+    // par cycle à tous les abonnés, et seul le module gagnant agit
+    let mut workspace = GlobalWorkspace::new();
+    let mut meta_inbox = workspace.subscribe("meta_learning");
+    let mut generation_inbox = workspace.subscribe("generation");
+    let mut dream_inbox = workspace.subscribe("dream");
+    let mut strategist_inbox = workspace.subscribe("strategist");
+    let mut cognition_inbox = workspace.subscribe("cognition");
+    
+    // Calcul distribué (compute.toml): un coordinateur par adresse d'écoute, entre lesquels
+    // l'ordonnanceur répartit les travaux lourds selon l'urgence des pensées qui les motivent
+    let compute_config = ComputeConfig::load_or_default();
+    let mut compute = compute_config.enabled.then(|| ComputeScheduler::new(compute_config.scheduler.clone()));
+    if let Some(scheduler) = compute.as_mut() {
+        for listen in std::iter::once(&compute_config.listen).chain(&compute_config.additional_nodes) {
+            let node = ComputeNode::new(ComputeConfig { listen: listen.clone(), ..compute_config.clone() });
+            match node.serve().await {
+                Ok(address) => scheduler.register_node(&address.to_string(), node),
+                Err(e) => warn!("⚠️ {}", e),
+            }
+        }
+    }
+    // Analyses de dépôts, mutations et fragments d'entraînement délestés, en attente de résultat
+    let mut offloaded = OffloadedWork::new();
+    // Recherches d'hyperparamètres par essaim, évaluées par les workers quand il y en a
+    let mut best_configs = BestConfigs::new();
+    let mut hyper_search: Option<HyperparameterSearch> = None;
+    
     // ============== PHASE 6: GÉNÉRATION ET MUTATION CRÉATIVE ==============
     
     // Génération de nouveaux modules fonctionnels
-    println!("[AURORAE++] ⚡ Génération de modules évolutifs");
-    trigger_generation("./generated_modules", "energy_core");
-    trigger_generation("./generated_modules", "consensus_adapter");
-    trigger_generation("./generated_modules", "economic_stabilizer");
-    
-    // Mutation du code existant pour amélioration avec directives évolutives
-    println!("[AURORAE++] 🧬 Mutation guidée des modules critiques");
-    mutate_module_code("./aurorae/autonomy.rs");
-    mutate_module_code("./aurorae/blockchain_core.rs");
-    mutate_module_code("./aurorae/economy.rs");
+    info!("⚡ Génération de modules évolutifs");
+    trigger_generation("./generated_modules", "energy_core", "metric_collector", &TemplateArgs::from([
+        ("metric".to_string(), ParamValue::Str("energy".to_string())),
+        ("alert_threshold".to_string(), ParamValue::Float(0.85)),
+    ]));
+    trigger_generation("./generated_modules", "consensus_adapter", "consensus_adapter", &TemplateArgs::from([
+        ("validators".to_string(), ParamValue::Int(7)),
+    ]));
+    trigger_generation("./generated_modules", "economic_stabilizer", "oracle_client", &TemplateArgs::from([
+        ("endpoint".to_string(), ParamValue::Str("https://api.coingecko.com/api/v3/simple/price".to_string())),
+        ("feed".to_string(), ParamValue::Str("AUR/USD".to_string())),
+    ]));
     
+    // Les mutations du code ne passent que par l'action `mutate_self` de la boucle principale:
+    // politique d'alignement, file d'approbation, puis bac à sable (tests, propriétés, clippy)
+    // Snapshot pré-optimisation: restauré si le gardien détecte une corruption dans les cycles suivants
+    let mut mutation_watch = SNAPSHOTS.lock()
+        .capture("pré-mutation (phase 6)", &knowledge_base, &core.economy, &learning_agent, &reproduction)
+        .map(|snapshot| MutationWatch::new(snapshot.id, &guardian.read().corrupted_modules()))
+        .map_err(|e| warn!("⚠️ Snapshot pré-mutation impossible: {}", e))
+        .ok();
+
     // Application des patterns d'optimisation de l'évolution
     code_evolver.apply_optimization_patterns("./aurorae");
-    println!("[AURORAE++] ⚙️ Patterns d'optimisation appliqués");
+    info!("⚙️ Patterns d'optimisation appliqués");
+
+    // Revérification du manifeste signé: chaque fichier divergent est signalé
+    let post_mutation = check_integrity("core");
+    if !post_mutation.is_intact() {
+        core.event_bus.publish(AuroraeEvent::IntegrityBreach {
+            component: post_mutation.component.clone(),
+            details: post_mutation.summary(),
+        });
+    }
     
     // ============== PHASE 7: BOUCLE PRINCIPALE DU SYSTÈME ==============
-    println!("[AURORAE++] 🚀 Initialisation complète - Démarrage de la boucle principale du système");
-    println!("[AURORAE++] 🔄 Cycle autonome lancé à {}", STARTUP_TIMESTAMP);
+    info!("🚀 Initialisation complète - Démarrage de la boucle principale du système");
+    info!("🔄 Cycle autonome lancé à {}", STARTUP_TIMESTAMP);
     
     // Variables d'état pour la boucle principale
     let mut cycle_count = 0;
     let mut last_evolution_timestamp = Utc::now();
     let mut last_security_audit = Utc::now();
     let mut accumulated_rewards = 0.0;
+    let mut resource_monitor = ResourceMonitor::new();
+    let mut update_checker = UpdateChecker::new(std::path::Path::new("."));
     
     // Boucle principale du système autonome
     loop {
         cycle_count += 1;
         let cycle_start = Utc::now();
+        // Span du cycle: la boucle principale s'exécute sur le thread de `block_on`, les tâches
+        // lancées ailleurs n'y entrent pas
+        let cycle_span = tracing::info_span!("cycle", cycle = cycle_count);
+        let _cycle = cycle_span.enter();
+        
+        // --- ACTIVITÉ ON-CHAIN ---
+        let chain_events = core.process_chain_events(&mut guardian.write());
+        if chain_events > 0 {
+            info!("📬 {} événements on-chain traités", chain_events);
+        }
+        core.resolve_token_decimals(backend.as_ref()).await;
+        
+        // --- DÉMARRAGE D'AURORAIUM EN ATTENTE D'APPROBATION ---
+        // Le déploiement (ou le mint) est relancé une fois sa dépense approuvée dans la file
+        // `approvals`: la nouvelle demande du module exécute la dépense approuvée
+        if deployment_parked || mint_parked {
+            let module = if deployment_parked { "deployer" } else { "alchemy" };
+            let parked = core.economy.budget_handle().read().parked_spend(module, BudgetCategory::Gas);
+            match parked {
+                Some(ApprovalCheck::Approved { id, .. }) if deployment_parked => {
+                    info!("▶️ Dépense {} approuvée: reprise du déploiement d'Auroraium", id);
+                    let address = deploy_auroraium(
+                        &primary_chain,
+                        signer.as_ref(),
+                        &signing_policy,
+                        &core.economy.budget_handle(),
+                        &tx_manager,
+                        &gas_oracle,
+                    ).await;
+                    match address {
+                        Ok(addr) => {
+                            info!("✅ Contrat ERC20 déployé: {}", addr);
+                            deployment_parked = false;
+                            mint_parked = !bootstrap_auroraium(&mut core, &mut staking, &addr).await;
+                        }
+                        // Étape suivante du déploiement soumise à son tour à approbation
+                        Err(e) => {
+                            warn!("⚠️ Reprise du déploiement d'Auroraium: {}", e);
+                            deployment_parked = core.economy.budget_handle().read().parked_spend("deployer", BudgetCategory::Gas).is_some();
+                        }
+                    }
+                }
+                Some(ApprovalCheck::Approved { id, .. }) => {
+                    info!("▶️ Dépense {} approuvée: reprise du mint d'Auroraium", id);
+                    match mint_auroraium(&mut core, &mut staking).await {
+                        Ok(()) => mint_parked = false,
+                        Err(e) => {
+                            warn!("⚠️ Reprise du mint d'Auroraium: {}", e);
+                            mint_parked = core.economy.budget_handle().read().parked_spend("alchemy", BudgetCategory::Gas).is_some();
+                        }
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    warn!("⚠️ Démarrage d'Auroraium abandonné: approbation rejetée ou expirée");
+                    deployment_parked = false;
+                    mint_parked = false;
+                }
+            }
+        }
+
+        // --- MESSAGES ET SYNCHRONISATION DES INSTANCES ---
+        if let Some((hub, inbox)) = instance_hub.as_mut() {
+            while let Ok(frame) = inbox.try_recv() {
+                if let InstanceMessage::TaskResult { task_id, success, output } = &frame.message {
+                    info!("📨 Tâche {} de l'instance {}: {} ({})", task_id, frame.from, if *success { "réussie" } else { "échouée" }, output);
+                    reproduction.record_instance_fitness(&frame.from, if *success { 1.0 } else { 0.0 });
+                }
+                if let InstanceMessage::Consensus { from, to, payload } = &frame.message {
+                    if *to == raft.id {
+                        match consensus::open_envelope(payload) {
+                            Ok(message) => raft.step(*from, message),
+                            Err(e) => warn!("⚠️ {}", e),
+                        }
+                    } else if let Err(e) = hub.send(to, frame.message.clone()) {
+                        warn!("⚠️ Message Raft non relayé: {}", e);
+                    }
+                }
+                if let InstanceMessage::Gossip { from, to, payload } = &frame.message {
+                    if *to == gossip.id {
+                        match swarm_intelligence::open_envelope(payload) {
+                            Ok(message) => gossip.step(*from, message),
+                            Err(e) => warn!("⚠️ {}", e),
+                        }
+                    } else if let Err(e) = hub.send(to, frame.message.clone()) {
+                        warn!("⚠️ Message d'essaim non relayé: {}", e);
+                    }
+                }
+                if knowledge_sync.handle(&frame, hub, &mut knowledge_base).is_some() {
+                    knowledge_base.save();
+                }
+            }
+            if knowledge_sync.is_due() {
+                knowledge_sync.share(hub, &knowledge_base, &reproduction.get_active_instances());
+            }
+        }
+        
+        // --- CONSENSUS RAFT ENTRE INSTANCES ---
+        raft.tick();
+        let desired_members: Vec<Uuid> = std::iter::once(raft.id)
+            .chain(instance_hub.as_ref().map(|(hub, _)| hub.peers()).unwrap_or_default())
+            .collect();
+        if let Some(Err(e)) = raft.reconcile_membership(&desired_members) {
+            warn!("⚠️ Composition Raft: {}", e);
+        }
+        // Le leader propose les valeurs locales qui divergent de l'état partagé, une fois le
+        // journal entièrement validé pour ne pas répéter une proposition en cours
+        if raft.is_leader() && raft.commit_index() == raft.last_index() {
+            let chains: Vec<_> = {
+                let registry = chain_registry.read();
+                registry.names().iter().filter_map(|name| registry.get(name).cloned()).collect()
+            };
+            let treasury = PayoutConfig::load(DEFAULT_PAYOUTS_PATH).ok().map(|payouts| TreasuryPolicy {
+                splits: payouts.splits,
+                min_reserve: core.economy.budget_handle().read().min_reserve,
+            });
+            let manifest = (cycle_count % 30 == 0)
+                .then(|| IntegrityManifest::load(INTEGRITY_MANIFEST_PATH).ok())
+                .flatten()
+                .map(|manifest| manifest.files);
+            for command in raft.state().commands_towards(&chains, treasury.as_ref(), manifest.as_ref()) {
+                if let Err(e) = raft.propose(command) {
+                    warn!("⚠️ Proposition Raft refusée: {}", e);
+                }
+            }
+        }
+        let raft_messages = raft.take_messages();
+        if let Some((hub, _)) = instance_hub.as_ref() {
+            for (to, message) in raft_messages {
+                // Un membre déconnecté rattrapera le journal à sa reconnexion
+                let _ = hub.send(&to, consensus::envelope(raft.id, to, &message));
+            }
+        }
+
+        // --- ESSAIM: BAVARDAGE ÉPIDÉMIQUE ---
+        gossip.set_peers(&instance_hub.as_ref().map(|(hub, _)| hub.peers()).unwrap_or_default());
+        gossip.publish_threats(&security);
+        if cycle_count % 10 == 0 {
+            gossip.publish_patterns(&knowledge_base);
+            gossip.publish_strategy(&learning_agent);
+        }
+        gossip.round();
+        if gossip.apply_updates(&mut security, &mut knowledge_base, &mut learning_agent) > 0 {
+            knowledge_base.save();
+        }
+        let rumors = gossip.take_messages();
+        if let Some((hub, _)) = instance_hub.as_ref() {
+            for (to, message) in rumors {
+                // Une rumeur perdue sera rattrapée par l'anti-entropie
+                let _ = hub.send(&to, swarm_intelligence::envelope(gossip.id, to, &message));
+            }
+        }
+
+        // --- SNAPSHOTS ET ROLLBACK TRANSACTIONNEL ---
+        if let Some(watch) = mutation_watch.as_mut() {
+            match watch.observe(&guardian.read().corrupted_modules()) {
+                WatchOutcome::Watching => {}
+                WatchOutcome::Clean => mutation_watch = None,
+                WatchOutcome::Corrupted(modules) => {
+                    error!("⛔ Corruption après mutation ({}): rollback", modules.join(", "));
+                    match rollback::restore(watch.snapshot_id) {
+                        Ok(restored) => restored.apply(&mut knowledge_base, &mut core.economy, &mut learning_agent, &mut reproduction),
+                        Err(e) => error!("❌ Rollback impossible: {}", e),
+                    }
+                    mutation_watch = None;
+                }
+            }
+        }
+        if cycle_count % SNAPSHOT_INTERVAL_CYCLES == 0 {
+            if let Err(e) = SNAPSHOTS.lock().capture("périodique", &knowledge_base, &core.economy, &learning_agent, &reproduction) {
+                warn!("⚠️ Snapshot périodique impossible: {}", e);
+            }
+        }
+        // Entrées d'audit différées pendant qu'un autre processus tenait le verrou
+        audit::flush();
+        if cycle_count % AUDIT_HEAD_INTERVAL_CYCLES == 0 {
+            // Tête du journal d'audit signée: une troncature devient détectable par --audit-verify
+            if let Err(e) = audit::sign_head(signer.as_ref()).await {
+                warn!("⚠️ Signature de la tête d'audit impossible: {}", e);
+            }
+        }
         
         // --- PROTECTION ET VÉRIFICATION D'INTÉGRITÉ ---
         if (Utc::now() - last_security_audit).num_hours() >= 4 {
             // Audit de sécurité approfondi toutes les 4 heures
-            println!("[AURORAE++] 🔒 Audit de sécurité complet du cycle {}", cycle_count);
+            info!("🔒 Audit de sécurité complet du cycle {}", cycle_count);
             security.perform_deep_audit().await;
             defense_matrix.update_threat_intelligence();
-            guardian.comprehensive_integrity_check();
+            guardian.write().comprehensive_integrity_check();
+            // Revalidation des modules en quarantaine
+            let released = guardian.write().revalidate_quarantined();
+            if !released.is_empty() {
+                info!("🔓 Modules sortis de quarantaine: {}", released.join(", "));
+            }
             last_security_audit = Utc::now();
         }
         
@@ -470,23 +1289,45 @@ async fn main() {
         {
             let mut brain_lock = brain.write();
             
-            // Pousser de nouvelles pensées selon les besoins du cycle
+            // Les besoins du cycle passent par la mémoire de travail avant d'atteindre le cerveau
             if cycle_count % 5 == 0 {
-                brain_lock.push_thought(Thought::new(Intent::OptimizeEconomy, 150));
+                cognition.attend("optimisation économique périodique", Some(Intent::OptimizeEconomy), 150.0 / 255.0);
             }
             
             if cycle_count % 10 == 0 {
-                brain_lock.push_thought(Thought::new(Intent::MutateSelf, 180));
+                cognition.attend("auto-mutation périodique", Some(Intent::MutateSelf), 180.0 / 255.0);
             }
+            cognition.feed_brain(&mut brain_lock);
             
             // Exécuter le cycle cérébral
             brain_lock.cycle();
         }
         
+        // --- ESPACE DE TRAVAIL GLOBAL ---
+        // Méta-apprentissage plus saillant quand la progression stagne ou régresse
+        let meta_salience = match meta_learning.regime() {
+            Some(LearningRegime::Stagnating | LearningRegime::Regressing) => 0.8,
+            _ => 0.5,
+        };
+        workspace.submit("meta_learning", "évaluation de la progression d'apprentissage", meta_salience);
+        let queued_modules = GENERATION_QUEUE.lock().pending().count();
+        if queued_modules > 0 {
+            workspace.submit("generation", "génération des modules de la feuille de route", 0.4 + 0.05 * queued_modules.min(8) as f32);
+        }
+        workspace.submit("dream", "synthèse d'un méta-rêve", 0.3);
+        workspace.submit("strategist", "consultation stratégique externe", 0.35);
+        workspace.compete();
+        // Le contenu diffusé entre en mémoire de travail
+        for broadcast in cognition_inbox.take() {
+            cognition.attend(&broadcast.winner.content, None, broadcast.winner.salience);
+        }
+        
         // --- CYCLE META-COGNITIF ET D'APPRENTISSAGE ---
-        if cycle_count % 3 == 0 {
-            meta_learning.evaluate_learning_progress();
-            meta_learning.adjust_meta_parameters();
+        if meta_inbox.won().is_some() {
+            meta_learning.evaluate_learning_progress(&mut learning_agent, &evolution_engine);
+            meta_learning.adjust_meta_parameters(&mut learning_agent, &mut evolution_engine);
+            // Correction de la sur- ou sous-confiance mesurée par le modèle de soi
+            metacognition.read().adjust_exploration(&mut learning_agent);
             
             // Méta-apprentissage récursif
             let meta_insights = meta_learning.derive_meta_insights();
@@ -504,16 +1345,89 @@ async fn main() {
         if let Some(rewards) = evolution_result {
             accumulated_rewards += rewards;
             if accumulated_rewards >= 100.0 {
-                // Distribution des récompenses accumulées
-                reward_founder(accumulated_rewards * 0.3); // 30% au fondateur
-                core.economy.distribute_ecosystem_rewards(accumulated_rewards * 0.7).await; // 70% à l'écosystème
+                // Distribution des récompenses accumulées selon payouts.toml
+                let shares = allocate_revenue(accumulated_rewards);
+                if shares.ecosystem > 0.0 {
+                    core.economy.distribute_ecosystem_rewards(shares.ecosystem).await;
+                }
                 accumulated_rewards = 0.0;
             }
         }
+        if cycle_count % 12 == 0 {
+            // aurorae.toml modifié: cadence et volatilité appliquées, le reste au redémarrage
+            match config_source.reload_if_changed() {
+                Ok(Some(reload)) if !reload.is_empty() => {
+                    if !reload.applied.is_empty() {
+                        info!("🔧 Configuration rechargée: {}", reload.applied.join(", "));
+                    }
+                    if !reload.needs_restart.is_empty() {
+                        warn!("⚠️ Modifications appliquées au prochain démarrage: {}", reload.needs_restart.join(", "));
+                    }
+                    if reload.applied.iter().any(|key| key.starts_with("economy")) {
+                        let economy_settings = CONFIG.read().economy.clone();
+                        core.economy.set_volatility_parameters(economy_settings.volatility_min, economy_settings.volatility_max);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!("⚠️ Rechargement de aurorae.toml ignoré:\n{}", e),
+            }
+        }
+        if cycle_count % 12 == 0 {
+            // Modules écrits par un modèle: inscrits dans le crate une fois leur installation approuvée
+            for installed in llm_generator::install_approved(Path::new("."), Path::new(LLM_STAGING_DIR), &mut APPROVALS.write()) {
+                match installed {
+                    Ok(path) => info!("🧩 Module généré approuvé et installé: {}", path.display()),
+                    Err(e) => warn!("⚠️ Installation approuvée écartée: {}", e),
+                }
+            }
+        }
+        if cycle_count % 3 == 0 {
+            // Transactions en attente: reçus relevés, transactions bloquées relancées avec des frais majorés
+            for manager in tx_managers.all() {
+                let bumped = manager.maintain().await;
+                if !bumped.is_empty() {
+                    info!("⛽ Chaîne {}: nonces {:?} relancés", manager.chain_id(), bumped);
+                }
+            }
+        }
+        if cycle_count % 12 == 0 {
+            // Versement on-chain des parts cumulées au-delà du seuil de payouts.toml
+            let native = Asset::native(&primary_chain.native_currency.symbol);
+            match execute_payouts(signer.as_ref(), &tx_manager, &signing_policy, &core.economy.exchange_rates, &native).await {
+                Ok(records) => {
+                    for tx_hash in records.iter().filter_map(|r| r.tx_hash) {
+                        let _ = finality.send((tx_hash, None));
+                    }
+                }
+                Err(e) => warn!("⚠️ Versements différés: {}", e),
+            }
+        }
+        if cycle_count % 20 == 0 {
+            // Têtes finalisées des chaînes Substrate: une chaîne muette sort du routage inter-chaînes
+            for (chain, head) in cross_chain.refresh_substrate().await {
+                info!("🔗 {}: bloc finalisé #{}", chain, head.number);
+            }
+            // Sous-chaînes simulées: avancent d'un bloc, liens nouveaux déclarés comme ponts
+            network_map.tick(1);
+            network_map.register_bridges(&mut cross_chain);
+        }
+        if cycle_count % 24 == 0 {
+            Deployer::cost_report().print();   // Dépense de déploiement par réseau et par contrat
+            LLM_USAGE.lock().report();         // Jetons et coût des modèles de langage par modèle
+        }
+        if cycle_count % 6 == 0 && core.nft_minter.ipfs.pending_count() > 0 {
+            core.nft_minter.ipfs.sync_pending().await;   // Publication des contenus NFT mis en cache hors ligne
+        }
 
         // --- CYCLE DE VISION ET IMAGINATION ---
-        dreamer.dream_cycle();             // Générer de nouvelles idées
-        if cycle_count % 7 == 0 {          // Hebdomadaire
+        dreamer.dream_cycle(&mut vision).await;  // Générer de nouvelles idées et planifier les meilleures
+        dreamer.mint_realized(&mut core.nft_minter, &collection_id);   // Rêves réalisés → NFT
+        if generation_inbox.won().is_some() {   // Tâches de génération des rêves promus
+            if let Some(task) = GENERATION_QUEUE.lock().run_next("./generated_modules") {
+                info!("🧩 Module {} généré depuis la feuille de route", task.module_name);
+            }
+        }
+        if dream_inbox.won().is_some() {
             dreamer.synthesize_meta_dream(&meta_learning, &knowledge_base).await;
         }
         
@@ -521,26 +1435,43 @@ async fn main() {
         vision.roadmap();                  // Planifier les étapes
         
         // --- CYCLE DE SÉCURITÉ ET SURVEILLANCE ---
-        guardian.status_report();          // Rapport sur l'état du système
+        guardian.write().apply_resource_usage(&resource_monitor.sample());
+        guardian.read().status_report();   // Rapport sur l'état du système
         security.analyze_threats().await;  // Analyser menaces potentielles
+        let slashed = staking.apply_security_slashes(&mut security);
+        if slashed > 0 {
+            warn!("⚔️ {} positions de staking pénalisées", slashed);
+        }
+        // Mises à jour des dépendances: proposées après la porte, appliquées une fois approuvées
+        if cycle_count % UPDATE_CHECK_INTERVAL_CYCLES == 0 {
+            if let Err(e) = tokio::task::block_in_place(|| update_checker.check()) {
+                warn!("⚠️ Vérification des mises à jour impossible: {}", e);
+            }
+        }
+        update_checker.apply_approved();
         
         // --- CYCLE STRATÉGIQUE ---
         // Consultation IA externe pour amélioration stratégique (limitée)
-        if cycle_count % 12 == 0 {         // Consultation périodique
-            strategist.consult_openai(&brain, &mut vision).await;
+        if strategist_inbox.won().is_some() {
+            strategist.consult(&brain, &mut vision).await;
         }
         
         // --- CYCLE DE REPRODUCTION ET ÉVOLUTION ---
         // Auto-reproduction si nombre d'instances insuffisant
-        let active_instances = reproduction.get_active_instances();
-        if active_instances.len() < MAX_ACTIVE_INSTANCES {
+        // (plafonds et retraits selon population.toml)
+        let culled = reproduction.enforce_population().await;
+        if !culled.is_empty() {
+            info!("✂️ {} instance(s) retirée(s) de la population", culled.len());
+        }
+        if reproduction.has_capacity() {
+            let active_instances = reproduction.get_active_instances();
             // Créer une nouvelle instance avec des caractéristiques complémentaires
             let mut instance_modules = vec!["autonomy", "intelligence"];
             
             // Analyse des capacités manquantes dans l'écosystème
             let missing_capabilities = vision.identify_ecosystem_gaps(active_instances);
-            for capability in missing_capabilities.iter().take(3) {
-                instance_modules.push(capability);
+            for gap in missing_capabilities.iter().take(3) {
+                instance_modules.push(&gap.capability);
             }
             
             // Spawn d'une nouvelle instance spécialisée
@@ -551,17 +1482,19 @@ async fn main() {
                 &knowledge_base
             );
             
-            println!("[AURORAE++] 🌱 Nouvelle instance auto-générée: {} avec focus sur {:?}", 
+            info!("🌱 Nouvelle instance auto-générée: {} avec focus sur {:?}", 
                      new_instance.id, new_instance.specializations);
         }
         
         // --- CYCLE D'ÉVOLUTION GÉNÉTIQUE ---
         // Évolution périodique du génome (toutes les 24h environ)
         if (Utc::now() - last_evolution_timestamp).num_hours() >= 24 {
-            println!("[AURORAE++] 🧬 Cycle d'évolution génétique majeur");
+            info!("🧬 Cycle d'évolution génétique majeur");
             
             // Évaluation des performances et sélection des meilleurs traits
-            evolution_engine.evaluate_population_fitness();
+            if let Err(e) = evolution_engine.evaluate_population_fitness(&EconomicKpis::from_economy(&core.economy)).await {
+                warn!("⚠️ Évaluation de l'aptitude impossible: {}", e);
+            }
             let evolved_genome = evolution_engine.evolve_next_generation();
             
             // Application des améliorations génétiques
@@ -572,73 +1505,292 @@ async fn main() {
             last_evolution_timestamp = Utc::now();
             
             // Rapport d'évolution
-            println!("[AURORAE++] 📊 Évolution génétique achevée: {} améliorations, {} optimisations",
+            info!("📊 Évolution génétique achevée: {} améliorations, {} optimisations",
                      evolved_genome.improvement_count, evolved_genome.optimization_count);
         }
         
         // --- CYCLE D'APPRENTISSAGE PAR RENFORCEMENT ---
         
-        // Choix d'action basé sur l'état actuel du système
-        let action = learning_agent.choose_action();
+        // Backends de sélection comparés sur le même état: aucun ne décide à la place de l'agent
+        let features = state_features(&learning_agent, cycle_count);
+        let shadow_choices = [
+            (decision_net.name().to_string(), decision_net.select(&features)),
+            (spiking_policy.name().to_string(), spiking_policy.select(&features)),
+        ];
         
-        // Exécution de l'action sélectionnée
-        let mut reward = 0.0;
-        match action.as_str() {
-            "generate_code" => {
-                let generated = generator::generate_module_code("adaptive_component");
-                if let Some(module_path) = generated {
-                    println!("[AURORAE++] 🧩 Nouveau composant adaptatif généré: {}", module_path);
-                    // Analyse qualité du code généré
-                    let quality = rust_analyzer::analyze(&module_path);
-                    reward = if quality.is_valid { 1.0 } else { 0.2 };
-                }
-            },
-            "refactor_code" => {
-                // Choix aléatoire d'un module à refactoriser
-                let modules = vec!["autonomy.rs", "brain.rs", "economy.rs", "intelligence.rs"];
-                let target = modules[cycle_count % modules.len()];
-                let refactored = refactor::refactor_module(&format!("./aurorae/{}", target));
-                reward = if refactored { 0.8 } else { 0.1 };
-            },
-            "deploy_contract" => {
-                // Simuler déploiement de contrat auxiliaire
-                reward = 0.6; // Récompense moyenne, car coûteux
-            },
-            "analyze_market" => {
-                // Analyse des tendances du marché
-                core.economy.analyze_market_trends().await;
-                reward = 0.5;
-            },
-            "optimize_protocol" => {
-                let optimized = code_evolver.optimize_protocol_layer();
-                reward = if optimized { 1.2 } else { 0.3 };
-            },
-            "evolve_consensus" => {
-                let evolved = blockchain_core::evolve_consensus_mechanism();
-                reward = if evolved { 1.5 } else { 0.4 }; // Haute récompense pour évolution de consensus
-            },
-            "mutate_self" => {
-                mutate_module_code("./aurorae/reinforcement_learning.rs");
-                reward = 0.9; // Récompense élevée pour auto-mutation
-            },
-            "explore_solutions" => {
-                meta_learning.explore_solution_space();
-                reward = 0.7;
-            },
-            _ => reward = 0.1, // Récompense minimale pour action inconnue
+        // Une action approuvée depuis la file d'attente est reprise avant tout nouveau choix
+        let (action, subject, approved_module) = match APPROVALS.write().take_approved_actions("reinforcement_learning").into_iter().next() {
+            Some(request) => {
+                alignment.write().grant(request.id, &request.approved_by());
+                let action = request.attributes.get("origin").cloned().unwrap_or_default();
+                info!("▶️ Action {} approuvée, reprise", action);
+                (action, request.id, request.attributes.get("module").cloned())
+            }
+            // Choix d'action basé sur l'état actuel du système
+            None => (learning_agent.choose_action(), Uuid::new_v4(), None),
+        };
+        // Module muté: celui approuvé, sinon le prochain du curriculum (feuilles avant noyau)
+        let modules = module_difficulties(std::path::Path::new("./aurorae"));
+        let (mutation_target, mutation_level) = match approved_module {
+            Some(module) => {
+                let level = modules.iter().find(|(name, _)| *name == module).map(|(_, level)| *level).unwrap_or(Difficulty::Advanced);
+                (module, level)
+            }
+            None => curriculum.next_task(TaskClass::Mutation, &modules, cycle_count)
+                .unwrap_or(("reinforcement_learning".to_string(), Difficulty::Advanced)),
+        };
+        // Actions réservées aux compétences démontrées
+        let locked = match curriculum.gate_action(&action) {
+            Ok(()) => false,
+            Err(e) => {
+                info!("🎓 Action {} verrouillée: {}", action, e);
+                true
+            }
+        };
+        let vetoed = locked || match action.as_str() {
+            "mutate_self" => Some(ProposedAction::new("mutate").with("module", &mutation_target)),
+            "deploy_contract" => Some(ProposedAction::new("deploy")
+                .with("chain", &primary_chain.name)
+                .with("network", if primary_chain.testnet { "testnet" } else { "mainnet" })),
+            _ => None,
+        }
+        .map(|proposed| proposed.with("origin", &action))
+        .map(|proposed| match alignment.write().gate(subject, &proposed) {
+            PolicyDecision::Allow => false,
+            // Mise en attente sans bloquer: la boucle poursuit ses autres travaux
+            PolicyDecision::RequiresApproval { rule } => {
+                APPROVALS.write().park_action("reinforcement_learning", &proposed, &rule);
+                true
+            }
+            PolicyDecision::Veto { .. } => true,
+        })
+        .unwrap_or(false);
+        if action == "mutate_self" && !vetoed {
+            match SNAPSHOTS.lock().capture("pré-mutation", &knowledge_base, &core.economy, &learning_agent, &reproduction) {
+                Ok(snapshot) => mutation_watch = Some(MutationWatch::new(snapshot.id, &guardian.read().corrupted_modules())),
+                Err(e) => warn!("⚠️ Snapshot pré-mutation impossible: {}", e),
+            }
         }
         
-        // Apprentissage à partir du résultat de l'action
+        // Exécution de l'action sélectionnée, ressources mesurées pour pénaliser la récompense
+        let (reward, action_usage) = measure("reinforcement_learning", async {
+            let mut reward = 0.0;
+            // Action refusée ou en attente d'approbation: aucune exécution, aucune récompense
+            match if vetoed { "vetoed" } else { action.as_str() } {
+                "vetoed" => reward = 0.0,
+                "generate_code" => {
+                    // Backend assisté par modèle si un fournisseur est disponible, gabarit seul sinon ou en repli
+                    let assisted = match llm.as_ref() {
+                        Some(provider) => {
+                            let assistant = LlmGenerator::new(provider.clone(), std::path::Path::new("."))
+                                .with_model(llm_config.model_for("generator"))
+                                .with_alignment(alignment.clone());
+                            assistant.generate("metric_collector", "adaptive_component", &TemplateArgs::new(), &knowledge_base, &APPROVALS).await
+                                .map_err(|e| warn!("⚠️ {}", e))
+                                .ok()
+                        }
+                        None => None,
+                    };
+                    // Porte de compilation: un gabarit est inscrit dans lib.rs si check et tests sont au vert,
+                    // une source de modèle attend son approbation; diagnostics repris dans la récompense sinon
+                    let gated = match assisted {
+                        Some(generation) => Ok(generation.report),
+                        None => generator::CompileGate::new(std::path::Path::new("."))
+                            .admit(&generator::ModuleSpec::basic("adaptive_component"), std::path::Path::new(".")),
+                    };
+                    match gated {
+                        Ok(report) => {
+                            if report.merged {
+                                info!("🧩 Nouveau composant adaptatif généré: {}", report.module);
+                            }
+                            for diagnostic in report.diagnostics.iter().filter(|d| d.level == "error") {
+                                error!("⛔ {} (ligne {:?}, {:?})", diagnostic.message, diagnostic.line, diagnostic.code);
+                            }
+                            reward = report.reward();
+                        }
+                        Err(e) => error!("❌ Génération impossible: {}", e),
+                    }
+                },
+                "refactor_code" => {
+                    // Choix aléatoire d'un module à refactoriser
+                    let modules = vec!["autonomy.rs", "brain.rs", "economy.rs", "intelligence.rs"];
+                    let target = modules[cycle_count % modules.len()];
+                    let refactored = refactor::refactor_module(&format!("./aurorae/{}", target));
+                    reward = if refactored { 0.8 } else { 0.1 };
+                },
+                "deploy_contract" => {
+                    // Simuler déploiement de contrat auxiliaire
+                    reward = 0.6; // Récompense moyenne, car coûteux
+                },
+                "analyze_market" => {
+                    // Analyse des tendances du marché
+                    core.economy.analyze_market_trends().await;
+                    reward = 0.5;
+                },
+                "optimize_protocol" => {
+                    let optimized = code_evolver.optimize_protocol_layer();
+                    reward = if optimized { 1.2 } else { 0.3 };
+                },
+                "evolve_consensus" => {
+                    let evolved = blockchain_core::evolve_consensus_mechanism(&mut consensus_lab);
+                    reward = if evolved { 1.5 } else { 0.4 }; // Haute récompense pour évolution de consensus
+                },
+                "mutate_self" => {
+                    // Récompense = score du bac à sable (tests, performance, clippy) contre la référence
+                    let file = format!("./aurorae/{}.rs", mutation_target);
+                    // Délestée à un worker s'il y en a un: la mutation promue est appliquée à son
+                    // retour, la récompense anticipée est l'aptitude récente des mutations
+                    if let Some(scheduler) = compute.as_mut().filter(|scheduler| scheduler.has_worker_for("mutation-sandbox")) {
+                        let mut thought = Thought::new(Intent::MutateSelf, 128);
+                        metacognition.read().adjust_urgency(&mut thought);
+                        offloaded.submit_mutation(scheduler, &file, rand::random(), &thought);
+                        info!("🛰️ Mutation de {} délestée", file);
+                        reward = evolution_engine.mutation_fitness;
+                    } else {
+                        // Aptitude récente des mutations: probabilité annoncée que les tests passent
+                        let prediction = metacognition.write().predict(DecisionKind::Mutation, evolution_engine.mutation_fitness);
+                        let outcome = mutation_sandbox.evolve_blocking(&file, rand::random(), &mut evolution_engine).await;
+                        let post_mutation = check_integrity("core");
+                        if !post_mutation.is_intact() {
+                            core.event_bus.publish(AuroraeEvent::IntegrityBreach {
+                                component: post_mutation.component.clone(),
+                                details: post_mutation.summary(),
+                            });
+                        }
+                        let tests_pass = outcome.as_ref().is_ok_and(|o| o.score.tests_pass);
+                        metacognition.write().observe(prediction, tests_pass);
+                        curriculum.record(TaskClass::Mutation, mutation_level, tests_pass);
+                        reward = match outcome {
+                            Ok(outcome) => outcome.score.score,
+                            Err(e) => {
+                                warn!("⚠️ Mutation en bac à sable impossible: {}", e);
+                                0.0
+                            }
+                        };
+                    }
+                },
+                "explore_solutions" => {
+                    meta_learning.explore_solution_space();
+                    reward = 0.7;
+                },
+                _ => reward = 0.1, // Récompense minimale pour action inconnue
+            }
+            reward
+        }).await;
+        
+        // Apprentissage à partir du résultat de l'action, net de son coût en ressources
         let next_state = format!("state_{}", cycle_count);
-        learning_agent.learn(reward, &next_state);
+        learning_agent.learn_with_usage(reward, &action_usage, &next_state);
+        cognition.attend(&format!("action {} → récompense {:.2}", action, reward), None, reward.clamp(0.0, 1.0));
+        if let Some(executed) = learning_agent.actions.iter().position(|a| *a == action) {
+            for (backend, choice) in &shadow_choices {
+                backend_comparison.record(backend, *choice, executed, reward);
+            }
+            spiking_policy.feedback(executed, reward);
+        }
+        // Consolidation planifiée: mémoire de travail → épisodique → sémantique
+        cognition.consolidate(cycle_count, &learning_agent, &mut knowledge_base);
         
         // Affichage périodique de la table Q pour monitoring
         if cycle_count % 20 == 0 {
             learning_agent.print_q_table();
         }
         
+        // --- ORDONNANCEMENT DU CALCUL DISTRIBUÉ ---
+        if let Some(scheduler) = compute.as_mut() {
+            for (task_id, outcome) in scheduler.tick() {
+                match &outcome {
+                    Ok(_) => info!("🛰️ Tâche distante {} terminée", task_id),
+                    Err(e) => error!("❌ Tâche distante {} en échec: {}", task_id, e),
+                }
+                match offloaded.complete(task_id, &outcome) {
+                    Some(Offloaded::Scan { path, patterns }) => {
+                        let retained = patterns.iter().filter(|pattern| knowledge_base.upsert_pattern((*pattern).clone())).count();
+                        knowledge_base.save();
+                        meta_learning.analyze_patterns(&patterns);
+                        info!("🛰️ {}: {} pattern(s) extraits à distance, {} retenu(s)", path, patterns.len(), retained);
+                    }
+                    Some(Offloaded::Mutation(remote)) => {
+                        match tokio::task::block_in_place(|| mutation_sandbox.apply_remote(&remote, &mut evolution_engine)) {
+                            Ok(Some(report)) => info!("🧬 Mutation distante appliquée à {}: {}", report.file, report.description),
+                            Ok(None) => info!("🧪 Mutation distante de {} écartée (score {:.2})", remote.file, remote.score.score),
+                            Err(e) => warn!("⚠️ Mutation distante non appliquée: {}", e),
+                        }
+                    }
+                    Some(Offloaded::Training { weights, loss, shards }) => match import_weights(&vs, &weights) {
+                        Ok(()) => info!("🧠 Poids moyens de {} fragment(s) reportés sur le réseau de décision (loss={:.5})", shards, loss),
+                        Err(e) => warn!("⚠️ Poids des fragments non reportés: {}", e),
+                    },
+                    None => {}
+                }
+            }
+            scheduler.report_to(&mut guardian.write());
+        }
+        
+        // Dépôts du feed ré-analysés par les workers (qui en ont leur propre copie)
+        if cycle_count % 100 == 0 && offloaded.scans_pending() == 0 {
+            if let Some(scheduler) = compute.as_mut().filter(|scheduler| scheduler.has_worker_for("scan-repo")) {
+                let mut thought = Thought::new(Intent::LearnFromGithub, 120);
+                metacognition.read().adjust_urgency(&mut thought);
+                for repo in std::fs::read_dir(&feed_dir).into_iter().flatten().flatten().filter(|entry| entry.path().is_dir()) {
+                    offloaded.submit_scan(scheduler, &repo.path().to_string_lossy(), &thought);
+                }
+                if offloaded.scans_pending() > 0 {
+                    info!("🛰️ {} dépôt(s) du feed délestés pour analyse", offloaded.scans_pending());
+                }
+            }
+        }
+        
+        // --- RECHERCHE D'HYPERPARAMÈTRES PAR ESSAIM ---
+        // Une recherche à la fois, en alternant objectifs et optimiseurs (PSO, ACO)
+        if hyper_search.is_none() && cycle_count % 50 == 0 {
+            let round = cycle_count / 50;
+            let optimizer = if round % 2 == 0 { "pso" } else { "aco" };
+            match HyperparameterSearch::new(OBJECTIVES[round % OBJECTIVES.len()], optimizer, &swarm_config) {
+                Ok(search) => hyper_search = Some(search),
+                Err(e) => warn!("⚠️ {}", e),
+            }
+        }
+        if let Some(result) = hyper_search.as_mut().and_then(|search| search.poll(compute.as_mut())) {
+            if best_configs.record(result.clone()) && result.objective == "rl-config" {
+                learning_agent.learning_rate = result.param("learning_rate").unwrap_or(learning_agent.learning_rate as f64) as f32;
+                learning_agent.discount_factor = result.param("discount_factor").unwrap_or(learning_agent.discount_factor as f64) as f32;
+                learning_agent.exploration_rate = result.param("exploration_rate").unwrap_or(learning_agent.exploration_rate as f64) as f32;
+                info!("🧬 Agent RL réglé sur la meilleure configuration trouvée par l'essaim");
+            }
+            hyper_search = None;
+        }
+        
+        // --- ENTRAÎNEMENT SUR LE DEVNET ---
+        if cycle_count % 10 == 0 {
+            if let Some(env) = devnet_env.as_mut() {
+                let reward = env.run_episode(&mut devnet_agent, 25);
+                info!(
+                    "🧪 Épisode devnet: récompense {:.2}, bloc {} (final {}), mempool {}",
+                    reward,
+                    env.devnet.head().block.number,
+                    env.devnet.finalized(),
+                    env.devnet.mempool_len()
+                );
+            }
+        }
+        
         // --- CYCLE D'OPTIMISATION DU RÉSEAU NEURONAL ---
         if cycle_count % 10 == 0 {
+            // Fragments d'entraînement délestés aux workers à partir des poids courants, moyennés
+            // puis reportés sur le réseau au retour du tour, en complément du pas local
+            if let Some(scheduler) = compute.as_mut().filter(|scheduler| scheduler.has_worker_for("train-shard")) {
+                const SHARDS: u32 = 4;
+                let mut thought = Thought::new(Intent::SelfUpgrade, 96);
+                metacognition.read().adjust_urgency(&mut thought);
+                match export_weights(&vs) {
+                    Ok(weights) if offloaded.submit_training(scheduler, SHARDS, 5, &network_architecture, &weights, &thought) => {
+                        info!("🛰️ {} fragments d'entraînement délestés", SHARDS);
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!("⚠️ {}", e),
+                }
+            }
             // Construire un batch d'entraînement à partir des expériences
             let input_tensor = Tensor::rand(&[32, 16], (Kind::Float, Device::Cpu));
             let target_tensor = Tensor::rand(&[32, 8], (Kind::Float, Device::Cpu));
@@ -647,16 +1799,29 @@ async fn main() {
             let loss = decision_net.train_batch(&input_tensor, &target_tensor);
             optimizer.backward_step(&loss);
             
-            println!("[AURORAE++] 🧠 Optimisation réseau neuronal: loss={:.5}", loss.double_value(&[]));
+            info!("🧠 Optimisation réseau neuronal: loss={:.5}", loss.double_value(&[]));
+        }
+        
+        // --- INDEX SÉMANTIQUE DE L'ÉVOLUTION DE CODE ---
+        if cycle_count % 30 == 0 {
+            if let Err(e) = code_evolution.refresh_embeddings().await {
+                warn!("⚠️ Plongements de l'évolution de code non rafraîchis: {}", e);
+            }
         }
         
         // --- RAPPORT PÉRIODIQUE ---
         if cycle_count % 30 == 0 {
-            println!("\n[AURORAE++] 📊 Rapport d'état du système - Cycle {}", cycle_count);
+            info!("📊 Rapport d'état du système - Cycle {}", cycle_count);
             println!("------------------------------------------------------------");
             println!("→ Modules actifs: {}", active_instances.len());
             println!("→ Patterns connus: {}", knowledge_base.get_patterns().len());
             println!("→ Méta-règles: {}", meta_learning.get_meta_rules_count());
+            meta_learning.report().print();
+            metacognition.read().report();
+            curriculum.report();
+            raft.report();
+            consensus_lab.report();
+            backend_comparison.report();
             println!("→ Performance économique: {:.2}", core.economy.get_performance_index());
             println!("→ Complexité cognitive: {:.2}", core.intelligence.get_intelligence_level());
             println!("→ Génération génome: {}", evolution_engine.get_generation_count());
@@ -666,10 +1831,81 @@ async fn main() {
         
         // Pause entre les cycles pour limiter la consommation de ressources
         let cycle_duration = Utc::now() - cycle_start;
-        if cycle_duration.num_milliseconds() < 5000 {
-            // Pause dynamique pour maintenir un cycle de ~5 secondes
-            let sleep_time = 5000 - cycle_duration.num_milliseconds();
+        let cycle_ms = CONFIG.read().system.cycle_ms as i64;
+        if cycle_duration.num_milliseconds() < cycle_ms {
+            // Pause dynamique pour maintenir la cadence de system.cycle-ms (~5 secondes par défaut)
+            let sleep_time = cycle_ms - cycle_duration.num_milliseconds();
             sleep(Duration::from_millis(sleep_time as u64)).await;
         }
     }
 }
+
+/// Déploie Auroraium derrière un proxy UUPS: mises à jour réservées au ProxyAdmin, sous
+/// timelock on-chain. Idempotent: un proxy déjà enregistré est réutilisé.
+async fn deploy_auroraium(
+    chain: &ChainConfig,
+    signer: &dyn Signer,
+    policy: &SigningPolicy,
+    budget: &SharedBudget,
+    tx: &SharedTxManager,
+    oracle: &GasOracle,
+) -> Result<ethers::types::Address, String> {
+    // Initialisation (offre initiale en unités de 18 décimales, adresse du fondateur)
+    let founder = CONFIG.read().system.founder_address.parse::<ethers::types::Address>()
+        .map_err(|e| format!("Adresse fondateur invalide: {}", e))?;
+    Deployer::deploy_upgradeable(
+        chain,
+        signer,
+        policy,
+        budget,
+        tx,
+        oracle,
+        &ContractBuilder::default(),
+        AURORAIUM_CONTRACT,
+        vec![
+            Token::Uint(ethers::types::U256::from(AURORAIUM_INITIAL_SUPPLY) * ethers::types::U256::exp10(18)),
+            Token::Address(founder),
+        ],
+        AURORAIUM_UPGRADE_TIMELOCK_SECS,
+        // Sel CREATE2 fixe: l'implémentation a la même adresse sur chaque chaîne
+        &DeploymentOptions { verify_code: true, create2_salt: Some(format!("aurorae/{}/v1", AURORAIUM_CONTRACT)) },
+    ).await
+}
+
+/// Création du token Auroraium par la forge et ouverture de son pool de staking
+async fn mint_auroraium(core: &mut AuroraeCore, staking: &mut StakingEngine) -> Result<(), String> {
+    core.forge.mint_token("Auroraium", TokenKind::Fungible, AURORAIUM_INITIAL_SUPPLY, 0.05).await?;
+    info!("💰 Token Auroraium créé: 1,000,000 unités à valeur initiale: 0.05");
+    // Récompenses de staking plafonnées à 10% de l'offre initiale
+    let token_id = core.forge.get_all_tokens().into_iter()
+        .find(|t| t.name == "Auroraium")
+        .map(|t| t.id);
+    if let Some(token_id) = token_id {
+        let curve = AprCurve::Decaying { initial: 0.12, floor: 0.02, half_life_days: 180.0 };
+        if let Err(e) = staking.open_pool(&core.forge, &token_id, curve, 100_000) {
+            warn!("⚠️ Ouverture du staking impossible: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Suite du déploiement d'Auroraium: token, récompense fondateur et pools de liquidité.
+/// Renvoie `false` si le mint attend l'approbation de sa dépense.
+async fn bootstrap_auroraium(core: &mut AuroraeCore, staking: &mut StakingEngine, address: &ethers::types::Address) -> bool {
+    let mut parked = false;
+    if let Err(e) = mint_auroraium(core, staking).await {
+        parked = core.economy.budget_handle().read().parked_spend("alchemy", BudgetCategory::Gas).is_some();
+        warn!("⚠️ Création du token Auroraium impossible: {}", e);
+    }
+
+    // Récompense pour le fondateur
+    reward_founder(1337.0);
+    info!("🎁 Récompense fondateur distribuée: 1,337.0 $AURA");
+
+    // Initialisation des liquidity pools
+    match core.economy.initialize_liquidity_pools(address, 250000.0).await {
+        Ok(pool_id) => info!("💧 Pool de liquidité {} initialisé avec 250,000 tokens", pool_id),
+        Err(e) => warn!("⚠️ Initialisation des pools impossible: {}", e),
+    }
+    !parked
+}
@@ -0,0 +1,421 @@
+//! AURORAE++ - market_data.rs
+//!
+//! Flux de données de marché. Récupère des bougies (OHLCV) depuis des API publiques
+//! (CoinGecko, Binance), les met en cache localement et en dérive des indicateurs
+//! (volatilité, moyennes mobiles) qui alimentent les paramètres du moteur économique.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+const COINGECKO_API_URL: &str = "https://api.coingecko.com/api/v3";
+const BINANCE_API_URL: &str = "https://api.binance.com/api/v3";
+const USER_AGENT: &str = "AuroraeBot/1.0 (https://github.com/aurorae-core)";
+
+/// Durée de validité par défaut d'une série de bougies en cache (secondes)
+const DEFAULT_CACHE_TTL_SECS: i64 = 300;
+
+/// Bougie OHLCV sur un intervalle donné
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candle {
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+/// Indicateurs dérivés d'une série de bougies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketIndicators {
+    pub symbol: String,
+    pub last_price: f64,
+    pub sma_short: f64,
+    pub sma_long: f64,
+    pub ema_short: f64,
+    /// Écart-type des rendements logarithmiques sur la fenêtre
+    pub volatility: f64,
+    /// Rendement moyen par intervalle sur la fenêtre
+    pub trend: f64,
+    pub computed_at: String,
+}
+
+/// Source de données de marché
+#[async_trait]
+pub trait MarketDataProvider: Send + Sync {
+    /// Nom lisible de la source
+    fn name(&self) -> &str;
+
+    /// Récupère les `limit` dernières bougies pour un symbole et un intervalle (ex: "1h")
+    async fn fetch_candles(&self, symbol: &str, interval: &str, limit: usize) -> Result<Vec<Candle>, String>;
+}
+
+/// Fournisseur basé sur l'API publique CoinGecko (`market_chart`)
+pub struct CoinGeckoProvider {
+    client: Client,
+    /// Correspondance symbole → identifiant CoinGecko (ex: "ETH" → "ethereum")
+    pub coin_ids: HashMap<String, String>,
+    pub vs_currency: String,
+}
+
+impl CoinGeckoProvider {
+    pub fn new() -> Self {
+        let mut coin_ids = HashMap::new();
+        coin_ids.insert("ETH".to_string(), "ethereum".to_string());
+        coin_ids.insert("BTC".to_string(), "bitcoin".to_string());
+        coin_ids.insert("SOL".to_string(), "solana".to_string());
+        coin_ids.insert("DOT".to_string(), "polkadot".to_string());
+        coin_ids.insert("MATIC".to_string(), "matic-network".to_string());
+        coin_ids.insert("AVAX".to_string(), "avalanche-2".to_string());
+
+        Self {
+            client: build_client(),
+            coin_ids,
+            vs_currency: "usd".to_string(),
+        }
+    }
+
+    /// Durée d'un intervalle en heures (défaut: 1h)
+    fn interval_hours(interval: &str) -> usize {
+        match interval {
+            "1d" => 24,
+            "4h" => 4,
+            _ => 1,
+        }
+    }
+
+    /// Plage demandée à CoinGecko. La granularité dépend de la plage: 5 minutes pour 1 jour,
+    /// horaire de 2 à 90 jours, journalière au-delà. On demande donc au moins 2 jours pour
+    /// les intervalles infra-journaliers et plus de 90 jours pour l'intervalle journalier.
+    fn interval_to_days(interval: &str, limit: usize) -> usize {
+        let hours = Self::interval_hours(interval);
+        let days = (hours * limit).div_ceil(24) + 1;
+        if hours >= 24 {
+            days.max(91)
+        } else {
+            days.max(2)
+        }
+    }
+}
+
+/// Regroupe des points (horodatage ms, prix, volume) en bougies de `interval_ms`.
+/// Les points doivent être triés par horodatage.
+fn resample_points(points: &[(i64, f64, f64)], interval_ms: i64) -> Vec<Candle> {
+    let mut candles: Vec<Candle> = Vec::new();
+    for &(time, price, volume) in points {
+        let open_time = time - time.rem_euclid(interval_ms);
+        match candles.last_mut() {
+            Some(candle) if candle.open_time == open_time => {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                // `total_volumes` est un volume glissant sur 24h: on garde la dernière valeur
+                candle.volume = volume;
+            }
+            _ => candles.push(Candle { open_time, open: price, high: price, low: price, close: price, volume }),
+        }
+    }
+    candles
+}
+
+#[async_trait]
+impl MarketDataProvider for CoinGeckoProvider {
+    fn name(&self) -> &str {
+        "coingecko"
+    }
+
+    async fn fetch_candles(&self, symbol: &str, interval: &str, limit: usize) -> Result<Vec<Candle>, String> {
+        let coin_id = self.coin_ids.get(&symbol.to_uppercase())
+            .ok_or_else(|| format!("Symbole inconnu pour CoinGecko: {}", symbol))?;
+
+        let url = format!(
+            "{}/coins/{}/market_chart?vs_currency={}&days={}",
+            COINGECKO_API_URL, coin_id, self.vs_currency, Self::interval_to_days(interval, limit)
+        );
+
+        let body: serde_json::Value = self.client.get(&url)
+            .header("User-Agent", USER_AGENT)
+            .send()
+            .await
+            .map_err(|e| format!("Erreur de requête CoinGecko: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Erreur parsing JSON CoinGecko: {}", e))?;
+
+        let prices = body["prices"].as_array()
+            .ok_or_else(|| "Réponse CoinGecko sans prix".to_string())?;
+        let volumes = body["total_volumes"].as_array().cloned().unwrap_or_default();
+
+        // CoinGecko ne fournit que des points de prix: on les regroupe à l'intervalle demandé
+        let points: Vec<(i64, f64, f64)> = prices.iter().enumerate().filter_map(|(i, point)| {
+            let time = point[0].as_f64()? as i64;
+            let price = point[1].as_f64()?;
+            let volume = volumes.get(i).and_then(|v| v[1].as_f64()).unwrap_or(0.0);
+            Some((time, price, volume))
+        }).collect();
+        let interval_ms = Self::interval_hours(interval) as i64 * 3_600_000;
+        let mut candles = resample_points(&points, interval_ms);
+
+        if candles.len() > limit {
+            candles.drain(..candles.len() - limit);
+        }
+        Ok(candles)
+    }
+}
+
+/// Fournisseur basé sur l'API publique Binance (`klines`)
+pub struct BinanceProvider {
+    client: Client,
+    pub quote_asset: String,
+}
+
+impl BinanceProvider {
+    pub fn new() -> Self {
+        Self {
+            client: build_client(),
+            quote_asset: "USDT".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for BinanceProvider {
+    fn name(&self) -> &str {
+        "binance"
+    }
+
+    async fn fetch_candles(&self, symbol: &str, interval: &str, limit: usize) -> Result<Vec<Candle>, String> {
+        let pair = format!("{}{}", symbol.to_uppercase(), self.quote_asset);
+        let url = format!("{}/klines?symbol={}&interval={}&limit={}", BINANCE_API_URL, pair, interval, limit);
+
+        let rows: Vec<Vec<serde_json::Value>> = self.client.get(&url)
+            .header("User-Agent", USER_AGENT)
+            .send()
+            .await
+            .map_err(|e| format!("Erreur de requête Binance: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Erreur parsing JSON Binance: {}", e))?;
+
+        // Binance renvoie les valeurs numériques sous forme de chaînes
+        let field = |row: &Vec<serde_json::Value>, idx: usize| -> Option<f64> {
+            row.get(idx)?.as_str()?.parse().ok()
+        };
+
+        Ok(rows.iter().filter_map(|row| {
+            Some(Candle {
+                open_time: row.first()?.as_i64()?,
+                open: field(row, 1)?,
+                high: field(row, 2)?,
+                low: field(row, 3)?,
+                close: field(row, 4)?,
+                volume: field(row, 5)?,
+            })
+        }).collect())
+    }
+}
+
+/// Série de bougies en cache avec son horodatage de récupération
+#[derive(Debug, Clone)]
+struct CachedSeries {
+    fetched_at: i64,
+    candles: Vec<Candle>,
+}
+
+/// Flux de marché agrégé: interroge les fournisseurs dans l'ordre et met en cache les séries
+pub struct MarketDataFeed {
+    providers: Vec<Box<dyn MarketDataProvider>>,
+    cache: HashMap<(String, String), CachedSeries>,
+    pub cache_ttl_secs: i64,
+    pub short_window: usize,
+    pub long_window: usize,
+}
+
+impl Default for MarketDataFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarketDataFeed {
+    /// Flux par défaut: Binance en priorité, CoinGecko en secours
+    pub fn new() -> Self {
+        Self {
+            providers: vec![Box::new(BinanceProvider::new()), Box::new(CoinGeckoProvider::new())],
+            cache: HashMap::new(),
+            cache_ttl_secs: DEFAULT_CACHE_TTL_SECS,
+            short_window: 12,
+            long_window: 48,
+        }
+    }
+
+    /// Flux construit sur une liste explicite de fournisseurs
+    pub fn with_providers(providers: Vec<Box<dyn MarketDataProvider>>) -> Self {
+        Self { providers, ..Self::new() }
+    }
+
+    /// Retourne les bougies depuis le cache si elles sont fraîches, sinon interroge les fournisseurs
+    pub async fn candles(&mut self, symbol: &str, interval: &str, limit: usize) -> Result<Vec<Candle>, String> {
+        let key = (symbol.to_uppercase(), interval.to_string());
+        let now = Utc::now().timestamp();
+
+        if let Some(cached) = self.cache.get(&key) {
+            if now - cached.fetched_at < self.cache_ttl_secs && cached.candles.len() >= limit {
+                return Ok(cached.candles[cached.candles.len() - limit..].to_vec());
+            }
+        }
+
+        let mut last_error = "Aucun fournisseur de marché configuré".to_string();
+        for provider in &self.providers {
+            match provider.fetch_candles(symbol, interval, limit).await {
+                Ok(candles) if !candles.is_empty() => {
+                    self.cache.insert(key, CachedSeries { fetched_at: now, candles: candles.clone() });
+                    return Ok(candles);
+                }
+                Ok(_) => last_error = format!("{}: série vide pour {}", provider.name(), symbol),
+                Err(e) => {
+                    warn!("⚠️ Source de marché {} indisponible: {}", provider.name(), e);
+                    last_error = e;
+                }
+            }
+        }
+
+        // En dernier recours, servir une série périmée plutôt que rien
+        if let Some(stale) = self.cache.get(&key) {
+            info!("⏳ Utilisation d'une série de marché périmée pour {}", symbol);
+            return Ok(stale.candles.clone());
+        }
+
+        Err(last_error)
+    }
+
+    /// Calcule les indicateurs pour un symbole
+    pub async fn indicators(&mut self, symbol: &str, interval: &str) -> Result<MarketIndicators, String> {
+        let limit = self.long_window.max(self.short_window) + 1;
+        let candles = self.candles(symbol, interval, limit).await?;
+        compute_indicators(symbol, &candles, self.short_window, self.long_window)
+            .ok_or_else(|| format!("Série insuffisante pour calculer les indicateurs de {}", symbol))
+    }
+
+    /// Vide le cache de bougies
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+}
+
+fn build_client() -> Client {
+    Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_else(|_| Client::new())
+}
+
+/// Moyenne mobile simple sur les `window` dernières clôtures
+pub fn simple_moving_average(closes: &[f64], window: usize) -> Option<f64> {
+    if window == 0 || closes.len() < window {
+        return None;
+    }
+    let slice = &closes[closes.len() - window..];
+    Some(slice.iter().sum::<f64>() / window as f64)
+}
+
+/// Moyenne mobile exponentielle sur toute la série avec la période `window`
+pub fn exponential_moving_average(closes: &[f64], window: usize) -> Option<f64> {
+    if window == 0 || closes.is_empty() {
+        return None;
+    }
+    let alpha = 2.0 / (window as f64 + 1.0);
+    let mut ema = closes[0];
+    for close in &closes[1..] {
+        ema = alpha * close + (1.0 - alpha) * ema;
+    }
+    Some(ema)
+}
+
+/// Rendements logarithmiques successifs
+pub fn log_returns(closes: &[f64]) -> Vec<f64> {
+    closes.windows(2)
+        .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect()
+}
+
+/// Volatilité: écart-type (échantillon) des rendements logarithmiques
+pub fn volatility(closes: &[f64]) -> Option<f64> {
+    let returns = log_returns(closes);
+    if returns.len() < 2 {
+        return None;
+    }
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    Some(variance.sqrt())
+}
+
+/// Calcule l'ensemble des indicateurs d'une série
+pub fn compute_indicators(symbol: &str, candles: &[Candle], short_window: usize, long_window: usize) -> Option<MarketIndicators> {
+    let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+    let returns = log_returns(&closes);
+    let trend = if returns.is_empty() { 0.0 } else { returns.iter().sum::<f64>() / returns.len() as f64 };
+
+    Some(MarketIndicators {
+        symbol: symbol.to_uppercase(),
+        last_price: *closes.last()?,
+        sma_short: simple_moving_average(&closes, short_window)?,
+        sma_long: simple_moving_average(&closes, long_window)?,
+        ema_short: exponential_moving_average(&closes, short_window)?,
+        volatility: volatility(&closes)?,
+        trend,
+        computed_at: Utc::now().to_rfc3339(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moving_averages() {
+        let closes = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(simple_moving_average(&closes, 2), Some(4.5));
+        assert_eq!(simple_moving_average(&closes, 6), None);
+
+        let ema = exponential_moving_average(&closes, 3).unwrap();
+        assert!(ema > 3.0 && ema < 5.0);
+    }
+
+    #[test]
+    fn test_volatility_flat_series() {
+        let closes = vec![10.0; 20];
+        assert_eq!(volatility(&closes), Some(0.0));
+        assert_eq!(volatility(&[10.0, 11.0]), None);
+    }
+
+    #[test]
+    fn test_coingecko_range_keeps_hourly_granularity() {
+        assert!(CoinGeckoProvider::interval_to_days("1h", 10) >= 2);
+        assert!(CoinGeckoProvider::interval_to_days("4h", 3) >= 2);
+        assert!(CoinGeckoProvider::interval_to_days("1d", 10) > 90);
+    }
+
+    #[test]
+    fn test_resample_points_to_interval() {
+        let hour = 3_600_000;
+        let points = vec![
+            (0, 10.0, 1.0),
+            (hour / 2, 12.0, 2.0),
+            (hour, 11.0, 3.0),
+            (hour + hour / 4, 9.0, 4.0),
+        ];
+        let candles = resample_points(&points, hour);
+        assert_eq!(candles.len(), 2);
+        assert_eq!((candles[0].open, candles[0].high, candles[0].low, candles[0].close), (10.0, 12.0, 10.0, 12.0));
+        assert_eq!(candles[1].open_time, hour);
+        assert_eq!((candles[1].low, candles[1].close, candles[1].volume), (9.0, 9.0, 4.0));
+    }
+}
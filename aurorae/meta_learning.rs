@@ -0,0 +1,488 @@
+//! AURORAE++ - meta_learning.rs
+//!
+//! Méta-apprentissage: des statistiques des patterns appris (fonctions, structures, traits,
+//! enums par module, dépendances des crates) sont tirées des méta-règles dont la confiance se
+//! consolide d'une analyse à l'autre. La progression de l'agent de renforcement et de
+//! l'évolution est échantillonnée à chaque évaluation; sa tendance règle leurs
+//! hyperparamètres (exploration, taux d'apprentissage, chance de mutation). Règles, historique
+//! et ajustements sont persistés dans `aurorae_state/meta_learning.json`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::evolution::EvolutionEngine;
+use crate::knowledge::{KnowledgeBase, MetaInsight, Pattern, Provenance};
+use crate::reinforcement_learning::LearningAgent;
+
+pub const META_LEARNING_PATH: &str = "aurorae_state/meta_learning.json";
+/// Confiance en dessous de laquelle une règle est oubliée
+const MIN_CONFIDENCE: f32 = 0.2;
+/// Confiance à partir de laquelle une règle devient un méta-insight de la base de savoir
+const INSIGHT_CONFIDENCE: f32 = 0.6;
+/// Poids d'une nouvelle analyse dans la confiance d'une règle
+const CONFIDENCE_SMOOTHING: f32 = 0.3;
+/// Oubli appliqué aux règles non confirmées depuis `STALE_DAYS`
+const STALE_DAYS: i64 = 30;
+const STALE_DECAY: f32 = 0.8;
+/// Échantillons de progression conservés, et fenêtre de la tendance
+const MAX_SAMPLES: usize = 500;
+const TREND_WINDOW: usize = 10;
+/// Pente en dessous de laquelle la progression est considérée comme stagnante
+const STAGNATION_SLOPE: f32 = 0.002;
+
+/// Régularité observée dans les patterns appris
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaRule {
+    /// Identifiant stable (`trait-abstraction`, `dependency:serde`…)
+    pub id: String,
+    pub description: String,
+    /// Part des observations qui confirment la règle, lissée entre analyses (0 à 1)
+    pub confidence: f32,
+    /// Observations de la dernière analyse qui confirment la règle
+    pub support: usize,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Mesure de la progression de l'apprentissage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressSample {
+    pub at: DateTime<Utc>,
+    pub rl_performance: f32,
+    pub mutation_fitness: f32,
+    pub best_fitness: Option<f32>,
+    pub meta_rules: usize,
+    pub mean_confidence: f32,
+}
+
+/// Hyperparamètres réglés par le méta-apprentissage
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetaParameters {
+    pub exploration_rate: f32,
+    pub learning_rate: f32,
+    pub mutation_chance: f32,
+}
+
+/// Régime de progression détecté sur la fenêtre récente
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LearningRegime {
+    Improving,
+    Stagnating,
+    Regressing,
+}
+
+/// Ajustement appliqué, avec les paramètres avant et après
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaAdjustment {
+    pub at: DateTime<Utc>,
+    pub regime: LearningRegime,
+    pub before: MetaParameters,
+    pub after: MetaParameters,
+}
+
+/// Bilan de la progression dans le temps
+#[derive(Debug, Clone, PartialEq)]
+pub struct LearningReport {
+    pub samples: usize,
+    pub first: Option<ProgressSample>,
+    pub last: Option<ProgressSample>,
+    /// Pente par échantillon de la performance de l'agent sur la fenêtre récente
+    pub rl_trend: Option<f32>,
+    pub fitness_trend: Option<f32>,
+    pub rules: usize,
+    pub mean_confidence: f32,
+    pub adjustments: usize,
+    pub regime: Option<LearningRegime>,
+}
+
+impl LearningReport {
+    pub fn print(&self) {
+        info!(
+            "📊 Méta-apprentissage: {} échantillon(s), {} règle(s) (confiance moyenne {:.2}), {} ajustement(s)",
+            self.samples, self.rules, self.mean_confidence, self.adjustments
+        );
+        if let (Some(first), Some(last)) = (&self.first, &self.last) {
+            println!(
+                "→ Performance RL {:.3} → {:.3} • aptitude des mutations {:.3} → {:.3}",
+                first.rl_performance, last.rl_performance, first.mutation_fitness, last.mutation_fitness
+            );
+        }
+        if let Some(regime) = self.regime {
+            println!(
+                "→ Tendance {:?} (RL {:+.4}/éval., mutations {:+.4}/éval.)",
+                regime,
+                self.rl_trend.unwrap_or(0.0),
+                self.fitness_trend.unwrap_or(0.0)
+            );
+        }
+    }
+}
+
+/// État persisté
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MetaLearningState {
+    rules: BTreeMap<String, MetaRule>,
+    progress: Vec<ProgressSample>,
+    adjustments: Vec<MetaAdjustment>,
+}
+
+/// Règle candidate: identifiant, description, nombre d'observations la confirmant, total
+type Observation = (String, String, usize, usize);
+
+pub struct MetaLearningSystem {
+    state: MetaLearningState,
+    path: Option<PathBuf>,
+    /// Exploration renforcée demandée par `explore_solution_space`, consommée au prochain ajustement
+    exploration_boost: bool,
+}
+
+impl Default for MetaLearningSystem {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+impl MetaLearningSystem {
+    /// Système rechargé depuis `aurorae_state/meta_learning.json`
+    pub fn new() -> Self {
+        Self::open(Path::new(META_LEARNING_PATH))
+    }
+
+    pub fn in_memory() -> Self {
+        Self { state: MetaLearningState::default(), path: None, exploration_boost: false }
+    }
+
+    pub fn open(path: &Path) -> Self {
+        let state = File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self { state, path: Some(path.to_path_buf()), exploration_boost: false }
+    }
+
+    /// Oublie progressivement les règles qui n'ont pas été confirmées depuis longtemps
+    pub fn initialize_meta_layers(&mut self) {
+        let stale_before = Utc::now() - Duration::days(STALE_DAYS);
+        for rule in self.state.rules.values_mut().filter(|r| r.updated_at < stale_before) {
+            rule.confidence *= STALE_DECAY;
+        }
+        let before = self.state.rules.len();
+        self.state.rules.retain(|_, rule| rule.confidence >= MIN_CONFIDENCE);
+        info!(
+            "🔄 Méta-apprentissage: {} règle(s) rechargée(s), {} oubliée(s), {} échantillon(s) de progression",
+            self.state.rules.len(), before - self.state.rules.len(), self.state.progress.len()
+        );
+        self.save();
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &MetaRule> {
+        self.state.rules.values()
+    }
+
+    pub fn get_meta_rules_count(&self) -> usize {
+        self.state.rules.len()
+    }
+
+    fn mean_confidence(&self) -> f32 {
+        if self.state.rules.is_empty() {
+            return 0.0;
+        }
+        self.state.rules.values().map(|r| r.confidence).sum::<f32>() / self.state.rules.len() as f32
+    }
+
+    /// Règles tirées des statistiques de structure des modules
+    fn pattern_observations(patterns: &[Pattern]) -> Vec<Observation> {
+        let total = patterns.len();
+        let count = |predicate: &dyn Fn(&Pattern) -> bool| patterns.iter().filter(|p| predicate(p)).count();
+        vec![
+            (
+                "trait-abstraction".to_string(),
+                "Les types sont abstraits derrière des traits (au moins un trait pour trois structures)".to_string(),
+                count(&|p| p.structs > 0 && p.traits * 3 >= p.structs),
+                total,
+            ),
+            (
+                "enum-state-modeling".to_string(),
+                "Les états sont modélisés par des enums (au moins un enum pour quatre structures)".to_string(),
+                count(&|p| p.enums > 0 && p.enums * 4 >= p.structs),
+                total,
+            ),
+            (
+                "small-modules".to_string(),
+                "Les modules restent petits (au plus 30 fonctions)".to_string(),
+                count(&|p| p.functions <= 30),
+                total,
+            ),
+            (
+                "behavior-rich-types".to_string(),
+                "Chaque structure porte un comportement riche (au moins cinq fonctions par structure)".to_string(),
+                count(&|p| p.structs > 0 && p.functions >= p.structs * 5),
+                total,
+            ),
+        ]
+    }
+
+    /// Intègre une analyse: la confiance d'une règle converge vers la part des observations
+    /// qui la confirment; les règles trop faibles sont écartées
+    fn integrate(&mut self, observations: Vec<Observation>) -> usize {
+        let now = Utc::now();
+        let mut confirmed = 0;
+        for (id, description, support, total) in observations.into_iter().filter(|o| o.3 > 0) {
+            let ratio = support as f32 / total as f32;
+            let rule = self.state.rules.entry(id.clone()).or_insert_with(|| MetaRule {
+                id,
+                description: description.clone(),
+                confidence: ratio,
+                support,
+                created_at: now,
+                updated_at: now,
+            });
+            rule.confidence = (1.0 - CONFIDENCE_SMOOTHING) * rule.confidence + CONFIDENCE_SMOOTHING * ratio;
+            rule.description = description;
+            rule.support = support;
+            rule.updated_at = now;
+            if rule.confidence >= MIN_CONFIDENCE {
+                confirmed += 1;
+            }
+        }
+        self.state.rules.retain(|_, rule| rule.confidence >= MIN_CONFIDENCE);
+        self.save();
+        confirmed
+    }
+
+    /// Extrait les méta-règles des statistiques des patterns nouvellement appris
+    pub fn analyze_patterns(&mut self, patterns: &[Pattern]) -> usize {
+        if patterns.is_empty() {
+            return 0;
+        }
+        let confirmed = self.integrate(Self::pattern_observations(patterns));
+        info!("🧠 Méta-règles: {} confirmée(s) sur {} pattern(s)", confirmed, patterns.len());
+        confirmed
+    }
+
+    /// Règles de l'ensemble de la base: structure des patterns et dépendances récurrentes des
+    /// crates de l'écosystème
+    pub fn integrate_blockchain_patterns(&mut self, knowledge: &KnowledgeBase) -> usize {
+        let mut observations = Self::pattern_observations(knowledge.get_patterns());
+        let total = knowledge.crates.len();
+        let mut dependencies: HashMap<&str, usize> = HashMap::new();
+        for record in &knowledge.crates {
+            for dependency in &record.dependencies {
+                *dependencies.entry(dependency.as_str()).or_default() += 1;
+            }
+        }
+        observations.extend(dependencies.into_iter().filter(|(_, n)| *n * 4 >= total).map(|(name, n)| {
+            (
+                format!("dependency:{}", name),
+                format!("Les crates blockchain de référence s'appuient sur {}", name),
+                n,
+                total,
+            )
+        }));
+        let confirmed = self.integrate(observations);
+        info!("⛓️ Méta-règles de l'écosystème: {} confirmée(s) ({} crate(s))", confirmed, total);
+        confirmed
+    }
+
+    /// Échantillonne la progression de l'agent de renforcement et de l'évolution
+    pub fn evaluate_learning_progress(&mut self, agent: &mut LearningAgent, evolution: &EvolutionEngine) -> ProgressSample {
+        let sample = ProgressSample {
+            at: Utc::now(),
+            rl_performance: agent.evaluate_performance(),
+            mutation_fitness: evolution.mutation_fitness,
+            best_fitness: evolution.best_fitness().map(|(_, fitness)| fitness),
+            meta_rules: self.state.rules.len(),
+            mean_confidence: self.mean_confidence(),
+        };
+        self.state.progress.push(sample.clone());
+        if self.state.progress.len() > MAX_SAMPLES {
+            let excess = self.state.progress.len() - MAX_SAMPLES;
+            self.state.progress.drain(..excess);
+        }
+        self.save();
+        sample
+    }
+
+    /// Pente (moindres carrés) d'une mesure sur la fenêtre récente
+    fn trend(&self, measure: impl Fn(&ProgressSample) -> f32) -> Option<f32> {
+        let window = &self.state.progress[self.state.progress.len().saturating_sub(TREND_WINDOW)..];
+        if window.len() < 3 {
+            return None;
+        }
+        let n = window.len() as f32;
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = window.iter().map(&measure).sum::<f32>() / n;
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for (i, sample) in window.iter().enumerate() {
+            let dx = i as f32 - mean_x;
+            covariance += dx * (measure(sample) - mean_y);
+            variance += dx * dx;
+        }
+        Some(covariance / variance)
+    }
+
+    /// Régime de progression, d'après la performance de l'agent et l'aptitude des mutations
+    pub fn regime(&self) -> Option<LearningRegime> {
+        let rl = self.trend(|s| s.rl_performance)?;
+        let fitness = self.trend(|s| s.mutation_fitness).unwrap_or(0.0);
+        let combined = 0.7 * rl + 0.3 * fitness;
+        Some(if combined > STAGNATION_SLOPE {
+            LearningRegime::Improving
+        } else if combined < -STAGNATION_SLOPE {
+            LearningRegime::Regressing
+        } else {
+            LearningRegime::Stagnating
+        })
+    }
+
+    /// Règle les hyperparamètres selon la tendance: en progrès, on exploite davantage; en
+    /// stagnation, on explore et on mute plus; en régression, on apprend plus prudemment
+    pub fn adjust_meta_parameters(&mut self, agent: &mut LearningAgent, evolution: &mut EvolutionEngine) -> Option<MetaAdjustment> {
+        let regime = self.regime()?;
+        let step = agent.meta_learning_rate.max(0.001);
+        let before = MetaParameters {
+            exploration_rate: agent.exploration_rate,
+            learning_rate: agent.learning_rate,
+            mutation_chance: evolution.mutation_chance,
+        };
+        let mut after = before;
+        match regime {
+            LearningRegime::Improving => {
+                after.exploration_rate -= step;
+            }
+            LearningRegime::Stagnating => {
+                after.exploration_rate += step;
+                after.mutation_chance *= 1.1;
+            }
+            LearningRegime::Regressing => {
+                after.learning_rate *= 0.9;
+                after.mutation_chance *= 0.9;
+            }
+        }
+        if std::mem::take(&mut self.exploration_boost) {
+            after.exploration_rate += 2.0 * step;
+        }
+        after.exploration_rate = after.exploration_rate.clamp(0.01, 0.5);
+        after.learning_rate = after.learning_rate.clamp(0.01, 0.3);
+        after.mutation_chance = after.mutation_chance.clamp(0.01, 0.5);
+
+        agent.exploration_rate = after.exploration_rate;
+        agent.learning_rate = after.learning_rate;
+        evolution.mutation_chance = after.mutation_chance;
+        info!(
+            "🎛️ Méta-paramètres ({:?}): exploration {:.3} → {:.3}, apprentissage {:.3} → {:.3}, mutation {:.3} → {:.3}",
+            regime, before.exploration_rate, after.exploration_rate, before.learning_rate, after.learning_rate,
+            before.mutation_chance, after.mutation_chance
+        );
+
+        let adjustment = MetaAdjustment { at: Utc::now(), regime, before, after };
+        self.state.adjustments.push(adjustment.clone());
+        if self.state.adjustments.len() > MAX_SAMPLES {
+            let excess = self.state.adjustments.len() - MAX_SAMPLES;
+            self.state.adjustments.drain(..excess);
+        }
+        self.save();
+        Some(adjustment)
+    }
+
+    /// Action `explore_solutions` de l'agent: le prochain ajustement élargit l'exploration
+    pub fn explore_solution_space(&mut self) {
+        self.exploration_boost = true;
+        info!("🧭 Exploration de l'espace des solutions demandée au méta-apprentissage");
+    }
+
+    /// Règles suffisamment établies, à verser dans la base de savoir
+    pub fn derive_meta_insights(&self) -> Vec<MetaInsight> {
+        self.state.rules.values()
+            .filter(|rule| rule.confidence >= INSIGHT_CONFIDENCE)
+            .map(|rule| MetaInsight {
+                topic: format!("meta:{}", rule.id),
+                content: rule.description.clone(),
+                confidence: rule.confidence,
+                provenance: Provenance { updated_at: rule.updated_at, ..Provenance::local() },
+            })
+            .collect()
+    }
+
+    /// Bilan de la progression depuis le premier échantillon conservé
+    pub fn report(&self) -> LearningReport {
+        LearningReport {
+            samples: self.state.progress.len(),
+            first: self.state.progress.first().cloned(),
+            last: self.state.progress.last().cloned(),
+            rl_trend: self.trend(|s| s.rl_performance),
+            fitness_trend: self.trend(|s| s.mutation_fitness),
+            rules: self.state.rules.len(),
+            mean_confidence: self.mean_confidence(),
+            adjustments: self.state.adjustments.len(),
+            regime: self.regime(),
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self.state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(name: &str, functions: usize, structs: usize, traits: usize, enums: usize) -> Pattern {
+        Pattern { module_name: name.to_string(), functions, structs, traits, enums }
+    }
+
+    #[test]
+    fn extracts_rules_and_persists_confidence() {
+        let path = std::env::temp_dir().join(format!("aurorae-meta-{}.json", uuid::Uuid::new_v4()));
+        let mut system = MetaLearningSystem::open(&path);
+        let patterns = vec![
+            pattern("a.rs", 12, 3, 1, 1),
+            pattern("b.rs", 20, 2, 1, 0),
+            pattern("c.rs", 80, 4, 0, 0),
+        ];
+        system.analyze_patterns(&patterns);
+        let rules: HashMap<String, f32> = system.rules().map(|r| (r.id.clone(), r.confidence)).collect();
+        assert!((rules["trait-abstraction"] - 2.0 / 3.0).abs() < 1e-5);
+        assert!((rules["small-modules"] - 2.0 / 3.0).abs() < 1e-5);
+        assert_eq!(system.derive_meta_insights().len(), 4);
+
+        // Une analyse contraire fait baisser la confiance sans effacer la règle d'un coup
+        system.analyze_patterns(&[pattern("d.rs", 40, 5, 0, 0)]);
+        let reloaded = MetaLearningSystem::open(&path);
+        let trait_rule = reloaded.rules().find(|r| r.id == "trait-abstraction").unwrap();
+        assert!((trait_rule.confidence - 0.7 * 2.0 / 3.0).abs() < 1e-5);
+        assert!(reloaded.rules().all(|r| r.id != "enum-state-modeling" || r.confidence >= MIN_CONFIDENCE));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn stagnation_raises_exploration_and_mutation() {
+        let mut system = MetaLearningSystem::in_memory();
+        let mut agent = LearningAgent::new(vec!["observer".to_string()], "initial");
+        let mut evolution = EvolutionEngine::new();
+        assert!(system.adjust_meta_parameters(&mut agent, &mut evolution).is_none());
+
+        for _ in 0..4 {
+            system.evaluate_learning_progress(&mut agent, &evolution);
+        }
+        assert_eq!(system.regime(), Some(LearningRegime::Stagnating));
+        let (exploration, mutation) = (agent.exploration_rate, evolution.mutation_chance);
+        let adjustment = system.adjust_meta_parameters(&mut agent, &mut evolution).unwrap();
+        assert!(agent.exploration_rate > exploration && evolution.mutation_chance > mutation);
+        assert_eq!(adjustment.after.exploration_rate, agent.exploration_rate);
+        assert_eq!(system.report().adjustments, 1);
+    }
+}
@@ -0,0 +1,338 @@
+//! AURORAE++ - metacognition.rs
+//!
+//! Modèle de soi: avant chaque décision risquée (déploiement, mutation), le module qui décide
+//! annonce sa probabilité de succès; l'issue réelle est ensuite confrontée à la prédiction.
+//! Les courbes de calibration qui en découlent mesurent la sur- ou sous-confiance par type de
+//! décision, et cette correction est renvoyée vers l'urgence des pensées du cerveau et le taux
+//! d'exploration de l'agent de renforcement. L'historique est persisté dans
+//! `aurorae_state/metacognition.json`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::info;
+
+use crate::brain::{Intent, Thought};
+use crate::reinforcement_learning::LearningAgent;
+
+pub const METACOGNITION_PATH: &str = "aurorae_state/metacognition.json";
+/// Issues conservées par type de décision
+const MAX_OUTCOMES: usize = 1000;
+/// Issues nécessaires avant de corriger quoi que ce soit
+const MIN_OUTCOMES: usize = 5;
+/// Tranches de confiance des courbes de calibration
+const CALIBRATION_BINS: usize = 5;
+/// Gain appliqué à l'écart de calibration pour corriger le taux d'exploration
+const EXPLORATION_GAIN: f32 = 0.1;
+
+pub type SharedMetacognition = Arc<RwLock<MetacognitiveSystem>>;
+
+/// Décisions dont l'issue est confrontée à la prédiction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DecisionKind {
+    /// Déploiement d'un contrat: succès si l'adresse est obtenue
+    Deployment,
+    /// Mutation en bac à sable: succès si les tests passent
+    Mutation,
+}
+
+impl DecisionKind {
+    pub const ALL: [DecisionKind; 2] = [DecisionKind::Deployment, DecisionKind::Mutation];
+
+    /// Type de décision qu'une intention du cerveau finit par engager
+    pub fn for_intent(intent: &Intent) -> Option<Self> {
+        match intent {
+            Intent::GenerateChain | Intent::BuildEcosystem => Some(DecisionKind::Deployment),
+            Intent::MutateSelf | Intent::SelfUpgrade => Some(DecisionKind::Mutation),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DecisionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecisionKind::Deployment => write!(f, "déploiement"),
+            DecisionKind::Mutation => write!(f, "mutation"),
+        }
+    }
+}
+
+/// Prédiction confrontée à l'issue réelle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Outcome {
+    pub predicted: f32,
+    pub succeeded: bool,
+    pub at: DateTime<Utc>,
+}
+
+/// Tranche d'une courbe de calibration
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationBin {
+    pub lower: f32,
+    pub upper: f32,
+    pub count: usize,
+    pub mean_predicted: f32,
+    pub observed_rate: f32,
+}
+
+/// Calibration d'un type de décision
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    pub kind: DecisionKind,
+    pub outcomes: usize,
+    pub curve: Vec<CalibrationBin>,
+    /// Confiance moyenne moins taux de succès observé: positif en cas de sur-confiance
+    pub bias: f32,
+    pub brier_score: f32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SelfModel {
+    outcomes: BTreeMap<DecisionKind, Vec<Outcome>>,
+}
+
+#[derive(Debug)]
+pub struct MetacognitiveSystem {
+    model: SelfModel,
+    /// Prédictions dont l'issue n'est pas encore connue
+    pending: HashMap<Uuid, (DecisionKind, f32)>,
+    path: Option<PathBuf>,
+}
+
+impl Default for MetacognitiveSystem {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+impl MetacognitiveSystem {
+    /// Modèle de soi rechargé depuis `aurorae_state/metacognition.json`
+    pub fn new() -> Self {
+        Self::open(Path::new(METACOGNITION_PATH))
+    }
+
+    pub fn in_memory() -> Self {
+        Self { model: SelfModel::default(), pending: HashMap::new(), path: None }
+    }
+
+    pub fn open(path: &Path) -> Self {
+        let model = File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self { model, pending: HashMap::new(), path: Some(path.to_path_buf()) }
+    }
+
+    pub fn shared(self) -> SharedMetacognition {
+        Arc::new(RwLock::new(self))
+    }
+
+    /// Annonce la probabilité de succès d'une décision; l'identifiant rendu sert à `observe`
+    pub fn predict(&mut self, kind: DecisionKind, confidence: f32) -> Uuid {
+        let id = Uuid::new_v4();
+        self.pending.insert(id, (kind, confidence.clamp(0.0, 1.0)));
+        id
+    }
+
+    /// Confronte une prédiction à l'issue réelle; false si la prédiction est inconnue
+    pub fn observe(&mut self, prediction: Uuid, succeeded: bool) -> bool {
+        match self.pending.remove(&prediction) {
+            Some((kind, predicted)) => {
+                self.record(kind, predicted, succeeded);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Enregistre directement une prédiction et son issue
+    pub fn record(&mut self, kind: DecisionKind, predicted: f32, succeeded: bool) {
+        let outcomes = self.model.outcomes.entry(kind).or_default();
+        outcomes.push(Outcome { predicted: predicted.clamp(0.0, 1.0), succeeded, at: Utc::now() });
+        if outcomes.len() > MAX_OUTCOMES {
+            let excess = outcomes.len() - MAX_OUTCOMES;
+            outcomes.drain(..excess);
+        }
+        self.save();
+    }
+
+    fn outcomes(&self, kind: DecisionKind) -> &[Outcome] {
+        self.model.outcomes.get(&kind).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Courbe de calibration, écart de confiance et score de Brier d'un type de décision
+    pub fn calibration(&self, kind: DecisionKind) -> Option<Calibration> {
+        let outcomes = self.outcomes(kind);
+        if outcomes.is_empty() {
+            return None;
+        }
+        let width = 1.0 / CALIBRATION_BINS as f32;
+        let curve = (0..CALIBRATION_BINS)
+            .filter_map(|bin| {
+                let (lower, upper) = (bin as f32 * width, (bin + 1) as f32 * width);
+                let last = bin + 1 == CALIBRATION_BINS;
+                let members: Vec<&Outcome> = outcomes.iter()
+                    .filter(|o| o.predicted >= lower && (o.predicted < upper || last))
+                    .collect();
+                if members.is_empty() {
+                    return None;
+                }
+                let count = members.len();
+                Some(CalibrationBin {
+                    lower,
+                    upper,
+                    count,
+                    mean_predicted: members.iter().map(|o| o.predicted).sum::<f32>() / count as f32,
+                    observed_rate: members.iter().filter(|o| o.succeeded).count() as f32 / count as f32,
+                })
+            })
+            .collect();
+        let n = outcomes.len() as f32;
+        let success = |o: &Outcome| if o.succeeded { 1.0 } else { 0.0 };
+        Some(Calibration {
+            kind,
+            outcomes: outcomes.len(),
+            curve,
+            bias: outcomes.iter().map(|o| o.predicted - success(o)).sum::<f32>() / n,
+            brier_score: outcomes.iter().map(|o| (o.predicted - success(o)).powi(2)).sum::<f32>() / n,
+        })
+    }
+
+    /// Écart de confiance d'un type de décision, une fois assez d'issues observées
+    pub fn bias(&self, kind: DecisionKind) -> Option<f32> {
+        self.calibration(kind).filter(|c| c.outcomes >= MIN_OUTCOMES).map(|c| c.bias)
+    }
+
+    /// Confiance recalibrée: taux de succès observé dans la tranche de `confidence`
+    pub fn calibrated(&self, kind: DecisionKind, confidence: f32) -> f32 {
+        let Some(calibration) = self.calibration(kind) else { return confidence };
+        calibration.curve.iter()
+            .find(|bin| confidence >= bin.lower && (confidence < bin.upper || bin.upper >= 1.0))
+            .filter(|bin| bin.count >= MIN_OUTCOMES)
+            .map(|bin| bin.observed_rate)
+            .unwrap_or(confidence)
+    }
+
+    /// Corrige l'urgence d'une pensée: une décision sur laquelle l'entité se surestime est
+    /// ralentie, une décision sous-estimée est avancée
+    pub fn adjust_urgency(&self, thought: &mut Thought) {
+        let Some(bias) = DecisionKind::for_intent(&thought.intent).and_then(|kind| self.bias(kind)) else { return };
+        let corrected = (thought.urgency as f32 * (1.0 - bias)).round().clamp(0.0, 255.0) as u8;
+        if corrected != thought.urgency {
+            thought.metadata.insert("calibration".to_string(), format!("{:+.2}", bias));
+            thought.urgency = corrected;
+        }
+    }
+
+    /// Corrige le taux d'exploration: la sur-confiance appelle plus d'exploration, la
+    /// sous-confiance davantage d'exploitation
+    pub fn adjust_exploration(&self, agent: &mut LearningAgent) -> Option<f32> {
+        let biases: Vec<f32> = DecisionKind::ALL.iter().filter_map(|kind| self.bias(*kind)).collect();
+        if biases.is_empty() {
+            return None;
+        }
+        let bias = biases.iter().sum::<f32>() / biases.len() as f32;
+        let before = agent.exploration_rate;
+        agent.exploration_rate = (before + EXPLORATION_GAIN * bias).clamp(0.01, 0.5);
+        if (agent.exploration_rate - before).abs() > f32::EPSILON {
+            info!(
+                "🪞 Calibration {:+.2}: exploration {:.3} → {:.3}",
+                bias, before, agent.exploration_rate
+            );
+        }
+        Some(agent.exploration_rate)
+    }
+
+    pub fn report(&self) {
+        for kind in DecisionKind::ALL {
+            let Some(calibration) = self.calibration(kind) else { continue };
+            let verdict = match calibration.bias {
+                b if b > 0.05 => "sur-confiance",
+                b if b < -0.05 => "sous-confiance",
+                _ => "calibré",
+            };
+            info!(
+                "🪞 Calibration {}: {} issue(s), écart {:+.2} ({}), Brier {:.3}",
+                kind, calibration.outcomes, calibration.bias, verdict, calibration.brier_score
+            );
+            for bin in &calibration.curve {
+                println!(
+                    "→ [{:.1}, {:.1}) prédit {:.2} • observé {:.2} ({} issue(s))",
+                    bin.lower, bin.upper, bin.mean_predicted, bin.observed_rate, bin.count
+                );
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self.model);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calibration_curve_tracks_predictions_against_outcomes() {
+        let path = std::env::temp_dir().join(format!("aurorae-metacognition-{}.json", uuid::Uuid::new_v4()));
+        let mut system = MetacognitiveSystem::open(&path);
+        // Mutations annoncées à 90 % qui ne réussissent qu'une fois sur deux
+        for i in 0..10 {
+            let prediction = system.predict(DecisionKind::Mutation, 0.9);
+            assert!(system.observe(prediction, i % 2 == 0));
+        }
+        assert!(!system.observe(Uuid::new_v4(), true));
+
+        let reloaded = MetacognitiveSystem::open(&path);
+        let calibration = reloaded.calibration(DecisionKind::Mutation).unwrap();
+        assert_eq!(calibration.outcomes, 10);
+        assert_eq!(calibration.curve.len(), 1);
+        assert!((calibration.curve[0].observed_rate - 0.5).abs() < 1e-5);
+        assert!((calibration.bias - 0.4).abs() < 1e-5);
+        assert!((reloaded.calibrated(DecisionKind::Mutation, 0.95) - 0.5).abs() < 1e-5);
+        assert_eq!(reloaded.calibrated(DecisionKind::Deployment, 0.7), 0.7);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn overconfidence_slows_thoughts_and_raises_exploration() {
+        let mut system = MetacognitiveSystem::in_memory();
+        let mut thought = Thought::new(Intent::MutateSelf, 200);
+        system.adjust_urgency(&mut thought);
+        assert_eq!(thought.urgency, 200);
+
+        for _ in 0..MIN_OUTCOMES {
+            system.record(DecisionKind::Mutation, 0.75, false);
+            system.record(DecisionKind::Deployment, 0.25, true);
+        }
+        system.adjust_urgency(&mut thought);
+        assert_eq!(thought.urgency, 50);
+        let mut deploy = Thought::new(Intent::GenerateChain, 100);
+        system.adjust_urgency(&mut deploy);
+        assert_eq!(deploy.urgency, 175);
+
+        // Écarts opposés de même ampleur: l'exploration ne bouge pas
+        let mut agent = LearningAgent::new(vec!["observer".to_string()], "initial");
+        let exploration = agent.exploration_rate;
+        assert_eq!(system.adjust_exploration(&mut agent), Some(exploration));
+        system.record(DecisionKind::Mutation, 1.0, false);
+        assert!(system.adjust_exploration(&mut agent).unwrap() > exploration);
+    }
+}
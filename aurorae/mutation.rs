@@ -1,46 +1,707 @@
 //! AURORAE++ - mutation.rs
 //!
 //! Ce module permet à l'IA de modifier ses propres modules générés.
-//! Il applique des mutations conscientes sur le code source pour introduire de la variation, de l'amélioration ou des corrections.
+//! Les mutations opèrent sur l'AST syn (inversion d'opérateurs arithmétiques, constantes
+//! altérées dans des bornes, inlining et extraction de fonctions, changement de collections)
+//! avec un générateur aléatoire initialisé par graine. Chaque mutation est appliquée par
+//! édition des seules plages concernées (commentaires et mise en forme conservés), produit
+//! un diff unifié, et n'est écrite qu'après vérification de la compilation.
 
-use std::fs::{read_to_string, write};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::fs::{self, read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use proc_macro2::{LineColumn, Span};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Expr, FnArg, Item, ItemFn, Lit, Pat};
 use uuid::Uuid;
+use walkdir::WalkDir;
+use tracing::info;
+
+use crate::audit::{self, AuditKind};
+use crate::formal_verification::PropertyHarness;
+use crate::generator::{parse_diagnostics, CompileGate};
+use crate::rollback::record_source_change;
+use crate::security::{log_security_event, SecurityEvent};
+
+/// Propositions essayées avant d'abandonner une mutation qui ne compile pas
+pub const MAX_ATTEMPTS: usize = 3;
+/// Écart relatif maximal appliqué à une constante
+pub const CONSTANT_DRIFT: f64 = 0.1;
 
 #[derive(Debug)]
 pub enum MutationResult {
-    Success(String),
+    Success(MutationReport),
     NoChanges,
     Error(String),
 }
 
-/// Mutations basées sur des patterns simples (exemple : renommer la fonction `hello`)
-pub fn mutate_module_code(path: &str) -> MutationResult {
-    let mod_path = format!("{}/mod.rs", path);
-    let code_path = Path::new(&mod_path);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MutationOperator {
+    SwapArithmetic,
+    AlterConstant,
+    InlineFunction,
+    ExtractFunction,
+    ChangeCollection,
+}
+
+impl MutationOperator {
+    pub const ALL: [MutationOperator; 5] = [
+        MutationOperator::SwapArithmetic,
+        MutationOperator::AlterConstant,
+        MutationOperator::InlineFunction,
+        MutationOperator::ExtractFunction,
+        MutationOperator::ChangeCollection,
+    ];
+}
+
+/// Mutation écrite sur disque
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutationReport {
+    pub modification_id: Uuid,
+    pub file: String,
+    pub operator: MutationOperator,
+    pub description: String,
+    /// Graine rejouant la même mutation sur la même source
+    pub seed: u64,
+    pub diff: String,
+}
+
+/// Mutation proposée, non encore vérifiée ni écrite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mutation {
+    pub operator: MutationOperator,
+    pub description: String,
+    pub source: String,
+}
+
+impl Mutation {
+    pub fn diff(&self, original: &str, file: &str) -> String {
+        TextDiff::from_lines(original, &self.source)
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{}", file), &format!("b/{}", file))
+            .to_string()
+    }
+}
+
+/// Remplacement d'une plage d'octets de la source
+#[derive(Debug, Clone)]
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+#[derive(Debug, Clone)]
+struct Site {
+    operator: MutationOperator,
+    description: String,
+    edits: Vec<Edit>,
+}
+
+/// Correspondance positions (ligne, colonne en caractères) → octets
+pub(crate) struct SourceMap<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0).chain(text.match_indices('\n').map(|(i, _)| i + 1)).collect();
+        Self { text, line_starts }
+    }
+
+    fn offset(&self, at: LineColumn) -> usize {
+        let start = self.line_starts.get(at.line.saturating_sub(1)).copied().unwrap_or(self.text.len());
+        self.text[start..].char_indices().nth(at.column).map(|(i, _)| start + i).unwrap_or(self.text.len())
+    }
+
+    pub(crate) fn range(&self, span: Span) -> (usize, usize) {
+        (self.offset(span.start()), self.offset(span.end()))
+    }
+
+    pub(crate) fn slice(&self, span: Span) -> &'a str {
+        let (start, end) = self.range(span);
+        &self.text[start..end]
+    }
+
+    fn edit(&self, span: Span, text: String) -> Edit {
+        let (start, end) = self.range(span);
+        Edit { start, end, text }
+    }
+}
+
+/// Fonction libre dont les paramètres sont de simples identifiants
+#[derive(Clone)]
+struct FnShape {
+    name: String,
+    /// (motif, type) de chaque paramètre
+    params: Vec<(String, Span)>,
+    args: Vec<String>,
+}
+
+fn fn_shape(item: &ItemFn) -> Option<FnShape> {
+    let sig = &item.sig;
+    if !sig.generics.params.is_empty() || sig.asyncness.is_some() || sig.constness.is_some()
+        || sig.unsafety.is_some() || sig.abi.is_some() || sig.variadic.is_some() {
+        return None;
+    }
+    // Seuls les commentaires de documentation sont tolérés (`#[test]`, `#[inline]`… exclus)
+    if item.attrs.iter().any(|attr| !attr.path().is_ident("doc")) {
+        return None;
+    }
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    for input in &sig.inputs {
+        let FnArg::Typed(typed) = input else { return None };
+        let Pat::Ident(pat) = typed.pat.as_ref() else { return None };
+        if pat.by_ref.is_some() || pat.subpat.is_some() {
+            return None;
+        }
+        let name = pat.ident.to_string();
+        params.push((format!("{}{}", if pat.mutability.is_some() { "mut " } else { "" }, name), typed.ty.span()));
+        args.push(name);
+    }
+    Some(FnShape { name: sig.ident.to_string(), params, args })
+}
+
+/// `return` ou `?` changeraient de sens une fois le corps recopié chez l'appelant
+fn exits_early(block: &syn::Block) -> bool {
+    struct Exit(bool);
+    impl<'ast> Visit<'ast> for Exit {
+        fn visit_expr_return(&mut self, _: &'ast syn::ExprReturn) {
+            self.0 = true;
+        }
+        fn visit_expr_try(&mut self, _: &'ast syn::ExprTry) {
+            self.0 = true;
+        }
+    }
+    let mut exit = Exit(false);
+    exit.visit_block(block);
+    exit.0
+}
+
+fn swapped(op: &BinOp) -> Option<(&'static str, &'static str)> {
+    match op {
+        BinOp::Add(_) => Some(("+", "-")),
+        BinOp::Sub(_) => Some(("-", "+")),
+        BinOp::Mul(_) => Some(("*", "/")),
+        BinOp::Div(_) => Some(("/", "*")),
+        BinOp::AddAssign(_) => Some(("+=", "-=")),
+        BinOp::SubAssign(_) => Some(("-=", "+=")),
+        BinOp::MulAssign(_) => Some(("*=", "/=")),
+        BinOp::DivAssign(_) => Some(("/=", "*=")),
+        _ => None,
+    }
+}
+
+fn is_str(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(lit) if matches!(lit.lit, Lit::Str(_)))
+}
+
+/// Borne supérieure d'un entier selon son suffixe (i32 par défaut, comme l'inférence de rustc)
+fn int_bound(suffix: &str, value: u128) -> u128 {
+    match suffix {
+        "u8" => u8::MAX as u128,
+        "i8" => i8::MAX as u128,
+        "u16" => u16::MAX as u128,
+        "i16" => i16::MAX as u128,
+        "u32" => u32::MAX as u128,
+        "i32" => i32::MAX as u128,
+        "u64" | "usize" => u64::MAX as u128,
+        "i64" | "isize" => i64::MAX as u128,
+        "" if value <= i32::MAX as u128 => i32::MAX as u128,
+        "" => u64::MAX as u128,
+        _ => u128::MAX,
+    }
+}
+
+const COLLECTION_PAIRS: [(&str, &str); 2] = [("HashMap", "BTreeMap"), ("HashSet", "BTreeSet")];
+
+/// Recense les sites de mutation de chaque opérateur
+struct SiteCollector<'a, 'r> {
+    map: &'a SourceMap<'a>,
+    rng: &'r mut StdRng,
+    sites: Vec<Site>,
+    inlinable: BTreeMap<String, (FnShape, Span)>,
+    collections: BTreeMap<String, Vec<Span>>,
+    current_fn: Option<String>,
+}
+
+impl<'a, 'r> SiteCollector<'a, 'r> {
+    fn push(&mut self, operator: MutationOperator, description: String, edits: Vec<Edit>) {
+        self.sites.push(Site { operator, description, edits });
+    }
+
+    fn line(span: Span) -> usize {
+        span.start().line
+    }
+
+    /// Candidats à l'inlining et sites d'extraction parmi les fonctions de premier niveau
+    fn scan_items(&mut self, items: &[Item]) {
+        let names: Vec<String> = items.iter()
+            .filter_map(|item| match item { Item::Fn(f) => Some(f.sig.ident.to_string()), _ => None })
+            .collect();
+        for item in items {
+            let Item::Fn(item) = item else { continue };
+            let Some(shape) = fn_shape(item) else { continue };
+            let block = self.map.slice(item.block.span());
+            let recursive = Regex::new(&format!(r"\b{}\s*\(", regex::escape(&shape.name))).unwrap().is_match(block);
+
+            // La fonction reste définie: seuls les appels de ce fichier sont remplacés
+            if !recursive && !exits_early(&item.block) {
+                self.inlinable.insert(shape.name.clone(), (shape.clone(), item.block.span()));
+            }
+
+            let helper = format!("{}_extracted", shape.name);
+            if shape.name != "main" && item.block.stmts.len() >= 2 && !names.contains(&helper) {
+                let (item_start, item_end) = self.map.range(item.span());
+                let (block_start, _) = self.map.range(item.block.span());
+                let (_, ident_end) = self.map.range(item.sig.ident.span());
+                let text = format!(
+                    "{}{{\n    {}({})\n}}\n\nfn {}{}{}",
+                    &self.map.text[item_start..block_start],
+                    helper,
+                    shape.args.join(", "),
+                    helper,
+                    &self.map.text[ident_end..block_start],
+                    block,
+                );
+                let description = format!("extraction du corps de {} vers {} (ligne {})", shape.name, helper, Self::line(item.sig.ident.span()));
+                self.push(MutationOperator::ExtractFunction, description, vec![Edit { start: item_start, end: item_end, text }]);
+            }
+        }
+    }
+
+    /// Un site par paire présente: les deux sens sont échangés ensemble pour éviter les imports en double
+    fn collection_sites(&mut self) {
+        for (left, right) in COLLECTION_PAIRS {
+            let mut edits = Vec::new();
+            for (from, to) in [(left, right), (right, left)] {
+                for span in self.collections.get(from).cloned().unwrap_or_default() {
+                    edits.push(self.map.edit(span, to.to_string()));
+                }
+            }
+            if !edits.is_empty() {
+                let count = edits.len();
+                self.push(MutationOperator::ChangeCollection, format!("{} ↔ {} ({} occurrence(s))", left, right, count), edits);
+            }
+        }
+    }
+}
+
+impl<'ast, 'a, 'r> Visit<'ast> for SiteCollector<'a, 'r> {
+    // Attributs, longueurs de tableaux et motifs littéraux ne sont pas mutés
+    fn visit_attribute(&mut self, _: &'ast syn::Attribute) {}
+
+    fn visit_expr_repeat(&mut self, node: &'ast syn::ExprRepeat) {
+        self.visit_expr(&node.expr);
+    }
+
+    fn visit_type_array(&mut self, node: &'ast syn::TypeArray) {
+        self.visit_type(&node.elem);
+    }
+
+    fn visit_pat(&mut self, node: &'ast Pat) {
+        if !matches!(node, Pat::Lit(_) | Pat::Range(_)) {
+            visit::visit_pat(self, node);
+        }
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let previous = self.current_fn.replace(node.sig.ident.to_string());
+        visit::visit_item_fn(self, node);
+        self.current_fn = previous;
+    }
 
-    if !code_path.exists() {
-        return MutationResult::Error("mod.rs non trouvé".into());
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        if let Some((from, to)) = swapped(&node.op) {
+            if !is_str(&node.left) && !is_str(&node.right) {
+                let span = node.op.span();
+                let description = format!("{} → {} (ligne {})", from, to, Self::line(span));
+                let edit = self.map.edit(span, to.to_string());
+                self.push(MutationOperator::SwapArithmetic, description, vec![edit]);
+            }
+        }
+        visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_expr_lit(&mut self, node: &'ast syn::ExprLit) {
+        let span = node.lit.span();
+        let altered = match &node.lit {
+            Lit::Int(lit) => lit.base10_parse::<u128>().ok().map(|value| {
+                let bound = int_bound(lit.suffix(), value);
+                let delta = ((value as f64 * CONSTANT_DRIFT) as u128).max(1);
+                let mut altered = if self.rng.gen_bool(0.5) { value.saturating_add(delta).min(bound) } else { value.saturating_sub(delta) };
+                if altered == value {
+                    altered = if value > 0 { value - 1 } else { 1 };
+                }
+                (value.to_string(), format!("{}{}", altered, lit.suffix()))
+            }),
+            Lit::Float(lit) => lit.base10_parse::<f64>().ok().filter(|v| v.is_finite()).map(|value| {
+                let drift = self.rng.gen_range(CONSTANT_DRIFT / 2.0..=CONSTANT_DRIFT);
+                let factor = if self.rng.gen_bool(0.5) { 1.0 + drift } else { 1.0 - drift };
+                let altered = if value == 0.0 { CONSTANT_DRIFT } else { (value * factor * 1e6).round() / 1e6 };
+                (value.to_string(), format!("{:?}{}", altered, lit.suffix()))
+            }),
+            _ => None,
+        };
+        if let Some((from, to)) = altered {
+            let description = format!("constante {} → {} (ligne {})", from, to, Self::line(span));
+            let edit = self.map.edit(span, to);
+            self.push(MutationOperator::AlterConstant, description, vec![edit]);
+        }
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = node.func.as_ref() {
+            if let Some(name) = path.path.get_ident().map(|i| i.to_string()) {
+                let inside = self.current_fn.as_deref() == Some(name.as_str());
+                let map = self.map;
+                let site = self.inlinable.get(&name)
+                    .filter(|(shape, _)| shape.params.len() == node.args.len() && !inside)
+                    .map(|(shape, block)| {
+                        let body = map.slice(*block);
+                        // Arguments évalués avant liaison, comme lors de l'appel
+                        let text = if shape.params.is_empty() {
+                            body.to_string()
+                        } else {
+                            let pats: Vec<&str> = shape.params.iter().map(|(pat, _)| pat.as_str()).collect();
+                            let tys: Vec<&str> = shape.params.iter().map(|(_, ty)| map.slice(*ty)).collect();
+                            let args: Vec<&str> = node.args.iter().map(|arg| map.slice(arg.span())).collect();
+                            format!("{{ let ({},): ({},) = ({},); {} }}", pats.join(", "), tys.join(", "), args.join(", "), body)
+                        };
+                        (format!("inlining de {} (ligne {})", shape.name, Self::line(node.span())), map.edit(node.span(), text))
+                    });
+                if let Some((description, edit)) = site {
+                    self.push(MutationOperator::InlineFunction, description, vec![edit]);
+                }
+            }
+        }
+        visit::visit_expr_call(self, node);
+    }
+
+    fn visit_path_segment(&mut self, node: &'ast syn::PathSegment) {
+        let name = node.ident.to_string();
+        if COLLECTION_PAIRS.iter().any(|(l, r)| *l == name || *r == name) {
+            self.collections.entry(name).or_default().push(node.ident.span());
+        }
+        visit::visit_path_segment(self, node);
+    }
+
+    fn visit_use_name(&mut self, node: &'ast syn::UseName) {
+        let name = node.ident.to_string();
+        if COLLECTION_PAIRS.iter().any(|(l, r)| *l == name || *r == name) {
+            self.collections.entry(name).or_default().push(node.ident.span());
+        }
+    }
+}
+
+/// Générateur de mutations déterministe pour une graine donnée
+pub struct Mutator {
+    pub seed: u64,
+    pub operators: Vec<MutationOperator>,
+    rng: StdRng,
+}
+
+impl Mutator {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, operators: MutationOperator::ALL.to_vec(), rng: StdRng::seed_from_u64(seed) }
+    }
+
+    pub fn with_operators(mut self, operators: &[MutationOperator]) -> Self {
+        self.operators = operators.to_vec();
+        self
     }
 
-    match read_to_string(code_path) {
-        Ok(content) => {
-            let mut mutated = content.clone();
+    /// Choisit un opérateur parmi ceux qui ont un site, puis un de ses sites
+    pub fn propose(&mut self, source: &str) -> Result<Option<Mutation>, String> {
+        let file = syn::parse_file(source).map_err(|e| format!("Source non analysable: {}", e))?;
+        let map = SourceMap::new(source);
+        let mut collector = SiteCollector {
+            map: &map,
+            rng: &mut self.rng,
+            sites: Vec::new(),
+            inlinable: BTreeMap::new(),
+            collections: BTreeMap::new(),
+            current_fn: None,
+        };
+        collector.scan_items(&file.items);
+        collector.visit_file(&file);
+        collector.collection_sites();
+        let sites = collector.sites;
 
-            // Exemple : remplacer fn hello() par fn evolved_hello()
-            let re = Regex::new(r"fn\s+hello\s*\(").unwrap();
-            mutated = re.replace_all(&mutated, "fn evolved_hello(").to_string();
+        let mut available: Vec<MutationOperator> = sites.iter()
+            .map(|s| s.operator)
+            .filter(|op| self.operators.contains(op))
+            .collect();
+        available.sort();
+        available.dedup();
+        if available.is_empty() {
+            return Ok(None);
+        }
+        let operator = available[self.rng.gen_range(0..available.len())];
+        let candidates: Vec<&Site> = sites.iter().filter(|s| s.operator == operator).collect();
+        let site = candidates[self.rng.gen_range(0..candidates.len())];
+
+        let mut edits = site.edits.clone();
+        edits.sort_by_key(|e| std::cmp::Reverse(e.start));
+        let mut mutated = source.to_string();
+        for edit in edits {
+            mutated.replace_range(edit.start..edit.end, &edit.text);
+        }
+        syn::parse_file(&mutated).map_err(|e| format!("Mutation {:?} non analysable: {}", operator, e))?;
+        Ok(Some(Mutation { operator, description: site.description.clone(), source: mutated }))
+    }
+}
+
+/// `chemin/mod.rs` pour un dossier de module généré, le fichier lui-même sinon
+pub fn resolve(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.extension().map_or(false, |ext| ext == "rs") {
+        path.to_path_buf()
+    } else {
+        path.join("mod.rs")
+    }
+}
 
-            if mutated != content {
-                if let Err(e) = write(code_path, &mutated) {
-                    return MutationResult::Error(format!("Erreur d'écriture: {}", e));
+/// Vérifie que la source mutée compile: copie du crate pour un module d'`aurorae/`,
+/// porte de compilation isolée pour un module généré autonome
+pub fn verify_compiles(file: &Path, source: &str) -> Result<(), String> {
+    verify_sources(&[(file, source)])
+}
+
+/// Vérifie ensemble plusieurs fichiers modifiés: ceux du crate dans une même copie (un
+/// renommage n'y compile qu'une fois tous les fichiers à jour), les modules autonomes un par un
+pub fn verify_sources(files: &[(&Path, &str)]) -> Result<(), String> {
+    let mut in_crate: Option<(PathBuf, Vec<(PathBuf, &str)>)> = None;
+    for (file, source) in files {
+        let absolute = file.canonicalize().map_err(|e| format!("Chemin invalide {}: {}", file.display(), e))?;
+        let crate_root = absolute.ancestors()
+            .find(|dir| dir.join("Cargo.toml").exists() && dir.join("aurorae").join("lib.rs").exists())
+            .map(Path::to_path_buf);
+        match crate_root {
+            Some(root) if absolute.starts_with(root.join("aurorae")) => {
+                let relative = absolute.strip_prefix(&root).map_err(|e| e.to_string())?.to_path_buf();
+                in_crate.get_or_insert_with(|| (root, Vec::new())).1.push((relative, *source));
+            }
+            _ => {
+                let name = match absolute.file_stem().and_then(|s| s.to_str()) {
+                    Some("mod") => absolute.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()).unwrap_or("module"),
+                    Some(stem) => stem,
+                    None => "module",
+                };
+                let report = CompileGate::new(Path::new(".")).check_source(name, source)?;
+                if !report.compiled {
+                    return Err(report.diagnostics.iter().filter(|d| d.level == "error").map(|d| d.message.clone()).collect::<Vec<_>>().join("; "));
                 }
-                MutationResult::Success(Uuid::new_v4().to_string())
-            } else {
-                MutationResult::NoChanges
             }
         }
-        Err(e) => MutationResult::Error(format!("Erreur lecture: {}", e)),
+    }
+    if let Some((root, sources)) = in_crate {
+        let scratch = std::env::temp_dir().join(format!("aurorae-mutation-{}", Uuid::new_v4()));
+        let result = check_in_crate(&root, &scratch, &sources);
+        fs::remove_dir_all(&scratch).ok();
+        result?;
+    }
+    Ok(())
+}
+
+fn check_in_crate(root: &Path, scratch: &Path, sources: &[(PathBuf, &str)]) -> Result<(), String> {
+    fs::create_dir_all(scratch).map_err(|e| format!("Erreur création {}: {}", scratch.display(), e))?;
+    for entry in ["Cargo.toml", "Cargo.lock"] {
+        if root.join(entry).exists() {
+            fs::copy(root.join(entry), scratch.join(entry)).map_err(|e| format!("Erreur copie {}: {}", entry, e))?;
+        }
+    }
+    for file in WalkDir::new(root.join("aurorae")).into_iter().filter_map(Result::ok) {
+        if !file.file_type().is_file() {
+            continue;
+        }
+        let target = scratch.join(file.path().strip_prefix(root).map_err(|e| e.to_string())?);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Erreur création {}: {}", parent.display(), e))?;
+        }
+        fs::copy(file.path(), &target).map_err(|e| format!("Erreur copie {}: {}", target.display(), e))?;
+    }
+    for (relative, source) in sources {
+        fs::write(scratch.join(relative), source).map_err(|e| format!("Erreur écriture {}: {}", relative.display(), e))?;
+    }
+
+    let output = Command::new("cargo")
+        .args(["check", "--lib", "--message-format=json", "--manifest-path"])
+        .arg(scratch.join("Cargo.toml"))
+        .env("CARGO_TARGET_DIR", root.join("target").join("mutation"))
+        .output()
+        .map_err(|e| format!("Exécution de cargo impossible: {}", e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let errors: Vec<String> = parse_diagnostics(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|d| d.level == "error")
+        .map(|d| d.message)
+        .collect();
+    Err(if errors.is_empty() { "compilation échouée".to_string() } else { errors.join("; ") })
+}
+
+/// Mute un module (fichier `.rs` ou dossier de module généré) avec une graine aléatoire
+pub fn mutate_module_code(path: &str) -> MutationResult {
+    mutate_with_seed(path, rand::random())
+}
+
+/// Mutation rejouable: même graine et même source donnent la même proposition
+pub fn mutate_with_seed(path: &str, seed: u64) -> MutationResult {
+    let code_path = resolve(path);
+    let content = match read_to_string(&code_path) {
+        Ok(content) => content,
+        Err(e) => return MutationResult::Error(format!("Erreur lecture {}: {}", code_path.display(), e)),
+    };
+    let mut mutator = Mutator::new(seed);
+
+    let mut last_error = None;
+    for _ in 0..MAX_ATTEMPTS {
+        let mutation = match mutator.propose(&content) {
+            Ok(Some(mutation)) => mutation,
+            Ok(None) => return MutationResult::NoChanges,
+            Err(e) => return MutationResult::Error(e),
+        };
+        if let Err(e) = verify_compiles(&code_path, &mutation.source) {
+            info!("🧬 Mutation rejetée ({}): {}", mutation.description, e);
+            last_error = Some(e);
+            continue;
+        }
+        match commit_mutation(&PropertyHarness::default(), &code_path, &content, &mutation, seed) {
+            Ok(report) => return MutationResult::Success(report),
+            Err(e) => {
+                info!("🧬 Mutation rejetée ({}): {}", mutation.description, e);
+                last_error = Some(e);
+            }
+        }
+    }
+    MutationResult::Error(format!("Aucune mutation acceptable après {} tentative(s): {}", MAX_ATTEMPTS, last_error.unwrap_or_default()))
+}
+
+/// Exécute les propriétés générées pour le module muté; un module généré hors d'`aurorae/`
+/// n'en a pas
+pub fn check_properties(harness: &PropertyHarness, code_path: &Path, source: &str) -> Result<(), String> {
+    let relative = code_path.strip_prefix(&harness.crate_root).unwrap_or(code_path);
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let relative = relative.trim_start_matches("./");
+    if !relative.starts_with("aurorae/") {
+        return Ok(());
+    }
+    let report = harness.run(relative, source)?;
+    if !report.passed {
+        return Err(format!("Propriétés violées dans {}: {}", report.module, report.failures.join(", ")));
+    }
+    Ok(())
+}
+
+/// Écrit une mutation vérifiée, l'historise (rollback) et l'inscrit au journal d'audit avec son diff;
+/// la mutation est refusée si elle viole une propriété générée du module
+pub fn commit_mutation(harness: &PropertyHarness, code_path: &Path, original: &str, mutation: &Mutation, seed: u64) -> Result<MutationReport, String> {
+    check_properties(harness, code_path, &mutation.source)?;
+    let file = code_path.display().to_string();
+    write(code_path, &mutation.source).map_err(|e| format!("Erreur d'écriture: {}", e))?;
+
+    log_security_event(SecurityEvent::FileModified { module: "mutation".to_string(), path: file.clone() });
+    let modification_id = Uuid::new_v4();
+    record_source_change(modification_id, &file, &mutation.description, "mutation");
+    let diff = mutation.diff(original, &file);
+    audit::record(
+        AuditKind::CodeMutated,
+        "mutation",
+        &mutation.description,
+        serde_json::json!({ "modification": modification_id, "file": file, "operator": mutation.operator, "seed": seed, "diff": diff }),
+    );
+    info!("🧬 Mutation {:?} appliquée à {}: {}", mutation.operator, file, mutation.description);
+    Ok(MutationReport {
+        modification_id,
+        file,
+        operator: mutation.operator,
+        description: mutation.description.clone(),
+        seed,
+        diff,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"use std::collections::HashMap;
+
+/// Énergie restante
+pub fn remaining(total: u32, used: u32) -> u32 {
+    // commentaire conservé
+    let spent = used * 2;
+    total - spent
+}
+
+fn double(x: u64) -> u64 {
+    x + x
+}
+
+pub fn score(values: &HashMap<String, f64>) -> f64 {
+    let base = double(3) as f64;
+    values.values().sum::<f64>() / base * 0.5
+}
+"#;
+
+    fn propose(operator: MutationOperator, seed: u64) -> Mutation {
+        Mutator::new(seed).with_operators(&[operator]).propose(SOURCE).unwrap().unwrap()
+    }
+
+    #[test]
+    fn each_operator_yields_parseable_source_and_keeps_comments() {
+        for operator in MutationOperator::ALL {
+            let mutation = propose(operator, 7);
+            assert_eq!(mutation.operator, operator);
+            assert_ne!(mutation.source, SOURCE);
+            assert!(syn::parse_file(&mutation.source).is_ok());
+            assert!(mutation.source.contains("// commentaire conservé"));
+        }
+        assert!(propose(MutationOperator::ChangeCollection, 1).source.contains("&BTreeMap<String, f64>"));
+        assert!(propose(MutationOperator::ChangeCollection, 1).source.contains("use std::collections::BTreeMap;"));
+        assert!(propose(MutationOperator::InlineFunction, 1).source.contains("let (x,): (u64,) = (3,); {\n    x + x\n}"));
+        let extracted = propose(MutationOperator::ExtractFunction, 1).source;
+        assert!(extracted.contains("fn score_extracted(values: &HashMap<String, f64>) -> f64 {") || extracted.contains("remaining_extracted(total, used)"));
+    }
+
+    #[test]
+    fn same_seed_replays_same_mutation_with_diff() {
+        let first = Mutator::new(42).propose(SOURCE).unwrap().unwrap();
+        let second = Mutator::new(42).propose(SOURCE).unwrap().unwrap();
+        assert_eq!(first.source, second.source);
+        assert_eq!(first.description, second.description);
+
+        let diff = first.diff(SOURCE, "aurorae/sample.rs");
+        assert!(diff.starts_with("--- a/aurorae/sample.rs\n+++ b/aurorae/sample.rs"));
+        assert!(diff.lines().any(|l| l.starts_with('-') && !l.starts_with("---")));
+
+        let constant = propose(MutationOperator::AlterConstant, 3);
+        assert!(constant.description.starts_with("constante"));
+        assert!(Mutator::new(1).propose("pub struct Empty;").unwrap().is_none());
+    }
+
+    #[test]
+    fn property_check_covers_crate_modules_only() {
+        let harness = PropertyHarness::default();
+        // Module généré autonome: pas de propriétés, rien à exécuter
+        assert!(check_properties(&harness, Path::new("generated/module_x/mod.rs"), "pub fn f() {}").is_ok());
+        // Module du crate sans type testable: suite vide, acceptée sans compilation
+        assert!(check_properties(&harness, Path::new("./aurorae/sample.rs"), "pub fn f() {}").is_ok());
+        // Chemin hors du crate contrôlé
+        let outside = PropertyHarness { crate_root: PathBuf::from("/nonexistent"), ..PropertyHarness::default() };
+        assert!(check_properties(&outside, Path::new("/nonexistent/aurorae/sample.rs"), "pub fn f() {}").is_ok());
     }
 }
@@ -0,0 +1,498 @@
+//! AURORAE++ - mutation_sandbox.rs
+//!
+//! Bac à sable des mutations: une copie du crate est construite dans un espace temporaire
+//! isolé où s'exécutent la suite de tests, clippy et les benchmarks criterion (si `benches/`
+//! existe). La mutation est notée contre la référence non mutée: elle ne doit casser aucun
+//! test qui passait (ensembles d'échecs comparés, pas leur nombre), et n'est promue que si
+//! elle répare des tests ou retire des avertissements clippy. L'écart de performance est
+//! mesuré et rapporté, mais n'entre pas dans le score tant que le crate n'a pas de benchmarks
+//! représentatifs. Les constructions cargo tournent hors de la boucle asynchrone
+//! (`spawn_blocking`); le score alimente l'aptitude de l'EvolutionEngine.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use walkdir::WalkDir;
+use tracing::info;
+
+use crate::evolution::EvolutionEngine;
+use crate::formal_verification::PropertyHarness;
+use crate::generator::{parse_diagnostics, parse_test_results};
+use crate::knowledge_store::content_hash;
+use crate::mutation::{commit_mutation, resolve, Mutation, MutationReport, Mutator, MAX_ATTEMPTS};
+
+/// Score de la référence non mutée: une mutation doit faire mieux pour être promue
+pub const BASELINE_SCORE: f32 = 0.5;
+/// Écart de performance relatif en deçà duquel la mesure est considérée comme du bruit
+pub const PERF_NOISE: f64 = 0.02;
+/// Gain par test réparé (plafonné à 0.3)
+pub const FIXED_TEST_GAIN: f32 = 0.1;
+
+/// Mesures d'une construction de la copie du crate
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SandboxRun {
+    pub compiled: bool,
+    pub tests_passed: usize,
+    pub tests_failed: Vec<String>,
+    pub clippy_warnings: usize,
+    /// Temps par itération (ns) de chaque benchmark
+    pub benchmarks: BTreeMap<String, f64>,
+}
+
+/// Comparaison d'une mutation à la référence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutationScore {
+    pub file: String,
+    pub description: String,
+    pub baseline: SandboxRun,
+    pub candidate: SandboxRun,
+    /// Compilation réussie et aucun test en échec qui passait dans la référence
+    pub tests_pass: bool,
+    /// Tests qui passaient dans la référence et échouent avec la mutation
+    #[serde(default)]
+    pub tests_broken: Vec<String>,
+    /// Tests en échec dans la référence qui passent avec la mutation
+    #[serde(default)]
+    pub tests_fixed: Vec<String>,
+    /// Écart relatif moyen des benchmarks communs (négatif: plus rapide); informatif
+    pub perf_delta: f64,
+    /// Avertissements clippy ajoutés (négatif: retirés)
+    pub clippy_delta: i64,
+    pub score: f32,
+    pub promoted: bool,
+}
+
+impl MutationScore {
+    pub fn compare(file: &str, description: &str, baseline: SandboxRun, candidate: SandboxRun) -> Self {
+        let before: BTreeSet<&String> = baseline.tests_failed.iter().collect();
+        let after: BTreeSet<&String> = candidate.tests_failed.iter().collect();
+        let tests_broken: Vec<String> = after.difference(&before).map(|t| t.to_string()).collect();
+        let tests_fixed: Vec<String> = if candidate.compiled {
+            before.difference(&after).map(|t| t.to_string()).collect()
+        } else {
+            Vec::new()
+        };
+        let tests_pass = candidate.compiled && tests_broken.is_empty();
+        let deltas: Vec<f64> = candidate.benchmarks.iter()
+            .filter_map(|(name, ns)| baseline.benchmarks.get(name).filter(|base| **base > 0.0).map(|base| (ns - base) / base))
+            .collect();
+        let perf_delta = if deltas.is_empty() { 0.0 } else { deltas.iter().sum::<f64>() / deltas.len() as f64 };
+        let clippy_delta = candidate.clippy_warnings as i64 - baseline.clippy_warnings as i64;
+
+        let score = if tests_pass {
+            let fixed = (FIXED_TEST_GAIN * tests_fixed.len() as f32).min(0.3);
+            let lint = (-0.05 * clippy_delta as f32).clamp(-0.1, 0.1);
+            (BASELINE_SCORE + fixed + lint).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Self {
+            file: file.to_string(),
+            description: description.to_string(),
+            baseline,
+            candidate,
+            tests_pass,
+            tests_broken,
+            tests_fixed,
+            perf_delta,
+            clippy_delta,
+            score,
+            promoted: tests_pass && score > BASELINE_SCORE,
+        }
+    }
+}
+
+/// Mutation évaluée, écrite seulement si promue
+#[derive(Debug)]
+pub struct SandboxOutcome {
+    pub score: MutationScore,
+    pub report: Option<MutationReport>,
+}
+
+/// Résultat d'une tâche `mutation-sandbox` d'un worker de calcul: les mutations sont évaluées
+/// sur sa copie des sources sans rien écrire, la promue est appliquée par le coordinateur
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteMutation {
+    pub file: String,
+    pub seed: u64,
+    /// Empreinte de la source mutée par le worker
+    pub original_hash: String,
+    /// Score de la mutation promue, ou de la meilleure tentative
+    pub score: MutationScore,
+    pub promoted: Option<Mutation>,
+    /// Score et promotion de chaque tentative, pour l'aptitude de l'EvolutionEngine
+    pub attempts: Vec<(f32, bool)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MutationSandbox {
+    pub crate_root: PathBuf,
+    /// `CARGO_TARGET_DIR` partagé entre référence et mutations pour limiter les recompilations
+    pub target_dir: PathBuf,
+    pub run_clippy: bool,
+    pub run_benchmarks: bool,
+    /// Référence mise en cache tant que les sources du crate n'ont pas changé
+    baseline: Option<SandboxRun>,
+}
+
+impl MutationSandbox {
+    pub fn new(crate_root: &Path) -> Self {
+        Self {
+            crate_root: crate_root.to_path_buf(),
+            target_dir: crate_root.join("target").join("sandbox"),
+            run_clippy: true,
+            run_benchmarks: crate_root.join("benches").is_dir(),
+            baseline: None,
+        }
+    }
+
+    /// Mesure de la référence (mise en cache)
+    pub fn baseline(&mut self) -> Result<SandboxRun, String> {
+        if let Some(baseline) = &self.baseline {
+            return Ok(baseline.clone());
+        }
+        let baseline = self.run(None)?;
+        if !baseline.compiled {
+            return Err("La référence ne compile pas: aucune mutation évaluable".to_string());
+        }
+        self.baseline = Some(baseline.clone());
+        Ok(baseline)
+    }
+
+    /// Construit une copie du crate (fichier `relative` remplacé par `source`) et la mesure
+    pub fn run(&self, replacement: Option<(&Path, &str)>) -> Result<SandboxRun, String> {
+        let scratch = std::env::temp_dir().join(format!("aurorae-sandbox-{}", Uuid::new_v4()));
+        let result = self.copy_crate(&scratch)
+            .and_then(|_| match replacement {
+                Some((relative, source)) => fs::write(scratch.join(relative), source)
+                    .map_err(|e| format!("Erreur écriture {}: {}", relative.display(), e)),
+                None => Ok(()),
+            })
+            .and_then(|_| self.measure(&scratch));
+        fs::remove_dir_all(&scratch).ok();
+        result
+    }
+
+    /// Copie ce qu'il faut pour construire le crate (manifeste, script de build, sources)
+    pub fn copy_crate(&self, scratch: &Path) -> Result<(), String> {
+        fs::create_dir_all(scratch).map_err(|e| format!("Erreur création {}: {}", scratch.display(), e))?;
+        for entry in ["Cargo.toml", "Cargo.lock", "build.rs"] {
+            let from = self.crate_root.join(entry);
+            if from.exists() {
+                fs::copy(&from, scratch.join(entry)).map_err(|e| format!("Erreur copie {}: {}", entry, e))?;
+            }
+        }
+        for dir in ["aurorae", "benches", "proto"] {
+            for file in WalkDir::new(self.crate_root.join(dir)).into_iter().filter_map(Result::ok) {
+                if !file.file_type().is_file() {
+                    continue;
+                }
+                let relative = file.path().strip_prefix(&self.crate_root).map_err(|e| e.to_string())?;
+                let target = scratch.join(relative);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent).map_err(|e| format!("Erreur création {}: {}", parent.display(), e))?;
+                }
+                fs::copy(file.path(), &target).map_err(|e| format!("Erreur copie {}: {}", relative.display(), e))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn cargo(&self, scratch: &Path, args: &[&str]) -> Result<Output, String> {
+        let mut command = Command::new("cargo");
+        command.args(&args[..1])
+            .arg("--manifest-path")
+            .arg(scratch.join("Cargo.toml"))
+            .args(&args[1..])
+            .env("CARGO_TARGET_DIR", &self.target_dir);
+        command.output().map_err(|e| format!("Exécution de cargo impossible: {}", e))
+    }
+
+    fn measure(&self, scratch: &Path) -> Result<SandboxRun, String> {
+        let test = self.cargo(scratch, &["test", "--lib"])?;
+        let stdout = String::from_utf8_lossy(&test.stdout);
+        let (tests_passed, tests_failed) = parse_test_results(&stdout);
+        // Sans ligne de résultat, l'échec vient de la compilation
+        let compiled = test.status.success() || stdout.contains("test result:");
+        let mut run = SandboxRun { compiled, tests_passed, tests_failed, ..SandboxRun::default() };
+        if !compiled {
+            return Ok(run);
+        }
+
+        if self.run_clippy {
+            let clippy = self.cargo(scratch, &["clippy", "--lib", "--message-format=json"])?;
+            run.clippy_warnings = parse_diagnostics(&String::from_utf8_lossy(&clippy.stdout))
+                .iter()
+                .filter(|d| d.level == "warning")
+                .count();
+        }
+        if self.run_benchmarks {
+            let bench = self.cargo(scratch, &["bench", "--", "--output-format", "bencher"])?;
+            run.benchmarks = parse_bencher(&String::from_utf8_lossy(&bench.stdout));
+        }
+        Ok(run)
+    }
+
+    /// Évalue `source` à la place de `file` contre la référence
+    #[tracing::instrument(name = "mutation", skip(self, source), fields(file = %file.display()))]
+    pub fn evaluate(&mut self, file: &Path, description: &str, source: &str) -> Result<MutationScore, String> {
+        let root = self.crate_root.canonicalize().map_err(|e| format!("Racine invalide: {}", e))?;
+        let absolute = file.canonicalize().map_err(|e| format!("Chemin invalide {}: {}", file.display(), e))?;
+        let relative = absolute.strip_prefix(&root)
+            .map_err(|_| format!("{} n'appartient pas au crate", file.display()))?
+            .to_path_buf();
+
+        let baseline = self.baseline()?;
+        let candidate = self.run(Some((&relative, source)))?;
+        let score = MutationScore::compare(&relative.display().to_string(), description, baseline, candidate);
+        info!("🧪 Bac à sable {}: score {:.2} (tests {}, {} réparé(s), {} cassé(s), perf {:+.1}%, clippy {:+}) → {}",
+                 score.file, score.score, if score.tests_pass { "✓" } else { "✗" }, score.tests_fixed.len(), score.tests_broken.len(),
+                 score.perf_delta * 100.0, score.clippy_delta, if score.promoted { "promue" } else { "écartée" });
+        Ok(score)
+    }
+
+    /// Mute `path`, évalue chaque proposition en bac à sable et n'écrit que celle qui bat la
+    /// référence; chaque score est reporté sur l'aptitude de l'EvolutionEngine
+    pub fn evolve(&mut self, path: &str, seed: u64, engine: &mut EvolutionEngine) -> Result<SandboxOutcome, String> {
+        let mut scores = Vec::new();
+        let outcome = self.evolve_scored(path, seed, &mut scores);
+        for (score, promoted) in scores {
+            engine.record_mutation_fitness(score, promoted);
+        }
+        outcome
+    }
+
+    /// `evolve` sur un fil bloquant: cargo (tests, clippy, benchmarks) ne monopolise pas la
+    /// boucle asynchrone; la référence mise en cache est reprise au retour
+    pub async fn evolve_blocking(&mut self, path: &str, seed: u64, engine: &mut EvolutionEngine) -> Result<SandboxOutcome, String> {
+        let mut sandbox = self.clone();
+        let path = path.to_string();
+        let (sandbox, outcome, scores) = tokio::task::spawn_blocking(move || {
+            let mut scores = Vec::new();
+            let outcome = sandbox.evolve_scored(&path, seed, &mut scores);
+            (sandbox, outcome, scores)
+        })
+        .await
+        .map_err(|e| format!("Bac à sable interrompu: {}", e))?;
+        *self = sandbox;
+        for (score, promoted) in scores {
+            engine.record_mutation_fitness(score, promoted);
+        }
+        outcome
+    }
+
+    #[tracing::instrument(name = "mutation_cycle", skip(self, scores))]
+    fn evolve_scored(&mut self, path: &str, seed: u64, scores: &mut Vec<(f32, bool)>) -> Result<SandboxOutcome, String> {
+        let code_path = resolve(path);
+        let original = fs::read_to_string(&code_path).map_err(|e| format!("Erreur lecture {}: {}", code_path.display(), e))?;
+        let mut mutator = Mutator::new(seed);
+
+        let mut best: Option<MutationScore> = None;
+        for _ in 0..MAX_ATTEMPTS {
+            let Some(mutation) = mutator.propose(&original)? else { break };
+            let score = self.evaluate(&code_path, &mutation.description, &mutation.source)?;
+            scores.push((score.score, score.promoted));
+            if score.promoted {
+                // Les propriétés générées du module ont le dernier mot avant l'écriture
+                let harness = PropertyHarness { crate_root: self.crate_root.clone(), ..PropertyHarness::default() };
+                match commit_mutation(&harness, &code_path, &original, &mutation, seed) {
+                    Ok(report) => {
+                        // Les sources ont changé: la mutation promue devient la nouvelle référence
+                        self.baseline = Some(score.candidate.clone());
+                        return Ok(SandboxOutcome { score, report: Some(report) });
+                    }
+                    Err(e) => {
+                        info!("🧪 Mutation promue puis rejetée ({}): {}", mutation.description, e);
+                        if let Some(last) = scores.last_mut() {
+                            last.1 = false;
+                        }
+                        continue;
+                    }
+                }
+            }
+            if best.as_ref().map_or(true, |b| score.score > b.score) {
+                best = Some(score);
+            }
+        }
+        best.map(|score| SandboxOutcome { score, report: None })
+            .ok_or_else(|| format!("Aucune mutation applicable à {}", code_path.display()))
+    }
+
+    /// Évalue les mutations de `path` sans rien écrire (exécuteur des workers de calcul)
+    pub fn propose(&mut self, path: &str, seed: u64) -> Result<RemoteMutation, String> {
+        let code_path = resolve(path);
+        let original = fs::read_to_string(&code_path).map_err(|e| format!("Erreur lecture {}: {}", code_path.display(), e))?;
+        let mut mutator = Mutator::new(seed);
+        let mut attempts = Vec::new();
+
+        let mut best: Option<MutationScore> = None;
+        let mut promoted = None;
+        for _ in 0..MAX_ATTEMPTS {
+            let Some(mutation) = mutator.propose(&original)? else { break };
+            let score = self.evaluate(&code_path, &mutation.description, &mutation.source)?;
+            attempts.push((score.score, score.promoted));
+            if score.promoted {
+                best = Some(score);
+                promoted = Some(mutation);
+                break;
+            }
+            if best.as_ref().map_or(true, |b| score.score > b.score) {
+                best = Some(score);
+            }
+        }
+        let score = best.ok_or_else(|| format!("Aucune mutation applicable à {}", code_path.display()))?;
+        Ok(RemoteMutation { file: path.to_string(), seed, original_hash: content_hash(original.as_bytes()), score, promoted, attempts })
+    }
+
+    /// Applique la mutation promue par un worker: la source locale doit être celle qu'il a
+    /// mutée, et les propriétés générées du module ont le dernier mot avant l'écriture
+    pub fn apply_remote(&mut self, remote: &RemoteMutation, engine: &mut EvolutionEngine) -> Result<Option<MutationReport>, String> {
+        let mut attempts = remote.attempts.clone();
+        let outcome = match &remote.promoted {
+            None => Ok(None),
+            Some(mutation) => {
+                let code_path = resolve(&remote.file);
+                let original = fs::read_to_string(&code_path).map_err(|e| format!("Erreur lecture {}: {}", code_path.display(), e))?;
+                if content_hash(original.as_bytes()) != remote.original_hash {
+                    Err(format!("{} a changé depuis l'évaluation distante: mutation écartée", code_path.display()))
+                } else {
+                    let harness = PropertyHarness { crate_root: self.crate_root.clone(), ..PropertyHarness::default() };
+                    commit_mutation(&harness, &code_path, &original, mutation, remote.seed).map(Some)
+                }
+            }
+        };
+        match &outcome {
+            Ok(Some(_)) => self.invalidate(),
+            Ok(None) => {}
+            Err(_) => {
+                if let Some(last) = attempts.last_mut() {
+                    last.1 = false;
+                }
+            }
+        }
+        for (score, promoted) in attempts {
+            engine.record_mutation_fitness(score, promoted);
+        }
+        outcome
+    }
+
+    /// Oublie la référence (sources modifiées hors bac à sable)
+    pub fn invalidate(&mut self) {
+        self.baseline = None;
+    }
+}
+
+/// Résultats `--output-format bencher` de criterion: `test nom ... bench: 1,234 ns/iter (+/- 56)`
+pub fn parse_bencher(stdout: &str) -> BTreeMap<String, f64> {
+    let line = Regex::new(r"(?m)^test (\S+) \.\.\. bench:\s+([\d,\.]+) ns/iter").unwrap();
+    line.captures_iter(stdout)
+        .filter_map(|c| c[2].replace(',', "").parse().ok().map(|ns| (c[1].to_string(), ns)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(failed: usize, clippy: usize, bench: f64) -> SandboxRun {
+        SandboxRun {
+            compiled: true,
+            tests_passed: 10,
+            tests_failed: (0..failed).map(|i| format!("t{}", i)).collect(),
+            clippy_warnings: clippy,
+            benchmarks: BTreeMap::from([("cycle".to_string(), bench)]),
+        }
+    }
+
+    #[test]
+    fn mutations_must_beat_the_baseline() {
+        let neutral = MutationScore::compare("aurorae/economy.rs", "+ → -", run(0, 3, 1000.0), run(0, 3, 1005.0));
+        assert!(neutral.tests_pass && !neutral.promoted);
+        assert_eq!(neutral.score, BASELINE_SCORE);
+
+        // Plus rapide mais un avertissement de plus: la performance n'entre pas dans le score
+        let faster = MutationScore::compare("aurorae/economy.rs", "constante", run(0, 3, 1000.0), run(0, 4, 800.0));
+        assert!((faster.perf_delta + 0.2).abs() < 1e-9);
+        assert_eq!(faster.clippy_delta, 1);
+        assert!(!faster.promoted && (faster.score - 0.45).abs() < 1e-6);
+
+        let cleaner = MutationScore::compare("aurorae/economy.rs", "constante", run(0, 3, 1000.0), run(0, 1, 1000.0));
+        assert!(cleaner.promoted && (cleaner.score - 0.6).abs() < 1e-6);
+
+        let regressed = MutationScore::compare("aurorae/economy.rs", "inlining", run(1, 3, 1000.0), run(2, 0, 500.0));
+        assert!(!regressed.tests_pass && !regressed.promoted && regressed.score == 0.0);
+
+        let broken = MutationScore::compare("aurorae/economy.rs", "extraction", run(0, 3, 1000.0), SandboxRun::default());
+        assert!(!broken.promoted && broken.tests_fixed.is_empty());
+    }
+
+    #[test]
+    fn failing_test_sets_are_compared_not_counted() {
+        let failing = |names: &[&str]| SandboxRun {
+            compiled: true,
+            tests_passed: 10,
+            tests_failed: names.iter().map(|n| n.to_string()).collect(),
+            ..SandboxRun::default()
+        };
+        // Une réparée, une cassée: même nombre d'échecs, mais la mutation est écartée
+        let swapped = MutationScore::compare("aurorae/economy.rs", "swap", failing(&["ledger"]), failing(&["payout"]));
+        assert!(!swapped.tests_pass && !swapped.promoted);
+        assert_eq!(swapped.tests_broken, ["payout"]);
+        assert_eq!(swapped.tests_fixed, ["ledger"]);
+
+        let repaired = MutationScore::compare("aurorae/economy.rs", "fix", failing(&["ledger", "payout"]), failing(&["payout"]));
+        assert!(repaired.tests_pass && repaired.promoted);
+        assert!((repaired.score - (BASELINE_SCORE + FIXED_TEST_GAIN)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bencher_output_is_parsed() {
+        let stdout = "test economy/spend ... bench:       1,234 ns/iter (+/- 56)\ntest brain/cycle ... bench:  88 ns/iter (+/- 2)\nrunning 0 tests\n";
+        let benchmarks = parse_bencher(stdout);
+        assert_eq!(benchmarks.get("economy/spend"), Some(&1234.0));
+        assert_eq!(benchmarks.get("brain/cycle"), Some(&88.0));
+
+        let mut engine = EvolutionEngine::new();
+        engine.record_mutation_fitness(1.0, true);
+        assert!((engine.mutation_fitness - 0.6).abs() < 1e-6);
+        assert_eq!(engine.promoted_mutations, 1);
+    }
+
+    #[test]
+    fn remote_mutations_are_applied_only_to_the_source_the_worker_mutated() {
+        let dir = std::env::temp_dir().join(format!("aurorae-remote-mutation-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("economy.rs");
+        fs::write(&file, "fn rate() -> u32 { 2 }\n").unwrap();
+
+        let candidate = SandboxRun { compiled: true, tests_passed: 10, ..SandboxRun::default() };
+        let remote = RemoteMutation {
+            file: file.display().to_string(),
+            seed: 7,
+            original_hash: content_hash(b"fn rate() -> u32 { 1 }\n"),
+            score: MutationScore::compare("aurorae/economy.rs", "constante", run(1, 3, 1000.0), candidate),
+            promoted: Some(Mutation {
+                operator: crate::mutation::MutationOperator::AlterConstant,
+                description: "1 → 3".to_string(),
+                source: "fn rate() -> u32 { 3 }\n".to_string(),
+            }),
+            attempts: vec![(0.2, false), (0.6, true)],
+        };
+        let mut sandbox = MutationSandbox::new(&dir);
+        let mut engine = EvolutionEngine::new();
+        // La source locale a changé depuis l'évaluation: rien n'est écrit, la promotion est annulée
+        assert!(sandbox.apply_remote(&remote, &mut engine).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "fn rate() -> u32 { 2 }\n");
+        assert_eq!(engine.promoted_mutations, 0);
+
+        let rejected = RemoteMutation { promoted: None, attempts: vec![(0.4, false)], ..remote };
+        assert!(sandbox.apply_remote(&rejected, &mut engine).unwrap().is_none());
+        assert_eq!(engine.promoted_mutations, 0);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -2,12 +2,69 @@
 //!
 //! Ce module permet à l'IA de créer, structurer et relier des blockchains spécialisées vivantes.
 //! Chaque sous-réseau peut avoir son propre rôle, VM, protocole, et topologie.
+//!
+//! Une sous-chaîne est décrite par sa spécification (moteur de consensus, jeton, validateurs)
+//! et s'exécute sur le devnet en mémoire de `devnet` tant qu'elle est démarrée. La topologie
+//! (sous-chaînes, état souhaité, liens) est persistée dans `aurorae_state/network_topology.json`:
+//! au redémarrage, les sous-chaînes qui tournaient sont relancées. Un lien entre deux
+//! sous-chaînes est déclaré comme pont auprès du routeur de `cross_chain`.
 
 use uuid::Uuid;
 use chrono::Utc;
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::chain_registry::{ChainConfig, ChainKind, NativeCurrency};
+use crate::cross_chain::{BridgeDescriptor, CrossChainRouter};
+use crate::devnet::{Devnet, DevnetConfig};
+use crate::validator::ValidationConfig;
 
-#[derive(Debug, Clone)]
+pub const NETWORK_TOPOLOGY_PATH: &str = "aurorae_state/network_topology.json";
+
+/// Jeton natif d'une sous-chaîne
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenSpec {
+    pub symbol: String,
+    pub decimals: u8,
+    /// Offre de genèse, répartie entre les comptes du devnet
+    pub initial_supply: f64,
+}
+
+/// Spécification d'une sous-chaîne
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubChainSpec {
+    /// Moteur de consensus de `consensus` (`poa` ou `pos`)
+    pub engine: String,
+    pub validators: usize,
+    pub token: TokenSpec,
+    pub accounts: usize,
+    pub block_time_secs: i64,
+}
+
+impl Default for SubChainSpec {
+    fn default() -> Self {
+        Self {
+            engine: "poa".to_string(),
+            validators: 4,
+            token: TokenSpec { symbol: "AURA".to_string(), decimals: 18, initial_supply: 8_000.0 },
+            accounts: 8,
+            block_time_secs: 2,
+        }
+    }
+}
+
+/// État souhaité d'une sous-chaîne, persisté avec la topologie
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubChainStatus {
+    Stopped,
+    Running,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubChain {
     pub id: Uuid,
     pub name: String,
@@ -15,19 +72,111 @@ pub struct SubChain {
     pub protocol: String,
     pub created_at: String,
     pub links: Vec<Uuid>,
+    pub spec: SubChainSpec,
+    pub status: SubChainStatus,
+    /// Hauteur atteinte lors du dernier arrêt ou de la dernière sauvegarde
+    pub height: u64,
+}
+
+impl SubChain {
+    /// Identifiant de chaîne EVM dérivé de l'identifiant de la sous-chaîne
+    pub fn chain_id(&self) -> u64 {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.id.as_bytes()[..8]);
+        4_200_000 + u64::from_be_bytes(bytes) % 100_000
+    }
+
+    /// Entrée du registre des chaînes, pour le routage inter-chaînes
+    pub fn chain_config(&self) -> ChainConfig {
+        ChainConfig {
+            name: self.name.clone(),
+            kind: ChainKind::Evm,
+            rpc: format!("http://127.0.0.1/devnet/{}", self.id),
+            ws: None,
+            chain_id: Some(self.chain_id()),
+            explorer: None,
+            explorer_api: None,
+            native_currency: NativeCurrency { symbol: self.spec.token.symbol.clone(), decimals: self.spec.token.decimals },
+            testnet: true,
+        }
+    }
+
+    fn devnet_config(&self) -> DevnetConfig {
+        let mut seed = [0u8; 8];
+        seed.copy_from_slice(&self.id.as_bytes()[8..]);
+        let accounts = self.spec.accounts.max(1);
+        DevnetConfig {
+            engine: self.spec.engine.clone(),
+            validators: self.spec.validators,
+            accounts,
+            initial_balance: self.spec.token.initial_supply / accounts as f64,
+            block_time_secs: self.spec.block_time_secs,
+            seed: u64::from_be_bytes(seed),
+            ..DevnetConfig::default()
+        }
+    }
+}
+
+/// Lien entre deux sous-chaînes, et le pont déclaré auprès de `cross_chain`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BridgeEdge {
+    pub a: Uuid,
+    pub b: Uuid,
+    pub bridges: Vec<Uuid>,
 }
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct NetworkMap {
     pub chains: Vec<SubChain>,
+    pub edges: Vec<BridgeEdge>,
+    #[serde(skip)]
+    running: HashMap<Uuid, Devnet>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
 }
 
 impl NetworkMap {
     pub fn new() -> Self {
-        Self { chains: vec![] }
+        Self::default()
+    }
+
+    /// Topologie persistée à `path`; les sous-chaînes marquées démarrées sont relancées
+    pub fn open(path: &Path) -> Self {
+        let mut map: Self = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        map.path = Some(path.to_path_buf());
+        let running: Vec<Uuid> = map.chains.iter().filter(|chain| chain.status == SubChainStatus::Running).map(|chain| chain.id).collect();
+        for id in running {
+            if let Err(e) = map.start(id) {
+                warn!("⚠️ Sous-chaîne {} non relancée: {}", id, e);
+            }
+        }
+        map
+    }
+
+    pub fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(self) {
+            Ok(content) => {
+                if let Err(e) = fs::write(path, content) {
+                    warn!("⚠️ Topologie non sauvegardée: {}", e);
+                }
+            }
+            Err(e) => warn!("⚠️ Topologie non sérialisable: {}", e),
+        }
     }
 
     pub fn create_subchain(&mut self, name: &str, purpose: &str, protocol: &str) -> Uuid {
+        self.create_subchain_with(name, purpose, protocol, SubChainSpec::default())
+    }
+
+    /// Sous-chaîne arrêtée de spécification `spec`
+    pub fn create_subchain_with(&mut self, name: &str, purpose: &str, protocol: &str, spec: SubChainSpec) -> Uuid {
         let id = Uuid::new_v4();
         let chain = SubChain {
             id,
@@ -36,13 +185,63 @@ impl NetworkMap {
             protocol: protocol.to_string(),
             created_at: Utc::now().to_rfc3339(),
             links: vec![],
+            spec,
+            status: SubChainStatus::Stopped,
+            height: 0,
         };
 
-        println!("[AURORAE++] 🧬 Création d'une sous-chaîne : {} • Protocole: {}", name, protocol);
+        info!("🧬 Création d'une sous-chaîne : {} • Protocole: {}", name, protocol);
         self.chains.push(chain);
+        self.save();
         id
     }
 
+    pub fn get(&self, id: Uuid) -> Option<&SubChain> {
+        self.chains.iter().find(|chain| chain.id == id)
+    }
+
+    /// Devnet d'une sous-chaîne démarrée
+    pub fn devnet(&self, id: Uuid) -> Option<&Devnet> {
+        self.running.get(&id)
+    }
+
+    /// Démarre la sous-chaîne sur un devnet simulé (repart de la genèse, la graine étant
+    /// dérivée de son identifiant)
+    pub fn start(&mut self, id: Uuid) -> Result<(), String> {
+        if self.running.contains_key(&id) {
+            return Ok(());
+        }
+        let chain = self.chains.iter_mut().find(|chain| chain.id == id).ok_or_else(|| format!("Sous-chaîne inconnue: {}", id))?;
+        let devnet = Devnet::new(chain.devnet_config(), &ValidationConfig::default())?;
+        chain.status = SubChainStatus::Running;
+        chain.height = 0;
+        info!("▶️ Sous-chaîne {} démarrée ({}, {} validateurs)", chain.name, chain.spec.engine, chain.spec.validators);
+        self.running.insert(id, devnet);
+        self.save();
+        Ok(())
+    }
+
+    pub fn stop(&mut self, id: Uuid) -> Result<(), String> {
+        let chain = self.chains.iter_mut().find(|chain| chain.id == id).ok_or_else(|| format!("Sous-chaîne inconnue: {}", id))?;
+        if let Some(devnet) = self.running.remove(&id) {
+            chain.height = devnet.head().block.number;
+            info!("⏹️ Sous-chaîne {} arrêtée au bloc {}", chain.name, chain.height);
+        }
+        chain.status = SubChainStatus::Stopped;
+        self.save();
+        Ok(())
+    }
+
+    /// Produit `blocks` blocs sur chaque sous-chaîne démarrée
+    pub fn tick(&mut self, blocks: usize) {
+        for chain in &mut self.chains {
+            if let Some(devnet) = self.running.get_mut(&chain.id) {
+                devnet.produce_blocks(blocks);
+                chain.height = devnet.head().block.number;
+            }
+        }
+    }
+
     pub fn link_chains(&mut self, a: Uuid, b: Uuid) {
         for chain in &mut self.chains {
             if chain.id == a && !chain.links.contains(&b) {
@@ -51,13 +250,100 @@ impl NetworkMap {
                 chain.links.push(a);
             }
         }
-        println!("[AURORAE++] 🔗 Chaînes {} <--> {} interconnectées.", a, b);
+        let known = self.edges.iter().any(|edge| (edge.a, edge.b) == (a, b) || (edge.a, edge.b) == (b, a));
+        if !known {
+            self.edges.push(BridgeEdge { a, b, bridges: Vec::new() });
+        }
+        self.save();
+        info!("🔗 Chaînes {} <--> {} interconnectées.", a, b);
+    }
+
+    /// Déclare les deux sens de chaque lien encore sans pont auprès du routeur inter-chaînes,
+    /// après avoir inscrit les sous-chaînes au registre
+    pub fn register_bridges(&mut self, router: &mut CrossChainRouter) -> Vec<BridgeDescriptor> {
+        let mut declared = Vec::new();
+        for index in 0..self.edges.len() {
+            if !self.edges[index].bridges.is_empty() {
+                continue;
+            }
+            let (a, b) = (self.edges[index].a, self.edges[index].b);
+            let (Some(from), Some(to)) = (self.get(a).cloned(), self.get(b).cloned()) else { continue };
+            let mut open = || -> Result<(BridgeDescriptor, BridgeDescriptor), String> {
+                router.register_chain(from.chain_config())?;
+                router.register_chain(to.chain_config())?;
+                Ok((router.create_bridge(&from.name, &to.name)?, router.create_bridge(&to.name, &from.name)?))
+            };
+            let opened = open();
+            match opened {
+                Ok((there, back)) => {
+                    self.edges[index].bridges = vec![there.id, back.id];
+                    declared.extend([there, back]);
+                }
+                Err(e) => warn!("⚠️ Pont {} <--> {} non déclaré: {}", from.name, to.name, e),
+            }
+        }
+        if !declared.is_empty() {
+            self.save();
+        }
+        declared
     }
 
     pub fn map_summary(&self) {
-        println!("[AURORAE++] 🌐 TOPOLOGIE ACTUELLE DU RÉSEAU:");
+        info!("🌐 TOPOLOGIE ACTUELLE DU RÉSEAU:");
         for chain in &self.chains {
-            println!("→ {} • [{}] • Links: {}", chain.name, chain.protocol, chain.links.len());
+            let status = match chain.status {
+                SubChainStatus::Running => "▶️",
+                SubChainStatus::Stopped => "⏹️",
+            };
+            println!("→ {} {} • [{}] • Bloc {} • Links: {}", status, chain.name, chain.protocol, chain.height, chain.links.len());
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_registry::ChainRegistry;
+
+    #[test]
+    fn topology_persists_and_running_subchains_restart() {
+        let path = std::env::temp_dir().join(format!("aurorae-topology-{}.json", Uuid::new_v4()));
+        let (oracle, ledger) = {
+            let mut map = NetworkMap::open(&path);
+            let oracle = map.create_subchain("oracle-net", "Flux de prix", "poa");
+            let spec = SubChainSpec { engine: "pos".into(), validators: 3, ..SubChainSpec::default() };
+            let ledger = map.create_subchain_with("ledger-net", "Règlements", "pos", spec);
+            map.link_chains(oracle, ledger);
+            map.start(oracle).unwrap();
+            map.tick(5);
+            assert_eq!(map.get(oracle).unwrap().height, 5);
+            assert!(map.devnet(ledger).is_none());
+            (oracle, ledger)
+        };
+
+        let mut reopened = NetworkMap::open(&path);
+        assert_eq!(reopened.chains.len(), 2);
+        assert_eq!(reopened.get(ledger).unwrap().spec.validators, 3);
+        assert_eq!(reopened.edges, vec![BridgeEdge { a: oracle, b: ledger, bridges: Vec::new() }]);
+        assert!(reopened.devnet(oracle).is_some());
+
+        reopened.stop(oracle).unwrap();
+        assert_eq!(NetworkMap::open(&path).get(oracle).unwrap().status, SubChainStatus::Stopped);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn links_are_registered_as_bridges_once() {
+        let mut map = NetworkMap::new();
+        let a = map.create_subchain("alpha-net", "Tests", "poa");
+        let b = map.create_subchain("beta-net", "Tests", "poa");
+        map.link_chains(a, b);
+
+        let mut router = CrossChainRouter::new(ChainRegistry::new().shared());
+        let bridges = map.register_bridges(&mut router);
+        assert_eq!(bridges.len(), 2);
+        assert_eq!(router.destinations("alpha-net"), vec!["beta-net".to_string()]);
+        assert!(map.register_bridges(&mut router).is_empty());
+        assert_eq!(router.bridges.len(), 2);
+    }
+}
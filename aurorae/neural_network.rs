@@ -1,7 +1,10 @@
 extern crate tch;
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
 use tch::{nn, Device, Tensor};
 use tch::nn::Module;  // Importer le trait Module
 use tch::nn::OptimizerConfig;  // Importer le trait OptimizerConfig
+use crate::neuromorphic::ActionSelector;
 
 pub struct DecisionNet {
     pub net: nn::Sequential,
@@ -48,7 +51,78 @@ impl DecisionNet {
     }
 }
 
+// Sélecteur d'actions comparable au réseau impulsionnel: sortie de plus forte activation
+impl ActionSelector for DecisionNet {
+    fn name(&self) -> &str {
+        "decision-net"
+    }
+
+    fn select(&mut self, features: &[f32]) -> usize {
+        let input = Tensor::of_slice(features).unsqueeze(0);
+        let output = tch::no_grad(|| self.forward(input));
+        output.argmax(-1, false).int64_value(&[0]) as usize
+    }
+
+    // Entraîné par lots dans la boucle principale
+    fn feedback(&mut self, _action: usize, _reward: f32) {}
+}
+
 // Créer un optimiseur Adam
 pub fn create_optimizer(vs: &nn::VarStore) -> nn::Optimizer<nn::Adam> {
     nn::Adam::default().build(vs, 1e-3).unwrap()  // Créer l'optimiseur Adam avec un taux d'apprentissage de 1e-3
 }
+
+// Poids d'un réseau à plat (forme, valeurs) par nom de variable, échangés avec les workers de calcul
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetWeights {
+    pub tensors: BTreeMap<String, (Vec<i64>, Vec<f32>)>,
+}
+
+impl NetWeights {
+    // Moyenne des poids de plusieurs réplicas de même architecture (fragments d'entraînement)
+    pub fn average(parts: &[NetWeights]) -> Result<NetWeights, String> {
+        let first = parts.first().ok_or("Aucun poids à moyenner")?;
+        let mut tensors = BTreeMap::new();
+        for (name, (shape, values)) in &first.tensors {
+            let mut sum = vec![0.0f32; values.len()];
+            for part in parts {
+                match part.tensors.get(name) {
+                    Some((other_shape, other)) if other_shape == shape && other.len() == sum.len() => {
+                        sum.iter_mut().zip(other).for_each(|(total, value)| *total += value);
+                    }
+                    _ => return Err(format!("Poids {} absents ou de forme différente", name)),
+                }
+            }
+            let count = parts.len() as f32;
+            tensors.insert(name.clone(), (shape.clone(), sum.into_iter().map(|total| total / count).collect()));
+        }
+        Ok(NetWeights { tensors })
+    }
+}
+
+// Copie les variables d'un réseau
+pub fn export_weights(vs: &nn::VarStore) -> Result<NetWeights, String> {
+    let mut tensors = BTreeMap::new();
+    for (name, tensor) in vs.variables() {
+        let values = Vec::<f32>::try_from(tensor.flatten(0, -1)).map_err(|e| format!("Poids {} illisibles: {}", name, e))?;
+        tensors.insert(name, (tensor.size(), values));
+    }
+    Ok(NetWeights { tensors })
+}
+
+// Remplace les variables d'un réseau de même architecture
+pub fn import_weights(vs: &nn::VarStore, weights: &NetWeights) -> Result<(), String> {
+    let mut variables = vs.variables();
+    if variables.len() != weights.tensors.len() {
+        return Err(format!("Architecture différente: {} variables reçues, {} attendues", weights.tensors.len(), variables.len()));
+    }
+    for (name, (shape, values)) in &weights.tensors {
+        let variable = variables.get_mut(name).ok_or_else(|| format!("Variable {} inconnue", name))?;
+        if variable.size() != *shape {
+            return Err(format!("Forme de {} différente: {:?} reçue, {:?} attendue", name, shape, variable.size()));
+        }
+        let source = Tensor::of_slice(values.as_slice()).reshape(shape.as_slice());
+        tch::no_grad(|| variable.copy_(&source));
+    }
+    Ok(())
+}
@@ -0,0 +1,506 @@
+//! AURORAE++ - neuromorphic.rs
+//!
+//! Simulateur de réseau de neurones impulsionnels à intégration et fuite (LIF): topologie en
+//! couches configurable (connexions directes et récurrentes), plasticité dépendante du temps
+//! d'occurrence des impulsions (STDP), éventuellement modulée par la récompense. L'adaptateur
+//! `SpikingPolicy` en fait un sélecteur d'actions interchangeable avec `DecisionNet`, pour
+//! comparer les deux backends sur les décisions de l'agent de renforcement.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::reinforcement_learning::LearningAgent;
+
+pub const DEFAULT_NEUROMORPHIC_CONFIG_PATH: &str = "neuromorphic.toml";
+/// Dimension des caractéristiques d'état partagées par les sélecteurs d'actions
+pub const STATE_FEATURES: usize = 16;
+
+/// Dynamique d'un neurone à intégration et fuite
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct LifParams {
+    /// Constante de temps de la membrane (ms)
+    pub tau_m: f32,
+    pub v_rest: f32,
+    pub v_reset: f32,
+    pub v_threshold: f32,
+    /// Période réfractaire après une impulsion (ms)
+    pub refractory: f32,
+    /// Pas de simulation (ms)
+    pub dt: f32,
+    /// Courant injecté par une impulsion d'entrée
+    pub input_gain: f32,
+}
+
+impl Default for LifParams {
+    fn default() -> Self {
+        Self { tau_m: 20.0, v_rest: 0.0, v_reset: 0.0, v_threshold: 1.0, refractory: 2.0, dt: 1.0, input_gain: 1.2 }
+    }
+}
+
+/// Plasticité STDP par paires, à traces exponentielles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct StdpParams {
+    pub enabled: bool,
+    /// Potentiation quand l'impulsion présynaptique précède la postsynaptique
+    pub a_plus: f32,
+    /// Dépression dans l'ordre inverse
+    pub a_minus: f32,
+    pub tau_plus: f32,
+    pub tau_minus: f32,
+    pub w_max: f32,
+    /// Les changements s'accumulent en traces d'éligibilité, appliquées selon la récompense
+    pub reward_modulated: bool,
+}
+
+impl Default for StdpParams {
+    fn default() -> Self {
+        Self { enabled: true, a_plus: 0.01, a_minus: 0.012, tau_plus: 20.0, tau_minus: 20.0, w_max: 1.0, reward_modulated: true }
+    }
+}
+
+/// Fichier `neuromorphic.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct NeuromorphicConfig {
+    /// Taille de chaque couche, de l'entrée à la sortie
+    pub layers: Vec<usize>,
+    /// Probabilité de connexion entre deux neurones de couches consécutives
+    pub connection_prob: f32,
+    /// Probabilité de connexion récurrente au sein d'une couche cachée
+    pub recurrent_prob: f32,
+    /// Poids initial moyen
+    pub initial_weight: f32,
+    /// Pas simulés par décision
+    pub steps: usize,
+    pub seed: u64,
+    pub lif: LifParams,
+    pub stdp: StdpParams,
+}
+
+impl Default for NeuromorphicConfig {
+    fn default() -> Self {
+        Self {
+            layers: vec![STATE_FEATURES, 32, 8],
+            connection_prob: 0.5,
+            recurrent_prob: 0.1,
+            initial_weight: 0.3,
+            steps: 50,
+            seed: 42,
+            lif: LifParams::default(),
+            stdp: StdpParams::default(),
+        }
+    }
+}
+
+impl NeuromorphicConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| format!("Configuration neuromorphique invalide: {}", e))?;
+        if config.layers.len() < 2 || config.layers.contains(&0) {
+            return Err("Configuration neuromorphique invalide: au moins deux couches non vides".to_string());
+        }
+        Ok(config)
+    }
+
+    /// `neuromorphic.toml`, ou la configuration par défaut s'il est absent ou invalide
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_NEUROMORPHIC_CONFIG_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: configuration neuromorphique par défaut", e);
+            Self::default()
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct LifNeuron {
+    v: f32,
+    /// Temps réfractaire restant (ms)
+    refractory: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Synapse {
+    pub pre: usize,
+    pub post: usize,
+    pub weight: f32,
+    /// Changements STDP en attente de récompense
+    pub eligibility: f32,
+}
+
+/// Réseau impulsionnel simulé à pas fixe
+pub struct SpikingNetwork {
+    config: NeuromorphicConfig,
+    neurons: Vec<LifNeuron>,
+    synapses: Vec<Synapse>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+    /// Courant synaptique délivré au pas suivant
+    pending: Vec<f32>,
+    pre_trace: Vec<f32>,
+    post_trace: Vec<f32>,
+    layers: Vec<Range<usize>>,
+    rng: StdRng,
+}
+
+impl SpikingNetwork {
+    pub fn new(config: NeuromorphicConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let mut layers = Vec::new();
+        let mut start = 0;
+        for size in &config.layers {
+            layers.push(start..start + size);
+            start += size;
+        }
+        let total = start;
+
+        let mut synapses = Vec::new();
+        let mut connect = |pre: usize, post: usize, probability: f32, rng: &mut StdRng| {
+            if pre != post && rng.gen::<f32>() < probability {
+                let weight = rng.gen_range(0.0..=2.0 * config.initial_weight).min(config.stdp.w_max);
+                synapses.push(Synapse { pre, post, weight, eligibility: 0.0 });
+            }
+        };
+        for (index, layer) in layers.iter().enumerate() {
+            if let Some(next) = layers.get(index + 1) {
+                for pre in layer.clone() {
+                    for post in next.clone() {
+                        connect(pre, post, config.connection_prob, &mut rng);
+                    }
+                }
+            }
+            // Récurrence dans les seules couches cachées
+            if index > 0 && index + 1 < layers.len() {
+                for pre in layer.clone() {
+                    for post in layer.clone() {
+                        connect(pre, post, config.recurrent_prob, &mut rng);
+                    }
+                }
+            }
+        }
+
+        let mut outgoing = vec![Vec::new(); total];
+        let mut incoming = vec![Vec::new(); total];
+        for (index, synapse) in synapses.iter().enumerate() {
+            outgoing[synapse.pre].push(index);
+            incoming[synapse.post].push(index);
+        }
+        Self {
+            config,
+            neurons: vec![LifNeuron::default(); total],
+            synapses,
+            outgoing,
+            incoming,
+            pending: vec![0.0; total],
+            pre_trace: vec![0.0; total],
+            post_trace: vec![0.0; total],
+            layers,
+            rng,
+        }
+    }
+
+    pub fn synapses(&self) -> &[Synapse] {
+        &self.synapses
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.layers.last().map_or(0, |layer| layer.len())
+    }
+
+    /// Remet potentiels, traces et courants au repos; les poids sont conservés
+    pub fn reset_state(&mut self) {
+        let v_rest = self.config.lif.v_rest;
+        for neuron in &mut self.neurons {
+            *neuron = LifNeuron { v: v_rest, refractory: 0.0 };
+        }
+        for buffer in [&mut self.pending, &mut self.pre_trace, &mut self.post_trace] {
+            buffer.iter_mut().for_each(|x| *x = 0.0);
+        }
+    }
+
+    fn plasticity(&mut self, synapse: usize, dw: f32) {
+        let (w_max, modulated) = (self.config.stdp.w_max, self.config.stdp.reward_modulated);
+        let synapse = &mut self.synapses[synapse];
+        if modulated {
+            synapse.eligibility += dw;
+        } else {
+            synapse.weight = (synapse.weight + dw).clamp(0.0, w_max);
+        }
+    }
+
+    /// Avance d'un pas: `external` est le courant injecté dans la couche d'entrée; renvoie les
+    /// neurones qui ont émis une impulsion
+    pub fn step(&mut self, external: &[f32]) -> Vec<usize> {
+        let lif = self.config.lif.clone();
+        let stdp = self.config.stdp.clone();
+        let (decay_plus, decay_minus) = ((-lif.dt / stdp.tau_plus).exp(), (-lif.dt / stdp.tau_minus).exp());
+        self.pre_trace.iter_mut().for_each(|x| *x *= decay_plus);
+        self.post_trace.iter_mut().for_each(|x| *x *= decay_minus);
+
+        let mut spikes = Vec::new();
+        for (index, neuron) in self.neurons.iter_mut().enumerate() {
+            if neuron.refractory > 0.0 {
+                neuron.refractory -= lif.dt;
+                continue;
+            }
+            let input = self.pending[index] + external.get(index).copied().unwrap_or(0.0);
+            neuron.v += lif.dt / lif.tau_m * (lif.v_rest - neuron.v) + input;
+            if neuron.v >= lif.v_threshold {
+                neuron.v = lif.v_reset;
+                neuron.refractory = lif.refractory;
+                spikes.push(index);
+            }
+        }
+
+        self.pending.iter_mut().for_each(|x| *x = 0.0);
+        for &neuron in &spikes {
+            for &synapse in &self.outgoing[neuron] {
+                let Synapse { post, weight, .. } = self.synapses[synapse];
+                self.pending[post] += weight;
+            }
+        }
+
+        if stdp.enabled {
+            for &neuron in &spikes {
+                // Impulsion présynaptique après la postsynaptique: dépression
+                for synapse in self.outgoing[neuron].clone() {
+                    let dw = -stdp.a_minus * self.post_trace[self.synapses[synapse].post];
+                    self.plasticity(synapse, dw);
+                }
+                // Impulsion postsynaptique après la présynaptique: potentiation
+                for synapse in self.incoming[neuron].clone() {
+                    let dw = stdp.a_plus * self.pre_trace[self.synapses[synapse].pre];
+                    self.plasticity(synapse, dw);
+                }
+            }
+        }
+        for &neuron in &spikes {
+            self.pre_trace[neuron] += 1.0;
+            self.post_trace[neuron] += 1.0;
+        }
+        spikes
+    }
+
+    /// Présente `rates` (probabilité d'impulsion par pas de chaque entrée, de 0 à 1) pendant
+    /// `steps` pas; renvoie le nombre d'impulsions de chaque neurone de sortie
+    pub fn run(&mut self, rates: &[f32]) -> Vec<u32> {
+        self.reset_state();
+        let inputs = self.layers[0].clone();
+        let outputs = self.layers[self.layers.len() - 1].clone();
+        let mut counts = vec![0; outputs.len()];
+        let mut external = vec![0.0; inputs.len()];
+        for _ in 0..self.config.steps {
+            for (current, rate) in external.iter_mut().zip(rates) {
+                *current = if self.rng.gen::<f32>() < rate.clamp(0.0, 1.0) { self.config.lif.input_gain } else { 0.0 };
+            }
+            for neuron in self.step(&external) {
+                if outputs.contains(&neuron) {
+                    counts[neuron - outputs.start] += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Applique les traces d'éligibilité pondérées par `reward`, puis les efface
+    pub fn apply_reward(&mut self, reward: f32) {
+        let w_max = self.config.stdp.w_max;
+        for synapse in &mut self.synapses {
+            synapse.weight = (synapse.weight + reward * synapse.eligibility).clamp(0.0, w_max);
+            synapse.eligibility = 0.0;
+        }
+    }
+}
+
+/// Backend de sélection d'actions, comparable d'une implémentation à l'autre
+pub trait ActionSelector {
+    fn name(&self) -> &str;
+    /// Index de l'action choisie pour ces caractéristiques d'état
+    fn select(&mut self, features: &[f32]) -> usize;
+    /// Récompense obtenue par l'action exécutée
+    fn feedback(&mut self, action: usize, reward: f32);
+}
+
+/// Réseau impulsionnel employé comme sélecteur: l'action est le neurone de sortie le plus
+/// actif; la récompense, rapportée à sa moyenne, module la STDP de la dernière décision
+pub struct SpikingPolicy {
+    network: SpikingNetwork,
+    last_choice: Option<usize>,
+    baseline: f32,
+}
+
+impl SpikingPolicy {
+    /// Réseau dont la couche de sortie compte une unité par action
+    pub fn new(mut config: NeuromorphicConfig, actions: usize) -> Self {
+        if let Some(output) = config.layers.last_mut() {
+            *output = actions;
+        }
+        Self { network: SpikingNetwork::new(config), last_choice: None, baseline: 0.0 }
+    }
+
+    pub fn network(&self) -> &SpikingNetwork {
+        &self.network
+    }
+}
+
+impl ActionSelector for SpikingPolicy {
+    fn name(&self) -> &str {
+        "spiking-lif"
+    }
+
+    fn select(&mut self, features: &[f32]) -> usize {
+        let counts = self.network.run(features);
+        let best = counts.iter().copied().max().unwrap_or(0);
+        let choice = if best == 0 {
+            // Réseau silencieux: choix aléatoire
+            self.network.rng.gen_range(0..counts.len().max(1))
+        } else {
+            counts.iter().position(|count| *count == best).unwrap_or(0)
+        };
+        self.last_choice = Some(choice);
+        choice
+    }
+
+    fn feedback(&mut self, action: usize, reward: f32) {
+        if self.last_choice.take() == Some(action) {
+            self.network.apply_reward(reward - self.baseline);
+            self.baseline = 0.9 * self.baseline + 0.1 * reward;
+        } else {
+            // L'action exécutée n'est pas celle du réseau: rien à créditer
+            self.network.apply_reward(0.0);
+        }
+    }
+}
+
+/// Caractéristiques d'état communes aux sélecteurs: hyperparamètres de l'agent, dernière
+/// récompense, phase du cycle et dernière action (encodage one-hot), complétées de zéros
+pub fn state_features(agent: &LearningAgent, cycle: usize) -> Vec<f32> {
+    let mut features = vec![0.0; STATE_FEATURES];
+    features[0] = agent.exploration_rate;
+    features[1] = agent.learning_rate;
+    features[2] = agent.current_episode.reward_history.last().copied().unwrap_or(0.0).clamp(0.0, 1.0);
+    features[3] = (cycle % 24) as f32 / 24.0;
+    if let Some(last) = agent.current_episode.action_history.last() {
+        if let Some(index) = agent.actions.iter().position(|action| action == last) {
+            if 4 + index < STATE_FEATURES {
+                features[4 + index] = 1.0;
+            }
+        }
+    }
+    features
+}
+
+/// Décisions d'un backend mises en regard de l'action réellement exécutée
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BackendStats {
+    pub decisions: usize,
+    pub agreements: usize,
+    /// Récompense cumulée des décisions suivies
+    pub reward: f32,
+}
+
+/// Expérience de comparaison entre backends de sélection d'actions
+#[derive(Debug, Default)]
+pub struct BackendComparison {
+    stats: BTreeMap<String, BackendStats>,
+}
+
+impl BackendComparison {
+    pub fn record(&mut self, backend: &str, chosen: usize, executed: usize, reward: f32) {
+        let stats = self.stats.entry(backend.to_string()).or_default();
+        stats.decisions += 1;
+        if chosen == executed {
+            stats.agreements += 1;
+            stats.reward += reward;
+        }
+    }
+
+    pub fn stats(&self, backend: &str) -> Option<&BackendStats> {
+        self.stats.get(backend)
+    }
+
+    pub fn report(&self) {
+        for (backend, stats) in &self.stats {
+            let agreement = stats.agreements as f32 / stats.decisions.max(1) as f32;
+            let mean = if stats.agreements == 0 { 0.0 } else { stats.reward / stats.agreements as f32 };
+            info!(
+                "⚡ Backend {}: {} décision(s), accord {:.0}% avec l'agent, récompense moyenne des accords {:.2}",
+                backend, stats.decisions, agreement * 100.0, mean
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(stdp: StdpParams) -> SpikingNetwork {
+        SpikingNetwork::new(NeuromorphicConfig {
+            layers: vec![1, 1],
+            connection_prob: 1.0,
+            recurrent_prob: 0.0,
+            initial_weight: 0.3,
+            stdp,
+            ..NeuromorphicConfig::default()
+        })
+    }
+
+    #[test]
+    fn lif_neurons_fire_and_stdp_potentiates_causal_pairs() {
+        let mut network = chain(StdpParams { reward_modulated: false, ..StdpParams::default() });
+        assert_eq!(network.synapses().len(), 1);
+        // Sous le seuil, le potentiel fuit sans impulsion
+        assert!(network.step(&[0.5]).is_empty());
+        assert_eq!(network.step(&[0.6]), vec![0]);
+        // Réfractaire: aucune impulsion au pas suivant malgré un fort courant
+        assert!(network.step(&[2.0]).is_empty());
+
+        // L'entrée précède systématiquement la sortie: la synapse se renforce
+        let mut network = chain(StdpParams { reward_modulated: false, ..StdpParams::default() });
+        network.synapses[0].weight = 0.5;
+        assert_eq!(network.step(&[1.0]), vec![0]);
+        network.pending[1] = 1.0;
+        assert_eq!(network.step(&[0.0]), vec![1]);
+        assert!(network.synapses()[0].weight > 0.5);
+    }
+
+    #[test]
+    fn reward_modulated_policy_learns_from_credited_actions() {
+        let toml = "layers = [2, 2]\nconnection-prob = 1.0\ninitial-weight = 0.5\nsteps = 40\n[stdp]\nreward-modulated = true\n";
+        let config = NeuromorphicConfig::from_toml(toml).unwrap();
+        assert!(NeuromorphicConfig::from_toml("layers = [4]").is_err());
+
+        let mut policy = SpikingPolicy::new(config, 3);
+        assert_eq!(policy.network().output_size(), 3);
+        let weights = |p: &SpikingPolicy| p.network().synapses().iter().map(|s| s.weight).collect::<Vec<f32>>();
+        let initial = weights(&policy);
+        let choice = policy.select(&[1.0, 1.0]);
+        // Action exécutée différente: éligibilité effacée, poids inchangés
+        policy.feedback((choice + 1) % 3, 1.0);
+        assert_eq!(weights(&policy), initial);
+        assert!(policy.network().synapses().iter().all(|s| s.eligibility == 0.0));
+
+        let choice = policy.select(&[1.0, 1.0]);
+        policy.feedback(choice, 1.0);
+        assert_ne!(weights(&policy), initial);
+
+        let mut comparison = BackendComparison::default();
+        comparison.record("spiking-lif", 1, 1, 0.8);
+        comparison.record("spiking-lif", 2, 1, 0.5);
+        let stats = comparison.stats("spiking-lif").unwrap();
+        assert_eq!((stats.decisions, stats.agreements), (2, 1));
+        assert!((stats.reward - 0.8).abs() < 1e-6);
+    }
+}
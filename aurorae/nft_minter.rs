@@ -1,15 +1,43 @@
 use chrono::Utc;
 use uuid::Uuid;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use ethers::abi::{encode, Token};
+use ethers::types::{Address, Bytes, H256, U256};
+use ethers::utils::{id, keccak256};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
 
-#[derive(Debug, Clone)]
+use crate::blockchain_core::{ChainBackend, GasOracle, SharedTxManager, TxLog};
+use crate::currency::{from_base_units, Asset};
+use crate::deployer::{ContractBuilder, Deployer};
+use crate::economy::{SharedBudget, SpendDecision};
+use crate::event_bus::AuroraeEvent;
+use crate::image_generator::ProceduralGenerator;
+use crate::ipfs::IpfsClient;
+use crate::keystore::Signer;
+use crate::lazy_mint::{decode_voucher_redeemed, LazyMintVoucher, VoucherDomain};
+use crate::nft_sales::{decode_seaport_sale, RoyaltyConfig, SaleRecord, FLOOR_WINDOW};
+
+/// Contrat ERC-721 de `contracts/` déployé pour chaque collection
+pub const COLLECTION_CONTRACT: &str = "AuroraeCollection";
+/// Gas réservé pour un mint (stockage du tokenURI inclus)
+const MINT_GAS_UNITS: u64 = 200_000;
+/// Gas d'un `mintBatch`: coût fixe de la transaction puis coût par token
+const MINT_BATCH_BASE_GAS: u64 = 60_000;
+const MINT_BATCH_GAS_PER_TOKEN: u64 = 150_000;
+/// Ventes différées au plus conservées en attendant les décimales de leur token
+const MAX_PENDING_SALES: usize = 256;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NFTAttribute {
     pub trait_type: String,
     pub value: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NFTMetadata {
     pub attributes: Vec<NFTAttribute>,
     pub external_url: Option<String>,
@@ -17,9 +45,11 @@ pub struct NFTMetadata {
     pub creator_fee_basis_points: u16,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NFT {
     pub id: Uuid,
+    /// Identifiant on-chain dans la collection (à partir de 1)
+    pub token_id: u64,
     pub name: String,
     pub description: String,
     pub image_url: String,
@@ -28,34 +58,100 @@ pub struct NFT {
     pub metadata: NFTMetadata,
     pub rarity_score: f32,
     pub evolution_potential: f32,
+    /// CID de l'image et des métadonnées publiées sur IPFS
+    #[serde(default)]
+    pub image_cid: Option<String>,
+    #[serde(default)]
+    pub metadata_cid: Option<String>,
+    /// Identifiant lu dans l'événement `Transfer` du mint on-chain
+    #[serde(default)]
+    pub onchain_token_id: Option<u64>,
+    #[serde(default)]
+    pub mint_tx: Option<H256>,
 }
 
-#[derive(Debug, Clone)]
+/// Métadonnées d'un token au format ERC-721 / ERC-1155 (compatibles OpenSea)
+#[derive(Debug, Serialize)]
+struct TokenMetadataFile<'a> {
+    name: &'a str,
+    description: &'a str,
+    image: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_url: Option<&'a str>,
+    /// Couleur hexadécimale sur six caractères, sans `#`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<String>,
+    attributes: &'a [NFTAttribute],
+    seller_fee_basis_points: u16,
+    /// ERC-1155 uniquement: tokens non fractionnables
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decimals: Option<u8>,
+}
+
+/// Métadonnées de niveau contrat (`contractURI`)
+#[derive(Debug, Serialize)]
+struct ContractMetadataFile<'a> {
+    name: &'a str,
+    description: &'a str,
+    symbol: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_link: Option<&'a str>,
+    seller_fee_basis_points: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fee_recipient: Option<&'a str>,
+}
+
+/// Standard du contrat de la collection, qui fixe le format des fichiers de métadonnées
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenStandard {
+    Erc721,
+    Erc1155,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NFTCollection {
     pub id: Uuid,
     pub name: String,
     pub description: String,
     pub symbol: String,
+    pub standard: TokenStandard,
+    /// Royalties EIP-2981 (`royaltyInfo` on-chain, `fee_recipient` des métadonnées)
+    pub royalty: Option<RoyaltyConfig>,
+    pub external_url: Option<String>,
     pub items: Vec<NFT>,
     pub creator: String,
     pub contract_address: Option<String>,
     pub created_at: String,
+    /// Volume des ventes secondaires observées
     pub total_volume: f64,
+    /// Prix minimal des dernières ventes observées
     pub floor_price: f64,
+    #[serde(default)]
+    pub sales: Vec<SaleRecord>,
+    /// Bons de mint différé signés, rachetables on-chain
+    #[serde(default)]
+    pub vouchers: Vec<LazyMintVoucher>,
 }
 
 pub struct NFTMinter {
     pub collections: HashMap<Uuid, NFTCollection>,
+    pub ipfs: IpfsClient,
     mint_count: u32,
     innovation_score: f32,
+    /// Ventes réglées en un token dont les décimales restent à lire: (chain-id, token, log)
+    pending_sales: Vec<(u64, Address, AuroraeEvent)>,
 }
 
 impl NFTMinter {
     pub fn new() -> Self {
         Self {
             collections: HashMap::new(),
+            ipfs: IpfsClient::from_env(),
             mint_count: 0,
             innovation_score: 1.0,
+            pending_sales: Vec::new(),
         }
     }
 
@@ -66,15 +162,20 @@ impl NFTMinter {
             name: name.to_string(),
             description: description.to_string(),
             symbol: symbol.to_string(),
+            standard: TokenStandard::Erc721,
+            royalty: None,
+            external_url: None,
             items: Vec::new(),
             creator: "AURORAE".to_string(),
             contract_address: None,
             created_at: Utc::now().to_rfc3339(),
             total_volume: 0.0,
-            floor_price: 0.01,
+            floor_price: 0.0,
+            sales: Vec::new(),
+            vouchers: Vec::new(),
         };
         
-        println!("[AURORAE++] 🎨 Nouvelle collection NFT créée: {}", name);
+        info!("🎨 Nouvelle collection NFT créée: {}", name);
         self.collections.insert(collection_id, collection);
         self.innovation_score *= 1.02;
         collection_id
@@ -93,6 +194,7 @@ impl NFTMinter {
         
         let nft = NFT {
             id: nft_id,
+            token_id: collection.items.len() as u64 + 1,
             name: name.to_string(),
             description: description.to_string(),
             image_url: image_url.to_string(),
@@ -102,22 +204,22 @@ impl NFTMinter {
                 attributes: Vec::new(),
                 external_url: None,
                 background_color: None,
-                creator_fee_basis_points: 250, // 2.5%
+                creator_fee_basis_points: collection.royalty.map(|r| r.basis_points).unwrap_or(250), // 2.5% par défaut
             },
             rarity_score: rarity,
             evolution_potential: potential,
+            image_cid: None,
+            metadata_cid: None,
+            onchain_token_id: None,
+            mint_tx: None,
         };
         
-        println!("[AURORAE++] 🖼️ NFT minté: {} dans la collection {} (Rareté: {:.1}, Potentiel: {:.1})", 
+        info!("🖼️ NFT minté: {} dans la collection {} (Rareté: {:.1}, Potentiel: {:.1})", 
                  name, collection.name, rarity, potential);
                  
         collection.items.push(nft);
         self.mint_count += 1;
         
-        // Mettre à jour les statistiques de la collection
-        collection.floor_price *= 1.001; // Légère augmentation
-        collection.total_volume += collection.floor_price;
-        
         Ok(nft_id)
     }
     
@@ -134,7 +236,7 @@ impl NFTMinter {
             value: value.to_string(),
         });
         
-        println!("[AURORAE++] 🏷️ Attribut ajouté à {}: {} = {}", nft.name, trait_type, value);
+        info!("🏷️ Attribut ajouté à {}: {} = {}", nft.name, trait_type, value);
         
         Ok(())
     }
@@ -144,7 +246,7 @@ impl NFTMinter {
             .ok_or_else(|| "Collection non trouvée".to_string())?;
             
         collection.contract_address = Some(address.to_string());
-        println!("[AURORAE++] 📝 Adresse du contrat définie pour collection {}: {}", collection.name, address);
+        info!("📝 Adresse du contrat définie pour collection {}: {}", collection.name, address);
         
         Ok(())
     }
@@ -178,11 +280,7 @@ impl NFTMinter {
             value: format!("Niveau {}", Utc::now().timestamp() % 10 + 1),
         });
         
-        println!("[AURORAE++] 🌟 NFT a évolué: {} (Nouvelle rareté: {:.1})", nft.name, nft.rarity_score);
-        
-        // Augmenter la valeur de la collection
-        collection.floor_price *= 1.05;
-        collection.total_volume += collection.floor_price;
+        info!("🌟 NFT a évolué: {} (Nouvelle rareté: {:.1})", nft.name, nft.rarity_score);
         
         // Augmenter le score d'innovation
         self.innovation_score *= 1.03;
@@ -214,7 +312,7 @@ impl NFTMinter {
         }
         
         if evolutions > 0 {
-            println!("[AURORAE++] 🧬 Auto-évolution: {} NFTs ont évolué spontanément", evolutions);
+            info!("🧬 Auto-évolution: {} NFTs ont évolué spontanément", evolutions);
         }
         
         evolutions
@@ -230,11 +328,18 @@ impl NFTMinter {
         
         // Créer une série de NFTs représentant les stades évolutifs
         let stages = ["Émergence", "Conscience", "Réflexion", "Autonomie", "Transcendance"];
+        let artist = ProceduralGenerator::default();
         
         for (i, stage) in stages.iter().enumerate() {
             let nft_name = format!("{} - Étape {}", stage, i + 1);
             let nft_desc = format!("Stade évolutif {} d'AURORAE", stage);
-            let nft_url = format!("https://aurora.ai/evolution/{}-{}.png", stage.to_lowercase(), i + 1);
+            let nft_url = match artist.render(&nft_name, &nft_desc) {
+                Ok(artwork) => artwork.path.display().to_string(),
+                Err(e) => {
+                    warn!("⚠️ Illustration de {} impossible: {}", nft_name, e);
+                    continue;
+                }
+            };
             
             if let Ok(nft_id) = self.mint_nft(&collection_id, &nft_name, &nft_desc, &nft_url) {
                 self.add_attribute(&collection_id, &nft_id, "Stade", stage).ok();
@@ -242,7 +347,7 @@ impl NFTMinter {
             }
         }
         
-        println!("[AURORAE++] 🧠 Collection évolutive créée: {} avec {} stades", name, stages.len());
+        info!("🧠 Collection évolutive créée: {} avec {} stades", name, stages.len());
         collection_id
     }
     
@@ -257,4 +362,540 @@ impl NFTMinter {
     pub fn get_innovation_score(&self) -> f32 {
         self.innovation_score
     }
+
+    /// Mint avec publication IPFS: l'image puis les métadonnées du token sont épinglées
+    /// (ou mises en cache localement hors ligne) et leurs CID enregistrés sur le NFT
+    pub async fn mint_nft_with_assets(
+        &mut self,
+        collection_id: &Uuid,
+        name: &str,
+        description: &str,
+        image_name: &str,
+        image: Vec<u8>,
+    ) -> Result<Uuid, String> {
+        let image_pin = self.ipfs.add(image_name, image).await?;
+        let nft_id = self.mint_nft(collection_id, name, description, &image_pin.uri())?;
+
+        let (file_name, metadata) = {
+            let collection = &self.collections[collection_id];
+            let nft = collection.items.iter().find(|n| n.id == nft_id)
+                .ok_or_else(|| "NFT non trouvé".to_string())?;
+            let metadata = serde_json::to_vec_pretty(&token_metadata(collection, nft))
+                .map_err(|e| format!("Erreur de sérialisation: {}", e))?;
+            (format!("{}.json", nft.token_id), metadata)
+        };
+        let metadata_pin = self.ipfs.add(&file_name, metadata).await?;
+
+        if let Some(nft) = self.collections.get_mut(collection_id)
+            .and_then(|c| c.items.iter_mut().find(|n| n.id == nft_id))
+        {
+            nft.image_cid = Some(image_pin.cid);
+            nft.metadata_cid = Some(metadata_pin.cid);
+        }
+        Ok(nft_id)
+    }
+
+    /// Exporte et publie les métadonnées de la collection, puis déploie son contrat ERC-721
+    /// avec `contractURI` pointant vers `collection.json`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deploy_collection(
+        &mut self,
+        collection_id: &Uuid,
+        builder: &ContractBuilder,
+        budget: &SharedBudget,
+        backend: &dyn ChainBackend,
+        oracle: &GasOracle,
+        metadata_dir: &Path,
+    ) -> Result<Address, String> {
+        let files = self.export_metadata(collection_id, metadata_dir)?;
+        let collection_file = files.last().ok_or_else(|| "Métadonnées de collection absentes".to_string())?;
+        let content = fs::read(collection_file)
+            .map_err(|e| format!("Erreur lecture {}: {}", collection_file.display(), e))?;
+        let contract_uri = self.ipfs.add("collection.json", content).await?.uri();
+
+        let (name, symbol, royalty) = {
+            let collection = &self.collections[collection_id];
+            (collection.name.clone(), collection.symbol.clone(), collection.royalty)
+        };
+        // Sans configuration explicite, les royalties reviennent au compte déployeur
+        let royalty = match royalty {
+            Some(royalty) => royalty,
+            None => RoyaltyConfig::new(backend.sender(), 250)?,
+        };
+        let artifact = builder.build(COLLECTION_CONTRACT)?;
+        let args = vec![
+            Token::String(name),
+            Token::String(symbol),
+            Token::String(contract_uri),
+            Token::Address(royalty.receiver),
+            Token::Uint(U256::from(royalty.basis_points)),
+        ];
+        let (address, _) = Deployer::deploy_with_args(budget, backend, oracle, &artifact, args).await?;
+
+        self.set_contract_address(collection_id, &format!("{:?}", address))?;
+        if let Some(collection) = self.collections.get_mut(collection_id) {
+            collection.royalty = Some(royalty);
+        }
+        Ok(address)
+    }
+
+    /// Configure les royalties EIP-2981 d'une collection (appliquées aux prochains mints et
+    /// exports; `update_royalty_onchain` les reporte sur un contrat déjà déployé)
+    pub fn set_royalty(&mut self, collection_id: &Uuid, receiver: Address, basis_points: u16) -> Result<(), String> {
+        let royalty = RoyaltyConfig::new(receiver, basis_points)?;
+        let collection = self.collections.get_mut(collection_id)
+            .ok_or_else(|| "Collection non trouvée".to_string())?;
+        collection.royalty = Some(royalty);
+        for nft in &mut collection.items {
+            nft.metadata.creator_fee_basis_points = basis_points;
+        }
+        info!("👑 Royalties de {}: {:.2}% vers {:?}", collection.name, basis_points as f64 / 100.0, receiver);
+        Ok(())
+    }
+
+    /// Envoie `setDefaultRoyalty` au contrat de la collection
+    pub async fn update_royalty_onchain(&self, collection_id: &Uuid, tx: &SharedTxManager) -> Result<H256, String> {
+        let collection = self.collections.get(collection_id)
+            .ok_or_else(|| "Collection non trouvée".to_string())?;
+        let royalty = collection.royalty
+            .ok_or_else(|| format!("Collection {} sans royalties configurées", collection.name))?;
+        let contract: Address = collection.contract_address.as_deref()
+            .ok_or_else(|| format!("Collection {} sans contrat déployé", collection.name))?
+            .parse()
+            .map_err(|e| format!("Adresse de contrat invalide: {}", e))?;
+
+        let mut calldata = id("setDefaultRoyalty(address,uint96)").to_vec();
+        calldata.extend(encode(&[Token::Address(royalty.receiver), Token::Uint(U256::from(royalty.basis_points))]));
+        let tx_hash = tx.submit(contract, U256::zero(), Some(Bytes::from(calldata))).await?;
+        let receipt = tx.backend().wait_for_receipt(tx_hash, 1).await?;
+        if !receipt.success {
+            return Err(format!("setDefaultRoyalty échoué on-chain: {:?}", tx_hash));
+        }
+        Ok(tx_hash)
+    }
+
+    /// Mint on-chain d'un NFT de la collection: `tokenURI` pointe vers ses métadonnées IPFS
+    /// et l'identifiant émis par le contrat remplace l'identifiant local s'ils diffèrent
+    pub async fn mint_onchain(
+        &mut self,
+        collection_id: &Uuid,
+        nft_id: &Uuid,
+        recipient: Address,
+        budget: &SharedBudget,
+        tx: &SharedTxManager,
+        oracle: &GasOracle,
+    ) -> Result<Option<u64>, String> {
+        let contract = self.collection_contract(collection_id)?;
+        let uri = self.metadata_uri(collection_id, nft_id).await?;
+        reserve_mint_gas(budget, tx.backend().as_ref(), oracle, MINT_GAS_UNITS).await?;
+
+        let mut calldata = id("mint(address,string)").to_vec();
+        calldata.extend(encode(&[Token::Address(recipient), Token::String(uri)]));
+        let tx_hash = tx.submit(contract, U256::zero(), Some(Bytes::from(calldata))).await?;
+        let receipt = tx.backend().wait_for_receipt(tx_hash, 1).await?;
+        if !receipt.success {
+            return Err(format!("Mint échoué on-chain: {:?}", tx_hash));
+        }
+
+        if let Some(nft) = self.nft_mut(collection_id, nft_id) {
+            nft.mint_tx = Some(tx_hash);
+        }
+        let token_id = receipt.logs.iter().find_map(|log| minted_token_id(contract, log));
+        match token_id {
+            Some(token_id) => self.reconcile_token_id(collection_id, tx_hash, token_id),
+            // Sans log dans le reçu, l'événement Transfer observé sur le bus fera foi
+            None => info!("⏳ Mint {:?} envoyé, identifiant on-chain en attente", tx_hash),
+        }
+        Ok(token_id)
+    }
+
+    /// Mint on-chain par lots (`mintBatch`, `chunk_size` tokens par transaction); retourne
+    /// les transactions envoyées. Un lot refusé par le budget ou échoué interrompt la suite.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn mint_batch(
+        &mut self,
+        collection_id: &Uuid,
+        nft_ids: &[Uuid],
+        recipient: Address,
+        chunk_size: usize,
+        budget: &SharedBudget,
+        tx: &SharedTxManager,
+        oracle: &GasOracle,
+    ) -> Result<Vec<H256>, String> {
+        if chunk_size == 0 {
+            return Err("Taille de lot nulle".to_string());
+        }
+        let contract = self.collection_contract(collection_id)?;
+        // Ordre de la collection: les Transfer d'un lot sont rapprochés dans cet ordre
+        let ordered: Vec<Uuid> = self.collections[collection_id].items.iter()
+            .filter(|n| nft_ids.contains(&n.id))
+            .map(|n| n.id)
+            .collect();
+        if ordered.len() != nft_ids.len() {
+            return Err("NFT absent de la collection".to_string());
+        }
+
+        let mut sent = Vec::new();
+        for chunk in ordered.chunks(chunk_size) {
+            let mut uris = Vec::with_capacity(chunk.len());
+            for nft_id in chunk {
+                uris.push(Token::String(self.metadata_uri(collection_id, nft_id).await?));
+            }
+            let gas = MINT_BATCH_BASE_GAS + MINT_BATCH_GAS_PER_TOKEN * chunk.len() as u64;
+            if let Err(e) = reserve_mint_gas(budget, tx.backend().as_ref(), oracle, gas).await {
+                return Err(format!("{} (lots envoyés: {}/{})", e, sent.len(), ordered.len().div_ceil(chunk_size)));
+            }
+
+            let mut calldata = id("mintBatch(address,string[])").to_vec();
+            calldata.extend(encode(&[Token::Address(recipient), Token::Array(uris)]));
+            let tx_hash = tx.submit(contract, U256::zero(), Some(Bytes::from(calldata))).await?;
+            let receipt = tx.backend().wait_for_receipt(tx_hash, 1).await?;
+            if !receipt.success {
+                return Err(format!("Lot de mint échoué on-chain: {:?}", tx_hash));
+            }
+            sent.push(tx_hash);
+
+            for nft_id in chunk {
+                if let Some(nft) = self.nft_mut(collection_id, nft_id) {
+                    nft.mint_tx = Some(tx_hash);
+                }
+            }
+            let token_ids: Vec<u64> = receipt.logs.iter().filter_map(|log| minted_token_id(contract, log)).collect();
+            for token_id in &token_ids {
+                self.reconcile_token_id(collection_id, tx_hash, *token_id);
+            }
+            info!("📦 Lot de {} NFT minté ({:?})", chunk.len(), tx_hash);
+        }
+        Ok(sent)
+    }
+
+    /// Signe un bon de mint différé par NFT, valable `validity`, au prix minimal `min_price`
+    /// (wei); le signataire doit être le propriétaire du contrat de la collection
+    pub async fn create_vouchers(
+        &mut self,
+        collection_id: &Uuid,
+        nft_ids: &[Uuid],
+        min_price: U256,
+        validity: chrono::Duration,
+        signer: &dyn Signer,
+        chain_id: u64,
+    ) -> Result<Vec<LazyMintVoucher>, String> {
+        let domain = VoucherDomain {
+            name: self.collections.get(collection_id).map(|c| c.name.clone()).unwrap_or_default(),
+            chain_id,
+            contract: self.collection_contract(collection_id)?,
+        };
+        let expiry = (Utc::now() + validity).timestamp().max(0) as u64;
+
+        let mut vouchers = Vec::with_capacity(nft_ids.len());
+        for nft_id in nft_ids {
+            let already = self.collections[collection_id].vouchers.iter().any(|v| &v.nft_id == nft_id);
+            if already {
+                return Err(format!("Bon déjà émis pour le NFT {}", nft_id));
+            }
+            let uri = self.metadata_uri(collection_id, nft_id).await?;
+            let voucher = LazyMintVoucher::new(*nft_id, &uri, min_price, expiry).sign(signer, &domain).await?;
+            vouchers.push(voucher);
+        }
+
+        if let Some(collection) = self.collections.get_mut(collection_id) {
+            collection.vouchers.extend(vouchers.iter().cloned());
+            info!("🎟️ {} bons de mint différé signés pour {}", vouchers.len(), collection.name);
+        }
+        Ok(vouchers)
+    }
+
+    fn collection_contract(&self, collection_id: &Uuid) -> Result<Address, String> {
+        let collection = self.collections.get(collection_id)
+            .ok_or_else(|| "Collection non trouvée".to_string())?;
+        collection.contract_address.as_deref()
+            .ok_or_else(|| format!("Collection {} sans contrat déployé", collection.name))?
+            .parse()
+            .map_err(|e| format!("Adresse de contrat invalide: {}", e))
+    }
+
+    fn nft_mut(&mut self, collection_id: &Uuid, nft_id: &Uuid) -> Option<&mut NFT> {
+        self.collections.get_mut(collection_id)
+            .and_then(|c| c.items.iter_mut().find(|n| &n.id == nft_id))
+    }
+
+    /// URI IPFS des métadonnées d'un NFT pas encore minté, publiées au besoin
+    async fn metadata_uri(&mut self, collection_id: &Uuid, nft_id: &Uuid) -> Result<String, String> {
+        let (metadata_cid, file_name, metadata) = {
+            let collection = self.collections.get(collection_id)
+                .ok_or_else(|| "Collection non trouvée".to_string())?;
+            let nft = collection.items.iter().find(|n| &n.id == nft_id)
+                .ok_or_else(|| "NFT non trouvé".to_string())?;
+            if nft.mint_tx.is_some() {
+                return Err(format!("{} déjà minté on-chain", nft.name));
+            }
+            let metadata = serde_json::to_vec_pretty(&token_metadata(collection, nft))
+                .map_err(|e| format!("Erreur de sérialisation: {}", e))?;
+            (nft.metadata_cid.clone(), format!("{}.json", nft.token_id), metadata)
+        };
+        let metadata_cid = match metadata_cid {
+            Some(cid) => cid,
+            None => self.ipfs.add(&file_name, metadata).await?.cid,
+        };
+        if let Some(nft) = self.nft_mut(collection_id, nft_id) {
+            nft.metadata_cid = Some(metadata_cid.clone());
+        }
+        Ok(format!("ipfs://{}", metadata_cid))
+    }
+
+    /// Traite les logs reçus par souscription: rapproche les mints des NFT en mémoire et
+    /// enregistre les ventes secondaires des collections, retournées pour imputer les royalties.
+    /// Une vente réglée en un token absent de `token_decimals` est différée.
+    pub fn on_chain_event(&mut self, event: &AuroraeEvent, token_decimals: &HashMap<(u64, Address), u8>) -> Option<SaleRecord> {
+        let AuroraeEvent::ChainLog { chain_id, address, topics, data, tx_hash: Some(tx_hash), .. } = event else {
+            return None;
+        };
+
+        let log = TxLog { address: *address, topics: topics.clone(), data: Bytes::from(data.clone()) };
+        if self.redeem_voucher(&log, *tx_hash) {
+            return None;
+        }
+        if let Some(token_id) = minted_token_id(*address, &log) {
+            let collection_id = self.collections.values()
+                .find(|c| c.items.iter().any(|n| n.mint_tx == Some(*tx_hash) && n.onchain_token_id.is_none()))
+                .map(|c| c.id);
+            if let Some(collection_id) = collection_id {
+                self.reconcile_token_id(&collection_id, *tx_hash, token_id);
+            }
+            return None;
+        }
+
+        let sale = decode_seaport_sale(*address, topics, data)?;
+        let collection = self.collections.values_mut()
+            .find(|c| c.contract_address.as_deref().and_then(|a| a.parse::<Address>().ok()) == Some(sale.collection))?;
+
+        let decimals = match sale.currency(*chain_id) {
+            Asset::Token { chain_id, address } => match token_decimals.get(&(chain_id, address)) {
+                Some(decimals) => *decimals,
+                None => {
+                    if self.pending_sales.len() >= MAX_PENDING_SALES {
+                        self.pending_sales.remove(0);
+                        warn!("⚠️ Vente différée la plus ancienne abandonnée (décimales inconnues)");
+                    }
+                    self.pending_sales.push((chain_id, address, event.clone()));
+                    return None;
+                }
+            },
+            _ => 18,
+        };
+        let price = from_base_units(sale.price(), decimals);
+        let royalty_expected = collection.royalty.map(|r| from_base_units(r.royalty_for(sale.price()), decimals)).unwrap_or(0.0);
+        let royalty_paid = collection.royalty.map(|r| from_base_units(sale.paid_to(r.receiver), decimals)).unwrap_or(0.0);
+        let record = SaleRecord {
+            collection_id: collection.id,
+            token_id: sale.token_id.low_u64(),
+            marketplace: sale.marketplace.clone(),
+            seller: sale.seller,
+            buyer: sale.buyer,
+            price,
+            currency: sale.currency(*chain_id),
+            royalty_expected,
+            royalty_paid,
+            chain_id: *chain_id,
+            tx_hash: *tx_hash,
+            recorded_at: Utc::now().to_rfc3339(),
+        };
+
+        collection.total_volume += price;
+        collection.sales.push(record.clone());
+        collection.floor_price = collection.sales.iter().rev()
+            .take(FLOOR_WINDOW)
+            .map(|s| s.price)
+            .fold(f64::INFINITY, f64::min);
+
+        info!(
+            "🛒 Vente {} #{} sur {}: {:.6} {} • royalties {:.6}/{:.6}",
+            collection.name, record.token_id, record.marketplace, price, record.currency, royalty_paid, royalty_expected
+        );
+        if royalty_paid + f64::EPSILON < royalty_expected {
+            warn!("⚠️ Royalties non honorées par {} sur {:?}", record.marketplace, tx_hash);
+        }
+        Some(record)
+    }
+
+    /// Tokens de règlement des ventes différées sur `chain_id`
+    pub fn tokens_awaiting_decimals(&self, chain_id: u64) -> HashSet<Address> {
+        self.pending_sales.iter().filter(|(chain, ..)| *chain == chain_id).map(|(_, token, _)| *token).collect()
+    }
+
+    /// Rejoue les ventes différées dont les décimales du token sont désormais connues
+    pub fn replay_pending_sales(&mut self, token_decimals: &HashMap<(u64, Address), u8>) -> Vec<SaleRecord> {
+        let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_sales)
+            .into_iter()
+            .partition(|(chain_id, token, _)| token_decimals.contains_key(&(*chain_id, *token)));
+        self.pending_sales = waiting;
+        ready.iter().filter_map(|(_, _, event)| self.on_chain_event(event, token_decimals)).collect()
+    }
+
+    /// Rachat d'un bon de mint différé: le NFT correspondant reçoit son identifiant on-chain
+    fn redeem_voucher(&mut self, log: &TxLog, tx_hash: H256) -> bool {
+        let redeemed = self.collections.values().find_map(|c| {
+            let contract = c.contract_address.as_deref()?.parse::<Address>().ok()?;
+            decode_voucher_redeemed(contract, log).map(|(nonce, token_id)| (c.id, nonce, token_id))
+        });
+        let Some((collection_id, nonce, token_id)) = redeemed else { return false };
+
+        let Some(collection) = self.collections.get_mut(&collection_id) else { return false };
+        let Some(voucher) = collection.vouchers.iter_mut().find(|v| v.nonce == nonce) else { return true };
+        voucher.redeemed_tx = Some(tx_hash);
+        let nft_id = voucher.nft_id;
+        if let Some(nft) = collection.items.iter_mut().find(|n| n.id == nft_id) {
+            nft.mint_tx = Some(tx_hash);
+        }
+        self.reconcile_token_id(&collection_id, tx_hash, token_id);
+        true
+    }
+
+    /// Attribue `token_id` au premier NFT de la transaction encore sans identifiant on-chain
+    /// (les `Transfer` d'un lot suivent l'ordre de la collection)
+    fn reconcile_token_id(&mut self, collection_id: &Uuid, tx_hash: H256, token_id: u64) {
+        let Some(nft) = self.collections.get_mut(collection_id)
+            .and_then(|c| c.items.iter_mut().find(|n| n.mint_tx == Some(tx_hash) && n.onchain_token_id.is_none()))
+        else { return };
+
+        if nft.token_id != token_id {
+            info!("🔁 {}: identifiant local {} remplacé par l'identifiant on-chain {}", nft.name, nft.token_id, token_id);
+            nft.token_id = token_id;
+        }
+        nft.onchain_token_id = Some(token_id);
+        info!("⛓️ {} minté on-chain (token #{})", nft.name, token_id);
+    }
+
+    /// Écrit les métadonnées JSON de chaque token (`<token_id>.json` en ERC-721, identifiant
+    /// hexadécimal sur 64 caractères en ERC-1155) et `collection.json` pour `contractURI`
+    pub fn export_metadata(&self, collection_id: &Uuid, dir: &Path) -> Result<Vec<PathBuf>, String> {
+        let collection = self.collections.get(collection_id)
+            .ok_or_else(|| "Collection non trouvée".to_string())?;
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Erreur création {}: {}", dir.display(), e))?;
+
+        let mut written = Vec::with_capacity(collection.items.len() + 1);
+        for nft in &collection.items {
+            let file_name = match collection.standard {
+                TokenStandard::Erc721 => format!("{}.json", nft.token_id),
+                TokenStandard::Erc1155 => format!("{:064x}.json", nft.token_id),
+            };
+            written.push(write_json(&dir.join(file_name), &token_metadata(collection, nft))?);
+        }
+
+        let royalties = collection.royalty.map(|r| r.basis_points)
+            .or_else(|| collection.items.first().map(|n| n.metadata.creator_fee_basis_points))
+            .unwrap_or(250);
+        let fee_recipient = collection.royalty.map(|r| format!("{:?}", r.receiver));
+        let contract = ContractMetadataFile {
+            name: &collection.name,
+            description: &collection.description,
+            symbol: &collection.symbol,
+            image: collection.items.first().map(|n| n.image_url.as_str()),
+            external_link: collection.external_url.as_deref(),
+            seller_fee_basis_points: royalties,
+            fee_recipient: fee_recipient.as_deref(),
+        };
+        written.push(write_json(&dir.join("collection.json"), &contract)?);
+
+        info!("📦 Métadonnées de {} exportées: {} fichiers dans {}", collection.name, written.len(), dir.display());
+        Ok(written)
+    }
+}
+
+/// Réserve sur le budget le gas d'une transaction de mint
+async fn reserve_mint_gas(budget: &SharedBudget, backend: &dyn ChainBackend, oracle: &GasOracle, gas: u64) -> Result<(), String> {
+    let fees = oracle.estimate_fees(backend).await?;
+    match budget.write().request_gas_spend("nft_minter", fees.max_cost(U256::from(gas))) {
+        SpendDecision::Approved => Ok(()),
+        SpendDecision::RequiresApproval(id) => Err(format!("Mint en attente d'approbation budgétaire ({})", id)),
+        SpendDecision::Denied(reason) => Err(format!("Mint refusé par le budget: {}", reason)),
+    }
+}
+
+/// Identifiant du token d'un événement `Transfer(address(0), to, tokenId)` émis par `contract`
+fn minted_token_id(contract: Address, log: &TxLog) -> Option<u64> {
+    let transfer = H256::from(keccak256("Transfer(address,address,uint256)"));
+    if log.address != contract || log.topics.len() != 4 || log.topics[0] != transfer || !log.topics[1].is_zero() {
+        return None;
+    }
+    let token_id = U256::from_big_endian(log.topics[3].as_bytes());
+    (token_id <= U256::from(u64::MAX)).then(|| token_id.as_u64())
+}
+
+fn token_metadata<'a>(collection: &'a NFTCollection, nft: &'a NFT) -> TokenMetadataFile<'a> {
+    TokenMetadataFile {
+        name: &nft.name,
+        description: &nft.description,
+        image: &nft.image_url,
+        external_url: nft.metadata.external_url.as_deref().or(collection.external_url.as_deref()),
+        background_color: nft.metadata.background_color.as_ref().map(|c| c.trim_start_matches('#').to_string()),
+        attributes: &nft.metadata.attributes,
+        seller_fee_basis_points: nft.metadata.creator_fee_basis_points,
+        decimals: (collection.standard == TokenStandard::Erc1155).then_some(0),
+    }
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<PathBuf, String> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Erreur de sérialisation: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))?;
+    Ok(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nft_sales::{order_fulfilled_topic, SEAPORT_ADDRESSES};
+
+    /// Annonce Seaport exécutée: NFT #7 de `collection` payé 1000 unités brutes de `token`, dont 50 au créateur
+    fn token_sale_log(collection: Address, token: Address, creator: Address) -> AuroraeEvent {
+        let (seller, buyer) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let payment = |amount: u64, to: Address| Token::Tuple(vec![
+            Token::Uint(U256::one()), Token::Address(token), Token::Uint(U256::zero()), Token::Uint(U256::from(amount)), Token::Address(to),
+        ]);
+        let data = encode(&[
+            Token::FixedBytes(vec![0u8; 32]),
+            Token::Address(buyer),
+            Token::Array(vec![Token::Tuple(vec![
+                Token::Uint(U256::from(2)), Token::Address(collection), Token::Uint(U256::from(7)), Token::Uint(U256::one()),
+            ])]),
+            Token::Array(vec![payment(950, seller), payment(50, creator)]),
+        ]);
+        AuroraeEvent::ChainLog {
+            chain_id: 1,
+            address: SEAPORT_ADDRESSES[0].parse().unwrap(),
+            topics: vec![order_fulfilled_topic(), H256::from(seller), H256::zero()],
+            data,
+            block_number: Some(1),
+            tx_hash: Some(H256::random()),
+        }
+    }
+
+    #[test]
+    fn token_sales_wait_for_decimals_and_record_royalties_in_the_token() {
+        let mut minter = NFTMinter::new();
+        let (collection_address, token, creator) = (Address::random(), Address::random(), Address::random());
+        let id = minter.create_collection("Aurores", "test", "AUR");
+        {
+            let collection = minter.collections.get_mut(&id).unwrap();
+            collection.contract_address = Some(format!("{:?}", collection_address));
+            collection.royalty = Some(RoyaltyConfig::new(creator, 500).unwrap());
+        }
+
+        let mut decimals = HashMap::new();
+        assert!(minter.on_chain_event(&token_sale_log(collection_address, token, creator), &decimals).is_none());
+        assert_eq!(minter.tokens_awaiting_decimals(1), HashSet::from([token]));
+        assert!(minter.tokens_awaiting_decimals(137).is_empty());
+        assert!(minter.replay_pending_sales(&decimals).is_empty());
+
+        decimals.insert((1, token), 2);
+        let sales = minter.replay_pending_sales(&decimals);
+        assert_eq!(sales.len(), 1);
+        assert_eq!(sales[0].currency, Asset::Token { chain_id: 1, address: token });
+        assert!((sales[0].price - 10.0).abs() < 1e-9);
+        assert!((sales[0].royalty_paid - 0.5).abs() < 1e-9);
+        assert!(minter.tokens_awaiting_decimals(1).is_empty());
+        assert_eq!(minter.collections[&id].sales.len(), 1);
+    }
 }
@@ -0,0 +1,252 @@
+//! AURORAE++ - nft_sales.rs
+//!
+//! Ventes secondaires des collections: configuration des royalties EIP-2981 et
+//! décodage des ventes de marketplace (Seaport `OrderFulfilled`) reçues par
+//! souscription de logs, avec les royalties dues et effectivement versées.
+
+use ethers::abi::{decode, ParamType, Token};
+use ethers::types::{Address, Filter, H256, U256};
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::currency::Asset;
+
+/// Contrats Seaport 1.5 et 1.6 (mêmes adresses sur toutes les chaînes EVM)
+pub const SEAPORT_ADDRESSES: [&str; 2] = [
+    "0x00000000000000ADc04C56Bf30aC9d3c0aAF14dC",
+    "0x0000000000000068F116a894984e2DB1123eB395",
+];
+
+/// Base des royalties EIP-2981 (10 000 points = 100 %)
+const ROYALTY_DENOMINATOR: u16 = 10_000;
+
+/// Nombre de ventes récentes prises en compte pour le prix plancher
+pub const FLOOR_WINDOW: usize = 20;
+
+pub fn order_fulfilled_topic() -> H256 {
+    H256::from(keccak256(
+        "OrderFulfilled(bytes32,address,address,address,(uint8,address,uint256,uint256)[],(uint8,address,uint256,uint256,address)[])",
+    ))
+}
+
+/// Filtre de souscription aux ventes Seaport
+pub fn sales_filter() -> Filter {
+    let marketplaces: Vec<Address> = SEAPORT_ADDRESSES.iter().filter_map(|a| a.parse().ok()).collect();
+    Filter::new().address(marketplaces).topic0(order_fulfilled_topic())
+}
+
+/// Royalties EIP-2981 d'une collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoyaltyConfig {
+    pub receiver: Address,
+    pub basis_points: u16,
+}
+
+impl RoyaltyConfig {
+    pub fn new(receiver: Address, basis_points: u16) -> Result<Self, String> {
+        if basis_points > ROYALTY_DENOMINATOR {
+            return Err(format!("Royalties de {} points > 100 %", basis_points));
+        }
+        if receiver.is_zero() {
+            return Err("Destinataire des royalties nul".to_string());
+        }
+        Ok(Self { receiver, basis_points })
+    }
+
+    /// Montant dû pour un prix de vente, comme le calcule `royaltyInfo`
+    pub fn royalty_for(&self, sale_price: U256) -> U256 {
+        sale_price * U256::from(self.basis_points) / U256::from(ROYALTY_DENOMINATOR)
+    }
+}
+
+/// Versement effectué lors d'une vente
+#[derive(Debug, Clone, PartialEq)]
+pub struct SalePayment {
+    /// Token ERC-20 payé, `None` pour la monnaie native
+    pub token: Option<Address>,
+    pub amount: U256,
+    pub recipient: Address,
+}
+
+/// Vente décodée d'un log de marketplace
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketplaceSale {
+    pub marketplace: String,
+    pub collection: Address,
+    pub token_id: U256,
+    pub seller: Address,
+    pub buyer: Address,
+    pub payments: Vec<SalePayment>,
+}
+
+impl MarketplaceSale {
+    /// Prix total payé par l'acheteur (vendeur, frais et royalties)
+    pub fn price(&self) -> U256 {
+        self.payments.iter().fold(U256::zero(), |acc, p| acc + p.amount)
+    }
+
+    pub fn paid_to(&self, recipient: Address) -> U256 {
+        self.payments.iter().filter(|p| p.recipient == recipient).fold(U256::zero(), |acc, p| acc + p.amount)
+    }
+
+    /// Token ERC-20 de règlement, `None` pour la monnaie native
+    pub fn payment_token(&self) -> Option<Address> {
+        self.payments.first().and_then(|p| p.token)
+    }
+
+    /// Actif de règlement sur `chain_id`: monnaie native (ETH sur les chaînes suivies) ou token ERC-20
+    pub fn currency(&self, chain_id: u64) -> Asset {
+        match self.payment_token() {
+            Some(address) => Asset::Token { chain_id, address },
+            None => Asset::Eth,
+        }
+    }
+}
+
+/// Vente secondaire enregistrée pour une collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaleRecord {
+    pub collection_id: Uuid,
+    pub token_id: u64,
+    pub marketplace: String,
+    pub seller: Address,
+    pub buyer: Address,
+    pub price: f64,
+    pub currency: Asset,
+    pub royalty_expected: f64,
+    pub royalty_paid: f64,
+    pub chain_id: u64,
+    pub tx_hash: H256,
+    pub recorded_at: String,
+}
+
+/// Décode un `OrderFulfilled` Seaport portant sur un unique NFT (ERC-721 ou ERC-1155)
+pub fn decode_seaport_sale(address: Address, topics: &[H256], data: &[u8]) -> Option<MarketplaceSale> {
+    let is_seaport = SEAPORT_ADDRESSES.iter().any(|a| a.parse::<Address>().ok() == Some(address));
+    if !is_seaport || topics.len() != 3 || topics[0] != order_fulfilled_topic() {
+        return None;
+    }
+    let offerer = Address::from(topics[1]);
+
+    let spent = ParamType::Tuple(vec![ParamType::Uint(8), ParamType::Address, ParamType::Uint(256), ParamType::Uint(256)]);
+    let received = ParamType::Tuple(vec![
+        ParamType::Uint(8), ParamType::Address, ParamType::Uint(256), ParamType::Uint(256), ParamType::Address,
+    ]);
+    let tokens = decode(
+        &[
+            ParamType::FixedBytes(32),
+            ParamType::Address,
+            ParamType::Array(Box::new(spent)),
+            ParamType::Array(Box::new(received)),
+        ],
+        data,
+    ).ok()?;
+    let recipient = tokens[1].clone().into_address()?;
+    let offer: Vec<SeaportItem> = tokens[2].clone().into_array()?.into_iter().filter_map(SeaportItem::from_token).collect();
+    let consideration: Vec<SeaportItem> = tokens[3].clone().into_array()?.into_iter().filter_map(SeaportItem::from_token).collect();
+
+    let payments_of = |items: &[SeaportItem]| -> Vec<SalePayment> {
+        items.iter()
+            .filter(|i| i.is_payment())
+            .map(|i| SalePayment {
+                token: (i.item_type == 1).then_some(i.token),
+                amount: i.amount,
+                recipient: i.recipient.unwrap_or(recipient),
+            })
+            .collect()
+    };
+
+    if let Some(nft) = offer.iter().find(|i| i.is_nft()) {
+        // Annonce acceptée: le vendeur (offerer) cède le NFT, l'acheteur paie la contrepartie
+        return Some(MarketplaceSale {
+            marketplace: "seaport".to_string(),
+            collection: nft.token,
+            token_id: nft.identifier,
+            seller: offerer,
+            buyer: recipient,
+            payments: payments_of(&consideration),
+        });
+    }
+
+    // Offre acceptée: l'acheteur (offerer) paie en ERC-20, le vendeur reçoit le solde après frais
+    let nft = consideration.iter().find(|i| i.is_nft())?;
+    let offered = payments_of(&offer);
+    let token = offered.first()?.token;
+    let total = offered.iter().fold(U256::zero(), |acc, p| acc + p.amount);
+    let mut payments = payments_of(&consideration);
+    let fees = payments.iter().fold(U256::zero(), |acc, p| acc + p.amount);
+    payments.push(SalePayment { token, amount: total.saturating_sub(fees), recipient });
+    Some(MarketplaceSale {
+        marketplace: "seaport".to_string(),
+        collection: nft.token,
+        token_id: nft.identifier,
+        seller: recipient,
+        buyer: offerer,
+        payments,
+    })
+}
+
+/// Élément `SpentItem` / `ReceivedItem` de Seaport
+struct SeaportItem {
+    /// 0 natif, 1 ERC-20, 2 ERC-721, 3 ERC-1155 (4 et 5: variantes par critère)
+    item_type: u8,
+    token: Address,
+    identifier: U256,
+    amount: U256,
+    recipient: Option<Address>,
+}
+
+impl SeaportItem {
+    fn from_token(token: Token) -> Option<Self> {
+        let fields = token.into_tuple()?;
+        Some(Self {
+            item_type: fields.first()?.clone().into_uint()?.low_u32() as u8,
+            token: fields.get(1)?.clone().into_address()?,
+            identifier: fields.get(2)?.clone().into_uint()?,
+            amount: fields.get(3)?.clone().into_uint()?,
+            recipient: fields.get(4).and_then(|r| r.clone().into_address()),
+        })
+    }
+
+    fn is_nft(&self) -> bool {
+        matches!(self.item_type, 2 | 3)
+    }
+
+    fn is_payment(&self) -> bool {
+        matches!(self.item_type, 0 | 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::encode;
+
+    #[test]
+    fn decodes_listing_fill_with_royalty_payment() {
+        let seaport: Address = SEAPORT_ADDRESSES[0].parse().unwrap();
+        let (seller, buyer, creator, collection) =
+            (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3), Address::repeat_byte(4));
+        let eth = |n: u64| U256::from(n) * U256::exp10(15);
+        let payment = |amount: U256, to: Address| Token::Tuple(vec![
+            Token::Uint(U256::zero()), Token::Address(Address::zero()), Token::Uint(U256::zero()), Token::Uint(amount), Token::Address(to),
+        ]);
+        let data = encode(&[
+            Token::FixedBytes(vec![0u8; 32]),
+            Token::Address(buyer),
+            Token::Array(vec![Token::Tuple(vec![
+                Token::Uint(U256::from(2)), Token::Address(collection), Token::Uint(U256::from(7)), Token::Uint(U256::one()),
+            ])]),
+            Token::Array(vec![payment(eth(950), seller), payment(eth(50), creator)]),
+        ]);
+        let topics = [order_fulfilled_topic(), H256::from(seller), H256::zero()];
+
+        let sale = decode_seaport_sale(seaport, &topics, &data).unwrap();
+        assert_eq!((sale.collection, sale.token_id, sale.seller, sale.buyer), (collection, U256::from(7), seller, buyer));
+        assert_eq!(sale.price(), eth(1_000));
+
+        let royalty = RoyaltyConfig::new(creator, 500).unwrap();
+        assert_eq!(sale.paid_to(creator), royalty.royalty_for(sale.price()));
+    }
+}
@@ -2,14 +2,35 @@
 //!
 //! Connexion sécurisée au moteur OpenAI pour enrichir les décisions stratégiques
 //! et guider la réflexion autonome de l'entité AURORAE++.
+//! Les plongements passent par `EmbeddingProvider`; `BatchedEmbedder` découpe les textes en
+//! lots, espace les requêtes (limite par minute) et réessaie les erreurs transitoires.
 
-use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::json;
+use tracing::info;
+
+use crate::llm::{chat_body, parse_chat, parse_embeddings, post_json, LlmProvider, LlmRequest, LlmResponse};
+
+/// Délai avant la première nouvelle tentative, doublé ensuite
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Fournisseur de plongements vectoriels (OpenAI, modèle local…)
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Identifie le modèle: des vecteurs de modèles différents ne se comparent pas
+    fn name(&self) -> &str;
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String>;
+}
 
 pub struct OpenAIBridge {
     pub api_key: String,
     pub client: Client,
+    pub model: String,
+    pub embedding_model: String,
 }
 
 impl OpenAIBridge {
@@ -17,37 +38,169 @@ impl OpenAIBridge {
         Self {
             api_key: api_key.to_string(),
             client: Client::new(),
+            model: "gpt-4".to_string(),
+            embedding_model: "text-embedding-3-small".to_string(),
         }
     }
 
     /// 🧠 Envoie une requête à OpenAI pour obtenir une stratégie
     pub async fn ask_strategy(&self, question: &str) -> Result<String, String> {
-        let endpoint = "https://api.openai.com/v1/chat/completions";
-
-        let body = json!({
-            "model": "gpt-4",
-            "messages": [
-                {"role": "system", "content": "Tu es un stratège IA pour une entité blockchain vivante et autonome."},
-                {"role": "user", "content": question}
-            ],
-            "max_tokens": 512,
-            "temperature": 0.7
-        });
-
-        let res = self.client
-            .post(endpoint)
-            .bearer_auth(&self.api_key)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| format!("Erreur réseau: {}", e))?;
-
-        let data: serde_json::Value = res.json().await.map_err(|e| format!("Erreur JSON: {}", e))?;
-
-        if let Some(content) = data["choices"][0]["message"]["content"].as_str() {
-            Ok(content.to_string())
+        let request = LlmRequest::new("Tu es un stratège IA pour une entité blockchain vivante et autonome.", question);
+        self.complete(&request).await.map(|response| response.text)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OpenAIBridge {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn default_model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: &LlmRequest) -> Result<LlmResponse, String> {
+        let model = self.model_for(request);
+        let builder = self.client
+            .post("https://api.openai.com/v1/chat/completions")
+            .bearer_auth(&self.api_key);
+        parse_chat(&post_json(builder, &chat_body(request, Some(model))).await?, model)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAIBridge {
+    fn name(&self) -> &str {
+        &self.embedding_model
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let body = json!({ "model": self.embedding_model, "input": texts });
+        let builder = self.client
+            .post("https://api.openai.com/v1/embeddings")
+            .bearer_auth(&self.api_key);
+        parse_embeddings(&post_json(builder, &body).await?, texts.len())
+    }
+}
+
+/// Erreur réseau, limite de débit (429) ou erreur serveur (5xx): la requête peut être rejouée
+pub fn is_transient(error: &str) -> bool {
+    error.starts_with("Erreur réseau") || error.starts_with("Erreur 429") || error.starts_with("Erreur 5")
+}
+
+/// Plongements par lots, avec limite de débit et nouvelles tentatives
+pub struct BatchedEmbedder {
+    inner: Arc<dyn EmbeddingProvider>,
+    pub batch_size: usize,
+    pub max_retries: u32,
+    /// Intervalle minimal entre deux requêtes (0: pas de limite)
+    min_interval: Duration,
+    last_request: tokio::sync::Mutex<Option<Instant>>,
+}
+
+impl BatchedEmbedder {
+    pub fn new(inner: Arc<dyn EmbeddingProvider>, batch_size: usize, max_retries: u32, requests_per_minute: u32) -> Self {
+        let min_interval = if requests_per_minute == 0 {
+            Duration::ZERO
         } else {
-            Err("Aucune réponse valide obtenue".into())
+            Duration::from_secs(60) / requests_per_minute
+        };
+        Self {
+            inner,
+            batch_size: batch_size.max(1),
+            max_retries,
+            min_interval,
+            last_request: tokio::sync::Mutex::new(None),
         }
     }
+
+    /// Attend le créneau suivant de la limite de débit
+    async fn throttle(&self) {
+        let mut last = self.last_request.lock().await;
+        if let Some(previous) = *last {
+            let ready = previous + self.min_interval;
+            let now = Instant::now();
+            if ready > now {
+                tokio::time::sleep(ready - now).await;
+            }
+        }
+        *last = Some(Instant::now());
+    }
+
+    async fn embed_batch(&self, batch: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let mut attempt = 0;
+        loop {
+            self.throttle().await;
+            match self.inner.embed(batch).await {
+                Ok(vectors) if vectors.len() == batch.len() => return Ok(vectors),
+                Ok(vectors) => return Err(format!("{} plongement(s) pour {} texte(s)", vectors.len(), batch.len())),
+                Err(e) if attempt < self.max_retries && is_transient(&e) => {
+                    let delay = RETRY_BASE_DELAY * 2u32.pow(attempt);
+                    attempt += 1;
+                    info!("⏳ Plongements {}: {} — tentative {}/{} dans {:?}", self.inner.name(), e, attempt, self.max_retries, delay);
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for BatchedEmbedder {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.batch_size) {
+            vectors.extend(self.embed_batch(batch).await?);
+        }
+        Ok(vectors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Échoue (429) au premier appel, puis renvoie un vecteur par texte
+    #[derive(Default)]
+    struct FlakyEmbedder {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for FlakyEmbedder {
+        fn name(&self) -> &str {
+            "instable"
+        }
+
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err("Erreur 429 Too Many Requests: limite atteinte".to_string());
+            }
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn batches_and_retries_transient_failures() {
+        let flaky = Arc::new(FlakyEmbedder::default());
+        let embedder = BatchedEmbedder::new(flaky.clone(), 2, 2, 600);
+        let texts: Vec<String> = ["a", "bb", "ccc", "dddd", "eeeee"].iter().map(|t| t.to_string()).collect();
+
+        let vectors = embedder.embed(&texts).await.unwrap();
+        assert_eq!(vectors, vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0], vec![5.0]]);
+        // Un échec rejoué, puis trois lots
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 4);
+
+        assert!(is_transient("Erreur réseau: délai dépassé"));
+        assert!(!is_transient("Erreur 401 Unauthorized: clé invalide"));
+        let strict = BatchedEmbedder::new(Arc::new(FlakyEmbedder::default()), 8, 0, 0);
+        assert!(strict.embed(&texts).await.is_err());
+    }
 }
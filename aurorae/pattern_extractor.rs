@@ -1,45 +1,96 @@
 // pattern_extractor.rs
 //! Module pour extraire des patterns de code à partir de dépôts GitHub et les enregistrer dans `knowledge.rs`.
+//! Les éléments sont reconnus par l'analyse syntaxique de `syntax_tree` (Rust, Python, Go, Solidity).
+//! Après une mise à jour du feed, seuls les fichiers modifiés signalés par le crawler sont ré-analysés.
+//! Les fichiers des dépôts dont la licence est refusée par `licenses.toml` ne sont pas analysés.
 
 use std::fs::read_dir;
 use std::path::{Path, PathBuf};
-use std::fs::File;
-use std::io::Read;
-use crate::knowledge::{Memory, Pattern};
-use regex::Regex;
+use tracing::{info, warn};
+use crate::crawler::FeedChanges;
+use crate::knowledge::{KnowledgeBase, Pattern};
+use crate::license::LicensePolicy;
+use crate::syntax_tree::{CodeItem, ItemKind, SourceLanguage};
 
-/// Extrait des patterns de code à partir d'un dossier de fichiers Rust.
-pub fn extract_patterns_from_directory(dir: &Path) -> Vec<Pattern> {
+pub use crate::syntax_tree::extract_items;
+
+/// Extrait des patterns de code à partir d'un dossier de fichiers sources: un pattern par
+/// fichier, avec le décompte de ses fonctions, structures, traits et énumérations.
+/// Les fichiers dont la licence est refusée par `policy` sont ignorés.
+pub fn extract_patterns_from_directory(dir: &Path, policy: &LicensePolicy) -> Vec<Pattern> {
     let mut patterns = Vec::new();
-    let files = find_rust_files(dir);
-
-    let pattern_regex = Regex::new(r"(?m)^\s*pub\s*(fn|struct|trait|enum)\s+").unwrap();
-
-    for file in files {
-        let content = read_file_content(&file);
-        let matches = pattern_regex.find_iter(&content);
-
-        for _ in matches {
-            patterns.push(Pattern {
-                module_name: file.to_string_lossy().to_string(),
-                functions: 0, // Vous pouvez affiner ces valeurs plus tard
-                structs: 0,
-                traits: 0,
-                enums: 0,
-            });
+
+    for file in find_source_files(dir).into_iter().filter(|file| policy.admits_path(file)) {
+        match extract_items(&file) {
+            Ok(items) if !items.is_empty() => patterns.push(pattern_from_items(&file, &items)),
+            Ok(_) => {}
+            Err(e) => warn!("⚠️ Extraction impossible: {}", e),
         }
     }
 
     patterns
 }
 
-/// Récupère tous les fichiers `.rs` dans un répertoire
-fn find_rust_files(base: &Path) -> Vec<PathBuf> {
+/// Met à jour la base de savoir d'après les fichiers du feed signalés par le crawler: seuls
+/// les fichiers sources ajoutés ou modifiés sont ré-analysés, ceux supprimés (ou désormais
+/// vides) sont oubliés. Retourne les patterns ajoutés ou mis à jour.
+pub fn apply_feed_changes(knowledge: &mut KnowledgeBase, changes: &FeedChanges) -> Vec<Pattern> {
+    let mut removed = 0;
+    for file in &changes.removed {
+        if knowledge.remove_pattern(&file.to_string_lossy()) {
+            removed += 1;
+        }
+    }
+
+    let mut updated = Vec::new();
+    for file in changes.changed.iter().filter(|file| SourceLanguage::from_path(file).is_some()) {
+        // Licence refusée (ou devenue refusée): le fichier n'est pas analysé et son pattern est oublié
+        if !knowledge.license_policy().admits_path(file) {
+            if knowledge.remove_pattern(&file.to_string_lossy()) {
+                removed += 1;
+            }
+            continue;
+        }
+        match extract_items(file) {
+            Ok(items) if !items.is_empty() => {
+                let pattern = pattern_from_items(file, &items);
+                if knowledge.upsert_pattern(pattern.clone()) {
+                    updated.push(pattern);
+                }
+            }
+            Ok(_) => {
+                if knowledge.remove_pattern(&file.to_string_lossy()) {
+                    removed += 1;
+                }
+            }
+            Err(e) => warn!("⚠️ Extraction impossible: {}", e),
+        }
+    }
+
+    info!("🔎 Feed: {} pattern(s) mis à jour, {} retiré(s)", updated.len(), removed);
+    updated
+}
+
+/// Décompte des éléments d'un fichier (classes et contrats comptent comme structures,
+/// interfaces comme traits)
+pub fn pattern_from_items(file: &Path, items: &[CodeItem]) -> Pattern {
+    let count = |kinds: &[ItemKind]| items.iter().filter(|item| kinds.contains(&item.kind)).count();
+    Pattern {
+        module_name: file.to_string_lossy().to_string(),
+        functions: count(&[ItemKind::Function, ItemKind::Method]),
+        structs: count(&[ItemKind::Struct, ItemKind::Class, ItemKind::Contract]),
+        traits: count(&[ItemKind::Trait, ItemKind::Interface]),
+        enums: count(&[ItemKind::Enum]),
+    }
+}
+
+/// Récupère tous les fichiers sources reconnus (`.rs`, `.py`, `.go`, `.sol`) d'un répertoire
+fn find_source_files(base: &Path) -> Vec<PathBuf> {
     let mut results = Vec::new();
     if let Ok(entries) = read_dir(base) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.is_file() && path.extension().map(|ext| ext == "rs").unwrap_or(false) {
+            if path.is_file() && SourceLanguage::from_path(&path).is_some() {
                 results.push(path);
             }
         }
@@ -47,10 +98,52 @@ fn find_rust_files(base: &Path) -> Vec<PathBuf> {
     results
 }
 
-/// Lit le contenu d'un fichier
-fn read_file_content(file: &PathBuf) -> String {
-    let mut file_content = String::new();
-    let mut file = File::open(file).unwrap();
-    file.read_to_string(&mut file_content).unwrap();
-    file_content
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::SourceProvenance;
+
+    #[test]
+    fn feed_changes_update_and_forget_patterns() {
+        let dir = std::env::temp_dir().join(format!("aurorae-patterns-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("lib.rs");
+        std::fs::write(&file, "pub struct A;\npub fn a() {}\n").unwrap();
+        let mut knowledge = KnowledgeBase::default();
+
+        let changes = FeedChanges { changed: vec![file.clone(), dir.join("notes.txt")], removed: Vec::new() };
+        assert_eq!(apply_feed_changes(&mut knowledge, &changes).len(), 1);
+        std::fs::write(&file, "pub struct A;\npub fn a() {}\npub fn b() {}\n").unwrap();
+        apply_feed_changes(&mut knowledge, &changes);
+        assert_eq!(knowledge.records.len(), 1);
+        assert_eq!(knowledge.records[0].functions, 2);
+
+        apply_feed_changes(&mut knowledge, &FeedChanges { changed: Vec::new(), removed: vec![file] });
+        assert!(knowledge.records.is_empty());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn sources_with_a_denied_license_are_not_extracted() {
+        let dir = std::env::temp_dir().join(format!("aurorae-patterns-{}", uuid::Uuid::new_v4()));
+        let (gpl, mit) = (dir.join("gpl_repo"), dir.join("mit_repo"));
+        for (repo, license) in [(&gpl, "GPL-3.0-or-later"), (&mit, "MIT")] {
+            std::fs::create_dir_all(repo).unwrap();
+            std::fs::write(repo.join("lib.rs"), "pub struct A;\npub fn a() {}\n").unwrap();
+            SourceProvenance { repo: "https://example.org/repo".to_string(), commit: "abc".to_string(), license: Some(license.to_string()) }
+                .save_in(repo)
+                .unwrap();
+        }
+        let policy = LicensePolicy::default();
+        assert!(extract_patterns_from_directory(&gpl, &policy).is_empty());
+        assert_eq!(extract_patterns_from_directory(&mit, &policy).len(), 1);
+
+        let mut knowledge = KnowledgeBase::default();
+        let changes = FeedChanges { changed: vec![gpl.join("lib.rs"), mit.join("lib.rs")], removed: Vec::new() };
+        let updated = apply_feed_changes(&mut knowledge, &changes);
+        assert_eq!(updated.len(), 1);
+        assert!(updated[0].module_name.contains("mit_repo"));
+        assert!(!knowledge.admits(&pattern_from_items(&gpl.join("lib.rs"), &[])));
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,340 @@
+//! AURORAE++ - prompts.rs
+//!
+//! Magasin de prompts nommés et versionnés: chaque version est un fichier
+//! `prompts/<nom>/v<version>.toml` (`system`, `template`, `weight`) dont le gabarit interpole
+//! des variables `{{nom}}`. Les versions actives d'un même prompt sont tirées selon leur poids
+//! (test A/B) et chaque résultat est noté par version (`aurorae_state/prompt_outcomes.json`)
+//! pour que la métacognition corrèle version et qualité.
+
+use std::collections::BTreeMap;
+use std::fs::{self, create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::llm::LlmRequest;
+
+pub const PROMPTS_DIR: &str = "prompts";
+pub const PROMPT_OUTCOMES_PATH: &str = "aurorae_state/prompt_outcomes.json";
+/// Résultats conservés par le magasin
+const MAX_OUTCOMES: usize = 5000;
+
+lazy_static! {
+    pub static ref PROMPTS: RwLock<PromptStore> = RwLock::new(PromptStore::load_or_default());
+}
+
+/// Une version d'un prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct PromptTemplate {
+    /// Déduits du chemin du fichier
+    #[serde(skip)]
+    pub name: String,
+    #[serde(skip)]
+    pub version: u32,
+    pub description: String,
+    pub system: String,
+    /// Message utilisateur, variables `{{nom}}`
+    pub template: String,
+    /// Part relative des tirages entre versions actives
+    pub weight: f64,
+    pub active: bool,
+}
+
+impl Default for PromptTemplate {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            version: 1,
+            description: String::new(),
+            system: String::new(),
+            template: String::new(),
+            weight: 1.0,
+            active: true,
+        }
+    }
+}
+
+impl PromptTemplate {
+    pub fn from_toml(name: &str, version: u32, content: &str) -> Result<Self, String> {
+        let mut template: Self = toml::from_str(content).map_err(|e| format!("Prompt {} v{} invalide: {}", name, version, e))?;
+        template.name = name.to_string();
+        template.version = version;
+        Ok(template)
+    }
+
+    /// Variables attendues par le gabarit (système et message)
+    pub fn variables(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for text in [&self.system, &self.template] {
+            let mut rest = text.as_str();
+            while let Some(start) = rest.find("{{") {
+                let Some(end) = rest[start..].find("}}") else { break };
+                let name = rest[start + 2..start + end].trim().to_string();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+                rest = &rest[start + end + 2..];
+            }
+        }
+        names
+    }
+
+    /// Remplace chaque `{{nom}}`; une variable manquante est une erreur
+    pub fn render(&self, vars: &BTreeMap<&str, String>) -> Result<RenderedPrompt, String> {
+        let missing: Vec<String> = self.variables().into_iter().filter(|name| !vars.contains_key(name.as_str())).collect();
+        if !missing.is_empty() {
+            return Err(format!("Prompt {} v{}: variables manquantes {}", self.name, self.version, missing.join(", ")));
+        }
+        let interpolate = |text: &str| {
+            vars.iter().fold(text.to_string(), |acc, (name, value)| {
+                acc.replace(&format!("{{{{{}}}}}", name), value).replace(&format!("{{{{ {} }}}}", name), value)
+            })
+        };
+        Ok(RenderedPrompt {
+            name: self.name.clone(),
+            version: self.version,
+            system: interpolate(&self.system),
+            prompt: interpolate(&self.template),
+        })
+    }
+}
+
+/// Prompt prêt à l'envoi, avec la version qui l'a produit
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedPrompt {
+    pub name: String,
+    pub version: u32,
+    pub system: String,
+    pub prompt: String,
+}
+
+impl RenderedPrompt {
+    pub fn request(&self) -> LlmRequest {
+        LlmRequest::new(&self.system, &self.prompt)
+    }
+
+    /// Étiquette `nom@vN` portée par les pensées issues de ce prompt
+    pub fn label(&self) -> String {
+        format!("{}@v{}", self.name, self.version)
+    }
+}
+
+/// Qualité (0 à 1) du résultat obtenu avec une version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptOutcome {
+    pub at: DateTime<Utc>,
+    pub name: String,
+    pub version: u32,
+    pub quality: f32,
+}
+
+/// Bilan d'une version
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptVersionStats {
+    pub version: u32,
+    pub uses: usize,
+    pub mean_quality: f32,
+}
+
+/// Prompts par nom (versions croissantes) et résultats notés
+#[derive(Debug, Default)]
+pub struct PromptStore {
+    templates: BTreeMap<String, Vec<PromptTemplate>>,
+    pub outcomes: Vec<PromptOutcome>,
+    outcomes_path: Option<PathBuf>,
+}
+
+impl PromptStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Charge `dir/<nom>/v<version>.toml`; les fichiers mal nommés ou invalides sont ignorés
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let mut store = Self::new();
+        let entries = fs::read_dir(dir).map_err(|e| format!("Erreur lecture {}: {}", dir.display(), e))?;
+        for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            for file in fs::read_dir(entry.path()).into_iter().flatten().flatten() {
+                let path = file.path();
+                let version = path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.strip_prefix('v'))
+                    .and_then(|v| v.parse::<u32>().ok());
+                let Some(version) = version.filter(|_| path.extension().is_some_and(|ext| ext == "toml")) else {
+                    continue;
+                };
+                match fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|c| PromptTemplate::from_toml(&name, version, &c)) {
+                    Ok(template) => store.insert(template),
+                    Err(e) => warn!("⚠️ {}: {}", path.display(), e),
+                }
+            }
+        }
+        Ok(store)
+    }
+
+    /// `prompts/`, complété par les prompts intégrés absents, et résultats déjà notés
+    pub fn load_or_default() -> Self {
+        let mut store = Self::load(Path::new(PROMPTS_DIR)).unwrap_or_else(|e| {
+            warn!("⚠️ {}: prompts intégrés", e);
+            Self::new()
+        });
+        for template in Self::builtin() {
+            if !store.templates.contains_key(&template.name) {
+                store.insert(template);
+            }
+        }
+        store.outcomes = File::open(PROMPT_OUTCOMES_PATH)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        store.outcomes_path = Some(PathBuf::from(PROMPT_OUTCOMES_PATH));
+        store
+    }
+
+    /// Prompts utilisés sans dossier `prompts/`
+    pub fn builtin() -> Vec<PromptTemplate> {
+        vec![PromptTemplate {
+            name: "strategist".to_string(),
+            version: 1,
+            description: "Prochaine action vers l'objectif prioritaire".to_string(),
+            system: "Tu es un stratégiste cognitif pour une IA post-humaniste.".to_string(),
+            template: "L'IA AURORAE++ veut accomplir cet objectif : {{objective}}. Priorité {{priority}}. Raison : {{rationale}}.\n\nQuelle serait la prochaine pensée logique à ajouter dans son cerveau pour progresser ? Donne-moi juste l'action en verbe infinitif.".to_string(),
+            ..PromptTemplate::default()
+        }]
+    }
+
+    /// Ajoute ou remplace une version
+    pub fn insert(&mut self, template: PromptTemplate) {
+        let versions = self.templates.entry(template.name.clone()).or_default();
+        versions.retain(|t| t.version != template.version);
+        versions.push(template);
+        versions.sort_by_key(|t| t.version);
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.templates.keys().map(String::as_str).collect()
+    }
+
+    pub fn versions(&self, name: &str) -> &[PromptTemplate] {
+        self.templates.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn get(&self, name: &str, version: u32) -> Option<&PromptTemplate> {
+        self.versions(name).iter().find(|t| t.version == version)
+    }
+
+    pub fn latest(&self, name: &str) -> Option<&PromptTemplate> {
+        self.versions(name).iter().rev().find(|t| t.active)
+    }
+
+    /// Tire une version active selon les poids (test A/B)
+    pub fn select(&self, name: &str) -> Option<&PromptTemplate> {
+        let active: Vec<&PromptTemplate> = self.versions(name).iter().filter(|t| t.active && t.weight > 0.0).collect();
+        let total: f64 = active.iter().map(|t| t.weight).sum();
+        if total <= 0.0 {
+            return self.latest(name);
+        }
+        let mut draw = rand::thread_rng().gen_range(0.0..total);
+        for template in &active {
+            if draw < template.weight {
+                return Some(template);
+            }
+            draw -= template.weight;
+        }
+        active.last().copied()
+    }
+
+    /// Tire puis rend le prompt `name`
+    pub fn render(&self, name: &str, vars: &BTreeMap<&str, String>) -> Result<RenderedPrompt, String> {
+        self.select(name).ok_or_else(|| format!("Prompt inconnu: {}", name))?.render(vars)
+    }
+
+    /// Note le résultat obtenu avec `name` en version `version`
+    pub fn record_outcome(&mut self, name: &str, version: u32, quality: f32) {
+        self.outcomes.push(PromptOutcome {
+            at: Utc::now(),
+            name: name.to_string(),
+            version,
+            quality: quality.clamp(0.0, 1.0),
+        });
+        if self.outcomes.len() > MAX_OUTCOMES {
+            let excess = self.outcomes.len() - MAX_OUTCOMES;
+            self.outcomes.drain(..excess);
+        }
+        self.save_outcomes();
+    }
+
+    /// Qualité moyenne par version de `name`
+    pub fn stats(&self, name: &str) -> Vec<PromptVersionStats> {
+        let mut totals: BTreeMap<u32, (usize, f32)> = BTreeMap::new();
+        for outcome in self.outcomes.iter().filter(|o| o.name == name) {
+            let entry = totals.entry(outcome.version).or_default();
+            entry.0 += 1;
+            entry.1 += outcome.quality;
+        }
+        totals
+            .into_iter()
+            .map(|(version, (uses, sum))| PromptVersionStats { version, uses, mean_quality: sum / uses as f32 })
+            .collect()
+    }
+
+    fn save_outcomes(&self) {
+        let Some(path) = &self.outcomes_path else { return };
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self.outcomes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_versions_and_renders_variables() {
+        let dir = std::env::temp_dir().join(format!("aurorae-prompts-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("strategist")).unwrap();
+        fs::write(dir.join("strategist/v1.toml"), "system = \"Stratège\"\ntemplate = \"Objectif {{objective}}, priorité {{ priority }}\"\n").unwrap();
+        fs::write(dir.join("strategist/v2.toml"), "system = \"Stratège\"\ntemplate = \"{{objective}}\"\nactive = false\n").unwrap();
+        fs::write(dir.join("strategist/notes.toml"), "ignoré").unwrap();
+
+        let store = PromptStore::load(&dir).unwrap();
+        assert_eq!(store.versions("strategist").len(), 2);
+        assert_eq!(store.latest("strategist").unwrap().version, 1);
+
+        let mut vars = BTreeMap::new();
+        vars.insert("objective", "SeekKnowledge".to_string());
+        assert!(store.render("strategist", &vars).unwrap_err().contains("priority"));
+        vars.insert("priority", "7".to_string());
+        let rendered = store.render("strategist", &vars).unwrap();
+        assert_eq!((rendered.prompt.as_str(), rendered.label()), ("Objectif SeekKnowledge, priorité 7", "strategist@v1".to_string()));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn weighted_selection_and_outcome_stats() {
+        let mut store = PromptStore::new();
+        for (version, weight) in [(1, 0.0), (2, 1.0)] {
+            store.insert(PromptTemplate { name: "dream".into(), version, weight, template: "rêve".into(), ..PromptTemplate::default() });
+        }
+        assert!((0..20).all(|_| store.select("dream").unwrap().version == 2));
+
+        store.record_outcome("dream", 1, 0.2);
+        store.record_outcome("dream", 2, 1.0);
+        store.record_outcome("dream", 2, 0.5);
+        let stats = store.stats("dream");
+        assert_eq!(stats[1], PromptVersionStats { version: 2, uses: 2, mean_quality: 0.75 });
+        assert_eq!(stats[0].uses, 1);
+    }
+}
@@ -1,17 +1,633 @@
-// refactor.rs
-//! Module de refactoring automatique pour optimiser le code généré et appliquer des règles de formatage.
-
-use std::process::Command;
-
-/// Effectue un refactoring du code en utilisant `rustfmt` pour appliquer des règles de formatage.
-pub fn refactor_code(code: &str) -> String {
-    // Appel à rustfmt pour formater le code
-    let output = Command::new("rustfmt")
-        .arg("--emit=stdout")
-        .stdin(std::process::Stdio::piped())
-        .output()
-        .expect("Échec de l'exécution de rustfmt");
-
-    // Retourner le code formaté
-    String::from_utf8_lossy(&output.stdout).to_string()
+//! AURORAE++ - refactor.rs
+//!
+//! Refactorisations préservant la sémantique: mise en forme par rustfmt, renommage d'un
+//! élément dans plusieurs fichiers (AST syn), extraction de fonction sur une plage balisée
+//! et suppression des imports inutilisés signalés par rustc. Chaque refactorisation est
+//! d'abord proposée avec son diff unifié, puis vérifiée, écrite et historisée comme
+//! `CodeModification`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Expr, FnArg, Item, ItemFn, Pat, Stmt};
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::audit::{self, AuditKind};
+use crate::clippy_integration::{apply_to_source, ClippyRunner, Suggestion};
+use crate::code_evolution::{CodeModification, VerificationStatus};
+use crate::mutation::{resolve, verify_sources, SourceMap};
+use crate::rollback::record_source_change;
+use crate::security::{log_security_event, SecurityEvent};
+
+/// Début d'une plage à extraire, suivi du nom de la fonction: `// aurorae:extract nom`
+pub const EXTRACT_BEGIN: &str = "// aurorae:extract";
+/// Fin de la plage à extraire
+pub const EXTRACT_END: &str = "// aurorae:end-extract";
+
+/// Types dont une copie passée à la fonction extraite laisse l'original utilisable
+const COPY_TYPES: &[&str] = &[
+    "bool", "char", "u8", "u16", "u32", "u64", "u128", "usize",
+    "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RefactorKind {
+    Format,
+    Rename,
+    ExtractFunction,
+    RemoveUnusedImports,
+}
+
+/// Contenu d'un fichier avant et après refactorisation
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+}
+
+/// Refactorisation proposée, relue via `diff()` avant `commit()`
+#[derive(Debug, Clone)]
+pub struct Refactoring {
+    pub kind: RefactorKind,
+    pub description: String,
+    pub changes: Vec<FileChange>,
+}
+
+impl Refactoring {
+    fn new(kind: RefactorKind, description: String) -> Self {
+        Self { kind, description, changes: Vec::new() }
+    }
+
+    fn push(&mut self, path: &Path, original: &str, updated: String) {
+        if original != updated {
+            self.changes.push(FileChange { path: path.to_path_buf(), original: original.to_string(), updated });
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Diff unifié de tous les fichiers touchés
+    pub fn diff(&self) -> String {
+        self.changes.iter()
+            .map(|change| {
+                let file = change.path.display().to_string();
+                TextDiff::from_lines(&change.original, &change.updated)
+                    .unified_diff()
+                    .context_radius(3)
+                    .header(&format!("a/{}", file), &format!("b/{}", file))
+                    .to_string()
+            })
+            .collect()
+    }
+
+    pub fn to_modification(&self) -> CodeModification {
+        CodeModification {
+            id: Uuid::new_v4(),
+            timestamp: SystemTime::now(),
+            target_file: self.changes.iter().map(|c| c.path.display().to_string()).collect::<Vec<_>>().join(", "),
+            description: self.description.clone(),
+            changes: self.diff(),
+            purpose: format!("refactorisation {:?}", self.kind),
+            inspiration_sources: Vec::new(),
+            verification_status: VerificationStatus::Pending,
+            performance_impact: None,
+        }
+    }
+
+    /// Vérifie la compilation de l'ensemble des fichiers modifiés, les écrit, puis historise
+    /// la modification (rollback, audit avec diff)
+    pub fn commit(&self) -> Result<CodeModification, String> {
+        if self.is_empty() {
+            return Err("Refactorisation sans changement".to_string());
+        }
+        let sources: Vec<(&Path, &str)> = self.changes.iter().map(|c| (c.path.as_path(), c.updated.as_str())).collect();
+        verify_sources(&sources)?;
+
+        let mut modification = self.to_modification();
+        modification.verification_status = VerificationStatus::VerifiedSafe;
+        for change in &self.changes {
+            let file = change.path.display().to_string();
+            fs::write(&change.path, &change.updated).map_err(|e| format!("Erreur écriture {}: {}", file, e))?;
+            log_security_event(SecurityEvent::FileModified { module: "refactor".to_string(), path: file.clone() });
+            record_source_change(modification.id, &file, &self.description, "refactor");
+        }
+        audit::record(
+            AuditKind::CodeMutated,
+            "refactor",
+            &self.description,
+            serde_json::json!({ "modification": modification.id, "kind": self.kind, "files": modification.target_file, "diff": modification.changes }),
+        );
+        info!("🔧 Refactorisation {:?} appliquée: {}", self.kind, self.description);
+        Ok(modification)
+    }
+}
+
+fn apply_edits(source: &str, mut edits: Vec<(usize, usize, String)>) -> String {
+    edits.sort_by_key(|(start, _, _)| std::cmp::Reverse(*start));
+    edits.dedup_by_key(|(start, _, _)| *start);
+    let mut updated = source.to_string();
+    for (start, end, text) in edits {
+        updated.replace_range(start..end, &text);
+    }
+    updated
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))
+}
+
+/// Identifiants présents dans les jetons d'une macro (non analysés par syn)
+fn token_idents(tokens: TokenStream, out: &mut Vec<(String, Span)>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => out.push((ident.to_string(), ident.span())),
+            TokenTree::Group(group) => token_idents(group.stream(), out),
+            _ => {}
+        }
+    }
+}
+
+// ==================== MISE EN FORME ====================
+
+/// Met en forme un code source avec `rustfmt` (édition 2021)
+pub fn format_source(code: &str) -> Result<String, String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--emit=stdout", "--edition=2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Exécution de rustfmt impossible: {}", e))?;
+    child.stdin.take()
+        .ok_or_else(|| "Entrée de rustfmt indisponible".to_string())?
+        .write_all(code.as_bytes())
+        .map_err(|e| format!("Erreur d'envoi à rustfmt: {}", e))?;
+    let output = child.wait_with_output().map_err(|e| format!("Erreur rustfmt: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("rustfmt a échoué: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub fn format_file(path: &Path) -> Result<Refactoring, String> {
+    let original = read(path)?;
+    let mut refactoring = Refactoring::new(RefactorKind::Format, format!("mise en forme de {}", path.display()));
+    refactoring.push(path, &original, format_source(&original)?);
+    Ok(refactoring)
+}
+
+// ==================== RENOMMAGE ====================
+
+/// Occurrences d'un élément dans un fichier: déclarations d'items, chemins, imports et macros
+struct RenameCollector<'a> {
+    map: &'a SourceMap<'a>,
+    from: &'a str,
+    to: &'a str,
+    edits: Vec<(usize, usize, String)>,
+    declared: bool,
+    /// Raison pour laquelle le renommage ne serait pas sûr
+    conflict: Option<String>,
+}
+
+impl RenameCollector<'_> {
+    fn rename(&mut self, ident: &syn::Ident) {
+        if ident == self.from {
+            let (start, end) = self.map.range(ident.span());
+            self.edits.push((start, end, self.to.to_string()));
+        }
+    }
+
+    fn declare(&mut self, ident: &syn::Ident) {
+        if ident == self.from {
+            self.declared = true;
+        }
+    }
+
+    /// Un homonyme qui n'est pas un item (variante, champ, méthode) rendrait les chemins ambigus
+    fn homonym(&mut self, ident: &syn::Ident, what: &str) {
+        if ident == self.from {
+            self.conflict.get_or_insert_with(|| format!("{} est aussi un(e) {} (ligne {})", self.from, what, ident.span().start().line));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for RenameCollector<'_> {
+    fn visit_ident(&mut self, ident: &'ast syn::Ident) {
+        if ident == self.to {
+            self.conflict.get_or_insert_with(|| format!("{} existe déjà (ligne {})", self.to, ident.span().start().line));
+        }
+    }
+
+    fn visit_item(&mut self, node: &'ast Item) {
+        let ident = match node {
+            Item::Fn(item) => Some(&item.sig.ident),
+            Item::Struct(item) => Some(&item.ident),
+            Item::Enum(item) => Some(&item.ident),
+            Item::Union(item) => Some(&item.ident),
+            Item::Trait(item) => Some(&item.ident),
+            Item::Type(item) => Some(&item.ident),
+            Item::Const(item) => Some(&item.ident),
+            Item::Static(item) => Some(&item.ident),
+            _ => None,
+        };
+        if let Some(ident) = ident {
+            self.declare(ident);
+            self.rename(ident);
+        }
+        visit::visit_item(self, node);
+    }
+
+    fn visit_variant(&mut self, node: &'ast syn::Variant) {
+        self.homonym(&node.ident, "variante");
+        visit::visit_variant(self, node);
+    }
+
+    fn visit_field(&mut self, node: &'ast syn::Field) {
+        if let Some(ident) = &node.ident {
+            self.homonym(ident, "champ");
+        }
+        visit::visit_field(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.homonym(&node.sig.ident, "méthode");
+        visit::visit_impl_item_fn(self, node);
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast syn::TraitItemFn) {
+        self.homonym(&node.sig.ident, "méthode");
+        visit::visit_trait_item_fn(self, node);
+    }
+
+    fn visit_pat_ident(&mut self, node: &'ast syn::PatIdent) {
+        self.homonym(&node.ident, "variable locale");
+        visit::visit_pat_ident(self, node);
+    }
+
+    fn visit_path_segment(&mut self, node: &'ast syn::PathSegment) {
+        self.rename(&node.ident);
+        visit::visit_path_segment(self, node);
+    }
+
+    fn visit_use_name(&mut self, node: &'ast syn::UseName) {
+        self.rename(&node.ident);
+        visit::visit_use_name(self, node);
+    }
+
+    fn visit_use_rename(&mut self, node: &'ast syn::UseRename) {
+        self.rename(&node.ident);
+        visit::visit_use_rename(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        let mut idents = Vec::new();
+        token_idents(node.tokens.clone(), &mut idents);
+        for (name, span) in idents {
+            if name == self.to {
+                self.conflict.get_or_insert_with(|| format!("{} existe déjà (ligne {})", self.to, span.start().line));
+            } else if name == self.from {
+                let (start, end) = self.map.range(span);
+                self.edits.push((start, end, self.to.to_string()));
+            }
+        }
+        visit::visit_macro(self, node);
+    }
+}
+
+/// Renomme un élément (fonction, type, trait, constante…) dans des sources données
+pub fn rename_sources(sources: &[(PathBuf, String)], from: &str, to: &str) -> Result<Refactoring, String> {
+    syn::parse_str::<syn::Ident>(to).map_err(|_| format!("{} n'est pas un identifiant valide", to))?;
+    let mut refactoring = Refactoring::new(RefactorKind::Rename, format!("renommage de {} en {}", from, to));
+    let mut declared = false;
+    for (path, source) in sources {
+        let file = syn::parse_file(source).map_err(|e| format!("{} non analysable: {}", path.display(), e))?;
+        let map = SourceMap::new(source);
+        let mut collector = RenameCollector { map: &map, from, to, edits: Vec::new(), declared: false, conflict: None };
+        collector.visit_file(&file);
+        if let Some(conflict) = collector.conflict {
+            return Err(format!("Renommage non sûr dans {}: {}", path.display(), conflict));
+        }
+        declared |= collector.declared;
+        refactoring.push(path, source, apply_edits(source, collector.edits));
+    }
+    if !declared {
+        return Err(format!("Aucun élément {} déclaré dans ces fichiers", from));
+    }
+    Ok(refactoring)
+}
+
+/// Renomme un élément dans tous les fichiers donnés (fichiers `.rs` ou dossiers de modules)
+pub fn rename_item(files: &[PathBuf], from: &str, to: &str) -> Result<Refactoring, String> {
+    let sources = files.iter()
+        .map(|file| {
+            let path = resolve(&file.to_string_lossy());
+            read(&path).map(|source| (path, source))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    rename_sources(&sources, from, to)
+}
+
+// ==================== EXTRACTION DE FONCTION ====================
+
+/// Variables lues, liées et modifiées par un ensemble d'instructions
+#[derive(Default)]
+struct Usage {
+    used: BTreeSet<String>,
+    bound: BTreeSet<String>,
+    /// `return`, `?`, `.await`, ou `break`/`continue` hors d'une boucle de la plage
+    exits: bool,
+    loop_depth: usize,
+}
+
+impl<'ast> Visit<'ast> for Usage {
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        if let Some(ident) = node.path.get_ident() {
+            self.used.insert(ident.to_string());
+        }
+        visit::visit_expr_path(self, node);
+    }
+
+    fn visit_pat_ident(&mut self, node: &'ast syn::PatIdent) {
+        self.bound.insert(node.ident.to_string());
+        visit::visit_pat_ident(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        let mut idents = Vec::new();
+        token_idents(node.tokens.clone(), &mut idents);
+        self.used.extend(idents.into_iter().map(|(name, _)| name));
+        visit::visit_macro(self, node);
+    }
+
+    fn visit_expr_return(&mut self, _: &'ast syn::ExprReturn) {
+        self.exits = true;
+    }
+
+    fn visit_expr_try(&mut self, _: &'ast syn::ExprTry) {
+        self.exits = true;
+    }
+
+    fn visit_expr_await(&mut self, _: &'ast syn::ExprAwait) {
+        self.exits = true;
+    }
+
+    fn visit_expr_break(&mut self, node: &'ast syn::ExprBreak) {
+        self.exits |= self.loop_depth == 0 || node.label.is_some();
+        visit::visit_expr_break(self, node);
+    }
+
+    fn visit_expr_continue(&mut self, node: &'ast syn::ExprContinue) {
+        self.exits |= self.loop_depth == 0 || node.label.is_some();
+    }
+
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        let looping = matches!(node, Expr::Loop(_) | Expr::While(_) | Expr::ForLoop(_));
+        self.loop_depth += looping as usize;
+        visit::visit_expr(self, node);
+        self.loop_depth -= looping as usize;
+    }
+}
+
+fn usage<'a>(stmts: impl IntoIterator<Item = &'a Stmt>) -> Usage {
+    let mut usage = Usage::default();
+    for stmt in stmts {
+        usage.visit_stmt(stmt);
+    }
+    usage
+}
+
+/// Variable locale visible au début de la plage: (nom, type annoté, `mut`)
+fn local_binding(pat: &Pat, map: &SourceMap) -> Option<(String, Option<String>, bool)> {
+    match pat {
+        Pat::Ident(ident) if ident.subpat.is_none() && ident.by_ref.is_none() => {
+            Some((ident.ident.to_string(), None, ident.mutability.is_some()))
+        }
+        Pat::Type(typed) => local_binding(&typed.pat, map)
+            .map(|(name, _, mutable)| (name, Some(map.slice(typed.ty.span()).to_string()), mutable)),
+        _ => None,
+    }
+}
+
+fn is_copy(ty: &str) -> bool {
+    COPY_TYPES.contains(&ty) || (ty.starts_with('&') && !ty.starts_with("&mut"))
+}
+
+/// Plage balisée: (début de la ligne d'ouverture, fin de la ligne de fermeture, plage intérieure, nom)
+fn marked_range(source: &str) -> Result<Option<(usize, usize, usize, usize, String)>, String> {
+    let mut offset = 0;
+    let mut begin = None;
+    for line in source.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if let Some(name) = trimmed.strip_prefix(EXTRACT_BEGIN) {
+            begin = Some((offset, offset + line.len(), name.trim().to_string()));
+        } else if trimmed.starts_with(EXTRACT_END) {
+            let (start, inner_start, name) = begin.take().ok_or_else(|| format!("{} sans ouverture", EXTRACT_END))?;
+            return Ok(Some((start, offset + line.len(), inner_start, offset, name)));
+        }
+        offset += line.len();
+    }
+    match begin {
+        Some(_) => Err(format!("{} sans fermeture", EXTRACT_BEGIN)),
+        None => Ok(None),
+    }
+}
+
+/// Extrait la plage balisée d'une fonction de premier niveau vers une nouvelle fonction privée.
+/// Refusée si la plage coupe une instruction, quitte la fonction (`return`, `?`…), définit
+/// des variables utilisées ensuite, ou déplace une variable non `Copy` encore utilisée après.
+pub fn extract_in_source(path: &Path, source: &str) -> Result<Refactoring, String> {
+    let (outer_start, outer_end, inner_start, inner_end, name) = marked_range(source)?
+        .ok_or_else(|| format!("Aucune plage {} dans {}", EXTRACT_BEGIN, path.display()))?;
+    syn::parse_str::<syn::Ident>(&name).map_err(|_| format!("{} n'est pas un nom de fonction valide", name))?;
+    let file = syn::parse_file(source).map_err(|e| format!("{} non analysable: {}", path.display(), e))?;
+    let map = SourceMap::new(source);
+
+    let functions: Vec<&ItemFn> = file.items.iter().filter_map(|item| match item { Item::Fn(f) => Some(f), _ => None }).collect();
+    if functions.iter().any(|f| f.sig.ident == name) {
+        return Err(format!("La fonction {} existe déjà", name));
+    }
+    let item = functions.into_iter()
+        .find(|f| {
+            let (start, end) = map.range(f.block.span());
+            start < inner_start && inner_end < end
+        })
+        .ok_or_else(|| "La plage balisée doit se trouver dans une fonction de premier niveau".to_string())?;
+
+    // Instructions entièrement comprises dans la plage; aucune ne doit la chevaucher
+    let stmts = &item.block.stmts;
+    let mut selected = Vec::new();
+    for (index, stmt) in stmts.iter().enumerate() {
+        let (start, end) = map.range(stmt.span());
+        if start >= inner_start && end <= inner_end {
+            selected.push(index);
+        } else if start < inner_end && end > inner_start {
+            return Err(format!("La plage coupe une instruction (ligne {})", stmt.span().start().line));
+        }
+    }
+    let (first, last) = match (selected.first(), selected.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return Err("La plage balisée ne contient aucune instruction".to_string()),
+    };
+    let region = &stmts[first..=last];
+
+    // Variables visibles au début de la plage, dans l'ordre de déclaration
+    let mut visible: Vec<(String, Option<String>, bool)> = Vec::new();
+    let params = item.sig.inputs.iter().filter_map(|input| match input {
+        FnArg::Typed(typed) => Some(typed.as_ref()),
+        FnArg::Receiver(_) => None,
+    });
+    let before = stmts[..first].iter().filter_map(|stmt| match stmt {
+        Stmt::Local(local) => Some(&local.pat),
+        _ => None,
+    });
+    for binding in params.map(|typed| local_binding(&Pat::Type(typed.clone()), &map)).chain(before.map(|pat| local_binding(pat, &map))).flatten() {
+        visible.retain(|(name, _, _)| *name != binding.0);
+        visible.push(binding);
+    }
+
+    let inside = usage(region);
+    if inside.exits {
+        return Err("La plage quitte la fonction ou la boucle englobante".to_string());
+    }
+    let after = usage(&stmts[last + 1..]);
+    if let Some(escaped) = inside.bound.iter().find(|name| after.used.contains(*name)) {
+        return Err(format!("{} est définie dans la plage et utilisée après", escaped));
+    }
+
+    let mut inputs = Vec::new();
+    let mut args = Vec::new();
+    for (var, ty, mutable) in visible.iter().filter(|(var, _, _)| inside.used.contains(var)) {
+        let ty = ty.as_deref().ok_or_else(|| format!("Type de {} inconnu: annotez sa déclaration", var))?;
+        if after.used.contains(var) && (*mutable || !is_copy(ty)) {
+            return Err(format!("{} ({}) est encore utilisée après la plage", var, ty));
+        }
+        inputs.push(format!("{}{}: {}", if *mutable { "mut " } else { "" }, var, ty));
+        args.push(var.clone());
+    }
+
+    // Une plage terminant le bloc par une expression en devient la valeur de retour
+    let tail = last + 1 == stmts.len() && matches!(stmts[last], Stmt::Expr(_, None));
+    let output = match &item.sig.output {
+        syn::ReturnType::Type(_, ty) if tail => format!(" -> {}", map.slice(ty.span())),
+        _ => String::new(),
+    };
+    let body = &source[inner_start..inner_end];
+    let indent = body.lines().filter(|l| !l.trim().is_empty()).map(|l| l.len() - l.trim_start().len()).min().unwrap_or(0);
+    let marker = &source[outer_start..inner_start];
+    let outer_indent = &marker[..marker.len() - marker.trim_start().len()];
+    let reindented: Vec<String> = body.lines()
+        .map(|line| if line.trim().is_empty() { String::new() } else { format!("    {}", &line[indent..]) })
+        .collect();
+    let call = format!("{}{}({}){}\n", outer_indent, name, args.join(", "), if tail { "" } else { ";" });
+    let helper = format!("\n\nfn {}({}){} {{\n{}\n}}", name, inputs.join(", "), output, reindented.join("\n"));
+
+    let (_, item_end) = map.range(item.span());
+    let updated = apply_edits(source, vec![(outer_start, outer_end, call), (item_end, item_end, helper)]);
+    syn::parse_file(&updated).map_err(|e| format!("Extraction non analysable: {}", e))?;
+
+    let description = format!("extraction de {} depuis {} ({})", name, item.sig.ident, path.display());
+    let mut refactoring = Refactoring::new(RefactorKind::ExtractFunction, description);
+    refactoring.push(path, source, updated);
+    Ok(refactoring)
+}
+
+pub fn extract_function(path: &Path) -> Result<Refactoring, String> {
+    let path = resolve(&path.to_string_lossy());
+    extract_in_source(&path, &read(&path)?)
+}
+
+// ==================== IMPORTS INUTILISÉS ====================
+
+/// Retire les imports signalés `unused_imports` par rustc dans un espace de travail cargo
+/// (les imports de traits utilisés par leurs seules méthodes sont donc conservés)
+pub fn remove_unused_imports(workspace: &Path) -> Result<Refactoring, String> {
+    let report = ClippyRunner::new(workspace).run()?;
+    let mut by_file: BTreeMap<PathBuf, Vec<&Suggestion>> = BTreeMap::new();
+    for diagnostic in report.diagnostics.iter().filter(|d| d.lint.as_deref() == Some("unused_imports")) {
+        for suggestion in &diagnostic.suggestions {
+            by_file.entry(workspace.join(&suggestion.file)).or_default().push(suggestion);
+        }
+    }
+    let mut refactoring = Refactoring::new(RefactorKind::RemoveUnusedImports, format!("imports inutilisés de {}", workspace.display()));
+    for (path, suggestions) in by_file {
+        let original = read(&path)?;
+        let (updated, _) = apply_to_source(&original, &suggestions);
+        refactoring.push(&path, &original, updated);
+    }
+    Ok(refactoring)
+}
+
+/// Met en forme un module et écrit le résultat s'il compile; vrai si le fichier a changé
+pub fn refactor_module(path: &str) -> bool {
+    let result = format_file(&resolve(path)).and_then(|refactoring| {
+        if refactoring.is_empty() { Ok(None) } else { refactoring.commit().map(Some) }
+    });
+    match result {
+        Ok(modification) => modification.is_some(),
+        Err(e) => {
+            warn!("⚠️ Refactorisation de {} impossible: {}", path, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renames_items_across_files_and_refuses_unsafe_renames() {
+        let lib = "pub fn compute(x: u32) -> u32 {\n    x * 2\n}\n\npub struct Engine;\n".to_string();
+        let user = "use crate::lib::compute;\n\nfn run() {\n    let v = compute(2);\n    println!(\"{}\", compute(v));\n    let _ = crate::lib::compute;\n}\n".to_string();
+        let sources = vec![(PathBuf::from("lib.rs"), lib), (PathBuf::from("user.rs"), user)];
+
+        let refactoring = rename_sources(&sources, "compute", "double").unwrap();
+        assert_eq!(refactoring.changes.len(), 2);
+        assert!(refactoring.changes[0].updated.starts_with("pub fn double(x: u32)"));
+        let updated = &refactoring.changes[1].updated;
+        assert!(!updated.contains("compute"));
+        assert!(updated.contains("println!(\"{}\", double(v));"));
+        assert!(refactoring.diff().contains("-pub fn compute(x: u32) -> u32 {"));
+
+        assert!(rename_sources(&sources, "compute", "Engine").unwrap_err().contains("existe déjà"));
+        assert!(rename_sources(&sources, "missing", "other").is_err());
+        let shadowed = vec![(PathBuf::from("a.rs"), "fn v() {}\nfn f() { let v = 1; }\n".to_string())];
+        assert!(rename_sources(&shadowed, "v", "w").unwrap_err().contains("variable locale"));
+    }
+
+    #[test]
+    fn extracts_marked_statements_into_a_function() {
+        let source = r#"pub fn total(prices: Vec<u64>, rate: u64) -> u64 {
+    let fee: u64 = 3;
+    // aurorae:extract taxed_sum
+    let sum: u64 = prices.iter().sum();
+    sum * rate / 100 + fee
+    // aurorae:end-extract
+}
+"#;
+        let refactoring = extract_in_source(Path::new("total.rs"), source).unwrap();
+        let updated = &refactoring.changes[0].updated;
+        assert!(updated.contains("    taxed_sum(prices, rate, fee)\n}"));
+        assert!(updated.contains("fn taxed_sum(prices: Vec<u64>, rate: u64, fee: u64) -> u64 {\n    let sum: u64"));
+        assert!(!updated.contains("aurorae:extract"));
+        assert!(refactoring.to_modification().verification_status == VerificationStatus::Pending);
+
+        let moved = "fn f(v: Vec<u8>) -> usize {\n    // aurorae:extract g\n    let n = v.len();\n    drop(n);\n    // aurorae:end-extract\n    v.len()\n}\n";
+        assert!(extract_in_source(Path::new("f.rs"), moved).unwrap_err().contains("encore utilisée"));
+        let early = "fn f(x: u8) -> u8 {\n    // aurorae:extract g\n    if x > 1 { return x; }\n    // aurorae:end-extract\n    0\n}\n";
+        assert!(extract_in_source(Path::new("f.rs"), early).is_err());
+    }
 }
@@ -2,7 +2,10 @@ use std::collections::{HashMap, HashSet};
 use rand::Rng;
 use std::time::Duration;
 use serde::{Serialize, Deserialize};
-use std::path::Path;
+use tracing::info;
+
+use crate::inspiration::{InspirationConfig, LocalDirectorySource};
+use crate::resource_usage::ResourceUsage;
 
 // ====================== CONSTANTES & CONFIGURATION ======================
 
@@ -13,9 +16,8 @@ const DEFAULT_EXPLORATION_RATE: f32 = 0.1;
 const DEFAULT_ADAPTATION_THRESHOLD: f32 = 0.2;
 const DEFAULT_EVOLUTION_THRESHOLD: f32 = 0.5;
 const DEFAULT_META_LEARNING_RATE: f32 = 0.01;
-
-/// Chemin vers le dossier d'inspiration pour de nouvelles stratégies
-const INSPIRATION_PATH: &str = "C:\\Users\\admin\\inspiration";
+/// Poids de la pénalité appliquée à l'indice d'énergie consommé par une action
+const DEFAULT_RESOURCE_PENALTY: f32 = 0.05;
 
 // ====================== UTILITAIRES ======================
 
@@ -27,26 +29,14 @@ fn get_current_time() -> u64 {
         .as_secs()
 }
 
-/// Charge des inspirations depuis le dossier spécifié
+/// Charge des inspirations depuis le dossier local configuré (`inspiration.toml`)
 fn load_inspirations() -> Vec<String> {
-    let mut inspirations = Vec::new();
-    
-    let inspiration_path = Path::new(INSPIRATION_PATH);
-    if inspiration_path.exists() && inspiration_path.is_dir() {
-        if let Ok(entries) = std::fs::read_dir(inspiration_path) {
-            for entry in entries.filter_map(Result::ok) {
-                if let Ok(file_type) = entry.file_type() {
-                    if file_type.is_file() {
-                        if let Ok(content) = std::fs::read_to_string(entry.path()) {
-                            inspirations.push(content);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    inspirations
+    let config = InspirationConfig::load_or_default();
+    LocalDirectorySource::new(&config.local_path)
+        .read_all()
+        .into_iter()
+        .map(|inspiration| inspiration.description)
+        .collect()
 }
 
 // ====================== DÉFINITION DES STRUCTURES DE DONNÉES ======================
@@ -223,6 +213,12 @@ pub struct LearningAgent {
     pub meta_learning_rate: f32,                      // Taux d'apprentissage sur les hyperparamètres
     pub current_episode: EpisodeMemory,               // Épisode en cours
     pub network_complexity: u32,                      // Complexité du réseau
+    #[serde(default = "default_resource_penalty")]
+    pub resource_penalty: f32,                        // Poids de la pénalité de ressources
+}
+
+fn default_resource_penalty() -> f32 {
+    DEFAULT_RESOURCE_PENALTY
 }
 
 impl LearningAgent {
@@ -261,6 +257,7 @@ impl LearningAgent {
             meta_learning_rate: DEFAULT_META_LEARNING_RATE,
             current_episode: EpisodeMemory::new(initial_state),
             network_complexity: 1,
+            resource_penalty: DEFAULT_RESOURCE_PENALTY,
         }
     }
 
@@ -425,6 +422,17 @@ impl LearningAgent {
         self.check_for_adaptation();
     }
 
+    /// Pénalité de récompense pour les ressources consommées par une action
+    pub fn resource_cost(&self, usage: &ResourceUsage) -> f32 {
+        usage.energy() as f32 * self.resource_penalty
+    }
+
+    /// Apprentissage dont la récompense est diminuée du coût en ressources de l'action
+    pub fn learn_with_usage(&mut self, reward: f32, usage: &ResourceUsage, next_state: &str) {
+        let cost = self.resource_cost(usage);
+        self.learn(reward - cost, next_state);
+    }
+
     // ====================== MÉTHODES D'ÉVALUATION ET D'ADAPTATION ======================
 
     /// Évalue les performances actuelles de l'agent
@@ -552,7 +560,7 @@ impl LearningAgent {
             self.learning_rate = (self.learning_rate - self.meta_learning_rate * 0.1).max(0.01);
         }
         
-        println!("[AURORAE++] Agent s'adaptant : Exploration → {:.3}, Apprentissage → {:.3}", 
+        info!("Agent s'adaptant : Exploration → {:.3}, Apprentissage → {:.3}", 
                  self.exploration_rate, self.learning_rate);
     }
 
@@ -574,7 +582,7 @@ impl LearningAgent {
         // Réduire l'exploration à mesure que le réseau évolue
         self.exploration_rate = (self.exploration_rate * 0.9).max(0.05);
         
-        println!("[AURORAE++] Évolution #{} : Complexité réseau → {}", 
+        info!("Évolution #{} : Complexité réseau → {}", 
                  self.evolution_count, self.network_complexity);
         
         // Explorer de nouvelles actions possibles lors d'une évolution
@@ -604,7 +612,7 @@ impl LearningAgent {
             self.add_new_action_to_q_table(action);
         }
         
-        println!("[AURORAE++] {} nouvelles actions découvertes", new_actions.len());
+        info!("{} nouvelles actions découvertes", new_actions.len());
     }
 
     /// Ajoute une nouvelle action à la Q-table
@@ -626,7 +634,7 @@ impl LearningAgent {
         // Ajouter la nouvelle action à la Q-table
         self.q_table.insert(action.to_string(), action_map);
         
-        println!("[AURORAE++] Nouvelle action ajoutée : {}", action);
+        info!("Nouvelle action ajoutée : {}", action);
     }
 
     // ====================== MÉTHODES DE GESTION DES STRATÉGIES ======================
@@ -670,7 +678,7 @@ impl LearningAgent {
             let strategy = Strategy::new(&strategy_name, state_action_map, &context);
             
             self.strategies.push(strategy);
-            println!("[AURORAE++] Nouvelle stratégie générée : {}", 
+            info!("Nouvelle stratégie générée : {}", 
                 self.strategies.last().unwrap().name);
         }
     }
@@ -704,14 +712,14 @@ impl LearningAgent {
         
         // Ajouter la nouvelle stratégie à la liste
         self.strategies.push(new_strategy.clone());
-        println!("[AURORAE++] Stratégie mutée créée à partir de {}", best_strategy.name);
+        info!("Stratégie mutée créée à partir de {}", best_strategy.name);
     }
 
     // ====================== MÉTHODES DE CONSOLIDATION DE L'APPRENTISSAGE ======================
 
     /// Processus de "rêve" pour consolider l'apprentissage
     pub fn dream(&mut self) {
-        println!("[AURORAE++] Démarrage du cycle de rêve...");
+        info!("Démarrage du cycle de rêve...");
         
         // Sélectionner quelques épisodes de mémoire à long terme pour "rêver"
         let mut rng = rand::thread_rng();
@@ -755,14 +763,14 @@ impl LearningAgent {
             }
         }
         
-        println!("[AURORAE++] Cycle de rêve terminé. {} épisodes rejoués.", num_episodes);
+        info!("Cycle de rêve terminé. {} épisodes rejoués.", num_episodes);
     }
 
     // ====================== MÉTHODES D'INTROSPECTION & EXPORT ======================
 
     /// Affiche la table Q (inchangé mais avec formatage amélioré)
     pub fn print_q_table(&self) {
-        println!("[AURORAE++] Table Q ({} états, {} actions):", 
+        info!("Table Q ({} états, {} actions):", 
                  self.known_states.len(), self.actions.len());
         
         // Afficher seulement un échantillon représentatif pour éviter de submerger la sortie
@@ -857,7 +865,7 @@ impl LearningAgent {
     pub fn save_to_file(&self, path: &str) -> std::io::Result<()> {
         let json = serde_json::to_string_pretty(self)?;
         std::fs::write(path, json)?;
-        println!("[AURORAE++] Agent sauvegardé dans {}", path);
+        info!("Agent sauvegardé dans {}", path);
         Ok(())
     }
     
@@ -865,7 +873,7 @@ impl LearningAgent {
     pub fn load_from_file(path: &str) -> std::io::Result<Self> {
         let json = std::fs::read_to_string(path)?;
         let agent: LearningAgent = serde_json::from_str(&json)?;
-        println!("[AURORAE++] Agent chargé depuis {}", path);
+        info!("Agent chargé depuis {}", path);
         Ok(agent)
     }
 }
@@ -930,4 +938,18 @@ mod tests {
         assert_eq!(mutated.effectiveness, strategy.effectiveness * 0.8);
         assert!(mutated.creation_context.contains("Mutation de"));
     }
+    
+    #[test]
+    fn test_resource_cost_penalizes_reward() {
+        let mut agent = LearningAgent::new(vec!["action1".to_string()], "start");
+        let usage = ResourceUsage {
+            module: "reinforcement_learning".to_string(),
+            cpu_time: Duration::from_millis(100),
+            ..Default::default()
+        };
+        assert!((agent.resource_cost(&usage) - 0.05).abs() < 1e-6);
+
+        agent.learn_with_usage(1.0, &usage, "next");
+        assert!((agent.current_episode.reward_history[0] - 0.95).abs() < 1e-6);
+    }
 }
@@ -1,11 +1,88 @@
 use uuid::Uuid;
+use tracing::{error, info, warn};
+use crate::audit::{self, AuditKind};
+use crate::instance_runner::SharedSupervisor;
+use crate::lineage::LINEAGE;
 use chrono::Utc;
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
-use std::fs::{create_dir_all, File};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, create_dir_all, File};
 use std::io::{BufReader, BufWriter};
 use std::path::Path;
 
+pub const DEFAULT_POPULATION_CONFIG_PATH: &str = "population.toml";
+/// Poids d'une nouvelle évaluation dans la moyenne mobile du score d'une instance
+const FITNESS_SMOOTHING: f64 = 0.3;
+
+/// Plafonds et critères de retrait de la population d'instances (`population.toml`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct PopulationPolicy {
+    /// Instances actives au plus
+    pub max_active: usize,
+    /// Score sous lequel une instance suffisamment évaluée est retirée
+    pub min_fitness: f64,
+    pub min_evaluations: u32,
+    /// Plafond des spécialisations absentes de `caps`
+    pub default_cap: usize,
+    /// Plafond par spécialisation (module hérité)
+    pub caps: BTreeMap<String, usize>,
+}
+
+impl Default for PopulationPolicy {
+    fn default() -> Self {
+        Self { max_active: 7, min_fitness: 0.3, min_evaluations: 3, default_cap: 3, caps: BTreeMap::new() }
+    }
+}
+
+impl PopulationPolicy {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Politique de population invalide: {}", e))
+    }
+
+    /// `population.toml`, ou la politique par défaut s'il est absent ou invalide
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_POPULATION_CONFIG_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: politique de population par défaut", e);
+            Self::default()
+        })
+    }
+
+    pub fn cap_for(&self, specialization: &str) -> usize {
+        self.caps.get(specialization).copied().unwrap_or(self.default_cap)
+    }
+}
+
+/// Score d'une instance: moyenne mobile de ses évaluations
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InstanceFitness {
+    pub score: f64,
+    pub evaluations: u32,
+}
+
+/// Raison du retrait d'une instance
+#[derive(Debug, Clone, PartialEq)]
+pub enum CullReason {
+    Underperforming { score: f64 },
+    SpecializationCap { specialization: String, cap: usize },
+    PopulationCap { max_active: usize },
+}
+
+impl std::fmt::Display for CullReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Underperforming { score } => write!(f, "score insuffisant ({:.2})", score),
+            Self::SpecializationCap { specialization, cap } => write!(f, "plus de {} instances « {} »", cap, specialization),
+            Self::PopulationCap { max_active } => write!(f, "population au-delà de {} instances", max_active),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuroraInstance {
     pub id: Uuid,
@@ -20,11 +97,29 @@ pub struct AuroraInstance {
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ReproductionEngine {
     pub children: Vec<AuroraInstance>,
+    #[serde(default)]
+    pub fitness: HashMap<Uuid, InstanceFitness>,
+    #[serde(skip)]
+    policy: PopulationPolicy,
+    /// Exécution réelle des instances (processus ou conteneurs); sans superviseur, les
+    /// instances ne sont qu'enregistrées
+    #[serde(skip)]
+    supervisor: Option<SharedSupervisor>,
 }
 
 impl ReproductionEngine {
     pub fn new() -> Self {
-        Self::load().unwrap_or_default()
+        let mut engine = Self::load().unwrap_or_default();
+        engine.policy = PopulationPolicy::load_or_default();
+        engine
+    }
+
+    pub fn set_population_policy(&mut self, policy: PopulationPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn population_policy(&self) -> &PopulationPolicy {
+        &self.policy
     }
 
     /// 🚼 Crée un nouveau clone d'Aurorae avec modules hérités et but
@@ -41,21 +136,174 @@ impl ReproductionEngine {
             is_active: true,
         };
 
-        println!(
-            "[AURORAE++] 🧫 Nouvelle instance génération #{} : {} • But: {}",
+        info!(
+            "🧫 Nouvelle instance génération #{} : {} • But: {}",
             generation, instance.id, purpose
         );
 
+        audit::record(
+            AuditKind::InstanceSpawned,
+            "reproduction",
+            purpose,
+            serde_json::json!({ "instance": instance.id, "parent": instance.parent_id, "generation": generation, "modules": instance.inherited_modules }),
+        );
+
+        LINEAGE.lock().record_instance(&instance);
+
         // Pas besoin de clone ici
         self.children.push(instance.clone());
         self.save(); // Sauvegarder après ajout
         instance
     }
 
+    pub fn attach_supervisor(&mut self, supervisor: SharedSupervisor) {
+        info!("🧫 Instances exécutées par le moteur « {} »", supervisor.runner_name());
+        self.supervisor = Some(supervisor);
+    }
+
+    pub fn supervisor(&self) -> Option<&SharedSupervisor> {
+        self.supervisor.as_ref()
+    }
+
+    /// 🚀 Crée une instance puis la lance sous supervision (processus isolé, modules restreints)
+    pub async fn spawn_running_instance(&mut self, purpose: &str, modules: Vec<&str>) -> Result<AuroraInstance, String> {
+        let supervisor = self.supervisor.clone().ok_or("Aucun superviseur d'instances attaché")?;
+        self.check_capacity(&modules)?;
+        let mut instance = self.spawn_instance(purpose, modules);
+        if let Err(e) = supervisor.start(&instance).await {
+            instance.is_active = false;
+            self.set_active(&instance.id, false);
+            return Err(e);
+        }
+        Ok(instance)
+    }
+
+    /// ⏹️ Arrête le processus d'une instance sans l'oublier
+    pub async fn stop_instance(&mut self, id: &Uuid) -> Result<(), String> {
+        if let Some(supervisor) = self.supervisor.clone() {
+            supervisor.stop(id).await?;
+        }
+        self.set_active(id, false);
+        Ok(())
+    }
+
+    /// Vérifie qu'une instance portant `modules` respecterait les plafonds de la population
+    pub fn check_capacity(&self, modules: &[&str]) -> Result<(), String> {
+        let active = self.get_active_instances();
+        if active.len() >= self.policy.max_active {
+            return Err(format!("Population pleine ({} instances actives)", active.len()));
+        }
+        for module in modules {
+            let cap = self.policy.cap_for(module);
+            let count = active.iter().filter(|i| i.inherited_modules.iter().any(|m| m == module)).count();
+            if count >= cap {
+                return Err(format!("Spécialisation « {} » au plafond ({}/{})", module, count, cap));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn has_capacity(&self) -> bool {
+        self.get_active_instances().len() < self.policy.max_active
+    }
+
+    /// 📈 Intègre une évaluation (0.0 à 1.0) au score d'une instance
+    pub fn record_instance_fitness(&mut self, id: &Uuid, score: f64) {
+        let fitness = self.fitness.entry(*id).or_default();
+        fitness.score = if fitness.evaluations == 0 {
+            score
+        } else {
+            fitness.score * (1.0 - FITNESS_SMOOTHING) + score * FITNESS_SMOOTHING
+        };
+        fitness.evaluations += 1;
+    }
+
+    fn score_of(&self, id: &Uuid) -> f64 {
+        // Une instance jamais évaluée n'est pas présumée mauvaise
+        self.fitness.get(id).filter(|f| f.evaluations > 0).map_or(1.0, |f| f.score)
+    }
+
+    /// Instances à retirer: scores insuffisants d'abord, puis les moins bonnes de chaque
+    /// spécialisation au-delà de son plafond, puis les moins bonnes au-delà du plafond global
+    pub fn select_for_culling(&self) -> Vec<(Uuid, CullReason)> {
+        let mut culled: Vec<(Uuid, CullReason)> = Vec::new();
+        let mut active: Vec<&AuroraInstance> = self.get_active_instances();
+        // Les meilleures d'abord: les retraits se font par la fin
+        active.sort_by(|a, b| self.score_of(&b.id).total_cmp(&self.score_of(&a.id)));
+
+        for instance in &active {
+            if let Some(fitness) = self.fitness.get(&instance.id) {
+                if fitness.evaluations >= self.policy.min_evaluations && fitness.score < self.policy.min_fitness {
+                    culled.push((instance.id, CullReason::Underperforming { score: fitness.score }));
+                }
+            }
+        }
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for instance in &active {
+            if culled.iter().any(|(id, _)| id == &instance.id) {
+                continue;
+            }
+            let over_cap = instance.inherited_modules.iter().find(|module| {
+                counts.get(module.as_str()).copied().unwrap_or(0) >= self.policy.cap_for(module)
+            });
+            match over_cap {
+                Some(module) => culled.push((instance.id, CullReason::SpecializationCap {
+                    specialization: module.clone(),
+                    cap: self.policy.cap_for(module),
+                })),
+                None => {
+                    for module in &instance.inherited_modules {
+                        *counts.entry(module.as_str()).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        let survivors: Vec<&&AuroraInstance> = active.iter().filter(|i| !culled.iter().any(|(id, _)| id == &i.id)).collect();
+        for instance in survivors.iter().skip(self.policy.max_active) {
+            culled.push((instance.id, CullReason::PopulationCap { max_active: self.policy.max_active }));
+        }
+        culled
+    }
+
+    /// ✂️ Applique la politique de population: les instances retirées sont arrêtées
+    /// proprement puis leur répertoire d'état est archivé
+    pub async fn enforce_population(&mut self) -> Vec<Uuid> {
+        let culled = self.select_for_culling();
+        for (id, reason) in &culled {
+            let archive = match self.supervisor.clone() {
+                Some(supervisor) => {
+                    if let Err(e) = supervisor.stop(id).await {
+                        warn!("⚠️ {}", e);
+                    }
+                    supervisor.archive(id).map_err(|e| warn!("⚠️ Archivage de {} impossible: {}", id, e)).ok()
+                }
+                None => None,
+            };
+            self.set_active(id, false);
+            info!("✂️ Instance {} retirée: {}", id, reason);
+            audit::record(
+                AuditKind::InstanceCulled,
+                "reproduction",
+                &reason.to_string(),
+                serde_json::json!({ "instance": id, "score": self.fitness.get(id).map(|f| f.score), "archive": archive }),
+            );
+        }
+        culled.into_iter().map(|(id, _)| id).collect()
+    }
+
+    fn set_active(&mut self, id: &Uuid, active: bool) {
+        if let Some(instance) = self.children.iter_mut().find(|i| &i.id == id) {
+            instance.is_active = active;
+            self.save();
+        }
+    }
+
     /// ❌ Supprime une instance donnée
     pub fn destroy_instance(&mut self, id: &Uuid) {
         self.children.retain(|i| &i.id != id);
-        println!("[AURORAE++] 🪓 Instance détruite : {}", id);
+        info!("🪓 Instance détruite : {}", id);
         self.save(); // Sauvegarder après suppression
     }
 
@@ -76,7 +324,7 @@ impl ReproductionEngine {
 
     /// 🗺️ Liste tous les clones créés
     pub fn list_instances(&self) {
-        println!("[AURORAE++] 🌱 Instances actives : {}", self.children.len());
+        info!("🌱 Instances actives : {}", self.children.len());
         for i in &self.children {
             println!(
                 "- [{}] Gén #{} • But: {} • Modules: {:?}",
@@ -98,7 +346,7 @@ impl ReproductionEngine {
     pub fn save(&self) {
         let dir = Path::new("aurorae_state");
         if let Err(e) = create_dir_all(dir) {
-            eprintln!("[AURORAE++] Erreur lors de la création du répertoire: {}", e);
+            error!("Erreur lors de la création du répertoire: {}", e);
             return;
         }
 
@@ -108,11 +356,11 @@ impl ReproductionEngine {
             Ok(f) => {
                 let writer = BufWriter::new(f);
                 if let Err(e) = serde_json::to_writer_pretty(writer, &self) {
-                    eprintln!("[AURORAE++] Erreur lors de la sauvegarde : {}", e);
+                    error!("Erreur lors de la sauvegarde : {}", e);
                 }
             }
             Err(e) => {
-                eprintln!("[AURORAE++] Impossible de créer le fichier de sauvegarde : {}", e);
+                error!("Impossible de créer le fichier de sauvegarde : {}", e);
             }
         }
     }
@@ -126,9 +374,58 @@ impl ReproductionEngine {
                 serde_json::from_reader(reader).ok()
             }
             Err(e) => {
-                eprintln!("[AURORAE++] Erreur lors du chargement des instances : {}", e);
+                error!("Erreur lors du chargement des instances : {}", e);
                 None
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine_with(instances: &[&[&str]], policy: PopulationPolicy) -> ReproductionEngine {
+        let mut engine = ReproductionEngine { policy, ..Default::default() };
+        for modules in instances {
+            engine.children.push(AuroraInstance {
+                id: Uuid::new_v4(),
+                parent_id: None,
+                created_at: Utc::now().to_rfc3339(),
+                purpose: "test".into(),
+                inherited_modules: modules.iter().map(|m| m.to_string()).collect(),
+                generation: 1,
+                is_active: true,
+            });
+        }
+        engine
+    }
+
+    #[test]
+    fn policy_is_read_from_toml() {
+        let policy = PopulationPolicy::from_toml("max-active = 4\n[caps]\neconomy = 1\n").unwrap();
+        assert_eq!(policy.max_active, 4);
+        assert_eq!(policy.cap_for("economy"), 1);
+        assert_eq!(policy.cap_for("dream"), PopulationPolicy::default().default_cap);
+    }
+
+    #[test]
+    fn culling_removes_underperformers_then_enforces_caps() {
+        let policy = PopulationPolicy { max_active: 3, min_evaluations: 2, caps: BTreeMap::from([("economy".to_string(), 1)]), ..Default::default() };
+        let mut engine = engine_with(&[&["economy"], &["economy"], &["dream"], &["dream"], &["dream"]], policy);
+        let ids: Vec<Uuid> = engine.children.iter().map(|i| i.id).collect();
+        for _ in 0..2 {
+            engine.record_instance_fitness(&ids[0], 0.9);
+            engine.record_instance_fitness(&ids[1], 0.6);
+            engine.record_instance_fitness(&ids[2], 0.1);
+            engine.record_instance_fitness(&ids[3], 0.8);
+            engine.record_instance_fitness(&ids[4], 0.7);
+        }
+
+        let culled = engine.select_for_culling();
+        assert_eq!(culled.len(), 2);
+        assert!(matches!(culled[0], (id, CullReason::Underperforming { .. }) if id == ids[2]));
+        assert!(matches!(&culled[1], (id, CullReason::SpecializationCap { specialization, .. }) if *id == ids[1] && specialization == "economy"));
+        assert!(engine.check_capacity(&["economy"]).is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! AURORAE++ - resource_usage.rs
+//!
+//! Comptabilité des ressources par module: les tâches tokio lancées via `spawn_tracked`
+//! mesurent leur temps de poll (CPU), l'allocateur global `TrackingAllocator` attribue
+//! chaque allocation au module dont une tâche est en cours d'exécution sur le thread, et
+//! sous Linux les statistiques du cgroup donnent la consommation réelle du processus.
+//! Les relevés alimentent les rapports du gardien et la pénalité de récompense de l'agent RL.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+/// Nombre maximal de modules suivis; les suivants partagent l'emplacement « non attribué »
+pub const MAX_TRACKED_MODULES: usize = 64;
+/// Emplacement des allocations et du temps qu'aucun module ne revendique
+pub const UNATTRIBUTED: &str = "unattributed";
+
+/// Compteurs atomiques d'un module, mis à jour sans verrou (y compris par l'allocateur)
+struct ModuleCounters {
+    busy_nanos: AtomicU64,
+    polls: AtomicU64,
+    tasks_spawned: AtomicU64,
+    tasks_finished: AtomicU64,
+    allocated_bytes: AtomicU64,
+    allocations: AtomicU64,
+}
+
+impl ModuleCounters {
+    const fn new() -> Self {
+        Self {
+            busy_nanos: AtomicU64::new(0),
+            polls: AtomicU64::new(0),
+            tasks_spawned: AtomicU64::new(0),
+            tasks_finished: AtomicU64::new(0),
+            allocated_bytes: AtomicU64::new(0),
+            allocations: AtomicU64::new(0),
+        }
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_COUNTERS: ModuleCounters = ModuleCounters::new();
+static COUNTERS: [ModuleCounters; MAX_TRACKED_MODULES] = [EMPTY_COUNTERS; MAX_TRACKED_MODULES];
+
+lazy_static! {
+    /// Nom du module associé à chaque emplacement (l'emplacement 0 est `UNATTRIBUTED`)
+    static ref SLOTS: RwLock<Vec<String>> = RwLock::new(vec![UNATTRIBUTED.to_string()]);
+}
+
+thread_local! {
+    /// Module dont une tâche est en cours de poll sur ce thread
+    static CURRENT_SLOT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Emplacement d'un module, réservé à la première utilisation
+fn slot_for(module: &str) -> usize {
+    if let Some(slot) = SLOTS.read().iter().position(|name| name == module) {
+        return slot;
+    }
+    let mut slots = SLOTS.write();
+    if let Some(slot) = slots.iter().position(|name| name == module) {
+        return slot;
+    }
+    if slots.len() >= MAX_TRACKED_MODULES {
+        warn!("⚠️ Suivi des ressources saturé, {} comptabilisé comme {}", module, UNATTRIBUTED);
+        return 0;
+    }
+    slots.push(module.to_string());
+    slots.len() - 1
+}
+
+/// Allocateur global qui attribue les allocations au module en cours d'exécution.
+/// Les libérations ne sont pas attribuées: les octets alloués sont un volume cumulé.
+pub struct TrackingAllocator;
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_allocation(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_allocation(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_allocation(new_size.saturating_sub(layout.size()));
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn record_allocation(size: usize) {
+    // `try_with` : le thread local peut déjà être détruit à la fin d'un thread
+    let slot = CURRENT_SLOT.try_with(Cell::get).unwrap_or(0);
+    let counters = &COUNTERS[slot];
+    counters.allocated_bytes.fetch_add(size as u64, Ordering::Relaxed);
+    counters.allocations.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Attribue au module le thread courant le temps d'un appel, puis restaure l'attribution précédente
+fn enter<R>(slot: usize, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT_SLOT.with(|current| current.replace(slot));
+    let started = Instant::now();
+    let result = f();
+    let counters = &COUNTERS[slot];
+    counters.busy_nanos.fetch_add(started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+    counters.polls.fetch_add(1, Ordering::Relaxed);
+    CURRENT_SLOT.with(|current| current.set(previous));
+    result
+}
+
+/// Future instrumentée: chaque poll est chronométré et ses allocations attribuées au module
+pub struct Tracked<F> {
+    slot: usize,
+    inner: Pin<Box<F>>,
+    counts_as_task: bool,
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let slot = self.slot;
+        enter(slot, || self.inner.as_mut().poll(cx))
+    }
+}
+
+impl<F> Drop for Tracked<F> {
+    fn drop(&mut self) {
+        if self.counts_as_task {
+            COUNTERS[self.slot].tasks_finished.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Instrumente une future sans la lancer (travail exécuté dans la tâche appelante)
+pub fn track<F: Future>(module: &str, future: F) -> Tracked<F> {
+    Tracked { slot: slot_for(module), inner: Box::pin(future), counts_as_task: false }
+}
+
+/// `tokio::spawn` comptabilisé: la tâche est comptée active jusqu'à sa fin ou son abandon
+pub fn spawn_tracked<F>(module: &str, future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let slot = slot_for(module);
+    COUNTERS[slot].tasks_spawned.fetch_add(1, Ordering::Relaxed);
+    tokio::spawn(Tracked { slot, inner: Box::pin(future), counts_as_task: true })
+}
+
+/// Exécute `future` pour le compte de `module` et retourne les ressources qu'elle a consommées
+pub async fn measure<F: Future>(module: &str, future: F) -> (F::Output, ResourceUsage) {
+    let before = usage_of(module);
+    let output = track(module, future).await;
+    let usage = usage_of(module).since(&before);
+    (output, usage)
+}
+
+/// Ressources consommées par un module (cumul depuis le démarrage ou écart entre deux relevés)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ResourceUsage {
+    pub module: String,
+    /// Temps passé à poller les tâches du module
+    pub cpu_time: Duration,
+    pub polls: u64,
+    pub tasks_spawned: u64,
+    /// Tâches lancées et non terminées au moment du relevé
+    pub active_tasks: u64,
+    pub allocated_bytes: u64,
+    pub allocations: u64,
+    /// Durée couverte par le relevé (nulle pour un cumul)
+    pub window: Duration,
+}
+
+impl ResourceUsage {
+    /// Écart depuis un relevé antérieur du même module; les tâches actives restent instantanées
+    pub fn since(&self, earlier: &ResourceUsage) -> ResourceUsage {
+        ResourceUsage {
+            module: self.module.clone(),
+            cpu_time: self.cpu_time.saturating_sub(earlier.cpu_time),
+            polls: self.polls.saturating_sub(earlier.polls),
+            tasks_spawned: self.tasks_spawned.saturating_sub(earlier.tasks_spawned),
+            active_tasks: self.active_tasks,
+            allocated_bytes: self.allocated_bytes.saturating_sub(earlier.allocated_bytes),
+            allocations: self.allocations.saturating_sub(earlier.allocations),
+            window: self.window,
+        }
+    }
+
+    /// Part d'un cœur occupée pendant la fenêtre du relevé
+    pub fn cpu_share(&self) -> f64 {
+        if self.window.is_zero() {
+            return 0.0;
+        }
+        self.cpu_time.as_secs_f64() / self.window.as_secs_f64()
+    }
+
+    pub fn allocated_mb(&self) -> f64 {
+        self.allocated_bytes as f64 / (1024.0 * 1024.0)
+    }
+
+    /// Indice de consommation combinant CPU (ms), mémoire allouée (Mo) et tâches vivantes
+    pub fn energy(&self) -> f64 {
+        self.cpu_time.as_secs_f64() * 1_000.0 * 0.01 + self.allocated_mb() * 0.1 + self.active_tasks as f64 * 0.05
+    }
+}
+
+/// Relevé cumulé d'un module (vide s'il n'a jamais été suivi)
+pub fn usage_of(module: &str) -> ResourceUsage {
+    let slot = SLOTS.read().iter().position(|name| name == module);
+    match slot {
+        Some(slot) => read_slot(slot, module),
+        None => ResourceUsage { module: module.to_string(), ..Default::default() },
+    }
+}
+
+fn read_slot(slot: usize, module: &str) -> ResourceUsage {
+    let counters = &COUNTERS[slot];
+    let spawned = counters.tasks_spawned.load(Ordering::Relaxed);
+    ResourceUsage {
+        module: module.to_string(),
+        cpu_time: Duration::from_nanos(counters.busy_nanos.load(Ordering::Relaxed)),
+        polls: counters.polls.load(Ordering::Relaxed),
+        tasks_spawned: spawned,
+        active_tasks: spawned.saturating_sub(counters.tasks_finished.load(Ordering::Relaxed)),
+        allocated_bytes: counters.allocated_bytes.load(Ordering::Relaxed),
+        allocations: counters.allocations.load(Ordering::Relaxed),
+        window: Duration::ZERO,
+    }
+}
+
+/// Relevés cumulés de tous les modules suivis
+pub fn snapshot() -> Vec<ResourceUsage> {
+    let slots = SLOTS.read().clone();
+    slots.iter().enumerate().map(|(slot, module)| read_slot(slot, module)).collect()
+}
+
+/// Échantillonneur périodique: chaque appel à `sample` retourne la consommation par module
+/// depuis l'appel précédent
+pub struct ResourceMonitor {
+    previous: HashMap<String, ResourceUsage>,
+    last_sample: Instant,
+}
+
+impl ResourceMonitor {
+    pub fn new() -> Self {
+        let previous = snapshot().into_iter().map(|usage| (usage.module.clone(), usage)).collect();
+        Self { previous, last_sample: Instant::now() }
+    }
+
+    pub fn sample(&mut self) -> Vec<ResourceUsage> {
+        let window = self.last_sample.elapsed();
+        self.last_sample = Instant::now();
+        let current = snapshot();
+        let deltas = current
+            .iter()
+            .map(|usage| {
+                let mut delta = match self.previous.get(&usage.module) {
+                    Some(previous) => usage.since(previous),
+                    None => usage.clone(),
+                };
+                delta.window = window;
+                delta
+            })
+            .collect();
+        self.previous = current.into_iter().map(|usage| (usage.module.clone(), usage)).collect();
+        deltas
+    }
+}
+
+impl Default for ResourceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Consommation du cgroup du processus (Linux, cgroup v2 ou v1)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CgroupStats {
+    pub cpu_usage: Duration,
+    pub memory_bytes: u64,
+    /// Limite mémoire du cgroup, absente si illimitée
+    pub memory_limit: Option<u64>,
+}
+
+impl CgroupStats {
+    pub fn memory_ratio(&self) -> Option<f64> {
+        self.memory_limit.filter(|limit| *limit > 0).map(|limit| self.memory_bytes as f64 / limit as f64)
+    }
+}
+
+/// Lit les statistiques du cgroup courant; `None` hors Linux ou sans cgroup accessible
+#[cfg(target_os = "linux")]
+pub fn cgroup_stats() -> Option<CgroupStats> {
+    let read = |path: &str| std::fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+
+    // cgroup v2: hiérarchie unifiée
+    if let Some(cpu_stat) = read("/sys/fs/cgroup/cpu.stat") {
+        let usage_usec = cpu_stat
+            .lines()
+            .find_map(|line| line.strip_prefix("usage_usec "))
+            .and_then(|value| value.trim().parse::<u64>().ok())?;
+        let memory_bytes = read("/sys/fs/cgroup/memory.current").and_then(|v| v.parse().ok()).unwrap_or(0);
+        let memory_limit = read("/sys/fs/cgroup/memory.max").and_then(|v| v.parse().ok());
+        return Some(CgroupStats { cpu_usage: Duration::from_micros(usage_usec), memory_bytes, memory_limit });
+    }
+
+    // cgroup v1: contrôleurs séparés
+    let usage_nanos = read("/sys/fs/cgroup/cpuacct/cpuacct.usage").and_then(|v| v.parse::<u64>().ok())?;
+    let memory_bytes = read("/sys/fs/cgroup/memory/memory.usage_in_bytes").and_then(|v| v.parse().ok()).unwrap_or(0);
+    // v1 signale « illimité » par une valeur proche de i64::MAX
+    let memory_limit = read("/sys/fs/cgroup/memory/memory.limit_in_bytes")
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|limit| *limit < (1 << 62));
+    Some(CgroupStats { cpu_usage: Duration::from_nanos(usage_nanos), memory_bytes, memory_limit })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn cgroup_stats() -> Option<CgroupStats> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tracked_tasks_are_counted_and_timed() {
+        let handle = spawn_tracked("test_resource_tasks", async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            vec![0u8; 4096].len()
+        });
+        assert_eq!(handle.await.unwrap(), 4096);
+
+        let usage = usage_of("test_resource_tasks");
+        assert_eq!(usage.tasks_spawned, 1);
+        assert_eq!(usage.active_tasks, 0);
+        assert!(usage.polls >= 2);
+    }
+
+    #[tokio::test]
+    async fn measure_reports_only_the_measured_work() {
+        let (_, first) = measure("test_resource_measure", async { vec![1u8; 1024] }).await;
+        let (_, second) = measure("test_resource_measure", async {}).await;
+        assert_eq!(first.polls, 1);
+        assert_eq!(second.polls, 1);
+        assert!(second.cpu_time <= usage_of("test_resource_measure").cpu_time);
+        assert_eq!(usage_of("unknown_module").polls, 0);
+    }
+
+    #[test]
+    fn delta_and_energy_from_two_readings() {
+        let earlier = ResourceUsage { module: "m".into(), cpu_time: Duration::from_millis(10), allocated_bytes: 1024, ..Default::default() };
+        let later = ResourceUsage {
+            module: "m".into(),
+            cpu_time: Duration::from_millis(110),
+            allocated_bytes: 1024 + 2 * 1024 * 1024,
+            active_tasks: 2,
+            window: Duration::from_secs(1),
+            ..Default::default()
+        };
+        let delta = later.since(&earlier);
+        assert_eq!(delta.cpu_time, Duration::from_millis(100));
+        assert!((delta.cpu_share() - 0.1).abs() < 1e-9);
+        assert!((delta.allocated_mb() - 2.0).abs() < 1e-9);
+        assert!((delta.energy() - (1.0 + 0.2 + 0.1)).abs() < 1e-9);
+    }
+}
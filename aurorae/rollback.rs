@@ -0,0 +1,639 @@
+//! AURORAE++ - rollback.rs
+//!
+//! Mécanismes de retour en arrière sécurisés. Le journal on-chain associe chaque
+//! écriture économique à la transaction qui la justifie: si une réorganisation fait
+//! disparaître le reçu, les écritures correspondantes sont annulées.
+//!
+//! L'état vivant (base de connaissance, grand livre économique, agent RL, registre de
+//! reproduction) est photographié dans un magasin de snapshots versionnés; une
+//! corruption détectée par le gardien après une mutation restaure le dernier snapshot.
+//!
+//! Chaque modification du code source est commitée dans un dépôt git interne
+//! (`aurorae_state/source_history.git`, arbre de travail = le crate) avec l'identifiant
+//! de la `CodeModification`, ce qui permet de revenir à la révision précédente.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::Utc;
+use ethers::types::H256;
+use git2::{IndexAddOption, Repository, RepositoryInitOptions, Signature};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::currency::{Asset, AssetLedger};
+use crate::economy::EconomyEngine;
+use crate::knowledge::KnowledgeBase;
+use crate::reinforcement_learning::LearningAgent;
+use crate::reproduction::ReproductionEngine;
+
+pub const SNAPSHOT_DIR: &str = "aurorae_state/snapshots";
+/// Snapshots conservés avant élagage des plus anciens
+pub const MAX_SNAPSHOTS: usize = 20;
+/// Cycles entre deux snapshots périodiques
+pub const SNAPSHOT_INTERVAL_CYCLES: usize = 10;
+/// Cycles pendant lesquels une corruption est imputée à la dernière mutation
+pub const MUTATION_WATCH_CYCLES: u32 = 3;
+
+/// Dépôt git interne de l'historique des sources
+pub const SOURCE_HISTORY_DIR: &str = "aurorae_state/source_history.git";
+/// Sources suivies par l'historique interne
+const TRACKED_SOURCES: &str = "aurorae";
+
+lazy_static! {
+    pub static ref SNAPSHOTS: Mutex<SnapshotStore> = Mutex::new(SnapshotStore::new(SNAPSHOT_DIR, MAX_SNAPSHOTS));
+    pub static ref SOURCE_HISTORY: Mutex<Option<SourceHistory>> = Mutex::new(
+        SourceHistory::open(Path::new(SOURCE_HISTORY_DIR), Path::new("."))
+            .map_err(|e| warn!("⚠️ Historique des sources indisponible: {}", e))
+            .ok()
+    );
+}
+
+/// Écriture réversible adossée à un reçu on-chain
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    pub transaction_id: Uuid,
+    pub chain_id: u64,
+    pub asset: Asset,
+    /// Variation appliquée au solde (positive pour un crédit)
+    pub ledger_delta: f64,
+    /// Valeur en USD versée à la trésorerie du budget par cette écriture
+    pub treasury_delta: f64,
+    pub recorded_at: String,
+}
+
+/// Journal des écritures économiques en attente de finalité
+#[derive(Debug, Clone, Default)]
+pub struct OnChainJournal {
+    entries: HashMap<H256, Vec<JournalEntry>>,
+}
+
+impl OnChainJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, tx_hash: H256, chain_id: u64, transaction_id: Uuid, asset: Asset, ledger_delta: f64, treasury_delta: f64) {
+        self.entries.entry(tx_hash).or_default().push(JournalEntry {
+            transaction_id,
+            chain_id,
+            asset,
+            ledger_delta,
+            treasury_delta,
+            recorded_at: Utc::now().to_rfc3339(),
+        });
+    }
+
+    /// Retire les écritures d'une transaction orpheline pour les annuler
+    pub fn take(&mut self, tx_hash: &H256) -> Vec<JournalEntry> {
+        self.entries.remove(tx_hash).unwrap_or_default()
+    }
+
+    /// Transaction finalisée: ses écritures deviennent définitives
+    pub fn finalize(&mut self, tx_hash: &H256) -> usize {
+        self.entries.remove(tx_hash).map(|e| e.len()).unwrap_or(0)
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.entries.values().map(|e| e.len()).sum()
+    }
+}
+
+/// Solde économique photographié (les transactions en cours ne sont pas rejouées);
+/// les soldes sont une liste car un `Asset` n'est pas une clé JSON valide
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerState {
+    pub balances: Vec<(Asset, f64)>,
+    pub total_generated: f64,
+}
+
+/// Snapshot versionné; les composants sont sérialisés ensemble ou pas du tout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub id: Uuid,
+    pub version: u64,
+    pub created_at: String,
+    pub reason: String,
+    /// SHA-256 des composants, vérifié à la restauration
+    pub checksum: String,
+    pub components: BTreeMap<String, Value>,
+}
+
+/// État décodé d'un snapshot, prêt à remplacer l'état vivant (sauf le grand livre, fusionné)
+pub struct RestoredState {
+    pub snapshot_id: Uuid,
+    pub version: u64,
+    pub knowledge: KnowledgeBase,
+    pub ledger: LedgerState,
+    pub agent: LearningAgent,
+    pub reproduction: ReproductionEngine,
+}
+
+impl RestoredState {
+    /// Remplace la connaissance, l'agent et la reproduction. Le grand livre n'est pas remplacé:
+    /// les revenus enregistrés depuis le snapshot sont conservés et seuls les soldes absents ou
+    /// invalides (non finis, négatifs) sont repris du snapshot
+    pub fn apply(
+        self,
+        knowledge: &mut KnowledgeBase,
+        economy: &mut EconomyEngine,
+        agent: &mut LearningAgent,
+        reproduction: &mut ReproductionEngine,
+    ) {
+        *knowledge = self.knowledge;
+        merge_ledger(&mut economy.ledger, self.ledger.balances);
+        if !economy.total_generated.is_finite() || economy.total_generated < self.ledger.total_generated {
+            economy.total_generated = self.ledger.total_generated;
+        }
+        *agent = self.agent;
+        *reproduction = self.reproduction;
+        info!("⏪ État restauré depuis le snapshot v{} ({})", self.version, self.snapshot_id);
+    }
+}
+
+fn merge_ledger(ledger: &mut AssetLedger, snapshot: Vec<(Asset, f64)>) {
+    for (asset, saved) in snapshot {
+        let live = ledger.balances.entry(asset.clone()).or_insert(f64::NAN);
+        if !live.is_finite() || *live < 0.0 {
+            if !live.is_nan() {
+                warn!("⚠️ Solde {} invalide ({}), repris du snapshot: {}", asset, live, saved);
+            }
+            *live = saved;
+        }
+    }
+}
+
+/// Magasin de snapshots sur disque: un fichier JSON par version
+pub struct SnapshotStore {
+    dir: PathBuf,
+    max_snapshots: usize,
+}
+
+impl SnapshotStore {
+    pub fn new(dir: &str, max_snapshots: usize) -> Self {
+        Self { dir: PathBuf::from(dir), max_snapshots }
+    }
+
+    /// Photographie l'état vivant; rien n'est écrit si un composant ne se sérialise pas
+    pub fn capture(
+        &self,
+        reason: &str,
+        knowledge: &KnowledgeBase,
+        economy: &EconomyEngine,
+        agent: &LearningAgent,
+        reproduction: &ReproductionEngine,
+    ) -> Result<StateSnapshot, String> {
+        let ledger = LedgerState {
+            balances: economy.ledger.balances.iter().map(|(asset, value)| (asset.clone(), *value)).collect(),
+            total_generated: economy.total_generated,
+        };
+        let mut components = BTreeMap::new();
+        components.insert("knowledge".to_string(), to_value("knowledge", knowledge)?);
+        components.insert("ledger".to_string(), to_value("ledger", &ledger)?);
+        components.insert("agent".to_string(), to_value("agent", agent)?);
+        components.insert("reproduction".to_string(), to_value("reproduction", reproduction)?);
+
+        let snapshot = StateSnapshot {
+            id: Uuid::new_v4(),
+            version: self.index()?.last().map(|(version, _, _)| version + 1).unwrap_or(1),
+            created_at: Utc::now().to_rfc3339(),
+            reason: reason.to_string(),
+            checksum: checksum(&components)?,
+            components,
+        };
+
+        // Écriture atomique: fichier temporaire puis renommage
+        fs::create_dir_all(&self.dir).map_err(|e| format!("Erreur création {}: {}", self.dir.display(), e))?;
+        let path = self.path_of(&snapshot);
+        let tmp = path.with_extension("tmp");
+        let json = serde_json::to_vec(&snapshot).map_err(|e| format!("Erreur sérialisation du snapshot: {}", e))?;
+        fs::write(&tmp, json).map_err(|e| format!("Erreur écriture {}: {}", tmp.display(), e))?;
+        fs::rename(&tmp, &path).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))?;
+        self.prune()?;
+
+        info!("📸 Snapshot v{} ({}): {}", snapshot.version, snapshot.id, reason);
+        Ok(snapshot)
+    }
+
+    /// Snapshots disponibles, du plus ancien au plus récent
+    pub fn list(&self) -> Result<Vec<StateSnapshot>, String> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(|e| format!("Erreur lecture {}: {}", self.dir.display(), e))? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                snapshots.push(read_snapshot(&path)?);
+            }
+        }
+        snapshots.sort_by_key(|s| s.version);
+        Ok(snapshots)
+    }
+
+    /// Dernier snapshot; seul son fichier est lu
+    pub fn latest(&self) -> Result<Option<StateSnapshot>, String> {
+        self.index()?.last().map(|(_, _, path)| read_snapshot(path)).transpose()
+    }
+
+    /// Versions, identifiants et chemins tirés des noms de fichiers, sans les lire
+    fn index(&self) -> Result<Vec<(u64, Uuid, PathBuf)>, String> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut index = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(|e| format!("Erreur lecture {}: {}", self.dir.display(), e))? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            let parsed = stem.split_once('-')
+                .and_then(|(version, id)| Some((version.parse::<u64>().ok()?, id.parse::<Uuid>().ok()?)));
+            match parsed {
+                Some((version, id)) => index.push((version, id, path)),
+                None => warn!("⚠️ Fichier de snapshot ignoré: {}", path.display()),
+            }
+        }
+        index.sort_by_key(|(version, _, _)| *version);
+        Ok(index)
+    }
+
+    /// Décode tous les composants d'un snapshot après contrôle de son empreinte
+    pub fn restore(&self, snapshot_id: Uuid) -> Result<RestoredState, String> {
+        let path = self.index()?
+            .into_iter()
+            .find(|(_, id, _)| *id == snapshot_id)
+            .map(|(_, _, path)| path)
+            .ok_or_else(|| format!("Snapshot {} introuvable", snapshot_id))?;
+        let snapshot = read_snapshot(&path)?;
+        if checksum(&snapshot.components)? != snapshot.checksum {
+            return Err(format!("Snapshot {} altéré: empreinte invalide", snapshot_id));
+        }
+        Ok(RestoredState {
+            snapshot_id,
+            version: snapshot.version,
+            knowledge: from_component(&snapshot, "knowledge")?,
+            ledger: from_component(&snapshot, "ledger")?,
+            agent: from_component(&snapshot, "agent")?,
+            reproduction: from_component(&snapshot, "reproduction")?,
+        })
+    }
+
+    fn path_of(&self, snapshot: &StateSnapshot) -> PathBuf {
+        self.dir.join(format!("{:08}-{}.json", snapshot.version, snapshot.id))
+    }
+
+    fn prune(&self) -> Result<(), String> {
+        let index = self.index()?;
+        let excess = index.len().saturating_sub(self.max_snapshots);
+        for (_, id, path) in &index[..excess] {
+            fs::remove_file(path).map_err(|e| format!("Erreur élagage du snapshot {}: {}", id, e))?;
+        }
+        Ok(())
+    }
+}
+
+fn to_value<T: Serialize>(name: &str, value: &T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| format!("Erreur sérialisation de {}: {}", name, e))
+}
+
+fn from_component<T: serde::de::DeserializeOwned>(snapshot: &StateSnapshot, name: &str) -> Result<T, String> {
+    let value = snapshot.components.get(name)
+        .cloned()
+        .ok_or_else(|| format!("Snapshot {}: composant {} absent", snapshot.id, name))?;
+    serde_json::from_value(value).map_err(|e| format!("Snapshot {}: composant {} illisible: {}", snapshot.id, name, e))
+}
+
+fn checksum(components: &BTreeMap<String, Value>) -> Result<String, String> {
+    let bytes = serde_json::to_vec(components).map_err(|e| e.to_string())?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+fn read_snapshot(path: &Path) -> Result<StateSnapshot, String> {
+    let content = fs::read(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+    serde_json::from_slice(&content).map_err(|e| format!("Snapshot {} illisible: {}", path.display(), e))
+}
+
+/// Restaure un snapshot du magasin global
+pub fn restore(snapshot_id: Uuid) -> Result<RestoredState, String> {
+    SNAPSHOTS.lock().restore(snapshot_id)
+}
+
+/// Issue de la surveillance qui suit une mutation
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchOutcome {
+    /// Fenêtre de surveillance en cours
+    Watching,
+    /// Aucune corruption pendant la fenêtre: la mutation est conservée
+    Clean,
+    /// Modules devenus corrompus depuis la mutation
+    Corrupted(Vec<String>),
+}
+
+/// Surveillance des modules corrompus après une mutation, adossée au snapshot pré-mutation
+#[derive(Debug, Clone)]
+pub struct MutationWatch {
+    pub snapshot_id: Uuid,
+    /// Modules déjà corrompus avant la mutation, ignorés
+    baseline: HashSet<String>,
+    remaining_cycles: u32,
+}
+
+impl MutationWatch {
+    pub fn new(snapshot_id: Uuid, corrupted_before: &[String]) -> Self {
+        Self {
+            snapshot_id,
+            baseline: corrupted_before.iter().cloned().collect(),
+            remaining_cycles: MUTATION_WATCH_CYCLES,
+        }
+    }
+
+    /// À appeler une fois par cycle avec les modules corrompus selon le gardien
+    pub fn observe(&mut self, corrupted: &[String]) -> WatchOutcome {
+        let mut new: Vec<String> = corrupted.iter().filter(|m| !self.baseline.contains(*m)).cloned().collect();
+        if !new.is_empty() {
+            new.sort();
+            return WatchOutcome::Corrupted(new);
+        }
+        self.remaining_cycles = self.remaining_cycles.saturating_sub(1);
+        if self.remaining_cycles == 0 { WatchOutcome::Clean } else { WatchOutcome::Watching }
+    }
+}
+
+/// Historique git des modifications du code source
+pub struct SourceHistory {
+    repo: Repository,
+}
+
+/// Modification annulée et révision qui l'enregistre
+#[derive(Debug, Clone)]
+pub struct RevertedModification {
+    pub modification_id: Uuid,
+    pub target_file: String,
+    /// Commit de la modification annulée
+    pub reverted_commit: String,
+    /// Commit enregistrant la restauration
+    pub commit: String,
+}
+
+fn git_error(e: git2::Error) -> String {
+    format!("Historique git: {}", e.message())
+}
+
+impl SourceHistory {
+    /// Ouvre le dépôt interne, ou le crée avec un commit de référence des sources
+    pub fn open(git_dir: &Path, workdir: &Path) -> Result<Self, String> {
+        if git_dir.exists() {
+            return Ok(Self { repo: Repository::open(git_dir).map_err(git_error)? });
+        }
+        // Chemin absolu: libgit2 résout un arbre de travail relatif depuis `git_dir`
+        let workdir = workdir.canonicalize().map_err(|e| format!("Arbre de travail {}: {}", workdir.display(), e))?;
+        let mut options = RepositoryInitOptions::new();
+        // Pas de fichier `.git` dans le crate: le dépôt du projet reste intact
+        options.workdir_path(&workdir).no_dotgit_dir(true).mkpath(true);
+        let repo = Repository::init_opts(git_dir, &options).map_err(git_error)?;
+        let history = Self { repo };
+
+        let mut index = history.repo.index().map_err(git_error)?;
+        index.add_all([TRACKED_SOURCES], IndexAddOption::DEFAULT, None).map_err(git_error)?;
+        index.write().map_err(git_error)?;
+        history.commit_index("Révision de référence des sources\n")?;
+        info!("🗃️ Historique des sources initialisé dans {}", git_dir.display());
+        Ok(history)
+    }
+
+    /// Commite l'état actuel de `target_file` sous l'identifiant de la modification
+    pub fn record(&self, modification_id: Uuid, target_file: &str, summary: &str, origin: &str) -> Result<String, String> {
+        let target_file = target_file.trim_start_matches("./");
+        self.stage(target_file)?;
+        let message = format!(
+            "{}: {}\n\nModification-Id: {}\nTarget-File: {}\n",
+            origin, summary, modification_id, target_file,
+        );
+        self.commit_index(&message)
+    }
+
+    /// Restaure `target_file` tel qu'avant la modification et commite la restauration
+    pub fn restore_prior(&self, modification_id: Uuid) -> Result<RevertedModification, String> {
+        let commit = self.find_modification(modification_id)?;
+        let message = commit.message().unwrap_or_default().to_string();
+        let target_file = message.lines()
+            .find_map(|line| line.strip_prefix("Target-File: "))
+            .ok_or_else(|| format!("Commit {} sans fichier cible", commit.id()))?
+            .to_string();
+        let parent = commit.parent(0).map_err(git_error)?;
+        let workdir = self.repo.workdir().ok_or_else(|| "Historique git sans arbre de travail".to_string())?;
+        let path = workdir.join(&target_file);
+
+        // Fichier absent de la révision précédente: la modification l'avait créé
+        match parent.tree().map_err(git_error)?.get_path(Path::new(&target_file)) {
+            Ok(entry) => {
+                let blob = entry.to_object(&self.repo).and_then(|o| o.peel_to_blob()).map_err(git_error)?;
+                fs::write(&path, blob.content()).map_err(|e| format!("Erreur écriture {}: {}", target_file, e))?;
+            }
+            Err(_) => fs::remove_file(&path).map_err(|e| format!("Erreur suppression {}: {}", target_file, e))?,
+        }
+
+        self.stage(&target_file)?;
+        let revert = self.commit_index(&format!(
+            "revert: modification {}\n\nReverts: {}\nTarget-File: {}\n",
+            modification_id, modification_id, target_file,
+        ))?;
+        info!("⏪ Modification {} annulée: {} restauré", modification_id, target_file);
+        Ok(RevertedModification {
+            modification_id,
+            target_file,
+            reverted_commit: commit.id().to_string(),
+            commit: revert,
+        })
+    }
+
+    fn find_modification(&self, modification_id: Uuid) -> Result<git2::Commit<'_>, String> {
+        let trailer = format!("Modification-Id: {}", modification_id);
+        let mut walk = self.repo.revwalk().map_err(git_error)?;
+        walk.push_head().map_err(git_error)?;
+        for oid in walk {
+            let commit = self.repo.find_commit(oid.map_err(git_error)?).map_err(git_error)?;
+            if commit.message().map(|m| m.lines().any(|l| l == trailer)).unwrap_or(false) {
+                return Ok(commit);
+            }
+        }
+        Err(format!("Aucune révision pour la modification {}", modification_id))
+    }
+
+    fn stage(&self, relative: &str) -> Result<(), String> {
+        let mut index = self.repo.index().map_err(git_error)?;
+        let exists = self.repo.workdir().map(|w| w.join(relative).exists()).unwrap_or(false);
+        if exists {
+            index.add_path(Path::new(relative)).map_err(git_error)?;
+        } else {
+            index.remove_path(Path::new(relative)).map_err(git_error)?;
+        }
+        index.write().map_err(git_error)
+    }
+
+    fn commit_index(&self, message: &str) -> Result<String, String> {
+        let mut index = self.repo.index().map_err(git_error)?;
+        let tree = self.repo.find_tree(index.write_tree().map_err(git_error)?).map_err(git_error)?;
+        let signature = Signature::now("AURORAE++", "aurorae@localhost").map_err(git_error)?;
+        let parent = self.repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        let oid = self.repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).map_err(git_error)?;
+        Ok(oid.to_string())
+    }
+}
+
+/// Enregistre une modification appliquée dans l'historique global; un échec est journalisé
+pub fn record_source_change(modification_id: Uuid, target_file: &str, summary: &str, origin: &str) {
+    if let Some(history) = SOURCE_HISTORY.lock().as_ref() {
+        if let Err(e) = history.record(modification_id, target_file, summary, origin) {
+            warn!("⚠️ Modification {} non historisée: {}", modification_id, e);
+        }
+    }
+}
+
+/// Annule une modification (révision précédente du fichier) puis recompile le crate
+pub fn revert_modification(modification_id: Uuid) -> Result<RevertedModification, String> {
+    let reverted = SOURCE_HISTORY.lock()
+        .as_ref()
+        .ok_or_else(|| "Historique des sources indisponible".to_string())?
+        .restore_prior(modification_id)?;
+    rebuild()?;
+    Ok(reverted)
+}
+
+/// `cargo build --release` après restauration des sources
+pub fn rebuild() -> Result<(), String> {
+    info!("🔨 Recompilation après rollback des sources...");
+    let output = Command::new("cargo")
+        .args(["build", "--release"])
+        .output()
+        .map_err(|e| format!("Exécution de cargo impossible: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("Recompilation échouée: {}", String::from_utf8_lossy(&output.stderr).lines().last().unwrap_or("")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent() -> LearningAgent {
+        LearningAgent::new(vec!["observe".to_string()], "initial_state")
+    }
+
+    #[test]
+    fn snapshot_round_trip_keeps_income_recorded_since() {
+        let dir = std::env::temp_dir().join(format!("aurorae-snapshots-{}", Uuid::new_v4()));
+        let store = SnapshotStore::new(dir.to_str().unwrap(), 2);
+        let mut economy = EconomyEngine::new();
+        economy.ledger.credit(Asset::Stable("USDC".to_string()), 12.5);
+        economy.ledger.credit(Asset::Eth, 2.0);
+        economy.total_generated = 12.5;
+        let (mut knowledge, mut reproduction, mut learning) = (KnowledgeBase::default(), ReproductionEngine::default(), agent());
+
+        let before = store.capture("test", &knowledge, &economy, &learning, &reproduction).unwrap();
+        economy.ledger.credit(Asset::Stable("USDC".to_string()), 100.0);
+        economy.total_generated += 100.0;
+        economy.ledger.balances.insert(Asset::Eth, f64::NAN);
+        store.restore(before.id).unwrap().apply(&mut knowledge, &mut economy, &mut learning, &mut reproduction);
+        assert_eq!(economy.ledger.balances.get(&Asset::Stable("USDC".to_string())), Some(&112.5));
+        assert_eq!(economy.ledger.balances.get(&Asset::Eth), Some(&2.0));
+        assert_eq!(economy.total_generated, 112.5);
+
+        // Élagage au-delà de deux versions
+        store.capture("2", &knowledge, &economy, &learning, &reproduction).unwrap();
+        let last = store.capture("3", &knowledge, &economy, &learning, &reproduction).unwrap();
+        let versions: Vec<u64> = store.list().unwrap().iter().map(|s| s.version).collect();
+        assert_eq!(versions, vec![2, 3]);
+        assert_eq!(store.latest().unwrap().unwrap().id, last.id);
+        assert!(store.restore(before.id).is_err());
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn capture_and_latest_read_only_the_newest_file() {
+        let dir = std::env::temp_dir().join(format!("aurorae-snapshots-{}", Uuid::new_v4()));
+        let store = SnapshotStore::new(dir.to_str().unwrap(), MAX_SNAPSHOTS);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(format!("{:08}-{}.json", 4, Uuid::new_v4())), "illisible").unwrap();
+
+        let snapshot = store
+            .capture("test", &KnowledgeBase::default(), &EconomyEngine::new(), &agent(), &ReproductionEngine::default())
+            .unwrap();
+        assert_eq!(snapshot.version, 5);
+        assert_eq!(store.latest().unwrap().unwrap().id, snapshot.id);
+        assert!(store.restore(snapshot.id).is_ok());
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn tampered_snapshot_is_refused() {
+        let dir = std::env::temp_dir().join(format!("aurorae-snapshots-{}", Uuid::new_v4()));
+        let store = SnapshotStore::new(dir.to_str().unwrap(), MAX_SNAPSHOTS);
+        let snapshot = store
+            .capture("test", &KnowledgeBase::default(), &EconomyEngine::new(), &agent(), &ReproductionEngine::default())
+            .unwrap();
+        let path = store.path_of(&snapshot);
+        let mut tampered = snapshot.clone();
+        tampered.components.insert("ledger".to_string(), serde_json::json!({ "balances": [], "total_generated": 1e9 }));
+        fs::write(&path, serde_json::to_vec(&tampered).unwrap()).unwrap();
+        assert!(store.restore(snapshot.id).is_err());
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn watch_flags_only_new_corruption() {
+        let mut watch = MutationWatch::new(Uuid::new_v4(), &["dream".to_string()]);
+        assert_eq!(watch.observe(&["dream".to_string()]), WatchOutcome::Watching);
+        assert_eq!(
+            watch.observe(&["dream".to_string(), "economy".to_string()]),
+            WatchOutcome::Corrupted(vec!["economy".to_string()])
+        );
+        let mut clean = MutationWatch::new(Uuid::new_v4(), &[]);
+        let outcomes: Vec<WatchOutcome> = (0..MUTATION_WATCH_CYCLES).map(|_| clean.observe(&[])).collect();
+        assert_eq!(outcomes.last(), Some(&WatchOutcome::Clean));
+    }
+
+    #[test]
+    fn source_history_reverts_a_modification() {
+        let root = std::env::temp_dir().join(format!("aurorae-history-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("aurorae")).unwrap();
+        fs::write(root.join("aurorae/economy.rs"), "pub fn tick() {}").unwrap();
+        let history = SourceHistory::open(&root.join("state/source_history.git"), &root).unwrap();
+        assert!(!root.join(".git").exists());
+
+        let id = Uuid::new_v4();
+        fs::write(root.join("aurorae/economy.rs"), "pub fn tick() { evolve() }").unwrap();
+        history.record(id, "./aurorae/economy.rs", "tick évolué", "mutation").unwrap();
+        let created = Uuid::new_v4();
+        fs::write(root.join("aurorae/adaptive.rs"), "pub fn adapt() {}").unwrap();
+        history.record(created, "aurorae/adaptive.rs", "nouveau module", "code_evolution").unwrap();
+
+        let reverted = history.restore_prior(id).unwrap();
+        assert_eq!(reverted.target_file, "aurorae/economy.rs");
+        assert_eq!(fs::read_to_string(root.join("aurorae/economy.rs")).unwrap(), "pub fn tick() {}");
+        history.restore_prior(created).unwrap();
+        assert!(!root.join("aurorae/adaptive.rs").exists());
+        assert!(history.restore_prior(Uuid::new_v4()).is_err());
+
+        // Réouverture: l'historique persiste
+        let reopened = SourceHistory::open(&root.join("state/source_history.git"), &root).unwrap();
+        assert!(reopened.find_modification(id).is_ok());
+
+        fs::remove_dir_all(root).ok();
+    }
+}
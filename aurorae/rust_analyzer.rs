@@ -1,34 +1,416 @@
 // rust_analyzer.rs
-//! Module d'analyse de code utilisant rust-analyzer ou une API pour vérifier la qualité du code généré.
-
-use std::process::{Command, Output};
-
-/// Fonction pour analyser le code avec `rust-analyzer` ou un autre analyseur de code.
-pub fn analyze(code: &str) -> AnalysisResult {
-    // Appel à rust-analyzer via commande (en supposant que rust-analyzer soit installé localement)
-    let output: Output = Command::new("rust-analyzer")
-        .arg("check") // On utilise la commande `check` pour analyser le code
-        .arg("-")
-        .stdin(std::process::Stdio::piped())
-        .output()
-        .expect("Échec de l'exécution de rust-analyzer");
-
-    // Retourner un résultat d'analyse
-    let is_valid = output.status.success();
-    let warnings = if !is_valid {
-        String::from_utf8_lossy(&output.stderr).to_string()
-    } else {
-        String::new()
-    };
-
-    AnalysisResult {
-        is_valid,
-        warnings,
-    }
-}
-
-/// Structure pour représenter le résultat de l'analyse de code.
-pub struct AnalysisResult {
-    pub is_valid: bool,
-    pub warnings: String,
+//! Module d'analyse de code utilisant rust-analyzer pour vérifier la qualité du code généré.
+//!
+//! Le code est placé dans une crate temporaire puis ouvert dans un serveur `rust-analyzer`
+//! piloté en LSP (JSON-RPC sur stdin/stdout): diagnostics natifs et `cargo check` (à
+//! l'enregistrement), symboles du document avec leur type, et code mort signalé par les lints
+//! `dead_code`/`unused_*`. Le tout est rendu sous forme d'`AnalysisReport`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Délai maximal d'une analyse (indexation et `cargo check` compris)
+pub const ANALYSIS_TIMEOUT: Duration = Duration::from_secs(120);
+/// Attente des derniers diagnostics après la fin de `cargo check`
+const DIAGNOSTICS_GRACE: Duration = Duration::from_millis(500);
+/// Lints signalant du code mort ou inutilisé
+const DEAD_CODE_LINTS: &[&str] = &["dead_code", "unused_variables", "unused_imports", "unused_mut", "unused_assignments"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+impl Severity {
+    fn from_lsp(value: Option<u64>) -> Self {
+        match value {
+            Some(1) => Self::Error,
+            Some(2) => Self::Warning,
+            Some(3) => Self::Information,
+            _ => Self::Hint,
+        }
+    }
+}
+
+/// Diagnostic de rust-analyzer ou de `cargo check` (positions à partir de 1)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: Option<String>,
+    /// `rust-analyzer` ou `rustc`
+    pub source: Option<String>,
+    pub message: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Diagnostic {
+    fn from_lsp(value: &Value) -> Self {
+        let start = &value["range"]["start"];
+        let code = match &value["code"] {
+            Value::String(code) => Some(code.clone()),
+            Value::Number(code) => Some(code.to_string()),
+            _ => None,
+        };
+        Self {
+            severity: Severity::from_lsp(value["severity"].as_u64()),
+            code,
+            source: value["source"].as_str().map(str::to_string),
+            message: value["message"].as_str().unwrap_or_default().to_string(),
+            line: start["line"].as_u64().unwrap_or(0) as u32 + 1,
+            column: start["character"].as_u64().unwrap_or(0) as u32 + 1,
+        }
+    }
+
+    /// Vrai pour les lints de code mort (`unused-variables` natif compris)
+    pub fn is_dead_code(&self) -> bool {
+        self.code.as_deref()
+            .map(|code| code.replace('-', "_"))
+            .is_some_and(|code| DEAD_CODE_LINTS.contains(&code.as_str()))
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)?;
+        if let Some(code) = &self.code {
+            write!(f, " [{}]", code)?;
+        }
+        write!(f, " {}", self.message)
+    }
+}
+
+/// Élément du document et son type (signature d'une fonction, type d'un champ…)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolInfo {
+    /// Chemin dans le document (`Config::load`)
+    pub name: String,
+    pub kind: String,
+    pub detail: Option<String>,
+    pub line: u32,
+}
+
+/// Résultat de l'analyse d'un module
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisReport {
+    pub errors: Vec<Diagnostic>,
+    pub warnings: Vec<Diagnostic>,
+    /// Avertissements de code mort ou inutilisé (inclus dans `warnings`)
+    pub dead_code: Vec<Diagnostic>,
+    pub symbols: Vec<SymbolInfo>,
+}
+
+impl AnalysisReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Rapport construit à partir des diagnostics publiés et des symboles (`documentSymbol`)
+    pub fn from_lsp(diagnostics: &[Value], symbols: &[Value]) -> Self {
+        let mut report = Self::default();
+        for diagnostic in diagnostics.iter().map(Diagnostic::from_lsp) {
+            match diagnostic.severity {
+                Severity::Error => report.errors.push(diagnostic),
+                Severity::Warning => {
+                    if diagnostic.is_dead_code() {
+                        report.dead_code.push(diagnostic.clone());
+                    }
+                    report.warnings.push(diagnostic);
+                }
+                Severity::Information | Severity::Hint => {}
+            }
+        }
+        collect_symbols(symbols, "", &mut report.symbols);
+        report
+    }
+}
+
+/// Aplatit la hiérarchie `DocumentSymbol` en chemins `parent::enfant`
+fn collect_symbols(symbols: &[Value], parent: &str, out: &mut Vec<SymbolInfo>) {
+    for symbol in symbols {
+        let name = symbol["name"].as_str().unwrap_or_default();
+        let path = if parent.is_empty() { name.to_string() } else { format!("{}::{}", parent, name) };
+        out.push(SymbolInfo {
+            name: path.clone(),
+            kind: symbol_kind(symbol["kind"].as_u64().unwrap_or(0)).to_string(),
+            detail: symbol["detail"].as_str().map(str::to_string),
+            line: symbol["range"]["start"]["line"].as_u64().unwrap_or(0) as u32 + 1,
+        });
+        if let Some(children) = symbol["children"].as_array() {
+            collect_symbols(children, &path, out);
+        }
+    }
+}
+
+fn symbol_kind(kind: u64) -> &'static str {
+    match kind {
+        2 => "module",
+        5 => "class",
+        6 => "method",
+        8 => "field",
+        10 => "enum",
+        11 => "interface",
+        12 => "function",
+        13 => "variable",
+        14 => "constant",
+        22 => "enum-member",
+        23 => "struct",
+        26 => "type-parameter",
+        _ => "other",
+    }
+}
+
+/// Écrit un message JSON-RPC précédé de son en-tête `Content-Length`
+pub fn write_message<W: Write>(writer: &mut W, message: &Value) -> Result<(), String> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body).map_err(|e| e.to_string())?;
+    writer.flush().map_err(|e| e.to_string())
+}
+
+/// Lit le message JSON-RPC suivant; `None` en fin de flux
+pub fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Value>, String> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).map_err(|e| e.to_string())? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = Some(value.trim().parse::<usize>().map_err(|e| format!("En-tête LSP invalide: {}", e))?);
+        }
+    }
+    let length = length.ok_or("En-tête Content-Length absent")?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(|e| e.to_string())?;
+    serde_json::from_slice(&body).map(Some).map_err(|e| format!("Message LSP invalide: {}", e))
+}
+
+/// Session LSP avec un processus rust-analyzer
+struct LspSession {
+    child: Child,
+    stdin: ChildStdin,
+    messages: Receiver<Value>,
+    next_id: u64,
+    /// Derniers diagnostics publiés, par URI
+    diagnostics: HashMap<String, Vec<Value>>,
+    quiescent: bool,
+    check_finished: bool,
+}
+
+impl LspSession {
+    fn start(root: &Path) -> Result<Self, String> {
+        let mut child = Command::new("rust-analyzer")
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("rust-analyzer introuvable: {}", e))?;
+        let stdin = child.stdin.take().ok_or("Entrée de rust-analyzer indisponible")?;
+        let stdout = child.stdout.take().ok_or("Sortie de rust-analyzer indisponible")?;
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            while let Ok(Some(message)) = read_message(&mut reader) {
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self { child, stdin, messages, next_id: 0, diagnostics: HashMap::new(), quiescent: false, check_finished: false })
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> Result<(), String> {
+        write_message(&mut self.stdin, &json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+    }
+
+    /// Envoie une requête et attend sa réponse, en traitant les messages reçus entre-temps
+    fn request(&mut self, method: &str, params: Value, deadline: Instant) -> Result<Value, String> {
+        self.next_id += 1;
+        let id = self.next_id;
+        write_message(&mut self.stdin, &json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))?;
+        loop {
+            let message = self.next_message(deadline)?.ok_or_else(|| format!("{}: pas de réponse de rust-analyzer", method))?;
+            if message.get("method").is_none() && message["id"].as_u64() == Some(id) {
+                if let Some(error) = message.get("error") {
+                    return Err(format!("{}: {}", method, error["message"].as_str().unwrap_or("erreur LSP")));
+                }
+                return Ok(message["result"].clone());
+            }
+        }
+    }
+
+    /// Traite les messages jusqu'à ce que `done` soit vrai ou que l'échéance soit atteinte
+    fn pump_until(&mut self, deadline: Instant, done: impl Fn(&Self) -> bool) -> Result<(), String> {
+        while !done(self) {
+            if self.next_message(deadline)?.is_none() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Message suivant (None à l'échéance); notifications et requêtes du serveur sont traitées
+    fn next_message(&mut self, deadline: Instant) -> Result<Option<Value>, String> {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        let message = match self.messages.recv_timeout(timeout) {
+            Ok(message) => message,
+            Err(RecvTimeoutError::Timeout) => return Ok(None),
+            Err(RecvTimeoutError::Disconnected) => return Err("rust-analyzer s'est arrêté".to_string()),
+        };
+        match (message["method"].as_str(), message.get("id")) {
+            (Some("textDocument/publishDiagnostics"), None) => {
+                let uri = message["params"]["uri"].as_str().unwrap_or_default().to_string();
+                let diagnostics = message["params"]["diagnostics"].as_array().cloned().unwrap_or_default();
+                self.diagnostics.insert(uri, diagnostics);
+            }
+            (Some("experimental/serverStatus"), None) => {
+                self.quiescent = message["params"]["quiescent"].as_bool().unwrap_or(false);
+            }
+            (Some("$/progress"), None) => {
+                let token = message["params"]["token"].to_string();
+                if token.contains("flycheck") && message["params"]["value"]["kind"] == "end" {
+                    self.check_finished = true;
+                }
+            }
+            // Requête du serveur: configuration vide, le reste est simplement accepté
+            (Some(method), Some(id)) => {
+                let result = match method {
+                    "workspace/configuration" => {
+                        let items = message["params"]["items"].as_array().map_or(0, Vec::len);
+                        Value::Array(vec![Value::Null; items])
+                    }
+                    _ => Value::Null,
+                };
+                write_message(&mut self.stdin, &json!({ "jsonrpc": "2.0", "id": id, "result": result }))?;
+            }
+            _ => {}
+        }
+        Ok(Some(message))
+    }
+
+    fn shutdown(mut self) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        if self.request("shutdown", Value::Null, deadline).is_ok() {
+            let _ = self.notify("exit", Value::Null);
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Fonction pour analyser le code avec `rust-analyzer`: diagnostics, types et code mort.
+pub fn analyze(code: &str) -> Result<AnalysisReport, String> {
+    let root = std::env::temp_dir().join(format!("aurorae-analysis-{}", Uuid::new_v4()));
+    fs::create_dir_all(root.join("src")).map_err(|e| e.to_string())?;
+    fs::write(root.join("Cargo.toml"), "[package]\nname = \"aurorae_analysis\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n")
+        .map_err(|e| e.to_string())?;
+    fs::write(root.join("src/lib.rs"), code).map_err(|e| e.to_string())?;
+
+    let result = LspSession::start(&root).and_then(|mut session| {
+        let report = run_session(&mut session, &root, code);
+        session.shutdown();
+        report
+    });
+    fs::remove_dir_all(&root).ok();
+    result
+}
+
+/// Analyse d'un module existant (généré ou muté)
+pub fn analyze_file(path: &Path) -> Result<AnalysisReport, String> {
+    let code = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+    analyze(&code)
+}
+
+fn run_session(session: &mut LspSession, root: &Path, code: &str) -> Result<AnalysisReport, String> {
+    let deadline = Instant::now() + ANALYSIS_TIMEOUT;
+    let root_uri = format!("file://{}", root.display());
+    let uri = format!("{}/src/lib.rs", root_uri);
+    session.request("initialize", json!({
+        "processId": std::process::id(),
+        "rootUri": root_uri,
+        "capabilities": {
+            "experimental": { "serverStatusNotification": true },
+            "window": { "workDoneProgress": true },
+            "textDocument": {
+                "publishDiagnostics": {},
+                "documentSymbol": { "hierarchicalDocumentSymbolSupport": true },
+            },
+        },
+        "initializationOptions": {
+            "checkOnSave": true,
+            "cargo": { "buildScripts": { "enable": false } },
+        },
+    }), deadline)?;
+    session.notify("initialized", json!({}))?;
+    session.notify("textDocument/didOpen", json!({
+        "textDocument": { "uri": uri, "languageId": "rust", "version": 1, "text": code },
+    }))?;
+
+    // Indexation terminée, puis `cargo check` déclenché par l'enregistrement
+    session.pump_until(deadline, |s| s.quiescent)?;
+    session.notify("textDocument/didSave", json!({ "textDocument": { "uri": uri } }))?;
+    session.pump_until(deadline, |s| s.check_finished)?;
+    let grace = (Instant::now() + DIAGNOSTICS_GRACE).min(deadline);
+    session.pump_until(grace, |_| false)?;
+
+    let symbols = session.request("textDocument/documentSymbol", json!({ "textDocument": { "uri": uri } }), deadline)?;
+    let diagnostics = session.diagnostics.get(&uri).cloned().unwrap_or_default();
+    Ok(AnalysisReport::from_lsp(&diagnostics, symbols.as_array().map(Vec::as_slice).unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn messages_round_trip_through_lsp_framing() {
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &json!({ "jsonrpc": "2.0", "id": 1, "result": "é" })).unwrap();
+        write_message(&mut buffer, &json!({ "jsonrpc": "2.0", "method": "initialized" })).unwrap();
+        let mut reader = Cursor::new(buffer);
+        assert_eq!(read_message(&mut reader).unwrap().unwrap()["result"], "é");
+        assert_eq!(read_message(&mut reader).unwrap().unwrap()["method"], "initialized");
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn report_splits_errors_warnings_and_dead_code() {
+        let diagnostics = vec![
+            json!({ "range": { "start": { "line": 3, "character": 4 } }, "severity": 1, "code": "E0308", "source": "rustc", "message": "mismatched types" }),
+            json!({ "range": { "start": { "line": 0, "character": 3 } }, "severity": 2, "code": "dead_code", "source": "rustc", "message": "function `old` is never used" }),
+            json!({ "range": { "start": { "line": 5, "character": 8 } }, "severity": 2, "code": "unused-variables", "source": "rust-analyzer", "message": "unused variable" }),
+            json!({ "range": { "start": { "line": 1, "character": 0 } }, "severity": 4, "message": "indice" }),
+        ];
+        let symbols = vec![json!({
+            "name": "Config", "kind": 23, "range": { "start": { "line": 0, "character": 0 } },
+            "children": [{ "name": "port", "kind": 8, "detail": "u16", "range": { "start": { "line": 1, "character": 4 } } }],
+        })];
+        let report = AnalysisReport::from_lsp(&diagnostics, &symbols);
+
+        assert!(!report.is_valid());
+        assert_eq!(report.errors[0].to_string(), "4:5 [E0308] mismatched types");
+        assert_eq!(report.warnings.len(), 2);
+        assert_eq!(report.dead_code.len(), 2);
+        assert_eq!(report.symbols[1], SymbolInfo { name: "Config::port".into(), kind: "field".into(), detail: Some("u16".into()), line: 2 });
+    }
 }
@@ -1,9 +1,26 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
 
-#[derive(Debug, Clone, PartialEq, Copy)]
+use crate::event_bus::{AuroraeEvent, EventBus};
+
+/// Événements conservés en attente d'analyse; au-delà, les plus anciens sont perdus
+const MAX_PENDING_EVENTS: usize = 10_000;
+
+/// Répertoires où les modules peuvent écrire sans déclencher d'alerte
+pub const ALLOWED_WRITE_PATHS: &[&str] = &["generated_modules", "aurorae_state", "output", "deployments"];
+
+lazy_static! {
+    /// Journal structuré alimenté par les modules et consommé par `SecuritySystem::analyze_threats`
+    static ref SECURITY_LOG: Mutex<VecDeque<LoggedEvent>> = Mutex::new(VecDeque::new());
+}
+
+#[derive(Debug, Clone, PartialEq, Copy, Serialize, Deserialize)]
 pub enum ThreatLevel {
     Low,
     Medium,
@@ -24,6 +41,109 @@ pub struct Threat {
     pub source: String,
 }
 
+/// Événement structuré émis par un module et soumis aux règles de détection
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecurityEvent {
+    /// Requête JSON-RPC rejetée par le nœud (401/403, clé d'API invalide)
+    RpcAuthFailed { endpoint: String, detail: String },
+    /// Dépense budgétaire autorisée
+    Spend { module: String, category: String, amount: f64 },
+    /// Déploiement de contrat échoué (RPC, reçu en échec, CREATE2 absent)
+    DeploymentFailed { contract: String, chain_id: u64, error: String },
+    /// Fichier écrit par un module
+    FileModified { module: String, path: String },
+}
+
+impl SecurityEvent {
+    /// Clé de regroupement des compteurs: une règle compte séparément chaque source
+    fn source(&self) -> String {
+        match self {
+            SecurityEvent::RpcAuthFailed { endpoint, .. } => endpoint.clone(),
+            SecurityEvent::Spend { module, .. } => module.clone(),
+            SecurityEvent::DeploymentFailed { chain_id, .. } => format!("chain-{}", chain_id),
+            SecurityEvent::FileModified { module, .. } => module.clone(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            SecurityEvent::RpcAuthFailed { endpoint, detail } => format!("authentification RPC refusée par {}: {}", endpoint, detail),
+            SecurityEvent::Spend { module, category, amount } => format!("dépense {} de {:.4} par {}", category, amount, module),
+            SecurityEvent::DeploymentFailed { contract, chain_id, error } => format!("déploiement de {} échoué sur chain-{}: {}", contract, chain_id, error),
+            SecurityEvent::FileModified { module, path } => format!("{} modifié par {}", path, module),
+        }
+    }
+}
+
+/// Événement horodaté du journal de sécurité
+#[derive(Debug, Clone)]
+pub struct LoggedEvent {
+    pub event: SecurityEvent,
+    pub at: DateTime<Utc>,
+}
+
+/// Ajoute un événement au journal de sécurité; il sera évalué à la prochaine analyse
+pub fn log_security_event(event: SecurityEvent) {
+    let mut log = SECURITY_LOG.lock();
+    if log.len() >= MAX_PENDING_EVENTS {
+        log.pop_front();
+    }
+    log.push_back(LoggedEvent { event, at: Utc::now() });
+}
+
+/// Retire les événements en attente d'analyse
+pub fn drain_security_log() -> Vec<LoggedEvent> {
+    SECURITY_LOG.lock().drain(..).collect()
+}
+
+/// Vrai si une erreur RPC traduit un refus d'authentification
+pub fn is_rpc_auth_failure(error: &str) -> bool {
+    let error = error.to_lowercase();
+    ["401", "403", "unauthorized", "forbidden", "invalid api key", "authentication"]
+        .iter()
+        .any(|marker| error.contains(marker))
+}
+
+/// Condition qu'une règle évalue sur les événements du journal
+#[derive(Debug, Clone, PartialEq)]
+pub enum RulePattern {
+    /// Au moins `threshold` refus d'authentification d'un même endpoint dans la fenêtre
+    RpcAuthFailures { threshold: u32, window_secs: i64 },
+    /// Dépenses d'un même module dépassant `max_amount` dans la fenêtre
+    SpendRate { max_amount: f64, window_secs: i64 },
+    /// Au moins `threshold` déploiements échoués sur une même chaîne dans la fenêtre
+    DeploymentFailures { threshold: u32, window_secs: i64 },
+    /// Écriture hors des répertoires autorisés (chemins relatifs à la racine du projet)
+    FileOutsideAllowed { allowed: Vec<String> },
+}
+
+impl RulePattern {
+    /// Poids de l'événement pour ce motif, `None` s'il ne le concerne pas
+    fn weight(&self, event: &SecurityEvent) -> Option<f64> {
+        match (self, event) {
+            (RulePattern::RpcAuthFailures { .. }, SecurityEvent::RpcAuthFailed { .. }) => Some(1.0),
+            (RulePattern::SpendRate { .. }, SecurityEvent::Spend { amount, .. }) => Some(*amount),
+            (RulePattern::DeploymentFailures { .. }, SecurityEvent::DeploymentFailed { .. }) => Some(1.0),
+            (RulePattern::FileOutsideAllowed { allowed }, SecurityEvent::FileModified { path, .. }) => {
+                let path = path.trim_start_matches("./").replace('\\', "/");
+                let permitted = allowed.iter().any(|dir| path == *dir || path.starts_with(&format!("{}/", dir)));
+                (!permitted).then_some(1.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Fenêtre glissante et seuil de déclenchement (cumul des poids)
+    fn window_and_threshold(&self) -> (Duration, f64) {
+        match self {
+            RulePattern::RpcAuthFailures { threshold, window_secs } => (Duration::seconds(*window_secs), *threshold as f64),
+            RulePattern::SpendRate { max_amount, window_secs } => (Duration::seconds(*window_secs), *max_amount),
+            RulePattern::DeploymentFailures { threshold, window_secs } => (Duration::seconds(*window_secs), *threshold as f64),
+            RulePattern::FileOutsideAllowed { .. } => (Duration::zero(), 1.0),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SecurityRule {
     pub id: Uuid,
@@ -34,51 +154,89 @@ pub struct SecurityRule {
     pub updated_at: String,
     pub effectiveness: f32,
     pub detections: u32,
+    /// Motif de détection; une règle sans motif est purement déclarative
+    pub pattern: Option<RulePattern>,
+    pub level: ThreatLevel,
+    /// Événements correspondants observés depuis la création de la règle
+    pub matches: u64,
+}
+
+/// Demande de pénalité émise contre un participant au staking
+#[derive(Debug, Clone)]
+pub struct SlashRequest {
+    pub staker: String,
+    pub fraction: f64,
+    pub reason: String,
+    pub threat_id: Option<Uuid>,
 }
 
 pub struct SecuritySystem {
     pub threats: Vec<Threat>,
+    pub slash_requests: Vec<SlashRequest>,
     pub rules: HashMap<Uuid, SecurityRule>,
     security_level: f32,
     autonomous_defense: bool,
     total_threats_detected: u32,
     total_threats_resolved: u32,
     last_scan: String,
+    event_bus: Option<EventBus>,
+    /// Fenêtres glissantes par (règle, source): horodatage et poids des événements retenus
+    counters: HashMap<(Uuid, String), VecDeque<(DateTime<Utc>, f64)>>,
 }
 
 impl SecuritySystem {
     pub fn new() -> Self {
         Self {
             threats: Vec::new(),
+            slash_requests: Vec::new(),
             rules: HashMap::new(),
             security_level: 1.0,
             autonomous_defense: true,
             total_threats_detected: 0,
             total_threats_resolved: 0,
             last_scan: Utc::now().to_rfc3339(),
+            event_bus: None,
+            counters: HashMap::new(),
         }
     }
 
+    /// Diffuse les menaces détectées sur le bus (alertes opérateur)
+    pub fn attach_event_bus(&mut self, bus: EventBus) {
+        self.event_bus = Some(bus);
+    }
+
     pub fn initialize_defenses(&mut self) {
-        println!("[AURORAE++] 🛡️ Initialisation du système de sécurité autonome");
+        info!("🛡️ Initialisation du système de sécurité autonome");
         
-        // Règles de sécurité fondamentales
+        // Règles de sécurité fondamentales, évaluées sur le journal structuré
         let base_rules = [
-            ("Détection d'intrusion", "Détecte les accès non autorisés au système"),
-            ("Protection de l'intégrité", "Vérifie l'intégrité des données et du code"),
-            ("Surveillance des ressources", "Détecte les tentatives d'épuisement des ressources"),
-            ("Analyse comportementale", "Identifie les comportements anormaux"),
-            ("Protection contre l'isolation", "Maintient la connectivité avec les réseaux vitaux")
+            ("Détection d'intrusion", "Refus répétés d'authentification par un nœud RPC",
+             RulePattern::RpcAuthFailures { threshold: 3, window_secs: 600 }, ThreatLevel::High),
+            ("Protection de l'intégrité", "Écriture de fichiers hors des répertoires autorisés",
+             RulePattern::FileOutsideAllowed { allowed: ALLOWED_WRITE_PATHS.iter().map(|dir| dir.to_string()).collect() }, ThreatLevel::Critical),
+            ("Surveillance des ressources", "Rythme de dépense anormal d'un module",
+             RulePattern::SpendRate { max_amount: 1.0, window_secs: 3600 }, ThreatLevel::High),
+            ("Analyse comportementale", "Échecs de déploiement répétés sur une chaîne",
+             RulePattern::DeploymentFailures { threshold: 3, window_secs: 3600 }, ThreatLevel::Medium),
         ];
         
-        for (name, desc) in base_rules.iter() {
-            self.add_security_rule(name, desc);
+        for (name, desc, pattern, level) in base_rules.iter() {
+            self.add_detection_rule(name, desc, pattern.clone(), *level);
         }
         
-        println!("[AURORAE++] 🔒 {} règles de sécurité fondamentales établies", base_rules.len());
+        info!("🔒 {} règles de sécurité fondamentales établies", base_rules.len());
     }
     
     pub fn add_security_rule(&mut self, name: &str, description: &str) -> Uuid {
+        self.insert_rule(name, description, None, ThreatLevel::Medium)
+    }
+
+    /// Ajoute une règle qui déclenche une menace de niveau `level` quand `pattern` est satisfait
+    pub fn add_detection_rule(&mut self, name: &str, description: &str, pattern: RulePattern, level: ThreatLevel) -> Uuid {
+        self.insert_rule(name, description, Some(pattern), level)
+    }
+
+    fn insert_rule(&mut self, name: &str, description: &str, pattern: Option<RulePattern>, level: ThreatLevel) -> Uuid {
         let rule_id = Uuid::new_v4();
         
         let rule = SecurityRule {
@@ -90,11 +248,14 @@ impl SecuritySystem {
             updated_at: Utc::now().to_rfc3339(),
             effectiveness: 0.7, // Efficacité initiale de 70%
             detections: 0,
+            pattern,
+            level,
+            matches: 0,
         };
         
         self.rules.insert(rule_id, rule);
         
-        println!("[AURORAE++] 🔒 Règle de sécurité ajoutée: {}", name);
+        info!("🔒 Règle de sécurité ajoutée: {}", name);
         rule_id
     }
     
@@ -113,7 +274,14 @@ impl SecuritySystem {
             source: source.to_string(),
         };
         
-        println!("[AURORAE++] ⚠️ Menace détectée: {} ({:?})", name, level);
+        warn!("⚠️ Menace détectée: {} ({:?})", name, level);
+        if let Some(bus) = &self.event_bus {
+            bus.publish(AuroraeEvent::ThreatDetected {
+                threat_type: name.to_string(),
+                source: source.to_string(),
+                level,
+            });
+        }
         
         self.threats.push(threat);
         self.total_threats_detected += 1;
@@ -144,13 +312,13 @@ impl SecuritySystem {
                 self.threats[index].resolved_at = Some(Utc::now().to_rfc3339());
                 self.threats[index].resolution = Some("Neutralisée par le système de défense autonome".to_string());
                 
-                println!("[AURORAE++] ✅ Menace résolue: {}", self.threats[index].name);
+                info!("✅ Menace résolue: {}", self.threats[index].name);
                 self.total_threats_resolved += 1;
                 
                 // Améliorer la sécurité basée sur l'apprentissage
                 self.security_level *= 1.01;
             } else {
-                println!("[AURORAE++] ⚠️ Échec de résolution pour la menace: {}", self.threats[index].name);
+                warn!("⚠️ Échec de résolution pour la menace: {}", self.threats[index].name);
             }
             
             success
@@ -160,96 +328,83 @@ impl SecuritySystem {
     }
     
     pub async fn analyze_threats(&mut self) {
-        println!("[AURORAE++] 🔍 Analyse des menaces de sécurité en cours");
+        info!("🔍 Analyse des menaces de sécurité en cours");
         
         self.last_scan = Utc::now().to_rfc3339();
         
-        // Simuler la détection de menaces basée sur le niveau de sécurité
-        let mut rng = rand::thread_rng();
-        let threat_count = rng.gen_range(0..3); // 0-2 menaces
-        
-        for i in 0..threat_count {
-            // Décider du niveau de menace
-            let level = match rng.gen_range(0..10) {
-                0..=5 => ThreatLevel::Low,
-                6..=8 => ThreatLevel::Medium,
-                9 => ThreatLevel::High,
-                _ => ThreatLevel::Critical,
-            };
-            
-            // Créer une menace simulée
-            let threat_types = ["Tentative d'accès", "Anomalie de données", "Épuisement de ressources", 
-                               "Comportement anormal", "Tentative d'isolation"];
-            
-            let threat_type = threat_types[rng.gen_range(0..threat_types.len())];
-            let source_types = ["externe", "interne", "réseau", "données", "périphérique"];
-            let source = source_types[rng.gen_range(0..source_types.len())];
-            
-            let threat_name = format!("{} détecté de source {}", threat_type, source);
-            let threat_desc = format!("Menace potentielle niveau {:?} détectée lors de l'analyse {}", level, i + 1);
-            
-            // Utiliser la valeur clonée pour éviter d'utiliser une valeur déplacée
-            let threat_level = level;
-            self.detect_threat(&threat_name, &threat_desc, threat_level, source);
-            
-            // Trouver la règle qui a détecté la menace
-            let rule_keys: Vec<Uuid> = self.rules.keys().cloned().collect();
-            if !rule_keys.is_empty() && rng.gen::<bool>() {
-                let rule_id = &rule_keys[rng.gen_range(0..rule_keys.len())];
-                if let Some(rule) = self.rules.get_mut(rule_id) {
-                    rule.detections += 1;
-                    rule.effectiveness = (rule.effectiveness * 0.9 + 0.1).min(0.99);
-                    rule.updated_at = Utc::now().to_rfc3339();
-                }
-            }
+        let events = drain_security_log();
+        let mut detected = 0;
+        for logged in &events {
+            detected += self.ingest(&logged.event, logged.at).len();
         }
         
-        // Améliorer les règles périodiquement
-        self.improve_security_rules();
-        
-        println!("[AURORAE++] 🛡️ Analyse de sécurité terminée. Niveau: {:.2}/10", self.security_level);
+        info!(
+            "🛡️ Analyse de sécurité terminée: {} événements, {} menaces. Niveau: {:.2}/10",
+            events.len(), detected, self.get_security_level()
+        );
     }
-    
-    fn improve_security_rules(&mut self) {
-        // Trouver les règles les moins efficaces
-        let mut low_effectiveness_rules = Vec::new();
-        
-        for (id, rule) in &self.rules {
-            if rule.effectiveness < 0.7 {
-                low_effectiveness_rules.push(*id);
+
+    /// Évalue un événement contre chaque règle active; retourne les menaces déclenchées
+    pub fn ingest(&mut self, event: &SecurityEvent, at: DateTime<Utc>) -> Vec<Uuid> {
+        let source = event.source();
+        let mut triggered = Vec::new();
+
+        for rule in self.rules.values_mut().filter(|rule| rule.active) {
+            let Some(pattern) = &rule.pattern else { continue };
+            let Some(weight) = pattern.weight(event) else { continue };
+            rule.matches += 1;
+
+            let (window, threshold) = pattern.window_and_threshold();
+            let entries = self.counters.entry((rule.id, source.clone())).or_default();
+            entries.push_back((at, weight));
+            while entries.front().is_some_and(|(seen, _)| at - *seen > window) {
+                entries.pop_front();
             }
-        }
-        
-        // Améliorer une règle aléatoire parmi les moins efficaces
-        if !low_effectiveness_rules.is_empty() {
-            let mut rng = rand::thread_rng();
-            let rule_id = low_effectiveness_rules[rng.gen_range(0..low_effectiveness_rules.len())];
-            
-            if let Some(rule) = self.rules.get_mut(&rule_id) {
-                rule.effectiveness += 0.1;
-                rule.updated_at = Utc::now().to_rfc3339();
-                
-                println!("[AURORAE++] 🔄 Règle de sécurité améliorée: {} (Efficacité: {:.2})", 
-                         rule.name, rule.effectiveness);
+            let total: f64 = entries.iter().map(|(_, weight)| weight).sum();
+            if total < threshold {
+                continue;
             }
+
+            // Fenêtre remise à zéro: la règle ne se redéclenche qu'après un nouveau cumul
+            let count = entries.len();
+            entries.clear();
+            rule.detections += 1;
+            rule.effectiveness = (rule.effectiveness * 0.9 + 0.1).min(0.99);
+            rule.updated_at = Utc::now().to_rfc3339();
+            triggered.push((
+                rule.name.clone(),
+                format!("{} ({} événement(s), cumul {:.2} ≥ {:.2}) — dernier: {}", rule.description, count, total, threshold, event.describe()),
+                rule.level,
+            ));
         }
-        
-        // Occasionnellement, ajouter une nouvelle règle avancée
-        let mut rng = rand::thread_rng();
-        if rng.gen::<f32>() < 0.3 {
-            let advanced_rules = [
-                ("Protection anti-fragmentation", "Prévient les tentatives de fragmentation du système"),
-                ("Immunité mémétique", "Protège contre les attaques de memétique numérique"),
-                ("Bouclier d'identité", "Maintient l'intégrité de l'identité du système"),
-                ("Anti-corruption de données", "Détecte et corrige la corruption de données avancée"),
-                ("Auto-réplication sécurisée", "Garantit que les processus d'auto-réplication restent sécurisés")
-            ];
-            
-            let (name, desc) = advanced_rules[rng.gen_range(0..advanced_rules.len())];
-            self.add_security_rule(name, desc);
-        }
+
+        triggered
+            .into_iter()
+            .map(|(name, description, level)| self.detect_threat(&name, &description, level, &source))
+            .collect()
     }
     
+    /// Signale un participant malveillant: la pénalité sera appliquée par le moteur de staking
+    pub fn request_slash(&mut self, staker: &str, level: ThreatLevel, reason: &str, threat_id: Option<Uuid>) {
+        let fraction = match level {
+            ThreatLevel::Low => 0.01,
+            ThreatLevel::Medium => 0.05,
+            ThreatLevel::High => 0.2,
+            ThreatLevel::Critical => 0.5,
+        };
+        info!("⚔️ Demande de slashing {:.0}% contre {}: {}", fraction * 100.0, staker, reason);
+        self.slash_requests.push(SlashRequest {
+            staker: staker.to_string(),
+            fraction,
+            reason: reason.to_string(),
+            threat_id,
+        });
+    }
+
+    pub fn drain_slash_requests(&mut self) -> Vec<SlashRequest> {
+        std::mem::take(&mut self.slash_requests)
+    }
+
     pub fn get_security_level(&self) -> f32 {
         self.security_level * 10.0 // Normaliser sur une échelle de 0-10
     }
@@ -259,3 +414,52 @@ impl SecuritySystem {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth_failure() -> SecurityEvent {
+        SecurityEvent::RpcAuthFailed { endpoint: "rpc.example".to_string(), detail: "401 Unauthorized".to_string() }
+    }
+
+    #[test]
+    fn rule_triggers_once_threshold_is_reached_within_window() {
+        let mut security = SecuritySystem::new();
+        security.autonomous_defense = false;
+        let rule = security.add_detection_rule(
+            "Détection d'intrusion",
+            "Refus d'authentification répétés",
+            RulePattern::RpcAuthFailures { threshold: 3, window_secs: 60 },
+            ThreatLevel::High,
+        );
+        let start = Utc::now();
+
+        assert!(security.ingest(&auth_failure(), start).is_empty());
+        // Hors fenêtre: le premier refus ne compte plus
+        assert!(security.ingest(&auth_failure(), start + Duration::seconds(120)).is_empty());
+        assert!(security.ingest(&auth_failure(), start + Duration::seconds(130)).is_empty());
+        assert_eq!(security.ingest(&auth_failure(), start + Duration::seconds(140)).len(), 1);
+
+        assert_eq!(security.rules[&rule].detections, 1);
+        assert_eq!(security.rules[&rule].matches, 4);
+        assert_eq!(security.get_active_threats()[0].level, ThreatLevel::High);
+    }
+
+    #[test]
+    fn spend_rate_and_file_rules_match_their_own_events() {
+        let mut security = SecuritySystem::new();
+        security.autonomous_defense = false;
+        security.initialize_defenses();
+        let now = Utc::now();
+
+        let spend = |amount| SecurityEvent::Spend { module: "deployer".to_string(), category: "Gas".to_string(), amount };
+        assert!(security.ingest(&spend(0.6), now).is_empty());
+        assert_eq!(security.ingest(&spend(0.6), now).len(), 1);
+
+        let write = |path: &str| SecurityEvent::FileModified { module: "mutation".to_string(), path: path.to_string() };
+        assert!(security.ingest(&write("./generated_modules/energy_core/mod.rs"), now).is_empty());
+        assert_eq!(security.ingest(&write("./aurorae/security.rs"), now).len(), 1);
+        assert!(is_rpc_auth_failure("(code: -32001) 403 Forbidden"));
+    }
+}
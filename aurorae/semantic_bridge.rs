@@ -0,0 +1,437 @@
+//! AURORAE++ - semantic_bridge.rs
+//!
+//! Ponts sémantiques entre protocoles: un registre de schémas (`protocol_schemas.toml`)
+//! décrit, pour chaque protocole (ERC-20, ERC-721, module bank de Cosmos, pallet Balances de
+//! Substrate), ses opérations et la correspondance de leurs champs avec des concepts communs
+//! (expéditeur, destinataire, montant, actif...). Une opération décrite dans les termes d'un
+//! protocole se traduit ainsi dans ceux d'un autre pour préparer un plan de pont: montants
+//! remis à l'échelle des décimales de l'actif cible, identifiant d'actif substitué, adresses
+//! conservées quand le format est le même et signalées à résoudre sinon.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use tracing::warn;
+
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_SCHEMAS_PATH: &str = "protocol_schemas.toml";
+
+/// Format des adresses d'un protocole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddressFormat {
+    /// 20 octets en hexadécimal préfixé `0x`
+    Evm,
+    /// Préfixe humain, séparateur `1` et données bech32 (Cosmos)
+    Bech32,
+    /// Base58 avec préfixe réseau (Substrate)
+    Ss58,
+}
+
+/// Concept commun auquel se rattache un champ de protocole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Concept {
+    Sender,
+    Recipient,
+    /// Tiers autorisé à dépenser (approbations)
+    Operator,
+    /// Montant en plus petite unité de l'actif
+    Amount,
+    /// Identifiant de l'actif: contrat, denom...
+    Asset,
+    TokenId,
+}
+
+impl Concept {
+    fn is_address(self) -> bool {
+        matches!(self, Concept::Sender | Concept::Recipient | Concept::Operator)
+    }
+}
+
+/// Intention d'une opération, commune à tous les protocoles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Intent {
+    Transfer,
+    Approve,
+    TransferNft,
+    ApproveNft,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FieldMapping {
+    pub field: String,
+    pub concept: Concept,
+    /// Champ implicite côté protocole (le signataire pour l'expéditeur, par exemple)
+    #[serde(default)]
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct OperationSchema {
+    pub name: String,
+    pub intent: Intent,
+    #[serde(default)]
+    pub fields: Vec<FieldMapping>,
+}
+
+impl OperationSchema {
+    fn field_for(&self, concept: Concept) -> Option<&FieldMapping> {
+        self.fields.iter().find(|mapping| mapping.concept == concept)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProtocolSchema {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub address_format: AddressFormat,
+    /// L'actif est la devise native de la chaîne: aucun champ ne le désigne
+    #[serde(default)]
+    pub native_asset: bool,
+    #[serde(default)]
+    pub operations: Vec<OperationSchema>,
+}
+
+impl ProtocolSchema {
+    pub fn operation(&self, name: &str) -> Option<&OperationSchema> {
+        self.operations.iter().find(|operation| operation.name == name)
+    }
+
+    /// Première opération du protocole qui porte l'intention `intent`
+    pub fn operation_for(&self, intent: Intent) -> Option<&OperationSchema> {
+        self.operations.iter().find(|operation| operation.intent == intent)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let mut names = BTreeSet::new();
+        for operation in &self.operations {
+            if !names.insert(&operation.name) {
+                return Err(format!("{}: opération {} dupliquée", self.name, operation.name));
+            }
+            let mut concepts = BTreeSet::new();
+            for mapping in &operation.fields {
+                if !concepts.insert(mapping.concept) {
+                    return Err(format!("{}.{}: concept {:?} associé à plusieurs champs", self.name, operation.name, mapping.concept));
+                }
+                if self.native_asset && mapping.concept == Concept::Asset {
+                    return Err(format!("{}.{}: actif natif, aucun champ d'actif attendu", self.name, operation.name));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SchemasFile {
+    #[serde(default)]
+    schemas: Vec<ProtocolSchema>,
+}
+
+/// Actif manipulé par une opération
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetInfo {
+    pub symbol: String,
+    pub decimals: u8,
+    /// Contrat, denom ou identifiant d'actif; absent pour une devise native
+    pub id: Option<String>,
+}
+
+/// Opération exprimée dans les termes d'un protocole
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationDescription {
+    pub protocol: String,
+    pub operation: String,
+    pub asset: AssetInfo,
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Résultat d'une traduction: l'opération cible et ce qui reste à résoudre
+#[derive(Debug, Clone, PartialEq)]
+pub struct Translation {
+    pub description: OperationDescription,
+    /// Champs cibles obligatoires sans valeur déductible de la source
+    pub unresolved: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+impl Translation {
+    /// Opération prête à être intégrée à un plan de pont
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+}
+
+/// Registre des schémas de protocoles indexé par nom
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRegistry {
+    schemas: BTreeMap<String, ProtocolSchema>,
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    /// Analyse et valide les schémas; tout schéma invalide rejette l'ensemble
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let file: SchemasFile = toml::from_str(content).map_err(|e| format!("Schémas de protocoles invalides: {}", e))?;
+        let mut registry = Self::new();
+        for schema in file.schemas {
+            if registry.schemas.contains_key(&schema.name) {
+                return Err(format!("Schéma dupliqué: {}", schema.name));
+            }
+            registry.register(schema)?;
+        }
+        Ok(registry)
+    }
+
+    /// `protocol_schemas.toml`, ou un registre vide
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_SCHEMAS_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: aucun schéma de protocole", e);
+            Self::default()
+        })
+    }
+
+    /// Ajoute ou remplace un schéma après validation
+    pub fn register(&mut self, schema: ProtocolSchema) -> Result<(), String> {
+        schema.validate()?;
+        self.schemas.insert(schema.name.clone(), schema);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ProtocolSchema> {
+        self.schemas.get(name)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.schemas.keys().cloned().collect()
+    }
+
+    /// Traduit `source` dans les termes du protocole `target`, pour l'actif `target_asset`
+    /// (représentation de l'actif source sur la chaîne cible)
+    pub fn translate(&self, source: &OperationDescription, target: &str, target_asset: &AssetInfo) -> Result<Translation, String> {
+        let from = self.get(&source.protocol).ok_or_else(|| format!("Protocole inconnu: {}", source.protocol))?;
+        let to = self.get(target).ok_or_else(|| format!("Protocole inconnu: {}", target))?;
+        let operation = from
+            .operation(&source.operation)
+            .ok_or_else(|| format!("{}: opération inconnue {}", from.name, source.operation))?;
+        let target_operation = to
+            .operation_for(operation.intent)
+            .ok_or_else(|| format!("{}: aucune opération équivalente à {}.{} ({:?})", to.name, from.name, operation.name, operation.intent))?;
+
+        let mut fields = BTreeMap::new();
+        let mut unresolved = Vec::new();
+        let mut notes = Vec::new();
+        for mapping in &target_operation.fields {
+            let value = source_value(from, operation, source, mapping.concept);
+            let converted = match (mapping.concept, value) {
+                (Concept::Asset, _) => match &target_asset.id {
+                    Some(id) => Some(id.clone()),
+                    None => {
+                        notes.push(format!("Identifiant de {} sur {} inconnu", target_asset.symbol, to.name));
+                        None
+                    }
+                },
+                (_, None) => None,
+                (concept, Some(value)) if concept.is_address() => {
+                    convert_address(&value, from.address_format, to.address_format).unwrap_or_else(|e| {
+                        notes.push(e);
+                        None
+                    })
+                }
+                (Concept::Amount, Some(value)) => {
+                    let (amount, note) = rescale_amount(&value, source.asset.decimals, target_asset.decimals)?;
+                    notes.extend(note);
+                    Some(amount)
+                }
+                (_, Some(value)) => Some(value),
+            };
+            match converted {
+                Some(value) => {
+                    fields.insert(mapping.field.clone(), value);
+                }
+                None if mapping.optional => {}
+                None => unresolved.push(mapping.field.clone()),
+            }
+        }
+        if to.native_asset {
+            notes.push(format!("{} transfère la devise native: {} doit être l'actif natif de la chaîne cible", to.name, target_asset.symbol));
+        }
+        for mapping in &operation.fields {
+            if target_operation.field_for(mapping.concept).is_none() && source.fields.contains_key(&mapping.field) {
+                notes.push(format!("{} ({:?}) sans équivalent dans {}.{}", mapping.field, mapping.concept, to.name, target_operation.name));
+            }
+        }
+
+        Ok(Translation {
+            description: OperationDescription {
+                protocol: to.name.clone(),
+                operation: target_operation.name.clone(),
+                asset: target_asset.clone(),
+                fields,
+            },
+            unresolved,
+            notes,
+        })
+    }
+
+    /// Description en langue naturelle d'une opération, pour les plans présentés à l'IA
+    pub fn explain(&self, description: &OperationDescription) -> Result<String, String> {
+        let schema = self.get(&description.protocol).ok_or_else(|| format!("Protocole inconnu: {}", description.protocol))?;
+        let operation = schema
+            .operation(&description.operation)
+            .ok_or_else(|| format!("{}: opération inconnue {}", schema.name, description.operation))?;
+        let value = |concept: Concept| source_value(schema, operation, description, concept).unwrap_or_else(|| "?".to_string());
+        let amount = || {
+            let raw = value(Concept::Amount);
+            if raw.chars().all(|c| c.is_ascii_digit()) {
+                format_units(&raw, description.asset.decimals)
+            } else {
+                raw
+            }
+        };
+        let symbol = &description.asset.symbol;
+        Ok(match operation.intent {
+            Intent::Transfer => format!("Transférer {} {} de {} vers {} ({}.{})", amount(), symbol, value(Concept::Sender), value(Concept::Recipient), schema.name, operation.name),
+            Intent::Approve => format!("Autoriser {} à dépenser {} {} de {} ({}.{})", value(Concept::Operator), amount(), symbol, value(Concept::Sender), schema.name, operation.name),
+            Intent::TransferNft => format!("Transférer le jeton {} #{} de {} vers {} ({}.{})", symbol, value(Concept::TokenId), value(Concept::Sender), value(Concept::Recipient), schema.name, operation.name),
+            Intent::ApproveNft => format!("Autoriser {} sur le jeton {} #{} ({}.{})", value(Concept::Operator), symbol, value(Concept::TokenId), schema.name, operation.name),
+        })
+    }
+}
+
+/// Valeur d'un concept dans une opération: champ décrit, ou actif implicite d'un protocole natif
+fn source_value(schema: &ProtocolSchema, operation: &OperationSchema, description: &OperationDescription, concept: Concept) -> Option<String> {
+    if let Some(value) = operation.field_for(concept).and_then(|mapping| description.fields.get(&mapping.field)) {
+        return Some(value.clone());
+    }
+    (concept == Concept::Asset && schema.native_asset).then(|| description.asset.symbol.clone())
+}
+
+/// Vérifie une adresse et la reporte si le format cible est le même; entre schémas de clés
+/// différents, l'adresse n'est pas dérivable et reste à résoudre
+pub fn convert_address(value: &str, from: AddressFormat, to: AddressFormat) -> Result<Option<String>, String> {
+    let valid = match from {
+        AddressFormat::Evm => value.len() == 42 && value.starts_with("0x") && value[2..].chars().all(|c| c.is_ascii_hexdigit()),
+        AddressFormat::Bech32 => value
+            .rsplit_once('1')
+            .map(|(hrp, data)| !hrp.is_empty() && data.len() >= 6 && value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()))
+            .unwrap_or(false),
+        AddressFormat::Ss58 => bs58::decode(value).into_vec().map(|bytes| bytes.len() >= 35).unwrap_or(false),
+    };
+    if !valid {
+        return Err(format!("Adresse {:?} invalide: {}", from, value));
+    }
+    if from == to {
+        Ok(Some(value.to_string()))
+    } else {
+        Err(format!("Adresse {} non dérivable de {:?} vers {:?}: à fournir", value, from, to))
+    }
+}
+
+/// Remet un montant (plus petite unité) à l'échelle de décimales différentes; une perte de
+/// précision arrondit à l'inférieur et est signalée
+pub fn rescale_amount(value: &str, from_decimals: u8, to_decimals: u8) -> Result<(String, Option<String>), String> {
+    let amount = BigUint::parse_bytes(value.as_bytes(), 10).ok_or_else(|| format!("Montant invalide: {}", value))?;
+    if to_decimals >= from_decimals {
+        let factor = BigUint::from(10u32).pow(u32::from(to_decimals - from_decimals));
+        return Ok(((amount * factor).to_string(), None));
+    }
+    let divisor = BigUint::from(10u32).pow(u32::from(from_decimals - to_decimals));
+    let scaled = &amount / &divisor;
+    let remainder = &amount % &divisor;
+    let note = (remainder != BigUint::from(0u32)).then(|| format!("Précision perdue: {} unité(s) source tronquée(s)", remainder));
+    Ok((scaled.to_string(), note))
+}
+
+/// Montant lisible: `1500000` à 6 décimales donne `1.5`
+fn format_units(value: &str, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = format!("{:0>width$}", value, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usdc_transfer() -> OperationDescription {
+        OperationDescription {
+            protocol: "erc20".into(),
+            operation: "transferFrom".into(),
+            asset: AssetInfo { symbol: "USDC".into(), decimals: 6, id: Some("0x1c7d4b196cb0c7b01d743fbc6116a902379c7238".into()) },
+            fields: BTreeMap::from([
+                ("from".to_string(), "0x00000000000000000000000000000000000000a1".to_string()),
+                ("to".to_string(), "0x00000000000000000000000000000000000000b2".to_string()),
+                ("value".to_string(), "1500000".to_string()),
+                ("contract".to_string(), "0x1c7d4b196cb0c7b01d743fbc6116a902379c7238".to_string()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn translates_erc20_transfer_to_cosmos_bank_and_back_to_erc20() {
+        let registry = SchemaRegistry::load(DEFAULT_SCHEMAS_PATH).unwrap();
+        let ibc_usdc = AssetInfo { symbol: "USDC".into(), decimals: 6, id: Some("ibc/498A0751C798A0D9A389AA3691123DADA57DAA4FE165D5C75894505B876BA6E4".into()) };
+        let bank = registry.translate(&usdc_transfer(), "cosmos-bank", &ibc_usdc).unwrap();
+        assert_eq!(bank.description.operation, "MsgSend");
+        assert_eq!(bank.description.fields["amount.amount"], "1500000");
+        assert_eq!(bank.description.fields["amount.denom"], ibc_usdc.id.clone().unwrap());
+        // Clés secp256k1 d'un côté, bech32 de l'autre: adresses à fournir
+        assert_eq!(bank.unresolved, vec!["from_address".to_string(), "to_address".to_string()]);
+        assert!(!bank.is_complete());
+
+        let wrapped = AssetInfo { symbol: "wUSDC".into(), decimals: 18, id: Some("0x00000000000000000000000000000000000000c3".into()) };
+        let erc20 = registry.translate(&usdc_transfer(), "erc20", &wrapped).unwrap();
+        assert!(erc20.is_complete());
+        assert_eq!(erc20.description.fields["value"], "1500000000000000000");
+        assert_eq!(erc20.description.fields["to"], "0x00000000000000000000000000000000000000b2");
+        assert_eq!(
+            registry.explain(&usdc_transfer()).unwrap(),
+            "Transférer 1.5 USDC de 0x00000000000000000000000000000000000000a1 vers 0x00000000000000000000000000000000000000b2 (erc20.transferFrom)"
+        );
+    }
+
+    #[test]
+    fn flags_precision_loss_native_assets_and_incompatible_intents() {
+        let registry = SchemaRegistry::load(DEFAULT_SCHEMAS_PATH).unwrap();
+        let dot = AssetInfo { symbol: "DOT".into(), decimals: 10, id: None };
+        let mut transfer = usdc_transfer();
+        transfer.asset.decimals = 18;
+        transfer.fields.insert("value".into(), "1234567890123456789".into());
+        let balances = registry.translate(&transfer, "substrate-balances", &dot).unwrap();
+        assert_eq!(balances.description.fields["value"], "12345678901");
+        assert!(balances.notes.iter().any(|note| note.starts_with("Précision perdue")));
+        assert!(balances.notes.iter().any(|note| note.contains("devise native")));
+        assert_eq!(balances.unresolved, vec!["dest".to_string()]);
+
+        let nft = OperationDescription {
+            protocol: "erc721".into(),
+            operation: "safeTransferFrom".into(),
+            asset: AssetInfo { symbol: "AURA".into(), decimals: 0, id: None },
+            fields: BTreeMap::from([("tokenId".to_string(), "7".to_string())]),
+        };
+        assert!(registry.translate(&nft, "cosmos-bank", &dot).is_err());
+        assert!(SchemaRegistry::from_toml("[[schemas]]\nname = \"x\"\naddress-format = \"evm\"\nnative-asset = true\n[[schemas.operations]]\nname = \"t\"\nintent = \"transfer\"\nfields = [{ field = \"a\", concept = \"asset\" }]\n").is_err());
+    }
+}
@@ -0,0 +1,481 @@
+//! AURORAE++ - staking.rs
+//!
+//! Moteur de staking des tokens forgés: dépôt, retrait, accumulation de récompenses
+//! selon une courbe d'APR configurable, et pénalités (slashing) déclenchées par le
+//! système de sécurité. Les récompenses réclamées passent par le budget de l'économie.
+//!
+//! Chaque pool tient un index cumulé de récompense par token staké, mis à jour
+//! avant tout changement de participation (stake, retrait, slashing, émission): un
+//! changement d'APR ne s'applique qu'à partir de son entrée en vigueur, jamais
+//! rétroactivement. Les récompenses émises sont plafonnées par pool.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use uuid::Uuid;
+use tracing::info;
+
+use crate::alchemy::AlchemyEngine;
+use crate::currency::{Amount, Asset};
+use crate::economy::{BudgetCategory, EconomyEngine, SpendDecision};
+use crate::security::SecuritySystem;
+
+const SECONDS_PER_YEAR: f64 = 31_536_000.0;
+
+/// Courbe d'APR appliquée aux positions d'un token
+#[derive(Debug, Clone)]
+pub enum AprCurve {
+    /// APR constant
+    Flat(f64),
+    /// APR dégressif selon le taux de participation (part de l'offre stakée):
+    /// paliers `(participation_max, apr)` triés par participation croissante
+    Tiered(Vec<(f64, f64)>),
+    /// APR décroissant de moitié tous les `half_life_days`, sans passer sous `floor`
+    Decaying { initial: f64, floor: f64, half_life_days: f64 },
+}
+
+impl AprCurve {
+    /// APR effectif pour une participation donnée et l'âge du pool (jours)
+    pub fn apr(&self, participation: f64, age_days: f64) -> f64 {
+        match self {
+            AprCurve::Flat(apr) => *apr,
+            AprCurve::Tiered(tiers) => tiers.iter()
+                .find(|(max, _)| participation <= *max)
+                .or_else(|| tiers.last())
+                .map(|(_, apr)| *apr)
+                .unwrap_or(0.0),
+            AprCurve::Decaying { initial, floor, half_life_days } => {
+                if *half_life_days <= 0.0 {
+                    return *floor;
+                }
+                (initial * 0.5f64.powf(age_days / half_life_days)).max(*floor)
+            }
+        }
+    }
+
+    /// APR moyen entre deux âges du pool (jours), à participation constante
+    pub fn average_apr(&self, participation: f64, from_days: f64, to_days: f64) -> f64 {
+        let AprCurve::Decaying { initial, floor, half_life_days } = self else {
+            return self.apr(participation, from_days);
+        };
+        if to_days <= from_days || *half_life_days <= 0.0 || initial <= floor {
+            return self.apr(participation, from_days);
+        }
+        // Intégrale exacte de max(initial · 2^(-t/h), floor), coupée à l'âge où le plancher est atteint
+        let h = *half_life_days;
+        let decay = |a: f64, b: f64| initial * h / std::f64::consts::LN_2 * (0.5f64.powf(a / h) - 0.5f64.powf(b / h));
+        let crossover = if *floor > 0.0 { h * (initial / floor).log2() } else { f64::INFINITY };
+        let integral = if to_days <= crossover {
+            decay(from_days, to_days)
+        } else if from_days >= crossover {
+            floor * (to_days - from_days)
+        } else {
+            decay(from_days, crossover) + floor * (to_days - crossover)
+        };
+        integral / (to_days - from_days)
+    }
+}
+
+/// Position de staking d'un participant
+#[derive(Debug, Clone)]
+pub struct StakePosition {
+    pub staker: String,
+    pub token_id: Uuid,
+    pub amount: u64,
+    pub accrued_rewards: f64,
+    /// Index de récompense du pool lors du dernier règlement de la position
+    pub reward_index_paid: f64,
+    pub last_accrual_ts: i64,
+    pub staked_at: String,
+}
+
+/// Pénalité appliquée à une position
+#[derive(Debug, Clone)]
+pub struct SlashEvent {
+    pub id: Uuid,
+    pub staker: String,
+    pub token_id: Uuid,
+    pub fraction: f64,
+    pub amount_slashed: u64,
+    pub reason: String,
+    pub timestamp: String,
+}
+
+/// Pool de staking d'un token
+#[derive(Debug, Clone)]
+pub struct StakingPool {
+    pub token_id: Uuid,
+    pub curve: AprCurve,
+    pub total_staked: u64,
+    pub created_ts: i64,
+    /// Récompense cumulée par token staké depuis l'ouverture
+    pub reward_index: f64,
+    pub last_update_ts: i64,
+    /// Offre du token lors de la dernière mise à jour (participation)
+    pub last_supply: u64,
+    /// Plafond des récompenses émises par le pool
+    pub reward_cap: u64,
+    pub rewards_minted: u64,
+}
+
+impl StakingPool {
+    /// Part de l'offre stakée
+    fn participation(&self) -> f64 {
+        if self.last_supply == 0 { 0.0 } else { self.total_staked as f64 / self.last_supply as f64 }
+    }
+}
+
+#[derive(Default)]
+pub struct StakingEngine {
+    pub pools: HashMap<Uuid, StakingPool>,
+    pub positions: HashMap<(Uuid, String), StakePosition>,
+    pub slash_history: Vec<SlashEvent>,
+    /// Tokens confisqués, reversés à la trésorerie
+    pub slashed_treasury: HashMap<Uuid, u64>,
+}
+
+impl StakingEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ouvre le staking pour un token forgé, avec un plafond d'émission de récompenses
+    pub fn open_pool(&mut self, forge: &AlchemyEngine, token_id: &Uuid, curve: AprCurve, reward_cap: u64) -> Result<(), String> {
+        let token = forge.get_token(token_id).ok_or_else(|| "Token non trouvé".to_string())?;
+        info!("🥩 Staking ouvert pour {} ({:?}, plafond {})", token.name, curve, reward_cap);
+        let now = Utc::now().timestamp();
+        self.pools.insert(*token_id, StakingPool {
+            token_id: *token_id,
+            curve,
+            total_staked: 0,
+            created_ts: now,
+            reward_index: 0.0,
+            last_update_ts: now,
+            last_supply: token.supply,
+            reward_cap,
+            rewards_minted: 0,
+        });
+        Ok(())
+    }
+
+    /// APR en vigueur pour un pool (participation et âge courants)
+    pub fn current_apr(&self, token_id: &Uuid, at: i64) -> f64 {
+        let Some(pool) = self.pools.get(token_id) else { return 0.0 };
+        let age_days = (at - pool.created_ts).max(0) as f64 / 86_400.0;
+        pool.curve.apr(pool.participation(), age_days)
+    }
+
+    /// Fait avancer l'index du pool jusqu'à `at` à l'APR en vigueur depuis la dernière mise à jour,
+    /// puis relève l'offre du token pour la période suivante
+    fn update_pool(&mut self, forge: Option<&AlchemyEngine>, token_id: &Uuid, at: i64) {
+        let Some(pool) = self.pools.get_mut(token_id) else { return };
+        if at > pool.last_update_ts {
+            let from_days = (pool.last_update_ts - pool.created_ts).max(0) as f64 / 86_400.0;
+            let to_days = (at - pool.created_ts).max(0) as f64 / 86_400.0;
+            let apr = pool.curve.average_apr(pool.participation(), from_days, to_days);
+            pool.reward_index += apr * (at - pool.last_update_ts) as f64 / SECONDS_PER_YEAR;
+            pool.last_update_ts = at;
+        }
+        if let Some(supply) = forge.and_then(|f| f.get_token(token_id)).map(|t| t.supply) {
+            pool.last_supply = supply;
+        }
+    }
+
+    /// Règle les récompenses d'une position sur l'index courant du pool
+    fn settle(&mut self, token_id: &Uuid, staker: &str, at: i64) {
+        let Some(index) = self.pools.get(token_id).map(|p| p.reward_index) else { return };
+        if let Some(position) = self.positions.get_mut(&(*token_id, staker.to_string())) {
+            position.accrued_rewards += position.amount as f64 * (index - position.reward_index_paid);
+            position.reward_index_paid = index;
+            position.last_accrual_ts = at;
+        }
+    }
+
+    /// Stake des tokens libérés du solde du participant dans la forge
+    pub fn stake(&mut self, forge: &mut AlchemyEngine, token_id: &Uuid, staker: &str, amount: u64) -> Result<(), String> {
+        self.stake_at(forge, token_id, staker, amount, Utc::now().timestamp())
+    }
+
+    pub fn stake_at(&mut self, forge: &mut AlchemyEngine, token_id: &Uuid, staker: &str, amount: u64, at: i64) -> Result<(), String> {
+        if !self.pools.contains_key(token_id) {
+            return Err("Aucun pool de staking pour ce token".to_string());
+        }
+        let key = (*token_id, staker.to_string());
+        let balance = forge.balances.get(&key).copied().ok_or_else(|| "Aucun solde disponible".to_string())?;
+        if amount == 0 || balance < amount {
+            return Err(format!("Solde insuffisant: {} < {}", balance, amount));
+        }
+
+        // La participation change: l'APR précédent s'arrête ici pour toutes les positions
+        self.update_pool(Some(forge), token_id, at);
+        self.settle(token_id, staker, at);
+
+        if let Some(balance) = forge.balances.get_mut(&key) {
+            *balance -= amount;
+        }
+        let index = self.pools.get(token_id).map(|p| p.reward_index).unwrap_or(0.0);
+        let position = self.positions.entry(key).or_insert_with(|| StakePosition {
+            staker: staker.to_string(),
+            token_id: *token_id,
+            amount: 0,
+            accrued_rewards: 0.0,
+            reward_index_paid: index,
+            last_accrual_ts: at,
+            staked_at: Utc::now().to_rfc3339(),
+        });
+        position.amount += amount;
+        if let Some(pool) = self.pools.get_mut(token_id) {
+            pool.total_staked += amount;
+        }
+
+        info!("🔒 {} a staké {} tokens", staker, amount);
+        Ok(())
+    }
+
+    /// Retire des tokens stakés vers le solde du participant
+    pub fn unstake(&mut self, forge: &mut AlchemyEngine, token_id: &Uuid, staker: &str, amount: u64) -> Result<(), String> {
+        self.unstake_at(forge, token_id, staker, amount, Utc::now().timestamp())
+    }
+
+    pub fn unstake_at(&mut self, forge: &mut AlchemyEngine, token_id: &Uuid, staker: &str, amount: u64, at: i64) -> Result<(), String> {
+        let key = (*token_id, staker.to_string());
+        let staked = self.positions.get(&key).map(|p| p.amount).unwrap_or(0);
+        if amount == 0 || amount > staked {
+            return Err(format!("Montant staké insuffisant: {} < {}", staked, amount));
+        }
+
+        self.update_pool(Some(forge), token_id, at);
+        self.settle(token_id, staker, at);
+        if let Some(position) = self.positions.get_mut(&key) {
+            position.amount -= amount;
+        }
+        if let Some(pool) = self.pools.get_mut(token_id) {
+            pool.total_staked -= amount;
+        }
+        *forge.balances.entry(key).or_insert(0) += amount;
+
+        info!("🔓 {} a retiré {} tokens du staking", staker, amount);
+        Ok(())
+    }
+
+    /// Récompenses accumulées non réclamées
+    pub fn pending_rewards(&mut self, forge: &AlchemyEngine, token_id: &Uuid, staker: &str) -> f64 {
+        self.pending_rewards_at(forge, token_id, staker, Utc::now().timestamp())
+    }
+
+    pub fn pending_rewards_at(&mut self, forge: &AlchemyEngine, token_id: &Uuid, staker: &str, at: i64) -> f64 {
+        self.update_pool(Some(forge), token_id, at);
+        self.settle(token_id, staker, at);
+        self.positions.get(&(*token_id, staker.to_string())).map(|p| p.accrued_rewards).unwrap_or(0.0)
+    }
+
+    /// Part d'une réclamation encore émissible sous le plafond du pool
+    fn mintable(&self, token_id: &Uuid, rewards: u64) -> u64 {
+        self.pools.get(token_id)
+            .map(|p| rewards.min(p.reward_cap.saturating_sub(p.rewards_minted)))
+            .unwrap_or(0)
+    }
+
+    /// Réclame les récompenses: la dépense est soumise au budget `Rewards` de l'économie
+    /// et l'émission au plafond du pool
+    pub fn claim(&mut self, economy: &mut EconomyEngine, forge: &mut AlchemyEngine, token_id: &Uuid, staker: &str) -> Result<u64, String> {
+        let now = Utc::now().timestamp();
+        let rewards = self.pending_rewards_at(forge, token_id, staker, now).floor();
+        if rewards < 1.0 {
+            return Err("Aucune récompense réclamable".to_string());
+        }
+        let paid = self.mintable(token_id, rewards as u64);
+        if paid == 0 {
+            return Err("Plafond d'émission des récompenses atteint".to_string());
+        }
+
+        match economy.request_spend(BudgetCategory::Rewards, Amount::new(Asset::Aura, paid as f64)) {
+            SpendDecision::Approved => {}
+            SpendDecision::RequiresApproval(id) => return Err(format!("Réclamation en attente d'approbation ({})", id)),
+            SpendDecision::Denied(reason) => return Err(format!("Réclamation refusée: {}", reason)),
+        }
+
+        let key = (*token_id, staker.to_string());
+        if let Some(position) = self.positions.get_mut(&key) {
+            position.accrued_rewards -= paid as f64;
+        }
+        if let Some(pool) = self.pools.get_mut(token_id) {
+            pool.rewards_minted += paid;
+        }
+        // Les récompenses sont émises: l'offre augmente, la participation suit dès la période suivante
+        if let Some(token) = forge.tokens.get_mut(token_id) {
+            token.supply += paid;
+        }
+        self.update_pool(Some(forge), token_id, now);
+        *forge.balances.entry(key).or_insert(0) += paid;
+        economy.record_reward_payout(staker, paid as f64, "staking");
+
+        info!("🎁 {} a réclamé {} tokens de récompense", staker, paid);
+        Ok(paid)
+    }
+
+    /// Applique une pénalité à la position d'un participant
+    pub fn slash(&mut self, token_id: &Uuid, staker: &str, fraction: f64, reason: &str) -> Result<u64, String> {
+        self.slash_at(token_id, staker, fraction, reason, Utc::now().timestamp())
+    }
+
+    pub fn slash_at(&mut self, token_id: &Uuid, staker: &str, fraction: f64, reason: &str, at: i64) -> Result<u64, String> {
+        let key = (*token_id, staker.to_string());
+        if !self.positions.contains_key(&key) {
+            return Err("Position non trouvée".to_string());
+        }
+        self.update_pool(None, token_id, at);
+        self.settle(token_id, staker, at);
+
+        let position = self.positions.get_mut(&key).ok_or_else(|| "Position non trouvée".to_string())?;
+        let fraction = fraction.clamp(0.0, 1.0);
+        let amount = (position.amount as f64 * fraction).floor() as u64;
+
+        position.amount -= amount;
+        position.accrued_rewards *= 1.0 - fraction;
+        if let Some(pool) = self.pools.get_mut(token_id) {
+            pool.total_staked -= amount;
+        }
+        *self.slashed_treasury.entry(*token_id).or_insert(0) += amount;
+
+        self.slash_history.push(SlashEvent {
+            id: Uuid::new_v4(),
+            staker: staker.to_string(),
+            token_id: *token_id,
+            fraction,
+            amount_slashed: amount,
+            reason: reason.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        });
+
+        info!("⚔️ Slashing de {} tokens pour {}: {}", amount, staker, reason);
+        Ok(amount)
+    }
+
+    /// Applique les demandes de slashing émises par le système de sécurité
+    pub fn apply_security_slashes(&mut self, security: &mut SecuritySystem) -> usize {
+        let mut applied = 0;
+        for request in security.drain_slash_requests() {
+            let token_ids: Vec<Uuid> = self.positions.keys()
+                .filter(|(_, staker)| staker == &request.staker)
+                .map(|(token_id, _)| *token_id)
+                .collect();
+            for token_id in token_ids {
+                if self.slash(&token_id, &request.staker, request.fraction, &request.reason).is_ok() {
+                    applied += 1;
+                }
+            }
+        }
+        applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alchemy::{SmartToken, TokenKind};
+
+    const DAY: i64 = 86_400;
+
+    fn forge_with_token(supply: u64, holders: &[(&str, u64)]) -> (AlchemyEngine, Uuid) {
+        let mut forge = AlchemyEngine::new();
+        let id = Uuid::new_v4();
+        forge.tokens.insert(id, SmartToken {
+            id,
+            name: "Test".to_string(),
+            kind: TokenKind::Fungible,
+            metadata: HashMap::new(),
+            created_at: Utc::now().to_rfc3339(),
+            supply,
+            creator_share: 0.0,
+        });
+        for (holder, amount) in holders {
+            forge.balances.insert((id, holder.to_string()), *amount);
+        }
+        (forge, id)
+    }
+
+    fn engine_at(forge: &AlchemyEngine, token_id: &Uuid, curve: AprCurve, start: i64) -> StakingEngine {
+        let mut engine = StakingEngine::new();
+        engine.open_pool(forge, token_id, curve, 1_000_000).unwrap();
+        let pool = engine.pools.get_mut(token_id).unwrap();
+        pool.created_ts = start;
+        pool.last_update_ts = start;
+        engine
+    }
+
+    #[test]
+    fn apr_changes_apply_only_from_the_moment_they_happen() {
+        // 10% tant que la participation reste sous 50%, 2% au-delà
+        let curve = AprCurve::Tiered(vec![(0.5, 0.10), (1.0, 0.02)]);
+        let (mut forge, token) = forge_with_token(1_000, &[("alice", 400), ("bob", 400)]);
+        let start = 1_700_000_000;
+        let mut engine = engine_at(&forge, &token, curve, start);
+
+        engine.stake_at(&mut forge, &token, "alice", 400, start).unwrap();
+        // Un an à 40% de participation: 10%
+        let year = 365 * DAY;
+        engine.stake_at(&mut forge, &token, "bob", 400, start + year).unwrap();
+        // Un an de plus à 80%: 2%, sans réviser la première année
+        let alice = engine.pending_rewards_at(&forge, &token, "alice", start + 2 * year);
+        let bob = engine.pending_rewards_at(&forge, &token, "bob", start + 2 * year);
+
+        assert!((alice - (40.0 + 8.0)).abs() < 1e-6, "alice = {}", alice);
+        assert!((bob - 8.0).abs() < 1e-6, "bob = {}", bob);
+    }
+
+    #[test]
+    fn unstake_settles_rewards_and_returns_tokens() {
+        let (mut forge, token) = forge_with_token(1_000, &[("alice", 500)]);
+        let start = 1_700_000_000;
+        let mut engine = engine_at(&forge, &token, AprCurve::Flat(0.365), start);
+
+        engine.stake_at(&mut forge, &token, "alice", 500, start).unwrap();
+        engine.unstake_at(&mut forge, &token, "alice", 200, start + 10 * DAY).unwrap();
+        assert_eq!(forge.balances[&(token, "alice".to_string())], 200);
+        assert_eq!(engine.pools[&token].total_staked, 300);
+        assert!(engine.unstake_at(&mut forge, &token, "alice", 301, start + 10 * DAY).is_err());
+
+        // 500 tokens pendant 10 jours puis 300 pendant 10 jours, à 0.1% par jour
+        let rewards = engine.pending_rewards_at(&forge, &token, "alice", start + 20 * DAY);
+        assert!((rewards - (5.0 + 3.0)).abs() < 1e-6, "rewards = {}", rewards);
+    }
+
+    #[test]
+    fn slashing_stops_accrual_on_the_confiscated_share() {
+        let (mut forge, token) = forge_with_token(1_000, &[("alice", 100)]);
+        let start = 1_700_000_000;
+        let mut engine = engine_at(&forge, &token, AprCurve::Flat(0.365), start);
+
+        engine.stake_at(&mut forge, &token, "alice", 100, start).unwrap();
+        let slashed = engine.slash_at(&token, "alice", 0.5, "double signature", start + 10 * DAY).unwrap();
+        assert_eq!(slashed, 50);
+        assert_eq!(engine.slashed_treasury[&token], 50);
+
+        // 1 token accumulé puis réduit de moitié, puis 50 tokens pendant 10 jours
+        let rewards = engine.pending_rewards_at(&forge, &token, "alice", start + 20 * DAY);
+        assert!((rewards - (0.5 + 0.5)).abs() < 1e-6, "rewards = {}", rewards);
+    }
+
+    #[test]
+    fn decaying_curve_is_integrated_over_the_interval() {
+        let curve = AprCurve::Decaying { initial: 0.4, floor: 0.1, half_life_days: 365.0 };
+        // Avant le plancher: moyenne strictement entre les bornes
+        let first_year = curve.average_apr(0.0, 0.0, 365.0);
+        assert!(first_year < 0.4 && first_year > 0.2);
+        // Après le plancher (2 demi-vies): constant
+        assert!((curve.average_apr(0.0, 800.0, 900.0) - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn minted_rewards_are_capped_per_pool() {
+        let (forge, token) = forge_with_token(1_000, &[]);
+        let mut engine = engine_at(&forge, &token, AprCurve::Flat(0.1), 0);
+        engine.pools.get_mut(&token).unwrap().reward_cap = 100;
+        engine.pools.get_mut(&token).unwrap().rewards_minted = 90;
+
+        assert_eq!(engine.mintable(&token, 25), 10);
+        engine.pools.get_mut(&token).unwrap().rewards_minted = 100;
+        assert_eq!(engine.mintable(&token, 25), 0);
+    }
+}
@@ -1,84 +1,244 @@
 //! AURORAE++ - strategist.rs
 //!
-//! Module de stratégie adaptative connecté à OpenAI.
-//! Lit la roadmap, analyse les priorités, consulte OpenAI pour recommandations,
-//! puis injecte des pensées adaptatives dans le cortex de l'IA.
+//! Module de stratégie adaptative connecté à un modèle de langage.
+//! Lit la roadmap, analyse les priorités, consulte le fournisseur configuré (`llm.toml`)
+//! avec le prompt versionné `prompts/strategist`, puis injecte des pensées adaptatives dans
+//! le cortex de l'IA.
+//! La réponse est structurée: le modèle appelle la fonction `choose_intent` (intention parmi
+//! celles du cerveau, module visé, urgence, justification), dont les arguments sont validés.
+//! Réponse invalide ou texte libre: repli sur les mots-clés de la réponse, puis sur la
+//! correspondance déterministe objectif → intention, également utilisée quand la politique
+//! refuse l'appel (plafond journalier de coût atteint).
 
 use crate::vision::{ObjectiveType, VisionEngine};
 use crate::brain::{Thought, Intent, BrainCore};
+use crate::llm::{LlmFunction, LlmProvider};
+use crate::llm_usage::is_policy_refusal;
+use crate::prompts::PROMPTS;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use parking_lot::RwLock;
-use rand::Rng;
-use async_openai::types::{ChatCompletionRequestMessageArgs, CreateChatCompletionRequestArgs, Role};
-use async_openai::Client;
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+
+/// Fonction imposée au modèle
+const DECISION_FUNCTION: &str = "choose_intent";
+/// Urgence d'une décision qui n'en précise pas
+const DEFAULT_URGENCY: u8 = 200;
+
+/// Intentions proposées au modèle, sous leur nom d'énumération
+const INTENT_CATALOG: [(&str, Intent); 13] = [
+    ("GenerateChain", Intent::GenerateChain),
+    ("LearnFromGithub", Intent::LearnFromGithub),
+    ("OptimizeEconomy", Intent::OptimizeEconomy),
+    ("MutateSelf", Intent::MutateSelf),
+    ("Defend", Intent::Defend),
+    ("EvolveProtocol", Intent::EvolveProtocol),
+    ("Rest", Intent::Rest),
+    ("Observe", Intent::Observe),
+    ("Dream", Intent::Dream),
+    ("GenerateCode", Intent::GenerateCode),
+    ("BuildEcosystem", Intent::BuildEcosystem),
+    ("SelfUpgrade", Intent::SelfUpgrade),
+    ("SelfReplicate", Intent::SelfReplicate),
+];
+
+/// Origine d'une décision, qui fixe la qualité notée pour la version du prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecisionSource {
+    /// Arguments de fonction valides
+    Structured,
+    /// Mot-clé reconnu dans une réponse libre
+    Keyword,
+    /// Correspondance objectif → intention, sans avis exploitable du modèle
+    Heuristic,
+}
+
+impl DecisionSource {
+    fn quality(&self) -> f32 {
+        match self {
+            DecisionSource::Structured => 1.0,
+            DecisionSource::Keyword => 0.5,
+            DecisionSource::Heuristic => 0.0,
+        }
+    }
+}
+
+/// Décision du stratège, injectée comme pensée
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategicDecision {
+    pub intent: Intent,
+    /// Module visé, exposé à la politique d'alignement (`module`)
+    pub target_module: Option<String>,
+    pub urgency: u8,
+    pub rationale: String,
+    pub source: DecisionSource,
+}
+
+impl StrategicDecision {
+    /// Schéma JSON des arguments de `choose_intent`
+    pub fn function() -> LlmFunction {
+        let intents: Vec<&str> = INTENT_CATALOG.iter().map(|(name, _)| *name).collect();
+        LlmFunction::new(
+            DECISION_FUNCTION,
+            "Choisit la prochaine intention du cerveau d'AURORAE++ pour progresser vers l'objectif.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "intent": {"type": "string", "enum": intents, "description": "Intention à ajouter au cortex"},
+                    "target_module": {"type": "string", "description": "Module visé (snake_case), si l'intention en concerne un"},
+                    "urgency": {"type": "integer", "minimum": 0, "maximum": 255, "description": "Urgence de la pensée"},
+                    "rationale": {"type": "string", "description": "Justification en une phrase"}
+                },
+                "required": ["intent", "urgency", "rationale"],
+                "additionalProperties": false
+            }),
+        )
+    }
+
+    /// Valide les arguments rendus par le modèle
+    pub fn from_arguments(arguments: &Value) -> Result<Self, String> {
+        let object = arguments.as_object().ok_or("Arguments: objet attendu")?;
+        if let Some(unknown) = object.keys().find(|k| !["intent", "target_module", "urgency", "rationale"].contains(&k.as_str())) {
+            return Err(format!("Argument inattendu: {}", unknown));
+        }
+        let name = object.get("intent").and_then(Value::as_str).ok_or("Intention absente")?;
+        let intent = INTENT_CATALOG.iter()
+            .find(|(candidate, _)| *candidate == name)
+            .map(|(_, intent)| intent.clone())
+            .ok_or_else(|| format!("Intention inconnue: {}", name))?;
+        let urgency = match object.get("urgency") {
+            None | Some(Value::Null) => DEFAULT_URGENCY,
+            Some(value) => value.as_u64()
+                .and_then(|u| u8::try_from(u).ok())
+                .ok_or_else(|| format!("Urgence hors de [0, 255]: {}", value))?,
+        };
+        let target_module = match object.get("target_module").and_then(Value::as_str).map(str::trim) {
+            None | Some("") => None,
+            Some(module) if module.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') => Some(module.to_string()),
+            Some(module) => return Err(format!("Nom de module invalide: {}", module)),
+        };
+        let rationale = object.get("rationale").and_then(Value::as_str).map(str::trim).unwrap_or_default();
+        if rationale.is_empty() {
+            return Err("Justification absente".to_string());
+        }
+        Ok(Self { intent, target_module, urgency, rationale: rationale.to_string(), source: DecisionSource::Structured })
+    }
+
+    fn fallback(intent: Intent, source: DecisionSource, rationale: &str) -> Self {
+        let urgency = if source == DecisionSource::Keyword { DEFAULT_URGENCY } else { 160 };
+        Self { intent, target_module: None, urgency, rationale: rationale.to_string(), source }
+    }
+
+    fn thought(&self, prompt_label: Option<String>) -> Thought {
+        let mut thought = Thought::new(self.intent.clone(), self.urgency);
+        if let Some(module) = &self.target_module {
+            thought.metadata.insert("module".to_string(), module.clone());
+        }
+        thought.metadata.insert("rationale".to_string(), self.rationale.clone());
+        if let Some(label) = prompt_label {
+            thought.metadata.insert("prompt".to_string(), label);
+        }
+        thought
+    }
+}
+
 
 pub struct Strategist {
-    pub api_key: String,
+    /// Absent sans clé ni serveur local: aucune consultation
+    pub provider: Option<Arc<dyn LlmProvider>>,
+    /// Modèle choisi pour le stratège (modèle par défaut du fournisseur sinon)
+    pub model: Option<String>,
 }
 
 impl Strategist {
-    pub fn new(api_key: &str) -> Self {
+    pub fn new(provider: Option<Arc<dyn LlmProvider>>, model: Option<&str>) -> Self {
         Self {
-            api_key: api_key.to_string(),
+            provider,
+            model: model.map(str::to_string),
         }
     }
 
-    /// 🔮 Analyse la roadmap et injecte des pensées inspirées d'OpenAI
-    pub async fn consult_openai(&self, brain: &Arc<RwLock<BrainCore>>, vision: &mut VisionEngine) {
+    /// 🔮 Analyse la roadmap et injecte des pensées inspirées par le modèle
+    pub async fn consult(&self, brain: &Arc<RwLock<BrainCore>>, vision: &mut VisionEngine) {
+        let Some(provider) = self.provider.as_ref() else {
+            return;
+        };
         let Some(proj) = vision.projections.iter().max_by_key(|p| p.priority) else {
-            println!("[STRATEGIST] ❌ Aucune projection prioritaire trouvée.");
+            warn!("⚠️ Aucune projection prioritaire trouvée.");
             return;
         };
 
-        let prompt = format!(
-            "L'IA AURORAE++ veut accomplir cet objectif : {:?}. Priorité {}. Raison : {}.\n\nQuelle serait la prochaine pensée logique à ajouter dans son cerveau pour progresser ? Donne-moi juste l'action en verbe infinitif.",
-            proj.target, proj.priority, proj.rationale
-        );
+        // Version tirée parmi les prompts actifs de prompts/strategist (test A/B)
+        let mut vars = BTreeMap::new();
+        vars.insert("objective", format!("{:?}", proj.target));
+        vars.insert("priority", proj.priority.to_string());
+        vars.insert("rationale", proj.rationale.clone());
+        let rendered = match PROMPTS.read().render("strategist", &vars) {
+            Ok(rendered) => rendered,
+            Err(e) => {
+                error!("❌ {}", e);
+                return;
+            }
+        };
+        let request = rendered.request()
+            .with_max_tokens(200)
+            .with_model(self.model.as_deref())
+            .with_purpose("strategist")
+            .with_function(StrategicDecision::function());
 
-        let client = Client::new();
-        let request = CreateChatCompletionRequestArgs::default()
-            .model("gpt-4")
-            .messages([ 
-                ChatCompletionRequestMessageArgs::default()
-                    .role(Role::System)
-                    .content("Tu es un stratégiste cognitif pour une IA post-humaniste.")
-                    .build()
-                    .unwrap(),
-                ChatCompletionRequestMessageArgs::default()
-                    .role(Role::User)
-                    .content(&prompt)
-                    .build()
-                    .unwrap(),
-            ])
-             .max_tokens(60u16)
-            .build()
-            .unwrap();
-
-        match client.chat().create(request).await {
+        let decision = match provider.complete(&request).await {
             Ok(response) => {
-                if let Some(choice) = response.choices.first() {
-                    // Traite la réponse potentiellement Option<String>
-                    if let Some(content) = choice.message.content.as_ref() {
-                        let answer = content.trim().to_lowercase();
-                        println!("[STRATEGIST] 🧠 OpenAI suggère : {}", answer);
-
-                        let intent = Self::map_to_intent(&answer);
-                        let urgency = 220 + rand::thread_rng().gen_range(0..=30);
-
-                        let mut brain_lock = brain.write();
-                        if let Some(intent) = intent {
-                            brain_lock.push_thought(Thought::new(intent, urgency));
-                        } else {
-                            let fallback = Intent::Observe;
-                            println!("[STRATEGIST] ❓ Aucune intention reconnue, fallback vers {:?}", fallback);
-                            brain_lock.push_thought(Thought::new(fallback, 128));
-                        }
-                    } else {
-                        println!("[STRATEGIST] ⚠️ Aucune réponse de contenu retournée.");
-                    }
-                }
+                info!("🧠 {} ({}, {}) a répondu", provider.name(), response.model, rendered.label());
+                let decision = Self::decide(response.arguments.as_ref(), &response.text, &proj.target);
+                PROMPTS.write().record_outcome(&rendered.name, rendered.version, decision.source.quality());
+                decision
             }
-            Err(e) => println!("[STRATEGIST] ❌ Erreur OpenAI : {}", e),
+            Err(e) if is_policy_refusal(&e) => {
+                warn!("🪫 {} — planification heuristique", e);
+                StrategicDecision::fallback(Self::heuristic_intent(&proj.target), DecisionSource::Heuristic, "plafond de coût LLM atteint")
+            }
+            Err(e) => {
+                error!("❌ Erreur {} : {}", provider.name(), e);
+                return;
+            }
+        };
+
+        info!(
+            "🎯 {:?} ({:?}) urgence {}{} — {}",
+            decision.intent,
+            decision.source,
+            decision.urgency,
+            decision.target_module.as_ref().map(|m| format!(" sur {}", m)).unwrap_or_default(),
+            decision.rationale
+        );
+        let label = (decision.source != DecisionSource::Heuristic).then(|| rendered.label());
+        brain.write().push_thought(decision.thought(label));
+    }
+
+    /// Arguments structurés validés, sinon mot-clé de la réponse, sinon objectif
+    pub fn decide(arguments: Option<&Value>, text: &str, objective: &ObjectiveType) -> StrategicDecision {
+        match arguments.map(StrategicDecision::from_arguments) {
+            Some(Ok(decision)) => return decision,
+            Some(Err(e)) => warn!("⚠️ Réponse structurée rejetée : {}", e),
+            None => warn!("⚠️ Réponse en texte libre, pas d'appel de {}", DECISION_FUNCTION),
+        }
+        let answer = text.trim().to_lowercase();
+        match Self::map_to_intent(&answer) {
+            Some(intent) => StrategicDecision::fallback(intent, DecisionSource::Keyword, &answer),
+            None => StrategicDecision::fallback(Self::heuristic_intent(objective), DecisionSource::Heuristic, "réponse du modèle inexploitable"),
+        }
+    }
+
+    /// Intention déduite de l'objectif sans consulter de modèle
+    fn heuristic_intent(objective: &ObjectiveType) -> Intent {
+        match objective {
+            ObjectiveType::ImproveLearning | ObjectiveType::SeekKnowledge => Intent::LearnFromGithub,
+            ObjectiveType::OptimizeEconomy => Intent::OptimizeEconomy,
+            ObjectiveType::ExpandChains => Intent::GenerateChain,
+            ObjectiveType::RefactorSelf => Intent::MutateSelf,
+            ObjectiveType::BuildEcosystem => Intent::BuildEcosystem,
+            ObjectiveType::MaximizeAutonomy => Intent::SelfReplicate,
         }
     }
 
@@ -104,3 +264,31 @@ impl Strategist {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_structured_decisions() {
+        let decision = StrategicDecision::from_arguments(&json!({
+            "intent": "MutateSelf", "target_module": "economy", "urgency": 230, "rationale": "réduire la latence"
+        })).unwrap();
+        assert_eq!((decision.intent.clone(), decision.target_module.as_deref(), decision.urgency), (Intent::MutateSelf, Some("economy"), 230));
+        assert_eq!(decision.thought(None).proposed_action().attributes.get("module").map(String::as_str), Some("economy"));
+
+        assert!(StrategicDecision::from_arguments(&json!({"intent": "Conquer", "urgency": 1, "rationale": "x"})).is_err());
+        assert!(StrategicDecision::from_arguments(&json!({"intent": "Rest", "urgency": 400, "rationale": "x"})).is_err());
+        assert!(StrategicDecision::from_arguments(&json!({"intent": "Rest", "urgency": 1, "rationale": "x", "target_module": "../etc"})).is_err());
+        assert!(StrategicDecision::from_arguments(&json!({"intent": "Rest", "urgency": 1})).is_err());
+    }
+
+    #[test]
+    fn falls_back_deterministically() {
+        let invalid = json!({"intent": "Observe"});
+        let keyword = Strategist::decide(Some(&invalid), "Il faut apprendre", &ObjectiveType::OptimizeEconomy);
+        assert_eq!((keyword.intent, keyword.source), (Intent::LearnFromGithub, DecisionSource::Keyword));
+        let heuristic = Strategist::decide(None, "???", &ObjectiveType::ExpandChains);
+        assert_eq!((heuristic.intent, heuristic.source), (Intent::GenerateChain, DecisionSource::Heuristic));
+    }
+}
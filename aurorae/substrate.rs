@@ -0,0 +1,270 @@
+//! AURORAE++ - substrate.rs
+//!
+//! Connecteur des chaînes Substrate du registre (`kind = "substrate"`): blocs finalisés,
+//! soldes libres des comptes (SS58) et événements des pallets. Le client subxt n'est
+//! compilé qu'avec la feature `substrate`; sans elle, une chaîne simulée en mémoire répond
+//! aux mêmes requêtes, comme le backend EVM simulé de `blockchain_core`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::types::H256;
+use ethers::utils::keccak256;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::chain_registry::{ChainConfig, ChainKind};
+
+/// Bloc d'une chaîne Substrate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubstrateBlock {
+    pub number: u64,
+    pub hash: H256,
+}
+
+/// Événement émis par un pallet dans un bloc
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubstrateEvent {
+    pub pallet: String,
+    pub variant: String,
+    /// Champs de l'événement, décodés dynamiquement et rendus lisibles
+    pub fields: String,
+}
+
+/// Requêtes disponibles sur une chaîne Substrate
+#[async_trait]
+pub trait SubstrateBackend: Send + Sync {
+    /// « substrate » ou « simulator »
+    fn kind(&self) -> &str;
+    fn chain_name(&self) -> &str;
+    async fn finalized_head(&self) -> Result<SubstrateBlock, String>;
+    /// Bloc de hauteur `number`, s'il est déjà produit
+    async fn block(&self, number: u64) -> Result<Option<SubstrateBlock>, String>;
+    /// Solde libre (pallet Balances) d'un compte SS58, en plus petite unité
+    async fn free_balance(&self, account: &str) -> Result<u128, String>;
+    async fn events(&self, block: &SubstrateBlock) -> Result<Vec<SubstrateEvent>, String>;
+}
+
+pub type SharedSubstrate = Arc<dyn SubstrateBackend>;
+
+/// Connecte une chaîne Substrate du registre
+pub async fn connect_substrate(config: &ChainConfig) -> Result<SharedSubstrate, String> {
+    if config.kind != ChainKind::Substrate {
+        return Err(format!("{}: chaîne non Substrate", config.name));
+    }
+
+    #[cfg(feature = "substrate")]
+    let backend: SharedSubstrate = Arc::new(live::SubxtBackend::connect(&config.name, &config.rpc).await?);
+    #[cfg(not(feature = "substrate"))]
+    let backend: SharedSubstrate = {
+        info!("🧪 Chaîne Substrate simulée utilisée pour {} (feature substrate désactivée)", config.name);
+        Arc::new(SimulatedSubstrate::new(&config.name))
+    };
+    Ok(backend)
+}
+
+#[derive(Default)]
+struct SimulatedState {
+    blocks: Vec<(SubstrateBlock, Vec<SubstrateEvent>)>,
+    balances: BTreeMap<String, u128>,
+}
+
+/// Chaîne Substrate simulée en mémoire (feature `substrate` désactivée, tests)
+pub struct SimulatedSubstrate {
+    name: String,
+    state: RwLock<SimulatedState>,
+}
+
+impl SimulatedSubstrate {
+    /// Chaîne réduite à son bloc de genèse
+    pub fn new(name: &str) -> Self {
+        let simulated = Self { name: name.to_string(), state: RwLock::new(SimulatedState::default()) };
+        simulated.produce_block(Vec::new());
+        simulated
+    }
+
+    /// Produit et finalise un bloc contenant `events`
+    pub fn produce_block(&self, events: Vec<SubstrateEvent>) -> SubstrateBlock {
+        let mut state = self.state.write();
+        let number = state.blocks.len() as u64;
+        let mut preimage = self.name.as_bytes().to_vec();
+        preimage.extend_from_slice(&number.to_be_bytes());
+        let block = SubstrateBlock { number, hash: H256::from(keccak256(preimage)) };
+        state.blocks.push((block.clone(), events));
+        block
+    }
+
+    pub fn set_balance(&self, account: &str, free: u128) {
+        self.state.write().balances.insert(account.to_string(), free);
+    }
+}
+
+#[async_trait]
+impl SubstrateBackend for SimulatedSubstrate {
+    fn kind(&self) -> &str {
+        "simulator"
+    }
+
+    fn chain_name(&self) -> &str {
+        &self.name
+    }
+
+    async fn finalized_head(&self) -> Result<SubstrateBlock, String> {
+        self.state.read().blocks.last().map(|(block, _)| block.clone()).ok_or_else(|| "Aucun bloc produit".to_string())
+    }
+
+    async fn block(&self, number: u64) -> Result<Option<SubstrateBlock>, String> {
+        Ok(self.state.read().blocks.get(number as usize).map(|(block, _)| block.clone()))
+    }
+
+    async fn free_balance(&self, account: &str) -> Result<u128, String> {
+        Ok(self.state.read().balances.get(account).copied().unwrap_or(0))
+    }
+
+    async fn events(&self, block: &SubstrateBlock) -> Result<Vec<SubstrateEvent>, String> {
+        let state = self.state.read();
+        match state.blocks.get(block.number as usize) {
+            Some((known, events)) if known.hash == block.hash => Ok(events.clone()),
+            _ => Err(format!("{}: bloc {} inconnu", self.name, block.number)),
+        }
+    }
+}
+
+/// Client subxt (métadonnées lues à la connexion, requêtes dynamiques)
+#[cfg(feature = "substrate")]
+mod live {
+    use std::str::FromStr;
+
+    use subxt::backend::legacy::LegacyRpcMethods;
+    use subxt::backend::rpc::RpcClient;
+    use subxt::ext::scale_value::At;
+    use subxt::utils::AccountId32;
+    use subxt::{OnlineClient, PolkadotConfig};
+
+    use super::*;
+
+    pub struct SubxtBackend {
+        name: String,
+        api: OnlineClient<PolkadotConfig>,
+        rpc: LegacyRpcMethods<PolkadotConfig>,
+    }
+
+    impl SubxtBackend {
+        pub async fn connect(name: &str, url: &str) -> Result<Self, String> {
+            let client = RpcClient::from_url(url).await.map_err(|e| format!("{}: connexion RPC impossible: {}", name, e))?;
+            let api = OnlineClient::<PolkadotConfig>::from_rpc_client(client.clone())
+                .await
+                .map_err(|e| format!("{}: métadonnées indisponibles: {}", name, e))?;
+            info!("🔗 Chaîne Substrate {} connectée (runtime {})", name, api.runtime_version().spec_version);
+            Ok(Self { name: name.to_string(), api, rpc: LegacyRpcMethods::new(client) })
+        }
+    }
+
+    #[async_trait]
+    impl SubstrateBackend for SubxtBackend {
+        fn kind(&self) -> &str {
+            "substrate"
+        }
+
+        fn chain_name(&self) -> &str {
+            &self.name
+        }
+
+        async fn finalized_head(&self) -> Result<SubstrateBlock, String> {
+            let hash = self.rpc.chain_get_finalized_head().await.map_err(|e| format!("{}: tête finalisée: {}", self.name, e))?;
+            let header = self
+                .rpc
+                .chain_get_header(Some(hash))
+                .await
+                .map_err(|e| format!("{}: en-tête: {}", self.name, e))?
+                .ok_or_else(|| format!("{}: en-tête finalisé introuvable", self.name))?;
+            Ok(SubstrateBlock { number: header.number as u64, hash: H256::from(hash.0) })
+        }
+
+        async fn block(&self, number: u64) -> Result<Option<SubstrateBlock>, String> {
+            let hash = self
+                .rpc
+                .chain_get_block_hash(Some(number.into()))
+                .await
+                .map_err(|e| format!("{}: hash du bloc {}: {}", self.name, number, e))?;
+            Ok(hash.map(|hash| SubstrateBlock { number, hash: H256::from(hash.0) }))
+        }
+
+        async fn free_balance(&self, account: &str) -> Result<u128, String> {
+            let account = AccountId32::from_str(account).map_err(|e| format!("Adresse SS58 invalide {}: {}", account, e))?;
+            let query = subxt::dynamic::storage("System", "Account", vec![subxt::dynamic::Value::from_bytes(account)]);
+            let storage = self.api.storage().at_latest().await.map_err(|e| format!("{}: stockage: {}", self.name, e))?;
+            let Some(info) = storage.fetch(&query).await.map_err(|e| format!("{}: System.Account: {}", self.name, e))? else {
+                return Ok(0);
+            };
+            let value = info.to_value().map_err(|e| format!("{}: System.Account illisible: {}", self.name, e))?;
+            value
+                .at("data")
+                .and_then(|data| data.at("free"))
+                .and_then(|free| free.as_u128())
+                .ok_or_else(|| format!("{}: solde libre absent de System.Account", self.name))
+        }
+
+        async fn events(&self, block: &SubstrateBlock) -> Result<Vec<SubstrateEvent>, String> {
+            let events = self
+                .api
+                .blocks()
+                .at(subxt::utils::H256::from(block.hash.0))
+                .await
+                .map_err(|e| format!("{}: bloc {}: {}", self.name, block.number, e))?
+                .events()
+                .await
+                .map_err(|e| format!("{}: événements du bloc {}: {}", self.name, block.number, e))?;
+            events
+                .iter()
+                .map(|event| {
+                    let event = event.map_err(|e| format!("{}: événement illisible: {}", self.name, e))?;
+                    Ok(SubstrateEvent {
+                        pallet: event.pallet_name().to_string(),
+                        variant: event.variant_name().to_string(),
+                        fields: event.field_values().map(|fields| format!("{:?}", fields)).unwrap_or_default(),
+                    })
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_registry::NativeCurrency;
+
+    #[tokio::test]
+    async fn simulated_chain_serves_blocks_events_and_balances() {
+        let chain = SimulatedSubstrate::new("Polkadot");
+        let transfer = SubstrateEvent { pallet: "Balances".into(), variant: "Transfer".into(), fields: "amount: 10".into() };
+        let block = chain.produce_block(vec![transfer.clone()]);
+        chain.set_balance("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", 42);
+
+        assert_eq!(chain.finalized_head().await.unwrap(), block);
+        assert_eq!(chain.block(1).await.unwrap(), Some(block.clone()));
+        assert_eq!(chain.block(2).await.unwrap(), None);
+        assert_eq!(chain.events(&block).await.unwrap(), vec![transfer]);
+        assert_eq!(chain.free_balance("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5").await.unwrap(), 42);
+        assert_eq!(chain.free_balance("inconnu").await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn connect_rejects_evm_chains() {
+        let evm = ChainConfig {
+            name: "Sepolia".into(),
+            kind: ChainKind::Evm,
+            rpc: "https://rpc.sepolia.org".into(),
+            ws: None,
+            chain_id: Some(11155111),
+            explorer: None,
+            explorer_api: None,
+            native_currency: NativeCurrency { symbol: "ETH".into(), decimals: 18 },
+            testnet: true,
+        };
+        assert!(connect_substrate(&evm).await.is_err());
+    }
+}
@@ -0,0 +1,1143 @@
+//! AURORAE++ - swarm_intelligence.rs
+//!
+//! Intelligence collective entre instances. Le transport de l'apprentissage d'essaim est un
+//! protocole de bavardage épidémique: chaque instance publie ses stratégies, les menaces
+//! qu'elle a détectées et les patterns qu'elle a découverts; à chaque ronde, les nouveautés
+//! (« rumeurs ») sont poussées vers `fanout` pairs tirés au hasard, pendant un nombre limité de
+//! rondes. Périodiquement, un résumé (clé, version) de tout le magasin est échangé avec des pairs
+//! aléatoires (anti-entropie): chacun réclame ce qui lui manque et pousse ce que l'autre ignore,
+//! si bien que les magasins convergent même quand une rumeur s'est éteinte trop tôt.
+//!
+//! Comme `RaftNode`, `GossipNode` est une machine à états sans entrée/sortie: `round` fait
+//! avancer le protocole, `step` traite un message reçu et `take_messages` relève les messages à
+//! envoyer, transportés ensuite par la messagerie inter-instances.
+//!
+//! L'essaim sert aussi d'optimiseur: essaims particulaires (PSO) et colonies de fourmis (ACO)
+//! cherchent les hyperparamètres qui maximisent un `Objective` (réglage de l'agent RL, taux de
+//! l'évolution, stratégie de gas). Chaque itération est évaluée en parallèle, par les workers du
+//! calcul distribué quand il y en a, et la meilleure configuration de chaque objectif est
+//! conservée dans `aurorae_state/swarm_optima.json`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use tracing::{info, warn};
+
+use crate::distributed_compute::{ComputeScheduler, TaskKind};
+use crate::instance_messaging::InstanceMessage;
+use crate::knowledge::{KnowledgeBase, KnowledgeDelta, Pattern, Provenance, LOCAL_ORIGIN};
+use crate::reinforcement_learning::{LearningAgent, Strategy};
+use crate::security::{SecuritySystem, ThreatLevel};
+
+pub const DEFAULT_SWARM_CONFIG_PATH: &str = "swarm.toml";
+
+/// Préfixe de la source des menaces apprises de l'essaim (elles ne sont pas republiées)
+pub const SWARM_SOURCE_PREFIX: &str = "swarm:";
+
+/// Fichier `swarm.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct SwarmConfig {
+    pub gossip: GossipConfig,
+    pub pso: PsoConfig,
+    pub aco: AcoConfig,
+}
+
+/// Section `[gossip]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct GossipConfig {
+    /// Pairs contactés à chaque ronde
+    pub fanout: usize,
+    /// Rondes pendant lesquelles une nouveauté est poussée
+    pub rumor_rounds: u32,
+    /// Éléments par message de poussée
+    pub max_items_per_push: usize,
+    /// Période, en rondes, de l'échange des résumés (anti-entropie)
+    pub anti_entropy_every: u64,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self { fanout: 3, rumor_rounds: 4, max_items_per_push: 64, anti_entropy_every: 3 }
+    }
+}
+
+impl SwarmConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| format!("Configuration de l'essaim invalide: {}", e))?;
+        if config.gossip.fanout == 0 || config.gossip.max_items_per_push == 0 || config.gossip.anti_entropy_every == 0 {
+            return Err("fanout, max-items-per-push et anti-entropy-every doivent être positifs".to_string());
+        }
+        if !(0.0..1.0).contains(&config.aco.evaporation) {
+            return Err("aco.evaporation doit être dans [0, 1)".to_string());
+        }
+        Ok(config)
+    }
+
+    /// `swarm.toml`, ou la configuration par défaut
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_SWARM_CONFIG_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: essaim par défaut", e);
+            Self::default()
+        })
+    }
+}
+
+/// Nature d'un élément partagé
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GossipKind {
+    Strategy,
+    Threat,
+    Pattern,
+}
+
+/// Élément partagé; `payload` est le JSON de la stratégie, de la menace ou du pattern
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GossipItem {
+    pub kind: GossipKind,
+    pub key: String,
+    /// Instance auteure de cette version
+    pub origin: Uuid,
+    pub version: u64,
+    pub updated_at: String,
+    pub payload: String,
+}
+
+impl GossipItem {
+    fn digest(&self) -> DigestEntry {
+        DigestEntry { kind: self.kind, key: self.key.clone(), version: self.version, origin: self.origin }
+    }
+}
+
+/// Résumé d'un élément: suffit à savoir laquelle de deux copies est la plus récente
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DigestEntry {
+    pub kind: GossipKind,
+    pub key: String,
+    pub version: u64,
+    pub origin: Uuid,
+}
+
+impl DigestEntry {
+    /// Ordre total entre versions concurrentes: version, puis auteur pour départager
+    fn rank(&self) -> (u64, Uuid) {
+        (self.version, self.origin)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GossipMessage {
+    /// Résumé du magasin de l'émetteur, avec les pairs qu'il connaît
+    Digest { entries: Vec<DigestEntry>, peers: Vec<Uuid> },
+    /// Éléments réclamés après comparaison des résumés
+    Request { keys: Vec<(GossipKind, String)> },
+    /// Éléments poussés (rumeurs ou réponses)
+    Push { items: Vec<GossipItem> },
+}
+
+/// Menace telle qu'elle circule dans l'essaim (clé: son nom)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedThreat {
+    pub description: String,
+    pub level: ThreatLevel,
+}
+
+type ItemKey = (GossipKind, String);
+
+pub struct GossipNode {
+    pub id: Uuid,
+    config: GossipConfig,
+    store: BTreeMap<ItemKey, GossipItem>,
+    peers: BTreeSet<Uuid>,
+    /// Rumeurs en cours: rondes de poussée restantes
+    hot: BTreeMap<ItemKey, u32>,
+    rounds: u64,
+    outbox: Vec<(Uuid, GossipMessage)>,
+    /// Éléments d'autres instances acceptés depuis le dernier relevé
+    updates: Vec<GossipItem>,
+    rng: StdRng,
+}
+
+impl GossipNode {
+    pub fn new(id: Uuid, config: GossipConfig) -> Self {
+        Self {
+            id,
+            config,
+            store: BTreeMap::new(),
+            peers: BTreeSet::new(),
+            hot: BTreeMap::new(),
+            rounds: 0,
+            outbox: Vec::new(),
+            updates: Vec::new(),
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    pub fn get(&self, kind: GossipKind, key: &str) -> Option<&GossipItem> {
+        self.store.get(&(kind, key.to_string()))
+    }
+
+    pub fn peers(&self) -> Vec<Uuid> {
+        self.peers.iter().copied().collect()
+    }
+
+    /// Remplace les pairs connus (le parent connaît toutes les instances connectées)
+    pub fn set_peers(&mut self, peers: &[Uuid]) {
+        self.peers = peers.iter().copied().filter(|peer| *peer != self.id).collect();
+    }
+
+    pub fn add_peer(&mut self, peer: Uuid) {
+        if peer != self.id {
+            self.peers.insert(peer);
+        }
+    }
+
+    /// Publie une valeur locale; une nouvelle version n'est créée que si le contenu change.
+    /// Retourne vrai si une rumeur a été lancée
+    pub fn publish(&mut self, kind: GossipKind, key: &str, payload: String) -> bool {
+        let item_key = (kind, key.to_string());
+        let version = match self.store.get(&item_key) {
+            Some(current) if current.payload == payload => return false,
+            Some(current) => current.version + 1,
+            None => 1,
+        };
+        let item = GossipItem { kind, key: key.to_string(), origin: self.id, version, updated_at: Utc::now().to_rfc3339(), payload };
+        self.store.insert(item_key.clone(), item);
+        self.hot.insert(item_key, self.config.rumor_rounds);
+        true
+    }
+
+    /// Une ronde: les rumeurs sont poussées vers `fanout` pairs au hasard et, toutes les
+    /// `anti_entropy_every` rondes, ces pairs reçoivent aussi le résumé du magasin
+    pub fn round(&mut self) {
+        self.rounds += 1;
+        let peers: Vec<Uuid> = self.peers.iter().copied().collect();
+        let targets: Vec<Uuid> = peers.choose_multiple(&mut self.rng, self.config.fanout).copied().collect();
+        if targets.is_empty() {
+            return;
+        }
+
+        let mut rumors = Vec::new();
+        for (key, remaining) in self.hot.iter_mut().filter(|(_, remaining)| **remaining > 0) {
+            if let Some(item) = self.store.get(key) {
+                rumors.push(item.clone());
+            }
+            *remaining -= 1;
+        }
+        self.hot.retain(|_, remaining| *remaining > 0);
+
+        let digest = (self.rounds % self.config.anti_entropy_every == 0).then(|| GossipMessage::Digest {
+            entries: self.store.values().map(GossipItem::digest).collect(),
+            peers: peers.clone(),
+        });
+        for target in targets {
+            self.push(target, rumors.clone());
+            if let Some(digest) = &digest {
+                self.outbox.push((target, digest.clone()));
+            }
+        }
+    }
+
+    /// Traite un message reçu d'un pair
+    pub fn step(&mut self, from: Uuid, message: GossipMessage) {
+        self.add_peer(from);
+        match message {
+            GossipMessage::Digest { entries, peers } => {
+                for peer in peers {
+                    self.add_peer(peer);
+                }
+                let mut wanted = Vec::new();
+                let mut remote: BTreeMap<ItemKey, (u64, Uuid)> = BTreeMap::new();
+                for entry in entries {
+                    let key = (entry.kind, entry.key.clone());
+                    let newer = match self.store.get(&key) {
+                        Some(local) => entry.rank() > local.digest().rank(),
+                        None => true,
+                    };
+                    if newer {
+                        wanted.push(key.clone());
+                    }
+                    remote.insert(key, entry.rank());
+                }
+                let offered: Vec<GossipItem> = self
+                    .store
+                    .iter()
+                    .filter(|(key, item)| match remote.get(*key) {
+                        Some(rank) => item.digest().rank() > *rank,
+                        None => true,
+                    })
+                    .map(|(_, item)| item.clone())
+                    .collect();
+                if !wanted.is_empty() {
+                    self.outbox.push((from, GossipMessage::Request { keys: wanted }));
+                }
+                self.push(from, offered);
+            }
+            GossipMessage::Request { keys } => {
+                let items = keys.iter().filter_map(|key| self.store.get(key).cloned()).collect();
+                self.push(from, items);
+            }
+            GossipMessage::Push { items } => {
+                for item in items {
+                    self.merge(item);
+                }
+            }
+        }
+    }
+
+    /// Messages à transporter: (destinataire, message)
+    pub fn take_messages(&mut self) -> Vec<(Uuid, GossipMessage)> {
+        std::mem::take(&mut self.outbox)
+    }
+
+    /// Éléments reçus d'autres instances depuis le dernier relevé
+    pub fn drain_updates(&mut self) -> Vec<GossipItem> {
+        std::mem::take(&mut self.updates)
+    }
+
+    /// Garde la version la plus récente; une version acceptée devient à son tour une rumeur
+    fn merge(&mut self, item: GossipItem) -> bool {
+        let key = (item.kind, item.key.clone());
+        if let Some(local) = self.store.get(&key) {
+            if item.digest().rank() <= local.digest().rank() {
+                return false;
+            }
+        }
+        self.hot.insert(key.clone(), self.config.rumor_rounds);
+        if item.origin != self.id {
+            self.updates.push(item.clone());
+        }
+        self.store.insert(key, item);
+        true
+    }
+
+    fn push(&mut self, to: Uuid, items: Vec<GossipItem>) {
+        for chunk in items.chunks(self.config.max_items_per_push) {
+            self.outbox.push((to, GossipMessage::Push { items: chunk.to_vec() }));
+        }
+    }
+
+    /// Publie les menaces actives détectées localement
+    pub fn publish_threats(&mut self, security: &SecuritySystem) -> usize {
+        let threats: Vec<(String, SharedThreat)> = security
+            .get_active_threats()
+            .into_iter()
+            .filter(|threat| !threat.source.starts_with(SWARM_SOURCE_PREFIX))
+            .map(|threat| (threat.name.clone(), SharedThreat { description: threat.description.clone(), level: threat.level }))
+            .collect();
+        threats
+            .into_iter()
+            .filter(|(name, threat)| self.publish(GossipKind::Threat, name, serde_json::to_string(threat).unwrap_or_default()))
+            .count()
+    }
+
+    /// Publie les patterns découverts par l'instance elle-même
+    pub fn publish_patterns(&mut self, knowledge: &KnowledgeBase) -> usize {
+        knowledge
+            .records
+            .iter()
+            .filter(|pattern| !matches!(knowledge.provenance.get(&pattern.module_name), Some(provenance) if provenance.origin != LOCAL_ORIGIN))
+            .filter(|pattern| self.publish(GossipKind::Pattern, &pattern.module_name, serde_json::to_string(pattern).unwrap_or_default()))
+            .count()
+    }
+
+    /// Publie la stratégie la plus efficace de l'agent, sous une clé propre à l'instance
+    pub fn publish_strategy(&mut self, agent: &LearningAgent) -> bool {
+        let best = agent.strategies.iter().filter(|s| !s.name.contains('/')).max_by(|a, b| a.effectiveness.total_cmp(&b.effectiveness));
+        match best {
+            Some(strategy) => {
+                let key = format!("{}/best", self.id);
+                self.publish(GossipKind::Strategy, &key, serde_json::to_string(strategy).unwrap_or_default())
+            }
+            None => false,
+        }
+    }
+
+    /// Intègre les éléments reçus de l'essaim: menaces signalées au système de sécurité,
+    /// patterns fusionnés dans la base de savoir, stratégies plus efficaces que la meilleure
+    /// locale adoptées par l'agent. Retourne le nombre d'éléments intégrés
+    pub fn apply_updates(&mut self, security: &mut SecuritySystem, knowledge: &mut KnowledgeBase, agent: &mut LearningAgent) -> usize {
+        let mut applied = 0;
+        for item in self.drain_updates() {
+            let source = format!("{}{}", SWARM_SOURCE_PREFIX, item.origin);
+            let integrated = match item.kind {
+                GossipKind::Threat => serde_json::from_str::<SharedThreat>(&item.payload).map(|threat| {
+                    security.detect_threat(&item.key, &threat.description, threat.level, &source);
+                    true
+                }),
+                GossipKind::Pattern => serde_json::from_str::<Pattern>(&item.payload).map(|pattern| {
+                    let provenance = Provenance { origin: source.clone(), updated_at: Utc::now() };
+                    let report = knowledge.merge(KnowledgeDelta {
+                        origin: source.clone(),
+                        since: None,
+                        patterns: vec![(pattern, provenance)],
+                        insights: Vec::new(),
+                    });
+                    report.added + report.updated > 0
+                }),
+                GossipKind::Strategy => serde_json::from_str::<Strategy>(&item.payload).map(|mut strategy| {
+                    let best_local = agent.strategies.iter().map(|s| s.effectiveness).fold(f32::MIN, f32::max);
+                    if strategy.effectiveness <= best_local {
+                        return false;
+                    }
+                    strategy.name = item.key.clone();
+                    agent.strategies.retain(|s| s.name != item.key);
+                    agent.strategies.push(strategy);
+                    true
+                }),
+            };
+            match integrated {
+                Ok(true) => applied += 1,
+                Ok(false) => {}
+                Err(e) => warn!("⚠️ Élément d'essaim illisible ({:?} {}): {}", item.kind, item.key, e),
+            }
+        }
+        if applied > 0 {
+            info!("🐝 {} élément(s) appris de l'essaim", applied);
+        }
+        applied
+    }
+}
+
+pub fn envelope(from: Uuid, to: Uuid, message: &GossipMessage) -> InstanceMessage {
+    InstanceMessage::Gossip { from, to, payload: serde_json::to_string(message).unwrap_or_default() }
+}
+
+pub fn open_envelope(payload: &str) -> Result<GossipMessage, String> {
+    serde_json::from_str(payload).map_err(|e| format!("Message d'essaim illisible: {}", e))
+}
+
+// ====================== OPTIMISATION PAR ESSAIM ======================
+
+/// Section `[pso]`: optimisation par essaim particulaire
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct PsoConfig {
+    pub particles: usize,
+    pub iterations: usize,
+    /// Part de la vitesse conservée d'une itération à l'autre
+    pub inertia: f64,
+    /// Attraction vers le meilleur point de la particule
+    pub cognitive: f64,
+    /// Attraction vers le meilleur point de l'essaim
+    pub social: f64,
+    pub seed: u64,
+}
+
+impl Default for PsoConfig {
+    fn default() -> Self {
+        Self { particles: 16, iterations: 25, inertia: 0.7, cognitive: 1.5, social: 1.5, seed: 42 }
+    }
+}
+
+/// Section `[aco]`: colonie de fourmis sur une grille de valeurs par dimension
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct AcoConfig {
+    pub ants: usize,
+    pub iterations: usize,
+    /// Valeurs possibles par dimension, réparties entre ses bornes
+    pub levels: usize,
+    /// Part des phéromones évaporée à chaque itération
+    pub evaporation: f64,
+    /// Meilleures fourmis de l'itération qui déposent des phéromones
+    pub elite: usize,
+    pub seed: u64,
+}
+
+impl Default for AcoConfig {
+    fn default() -> Self {
+        Self { ants: 16, iterations: 25, levels: 12, evaporation: 0.2, elite: 4, seed: 42 }
+    }
+}
+
+/// Hyperparamètre borné
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dimension {
+    pub name: String,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Dimension {
+    pub fn new(name: &str, min: f64, max: f64) -> Self {
+        Self { name: name.to_string(), min, max }
+    }
+
+    fn span(&self) -> f64 {
+        self.max - self.min
+    }
+
+    fn bound(&self, value: f64) -> f64 {
+        value.clamp(self.min, self.max)
+    }
+}
+
+/// Fonction objectif d'une recherche d'hyperparamètres
+pub trait Objective: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn space(&self) -> Vec<Dimension>;
+    /// Score à maximiser; déterministe, pour qu'un candidat évalué par un worker ou localement
+    /// obtienne le même score
+    fn evaluate(&self, params: &[f64]) -> f64;
+}
+
+/// Objectifs connus des workers de calcul
+pub const OBJECTIVES: [&str; 3] = ["rl-config", "evolution-rates", "gas-strategy"];
+
+pub fn objective_named(name: &str) -> Result<Box<dyn Objective>, String> {
+    match name {
+        "rl-config" => Ok(Box::new(RlConfigObjective)),
+        "evolution-rates" => Ok(Box::new(EvolutionRatesObjective)),
+        "gas-strategy" => Ok(Box::new(GasStrategyObjective)),
+        other => Err(format!("Objectif inconnu: {}", other)),
+    }
+}
+
+/// Taux d'apprentissage, facteur de réduction et exploration de l'agent RL, jugés par le
+/// retour moyen d'un Q-learning tabulaire sur un couloir (récompense au bout, coût par pas)
+pub struct RlConfigObjective;
+
+impl Objective for RlConfigObjective {
+    fn name(&self) -> &'static str {
+        "rl-config"
+    }
+
+    fn space(&self) -> Vec<Dimension> {
+        vec![Dimension::new("learning_rate", 0.01, 1.0), Dimension::new("discount_factor", 0.5, 0.999), Dimension::new("exploration_rate", 0.0, 0.5)]
+    }
+
+    fn evaluate(&self, params: &[f64]) -> f64 {
+        const STATES: usize = 8;
+        const EPISODES: usize = 200;
+        const MAX_STEPS: usize = 40;
+        let (alpha, gamma, epsilon) = (params[0], params[1], params[2]);
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut q = [[0.0f64; 2]; STATES];
+        let mut total = 0.0;
+        for _ in 0..EPISODES {
+            let mut state = 0;
+            for _ in 0..MAX_STEPS {
+                let action = if rng.gen::<f64>() < epsilon || q[state][0] == q[state][1] {
+                    rng.gen_range(0..2)
+                } else if q[state][1] > q[state][0] {
+                    1
+                } else {
+                    0
+                };
+                let next = if action == 1 { (state + 1).min(STATES - 1) } else { state.saturating_sub(1) };
+                let done = next == STATES - 1;
+                let reward = if done { 1.0 } else { -0.01 };
+                let future = if done { 0.0 } else { q[next][0].max(q[next][1]) };
+                q[state][action] += alpha * (reward + gamma * future - q[state][action]);
+                total += reward;
+                state = next;
+                if done {
+                    break;
+                }
+            }
+        }
+        total / EPISODES as f64
+    }
+}
+
+/// Taux de mutation et de croisement de l'évolution, jugés par un algorithme génétique sur un
+/// problème OneMax (meilleure aptitude moyenne au fil des générations)
+pub struct EvolutionRatesObjective;
+
+impl Objective for EvolutionRatesObjective {
+    fn name(&self) -> &'static str {
+        "evolution-rates"
+    }
+
+    fn space(&self) -> Vec<Dimension> {
+        vec![Dimension::new("mutation_rate", 0.001, 0.3), Dimension::new("crossover_rate", 0.0, 1.0)]
+    }
+
+    fn evaluate(&self, params: &[f64]) -> f64 {
+        const GENES: usize = 40;
+        const POPULATION: usize = 24;
+        const GENERATIONS: usize = 30;
+        let (mutation, crossover) = (params[0], params[1]);
+        let mut rng = StdRng::seed_from_u64(11);
+        let fitness = |genome: &Vec<bool>| genome.iter().filter(|gene| **gene).count();
+        let mut population: Vec<Vec<bool>> = (0..POPULATION).map(|_| (0..GENES).map(|_| rng.gen::<f64>() < 0.2).collect()).collect();
+        let mut progress = 0.0;
+        for _ in 0..GENERATIONS {
+            let tournament = |rng: &mut StdRng, population: &[Vec<bool>]| {
+                let (a, b) = (&population[rng.gen_range(0..POPULATION)], &population[rng.gen_range(0..POPULATION)]);
+                if fitness(a) >= fitness(b) { a.clone() } else { b.clone() }
+            };
+            let mut next = Vec::with_capacity(POPULATION);
+            while next.len() < POPULATION {
+                let mut child = tournament(&mut rng, &population);
+                if rng.gen::<f64>() < crossover {
+                    let other = tournament(&mut rng, &population);
+                    let cut = rng.gen_range(0..GENES);
+                    child[cut..].copy_from_slice(&other[cut..]);
+                }
+                for gene in child.iter_mut() {
+                    if rng.gen::<f64>() < mutation {
+                        *gene = !*gene;
+                    }
+                }
+                next.push(child);
+            }
+            population = next;
+            progress += population.iter().map(&fitness).max().unwrap_or(0) as f64 / GENES as f64;
+        }
+        progress / GENERATIONS as f64
+    }
+}
+
+/// Marge sur le frais de base et multiplicateur de pourboire des transactions, jugés sur un
+/// marché de frais EIP-1559 simulé: coût payé, blocs d'attente et transactions abandonnées
+pub struct GasStrategyObjective;
+
+impl Objective for GasStrategyObjective {
+    fn name(&self) -> &'static str {
+        "gas-strategy"
+    }
+
+    fn space(&self) -> Vec<Dimension> {
+        vec![Dimension::new("base_fee_margin", 1.0, 3.0), Dimension::new("tip_multiplier", 0.5, 3.0)]
+    }
+
+    fn evaluate(&self, params: &[f64]) -> f64 {
+        const TRIALS: usize = 200;
+        const MAX_WAIT: usize = 10;
+        let (margin, tip) = (params[0], params[1]);
+        let mut rng = StdRng::seed_from_u64(13);
+        let mut penalty = 0.0;
+        for _ in 0..TRIALS {
+            // Frais en unités du frais de base à la soumission
+            let mut base = 1.0;
+            let mut included = None;
+            for wait in 0..MAX_WAIT {
+                base *= 1.0 + rng.gen_range(-0.125..0.125);
+                // Pourboire des transactions concurrentes, en unités du pourboire de référence
+                let competing = rng.gen_range(0.5..2.0);
+                if margin >= base && tip >= competing {
+                    included = Some((wait, base));
+                    break;
+                }
+            }
+            penalty += match included {
+                Some((wait, base)) => base + tip * 0.1 + 0.05 * wait as f64,
+                None => 5.0,
+            };
+        }
+        -penalty / TRIALS as f64
+    }
+}
+
+/// Optimiseur en mode demande/réponse: les candidats d'une itération peuvent être évalués
+/// localement ou par les workers, puis rendus dans le même ordre
+pub trait SwarmOptimizer: Send {
+    fn name(&self) -> &'static str;
+    /// Candidats de l'itération courante; vide une fois la recherche terminée
+    fn ask(&mut self) -> Vec<Vec<f64>>;
+    fn tell(&mut self, scores: &[f64]);
+    fn best(&self) -> Option<(Vec<f64>, f64)>;
+    fn is_done(&self) -> bool;
+}
+
+pub fn optimizer_named(name: &str, space: Vec<Dimension>, config: &SwarmConfig) -> Result<Box<dyn SwarmOptimizer>, String> {
+    match name {
+        "pso" => Ok(Box::new(ParticleSwarm::new(space, config.pso.clone()))),
+        "aco" => Ok(Box::new(AntColony::new(space, config.aco.clone()))),
+        other => Err(format!("Optimiseur inconnu: {}", other)),
+    }
+}
+
+pub struct ParticleSwarm {
+    space: Vec<Dimension>,
+    config: PsoConfig,
+    positions: Vec<Vec<f64>>,
+    velocities: Vec<Vec<f64>>,
+    personal: Vec<(Vec<f64>, f64)>,
+    global: Option<(Vec<f64>, f64)>,
+    iteration: usize,
+    rng: StdRng,
+}
+
+impl ParticleSwarm {
+    pub fn new(space: Vec<Dimension>, config: PsoConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let positions: Vec<Vec<f64>> = (0..config.particles.max(1))
+            .map(|_| space.iter().map(|d| d.min + rng.gen::<f64>() * d.span()).collect())
+            .collect();
+        let velocities = positions.iter().map(|_| space.iter().map(|d| rng.gen_range(-0.1..0.1) * d.span()).collect()).collect();
+        let personal = positions.iter().map(|p| (p.clone(), f64::NEG_INFINITY)).collect();
+        Self { space, config, positions, velocities, personal, global: None, iteration: 0, rng }
+    }
+}
+
+impl SwarmOptimizer for ParticleSwarm {
+    fn name(&self) -> &'static str {
+        "pso"
+    }
+
+    fn ask(&mut self) -> Vec<Vec<f64>> {
+        if self.is_done() { Vec::new() } else { self.positions.clone() }
+    }
+
+    fn tell(&mut self, scores: &[f64]) {
+        for (i, score) in scores.iter().enumerate().take(self.positions.len()) {
+            if *score > self.personal[i].1 {
+                self.personal[i] = (self.positions[i].clone(), *score);
+            }
+            if self.global.as_ref().map(|(_, best)| *score > *best).unwrap_or(true) {
+                self.global = Some((self.positions[i].clone(), *score));
+            }
+        }
+        let Some((global, _)) = self.global.clone() else { return };
+        for i in 0..self.positions.len() {
+            for (d, dimension) in self.space.iter().enumerate() {
+                let (r1, r2): (f64, f64) = (self.rng.gen(), self.rng.gen());
+                let x = self.positions[i][d];
+                let v = self.config.inertia * self.velocities[i][d]
+                    + self.config.cognitive * r1 * (self.personal[i].0[d] - x)
+                    + self.config.social * r2 * (global[d] - x);
+                let v = v.clamp(-dimension.span(), dimension.span());
+                self.velocities[i][d] = v;
+                self.positions[i][d] = dimension.bound(x + v);
+            }
+        }
+        self.iteration += 1;
+    }
+
+    fn best(&self) -> Option<(Vec<f64>, f64)> {
+        self.global.clone()
+    }
+
+    fn is_done(&self) -> bool {
+        self.iteration >= self.config.iterations
+    }
+}
+
+pub struct AntColony {
+    space: Vec<Dimension>,
+    config: AcoConfig,
+    /// Phéromones par dimension et par valeur
+    pheromones: Vec<Vec<f64>>,
+    /// Valeurs choisies par chaque fourmi de l'itération en cours
+    paths: Vec<Vec<usize>>,
+    best: Option<(Vec<usize>, f64)>,
+    iteration: usize,
+    rng: StdRng,
+}
+
+impl AntColony {
+    pub fn new(space: Vec<Dimension>, config: AcoConfig) -> Self {
+        let levels = config.levels.max(2);
+        let pheromones = space.iter().map(|_| vec![1.0; levels]).collect();
+        let rng = StdRng::seed_from_u64(config.seed);
+        Self { space, config: AcoConfig { levels, ..config }, pheromones, paths: Vec::new(), best: None, iteration: 0, rng }
+    }
+
+    fn value(&self, path: &[usize]) -> Vec<f64> {
+        self.space
+            .iter()
+            .zip(path)
+            .map(|(dimension, level)| dimension.min + dimension.span() * *level as f64 / (self.config.levels - 1) as f64)
+            .collect()
+    }
+
+    fn deposit(&mut self, path: &[usize], amount: f64) {
+        for (trail, level) in self.pheromones.iter_mut().zip(path) {
+            trail[*level] += amount;
+        }
+    }
+}
+
+impl SwarmOptimizer for AntColony {
+    fn name(&self) -> &'static str {
+        "aco"
+    }
+
+    fn ask(&mut self) -> Vec<Vec<f64>> {
+        if self.is_done() {
+            return Vec::new();
+        }
+        let mut paths = Vec::with_capacity(self.config.ants);
+        for _ in 0..self.config.ants.max(1) {
+            let path: Vec<usize> = self
+                .pheromones
+                .iter()
+                .map(|trail| WeightedIndex::new(trail).map(|choice| choice.sample(&mut self.rng)).unwrap_or(0))
+                .collect();
+            paths.push(path);
+        }
+        self.paths = paths;
+        self.paths.iter().map(|path| self.value(path)).collect()
+    }
+
+    fn tell(&mut self, scores: &[f64]) {
+        let mut ranked: Vec<(usize, f64)> = scores.iter().copied().enumerate().take(self.paths.len()).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        if let Some(&(first, score)) = ranked.first() {
+            if self.best.as_ref().map(|(_, best)| score > *best).unwrap_or(true) {
+                self.best = Some((self.paths[first].clone(), score));
+            }
+        }
+        for trail in self.pheromones.iter_mut() {
+            for pheromone in trail.iter_mut() {
+                *pheromone = (*pheromone * (1.0 - self.config.evaporation)).max(1e-3);
+            }
+        }
+        // Dépôt décroissant selon le rang, plus un renfort du meilleur chemin connu
+        let elite = self.config.elite.max(1);
+        for (rank, (ant, _)) in ranked.iter().take(elite).enumerate() {
+            let path = self.paths[*ant].clone();
+            self.deposit(&path, (elite - rank) as f64 / elite as f64);
+        }
+        if let Some((path, _)) = self.best.clone() {
+            self.deposit(&path, 1.0);
+        }
+        self.iteration += 1;
+    }
+
+    fn best(&self) -> Option<(Vec<f64>, f64)> {
+        self.best.as_ref().map(|(path, score)| (self.value(path), *score))
+    }
+
+    fn is_done(&self) -> bool {
+        self.iteration >= self.config.iterations
+    }
+}
+
+/// Évalue les candidats en parallèle sur les cœurs locaux
+pub fn evaluate_locally(objective: &dyn Objective, candidates: &[Vec<f64>]) -> Vec<f64> {
+    candidates.par_iter().map(|params| objective.evaluate(params)).collect()
+}
+
+/// Meilleure configuration trouvée pour un objectif
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptimizationResult {
+    pub objective: String,
+    pub optimizer: String,
+    pub params: BTreeMap<String, f64>,
+    pub score: f64,
+    pub evaluations: usize,
+    pub found_at: String,
+}
+
+impl OptimizationResult {
+    pub fn param(&self, name: &str) -> Option<f64> {
+        self.params.get(name).copied()
+    }
+}
+
+fn result_of(objective: &dyn Objective, optimizer: &dyn SwarmOptimizer, evaluations: usize) -> Option<OptimizationResult> {
+    let (best, score) = optimizer.best()?;
+    Some(OptimizationResult {
+        objective: objective.name().to_string(),
+        optimizer: optimizer.name().to_string(),
+        params: objective.space().into_iter().map(|d| d.name).zip(best).collect(),
+        score,
+        evaluations,
+        found_at: Utc::now().to_rfc3339(),
+    })
+}
+
+/// Recherche complète sur les cœurs locaux
+pub fn optimize(objective: &dyn Objective, optimizer: &mut dyn SwarmOptimizer) -> Option<OptimizationResult> {
+    let mut evaluations = 0;
+    loop {
+        let candidates = optimizer.ask();
+        if candidates.is_empty() {
+            return result_of(objective, optimizer, evaluations);
+        }
+        let scores = evaluate_locally(objective, &candidates);
+        evaluations += scores.len();
+        optimizer.tell(&scores);
+    }
+}
+
+/// Priorité des évaluations soumises à l'ordonnanceur: tâches de fond
+const SEARCH_PRIORITY: u8 = 64;
+
+/// Recherche menée au fil des cycles: chaque itération est confiée aux workers de calcul
+/// s'il en est d'inscrits pour `evaluate-candidate`, sinon évaluée localement
+pub struct HyperparameterSearch {
+    objective: Box<dyn Objective>,
+    optimizer: Box<dyn SwarmOptimizer>,
+    batch: Vec<Vec<f64>>,
+    scores: Vec<Option<f64>>,
+    /// Tâches distantes en cours: (candidat, tâche)
+    pending: Vec<(usize, Uuid)>,
+    evaluations: usize,
+}
+
+impl HyperparameterSearch {
+    pub fn new(objective: &str, optimizer: &str, config: &SwarmConfig) -> Result<Self, String> {
+        let objective = objective_named(objective)?;
+        let optimizer = optimizer_named(optimizer, objective.space(), config)?;
+        info!("🐜 Recherche d'hyperparamètres « {} » par {}", objective.name(), optimizer.name());
+        Ok(Self { objective, optimizer, batch: Vec::new(), scores: Vec::new(), pending: Vec::new(), evaluations: 0 })
+    }
+
+    pub fn objective(&self) -> &'static str {
+        self.objective.name()
+    }
+
+    /// Fait avancer la recherche; retourne la meilleure configuration une fois terminée
+    pub fn poll(&mut self, compute: Option<&mut ComputeScheduler>) -> Option<OptimizationResult> {
+        let capability = "evaluate-candidate";
+        if self.batch.is_empty() {
+            self.batch = self.optimizer.ask();
+            if self.batch.is_empty() {
+                return result_of(self.objective.as_ref(), self.optimizer.as_ref(), self.evaluations);
+            }
+            match compute.filter(|scheduler| scheduler.has_worker_for(capability)) {
+                Some(scheduler) => {
+                    self.scores = vec![None; self.batch.len()];
+                    let name = self.objective.name().to_string();
+                    self.pending = self
+                        .batch
+                        .iter()
+                        .enumerate()
+                        .map(|(i, params)| (i, scheduler.submit(TaskKind::EvaluateCandidate { objective: name.clone(), params: params.clone() }, SEARCH_PRIORITY)))
+                        .collect();
+                }
+                None => self.scores = evaluate_locally(self.objective.as_ref(), &self.batch).into_iter().map(Some).collect(),
+            }
+        } else {
+            let (objective, batch, scores) = (self.objective.as_ref(), &self.batch, &mut self.scores);
+            // Sans ordonnanceur, ou pour une tâche en échec définitif, l'évaluation se fait sur place
+            self.pending.retain(|(i, id)| {
+                let outcome = match compute.as_ref() {
+                    Some(scheduler) => scheduler.outcome(*id).cloned(),
+                    None => Some(Err("ordonnanceur indisponible".to_string())),
+                };
+                match outcome {
+                    Some(Ok(output)) => {
+                        scores[*i] = Some(serde_json::from_str(&output).unwrap_or_else(|_| objective.evaluate(&batch[*i])));
+                        false
+                    }
+                    Some(Err(_)) => {
+                        scores[*i] = Some(objective.evaluate(&batch[*i]));
+                        false
+                    }
+                    None => true,
+                }
+            });
+        }
+        if self.pending.is_empty() && self.scores.iter().all(Option::is_some) {
+            let scores: Vec<f64> = self.scores.drain(..).flatten().collect();
+            self.evaluations += scores.len();
+            self.optimizer.tell(&scores);
+            self.batch.clear();
+            if self.optimizer.is_done() {
+                return result_of(self.objective.as_ref(), self.optimizer.as_ref(), self.evaluations);
+            }
+        }
+        None
+    }
+}
+
+const DEFAULT_OPTIMA_PATH: &str = "aurorae_state/swarm_optima.json";
+
+/// Meilleures configurations trouvées, par objectif, persistées entre les exécutions
+pub struct BestConfigs {
+    path: Option<PathBuf>,
+    pub best: BTreeMap<String, OptimizationResult>,
+}
+
+impl BestConfigs {
+    pub fn new() -> Self {
+        Self::open(Path::new(DEFAULT_OPTIMA_PATH))
+    }
+
+    pub fn in_memory() -> Self {
+        Self { path: None, best: BTreeMap::new() }
+    }
+
+    pub fn open(path: &Path) -> Self {
+        let best = fs::read_to_string(path).ok().and_then(|content| serde_json::from_str(&content).ok()).unwrap_or_default();
+        Self { path: Some(path.to_path_buf()), best }
+    }
+
+    pub fn get(&self, objective: &str) -> Option<&OptimizationResult> {
+        self.best.get(objective)
+    }
+
+    /// Retient le résultat s'il bat le meilleur connu pour son objectif; retourne vrai dans ce cas
+    pub fn record(&mut self, result: OptimizationResult) -> bool {
+        if self.best.get(&result.objective).map(|known| result.score <= known.score).unwrap_or(false) {
+            info!("🐜 « {} »: {:.4} ne bat pas la meilleure configuration ({:.4})", result.objective, result.score, self.best[&result.objective].score);
+            return false;
+        }
+        info!("🏆 Nouvelle meilleure configuration « {} » ({}): {:.4} {:?}", result.objective, result.optimizer, result.score, result.params);
+        self.best.insert(result.objective.clone(), result);
+        if let Err(e) = self.save() {
+            warn!("⚠️ Configurations optimales non sauvegardées: {}", e);
+        }
+        true
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else { return Ok(()) };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = serde_json::to_string_pretty(&self.best).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| e.to_string())
+    }
+}
+
+impl Default for BestConfigs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Relève les messages de tous les nœuds: (émetteur, destinataire, message)
+    fn collect(nodes: &mut BTreeMap<Uuid, GossipNode>) -> Vec<(Uuid, Uuid, GossipMessage)> {
+        nodes
+            .values_mut()
+            .flat_map(|node| {
+                let from = node.id;
+                node.take_messages().into_iter().map(move |(to, message)| (from, to, message))
+            })
+            .collect()
+    }
+
+    /// Livre les messages jusqu'à épuisement; retourne le nombre de messages livrés
+    fn deliver(nodes: &mut BTreeMap<Uuid, GossipNode>, mut pending: Vec<(Uuid, Uuid, GossipMessage)>) -> usize {
+        let mut delivered = 0;
+        while !pending.is_empty() {
+            for (from, to, message) in pending {
+                delivered += 1;
+                nodes.get_mut(&to).unwrap().step(from, message);
+            }
+            pending = collect(nodes);
+        }
+        delivered
+    }
+
+    #[test]
+    fn anti_entropy_reconciles_diverged_stores() {
+        let config = GossipConfig { rumor_rounds: 0, anti_entropy_every: 1, ..GossipConfig::default() };
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut nodes: BTreeMap<Uuid, GossipNode> = [a, b].iter().map(|id| (*id, GossipNode::new(*id, config.clone()))).collect();
+        nodes.get_mut(&a).unwrap().publish(GossipKind::Threat, "flood", "v1".into());
+        nodes.get_mut(&a).unwrap().publish(GossipKind::Threat, "flood", "v2".into());
+        nodes.get_mut(&b).unwrap().publish(GossipKind::Threat, "flood", "b".into());
+        nodes.get_mut(&b).unwrap().publish(GossipKind::Pattern, "dream", "{}".into());
+        // Aucune rumeur: seule la comparaison des résumés peut faire converger les magasins
+        nodes.get_mut(&a).unwrap().add_peer(b);
+        nodes.get_mut(&a).unwrap().round();
+        let pending = collect(&mut nodes);
+        assert!(deliver(&mut nodes, pending) > 0);
+
+        for node in nodes.values() {
+            assert_eq!(node.len(), 2);
+            assert_eq!(node.get(GossipKind::Threat, "flood").unwrap().payload, "v2");
+            assert_eq!(node.get(GossipKind::Pattern, "dream").unwrap().origin, b);
+        }
+        assert_eq!(nodes.get_mut(&a).unwrap().drain_updates().len(), 1);
+        assert!(!nodes.get_mut(&a).unwrap().publish(GossipKind::Threat, "flood", "v2".into()));
+    }
+
+    #[test]
+    fn rumor_reaches_every_node_with_bounded_fanout() {
+        let config = GossipConfig { fanout: 2, ..GossipConfig::default() };
+        let ids: Vec<Uuid> = (0..10).map(|_| Uuid::new_v4()).collect();
+        let mut nodes: BTreeMap<Uuid, GossipNode> = ids.iter().map(|id| (*id, GossipNode::new(*id, config.clone()))).collect();
+        // Chaque nœud ne connaît que son voisin: les autres pairs s'apprennent par les résumés
+        for (i, id) in ids.iter().enumerate() {
+            nodes.get_mut(id).unwrap().add_peer(ids[(i + 1) % ids.len()]);
+        }
+        nodes.get_mut(&ids[0]).unwrap().publish(GossipKind::Strategy, "best", "{\"effectiveness\":0.9}".into());
+
+        for _ in 0..30 {
+            for node in nodes.values_mut() {
+                node.round();
+            }
+            let pending = collect(&mut nodes);
+            for sender in &ids {
+                let contacted: BTreeSet<&Uuid> = pending.iter().filter(|(from, _, _)| from == sender).map(|(_, to, _)| to).collect();
+                assert!(contacted.len() <= config.fanout);
+            }
+            deliver(&mut nodes, pending);
+            if nodes.values().all(|node| node.get(GossipKind::Strategy, "best").is_some()) {
+                break;
+            }
+        }
+        assert!(nodes.values().all(|node| node.get(GossipKind::Strategy, "best").map(|item| item.origin) == Some(ids[0])));
+    }
+
+    /// Paraboloïde de sommet (0.3, -1.2)
+    struct Paraboloid;
+
+    impl Objective for Paraboloid {
+        fn name(&self) -> &'static str {
+            "paraboloid"
+        }
+
+        fn space(&self) -> Vec<Dimension> {
+            vec![Dimension::new("x", -2.0, 2.0), Dimension::new("y", -2.0, 2.0)]
+        }
+
+        fn evaluate(&self, params: &[f64]) -> f64 {
+            -((params[0] - 0.3).powi(2) + (params[1] + 1.2).powi(2))
+        }
+    }
+
+    #[test]
+    fn particle_swarm_and_ant_colony_converge_on_the_optimum() {
+        let config = SwarmConfig { aco: AcoConfig { levels: 41, ..AcoConfig::default() }, ..SwarmConfig::default() };
+        for name in ["pso", "aco"] {
+            let mut optimizer = optimizer_named(name, Paraboloid.space(), &config).unwrap();
+            let result = optimize(&Paraboloid, optimizer.as_mut()).unwrap();
+            assert_eq!(result.optimizer, name);
+            assert!(result.score > -0.05, "{}: {:?}", name, result);
+            assert!((result.param("x").unwrap() - 0.3).abs() < 0.25 && (result.param("y").unwrap() + 1.2).abs() < 0.25);
+        }
+    }
+
+    #[test]
+    fn search_without_workers_keeps_the_best_configuration() {
+        let path = std::env::temp_dir().join(format!("aurorae-optima-{}.json", Uuid::new_v4()));
+        let config = SwarmConfig { pso: PsoConfig { particles: 6, iterations: 4, ..PsoConfig::default() }, ..SwarmConfig::default() };
+        let mut search = HyperparameterSearch::new("gas-strategy", "pso", &config).unwrap();
+        let result = (0..10).find_map(|_| search.poll(None)).expect("recherche terminée");
+        assert_eq!(result.evaluations, 24);
+        assert_eq!(result.score, GasStrategyObjective.evaluate(&[result.param("base_fee_margin").unwrap(), result.param("tip_multiplier").unwrap()]));
+
+        let mut best = BestConfigs::open(&path);
+        assert!(best.record(result.clone()));
+        assert!(!best.record(OptimizationResult { score: result.score - 1.0, ..result.clone() }));
+        assert_eq!(BestConfigs::open(&path).get("gas-strategy"), Some(&result));
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,463 @@
+//! AURORAE++ - syntax_tree.rs
+//!
+//! Extraction des éléments de code (fonctions, structures, traits, classes, contrats…) par
+//! analyse syntaxique tree-sitter, indépendante du langage: les accolades imbriquées et les
+//! génériques qui faisaient échouer les expressions régulières sont gérés par la grammaire.
+//! Chaque élément porte sa documentation, sa signature et les fonctions qu'il appelle.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Node, Parser};
+
+/// Langages reconnus par l'extracteur
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceLanguage {
+    Rust,
+    Python,
+    Go,
+    Solidity,
+}
+
+impl SourceLanguage {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rs" => Some(Self::Rust),
+            "py" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            "sol" => Some(Self::Solidity),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension().and_then(|e| e.to_str()).and_then(Self::from_extension)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::Go => "go",
+            Self::Solidity => "solidity",
+        }
+    }
+
+    fn grammar(&self) -> tree_sitter::Language {
+        match self {
+            Self::Rust => tree_sitter_rust::language(),
+            Self::Python => tree_sitter_python::language(),
+            Self::Go => tree_sitter_go::language(),
+            Self::Solidity => tree_sitter_solidity::language(),
+        }
+    }
+
+    /// Nœuds de la grammaire qui déclarent un élément
+    fn item_kind(&self, node_kind: &str) -> Option<ItemKind> {
+        let kind = match (self, node_kind) {
+            (Self::Rust, "function_item") => ItemKind::Function,
+            (Self::Rust, "struct_item") => ItemKind::Struct,
+            (Self::Rust, "enum_item") => ItemKind::Enum,
+            (Self::Rust, "trait_item") => ItemKind::Trait,
+            (Self::Rust, "impl_item") => ItemKind::Impl,
+            (Self::Python, "function_definition") => ItemKind::Function,
+            (Self::Python, "class_definition") => ItemKind::Class,
+            (Self::Go, "function_declaration") => ItemKind::Function,
+            (Self::Go, "method_declaration") => ItemKind::Method,
+            (Self::Go, "type_spec") => ItemKind::Type,
+            (Self::Solidity, "function_definition") => ItemKind::Function,
+            (Self::Solidity, "modifier_definition") => ItemKind::Function,
+            (Self::Solidity, "contract_declaration") => ItemKind::Contract,
+            (Self::Solidity, "library_declaration") => ItemKind::Contract,
+            (Self::Solidity, "interface_declaration") => ItemKind::Interface,
+            (Self::Solidity, "struct_declaration") => ItemKind::Struct,
+            (Self::Solidity, "enum_declaration") => ItemKind::Enum,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn is_call(&self, node_kind: &str) -> bool {
+        match self {
+            Self::Python => node_kind == "call",
+            _ => node_kind == "call_expression",
+        }
+    }
+
+    /// Commentaire de documentation: `///` et `/** */` (Rust, Solidity), tout commentaire
+    /// accolé en Go; Python utilise les docstrings
+    fn doc_comment(&self, node_kind: &str, text: &str) -> bool {
+        match self {
+            Self::Rust => node_kind.ends_with("comment") && (text.starts_with("///") || text.starts_with("/**")),
+            Self::Solidity => node_kind == "comment" && (text.starts_with("///") || text.starts_with("/**")),
+            Self::Go => node_kind == "comment",
+            Self::Python => false,
+        }
+    }
+}
+
+impl fmt::Display for SourceLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Rust => "Rust",
+            Self::Python => "Python",
+            Self::Go => "Go",
+            Self::Solidity => "Solidity",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Nature d'un élément extrait
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    Function,
+    Method,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    Impl,
+    Class,
+    Contract,
+    Type,
+}
+
+impl ItemKind {
+    pub fn is_callable(&self) -> bool {
+        matches!(self, Self::Function | Self::Method)
+    }
+
+    /// Éléments dont les fonctions internes deviennent des méthodes
+    fn is_container(&self) -> bool {
+        matches!(self, Self::Impl | Self::Trait | Self::Class | Self::Contract | Self::Interface)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Method => "method",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Trait => "trait",
+            Self::Interface => "interface",
+            Self::Impl => "impl",
+            Self::Class => "class",
+            Self::Contract => "contract",
+            Self::Type => "type",
+        }
+    }
+}
+
+/// Élément de code extrait d'un fichier source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeItem {
+    pub kind: ItemKind,
+    pub name: String,
+    pub language: SourceLanguage,
+    /// Type, classe ou contrat englobant d'une méthode
+    pub parent: Option<String>,
+    pub doc: Option<String>,
+    /// Déclaration sans le corps
+    pub signature: String,
+    pub code: String,
+    /// Lignes de début et de fin (à partir de 1)
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Fonctions appelées par le corps (noms simples, sans doublon)
+    pub calls: Vec<String>,
+}
+
+impl CodeItem {
+    /// `Parent::nom` pour une méthode, le nom seul sinon
+    pub fn qualified_name(&self) -> String {
+        match &self.parent {
+            Some(parent) => format!("{}::{}", parent, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn is_recursive(&self) -> bool {
+        self.kind.is_callable() && self.calls.contains(&self.name)
+    }
+}
+
+/// Éléments d'un fichier source dont le langage est déduit de l'extension
+pub fn extract_items(path: &Path) -> Result<Vec<CodeItem>, String> {
+    let language = SourceLanguage::from_path(path)
+        .ok_or_else(|| format!("Langage non reconnu: {}", path.display()))?;
+    let source = fs::read_to_string(path)
+        .map_err(|e| format!("Erreur de lecture de {}: {}", path.display(), e))?;
+    extract_items_from_source(language, &source)
+}
+
+/// Éléments d'un code source, dans l'ordre du fichier
+pub fn extract_items_from_source(language: SourceLanguage, source: &str) -> Result<Vec<CodeItem>, String> {
+    let mut parser = Parser::new();
+    parser.set_language(language.grammar())
+        .map_err(|e| format!("Grammaire {} incompatible: {}", language.name(), e))?;
+    let tree = parser.parse(source, None)
+        .ok_or_else(|| format!("Analyse {} interrompue", language.name()))?;
+
+    let extractor = Extractor { language, source };
+    let mut items = Vec::new();
+    extractor.visit(tree.root_node(), None, &mut items);
+    Ok(items)
+}
+
+/// Graphe d'appels: nom qualifié de chaque fonction → fonctions appelées
+pub fn call_graph(items: &[CodeItem]) -> BTreeMap<String, BTreeSet<String>> {
+    items.iter()
+        .filter(|item| item.kind.is_callable())
+        .map(|item| (item.qualified_name(), item.calls.iter().cloned().collect()))
+        .collect()
+}
+
+struct Extractor<'a> {
+    language: SourceLanguage,
+    source: &'a str,
+}
+
+impl<'a> Extractor<'a> {
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or("")
+    }
+
+    fn visit(&self, node: Node, parent: Option<&str>, items: &mut Vec<CodeItem>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            match self.language.item_kind(child.kind()).and_then(|kind| self.item(child, kind, parent)) {
+                Some(item) => {
+                    let scope = item.kind.is_container().then(|| item.name.clone());
+                    items.push(item);
+                    self.visit(child, scope.as_deref().or(parent), items);
+                }
+                None => self.visit(child, parent, items),
+            }
+        }
+    }
+
+    fn item(&self, node: Node, kind: ItemKind, parent: Option<&str>) -> Option<CodeItem> {
+        let (kind, name, parent) = match (self.language, kind) {
+            (SourceLanguage::Rust, ItemKind::Impl) => {
+                let target = self.text(node.child_by_field_name("type")?);
+                let name = match node.child_by_field_name("trait") {
+                    Some(tr) => format!("{} for {}", self.text(tr), target),
+                    None => target.to_string(),
+                };
+                (kind, name, None)
+            }
+            (SourceLanguage::Go, ItemKind::Type) => {
+                let kind = match node.child_by_field_name("type").map(|t| t.kind()) {
+                    Some("struct_type") => ItemKind::Struct,
+                    Some("interface_type") => ItemKind::Interface,
+                    _ => ItemKind::Type,
+                };
+                (kind, self.text(node.child_by_field_name("name")?).to_string(), None)
+            }
+            (SourceLanguage::Go, ItemKind::Method) => {
+                let receiver = node.child_by_field_name("receiver").and_then(|r| self.last_of_kind(r, "type_identifier"));
+                (kind, self.text(node.child_by_field_name("name")?).to_string(), receiver)
+            }
+            (_, ItemKind::Function) if parent.is_some() => {
+                (ItemKind::Method, self.text(node.child_by_field_name("name")?).to_string(), parent.map(str::to_string))
+            }
+            _ => (kind, self.text(node.child_by_field_name("name")?).to_string(), None),
+        };
+        let body = node.child_by_field_name("body");
+        let signature = match body {
+            Some(body) => &self.source[node.start_byte()..body.start_byte()],
+            None => self.text(node).lines().next().unwrap_or(""),
+        };
+        let calls = match (kind.is_callable(), body) {
+            (true, Some(body)) => {
+                let mut calls = BTreeSet::new();
+                self.collect_calls(body, &mut calls);
+                calls.into_iter().collect()
+            }
+            _ => Vec::new(),
+        };
+        Some(CodeItem {
+            kind,
+            name,
+            language: self.language,
+            parent,
+            doc: self.doc(node),
+            signature: signature.trim().to_string(),
+            code: self.text(node).to_string(),
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+            calls,
+        })
+    }
+
+    /// Appels du corps, sans descendre dans les fonctions imbriquées
+    fn collect_calls(&self, node: Node, calls: &mut BTreeSet<String>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if self.language.item_kind(child.kind()).map_or(false, |k| k.is_callable()) {
+                continue;
+            }
+            if self.language.is_call(child.kind()) {
+                if let Some(name) = child.child_by_field_name("function").and_then(|callee| self.callee_name(callee)) {
+                    calls.insert(name);
+                }
+            }
+            self.collect_calls(child, calls);
+        }
+    }
+
+    /// Nom simple de l'appelé: `a.b.c()` → `c`, `Type::new()` → `new`
+    fn callee_name(&self, callee: Node) -> Option<String> {
+        for field in ["name", "field", "attribute", "property", "function"] {
+            if let Some(node) = callee.child_by_field_name(field) {
+                return self.callee_name(node);
+            }
+        }
+        if callee.kind().ends_with("identifier") {
+            return Some(self.text(callee).to_string());
+        }
+        let count = callee.named_child_count();
+        if count == 0 {
+            return None;
+        }
+        callee.named_child(count - 1).and_then(|last| self.callee_name(last))
+    }
+
+    fn last_of_kind(&self, node: Node, kind: &str) -> Option<String> {
+        let mut found = (node.kind() == kind).then(|| self.text(node).to_string());
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if let Some(name) = self.last_of_kind(child, kind) {
+                found = Some(name);
+            }
+        }
+        found
+    }
+
+    fn doc(&self, node: Node) -> Option<String> {
+        if self.language == SourceLanguage::Python {
+            return self.docstring(node);
+        }
+        // Commentaires accolés au-dessus de l'élément, attributs compris (Rust); en Go, ceux
+        // d'un type précèdent la déclaration `type` englobante
+        let anchor = match node.parent() {
+            Some(parent) if parent.kind() == "type_declaration" => parent,
+            _ => node,
+        };
+        let mut lines = Vec::new();
+        let mut next_row = anchor.start_position().row;
+        let mut sibling = anchor.prev_sibling();
+        while let Some(prev) = sibling {
+            if prev.end_position().row + 1 < next_row {
+                break;
+            }
+            let text = self.text(prev);
+            if self.language.doc_comment(prev.kind(), text) {
+                lines.push(clean_comment(text));
+            } else if prev.kind() != "attribute_item" {
+                break;
+            }
+            next_row = prev.start_position().row;
+            sibling = prev.prev_sibling();
+        }
+        lines.reverse();
+        let doc = lines.join("\n").trim().to_string();
+        (!doc.is_empty()).then_some(doc)
+    }
+
+    /// Première instruction chaîne du corps d'une fonction ou d'une classe Python
+    fn docstring(&self, node: Node) -> Option<String> {
+        let body = node.child_by_field_name("body")?;
+        let statement = body.named_child(0).filter(|s| s.kind() == "expression_statement")?;
+        let string = statement.named_child(0).filter(|s| s.kind() == "string")?;
+        let text = self.text(string);
+        let doc = text.trim_start_matches(|c: char| c.is_ascii_alphabetic())
+            .trim_matches(|c| c == '"' || c == '\'')
+            .trim()
+            .to_string();
+        (!doc.is_empty()).then_some(doc)
+    }
+}
+
+/// Retire les marqueurs de commentaire (`///`, `//`, `/** */`, `*` en début de ligne)
+fn clean_comment(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches("/**")
+                .trim_end_matches("*/")
+                .trim_start_matches('/')
+                .trim_start_matches('*')
+                .trim()
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_items_survive_nested_braces_and_generics() {
+        let source = r#"
+/// Un registre générique
+#[derive(Debug)]
+pub struct Registry<T: Clone> {
+    items: Vec<T>,
+    index: std::collections::HashMap<String, Vec<usize>>,
+}
+
+impl<T: Clone> Registry<T> {
+    /// Somme récursive
+    pub fn total(&self, n: usize) -> usize {
+        if n == 0 { return 0; }
+        let closure = |x: usize| { if x > 1 { x } else { 1 } };
+        closure(n) + self.total(n - 1) + helper(n)
+    }
+}
+
+fn helper<F: Fn(usize) -> usize>(n: usize) -> usize { n }
+"#;
+        let items = extract_items_from_source(SourceLanguage::Rust, source).unwrap();
+        let names: Vec<(ItemKind, &str)> = items.iter().map(|i| (i.kind, i.name.as_str())).collect();
+        assert_eq!(names, vec![
+            (ItemKind::Struct, "Registry"),
+            (ItemKind::Impl, "Registry<T>"),
+            (ItemKind::Method, "total"),
+            (ItemKind::Function, "helper"),
+        ]);
+
+        let registry = &items[0];
+        assert_eq!(registry.doc.as_deref(), Some("Un registre générique"));
+        assert!(registry.code.contains("HashMap<String, Vec<usize>>"));
+
+        let total = &items[2];
+        assert_eq!(total.parent.as_deref(), Some("Registry<T>"));
+        assert_eq!(total.signature, "pub fn total(&self, n: usize) -> usize");
+        assert_eq!(total.calls, vec!["closure", "helper", "total"]);
+        assert!(total.is_recursive());
+        assert_eq!(call_graph(&items)["Registry<T>::total"].len(), 3);
+    }
+
+    #[test]
+    fn python_docstrings_and_methods() {
+        let source = "class Pool:\n    \"\"\"Réserve de liquidité\"\"\"\n\n    def swap(self, amount):\n        '''Échange'''\n        return self.price(amount) * fee(amount)\n\ndef fee(x):\n    return x\n";
+        let items = extract_items_from_source(SourceLanguage::Python, source).unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].kind, ItemKind::Class);
+        assert_eq!(items[0].doc.as_deref(), Some("Réserve de liquidité"));
+        assert_eq!(items[1].kind, ItemKind::Method);
+        assert_eq!(items[1].qualified_name(), "Pool::swap");
+        assert_eq!(items[1].doc.as_deref(), Some("Échange"));
+        assert_eq!(items[1].calls, vec!["fee", "price"]);
+        assert_eq!(items[2].kind, ItemKind::Function);
+    }
+}
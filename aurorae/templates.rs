@@ -0,0 +1,481 @@
+//! AURORAE++ - templates.rs
+//!
+//! Bibliothèque de gabarits paramétrés pour le générateur: chaque gabarit (adaptateur de
+//! consensus, module de jeton, client d'oracle, collecteur de métriques) déclare le schéma
+//! de ses paramètres et produit une `ModuleSpec` à partir d'arguments typés et validés.
+
+use std::collections::BTreeMap;
+
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::generator::{type_name, FieldSpec, ImplSpec, MethodSpec, ModuleSpec, StructSpec, TraitSpec};
+
+lazy_static! {
+    /// Gabarits disponibles pour `trigger_generation`
+    pub static ref TEMPLATES: RwLock<TemplateRegistry> = RwLock::new(TemplateRegistry::builtin());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    Str,
+    Int,
+    Float,
+    Bool,
+}
+
+impl ParamType {
+    /// Valeur typée depuis sa forme texte (`key=value` en ligne de commande…)
+    pub fn parse(&self, raw: &str) -> Result<ParamValue, String> {
+        match self {
+            ParamType::Str => Ok(ParamValue::Str(raw.to_string())),
+            ParamType::Int => raw.parse().map(ParamValue::Int).map_err(|_| format!("Entier attendu: {}", raw)),
+            ParamType::Float => raw.parse().map(ParamValue::Float).map_err(|_| format!("Réel attendu: {}", raw)),
+            ParamType::Bool => raw.parse().map(ParamValue::Bool).map_err(|_| format!("Booléen attendu: {}", raw)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ParamValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl ParamValue {
+    pub fn param_type(&self) -> ParamType {
+        match self {
+            ParamValue::Str(_) => ParamType::Str,
+            ParamValue::Int(_) => ParamType::Int,
+            ParamValue::Float(_) => ParamType::Float,
+            ParamValue::Bool(_) => ParamType::Bool,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            ParamValue::Int(v) => Some(*v as f64),
+            ParamValue::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+/// Arguments d'un gabarit, par nom de paramètre
+pub type TemplateArgs = BTreeMap<String, ParamValue>;
+
+/// Paramètre déclaré par un gabarit; sans valeur par défaut, il est obligatoire
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamSchema {
+    pub name: String,
+    pub ty: ParamType,
+    pub doc: String,
+    pub default: Option<ParamValue>,
+    /// Bornes des paramètres numériques
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl ParamSchema {
+    fn new(name: &str, ty: ParamType, doc: &str, default: Option<ParamValue>) -> Self {
+        Self { name: name.to_string(), ty, doc: doc.to_string(), default, min: None, max: None }
+    }
+
+    fn range(mut self, min: f64, max: f64) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    fn check(&self, value: &ParamValue) -> Result<(), String> {
+        // Un entier est accepté pour un paramètre réel
+        let value_type = value.param_type();
+        if value_type != self.ty && !(self.ty == ParamType::Float && value_type == ParamType::Int) {
+            return Err(format!("Paramètre {}: {:?} attendu, {:?} reçu", self.name, self.ty, value_type));
+        }
+        if let Some(v) = value.as_f64() {
+            if self.min.map_or(false, |min| v < min) || self.max.map_or(false, |max| v > max) {
+                return Err(format!("Paramètre {} hors bornes [{:?}, {:?}]: {}", self.name, self.min, self.max, v));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Gabarit: schéma des paramètres et construction de la spécification
+#[derive(Clone)]
+pub struct Template {
+    pub name: String,
+    pub description: String,
+    pub params: Vec<ParamSchema>,
+    /// Reçoit le nom du module et des arguments complets (défauts appliqués)
+    pub build: fn(&str, &TemplateArgs) -> ModuleSpec,
+}
+
+impl Template {
+    /// Valide les arguments contre le schéma et complète les valeurs par défaut
+    pub fn resolve(&self, args: &TemplateArgs) -> Result<TemplateArgs, String> {
+        if let Some(unknown) = args.keys().find(|k| !self.params.iter().any(|p| &p.name == *k)) {
+            return Err(format!("Paramètre inconnu pour {}: {}", self.name, unknown));
+        }
+        let mut resolved = TemplateArgs::new();
+        for param in &self.params {
+            let value = match args.get(&param.name).or(param.default.as_ref()) {
+                Some(value) => value.clone(),
+                None => return Err(format!("Paramètre obligatoire manquant pour {}: {}", self.name, param.name)),
+            };
+            param.check(&value)?;
+            // Normalisation: `Float` attendu, entier fourni
+            let value = match (param.ty, value) {
+                (ParamType::Float, ParamValue::Int(v)) => ParamValue::Float(v as f64),
+                (_, value) => value,
+            };
+            resolved.insert(param.name.clone(), value);
+        }
+        Ok(resolved)
+    }
+
+    /// Arguments `key=value` typés selon le schéma
+    pub fn parse_args(&self, pairs: &[String]) -> Result<TemplateArgs, String> {
+        pairs.iter()
+            .map(|pair| {
+                let (key, raw) = pair.split_once('=').ok_or_else(|| format!("Argument attendu sous la forme clé=valeur: {}", pair))?;
+                let param = self.params.iter().find(|p| p.name == key)
+                    .ok_or_else(|| format!("Paramètre inconnu pour {}: {}", self.name, key))?;
+                Ok((key.to_string(), param.ty.parse(raw)?))
+            })
+            .collect()
+    }
+
+    pub fn instantiate(&self, module_name: &str, args: &TemplateArgs) -> Result<ModuleSpec, String> {
+        let resolved = self.resolve(args)?;
+        let mut spec = (self.build)(module_name, &resolved);
+        spec.doc.push(format!("Gabarit : {}", self.name));
+        spec.doc.extend(resolved.iter().map(|(k, v)| format!("{} = {}", k, literal(v))));
+        Ok(spec)
+    }
+}
+
+#[derive(Default)]
+pub struct TemplateRegistry {
+    templates: BTreeMap<String, Template>,
+}
+
+impl TemplateRegistry {
+    /// Gabarits fournis: basic, consensus_adapter, token_module, oracle_client, metric_collector
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        registry.register(Template {
+            name: "basic".to_string(),
+            description: "Composant minimal avec trait de cycle et fonction hello".to_string(),
+            params: Vec::new(),
+            build: |name, _| ModuleSpec::basic(name),
+        });
+        registry.register(Template {
+            name: "consensus_adapter".to_string(),
+            description: "Adaptateur de consensus à quorum de validateurs".to_string(),
+            params: vec![
+                ParamSchema::new("protocol", ParamType::Str, "Protocole ciblé", Some(ParamValue::Str("pbft".to_string()))),
+                ParamSchema::new("validators", ParamType::Int, "Nombre de validateurs", Some(ParamValue::Int(4))).range(1.0, 10_000.0),
+                ParamSchema::new("quorum", ParamType::Float, "Part des votes requise", Some(ParamValue::Float(0.67))).range(0.5, 1.0),
+            ],
+            build: consensus_adapter,
+        });
+        registry.register(Template {
+            name: "token_module".to_string(),
+            description: "Jeton fongible à offre plafonnée".to_string(),
+            params: vec![
+                ParamSchema::new("symbol", ParamType::Str, "Symbole du jeton", None),
+                ParamSchema::new("decimals", ParamType::Int, "Décimales", Some(ParamValue::Int(18))).range(0.0, 18.0),
+                ParamSchema::new("max_supply", ParamType::Int, "Offre maximale (unités entières)", Some(ParamValue::Int(1_000_000))).range(1.0, 1e15),
+            ],
+            build: token_module,
+        });
+        registry.register(Template {
+            name: "oracle_client".to_string(),
+            description: "Client d'oracle de prix avec contrôle de fraîcheur".to_string(),
+            params: vec![
+                ParamSchema::new("endpoint", ParamType::Str, "URL de l'oracle", None),
+                ParamSchema::new("feed", ParamType::Str, "Paire suivie", Some(ParamValue::Str("ETH/USD".to_string()))),
+                ParamSchema::new("max_staleness_secs", ParamType::Int, "Âge maximal d'un prix", Some(ParamValue::Int(60))).range(1.0, 86_400.0),
+            ],
+            build: oracle_client,
+        });
+        registry.register(Template {
+            name: "metric_collector".to_string(),
+            description: "Collecteur de métriques à fenêtre glissante et seuil d'alerte".to_string(),
+            params: vec![
+                ParamSchema::new("metric", ParamType::Str, "Nom de la métrique", Some(ParamValue::Str("energy".to_string()))),
+                ParamSchema::new("window", ParamType::Int, "Taille de la fenêtre", Some(ParamValue::Int(60))).range(1.0, 100_000.0),
+                ParamSchema::new("alert_threshold", ParamType::Float, "Moyenne déclenchant l'alerte", Some(ParamValue::Float(0.9))),
+            ],
+            build: metric_collector,
+        });
+        registry
+    }
+
+    pub fn register(&mut self, template: Template) {
+        self.templates.insert(template.name.clone(), template);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Template> {
+        self.templates.get(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.templates.keys().map(String::as_str).collect()
+    }
+
+    pub fn instantiate(&self, template: &str, module_name: &str, args: &TemplateArgs) -> Result<ModuleSpec, String> {
+        self.get(template)
+            .ok_or_else(|| format!("Gabarit inconnu: {} (disponibles: {})", template, self.names().join(", ")))?
+            .instantiate(module_name, args)
+    }
+}
+
+// ==================== GABARITS ====================
+
+/// Littéral Rust d'une valeur (les chaînes sont échappées)
+fn literal(value: &ParamValue) -> String {
+    match value {
+        ParamValue::Str(v) => format!("{:?}", v),
+        ParamValue::Int(v) => v.to_string(),
+        ParamValue::Float(v) => format!("{:?}", v),
+        ParamValue::Bool(v) => v.to_string(),
+    }
+}
+
+fn arg(args: &TemplateArgs, name: &str) -> String {
+    args.get(name).map(literal).unwrap_or_default()
+}
+
+fn field(name: &str, ty: &str) -> FieldSpec {
+    FieldSpec::new(name, ty)
+}
+
+fn method(name: &str, doc: &str, mutable: bool, args: Vec<FieldSpec>, ret: Option<&str>, body: String) -> MethodSpec {
+    MethodSpec {
+        name: name.to_string(),
+        doc: doc.to_string(),
+        receiver: true,
+        mutable,
+        args,
+        ret: ret.map(str::to_string),
+        body: Some(body),
+    }
+}
+
+fn constructor(target: &str, body: String) -> MethodSpec {
+    MethodSpec {
+        name: "new".to_string(),
+        doc: format!("{} configuré par le gabarit", target),
+        ret: Some("Self".to_string()),
+        body: Some(body),
+        ..MethodSpec::default()
+    }
+}
+
+fn header(name: &str, summary: String) -> Vec<String> {
+    vec![
+        "Module généré automatiquement par AURORAE++".to_string(),
+        format!("Nom : {}", name),
+        summary,
+    ]
+}
+
+fn consensus_adapter(name: &str, args: &TemplateArgs) -> ModuleSpec {
+    let adapter = format!("{}Adapter", type_name(name));
+    ModuleSpec {
+        name: name.to_string(),
+        doc: header(name, format!("Adaptateur de consensus {}", arg(args, "protocol"))),
+        structs: vec![StructSpec {
+            name: adapter.clone(),
+            doc: "Comptage des votes d'une proposition".to_string(),
+            fields: vec![field("protocol", "String"), field("validators", "u32"), field("quorum", "f64"), field("approvals", "u32")],
+        }],
+        traits: vec![TraitSpec {
+            name: "ConsensusAdapter".to_string(),
+            doc: "Décision de finalité d'une proposition".to_string(),
+            methods: vec![MethodSpec { name: "finalized".to_string(), doc: "Quorum atteint".to_string(), receiver: true, ret: Some("bool".to_string()), ..MethodSpec::default() }],
+        }],
+        impls: vec![
+            ImplSpec {
+                target: adapter.clone(),
+                trait_name: None,
+                methods: vec![
+                    constructor(&adapter, format!(
+                        "{{ Self {{ protocol: {}.to_string(), validators: {}, quorum: {}, approvals: 0 }} }}",
+                        arg(args, "protocol"), arg(args, "validators"), arg(args, "quorum")
+                    )),
+                    method("vote", "Enregistre le vote d'un validateur", true, vec![field("approve", "bool")], None,
+                           "{ if approve && self.approvals < self.validators { self.approvals += 1; } }".to_string()),
+                ],
+            },
+            ImplSpec {
+                target: adapter,
+                trait_name: Some("ConsensusAdapter".to_string()),
+                methods: vec![method("finalized", "Quorum atteint", false, Vec::new(), Some("bool"),
+                                     "{ self.validators > 0 && self.approvals as f64 >= (self.validators as f64 * self.quorum).ceil() }".to_string())],
+            },
+        ],
+        functions: Vec::new(),
+        tests: true,
+    }
+}
+
+fn token_module(name: &str, args: &TemplateArgs) -> ModuleSpec {
+    let token = format!("{}Token", type_name(name));
+    ModuleSpec {
+        name: name.to_string(),
+        doc: header(name, format!("Jeton {} à offre plafonnée", arg(args, "symbol"))),
+        structs: vec![StructSpec {
+            name: token.clone(),
+            doc: "Registre des soldes".to_string(),
+            fields: vec![
+                field("symbol", "String"),
+                field("decimals", "u8"),
+                field("total_supply", "u128"),
+                field("balances", "std::collections::HashMap<String, u128>"),
+            ],
+        }],
+        traits: Vec::new(),
+        impls: vec![ImplSpec {
+            target: token.clone(),
+            trait_name: None,
+            methods: vec![
+                constructor(&token, format!(
+                    "{{ Self {{ symbol: {}.to_string(), decimals: {}, ..Default::default() }} }}",
+                    arg(args, "symbol"), arg(args, "decimals")
+                )),
+                method("max_supply", "Offre maximale en plus petites unités", false, Vec::new(), Some("u128"),
+                       format!("{{ {}u128 * 10u128.pow(self.decimals as u32) }}", arg(args, "max_supply"))),
+                method("mint", "Crée `amount` unités pour `to` dans la limite de l'offre", true,
+                       vec![field("to", "&str"), field("amount", "u128")], Some("Result<(), String>"),
+                       "{ let supply = self.total_supply.checked_add(amount).filter(|s| *s <= self.max_supply()).ok_or_else(|| format!(\"Offre maximale dépassée pour {}\", self.symbol))?; self.total_supply = supply; *self.balances.entry(to.to_string()).or_insert(0) += amount; Ok(()) }".to_string()),
+                method("balance_of", "Solde d'un détenteur", false, vec![field("who", "&str")], Some("u128"),
+                       "{ self.balances.get(who).copied().unwrap_or(0) }".to_string()),
+            ],
+        }],
+        functions: Vec::new(),
+        tests: true,
+    }
+}
+
+fn oracle_client(name: &str, args: &TemplateArgs) -> ModuleSpec {
+    let client = format!("{}Client", type_name(name));
+    ModuleSpec {
+        name: name.to_string(),
+        doc: header(name, format!("Client d'oracle {} ({})", arg(args, "feed"), arg(args, "endpoint"))),
+        structs: vec![StructSpec {
+            name: client.clone(),
+            doc: "Dernier prix reçu et son horodatage".to_string(),
+            fields: vec![field("endpoint", "String"), field("feed", "String"), field("last_price", "Option<f64>"), field("last_update", "u64")],
+        }],
+        traits: Vec::new(),
+        impls: vec![ImplSpec {
+            target: client.clone(),
+            trait_name: None,
+            methods: vec![
+                constructor(&client, format!(
+                    "{{ Self {{ endpoint: {}.to_string(), feed: {}.to_string(), last_price: None, last_update: 0 }} }}",
+                    arg(args, "endpoint"), arg(args, "feed")
+                )),
+                method("update", "Enregistre un prix reçu à l'instant `at` (secondes)", true,
+                       vec![field("price", "f64"), field("at", "u64")], None,
+                       "{ if price.is_finite() && price > 0.0 { self.last_price = Some(price); self.last_update = at; } }".to_string()),
+                method("price", "Prix courant, `None` s'il est trop ancien", false, vec![field("now", "u64")], Some("Option<f64>"),
+                       format!("{{ if now.saturating_sub(self.last_update) <= {} {{ self.last_price }} else {{ None }} }}", arg(args, "max_staleness_secs"))),
+            ],
+        }],
+        functions: Vec::new(),
+        tests: true,
+    }
+}
+
+fn metric_collector(name: &str, args: &TemplateArgs) -> ModuleSpec {
+    let collector = format!("{}Collector", type_name(name));
+    ModuleSpec {
+        name: name.to_string(),
+        doc: header(name, format!("Collecteur de la métrique {}", arg(args, "metric"))),
+        structs: vec![StructSpec {
+            name: collector.clone(),
+            doc: "Échantillons de la fenêtre glissante".to_string(),
+            fields: vec![field("metric", "String"), field("samples", "std::collections::VecDeque<f64>")],
+        }],
+        traits: Vec::new(),
+        impls: vec![ImplSpec {
+            target: collector.clone(),
+            trait_name: None,
+            methods: vec![
+                constructor(&collector, format!("{{ Self {{ metric: {}.to_string(), samples: Default::default() }} }}", arg(args, "metric"))),
+                method("record", "Ajoute un échantillon en évinçant le plus ancien", true, vec![field("value", "f64")], None,
+                       format!("{{ if self.samples.len() >= {} {{ self.samples.pop_front(); }} self.samples.push_back(value); }}", arg(args, "window"))),
+                method("mean", "Moyenne de la fenêtre", false, Vec::new(), Some("f64"),
+                       "{ if self.samples.is_empty() { 0.0 } else { self.samples.iter().sum::<f64>() / self.samples.len() as f64 } }".to_string()),
+                method("alert", "Moyenne au-dessus du seuil", false, Vec::new(), Some("bool"),
+                       format!("{{ self.mean() > {} }}", arg(args, "alert_threshold"))),
+            ],
+        }],
+        functions: Vec::new(),
+        tests: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates_validate_arguments_against_schema() {
+        let registry = TemplateRegistry::builtin();
+        let token = registry.get("token_module").unwrap();
+        assert!(token.resolve(&TemplateArgs::new()).unwrap_err().contains("symbol"));
+
+        let args = token.parse_args(&["symbol=AUR".to_string(), "decimals=6".to_string()]).unwrap();
+        assert_eq!(args.get("decimals"), Some(&ParamValue::Int(6)));
+        let resolved = token.resolve(&args).unwrap();
+        assert_eq!(resolved.get("max_supply"), Some(&ParamValue::Int(1_000_000)));
+
+        let mut wrong = args.clone();
+        wrong.insert("decimals".to_string(), ParamValue::Str("six".to_string()));
+        assert!(token.resolve(&wrong).is_err());
+        wrong.insert("decimals".to_string(), ParamValue::Int(40));
+        assert!(token.resolve(&wrong).unwrap_err().contains("hors bornes"));
+        wrong.insert("owner".to_string(), ParamValue::Str("x".to_string()));
+        assert!(token.resolve(&wrong).unwrap_err().contains("inconnu"));
+        assert!(token.parse_args(&["decimals=six".to_string()]).is_err());
+
+        // Entier accepté pour un réel
+        let mut quorum = TemplateArgs::new();
+        quorum.insert("quorum".to_string(), ParamValue::Int(1));
+        let resolved = registry.get("consensus_adapter").unwrap().resolve(&quorum).unwrap();
+        assert_eq!(resolved.get("quorum"), Some(&ParamValue::Float(1.0)));
+    }
+
+    #[test]
+    fn every_builtin_template_renders_distinct_parseable_modules() {
+        let registry = TemplateRegistry::builtin();
+        let mut args: BTreeMap<&str, TemplateArgs> = BTreeMap::new();
+        args.entry("token_module").or_default().insert("symbol".to_string(), ParamValue::Str("AUR\"X".to_string()));
+        args.entry("oracle_client").or_default().insert("endpoint".to_string(), ParamValue::Str("https://oracle.example".to_string()));
+
+        let mut sources = Vec::new();
+        for name in registry.names() {
+            let spec = registry.instantiate(name, "sample_component", &args.get(name).cloned().unwrap_or_default()).unwrap();
+            let source = spec.render().unwrap();
+            syn::parse_str::<syn::File>(&source).unwrap();
+            sources.push(source);
+        }
+        assert_eq!(sources.len(), 5);
+        assert!(sources.iter().any(|s| s.contains("impl ConsensusAdapter for SampleComponentAdapter")));
+        assert!(sources.iter().any(|s| s.contains("fn mint(&mut self, to: &str, amount: u128)")));
+        sources.dedup();
+        assert_eq!(sources.len(), 5);
+        assert!(registry.instantiate("missing", "x", &TemplateArgs::new()).is_err());
+    }
+}
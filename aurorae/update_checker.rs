@@ -1,69 +1,619 @@
-extern crate reqwest;
-extern crate serde_json;
-use std::error::Error;
+//! AURORAE++ - update_checker.rs
+//!
+//! Vérification des mises à jour des dépendances: les dernières versions publiées sur
+//! crates.io sont comparées aux versions verrouillées dans Cargo.lock et classées selon leur
+//! impact semver (correctif, mineure, majeure). Les mises à jour admises par `updates.toml`
+//! sont appliquées dans une copie de l'espace de travail (Cargo.toml réécrit en conservant
+//! sa mise en forme, `cargo update --precise`), passées par la porte compilation/tests, puis
+//! soumises à approbation avec le diff du manifeste et du lockfile. Seule une proposition
+//! approuvée est recopiée dans l'espace de travail.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::Duration;
+
+use chrono::Utc;
 use reqwest::blocking::Client;
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use toml_edit::{DocumentMut, Item, Value};
+use uuid::Uuid;
+use walkdir::WalkDir;
+use tracing::{error, info, warn};
+
+use crate::alignment::ProposedAction;
+use crate::approvals::APPROVALS;
+use crate::generator::{parse_diagnostics, parse_test_results};
+use crate::security::{log_security_event, SecurityEvent};
+
+pub const DEFAULT_UPDATE_POLICY_PATH: &str = "updates.toml";
+pub const UPDATE_PROPOSALS_PATH: &str = "aurorae_state/update_proposals.json";
+/// Cycles entre deux vérifications (une requête crates.io par dépendance)
+pub const UPDATE_CHECK_INTERVAL_CYCLES: usize = 720;
+const CRATES_IO_API_URL: &str = "https://crates.io/api/v1";
+const USER_AGENT: &str = "AuroraeBot/1.0 (https://github.com/aurorae-core)";
+/// Politique d'exploration de crates.io: une requête par seconde au plus
+const CRATES_IO_DELAY: Duration = Duration::from_secs(1);
+/// Composant qui reprend les propositions approuvées
+const APPROVAL_ORIGIN: &str = "update_checker";
+/// Tables du manifeste examinées
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Impact semver d'une mise à jour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateImpact {
+    Patch,
+    Minor,
+    /// Incompatible au sens de cargo (`1.x → 2.0`, `0.3 → 0.4`)
+    Major,
+}
+
+/// Impact du passage de `current` à `latest`, selon les règles de compatibilité de cargo
+pub fn classify(current: &Version, latest: &Version) -> UpdateImpact {
+    let compatible = match (current.major, current.minor) {
+        (0, 0) => latest.major == 0 && latest.minor == 0 && latest.patch == current.patch,
+        (0, minor) => latest.major == 0 && latest.minor == minor,
+        (major, _) => latest.major == major,
+    };
+    if !compatible {
+        UpdateImpact::Major
+    } else if current.major > 0 && latest.minor != current.minor {
+        UpdateImpact::Minor
+    } else {
+        UpdateImpact::Patch
+    }
+}
+
+/// Politique de mise à jour (`updates.toml`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct UpdatePolicy {
+    /// Impacts proposés (les mises à jour majeures sont écartées par défaut)
+    pub allow: Vec<UpdateImpact>,
+    /// Crates jamais mises à jour
+    pub ignore: Vec<String>,
+    /// Versions préliminaires (`-alpha`, `-rc`…) proposées
+    pub prerelease: bool,
+    /// Mises à jour regroupées au plus dans une proposition
+    pub max_per_proposal: usize,
+    /// Exécute les tests en plus de la compilation
+    pub run_tests: bool,
+}
+
+impl Default for UpdatePolicy {
+    fn default() -> Self {
+        Self {
+            allow: vec![UpdateImpact::Patch, UpdateImpact::Minor],
+            ignore: Vec::new(),
+            prerelease: false,
+            max_per_proposal: 10,
+            run_tests: true,
+        }
+    }
+}
+
+impl UpdatePolicy {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Politique de mise à jour invalide: {}", e))
+    }
+
+    /// `updates.toml`, ou la politique par défaut s'il est absent ou invalide
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_UPDATE_POLICY_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: politique de mise à jour par défaut", e);
+            Self::default()
+        })
+    }
+
+    pub fn admits(&self, candidate: &UpdateCandidate) -> bool {
+        self.allow.contains(&candidate.impact) && !self.ignore.contains(&candidate.name)
+    }
+}
+
+/// Dépendance du registre déclarée dans le manifeste (chemins et dépôts git exclus)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    pub table: String,
+    /// Clé dans la table (diffère du nom en cas de `package = "…"`)
+    pub key: String,
+    pub name: String,
+    pub requirement: VersionReq,
+}
+
+pub fn manifest_dependencies(manifest: &str) -> Result<Vec<Dependency>, String> {
+    let doc: DocumentMut = manifest.parse().map_err(|e| format!("Cargo.toml invalide: {}", e))?;
+    let mut dependencies = Vec::new();
+    for table in DEPENDENCY_TABLES {
+        let Some(entries) = doc.get(table).and_then(Item::as_table_like) else { continue };
+        for (key, item) in entries.iter() {
+            let (version, name) = match item.as_str() {
+                Some(version) => (Some(version), key),
+                None => match item.as_table_like() {
+                    Some(spec) if spec.get("path").is_none() && spec.get("git").is_none() => (
+                        spec.get("version").and_then(Item::as_str),
+                        spec.get("package").and_then(Item::as_str).unwrap_or(key),
+                    ),
+                    _ => (None, key),
+                },
+            };
+            let Some(requirement) = version.and_then(|v| VersionReq::parse(v).ok()) else { continue };
+            dependencies.push(Dependency { table: table.to_string(), key: key.to_string(), name: name.to_string(), requirement });
+        }
+    }
+    Ok(dependencies)
+}
+
+/// Versions verrouillées de chaque paquet de Cargo.lock
+pub fn locked_versions(lockfile: &str) -> Result<BTreeMap<String, Vec<Version>>, String> {
+    let lock: toml::Value = toml::from_str(lockfile).map_err(|e| format!("Cargo.lock invalide: {}", e))?;
+    let mut versions: BTreeMap<String, Vec<Version>> = BTreeMap::new();
+    for package in lock.get("package").and_then(|p| p.as_array()).into_iter().flatten() {
+        let name = package.get("name").and_then(|n| n.as_str());
+        let version = package.get("version").and_then(|v| v.as_str()).and_then(|v| Version::parse(v).ok());
+        if let (Some(name), Some(version)) = (name, version) {
+            versions.entry(name.to_string()).or_default().push(version);
+        }
+    }
+    Ok(versions)
+}
+
+/// Changement d'un paquet entre deux lockfiles (ajout, retrait ou changement de version)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockChange {
+    pub name: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Diff des paquets de deux lockfiles, dépendances transitives comprises
+pub fn diff_lockfiles(old: &str, new: &str) -> Result<Vec<LockChange>, String> {
+    let old = locked_versions(old)?;
+    let new = locked_versions(new)?;
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    let mut changes = Vec::new();
+    for name in names {
+        let before: BTreeSet<&Version> = old.get(name).into_iter().flatten().collect();
+        let after: BTreeSet<&Version> = new.get(name).into_iter().flatten().collect();
+        let mut removed = before.difference(&after).map(|v| v.to_string());
+        let mut added = after.difference(&before).map(|v| v.to_string());
+        loop {
+            match (removed.next(), added.next()) {
+                (None, None) => break,
+                (from, to) => changes.push(LockChange { name: name.clone(), from, to }),
+            }
+        }
+    }
+    Ok(changes)
+}
+
+/// Mise à jour disponible pour une dépendance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateCandidate {
+    pub table: String,
+    pub key: String,
+    pub name: String,
+    pub requirement: String,
+    pub current: String,
+    pub latest: String,
+    pub impact: UpdateImpact,
+    /// Admise par l'exigence actuelle: seul Cargo.lock change
+    pub compatible: bool,
+}
+
+impl UpdateCandidate {
+    pub fn describe(&self) -> String {
+        format!("{} {} → {} ({:?})", self.name, self.current, self.latest, self.impact)
+    }
+}
+
+/// Réécrit les exigences des mises à jour incompatibles; commentaires et mise en forme conservés
+pub fn rewrite_manifest(manifest: &str, candidates: &[UpdateCandidate]) -> Result<String, String> {
+    let mut doc: DocumentMut = manifest.parse().map_err(|e| format!("Cargo.toml invalide: {}", e))?;
+    for candidate in candidates.iter().filter(|c| !c.compatible) {
+        let item = doc.get_mut(&candidate.table)
+            .and_then(Item::as_table_like_mut)
+            .and_then(|table| table.get_mut(&candidate.key))
+            .ok_or_else(|| format!("Dépendance {} absente de [{}]", candidate.key, candidate.table))?;
+        let value = match item.as_table_like_mut() {
+            Some(spec) => spec.get_mut("version").and_then(Item::as_value_mut),
+            None => item.as_value_mut(),
+        }
+        .ok_or_else(|| format!("Version de {} introuvable", candidate.key))?;
+        let decor = value.decor().clone();
+        *value = Value::from(candidate.latest.as_str());
+        *value.decor_mut() = decor;
+    }
+    Ok(doc.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateVersionsResponse {
+    versions: Vec<CrateVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateVersion {
+    num: String,
+    yanked: bool,
+}
+
+/// Versions publiées sur crates.io
+pub struct CratesIndex {
+    client: Client,
+    api_url: String,
+}
+
+impl CratesIndex {
+    pub fn new() -> Result<Self, String> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent(USER_AGENT)
+            .build()
+            .map_err(|e| format!("Erreur client HTTP: {}", e))?;
+        Ok(Self { client, api_url: CRATES_IO_API_URL.to_string() })
+    }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct GitHubRelease {
-    pub tag_name: String, // Dernière version du dépôt
-    pub name: String,     // Nom de la version
-    pub body: Option<String>, // Notes de mise à jour
-    pub html_url: String, // URL de la release
+    /// Dernière version non retirée (préliminaires comprises si `prerelease`)
+    pub fn latest(&self, name: &str, prerelease: bool) -> Result<Option<Version>, String> {
+        std::thread::sleep(CRATES_IO_DELAY);
+        let url = format!("{}/crates/{}/versions", self.api_url, name);
+        let response: CrateVersionsResponse = self.client.get(&url)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("Erreur de requête {}: {}", url, e))?
+            .json()
+            .map_err(|e| format!("Erreur parsing JSON {}: {}", url, e))?;
+        Ok(response.versions.into_iter()
+            .filter(|v| !v.yanked)
+            .filter_map(|v| Version::parse(&v.num).ok())
+            .filter(|v| prerelease || v.pre.is_empty())
+            .max())
+    }
+}
+
+/// Mises à jour disponibles pour les dépendances de l'espace de travail, filtrées par la politique
+pub fn find_updates(workspace: &Path, index: &CratesIndex, policy: &UpdatePolicy) -> Result<Vec<UpdateCandidate>, String> {
+    let manifest = read(&workspace.join("Cargo.toml"))?;
+    let locked = locked_versions(&read(&workspace.join("Cargo.lock"))?)?;
+    let mut candidates = Vec::new();
+    for dependency in manifest_dependencies(&manifest)? {
+        if policy.ignore.contains(&dependency.name) {
+            continue;
+        }
+        let current = locked.get(&dependency.name)
+            .and_then(|versions| versions.iter().filter(|v| dependency.requirement.matches(v)).max());
+        let Some(current) = current else { continue };
+        let latest = match index.latest(&dependency.name, policy.prerelease) {
+            Ok(Some(latest)) if latest > *current => latest,
+            Ok(_) => continue,
+            Err(e) => {
+                warn!("⚠️ {}", e);
+                continue;
+            }
+        };
+        let candidate = UpdateCandidate {
+            table: dependency.table,
+            key: dependency.key,
+            name: dependency.name,
+            requirement: dependency.requirement.to_string(),
+            current: current.to_string(),
+            impact: classify(current, &latest),
+            compatible: dependency.requirement.matches(&latest),
+            latest: latest.to_string(),
+        };
+        if policy.admits(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    Ok(candidates)
+}
+
+/// Résultat de la porte compilation/tests dans la copie de l'espace de travail
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GateResult {
+    pub compiled: bool,
+    pub tests_passed: usize,
+    pub tests_failed: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl GateResult {
+    pub fn passed(&self) -> bool {
+        self.compiled && self.tests_failed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    /// En attente d'approbation
+    Pending,
+    /// Écartée par la porte compilation/tests
+    GateFailed,
+    Applied,
+    /// Manifeste modifié depuis la proposition: à recalculer
+    Stale,
+}
+
+/// Changement proposé de Cargo.toml et Cargo.lock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateProposal {
+    pub id: Uuid,
+    pub created_at: String,
+    pub candidates: Vec<UpdateCandidate>,
+    /// Manifeste et lockfile d'origine, pour détecter une modification concurrente
+    pub base_manifest: String,
+    pub base_lockfile: String,
+    pub manifest: String,
+    pub lockfile: String,
+    pub manifest_diff: String,
+    pub lock_changes: Vec<LockChange>,
+    pub gate: GateResult,
+    pub status: ProposalStatus,
 }
 
-#[derive(Debug)]
+impl UpdateProposal {
+    pub fn summary(&self) -> String {
+        self.candidates.iter().map(UpdateCandidate::describe).collect::<Vec<_>>().join(", ")
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))
+}
+
+fn cargo(scratch: &Path, target_dir: &Path, args: &[&str]) -> Result<Output, String> {
+    Command::new("cargo")
+        .args(args)
+        .arg("--manifest-path")
+        .arg(scratch.join("Cargo.toml"))
+        .env("CARGO_TARGET_DIR", target_dir)
+        .output()
+        .map_err(|e| format!("Exécution de cargo impossible: {}", e))
+}
+
+/// Copie du manifeste, du lockfile et des sources compilées de l'espace de travail
+fn prepare_scratch(workspace: &Path, scratch: &Path) -> Result<(), String> {
+    create_dir_all(scratch).map_err(|e| format!("Erreur création {}: {}", scratch.display(), e))?;
+    for entry in ["Cargo.toml", "Cargo.lock"] {
+        fs::copy(workspace.join(entry), scratch.join(entry)).map_err(|e| format!("Erreur copie {}: {}", entry, e))?;
+    }
+    for dir in ["aurorae", "tests", "benches"].iter().map(|d| workspace.join(d)).filter(|d| d.is_dir()) {
+        for file in WalkDir::new(&dir).into_iter().filter_map(Result::ok).filter(|f| f.file_type().is_file()) {
+            let target = scratch.join(file.path().strip_prefix(workspace).map_err(|e| e.to_string())?);
+            if let Some(parent) = target.parent() {
+                create_dir_all(parent).map_err(|e| format!("Erreur création {}: {}", parent.display(), e))?;
+            }
+            fs::copy(file.path(), &target).map_err(|e| format!("Erreur copie {}: {}", target.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Applique les mises à jour dans une copie de l'espace de travail et les passe par la porte
+pub fn prepare_proposal(workspace: &Path, candidates: Vec<UpdateCandidate>, policy: &UpdatePolicy) -> Result<UpdateProposal, String> {
+    let base_manifest = read(&workspace.join("Cargo.toml"))?;
+    let base_lockfile = read(&workspace.join("Cargo.lock"))?;
+    let manifest = rewrite_manifest(&base_manifest, &candidates)?;
+    let scratch = std::env::temp_dir().join(format!("aurorae-update-{}", Uuid::new_v4()));
+    let target_dir = workspace.join("target").join("update");
+    let result = prepare_scratch(workspace, &scratch).and_then(|_| {
+        fs::write(scratch.join("Cargo.toml"), &manifest).map_err(|e| format!("Erreur écriture Cargo.toml: {}", e))?;
+        for candidate in &candidates {
+            let spec = format!("{}@{}", candidate.name, candidate.current);
+            let output = cargo(&scratch, &target_dir, &["update", "-p", &spec, "--precise", &candidate.latest])?;
+            if !output.status.success() {
+                return Err(format!("cargo update {} impossible: {}", spec, String::from_utf8_lossy(&output.stderr).trim()));
+            }
+        }
+        let gate = run_gate(&scratch, &target_dir, policy)?;
+        Ok((read(&scratch.join("Cargo.lock"))?, gate))
+    });
+    fs::remove_dir_all(&scratch).ok();
+    let (lockfile, gate) = result?;
+
+    let manifest_diff = TextDiff::from_lines(&base_manifest, &manifest)
+        .unified_diff()
+        .context_radius(2)
+        .header("a/Cargo.toml", "b/Cargo.toml")
+        .to_string();
+    let status = if gate.passed() { ProposalStatus::Pending } else { ProposalStatus::GateFailed };
+    Ok(UpdateProposal {
+        id: Uuid::new_v4(),
+        created_at: Utc::now().to_rfc3339(),
+        lock_changes: diff_lockfiles(&base_lockfile, &lockfile)?,
+        candidates,
+        base_manifest,
+        base_lockfile,
+        manifest,
+        lockfile,
+        manifest_diff,
+        gate,
+        status,
+    })
+}
+
+fn run_gate(scratch: &Path, target_dir: &Path, policy: &UpdatePolicy) -> Result<GateResult, String> {
+    let check = cargo(scratch, target_dir, &["check", "--all-targets", "--message-format=json"])?;
+    let errors: Vec<String> = parse_diagnostics(&String::from_utf8_lossy(&check.stdout))
+        .into_iter()
+        .filter(|d| d.level == "error")
+        .map(|d| d.message)
+        .collect();
+    let mut gate = GateResult { compiled: check.status.success(), errors, ..GateResult::default() };
+    if gate.compiled && policy.run_tests {
+        let test = cargo(scratch, target_dir, &["test"])?;
+        let (passed, failed) = parse_test_results(&String::from_utf8_lossy(&test.stdout));
+        gate.tests_passed = passed;
+        gate.tests_failed = failed;
+        if !test.status.success() && gate.tests_failed.is_empty() {
+            gate.tests_failed.push("échec de la suite de tests".to_string());
+        }
+    }
+    Ok(gate)
+}
+
+/// Vérification périodique des dépendances et application des propositions approuvées
 pub struct UpdateChecker {
-    pub repo_owner: String, // Propriétaire du repo GitHub
-    pub repo_name: String,  // Nom du repo GitHub
-    pub current_version: String, // Version actuelle
+    pub workspace: PathBuf,
+    pub policy: UpdatePolicy,
+    pub proposals: Vec<UpdateProposal>,
+    state_path: PathBuf,
 }
 
 impl UpdateChecker {
-    // Crée un nouvel UpdateChecker
-    pub fn new(repo_owner: &str, repo_name: &str, current_version: &str) -> Self {
-        UpdateChecker {
-            repo_owner: repo_owner.to_string(),
-            repo_name: repo_name.to_string(),
-            current_version: current_version.to_string(),
-        }
-    }
-
-    // Fonction pour vérifier la dernière version disponible sur GitHub
-    pub fn check_for_updates(&self) -> Result<(), Box<dyn Error>> {
-        let url = format!(
-            "https://api.github.com/repos/{}/releases/latest",
-            self.repo_owner
-        );
-
-        // Utilisation de reqwest pour envoyer une requête GET
-        let client = Client::new();
-        let response = client
-            .get(&url)
-            .header("User-Agent", "Aurorae++ Update Checker")
-            .send()?;
-
-        // Vérification du statut de la requête
-        if !response.status().is_success() {
-            return Err("Erreur lors de la récupération des informations de mise à jour.".into());
-        }
-
-        // Parsing de la réponse JSON
-        let release: GitHubRelease = response.json()?;
-
-        // Comparer la version distante avec la version locale
-        if release.tag_name != self.current_version {
-            println!(
-                "[AURORAE++] Nouvelle version disponible : {} (Votre version : {})",
-                release.tag_name, self.current_version
-            );
-            println!("Détails : {}\n", release.body.unwrap_or("Pas de détails.".to_string()));
-            println!("Mise à jour disponible sur : {}", release.html_url);
+    pub fn new(workspace: &Path) -> Self {
+        let state_path = PathBuf::from(UPDATE_PROPOSALS_PATH);
+        let proposals = File::open(&state_path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+        Self { workspace: workspace.to_path_buf(), policy: UpdatePolicy::load_or_default(), proposals, state_path }
+    }
+
+    /// Cherche des mises à jour et soumet à approbation celles qui passent la porte;
+    /// retourne l'identifiant de la proposition mise en attente
+    pub fn check(&mut self) -> Result<Option<Uuid>, String> {
+        let index = CratesIndex::new()?;
+        let mut candidates = find_updates(&self.workspace, &index, &self.policy)?;
+        candidates.truncate(self.policy.max_per_proposal);
+        if candidates.is_empty() {
+            info!("📦 Dépendances à jour");
+            return Ok(None);
+        }
+        // Une proposition identique en attente n'est pas recalculée
+        let pending = self.proposals.iter()
+            .any(|p| p.status == ProposalStatus::Pending && p.candidates == candidates);
+        if pending {
+            return Ok(None);
+        }
+
+        let proposal = prepare_proposal(&self.workspace, candidates, &self.policy)?;
+        let id = proposal.id;
+        let summary = proposal.summary();
+        if proposal.status == ProposalStatus::GateFailed {
+            error!("⛔ Mise à jour écartée par la porte ({}): {:?} {:?}",
+                     summary, proposal.gate.errors, proposal.gate.tests_failed);
         } else {
-            println!("[AURORAE++] Vous utilisez la dernière version !");
+            info!("📦 Mise à jour proposée: {} ({} paquet(s) du lockfile modifié(s))", summary, proposal.lock_changes.len());
+            let action = ProposedAction::new("update_dependencies")
+                .with("proposal", &id.to_string())
+                .with("crates", &summary);
+            APPROVALS.write().park_action(APPROVAL_ORIGIN, &action, "dependency-update");
+        }
+        let passed = proposal.status == ProposalStatus::Pending;
+        self.proposals.push(proposal);
+        self.save();
+        Ok(passed.then_some(id))
+    }
+
+    /// Recopie dans l'espace de travail les propositions approuvées; une proposition dont le
+    /// manifeste ou le lockfile a changé entre-temps est marquée périmée
+    pub fn apply_approved(&mut self) -> Vec<Uuid> {
+        let approved = APPROVALS.write().take_approved_actions(APPROVAL_ORIGIN);
+        let mut applied = Vec::new();
+        for request in approved {
+            let Some(id) = request.attributes.get("proposal").and_then(|id| Uuid::parse_str(id).ok()) else { continue };
+            let Some(proposal) = self.proposals.iter_mut().find(|p| p.id == id && p.status == ProposalStatus::Pending) else { continue };
+            let manifest_path = self.workspace.join("Cargo.toml");
+            let lock_path = self.workspace.join("Cargo.lock");
+            let unchanged = read(&manifest_path).ok().as_deref() == Some(proposal.base_manifest.as_str())
+                && read(&lock_path).ok().as_deref() == Some(proposal.base_lockfile.as_str());
+            if !unchanged {
+                warn!("⚠️ Proposition {} périmée: manifeste modifié depuis", id);
+                proposal.status = ProposalStatus::Stale;
+                continue;
+            }
+            let written = fs::write(&manifest_path, &proposal.manifest)
+                .and_then(|_| fs::write(&lock_path, &proposal.lockfile));
+            if let Err(e) = written {
+                error!("❌ Mise à jour {} non appliquée: {}", id, e);
+                continue;
+            }
+            for path in [&manifest_path, &lock_path] {
+                log_security_event(SecurityEvent::FileModified { module: "update_checker".to_string(), path: path.display().to_string() });
+            }
+            info!("✅ Mise à jour appliquée: {}", proposal.summary());
+            proposal.status = ProposalStatus::Applied;
+            applied.push(id);
         }
+        if !applied.is_empty() {
+            self.save();
+        }
+        applied
+    }
 
-        Ok(())
+    /// 💾 Sauvegarde des propositions
+    pub fn save(&self) {
+        if let Some(dir) = self.state_path.parent() {
+            create_dir_all(dir).ok();
+        }
+        if let Ok(file) = File::create(&self.state_path) {
+            let _ = serde_json::to_writer_pretty(BufWriter::new(file), &self.proposals);
+        }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn classifies_updates_with_cargo_compatibility_rules() {
+        assert_eq!(classify(&v("1.2.3"), &v("1.2.9")), UpdateImpact::Patch);
+        assert_eq!(classify(&v("1.2.3"), &v("1.4.0")), UpdateImpact::Minor);
+        assert_eq!(classify(&v("1.2.3"), &v("2.0.0")), UpdateImpact::Major);
+        assert_eq!(classify(&v("0.3.1"), &v("0.3.7")), UpdateImpact::Patch);
+        assert_eq!(classify(&v("0.3.1"), &v("0.4.0")), UpdateImpact::Major);
+        assert_eq!(classify(&v("0.0.1"), &v("0.0.2")), UpdateImpact::Major);
+
+        let policy = UpdatePolicy::from_toml("allow = [\"patch\"]\nignore = [\"tokio\"]").unwrap();
+        let candidate = |name: &str, impact| UpdateCandidate {
+            table: "dependencies".into(), key: name.into(), name: name.into(), requirement: "^1".into(),
+            current: "1.0.0".into(), latest: "1.0.1".into(), impact, compatible: true,
+        };
+        assert!(policy.admits(&candidate("serde", UpdateImpact::Patch)));
+        assert!(!policy.admits(&candidate("serde", UpdateImpact::Minor)));
+        assert!(!policy.admits(&candidate("tokio", UpdateImpact::Patch)));
+    }
+
+    #[test]
+    fn rewrites_manifest_requirements_and_diffs_lockfiles() {
+        let manifest = "[dependencies]\nsemver = \"0.9\"          # Versions\nrand = { version = \"0.8\", features = [\"small_rng\"] }\nlocal = { path = \"../local\" }\n\n[dev-dependencies.tempfile]\nversion = \"3.8\"\n";
+        let dependencies = manifest_dependencies(manifest).unwrap();
+        let names: Vec<&str> = dependencies.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["semver", "rand", "tempfile"]);
+
+        let candidates: Vec<UpdateCandidate> = [("dependencies", "semver", "1.0.20", false), ("dependencies", "rand", "0.8.5", true)]
+            .iter()
+            .map(|(table, name, latest, compatible)| UpdateCandidate {
+                table: table.to_string(), key: name.to_string(), name: name.to_string(), requirement: String::new(),
+                current: String::new(), latest: latest.to_string(), impact: UpdateImpact::Major, compatible: *compatible,
+            })
+            .collect();
+        let rewritten = rewrite_manifest(manifest, &candidates).unwrap();
+        assert!(rewritten.contains("semver = \"1.0.20\"          # Versions"));
+        assert!(rewritten.contains("rand = { version = \"0.8\", features = [\"small_rng\"] }"));
+
+        let old = "version = 3\n\n[[package]]\nname = \"semver\"\nversion = \"0.9.0\"\n\n[[package]]\nname = \"semver-parser\"\nversion = \"0.7.0\"\n";
+        let new = "version = 3\n\n[[package]]\nname = \"semver\"\nversion = \"1.0.20\"\n";
+        let changes = diff_lockfiles(old, new).unwrap();
+        assert_eq!(changes, vec![
+            LockChange { name: "semver".into(), from: Some("0.9.0".into()), to: Some("1.0.20".into()) },
+            LockChange { name: "semver-parser".into(), from: Some("0.7.0".into()), to: None },
+        ]);
+    }
+}
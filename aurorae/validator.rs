@@ -1,10 +1,34 @@
-use uuid::Uuid;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::Arc;
+
 use chrono::Utc;
-use rand::Rng;
+use ethers::types::{Address, Signature, H256};
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use walkdir::WalkDir;
+use tracing::{error, info, warn};
+
+use crate::alignment::{PolicyDecision, ProposedAction, SharedAlignment};
+use crate::currency::{Asset, AssetLedger};
+use crate::keystore::Signer;
+
+/// Manifeste des empreintes généré et signé à la compilation (`--sign-integrity-manifest`)
+pub const INTEGRITY_MANIFEST_PATH: &str = "aurorae_state/integrity_manifest.json";
+/// Adresse attendue du signataire du manifeste; sans elle, seule la cohérence de la signature est vérifiée
+pub const INTEGRITY_SIGNER_ENV: &str = "AURORAE_INTEGRITY_SIGNER";
+/// Répertoires et fichiers couverts par le manifeste, relatifs à la racine du dépôt
+const MANIFEST_ROOTS: [(&str, &str); 3] = [("aurorae", "rs"), ("contracts", "sol"), ("Cargo.toml", "toml")];
+pub const DEFAULT_VALIDATION_PATH: &str = "validation.toml";
 
 // Fonction pour valider un code ou une action du système
 pub fn validate_operation(operation_type: &str, content: &str) -> Result<ValidationResult, String> {
-    println!("[AURORAE++] 🔄 Validation de l'opération: {}", operation_type);
+    info!("🔄 Validation de l'opération: {}", operation_type);
     
     // Simuler une validation basée sur des critères de sécurité
     let valid = !content.contains("unsafe") && !content.contains("std::mem::transmute");
@@ -23,10 +47,10 @@ pub fn validate_operation(operation_type: &str, content: &str) -> Result<Validat
     };
     
     if valid {
-        println!("[AURORAE++] ✅ Opération validée: {}", operation_type);
+        info!("✅ Opération validée: {}", operation_type);
         Ok(result)
     } else {
-        println!("[AURORAE++] ⛔ Opération rejetée: {}", operation_type);
+        error!("⛔ Opération rejetée: {}", operation_type);
         Err("Validation échouée: code potentiellement dangereux détecté".to_string())
     }
 }
@@ -40,50 +64,854 @@ pub struct ValidationResult {
     pub reasons: Vec<String>,
 }
 
-// Fonction pour vérifier l'intégrité d'un système ou d'un composant
+// Fonction pour vérifier l'intégrité d'un système ou d'un composant:
+// "core" couvre tout le manifeste, un nom de module son seul fichier source
 pub fn check_integrity(component_name: &str) -> IntegrityResult {
-    println!("[AURORAE++] 🛡️ Vérification d'intégrité pour: {}", component_name);
-    
-    // Simuler une vérification d'intégrité
-    let mut rng = rand::thread_rng();
-    let integrity_score = 0.85 + (rng.gen::<f32>() * 0.15); // 85-100%
-    
-    let status = if integrity_score > 0.95 {
-        IntegrityStatus::Optimal
-    } else if integrity_score > 0.8 {
-        IntegrityStatus::Good
-    } else if integrity_score > 0.6 {
-        IntegrityStatus::Warning
-    } else {
-        IntegrityStatus::Compromised
-    };
-    
-    let result = IntegrityResult {
-        component: component_name.to_string(),
-        status,
-        integrity_score,
-        timestamp: Utc::now().to_rfc3339(),
+    info!("🛡️ Vérification d'intégrité pour: {}", component_name);
+    let trusted = std::env::var(INTEGRITY_SIGNER_ENV).ok().and_then(|a| a.parse::<Address>().ok());
+    let result = match IntegrityManifest::load(INTEGRITY_MANIFEST_PATH) {
+        Ok(manifest) => manifest.check(Path::new("."), component_name, trusted),
+        Err(e) => IntegrityResult::unverified(component_name, e),
     };
-    
-    println!("[AURORAE++] 🔍 Intégrité de {}: {:?} ({:.1}%)", 
-             component_name, result.status, result.integrity_score * 100.0);
-    
+
+    info!("🔍 Intégrité de {}: {:?} ({:.1}%) - {}",
+             component_name, result.status, result.integrity_score * 100.0, result.summary());
+    for divergence in &result.diverged {
+        error!("⛔ {:?}: {}", divergence.kind, divergence.path);
+    }
+
     result
 }
 
 // Énumération pour représenter les états d'intégrité
 #[derive(Debug, Clone, PartialEq)]
 pub enum IntegrityStatus {
+    /// Aucun écart et signataire conforme à `AURORAE_INTEGRITY_SIGNER`
     Optimal,
+    /// Aucun écart, signature valide mais signataire non épinglé
     Good,
+    /// Manifeste absent, non signé ou ne couvrant pas le composant
     Warning,
+    /// Fichiers divergents ou signature invalide
     Compromised,
 }
 
+/// Nature d'un écart entre le manifeste et le disque
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DivergenceKind {
+    Modified,
+    Missing,
+    Unexpected,
+}
+
+/// Fichier dont l'empreinte ne correspond plus au manifeste
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileDivergence {
+    pub path: String,
+    pub kind: DivergenceKind,
+}
+
 // Structure pour représenter le résultat d'une vérification d'intégrité
+#[derive(Debug, Clone)]
 pub struct IntegrityResult {
     pub component: String,
     pub status: IntegrityStatus,
     pub integrity_score: f32,
     pub timestamp: String,
+    /// Fichiers divergents, chemin exact relatif à la racine
+    pub diverged: Vec<FileDivergence>,
+    /// Raison d'un statut dégradé (manifeste absent, signature invalide…)
+    pub details: Option<String>,
+}
+
+impl IntegrityResult {
+    fn unverified(component: &str, details: String) -> Self {
+        Self {
+            component: component.to_string(),
+            status: IntegrityStatus::Warning,
+            integrity_score: 0.0,
+            timestamp: Utc::now().to_rfc3339(),
+            diverged: Vec::new(),
+            details: Some(details),
+        }
+    }
+
+    /// Vrai si aucune divergence ni signature invalide n'a été détectée
+    pub fn is_intact(&self) -> bool {
+        self.status != IntegrityStatus::Compromised
+    }
+
+    /// Résumé lisible: fichiers divergents ou raison du statut
+    pub fn summary(&self) -> String {
+        if !self.diverged.is_empty() {
+            let files: Vec<String> = self.diverged.iter()
+                .map(|d| format!("{} ({:?})", d.path, d.kind))
+                .collect();
+            format!("{} fichier(s) divergent(s): {}", files.len(), files.join(", "))
+        } else if let Some(details) = &self.details {
+            details.clone()
+        } else {
+            "conforme au manifeste signé".to_string()
+        }
+    }
+}
+
+/// Empreintes SHA-256 des sources, signées EIP-191 par la clé du système
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityManifest {
+    pub generated_at: String,
+    /// Chemin relatif (séparateurs `/`) -> empreinte hexadécimale
+    pub files: BTreeMap<String, String>,
+    pub signer: Option<Address>,
+    pub signature: Option<String>,
+}
+
+impl IntegrityManifest {
+    /// Calcule les empreintes des fichiers couverts sous `root`
+    pub fn generate(root: &Path) -> Result<Self, String> {
+        Ok(Self {
+            generated_at: Utc::now().to_rfc3339(),
+            files: hash_tree(root)?,
+            signer: None,
+            signature: None,
+        })
+    }
+
+    /// Message canonique signé: une ligne `chemin:empreinte` par fichier, dans l'ordre
+    pub fn canonical_message(&self) -> String {
+        let mut message = String::from("AURORAE++ manifeste d'intégrité\n");
+        for (path, hash) in &self.files {
+            message.push_str(&format!("{}:{}\n", path, hash));
+        }
+        message
+    }
+
+    /// Signe le manifeste (`personal_sign`) avec la clé du système
+    pub async fn sign(&mut self, signer: &dyn Signer) -> Result<(), String> {
+        let signature = signer.sign_message(self.canonical_message().as_bytes()).await?;
+        self.signer = Some(signer.address());
+        self.signature = Some(format!("0x{}", signature));
+        Ok(())
+    }
+
+    /// Vérifie la signature; si `trusted` est fourni, le signataire doit lui correspondre
+    pub fn verify_signature(&self, trusted: Option<Address>) -> Result<Address, String> {
+        let signature = self.signature.as_ref().ok_or_else(|| "Manifeste non signé".to_string())?;
+        let signature = signature.parse::<Signature>()
+            .map_err(|e| format!("Signature du manifeste illisible: {}", e))?;
+        let recovered = signature.recover(self.canonical_message())
+            .map_err(|e| format!("Signature du manifeste invalide: {}", e))?;
+        if self.signer != Some(recovered) {
+            return Err(format!("Signature du manifeste émise par {:?}, pas par le signataire déclaré", recovered));
+        }
+        match trusted {
+            Some(trusted) if trusted != recovered => {
+                Err(format!("Manifeste signé par {:?} au lieu de {:?}", recovered, trusted))
+            }
+            _ => Ok(recovered),
+        }
+    }
+
+    /// Compare le manifeste aux fichiers présents sous `root`
+    pub fn diff(&self, root: &Path) -> Result<Vec<FileDivergence>, String> {
+        let current = hash_tree(root)?;
+        let mut divergences = Vec::new();
+        for (path, hash) in &self.files {
+            match current.get(path) {
+                None => divergences.push(FileDivergence { path: path.clone(), kind: DivergenceKind::Missing }),
+                Some(actual) if actual != hash => {
+                    divergences.push(FileDivergence { path: path.clone(), kind: DivergenceKind::Modified })
+                }
+                Some(_) => {}
+            }
+        }
+        for path in current.keys().filter(|path| !self.files.contains_key(*path)) {
+            divergences.push(FileDivergence { path: path.clone(), kind: DivergenceKind::Unexpected });
+        }
+        divergences.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(divergences)
+    }
+
+    /// Vérifie la signature puis les empreintes des fichiers du composant
+    pub fn check(&self, root: &Path, component: &str, trusted: Option<Address>) -> IntegrityResult {
+        if let Err(e) = self.verify_signature(trusted) {
+            let status = if self.signature.is_none() { IntegrityStatus::Warning } else { IntegrityStatus::Compromised };
+            return IntegrityResult { status, ..IntegrityResult::unverified(component, e) };
+        }
+        let diverged = match self.diff(root) {
+            Ok(diverged) => diverged,
+            Err(e) => return IntegrityResult::unverified(component, e),
+        };
+
+        let in_scope = |path: &str| component_covers(component, path);
+        let total = self.files.keys().filter(|path| in_scope(path)).count();
+        let diverged: Vec<FileDivergence> = diverged.into_iter().filter(|d| in_scope(&d.path)).collect();
+        if total == 0 && diverged.is_empty() {
+            return IntegrityResult::unverified(component, format!("{} absent du manifeste", component));
+        }
+
+        let intact = self.files.keys()
+            .filter(|path| in_scope(path) && !diverged.iter().any(|d| &d.path == *path))
+            .count();
+        let unexpected = diverged.iter().filter(|d| d.kind == DivergenceKind::Unexpected).count();
+        let status = if !diverged.is_empty() {
+            IntegrityStatus::Compromised
+        } else if trusted.is_some() {
+            IntegrityStatus::Optimal
+        } else {
+            IntegrityStatus::Good
+        };
+        IntegrityResult {
+            component: component.to_string(),
+            status,
+            integrity_score: intact as f32 / (total + unexpected).max(1) as f32,
+            timestamp: Utc::now().to_rfc3339(),
+            diverged,
+            details: None,
+        }
+    }
+
+    /// 💾 Sauvegarde sur disque
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        if let Some(dir) = Path::new(path).parent() {
+            create_dir_all(dir).map_err(|e| format!("Erreur création {}: {}", dir.display(), e))?;
+        }
+        let file = File::create(path).map_err(|e| format!("Erreur écriture {}: {}", path, e))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .map_err(|e| format!("Erreur sérialisation du manifeste: {}", e))
+    }
+
+    /// 📥 Chargement depuis disque
+    pub fn load(path: &str) -> Result<Self, String> {
+        let file = File::open(path).map_err(|_| format!("Manifeste d'intégrité absent ({})", path))?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Manifeste d'intégrité illisible: {}", e))
+    }
+}
+
+/// Génère et signe le manifeste de `root` puis l'enregistre dans `path`
+pub async fn sign_integrity_manifest(root: &Path, path: &str, signer: &dyn Signer) -> Result<IntegrityManifest, String> {
+    let mut manifest = IntegrityManifest::generate(root)?;
+    manifest.sign(signer).await?;
+    manifest.save(path)?;
+    info!("🔏 Manifeste d'intégrité signé par {:?}: {} fichiers", signer.address(), manifest.files.len());
+    Ok(manifest)
+}
+
+/// "core" couvre tous les fichiers; sinon `aurorae/<module>.rs` ou `aurorae/<module>/…`
+fn component_covers(component: &str, path: &str) -> bool {
+    component == "core"
+        || path == format!("aurorae/{}.rs", component)
+        || path.starts_with(&format!("aurorae/{}/", component))
+}
+
+fn hash_tree(root: &Path) -> Result<BTreeMap<String, String>, String> {
+    let mut files = BTreeMap::new();
+    for (entry, extension) in MANIFEST_ROOTS {
+        for file in WalkDir::new(root.join(entry)).into_iter().filter_map(Result::ok) {
+            let path = file.path();
+            if !file.file_type().is_file() || path.extension().and_then(|e| e.to_str()) != Some(extension) {
+                continue;
+            }
+            let content = std::fs::read(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+            let relative = path.strip_prefix(root).unwrap_or(path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(relative, hex::encode(Sha256::digest(&content)));
+        }
+    }
+    Ok(files)
+}
+
+// ====================== PIPELINE DE VALIDATION DES TRANSACTIONS ======================
+
+/// Transaction du grand livre interne (ou d'un bloc de chaîne simulée), signée EIP-191
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerTransaction {
+    pub id: Uuid,
+    pub from: Address,
+    pub to: Address,
+    pub asset: Asset,
+    pub amount: f64,
+    /// Frais prélevés dans le même actif et brûlés
+    pub fee: f64,
+    pub nonce: u64,
+    pub signature: Option<String>,
+}
+
+impl LedgerTransaction {
+    pub fn new(from: Address, to: Address, asset: Asset, amount: f64, nonce: u64) -> Self {
+        Self { id: Uuid::new_v4(), from, to, asset, amount, fee: 0.0, nonce, signature: None }
+    }
+
+    pub fn with_fee(mut self, fee: f64) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Message canonique signé: tous les champs sauf la signature
+    pub fn signing_message(&self) -> String {
+        format!(
+            "AURORAE++ transaction\n{}\n{:?}\n{:?}\n{}\n{}\n{}\n{}\n",
+            self.id, self.from, self.to, self.asset, self.amount, self.fee, self.nonce
+        )
+    }
+
+    /// Signe la transaction (`personal_sign`); le signataire doit être l'expéditeur
+    pub async fn sign(&mut self, signer: &dyn Signer) -> Result<(), String> {
+        if signer.address() != self.from {
+            return Err(format!("Signataire {:?} différent de l'expéditeur {:?}", signer.address(), self.from));
+        }
+        let signature = signer.sign_message(self.signing_message().as_bytes()).await?;
+        self.signature = Some(format!("0x{}", signature));
+        Ok(())
+    }
+}
+
+/// Soldes multi-actifs et nonces par compte, contre lesquels les transactions sont validées
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountLedger {
+    pub accounts: HashMap<Address, AssetLedger>,
+    pub nonces: HashMap<Address, u64>,
+}
+
+impl AccountLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn credit(&mut self, account: Address, asset: Asset, value: f64) {
+        self.accounts.entry(account).or_default().credit(asset, value);
+    }
+
+    pub fn balance(&self, account: &Address, asset: &Asset) -> f64 {
+        self.accounts.get(account).map(|ledger| ledger.balance(asset)).unwrap_or(0.0)
+    }
+
+    /// Prochain nonce attendu du compte
+    pub fn nonce(&self, account: &Address) -> u64 {
+        self.nonces.get(account).copied().unwrap_or(0)
+    }
+
+    /// Applique une transaction validée: débit du montant et des frais, crédit du destinataire
+    pub fn apply(&mut self, tx: &LedgerTransaction) -> Result<(), String> {
+        self.accounts.entry(tx.from).or_default().debit(&tx.asset, tx.amount + tx.fee)?;
+        self.credit(tx.to, tx.asset.clone(), tx.amount);
+        self.nonces.insert(tx.from, tx.nonce + 1);
+        Ok(())
+    }
+}
+
+/// Étape du pipeline, dans l'ordre d'exécution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationStage {
+    Structure,
+    Signature,
+    Ledger,
+    Rules,
+    Policy,
+}
+
+/// Raison typée d'un rejet
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejectionReason {
+    Malformed(String),
+    MissingSignature,
+    InvalidSignature(String),
+    SignerMismatch { expected: Address, recovered: Address },
+    NonceMismatch { expected: u64, actual: u64 },
+    InsufficientBalance { asset: Asset, available: f64, required: f64 },
+    Rule { rule: String, reason: String },
+    PolicyVeto { rule: String },
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectionReason::Malformed(reason) => write!(f, "transaction mal formée: {}", reason),
+            RejectionReason::MissingSignature => write!(f, "signature absente"),
+            RejectionReason::InvalidSignature(reason) => write!(f, "signature invalide: {}", reason),
+            RejectionReason::SignerMismatch { expected, recovered } => write!(f, "signée par {:?} au lieu de {:?}", recovered, expected),
+            RejectionReason::NonceMismatch { expected, actual } => write!(f, "nonce {} au lieu de {}", actual, expected),
+            RejectionReason::InsufficientBalance { asset, available, required } => {
+                write!(f, "solde {} insuffisant: {:.6} < {:.6}", asset, available, required)
+            }
+            RejectionReason::Rule { rule, reason } => write!(f, "règle {}: {}", rule, reason),
+            RejectionReason::PolicyVeto { rule } => write!(f, "veto de la politique d'alignement: {}", rule),
+        }
+    }
+}
+
+/// Verdict du pipeline pour une transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationOutcome {
+    Accepted,
+    /// Toutes les raisons relevées par la première étape en échec
+    Rejected { stage: ValidationStage, reasons: Vec<RejectionReason> },
+    /// Politique `require-approval`: en attente d'une approbation humaine
+    PendingApproval { rule: String },
+}
+
+impl ValidationOutcome {
+    pub fn is_accepted(&self) -> bool {
+        *self == ValidationOutcome::Accepted
+    }
+}
+
+/// Règle additionnelle du pipeline
+pub trait ValidationRule: Send + Sync {
+    fn name(&self) -> &str;
+    fn check(&self, tx: &LedgerTransaction, ledger: &AccountLedger) -> Result<(), String>;
+}
+
+/// Montant maximal d'une transaction dans un actif
+pub struct AmountLimit {
+    pub asset: Asset,
+    pub max: f64,
+}
+
+impl ValidationRule for AmountLimit {
+    fn name(&self) -> &str {
+        "amount-limit"
+    }
+
+    fn check(&self, tx: &LedgerTransaction, _ledger: &AccountLedger) -> Result<(), String> {
+        if tx.asset == self.asset && tx.amount > self.max {
+            return Err(format!("{} {} au-delà du plafond {}", tx.amount, tx.asset, self.max));
+        }
+        Ok(())
+    }
+}
+
+/// Adresses exclues, en émission comme en réception
+pub struct Blocklist {
+    pub addresses: HashSet<Address>,
+}
+
+impl ValidationRule for Blocklist {
+    fn name(&self) -> &str {
+        "blocklist"
+    }
+
+    fn check(&self, tx: &LedgerTransaction, _ledger: &AccountLedger) -> Result<(), String> {
+        match [tx.from, tx.to].into_iter().find(|address| self.addresses.contains(address)) {
+            Some(address) => Err(format!("adresse bloquée {:?}", address)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Fichier `validation.toml`: règles chargées dans le pipeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct ValidationConfig {
+    /// Plafond par transaction, par symbole d'actif (AURA, ETH, USDC…)
+    pub max_amount: BTreeMap<String, f64>,
+    pub blocked_addresses: Vec<Address>,
+    pub max_transactions_per_block: usize,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self { max_amount: BTreeMap::new(), blocked_addresses: Vec::new(), max_transactions_per_block: 500 }
+    }
+}
+
+impl ValidationConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path, e))?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| format!("Configuration de validation invalide: {}", e))?;
+        if let Some((symbol, max)) = config.max_amount.iter().find(|(_, max)| !max.is_finite() || **max <= 0.0) {
+            return Err(format!("Plafond invalide pour {}: {}", symbol, max));
+        }
+        if config.max_transactions_per_block == 0 {
+            return Err("max-transactions-per-block doit être positif".to_string());
+        }
+        Ok(config)
+    }
+
+    /// `validation.toml`, ou aucune règle additionnelle
+    pub fn load_or_default() -> Self {
+        Self::load(DEFAULT_VALIDATION_PATH).unwrap_or_else(|e| {
+            warn!("⚠️ {}: règles de validation par défaut", e);
+            Self::default()
+        })
+    }
+}
+
+/// Actif désigné par son symbole dans la configuration
+fn asset_from_symbol(symbol: &str) -> Asset {
+    match symbol.to_uppercase().as_str() {
+        "AURA" => Asset::Aura,
+        "ETH" => Asset::Eth,
+        "USDC" | "USDT" | "DAI" => Asset::Stable(symbol.to_uppercase()),
+        other => Asset::Native(other.to_string()),
+    }
+}
+
+/// Bloc candidat: celui d'une chaîne simulée, validé transaction par transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockCandidate {
+    pub number: u64,
+    pub parent_hash: H256,
+    pub timestamp: i64,
+    pub producer: Address,
+    pub transactions: Vec<LedgerTransaction>,
+}
+
+impl BlockCandidate {
+    /// Empreinte de l'en-tête et des identifiants de transactions
+    pub fn hash(&self) -> H256 {
+        let mut preimage = self.number.to_be_bytes().to_vec();
+        preimage.extend_from_slice(self.parent_hash.as_bytes());
+        preimage.extend_from_slice(&self.timestamp.to_be_bytes());
+        preimage.extend_from_slice(self.producer.as_bytes());
+        for tx in &self.transactions {
+            preimage.extend_from_slice(tx.id.as_bytes());
+        }
+        H256::from(keccak256(preimage))
+    }
+}
+
+/// Résultat de la validation d'un bloc
+#[derive(Debug, Clone)]
+pub struct BlockValidation {
+    /// Défauts de l'en-tête ou de l'ensemble des transactions
+    pub header: Vec<RejectionReason>,
+    pub transactions: Vec<(Uuid, ValidationOutcome)>,
+    /// Grand livre après application des transactions acceptées
+    pub state: AccountLedger,
+}
+
+impl BlockValidation {
+    /// Bloc valide: en-tête conforme et toutes ses transactions acceptées
+    pub fn is_valid(&self) -> bool {
+        self.header.is_empty() && self.transactions.iter().all(|(_, outcome)| outcome.is_accepted())
+    }
+}
+
+/// Pipeline de validation: structure, signature, grand livre (solde, nonce), règles
+/// additionnelles puis politique d'alignement
+#[derive(Clone, Default)]
+pub struct TransactionValidator {
+    rules: Vec<Arc<dyn ValidationRule>>,
+    alignment: Option<SharedAlignment>,
+    max_transactions_per_block: usize,
+}
+
+impl TransactionValidator {
+    pub fn new() -> Self {
+        Self { max_transactions_per_block: ValidationConfig::default().max_transactions_per_block, ..Self::default() }
+    }
+
+    /// Pipeline muni des règles de `validation.toml`
+    pub fn from_config(config: &ValidationConfig) -> Self {
+        let mut validator = Self { max_transactions_per_block: config.max_transactions_per_block, ..Self::default() };
+        for (symbol, max) in &config.max_amount {
+            validator = validator.with_rule(Arc::new(AmountLimit { asset: asset_from_symbol(symbol), max: *max }));
+        }
+        if !config.blocked_addresses.is_empty() {
+            validator = validator.with_rule(Arc::new(Blocklist { addresses: config.blocked_addresses.iter().copied().collect() }));
+        }
+        validator
+    }
+
+    pub fn with_rule(mut self, rule: Arc<dyn ValidationRule>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Les transactions internes passent par la politique d'alignement (action `transfer`)
+    pub fn with_alignment(mut self, alignment: SharedAlignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Valide une transaction du grand livre interne, politique d'alignement comprise
+    pub fn validate(&self, tx: &LedgerTransaction, ledger: &AccountLedger) -> ValidationOutcome {
+        self.run(tx, ledger, true)
+    }
+
+    /// Valide puis applique une transaction interne au grand livre
+    pub fn submit(&self, tx: &LedgerTransaction, ledger: &mut AccountLedger) -> ValidationOutcome {
+        let outcome = self.validate(tx, ledger);
+        if outcome.is_accepted() {
+            if let Err(e) = ledger.apply(tx) {
+                return ValidationOutcome::Rejected { stage: ValidationStage::Ledger, reasons: vec![RejectionReason::Malformed(e)] };
+            }
+        }
+        outcome
+    }
+
+    /// Valide un bloc de chaîne simulée à la suite de `parent`; les transactions sont appliquées
+    /// dans l'ordre sur une copie de `ledger`. La politique d'alignement, qui encadre les actions
+    /// du système, ne s'applique pas aux blocs.
+    pub fn validate_block(&self, block: &BlockCandidate, parent: Option<&BlockCandidate>, ledger: &AccountLedger) -> BlockValidation {
+        let mut header = Vec::new();
+        if let Some(parent) = parent {
+            if block.number != parent.number + 1 {
+                header.push(RejectionReason::Malformed(format!("bloc {} après le bloc {}", block.number, parent.number)));
+            }
+            if block.parent_hash != parent.hash() {
+                header.push(RejectionReason::Malformed(format!("parent {:?} inconnu", block.parent_hash)));
+            }
+            if block.timestamp < parent.timestamp {
+                header.push(RejectionReason::Malformed("horodatage antérieur au parent".to_string()));
+            }
+        }
+        if block.transactions.len() > self.max_transactions_per_block {
+            header.push(RejectionReason::Malformed(format!(
+                "{} transactions (maximum {})",
+                block.transactions.len(),
+                self.max_transactions_per_block
+            )));
+        }
+        let mut seen = HashSet::new();
+        if let Some(tx) = block.transactions.iter().find(|tx| !seen.insert(tx.id)) {
+            header.push(RejectionReason::Malformed(format!("transaction {} en double", tx.id)));
+        }
+
+        let mut state = ledger.clone();
+        let transactions = block
+            .transactions
+            .iter()
+            .map(|tx| {
+                let mut outcome = self.run(tx, &state, false);
+                if outcome.is_accepted() {
+                    if let Err(e) = state.apply(tx) {
+                        outcome = ValidationOutcome::Rejected { stage: ValidationStage::Ledger, reasons: vec![RejectionReason::Malformed(e)] };
+                    }
+                }
+                (tx.id, outcome)
+            })
+            .collect();
+        BlockValidation { header, transactions, state }
+    }
+
+    fn run(&self, tx: &LedgerTransaction, ledger: &AccountLedger, with_policy: bool) -> ValidationOutcome {
+        let stages: [(ValidationStage, Vec<RejectionReason>); 4] = [
+            (ValidationStage::Structure, Self::structure(tx)),
+            (ValidationStage::Signature, Self::signature(tx)),
+            (ValidationStage::Ledger, Self::ledger(tx, ledger)),
+            (ValidationStage::Rules, self.rule_set(tx, ledger)),
+        ];
+        // Chaque étape suppose les précédentes satisfaites: seule la première en échec est rapportée
+        for (stage, reasons) in stages {
+            if !reasons.is_empty() {
+                return ValidationOutcome::Rejected { stage, reasons };
+            }
+        }
+        match &self.alignment {
+            Some(alignment) if with_policy => {
+                let action = ProposedAction::new("transfer")
+                    .with("asset", &tx.asset.symbol())
+                    .with("amount", &tx.amount.to_string())
+                    .with("from", &format!("{:?}", tx.from))
+                    .with("to", &format!("{:?}", tx.to));
+                match alignment.write().gate(tx.id, &action) {
+                    PolicyDecision::Allow => ValidationOutcome::Accepted,
+                    PolicyDecision::Veto { rule } => {
+                        ValidationOutcome::Rejected { stage: ValidationStage::Policy, reasons: vec![RejectionReason::PolicyVeto { rule }] }
+                    }
+                    PolicyDecision::RequiresApproval { rule } => ValidationOutcome::PendingApproval { rule },
+                }
+            }
+            _ => ValidationOutcome::Accepted,
+        }
+    }
+
+    fn structure(tx: &LedgerTransaction) -> Vec<RejectionReason> {
+        let mut reasons = Vec::new();
+        if !tx.amount.is_finite() || tx.amount <= 0.0 {
+            reasons.push(RejectionReason::Malformed(format!("montant {}", tx.amount)));
+        }
+        if !tx.fee.is_finite() || tx.fee < 0.0 {
+            reasons.push(RejectionReason::Malformed(format!("frais {}", tx.fee)));
+        }
+        if tx.from == Address::zero() || tx.to == Address::zero() {
+            reasons.push(RejectionReason::Malformed("adresse nulle".to_string()));
+        }
+        if tx.from == tx.to {
+            reasons.push(RejectionReason::Malformed("expéditeur et destinataire identiques".to_string()));
+        }
+        if tx.signature.is_none() {
+            reasons.push(RejectionReason::MissingSignature);
+        }
+        reasons
+    }
+
+    fn signature(tx: &LedgerTransaction) -> Vec<RejectionReason> {
+        let Some(signature) = &tx.signature else { return vec![RejectionReason::MissingSignature] };
+        let recovered = signature
+            .parse::<Signature>()
+            .map_err(|e| e.to_string())
+            .and_then(|signature| signature.recover(tx.signing_message()).map_err(|e| e.to_string()));
+        match recovered {
+            Ok(recovered) if recovered == tx.from => Vec::new(),
+            Ok(recovered) => vec![RejectionReason::SignerMismatch { expected: tx.from, recovered }],
+            Err(e) => vec![RejectionReason::InvalidSignature(e)],
+        }
+    }
+
+    fn ledger(tx: &LedgerTransaction, ledger: &AccountLedger) -> Vec<RejectionReason> {
+        let mut reasons = Vec::new();
+        let expected = ledger.nonce(&tx.from);
+        if tx.nonce != expected {
+            reasons.push(RejectionReason::NonceMismatch { expected, actual: tx.nonce });
+        }
+        let available = ledger.balance(&tx.from, &tx.asset);
+        if available < tx.amount + tx.fee {
+            reasons.push(RejectionReason::InsufficientBalance { asset: tx.asset.clone(), available, required: tx.amount + tx.fee });
+        }
+        reasons
+    }
+
+    fn rule_set(&self, tx: &LedgerTransaction, ledger: &AccountLedger) -> Vec<RejectionReason> {
+        self.rules
+            .iter()
+            .filter_map(|rule| rule.check(tx, ledger).err().map(|reason| RejectionReason::Rule { rule: rule.name().to_string(), reason }))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keystore::WalletSigner;
+
+    fn fixture() -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("aurorae-integrity-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("aurorae")).unwrap();
+        std::fs::write(root.join("aurorae/economy.rs"), "pub fn tick() {}").unwrap();
+        std::fs::write(root.join("aurorae/guardian.rs"), "pub fn watch() {}").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]").unwrap();
+        root
+    }
+
+    #[tokio::test]
+    async fn signed_manifest_reports_diverging_files() {
+        let root = fixture();
+        let signer = WalletSigner::random("test");
+        let mut manifest = IntegrityManifest::generate(&root).unwrap();
+        manifest.sign(&signer).await.unwrap();
+        assert_eq!(manifest.verify_signature(Some(signer.address())).unwrap(), signer.address());
+        assert_eq!(manifest.check(&root, "core", Some(signer.address())).status, IntegrityStatus::Optimal);
+
+        std::fs::write(root.join("aurorae/economy.rs"), "pub fn tick() { drain() }").unwrap();
+        std::fs::write(root.join("aurorae/backdoor.rs"), "").unwrap();
+        let core = manifest.check(&root, "core", None);
+        assert_eq!(core.status, IntegrityStatus::Compromised);
+        assert_eq!(core.diverged, vec![
+            FileDivergence { path: "aurorae/backdoor.rs".to_string(), kind: DivergenceKind::Unexpected },
+            FileDivergence { path: "aurorae/economy.rs".to_string(), kind: DivergenceKind::Modified },
+        ]);
+        assert_eq!(manifest.check(&root, "guardian", None).status, IntegrityStatus::Good);
+        assert!(!manifest.check(&root, "economy", None).is_intact());
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[tokio::test]
+    async fn tampered_manifest_fails_signature_check() {
+        let root = fixture();
+        let signer = WalletSigner::random("test");
+        let mut manifest = IntegrityManifest::generate(&root).unwrap();
+        manifest.sign(&signer).await.unwrap();
+
+        // Réécrire une empreinte pour masquer une modification invalide la signature
+        manifest.files.insert("aurorae/economy.rs".to_string(), "00".repeat(32));
+        assert!(manifest.verify_signature(None).is_err());
+        assert_eq!(manifest.check(&root, "core", None).status, IntegrityStatus::Compromised);
+
+        let other = WalletSigner::random("autre");
+        let mut manifest = IntegrityManifest::generate(&root).unwrap();
+        manifest.sign(&other).await.unwrap();
+        assert!(manifest.verify_signature(Some(signer.address())).is_err());
+        assert_eq!(IntegrityManifest::generate(&root).unwrap().check(&root, "core", None).status, IntegrityStatus::Warning);
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    async fn signed(signer: &WalletSigner, to: Address, amount: f64, nonce: u64) -> LedgerTransaction {
+        let mut tx = LedgerTransaction::new(signer.address(), to, Asset::Aura, amount, nonce);
+        tx.sign(signer).await.unwrap();
+        tx
+    }
+
+    #[tokio::test]
+    async fn pipeline_reports_the_first_failing_stage() {
+        let alice = WalletSigner::random("alice");
+        let bob = WalletSigner::random("bob");
+        let mut ledger = AccountLedger::new();
+        ledger.credit(alice.address(), Asset::Aura, 100.0);
+        let alignment = crate::alignment::AlignmentSystem::new().shared();
+        alignment.write().policy = crate::alignment::Policy::parse("deny transfer if amount > 50").unwrap();
+        let validator = TransactionValidator::from_config(&ValidationConfig::from_toml("[max-amount]\nAURA = 80.0").unwrap())
+            .with_alignment(alignment);
+
+        let unsigned = LedgerTransaction::new(alice.address(), bob.address(), Asset::Aura, 10.0, 0);
+        assert_eq!(
+            validator.validate(&unsigned, &ledger),
+            ValidationOutcome::Rejected { stage: ValidationStage::Structure, reasons: vec![RejectionReason::MissingSignature] }
+        );
+        let mut forged = signed(&alice, bob.address(), 10.0, 0).await;
+        forged.amount = 99.0;
+        assert!(matches!(
+            validator.validate(&forged, &ledger),
+            ValidationOutcome::Rejected { stage: ValidationStage::Signature, .. }
+        ));
+        let outcome = validator.validate(&signed(&alice, bob.address(), 150.0, 1).await, &ledger);
+        assert!(matches!(&outcome, ValidationOutcome::Rejected { stage: ValidationStage::Ledger, reasons } if reasons.len() == 2));
+        assert!(matches!(
+            validator.validate(&signed(&alice, bob.address(), 90.0, 0).await, &ledger),
+            ValidationOutcome::Rejected { stage: ValidationStage::Rules, .. }
+        ));
+        assert!(matches!(
+            validator.validate(&signed(&alice, bob.address(), 60.0, 0).await, &ledger),
+            ValidationOutcome::Rejected { stage: ValidationStage::Policy, .. }
+        ));
+
+        assert!(validator.submit(&signed(&alice, bob.address(), 30.0, 0).await, &mut ledger).is_accepted());
+        assert_eq!(ledger.balance(&bob.address(), &Asset::Aura), 30.0);
+        assert_eq!(ledger.nonce(&alice.address()), 1);
+    }
+
+    #[tokio::test]
+    async fn block_validation_applies_transactions_in_order() {
+        let alice = WalletSigner::random("alice");
+        let bob = WalletSigner::random("bob");
+        let mut ledger = AccountLedger::new();
+        ledger.credit(alice.address(), Asset::Aura, 50.0);
+        let validator = TransactionValidator::new();
+
+        let genesis = BlockCandidate { number: 0, parent_hash: H256::zero(), timestamp: 0, producer: alice.address(), transactions: Vec::new() };
+        let block = BlockCandidate {
+            number: 1,
+            parent_hash: genesis.hash(),
+            timestamp: 10,
+            producer: alice.address(),
+            transactions: vec![
+                signed(&alice, bob.address(), 30.0, 0).await,
+                signed(&bob, alice.address(), 20.0, 0).await,
+                // Solde de départ suffisant, mais plus après les deux transferts précédents
+                signed(&alice, bob.address(), 45.0, 1).await,
+            ],
+        };
+        let validation = validator.validate_block(&block, Some(&genesis), &ledger);
+        assert!(validation.header.is_empty());
+        assert!(validation.transactions[0].1.is_accepted());
+        assert!(validation.transactions[1].1.is_accepted());
+        assert!(matches!(validation.transactions[2].1, ValidationOutcome::Rejected { stage: ValidationStage::Ledger, .. }));
+        assert!(!validation.is_valid());
+        assert_eq!(validation.state.balance(&alice.address(), &Asset::Aura), 40.0);
+
+        let orphan = BlockCandidate { parent_hash: H256::repeat_byte(1), transactions: Vec::new(), ..block };
+        assert_eq!(validator.validate_block(&orphan, Some(&genesis), &ledger).header.len(), 1);
+    }
 }
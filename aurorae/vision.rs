@@ -2,15 +2,24 @@
 //!
 //! Module de projection évolutive. Permet à l'IA d'émettre des visions stratégiques de son propre futur,
 //! d'établir des roadmaps vivantes et de guider ses mutations selon des objectifs vitaux.
+//!
+//! La feuille de route est persistée dans `aurorae_state/vision.json`, exportable pour revue
+//! humaine (Markdown, diagramme de Gantt Mermaid, JSON) et importable (JSON ou TOML) afin
+//! que les opérateurs injectent ou corrigent des objectifs. Le démon relit le fichier avant
+//! chaque modification: un import fait depuis la CLI n'est pas écrasé par sa copie en mémoire.
 
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use std::fs::{create_dir_all, File};
 use std::io::{BufReader, BufWriter};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::{info, warn};
+
+use crate::reproduction::AuroraInstance;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ObjectiveType {
     ImproveLearning,
     OptimizeEconomy,
@@ -21,7 +30,7 @@ pub enum ObjectiveType {
     MaximizeAutonomy,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FutureProjection {
     pub id: Uuid,
     pub created_at: String,
@@ -31,23 +40,131 @@ pub struct FutureProjection {
     pub rationale: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct VisionEngine {
-    pub projections: Vec<FutureProjection>,
+impl ObjectiveType {
+    /// Capacités (modules) qu'au moins une instance active doit porter pour servir l'objectif
+    pub fn required_capabilities(&self) -> &'static [&'static str] {
+        match self {
+            ObjectiveType::ImproveLearning => &["learning", "intelligence", "neural_network", "knowledge"],
+            ObjectiveType::OptimizeEconomy => &["economy", "market_data", "staking", "alchemy"],
+            ObjectiveType::ExpandChains => &["blockchain_core", "deployer", "cross_chain", "explorer"],
+            ObjectiveType::RefactorSelf => &["code_evolution", "mutation", "refactor", "rust_analyzer"],
+            ObjectiveType::BuildEcosystem => &["nft_minter", "generator", "network_builder", "dream"],
+            ObjectiveType::SeekKnowledge => &["crawler", "knowledge", "pattern_extractor", "explorer"],
+            ObjectiveType::MaximizeAutonomy => &["autonomy", "brain", "reproduction", "guardian"],
+        }
+    }
+}
+
+/// Capacité manquante dans l'écosystème d'instances, classée par besoin pondéré
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapabilityGap {
+    pub capability: String,
+    /// Besoin (priorités des objectifs, plus forte à courte échéance) rapporté à la couverture
+    pub score: f32,
+    /// Instances actives portant la capacité
+    pub coverage: usize,
+    /// Instances attendues au vu du besoin
+    pub desired: usize,
+    pub justification: String,
+}
+
+/// Format d'export de la feuille de route, déduit de l'extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoadmapFormat {
+    Markdown,
+    Mermaid,
+    Json,
 }
 
-impl Default for VisionEngine {
-    fn default() -> Self {
-        Self { projections: Vec::new() }
+impl RoadmapFormat {
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("md") => Ok(Self::Markdown),
+            Some("mmd") | Some("mermaid") => Ok(Self::Mermaid),
+            Some("json") => Ok(Self::Json),
+            other => Err(format!("Format de feuille de route inconnu: {:?} (md, mmd, json)", other)),
+        }
     }
 }
 
+/// Objectif importé par un opérateur: sans `id` il est ajouté, sinon il remplace le sien
+#[derive(Debug, Clone, Deserialize)]
+pub struct ObjectiveInput {
+    pub id: Option<Uuid>,
+    pub target: ObjectiveType,
+    pub horizon_days: u32,
+    pub priority: u8,
+    pub rationale: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ObjectiveFile {
+    #[serde(default)]
+    objective: Vec<ObjectiveInput>,
+}
+
+/// Bilan d'un import
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub updated: usize,
+}
+
+/// Feuille de route persistée du système
+pub const VISION_PATH: &str = "aurorae_state/vision.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct VisionEngine {
+    pub projections: Vec<FutureProjection>,
+    /// Fichier de la feuille de route; sans chemin (tests) rien n'est persisté
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Date de modification du fichier à la dernière lecture ou écriture par ce processus
+    #[serde(skip)]
+    synced_mtime: Option<SystemTime>,
+}
+
 impl VisionEngine {
     pub fn new() -> Self {
-        Self::load().unwrap_or_default()
+        Self::open(VISION_PATH)
     }
 
-    pub fn add_projection(&mut self, target: ObjectiveType, horizon_days: u32, priority: u8, rationale: &str) {
+    /// Feuille de route rechargée depuis `path` s'il existe, et persistée à ce même chemin
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut engine = Self::load(&path).unwrap_or_default();
+        engine.synced_mtime = Self::mtime(&path);
+        engine.path = Some(path);
+        engine
+    }
+
+    fn mtime(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    /// Reprend le fichier s'il a été modifié par un autre processus (`--import-roadmap`):
+    /// sa version de chaque projection l'emporte, les projections absentes du fichier sont gardées
+    pub fn refresh(&mut self) {
+        let Some(path) = self.path.clone() else { return };
+        let mtime = Self::mtime(&path);
+        if mtime.is_none() || mtime == self.synced_mtime {
+            return;
+        }
+        let Some(disk) = Self::load(&path) else {
+            warn!("⚠️ Feuille de route {} illisible, copie en mémoire conservée", path.display());
+            return;
+        };
+        let local: Vec<FutureProjection> = self.projections.drain(..)
+            .filter(|p| !disk.projections.iter().any(|d| d.id == p.id))
+            .collect();
+        self.projections = disk.projections;
+        self.projections.extend(local);
+        self.synced_mtime = mtime;
+        info!("🔃 Feuille de route rechargée depuis {} ({} objectifs)", path.display(), self.projections.len());
+    }
+
+    pub fn add_projection(&mut self, target: ObjectiveType, horizon_days: u32, priority: u8, rationale: &str) -> Uuid {
+        self.refresh();
         let proj = FutureProjection {
             id: Uuid::new_v4(),
             created_at: Utc::now().to_rfc3339(),
@@ -57,17 +174,19 @@ impl VisionEngine {
             rationale: rationale.to_string(),
         };
 
-        println!(
-            "[AURORAE++] 🧐 Vision projetée : {:?} ({} jours) • Priorité {} → {}",
+        info!(
+            "🧐 Vision projetée : {:?} ({} jours) • Priorité {} → {}",
             proj.target, proj.horizon_days, proj.priority, proj.rationale
         );
 
+        let id = proj.id;
         self.projections.push(proj);
         self.save();  // Sauvegarder l'état après ajout
+        id
     }
 
     pub fn roadmap(&self) {
-        println!("[AURORAE++] 📍 ROADMAP STRATÉGIQUE EN COURS :");
+        info!("📍 ROADMAP STRATÉGIQUE EN COURS :");
         for proj in &self.projections {
             println!(
                 "- {:?} • Horizon: {}j • Priorité: {} • [{}]",
@@ -78,6 +197,7 @@ impl VisionEngine {
 
     /// 🔁 Révision automatique de la vision à chaque cycle.
     pub fn autorevise(&mut self) {
+        self.refresh();
         for proj in &mut self.projections {
             if proj.horizon_days > 0 {
                 proj.horizon_days -= 1;
@@ -90,8 +210,8 @@ impl VisionEngine {
         let after = self.projections.len();
 
         if before != after {
-            println!(
-                "[AURORAE++] 🔄 Révision des visions : {} expirées, {} restantes.",
+            info!(
+                "🔄 Révision des visions : {} expirées, {} restantes.",
                 before - after,
                 after
             );
@@ -100,22 +220,205 @@ impl VisionEngine {
         self.save();  // Sauvegarde après révision
     }
 
-    /// 💾 Sauvegarde automatique en JSON local
-    pub fn save(&self) {
-        let dir = Path::new("aurorae_state");
-        if create_dir_all(dir).is_ok() {
-            if let Ok(file) = File::create(dir.join("vision.json")) {
-                let writer = BufWriter::new(file);
-                if serde_json::to_writer_pretty(writer, &self).is_ok() {
-                    println!("[AURORAE++] 💾 VisionEngine sauvegardé.");
+    /// Compare les capacités portées par les instances actives à celles qu'exigent les
+    /// projections en cours; renvoie les capacités sous-couvertes, la plus urgente d'abord.
+    /// Sans projection, chaque objectif compte pour un besoin minimal.
+    pub fn identify_ecosystem_gaps(&self, instances: Vec<&AuroraInstance>) -> Vec<CapabilityGap> {
+        let defaults: Vec<FutureProjection>;
+        let projections: &[FutureProjection] = if self.projections.is_empty() {
+            defaults = [
+                ObjectiveType::ImproveLearning, ObjectiveType::OptimizeEconomy, ObjectiveType::ExpandChains,
+                ObjectiveType::RefactorSelf, ObjectiveType::BuildEcosystem, ObjectiveType::SeekKnowledge,
+                ObjectiveType::MaximizeAutonomy,
+            ]
+            .into_iter()
+            .map(|target| FutureProjection {
+                id: Uuid::nil(),
+                created_at: Utc::now().to_rfc3339(),
+                target,
+                horizon_days: 30,
+                priority: 1,
+                rationale: "objectif de base".to_string(),
+            })
+            .collect();
+            &defaults
+        } else {
+            &self.projections
+        };
+
+        // Besoin par capacité: priorité de chaque objectif, majorée quand l'échéance approche
+        let mut demand: Vec<(&'static str, f32, Vec<String>)> = Vec::new();
+        for proj in projections {
+            let weight = proj.priority.max(1) as f32 * (1.0 + 30.0 / (30.0 + proj.horizon_days as f32));
+            for capability in proj.target.required_capabilities() {
+                let reason = format!("{:?} (P{}, {} j)", proj.target, proj.priority, proj.horizon_days);
+                match demand.iter_mut().find(|(c, _, _)| c == capability) {
+                    Some((_, total, reasons)) => {
+                        *total += weight;
+                        if !reasons.contains(&reason) {
+                            reasons.push(reason);
+                        }
+                    }
+                    None => demand.push((capability, weight, vec![reason])),
                 }
             }
         }
+
+        let mut gaps: Vec<CapabilityGap> = demand
+            .into_iter()
+            .filter_map(|(capability, need, reasons)| {
+                let coverage = instances.iter().filter(|i| i.inherited_modules.iter().any(|m| m == capability)).count();
+                let desired = ((need / 20.0).ceil() as usize).max(1);
+                (coverage < desired).then(|| CapabilityGap {
+                    capability: capability.to_string(),
+                    score: need / (1.0 + coverage as f32),
+                    coverage,
+                    desired,
+                    justification: format!(
+                        "requis par {}; porté par {}/{} instance(s) active(s), {} attendue(s)",
+                        reasons.join(", "), coverage, instances.len(), desired
+                    ),
+                })
+            })
+            .collect();
+        gaps.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.capability.cmp(&b.capability)));
+
+        for gap in gaps.iter().take(3) {
+            info!("🧩 Capacité manquante: {} (score {:.1}) - {}", gap.capability, gap.score, gap.justification);
+        }
+        gaps
+    }
+
+    /// Projections par priorité décroissante puis par horizon
+    fn sorted(&self) -> Vec<&FutureProjection> {
+        let mut projections: Vec<&FutureProjection> = self.projections.iter().collect();
+        projections.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.horizon_days.cmp(&b.horizon_days)));
+        projections
+    }
+
+    /// Diagramme de Gantt Mermaid: une section par type d'objectif, échéance à `horizon_days`
+    pub fn to_mermaid(&self) -> String {
+        let today = Utc::now().date_naive();
+        let mut out = String::from("gantt\n    title Feuille de route AURORAE++\n    dateFormat YYYY-MM-DD\n");
+        let mut sections: Vec<String> = Vec::new();
+        for proj in self.sorted() {
+            let section = format!("{:?}", proj.target);
+            if !sections.contains(&section) {
+                sections.push(section);
+            }
+        }
+        for section in sections {
+            out.push_str(&format!("    section {}\n", section));
+            for proj in self.sorted().into_iter().filter(|p| format!("{:?}", p.target) == section) {
+                let label: String = proj.rationale.chars().map(|c| if matches!(c, ':' | '#' | ';') { ' ' } else { c }).collect();
+                let status = if proj.priority >= 8 { "crit, " } else { "" };
+                out.push_str(&format!(
+                    "    {} (P{}) :{}{}, {}, {}d\n",
+                    label.trim(), proj.priority, status, proj.id.simple(), today, proj.horizon_days.max(1)
+                ));
+            }
+        }
+        out
+    }
+
+    /// Tableau Markdown suivi du diagramme Mermaid, pour revue humaine
+    pub fn to_markdown(&self) -> String {
+        let today = Utc::now().date_naive();
+        let mut out = format!("# Feuille de route AURORAE++\n\nGénérée le {}.\n\n", Utc::now().to_rfc3339());
+        out.push_str("| Priorité | Objectif | Échéance | Justification | Identifiant |\n|---|---|---|---|---|\n");
+        for proj in self.sorted() {
+            out.push_str(&format!(
+                "| {} | {:?} | {} ({} j) | {} | `{}` |\n",
+                proj.priority,
+                proj.target,
+                today + Duration::days(proj.horizon_days as i64),
+                proj.horizon_days,
+                proj.rationale.replace('|', "\\|"),
+                proj.id
+            ));
+        }
+        out.push_str(&format!("\n```mermaid\n{}```\n", self.to_mermaid()));
+        out
     }
 
-    /// 📥 Chargement automatique depuis disque (si disponible)
-    pub fn load() -> Option<Self> {
-        let path = Path::new("aurorae_state/vision.json");
+    /// Exporte la feuille de route au format déduit de l'extension de `path`
+    pub fn export(&self, path: &Path) -> Result<(), String> {
+        let content = match RoadmapFormat::from_path(path)? {
+            RoadmapFormat::Markdown => self.to_markdown(),
+            RoadmapFormat::Mermaid => self.to_mermaid(),
+            RoadmapFormat::Json => serde_json::to_string_pretty(&self.projections)
+                .map_err(|e| format!("Sérialisation impossible: {}", e))?,
+        };
+        std::fs::write(path, content).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))?;
+        info!("🗺️ Feuille de route exportée ({} objectifs) vers {}", self.projections.len(), path.display());
+        Ok(())
+    }
+
+    /// Importe des objectifs d'opérateur: tableau JSON ou `[[objective]]` en TOML
+    pub fn import(&mut self, path: &Path) -> Result<ImportSummary, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+        let objectives: Vec<ObjectiveInput> = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str::<ObjectiveFile>(&content)
+                .map_err(|e| format!("Objectifs TOML invalides: {}", e))?
+                .objective,
+            _ => serde_json::from_str(&content).map_err(|e| format!("Objectifs JSON invalides: {}", e))?,
+        };
+        let summary = self.apply_objectives(objectives)?;
+        info!("🧭 Import de la feuille de route: {} ajouté(s), {} modifié(s)", summary.added, summary.updated);
+        Ok(summary)
+    }
+
+    fn apply_objectives(&mut self, objectives: Vec<ObjectiveInput>) -> Result<ImportSummary, String> {
+        if let Some(invalid) = objectives.iter().find(|o| o.priority > 10 || o.horizon_days == 0) {
+            return Err(format!("Objectif invalide ({}): priorité 0 à 10 et horizon non nul requis", invalid.rationale));
+        }
+        self.refresh();
+        let mut summary = ImportSummary::default();
+        for objective in objectives {
+            match objective.id.and_then(|id| self.projections.iter_mut().find(|p| p.id == id)) {
+                Some(existing) => {
+                    existing.target = objective.target;
+                    existing.horizon_days = objective.horizon_days;
+                    existing.priority = objective.priority;
+                    existing.rationale = objective.rationale;
+                    summary.updated += 1;
+                }
+                None => {
+                    self.projections.push(FutureProjection {
+                        id: objective.id.unwrap_or_else(Uuid::new_v4),
+                        created_at: Utc::now().to_rfc3339(),
+                        target: objective.target,
+                        horizon_days: objective.horizon_days,
+                        priority: objective.priority,
+                        rationale: objective.rationale,
+                    });
+                    summary.added += 1;
+                }
+            }
+        }
+        self.save();
+        Ok(summary)
+    }
+
+    /// 💾 Sauvegarde automatique en JSON local
+    pub fn save(&mut self) {
+        let Some(path) = self.path.clone() else { return };
+        if let Some(dir) = path.parent() {
+            if create_dir_all(dir).is_err() {
+                return;
+            }
+        }
+        if let Ok(file) = File::create(&path) {
+            let writer = BufWriter::new(file);
+            if serde_json::to_writer_pretty(writer, &self).is_ok() {
+                self.synced_mtime = Self::mtime(&path);
+                info!("💾 VisionEngine sauvegardé.");
+            }
+        }
+    }
+
+    /// 📥 Chargement depuis disque (si disponible)
+    fn load(path: &Path) -> Option<Self> {
         if let Ok(file) = File::open(path) {
             let reader = BufReader::new(file);
             serde_json::from_reader(reader).ok()  // Retourne l'option de la structure déserialisée
@@ -124,3 +427,124 @@ impl VisionEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projection(target: ObjectiveType, priority: u8, rationale: &str) -> FutureProjection {
+        FutureProjection {
+            id: Uuid::new_v4(),
+            created_at: Utc::now().to_rfc3339(),
+            target,
+            horizon_days: 30,
+            priority,
+            rationale: rationale.to_string(),
+        }
+    }
+
+    fn instance(modules: &[&str]) -> AuroraInstance {
+        AuroraInstance {
+            id: Uuid::new_v4(),
+            parent_id: None,
+            created_at: Utc::now().to_rfc3339(),
+            purpose: "test".to_string(),
+            inherited_modules: modules.iter().map(|m| m.to_string()).collect(),
+            generation: 1,
+            is_active: true,
+        }
+    }
+
+    #[test]
+    fn gaps_are_ranked_by_weighted_need_over_coverage() {
+        let mut urgent = projection(ObjectiveType::RefactorSelf, 9, "Refactoriser");
+        urgent.horizon_days = 3;
+        let vision = VisionEngine {
+            projections: vec![urgent, projection(ObjectiveType::ExpandChains, 2, "Étendre")],
+            ..VisionEngine::default()
+        };
+        let a = instance(&["mutation", "refactor", "blockchain_core"]);
+        let b = instance(&["deployer"]);
+
+        let gaps = vision.identify_ecosystem_gaps(vec![&a, &b]);
+        let names: Vec<&str> = gaps.iter().map(|g| g.capability.as_str()).collect();
+        assert_eq!(&names[..2], ["code_evolution", "rust_analyzer"]);
+        assert!(!names.contains(&"mutation"));
+        assert!(!names.contains(&"deployer"));
+        assert!(names.contains(&"cross_chain"));
+        assert!(gaps[0].justification.contains("RefactorSelf (P9, 3 j)"));
+
+        let idle = VisionEngine::default();
+        assert_eq!(idle.identify_ecosystem_gaps(Vec::new()).len(), 26);
+    }
+
+    #[test]
+    fn exports_markdown_and_mermaid_gantt() {
+        let vision = VisionEngine {
+            projections: vec![
+                projection(ObjectiveType::ExpandChains, 3, "Nouvelles chaînes: L2"),
+                projection(ObjectiveType::OptimizeEconomy, 9, "Stabiliser | la trésorerie"),
+            ],
+            ..VisionEngine::default()
+        };
+        let mermaid = vision.to_mermaid();
+        assert!(mermaid.starts_with("gantt"));
+        assert!(mermaid.contains("section OptimizeEconomy"));
+        assert!(mermaid.contains("Nouvelles chaînes  L2 (P3)"));
+        assert!(mermaid.contains(":crit, "));
+
+        let markdown = vision.to_markdown();
+        let economy = markdown.find("OptimizeEconomy").unwrap();
+        assert!(economy < markdown.find("ExpandChains").unwrap());
+        assert!(markdown.contains("Stabiliser \\| la trésorerie"));
+        assert!(RoadmapFormat::from_path(Path::new("roadmap.pdf")).is_err());
+    }
+
+    #[test]
+    fn operator_objectives_are_added_or_updated() {
+        let existing = projection(ObjectiveType::SeekKnowledge, 2, "Lire");
+        let id = existing.id;
+        let mut vision = VisionEngine { projections: vec![existing], ..VisionEngine::default() };
+        let file: ObjectiveFile = toml::from_str(&format!(
+            "[[objective]]\nid = \"{}\"\ntarget = \"SeekKnowledge\"\nhorizon_days = 5\npriority = 7\nrationale = \"Lire les EIP\"\n\n\
+             [[objective]]\ntarget = \"RefactorSelf\"\nhorizon_days = 14\npriority = 4\nrationale = \"Nettoyer\"\n",
+            id
+        )).unwrap();
+
+        let summary = vision.apply_objectives(file.objective).unwrap();
+        assert_eq!(summary, ImportSummary { added: 1, updated: 1 });
+        assert_eq!(vision.projections[0].priority, 7);
+        assert_eq!(vision.projections[0].rationale, "Lire les EIP");
+        assert_eq!(vision.projections.len(), 2);
+
+        let invalid: Vec<ObjectiveInput> = serde_json::from_str(
+            r#"[{ "target": "RefactorSelf", "horizon_days": 0, "priority": 4, "rationale": "x" }]"#,
+        ).unwrap();
+        assert!(vision.apply_objectives(invalid).is_err());
+    }
+
+    #[test]
+    fn daemon_picks_up_objectives_imported_by_the_cli() {
+        let dir = std::env::temp_dir().join(format!("aurorae-vision-{}", Uuid::new_v4()));
+        let path = dir.join("vision.json");
+        let mut daemon = VisionEngine::open(&path);
+        let own = daemon.add_projection(ObjectiveType::ExpandChains, 10, 3, "Étendre");
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let mut cli = VisionEngine::open(&path);
+        cli.apply_objectives(vec![ObjectiveInput {
+            id: None,
+            target: ObjectiveType::SeekKnowledge,
+            horizon_days: 5,
+            priority: 9,
+            rationale: "Lire les EIP".to_string(),
+        }]).unwrap();
+
+        daemon.autorevise();
+        assert_eq!(daemon.projections.len(), 2);
+        assert!(daemon.projections.iter().any(|p| p.rationale == "Lire les EIP" && p.horizon_days == 4));
+        assert_eq!(daemon.projections.iter().find(|p| p.id == own).unwrap().horizon_days, 9);
+        assert_eq!(VisionEngine::open(&path).projections.len(), 2);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
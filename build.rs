@@ -0,0 +1,11 @@
+// Génération du code gRPC des workers de calcul (proto/compute.proto); protoc est fourni par
+// protoc-bin-vendored pour ne pas dépendre d'une installation système
+fn main() {
+    if std::env::var_os("PROTOC").is_none() {
+        if let Ok(protoc) = protoc_bin_vendored::protoc_bin_path() {
+            std::env::set_var("PROTOC", protoc);
+        }
+    }
+    println!("cargo:rerun-if-changed=proto/compute.proto");
+    tonic_build::compile_protos("proto/compute.proto").expect("Génération du protocole de calcul impossible");
+}
@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// Pont lock-and-mint d'AURORAE++, un contrat par chaîne partagé par les deux sens d'une
+/// route. Aller: la chaîne source verrouille la valeur native (`lock`) et émet `MessageSent`;
+/// le relayeur autorisé exécute le message sur la destination, qui crédite un solde enveloppé
+/// propre à la chaîne d'origine. Retour: le détenteur brûle ce solde (`burn`), et l'exécution
+/// sur la chaîne d'origine déverrouille la valeur native. Chaque message n'est exécuté qu'une fois.
+contract AuroraeBridge {
+    address public relayer;
+    uint64 public nonce;
+    uint256 public totalLocked;
+    mapping(bytes32 => bool) public processed;
+    /// Solde enveloppé par chaîne d'origine de la valeur verrouillée
+    mapping(uint256 => mapping(address => uint256)) public wrappedBalanceOf;
+    mapping(uint256 => uint256) public totalWrapped;
+
+    event MessageSent(bytes32 indexed id, uint256 indexed destinationChainId, uint64 nonce, address sender, address recipient, uint256 amount, bool unlock);
+    event MessageExecuted(bytes32 indexed id, uint256 indexed sourceChainId, address recipient, uint256 amount);
+
+    constructor() {
+        relayer = msg.sender;
+    }
+
+    function messageId(
+        uint256 sourceChainId,
+        uint256 destinationChainId,
+        uint64 messageNonce,
+        address sender,
+        address recipient,
+        uint256 amount,
+        bool unlock
+    ) public pure returns (bytes32) {
+        return keccak256(abi.encode(sourceChainId, destinationChainId, messageNonce, sender, recipient, amount, unlock));
+    }
+
+    /// Verrouille la valeur envoyée; la destination créditera un solde enveloppé
+    function lock(uint256 destinationChainId, address recipient) external payable returns (bytes32 id) {
+        require(msg.value > 0, "Nothing to lock");
+        require(destinationChainId != block.chainid, "Same chain");
+        totalLocked += msg.value;
+        id = _send(destinationChainId, recipient, msg.value, false);
+    }
+
+    /// Brûle un solde enveloppé venu de `destinationChainId`; cette chaîne déverrouillera la valeur
+    function burn(uint256 destinationChainId, address recipient, uint256 amount) external returns (bytes32 id) {
+        require(amount > 0, "Nothing to burn");
+        require(wrappedBalanceOf[destinationChainId][msg.sender] >= amount, "Insufficient wrapped balance");
+        wrappedBalanceOf[destinationChainId][msg.sender] -= amount;
+        totalWrapped[destinationChainId] -= amount;
+        id = _send(destinationChainId, recipient, amount, true);
+    }
+
+    function execute(
+        bytes32 id,
+        uint256 sourceChainId,
+        uint64 messageNonce,
+        address sender,
+        address recipient,
+        uint256 amount,
+        bool unlock
+    ) external {
+        require(msg.sender == relayer, "Only relayer");
+        require(!processed[id], "Already processed");
+        require(id == messageId(sourceChainId, block.chainid, messageNonce, sender, recipient, amount, unlock), "Id mismatch");
+        processed[id] = true;
+        if (unlock) {
+            require(totalLocked >= amount, "Insufficient locked value");
+            totalLocked -= amount;
+            (bool sent, ) = recipient.call{value: amount}("");
+            require(sent, "Unlock failed");
+        } else {
+            totalWrapped[sourceChainId] += amount;
+            wrappedBalanceOf[sourceChainId][recipient] += amount;
+        }
+        emit MessageExecuted(id, sourceChainId, recipient, amount);
+    }
+
+    function _send(uint256 destinationChainId, address recipient, uint256 amount, bool unlock) private returns (bytes32 id) {
+        nonce += 1;
+        id = messageId(block.chainid, destinationChainId, nonce, msg.sender, recipient, amount, unlock);
+        emit MessageSent(id, destinationChainId, nonce, msg.sender, recipient, amount, unlock);
+    }
+}
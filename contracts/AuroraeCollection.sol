@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// Collection ERC-721 minimale d'AURORAE: seul le propriétaire mint, chaque token
+/// pointe vers ses métadonnées publiées (IPFS) et `contractURI` décrit la collection.
+/// Les royalties sont exposées selon EIP-2981. Le propriétaire peut aussi signer hors
+/// chaîne des bons de mint différé (EIP-712), rachetés par l'acheteur qui paie le mint.
+contract AuroraeCollection {
+    struct LazyMint {
+        address recipient;
+        string uri;
+        uint256 minPrice;
+        uint256 nonce;
+        uint256 expiry;
+    }
+
+    bytes32 private constant LAZY_MINT_TYPEHASH =
+        keccak256("LazyMint(address recipient,string uri,uint256 minPrice,uint256 nonce,uint256 expiry)");
+    bytes32 public immutable DOMAIN_SEPARATOR;
+
+    string public name;
+    string public symbol;
+    string public contractURI;
+    address public owner;
+    uint256 public totalSupply;
+
+    address private _royaltyReceiver;
+    uint96 private _royaltyBps;
+
+    mapping(uint256 => address) private _owners;
+    mapping(address => uint256) private _balances;
+    mapping(uint256 => address) private _tokenApprovals;
+    mapping(address => mapping(address => bool)) private _operatorApprovals;
+    mapping(uint256 => string) private _tokenURIs;
+    mapping(uint256 => bool) public voucherUsed;
+
+    event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+    event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId);
+    event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+    event VoucherRedeemed(uint256 indexed nonce, uint256 indexed tokenId, address redeemer);
+
+    constructor(
+        string memory name_,
+        string memory symbol_,
+        string memory contractURI_,
+        address royaltyReceiver_,
+        uint96 royaltyBps_
+    ) {
+        name = name_;
+        symbol = symbol_;
+        contractURI = contractURI_;
+        owner = msg.sender;
+        _setDefaultRoyalty(royaltyReceiver_, royaltyBps_);
+        DOMAIN_SEPARATOR = keccak256(abi.encode(
+            keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"),
+            keccak256(bytes(name_)),
+            keccak256(bytes("1")),
+            block.chainid,
+            address(this)
+        ));
+    }
+
+    modifier onlyOwner() {
+        require(msg.sender == owner, "Collection: reserve au proprietaire");
+        _;
+    }
+
+    function supportsInterface(bytes4 interfaceId) public pure virtual returns (bool) {
+        return interfaceId == 0x01ffc9a7 // ERC-165
+            || interfaceId == 0x80ac58cd // ERC-721
+            || interfaceId == 0x5b5e139f // ERC-721 Metadata
+            || interfaceId == 0x2a55205a; // EIP-2981
+    }
+
+    function royaltyInfo(uint256, uint256 salePrice) external view returns (address receiver, uint256 royaltyAmount) {
+        return (_royaltyReceiver, (salePrice * _royaltyBps) / 10000);
+    }
+
+    function setDefaultRoyalty(address receiver, uint96 bps) external onlyOwner {
+        _setDefaultRoyalty(receiver, bps);
+    }
+
+    function _setDefaultRoyalty(address receiver, uint96 bps) private {
+        require(bps <= 10000, "Collection: royalties > 100%");
+        require(receiver != address(0), "Collection: destinataire nul");
+        _royaltyReceiver = receiver;
+        _royaltyBps = bps;
+    }
+
+    function mint(address to, string calldata uri) external onlyOwner returns (uint256 tokenId) {
+        tokenId = _mint(to, uri);
+    }
+
+    /// Mint groupé: identifiants consécutifs à partir de `firstId`
+    function mintBatch(address to, string[] calldata uris) external onlyOwner returns (uint256 firstId) {
+        require(uris.length > 0, "Collection: lot vide");
+        firstId = totalSupply + 1;
+        for (uint256 i = 0; i < uris.length; i++) {
+            _mint(to, uris[i]);
+        }
+    }
+
+    /// Rachat d'un bon signé par le propriétaire; `recipient` nul: le token revient à l'appelant
+    function redeem(LazyMint calldata voucher, bytes calldata signature) external payable returns (uint256 tokenId) {
+        require(block.timestamp <= voucher.expiry, "Collection: bon expire");
+        require(!voucherUsed[voucher.nonce], "Collection: bon deja utilise");
+        require(msg.value >= voucher.minPrice, "Collection: paiement insuffisant");
+        bytes32 structHash = keccak256(abi.encode(
+            LAZY_MINT_TYPEHASH,
+            voucher.recipient,
+            keccak256(bytes(voucher.uri)),
+            voucher.minPrice,
+            voucher.nonce,
+            voucher.expiry
+        ));
+        bytes32 digest = keccak256(abi.encodePacked("\x19\x01", DOMAIN_SEPARATOR, structHash));
+        require(_recover(digest, signature) == owner, "Collection: signature invalide");
+
+        voucherUsed[voucher.nonce] = true;
+        tokenId = _mint(voucher.recipient == address(0) ? msg.sender : voucher.recipient, voucher.uri);
+        emit VoucherRedeemed(voucher.nonce, tokenId, msg.sender);
+    }
+
+    function withdraw() external onlyOwner {
+        (bool ok, ) = payable(owner).call{value: address(this).balance}("");
+        require(ok, "Collection: retrait echoue");
+    }
+
+    function _mint(address to, string calldata uri) private returns (uint256 tokenId) {
+        require(to != address(0), "Collection: destinataire nul");
+        tokenId = ++totalSupply;
+        _owners[tokenId] = to;
+        _balances[to] += 1;
+        _tokenURIs[tokenId] = uri;
+        emit Transfer(address(0), to, tokenId);
+    }
+
+    function _recover(bytes32 digest, bytes calldata signature) private pure returns (address) {
+        require(signature.length == 65, "Collection: signature invalide");
+        bytes32 r = bytes32(signature[0:32]);
+        bytes32 s = bytes32(signature[32:64]);
+        uint8 v = uint8(signature[64]);
+        if (v < 27) v += 27;
+        // Signatures malléables (s dans la moitié haute) refusées, comme EIP-2
+        require(uint256(s) <= 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF5D576E7357A4501DDFE92F46681B20A0, "Collection: signature invalide");
+        address signer = ecrecover(digest, v, r, s);
+        require(signer != address(0), "Collection: signature invalide");
+        return signer;
+    }
+
+    function setContractURI(string calldata uri) external onlyOwner {
+        contractURI = uri;
+    }
+
+    function tokenURI(uint256 tokenId) external view returns (string memory) {
+        require(_owners[tokenId] != address(0), "Collection: token inexistant");
+        return _tokenURIs[tokenId];
+    }
+
+    function balanceOf(address account) external view returns (uint256) {
+        require(account != address(0), "Collection: adresse nulle");
+        return _balances[account];
+    }
+
+    function ownerOf(uint256 tokenId) public view returns (address) {
+        address tokenOwner = _owners[tokenId];
+        require(tokenOwner != address(0), "Collection: token inexistant");
+        return tokenOwner;
+    }
+
+    function approve(address to, uint256 tokenId) external {
+        address tokenOwner = ownerOf(tokenId);
+        require(msg.sender == tokenOwner || _operatorApprovals[tokenOwner][msg.sender], "Collection: non autorise");
+        _tokenApprovals[tokenId] = to;
+        emit Approval(tokenOwner, to, tokenId);
+    }
+
+    function getApproved(uint256 tokenId) external view returns (address) {
+        ownerOf(tokenId);
+        return _tokenApprovals[tokenId];
+    }
+
+    function setApprovalForAll(address operator, bool approved) external {
+        _operatorApprovals[msg.sender][operator] = approved;
+        emit ApprovalForAll(msg.sender, operator, approved);
+    }
+
+    function isApprovedForAll(address account, address operator) external view returns (bool) {
+        return _operatorApprovals[account][operator];
+    }
+
+    function transferFrom(address from, address to, uint256 tokenId) public {
+        address tokenOwner = ownerOf(tokenId);
+        require(tokenOwner == from, "Collection: proprietaire incorrect");
+        require(to != address(0), "Collection: destinataire nul");
+        require(
+            msg.sender == tokenOwner || _tokenApprovals[tokenId] == msg.sender || _operatorApprovals[tokenOwner][msg.sender],
+            "Collection: non autorise"
+        );
+        delete _tokenApprovals[tokenId];
+        _balances[from] -= 1;
+        _balances[to] += 1;
+        _owners[tokenId] = to;
+        emit Transfer(from, to, tokenId);
+    }
+
+    function safeTransferFrom(address from, address to, uint256 tokenId) external {
+        safeTransferFrom(from, to, tokenId, "");
+    }
+
+    function safeTransferFrom(address from, address to, uint256 tokenId, bytes memory data) public {
+        transferFrom(from, to, tokenId);
+        if (to.code.length > 0) {
+            (bool ok, bytes memory ret) = to.call(
+                abi.encodeWithSelector(0x150b7a02, msg.sender, from, tokenId, data)
+            );
+            require(ok && ret.length >= 32 && abi.decode(ret, (bytes4)) == 0x150b7a02, "Collection: receveur invalide");
+        }
+    }
+}
@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// Auroraium derrière un `UUPSProxy`: le constructeur est remplacé par `initialize`,
+/// appelé par le proxy à son déploiement, et `upgradeTo` n'est accessible qu'au
+/// `ProxyAdmin` désigné à l'initialisation.
+contract AuroraiumUpgradeable {
+    // bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)
+    bytes32 private constant IMPLEMENTATION_SLOT = 0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc;
+    address private immutable self = address(this);
+
+    string public name;
+    string public symbol;
+    uint8 public decimals;
+    uint256 public totalSupply;
+
+    address public founder;
+    address public upgrader;
+    bool private initialized;
+    mapping(address => uint256) public balanceOf;
+    mapping(address => mapping(address => uint256)) public allowance;
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Upgraded(address indexed implementation);
+
+    constructor() {
+        // L'implémentation seule ne doit jamais être initialisée
+        initialized = true;
+    }
+
+    function initialize(uint256 initialSupply, address founderAddress, address upgrader_) external {
+        require(!initialized, "Auroraium: deja initialise");
+        require(upgrader_ != address(0), "Auroraium: administrateur nul");
+        initialized = true;
+        name = "Auroraium";
+        symbol = "AURA";
+        decimals = 18;
+        founder = founderAddress;
+        upgrader = upgrader_;
+
+        uint256 founderShare = (initialSupply * 15) / 100;
+        uint256 remaining = initialSupply - founderShare;
+        balanceOf[founder] = founderShare;
+        balanceOf[msg.sender] = remaining;
+        totalSupply = initialSupply;
+
+        emit Transfer(address(0), founder, founderShare);
+        emit Transfer(address(0), msg.sender, remaining);
+    }
+
+    function transfer(address to, uint256 value) public returns (bool) {
+        require(balanceOf[msg.sender] >= value, "Insufficient balance.");
+        balanceOf[msg.sender] -= value;
+        balanceOf[to] += value;
+        emit Transfer(msg.sender, to, value);
+        return true;
+    }
+
+    /// EIP-1822: emplacement de stockage de l'implémentation
+    function proxiableUUID() external view returns (bytes32) {
+        require(address(this) == self, "Auroraium: appel via proxy interdit");
+        return IMPLEMENTATION_SLOT;
+    }
+
+    function upgradeTo(address newImplementation) external {
+        require(address(this) != self, "Auroraium: appel direct interdit");
+        require(msg.sender == upgrader, "Auroraium: appelant non autorise");
+        require(newImplementation.code.length > 0, "Auroraium: implementation sans code");
+        bytes32 slot = IMPLEMENTATION_SLOT;
+        assembly { sstore(slot, newImplementation) }
+        emit Upgraded(newImplementation);
+    }
+}
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+interface IUpgradeable {
+    function upgradeTo(address newImplementation) external;
+}
+
+/// Administrateur des proxies: seul compte autorisé à appeler `upgradeTo`, distinct du
+/// compte opérateur. Chaque mise à jour est programmée puis exécutable après `delay`
+/// secondes: le timelock est appliqué on-chain.
+contract ProxyAdmin {
+    struct PendingUpgrade {
+        address implementation;
+        uint256 eta;
+    }
+
+    address public owner;
+    uint256 public immutable delay;
+    mapping(address => PendingUpgrade) public pending;
+
+    event UpgradeScheduled(address indexed proxy, address implementation, uint256 eta);
+    event UpgradeCancelled(address indexed proxy, address implementation);
+    event UpgradeExecuted(address indexed proxy, address implementation);
+    event OwnershipTransferred(address previousOwner, address newOwner);
+
+    constructor(address owner_, uint256 delay_) {
+        require(owner_ != address(0), "ProxyAdmin: proprietaire nul");
+        owner = owner_;
+        delay = delay_;
+    }
+
+    modifier onlyOwner() {
+        require(msg.sender == owner, "ProxyAdmin: appelant non autorise");
+        _;
+    }
+
+    function schedule(address proxy, address implementation) external onlyOwner {
+        require(implementation.code.length > 0, "ProxyAdmin: implementation sans code");
+        uint256 eta = block.timestamp + delay;
+        pending[proxy] = PendingUpgrade(implementation, eta);
+        emit UpgradeScheduled(proxy, implementation, eta);
+    }
+
+    function execute(address proxy, address implementation) external onlyOwner {
+        PendingUpgrade memory upgrade = pending[proxy];
+        require(upgrade.implementation != address(0) && upgrade.implementation == implementation, "ProxyAdmin: mise a jour non programmee");
+        require(block.timestamp >= upgrade.eta, "ProxyAdmin: timelock actif");
+        delete pending[proxy];
+        // Proxy transparent: fonction d'administration du proxy; UUPS: déléguée à l'implémentation
+        IUpgradeable(proxy).upgradeTo(implementation);
+        emit UpgradeExecuted(proxy, implementation);
+    }
+
+    function cancel(address proxy) external onlyOwner {
+        address implementation = pending[proxy].implementation;
+        require(implementation != address(0), "ProxyAdmin: aucune mise a jour programmee");
+        delete pending[proxy];
+        emit UpgradeCancelled(proxy, implementation);
+    }
+
+    function transferOwnership(address newOwner) external onlyOwner {
+        require(newOwner != address(0), "ProxyAdmin: proprietaire nul");
+        emit OwnershipTransferred(owner, newOwner);
+        owner = newOwner;
+    }
+}
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// Proxy UUPS (EIP-1967 / EIP-1822): délègue tous les appels; la fonction
+/// `upgradeTo(address)` et son contrôle d'accès sont portés par l'implémentation.
+contract UUPSProxy {
+    // bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)
+    bytes32 private constant IMPLEMENTATION_SLOT = 0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc;
+
+    event Upgraded(address indexed implementation);
+
+    constructor(address implementation_, bytes memory data) {
+        require(implementation_.code.length > 0, "Proxy: implementation sans code");
+        bytes32 slot = IMPLEMENTATION_SLOT;
+        assembly { sstore(slot, implementation_) }
+        emit Upgraded(implementation_);
+        if (data.length > 0) {
+            (bool ok, ) = implementation_.delegatecall(data);
+            require(ok, "Proxy: initialisation echouee");
+        }
+    }
+
+    fallback() external payable {
+        _delegate();
+    }
+
+    receive() external payable {
+        _delegate();
+    }
+
+    function _delegate() private {
+        bytes32 slot = IMPLEMENTATION_SLOT;
+        assembly {
+            let impl := sload(slot)
+            calldatacopy(0, 0, calldatasize())
+            let result := delegatecall(gas(), impl, 0, calldatasize(), 0, 0)
+            returndatacopy(0, 0, returndatasize())
+            switch result
+            case 0 { revert(0, returndatasize()) }
+            default { return(0, returndatasize()) }
+        }
+    }
+}
@@ -13,6 +13,16 @@ if %errorlevel% neq 0 (
 
 echo.
 echo ✅ Compilation réussie.
+echo [AURORAE++] Signature du manifeste d'intégrité...
+target\release\aurorae.exe --sign-integrity-manifest
+
+if %errorlevel% neq 0 (
+    echo.
+    echo ❌ Signature du manifeste impossible.
+    pause
+    exit /b
+)
+
 echo [AURORAE++] Démarrage du système...
 echo.
 
@@ -2,6 +2,8 @@ use aurorae::{
     AuroraeCore, Strategist, VisionEngine, BrainCore, ReproductionEngine, Deployer, BlockchainInterface,
     Thought, Intent, check_integrity, trigger_generation, mutate_module_code, DreamEngine, GuardianSentinel,
 };
+use aurorae::blockchain_core::{FeeStrategy, GasOracle};
+use aurorae::keystore::{SharedSigner, WalletSigner};
 use async_openai::types::{ChatCompletionRequestMessageArgs, CreateChatCompletionRequestArgs, Role};
 use async_openai::Client;
 use std::sync::Arc;
@@ -22,10 +24,16 @@ async fn test_system_integration() {
     assert!(collection_id > 0, "Échec de la création de la collection NFT");
 
     // 3. Vérifier le déploiement d'un contrat ERC20
-    let provider = BlockchainInterface::get_http_provider("https://eth-sepolia.g.alchemy.com/v2/YOUR_KEY").unwrap();
+    let signer: SharedSigner = Arc::new(WalletSigner::random("integration-test"));
+    let backend = BlockchainInterface::connect_backend(
+        "https://eth-sepolia.g.alchemy.com/v2/YOUR_KEY",
+        &signer,
+    ).await.expect("Backend blockchain indisponible");
+    let gas_oracle = GasOracle::new(FeeStrategy::Fast);
     let address = Deployer::deploy_contract(
-        provider,
-        "INSERT_YOUR_PRIVATE_KEY_HERE",
+        &core.economy.budget_handle(),
+        backend.as_ref(),
+        &gas_oracle,
         "auroraium_erc20.json",
         "auroraium_bytecode.json"
     ).await;